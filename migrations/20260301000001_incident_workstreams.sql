@@ -0,0 +1,19 @@
+-- Track the pinned incident summary so it can be refreshed in place (chat.update)
+ALTER TABLE incidents ADD COLUMN pinned_message_ts TEXT;
+
+-- Parallel workstreams within an incident, each with its own Slack thread and lead
+CREATE TABLE incident_workstreams (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    incident_id UUID NOT NULL REFERENCES incidents(id) ON DELETE CASCADE,
+    name TEXT NOT NULL CHECK (length(name) BETWEEN 1 AND 30),
+    lead_id TEXT NOT NULL,
+    thread_ts TEXT,
+    last_update TEXT,
+    last_update_at TIMESTAMPTZ,
+    created_by TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (incident_id, name)
+);
+
+CREATE INDEX idx_workstreams_incident ON incident_workstreams(incident_id);
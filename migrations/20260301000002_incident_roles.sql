@@ -0,0 +1,9 @@
+-- Roles claimed during an incident (comms lead, scribe, ...). The commander is
+-- tracked on incidents.commander_id and is never stored here.
+CREATE TABLE incident_roles (
+    incident_id UUID NOT NULL REFERENCES incidents(id) ON DELETE CASCADE,
+    role TEXT NOT NULL,
+    user_id TEXT NOT NULL,
+    claimed_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (incident_id, role)
+);
@@ -0,0 +1,56 @@
+-- Append-only change log: one row per mutation of an incident or its child
+-- rows, written by triggers so every code path (Slack, API, jobs) is covered.
+-- Standby regions and external audit stores tail it by `seq`, either via
+-- GET /api/v1/replication/changes or logical decoding, e.g.
+--   CREATE PUBLICATION incident_changes_pub FOR TABLE incident_changes;
+-- No foreign key: records must outlive deleted incidents.
+CREATE TABLE incident_changes (
+    seq BIGSERIAL PRIMARY KEY,
+    incident_id UUID NOT NULL,
+    table_name TEXT NOT NULL,
+    operation TEXT NOT NULL CHECK (operation IN ('INSERT', 'UPDATE', 'DELETE')),
+    row_data JSONB NOT NULL,
+    changed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_incident_changes_incident ON incident_changes(incident_id, seq);
+
+CREATE FUNCTION record_incident_change() RETURNS trigger AS $$
+DECLARE
+    data JSONB;
+BEGIN
+    IF TG_OP = 'DELETE' THEN
+        data := to_jsonb(OLD);
+    ELSE
+        data := to_jsonb(NEW);
+    END IF;
+
+    INSERT INTO incident_changes (incident_id, table_name, operation, row_data)
+    VALUES (
+        CASE WHEN TG_TABLE_NAME = 'incidents'
+            THEN (data->>'id')::uuid
+            ELSE (data->>'incident_id')::uuid
+        END,
+        TG_TABLE_NAME,
+        TG_OP,
+        data
+    );
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER incidents_record_change
+    AFTER INSERT OR UPDATE OR DELETE ON incidents
+    FOR EACH ROW EXECUTE FUNCTION record_incident_change();
+
+CREATE TRIGGER incident_timeline_record_change
+    AFTER INSERT OR UPDATE OR DELETE ON incident_timeline
+    FOR EACH ROW EXECUTE FUNCTION record_incident_change();
+
+CREATE TRIGGER incident_roles_record_change
+    AFTER INSERT OR UPDATE OR DELETE ON incident_roles
+    FOR EACH ROW EXECUTE FUNCTION record_incident_change();
+
+CREATE TRIGGER incident_workstreams_record_change
+    AFTER INSERT OR UPDATE OR DELETE ON incident_workstreams
+    FOR EACH ROW EXECUTE FUNCTION record_incident_change();
@@ -0,0 +1,7 @@
+-- When the commander was last nudged about an incident with no timeline
+-- activity. Kept out of `incidents` so reminders don't show up as incident
+-- changes in the replication feed.
+CREATE TABLE stale_reminders (
+    incident_id UUID PRIMARY KEY REFERENCES incidents(id) ON DELETE CASCADE,
+    reminded_at TIMESTAMPTZ NOT NULL
+);
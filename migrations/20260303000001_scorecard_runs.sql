@@ -0,0 +1,8 @@
+-- Monthly team scorecards already sent, so a restart or a second replica
+-- doesn't DM team leads twice for the same month.
+CREATE TABLE scorecard_runs (
+    period_start DATE NOT NULL,
+    team TEXT NOT NULL,
+    sent_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (period_start, team)
+);
@@ -0,0 +1,5 @@
+-- Free-form notes (e.g. from integrations via the batch timeline API) so the
+-- timeline can be filtered to them separately from status updates.
+ALTER TABLE incident_timeline DROP CONSTRAINT incident_timeline_event_type_check;
+ALTER TABLE incident_timeline ADD CONSTRAINT incident_timeline_event_type_check
+    CHECK (event_type IN ('declared', 'status_update', 'severity_change', 'resolved', 'note'));
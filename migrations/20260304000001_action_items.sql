@@ -0,0 +1,19 @@
+-- Follow-up work captured during or after an incident. `number` is the
+-- per-incident sequence users refer to (`/incident action done 2`).
+CREATE TABLE action_items (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    incident_id UUID NOT NULL REFERENCES incidents(id) ON DELETE CASCADE,
+    number INT NOT NULL,
+    description TEXT NOT NULL,
+    owner_id TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'open' CHECK (status IN ('open', 'done')),
+    jira_issue_key TEXT,
+    created_by TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    closed_at TIMESTAMPTZ,
+    UNIQUE (incident_id, number)
+);
+
+CREATE TRIGGER action_items_record_change
+    AFTER INSERT OR UPDATE OR DELETE ON action_items
+    FOR EACH ROW EXECUTE FUNCTION record_incident_change();
@@ -0,0 +1,13 @@
+-- Who a status update was written for. Only 'public' updates are sent to the
+-- public status page; the classification is kept for compliance review.
+ALTER TABLE incident_timeline
+    ADD COLUMN audience TEXT NOT NULL DEFAULT 'internal'
+        CHECK (audience IN ('internal', 'public'));
+
+-- Statuspage incident that public updates for an incident are posted to,
+-- created on the first public update.
+CREATE TABLE statuspage_incidents (
+    incident_id UUID PRIMARY KEY REFERENCES incidents(id) ON DELETE CASCADE,
+    statuspage_incident_id TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,13 @@
+-- Postmortems published to Confluence. One page per incident; republishing
+-- returns the stored URL instead of creating a duplicate page.
+CREATE TABLE postmortems (
+    incident_id UUID PRIMARY KEY REFERENCES incidents(id) ON DELETE CASCADE,
+    confluence_page_id TEXT NOT NULL,
+    url TEXT NOT NULL,
+    published_by TEXT NOT NULL,
+    published_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TRIGGER postmortems_record_change
+    AFTER INSERT OR UPDATE OR DELETE ON postmortems
+    FOR EACH ROW EXECUTE FUNCTION record_incident_change();
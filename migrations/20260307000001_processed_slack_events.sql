@@ -0,0 +1,10 @@
+-- Events API deliveries already handled. Slack retries an event (same
+-- event_id) up to 3 times when the ack is slow; rows older than the replay
+-- window are purged as new events arrive.
+CREATE TABLE processed_slack_events (
+    event_id TEXT PRIMARY KEY,
+    event_type TEXT NOT NULL,
+    received_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_processed_slack_events_received_at ON processed_slack_events (received_at);
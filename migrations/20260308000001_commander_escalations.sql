@@ -0,0 +1,18 @@
+-- When the current commander was last seen: a message in the incident
+-- channel or an acknowledged nag. Kept out of `incidents` so activity
+-- doesn't show up as incident changes in the replication feed.
+CREATE TABLE commander_activity (
+    incident_id UUID PRIMARY KEY REFERENCES incidents(id) ON DELETE CASCADE,
+    commander_id TEXT NOT NULL,
+    seen_at TIMESTAMPTZ NOT NULL
+);
+
+-- Backups offered command because a P1 commander went quiet. At most one
+-- escalation per incident and commander; `backup_ids` may take command.
+CREATE TABLE commander_escalations (
+    incident_id UUID NOT NULL REFERENCES incidents(id) ON DELETE CASCADE,
+    commander_id TEXT NOT NULL,
+    backup_ids TEXT[] NOT NULL,
+    escalated_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (incident_id, commander_id)
+);
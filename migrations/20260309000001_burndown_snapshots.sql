@@ -0,0 +1,13 @@
+-- Daily open-incidents sparkline, uploaded to Slack for App Home and the
+-- weekly digest. `slack_file_id` is NULL while the upload is in flight.
+CREATE TABLE burndown_snapshots (
+    rendered_on DATE PRIMARY KEY,
+    slack_file_id TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Weekly digests already posted, keyed by the Monday starting the week.
+CREATE TABLE digest_runs (
+    week_start DATE PRIMARY KEY,
+    sent_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,8 @@
+-- Trigram indexes so `/incident search text:...` (ILIKE '%...%') doesn't
+-- scan every incident title and timeline message.
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+CREATE INDEX idx_incidents_title_trgm
+    ON incidents USING GIN (title gin_trgm_ops);
+CREATE INDEX idx_incident_timeline_message_trgm
+    ON incident_timeline USING GIN (message gin_trgm_ops);
@@ -0,0 +1,21 @@
+-- Responders per incident: people who joined the incident channel or posted
+-- to its timeline. Feeds the postmortem responder list and load metrics.
+CREATE TABLE incident_participants (
+    incident_id UUID NOT NULL REFERENCES incidents(id) ON DELETE CASCADE,
+    user_id TEXT NOT NULL,
+    joined_at TIMESTAMPTZ,
+    timeline_events INTEGER NOT NULL DEFAULT 0,
+    first_seen_at TIMESTAMPTZ NOT NULL,
+    last_seen_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (incident_id, user_id)
+);
+
+CREATE INDEX idx_incident_participants_user ON incident_participants(user_id);
+
+-- Backfill from existing timelines; integrations post under names like
+-- "alertmanager", so only Slack user IDs count.
+INSERT INTO incident_participants (incident_id, user_id, timeline_events, first_seen_at, last_seen_at)
+SELECT incident_id, posted_by, COUNT(*), MIN(timestamp), MAX(timestamp)
+FROM incident_timeline
+WHERE posted_by ~ '^[UW][A-Z0-9]+$'
+GROUP BY incident_id, posted_by;
@@ -0,0 +1,7 @@
+-- In-progress /incident declare modal values, one per user, so a dismissed
+-- modal or dropped submission can be resumed on the next declare.
+CREATE TABLE declare_drafts (
+    user_id TEXT PRIMARY KEY,
+    draft JSONB NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL
+);
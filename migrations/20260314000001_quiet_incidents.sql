@@ -0,0 +1,3 @@
+-- Quiet (security) incidents: private channel, no broadcast notifications,
+-- no Statuspage or webhook traffic
+ALTER TABLE incidents ADD COLUMN is_quiet BOOLEAN NOT NULL DEFAULT FALSE;
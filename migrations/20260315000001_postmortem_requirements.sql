@@ -0,0 +1,11 @@
+-- Postmortems owed for resolved incidents whose severity requires one
+-- (POSTMORTEM_DUE_DAYS). Outstanding until a `postmortems` row exists;
+-- `reminded_at` is when the commander was last nagged about it.
+CREATE TABLE postmortem_requirements (
+    incident_id UUID PRIMARY KEY REFERENCES incidents(id) ON DELETE CASCADE,
+    due_at TIMESTAMPTZ NOT NULL,
+    required_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    reminded_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_postmortem_requirements_due_at ON postmortem_requirements(due_at);
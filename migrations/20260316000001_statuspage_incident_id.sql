@@ -0,0 +1,11 @@
+-- Statuspage incident opened for an incident, on declaration (when its
+-- service has a component mapping) or on the first public update. Replaces
+-- the `statuspage_incidents` side table.
+ALTER TABLE incidents ADD COLUMN statuspage_incident_id TEXT;
+
+UPDATE incidents i
+SET statuspage_incident_id = s.statuspage_incident_id
+FROM statuspage_incidents s
+WHERE s.incident_id = i.id;
+
+DROP TABLE statuspage_incidents;
@@ -0,0 +1,6 @@
+-- `/incident attach` binds incidents to existing channels, so a war room can
+-- host several incidents over time. Only one may be open there at once.
+ALTER TABLE incidents DROP CONSTRAINT incidents_slack_channel_id_key;
+
+CREATE UNIQUE INDEX idx_incidents_open_channel ON incidents(slack_channel_id)
+    WHERE status <> 'resolved';
@@ -0,0 +1,5 @@
+-- Resolved incidents can be reopened (/incident reopen); the timeline records
+-- each reopen so the incident's history shows both resolutions.
+ALTER TABLE incident_timeline DROP CONSTRAINT incident_timeline_event_type_check;
+ALTER TABLE incident_timeline ADD CONSTRAINT incident_timeline_event_type_check
+    CHECK (event_type IN ('declared', 'status_update', 'severity_change', 'resolved', 'note', 'reopened'));
@@ -0,0 +1,3 @@
+-- When the channel archive job archived (or claimed) an incident's channel,
+-- so each channel is summarized and archived once.
+ALTER TABLE incidents ADD COLUMN channel_archived_at TIMESTAMPTZ;
@@ -0,0 +1,14 @@
+-- Large generated files (DR snapshots, channel transcripts, ...) live in the
+-- artifact store (local disk, S3 or GCS); this table only indexes them.
+CREATE TABLE artifacts (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    incident_id UUID REFERENCES incidents(id) ON DELETE CASCADE,
+    kind TEXT NOT NULL,
+    storage_key TEXT NOT NULL UNIQUE,
+    content_type TEXT NOT NULL,
+    size_bytes BIGINT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_artifacts_incident ON artifacts(incident_id, created_at DESC);
+CREATE INDEX idx_artifacts_kind ON artifacts(kind, created_at DESC);
@@ -0,0 +1,19 @@
+-- Monthly paging tests: a marked test DM to everyone on the P1 escalation
+-- chain, and when (or whether) each recipient acknowledged it.
+CREATE TABLE paging_tests (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    month DATE NOT NULL UNIQUE,
+    started_at TIMESTAMPTZ NOT NULL,
+    reported_at TIMESTAMPTZ
+);
+
+CREATE TABLE paging_test_pages (
+    test_id UUID NOT NULL REFERENCES paging_tests(id) ON DELETE CASCADE,
+    -- Slack user ID, or the user group ID when the group couldn't be expanded
+    recipient_id TEXT NOT NULL,
+    source TEXT NOT NULL,
+    sent_at TIMESTAMPTZ NOT NULL,
+    delivery_error TEXT,
+    acknowledged_at TIMESTAMPTZ,
+    PRIMARY KEY (test_id, recipient_id)
+);
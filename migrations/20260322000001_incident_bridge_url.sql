@@ -0,0 +1,3 @@
+-- Join link of the conference bridge created when a P1/P2 is declared, so
+-- /incident bridge can re-surface it.
+ALTER TABLE incidents ADD COLUMN bridge_url TEXT;
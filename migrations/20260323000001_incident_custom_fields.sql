@@ -0,0 +1,3 @@
+-- Values entered for a template's {{placeholders}} when the incident was
+-- declared (e.g. {"region": "eu-west-1", "customer": "Acme"}).
+ALTER TABLE incidents ADD COLUMN custom_fields JSONB NOT NULL DEFAULT '{}'::jsonb;
@@ -0,0 +1,7 @@
+-- Timeline events mirrored to a service's partner channel, so each update is
+-- posted there once.
+CREATE TABLE partner_mirror_posts (
+    timeline_event_id UUID PRIMARY KEY REFERENCES incident_timeline(id) ON DELETE CASCADE,
+    channel_id TEXT NOT NULL,
+    posted_at TIMESTAMPTZ NOT NULL
+);
@@ -0,0 +1,13 @@
+-- Background jobs that failed because an external service was unavailable,
+-- kept so they can be retried in order once it recovers.
+CREATE TABLE failed_jobs (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    incident_id UUID REFERENCES incidents(id) ON DELETE CASCADE,
+    job JSONB NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 1,
+    last_error TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_attempted_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_failed_jobs_created_at ON failed_jobs(created_at);
@@ -0,0 +1,13 @@
+-- Alertmanager alerts that declared or joined an incident, keyed by
+-- fingerprint so repeat notifications are recognised as the same alert.
+CREATE TABLE alertmanager_alerts (
+    fingerprint TEXT PRIMARY KEY,
+    incident_id UUID NOT NULL REFERENCES incidents(id) ON DELETE CASCADE,
+    -- Whether this alert declared the incident (rather than joining one)
+    declared BOOLEAN NOT NULL DEFAULT FALSE,
+    firing BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_alertmanager_alerts_incident ON alertmanager_alerts(incident_id);
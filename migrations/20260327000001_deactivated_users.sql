@@ -0,0 +1,6 @@
+-- Slack users found deactivated while still named in config or commanding
+-- an incident, so admins are alerted once per user rather than every check.
+CREATE TABLE deactivated_users (
+    user_id TEXT PRIMARY KEY,
+    detected_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
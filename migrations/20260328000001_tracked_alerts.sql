@@ -0,0 +1,9 @@
+-- Alerts from any auto-declaring source (Alertmanager, Datadog), keyed by
+-- source and fingerprint since IDs are only unique within one tool.
+ALTER TABLE alertmanager_alerts RENAME TO tracked_alerts;
+ALTER INDEX idx_alertmanager_alerts_incident RENAME TO idx_tracked_alerts_incident;
+
+ALTER TABLE tracked_alerts ADD COLUMN source TEXT NOT NULL DEFAULT 'alertmanager';
+ALTER TABLE tracked_alerts ALTER COLUMN source DROP DEFAULT;
+ALTER TABLE tracked_alerts DROP CONSTRAINT alertmanager_alerts_pkey;
+ALTER TABLE tracked_alerts ADD PRIMARY KEY (source, fingerprint);
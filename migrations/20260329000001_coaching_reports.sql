@@ -0,0 +1,14 @@
+-- Commanders who asked for a quarterly coaching report by DM.
+CREATE TABLE coaching_opt_ins (
+    user_id TEXT PRIMARY KEY,
+    opted_in_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Coaching reports already sent, so a restart or a second replica doesn't
+-- DM a commander twice for the same quarter.
+CREATE TABLE coaching_report_runs (
+    period_start DATE NOT NULL,
+    user_id TEXT NOT NULL,
+    sent_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (period_start, user_id)
+);
@@ -0,0 +1,15 @@
+-- SLA targets (SLA config) missed by incidents, recorded by the sla_checks
+-- job. One row per missed deadline: an incident can breach its update
+-- cadence more than once, and a severity change moves its deadlines.
+CREATE TABLE sla_breaches (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    incident_id UUID NOT NULL REFERENCES incidents(id) ON DELETE CASCADE,
+    severity TEXT NOT NULL,
+    metric TEXT NOT NULL CHECK (metric IN ('ack', 'update', 'resolve')),
+    target_minutes INTEGER NOT NULL,
+    deadline TIMESTAMPTZ NOT NULL,
+    recorded_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (incident_id, metric, deadline)
+);
+
+CREATE INDEX idx_sla_breaches_recorded_at ON sla_breaches (recorded_at);
@@ -0,0 +1,7 @@
+-- Status updates and notes can be corrected or retracted by their author or
+-- the commander. Deleted events stay in place (keeping the numbers shown by
+-- `/incident timeline` stable) with their text hidden; the audit log keeps
+-- the old and new text.
+ALTER TABLE incident_timeline
+    ADD COLUMN updated_at TIMESTAMPTZ,
+    ADD COLUMN deleted_at TIMESTAMPTZ;
@@ -0,0 +1,14 @@
+-- Notifications that didn't go out keep their message so operators can
+-- resend or skip them (`/api/v1/admin/notifications`, `/incident
+-- notifications`). `resolved_by` records who retried or skipped one.
+ALTER TABLE incident_notifications
+    ADD COLUMN blocks JSONB,
+    ADD COLUMN attempts INTEGER NOT NULL DEFAULT 1,
+    ADD COLUMN resolved_by TEXT;
+
+ALTER TABLE incident_notifications DROP CONSTRAINT incident_notifications_status_check;
+ALTER TABLE incident_notifications ADD CONSTRAINT incident_notifications_status_check
+    CHECK (status IN ('sent', 'failed', 'pending', 'throttled', 'skipped'));
+
+CREATE INDEX idx_notifications_unsent ON incident_notifications(sent_at)
+    WHERE status IN ('failed', 'pending', 'throttled');
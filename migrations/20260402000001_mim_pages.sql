@@ -0,0 +1,11 @@
+-- Major incident manager pages: who was paged for each P1 and whether they
+-- accepted, and as what (MIM_ROLE at the time).
+CREATE TABLE mim_pages (
+    incident_id UUID PRIMARY KEY REFERENCES incidents(id) ON DELETE CASCADE,
+    user_id TEXT NOT NULL,
+    -- `MIM_USERS` or the on-call schedule the user was pulled from
+    source TEXT NOT NULL,
+    paged_at TIMESTAMPTZ NOT NULL,
+    accepted_at TIMESTAMPTZ,
+    accepted_as TEXT CHECK (accepted_as IN ('advisor', 'commander'))
+);
@@ -0,0 +1,9 @@
+-- Jobs that failed for good (rejected by Statuspage, a webhook that never
+-- answered, any other job that errored) are dead-lettered here instead of
+-- only logged, so admins can retry or discard them (`/incident jobs`).
+-- Rows without `dead_lettered_at` are still Statuspage jobs waiting for it
+-- to recover.
+ALTER TABLE failed_jobs ADD COLUMN dead_lettered_at TIMESTAMPTZ;
+
+CREATE INDEX idx_failed_jobs_dead_lettered ON failed_jobs(dead_lettered_at)
+    WHERE dead_lettered_at IS NOT NULL;
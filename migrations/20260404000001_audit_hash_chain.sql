@@ -0,0 +1,22 @@
+-- Tamper-evident audit trail: every new audit_log row stores the hash of the
+-- row before it plus its own content (see services::audit_chain), so any
+-- edited, deleted or reordered row breaks the chain from that point on.
+-- `seq` orders the chain. Existing rows are numbered by time but keep NULL
+-- hashes: they predate the chain and can't be vouched for.
+ALTER TABLE audit_log ADD COLUMN seq BIGINT;
+ALTER TABLE audit_log ADD COLUMN prev_hash TEXT;
+ALTER TABLE audit_log ADD COLUMN row_hash TEXT;
+
+UPDATE audit_log a SET seq = numbered.n
+FROM (
+    SELECT id, row_number() OVER (ORDER BY timestamp, created_at, id) AS n
+    FROM audit_log
+) numbered
+WHERE a.id = numbered.id;
+
+ALTER TABLE audit_log ALTER COLUMN seq SET NOT NULL;
+CREATE UNIQUE INDEX idx_audit_seq ON audit_log(seq);
+
+-- Audit rows outlive their incident unchanged: ON DELETE SET NULL would
+-- rewrite hashed rows, so the reference is no longer enforced
+ALTER TABLE audit_log DROP CONSTRAINT IF EXISTS audit_log_incident_id_fkey;
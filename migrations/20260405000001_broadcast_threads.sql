@@ -0,0 +1,10 @@
+-- First message announcing an incident in each fan-out channel (not the
+-- incident's own channel). Later notifications there are posted as replies
+-- in its thread.
+CREATE TABLE broadcast_threads (
+    incident_id UUID NOT NULL REFERENCES incidents(id) ON DELETE CASCADE,
+    channel_id TEXT NOT NULL,
+    message_ts TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (incident_id, channel_id)
+);
@@ -0,0 +1,4 @@
+-- Configurable severities (SEVERITIES): the level picked (SEV0, SEV1, ...)
+-- is stored by code next to the routing tier in `severity`. NULL for
+-- incidents declared before, which show as their tier.
+ALTER TABLE incidents ADD COLUMN severity_code TEXT;
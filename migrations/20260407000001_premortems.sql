@@ -0,0 +1,33 @@
+-- Pre-mortems (`/incident premortem <service>`): failure modes anticipated
+-- before a risky launch. Incidents declared for the service during the
+-- launch window are linked to the pre-mortem.
+CREATE TABLE premortems (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    service TEXT NOT NULL,
+    launch_name TEXT NOT NULL,
+    window_start TIMESTAMPTZ NOT NULL,
+    window_end TIMESTAMPTZ NOT NULL CHECK (window_end > window_start),
+    created_by TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_premortems_service_window ON premortems(LOWER(service), window_end);
+
+CREATE TABLE premortem_risks (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    premortem_id UUID NOT NULL REFERENCES premortems(id) ON DELETE CASCADE,
+    failure_mode TEXT NOT NULL,
+    likelihood TEXT NOT NULL CHECK (likelihood IN ('low', 'medium', 'high')),
+    mitigation TEXT,
+    added_by TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_premortem_risks_premortem ON premortem_risks(premortem_id, created_at);
+
+CREATE TABLE premortem_incidents (
+    premortem_id UUID NOT NULL REFERENCES premortems(id) ON DELETE CASCADE,
+    incident_id UUID NOT NULL REFERENCES incidents(id) ON DELETE CASCADE,
+    linked_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (premortem_id, incident_id)
+);
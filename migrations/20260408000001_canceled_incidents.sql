@@ -0,0 +1,12 @@
+-- False alarms are canceled (/incident cancel) instead of resolved: a
+-- terminal status of their own, left out of MTTR (no resolved_at or
+-- duration_minutes) and never requiring a postmortem.
+ALTER TABLE incidents DROP CONSTRAINT incidents_status_check;
+ALTER TABLE incidents ADD CONSTRAINT incidents_status_check
+    CHECK (status IN ('declared', 'investigating', 'identified', 'monitoring', 'resolved', 'canceled'));
+ALTER TABLE incidents ADD COLUMN canceled_at TIMESTAMPTZ;
+ALTER TABLE incidents ADD COLUMN cancel_reason TEXT;
+
+ALTER TABLE incident_timeline DROP CONSTRAINT incident_timeline_event_type_check;
+ALTER TABLE incident_timeline ADD CONSTRAINT incident_timeline_event_type_check
+    CHECK (event_type IN ('declared', 'status_update', 'severity_change', 'resolved', 'note', 'reopened', 'canceled'));
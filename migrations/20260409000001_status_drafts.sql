@@ -0,0 +1,18 @@
+-- Status updates drafted by the scribe, held until the commander approves
+-- (posted) or discards them.
+CREATE TABLE status_drafts (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    incident_id UUID NOT NULL REFERENCES incidents(id) ON DELETE CASCADE,
+    drafted_by TEXT NOT NULL,
+    message TEXT NOT NULL,
+    audience TEXT NOT NULL CHECK (audience IN ('internal', 'public')),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    decided_by TEXT,
+    decided_at TIMESTAMPTZ,
+    approved BOOLEAN
+);
+
+CREATE INDEX idx_status_drafts_incident ON status_drafts(incident_id);
+
+-- Co-author of a status update posted from an approved draft
+ALTER TABLE incident_timeline ADD COLUMN drafted_by TEXT;
@@ -0,0 +1,20 @@
+-- Pull requests, commits and deploys linked to incidents for root-cause
+-- correlation: by hand with /incident link github, or by GitHub deployment
+-- webhooks for the affected service.
+CREATE TABLE incident_links (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    incident_id UUID NOT NULL REFERENCES incidents(id) ON DELETE CASCADE,
+    provider TEXT NOT NULL CHECK (provider IN ('github')),
+    kind TEXT NOT NULL CHECK (kind IN ('pull_request', 'commit', 'deploy')),
+    -- owner/name
+    repo TEXT NOT NULL,
+    -- PR number or commit SHA
+    reference TEXT NOT NULL,
+    url TEXT NOT NULL,
+    -- Deploy target (production, staging, ...); empty for PRs and commits
+    environment TEXT NOT NULL DEFAULT '',
+    -- Slack user ID, or `github` for webhook deploys
+    linked_by TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (incident_id, kind, repo, reference, environment)
+);
@@ -0,0 +1,13 @@
+-- Exec notification tiers delivered per incident (EXEC_TIERS). A tier is
+-- claimed here before anyone is DMed, so each is delivered at most once; the
+-- DMs themselves are logged in incident_notifications.
+CREATE TABLE exec_tier_deliveries (
+    incident_id UUID NOT NULL REFERENCES incidents(id) ON DELETE CASCADE,
+    -- 1 on declaration, 2 after 30 minutes, 3 after 2 hours
+    tier SMALLINT NOT NULL CHECK (tier BETWEEN 1 AND 3),
+    -- Configured recipients at delivery time; groups are expanded when sent
+    users TEXT[] NOT NULL DEFAULT '{}',
+    user_groups TEXT[] NOT NULL DEFAULT '{}',
+    delivered_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (incident_id, tier)
+);
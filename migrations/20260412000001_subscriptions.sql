@@ -0,0 +1,18 @@
+-- Stakeholders who asked for DM digests of an incident's status changes
+-- (/incident subscribe), or of every incident on a service
+-- (/incident subscribe service:<name>).
+CREATE TABLE subscriptions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id TEXT NOT NULL,
+    incident_id UUID REFERENCES incidents(id) ON DELETE CASCADE,
+    -- As listed in SERVICES
+    service TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK ((incident_id IS NULL) <> (service IS NULL))
+);
+
+CREATE UNIQUE INDEX idx_subscriptions_incident ON subscriptions (user_id, incident_id)
+    WHERE incident_id IS NOT NULL;
+CREATE UNIQUE INDEX idx_subscriptions_service ON subscriptions (user_id, service)
+    WHERE service IS NOT NULL;
+CREATE INDEX idx_subscriptions_incident_id ON subscriptions (incident_id);
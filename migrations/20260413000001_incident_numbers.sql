@@ -0,0 +1,41 @@
+-- Human-friendly incident numbers (INC-42, PLAT-12, SEC-7). The prefix comes
+-- from the owning team or the security flag (see INCIDENT_NUMBER_PREFIX) and
+-- each prefix counts on its own: `incident_number_sequences` holds the last
+-- number handed out per prefix, bumped by a trigger in the inserting
+-- transaction so every code path that declares gets a unique number.
+CREATE TABLE incident_number_sequences (
+    prefix TEXT PRIMARY KEY,
+    last_number INT NOT NULL
+);
+
+ALTER TABLE incidents ADD COLUMN number_prefix TEXT NOT NULL DEFAULT 'INC';
+ALTER TABLE incidents ADD COLUMN number INT;
+
+-- Existing incidents become INC-1, INC-2, ... in declaration order
+UPDATE incidents
+SET number = numbered.n
+FROM (
+    SELECT id, ROW_NUMBER() OVER (ORDER BY declared_at, created_at, id) AS n
+    FROM incidents
+) numbered
+WHERE incidents.id = numbered.id;
+
+INSERT INTO incident_number_sequences (prefix, last_number)
+SELECT 'INC', COUNT(*) FROM incidents HAVING COUNT(*) > 0;
+
+ALTER TABLE incidents ALTER COLUMN number SET NOT NULL;
+CREATE UNIQUE INDEX idx_incidents_number ON incidents (number_prefix, number);
+
+CREATE FUNCTION assign_incident_number() RETURNS trigger AS $$
+BEGIN
+    INSERT INTO incident_number_sequences AS s (prefix, last_number)
+    VALUES (NEW.number_prefix, 1)
+    ON CONFLICT (prefix) DO UPDATE SET last_number = s.last_number + 1
+    RETURNING last_number INTO NEW.number;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER incidents_assign_number
+    BEFORE INSERT ON incidents
+    FOR EACH ROW EXECUTE FUNCTION assign_incident_number();
@@ -0,0 +1,8 @@
+-- Services from SERVICES hidden from new declarations and alert mapping by
+-- `/incident service disable`, until re-enabled. Incidents already open on
+-- them are unaffected.
+CREATE TABLE disabled_services (
+    service TEXT PRIMARY KEY,
+    disabled_by TEXT NOT NULL,
+    disabled_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
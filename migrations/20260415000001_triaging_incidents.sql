@@ -0,0 +1,14 @@
+-- Suspected issues opened with `/incident triage` start out 'triaging':
+-- no channel, no notifications, and numbered TRI-n so false alarms leave no
+-- gaps in the real prefixes. Declaring one renumbers it and restarts
+-- declared_at; dismissing one cancels it. `is_triage` stays set on triages
+-- never declared, which are left out of metrics.
+ALTER TABLE incidents DROP CONSTRAINT incidents_status_check;
+ALTER TABLE incidents ADD CONSTRAINT incidents_status_check
+    CHECK (status IN ('triaging', 'declared', 'investigating', 'identified', 'monitoring', 'resolved', 'canceled'));
+ALTER TABLE incidents ADD COLUMN triaged_at TIMESTAMPTZ;
+ALTER TABLE incidents ADD COLUMN is_triage BOOLEAN NOT NULL DEFAULT FALSE;
+
+ALTER TABLE incident_timeline DROP CONSTRAINT incident_timeline_event_type_check;
+ALTER TABLE incident_timeline ADD CONSTRAINT incident_timeline_event_type_check
+    CHECK (event_type IN ('triaged', 'declared', 'status_update', 'severity_change', 'resolved', 'note', 'reopened', 'canceled'));
@@ -0,0 +1,6 @@
+-- Weekly incident reports already queued for the leadership channel, keyed
+-- by the Monday starting the week they were sent (covering the week before).
+CREATE TABLE weekly_report_runs (
+    week_start DATE PRIMARY KEY,
+    queued_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
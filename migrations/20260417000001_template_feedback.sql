@@ -0,0 +1,10 @@
+-- Which template an incident was declared from, for template effectiveness
+-- analytics, and which incident a template was created from after it was
+-- resolved.
+ALTER TABLE incidents
+    ADD COLUMN template_id UUID REFERENCES incident_templates(id) ON DELETE SET NULL;
+
+ALTER TABLE incident_templates
+    ADD COLUMN source_incident_id UUID REFERENCES incidents(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_incidents_template ON incidents(template_id) WHERE template_id IS NOT NULL;
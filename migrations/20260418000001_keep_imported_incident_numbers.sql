@@ -0,0 +1,14 @@
+-- Rows restored by `import_snapshot` already carry their number; keep it so
+-- the PLAT-12 people quote still finds the incident after a DR restore. Only
+-- new incidents (number left NULL) are numbered from the sequence.
+CREATE OR REPLACE FUNCTION assign_incident_number() RETURNS trigger AS $$
+BEGIN
+    IF NEW.number IS NULL THEN
+        INSERT INTO incident_number_sequences AS s (prefix, last_number)
+        VALUES (NEW.number_prefix, 1)
+        ON CONFLICT (prefix) DO UPDATE SET last_number = s.last_number + 1
+        RETURNING last_number INTO NEW.number;
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
@@ -0,0 +1,3 @@
+-- Whether an unsent fan-out thread reply was to be broadcast to its channel
+-- too (`reply_broadcast`), so it keeps that when it's flushed or retried.
+ALTER TABLE incident_notifications ADD COLUMN broadcast BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,128 @@
+use super::{
+    has_bearer_token, invalid_payload, severity_from_label, Alert, AlertSource, AlertStatus,
+};
+use crate::error::IncidentResult;
+use axum::http::HeaderMap;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Prometheus Alertmanager `webhook_configs`, authenticated with
+/// `http_config.authorization` (a bearer token). Service and severity come
+/// from the `service` and `severity` labels.
+pub struct Alertmanager;
+
+#[derive(Debug, Deserialize)]
+struct Payload {
+    alerts: Vec<AlertmanagerAlert>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AlertmanagerAlert {
+    status: String,
+    #[serde(default)]
+    labels: HashMap<String, String>,
+    #[serde(default)]
+    annotations: HashMap<String, String>,
+    starts_at: Option<DateTime<Utc>>,
+    #[serde(rename = "generatorURL")]
+    generator_url: Option<String>,
+    #[serde(default)]
+    fingerprint: String,
+}
+
+impl AlertSource for Alertmanager {
+    fn name(&self) -> &'static str {
+        "alertmanager"
+    }
+
+    fn label(&self) -> &'static str {
+        "Alertmanager"
+    }
+
+    fn authenticate(&self, headers: &HeaderMap, secret: &str) -> bool {
+        has_bearer_token(headers, secret)
+    }
+
+    fn parse(&self, body: &[u8]) -> IncidentResult<Vec<Alert>> {
+        let payload: Payload =
+            serde_json::from_slice(body).map_err(|e| invalid_payload(self.label(), e))?;
+
+        Ok(payload
+            .alerts
+            .into_iter()
+            .map(|alert| {
+                let title = alert
+                    .annotations
+                    .get("summary")
+                    .or_else(|| alert.labels.get("alertname"))
+                    .cloned()
+                    .unwrap_or_else(|| "Alert".to_string());
+                Alert {
+                    fingerprint: alert.fingerprint,
+                    title,
+                    status: if alert.status == "resolved" {
+                        AlertStatus::Resolved
+                    } else {
+                        AlertStatus::Firing
+                    },
+                    severity: alert
+                        .labels
+                        .get("severity")
+                        .and_then(|s| severity_from_label(s)),
+                    service: alert.labels.get("service").cloned(),
+                    description: alert.annotations.get("description").cloned(),
+                    url: alert.generator_url.filter(|url| !url.is_empty()),
+                    starts_at: alert.starts_at,
+                    snapshot_url: None,
+                    labels: alert.labels.into_iter().collect(),
+                }
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::models::Severity;
+    use serde_json::json;
+
+    #[test]
+    fn test_parses_alertmanager_webhook() {
+        let body = json!({
+            "version": "4",
+            "status": "firing",
+            "alerts": [
+                {
+                    "status": "firing",
+                    "labels": { "alertname": "HighErrorRate", "service": "api-gateway", "severity": "critical" },
+                    "annotations": { "summary": "5xx above 5%", "description": "Error ratio 7.2%" },
+                    "startsAt": "2024-11-15T10:30:00Z",
+                    "generatorURL": "http://prometheus/graph?g0.expr=x",
+                    "fingerprint": "c0ffee"
+                },
+                {
+                    "status": "resolved",
+                    "labels": { "alertname": "DiskFull" },
+                    "annotations": {},
+                    "fingerprint": "beef"
+                }
+            ]
+        });
+
+        let alerts = Alertmanager.parse(body.to_string().as_bytes()).unwrap();
+        assert_eq!(alerts.len(), 2);
+        assert_eq!(alerts[0].title, "5xx above 5%");
+        assert_eq!(alerts[0].status, AlertStatus::Firing);
+        assert_eq!(alerts[0].severity, Some(Severity::P1));
+        assert_eq!(alerts[0].service.as_deref(), Some("api-gateway"));
+        assert_eq!(alerts[0].fingerprint, "c0ffee");
+        assert_eq!(alerts[1].title, "DiskFull");
+        assert_eq!(alerts[1].status, AlertStatus::Resolved);
+        assert_eq!(alerts[1].service, None);
+
+        assert!(Alertmanager.parse(b"{}").is_err());
+    }
+}
@@ -0,0 +1,179 @@
+use super::{has_basic_auth_password, invalid_payload, Alert, AlertSource, AlertStatus};
+use crate::error::IncidentResult;
+use axum::http::HeaderMap;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+/// CloudWatch alarms delivered through an SNS HTTPS subscription. SNS can't
+/// add headers, so the secret goes in the subscription URL as basic auth
+/// (`https://sns:<secret>@host/integrations/alerts/cloudwatch`). Alarms
+/// carry no labels: the service comes from a `service:<name>` token in the
+/// alarm description.
+pub struct CloudWatch;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct SnsMessage {
+    #[serde(rename = "Type")]
+    message_type: String,
+    #[serde(default)]
+    message: String,
+    #[serde(rename = "SubscribeURL")]
+    subscribe_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct Alarm {
+    alarm_name: String,
+    #[serde(default)]
+    alarm_description: Option<String>,
+    /// ALARM, OK or INSUFFICIENT_DATA
+    new_state_value: String,
+    #[serde(default)]
+    new_state_reason: Option<String>,
+    #[serde(default)]
+    state_change_time: Option<String>,
+    #[serde(default)]
+    alarm_arn: Option<String>,
+    #[serde(default)]
+    region: Option<String>,
+}
+
+impl AlertSource for CloudWatch {
+    fn name(&self) -> &'static str {
+        "cloudwatch"
+    }
+
+    fn label(&self) -> &'static str {
+        "CloudWatch"
+    }
+
+    fn authenticate(&self, headers: &HeaderMap, secret: &str) -> bool {
+        has_basic_auth_password(headers, secret)
+    }
+
+    fn parse(&self, body: &[u8]) -> IncidentResult<Vec<Alert>> {
+        let envelope: SnsMessage =
+            serde_json::from_slice(body).map_err(|e| invalid_payload(self.label(), e))?;
+        if envelope.message_type != "Notification" {
+            return Ok(vec![]);
+        }
+        let alarm: Alarm = serde_json::from_str(&envelope.message)
+            .map_err(|e| invalid_payload(self.label(), e))?;
+        // INSUFFICIENT_DATA isn't a state change worth recording
+        let status = match alarm.new_state_value.as_str() {
+            "ALARM" => AlertStatus::Firing,
+            "OK" => AlertStatus::Resolved,
+            _ => return Ok(vec![]),
+        };
+
+        let service = alarm.alarm_description.as_deref().and_then(|description| {
+            description
+                .split_whitespace()
+                .find_map(|word| word.strip_prefix("service:"))
+                .map(str::to_string)
+        });
+        let url = alarm.region.as_deref().map(|region| {
+            format!(
+                "https://console.aws.amazon.com/cloudwatch/home?region={}#alarmsV2:alarm/{}",
+                region, alarm.alarm_name
+            )
+        });
+
+        Ok(vec![Alert {
+            fingerprint: alarm.alarm_arn.unwrap_or_else(|| alarm.alarm_name.clone()),
+            title: alarm.alarm_name,
+            status,
+            severity: None,
+            service,
+            description: alarm.new_state_reason,
+            url,
+            starts_at: alarm
+                .state_change_time
+                .as_deref()
+                .and_then(|t| DateTime::parse_from_str(t, "%Y-%m-%dT%H:%M:%S%.3f%z").ok())
+                .map(|t| t.with_timezone(&Utc)),
+            snapshot_url: None,
+            labels: BTreeMap::new(),
+        }])
+    }
+
+    fn subscription_confirmation(&self, body: &[u8]) -> Option<String> {
+        let envelope: SnsMessage = serde_json::from_slice(body).ok()?;
+        if envelope.message_type != "SubscriptionConfirmation" {
+            return None;
+        }
+        // Only ever call back to SNS itself
+        let url = reqwest::Url::parse(envelope.subscribe_url.as_deref()?).ok()?;
+        let is_sns = url.scheme() == "https"
+            && url
+                .host_str()
+                .is_some_and(|host| host.starts_with("sns.") && host.ends_with(".amazonaws.com"));
+        is_sns.then(|| url.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parses_sns_alarm_notification() {
+        let alarm = json!({
+            "AlarmName": "checkout-5xx",
+            "AlarmDescription": "Checkout ALB errors service:checkout",
+            "NewStateValue": "ALARM",
+            "NewStateReason": "Threshold Crossed: 1 datapoint [42.0] was greater than 10.0",
+            "StateChangeTime": "2024-11-15T10:30:00.000+0000",
+            "Region": "us-east-1",
+            "AlarmArn": "arn:aws:cloudwatch:us-east-1:123456789012:alarm:checkout-5xx"
+        });
+        let body = json!({
+            "Type": "Notification",
+            "TopicArn": "arn:aws:sns:us-east-1:123456789012:alarms",
+            "Message": alarm.to_string()
+        });
+
+        let alert = CloudWatch
+            .parse(body.to_string().as_bytes())
+            .unwrap()
+            .remove(0);
+        assert_eq!(alert.title, "checkout-5xx");
+        assert_eq!(alert.status, AlertStatus::Firing);
+        assert_eq!(alert.service.as_deref(), Some("checkout"));
+        assert_eq!(
+            alert.fingerprint,
+            "arn:aws:cloudwatch:us-east-1:123456789012:alarm:checkout-5xx"
+        );
+        assert_eq!(alert.starts_at.unwrap().timestamp(), 1_731_666_600);
+
+        let confirmation = json!({
+            "Type": "SubscriptionConfirmation",
+            "SubscribeURL": "https://sns.us-east-1.amazonaws.com/?Action=ConfirmSubscription&Token=abc"
+        })
+        .to_string();
+        assert!(CloudWatch
+            .parse(confirmation.as_bytes())
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            CloudWatch
+                .subscription_confirmation(confirmation.as_bytes())
+                .as_deref(),
+            Some("https://sns.us-east-1.amazonaws.com/?Action=ConfirmSubscription&Token=abc")
+        );
+
+        let elsewhere = json!({
+            "Type": "SubscriptionConfirmation",
+            "SubscribeURL": "http://169.254.169.254/latest/meta-data/"
+        })
+        .to_string();
+        assert_eq!(
+            CloudWatch.subscription_confirmation(elsewhere.as_bytes()),
+            None
+        );
+    }
+}
@@ -0,0 +1,173 @@
+use super::{header_value, invalid_payload, severity_from_label, Alert, AlertSource, AlertStatus};
+use crate::error::IncidentResult;
+use axum::http::HeaderMap;
+use chrono::DateTime;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+/// Header carrying the shared secret, set under the webhook's custom headers.
+const TOKEN_HEADER: &str = "x-datadog-webhook-token";
+
+/// Datadog webhook integration. The payload template must send the fields
+/// below (see CONFIGURATION.md); the service comes from a `service:<name>`
+/// monitor tag and severity from the monitor priority. `key:value` tags
+/// become labels for `DATADOG_ROUTES`.
+pub struct Datadog;
+
+#[derive(Debug, Deserialize)]
+struct Payload {
+    /// `$ALERT_ID`
+    alert_id: String,
+    /// `$ALERT_SCOPE`: the group of a multi-alert monitor, e.g. `host:web-1`
+    #[serde(default)]
+    scope: Option<String>,
+    /// `$EVENT_TITLE`
+    title: String,
+    /// `$ALERT_TRANSITION`: Triggered, Re-Triggered, Warn, Recovered, ...
+    transition: String,
+    /// `$ALERT_PRIORITY`
+    #[serde(default)]
+    priority: Option<String>,
+    /// `$TAGS`, comma-separated
+    #[serde(default)]
+    tags: String,
+    /// `$EVENT_MSG`
+    #[serde(default)]
+    body: Option<String>,
+    /// `$LINK`
+    #[serde(default)]
+    link: Option<String>,
+    /// `$SNAPSHOT`
+    #[serde(default)]
+    snapshot: Option<String>,
+    /// `$DATE`, epoch milliseconds
+    #[serde(default)]
+    date: Option<String>,
+}
+
+impl AlertSource for Datadog {
+    fn name(&self) -> &'static str {
+        "datadog"
+    }
+
+    fn label(&self) -> &'static str {
+        "Datadog"
+    }
+
+    fn authenticate(&self, headers: &HeaderMap, secret: &str) -> bool {
+        header_value(headers, TOKEN_HEADER)
+            .is_some_and(|token| crate::api::constant_time_eq(token, secret))
+    }
+
+    // Renotifications and Warn/No Data transitions are news about a firing
+    // monitor, not resends
+    fn repeats_are_updates(&self) -> bool {
+        true
+    }
+
+    fn parse(&self, body: &[u8]) -> IncidentResult<Vec<Alert>> {
+        let payload: Payload =
+            serde_json::from_slice(body).map_err(|e| invalid_payload(self.label(), e))?;
+
+        let labels: BTreeMap<String, String> = payload
+            .tags
+            .split(',')
+            .map(str::trim)
+            .filter(|tag| !tag.is_empty())
+            .map(|tag| match tag.split_once(':') {
+                Some((key, value)) => (key.to_string(), value.to_string()),
+                None => (tag.to_string(), String::new()),
+            })
+            .collect();
+        // Each group of a multi-alert monitor alerts on its own
+        let fingerprint = match payload.scope.as_deref().map(str::trim) {
+            Some(scope) if !scope.is_empty() => format!("{}:{}", payload.alert_id, scope),
+            _ => payload.alert_id,
+        };
+        // The transition is already conveyed by the status
+        let title = match payload.title.strip_prefix('[') {
+            Some(rest) => rest
+                .split_once("] ")
+                .map_or(payload.title.as_str(), |(_, title)| title)
+                .to_string(),
+            None => payload.title,
+        };
+        let status = if payload.transition.eq_ignore_ascii_case("recovered") {
+            AlertStatus::Resolved
+        } else {
+            AlertStatus::Firing
+        };
+
+        Ok(vec![Alert {
+            fingerprint,
+            title,
+            status,
+            severity: payload.priority.as_deref().and_then(severity_from_label),
+            service: labels.get("service").cloned(),
+            description: payload.body.filter(|b| !b.is_empty()),
+            url: payload.link.filter(|l| !l.is_empty()),
+            starts_at: payload
+                .date
+                .and_then(|d| d.parse::<i64>().ok())
+                .and_then(DateTime::from_timestamp_millis),
+            snapshot_url: payload.snapshot.filter(|s| !s.is_empty()),
+            labels,
+        }])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::models::Severity;
+    use serde_json::json;
+
+    #[test]
+    fn test_parses_datadog_webhook() {
+        let body = json!({
+            "alert_id": "12345",
+            "title": "[Triggered] p99 latency high on checkout",
+            "transition": "Triggered",
+            "priority": "P2",
+            "tags": "env:prod, service:checkout,team:payments",
+            "body": "p99 above 2s for 5 minutes",
+            "link": "https://app.datadoghq.com/monitors/12345",
+            "snapshot": "https://p.datadoghq.com/snapshot/view/abc.png",
+            "date": "1731666600000"
+        });
+
+        let alert = Datadog
+            .parse(body.to_string().as_bytes())
+            .unwrap()
+            .remove(0);
+        assert_eq!(alert.fingerprint, "12345");
+        assert_eq!(alert.title, "p99 latency high on checkout");
+        assert_eq!(alert.status, AlertStatus::Firing);
+        assert_eq!(alert.severity, Some(Severity::P2));
+        assert_eq!(alert.service.as_deref(), Some("checkout"));
+        assert_eq!(
+            alert.labels.get("team").map(String::as_str),
+            Some("payments")
+        );
+        assert_eq!(
+            alert.snapshot_url.as_deref(),
+            Some("https://p.datadoghq.com/snapshot/view/abc.png")
+        );
+        assert_eq!(alert.starts_at.unwrap().timestamp(), 1_731_666_600);
+
+        let recovered = json!({
+            "alert_id": "12345",
+            "scope": "host:web-1",
+            "title": "[Recovered] p99 latency high on checkout",
+            "transition": "Recovered"
+        });
+        let alert = Datadog
+            .parse(recovered.to_string().as_bytes())
+            .unwrap()
+            .remove(0);
+        assert_eq!(alert.fingerprint, "12345:host:web-1");
+        assert_eq!(alert.status, AlertStatus::Resolved);
+        assert_eq!(alert.service, None);
+        assert!(alert.labels.is_empty());
+    }
+}
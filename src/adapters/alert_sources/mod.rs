@@ -0,0 +1,187 @@
+//! Inbound alerts from monitoring tools. Each tool has a parser registered
+//! here behind [`AlertSource`]; it checks the tool's own authentication and
+//! maps its payload to [`Alert`]s, which are served at
+//! `POST /integrations/alerts/{name}`.
+
+mod alertmanager;
+mod cloudwatch;
+mod datadog;
+mod newrelic;
+
+pub use alertmanager::Alertmanager;
+pub use cloudwatch::CloudWatch;
+pub use datadog::Datadog;
+pub use newrelic::NewRelic;
+
+use crate::db::models::Severity;
+use crate::error::{IncidentError, IncidentResult};
+use axum::http::{header, HeaderMap};
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Every supported monitoring tool.
+static SOURCES: &[&dyn AlertSource] = &[&Alertmanager, &Datadog, &CloudWatch, &NewRelic];
+
+/// A monitoring tool that posts alerts to the bot.
+pub trait AlertSource: Send + Sync {
+    /// Path segment and key in `ALERT_SOURCE_TOKENS`
+    fn name(&self) -> &'static str;
+
+    /// Human-readable name for timeline entries
+    fn label(&self) -> &'static str;
+
+    /// Whether the request carries `secret` the way this tool sends it.
+    fn authenticate(&self, headers: &HeaderMap, secret: &str) -> bool;
+
+    /// Map the tool's payload to alerts.
+    fn parse(&self, body: &[u8]) -> IncidentResult<Vec<Alert>>;
+
+    /// URL to request to confirm a subscription, if `body` is a handshake
+    /// rather than alerts.
+    fn subscription_confirmation(&self, _body: &[u8]) -> Option<String> {
+        None
+    }
+
+    /// Whether another firing notification for an alert already firing
+    /// carries news (a renotification, a changed value) worth posting,
+    /// rather than being a resend of the same state.
+    fn repeats_are_updates(&self) -> bool {
+        false
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertStatus {
+    Firing,
+    Resolved,
+}
+
+/// One alert notification, normalized across tools.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Alert {
+    /// Identifies the alert across notifications (fingerprint, monitor or
+    /// alarm ID)
+    pub fingerprint: String,
+    pub title: String,
+    pub status: AlertStatus,
+    pub severity: Option<Severity>,
+    /// Service as the tool names it; matched against `SERVICES` ignoring case
+    pub service: Option<String>,
+    pub description: Option<String>,
+    pub url: Option<String>,
+    /// Graph image of the alerting metric (Datadog only)
+    pub snapshot_url: Option<String>,
+    pub starts_at: Option<DateTime<Utc>>,
+    /// Labels the tool attached to the alert (Alertmanager labels, Datadog
+    /// `key:value` tags)
+    pub labels: BTreeMap<String, String>,
+}
+
+/// The registered source called `name`.
+pub fn find(name: &str) -> Option<&'static dyn AlertSource> {
+    SOURCES.iter().copied().find(|source| source.name() == name)
+}
+
+/// Names of every registered source.
+pub fn names() -> Vec<&'static str> {
+    SOURCES.iter().map(|source| source.name()).collect()
+}
+
+/// Map a tool's severity or priority label (`critical`, `P2`, `warning`, ...)
+/// to a severity.
+pub fn severity_from_label(label: &str) -> Option<Severity> {
+    if let Ok(severity) = label.parse::<Severity>() {
+        return Some(severity);
+    }
+    match label.trim().to_ascii_lowercase().as_str() {
+        "critical" | "page" | "disaster" => Some(Severity::P1),
+        "high" | "error" | "major" => Some(Severity::P2),
+        "warning" | "warn" | "medium" | "minor" => Some(Severity::P3),
+        "low" | "info" | "informational" => Some(Severity::P4),
+        _ => None,
+    }
+}
+
+fn invalid_payload(source: &str, error: impl std::fmt::Display) -> IncidentError {
+    IncidentError::ValidationError {
+        field: "body".to_string(),
+        reason: format!("Invalid {} payload: {}", source, error),
+    }
+}
+
+fn header_value<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers.get(name).and_then(|v| v.to_str().ok())
+}
+
+/// `Authorization: Bearer <secret>`
+fn has_bearer_token(headers: &HeaderMap, secret: &str) -> bool {
+    header_value(headers, header::AUTHORIZATION.as_str())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .is_some_and(|token| crate::api::constant_time_eq(token, secret))
+}
+
+/// `Authorization: Basic <user:secret>`, any user name.
+fn has_basic_auth_password(headers: &HeaderMap, secret: &str) -> bool {
+    header_value(headers, header::AUTHORIZATION.as_str())
+        .and_then(|v| v.strip_prefix("Basic "))
+        .and_then(|encoded| {
+            base64::engine::general_purpose::STANDARD
+                .decode(encoded)
+                .ok()
+        })
+        .and_then(|decoded| String::from_utf8(decoded).ok())
+        .and_then(|credentials| {
+            credentials
+                .split_once(':')
+                .map(|(_, password)| password.to_string())
+        })
+        .is_some_and(|password| crate::api::constant_time_eq(&password, secret))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    #[test]
+    fn test_sources_are_registered_by_name() {
+        assert_eq!(
+            names(),
+            vec!["alertmanager", "datadog", "cloudwatch", "newrelic"]
+        );
+        assert_eq!(find("datadog").unwrap().label(), "Datadog");
+        assert!(find("nagios").is_none());
+    }
+
+    #[test]
+    fn test_severity_from_label() {
+        assert_eq!(severity_from_label("critical"), Some(Severity::P1));
+        assert_eq!(severity_from_label("p2"), Some(Severity::P2));
+        assert_eq!(severity_from_label("Warning"), Some(Severity::P3));
+        assert_eq!(severity_from_label("info"), Some(Severity::P4));
+        assert_eq!(severity_from_label("P5"), None);
+    }
+
+    #[test]
+    fn test_auth_helpers() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_static("Bearer s3cret"),
+        );
+        assert!(has_bearer_token(&headers, "s3cret"));
+        assert!(!has_bearer_token(&headers, "other"));
+        assert!(!has_basic_auth_password(&headers, "s3cret"));
+
+        // sns:s3cret
+        headers.insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_static("Basic c25zOnMzY3JldA=="),
+        );
+        assert!(has_basic_auth_password(&headers, "s3cret"));
+        assert!(!has_bearer_token(&headers, "s3cret"));
+    }
+}
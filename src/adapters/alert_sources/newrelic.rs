@@ -0,0 +1,120 @@
+use super::{
+    has_bearer_token, invalid_payload, severity_from_label, Alert, AlertSource, AlertStatus,
+};
+use crate::error::IncidentResult;
+use axum::http::HeaderMap;
+use chrono::DateTime;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// New Relic workflow webhooks, authenticated with the destination's bearer
+/// token. The payload template must send the fields below (see
+/// CONFIGURATION.md).
+pub struct NewRelic;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Payload {
+    /// `{{ issueId }}`
+    issue_id: String,
+    /// `{{ json annotations.title }}`: a list of titles, or one string
+    title: Value,
+    /// `{{ state }}`: CREATED, ACTIVATED, ACKNOWLEDGED or CLOSED
+    state: String,
+    /// `{{ priority }}`: CRITICAL, HIGH, MEDIUM or LOW
+    #[serde(default)]
+    priority: Option<String>,
+    /// e.g. `{{ accumulations.tag.service.[0] }}`
+    #[serde(default)]
+    service: Option<String>,
+    /// `{{ issuePageUrl }}`
+    #[serde(default)]
+    issue_page_url: Option<String>,
+    /// `{{ createdAt }}`, epoch milliseconds
+    #[serde(default)]
+    created_at: Option<i64>,
+}
+
+impl AlertSource for NewRelic {
+    fn name(&self) -> &'static str {
+        "newrelic"
+    }
+
+    fn label(&self) -> &'static str {
+        "New Relic"
+    }
+
+    fn authenticate(&self, headers: &HeaderMap, secret: &str) -> bool {
+        has_bearer_token(headers, secret)
+    }
+
+    fn parse(&self, body: &[u8]) -> IncidentResult<Vec<Alert>> {
+        let payload: Payload =
+            serde_json::from_slice(body).map_err(|e| invalid_payload(self.label(), e))?;
+
+        let title = match &payload.title {
+            Value::String(title) => title.clone(),
+            Value::Array(titles) => titles
+                .first()
+                .and_then(Value::as_str)
+                .unwrap_or("Issue")
+                .to_string(),
+            _ => "Issue".to_string(),
+        };
+
+        Ok(vec![Alert {
+            fingerprint: payload.issue_id,
+            title,
+            status: if payload.state.eq_ignore_ascii_case("closed") {
+                AlertStatus::Resolved
+            } else {
+                AlertStatus::Firing
+            },
+            severity: payload.priority.as_deref().and_then(severity_from_label),
+            service: payload.service.filter(|s| !s.is_empty()),
+            description: None,
+            url: payload.issue_page_url.filter(|u| !u.is_empty()),
+            starts_at: payload.created_at.and_then(DateTime::from_timestamp_millis),
+            snapshot_url: None,
+            labels: BTreeMap::new(),
+        }])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::models::Severity;
+    use serde_json::json;
+
+    #[test]
+    fn test_parses_newrelic_workflow_webhook() {
+        let body = json!({
+            "issueId": "f1c2-9a",
+            "title": ["Apdex below 0.7 for checkout-web"],
+            "state": "ACTIVATED",
+            "priority": "HIGH",
+            "service": "checkout",
+            "issuePageUrl": "https://one.newrelic.com/alerts-ai/issues/f1c2-9a",
+            "createdAt": 1731666600000i64
+        });
+
+        let alert = NewRelic
+            .parse(body.to_string().as_bytes())
+            .unwrap()
+            .remove(0);
+        assert_eq!(alert.title, "Apdex below 0.7 for checkout-web");
+        assert_eq!(alert.status, AlertStatus::Firing);
+        assert_eq!(alert.severity, Some(Severity::P2));
+        assert_eq!(alert.service.as_deref(), Some("checkout"));
+
+        let closed = json!({ "issueId": "f1c2-9a", "title": "Apdex", "state": "CLOSED" });
+        let alert = NewRelic
+            .parse(closed.to_string().as_bytes())
+            .unwrap()
+            .remove(0);
+        assert_eq!(alert.status, AlertStatus::Resolved);
+        assert_eq!(alert.severity, None);
+    }
+}
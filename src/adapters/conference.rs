@@ -0,0 +1,210 @@
+use crate::config::ConferenceProvider;
+use crate::error::{IncidentError, IncidentResult};
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::time::Duration;
+use tracing::{debug, error, info};
+
+const ZOOM_TOKEN_URL: &str = "https://zoom.us/oauth/token";
+pub const ZOOM_API_URL: &str = "https://api.zoom.us/v2";
+const GOOGLE_TOKEN_URL: &str = "https://oauth2.googleapis.com/token";
+pub const GOOGLE_MEET_API_URL: &str = "https://meet.googleapis.com/v2";
+
+/// Creates conference bridges on Zoom (Server-to-Server OAuth) or Google
+/// Meet (OAuth client with a refresh token).
+///
+/// Each bridge fetches a fresh access token; declarations are rare enough
+/// that caching one isn't worth it.
+#[derive(Clone)]
+pub struct ConferenceClient {
+    http_client: Client,
+    provider: ConferenceProvider,
+    client_id: String,
+    client_secret: String,
+    /// Zoom account ID or Google refresh token
+    grant: String,
+    token_url: String,
+    api_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct AccessToken {
+    access_token: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ZoomMeeting {
+    join_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MeetSpace {
+    meeting_uri: String,
+}
+
+impl ConferenceClient {
+    pub fn new(
+        provider: ConferenceProvider,
+        client_id: String,
+        client_secret: String,
+        grant: String,
+    ) -> Self {
+        // Set 30-second timeout to prevent hanging requests to the conferencing API
+        let http_client = Client::builder()
+            .timeout(Duration::from_secs(30))
+            .build()
+            .expect("Failed to build HTTP client");
+        let (token_url, api_url) = match provider {
+            ConferenceProvider::Zoom => (ZOOM_TOKEN_URL, ZOOM_API_URL),
+            ConferenceProvider::GoogleMeet => (GOOGLE_TOKEN_URL, GOOGLE_MEET_API_URL),
+        };
+
+        Self {
+            http_client,
+            provider,
+            client_id,
+            client_secret,
+            grant,
+            token_url: token_url.to_string(),
+            api_url: api_url.to_string(),
+        }
+    }
+
+    /// Point the client at other token and API endpoints (proxies, tests).
+    pub fn with_endpoints(mut self, token_url: String, api_url: String) -> Self {
+        self.token_url = token_url;
+        self.api_url = api_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn provider(&self) -> ConferenceProvider {
+        self.provider
+    }
+
+    /// Create a bridge named `topic`. Returns its join URL.
+    pub async fn create_bridge(&self, topic: &str) -> IncidentResult<String> {
+        debug!("Creating {} bridge", self.provider.label());
+        let token = self.access_token().await?;
+
+        let join_url = match self.provider {
+            ConferenceProvider::Zoom => {
+                let response = self
+                    .http_client
+                    .post(format!("{}/users/me/meetings", self.api_url))
+                    .bearer_auth(&token)
+                    .json(&Self::zoom_meeting_request(topic))
+                    .send()
+                    .await?;
+                self.parse::<ZoomMeeting>(response).await?.join_url
+            }
+            ConferenceProvider::GoogleMeet => {
+                // Meet spaces have no title; anyone in the domain with the
+                // link can join
+                let response = self
+                    .http_client
+                    .post(format!("{}/spaces", self.api_url))
+                    .bearer_auth(&token)
+                    .json(&json!({ "config": { "accessType": "TRUSTED" } }))
+                    .send()
+                    .await?;
+                self.parse::<MeetSpace>(response).await?.meeting_uri
+            }
+        };
+
+        info!("Created {} bridge {}", self.provider.label(), join_url);
+        Ok(join_url)
+    }
+
+    async fn access_token(&self) -> IncidentResult<String> {
+        let request = match self.provider {
+            ConferenceProvider::Zoom => self
+                .http_client
+                .post(&self.token_url)
+                .basic_auth(&self.client_id, Some(&self.client_secret))
+                .query(&[
+                    ("grant_type", "account_credentials"),
+                    ("account_id", self.grant.as_str()),
+                ]),
+            ConferenceProvider::GoogleMeet => self.http_client.post(&self.token_url).form(&[
+                ("grant_type", "refresh_token"),
+                ("client_id", self.client_id.as_str()),
+                ("client_secret", self.client_secret.as_str()),
+                ("refresh_token", self.grant.as_str()),
+            ]),
+        };
+        let token: AccessToken = self.parse(request.send().await?).await?;
+        Ok(token.access_token)
+    }
+
+    async fn parse<T: for<'de> Deserialize<'de>>(
+        &self,
+        response: reqwest::Response,
+    ) -> IncidentResult<T> {
+        if !response.status().is_success() {
+            let status_code = response.status();
+            let error_text = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            error!(
+                "{} API error ({}): {}",
+                self.provider.label(),
+                status_code,
+                error_text
+            );
+            return Err(IncidentError::ExternalAPIError {
+                service: self.provider.label().to_string(),
+                message: format!("HTTP {}: {}", status_code, error_text),
+            });
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|e| IncidentError::ExternalAPIError {
+                service: self.provider.label().to_string(),
+                message: format!("Invalid response: {}", e),
+            })
+    }
+
+    /// `POST /users/me/meetings` body: an instant meeting responders can
+    /// join before the host.
+    fn zoom_meeting_request(topic: &str) -> Value {
+        json!({
+            "topic": topic,
+            "type": 1,
+            "settings": {
+                "join_before_host": true,
+                "waiting_room": false
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zoom_meeting_request_is_instant_and_open() {
+        let body = ConferenceClient::zoom_meeting_request("P1: Checkout down");
+        assert_eq!(body["topic"], "P1: Checkout down");
+        assert_eq!(body["type"], 1);
+        assert_eq!(body["settings"]["join_before_host"], true);
+
+        let client = ConferenceClient::new(
+            ConferenceProvider::GoogleMeet,
+            "client".to_string(),
+            "secret".to_string(),
+            "refresh".to_string(),
+        )
+        .with_endpoints(
+            "http://127.0.0.1:1/token".to_string(),
+            "http://127.0.0.1:1/v2/".to_string(),
+        );
+        assert_eq!(client.api_url, "http://127.0.0.1:1/v2");
+        assert_eq!(client.provider(), ConferenceProvider::GoogleMeet);
+    }
+}
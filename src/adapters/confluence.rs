@@ -0,0 +1,285 @@
+use crate::error::{IncidentError, IncidentResult};
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::time::Duration;
+use tracing::{debug, error, info};
+
+/// Confluence REST client (content API, basic auth with an API token).
+///
+/// `base_url` is the wiki root, e.g. `https://example.atlassian.net/wiki`.
+#[derive(Clone)]
+pub struct ConfluenceClient {
+    http_client: Client,
+    base_url: String,
+    email: String,
+    api_token: String,
+}
+
+/// A page created by [`ConfluenceClient::create_page`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfluencePage {
+    pub id: String,
+    pub url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct CreatedContent {
+    id: String,
+    #[serde(rename = "_links")]
+    links: ContentLinks,
+}
+
+#[derive(Debug, Deserialize)]
+struct ContentLinks {
+    webui: String,
+}
+
+impl ConfluenceClient {
+    pub fn new(base_url: String, email: String, api_token: String) -> Self {
+        // Set 30-second timeout to prevent hanging requests to Confluence API
+        let http_client = Client::builder()
+            .timeout(Duration::from_secs(30))
+            .build()
+            .expect("Failed to build HTTP client");
+
+        Self {
+            http_client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            email,
+            api_token,
+        }
+    }
+
+    /// Create a page in `space_key`, under `parent_page_id` when given.
+    /// `markdown` is converted to Confluence storage format.
+    pub async fn create_page(
+        &self,
+        space_key: &str,
+        parent_page_id: Option<&str>,
+        title: &str,
+        markdown: &str,
+    ) -> IncidentResult<ConfluencePage> {
+        debug!("Creating Confluence page in space {}", space_key);
+
+        let response = self
+            .http_client
+            .post(format!("{}/rest/api/content", self.base_url))
+            .basic_auth(&self.email, Some(&self.api_token))
+            .json(&Self::page_request(
+                space_key,
+                parent_page_id,
+                title,
+                &markdown_to_storage(markdown),
+            ))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let status_code = response.status();
+            let error_text = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            error!("Confluence API error ({}): {}", status_code, error_text);
+            return Err(IncidentError::ExternalAPIError {
+                service: "Confluence".to_string(),
+                message: format!("HTTP {}: {}", status_code, error_text),
+            });
+        }
+
+        let content: CreatedContent =
+            response
+                .json()
+                .await
+                .map_err(|e| IncidentError::ExternalAPIError {
+                    service: "Confluence".to_string(),
+                    message: format!("Invalid create page response: {}", e),
+                })?;
+
+        let page = ConfluencePage {
+            url: format!("{}{}", self.base_url, content.links.webui),
+            id: content.id,
+        };
+        info!("Created Confluence page {}", page.id);
+        Ok(page)
+    }
+
+    /// `POST /rest/api/content` body.
+    fn page_request(
+        space_key: &str,
+        parent_page_id: Option<&str>,
+        title: &str,
+        storage: &str,
+    ) -> Value {
+        let mut body = json!({
+            "type": "page",
+            "title": title,
+            "space": { "key": space_key },
+            "body": {
+                "storage": {
+                    "value": storage,
+                    "representation": "storage"
+                }
+            }
+        });
+        if let Some(parent) = parent_page_id {
+            body["ancestors"] = json!([{ "id": parent }]);
+        }
+        body
+    }
+}
+
+/// Convert the postmortem markdown subset (headings, bullet lists, block
+/// quotes, bold and italic lines, rules) to Confluence storage-format XHTML.
+fn markdown_to_storage(markdown: &str) -> String {
+    let mut html = String::new();
+    let mut in_list = false;
+    let mut paragraph: Vec<String> = Vec::new();
+    let mut quote: Vec<String> = Vec::new();
+
+    fn flush(html: &mut String, paragraph: &mut Vec<String>) {
+        if !paragraph.is_empty() {
+            html.push_str(&format!("<p>{}</p>", paragraph.join("<br/>")));
+            paragraph.clear();
+        }
+    }
+
+    fn flush_quote(html: &mut String, quote: &mut Vec<String>) {
+        if !quote.is_empty() {
+            html.push_str(&format!(
+                "<blockquote><p>{}</p></blockquote>",
+                quote.join("<br/>")
+            ));
+            quote.clear();
+        }
+    }
+
+    for line in markdown.lines() {
+        let line = line.trim_end();
+        if let Some(quoted) = line.strip_prefix('>') {
+            if in_list {
+                html.push_str("</ul>");
+                in_list = false;
+            }
+            flush(&mut html, &mut paragraph);
+            quote.push(inline(quoted.strip_prefix(' ').unwrap_or(quoted)));
+            continue;
+        }
+        flush_quote(&mut html, &mut quote);
+        let item = line.strip_prefix("- ");
+
+        if item.is_none() && in_list {
+            html.push_str("</ul>");
+            in_list = false;
+        }
+
+        if let Some(item) = item {
+            flush(&mut html, &mut paragraph);
+            if !in_list {
+                html.push_str("<ul>");
+                in_list = true;
+            }
+            html.push_str(&format!("<li>{}</li>", inline(item)));
+        } else if let Some(heading) = line.strip_prefix("## ") {
+            flush(&mut html, &mut paragraph);
+            html.push_str(&format!("<h2>{}</h2>", inline(heading)));
+        } else if let Some(heading) = line.strip_prefix("# ") {
+            flush(&mut html, &mut paragraph);
+            html.push_str(&format!("<h1>{}</h1>", inline(heading)));
+        } else if line == "---" {
+            flush(&mut html, &mut paragraph);
+            html.push_str("<hr/>");
+        } else if line.is_empty() {
+            flush(&mut html, &mut paragraph);
+        } else {
+            paragraph.push(inline(line));
+        }
+    }
+    if in_list {
+        html.push_str("</ul>");
+    }
+    flush(&mut html, &mut paragraph);
+    flush_quote(&mut html, &mut quote);
+    html
+}
+
+/// Escape XML and render `**bold**`, `*italic*` and `` `code` `` spans.
+fn inline(text: &str) -> String {
+    let escaped = text
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;");
+
+    let mut out = String::new();
+    let mut rest = escaped.as_str();
+    while !rest.is_empty() {
+        let (marker, open, close) = if rest.starts_with("**") {
+            ("**", "<strong>", "</strong>")
+        } else if rest.starts_with('*') {
+            ("*", "<em>", "</em>")
+        } else if rest.starts_with('`') {
+            ("`", "<code>", "</code>")
+        } else {
+            let c = rest.chars().next().unwrap();
+            out.push(c);
+            rest = &rest[c.len_utf8()..];
+            continue;
+        };
+
+        match rest[marker.len()..].find(marker) {
+            Some(end) if end > 0 => {
+                out.push_str(open);
+                out.push_str(&rest[marker.len()..marker.len() + end]);
+                out.push_str(close);
+                rest = &rest[2 * marker.len() + end..];
+            }
+            _ => {
+                out.push_str(marker);
+                rest = &rest[marker.len()..];
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page_request_sets_space_and_parent() {
+        let body = ConfluenceClient::page_request("OPS", Some("123"), "Postmortem", "<p>x</p>");
+        assert_eq!(body["space"]["key"], "OPS");
+        assert_eq!(body["ancestors"][0]["id"], "123");
+        assert_eq!(body["body"]["storage"]["value"], "<p>x</p>");
+
+        let body = ConfluenceClient::page_request("OPS", None, "Postmortem", "<p>x</p>");
+        assert!(body.get("ancestors").is_none());
+    }
+
+    #[test]
+    fn test_markdown_to_storage() {
+        let markdown = "# Postmortem: DB down\n\n## Incident Summary\n- **Severity**: P1 (Critical)\n- **Incident Commander**: <@U024>\n\n**14:02** — 🚨 Declared\n→ Incident declared\n\n---\n*Generated by Incident Bot*";
+        assert_eq!(
+            markdown_to_storage(markdown),
+            "<h1>Postmortem: DB down</h1>\
+             <h2>Incident Summary</h2>\
+             <ul><li><strong>Severity</strong>: P1 (Critical)</li>\
+             <li><strong>Incident Commander</strong>: &lt;@U024&gt;</li></ul>\
+             <p><strong>14:02</strong> — 🚨 Declared<br/>→ Incident declared</p>\
+             <hr/><p><em>Generated by Incident Bot</em></p>"
+        );
+    }
+
+    #[test]
+    fn test_markdown_to_storage_quotes_notes() {
+        let markdown = "**14:10** — 🗒️ Note from U024BOB\n> Pool size is 1 in 4.2.1\n> <b>not</b> html\n\n**14:30** — ✅ Resolved\n→ Incident resolved";
+        assert_eq!(
+            markdown_to_storage(markdown),
+            "<p><strong>14:10</strong> — 🗒️ Note from U024BOB</p>\
+             <blockquote><p>Pool size is 1 in 4.2.1<br/>&lt;b&gt;not&lt;/b&gt; html</p></blockquote>\
+             <p><strong>14:30</strong> — ✅ Resolved<br/>→ Incident resolved</p>"
+        );
+    }
+}
@@ -0,0 +1,271 @@
+use crate::db::models::LinkKind;
+use crate::error::{IncidentError, IncidentResult};
+use axum::http::HeaderMap;
+use hmac::{Hmac, Mac};
+use serde::Deserialize;
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Header GitHub signs webhook deliveries in: `sha256=<hex HMAC of the body>`.
+pub const SIGNATURE_HEADER: &str = "x-hub-signature-256";
+/// Header naming the webhook event (`deployment_status`, `ping`, ...).
+pub const EVENT_HEADER: &str = "x-github-event";
+
+/// A pull request or commit on github.com.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GithubRef {
+    /// `owner/name`
+    pub repo: String,
+    pub kind: LinkKind,
+    /// PR number or commit SHA
+    pub reference: String,
+    /// Canonical URL, without any trailing path, query or fragment
+    pub url: String,
+}
+
+impl GithubRef {
+    /// `acme/checkout#42` or `acme/checkout@1a2b3c4`.
+    pub fn label(&self) -> String {
+        link_label(&self.repo, self.kind, &self.reference)
+    }
+}
+
+/// Short name for a linked PR, commit or deploy.
+pub fn link_label(repo: &str, kind: LinkKind, reference: &str) -> String {
+    match kind {
+        LinkKind::PullRequest => format!("{}#{}", repo, reference),
+        LinkKind::Commit | LinkKind::Deploy => {
+            format!("{}@{}", repo, &reference[..reference.len().min(7)])
+        }
+    }
+}
+
+/// Parse a pull request (`/owner/name/pull/42`, any tab) or commit
+/// (`/owner/name/commit/<sha>`, also as seen within a PR) URL. Slack may wrap
+/// links as `<url>` or `<url|label>`.
+pub fn parse_url(text: &str) -> Option<GithubRef> {
+    let text = text.trim();
+    let text = text
+        .strip_prefix('<')
+        .and_then(|t| t.strip_suffix('>'))
+        .map_or(text, |t| t.split('|').next().unwrap_or(t));
+    let path = text
+        .strip_prefix("https://github.com/")
+        .or_else(|| text.strip_prefix("http://github.com/"))
+        .or_else(|| text.strip_prefix("github.com/"))?;
+    let path = path.split(['?', '#']).next().unwrap_or(path);
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    let (owner, name) = match segments.as_slice() {
+        [owner, name, ..] => (*owner, *name),
+        _ => return None,
+    };
+    let repo = format!("{}/{}", owner, name);
+
+    let (kind, reference) = match segments[2..] {
+        ["pull", _, "commits", sha, ..] if is_sha(sha) => {
+            (LinkKind::Commit, sha.to_ascii_lowercase())
+        }
+        ["pull", number, ..] if number.parse::<u64>().is_ok() => {
+            (LinkKind::PullRequest, number.to_string())
+        }
+        ["commit", sha, ..] if is_sha(sha) => (LinkKind::Commit, sha.to_ascii_lowercase()),
+        _ => return None,
+    };
+    let url = match kind {
+        LinkKind::PullRequest => format!("https://github.com/{}/pull/{}", repo, reference),
+        _ => format!("https://github.com/{}/commit/{}", repo, reference),
+    };
+    Some(GithubRef {
+        repo,
+        kind,
+        reference,
+        url,
+    })
+}
+
+fn is_sha(s: &str) -> bool {
+    (7..=40).contains(&s.len()) && s.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Check `X-Hub-Signature-256` against the raw body.
+pub fn verify_signature(headers: &HeaderMap, body: &[u8], secret: &str) -> bool {
+    let Some(signature) = headers
+        .get(SIGNATURE_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("sha256="))
+        .and_then(|v| hex::decode(v).ok())
+    else {
+        return false;
+    };
+    let mut mac =
+        HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC can take key of any size");
+    mac.update(body);
+    mac.verify_slice(&signature).is_ok()
+}
+
+/// A deployment that finished successfully.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Deployment {
+    /// `owner/name`
+    pub repo: String,
+    pub environment: String,
+    pub sha: String,
+    /// Deploy log or environment URL, else the deployed commit
+    pub url: String,
+    /// GitHub login of whoever triggered it
+    pub creator: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DeploymentStatusPayload {
+    deployment_status: DeploymentStatus,
+    deployment: DeploymentInfo,
+    repository: Repository,
+}
+
+#[derive(Debug, Deserialize)]
+struct DeploymentStatus {
+    state: String,
+    #[serde(default)]
+    target_url: Option<String>,
+    #[serde(default)]
+    environment_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DeploymentInfo {
+    sha: String,
+    environment: String,
+    #[serde(default)]
+    creator: Option<Account>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Account {
+    login: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Repository {
+    full_name: String,
+}
+
+/// Parse a `deployment_status` delivery. Only successful deploys are
+/// returned; pending, failed and errored ones are `None`.
+pub fn parse_deployment_status(body: &[u8]) -> IncidentResult<Option<Deployment>> {
+    let payload: DeploymentStatusPayload =
+        serde_json::from_slice(body).map_err(|e| IncidentError::ValidationError {
+            field: "body".to_string(),
+            reason: format!("Invalid GitHub payload: {}", e),
+        })?;
+    if payload.deployment_status.state != "success" {
+        return Ok(None);
+    }
+
+    let repo = payload.repository.full_name;
+    let url = [
+        payload.deployment_status.target_url,
+        payload.deployment_status.environment_url,
+    ]
+    .into_iter()
+    .flatten()
+    .find(|url| !url.is_empty())
+    .unwrap_or_else(|| {
+        format!(
+            "https://github.com/{}/commit/{}",
+            repo, payload.deployment.sha
+        )
+    });
+    Ok(Some(Deployment {
+        repo,
+        environment: payload.deployment.environment,
+        sha: payload.deployment.sha,
+        url,
+        creator: payload.deployment.creator.map(|c| c.login),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_pull_request_and_commit_urls() {
+        let pr = parse_url("<https://github.com/acme/checkout/pull/42/files?diff=split>").unwrap();
+        assert_eq!(pr.repo, "acme/checkout");
+        assert_eq!(pr.kind, LinkKind::PullRequest);
+        assert_eq!(pr.url, "https://github.com/acme/checkout/pull/42");
+        assert_eq!(pr.label(), "acme/checkout#42");
+
+        let commit =
+            parse_url("https://github.com/acme/checkout/commit/1A2B3C4D5E6F#diff-1").unwrap();
+        assert_eq!(commit.kind, LinkKind::Commit);
+        assert_eq!(commit.reference, "1a2b3c4d5e6f");
+        assert_eq!(commit.label(), "acme/checkout@1a2b3c4");
+
+        let in_pr = parse_url("<https://github.com/acme/checkout/pull/42/commits/abcdef1|abcdef1>")
+            .unwrap();
+        assert_eq!(in_pr.kind, LinkKind::Commit);
+        assert_eq!(in_pr.url, "https://github.com/acme/checkout/commit/abcdef1");
+
+        assert_eq!(parse_url("https://github.com/acme/checkout"), None);
+        assert_eq!(parse_url("https://github.com/acme/checkout/pull/new"), None);
+        assert_eq!(parse_url("https://gitlab.com/acme/checkout/pull/42"), None);
+    }
+
+    #[test]
+    fn test_verify_signature() {
+        let body = br#"{"zen":"Keep it logically awesome."}"#;
+        let mut mac = HmacSha256::new_from_slice(b"secret").unwrap();
+        mac.update(body);
+        let signature = format!("sha256={}", hex::encode(mac.finalize().into_bytes()));
+
+        let mut headers = HeaderMap::new();
+        headers.insert(SIGNATURE_HEADER, signature.parse().unwrap());
+        assert!(verify_signature(&headers, body, "secret"));
+        assert!(!verify_signature(&headers, body, "other"));
+        assert!(!verify_signature(&HeaderMap::new(), body, "secret"));
+    }
+
+    #[test]
+    fn test_parse_deployment_status() {
+        let delivery = |state: &str, target_url: Option<&str>| {
+            json!({
+                "deployment_status": { "state": state, "target_url": target_url },
+                "deployment": {
+                    "sha": "9f8e7d6c5b4a",
+                    "environment": "production",
+                    "creator": { "login": "octocat" }
+                },
+                "repository": { "full_name": "acme/checkout" }
+            })
+            .to_string()
+        };
+
+        let deployment = parse_deployment_status(delivery("success", None).as_bytes())
+            .unwrap()
+            .unwrap();
+        assert_eq!(deployment.repo, "acme/checkout");
+        assert_eq!(deployment.environment, "production");
+        assert_eq!(
+            deployment.url,
+            "https://github.com/acme/checkout/commit/9f8e7d6c5b4a"
+        );
+        assert_eq!(deployment.creator.as_deref(), Some("octocat"));
+
+        let logged = parse_deployment_status(
+            delivery("success", Some("https://ci.acme.test/runs/7")).as_bytes(),
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(logged.url, "https://ci.acme.test/runs/7");
+
+        assert_eq!(
+            parse_deployment_status(delivery("failure", None).as_bytes()).unwrap(),
+            None
+        );
+        assert!(parse_deployment_status(b"{}").is_err());
+    }
+}
@@ -0,0 +1,131 @@
+use crate::error::{IncidentError, IncidentResult};
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::time::Duration;
+use tracing::{debug, error, info};
+
+/// Jira Cloud REST client (v3 API, basic auth with an API token).
+#[derive(Clone)]
+pub struct JiraClient {
+    http_client: Client,
+    base_url: String,
+    email: String,
+    api_token: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct CreatedIssue {
+    key: String,
+}
+
+impl JiraClient {
+    pub fn new(base_url: String, email: String, api_token: String) -> Self {
+        // Set 30-second timeout to prevent hanging requests to Jira API
+        let http_client = Client::builder()
+            .timeout(Duration::from_secs(30))
+            .build()
+            .expect("Failed to build HTTP client");
+
+        Self {
+            http_client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            email,
+            api_token,
+        }
+    }
+
+    /// Create a Task in `project_key`. Returns the issue key (e.g. `OPS-123`).
+    pub async fn create_issue(
+        &self,
+        project_key: &str,
+        summary: &str,
+        description: &str,
+    ) -> IncidentResult<String> {
+        debug!("Creating Jira issue in project {}", project_key);
+
+        let response = self
+            .http_client
+            .post(format!("{}/rest/api/3/issue", self.base_url))
+            .basic_auth(&self.email, Some(&self.api_token))
+            .json(&Self::issue_request(project_key, summary, description))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let status_code = response.status();
+            let error_text = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            error!("Jira API error ({}): {}", status_code, error_text);
+            return Err(IncidentError::ExternalAPIError {
+                service: "Jira".to_string(),
+                message: format!("HTTP {}: {}", status_code, error_text),
+            });
+        }
+
+        let issue: CreatedIssue =
+            response
+                .json()
+                .await
+                .map_err(|e| IncidentError::ExternalAPIError {
+                    service: "Jira".to_string(),
+                    message: format!("Invalid create issue response: {}", e),
+                })?;
+
+        info!("Created Jira issue {}", issue.key);
+        Ok(issue.key)
+    }
+
+    /// Browser URL for an issue key.
+    pub fn issue_url(&self, issue_key: &str) -> String {
+        format!("{}/browse/{}", self.base_url, issue_key)
+    }
+
+    /// `POST /rest/api/3/issue` body. v3 descriptions are Atlassian Document
+    /// Format, so the plain-text description becomes a single paragraph.
+    fn issue_request(project_key: &str, summary: &str, description: &str) -> Value {
+        json!({
+            "fields": {
+                "project": { "key": project_key },
+                "issuetype": { "name": "Task" },
+                "summary": summary,
+                "description": {
+                    "type": "doc",
+                    "version": 1,
+                    "content": [{
+                        "type": "paragraph",
+                        "content": [{ "type": "text", "text": description }]
+                    }]
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_issue_request_uses_adf_description() {
+        let body = JiraClient::issue_request("OPS", "Fix failover", "From incident X");
+        assert_eq!(body["fields"]["project"]["key"], "OPS");
+        assert_eq!(body["fields"]["summary"], "Fix failover");
+        assert_eq!(
+            body["fields"]["description"]["content"][0]["content"][0]["text"],
+            "From incident X"
+        );
+
+        let client = JiraClient::new(
+            "https://example.atlassian.net/".to_string(),
+            "bot@example.com".to_string(),
+            "token".to_string(),
+        );
+        assert_eq!(
+            client.issue_url("OPS-7"),
+            "https://example.atlassian.net/browse/OPS-7"
+        );
+    }
+}
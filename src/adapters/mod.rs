@@ -1 +1,7 @@
+pub mod alert_sources;
+pub mod conference;
+pub mod confluence;
+pub mod github;
+pub mod jira;
+pub mod oncall;
 pub mod statuspage;
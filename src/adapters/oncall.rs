@@ -0,0 +1,173 @@
+use crate::config::{AppConfig, OnCallProvider};
+use crate::error::{IncidentError, IncidentResult};
+use reqwest::Client;
+use serde::Deserialize;
+use std::time::Duration;
+use tracing::{debug, error};
+
+pub const PAGERDUTY_API_URL: &str = "https://api.pagerduty.com";
+pub const OPSGENIE_API_URL: &str = "https://api.opsgenie.com";
+
+/// Reads who is currently on call from PagerDuty schedules or Opsgenie
+/// rotations. People are identified by email, which is how they are matched
+/// to Slack users.
+#[derive(Clone)]
+pub struct OnCallClient {
+    http_client: Client,
+    provider: OnCallProvider,
+    api_token: String,
+    api_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct PagerDutyOnCalls {
+    oncalls: Vec<PagerDutyOnCall>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PagerDutyOnCall {
+    escalation_level: u32,
+    user: PagerDutyUser,
+}
+
+#[derive(Debug, Deserialize)]
+struct PagerDutyUser {
+    #[serde(default)]
+    email: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpsgenieResponse {
+    data: OpsgenieOnCalls,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OpsgenieOnCalls {
+    #[serde(default)]
+    on_call_recipients: Vec<String>,
+}
+
+impl OnCallClient {
+    pub fn new(provider: OnCallProvider, api_token: String) -> Self {
+        // Set 30-second timeout to prevent hanging requests to the on-call API
+        let http_client = Client::builder()
+            .timeout(Duration::from_secs(30))
+            .build()
+            .expect("Failed to build HTTP client");
+        let api_url = match provider {
+            OnCallProvider::PagerDuty => PAGERDUTY_API_URL,
+            OnCallProvider::Opsgenie => OPSGENIE_API_URL,
+        };
+
+        Self {
+            http_client,
+            provider,
+            api_token,
+            api_url: api_url.to_string(),
+        }
+    }
+
+    /// Point the client at another API endpoint (Opsgenie EU, proxies, tests).
+    pub fn with_api_url(mut self, api_url: String) -> Self {
+        self.api_url = api_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// The configured client, or `None` when on-call lookups are disabled.
+    pub fn from_config(config: &AppConfig) -> Option<Self> {
+        let (provider, api_token) = config.oncall_credentials()?;
+        let client = Self::new(provider, api_token.to_string());
+        Some(match config.oncall_api_url.as_deref() {
+            Some(url) if !url.trim().is_empty() => client.with_api_url(url.to_string()),
+            _ => client,
+        })
+    }
+
+    pub fn provider(&self) -> OnCallProvider {
+        self.provider
+    }
+
+    /// Email of whoever is on call for `schedule_id` right now, or `None`
+    /// when nobody is.
+    pub async fn current_on_call(&self, schedule_id: &str) -> IncidentResult<Option<String>> {
+        debug!(
+            "Looking up {} on-call for schedule {}",
+            self.provider.label(),
+            schedule_id
+        );
+
+        match self.provider {
+            OnCallProvider::PagerDuty => {
+                let response = self
+                    .http_client
+                    .get(format!("{}/oncalls", self.api_url))
+                    .header("Authorization", format!("Token token={}", self.api_token))
+                    .header("Accept", "application/vnd.pagerduty+json;version=2")
+                    .query(&[
+                        ("schedule_ids[]", schedule_id),
+                        ("include[]", "users"),
+                        ("earliest", "true"),
+                    ])
+                    .send()
+                    .await?;
+                let oncalls = self.parse::<PagerDutyOnCalls>(response).await?.oncalls;
+                // Several escalation levels can share a schedule; the first
+                // level is the one who gets paged
+                Ok(oncalls
+                    .into_iter()
+                    .min_by_key(|oncall| oncall.escalation_level)
+                    .and_then(|oncall| oncall.user.email))
+            }
+            OnCallProvider::Opsgenie => {
+                let response = self
+                    .http_client
+                    .get(format!(
+                        "{}/v2/schedules/{}/on-calls",
+                        self.api_url, schedule_id
+                    ))
+                    .header("Authorization", format!("GenieKey {}", self.api_token))
+                    .query(&[("flat", "true")])
+                    .send()
+                    .await?;
+                let recipients = self
+                    .parse::<OpsgenieResponse>(response)
+                    .await?
+                    .data
+                    .on_call_recipients;
+                Ok(recipients.into_iter().next())
+            }
+        }
+    }
+
+    async fn parse<T: for<'de> Deserialize<'de>>(
+        &self,
+        response: reqwest::Response,
+    ) -> IncidentResult<T> {
+        if !response.status().is_success() {
+            let status_code = response.status();
+            let error_text = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            error!(
+                "{} API error ({}): {}",
+                self.provider.label(),
+                status_code,
+                error_text
+            );
+            return Err(IncidentError::ExternalAPIError {
+                service: self.provider.label().to_string(),
+                message: format!("HTTP {}: {}", status_code, error_text),
+            });
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|e| IncidentError::ExternalAPIError {
+                service: self.provider.label().to_string(),
+                message: format!("Invalid response: {}", e),
+            })
+    }
+}
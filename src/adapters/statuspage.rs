@@ -1,15 +1,79 @@
 use crate::db::models::{IncidentStatus, Severity};
 use crate::error::{IncidentError, IncidentResult};
-use reqwest::Client;
-use serde::Serialize;
-use std::time::Duration;
-use tracing::{debug, error, info};
+use crate::slack::client::RetryPolicy;
+use reqwest::{Client, Method, RequestBuilder, Response, StatusCode};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{debug, error, info, warn};
+
+pub const STATUSPAGE_API_BASE_URL: &str = "https://api.statuspage.io/v1";
+
+/// Prefix of error messages for calls that failed because Statuspage was
+/// unreachable or unhealthy, as opposed to rejecting the request.
+const UNAVAILABLE: &str = "unavailable";
 
 #[derive(Clone)]
 pub struct StatuspageClient {
     http_client: Client,
     api_key: String,
     page_id: String,
+    base_url: String,
+    retry_policy: RetryPolicy,
+    request_timeout: Duration,
+    circuit_breaker: Arc<CircuitBreaker>,
+}
+
+/// Pauses Statuspage calls after `failure_threshold` calls in a row failed
+/// (each after its retries). Once `cooldown` has passed, calls are let through
+/// again: a success closes the circuit, another failure reopens it.
+pub struct CircuitBreaker {
+    failure_threshold: u32,
+    cooldown: Duration,
+    state: Mutex<BreakerState>,
+}
+
+#[derive(Default)]
+struct BreakerState {
+    consecutive_failures: u32,
+    open_until: Option<Instant>,
+}
+
+impl CircuitBreaker {
+    pub fn new(failure_threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            failure_threshold,
+            cooldown,
+            state: Mutex::new(BreakerState::default()),
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.state
+            .lock()
+            .unwrap()
+            .open_until
+            .is_some_and(|until| Instant::now() < until)
+    }
+
+    fn record_success(&self) {
+        *self.state.lock().unwrap() = BreakerState::default();
+    }
+
+    fn record_failure(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.consecutive_failures += 1;
+        if state.consecutive_failures >= self.failure_threshold {
+            if state.open_until.is_none_or(|until| Instant::now() >= until) {
+                warn!(
+                    "Statuspage circuit breaker open after {} failed calls; pausing for {:?}",
+                    state.consecutive_failures, self.cooldown
+                );
+            }
+            state.open_until = Some(Instant::now() + self.cooldown);
+        }
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -22,11 +86,25 @@ struct ComponentUpdate {
     status: String,
 }
 
+#[derive(Debug, Deserialize)]
+struct StatuspageIncident {
+    id: String,
+}
+
+/// Whether `error` came from Statuspage being unreachable or unhealthy
+/// (including an open circuit breaker), so the call is worth retrying later.
+pub fn is_unavailable(error: &IncidentError) -> bool {
+    matches!(
+        error,
+        IncidentError::ExternalAPIError { service, message }
+            if service == "Statuspage" && message.starts_with(UNAVAILABLE)
+    )
+}
+
 impl StatuspageClient {
     pub fn new(api_key: String, page_id: String) -> Self {
-        // Set 30-second timeout to prevent hanging requests to Statuspage API
+        // Timeouts are set per request (`with_request_timeout`)
         let http_client = Client::builder()
-            .timeout(Duration::from_secs(30))
             .build()
             .expect("Failed to build HTTP client");
 
@@ -34,9 +112,41 @@ impl StatuspageClient {
             http_client,
             api_key,
             page_id,
+            base_url: STATUSPAGE_API_BASE_URL.to_string(),
+            retry_policy: RetryPolicy::default(),
+            request_timeout: Duration::from_secs(10),
+            circuit_breaker: Arc::new(CircuitBreaker::new(5, Duration::from_secs(300))),
         }
     }
 
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Timeout for each attempt, so a hung request is retried rather than
+    /// holding the job.
+    pub fn with_request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = timeout;
+        self
+    }
+
+    pub fn with_circuit_breaker(mut self, failure_threshold: u32, cooldown: Duration) -> Self {
+        self.circuit_breaker = Arc::new(CircuitBreaker::new(failure_threshold, cooldown));
+        self
+    }
+
+    /// Point the client at another API base URL (proxies, tests).
+    pub fn with_base_url(mut self, base_url: String) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Whether calls are currently paused after repeated failures.
+    pub fn is_circuit_open(&self) -> bool {
+        self.circuit_breaker.is_open()
+    }
+
     /// Update component status on Statuspage
     pub async fn update_component_status(
         &self,
@@ -52,8 +162,8 @@ impl StatuspageClient {
         );
 
         let url = format!(
-            "https://api.statuspage.io/v1/pages/{}/components/{}",
-            self.page_id, component_id
+            "{}/pages/{}/components/{}",
+            self.base_url, self.page_id, component_id
         );
 
         let request = ComponentUpdateRequest {
@@ -62,41 +172,212 @@ impl StatuspageClient {
             },
         };
 
+        self.send(|| self.http_client.patch(&url).json(&request))
+            .await?;
+
+        info!(
+            "Successfully updated Statuspage component {} to {}",
+            component_id, statuspage_status
+        );
+
+        Ok(())
+    }
+
+    /// Open a Statuspage incident named `name`, linked to `component_id` when
+    /// the service has one. Returns the Statuspage incident ID.
+    pub async fn create_incident(
+        &self,
+        name: &str,
+        status: IncidentStatus,
+        severity: Severity,
+        body: &str,
+        component_id: Option<&str>,
+    ) -> IncidentResult<String> {
+        let url = format!("{}/pages/{}/incidents", self.base_url, self.page_id);
+        let payload = Self::create_payload(name, status, severity, body, component_id);
+        let incident = self.send_incident(Method::POST, &url, &payload).await?;
+
+        info!("Opened Statuspage incident {}", incident.id);
+        Ok(incident.id)
+    }
+
+    /// Add an update to an open Statuspage incident.
+    pub async fn update_incident(
+        &self,
+        statuspage_incident_id: &str,
+        status: IncidentStatus,
+        body: &str,
+    ) -> IncidentResult<()> {
+        let url = format!(
+            "{}/pages/{}/incidents/{}",
+            self.base_url, self.page_id, statuspage_incident_id
+        );
+        let payload = Self::update_payload(status, body);
+        self.send_incident(Method::PATCH, &url, &payload).await?;
+
+        info!(
+            "Posted update to Statuspage incident {}",
+            statuspage_incident_id
+        );
+        Ok(())
+    }
+
+    /// Mark a Statuspage incident resolved with a final update.
+    pub async fn resolve_incident(
+        &self,
+        statuspage_incident_id: &str,
+        body: &str,
+    ) -> IncidentResult<()> {
+        self.update_incident(statuspage_incident_id, IncidentStatus::Resolved, body)
+            .await
+    }
+
+    async fn send_incident(
+        &self,
+        method: Method,
+        url: &str,
+        payload: &Value,
+    ) -> IncidentResult<StatuspageIncident> {
         let response = self
-            .http_client
-            .patch(&url)
-            .header("Authorization", format!("OAuth {}", self.api_key))
-            .header("Content-Type", "application/json")
-            .json(&request)
-            .send()
+            .send(|| self.http_client.request(method.clone(), url).json(payload))
             .await?;
+        Ok(response.json().await?)
+    }
 
-        if !response.status().is_success() {
-            let status_code = response.status();
-            let error_text = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "Unknown error".to_string());
-            error!("Statuspage API error ({}): {}", status_code, error_text);
+    /// Send the request built by `build`, retrying timeouts, network errors,
+    /// 429 and 5xx responses with jittered backoff. Other 4xx responses fail
+    /// immediately. While the circuit breaker is open, calls fail without
+    /// reaching Statuspage.
+    async fn send(&self, build: impl Fn() -> RequestBuilder) -> IncidentResult<Response> {
+        if self.circuit_breaker.is_open() {
             return Err(IncidentError::ExternalAPIError {
                 service: "Statuspage".to_string(),
-                message: format!("HTTP {}: {}", status_code, error_text),
+                message: format!(
+                    "{}: paused after repeated failures (circuit breaker open)",
+                    UNAVAILABLE
+                ),
             });
         }
 
-        info!(
-            "Successfully updated Statuspage component {} to {}",
-            component_id, statuspage_status
-        );
+        let mut attempt = 0;
+        loop {
+            let response = build()
+                .header("Authorization", format!("OAuth {}", self.api_key))
+                .header("Content-Type", "application/json")
+                .timeout(self.request_timeout)
+                .send()
+                .await;
 
-        Ok(())
+            let (retryable, message) = match response {
+                Ok(response) if response.status().is_success() => {
+                    self.circuit_breaker.record_success();
+                    return Ok(response);
+                }
+                Ok(response) => {
+                    let status_code = response.status();
+                    let error_text = response
+                        .text()
+                        .await
+                        .unwrap_or_else(|_| "Unknown error".to_string());
+                    (
+                        status_code == StatusCode::TOO_MANY_REQUESTS
+                            || status_code.is_server_error(),
+                        format!("HTTP {}: {}", status_code, error_text),
+                    )
+                }
+                Err(e) => (true, e.to_string()),
+            };
+
+            if !retryable {
+                error!("Statuspage API error: {}", message);
+                return Err(IncidentError::ExternalAPIError {
+                    service: "Statuspage".to_string(),
+                    message,
+                });
+            }
+            if attempt >= self.retry_policy.max_retries {
+                error!(
+                    "Statuspage API failed after {} attempts: {}",
+                    attempt + 1,
+                    message
+                );
+                self.circuit_breaker.record_failure();
+                return Err(IncidentError::ExternalAPIError {
+                    service: "Statuspage".to_string(),
+                    message: format!("{} ({})", UNAVAILABLE, message),
+                });
+            }
+
+            let delay = self.retry_policy.jittered_backoff(attempt);
+            warn!(
+                "Statuspage API failed (attempt {}/{}), retrying in {:?}: {}",
+                attempt + 1,
+                self.retry_policy.max_retries + 1,
+                delay,
+                message
+            );
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    /// Body for opening an incident. Impact is set from severity rather than
+    /// left for Statuspage to infer from component statuses.
+    fn create_payload(
+        name: &str,
+        status: IncidentStatus,
+        severity: Severity,
+        body: &str,
+        component_id: Option<&str>,
+    ) -> Value {
+        json!({
+            "incident": {
+                "name": name,
+                "status": Self::map_incident_status(status),
+                "impact_override": Self::map_impact(severity),
+                "body": body,
+                "component_ids": component_id.into_iter().collect::<Vec<_>>(),
+            }
+        })
+    }
+
+    /// Body for later updates; the name isn't sent so edits don't rename it.
+    fn update_payload(status: IncidentStatus, body: &str) -> Value {
+        json!({
+            "incident": {
+                "status": Self::map_incident_status(status),
+                "body": body,
+            }
+        })
+    }
+
+    /// Map severity to a Statuspage incident impact
+    fn map_impact(severity: Severity) -> &'static str {
+        match severity {
+            Severity::P1 => "critical",
+            Severity::P2 => "major",
+            Severity::P3 | Severity::P4 => "minor",
+        }
+    }
+
+    /// Map incident status to a Statuspage incident status
+    /// https://developer.statuspage.io/#operation/postPagesPageIdIncidents
+    fn map_incident_status(status: IncidentStatus) -> &'static str {
+        match status {
+            IncidentStatus::Triaging | IncidentStatus::Declared | IncidentStatus::Investigating => {
+                "investigating"
+            }
+            IncidentStatus::Identified => "identified",
+            IncidentStatus::Monitoring => "monitoring",
+            IncidentStatus::Resolved | IncidentStatus::Canceled => "resolved",
+        }
     }
 
     /// Map incident status + severity to Statuspage component status
     /// https://developer.statuspage.io/#operation/patchPagesPageIdComponentsComponentId
     fn map_status(status: IncidentStatus, severity: Severity) -> &'static str {
         match status {
-            IncidentStatus::Declared | IncidentStatus::Investigating => {
+            IncidentStatus::Triaging | IncidentStatus::Declared | IncidentStatus::Investigating => {
                 // Map severity to impact level
                 match severity {
                     Severity::P1 => "major_outage",         // Critical impact
@@ -113,18 +394,19 @@ impl StatuspageClient {
                     Severity::P3 | Severity::P4 => "degraded_performance",
                 }
             }
-            IncidentStatus::Resolved => "operational", // Back to normal
+            IncidentStatus::Resolved | IncidentStatus::Canceled => "operational", // Back to normal
         }
     }
 
     /// Test connectivity to Statuspage API
     pub async fn test_connection(&self) -> IncidentResult<()> {
-        let url = format!("https://api.statuspage.io/v1/pages/{}", self.page_id);
+        let url = format!("{}/pages/{}", self.base_url, self.page_id);
 
         let response = self
             .http_client
             .get(&url)
             .header("Authorization", format!("OAuth {}", self.api_key))
+            .timeout(self.request_timeout)
             .send()
             .await?;
 
@@ -211,4 +493,33 @@ mod tests {
             "operational"
         );
     }
+
+    #[test]
+    fn test_incident_payloads() {
+        let created = StatuspageClient::create_payload(
+            "Checkout errors",
+            IncidentStatus::Declared,
+            Severity::P2,
+            "We are investigating elevated errors.",
+            Some("cmp_checkout"),
+        );
+        assert_eq!(created["incident"]["name"], "Checkout errors");
+        assert_eq!(created["incident"]["status"], "investigating");
+        assert_eq!(created["incident"]["impact_override"], "major");
+        assert_eq!(
+            created["incident"]["component_ids"],
+            json!(["cmp_checkout"])
+        );
+        assert_eq!(
+            created["incident"]["body"],
+            "We are investigating elevated errors."
+        );
+
+        let updated = StatuspageClient::update_payload(
+            IncidentStatus::Monitoring,
+            "A fix has been deployed.",
+        );
+        assert!(updated["incident"].get("name").is_none());
+        assert_eq!(updated["incident"]["status"], "monitoring");
+    }
 }
@@ -0,0 +1,297 @@
+use crate::app_state::AppState;
+use crate::db::config_bundle::{self, ConfigBundle, ImportReport};
+use crate::db::models::{FailedJob, NotificationRecord};
+use crate::db::queries::audit as audit_queries;
+use crate::db::queries::failed_jobs;
+use crate::db::queries::notifications as notification_queries;
+use crate::db::queries::webhooks as webhook_queries;
+use crate::error::IncidentResult;
+use crate::services::audit::AuditService;
+use crate::services::audit_chain;
+use crate::services::dead_letters::DeadLetterService;
+use crate::services::notification::NotificationService;
+use crate::services::reconstruction::{
+    self, ChannelMessage, Reconstruction, ReconstructionRequest,
+};
+use crate::services::residency::{self, ExternalService, ResidencyReport};
+use axum::extract::{Path, Query, State};
+use axum::http::header;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde::Deserialize;
+use serde_json::json;
+use tracing::info;
+use uuid::Uuid;
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ImportQuery {
+    /// Report what would change without writing anything
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct HistoryQuery {
+    /// Slack ts bounds, inclusive
+    pub oldest: Option<String>,
+    pub latest: Option<String>,
+    #[serde(default = "default_history_limit")]
+    pub limit: usize,
+}
+
+fn default_history_limit() -> usize {
+    200
+}
+
+/// Longest look-back and page size for the pending-notification listing.
+const MAX_PENDING_HOURS: i64 = 168;
+const MAX_PENDING_LIMIT: i64 = 200;
+
+#[derive(Debug, Deserialize)]
+pub struct PendingNotificationsQuery {
+    #[serde(default = "default_pending_hours")]
+    pub hours: i64,
+    #[serde(default = "default_pending_limit")]
+    pub limit: i64,
+}
+
+fn default_pending_hours() -> i64 {
+    24
+}
+
+fn default_pending_limit() -> i64 {
+    50
+}
+
+/// Page size cap for the dead-letter listing.
+const MAX_DEAD_LETTER_LIMIT: i64 = 200;
+
+#[derive(Debug, Deserialize)]
+pub struct DeadLettersQuery {
+    #[serde(default = "default_pending_limit")]
+    pub limit: i64,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct AuditExportQuery {
+    /// Only rows after this `seq`, to continue from an earlier export
+    #[serde(default)]
+    pub after_seq: i64,
+}
+
+/// `GET /api/v1/admin/audit/export?after_seq=N` — the audit log as a
+/// hash-chained CSV, checkable with `incident-bot verify-audit <file>`.
+pub async fn export_audit_log(
+    State(state): State<AppState>,
+    Query(query): Query<AuditExportQuery>,
+) -> IncidentResult<Response> {
+    let entries = audit_queries::list_entries(&state.pool, query.after_seq.max(0)).await?;
+    let csv = audit_chain::to_csv(&entries);
+
+    AuditService::new(state.pool.clone())
+        .log_action(
+            None,
+            "audit_exported".to_string(),
+            "api".to_string(),
+            None,
+            None,
+            Some(json!({
+                "after_seq": query.after_seq,
+                "rows": entries.len(),
+                "head": entries.last().and_then(|entry| entry.row_hash.clone()),
+            })),
+        )
+        .await?;
+    info!("Exported {} audit log rows via API", entries.len());
+
+    let filename = format!(
+        "attachment; filename=\"audit-log-{}.csv\"",
+        chrono::Utc::now().format("%Y%m%d%H%M%S")
+    );
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+            (header::CONTENT_DISPOSITION, filename),
+        ],
+        csv,
+    )
+        .into_response())
+}
+
+/// `GET /api/v1/admin/config/export` — versioned configuration bundle.
+pub async fn export_config(State(state): State<AppState>) -> IncidentResult<Json<ConfigBundle>> {
+    let bundle = config_bundle::export_bundle(&state.pool, &state.config).await?;
+    info!("Exported config bundle via API");
+    Ok(Json(bundle))
+}
+
+/// `POST /api/v1/admin/config/import?dry_run=true` — diff a bundle against
+/// this environment and, unless `dry_run`, apply its templates and
+/// Statuspage mappings.
+pub async fn import_config(
+    State(state): State<AppState>,
+    Query(query): Query<ImportQuery>,
+    Json(bundle): Json<ConfigBundle>,
+) -> IncidentResult<Json<ImportReport>> {
+    let report =
+        config_bundle::import_bundle(&state.pool, &state.config, &bundle, query.dry_run).await?;
+
+    if !report.dry_run {
+        AuditService::new(state.pool.clone())
+            .log_action(
+                None,
+                "config_import".to_string(),
+                "api".to_string(),
+                None,
+                None,
+                Some(json!({
+                    "exported_at": bundle.exported_at,
+                    "changes": report.changes.len(),
+                    "environment_drift": report.environment_drift,
+                })),
+            )
+            .await?;
+    }
+
+    info!(
+        "Imported config bundle via API (dry_run: {}): {} changes, drift in {:?}",
+        report.dry_run,
+        report.changes.len(),
+        report.environment_drift
+    );
+    Ok(Json(report))
+}
+
+/// `GET /api/v1/admin/data-residency` — every external service that
+/// receives incident data, where it is hosted, and what it gets.
+pub async fn data_residency(
+    State(state): State<AppState>,
+) -> IncidentResult<Json<ResidencyReport>> {
+    let mut services = residency::audit(&state.config);
+    let webhooks = webhook_queries::list_webhooks(&state.pool).await?;
+    if let Some(row) = services
+        .iter_mut()
+        .find(|s| s.service == ExternalService::Webhooks.key())
+    {
+        row.endpoints = webhooks
+            .into_iter()
+            .filter(|w| w.is_active)
+            .map(|w| w.url)
+            .collect();
+        row.enabled = !row.endpoints.is_empty()
+            && residency::allows(&state.config, ExternalService::Webhooks);
+    }
+    Ok(Json(ResidencyReport::new(
+        state.config.data_residency,
+        services,
+    )))
+}
+
+/// `GET /api/v1/admin/channels/{channel_id}/history?oldest=&latest=&limit=` —
+/// channel messages with their timestamps, for picking the declare and
+/// resolve boundaries of a reconstruction.
+pub async fn channel_history(
+    State(state): State<AppState>,
+    Path(channel_id): Path<String>,
+    Query(query): Query<HistoryQuery>,
+) -> IncidentResult<Json<Vec<ChannelMessage>>> {
+    let limit = query.limit.clamp(1, reconstruction::MAX_MESSAGES);
+    let messages =
+        reconstruction::channel_history(&state, &channel_id, query.oldest, query.latest, limit)
+            .await?;
+    Ok(Json(messages))
+}
+
+/// `POST /api/v1/admin/incidents/reconstruct?dry_run=true` — rebuild a past,
+/// manually run incident and its timeline from its Slack channel.
+pub async fn reconstruct_incident(
+    State(state): State<AppState>,
+    Query(query): Query<ImportQuery>,
+    Json(request): Json<ReconstructionRequest>,
+) -> IncidentResult<Json<Reconstruction>> {
+    let reconstruction = reconstruction::reconstruct(&state, &request, query.dry_run).await?;
+    info!(
+        "Reconstructed incident from channel {} via API (dry_run: {}): {} events",
+        request.channel_id,
+        reconstruction.dry_run,
+        reconstruction.events.len()
+    );
+    Ok(Json(reconstruction))
+}
+
+/// `GET /api/v1/admin/notifications/pending-failed?hours=24&limit=50` —
+/// recent notifications that failed, were throttled or are still deferred.
+pub async fn pending_notifications(
+    State(state): State<AppState>,
+    Query(query): Query<PendingNotificationsQuery>,
+) -> IncidentResult<Json<Vec<NotificationRecord>>> {
+    let since =
+        chrono::Utc::now() - chrono::Duration::hours(query.hours.clamp(1, MAX_PENDING_HOURS));
+    let records = notification_queries::list_unsent(
+        &state.pool,
+        since,
+        query.limit.clamp(1, MAX_PENDING_LIMIT),
+    )
+    .await?;
+    Ok(Json(records))
+}
+
+/// `POST /api/v1/admin/notifications/{id}/retry` — resend it now.
+pub async fn retry_notification(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> IncidentResult<Json<NotificationRecord>> {
+    let record = notification_service(&state).retry(id, "api").await?;
+    Ok(Json(record))
+}
+
+/// `POST /api/v1/admin/notifications/{id}/skip` — give up on it.
+pub async fn skip_notification(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> IncidentResult<Json<NotificationRecord>> {
+    let record = notification_service(&state).skip(id, "api").await?;
+    Ok(Json(record))
+}
+
+/// `GET /api/v1/admin/jobs/dead-letters?limit=50` — background jobs that
+/// failed for good, most recent first.
+pub async fn dead_letters(
+    State(state): State<AppState>,
+    Query(query): Query<DeadLettersQuery>,
+) -> IncidentResult<Json<Vec<FailedJob>>> {
+    let jobs =
+        failed_jobs::list_dead_letters(&state.pool, query.limit.clamp(1, MAX_DEAD_LETTER_LIMIT))
+            .await?;
+    Ok(Json(jobs))
+}
+
+/// `POST /api/v1/admin/jobs/{id}/retry` — hand it back to the job worker.
+pub async fn retry_job(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> IncidentResult<Json<FailedJob>> {
+    let job = dead_letter_service(&state).retry(id, "api").await?;
+    Ok(Json(job))
+}
+
+/// `POST /api/v1/admin/jobs/{id}/discard` — drop it for good.
+pub async fn discard_job(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> IncidentResult<Json<FailedJob>> {
+    let job = dead_letter_service(&state).discard(id, "api").await?;
+    Ok(Json(job))
+}
+
+fn dead_letter_service(state: &AppState) -> DeadLetterService {
+    DeadLetterService::new(state.pool.clone(), state.job_sender.clone())
+}
+
+fn notification_service(state: &AppState) -> NotificationService {
+    NotificationService::new(
+        state.pool.clone(),
+        state.slack_client.clone(),
+        state.config.clone(),
+    )
+}
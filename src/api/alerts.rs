@@ -0,0 +1,102 @@
+use crate::adapters::alert_sources::{self, AlertSource, Alertmanager, Datadog};
+use crate::app_state::AppState;
+use crate::error::{IncidentError, IncidentResult};
+use crate::services::alerts::{self, AlertReport};
+use crate::services::auto_declare::{self, AutoDeclareReport};
+use axum::body::Bytes;
+use axum::extract::{Path, State};
+use axum::http::HeaderMap;
+use axum::routing::post;
+use axum::{Json, Router};
+use tracing::{info, warn};
+
+/// Routes served under `/integrations`. Each alert source authenticates with
+/// its own token from `ALERT_SOURCE_TOKENS` (GitHub with its signing secret),
+/// so these sit outside `/api/v1`.
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/alerts/{source}", post(receive_alerts))
+        .route("/alertmanager", post(receive_alertmanager))
+        .route("/datadog", post(receive_datadog))
+        .route("/github", post(crate::api::github::receive_github))
+}
+
+/// `POST /integrations/alerts/{source}` — alerts from a monitoring tool.
+pub async fn receive_alerts(
+    State(state): State<AppState>,
+    Path(source): Path<String>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> IncidentResult<Json<AlertReport>> {
+    // Unknown and unconfigured sources look the same from outside
+    let (source, secret) = alert_sources::find(&source)
+        .zip(state.config.alert_source_tokens.get(&source))
+        .ok_or(IncidentError::NotFound)?;
+
+    if !source.authenticate(&headers, secret) {
+        warn!("Rejected {} alert webhook: invalid token", source.label());
+        return Err(IncidentError::InvalidSignature);
+    }
+
+    if let Some(url) = source.subscription_confirmation(&body) {
+        reqwest::get(&url).await?.error_for_status()?;
+        info!("Confirmed {} alert subscription", source.label());
+        return Ok(Json(AlertReport::default()));
+    }
+
+    let alerts = source.parse(&body)?;
+    let report = alerts::record_alerts(&state, source, &alerts).await?;
+    Ok(Json(report))
+}
+
+/// `POST /integrations/alertmanager` — Alertmanager webhooks that declare and
+/// resolve incidents, rather than only noting alerts on open ones. Uses the
+/// `alertmanager` token from `ALERT_SOURCE_TOKENS`.
+pub async fn receive_alertmanager(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> IncidentResult<Json<AutoDeclareReport>> {
+    auto_declare_webhook(&state, &Alertmanager, &headers, &body).await
+}
+
+/// `POST /integrations/datadog` — Datadog monitor webhooks that declare,
+/// update and resolve incidents. Uses the `datadog` token from
+/// `ALERT_SOURCE_TOKENS`.
+pub async fn receive_datadog(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> IncidentResult<Json<AutoDeclareReport>> {
+    auto_declare_webhook(&state, &Datadog, &headers, &body).await
+}
+
+async fn auto_declare_webhook(
+    state: &AppState,
+    source: &dyn AlertSource,
+    headers: &HeaderMap,
+    body: &[u8],
+) -> IncidentResult<Json<AutoDeclareReport>> {
+    let secret = state
+        .config
+        .alert_source_tokens
+        .get(source.name())
+        .ok_or(IncidentError::NotFound)?;
+
+    if !source.authenticate(headers, secret) {
+        warn!("Rejected {} webhook: invalid token", source.label());
+        return Err(IncidentError::InvalidSignature);
+    }
+
+    let alerts = source.parse(body)?;
+    let report = auto_declare::process_alerts(state, source, &alerts).await?;
+    info!(
+        "{} webhook: {} declared, {} attached, {} updated, {} resolved",
+        source.label(),
+        report.declared,
+        report.attached,
+        report.updated,
+        report.resolved
+    );
+    Ok(Json(report))
+}
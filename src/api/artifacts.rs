@@ -0,0 +1,61 @@
+use crate::app_state::AppState;
+use crate::db::queries::artifacts;
+use crate::error::IncidentResult;
+use crate::services::artifact_store::{ArtifactLink, ArtifactService};
+use crate::services::incident::IncidentService;
+use axum::extract::{Path, Query, State};
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde::Deserialize;
+use serde_json::json;
+
+#[derive(Debug, Deserialize)]
+pub struct SignedQuery {
+    pub expires: i64,
+    pub signature: String,
+}
+
+/// `GET /api/v1/incidents/{id}/artifacts` — the incident's stored artifacts
+/// (channel transcripts, ...) with signed download links.
+pub async fn list_incident_artifacts(
+    State(state): State<AppState>,
+    Path(reference): Path<String>,
+) -> IncidentResult<Json<Vec<ArtifactLink>>> {
+    let incident = IncidentService::new(state.pool.clone())
+        .find(&reference)
+        .await?;
+    let links = ArtifactService::new(state.pool.clone(), state.artifact_store.clone())
+        .list_for_incident(incident.id, state.config.artifact_url_ttl())
+        .await?;
+    Ok(Json(links))
+}
+
+/// `GET /artifacts/{key}?expires=..&signature=..` — signed download link for
+/// the local artifact store. Not under `/api/v1`: the signature is the
+/// credential, so links can be handed to browsers and Slack.
+pub async fn download_artifact(
+    State(state): State<AppState>,
+    Path(key): Path<String>,
+    Query(query): Query<SignedQuery>,
+) -> IncidentResult<Response> {
+    let bytes = state
+        .artifact_store
+        .read_signed(&key, query.expires, &query.signature)
+        .await?;
+    let artifact = match bytes {
+        Some(_) => artifacts::get_by_storage_key(&state.pool, &key).await?,
+        None => None,
+    };
+
+    match (bytes, artifact) {
+        (Some(bytes), Some(artifact)) => {
+            Ok(([(header::CONTENT_TYPE, artifact.content_type)], bytes).into_response())
+        }
+        _ => Ok((
+            StatusCode::NOT_FOUND,
+            Json(json!({ "error": "Artifact not found or link expired" })),
+        )
+            .into_response()),
+    }
+}
@@ -0,0 +1,114 @@
+use crate::app_state::AppState;
+use crate::db::models::{AuditEntry, IncidentId};
+use crate::db::queries::audit::{self as audit_queries, AuditFilter};
+use crate::error::{IncidentError, IncidentResult};
+use axum::extract::{Query, State};
+use axum::Json;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+const DEFAULT_PAGE_LIMIT: i64 = 100;
+const MAX_PAGE_LIMIT: i64 = 1000;
+
+#[derive(Debug, Default, Deserialize)]
+pub struct AuditQuery {
+    pub incident_id: Option<IncidentId>,
+    pub actor_id: Option<String>,
+    pub action: Option<String>,
+    /// RFC 3339; `since` is inclusive, `until` exclusive
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+    /// `next_before_seq` of the previous page
+    pub before_seq: Option<i64>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AuditPage {
+    /// Newest first
+    pub entries: Vec<AuditEntry>,
+    /// Pass as `before_seq` for the next (older) page; `None` on the last page
+    pub next_before_seq: Option<i64>,
+}
+
+/// `GET /api/v1/audit?incident_id=&actor_id=&action=&since=&until=&before_seq=&limit=`
+/// — audit log rows, newest first, a page at a time.
+pub async fn list_audit_entries(
+    State(state): State<AppState>,
+    Query(query): Query<AuditQuery>,
+) -> IncidentResult<Json<AuditPage>> {
+    let filter = parse_audit_query(query)?;
+    let entries = audit_queries::search_entries(&state.pool, &filter).await?;
+    let next_before_seq = if entries.len() as i64 == filter.limit {
+        entries.last().map(|entry| entry.seq)
+    } else {
+        None
+    };
+
+    Ok(Json(AuditPage {
+        entries,
+        next_before_seq,
+    }))
+}
+
+fn parse_audit_query(query: AuditQuery) -> IncidentResult<AuditFilter> {
+    let limit = query.limit.unwrap_or(DEFAULT_PAGE_LIMIT);
+    if !(1..=MAX_PAGE_LIMIT).contains(&limit) {
+        return Err(IncidentError::ValidationError {
+            field: "limit".to_string(),
+            reason: format!("Must be between 1 and {}", MAX_PAGE_LIMIT),
+        });
+    }
+    if let (Some(since), Some(until)) = (query.since, query.until) {
+        if since >= until {
+            return Err(IncidentError::ValidationError {
+                field: "until".to_string(),
+                reason: "Must be after since".to_string(),
+            });
+        }
+    }
+    let non_empty = |value: Option<String>| value.filter(|v| !v.trim().is_empty());
+
+    Ok(AuditFilter {
+        incident_id: query.incident_id,
+        actor_id: non_empty(query.actor_id),
+        action: non_empty(query.action),
+        since: query.since,
+        until: query.until,
+        before_seq: query.before_seq,
+        limit,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_parse_audit_query() {
+        let filter = parse_audit_query(AuditQuery {
+            actor_id: Some("U_ADMIN".to_string()),
+            action: Some(" ".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(filter.actor_id.as_deref(), Some("U_ADMIN"));
+        assert_eq!(filter.action, None);
+        assert_eq!(filter.limit, DEFAULT_PAGE_LIMIT);
+
+        let too_many = AuditQuery {
+            limit: Some(MAX_PAGE_LIMIT + 1),
+            ..Default::default()
+        };
+        assert!(parse_audit_query(too_many).is_err());
+
+        let now = Utc::now();
+        let backwards = AuditQuery {
+            since: Some(now),
+            until: Some(now - Duration::hours(1)),
+            ..Default::default()
+        };
+        assert!(parse_audit_query(backwards).is_err());
+    }
+}
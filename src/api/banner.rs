@@ -0,0 +1,98 @@
+use super::constant_time_eq;
+use crate::app_state::AppState;
+use crate::db::models::IncidentStatus;
+use crate::db::queries::incidents;
+use crate::error::IncidentResult;
+use crate::utils::redact::redact;
+use axum::extract::{Query, State};
+use axum::http::{header, HeaderMap, HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+/// Header web apps may send `BANNER_API_KEY` in instead of `?key=`.
+pub const KEY_HEADER: &str = "x-banner-key";
+
+/// How long browsers and CDNs may cache the banner.
+const CACHE_CONTROL: &str = "public, max-age=30";
+
+#[derive(Debug, Default, Deserialize)]
+pub struct BannerQuery {
+    pub key: Option<String>,
+}
+
+/// What an in-app banner shows about one incident.
+#[derive(Debug, Serialize)]
+pub struct BannerIncident {
+    /// Title with names, emails and internal hosts redacted
+    pub title: String,
+    pub status: IncidentStatus,
+    pub started_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Banner {
+    /// Whether to show the banner
+    pub active: bool,
+    /// Customer-impacting incidents, oldest first
+    pub incidents: Vec<BannerIncident>,
+}
+
+/// `GET /api/v1/banner` — ongoing incidents with a public status update, for
+/// web apps to poll and show a status banner. Needs no API token; when
+/// `BANNER_API_KEY` is set it must be passed as `?key=` or `X-Banner-Key`.
+/// Any origin may read it.
+pub async fn get_banner(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<BannerQuery>,
+) -> IncidentResult<Response> {
+    if let Some(expected) = state
+        .config
+        .banner_api_key
+        .as_deref()
+        .filter(|k| !k.is_empty())
+    {
+        let provided = query
+            .key
+            .as_deref()
+            .or_else(|| headers.get(KEY_HEADER).and_then(|v| v.to_str().ok()))
+            .unwrap_or("");
+        if !constant_time_eq(provided, expected) {
+            return Ok((
+                StatusCode::UNAUTHORIZED,
+                Json(json!({ "error": "Invalid or missing banner key" })),
+            )
+                .into_response());
+        }
+    }
+
+    let domains = &state.config.partner_redact_domains;
+    let incidents: Vec<BannerIncident> = incidents::list_public_incidents(&state.pool)
+        .await?
+        .into_iter()
+        .map(|incident| BannerIncident {
+            title: redact(&incident.title, domains),
+            status: incident.status,
+            started_at: incident.declared_at,
+        })
+        .collect();
+    let banner = Banner {
+        active: !incidents.is_empty(),
+        incidents,
+    };
+
+    let mut response = Json(banner).into_response();
+    let response_headers = response.headers_mut();
+    response_headers.insert(
+        header::ACCESS_CONTROL_ALLOW_ORIGIN,
+        HeaderValue::from_static("*"),
+    );
+    response_headers.insert(
+        header::CACHE_CONTROL,
+        HeaderValue::from_static(CACHE_CONTROL),
+    );
+    Ok(response)
+}
@@ -0,0 +1,47 @@
+use crate::adapters::github;
+use crate::app_state::AppState;
+use crate::error::{IncidentError, IncidentResult};
+use crate::services::links::{self, DeployReport};
+use axum::body::Bytes;
+use axum::extract::State;
+use axum::http::HeaderMap;
+use axum::Json;
+use tracing::{info, warn};
+
+/// `POST /integrations/github` — GitHub webhook deliveries, signed with
+/// `GITHUB_WEBHOOK_SECRET`. Successful `deployment_status` events are noted
+/// on the open incident for the repository's service; other events (including
+/// the `ping` sent when the webhook is created) are acknowledged and ignored.
+pub async fn receive_github(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> IncidentResult<Json<DeployReport>> {
+    // Without a secret the route looks the same as an unknown one
+    let secret = state
+        .config
+        .github_webhook_secret
+        .as_deref()
+        .filter(|s| !s.is_empty())
+        .ok_or(IncidentError::NotFound)?;
+
+    if !github::verify_signature(&headers, &body, secret) {
+        warn!("Rejected GitHub webhook: invalid signature");
+        return Err(IncidentError::InvalidSignature);
+    }
+
+    let event = headers
+        .get(github::EVENT_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("");
+    if event != "deployment_status" {
+        info!("Ignoring GitHub {} event", event);
+        return Ok(Json(DeployReport::default()));
+    }
+
+    let report = match github::parse_deployment_status(&body)? {
+        Some(deployment) => links::record_deploy(&state, &deployment).await?,
+        None => DeployReport::default(),
+    };
+    Ok(Json(report))
+}
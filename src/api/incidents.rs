@@ -0,0 +1,395 @@
+use crate::app_state::AppState;
+use crate::db::models::{Incident, IncidentStatus, SeverityLevel, WebhookEvent};
+use crate::db::queries::incidents::IncidentFilter;
+use crate::error::{IncidentError, IncidentResult};
+use crate::services::incident::IncidentService;
+use crate::services::notification::NotificationService;
+use crate::services::permissions::FieldVisibility;
+use crate::services::roles::{RoleService, RoleStatus};
+use crate::services::webhook;
+use crate::slack::blocks;
+use crate::utils::severity;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::Json;
+use serde::Deserialize;
+use serde_json::json;
+use tracing::{error, info};
+
+const DEFAULT_LIST_LIMIT: i64 = 50;
+const MAX_LIST_LIMIT: i64 = 200;
+/// Actor recorded in the timeline/audit log when a request omits `actor_id`.
+const DEFAULT_API_ACTOR: &str = "api";
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ListIncidentsQuery {
+    pub status: Option<String>,
+    pub severity: Option<String>,
+    #[serde(default)]
+    pub open: bool,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateIncidentRequest {
+    pub title: String,
+    /// A configured severity code, or a tier (`P1`..`P4`)
+    pub severity: String,
+    pub affected_service: String,
+    pub commander_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateStatusRequest {
+    pub status: IncidentStatus,
+    pub actor_id: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ResolveRequest {
+    pub actor_id: Option<String>,
+}
+
+/// `GET /api/v1/incidents`
+pub async fn list_incidents(
+    State(state): State<AppState>,
+    Query(query): Query<ListIncidentsQuery>,
+) -> IncidentResult<Json<Vec<Incident>>> {
+    let filter = parse_list_query(&query)?;
+    let incidents = IncidentService::new(state.pool.clone())
+        .list(&filter)
+        .await?;
+    let visibility = FieldVisibility::api(&state.config);
+    Ok(Json(
+        incidents
+            .into_iter()
+            .map(|incident| visibility.redact_incident(incident))
+            .collect(),
+    ))
+}
+
+/// `GET /api/v1/incidents/{id}` — `id` may also be the incident's number
+/// (`PLAT-12`), as on every `/incidents/{id}` route.
+pub async fn get_incident(
+    State(state): State<AppState>,
+    Path(reference): Path<String>,
+) -> IncidentResult<Json<Incident>> {
+    let incident = IncidentService::new(state.pool.clone())
+        .find(&reference)
+        .await?;
+    Ok(api_json(&state, incident))
+}
+
+/// `GET /api/v1/incidents/{id}/roles` — required roles per the severity matrix,
+/// who claimed them, and which are still unfilled.
+pub async fn get_roles(
+    State(state): State<AppState>,
+    Path(reference): Path<String>,
+) -> IncidentResult<Json<RoleStatus>> {
+    let incident = IncidentService::new(state.pool.clone())
+        .find(&reference)
+        .await?;
+    let required = state.config.required_roles_for(incident.severity);
+    let status = RoleService::new(state.pool.clone())
+        .status(&incident, &required)
+        .await?;
+    Ok(Json(status))
+}
+
+/// `POST /api/v1/incidents`
+///
+/// Creates the incident record and sends severity-based notifications. Unlike
+/// `/incident declare`, no dedicated Slack channel is created.
+pub async fn create_incident(
+    State(state): State<AppState>,
+    Json(request): Json<CreateIncidentRequest>,
+) -> IncidentResult<(StatusCode, Json<Incident>)> {
+    let level = validate_create_request(&request, &state.config.services)?;
+    if !state
+        .service_catalog
+        .is_enabled(&state.pool, &request.affected_service)
+        .await
+    {
+        return Err(IncidentError::ValidationError {
+            field: "affected_service".to_string(),
+            reason: format!(
+                "'{}' is disabled for new incidents",
+                request.affected_service
+            ),
+        });
+    }
+
+    let incident = IncidentService::new(state.pool.clone())
+        .with_config(state.config.clone())
+        .create_incident(
+            request.title.trim().to_string(),
+            level,
+            request.affected_service,
+            request.commander_id,
+        )
+        .await?;
+
+    let notification_service = NotificationService::new(
+        state.pool.clone(),
+        state.slack_client.clone(),
+        state.config.clone(),
+    );
+    if let Err(e) = notification_service
+        .notify_incident_declared(
+            &incident,
+            blocks::incident_declared_blocks(&incident, &FieldVisibility::shared(&state.config)),
+        )
+        .await
+    {
+        error!("Failed to send notifications: {}", e);
+    }
+
+    crate::jobs::statuspage_sync::enqueue_for_incident(&state.pool, &state.job_sender, &incident)
+        .await;
+    crate::jobs::statuspage_sync::enqueue_incident_create(
+        &state.pool,
+        &state.job_sender,
+        &incident,
+    )
+    .await;
+    webhook::enqueue(&state, WebhookEvent::IncidentDeclared, &incident, None).await;
+    crate::jobs::channel_status::enqueue(&state, &incident);
+    crate::commands::premortem::link_declared(&state, &incident).await;
+
+    info!("Incident {} created via API", incident.id);
+    Ok((StatusCode::CREATED, api_json(&state, incident)))
+}
+
+/// `POST /api/v1/incidents/{id}/status`
+pub async fn update_status(
+    State(state): State<AppState>,
+    Path(reference): Path<String>,
+    Json(request): Json<UpdateStatusRequest>,
+) -> IncidentResult<Json<Incident>> {
+    let actor = actor_or_default(request.actor_id);
+    let incident_service = IncidentService::new(state.pool.clone());
+    let previous = incident_service.find(&reference).await?;
+    let incident = incident_service
+        .transition_status(previous.id, request.status, actor.clone())
+        .await?;
+
+    announce_status(&state, &incident, previous.status, &actor).await;
+    Ok(api_json(&state, incident))
+}
+
+/// `POST /api/v1/incidents/{id}/resolve` — idempotent, like `/incident resolved`.
+pub async fn resolve_incident(
+    State(state): State<AppState>,
+    Path(reference): Path<String>,
+    request: Option<Json<ResolveRequest>>,
+) -> IncidentResult<Json<Incident>> {
+    let actor = actor_or_default(request.and_then(|Json(r)| r.actor_id));
+    let incident_service = IncidentService::new(state.pool.clone());
+
+    let incident = incident_service.find(&reference).await?;
+    if incident.status.is_terminal() {
+        return Ok(api_json(&state, incident));
+    }
+
+    let resolved = incident_service
+        .transition_status(incident.id, IncidentStatus::Resolved, actor.clone())
+        .await?;
+
+    announce_status(&state, &resolved, incident.status, &actor).await;
+    Ok(api_json(&state, resolved))
+}
+
+/// Every incident the API returns goes through here, so restricted custom
+/// fields are only included for the `api` role.
+fn api_json(state: &AppState, incident: Incident) -> Json<Incident> {
+    Json(FieldVisibility::api(&state.config).redact_incident(incident))
+}
+
+/// Best-effort Slack announcement, required postmortem, Statuspage sync and
+/// webhooks after an API status change.
+pub(crate) async fn announce_status(
+    state: &AppState,
+    incident: &Incident,
+    previous: IncidentStatus,
+    actor: &str,
+) {
+    let notification_service = NotificationService::new(
+        state.pool.clone(),
+        state.slack_client.clone(),
+        state.config.clone(),
+    );
+
+    let result = if incident.status.is_terminal() {
+        notification_service
+            .notify_resolution(incident, blocks::resolution_blocks(incident, actor))
+            .await
+    } else {
+        let message = format!("Status changed to *{}*", incident.status.as_db_str());
+        notification_service
+            .notify_status_update(
+                incident,
+                blocks::status_update_blocks(incident.severity_level(), &message, actor),
+            )
+            .await
+    };
+    if let Err(e) = result {
+        error!("Failed to announce status change: {}", e);
+    }
+    if incident.status.is_terminal() {
+        if let Err(e) =
+            crate::commands::postmortem::require_postmortem(state, incident, actor).await
+        {
+            error!("Failed to require postmortem: {}", e);
+        }
+        crate::jobs::resolution_snapshot::enqueue(state, incident);
+    }
+
+    crate::jobs::statuspage_sync::enqueue_for_incident(&state.pool, &state.job_sender, incident)
+        .await;
+    crate::jobs::statuspage_sync::enqueue_status_change(&state.job_sender, incident);
+    crate::jobs::channel_status::enqueue(state, incident);
+    crate::services::pinned_summary::refresh(state, incident).await;
+    webhook::enqueue(
+        state,
+        webhook::status_event(incident.status),
+        incident,
+        Some(json!({ "status": previous })),
+    )
+    .await;
+}
+
+fn actor_or_default(actor_id: Option<String>) -> String {
+    actor_id
+        .map(|a| a.trim().to_string())
+        .filter(|a| !a.is_empty())
+        .unwrap_or_else(|| DEFAULT_API_ACTOR.to_string())
+}
+
+fn parse_list_query(query: &ListIncidentsQuery) -> IncidentResult<IncidentFilter> {
+    let status = query
+        .status
+        .as_deref()
+        .map(str::parse::<IncidentStatus>)
+        .transpose()
+        .map_err(|e| IncidentError::ValidationError {
+            field: "status".to_string(),
+            reason: e,
+        })?;
+    let severity = query
+        .severity
+        .as_deref()
+        .map(|code| severity::parse(code).map(|level| level.tier))
+        .transpose()
+        .map_err(|e| IncidentError::ValidationError {
+            field: "severity".to_string(),
+            reason: e,
+        })?;
+    let limit = query.limit.unwrap_or(DEFAULT_LIST_LIMIT);
+    if !(1..=MAX_LIST_LIMIT).contains(&limit) {
+        return Err(IncidentError::ValidationError {
+            field: "limit".to_string(),
+            reason: format!("Must be between 1 and {}", MAX_LIST_LIMIT),
+        });
+    }
+
+    Ok(IncidentFilter {
+        status,
+        severity,
+        open_only: query.open,
+        limit,
+    })
+}
+
+fn validate_create_request(
+    request: &CreateIncidentRequest,
+    services: &[String],
+) -> IncidentResult<&'static SeverityLevel> {
+    let title = request.title.trim();
+    if title.is_empty() || title.chars().count() > 100 {
+        return Err(IncidentError::ValidationError {
+            field: "title".to_string(),
+            reason: "Must be 1-100 characters".to_string(),
+        });
+    }
+    if !services.contains(&request.affected_service) {
+        return Err(IncidentError::ValidationError {
+            field: "affected_service".to_string(),
+            reason: format!("Unknown service '{}'", request.affected_service),
+        });
+    }
+    if request.commander_id.trim().is_empty() {
+        return Err(IncidentError::ValidationError {
+            field: "commander_id".to_string(),
+            reason: "Required".to_string(),
+        });
+    }
+    severity::parse(&request.severity).map_err(|reason| IncidentError::ValidationError {
+        field: "severity".to_string(),
+        reason,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::models::Severity;
+
+    fn create_request(title: &str, service: &str) -> CreateIncidentRequest {
+        CreateIncidentRequest {
+            title: title.to_string(),
+            severity: "P2".to_string(),
+            affected_service: service.to_string(),
+            commander_id: "U024COMMANDER".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_parse_list_query_defaults_and_case_insensitive_filters() {
+        let filter = parse_list_query(&ListIncidentsQuery {
+            status: Some("Investigating".to_string()),
+            severity: Some("p1".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(filter.status, Some(IncidentStatus::Investigating));
+        assert_eq!(filter.severity, Some(Severity::P1));
+        assert_eq!(filter.limit, DEFAULT_LIST_LIMIT);
+        assert!(!filter.open_only);
+    }
+
+    #[test]
+    fn test_parse_list_query_rejects_bad_values() {
+        let bad_status = ListIncidentsQuery {
+            status: Some("closed".to_string()),
+            ..Default::default()
+        };
+        assert!(parse_list_query(&bad_status).is_err());
+
+        let bad_limit = ListIncidentsQuery {
+            limit: Some(MAX_LIST_LIMIT + 1),
+            ..Default::default()
+        };
+        assert!(parse_list_query(&bad_limit).is_err());
+    }
+
+    #[test]
+    fn test_validate_create_request() {
+        let services = vec!["vpn".to_string()];
+        assert!(validate_create_request(&create_request("VPN down", "vpn"), &services).is_ok());
+        assert!(validate_create_request(&create_request("  ", "vpn"), &services).is_err());
+        assert!(
+            validate_create_request(&create_request(&"x".repeat(101), "vpn"), &services).is_err()
+        );
+        assert!(validate_create_request(&create_request("VPN down", "dns"), &services).is_err());
+        let mut sev9 = create_request("VPN down", "vpn");
+        sev9.severity = "SEV9".to_string();
+        assert!(validate_create_request(&sev9, &services).is_err());
+    }
+
+    #[test]
+    fn test_actor_or_default() {
+        assert_eq!(actor_or_default(None), "api");
+        assert_eq!(actor_or_default(Some("  ".to_string())), "api");
+        assert_eq!(actor_or_default(Some("U024ADMIN".to_string())), "U024ADMIN");
+    }
+}
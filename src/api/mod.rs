@@ -0,0 +1,149 @@
+pub mod admin;
+pub mod alerts;
+pub mod artifacts;
+pub mod audit;
+pub mod banner;
+pub mod github;
+pub mod incidents;
+pub mod replication;
+pub mod reports;
+pub mod timeline;
+pub mod webhooks;
+
+use crate::app_state::AppState;
+use axum::extract::{DefaultBodyLimit, Request, State};
+use axum::http::{header, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{delete, get, post};
+use axum::{Json, Router};
+use serde_json::json;
+use tracing::warn;
+
+/// Snapshot imports carry the whole database, well past axum's 2 MB default.
+const MAX_SNAPSHOT_BYTES: usize = 256 * 1024 * 1024;
+
+/// Routes served under `/api/v1`. Every route but `/banner` requires
+/// `Authorization: Bearer <API_TOKEN>`.
+pub fn router(state: AppState) -> Router<AppState> {
+    Router::new()
+        .route(
+            "/incidents",
+            get(incidents::list_incidents).post(incidents::create_incident),
+        )
+        .route("/incidents/{id}", get(incidents::get_incident))
+        .route("/incidents/{id}/status", post(incidents::update_status))
+        .route("/incidents/{id}/resolve", post(incidents::resolve_incident))
+        .route("/incidents/{id}/roles", get(incidents::get_roles))
+        .route(
+            "/incidents/{id}/artifacts",
+            get(artifacts::list_incident_artifacts),
+        )
+        .route(
+            "/incidents/{id}/timeline",
+            post(timeline::append_timeline_events),
+        )
+        .route("/audit", get(audit::list_audit_entries))
+        .route("/reports/load", get(reports::load_report))
+        .route("/reports/sla", get(reports::sla_report))
+        .route("/replication/changes", get(replication::list_changes))
+        .route(
+            "/replication/snapshot",
+            get(replication::export_snapshot)
+                .post(replication::import_snapshot)
+                .layer(DefaultBodyLimit::max(MAX_SNAPSHOT_BYTES)),
+        )
+        .route("/replication/snapshots", post(replication::store_snapshot))
+        .route(
+            "/replication/snapshots/latest",
+            get(replication::latest_snapshot),
+        )
+        .route(
+            "/webhooks",
+            get(webhooks::list_webhooks).post(webhooks::create_webhook),
+        )
+        .route("/webhooks/{id}", delete(webhooks::delete_webhook))
+        .route("/admin/audit/export", get(admin::export_audit_log))
+        .route("/admin/config/export", get(admin::export_config))
+        .route("/admin/config/import", post(admin::import_config))
+        .route("/admin/data-residency", get(admin::data_residency))
+        .route(
+            "/admin/channels/{channel_id}/history",
+            get(admin::channel_history),
+        )
+        .route(
+            "/admin/incidents/reconstruct",
+            post(admin::reconstruct_incident),
+        )
+        .route(
+            "/admin/notifications/pending-failed",
+            get(admin::pending_notifications),
+        )
+        .route(
+            "/admin/notifications/{id}/retry",
+            post(admin::retry_notification),
+        )
+        .route(
+            "/admin/notifications/{id}/skip",
+            post(admin::skip_notification),
+        )
+        .route("/admin/jobs/dead-letters", get(admin::dead_letters))
+        .route("/admin/jobs/{id}/retry", post(admin::retry_job))
+        .route("/admin/jobs/{id}/discard", post(admin::discard_job))
+        .route_layer(middleware::from_fn_with_state(state, require_api_token))
+        // Polled by web apps, which can't hold the API token
+        .route("/banner", get(banner::get_banner))
+}
+
+async fn require_api_token(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let provided = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .unwrap_or("");
+
+    match state.config.api_token.as_deref() {
+        Some(expected) if !expected.is_empty() && constant_time_eq(provided, expected) => {
+            next.run(request).await
+        }
+        _ => {
+            warn!(
+                "Rejected API request to {}: invalid token",
+                request.uri().path()
+            );
+            (
+                StatusCode::UNAUTHORIZED,
+                Json(json!({ "error": "Invalid or missing API token" })),
+            )
+                .into_response()
+        }
+    }
+}
+
+pub(crate) fn constant_time_eq(a: &str, b: &str) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.bytes()
+        .zip(b.bytes())
+        .fold(0u8, |acc, (x, y)| acc | (x ^ y))
+        == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq("secret-token", "secret-token"));
+        assert!(!constant_time_eq("secret-token", "secret-tokeN"));
+        assert!(!constant_time_eq("secret", "secret-token"));
+        assert!(!constant_time_eq("", "secret-token"));
+    }
+}
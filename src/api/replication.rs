@@ -0,0 +1,127 @@
+use crate::app_state::AppState;
+use crate::db::models::ChangeRecord;
+use crate::db::replication::{self, Snapshot};
+use crate::error::{IncidentError, IncidentResult};
+use crate::services::artifact_store::{ArtifactLink, ArtifactService};
+use crate::services::audit::AuditService;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::Json;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::BTreeMap;
+use tracing::info;
+
+const DEFAULT_CHANGES_LIMIT: i64 = 500;
+const MAX_CHANGES_LIMIT: i64 = 5000;
+const SNAPSHOT_ARTIFACT_KIND: &str = "replication_snapshot";
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ChangesQuery {
+    #[serde(default)]
+    pub after: i64,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ChangesResponse {
+    pub changes: Vec<ChangeRecord>,
+    /// Pass as `after` on the next poll
+    pub next_after: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportResponse {
+    pub inserted: BTreeMap<String, u64>,
+}
+
+/// `GET /api/v1/replication/changes?after=<seq>` — tail the incident change log.
+pub async fn list_changes(
+    State(state): State<AppState>,
+    Query(query): Query<ChangesQuery>,
+) -> IncidentResult<Json<ChangesResponse>> {
+    let limit = query.limit.unwrap_or(DEFAULT_CHANGES_LIMIT);
+    if !(1..=MAX_CHANGES_LIMIT).contains(&limit) {
+        return Err(IncidentError::ValidationError {
+            field: "limit".to_string(),
+            reason: format!("limit must be between 1 and {}", MAX_CHANGES_LIMIT),
+        });
+    }
+
+    let changes = replication::changes_since(&state.pool, query.after, limit).await?;
+    let next_after = changes.last().map_or(query.after, |c| c.seq);
+    Ok(Json(ChangesResponse {
+        changes,
+        next_after,
+    }))
+}
+
+/// `GET /api/v1/replication/snapshot` — full export for cold DR restores.
+pub async fn export_snapshot(State(state): State<AppState>) -> IncidentResult<Json<Snapshot>> {
+    let snapshot = replication::export_snapshot(&state.pool).await?;
+    info!("Exported DR snapshot via API");
+    Ok(Json(snapshot))
+}
+
+/// `POST /api/v1/replication/snapshots` — export a snapshot to the artifact
+/// store and return a signed link, for exports too large to pull inline.
+pub async fn store_snapshot(
+    State(state): State<AppState>,
+) -> IncidentResult<(StatusCode, Json<ArtifactLink>)> {
+    let snapshot = replication::export_snapshot(&state.pool).await?;
+    let bytes = serde_json::to_vec(&snapshot).map_err(|e| {
+        IncidentError::InternalError(format!("Failed to serialize snapshot: {}", e))
+    })?;
+    let key = format!(
+        "snapshots/{}.json",
+        snapshot.exported_at.format("%Y-%m-%dT%H-%M-%S%.3fZ")
+    );
+
+    let artifact_service = ArtifactService::new(state.pool.clone(), state.artifact_store.clone());
+    let artifact = artifact_service
+        .save(
+            None,
+            SNAPSHOT_ARTIFACT_KIND,
+            &key,
+            "application/json",
+            bytes,
+        )
+        .await?;
+    info!("Stored DR snapshot {} via API", key);
+    Ok((
+        StatusCode::CREATED,
+        Json(artifact_service.link(artifact, state.config.artifact_url_ttl())?),
+    ))
+}
+
+/// `GET /api/v1/replication/snapshots/latest` — a fresh link to the most
+/// recently stored snapshot.
+pub async fn latest_snapshot(State(state): State<AppState>) -> IncidentResult<Json<ArtifactLink>> {
+    let link = ArtifactService::new(state.pool.clone(), state.artifact_store.clone())
+        .latest(SNAPSHOT_ARTIFACT_KIND, state.config.artifact_url_ttl())
+        .await?
+        .ok_or(IncidentError::NotFound)?;
+    Ok(Json(link))
+}
+
+/// `POST /api/v1/replication/snapshot` — restore an export; existing rows are kept.
+pub async fn import_snapshot(
+    State(state): State<AppState>,
+    Json(snapshot): Json<Snapshot>,
+) -> IncidentResult<Json<ImportResponse>> {
+    let inserted = replication::import_snapshot(&state.pool, &snapshot).await?;
+
+    AuditService::new(state.pool.clone())
+        .log_action(
+            None,
+            "snapshot_import".to_string(),
+            "api".to_string(),
+            None,
+            None,
+            Some(json!({ "exported_at": snapshot.exported_at, "inserted": inserted })),
+        )
+        .await?;
+
+    info!("Imported DR snapshot via API: {:?}", inserted);
+    Ok(Json(ImportResponse { inserted }))
+}
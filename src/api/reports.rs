@@ -0,0 +1,77 @@
+use crate::app_state::AppState;
+use crate::db::models::SlaBreach;
+use crate::db::queries::sla;
+use crate::error::{IncidentError, IncidentResult};
+use crate::services::load::{LoadReport, LoadService};
+use axum::extract::{Query, State};
+use axum::Json;
+use chrono::{DateTime, Duration, Utc};
+use serde::Deserialize;
+
+const DEFAULT_REPORT_WINDOW_DAYS: i64 = 30;
+const MAX_REPORT_WINDOW_DAYS: i64 = 366;
+
+#[derive(Debug, Default, Deserialize)]
+pub struct LoadReportQuery {
+    pub user_id: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+}
+
+/// `GET /api/v1/reports/load` — per-person hours of incident command and
+/// roles, with nights and weekends, over `[since, until)` (default: the last
+/// 30 days).
+pub async fn load_report(
+    State(state): State<AppState>,
+    Query(query): Query<LoadReportQuery>,
+) -> IncidentResult<Json<LoadReport>> {
+    let (since, until) = report_window(query.since, query.until)?;
+    let report = LoadService::new(state.pool.clone())
+        .report(
+            query.user_id.as_deref().filter(|u| !u.is_empty()),
+            since,
+            until,
+            state.config.load_report_utc_offset_hours,
+        )
+        .await?;
+    Ok(Json(report))
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct SlaReportQuery {
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+}
+
+/// `GET /api/v1/reports/sla` — SLA breaches whose deadline fell in
+/// `[since, until)` (default: the last 30 days), oldest first.
+pub async fn sla_report(
+    State(state): State<AppState>,
+    Query(query): Query<SlaReportQuery>,
+) -> IncidentResult<Json<Vec<SlaBreach>>> {
+    let (since, until) = report_window(query.since, query.until)?;
+    let breaches = sla::breaches_between(&state.pool, since, until).await?;
+    Ok(Json(breaches))
+}
+
+/// `[since, until)`, defaulting to the last 30 days up to now.
+fn report_window(
+    since: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
+) -> IncidentResult<(DateTime<Utc>, DateTime<Utc>)> {
+    let until = until.unwrap_or_else(Utc::now);
+    let since = since.unwrap_or(until - Duration::days(DEFAULT_REPORT_WINDOW_DAYS));
+    if since >= until {
+        return Err(IncidentError::ValidationError {
+            field: "since".to_string(),
+            reason: "since must be before until".to_string(),
+        });
+    }
+    if until - since > Duration::days(MAX_REPORT_WINDOW_DAYS) {
+        return Err(IncidentError::ValidationError {
+            field: "since".to_string(),
+            reason: format!("period must be at most {} days", MAX_REPORT_WINDOW_DAYS),
+        });
+    }
+    Ok((since, until))
+}
@@ -0,0 +1,60 @@
+use crate::app_state::AppState;
+use crate::db::models::{NewTimelineEvent, TimelineEvent};
+use crate::error::IncidentResult;
+use crate::services::audit::AuditService;
+use crate::services::incident::IncidentService;
+use crate::services::timeline::TimelineService;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::Json;
+use serde::Serialize;
+use serde_json::json;
+use tracing::info;
+
+#[derive(Debug, Serialize)]
+pub struct AppendTimelineResponse {
+    pub inserted: usize,
+    pub events: Vec<TimelineEvent>,
+}
+
+/// `POST /api/v1/incidents/{id}/timeline` — append an array of notes in one write.
+pub async fn append_timeline_events(
+    State(state): State<AppState>,
+    Path(reference): Path<String>,
+    Json(events): Json<Vec<NewTimelineEvent>>,
+) -> IncidentResult<(StatusCode, Json<AppendTimelineResponse>)> {
+    let incident = IncidentService::new(state.pool.clone())
+        .find(&reference)
+        .await?;
+    let incident_id = incident.id;
+    let timeline_service = TimelineService::new(state.pool.clone());
+    let inserted = timeline_service
+        .log_integration_events(&incident, events)
+        .await?;
+
+    let audit_service = AuditService::new(state.pool.clone());
+    audit_service
+        .log_action(
+            Some(incident_id),
+            "timeline_batch_append".to_string(),
+            "api".to_string(),
+            None,
+            None,
+            Some(json!({ "count": inserted.len() })),
+        )
+        .await?;
+
+    info!(
+        "Appended {} timeline events to incident {} via API",
+        inserted.len(),
+        incident_id
+    );
+
+    Ok((
+        StatusCode::CREATED,
+        Json(AppendTimelineResponse {
+            inserted: inserted.len(),
+            events: inserted,
+        }),
+    ))
+}
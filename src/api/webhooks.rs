@@ -0,0 +1,92 @@
+use crate::app_state::AppState;
+use crate::db::models::{Webhook, WebhookEvent};
+use crate::error::{IncidentError, IncidentResult};
+use crate::services::audit::AuditService;
+use crate::services::residency::{self, ExternalService};
+use crate::services::webhook::WebhookService;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::Json;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use uuid::Uuid;
+
+#[derive(Debug, Deserialize)]
+pub struct CreateWebhookRequest {
+    pub url: String,
+    /// Generated when omitted
+    pub secret: Option<String>,
+    /// Empty or omitted subscribes to every event
+    #[serde(default)]
+    pub events: Vec<WebhookEvent>,
+}
+
+/// The only response that includes the signing secret.
+#[derive(Debug, Serialize)]
+pub struct CreateWebhookResponse {
+    #[serde(flatten)]
+    pub webhook: Webhook,
+    pub secret: String,
+}
+
+/// `GET /api/v1/webhooks`
+pub async fn list_webhooks(State(state): State<AppState>) -> IncidentResult<Json<Vec<Webhook>>> {
+    let webhooks = WebhookService::new(state.pool.clone()).list().await?;
+    Ok(Json(webhooks))
+}
+
+/// `POST /api/v1/webhooks`
+pub async fn create_webhook(
+    State(state): State<AppState>,
+    Json(request): Json<CreateWebhookRequest>,
+) -> IncidentResult<(StatusCode, Json<CreateWebhookResponse>)> {
+    if !residency::allows(&state.config, ExternalService::Webhooks) {
+        return Err(IncidentError::ValidationError {
+            field: "url".to_string(),
+            reason: "DATA_RESIDENCY=eu: set INTEGRATION_REGIONS webhooks to an EU region before registering webhooks".to_string(),
+        });
+    }
+    let webhook = WebhookService::new(state.pool.clone())
+        .register(request.url.trim(), request.secret, &request.events)
+        .await?;
+
+    AuditService::new(state.pool.clone())
+        .log_action(
+            None,
+            "create_webhook".to_string(),
+            "api".to_string(),
+            None,
+            Some(json!({ "url": webhook.url, "events": webhook.events })),
+            Some(json!({ "webhook_id": webhook.id })),
+        )
+        .await?;
+
+    let secret = webhook.secret.clone();
+    Ok((
+        StatusCode::CREATED,
+        Json(CreateWebhookResponse { webhook, secret }),
+    ))
+}
+
+/// `DELETE /api/v1/webhooks/{id}`
+pub async fn delete_webhook(
+    State(state): State<AppState>,
+    Path(webhook_id): Path<Uuid>,
+) -> IncidentResult<StatusCode> {
+    WebhookService::new(state.pool.clone())
+        .delete(webhook_id)
+        .await?;
+
+    AuditService::new(state.pool.clone())
+        .log_action(
+            None,
+            "delete_webhook".to_string(),
+            "api".to_string(),
+            None,
+            None,
+            Some(json!({ "webhook_id": webhook_id })),
+        )
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -1,26 +1,72 @@
+use crate::adapters::oncall::OnCallClient;
 use crate::config::AppConfig;
 use crate::jobs::Job;
-use crate::slack::client::SlackClient;
+use crate::services::artifact_store::{self, ArtifactStore};
+use crate::services::context_banner::ContextBanner;
+use crate::services::service_catalog::ServiceCatalog;
+use crate::shutdown::InFlight;
+use crate::slack::client::{RetryPolicy, SlackApi, SlackClient};
+use crate::slack::rate_limit::SlackRateLimiter;
 use sqlx_postgres::PgPool;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::mpsc;
 
 #[derive(Clone)]
 pub struct AppState {
     pub pool: PgPool,
     pub config: Arc<AppConfig>,
-    pub slack_client: SlackClient,
+    pub slack_client: Arc<dyn SlackApi>,
     pub job_sender: mpsc::UnboundedSender<Job>,
+    pub artifact_store: Arc<dyn ArtifactStore>,
+    /// Open high-severity incident banner, shared so its cache is too
+    pub context_banner: Arc<ContextBanner>,
+    /// Services disabled with `/incident service disable`, shared so toggles
+    /// invalidate one cache
+    pub service_catalog: Arc<ServiceCatalog>,
+    /// Per-user allowance on the Slack Request URLs (SLACK_RATE_LIMIT_PER_MINUTE)
+    pub slack_rate_limiter: Arc<SlackRateLimiter>,
+    /// Command and interaction tasks still running, awaited on shutdown
+    pub in_flight: InFlight,
+    /// PagerDuty/Opsgenie schedules, when ONCALL_PROVIDER is set
+    pub oncall_client: Option<OnCallClient>,
 }
 
 impl AppState {
     pub fn new(pool: PgPool, config: AppConfig, job_sender: mpsc::UnboundedSender<Job>) -> Self {
-        let slack_client = SlackClient::new(config.slack_bot_token.clone());
+        let retry_policy = RetryPolicy {
+            max_retries: config.slack_max_retries,
+            base_delay: Duration::from_millis(config.slack_retry_base_ms),
+            ..RetryPolicy::default()
+        };
+        let slack_client = Arc::new(
+            SlackClient::new(config.slack_bot_token.clone()).with_retry_policy(retry_policy),
+        );
+        Self::with_slack_client(pool, config, job_sender, slack_client)
+    }
+
+    /// Build state around an arbitrary `SlackApi` implementation (e.g. `MockSlackClient`).
+    pub fn with_slack_client(
+        pool: PgPool,
+        config: AppConfig,
+        job_sender: mpsc::UnboundedSender<Job>,
+        slack_client: Arc<dyn SlackApi>,
+    ) -> Self {
+        let artifact_store = artifact_store::from_config(&config);
+        let oncall_client = OnCallClient::from_config(&config);
+        let slack_rate_limiter =
+            Arc::new(SlackRateLimiter::new(config.slack_rate_limit_per_minute));
         Self {
             pool,
             config: Arc::new(config),
             slack_client,
             job_sender,
+            artifact_store,
+            context_banner: Arc::new(ContextBanner::new()),
+            service_catalog: Arc::new(ServiceCatalog::new()),
+            slack_rate_limiter,
+            in_flight: InFlight::new(),
+            oncall_client,
         }
     }
 }
@@ -0,0 +1,418 @@
+//! Load test harness: drives concurrent simulated declarations and status
+//! updates through the real command handlers, against a test database with
+//! a mock Slack client, and reports latency percentiles and connection pool
+//! saturation.
+//!
+//! ```text
+//! DATABASE_URL=postgres://localhost/incident_bot_bench \
+//!     cargo run --release --bin bench -- --incidents 200 --concurrency 50 --updates 3
+//! ```
+//!
+//! Slack acks slash commands and interactions before processing them, so the
+//! latencies here are the time from the ack until the user sees the result.
+//! `/incident declare` must open its modal within 3 seconds (trigger IDs
+//! expire), and the bench exits non-zero when any operation's p95 exceeds
+//! that budget. Use a throwaway database: incidents are created for real
+//! (and deleted afterwards unless `--keep` is passed).
+
+use incident_bot::commands::{declare, status};
+use incident_bot::jobs::worker::JobWorker;
+use incident_bot::slack::events::{SlashCommandPayload, ViewPayload};
+use incident_bot::slack::mock::MockSlackClient;
+use incident_bot::{db, AppConfig, AppState};
+use serde_json::json;
+use sqlx_postgres::PgPool;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::{mpsc, Semaphore};
+use uuid::Uuid;
+
+/// Slack's budget for acknowledging a request, and for using a trigger ID.
+const SLACK_BUDGET: Duration = Duration::from_secs(3);
+
+const POOL_SAMPLE_INTERVAL: Duration = Duration::from_millis(5);
+
+const USAGE: &str = "Usage: bench [--incidents N] [--concurrency N] [--updates N] [--keep]";
+
+#[derive(Debug, Clone, PartialEq)]
+struct Options {
+    incidents: usize,
+    concurrency: usize,
+    /// Status updates posted to each incident after it's declared
+    updates: usize,
+    /// Leave the bench incidents in the database
+    keep: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            incidents: 100,
+            concurrency: 20,
+            updates: 3,
+            keep: false,
+        }
+    }
+}
+
+fn parse_options(args: &[String]) -> Result<Options, String> {
+    let mut options = Options::default();
+    let mut args = args.iter();
+    while let Some(flag) = args.next() {
+        if flag == "--keep" {
+            options.keep = true;
+            continue;
+        }
+        let value = args
+            .next()
+            .and_then(|v| v.parse::<usize>().ok())
+            .ok_or_else(|| format!("{} needs a number\n{}", flag, USAGE))?;
+        match flag.as_str() {
+            "--incidents" => options.incidents = value,
+            "--concurrency" => options.concurrency = value,
+            "--updates" => options.updates = value,
+            _ => return Err(USAGE.to_string()),
+        }
+    }
+    if options.incidents == 0 || options.concurrency == 0 {
+        return Err("--incidents and --concurrency must be at least 1".to_string());
+    }
+    Ok(options)
+}
+
+/// The operations timed for every simulated incident, in order.
+#[derive(Debug, Clone, Copy)]
+enum Operation {
+    /// `/incident declare`: open the declare modal
+    Declare,
+    /// Declare modal submission: create the incident and its channel
+    Submit,
+    /// `/incident status <message>` in the incident channel
+    StatusUpdate,
+}
+
+impl Operation {
+    const ALL: [Operation; 3] = [
+        Operation::Declare,
+        Operation::Submit,
+        Operation::StatusUpdate,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            Operation::Declare => "declare",
+            Operation::Submit => "modal submit",
+            Operation::StatusUpdate => "status update",
+        }
+    }
+}
+
+#[derive(Default)]
+struct Samples {
+    latencies: [Vec<Duration>; 3],
+    errors: [usize; 3],
+}
+
+impl Samples {
+    fn record<T, E: std::fmt::Display>(
+        &mut self,
+        operation: Operation,
+        elapsed: Duration,
+        result: &Result<T, E>,
+    ) {
+        self.latencies[operation as usize].push(elapsed);
+        if let Err(e) = result {
+            self.errors[operation as usize] += 1;
+            eprintln!("{} failed: {}", operation.label(), e);
+        }
+    }
+}
+
+/// The `pct` percentile (0-100) of `sorted`, by nearest rank.
+fn percentile(sorted: &[Duration], pct: f64) -> Duration {
+    if sorted.is_empty() {
+        return Duration::ZERO;
+    }
+    let rank = ((pct / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Connection pool use observed while the bench ran.
+#[derive(Default)]
+struct PoolStats {
+    peak_in_use: AtomicU32,
+    samples: AtomicU32,
+    saturated_samples: AtomicU32,
+}
+
+async fn sample_pool(pool: PgPool, stats: Arc<PoolStats>, done: Arc<AtomicBool>) {
+    let max = pool.options().get_max_connections();
+    while !done.load(Ordering::Relaxed) {
+        let in_use = pool.size().saturating_sub(pool.num_idle() as u32);
+        stats.peak_in_use.fetch_max(in_use, Ordering::Relaxed);
+        stats.samples.fetch_add(1, Ordering::Relaxed);
+        if in_use >= max {
+            stats.saturated_samples.fetch_add(1, Ordering::Relaxed);
+        }
+        tokio::time::sleep(POOL_SAMPLE_INTERVAL).await;
+    }
+}
+
+fn slash_command(text: String, user_id: &str, channel_id: &str, n: usize) -> SlashCommandPayload {
+    SlashCommandPayload {
+        command: "/incident".to_string(),
+        text,
+        user_id: user_id.to_string(),
+        channel_id: channel_id.to_string(),
+        response_url: format!("https://hooks.slack.test/bench/{}", n),
+        trigger_id: format!("bench-trigger-{}", n),
+    }
+}
+
+fn declare_submission(title: &str, service: &str) -> ViewPayload {
+    serde_json::from_value(json!({
+        "id": "V_BENCH",
+        "callback_id": "declare_incident",
+        "state": { "values": {
+            "title_block": { "title_input": { "value": title } },
+            "severity_block": { "severity_select": { "selected_option": { "value": "P2" } } },
+            "service_block": { "service_select": { "selected_option": { "value": service } } },
+        } }
+    }))
+    .expect("declare submission payload")
+}
+
+/// Declare one incident and post `updates` status updates to it.
+async fn simulate_incident(
+    state: AppState,
+    run_id: String,
+    n: usize,
+    updates: usize,
+    samples: Arc<Mutex<Samples>>,
+) -> Option<Uuid> {
+    let user_id = format!("UBENCH{:05}", n);
+    let title = format!("Bench {} incident {}", run_id, n);
+    let service = state.config.services.first()?.clone();
+
+    let started = Instant::now();
+    let result = declare::handle_declare(
+        state.clone(),
+        slash_command("declare".to_string(), &user_id, "C_BENCH", n),
+    )
+    .await;
+    samples
+        .lock()
+        .unwrap()
+        .record(Operation::Declare, started.elapsed(), &result);
+
+    let started = Instant::now();
+    let result = declare::handle_modal_submission(
+        state.clone(),
+        declare_submission(&title, &service),
+        user_id.clone(),
+    )
+    .await;
+    samples
+        .lock()
+        .unwrap()
+        .record(Operation::Submit, started.elapsed(), &result);
+
+    let (incident_id, channel_id) = sqlx::query_as::query_as::<_, (Uuid, Option<String>)>(
+        "SELECT id, slack_channel_id FROM incidents WHERE title = $1",
+    )
+    .bind(&title)
+    .fetch_optional(&state.pool)
+    .await
+    .ok()
+    .flatten()?;
+    let channel_id = channel_id?;
+
+    for update in 1..=updates {
+        let started = Instant::now();
+        let result = status::handle_status(
+            state.clone(),
+            slash_command(
+                format!("status Bench update {} of {}", update, updates),
+                &user_id,
+                &channel_id,
+                n,
+            ),
+        )
+        .await;
+        samples
+            .lock()
+            .unwrap()
+            .record(Operation::StatusUpdate, started.elapsed(), &result);
+    }
+    Some(incident_id)
+}
+
+fn print_report(
+    options: &Options,
+    samples: &mut Samples,
+    elapsed: Duration,
+    pool: &PgPool,
+    pool_stats: &PoolStats,
+) -> bool {
+    let millis = |d: Duration| format!("{:.0}ms", d.as_secs_f64() * 1000.0);
+    let mut within_budget = true;
+
+    println!(
+        "{} incidents, {} concurrent, {} status updates each, in {:.1}s",
+        options.incidents,
+        options.concurrency,
+        options.updates,
+        elapsed.as_secs_f64()
+    );
+    println!(
+        "{:<14} {:>6} {:>7} {:>8} {:>8} {:>8} {:>10}",
+        "operation", "count", "errors", "p50", "p95", "max", "over 3s"
+    );
+    for operation in Operation::ALL {
+        let latencies = &mut samples.latencies[operation as usize];
+        latencies.sort();
+        let p95 = percentile(latencies, 95.0);
+        within_budget &= p95 <= SLACK_BUDGET;
+        println!(
+            "{:<14} {:>6} {:>7} {:>8} {:>8} {:>8} {:>10}",
+            operation.label(),
+            latencies.len(),
+            samples.errors[operation as usize],
+            millis(percentile(latencies, 50.0)),
+            millis(p95),
+            millis(latencies.last().copied().unwrap_or_default()),
+            latencies.iter().filter(|d| **d > SLACK_BUDGET).count()
+        );
+    }
+
+    let samples_taken = pool_stats.samples.load(Ordering::Relaxed).max(1);
+    println!(
+        "DB pool: peak {} of {} connections in use, saturated in {:.1}% of samples",
+        pool_stats.peak_in_use.load(Ordering::Relaxed),
+        pool.options().get_max_connections(),
+        100.0 * pool_stats.saturated_samples.load(Ordering::Relaxed) as f64 / samples_taken as f64
+    );
+    if !within_budget {
+        println!("❌ p95 latency exceeds Slack's 3 second budget");
+    }
+    within_budget
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| "incident_bot=error".into()),
+        )
+        .init();
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let options = match parse_options(&args) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("{}", message);
+            std::process::exit(2);
+        }
+    };
+
+    let config = AppConfig::from_env().expect("Failed to load configuration");
+    config.validate().expect("Configuration validation failed");
+
+    // Production pool settings, so saturation matches a real instance
+    let pool = db::create_pool(&config.database_url).await?;
+    db::run_migrations(&pool).await?;
+
+    // Jobs run too (against the mock), competing for the pool as they would live
+    let (job_sender, job_receiver) = mpsc::unbounded_channel();
+    let state = AppState::with_slack_client(
+        pool.clone(),
+        config,
+        job_sender,
+        Arc::new(MockSlackClient::new()),
+    );
+    let worker = JobWorker::new(job_receiver, None, None, None, state.clone());
+    tokio::spawn(async move {
+        worker.start().await;
+    });
+
+    let pool_stats = Arc::new(PoolStats::default());
+    let done = Arc::new(AtomicBool::new(false));
+    let sampler = tokio::spawn(sample_pool(pool.clone(), pool_stats.clone(), done.clone()));
+
+    let run_id = Uuid::new_v4().simple().to_string()[..8].to_string();
+    let samples = Arc::new(Mutex::new(Samples::default()));
+    let permits = Arc::new(Semaphore::new(options.concurrency));
+    let started = Instant::now();
+    let mut tasks = Vec::with_capacity(options.incidents);
+    for n in 0..options.incidents {
+        let permit = permits.clone().acquire_owned().await?;
+        let (state, run_id, samples) = (state.clone(), run_id.clone(), samples.clone());
+        let updates = options.updates;
+        tasks.push(tokio::spawn(async move {
+            let incident_id = simulate_incident(state, run_id, n, updates, samples).await;
+            drop(permit);
+            incident_id
+        }));
+    }
+    let mut incident_ids = Vec::with_capacity(tasks.len());
+    for task in tasks {
+        incident_ids.extend(task.await?);
+    }
+    let elapsed = started.elapsed();
+    done.store(true, Ordering::Relaxed);
+    sampler.await?;
+
+    let within_budget = print_report(
+        &options,
+        &mut samples.lock().unwrap(),
+        elapsed,
+        &pool,
+        &pool_stats,
+    );
+
+    if !options.keep {
+        sqlx::query::query("DELETE FROM incidents WHERE id = ANY($1)")
+            .bind(&incident_ids)
+            .execute(&pool)
+            .await?;
+    }
+
+    if !within_budget {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_options() {
+        let args = |s: &str| s.split_whitespace().map(String::from).collect::<Vec<_>>();
+        assert_eq!(parse_options(&[]), Ok(Options::default()));
+        assert_eq!(
+            parse_options(&args("--incidents 500 --concurrency 64 --updates 0 --keep")),
+            Ok(Options {
+                incidents: 500,
+                concurrency: 64,
+                updates: 0,
+                keep: true,
+            })
+        );
+        assert!(parse_options(&args("--incidents")).is_err());
+        assert!(parse_options(&args("--concurrency 0")).is_err());
+        assert!(parse_options(&args("--rate 10")).is_err());
+    }
+
+    #[test]
+    fn test_percentile() {
+        let latencies: Vec<Duration> = (1..=20).map(Duration::from_millis).collect();
+        assert_eq!(percentile(&latencies, 50.0), Duration::from_millis(10));
+        assert_eq!(percentile(&latencies, 95.0), Duration::from_millis(19));
+        assert_eq!(percentile(&latencies, 100.0), Duration::from_millis(20));
+        assert_eq!(percentile(&[], 95.0), Duration::ZERO);
+    }
+}
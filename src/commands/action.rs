@@ -0,0 +1,255 @@
+use crate::app_state::AppState;
+use crate::db::models::{ActionItem, ActionItemStatus};
+use crate::error::{IncidentError, IncidentResult};
+use crate::services::action_items::ActionItemService;
+use crate::services::incident::IncidentService;
+use crate::slack::blocks;
+use crate::slack::events::SlashCommandPayload;
+use crate::utils::mention::parse_user_mention;
+use tracing::{error, info};
+
+const USAGE: &str =
+    "Usage: /incident action \"description\" [@owner] | action done [number] | action list";
+
+#[derive(Debug, PartialEq)]
+enum ActionCommand {
+    Create {
+        description: String,
+        owner_id: Option<String>,
+    },
+    Done {
+        number: i32,
+    },
+    List,
+}
+
+fn parse_command(text: &str) -> Result<ActionCommand, String> {
+    // text is "action <rest...>"
+    let rest = text.trim().strip_prefix("action").unwrap_or("").trim();
+
+    if rest.is_empty() || rest == "list" {
+        return Ok(ActionCommand::List);
+    }
+    if let Some(number) = rest.strip_prefix("done ") {
+        return number
+            .trim()
+            .trim_start_matches('#')
+            .parse()
+            .map(|number| ActionCommand::Done { number })
+            .map_err(|_| USAGE.to_string());
+    }
+
+    // Quoted description (Slack may send curly quotes), then an optional owner
+    let (description, owner) = match rest.strip_prefix(['"', '“']) {
+        Some(quoted) => quoted
+            .split_once(['"', '”'])
+            .map(|(description, owner)| (description.trim(), owner.trim()))
+            .ok_or_else(|| "Missing closing quote".to_string())?,
+        // Unquoted: a trailing mention is the owner
+        None => match rest.rsplit_once(' ') {
+            Some((description, last)) if parse_user_mention(last).is_some() => {
+                (description.trim(), last)
+            }
+            _ => (rest, ""),
+        },
+    };
+
+    if description.is_empty() {
+        return Err(USAGE.to_string());
+    }
+    let owner_id = if owner.is_empty() {
+        None
+    } else {
+        Some(parse_user_mention(owner).ok_or_else(|| "Owner must be an @mention".to_string())?)
+    };
+
+    Ok(ActionCommand::Create {
+        description: description.to_string(),
+        owner_id,
+    })
+}
+
+pub async fn handle_action(state: AppState, payload: SlashCommandPayload) -> IncidentResult<()> {
+    let command = match parse_command(&payload.text) {
+        Ok(c) => c,
+        Err(message) => return reply(&state, &payload, blocks::error_blocks(&message)).await,
+    };
+
+    // Follow-ups are usually captured after resolution, so resolved incidents count
+    let incident_service = IncidentService::new(state.pool.clone());
+    let incident = match incident_service
+        .get_latest_by_channel(&payload.channel_id)
+        .await
+    {
+        Ok(inc) => inc,
+        Err(IncidentError::NotFound) => {
+            return reply(
+                &state,
+                &payload,
+                blocks::error_blocks("No incident found in this channel"),
+            )
+            .await;
+        }
+        Err(e) => return Err(e),
+    };
+
+    let service = ActionItemService::new(state.pool.clone());
+    let result = match command {
+        ActionCommand::Create {
+            description,
+            owner_id,
+        } => {
+            let owner_id = owner_id.unwrap_or_else(|| payload.user_id.clone());
+            match service
+                .create(&incident, &description, &owner_id, &payload.user_id)
+                .await
+            {
+                Ok(item) => {
+                    if let Some(channel_id) = &incident.slack_channel_id {
+                        if let Err(e) = state
+                            .slack_client
+                            .post_message(
+                                channel_id,
+                                blocks::action_item_added_blocks(&item, &payload.user_id),
+                            )
+                            .await
+                        {
+                            error!("Failed to announce action item: {}", e);
+                        }
+                    }
+                    crate::jobs::jira_sync::enqueue_for_action_item(&state, &incident, &item);
+                    info!(
+                        "Action item #{} added to incident {} by {}",
+                        item.number, incident.id, payload.user_id
+                    );
+                    Ok(format!("✅ Action item #{} added", item.number))
+                }
+                Err(e) => Err(e),
+            }
+        }
+        ActionCommand::Done { number } => service
+            .close(&incident, number, &payload.user_id)
+            .await
+            .map(|item| format!("✅ Action item #{} marked done", item.number)),
+        ActionCommand::List => service.list(&incident).await.map(|items| list_text(&items)),
+    };
+
+    let ack = match result {
+        Ok(text) => text,
+        Err(IncidentError::NotFound) => {
+            return reply(
+                &state,
+                &payload,
+                blocks::error_blocks("No such action item"),
+            )
+            .await;
+        }
+        Err(IncidentError::ValidationError { reason, .. }) => {
+            return reply(&state, &payload, blocks::error_blocks(&reason)).await;
+        }
+        Err(IncidentError::PermissionDenied { .. }) => {
+            return reply(
+                &state,
+                &payload,
+                blocks::error_blocks(
+                    "Only the action item owner or incident commander can close it",
+                ),
+            )
+            .await;
+        }
+        Err(e) => return Err(e),
+    };
+
+    reply(
+        &state,
+        &payload,
+        vec![serde_json::json!({
+            "type": "section",
+            "text": {
+                "type": "mrkdwn",
+                "text": ack
+            }
+        })],
+    )
+    .await
+}
+
+fn list_text(items: &[ActionItem]) -> String {
+    if items.is_empty() {
+        return "_No action items yet._".to_string();
+    }
+
+    items
+        .iter()
+        .map(|item| {
+            let check = match item.status {
+                ActionItemStatus::Open => "☐",
+                ActionItemStatus::Done => "☑",
+            };
+            let jira = item
+                .jira_issue_key
+                .as_ref()
+                .map(|key| format!(" ({})", key))
+                .unwrap_or_default();
+            format!(
+                "{} #{} {} — <@{}>{}",
+                check, item.number, item.description, item.owner_id, jira
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+async fn reply(
+    state: &AppState,
+    payload: &SlashCommandPayload,
+    blocks: Vec<serde_json::Value>,
+) -> IncidentResult<()> {
+    state
+        .slack_client
+        .post_to_response_url(&payload.response_url, blocks)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_quoted_description_and_owner() {
+        assert_eq!(
+            parse_command("action \"fix failover\" <@U024OWNER|dana>").unwrap(),
+            ActionCommand::Create {
+                description: "fix failover".to_string(),
+                owner_id: Some("U024OWNER".to_string())
+            }
+        );
+        assert_eq!(
+            parse_command("action “add replica alarms”").unwrap(),
+            ActionCommand::Create {
+                description: "add replica alarms".to_string(),
+                owner_id: None
+            }
+        );
+        assert!(parse_command("action \"fix failover\" dana").is_err());
+        assert!(parse_command("action \"fix failover <@U024OWNER>").is_err());
+    }
+
+    #[test]
+    fn test_parse_unquoted_done_and_list() {
+        assert_eq!(
+            parse_command("action rotate the DB creds <@U024OWNER>").unwrap(),
+            ActionCommand::Create {
+                description: "rotate the DB creds".to_string(),
+                owner_id: Some("U024OWNER".to_string())
+            }
+        );
+        assert_eq!(
+            parse_command("action done #2").unwrap(),
+            ActionCommand::Done { number: 2 }
+        );
+        assert!(parse_command("action done two").is_err());
+        assert_eq!(parse_command("action").unwrap(), ActionCommand::List);
+        assert_eq!(parse_command("action list").unwrap(), ActionCommand::List);
+    }
+}
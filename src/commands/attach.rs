@@ -0,0 +1,55 @@
+use crate::app_state::AppState;
+use crate::db::models::Incident;
+use crate::error::{IncidentError, IncidentResult};
+use crate::services::incident::IncidentService;
+use crate::slack::blocks;
+use crate::slack::events::SlashCommandPayload;
+use crate::slack::modals;
+
+/// `/incident attach` — declare an incident that runs in the current channel
+/// (an existing war room) instead of a new one. Opens the declare modal; the
+/// submission binds the incident to this channel.
+pub async fn handle_attach(state: AppState, payload: SlashCommandPayload) -> IncidentResult<()> {
+    if let Some(open) = open_incident_in(&state, &payload.channel_id).await? {
+        return state
+            .slack_client
+            .post_to_response_url(
+                &payload.response_url,
+                blocks::error_blocks(&already_bound_message(&open)),
+            )
+            .await;
+    }
+
+    let templates = crate::db::queries::templates::list_active_templates(&state.pool).await?;
+    let services = crate::commands::declare::enabled_services(&state).await;
+    let mut modal = modals::attach_incident_modal(&services, &templates, &payload.channel_id, None);
+    crate::commands::declare::with_banner(&state, &mut modal).await;
+    state
+        .slack_client
+        .open_modal(&payload.trigger_id, modal)
+        .await
+}
+
+/// The open incident bound to `channel_id`, if any. A channel may host
+/// several incidents over time, but only one at once.
+pub async fn open_incident_in(
+    state: &AppState,
+    channel_id: &str,
+) -> IncidentResult<Option<Incident>> {
+    match IncidentService::new(state.pool.clone())
+        .get_by_channel(channel_id)
+        .await
+    {
+        Ok(incident) => Ok(Some(incident)),
+        Err(IncidentError::NotFound) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+pub fn already_bound_message(open: &Incident) -> String {
+    format!(
+        "This channel already has an open incident ({} *{}*). Resolve it before attaching another.",
+        open.severity_level().emoji,
+        open.title
+    )
+}
@@ -0,0 +1,188 @@
+use crate::app_state::AppState;
+use crate::db::queries::audit::{self as audit_queries, AuditFilter};
+use crate::db::queries::incidents;
+use crate::error::{IncidentError, IncidentResult};
+use crate::services::permissions::Permissions;
+use crate::slack::blocks;
+use crate::slack::events::SlashCommandPayload;
+use crate::utils::incident_number;
+use chrono::{Days, NaiveDate};
+use uuid::Uuid;
+
+const USAGE: &str = "Usage: /incident audit [incident:<id>|<number>|here] [actor:@user] [action:<name>] [since:YYYY-MM-DD] [until:YYYY-MM-DD]";
+
+/// Entries shown; Slack sections cap out around 3,000 characters.
+const MAX_LISTED: i64 = 20;
+
+/// Filters parsed from the command text. `incident:here` and incident numbers
+/// (`incident:PLAT-12`) are resolved afterwards.
+#[derive(Debug, Default, PartialEq)]
+struct AuditCommand {
+    filter: AuditFilter,
+    incident_here: bool,
+    incident_number: Option<(String, i32)>,
+}
+
+fn parse_command(text: &str) -> Result<AuditCommand, String> {
+    let mut command = AuditCommand::default();
+    for token in text.split_whitespace().skip(1) {
+        let (key, value) = token
+            .split_once(':')
+            .filter(|(_, value)| !value.is_empty())
+            .ok_or_else(|| USAGE.to_string())?;
+        match key.to_ascii_lowercase().as_str() {
+            "incident" if value.eq_ignore_ascii_case("here") => command.incident_here = true,
+            "incident" => match Uuid::parse_str(value) {
+                Ok(incident_id) => command.filter.incident_id = Some(incident_id),
+                Err(_) => {
+                    command.incident_number = Some(
+                        incident_number::parse(value)
+                            .ok_or_else(|| format!("'{}' isn't an incident ID or number", value))?,
+                    )
+                }
+            },
+            "actor" | "by" => command.filter.actor_id = Some(parse_user(value)),
+            "action" => command.filter.action = Some(value.to_string()),
+            "since" => command.filter.since = Some(start_of(parse_date(value)?)),
+            // The whole day is included
+            "until" => {
+                let day = parse_date(value)?
+                    .checked_add_days(Days::new(1))
+                    .ok_or_else(|| format!("Invalid date '{}'", value))?;
+                command.filter.until = Some(start_of(day));
+            }
+            _ => return Err(USAGE.to_string()),
+        }
+    }
+    if let (Some(since), Some(until)) = (command.filter.since, command.filter.until) {
+        if since >= until {
+            return Err("`until` must not be before `since`".to_string());
+        }
+    }
+    Ok(command)
+}
+
+/// `<@U123|dana>` or `<@U123>` as typed in Slack, or a bare actor such as
+/// `api` or `system`.
+fn parse_user(value: &str) -> String {
+    value
+        .strip_prefix("<@")
+        .and_then(|rest| rest.strip_suffix('>'))
+        .map(|id| id.split('|').next().unwrap_or(id))
+        .unwrap_or(value)
+        .to_string()
+}
+
+fn parse_date(value: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|_| format!("Invalid date '{}', expected YYYY-MM-DD", value))
+}
+
+fn start_of(day: NaiveDate) -> chrono::DateTime<chrono::Utc> {
+    day.and_hms_opt(0, 0, 0)
+        .expect("midnight is a valid time")
+        .and_utc()
+}
+
+/// `/incident audit [filters]` — admin-only view of the most recent audit
+/// log entries; works from any channel.
+pub async fn handle_audit(state: AppState, payload: SlashCommandPayload) -> IncidentResult<()> {
+    let blocks = if !Permissions::from_state(&state)
+        .is_admin(&payload.user_id)
+        .await
+    {
+        blocks::error_blocks("Only bot admins can view the audit log")
+    } else {
+        match parse_command(&payload.text) {
+            Err(message) => blocks::error_blocks(&message),
+            Ok(command) => list(&state, command, &payload.channel_id).await?,
+        }
+    };
+
+    state
+        .slack_client
+        .post_to_response_url(&payload.response_url, blocks)
+        .await
+}
+
+async fn list(
+    state: &AppState,
+    command: AuditCommand,
+    channel_id: &str,
+) -> IncidentResult<Vec<serde_json::Value>> {
+    let mut filter = command.filter;
+    if command.incident_here {
+        match incidents::get_latest_incident_by_channel(&state.pool, channel_id).await {
+            Ok(incident) => filter.incident_id = Some(incident.id),
+            Err(IncidentError::NotFound) => {
+                return Ok(blocks::error_blocks("No incident found in this channel"))
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    if let Some((prefix, number)) = &command.incident_number {
+        match incidents::get_incident_by_number(&state.pool, prefix, *number).await {
+            Ok(incident) => filter.incident_id = Some(incident.id),
+            Err(IncidentError::NotFound) => {
+                return Ok(blocks::error_blocks(&format!(
+                    "No incident {} found",
+                    incident_number::format(prefix, *number)
+                )))
+            }
+            Err(e) => return Err(e),
+        }
+    }
+
+    // One extra row tells whether older entries match too
+    filter.limit = MAX_LISTED + 1;
+    let mut entries = audit_queries::search_entries(&state.pool, &filter).await?;
+    let more = entries.len() as i64 > MAX_LISTED;
+    entries.truncate(MAX_LISTED as usize);
+    Ok(blocks::audit_entries_blocks(&entries, more))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_command() {
+        assert_eq!(parse_command("audit").unwrap(), AuditCommand::default());
+
+        let command = parse_command(
+            "audit incident:here actor:<@U024COMMANDER|dana> action:resolve_incident since:2026-03-01 until:2026-03-31",
+        )
+        .unwrap();
+        assert!(command.incident_here);
+        assert_eq!(command.filter.actor_id.as_deref(), Some("U024COMMANDER"));
+        assert_eq!(command.filter.action.as_deref(), Some("resolve_incident"));
+        assert_eq!(
+            command.filter.since.unwrap().to_rfc3339(),
+            "2026-03-01T00:00:00+00:00"
+        );
+        assert_eq!(
+            command.filter.until.unwrap().to_rfc3339(),
+            "2026-04-01T00:00:00+00:00"
+        );
+        assert_eq!(
+            parse_command("audit by:api")
+                .unwrap()
+                .filter
+                .actor_id
+                .as_deref(),
+            Some("api")
+        );
+
+        assert_eq!(
+            parse_command("audit incident:plat-12")
+                .unwrap()
+                .incident_number,
+            Some(("PLAT".to_string(), 12))
+        );
+
+        assert!(parse_command("audit everything").is_err());
+        assert!(parse_command("audit incident:42").is_err());
+        assert!(parse_command("audit since:yesterday").is_err());
+        assert!(parse_command("audit since:2026-03-02 until:2026-03-01").is_err());
+    }
+}
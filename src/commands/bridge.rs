@@ -0,0 +1,35 @@
+use crate::app_state::AppState;
+use crate::error::{IncidentError, IncidentResult};
+use crate::services::incident::IncidentService;
+use crate::slack::blocks;
+use crate::slack::events::SlashCommandPayload;
+
+/// `/incident bridge` — re-surface the join link of this channel's
+/// conference bridge.
+pub async fn handle_bridge(state: AppState, payload: SlashCommandPayload) -> IncidentResult<()> {
+    let blocks = match IncidentService::new(state.pool.clone())
+        .get_latest_by_channel(&payload.channel_id)
+        .await
+    {
+        Ok(incident) => match &incident.bridge_url {
+            Some(url) => blocks::bridge_blocks(
+                state
+                    .config
+                    .conference_provider
+                    .map_or("Conference", |provider| provider.label()),
+                url,
+            ),
+            None if state.config.conference_provider.is_some() => blocks::error_blocks(
+                "This incident has no bridge. Bridges are created when a P1 or P2 is declared.",
+            ),
+            None => blocks::error_blocks("Conference bridges are not configured"),
+        },
+        Err(IncidentError::NotFound) => blocks::error_blocks("No incident found in this channel"),
+        Err(e) => return Err(e),
+    };
+
+    state
+        .slack_client
+        .post_to_response_url(&payload.response_url, blocks)
+        .await
+}
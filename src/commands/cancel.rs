@@ -0,0 +1,141 @@
+use crate::app_state::AppState;
+use crate::db::models::{Incident, WebhookEvent};
+use crate::db::queries::incidents;
+use crate::error::{IncidentError, IncidentResult};
+use crate::services::incident::IncidentService;
+use crate::services::notification::NotificationService;
+use crate::services::permissions::{Action, Permissions};
+use crate::services::webhook;
+use crate::slack::blocks;
+use crate::slack::events::SlashCommandPayload;
+use chrono::Utc;
+use serde_json::json;
+use tracing::{error, info};
+
+/// `/incident cancel <reason>` — close this channel's incident as a false
+/// alarm. Commander only. Unlike a resolution, no postmortem is required and
+/// the incident stays out of MTTR.
+pub async fn handle_cancel(state: AppState, payload: SlashCommandPayload) -> IncidentResult<()> {
+    let incident_service =
+        IncidentService::new(state.pool.clone()).with_permissions(Permissions::from_state(&state));
+    let incident = match incident_service
+        .get_latest_by_channel(&payload.channel_id)
+        .await
+    {
+        Ok(inc) => inc,
+        Err(IncidentError::NotFound) => {
+            return reply(&state, &payload, "No incident found in this channel").await;
+        }
+        Err(e) => return Err(e),
+    };
+
+    if let Err(IncidentError::PermissionDenied { .. }) = incident_service
+        .authorize(Action::Cancel, &incident, &payload.user_id)
+        .await
+    {
+        return state
+            .slack_client
+            .post_to_response_url(
+                &payload.response_url,
+                blocks::permission_denied_blocks("cancel the incident"),
+            )
+            .await;
+    }
+
+    if incident.status.is_terminal() {
+        return reply(&state, &payload, "This incident is already closed").await;
+    }
+    let Some(reason) = payload
+        .text
+        .trim()
+        .split_once(char::is_whitespace)
+        .map(|(_, reason)| reason.trim())
+        .filter(|reason| !reason.is_empty())
+    else {
+        return reply(
+            &state,
+            &payload,
+            "Usage: `/incident cancel <reason>` — say why this was a false alarm",
+        )
+        .await;
+    };
+
+    cancel_and_announce(&state, &incident, &payload.user_id, reason).await?;
+
+    state
+        .slack_client
+        .post_to_response_url(
+            &payload.response_url,
+            vec![serde_json::json!({
+                "type": "section",
+                "text": {
+                    "type": "mrkdwn",
+                    "text": "⚪ Incident canceled as a false alarm"
+                }
+            })],
+        )
+        .await
+}
+
+/// Cancel an open incident, tell the channels its declaration went to that
+/// it was a false alarm, close the Statuspage incident and archive the
+/// channel. Callers are responsible for the commander and open-state checks.
+pub async fn cancel_and_announce(
+    state: &AppState,
+    incident: &Incident,
+    user_id: &str,
+    reason: &str,
+) -> IncidentResult<Incident> {
+    let canceled = IncidentService::new(state.pool.clone())
+        .cancel_incident(incident.id, user_id.to_string(), reason.to_string())
+        .await?;
+
+    let notification_service = NotificationService::new(
+        state.pool.clone(),
+        state.slack_client.clone(),
+        state.config.clone(),
+    );
+    if let Err(e) = notification_service
+        .notify_canceled(
+            &canceled,
+            blocks::incident_canceled_blocks(&canceled, user_id, reason),
+        )
+        .await
+    {
+        error!("Failed to announce canceled incident: {}", e);
+    }
+
+    crate::jobs::statuspage_sync::enqueue_for_incident(&state.pool, &state.job_sender, &canceled)
+        .await;
+    crate::jobs::statuspage_sync::enqueue_status_change(&state.job_sender, &canceled);
+    crate::jobs::channel_status::enqueue(state, &canceled);
+    crate::services::pinned_summary::refresh(state, &canceled).await;
+    webhook::enqueue(
+        state,
+        WebhookEvent::StatusChanged,
+        &canceled,
+        Some(json!({ "status": incident.status })),
+    )
+    .await;
+
+    // Nothing is left to do in the channel; channels that existed before the
+    // incident (`/incident attach`) are left alone
+    if incidents::channel_archivable(&state.pool, canceled.id).await? {
+        crate::jobs::channel_archive::archive_incident_channel(state, &canceled, Utc::now())
+            .await?;
+    }
+
+    info!("Incident {} canceled by {}", incident.id, user_id);
+    incidents::get_incident_by_id(&state.pool, canceled.id).await
+}
+
+async fn reply(
+    state: &AppState,
+    payload: &SlashCommandPayload,
+    message: &str,
+) -> IncidentResult<()> {
+    state
+        .slack_client
+        .post_to_response_url(&payload.response_url, blocks::error_blocks(message))
+        .await
+}
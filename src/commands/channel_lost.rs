@@ -0,0 +1,249 @@
+use crate::app_state::AppState;
+use crate::db::models::{Incident, TimelineEventType};
+use crate::db::queries::incidents;
+use crate::error::{IncidentError, IncidentResult};
+use crate::services::audit::AuditService;
+use crate::services::participants::{is_slack_user_id, ParticipantService};
+use crate::services::permissions::Permissions;
+use crate::services::timeline::TimelineService;
+use crate::slack::blocks;
+use crate::utils::channel;
+use chrono::Utc;
+use serde_json::{json, Value};
+use tracing::{error, info};
+use uuid::Uuid;
+
+/// `channel_archive` / `channel_deleted` event (or their private-channel
+/// `group_*` twins). If it was an open incident's channel, the incident
+/// forgets it, so posts that would go there are skipped rather than failing
+/// on every update, and the commander is asked whether to replace it.
+/// `archived_by` is `None` for deletions. Resolved incidents' channels are
+/// left alone: archiving those is routine.
+pub async fn handle_channel_gone(
+    state: &AppState,
+    channel_id: &str,
+    archived_by: Option<&str>,
+) -> IncidentResult<()> {
+    let incident = match incidents::get_incident_by_channel(&state.pool, channel_id).await {
+        Ok(incident) => incident,
+        Err(IncidentError::NotFound) => return Ok(()),
+        Err(e) => return Err(e),
+    };
+    // An archive followed by a delete arrives as two events; act on the first
+    if !incidents::detach_channel(&state.pool, incident.id, channel_id).await? {
+        return Ok(());
+    }
+    let deleted = archived_by.is_none();
+    info!(
+        "Channel {} of incident {} was {}",
+        channel_id,
+        incident.id,
+        if deleted { "deleted" } else { "archived" }
+    );
+
+    let actor = archived_by.unwrap_or("system").to_string();
+    let message = match archived_by {
+        Some(user) => format!(
+            "Incident channel <#{}> was archived by <@{}>",
+            channel_id, user
+        ),
+        None => format!("Incident channel <#{}> was deleted", channel_id),
+    };
+    TimelineService::new(state.pool.clone())
+        .log_event(incident.id, TimelineEventType::Note, message, actor.clone())
+        .await?;
+    AuditService::new(state.pool.clone())
+        .log_action(
+            Some(incident.id),
+            "channel_lost".to_string(),
+            actor,
+            None,
+            None,
+            Some(json!({ "channel_id": channel_id, "deleted": deleted })),
+        )
+        .await?;
+
+    if let Err(e) = state
+        .slack_client
+        .send_dm(
+            &incident.commander_id,
+            blocks::channel_lost_blocks(&incident, channel_id, archived_by),
+        )
+        .await
+    {
+        error!(
+            "Failed to tell commander of incident {} its channel is gone: {}",
+            incident.id, e
+        );
+    }
+    Ok(())
+}
+
+/// "Create new channel" button: open a fresh channel for a channel-less
+/// incident, invite the commander, service owners and everyone who has
+/// responded so far, and re-pin the details there.
+pub async fn handle_replace_channel(
+    state: AppState,
+    user_id: String,
+    value: &str,
+    response_url: Option<String>,
+) -> IncidentResult<()> {
+    let incident = match channel_less_incident(&state, &user_id, value).await? {
+        Ok(incident) => incident,
+        Err(reply) => return respond(&state, &user_id, response_url, reply).await,
+    };
+
+    let (channel_id, channel_name) = channel::create_incident_channel(
+        state.slack_client.as_ref(),
+        &incident.affected_service,
+        Utc::now().date_naive(),
+        incident.id,
+        incident.is_quiet,
+    )
+    .await?;
+    if !incidents::attach_replacement_channel(&state.pool, incident.id, &channel_id).await? {
+        // Lost a race with another click; don't leave an orphan channel behind
+        if let Err(e) = state.slack_client.archive_channel(&channel_id).await {
+            error!("Failed to archive unused channel {}: {}", channel_id, e);
+        }
+        let reply = blocks::error_blocks("This incident already has a new channel");
+        return respond(&state, &user_id, response_url, reply).await;
+    }
+    let incident = incidents::get_incident_by_id(&state.pool, incident.id).await?;
+    info!(
+        "Incident {} moved to replacement channel #{} ({})",
+        incident.id, channel_name, channel_id
+    );
+
+    let mut invitees = vec![incident.commander_id.clone(), user_id.clone()];
+    if !incident.is_quiet {
+        if let Some(owners) = state.config.service_owners.get(&incident.affected_service) {
+            invitees.extend(owners.clone());
+        }
+    }
+    match ParticipantService::new(state.pool.clone())
+        .list(incident.id)
+        .await
+    {
+        Ok(participants) => invitees.extend(
+            participants
+                .into_iter()
+                .map(|p| p.user_id)
+                .filter(|u| is_slack_user_id(u)),
+        ),
+        Err(e) => error!("Failed to list participants of {}: {}", incident.id, e),
+    }
+    invitees.sort();
+    invitees.dedup();
+    if let Err(e) = state.slack_client.invite_users(&channel_id, invitees).await {
+        error!("Failed to invite responders to replacement channel: {}", e);
+    }
+
+    crate::commands::declare::post_pinned_details(&state, &incident, &channel_id).await;
+    crate::jobs::channel_status::enqueue(&state, &incident);
+
+    TimelineService::new(state.pool.clone())
+        .log_event(
+            incident.id,
+            TimelineEventType::Note,
+            format!("Incident moved to new channel <#{}>", channel_id),
+            user_id.clone(),
+        )
+        .await?;
+    AuditService::new(state.pool.clone())
+        .log_action(
+            Some(incident.id),
+            "channel_replaced".to_string(),
+            user_id.clone(),
+            None,
+            None,
+            Some(json!({ "channel_id": channel_id })),
+        )
+        .await?;
+
+    let reply = text_blocks(&format!(
+        "✅ *{}* continues in <#{}>.",
+        incident.title, channel_id
+    ));
+    respond(&state, &user_id, response_url, reply).await
+}
+
+/// "Continue without a channel" button: keep the incident channel-less.
+pub async fn handle_continue_without_channel(
+    state: AppState,
+    user_id: String,
+    value: &str,
+    response_url: Option<String>,
+) -> IncidentResult<()> {
+    let incident = match channel_less_incident(&state, &user_id, value).await? {
+        Ok(incident) => incident,
+        Err(reply) => return respond(&state, &user_id, response_url, reply).await,
+    };
+
+    TimelineService::new(state.pool.clone())
+        .log_event(
+            incident.id,
+            TimelineEventType::Note,
+            "Continuing without an incident channel".to_string(),
+            user_id.clone(),
+        )
+        .await?;
+
+    let reply = text_blocks(&format!(
+        "👍 *{}* continues without a channel. Manage it from App Home or the REST API; you can still create a new channel with the button above.",
+        incident.title
+    ));
+    respond(&state, &user_id, response_url, reply).await
+}
+
+/// The incident behind a button, if `user_id` may choose for it and it is
+/// still open and channel-less; otherwise the reply explaining why not.
+async fn channel_less_incident(
+    state: &AppState,
+    user_id: &str,
+    value: &str,
+) -> IncidentResult<Result<Incident, Vec<Value>>> {
+    let incident_id = Uuid::parse_str(value).map_err(|_| IncidentError::ValidationError {
+        field: "incident_id".to_string(),
+        reason: format!("Invalid incident id '{}'", value),
+    })?;
+    let incident = incidents::get_incident_by_id(&state.pool, incident_id).await?;
+
+    if incident.status.is_terminal() {
+        return Ok(Err(blocks::error_blocks(
+            "This incident is already resolved",
+        )));
+    }
+    if incident.commander_id != user_id && !Permissions::from_state(state).is_admin(user_id).await {
+        return Ok(Err(blocks::permission_denied_blocks(
+            "choose where this incident continues",
+        )));
+    }
+    if let Some(channel_id) = &incident.slack_channel_id {
+        return Ok(Err(text_blocks(&format!(
+            "*{}* already continues in <#{}>.",
+            incident.title, channel_id
+        ))));
+    }
+    Ok(Ok(incident))
+}
+
+fn text_blocks(text: &str) -> Vec<Value> {
+    vec![json!({
+        "type": "section",
+        "text": { "type": "mrkdwn", "text": text }
+    })]
+}
+
+/// Reply through the interaction's response URL, or by DM without one.
+async fn respond(
+    state: &AppState,
+    user_id: &str,
+    response_url: Option<String>,
+    blocks: Vec<Value>,
+) -> IncidentResult<()> {
+    match response_url {
+        Some(url) => state.slack_client.post_to_response_url(&url, blocks).await,
+        None => state.slack_client.send_dm(user_id, blocks).await,
+    }
+}
@@ -0,0 +1,52 @@
+use crate::app_state::AppState;
+use crate::db::queries::{incidents, postmortems};
+use crate::error::{IncidentError, IncidentResult};
+use crate::slack::blocks;
+use serde_json::Value;
+
+/// Subcommands that need the channel's incident to be open.
+const OPEN_ONLY: &[&str] = &[
+    "status",
+    "update-status",
+    "severity",
+    "cancel",
+    "workstream",
+    "roles",
+    "bridge",
+];
+
+/// Subcommands that still make sense after resolution (follow-up notes,
+/// action items, the postmortem, reopening), until the channel is archived.
+const UNTIL_ARCHIVED: &[&str] = &["resolved", "reopen", "note", "link", "action", "postmortem"];
+
+/// Checked by `slack::events` before dispatching a slash command: the reply
+/// for a command that can't run because the channel's incident is resolved,
+/// or its channel archived and locked. `None` lets the command run; lookups
+/// and read-only commands (`timeline`, `summary`, ...) always do.
+pub async fn closed_incident_reply(
+    state: &AppState,
+    subcommand: &str,
+    channel_id: &str,
+) -> IncidentResult<Option<Vec<Value>>> {
+    let open_only = OPEN_ONLY.contains(&subcommand);
+    if !open_only && !UNTIL_ARCHIVED.contains(&subcommand) {
+        return Ok(None);
+    }
+
+    let incident = match incidents::get_latest_incident_by_channel(&state.pool, channel_id).await {
+        Ok(incident) => incident,
+        Err(IncidentError::NotFound) => return Ok(None),
+        Err(e) => return Err(e),
+    };
+    let archived = incident.channel_archived_at.is_some();
+    if !incident.status.is_terminal() || !(archived || open_only) {
+        return Ok(None);
+    }
+
+    let postmortem = postmortems::get_postmortem(&state.pool, incident.id).await?;
+    Ok(Some(blocks::closed_incident_blocks(
+        &incident,
+        postmortem.as_ref(),
+        archived,
+    )))
+}
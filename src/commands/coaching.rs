@@ -0,0 +1,113 @@
+use crate::app_state::AppState;
+use crate::db::queries::coaching;
+use crate::error::IncidentResult;
+use crate::jobs::coaching_reports::previous_quarter;
+use crate::services::coaching::CoachingService;
+use crate::slack::blocks;
+use crate::slack::events::SlashCommandPayload;
+use chrono::Utc;
+use serde_json::{json, Value};
+
+const USAGE: &str = "Usage: /incident coaching [on|off|preview]";
+
+#[derive(Debug, PartialEq)]
+enum CoachingCommand {
+    Status,
+    On,
+    Off,
+    Preview,
+}
+
+fn parse_command(text: &str) -> Result<CoachingCommand, String> {
+    let args: Vec<String> = text
+        .split_whitespace()
+        .skip(1)
+        .map(str::to_ascii_lowercase)
+        .collect();
+    match args
+        .iter()
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .as_slice()
+    {
+        [] => Ok(CoachingCommand::Status),
+        ["on"] => Ok(CoachingCommand::On),
+        ["off"] => Ok(CoachingCommand::Off),
+        ["preview"] => Ok(CoachingCommand::Preview),
+        _ => Err(USAGE.to_string()),
+    }
+}
+
+/// `/incident coaching [on|off|preview]` — opt in or out of a private
+/// quarterly report on your incidents' comms cadence and postmortems, or
+/// see last quarter's now. Only ever about the person asking.
+pub async fn handle_coaching(state: AppState, payload: SlashCommandPayload) -> IncidentResult<()> {
+    let user_id = &payload.user_id;
+    let blocks = match parse_command(&payload.text) {
+        Ok(CoachingCommand::Status) => {
+            let message = if coaching::is_opted_in(&state.pool, user_id).await? {
+                "You get a coaching report by DM after each quarter. `/incident coaching off` to stop, `/incident coaching preview` to see last quarter's."
+            } else {
+                "Coaching reports are off. `/incident coaching on` to get a private report after each quarter on the incidents you commanded."
+            };
+            text_blocks(message)
+        }
+        Ok(CoachingCommand::On) => {
+            coaching::opt_in(&state.pool, user_id).await?;
+            text_blocks(
+                "✅ You'll get a private coaching report by DM after each quarter you command incidents.",
+            )
+        }
+        Ok(CoachingCommand::Off) => {
+            coaching::opt_out(&state.pool, user_id).await?;
+            text_blocks("Coaching reports turned off.")
+        }
+        Ok(CoachingCommand::Preview) => {
+            let (period_start, period_end) = previous_quarter(Utc::now());
+            match CoachingService::new(state.pool.clone())
+                .report(user_id, period_start, period_end)
+                .await?
+            {
+                Some(report) => blocks::coaching_report_blocks(&report),
+                None => text_blocks(&format!(
+                    "You didn't command any incidents in {}.",
+                    blocks::quarter_label(period_start)
+                )),
+            }
+        }
+        Err(message) => blocks::error_blocks(&message),
+    };
+
+    state
+        .slack_client
+        .post_to_response_url(&payload.response_url, blocks)
+        .await
+}
+
+fn text_blocks(text: &str) -> Vec<Value> {
+    vec![json!({
+        "type": "section",
+        "text": { "type": "mrkdwn", "text": text }
+    })]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_command() {
+        assert_eq!(parse_command("coaching"), Ok(CoachingCommand::Status));
+        assert_eq!(parse_command("coaching ON"), Ok(CoachingCommand::On));
+        assert_eq!(parse_command("coaching off"), Ok(CoachingCommand::Off));
+        assert_eq!(
+            parse_command("coaching preview"),
+            Ok(CoachingCommand::Preview)
+        );
+        assert_eq!(parse_command("coaching on off"), Err(USAGE.to_string()));
+        assert_eq!(
+            parse_command("coaching <@U024ALICE>"),
+            Err(USAGE.to_string())
+        );
+    }
+}
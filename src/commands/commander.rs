@@ -0,0 +1,150 @@
+use crate::app_state::AppState;
+use crate::db::queries::commanders;
+use crate::error::{IncidentError, IncidentResult};
+use crate::services::incident::IncidentService;
+use crate::services::permissions::Permissions;
+use crate::slack::blocks;
+use chrono::Utc;
+use serde_json::{json, Value};
+use tracing::{debug, error, info};
+use uuid::Uuid;
+
+/// Message event in a channel. Counts as activity if the author commands
+/// the open incident there.
+pub async fn record_channel_message(
+    state: &AppState,
+    channel_id: &str,
+    user_id: &str,
+) -> IncidentResult<()> {
+    if commanders::mark_commander_seen_in_channel(&state.pool, channel_id, user_id, Utc::now())
+        .await?
+    {
+        debug!("Commander {} active in {}", user_id, channel_id);
+    }
+    Ok(())
+}
+
+/// "I'm on it" button on a nag. Only the commander's click counts.
+pub async fn handle_commander_ack(
+    state: AppState,
+    user_id: String,
+    value: &str,
+    response_url: Option<String>,
+) -> IncidentResult<()> {
+    let incident_id = parse_incident_id(value)?;
+    let incident = IncidentService::new(state.pool.clone())
+        .get_by_id(incident_id)
+        .await?;
+
+    let reply = if incident.commander_id != user_id {
+        blocks::permission_denied_blocks("acknowledge this reminder")
+    } else {
+        commanders::mark_commander_seen(&state.pool, incident.id, &user_id, Utc::now()).await?;
+        text_blocks("👍 Thanks, noted that you're on it.")
+    };
+    respond(&state, &user_id, response_url, reply).await
+}
+
+/// "Take command" button on an escalation. Only the backups offered command
+/// when the current commander was escalated may take it, or an admin.
+pub async fn handle_take_command(
+    state: AppState,
+    user_id: String,
+    value: &str,
+    response_url: Option<String>,
+) -> IncidentResult<()> {
+    let incident_id = parse_incident_id(value)?;
+    let incident_service = IncidentService::new(state.pool.clone());
+    let incident = incident_service.get_by_id(incident_id).await?;
+
+    if incident.status.is_terminal() {
+        let reply = blocks::error_blocks("This incident is already resolved");
+        return respond(&state, &user_id, response_url, reply).await;
+    }
+    if incident.commander_id == user_id {
+        let reply = text_blocks("You are already the incident commander.");
+        return respond(&state, &user_id, response_url, reply).await;
+    }
+
+    if !Permissions::from_state(&state).is_admin(&user_id).await {
+        let backups =
+            commanders::escalation_backups(&state.pool, incident.id, &incident.commander_id)
+                .await?;
+        let Some(backups) = backups else {
+            // Command already changed hands since this escalation was posted
+            let reply = blocks::error_blocks(&format!(
+                "<@{}> is now commanding this incident; nothing to take over",
+                incident.commander_id
+            ));
+            return respond(&state, &user_id, response_url, reply).await;
+        };
+        if !backups.contains(&user_id) {
+            let reply =
+                blocks::error_blocks("Only the backup commanders offered command can take it");
+            return respond(&state, &user_id, response_url, reply).await;
+        }
+    }
+
+    let previous = incident.commander_id.clone();
+    let incident = incident_service
+        .reassign_commander(incident.id, user_id.clone(), user_id.clone())
+        .await?;
+    info!(
+        "{} took command of incident {} from {}",
+        user_id, incident.id, previous
+    );
+
+    if let Some(channel_id) = &incident.slack_channel_id {
+        if let Err(e) = state
+            .slack_client
+            .invite_users(channel_id, vec![user_id.clone()])
+            .await
+        {
+            error!("Failed to invite new commander to channel: {}", e);
+        }
+        if let Err(e) = state
+            .slack_client
+            .post_message(
+                channel_id,
+                blocks::command_transferred_blocks(&incident, &previous),
+            )
+            .await
+        {
+            error!("Failed to announce command transfer: {}", e);
+        }
+    }
+    crate::services::pinned_summary::refresh(&state, &incident).await;
+
+    let reply = text_blocks(&format!(
+        "✅ You are now the incident commander for *{}*",
+        incident.title
+    ));
+    respond(&state, &user_id, response_url, reply).await
+}
+
+fn parse_incident_id(value: &str) -> IncidentResult<Uuid> {
+    Uuid::parse_str(value).map_err(|_| IncidentError::ValidationError {
+        field: "incident_id".to_string(),
+        reason: format!("Invalid incident id '{}'", value),
+    })
+}
+
+fn text_blocks(text: &str) -> Vec<Value> {
+    vec![json!({
+        "type": "section",
+        "text": { "type": "mrkdwn", "text": text }
+    })]
+}
+
+/// Reply through the interaction's response URL, or by DM without one.
+async fn respond(
+    state: &AppState,
+    user_id: &str,
+    response_url: Option<String>,
+    blocks: Vec<Value>,
+) -> IncidentResult<()> {
+    match response_url {
+        Some(url) => state.slack_client.post_to_response_url(&url, blocks).await,
+        None => state.slack_client.send_dm(user_id, blocks).await,
+    }
+}
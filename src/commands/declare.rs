@@ -1,76 +1,414 @@
 use crate::app_state::AppState;
-use crate::error::IncidentResult;
+use crate::db::models::{DeclareDraft, Incident, IncidentTemplate, WebhookEvent};
+use crate::db::queries::drafts;
+use crate::error::{IncidentError, IncidentResult};
 use crate::services::notification::NotificationService;
+use crate::services::oncall;
+use crate::services::permissions::FieldVisibility;
+use crate::services::webhook;
 use crate::slack::blocks;
-use crate::slack::events::SlashCommandPayload;
+use crate::slack::events::{SlashCommandPayload, ViewPayload};
 use crate::slack::modals;
 use crate::utils::channel;
-use chrono::Utc;
+use crate::utils::placeholders;
+use chrono::{Duration, Utc};
+use serde_json::Value;
+use std::collections::BTreeMap;
 use tracing::{error, info};
 
+/// How long an unsubmitted declare draft is offered for.
+const DRAFT_TTL_MINUTES: i64 = 30;
+
 pub async fn handle_declare(state: AppState, payload: SlashCommandPayload) -> IncidentResult<()> {
-    // Fetch active templates
-    let templates = crate::db::queries::templates::list_active_templates(&state.pool).await?;
+    if payload.text.split_whitespace().nth(1) == Some("quiet") {
+        return handle_quiet_declare(state, payload).await;
+    }
 
-    // Open modal with templates
-    let modal = modals::declare_incident_modal(&state.config.services, &templates);
+    // Offer to resume a recent unsubmitted draft instead of a blank modal
+    let now = Utc::now();
+    let since = now - Duration::minutes(DRAFT_TTL_MINUTES);
+    if let Some((draft, saved_at)) =
+        drafts::get_declare_draft(&state.pool, &payload.user_id, since).await?
+    {
+        return state
+            .slack_client
+            .post_to_response_url(
+                &payload.response_url,
+                blocks::declare_draft_blocks(&draft, (now - saved_at).num_minutes()),
+            )
+            .await;
+    }
+
+    open_declare_modal(&state, &payload.trigger_id, None).await
+}
+
+/// `/incident declare quiet` — for suspected breaches, where announcing the
+/// incident is itself harmful. Limited to the security user group.
+async fn handle_quiet_declare(state: AppState, payload: SlashCommandPayload) -> IncidentResult<()> {
+    let error = match security_group_members(&state, &payload.user_id).await {
+        Ok(_) => None,
+        Err(IncidentError::PermissionDenied { .. }) => {
+            Some("Only members of the security user group can declare quietly")
+        }
+        Err(IncidentError::ValidationError { .. }) => {
+            Some("Quiet declare is not enabled (SECURITY_USER_GROUP is not set)")
+        }
+        Err(e) => return Err(e),
+    };
+    if let Some(message) = error {
+        return state
+            .slack_client
+            .post_to_response_url(&payload.response_url, blocks::error_blocks(message))
+            .await;
+    }
+
+    let templates = crate::db::queries::templates::list_active_templates(&state.pool).await?;
+    let services = enabled_services(&state).await;
+    let mut modal = modals::quiet_declare_modal(&services, &templates, None);
+    with_banner(&state, &mut modal).await;
     state
         .slack_client
         .open_modal(&payload.trigger_id, modal)
-        .await?;
+        .await
+}
+
+/// Members of `SECURITY_USER_GROUP`, provided `user_id` is one of them.
+async fn security_group_members(state: &AppState, user_id: &str) -> IncidentResult<Vec<String>> {
+    let group = state.config.security_user_group.as_deref().ok_or_else(|| {
+        IncidentError::ValidationError {
+            field: "security_user_group".to_string(),
+            reason: "Quiet declare requires SECURITY_USER_GROUP".to_string(),
+        }
+    })?;
+    let members = state.slack_client.usergroup_members(group).await?;
+    if !members.iter().any(|m| m == user_id) {
+        return Err(IncidentError::PermissionDenied {
+            user_id: user_id.to_string(),
+            action: "quiet declare".to_string(),
+        });
+    }
+    Ok(members)
+}
+
+async fn open_declare_modal(
+    state: &AppState,
+    trigger_id: &str,
+    draft: Option<&DeclareDraft>,
+) -> IncidentResult<()> {
+    // Fetch active templates
+    let templates = crate::db::queries::templates::list_active_templates(&state.pool).await?;
+
+    // Open modal with templates and the services not disabled
+    let services = enabled_services(state).await;
+    let mut modal = modals::declare_incident_modal(&services, &templates, draft);
+    with_banner(state, &mut modal).await;
+    state.slack_client.open_modal(trigger_id, modal).await?;
 
     Ok(())
 }
 
+/// Save the declare modal's current values; called for every `block_actions`
+/// the modal dispatches while the user fills it in.
+pub async fn save_draft(state: &AppState, user_id: &str, view: &ViewPayload) -> IncidentResult<()> {
+    // Security incident details are not kept around, and a resumed draft
+    // would open a plain declare modal rather than attach
+    if !view.private_metadata.is_empty() {
+        return Ok(());
+    }
+    let draft = draft_from_values(&view.state.values);
+    if draft.is_empty() {
+        return Ok(());
+    }
+    let now = Utc::now();
+    drafts::save_declare_draft(
+        &state.pool,
+        user_id,
+        &draft,
+        now,
+        now - Duration::minutes(DRAFT_TTL_MINUTES),
+    )
+    .await
+}
+
+/// "Resume draft": reopen the modal prefilled with the saved draft. If it
+/// expired in the meantime the modal opens blank.
+pub async fn handle_resume_draft(
+    state: AppState,
+    user_id: String,
+    trigger_id: Option<String>,
+) -> IncidentResult<()> {
+    let Some(trigger_id) = trigger_id else {
+        return Ok(());
+    };
+    let since = Utc::now() - Duration::minutes(DRAFT_TTL_MINUTES);
+    let draft = drafts::get_declare_draft(&state.pool, &user_id, since)
+        .await?
+        .map(|(draft, _)| draft);
+    open_declare_modal(&state, &trigger_id, draft.as_ref()).await
+}
+
+/// "Start fresh": discard the draft and open a blank modal.
+pub async fn handle_discard_draft(
+    state: AppState,
+    user_id: String,
+    trigger_id: Option<String>,
+) -> IncidentResult<()> {
+    drafts::delete_declare_draft(&state.pool, &user_id).await?;
+    let Some(trigger_id) = trigger_id else {
+        return Ok(());
+    };
+    open_declare_modal(&state, &trigger_id, None).await
+}
+
+/// Read whatever the user has entered so far from the modal's state.
+fn draft_from_values(values: &serde_json::Map<String, Value>) -> DeclareDraft {
+    let field = |block: &str, action: &str| values.get(block).and_then(|v| v.get(action));
+    let selected = |block: &str, action: &str| {
+        field(block, action)
+            .and_then(|v| v.get("selected_option"))
+            .and_then(|v| v.get("value"))
+            .and_then(|v| v.as_str())
+            .map(ToString::to_string)
+    };
+
+    DeclareDraft {
+        template: selected("template_block", "template_select"),
+        title: field("title_block", "title_input")
+            .and_then(|v| v.get("value"))
+            .and_then(|v| v.as_str())
+            .filter(|title| !title.trim().is_empty())
+            .map(ToString::to_string),
+        severity: selected("severity_block", "severity_select").and_then(|s| s.parse().ok()),
+        service: selected("service_block", "service_select"),
+        commander_id: field("commander_block", "commander_select")
+            .and_then(|v| v.get("selected_user"))
+            .and_then(|v| v.as_str())
+            .map(ToString::to_string),
+        fields: template_fields(values),
+    }
+}
+
+/// Values typed into the template placeholder inputs, by placeholder name.
+fn template_fields(values: &serde_json::Map<String, Value>) -> BTreeMap<String, String> {
+    values
+        .iter()
+        .filter_map(|(block_id, block)| {
+            let name = block_id.strip_prefix(modals::TEMPLATE_FIELD_BLOCK_PREFIX)?;
+            let value = block
+                .get(modals::TEMPLATE_FIELD_ACTION)
+                .and_then(|v| v.get("value"))
+                .and_then(|v| v.as_str())
+                .map(str::trim)
+                .filter(|v| !v.is_empty())?;
+            Some((name.to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+/// A template was picked in the declare modal: prefill its severity, service
+/// and title (and the service's on-call as commander), and rebuild the modal
+/// with an input per placeholder.
+pub async fn apply_template(state: &AppState, view: &ViewPayload) -> IncidentResult<()> {
+    let templates = crate::db::queries::templates::list_active_templates(&state.pool).await?;
+    let services = enabled_services(state).await;
+    let mut draft = draft_from_values(&view.state.values);
+    if let Some(template) = draft
+        .template
+        .as_ref()
+        .and_then(|name| templates.iter().find(|t| &t.name == name))
+    {
+        draft.severity = Some(
+            crate::utils::severity::for_tier(template.severity)
+                .code
+                .clone(),
+        );
+        if let Some(service) = template
+            .affected_service
+            .as_ref()
+            .filter(|s| services.contains(s))
+        {
+            draft.service = Some(service.clone());
+        }
+        // Don't overwrite a title the user wrote themselves
+        let untouched = draft
+            .title
+            .as_ref()
+            .is_none_or(|title| templates.iter().any(|t| &t.title == title));
+        if untouched {
+            draft.title = Some(template.title.clone());
+        }
+    }
+    suggest_commander(state, &mut draft).await;
+
+    rebuild_modal(state, view, &templates, &draft).await
+}
+
+/// A service was picked in the declare modal: pre-select whoever is on call
+/// for it as commander, unless a commander was already chosen.
+pub async fn apply_service(state: &AppState, view: &ViewPayload) -> IncidentResult<()> {
+    let mut draft = draft_from_values(&view.state.values);
+    if draft.commander_id.is_some() || !suggest_commander(state, &mut draft).await {
+        return Ok(());
+    }
+
+    let templates = crate::db::queries::templates::list_active_templates(&state.pool).await?;
+    rebuild_modal(state, view, &templates, &draft).await
+}
+
+/// Fill in the on-call for the draft's service when no commander is chosen.
+/// Returns whether a commander was suggested.
+async fn suggest_commander(state: &AppState, draft: &mut DeclareDraft) -> bool {
+    if draft.commander_id.is_some() {
+        return false;
+    }
+    let Some(service) = draft.service.as_deref() else {
+        return false;
+    };
+    draft.commander_id = oncall::suggested_commander(state, service).await;
+    draft.commander_id.is_some()
+}
+
+/// Replace the open declare, quiet declare, attach or triage declare modal
+/// with one built from `draft`.
+async fn rebuild_modal(
+    state: &AppState,
+    view: &ViewPayload,
+    templates: &[IncidentTemplate],
+    draft: &DeclareDraft,
+) -> IncidentResult<()> {
+    let services = &enabled_services(state).await;
+    let mut modal = if view.private_metadata == modals::QUIET_DECLARE_METADATA {
+        modals::quiet_declare_modal(services, templates, Some(draft))
+    } else if let Some(channel_id) = view
+        .private_metadata
+        .strip_prefix(modals::ATTACH_METADATA_PREFIX)
+    {
+        modals::attach_incident_modal(services, templates, channel_id, Some(draft))
+    } else if let Some(triage_id) = triage_id(view) {
+        let triage =
+            crate::db::queries::incidents::get_incident_by_id(&state.pool, triage_id?).await?;
+        modals::triage_declare_modal(services, templates, &triage, Some(draft))
+    } else {
+        modals::declare_incident_modal(services, templates, Some(draft))
+    };
+    with_banner(state, &mut modal).await;
+    state.slack_client.update_modal(&view.id, modal).await
+}
+
+/// The triage a declare modal upgrades, from its `private_metadata`.
+fn triage_id(view: &ViewPayload) -> Option<IncidentResult<uuid::Uuid>> {
+    let id = view
+        .private_metadata
+        .strip_prefix(modals::TRIAGE_METADATA_PREFIX)?;
+    Some(
+        uuid::Uuid::parse_str(id).map_err(|_| IncidentError::ValidationError {
+            field: "incident_id".to_string(),
+            reason: format!("Invalid incident id '{}'", id),
+        }),
+    )
+}
+
+/// `SERVICES` without those disabled with `/incident service disable`, for
+/// the declare modals' options.
+pub(crate) async fn enabled_services(state: &AppState) -> Vec<String> {
+    state
+        .service_catalog
+        .enabled(&state.pool, &state.config.services)
+        .await
+}
+
+/// Put the open-incident banner at the top of a declare modal, so whoever
+/// is declaring can see whether their problem is already being handled.
+pub(crate) async fn with_banner(state: &AppState, modal: &mut Value) {
+    if let Some(blocks) = modal["blocks"].as_array_mut() {
+        state.context_banner.insert(&state.pool, blocks, 0).await;
+    }
+}
+
 pub async fn handle_modal_submission(
     state: AppState,
-    view: crate::slack::events::ViewPayload,
+    view: ViewPayload,
     user_id: String,
 ) -> IncidentResult<()> {
     // Parse modal values
     let values = &view.state.values;
+    let selected = |block: &str, action: &str| {
+        values
+            .get(block)
+            .and_then(|v| v.get(action))
+            .and_then(|v| v.get("selected_option"))
+            .and_then(|v| v.get("value"))
+            .and_then(|v| v.as_str())
+    };
+    let required = |field: &str| crate::error::IncidentError::ValidationError {
+        field: field.to_string(),
+        reason: "Required".to_string(),
+    };
 
-    let title = values
+    // Inputs left empty fall back to the selected template's defaults
+    let template = match selected("template_block", "template_select") {
+        Some(name) => {
+            crate::db::queries::templates::get_template_by_name(&state.pool, name).await?
+        }
+        None => None,
+    };
+
+    let title_template = values
         .get("title_block")
         .and_then(|v| v.get("title_input"))
         .and_then(|v| v.get("value"))
         .and_then(|v| v.as_str())
-        .ok_or_else(|| crate::error::IncidentError::ValidationError {
-            field: "title".to_string(),
-            reason: "Required".to_string(),
-        })?
-        .to_string();
+        .filter(|title| !title.trim().is_empty())
+        .or(template.as_ref().map(|t| t.title.as_str()))
+        .ok_or_else(|| required("title"))?;
 
-    let severity_str = values
-        .get("severity_block")
-        .and_then(|v| v.get("severity_select"))
-        .and_then(|v| v.get("selected_option"))
-        .and_then(|v| v.get("value"))
-        .and_then(|v| v.as_str())
-        .ok_or_else(|| crate::error::IncidentError::ValidationError {
-            field: "severity".to_string(),
-            reason: "Required".to_string(),
-        })?;
-
-    let severity: crate::db::models::Severity =
-        severity_str
-            .parse()
-            .map_err(|e| crate::error::IncidentError::ValidationError {
+    // Template placeholders are filled in from their modal inputs. The title
+    // and description are read by everyone, so restricted fields stay hidden
+    let custom_fields = template_fields(values);
+    let shared_fields = FieldVisibility::shared(&state.config).redact(&custom_fields);
+    let title: String = placeholders::render(title_template, &shared_fields)
+        .chars()
+        .take(100)
+        .collect();
+    let template_description = template
+        .as_ref()
+        .and_then(|t| t.description.as_deref())
+        .map(|description| placeholders::render(description, &shared_fields));
+
+    let level = match selected("severity_block", "severity_select") {
+        Some(code) => crate::utils::severity::parse(code).map_err(|reason| {
+            crate::error::IncidentError::ValidationError {
                 field: "severity".to_string(),
-                reason: e,
-            })?;
+                reason,
+            }
+        })?,
+        None => template
+            .as_ref()
+            .map(|t| crate::utils::severity::for_tier(t.severity))
+            .ok_or_else(|| required("severity"))?,
+    };
+    let severity = level.tier;
 
-    let service = values
-        .get("service_block")
-        .and_then(|v| v.get("service_select"))
-        .and_then(|v| v.get("selected_option"))
-        .and_then(|v| v.get("value"))
-        .and_then(|v| v.as_str())
-        .ok_or_else(|| crate::error::IncidentError::ValidationError {
+    let service = selected("service_block", "service_select")
+        .map(ToString::to_string)
+        .or_else(|| {
+            template
+                .as_ref()
+                .and_then(|t| t.affected_service.clone())
+                .filter(|s| state.config.services.contains(s))
+        })
+        .ok_or_else(|| required("service"))?;
+    // The modal may have been opened before the service was disabled
+    if !state
+        .service_catalog
+        .is_enabled(&state.pool, &service)
+        .await
+    {
+        return Err(crate::error::IncidentError::ValidationError {
             field: "service".to_string(),
-            reason: "Required".to_string(),
-        })?
-        .to_string();
+            reason: format!("'{}' is disabled for new incidents", service),
+        });
+    }
 
     let commander_id = values
         .get("commander_block")
@@ -87,42 +425,129 @@ pub async fn handle_modal_submission(
         );
     }
 
+    // Quiet declares bring in the security group instead of service owners
+    let quiet = view.private_metadata == modals::QUIET_DECLARE_METADATA;
+    let security_members = if quiet {
+        security_group_members(&state, &user_id).await?
+    } else {
+        Vec::new()
+    };
+
+    // `/incident attach` runs the incident in an existing channel
+    let attach_channel = view
+        .private_metadata
+        .strip_prefix(modals::ATTACH_METADATA_PREFIX)
+        .map(ToString::to_string);
+
+    // A triage's "Declare incident" button upgrades the triage in place
+    let triage_id = triage_id(&view).transpose()?;
+
     info!("Declaring incident: {}", title);
 
     // Generate incident ID upfront (needed for channel name)
-    let incident_id = uuid::Uuid::new_v4();
+    let incident_id = triage_id.unwrap_or_else(uuid::Uuid::new_v4);
 
-    // Create Slack channel FIRST (fail fast if Slack is down)
-    let date = Utc::now().date_naive();
-    let (channel_id, channel_name) =
-        channel::create_incident_channel(&state.slack_client, &service, date, incident_id).await?;
+    let (channel_id, channel_name) = match &attach_channel {
+        Some(channel_id) => {
+            // Another incident may have been attached while the modal was open
+            if let Some(open) =
+                crate::commands::attach::open_incident_in(&state, channel_id).await?
+            {
+                return state
+                    .slack_client
+                    .send_dm(
+                        &user_id,
+                        blocks::error_blocks(&crate::commands::attach::already_bound_message(
+                            &open,
+                        )),
+                    )
+                    .await;
+            }
+            // Public channels can be joined; private ones need the bot invited
+            if let Err(e) = state.slack_client.join_conversation(channel_id).await {
+                info!("Could not join channel {}: {}", channel_id, e);
+            }
+            (channel_id.clone(), channel_id.clone())
+        }
+        None => {
+            // Create Slack channel FIRST (fail fast if Slack is down)
+            let date = Utc::now().date_naive();
+            channel::create_incident_channel(
+                state.slack_client.as_ref(),
+                &service,
+                date,
+                incident_id,
+                quiet,
+            )
+            .await?
+        }
+    };
 
     // Create incident in DB with channel ID
     // If this fails, we'll clean up the channel (compensation pattern)
-    let incident = match sqlx::query_as::query_as::<_, crate::db::models::Incident>(
-        r#"
-        INSERT INTO incidents (id, title, severity, affected_service, commander_id, status, declared_at, slack_channel_id)
-        VALUES ($1, $2, $3, $4, $5, 'declared', NOW(), $6)
-        RETURNING *
-        "#,
-    )
-    .bind(incident_id)
-    .bind(&title)
-    .bind(severity.as_db_str())
-    .bind(&service)
-    .bind(&commander_id)
-    .bind(&channel_id)
-    .fetch_one(&state.pool)
-    .await
-    {
-        Ok(inc) => inc,
-        Err(e) => {
-            error!("Failed to create incident in DB, cleaning up channel: {}", e);
-            // Compensation: Archive the channel we just created
+    let number_prefix = state.config.incident_prefix_for(&service, quiet);
+    let created = match triage_id {
+        Some(triage_id) => crate::db::queries::incidents::declare_triage(
+            &state.pool,
+            triage_id,
+            &title,
+            level,
+            &service,
+            &commander_id,
+            &channel_id,
+            &custom_fields,
+            number_prefix,
+            template.as_ref().map(|t| t.id),
+        )
+        .await,
+        None => sqlx::query_as::query_as::<_, crate::db::models::Incident>(
+            r#"
+            INSERT INTO incidents (id, title, severity, severity_code, affected_service, commander_id, status, declared_at, slack_channel_id, is_quiet, custom_fields, number_prefix, template_id)
+            VALUES ($1, $2, $3, $4, $5, $6, 'declared', NOW(), $7, $8, $9, $10, $11)
+            RETURNING *
+            "#,
+        )
+        .bind(incident_id)
+        .bind(&title)
+        .bind(severity.as_db_str())
+        .bind(&level.code)
+        .bind(&service)
+        .bind(&commander_id)
+        .bind(&channel_id)
+        .bind(quiet)
+        .bind(serde_json::json!(custom_fields))
+        .bind(number_prefix)
+        .bind(template.as_ref().map(|t| t.id))
+        .fetch_one(&state.pool)
+        .await
+        .map(Some)
+        .map_err(IncidentError::from),
+    };
+    let incident = match created {
+        Ok(Some(inc)) => inc,
+        Ok(None) => {
+            // The triage was declared or dismissed while the modal was open
             if let Err(archive_err) = state.slack_client.archive_channel(&channel_id).await {
                 error!("Failed to archive channel during cleanup: {}", archive_err);
             }
-            return Err(e.into());
+            return state
+                .slack_client
+                .send_dm(
+                    &user_id,
+                    blocks::error_blocks("This triage was already declared or dismissed"),
+                )
+                .await;
+        }
+        Err(e) => {
+            error!("Failed to create incident in DB: {}", e);
+            // Compensation: Archive the channel we just created (never an
+            // attached one, which existed before)
+            if attach_channel.is_none() {
+                if let Err(archive_err) = state.slack_client.archive_channel(&channel_id).await {
+                    error!("Failed to archive channel during cleanup: {}", archive_err);
+                }
+            }
+            return Err(e);
         }
     };
 
@@ -132,7 +557,19 @@ pub async fn handle_modal_submission(
         .log_event(
             incident.id,
             crate::db::models::TimelineEventType::Declared,
-            format!("Incident declared: {}", title),
+            {
+                let mut message = match (&attach_channel, triage_id) {
+                    (Some(_), _) => {
+                        format!("Incident declared in an existing channel: {}", title)
+                    }
+                    (None, Some(_)) => format!("Incident declared from triage: {}", title),
+                    (None, None) => format!("Incident declared: {}", title),
+                };
+                if let Some(description) = &template_description {
+                    message.push_str(&format!("\n{}", description));
+                }
+                message
+            },
             commander_id.clone(),
         )
         .await?;
@@ -150,15 +587,29 @@ pub async fn handle_modal_submission(
                 "title": title,
                 "severity": severity,
                 "service": service,
+                "quiet": quiet,
+                "attached": attach_channel.is_some(),
+                "triage": triage_id.is_some(),
+                "template": template.as_ref().map(|t| &t.name),
+                "custom_fields": custom_fields,
             })),
         )
         .await?;
 
+    crate::metrics::metrics().record_declared(severity);
+
+    // The modal made it through; its draft is no longer needed
+    if let Err(e) = drafts::delete_declare_draft(&state.pool, &user_id).await {
+        error!("Failed to delete declare draft for {}: {}", user_id, e);
+    }
+
     // Invite users to channel
     let mut invitees = vec![commander_id.clone()];
 
-    // Add service owners if configured
-    if let Some(owners) = state.config.service_owners.get(&service) {
+    if quiet {
+        invitees.extend(security_members);
+    } else if let Some(owners) = state.config.service_owners.get(&service) {
+        // Add service owners if configured
         invitees.extend(owners.clone());
     }
 
@@ -171,23 +622,38 @@ pub async fn handle_modal_submission(
         // Non-fatal: continue with incident creation
     }
 
-    // Post and pin incident details
-    let detail_blocks = blocks::incident_declared_blocks(&incident);
-    match state
-        .slack_client
-        .post_message(&channel_id, detail_blocks)
-        .await
-    {
-        Ok(ts) => {
-            // Pin the message
-            if let Err(e) = state.slack_client.pin_message(&channel_id, &ts).await {
-                error!("Failed to pin incident details: {}", e);
-            }
-        }
-        Err(e) => {
-            error!("Failed to post incident details: {}", e);
+    announce_declared(&state, &incident).await;
+
+    info!(
+        "Incident {} declared successfully in #{}{}",
+        incident.id,
+        channel_name,
+        if attach_channel.is_some() {
+            " (attached)"
+        } else {
+            ""
         }
+    );
+
+    Ok(())
+}
+
+/// Post and pin the details of a newly declared incident in its channel, then
+/// notify and start the integrations. Failures are logged: the incident
+/// already exists.
+pub(crate) async fn announce_declared(state: &AppState, incident: &Incident) {
+    let Some(channel_id) = incident.slack_channel_id.as_deref() else {
+        return;
+    };
+
+    post_pinned_details(state, incident, channel_id).await;
+    crate::commands::premortem::link_declared(state, incident).await;
+
+    // Ask for mandatory roles (comms lead, scribe, ...) per the severity matrix
+    if let Err(e) = crate::commands::roles::prompt_unfilled_roles(state, incident).await {
+        error!("Failed to post role claim prompt: {}", e);
     }
+    crate::commands::mim::page_major_incident_manager(state, incident).await;
 
     // Send notifications based on severity
     let notification_service = NotificationService::new(
@@ -196,36 +662,85 @@ pub async fn handle_modal_submission(
         state.config.clone(),
     );
 
-    let notification_blocks = blocks::incident_declared_blocks(&incident);
+    let notification_blocks =
+        blocks::incident_declared_blocks(incident, &FieldVisibility::shared(&state.config));
     if let Err(e) = notification_service
-        .notify_incident_declared(&incident, notification_blocks)
+        .notify_incident_declared(incident, notification_blocks)
         .await
     {
         error!("Failed to send notifications: {}", e);
         // Non-fatal: incident is created, just notifications failed
     }
 
-    // Enqueue Statuspage sync if component mapping exists
-    if let Ok(Some(component_id)) =
-        crate::db::queries::statuspage::get_component_id(&state.pool, &service).await
+    // Enqueue Statuspage sync if component mapping exists (best-effort)
+    crate::jobs::statuspage_sync::enqueue_for_incident(&state.pool, &state.job_sender, incident)
+        .await;
+    crate::jobs::statuspage_sync::enqueue_incident_create(&state.pool, &state.job_sender, incident)
+        .await;
+    crate::jobs::conference_bridge::enqueue_for_incident(state, incident);
+    crate::jobs::channel_status::enqueue(state, incident);
+    webhook::enqueue(state, WebhookEvent::IncidentDeclared, incident, None).await;
+}
+
+/// Post and pin the incident details in `channel_id`, remembering the
+/// message so workstream updates can refresh it in place. Failures are
+/// logged.
+pub(crate) async fn post_pinned_details(state: &AppState, incident: &Incident, channel_id: &str) {
+    let detail_blocks =
+        blocks::incident_declared_blocks(incident, &FieldVisibility::shared(&state.config));
+    match state
+        .slack_client
+        .post_message(channel_id, detail_blocks)
+        .await
     {
-        let job = crate::jobs::Job::StatuspageSync {
-            incident_id: incident.id,
-            component_id,
-            status: incident.status,
-            severity: incident.severity,
-        };
-
-        if let Err(e) = state.job_sender.send(job) {
-            error!("Failed to enqueue Statuspage sync job: {}", e);
-            // Non-fatal: best-effort sync
+        Ok(ts) => {
+            if let Err(e) = state.slack_client.pin_message(channel_id, &ts).await {
+                error!("Failed to pin incident details: {}", e);
+            }
+            if let Err(e) = crate::db::queries::incidents::update_pinned_message_ts(
+                &state.pool,
+                incident.id,
+                &ts,
+            )
+            .await
+            {
+                error!("Failed to store pinned message ts: {}", e);
+            }
+        }
+        Err(e) => {
+            error!("Failed to post incident details: {}", e);
         }
     }
+}
 
-    info!(
-        "Incident {} declared successfully in #{}",
-        incident.id, channel_name
-    );
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
 
-    Ok(())
+    #[test]
+    fn test_draft_from_values_reads_partial_modal_state() {
+        let values = json!({
+            "template_block": { "template_select": { "selected_option": null } },
+            "title_block": { "title_input": { "value": "Checkout errors" } },
+            "severity_block": { "severity_select": { "selected_option": { "value": "P1" } } },
+            "service_block": { "service_select": { "selected_option": { "value": "Payments" } } },
+            "commander_block": { "commander_select": { "selected_user": null } }
+        });
+        let draft = draft_from_values(values.as_object().unwrap());
+        assert_eq!(
+            draft,
+            DeclareDraft {
+                template: None,
+                title: Some("Checkout errors".to_string()),
+                severity: Some("P1".to_string()),
+                service: Some("Payments".to_string()),
+                commander_id: None,
+                fields: BTreeMap::new(),
+            }
+        );
+
+        let blank = json!({ "title_block": { "title_input": { "value": "  " } } });
+        assert!(draft_from_values(blank.as_object().unwrap()).is_empty());
+    }
 }
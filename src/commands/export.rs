@@ -0,0 +1,168 @@
+use crate::app_state::AppState;
+use crate::error::{IncidentError, IncidentResult};
+use crate::services::audit::AuditService;
+use crate::services::export::{self, ExportFormat};
+use crate::services::permissions::Permissions;
+use crate::slack::blocks;
+use crate::slack::events::SlashCommandPayload;
+use chrono::NaiveDate;
+use serde_json::{json, Value};
+use tracing::info;
+
+const USAGE: &str = "Usage: /incident export <from YYYY-MM-DD> <to YYYY-MM-DD> [csv|json]";
+
+#[derive(Debug, PartialEq)]
+struct ExportRequest {
+    from: NaiveDate,
+    to: NaiveDate,
+    format: ExportFormat,
+}
+
+fn parse_command(text: &str) -> Result<ExportRequest, String> {
+    let args: Vec<&str> = text.split_whitespace().skip(1).collect();
+    let (from, to, format) = match args.as_slice() {
+        [from, to] => (*from, *to, ExportFormat::Csv),
+        [from, to, format] => (*from, *to, format.parse()?),
+        _ => return Err(USAGE.to_string()),
+    };
+    let date = |value: &str| {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d")
+            .map_err(|_| format!("Invalid date '{}', expected YYYY-MM-DD", value))
+    };
+    Ok(ExportRequest {
+        from: date(from)?,
+        to: date(to)?,
+        format,
+    })
+}
+
+/// `/incident export <from> <to> [csv|json]` — admin-only export of every
+/// incident declared in the range with its timeline, sent to the caller as a
+/// file in a DM for compliance reviews.
+pub async fn handle_export(state: AppState, payload: SlashCommandPayload) -> IncidentResult<()> {
+    let blocks = if !Permissions::from_state(&state)
+        .is_admin(&payload.user_id)
+        .await
+    {
+        blocks::error_blocks("Only bot admins can export incidents")
+    } else {
+        match parse_command(&payload.text) {
+            Err(message) => blocks::error_blocks(&message),
+            Ok(request) => run(&state, &request, &payload.user_id).await?,
+        }
+    };
+
+    state
+        .slack_client
+        .post_to_response_url(&payload.response_url, blocks)
+        .await
+}
+
+async fn run(
+    state: &AppState,
+    request: &ExportRequest,
+    user_id: &str,
+) -> IncidentResult<Vec<Value>> {
+    let export = match export::export_incidents(
+        &state.pool,
+        &state.config,
+        request.from,
+        request.to,
+        request.format,
+    )
+    .await
+    {
+        Ok(export) => export,
+        Err(IncidentError::ValidationError { reason, .. }) => {
+            return Ok(blocks::error_blocks(&reason))
+        }
+        Err(e) => return Err(e),
+    };
+    if export.incident_count == 0 {
+        return Ok(text_blocks(&format!(
+            "No incidents were declared between {} and {}",
+            request.from, request.to
+        )));
+    }
+
+    let size_bytes = export.content.len();
+    state
+        .slack_client
+        .send_file_dm(
+            user_id,
+            &export.filename,
+            &format!("Incident export {} to {}", request.from, request.to),
+            export.content,
+        )
+        .await?;
+
+    // Exports carry restricted fields and internal updates out of the bot
+    AuditService::new(state.pool.clone())
+        .log_action(
+            None,
+            "incidents_exported".to_string(),
+            user_id.to_string(),
+            None,
+            None,
+            Some(json!({
+                "from": request.from,
+                "to": request.to,
+                "format": request.format.extension(),
+                "incidents": export.incident_count,
+                "size_bytes": size_bytes,
+            })),
+        )
+        .await?;
+    info!(
+        "{} exported {} incidents ({} to {}) as {}",
+        user_id,
+        export.incident_count,
+        request.from,
+        request.to,
+        request.format.extension()
+    );
+
+    Ok(text_blocks(&format!(
+        "📦 Exported {} incident{} declared {} to {}. `{}` is in your DMs.",
+        export.incident_count,
+        if export.incident_count == 1 { "" } else { "s" },
+        request.from,
+        request.to,
+        export.filename
+    )))
+}
+
+fn text_blocks(text: &str) -> Vec<Value> {
+    vec![json!({
+        "type": "section",
+        "text": { "type": "mrkdwn", "text": text }
+    })]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_command() {
+        assert_eq!(
+            parse_command("export 2024-07-01 2024-09-30"),
+            Ok(ExportRequest {
+                from: NaiveDate::from_ymd_opt(2024, 7, 1).unwrap(),
+                to: NaiveDate::from_ymd_opt(2024, 9, 30).unwrap(),
+                format: ExportFormat::Csv,
+            })
+        );
+        assert_eq!(
+            parse_command("export 2024-07-01 2024-09-30 JSON").map(|r| r.format),
+            Ok(ExportFormat::Json)
+        );
+        assert_eq!(parse_command("export"), Err(USAGE.to_string()));
+        assert_eq!(parse_command("export 2024-07-01"), Err(USAGE.to_string()));
+        assert_eq!(
+            parse_command("export 2024-07-01 Q3"),
+            Err("Invalid date 'Q3', expected YYYY-MM-DD".to_string())
+        );
+        assert!(parse_command("export 2024-07-01 2024-09-30 xlsx").is_err());
+    }
+}
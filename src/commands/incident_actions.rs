@@ -0,0 +1,203 @@
+use crate::app_state::AppState;
+use crate::commands::resolved::resolve_and_announce;
+use crate::commands::status::post_update_and_announce;
+use crate::commands::update_status::{change_status_and_announce, transition_error_text};
+use crate::db::models::{Audience, Incident, IncidentStatus};
+use crate::error::{IncidentError, IncidentResult};
+use crate::services::incident::IncidentService;
+use crate::services::permissions::{Action, Permissions};
+use crate::slack::events::ViewPayload;
+use crate::slack::{blocks, modals};
+use serde_json::{json, Value};
+use tracing::info;
+use uuid::Uuid;
+
+/// "Acknowledge" button: the first responder to click moves a freshly
+/// declared incident to investigating. Anyone may acknowledge.
+pub async fn handle_acknowledge(
+    state: AppState,
+    user_id: String,
+    value: &str,
+    response_url: Option<String>,
+) -> IncidentResult<()> {
+    let incident = get_incident(&state, value).await?;
+
+    let reply = match incident.status {
+        IncidentStatus::Declared => {
+            change_status_and_announce(&state, &incident, IncidentStatus::Investigating, &user_id)
+                .await?;
+            info!("Incident {} acknowledged by {}", incident.id, user_id);
+            text_blocks("👀 Acknowledged. The incident is now being investigated.")
+        }
+        IncidentStatus::Resolved => blocks::error_blocks("This incident is already resolved"),
+        status => text_blocks(&format!(
+            "Already acknowledged; the incident is {}.",
+            status.as_db_str()
+        )),
+    };
+    respond(&state, &user_id, response_url, reply).await
+}
+
+/// "Update Status" button: open the status modal. Commander only.
+pub async fn handle_update_status_button(
+    state: AppState,
+    user_id: String,
+    value: &str,
+    trigger_id: Option<String>,
+    response_url: Option<String>,
+) -> IncidentResult<()> {
+    let Some(trigger_id) = trigger_id else {
+        return Ok(());
+    };
+    let incident = get_incident(&state, value).await?;
+
+    if let Some(reply) = refuse(&state, &incident, &user_id, Action::ChangeStatus).await? {
+        return respond(&state, &user_id, response_url, reply).await;
+    }
+    state
+        .slack_client
+        .open_modal(&trigger_id, modals::update_status_modal(&incident))
+        .await
+}
+
+/// Update Status modal submission: change the status if it differs, then
+/// post the update message if one was written. Problems are reported by DM,
+/// since modal submissions have no response URL.
+pub async fn handle_update_status_submission(
+    state: AppState,
+    view: ViewPayload,
+    user_id: String,
+) -> IncidentResult<()> {
+    let incident = get_incident(&state, &view.private_metadata).await?;
+    if let Some(reply) = refuse(&state, &incident, &user_id, Action::ChangeStatus).await? {
+        return state.slack_client.send_dm(&user_id, reply).await;
+    }
+
+    let values = &view.state.values;
+    let new_status = values
+        .get("status_block")
+        .and_then(|v| v.get("status_select"))
+        .and_then(|v| v.get("selected_option"))
+        .and_then(|v| v.get("value"))
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| IncidentError::ValidationError {
+            field: "status".to_string(),
+            reason: "Required".to_string(),
+        })?
+        .parse::<IncidentStatus>()
+        .map_err(|e| IncidentError::ValidationError {
+            field: "status".to_string(),
+            reason: e,
+        })?;
+    let message = values
+        .get("message_block")
+        .and_then(|v| v.get("message_input"))
+        .and_then(|v| v.get("value"))
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .filter(|m| !m.is_empty());
+
+    // Resolution has its own button (duration, postmortem prompt, notifications)
+    if new_status == IncidentStatus::Resolved {
+        return state
+            .slack_client
+            .send_dm(
+                &user_id,
+                blocks::error_blocks("Use the Resolve button to resolve the incident"),
+            )
+            .await;
+    }
+    if new_status != incident.status {
+        match change_status_and_announce(&state, &incident, new_status, &user_id).await {
+            Ok(_) => {}
+            Err(IncidentError::InvalidStateTransition { from, to }) => {
+                return state
+                    .slack_client
+                    .send_dm(
+                        &user_id,
+                        blocks::error_blocks(&transition_error_text(from, to)),
+                    )
+                    .await;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    if let Some(message) = message {
+        post_update_and_announce(&state, &incident, message, &user_id, Audience::Internal).await?;
+    }
+    Ok(())
+}
+
+/// "Resolve" button (after Slack's confirmation dialog). Commander only.
+pub async fn handle_resolve_button(
+    state: AppState,
+    user_id: String,
+    value: &str,
+    response_url: Option<String>,
+) -> IncidentResult<()> {
+    let incident = get_incident(&state, value).await?;
+
+    let reply = match refuse(&state, &incident, &user_id, Action::Resolve).await? {
+        Some(reply) => reply,
+        None => {
+            resolve_and_announce(&state, &incident, &user_id).await?;
+            text_blocks("✅ Incident marked as resolved")
+        }
+    };
+    respond(&state, &user_id, response_url, reply).await
+}
+
+async fn get_incident(state: &AppState, value: &str) -> IncidentResult<Incident> {
+    let incident_id = Uuid::parse_str(value).map_err(|_| IncidentError::ValidationError {
+        field: "incident_id".to_string(),
+        reason: format!("Invalid incident id '{}'", value),
+    })?;
+    IncidentService::new(state.pool.clone())
+        .get_by_id(incident_id)
+        .await
+}
+
+/// Reply explaining why `user_id` can't take `action` on this incident, if
+/// they can't: only the commander or an admin may, and only while it's open.
+async fn refuse(
+    state: &AppState,
+    incident: &Incident,
+    user_id: &str,
+    action: Action,
+) -> IncidentResult<Option<Vec<Value>>> {
+    if incident.status.is_terminal() {
+        return Ok(Some(blocks::error_blocks(
+            "This incident is already resolved",
+        )));
+    }
+    match Permissions::from_state(state)
+        .authorize(action, incident, user_id)
+        .await
+    {
+        Ok(_) => Ok(None),
+        Err(IncidentError::PermissionDenied { .. }) => {
+            Ok(Some(blocks::permission_denied_blocks(action.description())))
+        }
+        Err(e) => Err(e),
+    }
+}
+
+fn text_blocks(text: &str) -> Vec<Value> {
+    vec![json!({
+        "type": "section",
+        "text": { "type": "mrkdwn", "text": text }
+    })]
+}
+
+/// Reply through the interaction's response URL, or by DM without one.
+async fn respond(
+    state: &AppState,
+    user_id: &str,
+    response_url: Option<String>,
+    blocks: Vec<Value>,
+) -> IncidentResult<()> {
+    match response_url {
+        Some(url) => state.slack_client.post_to_response_url(&url, blocks).await,
+        None => state.slack_client.send_dm(user_id, blocks).await,
+    }
+}
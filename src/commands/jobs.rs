@@ -0,0 +1,199 @@
+use crate::app_state::AppState;
+use crate::db::models::FailedJob;
+use crate::db::queries::failed_jobs;
+use crate::error::{IncidentError, IncidentResult};
+use crate::services::dead_letters::DeadLetterService;
+use crate::services::permissions::Permissions;
+use crate::slack::blocks;
+use crate::slack::events::SlashCommandPayload;
+use serde_json::{json, Value};
+
+const USAGE: &str = "Usage: /incident jobs [list | retry <id>|all | discard <id>]";
+
+/// Rows shown in the list; Slack sections cap out around 3,000 characters.
+const MAX_LISTED: i64 = 20;
+/// Cap on one `retry all`, so a backlog doesn't flood the worker at once.
+const MAX_RETRIED: i64 = 100;
+/// Shortest ID prefix accepted, so a typo can't match half the table.
+const MIN_PREFIX_LEN: usize = 4;
+/// Errors are cut to this many characters in the list.
+const MAX_ERROR_CHARS: usize = 120;
+
+#[derive(Debug, PartialEq)]
+enum JobsCommand {
+    List,
+    Retry(String),
+    RetryAll,
+    Discard(String),
+}
+
+fn parse_command(text: &str) -> Result<JobsCommand, String> {
+    let mut parts = text.split_whitespace().skip(1);
+    let command = match (parts.next(), parts.next()) {
+        (None, _) | (Some("list"), None) => return Ok(JobsCommand::List),
+        (Some("retry"), Some("all")) => JobsCommand::RetryAll,
+        (Some("retry"), Some(id)) => JobsCommand::Retry(parse_prefix(id)?),
+        (Some("discard"), Some(id)) => JobsCommand::Discard(parse_prefix(id)?),
+        _ => return Err(USAGE.to_string()),
+    };
+    if parts.next().is_some() {
+        return Err(USAGE.to_string());
+    }
+    Ok(command)
+}
+
+fn parse_prefix(id: &str) -> Result<String, String> {
+    let id = id.to_ascii_lowercase();
+    if id.len() < MIN_PREFIX_LEN || !id.chars().all(|c| c.is_ascii_hexdigit() || c == '-') {
+        return Err(format!(
+            "'{}' isn't a job ID. Use at least the first {} characters shown by `/incident jobs`.",
+            id, MIN_PREFIX_LEN
+        ));
+    }
+    Ok(id)
+}
+
+/// `/incident jobs` — admin-only view of dead-lettered background jobs
+/// (Statuspage sync, webhooks, bridges, ...), with retry and discard.
+pub async fn handle_jobs(state: AppState, payload: SlashCommandPayload) -> IncidentResult<()> {
+    let blocks = if !Permissions::from_state(&state)
+        .is_admin(&payload.user_id)
+        .await
+    {
+        blocks::error_blocks("Only bot admins can manage background jobs")
+    } else {
+        match parse_command(&payload.text) {
+            Err(message) => blocks::error_blocks(&message),
+            Ok(command) => run(&state, command, &payload.user_id).await?,
+        }
+    };
+
+    state
+        .slack_client
+        .post_to_response_url(&payload.response_url, blocks)
+        .await
+}
+
+async fn run(state: &AppState, command: JobsCommand, user_id: &str) -> IncidentResult<Vec<Value>> {
+    let service = DeadLetterService::new(state.pool.clone(), state.job_sender.clone());
+
+    let (retry, id) = match command {
+        JobsCommand::List => {
+            let jobs = failed_jobs::list_dead_letters(&state.pool, MAX_LISTED).await?;
+            if jobs.is_empty() {
+                return Ok(text_blocks("✅ No dead-lettered jobs"));
+            }
+            let lines = jobs.iter().map(describe).collect::<Vec<_>>();
+            return Ok(text_blocks(&format!(
+                "*Dead-lettered jobs*\n{}\n_Retry with `/incident jobs retry <id>` or `retry all`; drop with `discard <id>`._",
+                lines.join("\n")
+            )));
+        }
+        JobsCommand::RetryAll => {
+            let jobs = failed_jobs::list_dead_letters(&state.pool, MAX_RETRIED).await?;
+            let (mut requeued, mut unreadable) = (0, 0);
+            for job in jobs {
+                match service.retry(job.id, user_id).await {
+                    Ok(_) => requeued += 1,
+                    Err(IncidentError::ValidationError { field, .. }) if field == "job" => {
+                        // Unreadable, or handled by someone else in the meantime
+                        unreadable += 1;
+                    }
+                    Err(IncidentError::NotFound) => {}
+                    Err(e) => return Err(e),
+                }
+            }
+            let mut summary = format!("🔁 Requeued {} job(s)", requeued);
+            if unreadable > 0 {
+                summary.push_str(&format!(", {} couldn't be requeued", unreadable));
+            }
+            summary.push_str(". Any that fail again show up here with a new ID.");
+            return Ok(text_blocks(&summary));
+        }
+        JobsCommand::Retry(id) => (true, id),
+        JobsCommand::Discard(id) => (false, id),
+    };
+
+    let matches = failed_jobs::find_dead_letters_by_prefix(&state.pool, &id).await?;
+    let job = match matches.as_slice() {
+        [] => {
+            return Ok(blocks::error_blocks(&format!(
+                "No dead-lettered job starts with '{}'",
+                id
+            )))
+        }
+        [job] => job,
+        _ => {
+            return Ok(blocks::error_blocks(&format!(
+                "'{}' matches more than one job; use more of the ID",
+                id
+            )))
+        }
+    };
+
+    let result = if retry {
+        service.retry(job.id, user_id).await
+    } else {
+        service.discard(job.id, user_id).await
+    };
+    Ok(match result {
+        Ok(job) if retry => text_blocks(&format!(
+            "🔁 Requeued {}. If it fails again it shows up here with a new ID.",
+            describe(&job)
+        )),
+        Ok(job) => text_blocks(&format!("🗑️ Discarded {}", describe(&job))),
+        Err(IncidentError::ValidationError { reason, .. }) => blocks::error_blocks(&reason),
+        Err(IncidentError::NotFound) => {
+            blocks::error_blocks("Job was retried or discarded by someone else")
+        }
+        Err(e) => return Err(e),
+    })
+}
+
+/// `• a1b2c3d4 DeliverWebhook (3 attempts) — HTTP 502 Bad Gateway`
+fn describe(job: &FailedJob) -> String {
+    let mut line = format!("• `{}` {}", &job.id.to_string()[..8], job.kind());
+    if job.attempts > 1 {
+        line.push_str(&format!(" ({} attempts)", job.attempts));
+    }
+    let error: String = job.last_error.chars().take(MAX_ERROR_CHARS).collect();
+    line.push_str(&format!(" — {}", error));
+    if job.last_error.chars().count() > MAX_ERROR_CHARS {
+        line.push('…');
+    }
+    line
+}
+
+fn text_blocks(text: &str) -> Vec<Value> {
+    vec![json!({
+        "type": "section",
+        "text": { "type": "mrkdwn", "text": text }
+    })]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_command() {
+        assert_eq!(parse_command("jobs"), Ok(JobsCommand::List));
+        assert_eq!(parse_command("jobs list"), Ok(JobsCommand::List));
+        assert_eq!(parse_command("jobs retry all"), Ok(JobsCommand::RetryAll));
+        assert_eq!(
+            parse_command("jobs retry A1B2C3D4"),
+            Ok(JobsCommand::Retry("a1b2c3d4".to_string()))
+        );
+        assert_eq!(
+            parse_command("jobs discard a1b2"),
+            Ok(JobsCommand::Discard("a1b2".to_string()))
+        );
+        assert_eq!(parse_command("jobs retry"), Err(USAGE.to_string()));
+        assert_eq!(
+            parse_command("jobs discard a1b2 extra"),
+            Err(USAGE.to_string())
+        );
+        assert!(parse_command("jobs discard a1b").is_err());
+        assert!(parse_command("jobs skip a1b2c3d4").is_err());
+    }
+}
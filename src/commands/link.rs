@@ -0,0 +1,70 @@
+use crate::adapters::github;
+use crate::app_state::AppState;
+use crate::db::queries::links as link_queries;
+use crate::error::{IncidentError, IncidentResult};
+use crate::services::incident::IncidentService;
+use crate::services::links;
+use crate::slack::blocks;
+use crate::slack::events::SlashCommandPayload;
+
+const USAGE: &str = "Usage: /incident link github <pull request or commit URL>";
+
+/// `/incident link github <url>` — link a GitHub pull request or commit to
+/// this channel's incident for root-cause correlation. `/incident link` lists
+/// what is linked, including deploys GitHub reported. Anyone in the channel
+/// may add links, before or after resolution.
+pub async fn handle_link(state: AppState, payload: SlashCommandPayload) -> IncidentResult<()> {
+    let args = payload
+        .text
+        .trim()
+        .strip_prefix("link")
+        .map(str::trim)
+        .unwrap_or("");
+
+    let incident = match IncidentService::new(state.pool.clone())
+        .get_latest_by_channel(&payload.channel_id)
+        .await
+    {
+        Ok(incident) => incident,
+        Err(IncidentError::NotFound) => {
+            return state
+                .slack_client
+                .post_to_response_url(
+                    &payload.response_url,
+                    blocks::error_blocks("No incident found in this channel"),
+                )
+                .await;
+        }
+        Err(e) => return Err(e),
+    };
+
+    let reply = match args.split_once(char::is_whitespace) {
+        _ if args.is_empty() || args == "list" => {
+            let linked = link_queries::list_links(&state.pool, incident.id).await?;
+            blocks::incident_links_blocks(&linked)
+        }
+        Some(("github", url)) => match github::parse_url(url) {
+            Some(github_ref) => {
+                match links::link_github(&state, &incident, &github_ref, &payload.user_id).await? {
+                    // The channel announcement is the confirmation
+                    Some(_) => return Ok(()),
+                    None => blocks::error_blocks(&format!(
+                        "{} is already linked to this incident",
+                        github_ref.label()
+                    )),
+                }
+            }
+            None => blocks::error_blocks(&format!(
+                "Not a GitHub pull request or commit URL: {}\n{}",
+                url.trim(),
+                USAGE
+            )),
+        },
+        _ => blocks::error_blocks(USAGE),
+    };
+
+    state
+        .slack_client
+        .post_to_response_url(&payload.response_url, reply)
+        .await
+}
@@ -0,0 +1,73 @@
+use crate::app_state::AppState;
+use crate::error::IncidentResult;
+use crate::services::load::LoadService;
+use crate::slack::blocks;
+use crate::slack::events::SlashCommandPayload;
+use crate::utils::mention::parse_user_mention;
+use chrono::{Duration, Utc};
+
+const USAGE: &str = "Usage: /incident load [@user] [30d|90d]";
+
+/// Parse the optional user and window after `load`; everyone over 30 days
+/// when omitted.
+fn parse_command(text: &str) -> Result<(Option<String>, i64), String> {
+    let mut user_id = None;
+    let mut window_days = None;
+    for arg in text.split_whitespace().skip(1) {
+        match arg.to_ascii_lowercase().as_str() {
+            "30d" if window_days.is_none() => window_days = Some(30),
+            "90d" if window_days.is_none() => window_days = Some(90),
+            _ => match parse_user_mention(arg) {
+                Some(id) if user_id.is_none() => user_id = Some(id),
+                _ => return Err(USAGE.to_string()),
+            },
+        }
+    }
+    Ok((user_id, window_days.unwrap_or(30)))
+}
+
+/// `/incident load [@user] [30d|90d]` — hours of incident command and roles,
+/// and how many fell on nights and weekends; works from any channel.
+pub async fn handle_load(state: AppState, payload: SlashCommandPayload) -> IncidentResult<()> {
+    let blocks = match parse_command(&payload.text) {
+        Ok((user_id, window_days)) => {
+            let until = Utc::now();
+            let report = LoadService::new(state.pool.clone())
+                .report(
+                    user_id.as_deref(),
+                    until - Duration::days(window_days),
+                    until,
+                    state.config.load_report_utc_offset_hours,
+                )
+                .await?;
+            blocks::load_report_blocks(&report, window_days)
+        }
+        Err(message) => blocks::error_blocks(&message),
+    };
+
+    state
+        .slack_client
+        .post_to_response_url(&payload.response_url, blocks)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_command() {
+        assert_eq!(parse_command("load"), Ok((None, 30)));
+        assert_eq!(
+            parse_command("load <@U024ALICE|alice> 90d"),
+            Ok((Some("U024ALICE".to_string()), 90))
+        );
+        assert_eq!(
+            parse_command("load 30D U024ALICE"),
+            Ok((Some("U024ALICE".to_string()), 30))
+        );
+        assert_eq!(parse_command("load @alice"), Err(USAGE.to_string()));
+        assert_eq!(parse_command("load 7d"), Err(USAGE.to_string()));
+        assert_eq!(parse_command("load 30d 90d"), Err(USAGE.to_string()));
+    }
+}
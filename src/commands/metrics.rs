@@ -0,0 +1,114 @@
+use crate::app_state::AppState;
+use crate::error::IncidentResult;
+use crate::services::metrics::{DurationHistogram, MetricsService};
+use crate::services::permissions::Permissions;
+use crate::slack::blocks;
+use crate::slack::events::SlashCommandPayload;
+use crate::utils::histogram;
+use chrono::Utc;
+use tracing::warn;
+
+const USAGE: &str = "Usage: /incident metrics [30d|90d] [--chart]";
+
+const CHART_WIDTH: u32 = 360;
+const CHART_HEIGHT: u32 = 90;
+
+/// Parse the window after `metrics` (30 days when omitted) and whether to
+/// add resolution time charts.
+fn parse_args(text: &str) -> Result<(i64, bool), String> {
+    let mut window = None;
+    let mut chart = false;
+    for arg in text.split_whitespace().skip(1) {
+        match arg.to_ascii_lowercase().as_str() {
+            "--chart" if !chart => chart = true,
+            "30d" if window.is_none() => window = Some(30),
+            "90d" if window.is_none() => window = Some(90),
+            _ => return Err(USAGE.to_string()),
+        }
+    }
+    Ok((window.unwrap_or(30), chart))
+}
+
+/// `/incident metrics [30d|90d] [--chart]`; works from any channel. Members
+/// of service teams only see their teams' services unless they have the
+/// org-wide reporting scope. `--chart` adds a histogram of resolution times
+/// per severity, rendered and uploaded on the spot.
+pub async fn handle_metrics(state: AppState, payload: SlashCommandPayload) -> IncidentResult<()> {
+    let scope = Permissions::from_state(&state)
+        .analytics_scope(&payload.user_id)
+        .await;
+    let blocks = match parse_args(&payload.text) {
+        Ok(_) if scope.services().is_some_and(|s| s.is_empty()) => blocks::error_blocks(
+            "Metrics are limited to your team's services, and you aren't in a team in TEAMS. \
+             Ask an admin to add you to a team or to REPORTING_USERS.",
+        ),
+        Ok((window_days, chart)) => {
+            let service = MetricsService::new(state.pool.clone());
+            let now = Utc::now();
+            let report = service.report(window_days, now, scope).await?;
+            let mut blocks = blocks::metrics_report_blocks(&report);
+            if chart && report.overall.as_ref().is_some_and(|row| row.declared > 0) {
+                let histograms = service
+                    .duration_histograms(window_days, now, &report.scope)
+                    .await?;
+                let mut charts = Vec::new();
+                for histogram in histograms {
+                    let file_id = upload_chart(&state, &histogram, window_days).await;
+                    charts.push((histogram, file_id));
+                }
+                blocks.extend(blocks::duration_histogram_blocks(&charts));
+            }
+            blocks
+        }
+        Err(message) => blocks::error_blocks(&message),
+    };
+
+    state
+        .slack_client
+        .post_to_response_url(&payload.response_url, blocks)
+        .await
+}
+
+/// Upload one severity's histogram. A failed upload only costs the image;
+/// the bucket counts are still shown.
+async fn upload_chart(
+    state: &AppState,
+    histogram: &DurationHistogram,
+    window_days: i64,
+) -> Option<String> {
+    let png = histogram::render_png(&histogram.counts, CHART_WIDTH, CHART_HEIGHT);
+    let severity = histogram.severity.as_db_str();
+    match state
+        .slack_client
+        .upload_file(
+            None,
+            &format!("time-to-resolve-{}-{}d.png", severity, window_days),
+            &format!("{} time to resolve, last {} days", severity, window_days),
+            png,
+        )
+        .await
+    {
+        Ok(file_id) => Some(file_id),
+        Err(e) => {
+            warn!("Failed to upload {} duration chart: {}", severity, e);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(parse_args("metrics"), Ok((30, false)));
+        assert_eq!(parse_args("metrics 30d"), Ok((30, false)));
+        assert_eq!(parse_args("metrics 90D"), Ok((90, false)));
+        assert_eq!(parse_args("metrics --chart"), Ok((30, true)));
+        assert_eq!(parse_args("metrics --chart 90d"), Ok((90, true)));
+        assert_eq!(parse_args("metrics 7d"), Err(USAGE.to_string()));
+        assert_eq!(parse_args("metrics 30d 90d"), Err(USAGE.to_string()));
+        assert_eq!(parse_args("metrics 30d extra"), Err(USAGE.to_string()));
+    }
+}
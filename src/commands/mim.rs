@@ -0,0 +1,214 @@
+use crate::app_state::AppState;
+use crate::config::MimRole;
+use crate::db::models::{Incident, Severity, TimelineEventType};
+use crate::db::queries::mim_pages;
+use crate::error::{IncidentError, IncidentResult};
+use crate::services::incident::IncidentService;
+use crate::services::oncall::{self, OnCall};
+use crate::services::roles::RoleService;
+use crate::services::timeline::TimelineService;
+use crate::slack::blocks;
+use chrono::Utc;
+use serde_json::{json, Value};
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+/// Incident role claimed by a major incident manager accepting as advisor.
+pub const MIM_ROLE: &str = "major_incident_manager";
+
+/// Page the major incident manager on duty for a P1, whatever its service.
+/// Each incident pages at most once; quiet incidents and incidents the MIM
+/// already commands are skipped. Failures are logged: paging must never
+/// block a declaration or escalation.
+pub async fn page_major_incident_manager(state: &AppState, incident: &Incident) {
+    if incident.severity != Severity::P1
+        || incident.is_quiet
+        || incident.status.is_terminal()
+        || !state.config.mim_enabled()
+    {
+        return;
+    }
+
+    let now = Utc::now();
+    let (user_id, source) = match oncall::major_incident_manager(state, now).await {
+        Ok(Some((OnCall::User(user_id), source))) => (user_id, source),
+        Ok(Some((OnCall::Email(email), source))) => {
+            warn!(
+                "Major incident manager {} on {} has no Slack account; incident {} not paged",
+                email, source, incident.id
+            );
+            return;
+        }
+        Ok(Some((OnCall::Nobody, source))) => {
+            warn!(
+                "Nobody is on call in {}; incident {} not paged to a major incident manager",
+                source, incident.id
+            );
+            return;
+        }
+        Ok(None) => return,
+        Err(e) => {
+            error!("Major incident manager lookup failed: {}", e);
+            return;
+        }
+    };
+    if user_id == incident.commander_id {
+        info!(
+            "Major incident manager {} already commands incident {}",
+            user_id, incident.id
+        );
+        return;
+    }
+
+    match mim_pages::claim_page(&state.pool, incident.id, &user_id, &source, now).await {
+        Ok(Some(_)) => {}
+        Ok(None) => return,
+        Err(e) => {
+            error!("Failed to record major incident manager page: {}", e);
+            return;
+        }
+    }
+
+    let page = blocks::mim_page_blocks(incident, &source, state.config.mim_role);
+    if let Err(e) = state.slack_client.send_dm(&user_id, page).await {
+        error!(
+            "Failed to page major incident manager {} for incident {}: {}",
+            user_id, incident.id, e
+        );
+        return;
+    }
+    if let Err(e) = TimelineService::new(state.pool.clone())
+        .log_event(
+            incident.id,
+            TimelineEventType::StatusUpdate,
+            format!("Paged major incident manager <@{}> ({})", user_id, source),
+            "system".to_string(),
+        )
+        .await
+    {
+        error!("Failed to log major incident manager page: {}", e);
+    }
+    info!(
+        "Paged major incident manager {} for incident {}",
+        user_id, incident.id
+    );
+}
+
+/// "Accept" button on a MIM page. Only the paged manager may accept; they
+/// take command or the major incident manager role per MIM_ROLE.
+pub async fn handle_mim_accept(
+    state: AppState,
+    user_id: String,
+    value: &str,
+    response_url: Option<String>,
+) -> IncidentResult<()> {
+    let incident_id = Uuid::parse_str(value).map_err(|_| IncidentError::ValidationError {
+        field: "incident_id".to_string(),
+        reason: format!("Invalid incident id '{}'", value),
+    })?;
+    let incident_service = IncidentService::new(state.pool.clone());
+    let incident = incident_service.get_by_id(incident_id).await?;
+
+    let page = mim_pages::get_page(&state.pool, incident.id).await?;
+    let reply = match page {
+        _ if incident.status.is_terminal() => {
+            blocks::error_blocks("This incident is already resolved")
+        }
+        Some(page) if page.user_id != user_id => blocks::error_blocks(&format!(
+            "This page went to <@{}>; only they can accept it",
+            page.user_id
+        )),
+        None => blocks::error_blocks("No major incident manager was paged for this incident"),
+        Some(page) if page.accepted_at.is_some() => text_blocks(&format!(
+            "You already accepted this page as {}.",
+            page.accepted_as.as_deref().unwrap_or("advisor")
+        )),
+        Some(_) => accept(&state, &incident_service, incident, &user_id).await?,
+    };
+
+    match response_url {
+        Some(url) => state.slack_client.post_to_response_url(&url, reply).await,
+        None => state.slack_client.send_dm(&user_id, reply).await,
+    }
+}
+
+async fn accept(
+    state: &AppState,
+    incident_service: &IncidentService,
+    incident: Incident,
+    user_id: &str,
+) -> IncidentResult<Vec<Value>> {
+    let role = state.config.mim_role;
+    let Some(_) =
+        mim_pages::accept_page(&state.pool, incident.id, user_id, role.as_str(), Utc::now())
+            .await?
+    else {
+        return Ok(text_blocks("This page was already accepted."));
+    };
+
+    if let Some(channel_id) = &incident.slack_channel_id {
+        if let Err(e) = state
+            .slack_client
+            .invite_users(channel_id, vec![user_id.to_string()])
+            .await
+        {
+            error!("Failed to invite major incident manager to channel: {}", e);
+        }
+    }
+
+    let text = match role {
+        MimRole::Commander => {
+            let previous = incident.commander_id.clone();
+            let incident = incident_service
+                .reassign_commander(incident.id, user_id.to_string(), user_id.to_string())
+                .await?;
+            if let Some(channel_id) = &incident.slack_channel_id {
+                if let Err(e) = state
+                    .slack_client
+                    .post_message(
+                        channel_id,
+                        blocks::command_transferred_blocks(&incident, &previous),
+                    )
+                    .await
+                {
+                    error!("Failed to announce command transfer: {}", e);
+                }
+            }
+            crate::services::pinned_summary::refresh(state, &incident).await;
+            format!(
+                "✅ You are now the incident commander for *{}*",
+                incident.title
+            )
+        }
+        MimRole::Advisor => {
+            match RoleService::new(state.pool.clone())
+                .claim(&incident, &[MIM_ROLE.to_string()], MIM_ROLE, user_id)
+                .await
+            {
+                Ok(_) => {}
+                Err(IncidentError::ValidationError { reason, .. }) => {
+                    return Ok(blocks::error_blocks(&reason))
+                }
+                Err(e) => return Err(e),
+            }
+            format!(
+                "✅ You are the major incident manager for *{}*, alongside commander <@{}>",
+                incident.title, incident.commander_id
+            )
+        }
+    };
+    info!(
+        "{} accepted the major incident manager page for incident {} as {}",
+        user_id,
+        incident.id,
+        role.as_str()
+    );
+    Ok(text_blocks(&text))
+}
+
+fn text_blocks(text: &str) -> Vec<Value> {
+    vec![json!({
+        "type": "section",
+        "text": { "type": "mrkdwn", "text": text }
+    })]
+}
@@ -1,6 +1,79 @@
+pub mod action;
+pub mod attach;
+pub mod audit;
+pub mod bridge;
+pub mod cancel;
+pub mod channel_lost;
+pub mod closed;
+pub mod coaching;
+pub mod commander;
 pub mod declare;
+pub mod export;
+pub mod incident_actions;
+pub mod jobs;
+pub mod link;
+pub mod load;
+pub mod metrics;
+pub mod mim;
+pub mod note;
+pub mod notifications;
+pub mod paging_test;
 pub mod postmortem;
+pub mod premortem;
+pub mod reopen;
 pub mod resolved;
+pub mod roles;
+pub mod routing;
+pub mod search;
+pub mod service;
 pub mod severity;
+pub mod simulate;
 pub mod status;
+pub mod subscribe;
+pub mod summary;
+pub mod template;
 pub mod timeline;
+pub mod triage;
+pub mod update_status;
+pub mod whoisoncall;
+pub mod workstream;
+
+/// `/incident` subcommands, as dispatched by `slack::events`. Drives the
+/// unknown-subcommand hint, command metrics labels and the app manifest's
+/// usage hint.
+pub const SUBCOMMANDS: &[&str] = &[
+    "declare",
+    "status",
+    "update-status",
+    "severity",
+    "resolved",
+    "reopen",
+    "cancel",
+    "timeline",
+    "note",
+    "link",
+    "postmortem",
+    "premortem",
+    "action",
+    "workstream",
+    "roles",
+    "simulate",
+    "search",
+    "metrics",
+    "attach",
+    "routing",
+    "load",
+    "bridge",
+    "template",
+    "coaching",
+    "summary",
+    "whoisoncall",
+    "notifications",
+    "export",
+    "jobs",
+    "audit",
+    "subscribe",
+    "unsubscribe",
+    "service",
+    "triage",
+];
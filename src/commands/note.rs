@@ -0,0 +1,88 @@
+use crate::app_state::AppState;
+use crate::db::models::TimelineEventType;
+use crate::error::{IncidentError, IncidentResult};
+use crate::services::incident::IncidentService;
+use crate::services::timeline::TimelineService;
+use crate::slack::blocks;
+use crate::slack::events::SlashCommandPayload;
+use tracing::{error, info};
+
+const USAGE: &str = "Usage: /incident note <text>";
+
+/// Longest note accepted; Slack section text caps at 3000 characters.
+const MAX_NOTE_CHARS: usize = 2000;
+
+/// `/incident note <text>` — add a note to this channel's incident timeline.
+/// Anyone in the channel may add notes, not just the commander.
+pub async fn handle_note(state: AppState, payload: SlashCommandPayload) -> IncidentResult<()> {
+    let note = payload
+        .text
+        .trim()
+        .strip_prefix("note")
+        .map(str::trim)
+        .unwrap_or("");
+    if note.is_empty() {
+        return state
+            .slack_client
+            .post_to_response_url(&payload.response_url, blocks::error_blocks(USAGE))
+            .await;
+    }
+    if note.chars().count() > MAX_NOTE_CHARS {
+        return state
+            .slack_client
+            .post_to_response_url(
+                &payload.response_url,
+                blocks::error_blocks(&format!(
+                    "Notes can be at most {} characters",
+                    MAX_NOTE_CHARS
+                )),
+            )
+            .await;
+    }
+
+    // Notes are still useful after resolution, for the postmortem
+    let incident = match IncidentService::new(state.pool.clone())
+        .get_latest_by_channel(&payload.channel_id)
+        .await
+    {
+        Ok(incident) => incident,
+        Err(IncidentError::NotFound) => {
+            return state
+                .slack_client
+                .post_to_response_url(
+                    &payload.response_url,
+                    blocks::error_blocks("No incident found in this channel"),
+                )
+                .await;
+        }
+        Err(e) => return Err(e),
+    };
+
+    TimelineService::new(state.pool.clone())
+        .log_event(
+            incident.id,
+            TimelineEventType::Note,
+            note.to_string(),
+            payload.user_id.clone(),
+        )
+        .await?;
+    info!(
+        "Note added to incident {} by {}",
+        incident.id, payload.user_id
+    );
+
+    if let Some(channel_id) = &incident.slack_channel_id {
+        if let Err(e) = state
+            .slack_client
+            .post_message(
+                channel_id,
+                blocks::note_added_blocks(&payload.user_id, note),
+            )
+            .await
+        {
+            error!("Failed to post note: {}", e);
+        }
+    }
+
+    Ok(())
+}
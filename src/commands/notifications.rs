@@ -0,0 +1,254 @@
+use crate::app_state::AppState;
+use crate::db::models::{NotificationRecord, NotificationStatus, NotificationType};
+use crate::db::queries::notifications;
+use crate::error::{IncidentError, IncidentResult};
+use crate::services::notification::NotificationService;
+use crate::services::permissions::Permissions;
+use crate::slack::blocks;
+use crate::slack::events::SlashCommandPayload;
+use serde_json::{json, Value};
+
+const USAGE: &str = "Usage: /incident notifications [list | retry <id>|all | skip <id>]";
+
+/// How far back the list and `retry all` look.
+const LOOKBACK_HOURS: i64 = 24;
+/// Rows shown in the list; Slack sections cap out around 3,000 characters.
+const MAX_LISTED: i64 = 20;
+/// Cap on one `retry all`, to stay well inside Slack's rate limits.
+const MAX_RETRIED: i64 = 100;
+/// Shortest ID prefix accepted, so a typo can't match half the table.
+const MIN_PREFIX_LEN: usize = 4;
+
+#[derive(Debug, PartialEq)]
+enum NotificationsCommand {
+    List,
+    Retry(String),
+    RetryAll,
+    Skip(String),
+}
+
+fn parse_command(text: &str) -> Result<NotificationsCommand, String> {
+    let mut parts = text.split_whitespace().skip(1);
+    let command = match (parts.next(), parts.next()) {
+        (None, _) | (Some("list"), None) => return Ok(NotificationsCommand::List),
+        (Some("retry"), Some("all")) => NotificationsCommand::RetryAll,
+        (Some("retry"), Some(id)) => NotificationsCommand::Retry(parse_prefix(id)?),
+        (Some("skip"), Some(id)) => NotificationsCommand::Skip(parse_prefix(id)?),
+        _ => return Err(USAGE.to_string()),
+    };
+    if parts.next().is_some() {
+        return Err(USAGE.to_string());
+    }
+    Ok(command)
+}
+
+fn parse_prefix(id: &str) -> Result<String, String> {
+    let id = id.to_ascii_lowercase();
+    if id.len() < MIN_PREFIX_LEN || !id.chars().all(|c| c.is_ascii_hexdigit() || c == '-') {
+        return Err(format!(
+            "'{}' isn't a notification ID. Use at least the first {} characters shown by `/incident notifications`.",
+            id, MIN_PREFIX_LEN
+        ));
+    }
+    Ok(id)
+}
+
+/// `/incident notifications` — admin-only view of failed, throttled and
+/// deferred notifications, with retry and skip for recovering after a
+/// partial Slack outage.
+pub async fn handle_notifications(
+    state: AppState,
+    payload: SlashCommandPayload,
+) -> IncidentResult<()> {
+    let blocks = if !Permissions::from_state(&state)
+        .is_admin(&payload.user_id)
+        .await
+    {
+        blocks::error_blocks("Only bot admins can manage notifications")
+    } else {
+        match parse_command(&payload.text) {
+            Err(message) => blocks::error_blocks(&message),
+            Ok(command) => run(&state, command, &payload.user_id).await?,
+        }
+    };
+
+    state
+        .slack_client
+        .post_to_response_url(&payload.response_url, blocks)
+        .await
+}
+
+async fn run(
+    state: &AppState,
+    command: NotificationsCommand,
+    user_id: &str,
+) -> IncidentResult<Vec<Value>> {
+    let service = NotificationService::new(
+        state.pool.clone(),
+        state.slack_client.clone(),
+        state.config.clone(),
+    );
+    let since = chrono::Utc::now() - chrono::Duration::hours(LOOKBACK_HOURS);
+
+    let (verb, id) = match command {
+        NotificationsCommand::List => {
+            let records = notifications::list_unsent(&state.pool, since, MAX_LISTED).await?;
+            let text = if records.is_empty() {
+                format!(
+                    "✅ No failed, throttled or deferred notifications in the last {} hours",
+                    LOOKBACK_HOURS
+                )
+            } else {
+                let lines = records.iter().map(describe).collect::<Vec<_>>();
+                format!(
+                    "*Unsent notifications (last {} hours)*\n{}\n_Retry with `/incident notifications retry <id>` or `retry all`; give up with `skip <id>`._",
+                    LOOKBACK_HOURS,
+                    lines.join("\n")
+                )
+            };
+            let throttle = state.slack_client.throttle();
+            if !throttle.is_throttled() {
+                return Ok(text_blocks(&text));
+            }
+            return Ok(text_blocks(&format!(
+                "⚠️ *Slack is rate limiting the bot.* DMs are going out {} ms apart and fan-out thread replies are held back (`pending`) to be sent batched once it stops.\n\n{}",
+                throttle.spacing().as_millis(),
+                text
+            )));
+        }
+        NotificationsCommand::RetryAll => {
+            let records = notifications::list_unsent(&state.pool, since, MAX_RETRIED).await?;
+            let (mut sent, mut failed, mut skipped) = (0, 0, 0);
+            for record in records {
+                if record.blocks.is_none() {
+                    skipped += 1;
+                    continue;
+                }
+                match service.retry(record.id, user_id).await {
+                    Ok(updated) if updated.status == NotificationStatus::Sent => sent += 1,
+                    Ok(_) => failed += 1,
+                    // Handled by someone else in the meantime
+                    Err(IncidentError::ValidationError { .. }) => {}
+                    Err(e) => return Err(e),
+                }
+            }
+            let mut summary = format!("🔁 Retried notifications: {} sent, {} failed", sent, failed);
+            if skipped > 0 {
+                summary.push_str(&format!(
+                    ", {} logged without their message (skip those)",
+                    skipped
+                ));
+            }
+            return Ok(text_blocks(&summary));
+        }
+        NotificationsCommand::Retry(id) => ("retry", id),
+        NotificationsCommand::Skip(id) => ("skip", id),
+    };
+
+    let matches = notifications::find_unsent_by_prefix(&state.pool, &id).await?;
+    let record = match matches.as_slice() {
+        [] => {
+            return Ok(blocks::error_blocks(&format!(
+                "No failed, throttled or deferred notification starts with '{}'",
+                id
+            )))
+        }
+        [record] => record,
+        _ => {
+            return Ok(blocks::error_blocks(&format!(
+                "'{}' matches more than one notification; use more of the ID",
+                id
+            )))
+        }
+    };
+
+    let result = if verb == "retry" {
+        service.retry(record.id, user_id).await
+    } else {
+        service.skip(record.id, user_id).await
+    };
+    Ok(match result {
+        Ok(updated) => match updated.status {
+            NotificationStatus::Sent => {
+                text_blocks(&format!("✅ Resent to {}", recipient(&updated)))
+            }
+            NotificationStatus::Skipped => {
+                text_blocks(&format!("⏭️ Skipped {}", describe(&updated)))
+            }
+            _ => blocks::error_blocks(&format!(
+                "Retry to {} failed again: {}",
+                recipient(&updated),
+                updated.error_message.as_deref().unwrap_or("unknown error")
+            )),
+        },
+        Err(IncidentError::ValidationError { reason, .. }) => blocks::error_blocks(&reason),
+        Err(e) => return Err(e),
+    })
+}
+
+fn recipient(record: &NotificationRecord) -> String {
+    match record.notification_type {
+        NotificationType::SlackChannel => format!("<#{}>", record.recipient),
+        NotificationType::SlackDm => format!("<@{}>", record.recipient),
+    }
+}
+
+/// `• a1b2c3d4 <#C123> failed (2 attempts) — channel_not_found`
+fn describe(record: &NotificationRecord) -> String {
+    let mut line = format!(
+        "• `{}` {} {}",
+        &record.id.to_string()[..8],
+        recipient(record),
+        record.status.as_db_str()
+    );
+    if record.attempts > 1 {
+        line.push_str(&format!(" ({} attempts)", record.attempts));
+    }
+    if let Some(error) = &record.error_message {
+        line.push_str(&format!(" — {}", error));
+    }
+    line
+}
+
+fn text_blocks(text: &str) -> Vec<Value> {
+    vec![json!({
+        "type": "section",
+        "text": { "type": "mrkdwn", "text": text }
+    })]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_command() {
+        assert_eq!(
+            parse_command("notifications"),
+            Ok(NotificationsCommand::List)
+        );
+        assert_eq!(
+            parse_command("notifications list"),
+            Ok(NotificationsCommand::List)
+        );
+        assert_eq!(
+            parse_command("notifications retry all"),
+            Ok(NotificationsCommand::RetryAll)
+        );
+        assert_eq!(
+            parse_command("notifications retry A1B2C3D4"),
+            Ok(NotificationsCommand::Retry("a1b2c3d4".to_string()))
+        );
+        assert_eq!(
+            parse_command("notifications skip a1b2"),
+            Ok(NotificationsCommand::Skip("a1b2".to_string()))
+        );
+        assert_eq!(parse_command("notifications retry"), Err(USAGE.to_string()));
+        assert_eq!(
+            parse_command("notifications skip a1b2 extra"),
+            Err(USAGE.to_string())
+        );
+        assert!(parse_command("notifications skip a1b").is_err());
+        assert!(parse_command("notifications retry everything").is_err());
+    }
+}
@@ -0,0 +1,49 @@
+use crate::app_state::AppState;
+use crate::db::queries::paging_tests;
+use crate::error::{IncidentError, IncidentResult};
+use crate::slack::blocks;
+use chrono::Utc;
+use serde_json::json;
+use tracing::info;
+use uuid::Uuid;
+
+/// "Acknowledge" button on a test page; records the recipient's latency.
+pub async fn handle_paging_test_ack(
+    state: AppState,
+    user_id: String,
+    value: &str,
+    response_url: Option<String>,
+) -> IncidentResult<()> {
+    let test_id = Uuid::parse_str(value).map_err(|_| IncidentError::ValidationError {
+        field: "paging_test_id".to_string(),
+        reason: format!("Invalid paging test id '{}'", value),
+    })?;
+
+    let text = match paging_tests::acknowledge_page(&state.pool, test_id, &user_id, Utc::now())
+        .await?
+        .and_then(|page| page.ack_latency())
+    {
+        Some(latency) => {
+            info!(
+                "Paging test {} acknowledged by {} in {}s",
+                test_id,
+                user_id,
+                latency.num_seconds()
+            );
+            format!(
+                "✅ Test page acknowledged after {}. Thanks, pages reach you.",
+                blocks::latency_text(latency)
+            )
+        }
+        None => "This test page was already acknowledged.".to_string(),
+    };
+    let reply = vec![json!({
+        "type": "section",
+        "text": { "type": "mrkdwn", "text": text }
+    })];
+
+    match response_url {
+        Some(url) => state.slack_client.post_to_response_url(&url, reply).await,
+        None => state.slack_client.send_dm(&user_id, reply).await,
+    }
+}
@@ -1,11 +1,15 @@
+use crate::adapters::confluence::ConfluenceClient;
 use crate::app_state::AppState;
+use crate::db::models::{Incident, IncidentStatus};
 use crate::error::{IncidentError, IncidentResult};
+use crate::services::audit::AuditService;
 use crate::services::incident::IncidentService;
-use crate::services::postmortem::PostmortemService;
+use crate::services::postmortem::{self, PostmortemService};
 use crate::slack::blocks;
 use crate::slack::events::SlashCommandPayload;
+use chrono::{DateTime, Duration, Utc};
 use serde_json::json;
-use tracing::info;
+use tracing::{error, info};
 
 pub async fn handle_postmortem(
     state: AppState,
@@ -43,41 +47,62 @@ pub async fn handle_postmortem(
             .await;
     }
 
+    if incident.status == IncidentStatus::Canceled {
+        return state
+            .slack_client
+            .post_to_response_url(
+                &payload.response_url,
+                blocks::error_blocks(
+                    "This incident was canceled as a false alarm; it doesn't need a postmortem.",
+                ),
+            )
+            .await;
+    }
+
+    // `/incident postmortem publish` posts to Confluence instead of the channel
+    let subcommand = payload
+        .text
+        .trim()
+        .strip_prefix("postmortem")
+        .unwrap_or("")
+        .trim();
+    match subcommand {
+        "" => {}
+        "publish" => return publish(&state, &payload, &incident).await,
+        _ => {
+            return state
+                .slack_client
+                .post_to_response_url(
+                    &payload.response_url,
+                    blocks::error_blocks("Usage: /incident postmortem [publish]"),
+                )
+                .await;
+        }
+    }
+
     // Generate postmortem
     let postmortem_service = PostmortemService::new(state.pool.clone());
     let postmortem_md = postmortem_service.generate(&incident).await?;
 
-    // Post postmortem as code block
-    let postmortem_blocks = vec![
-        json!({
-            "type": "header",
-            "text": {
-                "type": "plain_text",
-                "text": "📋 Incident Postmortem Draft",
-            }
-        }),
-        json!({
-            "type": "section",
-            "text": {
-                "type": "mrkdwn",
-                "text": format!("```\n{}\n```", postmortem_md)
-            }
-        }),
-        json!({
-            "type": "context",
-            "elements": [{
-                "type": "mrkdwn",
-                "text": "_Edit this template and add action items, root cause analysis, and lessons learned._"
-            }]
-        }),
-    ];
-
     // Post to incident channel
+    let due_at = postmortem_service.due_date(incident.id).await?;
     if let Some(channel_id) = &incident.slack_channel_id {
-        state
-            .slack_client
-            .post_message(channel_id, postmortem_blocks)
-            .await?;
+        post_draft(&state, &incident, channel_id, &postmortem_md, due_at).await?;
+    }
+
+    // Scorecards count an incident's postmortem as done once a draft exists
+    if let Err(e) = AuditService::new(state.pool.clone())
+        .log_action(
+            Some(incident.id),
+            "generate_postmortem".to_string(),
+            payload.user_id.clone(),
+            None,
+            None,
+            None,
+        )
+        .await
+    {
+        error!("Failed to audit postmortem generation: {}", e);
     }
 
     info!("Postmortem generated for incident {}", incident.id);
@@ -97,3 +122,212 @@ pub async fn handle_postmortem(
         )
         .await
 }
+
+async fn publish(
+    state: &AppState,
+    payload: &SlashCommandPayload,
+    incident: &Incident,
+) -> IncidentResult<()> {
+    let Some((base_url, email, api_token, space_key)) = state.config.confluence_credentials()
+    else {
+        return reply(
+            state,
+            payload,
+            blocks::error_blocks(
+                "Confluence publishing is not configured. Set CONFLUENCE_BASE_URL, CONFLUENCE_EMAIL, CONFLUENCE_API_TOKEN and CONFLUENCE_SPACE_KEY.",
+            ),
+        )
+        .await;
+    };
+
+    let postmortem_service = PostmortemService::new(state.pool.clone());
+    if let Some(existing) = postmortem_service.get_published(incident).await? {
+        return reply(
+            state,
+            payload,
+            text_blocks(format!(
+                "ℹ️ Postmortem already published: <{}|Open in Confluence>",
+                existing.url
+            )),
+        )
+        .await;
+    }
+
+    let confluence = ConfluenceClient::new(
+        base_url.to_string(),
+        email.to_string(),
+        api_token.to_string(),
+    );
+    let postmortem = match postmortem_service
+        .publish(
+            &confluence,
+            space_key,
+            state.config.confluence_parent_page(),
+            incident,
+            payload.user_id.clone(),
+        )
+        .await
+    {
+        Ok(postmortem) => postmortem,
+        Err(IncidentError::ExternalAPIError { message, .. }) => {
+            error!(
+                "Failed to publish postmortem for incident {}: {}",
+                incident.id, message
+            );
+            return reply(
+                state,
+                payload,
+                blocks::error_blocks(&format!("Confluence rejected the page: {}", message)),
+            )
+            .await;
+        }
+        Err(e) => return Err(e),
+    };
+
+    if let Some(channel_id) = &incident.slack_channel_id {
+        if let Err(e) = state
+            .slack_client
+            .post_message(
+                channel_id,
+                blocks::postmortem_published_blocks(&postmortem.url, &payload.user_id),
+            )
+            .await
+        {
+            error!("Failed to announce published postmortem: {}", e);
+        }
+    }
+
+    reply(
+        state,
+        payload,
+        text_blocks(format!(
+            "✅ Postmortem published: <{}|Open in Confluence>",
+            postmortem.url
+        )),
+    )
+    .await
+}
+
+/// Require a postmortem for a just-resolved incident when its severity's
+/// policy (`POSTMORTEM_DUE_DAYS`) calls for one, and post a draft with the
+/// due date in the incident channel. Returns whether one was required.
+pub async fn require_postmortem(
+    state: &AppState,
+    incident: &Incident,
+    user_id: &str,
+) -> IncidentResult<bool> {
+    let Some(due_days) = state.config.postmortem_due_days_for(incident.severity) else {
+        return Ok(false);
+    };
+    let now = Utc::now();
+    let due_at = incident.resolved_at.unwrap_or(now) + Duration::days(due_days as i64);
+
+    let postmortem_service = PostmortemService::new(state.pool.clone());
+    if !postmortem_service.require(incident.id, due_at, now).await? {
+        return Ok(false);
+    }
+
+    if let Some(channel_id) = &incident.slack_channel_id {
+        let postmortem_md = postmortem_service.generate(incident).await?;
+        post_draft(state, incident, channel_id, &postmortem_md, Some(due_at)).await?;
+
+        AuditService::new(state.pool.clone())
+            .log_action(
+                Some(incident.id),
+                "generate_postmortem".to_string(),
+                user_id.to_string(),
+                None,
+                None,
+                Some(json!({ "due_at": due_at })),
+            )
+            .await?;
+    }
+
+    info!(
+        "Postmortem required for incident {} by {}",
+        incident.id, due_at
+    );
+    Ok(true)
+}
+
+/// Post a postmortem draft in the incident channel: a short message, then
+/// the full Markdown as a file, plus a PDF when `POSTMORTEM_PDF_COMMAND` is
+/// set. Long drafts would be cut off in a message, so the Markdown only goes
+/// inline if the upload fails.
+async fn post_draft(
+    state: &AppState,
+    incident: &Incident,
+    channel_id: &str,
+    markdown: &str,
+    due_at: Option<DateTime<Utc>>,
+) -> IncidentResult<()> {
+    let filename = format!("postmortem-{}", incident.reference().to_lowercase());
+    let title = format!("Postmortem: {}", incident.title);
+    state
+        .slack_client
+        .post_message(
+            channel_id,
+            blocks::postmortem_draft_blocks(&format!("{}.md", filename), due_at),
+        )
+        .await?;
+
+    if let Err(e) = state
+        .slack_client
+        .upload_file(
+            Some(channel_id),
+            &format!("{}.md", filename),
+            &title,
+            markdown.as_bytes().to_vec(),
+        )
+        .await
+    {
+        error!(
+            "Failed to upload postmortem for incident {}: {}",
+            incident.id, e
+        );
+        state
+            .slack_client
+            .post_message(channel_id, text_blocks(format!("```\n{}\n```", markdown)))
+            .await?;
+    }
+
+    let Some(command) = non_blank(&state.config.postmortem_pdf_command) else {
+        return Ok(());
+    };
+    let uploaded = async {
+        let pdf = postmortem::render_pdf(command, markdown).await?;
+        state
+            .slack_client
+            .upload_file(Some(channel_id), &format!("{}.pdf", filename), &title, pdf)
+            .await
+    };
+    if let Err(e) = uploaded.await {
+        error!(
+            "Failed to render or upload postmortem PDF for incident {}: {}",
+            incident.id, e
+        );
+    }
+    Ok(())
+}
+
+fn non_blank(value: &Option<String>) -> Option<&str> {
+    value.as_deref().filter(|value| !value.trim().is_empty())
+}
+
+fn text_blocks(text: String) -> Vec<serde_json::Value> {
+    vec![json!({
+        "type": "section",
+        "text": { "type": "mrkdwn", "text": text }
+    })]
+}
+
+async fn reply(
+    state: &AppState,
+    payload: &SlashCommandPayload,
+    blocks: Vec<serde_json::Value>,
+) -> IncidentResult<()> {
+    state
+        .slack_client
+        .post_to_response_url(&payload.response_url, blocks)
+        .await
+}
@@ -0,0 +1,439 @@
+use crate::app_state::AppState;
+use crate::db::models::{Incident, TimelineEventType};
+use crate::db::queries::premortems;
+use crate::error::{IncidentError, IncidentResult};
+use crate::services::audit::AuditService;
+use crate::services::timeline::TimelineService;
+use crate::slack::blocks;
+use crate::slack::events::{SlashCommandPayload, ViewPayload};
+use crate::slack::modals;
+use chrono::{DateTime, Duration, Utc};
+use serde_json::{json, Value};
+use tracing::{error, info};
+use uuid::Uuid;
+
+const MAX_WINDOW_HOURS: i64 = 720;
+
+/// The SERVICES entry named after `premortem`, ignoring case; `None` when
+/// no service was given.
+fn parse_command<'a>(text: &str, services: &'a [String]) -> Result<Option<&'a str>, String> {
+    let name = text
+        .trim()
+        .split_once(char::is_whitespace)
+        .map(|(_, rest)| rest.trim())
+        .unwrap_or_default();
+    if name.is_empty() {
+        return Ok(None);
+    }
+    services
+        .iter()
+        .find(|s| s.eq_ignore_ascii_case(name))
+        .map(|s| Some(s.as_str()))
+        .ok_or_else(|| {
+            format!(
+                "Unknown service '{}'. Services: {}",
+                name,
+                services.join(", ")
+            )
+        })
+}
+
+/// `/incident premortem <service>` — open the guided pre-mortem flow for a
+/// risky launch: the launch window, then one modal per anticipated failure
+/// mode. Without a service, lists pre-mortems whose window hasn't ended.
+pub async fn handle_premortem(state: AppState, payload: SlashCommandPayload) -> IncidentResult<()> {
+    let service = match parse_command(&payload.text, &state.config.services) {
+        Ok(service) => service,
+        Err(message) => {
+            return state
+                .slack_client
+                .post_to_response_url(&payload.response_url, blocks::error_blocks(&message))
+                .await
+        }
+    };
+
+    match service {
+        Some(service) => {
+            state
+                .slack_client
+                .open_modal(&payload.trigger_id, modals::premortem_launch_modal(service))
+                .await
+        }
+        None => {
+            let mut listed = Vec::new();
+            for premortem in premortems::list_upcoming(&state.pool, Utc::now()).await? {
+                let linked = premortems::linked_incidents(&state.pool, premortem.id)
+                    .await?
+                    .len();
+                listed.push((premortem, linked));
+            }
+            state
+                .slack_client
+                .post_to_response_url(
+                    &payload.response_url,
+                    blocks::premortem_list_blocks(&listed),
+                )
+                .await
+        }
+    }
+}
+
+/// Launch step submitted: save the pre-mortem and open the first failure
+/// mode step with the submission's trigger. Problems are reported by DM,
+/// since modal submissions have no response URL.
+pub async fn handle_launch_submission(
+    state: AppState,
+    view: ViewPayload,
+    user_id: String,
+    trigger_id: Option<String>,
+) -> IncidentResult<()> {
+    let service = view.private_metadata.trim();
+    let Some(service) = state
+        .config
+        .services
+        .iter()
+        .find(|s| s.eq_ignore_ascii_case(service))
+    else {
+        let message = format!("Unknown service '{}'", service);
+        return state
+            .slack_client
+            .send_dm(&user_id, blocks::error_blocks(&message))
+            .await;
+    };
+    let (launch_name, window_start, window_end) = match parse_launch(&view.state.values) {
+        Ok(launch) => launch,
+        Err(message) => {
+            return state
+                .slack_client
+                .send_dm(&user_id, blocks::error_blocks(&message))
+                .await
+        }
+    };
+
+    let premortem = premortems::create_premortem(
+        &state.pool,
+        service,
+        &launch_name,
+        window_start,
+        window_end,
+        &user_id,
+    )
+    .await?;
+    AuditService::new(state.pool.clone())
+        .log_action(
+            None,
+            "premortem_created".to_string(),
+            user_id.clone(),
+            None,
+            None,
+            Some(json!({
+                "premortem_id": premortem.id,
+                "service": premortem.service,
+                "launch_name": premortem.launch_name,
+                "window_start": premortem.window_start,
+                "window_end": premortem.window_end,
+            })),
+        )
+        .await?;
+    info!(
+        "Pre-mortem {} for {} started by {}",
+        premortem.id, premortem.service, user_id
+    );
+
+    next_step(&state, &user_id, trigger_id, premortem.id, 1).await
+}
+
+/// Failure mode step submitted: save it, then open the next step if asked
+/// for, or DM the finished pre-mortem.
+pub async fn handle_risk_submission(
+    state: AppState,
+    view: ViewPayload,
+    user_id: String,
+    trigger_id: Option<String>,
+) -> IncidentResult<()> {
+    let premortem_id =
+        Uuid::parse_str(&view.private_metadata).map_err(|_| IncidentError::ValidationError {
+            field: "premortem_id".to_string(),
+            reason: format!("Invalid pre-mortem id '{}'", view.private_metadata),
+        })?;
+    let form = match parse_risk(&view.state.values) {
+        Ok(form) => form,
+        Err(message) => {
+            return state
+                .slack_client
+                .send_dm(&user_id, blocks::error_blocks(&message))
+                .await
+        }
+    };
+
+    premortems::add_risk(
+        &state.pool,
+        premortem_id,
+        &form.failure_mode,
+        &form.likelihood,
+        form.mitigation.as_deref(),
+        &user_id,
+    )
+    .await?;
+
+    if form.another {
+        let count = premortems::list_risks(&state.pool, premortem_id)
+            .await?
+            .len();
+        return next_step(&state, &user_id, trigger_id, premortem_id, count + 1).await;
+    }
+    finish(&state, &user_id, premortem_id).await
+}
+
+/// Failure mode step closed with "Done": the pre-mortem is finished with
+/// what was recorded so far.
+pub async fn handle_risk_closed(
+    state: AppState,
+    view: ViewPayload,
+    user_id: String,
+) -> IncidentResult<()> {
+    let premortem_id =
+        Uuid::parse_str(&view.private_metadata).map_err(|_| IncidentError::ValidationError {
+            field: "premortem_id".to_string(),
+            reason: format!("Invalid pre-mortem id '{}'", view.private_metadata),
+        })?;
+    finish(&state, &user_id, premortem_id).await
+}
+
+/// Open failure mode step `number`. Without a usable trigger (it expires
+/// after 3 seconds) the flow ends with what was recorded so far.
+async fn next_step(
+    state: &AppState,
+    user_id: &str,
+    trigger_id: Option<String>,
+    premortem_id: Uuid,
+    number: usize,
+) -> IncidentResult<()> {
+    let premortem = premortems::get_premortem(&state.pool, premortem_id)
+        .await?
+        .ok_or(IncidentError::NotFound)?;
+    if let Some(trigger_id) = trigger_id.as_deref().filter(|t| !t.is_empty()) {
+        match state
+            .slack_client
+            .open_modal(trigger_id, modals::premortem_risk_modal(&premortem, number))
+            .await
+        {
+            Ok(()) => return Ok(()),
+            Err(e) => error!("Failed to open pre-mortem step {}: {}", number, e),
+        }
+    }
+    finish(state, user_id, premortem_id).await
+}
+
+async fn finish(state: &AppState, user_id: &str, premortem_id: Uuid) -> IncidentResult<()> {
+    let premortem = premortems::get_premortem(&state.pool, premortem_id)
+        .await?
+        .ok_or(IncidentError::NotFound)?;
+    let risks = premortems::list_risks(&state.pool, premortem_id).await?;
+    state
+        .slack_client
+        .send_dm(
+            user_id,
+            blocks::premortem_summary_blocks(&premortem, &risks),
+        )
+        .await
+}
+
+/// Link a newly declared incident to the pre-mortems whose launch window it
+/// falls in, and remind its channel what they anticipated. Failures are
+/// logged: the incident already exists.
+pub async fn link_declared(state: &AppState, incident: &Incident) {
+    let linked = match premortems::link_incident(
+        &state.pool,
+        incident.id,
+        &incident.affected_service,
+        incident.declared_at,
+    )
+    .await
+    {
+        Ok(linked) => linked,
+        Err(e) => {
+            error!(
+                "Failed to link incident {} to pre-mortems: {}",
+                incident.id, e
+            );
+            return;
+        }
+    };
+
+    let timeline = TimelineService::new(state.pool.clone());
+    for premortem in linked {
+        info!(
+            "Incident {} linked to pre-mortem {}",
+            incident.id, premortem.id
+        );
+        if let Err(e) = timeline
+            .log_event(
+                incident.id,
+                TimelineEventType::Note,
+                format!(
+                    "Linked to the pre-mortem of launch: {}",
+                    premortem.launch_name
+                ),
+                "system".to_string(),
+            )
+            .await
+        {
+            error!("Failed to log pre-mortem link: {}", e);
+        }
+        let Some(channel_id) = incident.slack_channel_id.as_deref() else {
+            continue;
+        };
+        let risks = match premortems::list_risks(&state.pool, premortem.id).await {
+            Ok(risks) => risks,
+            Err(e) => {
+                error!("Failed to load pre-mortem {} risks: {}", premortem.id, e);
+                continue;
+            }
+        };
+        if let Err(e) = state
+            .slack_client
+            .post_message(
+                channel_id,
+                blocks::premortem_linked_blocks(&premortem, &risks),
+            )
+            .await
+        {
+            error!("Failed to post pre-mortem to incident channel: {}", e);
+        }
+    }
+}
+
+fn parse_launch(
+    values: &serde_json::Map<String, Value>,
+) -> Result<(String, DateTime<Utc>, DateTime<Utc>), String> {
+    let field = |block: &str, action: &str| values.get(block).and_then(|v| v.get(action));
+
+    let launch_name = field("launch_block", "launch_input")
+        .and_then(|v| v.get("value"))
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .unwrap_or_default()
+        .to_string();
+    if launch_name.is_empty() || launch_name.chars().count() > 100 {
+        return Err("Launch name must be 1-100 characters".to_string());
+    }
+
+    let window_start = field("start_block", "start_input")
+        .and_then(|v| v.get("selected_date_time"))
+        .and_then(Value::as_i64)
+        .and_then(|secs| DateTime::from_timestamp(secs, 0))
+        .ok_or_else(|| "Pick when the launch window starts".to_string())?;
+
+    let hours = field("hours_block", "hours_input")
+        .and_then(|v| v.get("value"))
+        .and_then(Value::as_str)
+        .and_then(|v| v.trim().parse::<i64>().ok())
+        .filter(|h| (1..=MAX_WINDOW_HOURS).contains(h))
+        .ok_or_else(|| format!("Window length must be 1-{} hours", MAX_WINDOW_HOURS))?;
+
+    Ok((
+        launch_name,
+        window_start,
+        window_start + Duration::hours(hours),
+    ))
+}
+
+#[derive(Debug, PartialEq)]
+struct RiskForm {
+    failure_mode: String,
+    likelihood: String,
+    mitigation: Option<String>,
+    another: bool,
+}
+
+fn parse_risk(values: &serde_json::Map<String, Value>) -> Result<RiskForm, String> {
+    let field = |block: &str, action: &str| values.get(block).and_then(|v| v.get(action));
+    let text = |block: &str, action: &str| {
+        field(block, action)
+            .and_then(|v| v.get("value"))
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(str::to_string)
+    };
+
+    let failure_mode = text("failure_block", "failure_input")
+        .ok_or_else(|| "Describe what could go wrong".to_string())?;
+    let likelihood = field("likelihood_block", "likelihood_select")
+        .and_then(|v| v.get("selected_option"))
+        .and_then(|v| v.get("value"))
+        .and_then(Value::as_str)
+        .filter(|l| modals::PREMORTEM_LIKELIHOODS.contains(l))
+        .ok_or_else(|| "Pick a likelihood".to_string())?
+        .to_string();
+    let another = field("another_block", "another_input")
+        .and_then(|v| v.get("selected_options"))
+        .and_then(Value::as_array)
+        .is_some_and(|selected| !selected.is_empty());
+
+    Ok(RiskForm {
+        failure_mode,
+        likelihood,
+        mitigation: text("mitigation_block", "mitigation_input"),
+        another,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_command_matches_services_ignoring_case() {
+        let services = vec!["Okta SSO".to_string(), "VPN".to_string()];
+        assert_eq!(parse_command("premortem", &services), Ok(None));
+        assert_eq!(
+            parse_command("premortem okta sso", &services),
+            Ok(Some("Okta SSO"))
+        );
+        assert_eq!(
+            parse_command("premortem Billing", &services).unwrap_err(),
+            "Unknown service 'Billing'. Services: Okta SSO, VPN"
+        );
+    }
+
+    #[test]
+    fn test_parse_launch_and_risk_forms() {
+        let launch = json!({
+            "launch_block": { "launch_input": { "value": " Checkout v2 " } },
+            "start_block": { "start_input": { "selected_date_time": 1_767_225_600 } },
+            "hours_block": { "hours_input": { "value": "48" } },
+        });
+        let (name, start, end) = parse_launch(launch.as_object().unwrap()).unwrap();
+        assert_eq!(name, "Checkout v2");
+        assert_eq!(start.to_rfc3339(), "2026-01-01T00:00:00+00:00");
+        assert_eq!(end - start, Duration::hours(48));
+
+        let too_long = json!({
+            "launch_block": { "launch_input": { "value": "Checkout v2" } },
+            "start_block": { "start_input": { "selected_date_time": 1_767_225_600 } },
+            "hours_block": { "hours_input": { "value": "721" } },
+        });
+        assert_eq!(
+            parse_launch(too_long.as_object().unwrap()).unwrap_err(),
+            "Window length must be 1-720 hours"
+        );
+
+        let risk = json!({
+            "failure_block": { "failure_input": { "value": "Payment retries double charge" } },
+            "likelihood_block": { "likelihood_select": { "selected_option": { "value": "high" } } },
+            "mitigation_block": { "mitigation_input": { "value": null } },
+            "another_block": { "another_input": { "selected_options": [{ "value": "another" }] } },
+        });
+        assert_eq!(
+            parse_risk(risk.as_object().unwrap()).unwrap(),
+            RiskForm {
+                failure_mode: "Payment retries double charge".to_string(),
+                likelihood: "high".to_string(),
+                mitigation: None,
+                another: true,
+            }
+        );
+    }
+}
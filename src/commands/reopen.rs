@@ -0,0 +1,130 @@
+use crate::app_state::AppState;
+use crate::db::models::{Incident, IncidentStatus};
+use crate::error::{IncidentError, IncidentResult};
+use crate::services::incident::IncidentService;
+use crate::services::notification::NotificationService;
+use crate::services::permissions::{Action, Permissions};
+use crate::slack::blocks;
+use crate::slack::events::SlashCommandPayload;
+use tracing::{error, info};
+
+/// `/incident reopen [reason]` — reopen this channel's incident after it was
+/// resolved prematurely. Commander only.
+pub async fn handle_reopen(state: AppState, payload: SlashCommandPayload) -> IncidentResult<()> {
+    let incident_service =
+        IncidentService::new(state.pool.clone()).with_permissions(Permissions::from_state(&state));
+    let incident = match incident_service
+        .get_latest_by_channel(&payload.channel_id)
+        .await
+    {
+        Ok(inc) => inc,
+        Err(IncidentError::NotFound) => {
+            return reply(&state, &payload, "No incident found in this channel").await;
+        }
+        Err(e) => return Err(e),
+    };
+
+    if let Err(IncidentError::PermissionDenied { .. }) = incident_service
+        .authorize(Action::Reopen, &incident, &payload.user_id)
+        .await
+    {
+        return state
+            .slack_client
+            .post_to_response_url(
+                &payload.response_url,
+                blocks::permission_denied_blocks("reopen the incident"),
+            )
+            .await;
+    }
+
+    if !incident.status.is_terminal() {
+        return reply(&state, &payload, "This incident is still open").await;
+    }
+    if incident.status == IncidentStatus::Canceled {
+        return reply(
+            &state,
+            &payload,
+            "This incident was canceled as a false alarm; declare a new incident instead",
+        )
+        .await;
+    }
+    if incident.channel_archived_at.is_some() {
+        return reply(
+            &state,
+            &payload,
+            "This incident's channel has been archived; declare a new incident instead",
+        )
+        .await;
+    }
+
+    let reason = payload
+        .text
+        .trim()
+        .split_once(char::is_whitespace)
+        .map(|(_, reason)| reason.trim())
+        .filter(|reason| !reason.is_empty());
+    reopen_and_announce(&state, &incident, &payload.user_id, reason).await?;
+
+    state
+        .slack_client
+        .post_to_response_url(
+            &payload.response_url,
+            vec![serde_json::json!({
+                "type": "section",
+                "text": {
+                    "type": "mrkdwn",
+                    "text": "🔁 Incident reopened"
+                }
+            })],
+        )
+        .await
+}
+
+/// Reopen a resolved incident, re-notify the channels its declaration went
+/// to, and put Statuspage back into the degraded state. Callers are
+/// responsible for the commander and resolved-state checks.
+pub async fn reopen_and_announce(
+    state: &AppState,
+    incident: &Incident,
+    user_id: &str,
+    reason: Option<&str>,
+) -> IncidentResult<Incident> {
+    let reopened = IncidentService::new(state.pool.clone())
+        .reopen_incident(incident.id, user_id.to_string(), reason.map(str::to_string))
+        .await?;
+
+    let notification_service = NotificationService::new(
+        state.pool.clone(),
+        state.slack_client.clone(),
+        state.config.clone(),
+    );
+    if let Err(e) = notification_service
+        .notify_reopened(
+            &reopened,
+            blocks::incident_reopened_blocks(&reopened, user_id, reason),
+        )
+        .await
+    {
+        error!("Failed to announce reopened incident: {}", e);
+    }
+
+    crate::jobs::statuspage_sync::enqueue_for_incident(&state.pool, &state.job_sender, &reopened)
+        .await;
+    crate::jobs::statuspage_sync::enqueue_status_change(&state.job_sender, &reopened);
+    crate::jobs::channel_status::enqueue(state, &reopened);
+    crate::services::pinned_summary::refresh(state, &reopened).await;
+
+    info!("Incident {} reopened by {}", incident.id, user_id);
+    Ok(reopened)
+}
+
+async fn reply(
+    state: &AppState,
+    payload: &SlashCommandPayload,
+    message: &str,
+) -> IncidentResult<()> {
+    state
+        .slack_client
+        .post_to_response_url(&payload.response_url, blocks::error_blocks(message))
+        .await
+}
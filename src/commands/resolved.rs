@@ -1,14 +1,20 @@
 use crate::app_state::AppState;
+use crate::db::models::{Incident, IncidentStatus, WebhookEvent};
 use crate::error::{IncidentError, IncidentResult};
 use crate::services::incident::IncidentService;
 use crate::services::notification::NotificationService;
+use crate::services::permissions::{Action, Permissions};
+use crate::services::webhook;
 use crate::slack::blocks;
 use crate::slack::events::SlashCommandPayload;
+use serde_json::json;
 use tracing::{error, info};
+use uuid::Uuid;
 
 pub async fn handle_resolved(state: AppState, payload: SlashCommandPayload) -> IncidentResult<()> {
     // Get incident from channel
-    let incident_service = IncidentService::new(state.pool.clone());
+    let incident_service =
+        IncidentService::new(state.pool.clone()).with_permissions(Permissions::from_state(&state));
     let incident = match incident_service
         .get_latest_by_channel(&payload.channel_id)
         .await
@@ -26,9 +32,8 @@ pub async fn handle_resolved(state: AppState, payload: SlashCommandPayload) -> I
         Err(e) => return Err(e),
     };
 
-    // Validate commander
     if let Err(IncidentError::PermissionDenied { .. }) = incident_service
-        .validate_commander(&incident, &payload.user_id)
+        .authorize(Action::Resolve, &incident, &payload.user_id)
         .await
     {
         return state
@@ -42,6 +47,11 @@ pub async fn handle_resolved(state: AppState, payload: SlashCommandPayload) -> I
 
     // Check if already resolved
     if incident.status.is_terminal() {
+        let text = if incident.status == IncidentStatus::Canceled {
+            "⚪ Incident was canceled as a false alarm"
+        } else {
+            "✅ Incident is already resolved"
+        };
         return state
             .slack_client
             .post_to_response_url(
@@ -50,20 +60,48 @@ pub async fn handle_resolved(state: AppState, payload: SlashCommandPayload) -> I
                     "type": "section",
                     "text": {
                         "type": "mrkdwn",
-                        "text": "✅ Incident is already resolved"
+                        "text": text
                     }
                 })],
             )
             .await;
     }
 
-    // Resolve incident
+    resolve_and_announce(&state, &incident, &payload.user_id).await?;
+
+    // Acknowledge via response_url
+    state
+        .slack_client
+        .post_to_response_url(
+            &payload.response_url,
+            vec![serde_json::json!({
+                "type": "section",
+                "text": {
+                    "type": "mrkdwn",
+                    "text": "✅ Incident marked as resolved"
+                }
+            })],
+        )
+        .await
+}
+
+/// Resolve an open incident, announce it, require a postmortem if its
+/// severity calls for one, offer the commander a template from it, and
+/// sync Statuspage. Callers are
+/// responsible for the commander and terminal-state checks.
+pub async fn resolve_and_announce(
+    state: &AppState,
+    incident: &Incident,
+    user_id: &str,
+) -> IncidentResult<Incident> {
+    let incident_service =
+        IncidentService::new(state.pool.clone()).with_permissions(Permissions::from_state(state));
     let resolved_incident = incident_service
-        .resolve_incident(incident.id, payload.user_id.clone())
+        .resolve_incident(incident.id, user_id.to_string())
         .await?;
 
     // Post resolution to channel
-    let resolution_blocks = blocks::resolution_blocks(&resolved_incident, &payload.user_id);
+    let resolution_blocks = blocks::resolution_blocks(&resolved_incident, user_id);
 
     if let Some(_channel_id) = &resolved_incident.slack_channel_id {
         let notification_service = NotificationService::new(
@@ -80,42 +118,77 @@ pub async fn handle_resolved(state: AppState, payload: SlashCommandPayload) -> I
         }
     }
 
-    // Enqueue Statuspage sync if component mapping exists
-    if let Ok(Some(component_id)) = crate::db::queries::statuspage::get_component_id(
-        &state.pool,
-        &resolved_incident.affected_service,
-    )
-    .await
+    if let Err(e) =
+        crate::commands::postmortem::require_postmortem(state, &resolved_incident, user_id).await
     {
-        let job = crate::jobs::Job::StatuspageSync {
-            incident_id: resolved_incident.id,
-            component_id,
-            status: resolved_incident.status,
-            severity: resolved_incident.severity,
-        };
+        error!("Failed to require postmortem: {}", e);
+    }
 
-        if let Err(e) = state.job_sender.send(job) {
-            error!("Failed to enqueue Statuspage sync job: {}", e);
-        }
+    if let Err(e) =
+        crate::commands::template::prompt_template_from_incident(state, &resolved_incident).await
+    {
+        error!("Failed to send template prompt: {}", e);
     }
 
+    // Enqueue Statuspage sync if component mapping exists
+    crate::jobs::statuspage_sync::enqueue_for_incident(
+        &state.pool,
+        &state.job_sender,
+        &resolved_incident,
+    )
+    .await;
+    crate::jobs::statuspage_sync::enqueue_status_change(&state.job_sender, &resolved_incident);
+    crate::jobs::channel_status::enqueue(state, &resolved_incident);
+    crate::services::pinned_summary::refresh(state, &resolved_incident).await;
+    crate::jobs::resolution_snapshot::enqueue(state, &resolved_incident);
+    webhook::enqueue(
+        state,
+        WebhookEvent::Resolved,
+        &resolved_incident,
+        Some(json!({ "status": incident.status })),
+    )
+    .await;
+
     info!(
         "Incident {} resolved by {} (duration: {:?} min)",
-        incident.id, payload.user_id, resolved_incident.duration_minutes
+        incident.id, user_id, resolved_incident.duration_minutes
     );
 
-    // Acknowledge via response_url
-    state
-        .slack_client
-        .post_to_response_url(
-            &payload.response_url,
-            vec![serde_json::json!({
-                "type": "section",
-                "text": {
-                    "type": "mrkdwn",
-                    "text": "✅ Incident marked as resolved"
-                }
-            })],
-        )
+    Ok(resolved_incident)
+}
+
+/// "Resolve" button on the App Home tab. `value` is the incident id. There is
+/// no response_url for Home actions, so problems are reported by DM and the
+/// Home tab is republished either way.
+pub async fn handle_home_resolve(
+    state: AppState,
+    user_id: String,
+    value: &str,
+) -> IncidentResult<()> {
+    let incident_id = Uuid::parse_str(value).map_err(|_| IncidentError::ValidationError {
+        field: "incident_id".to_string(),
+        reason: format!("Invalid incident id '{}'", value),
+    })?;
+
+    let incident_service =
+        IncidentService::new(state.pool.clone()).with_permissions(Permissions::from_state(&state));
+    let incident = incident_service.get_by_id(incident_id).await?;
+
+    if incident_service
+        .authorize(Action::Resolve, &incident, &user_id)
         .await
+        .is_err()
+    {
+        state
+            .slack_client
+            .send_dm(
+                &user_id,
+                blocks::permission_denied_blocks("resolve the incident"),
+            )
+            .await?;
+    } else if !incident.status.is_terminal() {
+        resolve_and_announce(&state, &incident, &user_id).await?;
+    }
+
+    crate::slack::home::publish_home(&state, &user_id).await
 }
@@ -0,0 +1,156 @@
+use crate::app_state::AppState;
+use crate::db::models::{Incident, IncidentId};
+use crate::error::{IncidentError, IncidentResult};
+use crate::services::incident::IncidentService;
+use crate::services::roles::{role_label, RoleService};
+use crate::slack::blocks;
+use crate::slack::events::SlashCommandPayload;
+use tracing::{error, info};
+
+/// `/incident roles` — show the role roster with claim buttons for unfilled roles.
+pub async fn handle_roles(state: AppState, payload: SlashCommandPayload) -> IncidentResult<()> {
+    let incident_service = IncidentService::new(state.pool.clone());
+    let incident = match incident_service.get_by_channel(&payload.channel_id).await {
+        Ok(inc) => inc,
+        Err(IncidentError::NotFound) => {
+            return state
+                .slack_client
+                .post_to_response_url(
+                    &payload.response_url,
+                    blocks::error_blocks("No active incident in this channel"),
+                )
+                .await;
+        }
+        Err(e) => return Err(e),
+    };
+
+    let required = state.config.required_roles_for(incident.severity);
+    let status = RoleService::new(state.pool.clone())
+        .status(&incident, &required)
+        .await?;
+
+    state
+        .slack_client
+        .post_to_response_url(
+            &payload.response_url,
+            blocks::role_claim_blocks(&incident, &status.filled, &status.unfilled),
+        )
+        .await
+}
+
+/// "Claim <role>" button click. `value` is `<incident_id>:<role>`.
+pub async fn handle_claim_role(
+    state: AppState,
+    user_id: String,
+    value: &str,
+    response_url: Option<String>,
+) -> IncidentResult<()> {
+    let (incident_id, role) =
+        parse_claim_value(value).ok_or_else(|| IncidentError::ValidationError {
+            field: "value".to_string(),
+            reason: format!("Malformed claim_role value '{}'", value),
+        })?;
+
+    let incident = IncidentService::new(state.pool.clone())
+        .get_by_id(incident_id)
+        .await?;
+    let required = state.config.required_roles_for(incident.severity);
+    let role_service = RoleService::new(state.pool.clone());
+
+    match role_service
+        .claim(&incident, &required, role, &user_id)
+        .await
+    {
+        Ok(_) => {}
+        Err(IncidentError::ValidationError { reason, .. }) => {
+            if let Some(url) = response_url {
+                state
+                    .slack_client
+                    .post_to_response_url(&url, blocks::error_blocks(&reason))
+                    .await?;
+            }
+            return Ok(());
+        }
+        Err(e) => return Err(e),
+    }
+
+    info!(
+        "{} claimed {} for incident {}",
+        user_id,
+        role_label(role),
+        incident.id
+    );
+
+    // Repost the roster so the channel sees who holds what and what is still open
+    let status = role_service.status(&incident, &required).await?;
+    if let Some(channel_id) = &incident.slack_channel_id {
+        if let Err(e) = state
+            .slack_client
+            .post_message(
+                channel_id,
+                blocks::role_claim_blocks(&incident, &status.filled, &status.unfilled),
+            )
+            .await
+        {
+            error!("Failed to post role roster: {}", e);
+        }
+    }
+
+    Ok(())
+}
+
+/// Post the claim prompt to the incident channel if any required role is
+/// unfilled. Returns whether a prompt was posted.
+pub async fn prompt_unfilled_roles(state: &AppState, incident: &Incident) -> IncidentResult<bool> {
+    let Some(channel_id) = &incident.slack_channel_id else {
+        return Ok(false);
+    };
+
+    let required = state.config.required_roles_for(incident.severity);
+    if required.is_empty() {
+        return Ok(false);
+    }
+
+    let status = RoleService::new(state.pool.clone())
+        .status(incident, &required)
+        .await?;
+    if status.is_complete() {
+        return Ok(false);
+    }
+
+    state
+        .slack_client
+        .post_message(
+            channel_id,
+            blocks::role_claim_blocks(incident, &status.filled, &status.unfilled),
+        )
+        .await?;
+    Ok(true)
+}
+
+fn parse_claim_value(value: &str) -> Option<(IncidentId, &str)> {
+    let (id, role) = value.split_once(':')?;
+    let id = id.parse().ok()?;
+    if role.is_empty() {
+        return None;
+    }
+    Some((id, role))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_parse_claim_value() {
+        let id = Uuid::new_v4();
+        assert_eq!(
+            parse_claim_value(&format!("{}:comms_lead", id)),
+            Some((id, "comms_lead"))
+        );
+        assert_eq!(parse_claim_value(&format!("{}:", id)), None);
+        assert_eq!(parse_claim_value("not-a-uuid:scribe"), None);
+        assert_eq!(parse_claim_value("comms_lead"), None);
+    }
+}
@@ -0,0 +1,147 @@
+use crate::app_state::AppState;
+use crate::config::{AppConfig, ExecTier, NotificationEvent, NotificationRule};
+use crate::db::models::Severity;
+use crate::error::IncidentResult;
+use crate::services::permissions::Permissions;
+use crate::slack::blocks;
+use crate::slack::events::SlashCommandPayload;
+
+const SEVERITIES: [Severity; 4] = [Severity::P1, Severity::P2, Severity::P3, Severity::P4];
+
+/// `/incident routing` — admin-only view of where declare, escalation and
+/// resolution notifications go for each severity.
+pub async fn handle_routing(state: AppState, payload: SlashCommandPayload) -> IncidentResult<()> {
+    let blocks = if Permissions::from_state(&state)
+        .is_admin(&payload.user_id)
+        .await
+    {
+        let mut sections = routing_sections(&state.config);
+        let mut disabled = state
+            .service_catalog
+            .disabled(&state.pool)
+            .await?
+            .into_iter()
+            .collect::<Vec<_>>();
+        if !disabled.is_empty() {
+            disabled.sort();
+            sections.push(format!(
+                "*Disabled services* _(`/incident service enable <name>` to restore)_\nNot offered for new incidents or matched to alerts: {}",
+                disabled.join(", ")
+            ));
+        }
+        blocks::notification_routing_blocks(&sections)
+    } else {
+        blocks::error_blocks("Only bot admins can view notification routing")
+    };
+    state
+        .slack_client
+        .post_to_response_url(&payload.response_url, blocks)
+        .await
+}
+
+/// One mrkdwn section per severity with the effective rule for each event,
+/// then one for the services with their own channels.
+fn routing_sections(config: &AppConfig) -> Vec<String> {
+    let mut sections: Vec<String> = SEVERITIES
+        .iter()
+        .map(|&severity| {
+            let configured = config
+                .notification_rules
+                .keys()
+                .any(|key| key.parse::<Severity>().ok() == Some(severity));
+            let source = match (configured, severity) {
+                (true, _) => "NOTIFICATION_RULES",
+                (false, Severity::P1) => "default: P1_CHANNELS, P1_USERS, P1_USER_GROUPS",
+                (false, Severity::P2) => "default: P2_CHANNELS",
+                (false, _) => "default",
+            };
+            let mut events = NotificationEvent::ALL
+                .iter()
+                .map(|&event| {
+                    format!(
+                        "• {}: {}",
+                        event.as_key(),
+                        describe_rule(&config.notification_rule_for(severity, event))
+                    )
+                })
+                .collect::<Vec<_>>();
+            let tiers = config.exec_tiers_for(severity);
+            events.extend(
+                tiers
+                    .tiers()
+                    .into_iter()
+                    .filter(|(_, _, recipients)| !recipients.is_empty())
+                    .map(|(tier, after_minutes, recipients)| {
+                        format!(
+                            "• exec tier {} ({}): {}",
+                            tier,
+                            if after_minutes == 0 {
+                                "on declaration".to_string()
+                            } else {
+                                format!("after {} min unresolved", after_minutes)
+                            },
+                            describe_exec_tier(recipients)
+                        )
+                    }),
+            );
+            format!(
+                "*{}* _({})_\n{}",
+                severity.label(),
+                source,
+                events.join("\n")
+            )
+        })
+        .collect();
+
+    let mut services = config
+        .service_channels
+        .iter()
+        .filter(|(_, channels)| !channels.is_empty())
+        .collect::<Vec<_>>();
+    if !services.is_empty() {
+        services.sort();
+        let lines = services
+            .iter()
+            .map(|(service, channels)| {
+                format!(
+                    "• {}: {}",
+                    service,
+                    channels
+                        .iter()
+                        .map(|c| format!("<#{}>", c))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            })
+            .collect::<Vec<_>>();
+        sections.push(format!(
+            "*Service channels* _(SERVICE_CHANNELS, added for every severity and event)_\n{}",
+            lines.join("\n")
+        ));
+    }
+    sections
+}
+
+fn describe_exec_tier(tier: &ExecTier) -> String {
+    tier.users
+        .iter()
+        .map(|u| format!("<@{}>", u))
+        .chain(tier.user_groups.iter().map(|g| format!("<!subteam^{}>", g)))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn describe_rule(rule: &NotificationRule) -> String {
+    let recipients = rule
+        .channels
+        .iter()
+        .map(|c| format!("<#{}>", c))
+        .chain(rule.users.iter().map(|u| format!("<@{}>", u)))
+        .chain(rule.user_groups.iter().map(|g| format!("<!subteam^{}>", g)))
+        .collect::<Vec<_>>();
+    if recipients.is_empty() {
+        "incident channel only".to_string()
+    } else {
+        format!("incident channel, {}", recipients.join(", "))
+    }
+}
@@ -0,0 +1,192 @@
+use crate::app_state::AppState;
+use crate::db::queries::incidents::{self, IncidentSearch};
+use crate::error::{IncidentError, IncidentResult};
+use crate::slack::blocks;
+use crate::slack::events::SlashCommandPayload;
+use crate::utils::{incident_number, severity};
+use chrono::NaiveDate;
+
+const USAGE: &str = "Usage: /incident search [<number>] [service:<name>] [sev:P1-P4] [status:<status>] [after:YYYY-MM-DD] [text:\"...\"]";
+
+/// Results per page; the "Next page" button fetches the following page.
+pub const PAGE_SIZE: i64 = 10;
+
+/// Split on whitespace, keeping quoted runs (straight or curly quotes)
+/// together and dropping the quotes, so `service:"API Gateway"` is one token.
+fn tokenize(text: &str) -> Result<Vec<String>, String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    for c in text.chars() {
+        match c {
+            '"' | '“' | '”' => quoted = !quoted,
+            c if c.is_whitespace() && !quoted => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if quoted {
+        return Err("Missing closing quote".to_string());
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    Ok(tokens)
+}
+
+/// Parse the filters after `search`. Words without a `key:` prefix are
+/// added to the text filter.
+fn parse_search(query: &str) -> Result<IncidentSearch, String> {
+    let mut search = IncidentSearch::default();
+    let mut words = Vec::new();
+
+    for token in tokenize(query)? {
+        let Some((key, value)) = token.split_once(':') else {
+            words.push(token);
+            continue;
+        };
+        if value.is_empty() {
+            return Err(format!("Missing value for '{}:'", key));
+        }
+        match key.to_ascii_lowercase().as_str() {
+            "service" => search.service = Some(value.to_string()),
+            // Matches every severity on the same routing tier
+            "sev" | "severity" => search.severity = Some(severity::parse(value)?.tier),
+            "status" => search.status = Some(value.parse()?),
+            "after" => {
+                search.declared_after = Some(
+                    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                        .map_err(|_| format!("Invalid date '{}', expected YYYY-MM-DD", value))?,
+                )
+            }
+            "text" => words.push(value.to_string()),
+            // Not a filter, e.g. "error:timeout" in free text
+            _ => words.push(token),
+        }
+    }
+
+    if let [word] = words.as_slice() {
+        search.number = incident_number::parse(word);
+    }
+    if !words.is_empty() {
+        search.text = Some(words.join(" "));
+    }
+    if search == IncidentSearch::default() {
+        return Err(USAGE.to_string());
+    }
+    Ok(search)
+}
+
+/// `/incident search <filters>`; works from any channel.
+pub async fn handle_search(state: AppState, payload: SlashCommandPayload) -> IncidentResult<()> {
+    let query = payload
+        .text
+        .trim()
+        .strip_prefix("search")
+        .unwrap_or("")
+        .trim();
+    post_page(&state, &payload.response_url, query, 0).await
+}
+
+/// "Next page" button on search results. `value` is `<offset>:<query>`.
+pub async fn handle_search_page(
+    state: AppState,
+    value: &str,
+    response_url: Option<String>,
+) -> IncidentResult<()> {
+    let (offset, query) = value
+        .split_once(':')
+        .and_then(|(offset, query)| Some((offset.parse::<i64>().ok()?, query)))
+        .ok_or_else(|| IncidentError::ValidationError {
+            field: "value".to_string(),
+            reason: format!("Malformed search page value '{}'", value),
+        })?;
+    let Some(response_url) = response_url else {
+        return Ok(());
+    };
+    post_page(&state, &response_url, query, offset.max(0)).await
+}
+
+async fn post_page(
+    state: &AppState,
+    response_url: &str,
+    query: &str,
+    offset: i64,
+) -> IncidentResult<()> {
+    let search = match parse_search(query) {
+        Ok(search) => search,
+        Err(message) => {
+            return state
+                .slack_client
+                .post_to_response_url(response_url, blocks::error_blocks(&message))
+                .await;
+        }
+    };
+
+    // One extra row tells us whether there is a next page
+    let mut results =
+        incidents::search_incidents(&state.pool, &search, PAGE_SIZE + 1, offset).await?;
+    let has_more = results.len() as i64 > PAGE_SIZE;
+    results.truncate(PAGE_SIZE as usize);
+
+    state
+        .slack_client
+        .post_to_response_url(
+            response_url,
+            blocks::search_results_blocks(query, &results, offset, has_more),
+        )
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::models::{IncidentStatus, Severity};
+
+    #[test]
+    fn test_parse_search_filters() {
+        let search = parse_search(
+            "service:payments sev:p1 status:resolved after:2024-10-01 text:\"timeout\"",
+        )
+        .unwrap();
+        assert_eq!(
+            search,
+            IncidentSearch {
+                service: Some("payments".to_string()),
+                severity: Some(Severity::P1),
+                status: Some(IncidentStatus::Resolved),
+                declared_after: NaiveDate::from_ymd_opt(2024, 10, 1),
+                text: Some("timeout".to_string()),
+                number: None,
+            }
+        );
+
+        let search = parse_search("plat-12").unwrap();
+        assert_eq!(search.number, Some(("PLAT".to_string(), 12)));
+        assert_eq!(search.text.as_deref(), Some("plat-12"));
+
+        let search = parse_search("service:“API Gateway” connection reset").unwrap();
+        assert_eq!(search.service.as_deref(), Some("API Gateway"));
+        assert_eq!(search.text.as_deref(), Some("connection reset"));
+    }
+
+    #[test]
+    fn test_parse_search_rejects_bad_input() {
+        assert_eq!(parse_search("").unwrap_err(), USAGE);
+        assert_eq!(
+            parse_search("sev:P9").unwrap_err(),
+            "Invalid severity 'P9'. Use P1, P2, P3 or P4"
+        );
+        assert_eq!(
+            parse_search("after:October").unwrap_err(),
+            "Invalid date 'October', expected YYYY-MM-DD"
+        );
+        assert_eq!(
+            parse_search("text:\"open").unwrap_err(),
+            "Missing closing quote"
+        );
+    }
+}
@@ -0,0 +1,171 @@
+use crate::app_state::AppState;
+use crate::error::IncidentResult;
+use crate::services::audit::AuditService;
+use crate::services::permissions::Permissions;
+use crate::slack::blocks;
+use crate::slack::events::SlashCommandPayload;
+use serde_json::{json, Value};
+use tracing::info;
+
+const USAGE: &str = "Usage: /incident service [list] | enable <name> | disable <name>";
+
+#[derive(Debug, PartialEq)]
+enum ServiceCommand<'a> {
+    List,
+    Enable(&'a str),
+    Disable(&'a str),
+}
+
+/// Parse "service <action> <name>". Service names may contain spaces.
+fn parse_command(text: &str) -> Result<ServiceCommand<'_>, String> {
+    let args = text
+        .trim()
+        .split_once(char::is_whitespace)
+        .map_or("", |(_, rest)| rest.trim());
+    let (action, name) = args
+        .split_once(char::is_whitespace)
+        .map_or((args, ""), |(action, name)| (action, name.trim()));
+    match (action, name) {
+        ("" | "list", "") => Ok(ServiceCommand::List),
+        ("enable", name) if !name.is_empty() => Ok(ServiceCommand::Enable(name)),
+        ("disable", name) if !name.is_empty() => Ok(ServiceCommand::Disable(name)),
+        _ => Err(USAGE.to_string()),
+    }
+}
+
+/// `/incident service [list] | enable <name> | disable <name>` — admin-only
+/// switch for hiding one of `SERVICES` (say, a deprecated one) from the
+/// declare modal, the incident API and alert mapping without a redeploy.
+pub async fn handle_service(state: AppState, payload: SlashCommandPayload) -> IncidentResult<()> {
+    let blocks = if !Permissions::from_state(&state)
+        .is_admin(&payload.user_id)
+        .await
+    {
+        blocks::error_blocks("Only bot admins can enable or disable services")
+    } else {
+        match parse_command(&payload.text) {
+            Err(message) => blocks::error_blocks(&message),
+            Ok(command) => run(&state, command, &payload.user_id).await?,
+        }
+    };
+
+    state
+        .slack_client
+        .post_to_response_url(&payload.response_url, blocks)
+        .await
+}
+
+async fn run(
+    state: &AppState,
+    command: ServiceCommand<'_>,
+    user_id: &str,
+) -> IncidentResult<Vec<Value>> {
+    let (enable, name) = match command {
+        ServiceCommand::List => {
+            let disabled = state.service_catalog.disabled(&state.pool).await?;
+            let lines = state
+                .config
+                .services
+                .iter()
+                .map(|service| {
+                    if disabled.contains(service) {
+                        format!("• ~{}~ _(disabled)_", service)
+                    } else {
+                        format!("• {}", service)
+                    }
+                })
+                .collect::<Vec<_>>();
+            return Ok(text_blocks(&format!(
+                "*Services*\n{}\n_Hide one from new incidents with `/incident service disable <name>`._",
+                lines.join("\n")
+            )));
+        }
+        ServiceCommand::Enable(name) => (true, name),
+        ServiceCommand::Disable(name) => (false, name),
+    };
+
+    let Some(service) = state
+        .config
+        .services
+        .iter()
+        .find(|s| s.eq_ignore_ascii_case(name))
+    else {
+        return Ok(blocks::error_blocks(&format!(
+            "Unknown service '{}'. Services: {}",
+            name,
+            state.config.services.join(", ")
+        )));
+    };
+
+    let catalog = &state.service_catalog;
+    let changed = if enable {
+        catalog.enable(&state.pool, service).await?
+    } else {
+        catalog.disable(&state.pool, service, user_id).await?
+    };
+    if !changed {
+        return Ok(text_blocks(&format!(
+            "*{}* is already {}",
+            service,
+            if enable { "enabled" } else { "disabled" }
+        )));
+    }
+
+    let action = if enable {
+        "service_enabled"
+    } else {
+        "service_disabled"
+    };
+    AuditService::new(state.pool.clone())
+        .log_action(
+            None,
+            action.to_string(),
+            user_id.to_string(),
+            None,
+            None,
+            Some(json!({ "service": service })),
+        )
+        .await?;
+    info!("Service {} {} by {}", service, action, user_id);
+
+    Ok(text_blocks(&if enable {
+        format!(
+            "✅ *{}* is offered for new incidents and matched to alerts again",
+            service
+        )
+    } else {
+        format!(
+            "🚫 *{}* is no longer offered for new incidents or matched to alerts. Open incidents on it carry on; `/incident service enable {}` restores it.",
+            service, service
+        )
+    }))
+}
+
+fn text_blocks(text: &str) -> Vec<Value> {
+    vec![json!({
+        "type": "section",
+        "text": { "type": "mrkdwn", "text": text }
+    })]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_command() {
+        assert_eq!(parse_command("service"), Ok(ServiceCommand::List));
+        assert_eq!(parse_command("service list"), Ok(ServiceCommand::List));
+        assert_eq!(
+            parse_command("service disable  Legacy API "),
+            Ok(ServiceCommand::Disable("Legacy API"))
+        );
+        assert_eq!(
+            parse_command("service enable vpn"),
+            Ok(ServiceCommand::Enable("vpn"))
+        );
+        assert_eq!(parse_command("service disable"), Err(USAGE.to_string()));
+        assert_eq!(parse_command("service list vpn"), Err(USAGE.to_string()));
+        assert_eq!(parse_command("service remove vpn"), Err(USAGE.to_string()));
+    }
+}
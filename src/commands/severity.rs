@@ -1,10 +1,14 @@
 use crate::app_state::AppState;
-use crate::db::models::Severity;
+use crate::db::models::WebhookEvent;
 use crate::error::{IncidentError, IncidentResult};
 use crate::services::incident::IncidentService;
 use crate::services::notification::NotificationService;
+use crate::services::permissions::{Action, Permissions};
+use crate::services::webhook;
 use crate::slack::blocks;
 use crate::slack::events::SlashCommandPayload;
+use crate::utils::severity;
+use serde_json::json;
 use tracing::{error, info};
 
 pub async fn handle_severity(state: AppState, payload: SlashCommandPayload) -> IncidentResult<()> {
@@ -14,24 +18,28 @@ pub async fn handle_severity(state: AppState, payload: SlashCommandPayload) -> I
     let severity_str = if parts.len() > 1 {
         parts[1].trim()
     } else {
+        let codes: Vec<&str> = severity::levels()
+            .iter()
+            .map(|level| level.code.as_str())
+            .collect();
         return state
             .slack_client
             .post_to_response_url(
                 &payload.response_url,
-                blocks::error_blocks("Usage: /incident severity [P1|P2|P3|P4] [optional reason]"),
+                blocks::error_blocks(&format!(
+                    "Usage: /incident severity [{}] [optional reason]",
+                    codes.join("|")
+                )),
             )
             .await;
     };
 
-    let new_severity: Severity = match severity_str.parse() {
-        Ok(s) => s,
-        Err(_) => {
+    let new_level = match severity::parse(severity_str) {
+        Ok(level) => level,
+        Err(message) => {
             return state
                 .slack_client
-                .post_to_response_url(
-                    &payload.response_url,
-                    blocks::error_blocks("Invalid severity. Use P1, P2, P3, or P4"),
-                )
+                .post_to_response_url(&payload.response_url, blocks::error_blocks(&message))
                 .await;
         }
     };
@@ -43,7 +51,8 @@ pub async fn handle_severity(state: AppState, payload: SlashCommandPayload) -> I
     };
 
     // Get incident from channel
-    let incident_service = IncidentService::new(state.pool.clone());
+    let incident_service =
+        IncidentService::new(state.pool.clone()).with_permissions(Permissions::from_state(&state));
     let incident = match incident_service.get_by_channel(&payload.channel_id).await {
         Ok(inc) => inc,
         Err(IncidentError::NotFound) => {
@@ -58,9 +67,8 @@ pub async fn handle_severity(state: AppState, payload: SlashCommandPayload) -> I
         Err(e) => return Err(e),
     };
 
-    // Validate commander
     if let Err(IncidentError::PermissionDenied { .. }) = incident_service
-        .validate_commander(&incident, &payload.user_id)
+        .authorize(Action::ChangeSeverity, &incident, &payload.user_id)
         .await
     {
         return state
@@ -73,7 +81,7 @@ pub async fn handle_severity(state: AppState, payload: SlashCommandPayload) -> I
     }
 
     // Check if already at this severity
-    if incident.severity == new_severity {
+    if incident.severity_level().code == new_level.code {
         return state
             .slack_client
             .post_to_response_url(
@@ -82,7 +90,7 @@ pub async fn handle_severity(state: AppState, payload: SlashCommandPayload) -> I
                     "type": "section",
                     "text": {
                         "type": "mrkdwn",
-                        "text": format!("Incident is already {}", new_severity.label())
+                        "text": format!("Incident is already {}", new_level.label)
                     }
                 })],
             )
@@ -90,22 +98,18 @@ pub async fn handle_severity(state: AppState, payload: SlashCommandPayload) -> I
     }
 
     // Change severity
-    let (updated_incident, old_severity) = incident_service
+    let (updated_incident, old_level) = incident_service
         .change_severity(
             incident.id,
-            new_severity,
+            new_level,
             payload.user_id.clone(),
             reason.clone(),
         )
         .await?;
 
     // Post to channel
-    let severity_blocks = blocks::severity_change_blocks(
-        old_severity,
-        new_severity,
-        &payload.user_id,
-        reason.as_deref(),
-    );
+    let severity_blocks =
+        blocks::severity_change_blocks(old_level, new_level, &payload.user_id, reason.as_deref());
 
     if let Some(_channel_id) = &updated_incident.slack_channel_id {
         let notification_service = NotificationService::new(
@@ -115,7 +119,7 @@ pub async fn handle_severity(state: AppState, payload: SlashCommandPayload) -> I
         );
 
         if let Err(e) = notification_service
-            .notify_severity_change(&updated_incident, old_severity, severity_blocks)
+            .notify_severity_change(&updated_incident, old_level, severity_blocks)
             .await
         {
             error!("Failed to post severity change: {}", e);
@@ -123,27 +127,28 @@ pub async fn handle_severity(state: AppState, payload: SlashCommandPayload) -> I
     }
 
     // Enqueue Statuspage sync if component mapping exists
-    if let Ok(Some(component_id)) = crate::db::queries::statuspage::get_component_id(
+    crate::jobs::statuspage_sync::enqueue_for_incident(
         &state.pool,
-        &updated_incident.affected_service,
+        &state.job_sender,
+        &updated_incident,
     )
-    .await
-    {
-        let job = crate::jobs::Job::StatuspageSync {
-            incident_id: updated_incident.id,
-            component_id,
-            status: updated_incident.status,
-            severity: updated_incident.severity,
-        };
-
-        if let Err(e) = state.job_sender.send(job) {
-            error!("Failed to enqueue Statuspage sync job: {}", e);
-        }
-    }
+    .await;
+    webhook::enqueue(
+        &state,
+        WebhookEvent::SeverityChanged,
+        &updated_incident,
+        Some(json!({ "severity": old_level.tier, "severity_code": old_level.code })),
+    )
+    .await;
+    // The index topic breaks open incidents down by severity
+    crate::jobs::channel_status::enqueue(&state, &updated_incident);
+    crate::services::pinned_summary::refresh(&state, &updated_incident).await;
+    // Escalated to P1: the major incident manager joins as for a declared P1
+    crate::commands::mim::page_major_incident_manager(&state, &updated_incident).await;
 
     info!(
-        "Severity changed for incident {} from {:?} to {:?} by {}",
-        incident.id, old_severity, new_severity, payload.user_id
+        "Severity changed for incident {} from {} to {} by {}",
+        incident.id, old_level.code, new_level.code, payload.user_id
     );
 
     // Acknowledge via response_url
@@ -155,7 +160,7 @@ pub async fn handle_severity(state: AppState, payload: SlashCommandPayload) -> I
                 "type": "section",
                 "text": {
                     "type": "mrkdwn",
-                    "text": format!("✅ Severity changed to {}", new_severity.label())
+                    "text": format!("✅ Severity changed to {}", new_level.label)
                 }
             })],
         )
@@ -0,0 +1,483 @@
+use crate::app_state::AppState;
+use crate::config::{AppConfig, NotificationEvent};
+use crate::db::models::{Incident, IncidentStatus, Severity};
+use crate::error::IncidentResult;
+use crate::services::audit::AuditService;
+use crate::services::notification::{add_exec_targets, plan_notifications, NotificationTarget};
+use crate::services::permissions::{FieldVisibility, Permissions};
+use crate::services::roles::role_label;
+use crate::slack::blocks;
+use crate::slack::events::SlashCommandPayload;
+use crate::utils::channel::generate_channel_name;
+use chrono::{NaiveDate, Utc};
+use serde_json::json;
+use tracing::{error, info};
+use uuid::Uuid;
+
+const USAGE: &str = "Usage: /incident simulate declare [P1|P2|P3|P4] [service]";
+
+/// Inputs gathered before planning so `plan_declare` stays pure.
+struct DeclareSimulation<'a> {
+    incident_id: Uuid,
+    severity: Severity,
+    service: &'a str,
+    commander_id: &'a str,
+    date: NaiveDate,
+    channel_name_taken: bool,
+    statuspage_component: Option<String>,
+}
+
+/// `/incident simulate declare P1 api-gateway` — admin-only dry run of the
+/// declare path. Nothing is written to the database and no real incident
+/// channel, invite, notification, or Statuspage update is made; the only Slack
+/// writes are a preview post to `SIMULATION_CHANNEL` (if configured) and the
+/// ephemeral trace.
+pub async fn handle_simulate(state: AppState, payload: SlashCommandPayload) -> IncidentResult<()> {
+    if !Permissions::from_state(&state)
+        .is_admin(&payload.user_id)
+        .await
+    {
+        return reply(
+            &state,
+            &payload,
+            blocks::error_blocks("Only bot admins can run simulations"),
+        )
+        .await;
+    }
+
+    let (severity, service) = match parse_command(&payload.text, &state.config.services) {
+        Ok(parsed) => parsed,
+        Err(message) => return reply(&state, &payload, blocks::error_blocks(&message)).await,
+    };
+
+    let incident_id = Uuid::new_v4();
+    let date = Utc::now().date_naive();
+    let mut trace = Vec::new();
+
+    // Read-only Slack call: verifies the token works and detects name collisions
+    let channel_name = generate_channel_name(&service, date, incident_id);
+    let channel_name_taken = match state.slack_client.list_conversations().await {
+        Ok(channels) => channels.iter().any(|c| c.name == channel_name),
+        Err(e) => {
+            trace.push(format!(
+                "⚠️ Could not list channels (conversations.list): {}",
+                e
+            ));
+            false
+        }
+    };
+
+    let statuspage_component =
+        crate::db::queries::statuspage::get_component_id(&state.pool, &service).await?;
+
+    trace.extend(plan_declare(
+        &state.config,
+        &DeclareSimulation {
+            incident_id,
+            severity,
+            service: &service,
+            commander_id: &payload.user_id,
+            date,
+            channel_name_taken,
+            statuspage_component,
+        },
+    ));
+
+    // Exercise the real posting path against the sandbox channel
+    match &state.config.simulation_channel {
+        Some(sandbox) => {
+            let preview = preview_incident(
+                incident_id,
+                state.config.incident_prefix_for(&service, false),
+                severity,
+                &service,
+                &payload.user_id,
+            );
+            match state
+                .slack_client
+                .post_message(
+                    sandbox,
+                    blocks::incident_declared_blocks(
+                        &preview,
+                        &FieldVisibility::shared(&state.config),
+                    ),
+                )
+                .await
+            {
+                Ok(_) => trace.push(format!("✅ Preview posted to sandbox <#{}>", sandbox)),
+                Err(e) => trace.push(format!("❌ Sandbox post to <#{}> failed: {}", sandbox, e)),
+            }
+        }
+        None => trace.push("ℹ️ SIMULATION_CHANNEL not set; no sandbox post".to_string()),
+    }
+
+    if let Err(e) = AuditService::new(state.pool.clone())
+        .log_action(
+            None,
+            "simulate_declare".to_string(),
+            payload.user_id.clone(),
+            None,
+            None,
+            Some(json!({ "severity": severity, "service": service })),
+        )
+        .await
+    {
+        error!("Failed to audit simulation: {}", e);
+    }
+
+    info!(
+        "Declare simulation for {} {} by {}",
+        severity.as_db_str(),
+        service,
+        payload.user_id
+    );
+
+    reply(&state, &payload, blocks::simulation_trace_blocks(&trace)).await
+}
+
+fn parse_command(text: &str, services: &[String]) -> Result<(Severity, String), String> {
+    // text is "simulate declare <severity> <service...>"
+    let mut parts = text.trim().splitn(4, ' ').skip(1);
+    if parts.next() != Some("declare") {
+        return Err(USAGE.to_string());
+    }
+
+    let severity = parts
+        .next()
+        .unwrap_or("")
+        .parse::<Severity>()
+        .map_err(|_| USAGE.to_string())?;
+
+    // Service names may contain spaces
+    let service = parts.next().unwrap_or("").trim();
+    if service.is_empty() {
+        return Err(USAGE.to_string());
+    }
+    if !services.iter().any(|s| s == service) {
+        return Err(format!(
+            "Unknown service '{}'. Configured: {}",
+            service,
+            services.join(", ")
+        ));
+    }
+
+    Ok((severity, service.to_string()))
+}
+
+/// Step-by-step description of what `/incident declare` would do.
+fn plan_declare(config: &AppConfig, sim: &DeclareSimulation) -> Vec<String> {
+    let mut trace = Vec::new();
+
+    let base_name = generate_channel_name(sim.service, sim.date, sim.incident_id);
+    let channel_name = if sim.channel_name_taken {
+        format!("{}-{}", base_name, &sim.incident_id.to_string()[..8])
+    } else {
+        base_name.clone()
+    };
+    if sim.channel_name_taken {
+        trace.push(format!(
+            "1. Create channel #{} (#{} is taken)",
+            channel_name, base_name
+        ));
+    } else {
+        trace.push(format!("1. Create channel #{}", channel_name));
+    }
+
+    let mut invitees = vec![sim.commander_id.to_string()];
+    if let Some(owners) = config.service_owners.get(sim.service) {
+        invitees.extend(owners.clone());
+    }
+    invitees.sort();
+    invitees.dedup();
+    trace.push(format!(
+        "2. Invite {}",
+        invitees
+            .iter()
+            .map(|u| format!("<@{}>", u))
+            .collect::<Vec<_>>()
+            .join(", ")
+    ));
+
+    trace.push(format!(
+        "3. Number it {}-<next> and post and pin incident details",
+        config.incident_prefix_for(sim.service, false)
+    ));
+
+    let roles = config.required_roles_for(sim.severity);
+    if roles.is_empty() {
+        trace.push("4. No required roles beyond commander".to_string());
+    } else {
+        trace.push(format!(
+            "4. Prompt for required roles: {}",
+            roles
+                .iter()
+                .map(|r| role_label(r))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    }
+
+    let mut targets = plan_notifications(
+        config,
+        sim.severity,
+        NotificationEvent::Declared,
+        sim.service,
+        Some(&format!("#{}", channel_name)),
+    );
+    add_exec_targets(&mut targets, config.exec_tiers_for(sim.severity).tier1);
+    let targets = targets
+        .iter()
+        .map(|t| match t {
+            NotificationTarget::Channel(c) if c.starts_with('#') => format!("post to {}", c),
+            NotificationTarget::Channel(c) => format!("post to <#{}>", c),
+            NotificationTarget::Dm(u) => format!("DM <@{}>", u),
+            NotificationTarget::UserGroup(g) => format!("DM members of <!subteam^{}>", g),
+        })
+        .collect::<Vec<_>>();
+    trace.push(format!("5. Notify: {}", targets.join(", ")));
+
+    let statuspage_enabled =
+        config.statuspage_api_key.is_some() && config.statuspage_page_id.is_some();
+    let statuspage = match (&sim.statuspage_component, statuspage_enabled) {
+        (Some(component), true) => format!("sync component `{}`", component),
+        (Some(_), false) => "skipped (Statuspage not configured)".to_string(),
+        (None, _) => format!("skipped (no component mapping for {})", sim.service),
+    };
+    trace.push(format!("6. Statuspage: {}", statuspage));
+
+    trace
+}
+
+/// Numbered 0 in `number_prefix`, as no number is taken.
+fn preview_incident(
+    incident_id: Uuid,
+    number_prefix: &str,
+    severity: Severity,
+    service: &str,
+    commander_id: &str,
+) -> Incident {
+    let now = Utc::now();
+    Incident {
+        id: incident_id,
+        number_prefix: number_prefix.to_string(),
+        number: 0,
+        slack_channel_id: None,
+        title: "[SIMULATION] Declare dry run".to_string(),
+        severity,
+        severity_code: None,
+        status: IncidentStatus::Declared,
+        affected_service: service.to_string(),
+        commander_id: commander_id.to_string(),
+        declared_at: now,
+        resolved_at: None,
+        duration_minutes: None,
+        pinned_message_ts: None,
+        is_quiet: false,
+        statuspage_incident_id: None,
+        canceled_at: None,
+        cancel_reason: None,
+        triaged_at: None,
+        is_triage: false,
+        channel_archived_at: None,
+        bridge_url: None,
+        custom_fields: Default::default(),
+        template_id: None,
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+async fn reply(
+    state: &AppState,
+    payload: &SlashCommandPayload,
+    blocks: Vec<serde_json::Value>,
+) -> IncidentResult<()> {
+    state
+        .slack_client
+        .post_to_response_url(&payload.response_url, blocks)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::DataResidency;
+    use std::collections::HashMap;
+
+    fn config() -> AppConfig {
+        AppConfig {
+            slack_bot_token: "xoxb-test".to_string(),
+            slack_signing_secret: "secret".to_string(),
+            database_url: "postgres://localhost/test".to_string(),
+            statuspage_api_key: None,
+            statuspage_page_id: None,
+            statuspage_timeout_secs: 10,
+            statuspage_max_retries: 3,
+            statuspage_circuit_breaker_threshold: 5,
+            statuspage_circuit_breaker_cooldown_secs: 300,
+            statuspage_api_url: None,
+            jira_base_url: None,
+            jira_email: None,
+            jira_api_token: None,
+            jira_projects: HashMap::new(),
+            github_webhook_secret: None,
+            github_repos: HashMap::new(),
+            confluence_base_url: None,
+            confluence_email: None,
+            confluence_api_token: None,
+            confluence_space_key: None,
+            confluence_parent_page_id: None,
+            conference_provider: None,
+            conference_client_id: None,
+            conference_client_secret: None,
+            conference_account_id: None,
+            conference_refresh_token: None,
+            oncall_provider: None,
+            oncall_api_token: None,
+            oncall_api_url: None,
+            oncall_schedules: HashMap::new(),
+            api_token: None,
+            banner_api_key: None,
+            slack_max_retries: 3,
+            artifact_store: crate::config::ArtifactBackend::Local,
+            artifact_dir: "artifacts".to_string(),
+            artifact_public_url: None,
+            artifact_signing_secret: None,
+            artifact_bucket: None,
+            artifact_region: None,
+            artifact_endpoint: None,
+            artifact_access_key_id: None,
+            artifact_secret_access_key: None,
+            artifact_url_ttl_seconds: 900,
+            data_residency: DataResidency::Unrestricted,
+            integration_regions: HashMap::new(),
+            slack_retry_base_ms: 500,
+            slack_rate_limit_per_minute: 30,
+            webhook_max_retries: 5,
+            webhook_retry_base_ms: 1000,
+            slack_transport: crate::config::SlackTransport::Http,
+            slack_app_token: None,
+            host: "0.0.0.0".to_string(),
+            port: 3000,
+            shutdown_timeout_secs: 25,
+            p1_users: vec!["U_EXEC".to_string()],
+            p1_user_groups: vec![],
+            p2_channels: vec!["C_ENG".to_string()],
+            p1_channels: vec!["C_GENERAL".to_string()],
+            notification_rules: HashMap::new(),
+            exec_tiers: HashMap::new(),
+            severities: Vec::new(),
+            service_owners: HashMap::from([(
+                "API Gateway".to_string(),
+                vec!["U_OWNER".to_string()],
+            )]),
+            service_channels: HashMap::new(),
+            services: vec!["API Gateway".to_string(), "vpn".to_string()],
+            required_roles: HashMap::from([("P1".to_string(), vec!["comms_lead".to_string()])]),
+            role_reminder_minutes: 15,
+            restricted_fields: HashMap::new(),
+            stale_incident_minutes: HashMap::new(),
+            postmortem_due_days: HashMap::new(),
+            postmortem_reminder_hours: 24,
+            postmortem_pdf_command: None,
+            channel_archive_after_days: 0,
+            commander_absence_minutes: 20,
+            backup_commanders: vec![],
+            mim_users: vec![],
+            mim_schedule: None,
+            mim_role: crate::config::MimRole::Advisor,
+            deactivated_user_check_hours: 6,
+            paging_test_day: 0,
+            paging_test_ack_minutes: 60,
+            paging_test_channel: None,
+            sla: HashMap::new(),
+            teams: HashMap::new(),
+            alertmanager_routes: vec![],
+            datadog_routes: vec![],
+            digest_channel: None,
+            weekly_report_channel: None,
+            load_report_utc_offset_hours: 0,
+            admin_users: vec!["U_ADMIN".to_string()],
+            admin_user_groups: vec![],
+            reporting_users: vec![],
+            reporting_user_groups: vec![],
+            simulation_channel: None,
+            security_user_group: None,
+            incident_number_prefix: "INC".to_string(),
+            security_incident_prefix: None,
+            partner_channels: HashMap::new(),
+            partner_mirror_delay_minutes: 15,
+            partner_redact_domains: vec![],
+            alert_source_tokens: HashMap::new(),
+            timeline_reaction: "pushpin".to_string(),
+            channel_status_indicator: crate::config::ChannelStatusIndicator::Off,
+            incident_index_channel: None,
+            time_format: crate::config::ClockFormat::TwentyFourHour,
+            date_format: crate::config::DateFormat::Ymd,
+        }
+    }
+
+    fn simulation(severity: Severity, taken: bool) -> DeclareSimulation<'static> {
+        DeclareSimulation {
+            incident_id: Uuid::parse_str("12345678-0000-0000-0000-000000000000").unwrap(),
+            severity,
+            service: "API Gateway",
+            commander_id: "U_ADMIN",
+            date: NaiveDate::from_ymd_opt(2024, 11, 15).unwrap(),
+            channel_name_taken: taken,
+            statuspage_component: Some("cmp-1".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_parse_command_accepts_services_with_spaces() {
+        let services = config().services;
+        assert_eq!(
+            parse_command("simulate declare p1 API Gateway", &services).unwrap(),
+            (Severity::P1, "API Gateway".to_string())
+        );
+        assert!(parse_command("simulate declare P1 dns", &services)
+            .unwrap_err()
+            .contains("Unknown service"));
+        assert_eq!(
+            parse_command("simulate declare P7 vpn", &services).unwrap_err(),
+            USAGE
+        );
+        assert_eq!(
+            parse_command("simulate resolve", &services).unwrap_err(),
+            USAGE
+        );
+    }
+
+    #[test]
+    fn test_plan_declare_p1_traces_every_step() {
+        let trace = plan_declare(&config(), &simulation(Severity::P1, false));
+
+        assert_eq!(trace[0], "1. Create channel #inc-20241115-api-gateway");
+        assert_eq!(trace[1], "2. Invite <@U_ADMIN>, <@U_OWNER>");
+        assert_eq!(trace[3], "4. Prompt for required roles: Comms Lead");
+        assert_eq!(
+            trace[4],
+            "5. Notify: post to #inc-20241115-api-gateway, post to <#C_GENERAL>, DM <@U_EXEC>"
+        );
+        assert_eq!(
+            trace[5],
+            "6. Statuspage: skipped (Statuspage not configured)"
+        );
+    }
+
+    #[test]
+    fn test_plan_declare_reports_name_collision_and_lower_severity_routing() {
+        let trace = plan_declare(&config(), &simulation(Severity::P3, true));
+
+        assert_eq!(
+            trace[0],
+            "1. Create channel #inc-20241115-api-gateway-12345678 (#inc-20241115-api-gateway is taken)"
+        );
+        assert_eq!(trace[3], "4. No required roles beyond commander");
+        assert_eq!(
+            trace[4],
+            "5. Notify: post to #inc-20241115-api-gateway-12345678"
+        );
+    }
+}
@@ -1,23 +1,50 @@
 use crate::app_state::AppState;
+use crate::db::models::{Audience, Incident, StatusDraft};
+use crate::db::queries::{roles as role_queries, status_drafts};
 use crate::error::{IncidentError, IncidentResult};
+use crate::services::audit::AuditService;
 use crate::services::incident::IncidentService;
 use crate::services::notification::NotificationService;
+use crate::services::permissions::{Action, Permissions};
 use crate::slack::blocks;
 use crate::slack::events::SlashCommandPayload;
+use chrono::Utc;
+use serde_json::{json, Value};
 use tracing::{error, info};
+use uuid::Uuid;
+
+const USAGE: &str = "Usage: /incident status [--public|--internal] [--draft] [message]";
+
+/// Incident role whose holder may draft status updates for the commander.
+pub const SCRIBE_ROLE: &str = "scribe";
+
+/// Split leading `--public` / `--internal` / `--draft` flags off the message.
+/// Updates are internal unless explicitly marked public.
+fn parse_flags(text: &str) -> (Audience, bool, &str) {
+    let mut audience = Audience::Internal;
+    let mut draft = false;
+    let mut rest = text.trim();
+    loop {
+        let (flag, remainder) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+        match flag {
+            "--public" => audience = Audience::Public,
+            "--internal" => audience = Audience::Internal,
+            "--draft" => draft = true,
+            _ => return (audience, draft, rest),
+        }
+        rest = remainder.trim();
+    }
+}
 
 pub async fn handle_status(state: AppState, payload: SlashCommandPayload) -> IncidentResult<()> {
     // Extract message from command text (everything after "status")
     let parts: Vec<&str> = payload.text.splitn(2, ' ').collect();
-    let message = if parts.len() > 1 {
-        parts[1].trim()
+    let (audience, draft, message) = if parts.len() > 1 {
+        parse_flags(parts[1])
     } else {
         return state
             .slack_client
-            .post_to_response_url(
-                &payload.response_url,
-                blocks::error_blocks("Usage: /incident status [message]"),
-            )
+            .post_to_response_url(&payload.response_url, blocks::error_blocks(USAGE))
             .await;
     };
 
@@ -32,7 +59,8 @@ pub async fn handle_status(state: AppState, payload: SlashCommandPayload) -> Inc
     }
 
     // Get incident from channel
-    let incident_service = IncidentService::new(state.pool.clone());
+    let incident_service =
+        IncidentService::new(state.pool.clone()).with_permissions(Permissions::from_state(&state));
     let incident = match incident_service.get_by_channel(&payload.channel_id).await {
         Ok(inc) => inc,
         Err(IncidentError::NotFound) => {
@@ -47,11 +75,14 @@ pub async fn handle_status(state: AppState, payload: SlashCommandPayload) -> Inc
         Err(e) => return Err(e),
     };
 
-    // Validate commander
+    // Whoever may post directly does so; `--draft` only matters for the scribe
     if let Err(IncidentError::PermissionDenied { .. }) = incident_service
-        .validate_commander(&incident, &payload.user_id)
+        .authorize(Action::PostStatusUpdate, &incident, &payload.user_id)
         .await
     {
+        if draft {
+            return submit_draft(&state, &incident, &payload, message, audience).await;
+        }
         return state
             .slack_client
             .post_to_response_url(
@@ -61,14 +92,283 @@ pub async fn handle_status(state: AppState, payload: SlashCommandPayload) -> Inc
             .await;
     }
 
-    // Post status update
-    let updated_incident = incident_service
-        .post_status_update(incident.id, message.to_string(), payload.user_id.clone())
+    post_update_and_announce(&state, &incident, message, &payload.user_id, audience).await?;
+
+    // Acknowledge via response_url
+    state
+        .slack_client
+        .post_to_response_url(
+            &payload.response_url,
+            vec![serde_json::json!({
+                "type": "section",
+                "text": {
+                    "type": "mrkdwn",
+                    "text": match audience {
+                        Audience::Public => "✅ Status update posted and shared on the public status page",
+                        Audience::Internal => "✅ Status update posted",
+                    }
+                }
+            })],
+        )
+        .await
+}
+
+/// Hold a scribe's update for the commander: store it and DM the commander
+/// an approve/discard prompt.
+async fn submit_draft(
+    state: &AppState,
+    incident: &Incident,
+    payload: &SlashCommandPayload,
+    message: &str,
+    audience: Audience,
+) -> IncidentResult<()> {
+    let is_scribe = role_queries::list_roles(&state.pool, incident.id)
+        .await?
+        .iter()
+        .any(|r| r.role == SCRIBE_ROLE && r.user_id == payload.user_id);
+    if !is_scribe {
+        return state
+            .slack_client
+            .post_to_response_url(
+                &payload.response_url,
+                blocks::error_blocks(
+                    "Only the incident's scribe can draft status updates for the commander",
+                ),
+            )
+            .await;
+    }
+
+    let draft = status_drafts::create_draft(
+        &state.pool,
+        incident.id,
+        &payload.user_id,
+        message,
+        audience,
+    )
+    .await?;
+    AuditService::new(state.pool.clone())
+        .log_action(
+            Some(incident.id),
+            "draft_status_update".to_string(),
+            payload.user_id.clone(),
+            None,
+            None,
+            Some(json!({ "draft_id": draft.id, "message": message, "audience": audience })),
+        )
+        .await?;
+
+    state
+        .slack_client
+        .send_dm(
+            &incident.commander_id,
+            blocks::status_draft_blocks(incident, &draft),
+        )
+        .await?;
+    info!(
+        "{} drafted a status update for incident {}",
+        payload.user_id, incident.id
+    );
+
+    state
+        .slack_client
+        .post_to_response_url(
+            &payload.response_url,
+            text_blocks(&format!(
+                "✍️ Draft sent to <@{}> for approval",
+                incident.commander_id
+            )),
+        )
+        .await
+}
+
+/// "Approve & post" button on a draft DM. The commander (or an admin) posts
+/// the update, credited to both of them.
+pub async fn handle_approve_draft(
+    state: AppState,
+    user_id: String,
+    value: &str,
+    response_url: Option<String>,
+) -> IncidentResult<()> {
+    decide_draft(state, user_id, value, response_url, true).await
+}
+
+/// "Discard" button on a draft DM.
+pub async fn handle_discard_draft(
+    state: AppState,
+    user_id: String,
+    value: &str,
+    response_url: Option<String>,
+) -> IncidentResult<()> {
+    decide_draft(state, user_id, value, response_url, false).await
+}
+
+async fn decide_draft(
+    state: AppState,
+    user_id: String,
+    value: &str,
+    response_url: Option<String>,
+    approve: bool,
+) -> IncidentResult<()> {
+    let draft_id = Uuid::parse_str(value).map_err(|_| IncidentError::ValidationError {
+        field: "draft_id".to_string(),
+        reason: format!("Invalid draft id '{}'", value),
+    })?;
+    let draft = status_drafts::get_draft(&state.pool, draft_id)
+        .await?
+        .ok_or(IncidentError::NotFound)?;
+    let incident_service =
+        IncidentService::new(state.pool.clone()).with_permissions(Permissions::from_state(&state));
+    let incident = incident_service.get_by_id(draft.incident_id).await?;
+
+    let reply = if let Some(approved) = draft.approved {
+        text_blocks(if approved {
+            "This draft was already posted."
+        } else {
+            "This draft was already discarded."
+        })
+    } else if incident.status.is_terminal() {
+        blocks::error_blocks("This incident is closed; the draft can no longer be posted")
+    } else if incident_service
+        .authorize(Action::PostStatusUpdate, &incident, &user_id)
+        .await
+        .is_err()
+    {
+        blocks::permission_denied_blocks("approve drafted status updates")
+    } else {
+        match status_drafts::decide_draft(&state.pool, draft.id, &user_id, approve, Utc::now())
+            .await?
+        {
+            None => text_blocks("This draft was already handled."),
+            Some(draft) if approve => post_draft(&state, &incident, &draft, &user_id).await?,
+            Some(draft) => discard_draft(&state, &draft, &user_id).await?,
+        }
+    };
+
+    match response_url {
+        Some(url) => state.slack_client.post_to_response_url(&url, reply).await,
+        None => state.slack_client.send_dm(&user_id, reply).await,
+    }
+}
+
+async fn post_draft(
+    state: &AppState,
+    incident: &Incident,
+    draft: &StatusDraft,
+    user_id: &str,
+) -> IncidentResult<Vec<Value>> {
+    post_and_announce(
+        state,
+        incident,
+        &draft.message,
+        user_id,
+        Some(&draft.drafted_by),
+        draft.audience,
+    )
+    .await?;
+
+    if draft.drafted_by != user_id {
+        if let Err(e) = state
+            .slack_client
+            .send_dm(
+                &draft.drafted_by,
+                text_blocks(&format!(
+                    "✅ <@{}> approved and posted your status update for *{}*",
+                    user_id, incident.title
+                )),
+            )
+            .await
+        {
+            error!("Failed to tell scribe their draft was posted: {}", e);
+        }
+    }
+    Ok(text_blocks(&format!(
+        "✅ Posted <@{}>'s status update",
+        draft.drafted_by
+    )))
+}
+
+async fn discard_draft(
+    state: &AppState,
+    draft: &StatusDraft,
+    user_id: &str,
+) -> IncidentResult<Vec<Value>> {
+    AuditService::new(state.pool.clone())
+        .log_action(
+            Some(draft.incident_id),
+            "discard_status_draft".to_string(),
+            user_id.to_string(),
+            None,
+            None,
+            Some(json!({ "draft_id": draft.id, "drafted_by": draft.drafted_by })),
+        )
+        .await?;
+
+    if draft.drafted_by != user_id {
+        if let Err(e) = state
+            .slack_client
+            .send_dm(
+                &draft.drafted_by,
+                text_blocks(&format!(
+                    "🗑️ <@{}> discarded your draft status update:\n>{}",
+                    user_id, draft.message
+                )),
+            )
+            .await
+        {
+            error!("Failed to tell scribe their draft was discarded: {}", e);
+        }
+    }
+    Ok(text_blocks("🗑️ Draft discarded"))
+}
+
+fn text_blocks(text: &str) -> Vec<Value> {
+    vec![json!({
+        "type": "section",
+        "text": { "type": "mrkdwn", "text": text }
+    })]
+}
+
+/// Record a status update and send it wherever status updates are routed;
+/// public ones also go to Statuspage. Callers are responsible for the
+/// commander check.
+pub async fn post_update_and_announce(
+    state: &AppState,
+    incident: &Incident,
+    message: &str,
+    user_id: &str,
+    audience: Audience,
+) -> IncidentResult<Incident> {
+    post_and_announce(state, incident, message, user_id, None, audience).await
+}
+
+async fn post_and_announce(
+    state: &AppState,
+    incident: &Incident,
+    message: &str,
+    user_id: &str,
+    drafted_by: Option<&str>,
+    audience: Audience,
+) -> IncidentResult<Incident> {
+    let updated_incident = IncidentService::new(state.pool.clone())
+        .with_permissions(Permissions::from_state(state))
+        .post_co_authored_update(
+            incident.id,
+            message.to_string(),
+            user_id.to_string(),
+            drafted_by.map(str::to_string),
+            audience,
+        )
         .await?;
 
     // Post to channel
-    let status_blocks =
-        blocks::status_update_blocks(updated_incident.severity, message, &payload.user_id);
+    let mut status_blocks =
+        blocks::status_update_blocks(updated_incident.severity_level(), message, user_id);
+    if let Some(scribe) = drafted_by {
+        status_blocks.push(blocks::drafted_by_context(scribe));
+    }
+    if audience == Audience::Public {
+        status_blocks.push(blocks::public_update_context());
+    }
 
     if let Some(_channel_id) = &updated_incident.slack_channel_id {
         let notification_service = NotificationService::new(
@@ -86,41 +386,61 @@ pub async fn handle_status(state: AppState, payload: SlashCommandPayload) -> Inc
     }
 
     // Enqueue Statuspage sync if component mapping exists
-    if let Ok(Some(component_id)) = crate::db::queries::statuspage::get_component_id(
+    crate::jobs::statuspage_sync::enqueue_for_incident(
         &state.pool,
-        &updated_incident.affected_service,
+        &state.job_sender,
+        &updated_incident,
     )
-    .await
-    {
-        let job = crate::jobs::Job::StatuspageSync {
-            incident_id: updated_incident.id,
-            component_id,
-            status: updated_incident.status,
-            severity: updated_incident.severity,
-        };
-
-        if let Err(e) = state.job_sender.send(job) {
-            error!("Failed to enqueue Statuspage sync job: {}", e);
-        }
+    .await;
+
+    // Only updates marked public go to the public status page
+    if audience == Audience::Public {
+        crate::jobs::statuspage_sync::enqueue_public_update(
+            &state.job_sender,
+            &updated_incident,
+            message,
+        );
     }
 
     info!(
-        "Status update posted for incident {} by {}",
-        incident.id, payload.user_id
+        "{} status update posted for incident {} by {}",
+        audience.as_db_str(),
+        incident.id,
+        user_id
     );
+    Ok(updated_incident)
+}
 
-    // Acknowledge via response_url
-    state
-        .slack_client
-        .post_to_response_url(
-            &payload.response_url,
-            vec![serde_json::json!({
-                "type": "section",
-                "text": {
-                    "type": "mrkdwn",
-                    "text": "✅ Status update posted"
-                }
-            })],
-        )
-        .await
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_flags() {
+        assert_eq!(
+            parse_flags("--public Fix deployed, monitoring"),
+            (Audience::Public, false, "Fix deployed, monitoring")
+        );
+        assert_eq!(
+            parse_flags("--internal rolled back build 42"),
+            (Audience::Internal, false, "rolled back build 42")
+        );
+        assert_eq!(
+            parse_flags("DB failover in progress"),
+            (Audience::Internal, false, "DB failover in progress")
+        );
+        assert_eq!(parse_flags("--public"), (Audience::Public, false, ""));
+        assert_eq!(
+            parse_flags("--draft --public Fix deployed"),
+            (Audience::Public, true, "Fix deployed")
+        );
+        assert_eq!(
+            parse_flags("--public --draft Fix deployed"),
+            (Audience::Public, true, "Fix deployed")
+        );
+        assert_eq!(
+            parse_flags("Rolled back the --draft flag"),
+            (Audience::Internal, false, "Rolled back the --draft flag")
+        );
+    }
 }
@@ -0,0 +1,163 @@
+use crate::app_state::AppState;
+use crate::db::queries::subscriptions;
+use crate::error::{IncidentError, IncidentResult};
+use crate::services::incident::IncidentService;
+use crate::slack::blocks;
+use crate::slack::events::SlashCommandPayload;
+use tracing::info;
+
+const USAGE: &str =
+    "Usage: /incident subscribe [service:<name>|list] or /incident unsubscribe [service:<name>]";
+
+/// What a subscribe or unsubscribe command is about.
+#[derive(Debug, PartialEq)]
+enum Target<'a> {
+    /// The channel's incident
+    Incident,
+    Service(&'a str),
+    List,
+}
+
+/// Parse what follows the subcommand. Service names may contain spaces.
+fn parse_target(args: &str) -> Option<Target<'_>> {
+    let args = args.trim();
+    if args.is_empty() {
+        return Some(Target::Incident);
+    }
+    if args.eq_ignore_ascii_case("list") {
+        return Some(Target::List);
+    }
+    args.split_once(':')
+        .filter(|(key, _)| key.trim().eq_ignore_ascii_case("service"))
+        .map(|(_, service)| service.trim())
+        .filter(|service| !service.is_empty())
+        .map(Target::Service)
+}
+
+/// `/incident subscribe` — DM me a digest of every status change of this
+/// channel's incident. `service:<name>` subscribes to every incident on a
+/// service instead, and `list` shows the caller's subscriptions. Anyone may
+/// subscribe.
+pub async fn handle_subscribe(state: AppState, payload: SlashCommandPayload) -> IncidentResult<()> {
+    let reply = subscription_reply(&state, &payload, true).await?;
+    state
+        .slack_client
+        .post_to_response_url(&payload.response_url, reply)
+        .await
+}
+
+/// `/incident unsubscribe [service:<name>]` — stop the digests.
+pub async fn handle_unsubscribe(
+    state: AppState,
+    payload: SlashCommandPayload,
+) -> IncidentResult<()> {
+    let reply = subscription_reply(&state, &payload, false).await?;
+    state
+        .slack_client
+        .post_to_response_url(&payload.response_url, reply)
+        .await
+}
+
+async fn subscription_reply(
+    state: &AppState,
+    payload: &SlashCommandPayload,
+    subscribe: bool,
+) -> IncidentResult<Vec<serde_json::Value>> {
+    let args = payload
+        .text
+        .trim()
+        .split_once(char::is_whitespace)
+        .map_or("", |(_, rest)| rest);
+    let user_id = payload.user_id.as_str();
+    let verb = if subscribe {
+        "subscribed to"
+    } else {
+        "unsubscribed from"
+    };
+
+    let reply = match parse_target(args) {
+        Some(Target::List) if subscribe => {
+            let subscribed = subscriptions::list_for_user(&state.pool, user_id).await?;
+            let incident_service = IncidentService::new(state.pool.clone());
+            let mut services = Vec::new();
+            let mut incidents = Vec::new();
+            for subscription in subscribed {
+                match (subscription.service, subscription.incident_id) {
+                    (Some(service), _) => services.push(service),
+                    (None, Some(incident_id)) => {
+                        incidents.push(incident_service.get_by_id(incident_id).await?)
+                    }
+                    (None, None) => {}
+                }
+            }
+            blocks::subscriptions_blocks(&services, &incidents)
+        }
+        Some(Target::Service(name)) => {
+            let Some(service) = state
+                .config
+                .services
+                .iter()
+                .find(|s| s.eq_ignore_ascii_case(name))
+            else {
+                return Ok(blocks::error_blocks(&format!(
+                    "Unknown service '{}'. Services: {}",
+                    name,
+                    state.config.services.join(", ")
+                )));
+            };
+            let changed = if subscribe {
+                subscriptions::subscribe_to_service(&state.pool, user_id, service).await?
+            } else {
+                subscriptions::unsubscribe_from_service(&state.pool, user_id, service).await?
+            };
+            info!("{} {} service {}", user_id, verb, service);
+            blocks::subscription_changed_blocks(
+                &format!("incidents on *{}*", service),
+                subscribe,
+                changed,
+            )
+        }
+        Some(Target::Incident) => {
+            let incident = match IncidentService::new(state.pool.clone())
+                .get_latest_by_channel(&payload.channel_id)
+                .await
+            {
+                Ok(incident) => incident,
+                Err(IncidentError::NotFound) => {
+                    return Ok(blocks::error_blocks(
+                        "No incident found in this channel. To follow a service, use `service:<name>`.",
+                    ));
+                }
+                Err(e) => return Err(e),
+            };
+            let changed = if subscribe {
+                subscriptions::subscribe_to_incident(&state.pool, user_id, incident.id).await?
+            } else {
+                subscriptions::unsubscribe_from_incident(&state.pool, user_id, incident.id).await?
+            };
+            info!("{} {} incident {}", user_id, verb, incident.id);
+            blocks::subscription_changed_blocks("this incident", subscribe, changed)
+        }
+        _ => blocks::error_blocks(USAGE),
+    };
+    Ok(reply)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_target() {
+        assert_eq!(parse_target(""), Some(Target::Incident));
+        assert_eq!(parse_target(" LIST "), Some(Target::List));
+        assert_eq!(
+            parse_target("service:Test Service"),
+            Some(Target::Service("Test Service"))
+        );
+        assert_eq!(parse_target("Service: vpn"), Some(Target::Service("vpn")));
+        assert_eq!(parse_target("service:"), None);
+        assert_eq!(parse_target("team:sre"), None);
+        assert_eq!(parse_target("everything"), None);
+    }
+}
@@ -0,0 +1,193 @@
+use crate::app_state::AppState;
+use crate::db::queries::roles as role_queries;
+use crate::db::queries::timeline as timeline_queries;
+use crate::error::{IncidentError, IncidentResult};
+use crate::services::audit::AuditService;
+use crate::services::incident::IncidentService;
+use crate::services::permissions::{Action, FieldVisibility, Permissions};
+use crate::slack::blocks;
+use crate::slack::events::SlashCommandPayload;
+use crate::utils::mention::parse_channel_mention;
+use chrono::Utc;
+use serde_json::{json, Value};
+use tracing::info;
+
+const USAGE: &str = "Usage: /incident summary [#channel]";
+
+/// Status updates shown in a summary.
+const SUMMARY_UPDATES: i64 = 3;
+
+/// Channel to post to, or `None` to preview the summary privately.
+fn parse_command(text: &str) -> Result<Option<String>, String> {
+    let args: Vec<&str> = text.split_whitespace().skip(1).collect();
+    match args.as_slice() {
+        [] => Ok(None),
+        [channel] => match parse_channel_mention(channel) {
+            Some(channel_id) => Ok(Some(channel_id)),
+            None => Err(format!(
+                "Couldn't find channel {}. Pick it from Slack's autocomplete so it's sent as a link.\n{}",
+                channel, USAGE
+            )),
+        },
+        _ => Err(USAGE.to_string()),
+    }
+}
+
+/// `/incident summary [#channel]` — an executive summary of the channel's
+/// incident: severity, status, elapsed time and its latest status updates.
+/// Without a channel only the caller sees it; posting it elsewhere takes
+/// the commander (or an administrator), and quiet incidents never leave
+/// their channel.
+pub async fn handle_summary(state: AppState, payload: SlashCommandPayload) -> IncidentResult<()> {
+    let target = match parse_command(&payload.text) {
+        Ok(target) => target,
+        Err(message) => {
+            return state
+                .slack_client
+                .post_to_response_url(&payload.response_url, blocks::error_blocks(&message))
+                .await;
+        }
+    };
+
+    let incident_service =
+        IncidentService::new(state.pool.clone()).with_permissions(Permissions::from_state(&state));
+    let incident = match incident_service
+        .get_latest_by_channel(&payload.channel_id)
+        .await
+    {
+        Ok(incident) => incident,
+        Err(IncidentError::NotFound) => {
+            return state
+                .slack_client
+                .post_to_response_url(
+                    &payload.response_url,
+                    blocks::error_blocks("No incident in this channel"),
+                )
+                .await;
+        }
+        Err(e) => return Err(e),
+    };
+
+    let updates =
+        timeline_queries::latest_status_updates(&state.pool, incident.id, SUMMARY_UPDATES).await?;
+
+    let Some(channel_id) = target else {
+        // Only the caller sees this one, so it shows what they may see
+        let roles = role_queries::list_roles(&state.pool, incident.id).await?;
+        let visibility = Permissions::from_state(&state)
+            .field_visibility(&state.config, &incident, &roles, &payload.user_id)
+            .await;
+        return state
+            .slack_client
+            .post_to_response_url(
+                &payload.response_url,
+                blocks::executive_summary_blocks(&incident, &updates, Utc::now(), &visibility),
+            )
+            .await;
+    };
+
+    if incident.is_quiet {
+        return state
+            .slack_client
+            .post_to_response_url(
+                &payload.response_url,
+                blocks::error_blocks("Quiet incidents can't be summarized outside their channel"),
+            )
+            .await;
+    }
+    if let Err(IncidentError::PermissionDenied { .. }) = incident_service
+        .authorize(Action::PostStatusUpdate, &incident, &payload.user_id)
+        .await
+    {
+        return state
+            .slack_client
+            .post_to_response_url(
+                &payload.response_url,
+                blocks::permission_denied_blocks("post incident summaries"),
+            )
+            .await;
+    }
+
+    let mut message = blocks::executive_summary_blocks(
+        &incident,
+        &updates,
+        Utc::now(),
+        &FieldVisibility::shared(&state.config),
+    );
+    message.push(json!({
+        "type": "context",
+        "elements": [{
+            "type": "mrkdwn",
+            "text": format!("Shared by <@{}>", payload.user_id)
+        }]
+    }));
+    match state.slack_client.post_message(&channel_id, message).await {
+        Ok(_) => {}
+        Err(IncidentError::SlackAPIError {
+            slack_error_code, ..
+        }) if slack_error_code == "not_in_channel" || slack_error_code == "channel_not_found" => {
+            return state
+                .slack_client
+                .post_to_response_url(
+                    &payload.response_url,
+                    blocks::error_blocks(&format!(
+                        "I can't post in <#{}>. Invite me there first.",
+                        channel_id
+                    )),
+                )
+                .await;
+        }
+        Err(e) => return Err(e),
+    }
+
+    AuditService::new(state.pool.clone())
+        .log_action(
+            Some(incident.id),
+            "summary_posted".to_string(),
+            payload.user_id.clone(),
+            None,
+            None,
+            Some(json!({ "channel_id": channel_id })),
+        )
+        .await?;
+    info!(
+        "Summary of incident {} posted to {} by {}",
+        incident.id, channel_id, payload.user_id
+    );
+
+    state
+        .slack_client
+        .post_to_response_url(
+            &payload.response_url,
+            text_blocks(&format!("✅ Summary posted to <#{}>", channel_id)),
+        )
+        .await
+}
+
+fn text_blocks(text: &str) -> Vec<Value> {
+    vec![json!({
+        "type": "section",
+        "text": { "type": "mrkdwn", "text": text }
+    })]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_command() {
+        assert_eq!(parse_command("summary"), Ok(None));
+        assert_eq!(
+            parse_command("summary <#C024EXEC|exec-updates>"),
+            Ok(Some("C024EXEC".to_string()))
+        );
+        assert!(parse_command("summary #exec-updates")
+            .unwrap_err()
+            .contains("autocomplete"));
+        assert_eq!(
+            parse_command("summary <#C024EXEC> now"),
+            Err(USAGE.to_string())
+        );
+    }
+}
@@ -0,0 +1,701 @@
+use crate::app_state::AppState;
+use crate::db::models::{Incident, IncidentId, IncidentTemplate, Severity, TimelineEventType};
+use crate::db::queries::{incidents, templates, timeline};
+use crate::error::{IncidentError, IncidentResult};
+use crate::services::audit::AuditService;
+use crate::services::permissions::Permissions;
+use crate::slack::blocks;
+use crate::slack::events::{SlashCommandPayload, ViewPayload};
+use crate::slack::modals::{self, TemplateFields};
+use serde_json::{json, Value};
+use tracing::info;
+use uuid::Uuid;
+
+const USAGE: &str = "Usage: /incident template create | edit <name> | disable <name> | list";
+
+const MAX_NAME_CHARS: usize = 50;
+
+const MAX_DESCRIPTION_CHARS: usize = 1000;
+
+#[derive(Debug, PartialEq)]
+enum TemplateCommand {
+    Create,
+    Edit { name: String },
+    Disable { name: String },
+    List,
+}
+
+fn parse_command(text: &str) -> Result<TemplateCommand, String> {
+    // text is "template <action> <name>"
+    let mut parts = text.split_whitespace().skip(1);
+    let action = parts.next().unwrap_or("");
+    let name = parts.next();
+    if parts.next().is_some() {
+        return Err(USAGE.to_string());
+    }
+
+    match (action, name) {
+        ("list", None) => Ok(TemplateCommand::List),
+        ("create", None) => Ok(TemplateCommand::Create),
+        ("edit", Some(name)) => Ok(TemplateCommand::Edit {
+            name: name.to_string(),
+        }),
+        ("disable", Some(name)) => Ok(TemplateCommand::Disable {
+            name: name.to_string(),
+        }),
+        _ => Err(USAGE.to_string()),
+    }
+}
+
+/// What a template modal submission saves, from its `private_metadata`.
+#[derive(Debug, PartialEq)]
+enum Submission {
+    Create,
+    Edit {
+        template_id: Uuid,
+    },
+    /// Create from the resolution prompt
+    FromIncident {
+        incident_id: IncidentId,
+    },
+}
+
+fn parse_submission(private_metadata: &str) -> IncidentResult<Submission> {
+    if private_metadata.is_empty() {
+        return Ok(Submission::Create);
+    }
+    let (field, id) =
+        match private_metadata.strip_prefix(modals::TEMPLATE_FROM_INCIDENT_METADATA_PREFIX) {
+            Some(id) => ("incident_id", id),
+            None => ("template_id", private_metadata),
+        };
+    let id = Uuid::parse_str(id).map_err(|_| IncidentError::ValidationError {
+        field: field.to_string(),
+        reason: format!("Invalid id '{}'", id),
+    })?;
+    Ok(if field == "incident_id" {
+        Submission::FromIncident { incident_id: id }
+    } else {
+        Submission::Edit { template_id: id }
+    })
+}
+
+/// Fields of a submitted template modal. `name` is only set when creating.
+#[derive(Debug, PartialEq)]
+struct TemplateForm {
+    name: Option<String>,
+    title: String,
+    severity: Severity,
+    affected_service: Option<String>,
+    description: Option<String>,
+}
+
+/// `/incident template create|edit|disable|list` — admin-only management of
+/// the templates offered in the declare modal. Create and edit open a modal,
+/// saved by `handle_template_submission`.
+pub async fn handle_template(state: AppState, payload: SlashCommandPayload) -> IncidentResult<()> {
+    if !Permissions::from_state(&state)
+        .is_admin(&payload.user_id)
+        .await
+    {
+        return reply(
+            &state,
+            &payload,
+            blocks::error_blocks("Only bot admins can manage templates"),
+        )
+        .await;
+    }
+
+    let command = match parse_command(&payload.text) {
+        Ok(c) => c,
+        Err(message) => return reply(&state, &payload, blocks::error_blocks(&message)).await,
+    };
+
+    match command {
+        TemplateCommand::List => {
+            let templates = templates::list_all_templates(&state.pool).await?;
+            let usage = templates::template_usage(&state.pool).await?;
+            reply(
+                &state,
+                &payload,
+                blocks::template_list_blocks(&templates, &usage),
+            )
+            .await
+        }
+        TemplateCommand::Create => {
+            state
+                .slack_client
+                .open_modal(
+                    &payload.trigger_id,
+                    modals::template_modal(&state.config.services, None),
+                )
+                .await
+        }
+        TemplateCommand::Edit { name } => {
+            let Some(template) = templates::get_any_template_by_name(&state.pool, &name).await?
+            else {
+                return reply(&state, &payload, unknown_template(&name)).await;
+            };
+            state
+                .slack_client
+                .open_modal(
+                    &payload.trigger_id,
+                    modals::template_modal(&state.config.services, Some(&template)),
+                )
+                .await
+        }
+        TemplateCommand::Disable { name } => {
+            if !templates::disable_template(&state.pool, &name).await? {
+                return reply(&state, &payload, unknown_template(&name)).await;
+            }
+            audit(
+                &state,
+                "template_disabled",
+                &payload.user_id,
+                &name,
+                None,
+                None,
+            )
+            .await?;
+            info!("Template {} disabled by {}", name, payload.user_id);
+            reply(
+                &state,
+                &payload,
+                text_blocks(&format!(
+                    "Template `{}` disabled. Re-create it to enable it again.",
+                    name
+                )),
+            )
+            .await
+        }
+    }
+}
+
+/// Template modal submission: create the template, or update the one named
+/// in `private_metadata`. Templates created from the resolution prompt are
+/// linked to their incident, and may be saved by its commander as well as
+/// admins. Problems are reported by DM, since modal submissions have no
+/// response URL.
+pub async fn handle_template_submission(
+    state: AppState,
+    view: ViewPayload,
+    user_id: String,
+) -> IncidentResult<()> {
+    let submission = parse_submission(&view.private_metadata)?;
+    let source = match submission {
+        Submission::FromIncident { incident_id } => {
+            Some(incidents::get_incident_by_id(&state.pool, incident_id).await?)
+        }
+        _ => None,
+    };
+    if !may_manage(&state, source.as_ref(), &user_id).await {
+        return state
+            .slack_client
+            .send_dm(
+                &user_id,
+                blocks::error_blocks("Only bot admins can manage templates"),
+            )
+            .await;
+    }
+
+    let editing = match submission {
+        Submission::Edit { template_id } => Some(template_id),
+        _ => None,
+    };
+    let form = match parse_form(&view.state.values, editing.is_none()) {
+        Ok(form) => form,
+        Err(message) => {
+            return state
+                .slack_client
+                .send_dm(&user_id, blocks::error_blocks(&message))
+                .await
+        }
+    };
+
+    let (action, template) = match (editing, &form.name) {
+        (Some(id), _) => {
+            let template = templates::update_template(
+                &state.pool,
+                id,
+                &form.title,
+                form.severity,
+                form.affected_service.as_deref(),
+                form.description.as_deref(),
+            )
+            .await?;
+            ("template_updated", template)
+        }
+        (None, Some(name)) => {
+            let Some(template) = templates::create_template(
+                &state.pool,
+                name,
+                &form.title,
+                form.severity,
+                form.affected_service.as_deref(),
+                form.description.as_deref(),
+                source.as_ref().map(|incident| incident.id),
+            )
+            .await?
+            else {
+                let message = match source {
+                    Some(_) => format!(
+                        "A template named `{}` already exists; pick another name",
+                        name
+                    ),
+                    None => format!(
+                        "A template named `{}` already exists; use `/incident template edit {}`",
+                        name, name
+                    ),
+                };
+                return state
+                    .slack_client
+                    .send_dm(&user_id, blocks::error_blocks(&message))
+                    .await;
+            };
+            ("template_created", template)
+        }
+        (None, None) => unreachable!("parse_form requires a name when creating"),
+    };
+
+    audit(
+        &state,
+        action,
+        &user_id,
+        &template.name,
+        source.as_ref().map(|incident| incident.id),
+        Some(template_state(&template)),
+    )
+    .await?;
+    info!("Template {} saved by {}", template.name, user_id);
+
+    state
+        .slack_client
+        .send_dm(
+            &user_id,
+            text_blocks(&format!(
+                "✅ Template `{}` saved: *{}* ({})",
+                template.name,
+                template.title,
+                template.severity.as_db_str()
+            )),
+        )
+        .await
+}
+
+/// After resolution, ask the commander whether a template would have
+/// helped, unless the incident was declared from one. Quiet incidents are
+/// skipped so security details don't end up in a template anyone can pick.
+pub async fn prompt_template_from_incident(
+    state: &AppState,
+    incident: &Incident,
+) -> IncidentResult<()> {
+    if incident.template_id.is_some() || incident.is_quiet {
+        return Ok(());
+    }
+    state
+        .slack_client
+        .send_dm(
+            &incident.commander_id,
+            blocks::template_prompt_blocks(incident),
+        )
+        .await
+}
+
+/// "Create template" under the resolution prompt: open the template modal
+/// with values suggested from the incident. `value` is the incident id.
+pub async fn handle_create_from_incident(
+    state: AppState,
+    user_id: String,
+    value: &str,
+    trigger_id: Option<String>,
+    response_url: Option<String>,
+) -> IncidentResult<()> {
+    let Some(trigger_id) = trigger_id else {
+        return Ok(());
+    };
+    let incident_id = Uuid::parse_str(value).map_err(|_| IncidentError::ValidationError {
+        field: "incident_id".to_string(),
+        reason: format!("Invalid incident id '{}'", value),
+    })?;
+    let incident = incidents::get_incident_by_id(&state.pool, incident_id).await?;
+
+    if !may_manage(&state, Some(&incident), &user_id).await {
+        return respond(
+            &state,
+            &user_id,
+            response_url,
+            blocks::permission_denied_blocks("create a template from it"),
+        )
+        .await;
+    }
+    if templates::has_template_from_incident(&state.pool, incident.id).await? {
+        return respond(
+            &state,
+            &user_id,
+            response_url,
+            text_blocks(&format!(
+                "A template was already created from *{}*. See `/incident template list`.",
+                incident.reference()
+            )),
+        )
+        .await;
+    }
+
+    let first_update = timeline::get_timeline(&state.pool, incident.id)
+        .await?
+        .into_iter()
+        .find(|e| e.event_type == TimelineEventType::StatusUpdate && e.deleted_at.is_none())
+        .map(|e| e.message);
+    let mut suggestion = suggest_template(&incident, first_update.as_deref(), false);
+    if templates::get_template_by_name(&state.pool, &suggestion.name)
+        .await?
+        .is_some()
+    {
+        suggestion = suggest_template(&incident, first_update.as_deref(), true);
+    }
+
+    state
+        .slack_client
+        .open_modal(
+            &trigger_id,
+            modals::template_from_incident_modal(&state.config.services, &incident, &suggestion),
+        )
+        .await
+}
+
+/// Admins manage every template; a commander may also create one from
+/// their own incident (`source`).
+async fn may_manage(state: &AppState, source: Option<&Incident>, user_id: &str) -> bool {
+    source.is_some_and(|incident| incident.commander_id == user_id)
+        || Permissions::from_state(state).is_admin(user_id).await
+}
+
+/// Template fields inferred from a resolved incident: its title, severity
+/// and service, with its first status update as the description. The name
+/// is the title as a slug, followed by the incident's reference when
+/// `qualified` (the plain name is taken).
+fn suggest_template(
+    incident: &Incident,
+    first_update: Option<&str>,
+    qualified: bool,
+) -> TemplateFields {
+    let reference = slug(&incident.reference(), MAX_NAME_CHARS);
+    let name = if qualified {
+        let title = slug(
+            &incident.title,
+            MAX_NAME_CHARS.saturating_sub(reference.len() + 1),
+        );
+        if title.is_empty() {
+            reference
+        } else {
+            format!("{}-{}", title, reference)
+        }
+    } else {
+        Some(slug(&incident.title, MAX_NAME_CHARS))
+            .filter(|name| !name.is_empty())
+            .unwrap_or(reference)
+    };
+
+    TemplateFields {
+        name,
+        title: incident.title.clone(),
+        severity: incident.severity,
+        affected_service: Some(incident.affected_service.clone()),
+        description: first_update
+            .map(|update| update.trim().chars().take(MAX_DESCRIPTION_CHARS).collect())
+            .filter(|description: &String| !description.is_empty()),
+    }
+}
+
+/// `text` as a template name: lowercase letters and digits, with runs of
+/// anything else as one hyphen, cut to `max_chars`.
+fn slug(text: &str, max_chars: usize) -> String {
+    let mut slug = String::new();
+    for c in text.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.truncate(max_chars);
+    slug.trim_end_matches('-').to_string()
+}
+
+fn parse_form(
+    values: &serde_json::Map<String, Value>,
+    require_name: bool,
+) -> Result<TemplateForm, String> {
+    let text = |block: &str, action: &str| {
+        values
+            .get(block)
+            .and_then(|v| v.get(action))
+            .and_then(|v| v.get("value"))
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(str::to_string)
+    };
+    let selected = |block: &str, action: &str| {
+        values
+            .get(block)
+            .and_then(|v| v.get(action))
+            .and_then(|v| v.get("selected_option"))
+            .and_then(|v| v.get("value"))
+            .and_then(|v| v.as_str())
+            .map(str::to_string)
+    };
+
+    let name = if require_name {
+        let name = text("name_block", "name_input").unwrap_or_default();
+        validate_name(&name)?;
+        Some(name)
+    } else {
+        None
+    };
+    let title = text("title_block", "title_input").unwrap_or_default();
+    if title.is_empty() || title.chars().count() > 100 {
+        return Err("Title must be 1-100 characters".to_string());
+    }
+    let severity = selected("severity_block", "severity_select")
+        .ok_or_else(|| "Severity is required".to_string())?
+        .parse::<Severity>()?;
+
+    Ok(TemplateForm {
+        name,
+        title,
+        severity,
+        affected_service: selected("service_block", "service_select"),
+        description: text("description_block", "description_input"),
+    })
+}
+
+/// Names are what the declare modal stores in drafts and the config bundle
+/// keys templates by: lowercase letters, digits and hyphens.
+fn validate_name(name: &str) -> Result<(), String> {
+    let valid = !name.is_empty()
+        && name.len() <= MAX_NAME_CHARS
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+    if valid {
+        Ok(())
+    } else {
+        Err(format!(
+            "Template names are 1-{} lowercase letters, digits and hyphens, e.g. `database-outage`",
+            MAX_NAME_CHARS
+        ))
+    }
+}
+
+fn template_state(template: &IncidentTemplate) -> Value {
+    json!({
+        "title": template.title,
+        "severity": template.severity.as_db_str(),
+        "affected_service": template.affected_service,
+        "description": template.description,
+    })
+}
+
+async fn audit(
+    state: &AppState,
+    action: &str,
+    user_id: &str,
+    name: &str,
+    incident_id: Option<IncidentId>,
+    new_state: Option<Value>,
+) -> IncidentResult<()> {
+    AuditService::new(state.pool.clone())
+        .log_action(
+            incident_id,
+            action.to_string(),
+            user_id.to_string(),
+            None,
+            new_state,
+            Some(json!({ "template": name })),
+        )
+        .await
+}
+
+fn unknown_template(name: &str) -> Vec<Value> {
+    blocks::error_blocks(&format!(
+        "No template named `{}`. See `/incident template list`.",
+        name
+    ))
+}
+
+fn text_blocks(text: &str) -> Vec<Value> {
+    vec![json!({
+        "type": "section",
+        "text": { "type": "mrkdwn", "text": text }
+    })]
+}
+
+async fn respond(
+    state: &AppState,
+    user_id: &str,
+    response_url: Option<String>,
+    blocks: Vec<Value>,
+) -> IncidentResult<()> {
+    match response_url {
+        Some(url) => state.slack_client.post_to_response_url(&url, blocks).await,
+        None => state.slack_client.send_dm(user_id, blocks).await,
+    }
+}
+
+async fn reply(
+    state: &AppState,
+    payload: &SlashCommandPayload,
+    blocks: Vec<Value>,
+) -> IncidentResult<()> {
+    state
+        .slack_client
+        .post_to_response_url(&payload.response_url, blocks)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_command() {
+        assert_eq!(parse_command("template list"), Ok(TemplateCommand::List));
+        assert_eq!(
+            parse_command("template create"),
+            Ok(TemplateCommand::Create)
+        );
+        assert_eq!(
+            parse_command("template edit  database-outage"),
+            Ok(TemplateCommand::Edit {
+                name: "database-outage".to_string()
+            })
+        );
+        assert_eq!(
+            parse_command("template disable cdn-issues"),
+            Ok(TemplateCommand::Disable {
+                name: "cdn-issues".to_string()
+            })
+        );
+        assert!(parse_command("template edit").is_err());
+        assert!(parse_command("template create cdn-issues").is_err());
+        assert!(parse_command("template delete cdn-issues").is_err());
+    }
+
+    #[test]
+    fn test_parse_form_validates_name_and_title() {
+        let values = |name: &str, title: &str| {
+            json!({
+                "name_block": { "name_input": { "value": name } },
+                "title_block": { "title_input": { "value": title } },
+                "severity_block": { "severity_select": { "selected_option": { "value": "P1" } } },
+                "service_block": { "service_select": { "selected_option": null } },
+                "description_block": { "description_input": { "value": "  " } },
+            })
+            .as_object()
+            .unwrap()
+            .clone()
+        };
+
+        assert_eq!(
+            parse_form(&values("db-outage", " {{region}} DB outage "), true),
+            Ok(TemplateForm {
+                name: Some("db-outage".to_string()),
+                title: "{{region}} DB outage".to_string(),
+                severity: Severity::P1,
+                affected_service: None,
+                description: None,
+            })
+        );
+        assert!(parse_form(&values("DB Outage", "DB outage"), true).is_err());
+        assert!(parse_form(&values("db-outage", ""), true).is_err());
+        // Names can't be changed when editing
+        assert_eq!(
+            parse_form(&values("", "DB outage"), false).unwrap().name,
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_submission() {
+        let id = Uuid::new_v4();
+        assert_eq!(parse_submission("").unwrap(), Submission::Create);
+        assert_eq!(
+            parse_submission(&id.to_string()).unwrap(),
+            Submission::Edit { template_id: id }
+        );
+        assert_eq!(
+            parse_submission(&format!("incident:{}", id)).unwrap(),
+            Submission::FromIncident { incident_id: id }
+        );
+        assert!(parse_submission("incident:nope").is_err());
+    }
+
+    fn resolved_incident(title: &str) -> Incident {
+        let now = chrono::Utc::now();
+        Incident {
+            id: Uuid::new_v4(),
+            number_prefix: "PLAT".to_string(),
+            number: 12,
+            slack_channel_id: Some("C1".to_string()),
+            title: title.to_string(),
+            severity: Severity::P2,
+            severity_code: None,
+            status: crate::db::models::IncidentStatus::Resolved,
+            affected_service: "Checkout".to_string(),
+            commander_id: "U1".to_string(),
+            declared_at: now,
+            resolved_at: Some(now),
+            duration_minutes: Some(42),
+            pinned_message_ts: None,
+            is_quiet: false,
+            statuspage_incident_id: None,
+            canceled_at: None,
+            cancel_reason: None,
+            triaged_at: None,
+            is_triage: false,
+            channel_archived_at: None,
+            bridge_url: None,
+            custom_fields: Default::default(),
+            template_id: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn test_suggest_template_from_incident() {
+        let incident = resolved_incident("Checkout: 502s from payment API!");
+        let suggestion = suggest_template(&incident, Some("  Card payments failing  "), false);
+        assert_eq!(
+            suggestion,
+            TemplateFields {
+                name: "checkout-502s-from-payment-api".to_string(),
+                title: "Checkout: 502s from payment API!".to_string(),
+                severity: Severity::P2,
+                affected_service: Some("Checkout".to_string()),
+                description: Some("Card payments failing".to_string()),
+            }
+        );
+        assert!(validate_name(&suggestion.name).is_ok());
+
+        // A taken name gets the incident reference
+        assert_eq!(
+            suggest_template(&incident, None, true).name,
+            "checkout-502s-from-payment-api-plat-12"
+        );
+        assert_eq!(suggest_template(&incident, None, true).description, None);
+
+        // Long titles are cut to a valid name; ones with no letters or
+        // digits fall back to the reference
+        let long = resolved_incident(&"Database replica lag ".repeat(5));
+        for qualified in [false, true] {
+            let name = suggest_template(&long, None, qualified).name;
+            assert!(validate_name(&name).is_ok(), "{}", name);
+        }
+        assert_eq!(
+            suggest_template(&resolved_incident("🔥🔥"), None, false).name,
+            "plat-12"
+        );
+    }
+}
@@ -1,14 +1,110 @@
 use crate::app_state::AppState;
+use crate::db::models::{Incident, IncidentId, NewTimelineEvent, TimelineEventType};
+use crate::db::queries::timeline as timeline_queries;
 use crate::error::{IncidentError, IncidentResult};
+use crate::services::audit::AuditService;
 use crate::services::incident::IncidentService;
-use crate::services::timeline::TimelineService;
+use crate::services::permissions::{Action, Permissions};
+use crate::services::reconstruction::parse_slack_ts;
+use crate::services::timeline::{number_events, TimelineFilter, TimelineService};
 use crate::slack::blocks;
+use crate::slack::client::HistoryRange;
 use crate::slack::events::SlashCommandPayload;
-use tracing::info;
+use chrono::Utc;
+use serde_json::json;
+use tracing::{debug, info};
 
+const USAGE: &str =
+    "Usage: /incident timeline [--last N] | timeline edit <n> <text> | timeline delete <n>";
+
+#[derive(Debug, PartialEq)]
+enum TimelineCommand {
+    Show { last: Option<i64> },
+    Edit { number: usize, message: String },
+    Delete { number: usize },
+}
+
+/// Split off the first word of `text`, returning it and the rest unchanged.
+fn split_word(text: &str) -> (&str, &str) {
+    let text = text.trim_start();
+    match text.find(char::is_whitespace) {
+        Some(end) => (&text[..end], &text[end..]),
+        None => (text, ""),
+    }
+}
+
+fn parse_command(text: &str) -> Result<TimelineCommand, String> {
+    let (_, rest) = split_word(text);
+    let (subcommand, rest) = split_word(rest);
+    let parse_number = |word: &str| {
+        word.trim_start_matches('#')
+            .parse::<usize>()
+            .ok()
+            .filter(|n| *n > 0)
+            .ok_or_else(|| {
+                format!(
+                    "'{}' isn't an event number. `/incident timeline` shows them as #1, #2, ...",
+                    word
+                )
+            })
+    };
+
+    match subcommand {
+        "edit" => {
+            let (number, message) = split_word(rest);
+            if number.is_empty() || message.trim().is_empty() {
+                return Err(USAGE.to_string());
+            }
+            Ok(TimelineCommand::Edit {
+                number: parse_number(number)?,
+                message: message.trim().to_string(),
+            })
+        }
+        "delete" => match split_word(rest) {
+            (number, extra) if !number.is_empty() && extra.trim().is_empty() => {
+                Ok(TimelineCommand::Delete {
+                    number: parse_number(number)?,
+                })
+            }
+            _ => Err(USAGE.to_string()),
+        },
+        _ => parse_last(text).map(|last| TimelineCommand::Show { last }),
+    }
+}
+
+/// Most events `--last` will show.
+const MAX_LAST: i64 = 500;
+
+/// Parse the optional `--last N` after `timeline`.
+fn parse_last(text: &str) -> Result<Option<i64>, String> {
+    let mut args = text.split_whitespace().skip(1);
+    match (args.next(), args.next(), args.next()) {
+        (None, _, _) => Ok(None),
+        (Some("--last"), Some(n), None) => match n.parse::<i64>() {
+            Ok(n) if (1..=MAX_LAST).contains(&n) => Ok(Some(n)),
+            _ => Err(format!("--last takes a number from 1 to {}", MAX_LAST)),
+        },
+        _ => Err(USAGE.to_string()),
+    }
+}
+
+/// `/incident timeline [--last N]`: post the timeline to the incident
+/// channel, split across as many messages as it needs. `edit` and `delete`
+/// change a status update or note by its number.
 pub async fn handle_timeline(state: AppState, payload: SlashCommandPayload) -> IncidentResult<()> {
+    let command = match parse_command(&payload.text) {
+        Ok(command) => command,
+        Err(message) => {
+            return state
+                .slack_client
+                .post_to_response_url(&payload.response_url, blocks::error_blocks(&message))
+                .await;
+        }
+    };
+
     // Get incident from channel
-    let incident_service = IncidentService::new(state.pool.clone());
+    let incident_service =
+        IncidentService::new(state.pool.clone()).with_permissions(Permissions::from_state(&state));
     let incident = match incident_service
         .get_latest_by_channel(&payload.channel_id)
         .await
@@ -26,19 +122,51 @@ pub async fn handle_timeline(state: AppState, payload: SlashCommandPayload) -> I
         Err(e) => return Err(e),
     };
 
+    let last = match command {
+        TimelineCommand::Show { last } => last,
+        TimelineCommand::Edit { number, message } => {
+            return change_event(
+                &state,
+                &payload,
+                &incident_service,
+                &incident,
+                number,
+                Some(message),
+            )
+            .await;
+        }
+        TimelineCommand::Delete { number } => {
+            return change_event(&state, &payload, &incident_service, &incident, number, None)
+                .await;
+        }
+    };
+
     // Get timeline
     let timeline_service = TimelineService::new(state.pool.clone());
-    let events = timeline_service.get_timeline(incident.id).await?;
+    let (events, total) = match last {
+        Some(count) => timeline_service.get_last_events(incident.id, count).await?,
+        None => {
+            let events = timeline_service.get_timeline(incident.id).await?;
+            let total = events.len() as i64;
+            (events, total)
+        }
+    };
+    let first = (total as usize + 1).saturating_sub(events.len());
+    let events = number_events(events, first);
 
     // Format and post timeline
-    let timeline_blocks = blocks::timeline_blocks(&events);
+    let messages = blocks::timeline_messages(
+        incident.id,
+        &events,
+        &TimelineFilter::default(),
+        total as usize,
+    );
 
-    // Post to incident channel (visible to everyone)
+    // Post to incident channel (visible to everyone), in order
     if let Some(channel_id) = &incident.slack_channel_id {
-        state
-            .slack_client
-            .post_message(channel_id, timeline_blocks)
-            .await?;
+        for message in messages {
+            state.slack_client.post_message(channel_id, message).await?;
+        }
     }
 
     info!("Timeline displayed for incident {}", incident.id);
@@ -58,3 +186,292 @@ pub async fn handle_timeline(state: AppState, payload: SlashCommandPayload) -> I
         )
         .await
 }
+
+/// Re-render a posted timeline in place after a filter button or time-window
+/// menu change. `value` is the selected control's value.
+pub async fn handle_timeline_filter(
+    state: AppState,
+    channel_id: &str,
+    message_ts: &str,
+    value: &str,
+) -> IncidentResult<()> {
+    let (incident_id, filter) =
+        parse_filter_value(value).ok_or_else(|| IncidentError::ValidationError {
+            field: "value".to_string(),
+            reason: format!("Malformed timeline filter value '{}'", value),
+        })?;
+
+    let events = TimelineService::new(state.pool.clone())
+        .get_timeline(incident_id)
+        .await?;
+    let now = Utc::now();
+    let events: Vec<_> = number_events(events, 1)
+        .into_iter()
+        .filter(|(_, event)| filter.matches(event, now))
+        .collect();
+
+    state
+        .slack_client
+        .update_message(
+            channel_id,
+            message_ts,
+            blocks::timeline_blocks(incident_id, &events, &filter),
+        )
+        .await
+}
+
+/// Edit (`message` set) or delete event `number`. Authors may change their
+/// own status updates and notes; anyone else's takes the commander or an
+/// administrator. Old and new text go to the audit log.
+async fn change_event(
+    state: &AppState,
+    payload: &SlashCommandPayload,
+    incident_service: &IncidentService,
+    incident: &Incident,
+    number: usize,
+    message: Option<String>,
+) -> IncidentResult<()> {
+    let reply = |blocks| {
+        state
+            .slack_client
+            .post_to_response_url(&payload.response_url, blocks)
+    };
+    let timeline_service = TimelineService::new(state.pool.clone());
+    let event = match timeline_service
+        .get_event_by_number(incident.id, number)
+        .await
+    {
+        Ok(event) => event,
+        Err(IncidentError::NotFound) => {
+            return reply(blocks::error_blocks(&format!(
+                "No timeline event #{}. `/incident timeline` shows the numbers.",
+                number
+            )))
+            .await;
+        }
+        Err(e) => return Err(e),
+    };
+
+    if event.posted_by != payload.user_id {
+        if let Err(IncidentError::PermissionDenied { .. }) = incident_service
+            .authorize(Action::EditTimeline, incident, &payload.user_id)
+            .await
+        {
+            return reply(blocks::permission_denied_blocks(
+                Action::EditTimeline.description(),
+            ))
+            .await;
+        }
+    }
+
+    let result = match &message {
+        Some(message) => timeline_service.edit_event(&event, message).await,
+        None => timeline_service.delete_event(&event).await,
+    };
+    let changed = match result {
+        Ok(changed) => changed,
+        Err(IncidentError::ValidationError { reason, .. }) => {
+            return reply(blocks::error_blocks(&reason)).await;
+        }
+        Err(e) => return Err(e),
+    };
+
+    let (action, new_state, ack) = match message {
+        Some(_) => (
+            "timeline_event_edited",
+            Some(json!({ "message": changed.message })),
+            format!("✏️ Edited timeline event #{}", number),
+        ),
+        None => (
+            "timeline_event_deleted",
+            None,
+            format!("🗑️ Deleted timeline event #{}", number),
+        ),
+    };
+    AuditService::new(state.pool.clone())
+        .log_action(
+            Some(incident.id),
+            action.to_string(),
+            payload.user_id.clone(),
+            Some(json!({ "message": event.message })),
+            new_state,
+            Some(json!({
+                "event_id": event.id,
+                "number": number,
+                "event_type": event.event_type.as_db_str(),
+                "posted_by": event.posted_by,
+            })),
+        )
+        .await?;
+    info!(
+        "Timeline event {} of incident {} {} by {}",
+        event.id, incident.id, action, payload.user_id
+    );
+
+    reply(vec![json!({
+        "type": "section",
+        "text": { "type": "mrkdwn", "text": ack }
+    })])
+    .await
+}
+
+/// A `TIMELINE_REACTION` emoji was added to a message. If the channel belongs
+/// to an open incident, copy the message to its timeline as a note by the
+/// message's author, at the time it was posted. Returns whether a note was
+/// added; reacting again to the same message adds nothing.
+pub async fn record_reacted_message(
+    state: &AppState,
+    channel_id: &str,
+    message_ts: &str,
+    reacted_by: &str,
+) -> IncidentResult<bool> {
+    let incident = match IncidentService::new(state.pool.clone())
+        .get_by_channel(channel_id)
+        .await
+    {
+        Ok(incident) => incident,
+        Err(IncidentError::NotFound) => return Ok(false),
+        Err(e) => return Err(e),
+    };
+    let Some(posted_at) = parse_slack_ts(message_ts) else {
+        return Ok(false);
+    };
+
+    // Thread replies aren't in the channel history, so only top-level
+    // messages can be copied
+    let range = HistoryRange {
+        oldest: Some(message_ts.to_string()),
+        latest: Some(message_ts.to_string()),
+        max_messages: Some(1),
+    };
+    let message = state
+        .slack_client
+        .fetch_channel_history(channel_id, &range)
+        .await?
+        .into_iter()
+        .find(|m| m.ts == message_ts);
+    let Some((author, text)) = message
+        .and_then(|m| m.user.map(|user| (user, m.text)))
+        .filter(|(_, text)| !text.trim().is_empty())
+    else {
+        debug!(
+            "Ignoring reaction to {} in {}: not a user message",
+            message_ts, channel_id
+        );
+        return Ok(false);
+    };
+
+    if timeline_queries::has_event_at(&state.pool, incident.id, &author, posted_at).await? {
+        return Ok(false);
+    }
+
+    TimelineService::new(state.pool.clone())
+        .log_events_batch(
+            incident.id,
+            vec![NewTimelineEvent {
+                event_type: TimelineEventType::Note,
+                message: text,
+                posted_by: author,
+                timestamp: Some(posted_at),
+            }],
+        )
+        .await?;
+
+    info!(
+        "Added message {} to incident {} timeline (reaction by {})",
+        message_ts, incident.id, reacted_by
+    );
+    Ok(true)
+}
+
+fn parse_filter_value(value: &str) -> Option<(IncidentId, TimelineFilter)> {
+    let mut parts = value.split(':');
+    let incident_id = parts.next()?.parse().ok()?;
+    let event_type = match parts.next()? {
+        "all" => None,
+        t => Some(t.parse::<TimelineEventType>().ok()?),
+    };
+    let window_hours = match parts.next()? {
+        "all" => None,
+        h => Some(h.parse::<i64>().ok().filter(|h| *h > 0)?),
+    };
+    if parts.next().is_some() {
+        return None;
+    }
+    Some((
+        incident_id,
+        TimelineFilter {
+            event_type,
+            window_hours,
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_parse_last() {
+        assert_eq!(parse_last("timeline"), Ok(None));
+        assert_eq!(parse_last("timeline --last 20"), Ok(Some(20)));
+        assert!(parse_last("timeline --last 0").is_err());
+        assert!(parse_last("timeline --last 501").is_err());
+        assert!(parse_last("timeline --last").is_err());
+        assert!(parse_last("timeline 20").is_err());
+        assert!(parse_last("timeline --last 20 extra").is_err());
+    }
+
+    #[test]
+    fn test_parse_command() {
+        assert_eq!(
+            parse_command("timeline"),
+            Ok(TimelineCommand::Show { last: None })
+        );
+        assert_eq!(
+            parse_command("timeline --last 5"),
+            Ok(TimelineCommand::Show { last: Some(5) })
+        );
+        assert_eq!(
+            parse_command("timeline edit #3 Rolled back  4.2.1\nto 4.2.0 "),
+            Ok(TimelineCommand::Edit {
+                number: 3,
+                message: "Rolled back  4.2.1\nto 4.2.0".to_string()
+            })
+        );
+        assert_eq!(
+            parse_command("timeline delete 12"),
+            Ok(TimelineCommand::Delete { number: 12 })
+        );
+        assert_eq!(parse_command("timeline edit 3"), Err(USAGE.to_string()));
+        assert_eq!(parse_command("timeline delete"), Err(USAGE.to_string()));
+        assert_eq!(parse_command("timeline delete 3 4"), Err(USAGE.to_string()));
+        assert!(parse_command("timeline delete 0")
+            .unwrap_err()
+            .contains("isn't an event number"));
+        assert!(parse_command("timeline edit three typo")
+            .unwrap_err()
+            .contains("isn't an event number"));
+    }
+
+    #[test]
+    fn test_filter_value_round_trip() {
+        let id = Uuid::new_v4();
+        let filter = TimelineFilter {
+            event_type: Some(TimelineEventType::SeverityChange),
+            window_hours: Some(6),
+        };
+        assert_eq!(
+            parse_filter_value(&blocks::timeline_filter_value(id, &filter)),
+            Some((id, filter))
+        );
+        assert_eq!(
+            parse_filter_value(&format!("{}:all:all", id)),
+            Some((id, TimelineFilter::default()))
+        );
+        assert_eq!(parse_filter_value(&format!("{}:bogus:all", id)), None);
+        assert_eq!(parse_filter_value(&format!("{}:all:-1", id)), None);
+        assert_eq!(parse_filter_value("not-a-uuid:all:all"), None);
+    }
+}
@@ -0,0 +1,185 @@
+use crate::app_state::AppState;
+use crate::commands::declare::{enabled_services, with_banner};
+use crate::db::models::{Incident, IncidentStatus};
+use crate::db::queries::incidents;
+use crate::error::{IncidentError, IncidentResult};
+use crate::services::incident::IncidentService;
+use crate::services::permissions::{Action, Permissions};
+use crate::slack::events::SlashCommandPayload;
+use crate::slack::{blocks, modals};
+use serde_json::{json, Value};
+use tracing::info;
+use uuid::Uuid;
+
+const USAGE: &str = "Usage: `/incident triage <title>` or `/incident triage list`";
+
+/// Reason recorded when a triage is dismissed.
+const DISMISS_REASON: &str = "Not an incident";
+
+/// `/incident triage <title>` — note a suspected issue without declaring it:
+/// no channel, no notifications and no MTTR clock. The buttons posted with it
+/// upgrade it into a full declaration or dismiss it. `triage list` shows the
+/// open ones.
+pub async fn handle_triage(state: AppState, payload: SlashCommandPayload) -> IncidentResult<()> {
+    let title = payload
+        .text
+        .trim()
+        .split_once(char::is_whitespace)
+        .map_or("", |(_, title)| title.trim());
+
+    if title.is_empty() {
+        return state
+            .slack_client
+            .post_to_response_url(&payload.response_url, blocks::error_blocks(USAGE))
+            .await;
+    }
+    if title == "list" {
+        let triages = incidents::list_open_triages(&state.pool).await?;
+        return state
+            .slack_client
+            .post_to_response_url(&payload.response_url, blocks::triage_list_blocks(&triages))
+            .await;
+    }
+
+    let title: String = title.chars().take(100).collect();
+    let triage = IncidentService::new(state.pool.clone())
+        .start_triage(title, payload.user_id.clone())
+        .await?;
+
+    // Post where everyone in the channel can pick it up; the bot may not be
+    // a member, in which case only the reporter sees it
+    let triage_blocks = blocks::triage_blocks(&triage);
+    if let Err(e) = state
+        .slack_client
+        .post_message(&payload.channel_id, triage_blocks.clone())
+        .await
+    {
+        info!(
+            "Could not post triage {} to {}: {}",
+            triage.id, payload.channel_id, e
+        );
+        return state
+            .slack_client
+            .post_to_response_url(&payload.response_url, triage_blocks)
+            .await;
+    }
+    Ok(())
+}
+
+/// "Declare incident" button: open the declare modal for the triage. Anyone
+/// may declare, as with `/incident declare`.
+pub async fn handle_declare_button(
+    state: AppState,
+    user_id: String,
+    value: &str,
+    trigger_id: Option<String>,
+    response_url: Option<String>,
+) -> IncidentResult<()> {
+    let Some(trigger_id) = trigger_id else {
+        return Ok(());
+    };
+    let triage = match open_triage(&state, value).await? {
+        Ok(triage) => triage,
+        Err(reply) => return respond(&state, &user_id, response_url, reply).await,
+    };
+
+    let templates = crate::db::queries::templates::list_active_templates(&state.pool).await?;
+    let services = enabled_services(&state).await;
+    let mut modal = modals::triage_declare_modal(&services, &templates, &triage, None);
+    with_banner(&state, &mut modal).await;
+    state.slack_client.open_modal(&trigger_id, modal).await
+}
+
+/// "Dismiss" button: close the triage as not an incident. Whoever started it
+/// or an admin only.
+pub async fn handle_dismiss_button(
+    state: AppState,
+    user_id: String,
+    value: &str,
+    response_url: Option<String>,
+) -> IncidentResult<()> {
+    let triage = match open_triage(&state, value).await? {
+        Ok(triage) => triage,
+        Err(reply) => return respond(&state, &user_id, response_url, reply).await,
+    };
+
+    let incident_service =
+        IncidentService::new(state.pool.clone()).with_permissions(Permissions::from_state(&state));
+    if let Err(IncidentError::PermissionDenied { .. }) = incident_service
+        .authorize(Action::Cancel, &triage, &user_id)
+        .await
+    {
+        let reply = blocks::error_blocks("Only whoever started the triage can dismiss it");
+        return respond(&state, &user_id, response_url, reply).await;
+    }
+
+    // Nothing was announced, so there is nobody to tell
+    let reply = match incident_service
+        .cancel_incident(triage.id, user_id.clone(), DISMISS_REASON.to_string())
+        .await
+    {
+        Ok(_) => {
+            info!("Triage {} dismissed by {}", triage.id, user_id);
+            text_blocks(&format!(
+                "⚪ Triage *{}* dismissed: not an incident.",
+                triage.title
+            ))
+        }
+        // Declared or dismissed by someone else in the meantime
+        Err(IncidentError::ValidationError { .. }) => {
+            blocks::error_blocks("This triage was already declared or dismissed")
+        }
+        Err(e) => return Err(e),
+    };
+    respond(&state, &user_id, response_url, reply).await
+}
+
+/// The triage a button was clicked for, or the reply to send if it's no
+/// longer triaging.
+async fn open_triage(
+    state: &AppState,
+    value: &str,
+) -> IncidentResult<Result<Incident, Vec<Value>>> {
+    let incident_id = Uuid::parse_str(value).map_err(|_| IncidentError::ValidationError {
+        field: "incident_id".to_string(),
+        reason: format!("Invalid incident id '{}'", value),
+    })?;
+    let incident = incidents::get_incident_by_id(&state.pool, incident_id).await?;
+
+    Ok(match incident.status {
+        IncidentStatus::Triaging => Ok(incident),
+        IncidentStatus::Canceled => Err(text_blocks(&format!(
+            "Triage *{}* was already dismissed.",
+            incident.title
+        ))),
+        _ => Err(text_blocks(&format!(
+            "*{}* was already declared as {}{}.",
+            incident.title,
+            incident.reference(),
+            incident
+                .slack_channel_id
+                .as_ref()
+                .map(|channel| format!(" in <#{}>", channel))
+                .unwrap_or_default()
+        ))),
+    })
+}
+
+async fn respond(
+    state: &AppState,
+    user_id: &str,
+    response_url: Option<String>,
+    blocks: Vec<Value>,
+) -> IncidentResult<()> {
+    match response_url {
+        Some(url) => state.slack_client.post_to_response_url(&url, blocks).await,
+        None => state.slack_client.send_dm(user_id, blocks).await,
+    }
+}
+
+fn text_blocks(text: &str) -> Vec<Value> {
+    vec![json!({
+        "type": "section",
+        "text": { "type": "mrkdwn", "text": text }
+    })]
+}
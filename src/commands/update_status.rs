@@ -0,0 +1,195 @@
+use crate::app_state::AppState;
+use crate::db::models::{Incident, IncidentStatus};
+use crate::error::{IncidentError, IncidentResult};
+use crate::services::incident::IncidentService;
+use crate::services::notification::NotificationService;
+use crate::services::permissions::{Action, Permissions};
+use crate::services::webhook;
+use crate::slack::blocks;
+use crate::slack::events::SlashCommandPayload;
+use serde_json::json;
+use tracing::{error, info};
+
+const USAGE: &str = "Usage: /incident update-status [investigating|identified|monitoring]";
+
+pub async fn handle_update_status(
+    state: AppState,
+    payload: SlashCommandPayload,
+) -> IncidentResult<()> {
+    // Parse command: "/incident update-status identified"
+    let parts: Vec<&str> = payload.text.split_whitespace().collect();
+
+    // Resolution has its own command (duration, postmortem prompt, notifications)
+    let new_status = match parts.get(1).map(|s| s.parse::<IncidentStatus>()) {
+        Some(Ok(
+            status @ (IncidentStatus::Investigating
+            | IncidentStatus::Identified
+            | IncidentStatus::Monitoring),
+        )) if parts.len() == 2 => status,
+        _ => {
+            return state
+                .slack_client
+                .post_to_response_url(&payload.response_url, blocks::error_blocks(USAGE))
+                .await;
+        }
+    };
+
+    // Get incident from channel
+    let incident_service =
+        IncidentService::new(state.pool.clone()).with_permissions(Permissions::from_state(&state));
+    let incident = match incident_service.get_by_channel(&payload.channel_id).await {
+        Ok(inc) => inc,
+        Err(IncidentError::NotFound) => {
+            return state
+                .slack_client
+                .post_to_response_url(
+                    &payload.response_url,
+                    blocks::error_blocks("No active incident in this channel"),
+                )
+                .await;
+        }
+        Err(e) => return Err(e),
+    };
+
+    if let Err(IncidentError::PermissionDenied { .. }) = incident_service
+        .authorize(Action::ChangeStatus, &incident, &payload.user_id)
+        .await
+    {
+        return state
+            .slack_client
+            .post_to_response_url(
+                &payload.response_url,
+                blocks::permission_denied_blocks("change incident status"),
+            )
+            .await;
+    }
+
+    match change_status_and_announce(&state, &incident, new_status, &payload.user_id).await {
+        Ok(_) => {}
+        Err(IncidentError::InvalidStateTransition { from, to }) => {
+            return state
+                .slack_client
+                .post_to_response_url(
+                    &payload.response_url,
+                    blocks::error_blocks(&transition_error_text(from, to)),
+                )
+                .await;
+        }
+        Err(e) => return Err(e),
+    }
+
+    // Acknowledge via response_url
+    state
+        .slack_client
+        .post_to_response_url(
+            &payload.response_url,
+            vec![serde_json::json!({
+                "type": "section",
+                "text": {
+                    "type": "mrkdwn",
+                    "text": format!("✅ Status changed to {}", new_status.as_db_str())
+                }
+            })],
+        )
+        .await
+}
+
+/// Move an open incident to `new_status`, announce it in the channel, and
+/// sync Statuspage and webhooks. Callers are responsible for the commander
+/// check; invalid transitions come back as `InvalidStateTransition`.
+pub async fn change_status_and_announce(
+    state: &AppState,
+    incident: &Incident,
+    new_status: IncidentStatus,
+    user_id: &str,
+) -> IncidentResult<Incident> {
+    let old_status = incident.status;
+    let updated_incident = IncidentService::new(state.pool.clone())
+        .transition_status(incident.id, new_status, user_id.to_string())
+        .await?;
+
+    // Post to channel
+    let change_blocks = blocks::status_change_blocks(old_status, new_status, user_id);
+    if let Some(channel_id) = &updated_incident.slack_channel_id {
+        if let Err(e) = state
+            .slack_client
+            .post_message(channel_id, change_blocks.clone())
+            .await
+        {
+            error!("Failed to post status change: {}", e);
+        }
+    }
+    if let Err(e) = NotificationService::new(
+        state.pool.clone(),
+        state.slack_client.clone(),
+        state.config.clone(),
+    )
+    .notify_subscribers(&updated_incident, &change_blocks)
+    .await
+    {
+        error!("Failed to send subscriber digests: {}", e);
+    }
+
+    // Enqueue Statuspage sync if component mapping exists
+    crate::jobs::statuspage_sync::enqueue_for_incident(
+        &state.pool,
+        &state.job_sender,
+        &updated_incident,
+    )
+    .await;
+    crate::jobs::statuspage_sync::enqueue_status_change(&state.job_sender, &updated_incident);
+    crate::jobs::channel_status::enqueue(state, &updated_incident);
+    crate::services::pinned_summary::refresh(state, &updated_incident).await;
+    webhook::enqueue(
+        state,
+        webhook::status_event(new_status),
+        &updated_incident,
+        Some(json!({ "status": old_status })),
+    )
+    .await;
+
+    info!(
+        "Status changed for incident {} from {:?} to {:?} by {}",
+        incident.id, old_status, new_status, user_id
+    );
+    Ok(updated_incident)
+}
+
+/// Error shown when a status change isn't allowed from the current status.
+pub fn transition_error_text(from: IncidentStatus, to: IncidentStatus) -> String {
+    if from.is_terminal() {
+        return format!(
+            "Cannot move incident from {} to {}. Reopen it first with `/incident reopen`",
+            from.as_db_str(),
+            to.as_db_str()
+        );
+    }
+    let allowed: Vec<&str> = from
+        .valid_transitions()
+        .iter()
+        .map(|s| s.as_db_str())
+        .collect();
+    format!(
+        "Cannot move incident from {} to {}. Allowed: {}",
+        from.as_db_str(),
+        to.as_db_str(),
+        allowed.join(", ")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transition_error_text() {
+        assert_eq!(
+            transition_error_text(IncidentStatus::Monitoring, IncidentStatus::Identified),
+            "Cannot move incident from monitoring to identified. Allowed: resolved"
+        );
+        assert_eq!(
+            transition_error_text(IncidentStatus::Resolved, IncidentStatus::Monitoring),
+            "Cannot move incident from resolved to monitoring. Reopen it first with `/incident reopen`"
+        );
+    }
+}
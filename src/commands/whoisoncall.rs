@@ -0,0 +1,108 @@
+use crate::app_state::AppState;
+use crate::error::IncidentResult;
+use crate::services::oncall::{self, OnCall};
+use crate::slack::blocks;
+use crate::slack::events::SlashCommandPayload;
+use serde_json::{json, Value};
+use tracing::warn;
+
+const USAGE: &str = "Usage: /incident whoisoncall <service>";
+
+/// The SERVICES entry named after `whoisoncall`, ignoring case.
+fn parse_command<'a>(text: &str, services: &'a [String]) -> Result<&'a str, String> {
+    let name = text
+        .trim()
+        .split_once(char::is_whitespace)
+        .map(|(_, rest)| rest.trim())
+        .unwrap_or_default();
+    if name.is_empty() {
+        return Err(USAGE.to_string());
+    }
+    services
+        .iter()
+        .find(|s| s.eq_ignore_ascii_case(name))
+        .map(String::as_str)
+        .ok_or_else(|| {
+            format!(
+                "Unknown service '{}'. Services: {}",
+                name,
+                services.join(", ")
+            )
+        })
+}
+
+/// `/incident whoisoncall <service>` — who the service's PagerDuty or
+/// Opsgenie schedule has on call right now.
+pub async fn handle_whoisoncall(
+    state: AppState,
+    payload: SlashCommandPayload,
+) -> IncidentResult<()> {
+    let blocks = match (
+        state.oncall_client.as_ref(),
+        parse_command(&payload.text, &state.config.services),
+    ) {
+        (None, _) => blocks::error_blocks("On-call schedules are not configured"),
+        (_, Err(message)) => blocks::error_blocks(&message),
+        (Some(client), Ok(service)) => {
+            let provider = client.provider().label();
+            match oncall::current_on_call(&state, service).await {
+                Ok(Some(OnCall::User(user_id))) => {
+                    text_blocks(&format!("📟 <@{}> is on call for *{}*", user_id, service))
+                }
+                Ok(Some(OnCall::Email(email))) => text_blocks(&format!(
+                    "📟 {} is on call for *{}* (no Slack user has that email)",
+                    email, service
+                )),
+                Ok(Some(OnCall::Nobody)) => text_blocks(&format!(
+                    "Nobody is on call for *{}* in {} right now",
+                    service, provider
+                )),
+                Ok(None) => blocks::error_blocks(&format!(
+                    "{} has no on-call schedule in ONCALL_SCHEDULES",
+                    service
+                )),
+                Err(e) => {
+                    warn!("On-call lookup for {} failed: {}", service, e);
+                    blocks::error_blocks(&format!(
+                        "Couldn't reach {}. Try again shortly.",
+                        provider
+                    ))
+                }
+            }
+        }
+    };
+
+    state
+        .slack_client
+        .post_to_response_url(&payload.response_url, blocks)
+        .await
+}
+
+fn text_blocks(text: &str) -> Vec<Value> {
+    vec![json!({
+        "type": "section",
+        "text": { "type": "mrkdwn", "text": text }
+    })]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_command() {
+        let services = vec!["VPN".to_string(), "Payments API".to_string()];
+        assert_eq!(parse_command("whoisoncall vpn", &services), Ok("VPN"));
+        assert_eq!(
+            parse_command("whoisoncall  payments api ", &services),
+            Ok("Payments API")
+        );
+        assert_eq!(
+            parse_command("whoisoncall", &services),
+            Err(USAGE.to_string())
+        );
+        assert!(parse_command("whoisoncall dns", &services)
+            .unwrap_err()
+            .starts_with("Unknown service 'dns'"));
+    }
+}
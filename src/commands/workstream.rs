@@ -0,0 +1,383 @@
+use crate::app_state::AppState;
+use crate::db::models::Incident;
+use crate::error::{IncidentError, IncidentResult};
+use crate::services::incident::IncidentService;
+use crate::services::permissions::{Action, Permissions};
+use crate::services::workstream::WorkstreamService;
+use crate::slack::blocks;
+use crate::slack::events::SlashCommandPayload;
+use crate::utils::mention::parse_user_mention;
+use tracing::{error, info};
+
+const USAGE: &str = "Usage: /incident workstream create [name] [@lead] | update [name] [message] | lead [name] [@user] | list";
+
+#[derive(Debug, PartialEq)]
+enum WorkstreamCommand {
+    Create {
+        name: String,
+        lead_id: Option<String>,
+    },
+    Update {
+        name: String,
+        message: String,
+    },
+    Lead {
+        name: String,
+        lead_id: String,
+    },
+    List,
+}
+
+fn parse_command(text: &str) -> Result<WorkstreamCommand, String> {
+    // text is "workstream <action> <name> <rest...>"
+    let mut parts = text.trim().splitn(4, ' ').skip(1);
+    let action = parts.next().unwrap_or("").trim();
+    let name = parts.next().unwrap_or("").trim();
+    let rest = parts.next().unwrap_or("").trim();
+
+    if action == "list" {
+        return Ok(WorkstreamCommand::List);
+    }
+    if name.is_empty() {
+        return Err(USAGE.to_string());
+    }
+
+    match action {
+        "create" if rest.is_empty() => Ok(WorkstreamCommand::Create {
+            name: name.to_string(),
+            lead_id: None,
+        }),
+        "create" => parse_user_mention(rest)
+            .map(|lead_id| WorkstreamCommand::Create {
+                name: name.to_string(),
+                lead_id: Some(lead_id),
+            })
+            .ok_or_else(|| "Lead must be an @mention".to_string()),
+        "update" if rest.is_empty() => Err("Update message cannot be empty".to_string()),
+        "update" => Ok(WorkstreamCommand::Update {
+            name: name.to_string(),
+            message: rest.to_string(),
+        }),
+        "lead" => parse_user_mention(rest)
+            .map(|lead_id| WorkstreamCommand::Lead {
+                name: name.to_string(),
+                lead_id,
+            })
+            .ok_or_else(|| "Lead must be an @mention".to_string()),
+        _ => Err(USAGE.to_string()),
+    }
+}
+
+pub async fn handle_workstream(
+    state: AppState,
+    payload: SlashCommandPayload,
+) -> IncidentResult<()> {
+    let command = match parse_command(&payload.text) {
+        Ok(c) => c,
+        Err(message) => return reply(&state, &payload, blocks::error_blocks(&message)).await,
+    };
+
+    // Get incident from channel
+    let incident_service =
+        IncidentService::new(state.pool.clone()).with_permissions(Permissions::from_state(&state));
+    let incident = match incident_service.get_by_channel(&payload.channel_id).await {
+        Ok(inc) => inc,
+        Err(IncidentError::NotFound) => {
+            return reply(
+                &state,
+                &payload,
+                blocks::error_blocks("No active incident in this channel"),
+            )
+            .await;
+        }
+        Err(e) => return Err(e),
+    };
+
+    let workstream_service = WorkstreamService::new(state.pool.clone());
+
+    // Creating workstreams and assigning leads is reserved for the commander;
+    // updates are also open to the workstream lead (checked by the service).
+    if matches!(
+        command,
+        WorkstreamCommand::Create { .. } | WorkstreamCommand::Lead { .. }
+    ) {
+        if let Err(IncidentError::PermissionDenied { .. }) = incident_service
+            .authorize(Action::ManageWorkstreams, &incident, &payload.user_id)
+            .await
+        {
+            return reply(
+                &state,
+                &payload,
+                blocks::permission_denied_blocks("manage workstreams"),
+            )
+            .await;
+        }
+    }
+
+    let changes_summary = command != WorkstreamCommand::List;
+    let result = match command {
+        WorkstreamCommand::Create { name, lead_id } => {
+            let lead_id = lead_id.unwrap_or_else(|| payload.user_id.clone());
+            create(
+                &state,
+                &workstream_service,
+                &incident,
+                &name,
+                &lead_id,
+                &payload.user_id,
+            )
+            .await
+        }
+        WorkstreamCommand::Update { name, message } => {
+            update(
+                &state,
+                &workstream_service,
+                &incident,
+                &name,
+                &message,
+                &payload.user_id,
+            )
+            .await
+        }
+        WorkstreamCommand::Lead { name, lead_id } => {
+            change_lead(
+                &state,
+                &workstream_service,
+                &incident,
+                &name,
+                &lead_id,
+                &payload.user_id,
+            )
+            .await
+        }
+        WorkstreamCommand::List => list(&workstream_service, &incident).await,
+    };
+
+    let ack = match result {
+        Ok(text) => {
+            if changes_summary {
+                crate::services::pinned_summary::refresh(&state, &incident).await;
+            }
+            text
+        }
+        Err(IncidentError::NotFound) => {
+            return reply(&state, &payload, blocks::error_blocks("No such workstream")).await;
+        }
+        Err(IncidentError::ValidationError { reason, .. }) => {
+            return reply(&state, &payload, blocks::error_blocks(&reason)).await;
+        }
+        Err(IncidentError::PermissionDenied { .. }) => {
+            return reply(
+                &state,
+                &payload,
+                blocks::error_blocks(
+                    "Only the workstream lead or incident commander can post updates",
+                ),
+            )
+            .await;
+        }
+        Err(e) => return Err(e),
+    };
+
+    reply(
+        &state,
+        &payload,
+        vec![serde_json::json!({
+            "type": "section",
+            "text": {
+                "type": "mrkdwn",
+                "text": ack
+            }
+        })],
+    )
+    .await
+}
+
+async fn create(
+    state: &AppState,
+    service: &WorkstreamService,
+    incident: &Incident,
+    name: &str,
+    lead_id: &str,
+    user_id: &str,
+) -> IncidentResult<String> {
+    let workstream = service.create(incident, name, lead_id, user_id).await?;
+
+    // Open the workstream thread in the incident channel
+    if let Some(channel_id) = &incident.slack_channel_id {
+        match state
+            .slack_client
+            .post_message(channel_id, blocks::workstream_started_blocks(&workstream))
+            .await
+        {
+            Ok(ts) => service.set_thread_ts(&workstream, &ts).await?,
+            Err(e) => error!("Failed to open workstream thread: {}", e),
+        }
+
+        if let Err(e) = state
+            .slack_client
+            .invite_users(channel_id, vec![workstream.lead_id.clone()])
+            .await
+        {
+            // already_in_channel is the common case here
+            error!("Failed to invite workstream lead: {}", e);
+        }
+    }
+
+    info!(
+        "Workstream {} created for incident {} by {}",
+        workstream.name, incident.id, user_id
+    );
+    Ok(format!(
+        "✅ Workstream *{}* created (lead <@{}>)",
+        workstream.name, workstream.lead_id
+    ))
+}
+
+async fn update(
+    state: &AppState,
+    service: &WorkstreamService,
+    incident: &Incident,
+    name: &str,
+    message: &str,
+    user_id: &str,
+) -> IncidentResult<String> {
+    let workstream = service
+        .post_update(incident, name, message, user_id)
+        .await?;
+
+    if let (Some(channel_id), Some(thread_ts)) = (&incident.slack_channel_id, &workstream.thread_ts)
+    {
+        let update_blocks =
+            blocks::status_update_blocks(incident.severity_level(), message, user_id);
+        if let Err(e) = state
+            .slack_client
+            .post_thread_reply(channel_id, thread_ts, update_blocks, false)
+            .await
+        {
+            error!("Failed to post workstream update to thread: {}", e);
+        }
+    }
+
+    Ok(format!("✅ Update posted to *{}*", workstream.name))
+}
+
+async fn change_lead(
+    state: &AppState,
+    service: &WorkstreamService,
+    incident: &Incident,
+    name: &str,
+    lead_id: &str,
+    user_id: &str,
+) -> IncidentResult<String> {
+    let workstream = service.set_lead(incident, name, lead_id, user_id).await?;
+
+    if let Some(channel_id) = &incident.slack_channel_id {
+        if let Err(e) = state
+            .slack_client
+            .invite_users(channel_id, vec![workstream.lead_id.clone()])
+            .await
+        {
+            error!("Failed to invite workstream lead: {}", e);
+        }
+        if let Some(thread_ts) = &workstream.thread_ts {
+            let message = format!("Lead changed to <@{}>", workstream.lead_id);
+            if let Err(e) = state
+                .slack_client
+                .post_thread_reply(
+                    channel_id,
+                    thread_ts,
+                    blocks::status_update_blocks(incident.severity_level(), &message, user_id),
+                    false,
+                )
+                .await
+            {
+                error!("Failed to post lead change to thread: {}", e);
+            }
+        }
+    }
+
+    Ok(format!(
+        "✅ <@{}> now leads *{}*",
+        workstream.lead_id, workstream.name
+    ))
+}
+
+async fn list(service: &WorkstreamService, incident: &Incident) -> IncidentResult<String> {
+    let workstreams = service.list(incident).await?;
+    if workstreams.is_empty() {
+        return Ok("_No workstreams yet._".to_string());
+    }
+
+    Ok(workstreams
+        .iter()
+        .map(|w| {
+            format!(
+                "• *{}* (lead <@{}>): {}",
+                w.name,
+                w.lead_id,
+                w.last_update.as_deref().unwrap_or("_No updates yet_")
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
+
+async fn reply(
+    state: &AppState,
+    payload: &SlashCommandPayload,
+    blocks: Vec<serde_json::Value>,
+) -> IncidentResult<()> {
+    state
+        .slack_client
+        .post_to_response_url(&payload.response_url, blocks)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_create_with_and_without_lead() {
+        assert_eq!(
+            parse_command("workstream create database").unwrap(),
+            WorkstreamCommand::Create {
+                name: "database".to_string(),
+                lead_id: None
+            }
+        );
+        assert_eq!(
+            parse_command("workstream create database <@U024LEAD|dana>").unwrap(),
+            WorkstreamCommand::Create {
+                name: "database".to_string(),
+                lead_id: Some("U024LEAD".to_string())
+            }
+        );
+        assert!(parse_command("workstream create database @dana").is_err());
+    }
+
+    #[test]
+    fn test_parse_update_keeps_full_message() {
+        assert_eq!(
+            parse_command("workstream update database Failover to replica done").unwrap(),
+            WorkstreamCommand::Update {
+                name: "database".to_string(),
+                message: "Failover to replica done".to_string()
+            }
+        );
+        assert!(parse_command("workstream update database").is_err());
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_or_incomplete_commands() {
+        assert_eq!(
+            parse_command("workstream list").unwrap(),
+            WorkstreamCommand::List
+        );
+        assert!(parse_command("workstream").is_err());
+        assert!(parse_command("workstream create").is_err());
+        assert!(parse_command("workstream close database").is_err());
+        assert!(parse_command("workstream lead database").is_err());
+    }
+}
@@ -0,0 +1,586 @@
+//! Configuration bundles for promoting a tested setup between environments.
+//!
+//! A bundle holds two kinds of settings:
+//!
+//! * **Environment** — services, notification routing and per-severity
+//!   rules come from environment variables. Importing compares them and
+//!   names the variables that differ, but cannot change them.
+//! * **Database** — incident templates and Statuspage component mappings.
+//!   Importing makes the database match the bundle in one transaction:
+//!   templates missing from the bundle are deactivated, mappings deleted.
+//!
+//! Every import returns the diff; with `dry_run` nothing is written.
+
+use crate::config::{AppConfig, ExecTiers, NotificationRule};
+use crate::db::models::{IncidentTemplate, Severity};
+use crate::error::{IncidentError, IncidentResult};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sqlx_postgres::PgPool;
+use std::collections::{BTreeMap, BTreeSet};
+
+pub const CONFIG_BUNDLE_VERSION: u32 = 1;
+
+const SEVERITIES: [Severity; 4] = [Severity::P1, Severity::P2, Severity::P3, Severity::P4];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfigBundle {
+    pub version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub environment: EnvironmentConfig,
+    /// Active incident templates, by name
+    pub templates: Vec<TemplateConfig>,
+    /// Service name -> Statuspage component ID
+    pub statuspage_components: BTreeMap<String, String>,
+}
+
+/// Settings read from environment variables; compared, never applied.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EnvironmentConfig {
+    pub services: Vec<String>,
+    pub service_owners: BTreeMap<String, Vec<String>>,
+    /// Missing from bundles exported before SERVICE_CHANNELS existed
+    #[serde(default)]
+    pub service_channels: BTreeMap<String, Vec<String>>,
+    pub p1_channels: Vec<String>,
+    pub p2_channels: Vec<String>,
+    pub p1_users: Vec<String>,
+    /// Missing from bundles exported before P1_USER_GROUPS existed
+    #[serde(default)]
+    pub p1_user_groups: Vec<String>,
+    /// Missing from bundles exported before EXEC_TIERS existed
+    #[serde(default)]
+    pub exec_tiers: BTreeMap<String, ExecTiers>,
+    pub backup_commanders: Vec<String>,
+    /// "P1".."P4" -> roles and reminders for that severity
+    pub severities: BTreeMap<String, SeverityConfig>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SeverityConfig {
+    /// Roles to claim besides the commander
+    pub required_roles: Vec<String>,
+    pub stale_incident_minutes: Option<u64>,
+    /// Days after resolution a postmortem is due; `None` if it is optional
+    #[serde(default)]
+    pub postmortem_due_days: Option<u64>,
+    /// Event -> NOTIFICATION_RULES entry; empty when the severity uses the
+    /// P1/P2 channel settings
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub notification_rules: BTreeMap<String, NotificationRule>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TemplateConfig {
+    pub name: String,
+    pub title: String,
+    pub severity: Severity,
+    pub affected_service: Option<String>,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeAction {
+    Added,
+    Updated,
+    Removed,
+}
+
+/// One database-backed setting the import adds, changes or removes.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ConfigChange {
+    /// `templates` or `statuspage_components`
+    pub section: &'static str,
+    pub key: String,
+    pub action: ChangeAction,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ImportReport {
+    pub dry_run: bool,
+    pub changes: Vec<ConfigChange>,
+    /// Environment variables whose current value differs from the bundle
+    pub environment_drift: Vec<&'static str>,
+}
+
+impl EnvironmentConfig {
+    pub fn from_app_config(config: &AppConfig) -> Self {
+        Self {
+            services: config.services.clone(),
+            service_owners: config
+                .service_owners
+                .iter()
+                .map(|(service, owners)| (service.clone(), owners.clone()))
+                .collect(),
+            service_channels: config
+                .service_channels
+                .iter()
+                .map(|(service, channels)| (service.clone(), channels.clone()))
+                .collect(),
+            p1_channels: config.p1_channels.clone(),
+            p2_channels: config.p2_channels.clone(),
+            p1_users: config.p1_users.clone(),
+            p1_user_groups: config.p1_user_groups.clone(),
+            exec_tiers: config
+                .exec_tiers
+                .iter()
+                .map(|(severity, tiers)| (severity.clone(), tiers.clone()))
+                .collect(),
+            backup_commanders: config.backup_commanders.clone(),
+            severities: SEVERITIES
+                .iter()
+                .map(|&severity| {
+                    (
+                        severity.as_db_str().to_string(),
+                        SeverityConfig {
+                            required_roles: config.required_roles_for(severity),
+                            stale_incident_minutes: config.stale_threshold_for(severity),
+                            postmortem_due_days: config.postmortem_due_days_for(severity),
+                            notification_rules: config
+                                .notification_rules
+                                .iter()
+                                .find(|(key, _)| key.parse::<Severity>().ok() == Some(severity))
+                                .map(|(_, events)| {
+                                    events
+                                        .iter()
+                                        .map(|(event, rule)| (event.clone(), rule.clone()))
+                                        .collect()
+                                })
+                                .unwrap_or_default(),
+                        },
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    /// Variables to change for this environment to match `other`.
+    fn drift(&self, other: &Self) -> Vec<&'static str> {
+        let mut drift = Vec::new();
+        // Severities missing from a bundle have no roles, reminders or required
+        // postmortems
+        let per_severity = |config: &Self| -> Vec<SeverityConfig> {
+            SEVERITIES
+                .iter()
+                .map(|s| {
+                    config
+                        .severities
+                        .get(s.as_db_str())
+                        .cloned()
+                        .unwrap_or_default()
+                })
+                .collect()
+        };
+        let (ours, theirs) = (per_severity(self), per_severity(other));
+
+        if self.services != other.services {
+            drift.push("SERVICES");
+        }
+        if self.service_owners != other.service_owners {
+            drift.push("SERVICE_OWNERS");
+        }
+        if self.service_channels != other.service_channels {
+            drift.push("SERVICE_CHANNELS");
+        }
+        if self.p1_channels != other.p1_channels {
+            drift.push("P1_CHANNELS");
+        }
+        if self.p2_channels != other.p2_channels {
+            drift.push("P2_CHANNELS");
+        }
+        if self.p1_users != other.p1_users {
+            drift.push("P1_USERS");
+        }
+        if self.p1_user_groups != other.p1_user_groups {
+            drift.push("P1_USER_GROUPS");
+        }
+        if self.exec_tiers != other.exec_tiers {
+            drift.push("EXEC_TIERS");
+        }
+        if self.backup_commanders != other.backup_commanders {
+            drift.push("BACKUP_COMMANDERS");
+        }
+        if ours
+            .iter()
+            .map(|s| &s.required_roles)
+            .ne(theirs.iter().map(|s| &s.required_roles))
+        {
+            drift.push("REQUIRED_ROLES");
+        }
+        if ours
+            .iter()
+            .map(|s| s.stale_incident_minutes)
+            .ne(theirs.iter().map(|s| s.stale_incident_minutes))
+        {
+            drift.push("STALE_INCIDENT_MINUTES");
+        }
+        if ours
+            .iter()
+            .map(|s| s.postmortem_due_days)
+            .ne(theirs.iter().map(|s| s.postmortem_due_days))
+        {
+            drift.push("POSTMORTEM_DUE_DAYS");
+        }
+        if ours
+            .iter()
+            .map(|s| &s.notification_rules)
+            .ne(theirs.iter().map(|s| &s.notification_rules))
+        {
+            drift.push("NOTIFICATION_RULES");
+        }
+        drift
+    }
+}
+
+impl From<IncidentTemplate> for TemplateConfig {
+    fn from(template: IncidentTemplate) -> Self {
+        Self {
+            name: template.name,
+            title: template.title,
+            severity: template.severity,
+            affected_service: template.affected_service,
+            description: template.description,
+        }
+    }
+}
+
+/// Snapshot the current configuration.
+pub async fn export_bundle(pool: &PgPool, config: &AppConfig) -> IncidentResult<ConfigBundle> {
+    let templates = crate::db::queries::templates::list_active_templates(pool)
+        .await?
+        .into_iter()
+        .map(TemplateConfig::from)
+        .collect();
+
+    let statuspage_components = sqlx::query_as::query_as::<_, (String, String)>(
+        r#"
+        SELECT service_name, component_id FROM statuspage_mappings
+        ORDER BY service_name
+        "#,
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .collect();
+
+    Ok(ConfigBundle {
+        version: CONFIG_BUNDLE_VERSION,
+        exported_at: Utc::now(),
+        environment: EnvironmentConfig::from_app_config(config),
+        templates,
+        statuspage_components,
+    })
+}
+
+/// Diff `bundle` against the current configuration and, unless `dry_run`,
+/// apply its database-backed settings.
+pub async fn import_bundle(
+    pool: &PgPool,
+    config: &AppConfig,
+    bundle: &ConfigBundle,
+    dry_run: bool,
+) -> IncidentResult<ImportReport> {
+    validate_bundle(bundle)?;
+
+    let current = export_bundle(pool, config).await?;
+    let changes = diff_bundles(&current, bundle);
+    let environment_drift = current.environment.drift(&bundle.environment);
+
+    if !dry_run && !changes.is_empty() {
+        apply_changes(pool, bundle, &changes).await?;
+    }
+
+    Ok(ImportReport {
+        dry_run,
+        changes,
+        environment_drift,
+    })
+}
+
+async fn apply_changes(
+    pool: &PgPool,
+    bundle: &ConfigBundle,
+    changes: &[ConfigChange],
+) -> IncidentResult<()> {
+    let mut tx = pool.begin().await?;
+
+    for change in changes {
+        match (change.section, change.action) {
+            ("templates", ChangeAction::Removed) => {
+                sqlx::query::query(
+                    r#"
+                    UPDATE incident_templates SET is_active = false, updated_at = NOW()
+                    WHERE name = $1
+                    "#,
+                )
+                .bind(&change.key)
+                .execute(&mut *tx)
+                .await?;
+            }
+            ("templates", _) => {
+                let Some(template) = bundle.templates.iter().find(|t| t.name == change.key) else {
+                    continue;
+                };
+                sqlx::query::query(
+                    r#"
+                    INSERT INTO incident_templates
+                        (name, title, severity, affected_service, description, is_active)
+                    VALUES ($1, $2, $3, $4, $5, true)
+                    ON CONFLICT (name) DO UPDATE SET
+                        title = EXCLUDED.title,
+                        severity = EXCLUDED.severity,
+                        affected_service = EXCLUDED.affected_service,
+                        description = EXCLUDED.description,
+                        is_active = true,
+                        updated_at = NOW()
+                    "#,
+                )
+                .bind(&template.name)
+                .bind(&template.title)
+                .bind(template.severity.as_db_str())
+                .bind(&template.affected_service)
+                .bind(&template.description)
+                .execute(&mut *tx)
+                .await?;
+            }
+            // Mappings are deleted first and re-inserted below, so two
+            // services can swap components without tripping the unique key
+            ("statuspage_components", ChangeAction::Removed | ChangeAction::Updated) => {
+                sqlx::query::query("DELETE FROM statuspage_mappings WHERE service_name = $1")
+                    .bind(&change.key)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+            _ => {}
+        }
+    }
+
+    for change in changes.iter().filter(|c| {
+        c.section == "statuspage_components"
+            && matches!(c.action, ChangeAction::Added | ChangeAction::Updated)
+    }) {
+        sqlx::query::query(
+            r#"
+            INSERT INTO statuspage_mappings (service_name, component_id)
+            VALUES ($1, $2)
+            "#,
+        )
+        .bind(&change.key)
+        .bind(&bundle.statuspage_components[&change.key])
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Changes that make `current`'s database-backed settings match `target`,
+/// ordered by section, then key.
+fn diff_bundles(current: &ConfigBundle, target: &ConfigBundle) -> Vec<ConfigChange> {
+    let mut changes = Vec::new();
+
+    let templates = |bundle: &ConfigBundle| -> BTreeMap<String, Value> {
+        bundle
+            .templates
+            .iter()
+            .map(|t| (t.name.clone(), json!(t)))
+            .collect()
+    };
+    diff_section(
+        "templates",
+        &templates(current),
+        &templates(target),
+        &mut changes,
+    );
+
+    let components = |bundle: &ConfigBundle| -> BTreeMap<String, Value> {
+        bundle
+            .statuspage_components
+            .iter()
+            .map(|(service, component)| (service.clone(), json!(component)))
+            .collect()
+    };
+    diff_section(
+        "statuspage_components",
+        &components(current),
+        &components(target),
+        &mut changes,
+    );
+
+    changes
+}
+
+fn diff_section(
+    section: &'static str,
+    current: &BTreeMap<String, Value>,
+    target: &BTreeMap<String, Value>,
+    changes: &mut Vec<ConfigChange>,
+) {
+    let keys: BTreeSet<&String> = current.keys().chain(target.keys()).collect();
+    for key in keys {
+        let (before, after) = (current.get(key), target.get(key));
+        let action = match (before, after) {
+            (None, Some(_)) => ChangeAction::Added,
+            (Some(_), None) => ChangeAction::Removed,
+            (Some(before), Some(after)) if before != after => ChangeAction::Updated,
+            _ => continue,
+        };
+        changes.push(ConfigChange {
+            section,
+            key: key.clone(),
+            action,
+            before: before.cloned(),
+            after: after.cloned(),
+        });
+    }
+}
+
+fn validate_bundle(bundle: &ConfigBundle) -> IncidentResult<()> {
+    let invalid = |field: &str, reason: String| IncidentError::ValidationError {
+        field: field.to_string(),
+        reason,
+    };
+
+    if bundle.version != CONFIG_BUNDLE_VERSION {
+        return Err(invalid(
+            "version",
+            format!(
+                "Unsupported config bundle version {} (expected {})",
+                bundle.version, CONFIG_BUNDLE_VERSION
+            ),
+        ));
+    }
+
+    let mut names = BTreeSet::new();
+    for template in &bundle.templates {
+        if template.name.trim().is_empty() {
+            return Err(invalid("templates", "Template name cannot be empty".into()));
+        }
+        if !names.insert(template.name.as_str()) {
+            return Err(invalid(
+                "templates",
+                format!("Duplicate template '{}'", template.name),
+            ));
+        }
+        if template.title.chars().count() > 100 {
+            return Err(invalid(
+                "templates",
+                format!("Title of '{}' is over 100 characters", template.name),
+            ));
+        }
+    }
+
+    let mut components = BTreeSet::new();
+    for (service, component) in &bundle.statuspage_components {
+        if component.trim().is_empty() || !components.insert(component.as_str()) {
+            return Err(invalid(
+                "statuspage_components",
+                format!("Missing or duplicate component ID for '{}'", service),
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template(name: &str, title: &str) -> TemplateConfig {
+        TemplateConfig {
+            name: name.to_string(),
+            title: title.to_string(),
+            severity: Severity::P2,
+            affected_service: None,
+            description: None,
+        }
+    }
+
+    fn bundle(templates: Vec<TemplateConfig>, components: &[(&str, &str)]) -> ConfigBundle {
+        ConfigBundle {
+            version: CONFIG_BUNDLE_VERSION,
+            exported_at: Utc::now(),
+            environment: EnvironmentConfig::default(),
+            templates,
+            statuspage_components: components
+                .iter()
+                .map(|(s, c)| (s.to_string(), c.to_string()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_diff_bundles() {
+        let current = bundle(
+            vec![template("cdn", "CDN issues"), template("dns", "DNS outage")],
+            &[("API", "cmp_api"), ("Web", "cmp_web")],
+        );
+        let target = bundle(
+            vec![template("dns", "DNS failure"), template("vpn", "VPN down")],
+            &[("API", "cmp_web"), ("Web", "cmp_api")],
+        );
+
+        let changes = diff_bundles(&current, &target);
+        let summary: Vec<(&str, &str, ChangeAction)> = changes
+            .iter()
+            .map(|c| (c.section, c.key.as_str(), c.action))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("templates", "cdn", ChangeAction::Removed),
+                ("templates", "dns", ChangeAction::Updated),
+                ("templates", "vpn", ChangeAction::Added),
+                ("statuspage_components", "API", ChangeAction::Updated),
+                ("statuspage_components", "Web", ChangeAction::Updated),
+            ]
+        );
+        assert!(diff_bundles(&target, &target).is_empty());
+    }
+
+    #[test]
+    fn test_environment_drift_names_variables() {
+        let mut staging = EnvironmentConfig::default();
+        let mut prod = staging.clone();
+        assert!(staging.drift(&prod).is_empty());
+
+        staging.p1_channels = vec!["C_GENERAL".to_string()];
+        staging.service_channels =
+            BTreeMap::from([("vpn".to_string(), vec!["C_NETWORK".to_string()])]);
+        staging.severities.insert(
+            "P1".to_string(),
+            SeverityConfig {
+                required_roles: vec!["scribe".to_string()],
+                stale_incident_minutes: None,
+                postmortem_due_days: None,
+                notification_rules: BTreeMap::new(),
+            },
+        );
+        prod.severities
+            .insert("P1".to_string(), SeverityConfig::default());
+        assert_eq!(
+            prod.drift(&staging),
+            vec!["SERVICE_CHANNELS", "P1_CHANNELS", "REQUIRED_ROLES"]
+        );
+    }
+
+    #[test]
+    fn test_validate_bundle() {
+        assert!(validate_bundle(&bundle(vec![template("dns", "DNS")], &[])).is_ok());
+
+        let mut old = bundle(vec![], &[]);
+        old.version = 0;
+        assert!(validate_bundle(&old).is_err());
+        assert!(validate_bundle(&bundle(
+            vec![template("dns", "A"), template("dns", "B")],
+            &[]
+        ))
+        .is_err());
+        assert!(validate_bundle(&bundle(vec![], &[("API", "cmp"), ("Web", "cmp")])).is_err());
+    }
+}
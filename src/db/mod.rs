@@ -1,5 +1,7 @@
+pub mod config_bundle;
 pub mod models;
 pub mod queries;
+pub mod replication;
 
 use crate::error::IncidentResult;
 use sqlx_postgres::PgPool;
@@ -1,8 +1,9 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::from_row::FromRow;
 use sqlx::row::Row;
 use sqlx_postgres::PgRow;
+use std::collections::BTreeMap;
 use std::io::{Error as IoError, ErrorKind};
 use uuid::Uuid;
 
@@ -12,6 +13,9 @@ pub type SlackUserId = String; // e.g., "U024BE7LH"
 pub type SlackChannelId = String; // e.g., "C024BE91L"
 
 // ── Severity ──
+/// Routing tier. Notification routing, SLAs, required roles and the other
+/// per-severity settings are keyed by these four; the severities people see
+/// and pick are `SeverityLevel`s mapped onto them.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Severity {
     P1,
@@ -52,6 +56,43 @@ impl Severity {
     }
 }
 
+/// A severity as a team names it (`SEV0`, `SEV1`, ...), with its own label
+/// and emoji and the routing tier it behaves as. Configured with SEVERITIES
+/// and looked up through `utils::severity`; by default there's one per tier.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SeverityLevel {
+    pub code: String,
+    pub label: String,
+    pub emoji: String,
+    pub tier: Severity,
+}
+
+impl SeverityLevel {
+    /// The default level for a tier: `P1`, "P1 (Critical)", 🔴
+    pub fn builtin(tier: Severity) -> Self {
+        Self {
+            code: tier.as_db_str().to_string(),
+            label: tier.label().to_string(),
+            emoji: tier.emoji().to_string(),
+            tier,
+        }
+    }
+}
+
+impl From<Severity> for SeverityLevel {
+    /// The configured level standing in for a bare tier (templates, alert
+    /// routes, ...)
+    fn from(tier: Severity) -> Self {
+        crate::utils::severity::for_tier(tier).clone()
+    }
+}
+
+impl From<&SeverityLevel> for SeverityLevel {
+    fn from(level: &SeverityLevel) -> Self {
+        level.clone()
+    }
+}
+
 impl std::str::FromStr for Severity {
     type Err = String;
 
@@ -69,21 +110,27 @@ impl std::str::FromStr for Severity {
 // ── Incident Status (State Machine) ──
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum IncidentStatus {
+    /// Suspected issue opened with `/incident triage`, not yet declared
+    Triaging,
     Declared,
     Investigating,
     Identified,
     Monitoring,
     Resolved,
+    /// Closed as a false alarm with `/incident cancel`
+    Canceled,
 }
 
 impl IncidentStatus {
     pub fn as_db_str(&self) -> &'static str {
         match self {
+            IncidentStatus::Triaging => "triaging",
             IncidentStatus::Declared => "declared",
             IncidentStatus::Investigating => "investigating",
             IncidentStatus::Identified => "identified",
             IncidentStatus::Monitoring => "monitoring",
             IncidentStatus::Resolved => "resolved",
+            IncidentStatus::Canceled => "canceled",
         }
     }
 
@@ -95,11 +142,14 @@ impl IncidentStatus {
     pub fn valid_transitions(&self) -> &[IncidentStatus] {
         use IncidentStatus::*;
         match self {
+            // Declaring a triage and canceling bypass the state machine
+            Triaging => &[],
             Declared => &[Investigating, Identified, Monitoring, Resolved],
             Investigating => &[Identified, Monitoring, Resolved],
             Identified => &[Monitoring, Resolved],
             Monitoring => &[Resolved],
-            Resolved => &[], // Terminal
+            // Terminal; canceling bypasses the state machine
+            Resolved | Canceled => &[],
         }
     }
 
@@ -108,7 +158,32 @@ impl IncidentStatus {
     }
 
     pub fn is_terminal(&self) -> bool {
-        matches!(self, IncidentStatus::Resolved)
+        matches!(self, IncidentStatus::Resolved | IncidentStatus::Canceled)
+    }
+
+    /// Capitalised name for headers and topics.
+    pub fn label(&self) -> &'static str {
+        match self {
+            IncidentStatus::Triaging => "Triaging",
+            IncidentStatus::Declared => "Declared",
+            IncidentStatus::Investigating => "Investigating",
+            IncidentStatus::Identified => "Identified",
+            IncidentStatus::Monitoring => "Monitoring",
+            IncidentStatus::Resolved => "Resolved",
+            IncidentStatus::Canceled => "Canceled",
+        }
+    }
+
+    /// Traffic light shown in incident channel topics.
+    pub fn emoji(&self) -> &'static str {
+        match self {
+            IncidentStatus::Triaging => "🔍",
+            IncidentStatus::Declared | IncidentStatus::Investigating => "🔴",
+            IncidentStatus::Identified => "🟠",
+            IncidentStatus::Monitoring => "🟡",
+            IncidentStatus::Resolved => "🟢",
+            IncidentStatus::Canceled => "⚪",
+        }
     }
 }
 
@@ -117,11 +192,13 @@ impl std::str::FromStr for IncidentStatus {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s.to_ascii_lowercase().as_str() {
+            "triaging" => Ok(IncidentStatus::Triaging),
             "declared" => Ok(IncidentStatus::Declared),
             "investigating" => Ok(IncidentStatus::Investigating),
             "identified" => Ok(IncidentStatus::Identified),
             "monitoring" => Ok(IncidentStatus::Monitoring),
             "resolved" => Ok(IncidentStatus::Resolved),
+            "canceled" => Ok(IncidentStatus::Canceled),
             _ => Err(format!("Invalid incident status: {}", s)),
         }
     }
@@ -131,35 +208,190 @@ impl std::str::FromStr for IncidentStatus {
 #[derive(Debug, Clone, Serialize)]
 pub struct Incident {
     pub id: IncidentId,
+    /// With `number`, the incident's number as people quote it (`PLAT-12`)
+    pub number_prefix: String,
+    pub number: i32,
     pub slack_channel_id: Option<SlackChannelId>,
     pub title: String,
     pub severity: Severity,
+    /// Code of the `SeverityLevel` picked; `None` for incidents from before
+    /// SEVERITIES, which show as their tier's level
+    pub severity_code: Option<String>,
     pub status: IncidentStatus,
     pub affected_service: String,
     pub commander_id: SlackUserId,
     pub declared_at: DateTime<Utc>,
     pub resolved_at: Option<DateTime<Utc>>,
     pub duration_minutes: Option<i32>,
+    pub pinned_message_ts: Option<String>,
+    /// Security incident declared quietly: private channel, no broadcasts
+    pub is_quiet: bool,
+    /// Public Statuspage incident tracking this incident, once opened
+    pub statuspage_incident_id: Option<String>,
+    /// Set when the incident was canceled as a false alarm
+    pub canceled_at: Option<DateTime<Utc>>,
+    pub cancel_reason: Option<String>,
+    /// When `/incident triage` opened it, for incidents that began as a triage
+    pub triaged_at: Option<DateTime<Utc>>,
+    /// A triage never declared: open, or closed as not an incident. Left out
+    /// of metrics
+    pub is_triage: bool,
+    /// Set once the channel archive job has archived the incident channel
+    pub channel_archived_at: Option<DateTime<Utc>>,
+    /// Join link of the conference bridge created on declaration, if any
+    pub bridge_url: Option<String>,
+    /// Template placeholder values entered at declaration, by placeholder name
+    pub custom_fields: BTreeMap<String, String>,
+    /// Template picked in the declare modal, if any
+    pub template_id: Option<Uuid>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+impl Incident {
+    /// `PLAT-12`
+    pub fn reference(&self) -> String {
+        crate::utils::incident_number::format(&self.number_prefix, self.number)
+    }
+
+    /// The severity to show: its picked level, or its tier's
+    pub fn severity_level(&self) -> &'static SeverityLevel {
+        crate::utils::severity::resolve(self.severity_code.as_deref(), self.severity)
+    }
+}
+
+// ── Workstream ──
+#[derive(Debug, Clone, Serialize)]
+pub struct Workstream {
+    pub id: Uuid,
+    pub incident_id: IncidentId,
+    pub name: String,
+    pub lead_id: SlackUserId,
+    pub thread_ts: Option<String>,
+    pub last_update: Option<String>,
+    pub last_update_at: Option<DateTime<Utc>>,
+    pub created_by: SlackUserId,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// ── Action Item ──
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ActionItemStatus {
+    Open,
+    Done,
+}
+
+impl ActionItemStatus {
+    pub fn as_db_str(&self) -> &'static str {
+        match self {
+            ActionItemStatus::Open => "open",
+            ActionItemStatus::Done => "done",
+        }
+    }
+
+    pub fn from_db_str(s: &str) -> Result<Self, String> {
+        s.parse()
+    }
+}
+
+impl std::str::FromStr for ActionItemStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "open" => Ok(ActionItemStatus::Open),
+            "done" => Ok(ActionItemStatus::Done),
+            _ => Err(format!("Invalid action item status: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ActionItem {
+    pub id: Uuid,
+    pub incident_id: IncidentId,
+    /// Per-incident sequence number shown to users
+    pub number: i32,
+    pub description: String,
+    pub owner_id: SlackUserId,
+    pub status: ActionItemStatus,
+    pub jira_issue_key: Option<String>,
+    pub created_by: SlackUserId,
+    pub created_at: DateTime<Utc>,
+    pub closed_at: Option<DateTime<Utc>>,
+}
+
+/// A postmortem published to Confluence.
+#[derive(Debug, Clone, Serialize)]
+pub struct Postmortem {
+    pub incident_id: IncidentId,
+    pub confluence_page_id: String,
+    pub url: String,
+    pub published_by: SlackUserId,
+    pub published_at: DateTime<Utc>,
+}
+
+/// A resolved incident whose postmortem is required but not yet published.
+#[derive(Debug, Clone, Serialize)]
+pub struct PendingPostmortem {
+    pub incident: Incident,
+    pub due_at: DateTime<Utc>,
+}
+
+impl PendingPostmortem {
+    pub fn is_overdue(&self, now: DateTime<Utc>) -> bool {
+        self.due_at < now
+    }
+}
+
+// ── Incident Role ──
+#[derive(Debug, Clone, Serialize)]
+pub struct IncidentRole {
+    pub incident_id: IncidentId,
+    pub role: String,
+    pub user_id: SlackUserId,
+    pub claimed_at: DateTime<Utc>,
+}
+
+// ── Incident Participant ──
+/// Someone who joined the incident channel or posted to the timeline.
+#[derive(Debug, Clone, Serialize)]
+pub struct Participant {
+    pub incident_id: IncidentId,
+    pub user_id: SlackUserId,
+    /// When they joined the channel; `None` if they only posted
+    pub joined_at: Option<DateTime<Utc>>,
+    pub timeline_events: i32,
+    pub first_seen_at: DateTime<Utc>,
+    pub last_seen_at: DateTime<Utc>,
+}
+
 // ── Timeline Event ──
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TimelineEventType {
+    /// Opened with `/incident triage`, before any declaration
+    Triaged,
     Declared,
     StatusUpdate,
     SeverityChange,
     Resolved,
+    Note,
+    Reopened,
+    Canceled,
 }
 
 impl TimelineEventType {
     pub fn as_db_str(&self) -> &'static str {
         match self {
+            TimelineEventType::Triaged => "triaged",
             TimelineEventType::Declared => "declared",
             TimelineEventType::StatusUpdate => "status_update",
             TimelineEventType::SeverityChange => "severity_change",
             TimelineEventType::Resolved => "resolved",
+            TimelineEventType::Note => "note",
+            TimelineEventType::Reopened => "reopened",
+            TimelineEventType::Canceled => "canceled",
         }
     }
 
@@ -173,15 +405,53 @@ impl std::str::FromStr for TimelineEventType {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s.to_ascii_lowercase().as_str() {
+            "triaged" => Ok(TimelineEventType::Triaged),
             "declared" => Ok(TimelineEventType::Declared),
             "status_update" => Ok(TimelineEventType::StatusUpdate),
             "severity_change" => Ok(TimelineEventType::SeverityChange),
             "resolved" => Ok(TimelineEventType::Resolved),
+            "note" => Ok(TimelineEventType::Note),
+            "reopened" => Ok(TimelineEventType::Reopened),
+            "canceled" => Ok(TimelineEventType::Canceled),
             _ => Err(format!("Invalid timeline event type: {}", s)),
         }
     }
 }
 
+/// Who a timeline entry is written for. Only `Public` status updates leave
+/// Slack (to the public status page).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Audience {
+    #[default]
+    Internal,
+    Public,
+}
+
+impl Audience {
+    pub fn as_db_str(&self) -> &'static str {
+        match self {
+            Audience::Internal => "internal",
+            Audience::Public => "public",
+        }
+    }
+
+    pub fn from_db_str(s: &str) -> Result<Self, String> {
+        s.parse()
+    }
+}
+
+impl std::str::FromStr for Audience {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "internal" => Ok(Audience::Internal),
+            "public" => Ok(Audience::Public),
+            _ => Err(format!("Invalid audience: {}", s)),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct TimelineEvent {
     pub id: Uuid,
@@ -190,6 +460,39 @@ pub struct TimelineEvent {
     pub message: String,
     pub posted_by: SlackUserId,
     pub timestamp: DateTime<Utc>,
+    pub audience: Audience,
+    /// When the message was last edited
+    pub updated_at: Option<DateTime<Utc>>,
+    /// Deleted events keep their place in the timeline with the text hidden
+    pub deleted_at: Option<DateTime<Utc>>,
+    /// Scribe who drafted a status update the commander approved and posted
+    pub drafted_by: Option<SlackUserId>,
+}
+
+impl TimelineEvent {
+    /// Status updates and notes are written by people and may be edited or
+    /// deleted; lifecycle events record what the bot did.
+    pub fn is_editable(&self) -> bool {
+        matches!(
+            self.event_type,
+            TimelineEventType::StatusUpdate | TimelineEventType::Note
+        )
+    }
+
+    pub fn is_deleted(&self) -> bool {
+        self.deleted_at.is_some()
+    }
+}
+
+/// Timeline event submitted by an integration, not yet persisted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewTimelineEvent {
+    pub event_type: TimelineEventType,
+    pub message: String,
+    pub posted_by: SlackUserId,
+    /// When the event happened at the source; defaults to insert time.
+    #[serde(default)]
+    pub timestamp: Option<DateTime<Utc>>,
 }
 
 // ── Notification Record ──
@@ -228,8 +531,11 @@ impl std::str::FromStr for NotificationType {
 pub enum NotificationStatus {
     Sent,
     Failed,
+    /// Deferred; not sent yet
     Pending,
     Throttled,
+    /// Given up on by an operator
+    Skipped,
 }
 
 impl NotificationStatus {
@@ -239,6 +545,7 @@ impl NotificationStatus {
             NotificationStatus::Failed => "failed",
             NotificationStatus::Pending => "pending",
             NotificationStatus::Throttled => "throttled",
+            NotificationStatus::Skipped => "skipped",
         }
     }
 
@@ -256,12 +563,13 @@ impl std::str::FromStr for NotificationStatus {
             "failed" => Ok(NotificationStatus::Failed),
             "pending" => Ok(NotificationStatus::Pending),
             "throttled" => Ok(NotificationStatus::Throttled),
+            "skipped" => Ok(NotificationStatus::Skipped),
             _ => Err(format!("Invalid notification status: {}", s)),
         }
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct NotificationRecord {
     pub id: Uuid,
     pub incident_id: IncidentId,
@@ -270,6 +578,26 @@ pub struct NotificationRecord {
     pub sent_at: DateTime<Utc>,
     pub status: NotificationStatus,
     pub error_message: Option<String>,
+    /// The message, kept for notifications that weren't sent
+    #[serde(skip)]
+    pub blocks: Option<serde_json::Value>,
+    pub attempts: i32,
+    /// Operator who retried or skipped it
+    pub resolved_by: Option<String>,
+    /// A fan-out thread reply also shown in the channel (`reply_broadcast`)
+    pub broadcast: bool,
+}
+
+impl NotificationRecord {
+    /// Failed, throttled or deferred, and can still be retried or skipped.
+    pub fn is_unsent(&self) -> bool {
+        matches!(
+            self.status,
+            NotificationStatus::Failed
+                | NotificationStatus::Pending
+                | NotificationStatus::Throttled
+        )
+    }
 }
 
 // ── Incident Template ──
@@ -282,10 +610,383 @@ pub struct IncidentTemplate {
     pub affected_service: Option<String>,
     pub description: Option<String>,
     pub is_active: bool,
+    /// Resolved incident the template was created from, via the prompt the
+    /// commander gets on resolution
+    pub source_incident_id: Option<IncidentId>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+// ── Webhook ──
+/// Lifecycle events delivered to outbound webhooks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WebhookEvent {
+    #[serde(rename = "incident.declared")]
+    IncidentDeclared,
+    #[serde(rename = "status.changed")]
+    StatusChanged,
+    #[serde(rename = "severity.changed")]
+    SeverityChanged,
+    #[serde(rename = "resolved")]
+    Resolved,
+}
+
+impl WebhookEvent {
+    pub const ALL: [WebhookEvent; 4] = [
+        WebhookEvent::IncidentDeclared,
+        WebhookEvent::StatusChanged,
+        WebhookEvent::SeverityChanged,
+        WebhookEvent::Resolved,
+    ];
+
+    pub fn as_db_str(&self) -> &'static str {
+        match self {
+            WebhookEvent::IncidentDeclared => "incident.declared",
+            WebhookEvent::StatusChanged => "status.changed",
+            WebhookEvent::SeverityChanged => "severity.changed",
+            WebhookEvent::Resolved => "resolved",
+        }
+    }
+}
+
+impl std::str::FromStr for WebhookEvent {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        WebhookEvent::ALL
+            .into_iter()
+            .find(|event| event.as_db_str() == s)
+            .ok_or_else(|| format!("Invalid webhook event: {}", s))
+    }
+}
+
+/// An endpoint that receives signed lifecycle event payloads. An empty
+/// `events` list subscribes to every event.
+#[derive(Debug, Clone, Serialize)]
+pub struct Webhook {
+    pub id: Uuid,
+    pub url: String,
+    #[serde(skip_serializing)]
+    pub secret: String,
+    pub events: Vec<String>,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+// ── Artifact ──
+/// A large generated file kept in the artifact store rather than Postgres.
+#[derive(Debug, Clone, Serialize)]
+pub struct Artifact {
+    pub id: Uuid,
+    /// `None` for artifacts not tied to one incident (DR snapshots)
+    pub incident_id: Option<IncidentId>,
+    /// `channel_transcript`, `replication_snapshot`, ...
+    pub kind: String,
+    #[serde(skip_serializing)]
+    pub storage_key: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+// ── Failed Job ──
+/// A background job deferred because its external service was unavailable,
+/// or dead-lettered after failing for good.
+#[derive(Debug, Clone, Serialize)]
+pub struct FailedJob {
+    pub id: Uuid,
+    pub incident_id: Option<IncidentId>,
+    /// The serialized `jobs::Job`
+    pub job: serde_json::Value,
+    pub attempts: i32,
+    pub last_error: String,
+    pub created_at: DateTime<Utc>,
+    pub last_attempted_at: DateTime<Utc>,
+    /// Set once the job is given up on; waits for an admin to retry or discard
+    pub dead_lettered_at: Option<DateTime<Utc>>,
+}
+
+impl FailedJob {
+    /// The job's variant name (`DeliverWebhook`, `StatuspageSync`, ...).
+    pub fn kind(&self) -> &str {
+        match &self.job {
+            serde_json::Value::Object(fields) => fields
+                .keys()
+                .next()
+                .map(String::as_str)
+                .unwrap_or("Unknown"),
+            serde_json::Value::String(kind) => kind,
+            _ => "Unknown",
+        }
+    }
+}
+
+// ── Tracked Alert ──
+/// An auto-declaring source's alert that declared or joined an incident.
+#[derive(Debug, Clone, Serialize)]
+pub struct TrackedAlert {
+    /// Name of the alert source, e.g. `alertmanager`
+    pub source: String,
+    pub fingerprint: String,
+    pub incident_id: IncidentId,
+    /// Whether this alert declared the incident
+    pub declared: bool,
+    pub firing: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// ── SLA Breach ──
+/// Which SLA target an incident missed (see `config::SlaTarget`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SlaMetric {
+    /// First status update
+    Ack,
+    /// Status update cadence
+    Update,
+    Resolve,
+}
+
+impl SlaMetric {
+    pub fn as_db_str(&self) -> &'static str {
+        match self {
+            SlaMetric::Ack => "ack",
+            SlaMetric::Update => "update",
+            SlaMetric::Resolve => "resolve",
+        }
+    }
+}
+
+impl std::str::FromStr for SlaMetric {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ack" => Ok(SlaMetric::Ack),
+            "update" => Ok(SlaMetric::Update),
+            "resolve" => Ok(SlaMetric::Resolve),
+            _ => Err(format!("Invalid SLA metric: {}", s)),
+        }
+    }
+}
+
+/// A missed SLA deadline, recorded once.
+#[derive(Debug, Clone, Serialize)]
+pub struct SlaBreach {
+    pub id: Uuid,
+    pub incident_id: IncidentId,
+    /// Severity when the deadline was missed
+    pub severity: Severity,
+    pub metric: SlaMetric,
+    pub target_minutes: i32,
+    pub deadline: DateTime<Utc>,
+    pub recorded_at: DateTime<Utc>,
+}
+
+// ── Paging Test ──
+/// A monthly test page sent down the P1 escalation chain.
+#[derive(Debug, Clone, Serialize)]
+pub struct PagingTest {
+    pub id: Uuid,
+    /// First day of the month the test belongs to
+    pub month: NaiveDate,
+    pub started_at: DateTime<Utc>,
+    pub reported_at: Option<DateTime<Utc>>,
+}
+
+/// One recipient's test page and whether they acknowledged it.
+#[derive(Debug, Clone, Serialize)]
+pub struct PagingTestPage {
+    pub test_id: Uuid,
+    /// Slack user ID, or the user group ID when the group couldn't be expanded
+    pub recipient_id: String,
+    /// Why they're on the chain (`P1_USERS`, a user group, `BACKUP_COMMANDERS`)
+    pub source: String,
+    pub sent_at: DateTime<Utc>,
+    /// Slack error when the DM couldn't be delivered (`user_not_found`, ...)
+    pub delivery_error: Option<String>,
+    pub acknowledged_at: Option<DateTime<Utc>>,
+}
+
+impl PagingTestPage {
+    /// Time from the page being sent to its acknowledgement.
+    pub fn ack_latency(&self) -> Option<chrono::Duration> {
+        self.acknowledged_at.map(|at| at - self.sent_at)
+    }
+}
+
+// ── MIM Page ──
+/// A major incident manager paged for a P1.
+#[derive(Debug, Clone, Serialize)]
+pub struct MimPage {
+    pub incident_id: IncidentId,
+    pub user_id: SlackUserId,
+    /// `MIM_USERS` or the on-call schedule they were pulled from
+    pub source: String,
+    pub paged_at: DateTime<Utc>,
+    pub accepted_at: Option<DateTime<Utc>>,
+    /// `advisor` or `commander`
+    pub accepted_as: Option<String>,
+}
+
+// ── Pre-mortem ──
+/// Failure modes anticipated before a risky launch of `service`. Incidents
+/// declared for the service between `window_start` and `window_end` are
+/// linked to it.
+#[derive(Debug, Clone, Serialize)]
+pub struct Premortem {
+    pub id: Uuid,
+    pub service: String,
+    pub launch_name: String,
+    pub window_start: DateTime<Utc>,
+    pub window_end: DateTime<Utc>,
+    pub created_by: SlackUserId,
+    pub created_at: DateTime<Utc>,
+}
+
+/// One anticipated failure mode of a pre-mortem.
+#[derive(Debug, Clone, Serialize)]
+pub struct PremortemRisk {
+    pub id: Uuid,
+    pub premortem_id: Uuid,
+    pub failure_mode: String,
+    /// `low`, `medium` or `high`
+    pub likelihood: String,
+    pub mitigation: Option<String>,
+    pub added_by: SlackUserId,
+    pub created_at: DateTime<Utc>,
+}
+
+// ── Incident Link ──
+/// What an incident link points at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LinkKind {
+    PullRequest,
+    Commit,
+    /// A deploy that landed while the incident was open
+    Deploy,
+}
+
+impl LinkKind {
+    pub fn as_db_str(&self) -> &'static str {
+        match self {
+            LinkKind::PullRequest => "pull_request",
+            LinkKind::Commit => "commit",
+            LinkKind::Deploy => "deploy",
+        }
+    }
+
+    pub fn from_db_str(s: &str) -> Result<Self, String> {
+        match s {
+            "pull_request" => Ok(LinkKind::PullRequest),
+            "commit" => Ok(LinkKind::Commit),
+            "deploy" => Ok(LinkKind::Deploy),
+            _ => Err(format!("Invalid link kind: {}", s)),
+        }
+    }
+}
+
+/// A pull request, commit or deploy linked to an incident.
+#[derive(Debug, Clone, Serialize)]
+pub struct IncidentLink {
+    pub id: Uuid,
+    pub incident_id: IncidentId,
+    /// `github`
+    pub provider: String,
+    pub kind: LinkKind,
+    /// `owner/name`
+    pub repo: String,
+    /// PR number or commit SHA
+    pub reference: String,
+    pub url: String,
+    /// Deploy target (`production`, ...); empty for PRs and commits
+    pub environment: String,
+    /// Slack user ID, or `github` for deploys reported by webhook
+    pub linked_by: String,
+    pub created_at: DateTime<Utc>,
+}
+
+// ── Subscription ──
+/// A stakeholder's request for DM digests of one incident, or of every
+/// incident on a service. Exactly one of `incident_id` and `service` is set.
+#[derive(Debug, Clone, Serialize)]
+pub struct Subscription {
+    pub id: Uuid,
+    pub user_id: SlackUserId,
+    pub incident_id: Option<IncidentId>,
+    pub service: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+// ── Status Draft ──
+/// A status update drafted by the scribe and held for the commander's
+/// approval. `approved` is set once decided: posted or discarded.
+#[derive(Debug, Clone, Serialize)]
+pub struct StatusDraft {
+    pub id: Uuid,
+    pub incident_id: IncidentId,
+    pub drafted_by: SlackUserId,
+    pub message: String,
+    pub audience: Audience,
+    pub created_at: DateTime<Utc>,
+    pub decided_by: Option<SlackUserId>,
+    pub decided_at: Option<DateTime<Utc>>,
+    pub approved: Option<bool>,
+}
+
+// ── Declare Draft ──
+/// Values entered in the declare modal before it was submitted.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DeclareDraft {
+    pub template: Option<String>,
+    pub title: Option<String>,
+    /// `SeverityLevel` code
+    pub severity: Option<String>,
+    pub service: Option<String>,
+    pub commander_id: Option<SlackUserId>,
+    /// Template placeholder values, by placeholder name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub fields: BTreeMap<String, String>,
+}
+
+impl DeclareDraft {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+// ── Change Record ──
+/// One row of the `incident_changes` log (see `db::replication`).
+#[derive(Debug, Clone, Serialize)]
+pub struct ChangeRecord {
+    pub seq: i64,
+    pub incident_id: IncidentId,
+    pub table_name: String,
+    pub operation: String,
+    pub row_data: serde_json::Value,
+    pub changed_at: DateTime<Utc>,
+}
+
+// ── Audit Entry ──
+/// One `audit_log` row. `prev_hash`/`row_hash` chain the rows in `seq`
+/// order (see `services::audit_chain`); rows from before the chain have none.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AuditEntry {
+    pub id: Uuid,
+    pub seq: i64,
+    pub incident_id: Option<IncidentId>,
+    pub action: String,
+    pub actor_id: SlackUserId,
+    pub old_state: Option<serde_json::Value>,
+    pub new_state: Option<serde_json::Value>,
+    pub details: Option<serde_json::Value>,
+    pub timestamp: DateTime<Utc>,
+    pub prev_hash: Option<String>,
+    pub row_hash: Option<String>,
+}
+
 fn decode_parse_error(field: &str, value: &str, err: String) -> sqlx::Error {
     sqlx::Error::Decode(Box::new(IoError::new(
         ErrorKind::InvalidData,
@@ -302,29 +1003,137 @@ impl<'r> FromRow<'r, PgRow> for Incident {
             .map_err(|e| decode_parse_error("severity", &severity_raw, e))?;
         let status = IncidentStatus::from_db_str(&status_raw)
             .map_err(|e| decode_parse_error("status", &status_raw, e))?;
+        let custom_fields_raw: serde_json::Value = row.try_get("custom_fields")?;
+        let custom_fields = serde_json::from_value(custom_fields_raw.clone()).map_err(|e| {
+            decode_parse_error(
+                "custom_fields",
+                &custom_fields_raw.to_string(),
+                e.to_string(),
+            )
+        })?;
 
         Ok(Self {
             id: row.try_get("id")?,
+            number_prefix: row.try_get("number_prefix")?,
+            number: row.try_get("number")?,
             slack_channel_id: row.try_get("slack_channel_id")?,
             title: row.try_get("title")?,
             severity,
+            severity_code: row.try_get("severity_code")?,
             status,
             affected_service: row.try_get("affected_service")?,
             commander_id: row.try_get("commander_id")?,
             declared_at: row.try_get("declared_at")?,
             resolved_at: row.try_get("resolved_at")?,
             duration_minutes: row.try_get("duration_minutes")?,
+            pinned_message_ts: row.try_get("pinned_message_ts")?,
+            is_quiet: row.try_get("is_quiet")?,
+            statuspage_incident_id: row.try_get("statuspage_incident_id")?,
+            canceled_at: row.try_get("canceled_at")?,
+            cancel_reason: row.try_get("cancel_reason")?,
+            triaged_at: row.try_get("triaged_at")?,
+            is_triage: row.try_get("is_triage")?,
+            channel_archived_at: row.try_get("channel_archived_at")?,
+            bridge_url: row.try_get("bridge_url")?,
+            custom_fields,
+            template_id: row.try_get("template_id")?,
             created_at: row.try_get("created_at")?,
             updated_at: row.try_get("updated_at")?,
         })
     }
 }
 
+impl<'r> FromRow<'r, PgRow> for Workstream {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            id: row.try_get("id")?,
+            incident_id: row.try_get("incident_id")?,
+            name: row.try_get("name")?,
+            lead_id: row.try_get("lead_id")?,
+            thread_ts: row.try_get("thread_ts")?,
+            last_update: row.try_get("last_update")?,
+            last_update_at: row.try_get("last_update_at")?,
+            created_by: row.try_get("created_by")?,
+            created_at: row.try_get("created_at")?,
+            updated_at: row.try_get("updated_at")?,
+        })
+    }
+}
+
+impl<'r> FromRow<'r, PgRow> for ActionItem {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let status_raw: String = row.try_get("status")?;
+        let status = ActionItemStatus::from_db_str(&status_raw)
+            .map_err(|e| decode_parse_error("status", &status_raw, e))?;
+
+        Ok(Self {
+            id: row.try_get("id")?,
+            incident_id: row.try_get("incident_id")?,
+            number: row.try_get("number")?,
+            description: row.try_get("description")?,
+            owner_id: row.try_get("owner_id")?,
+            status,
+            jira_issue_key: row.try_get("jira_issue_key")?,
+            created_by: row.try_get("created_by")?,
+            created_at: row.try_get("created_at")?,
+            closed_at: row.try_get("closed_at")?,
+        })
+    }
+}
+
+impl<'r> FromRow<'r, PgRow> for PendingPostmortem {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            incident: Incident::from_row(row)?,
+            due_at: row.try_get("due_at")?,
+        })
+    }
+}
+
+impl<'r> FromRow<'r, PgRow> for Postmortem {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            incident_id: row.try_get("incident_id")?,
+            confluence_page_id: row.try_get("confluence_page_id")?,
+            url: row.try_get("url")?,
+            published_by: row.try_get("published_by")?,
+            published_at: row.try_get("published_at")?,
+        })
+    }
+}
+
+impl<'r> FromRow<'r, PgRow> for IncidentRole {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            incident_id: row.try_get("incident_id")?,
+            role: row.try_get("role")?,
+            user_id: row.try_get("user_id")?,
+            claimed_at: row.try_get("claimed_at")?,
+        })
+    }
+}
+
+impl<'r> FromRow<'r, PgRow> for Participant {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            incident_id: row.try_get("incident_id")?,
+            user_id: row.try_get("user_id")?,
+            joined_at: row.try_get("joined_at")?,
+            timeline_events: row.try_get("timeline_events")?,
+            first_seen_at: row.try_get("first_seen_at")?,
+            last_seen_at: row.try_get("last_seen_at")?,
+        })
+    }
+}
+
 impl<'r> FromRow<'r, PgRow> for TimelineEvent {
     fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
         let event_type_raw: String = row.try_get("event_type")?;
         let event_type = TimelineEventType::from_db_str(&event_type_raw)
             .map_err(|e| decode_parse_error("event_type", &event_type_raw, e))?;
+        let audience_raw: String = row.try_get("audience")?;
+        let audience = Audience::from_db_str(&audience_raw)
+            .map_err(|e| decode_parse_error("audience", &audience_raw, e))?;
 
         Ok(Self {
             id: row.try_get("id")?,
@@ -333,6 +1142,10 @@ impl<'r> FromRow<'r, PgRow> for TimelineEvent {
             message: row.try_get("message")?,
             posted_by: row.try_get("posted_by")?,
             timestamp: row.try_get("timestamp")?,
+            audience,
+            updated_at: row.try_get("updated_at")?,
+            deleted_at: row.try_get("deleted_at")?,
+            drafted_by: row.try_get("drafted_by")?,
         })
     }
 }
@@ -354,10 +1167,252 @@ impl<'r> FromRow<'r, PgRow> for NotificationRecord {
             sent_at: row.try_get("sent_at")?,
             status,
             error_message: row.try_get("error_message")?,
+            blocks: row.try_get("blocks")?,
+            attempts: row.try_get("attempts")?,
+            resolved_by: row.try_get("resolved_by")?,
+            broadcast: row.try_get("broadcast")?,
         })
     }
 }
 
+impl<'r> FromRow<'r, PgRow> for Webhook {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            id: row.try_get("id")?,
+            url: row.try_get("url")?,
+            secret: row.try_get("secret")?,
+            events: row.try_get("events")?,
+            is_active: row.try_get("is_active")?,
+            created_at: row.try_get("created_at")?,
+        })
+    }
+}
+
+impl<'r> FromRow<'r, PgRow> for Artifact {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            id: row.try_get("id")?,
+            incident_id: row.try_get("incident_id")?,
+            kind: row.try_get("kind")?,
+            storage_key: row.try_get("storage_key")?,
+            content_type: row.try_get("content_type")?,
+            size_bytes: row.try_get("size_bytes")?,
+            created_at: row.try_get("created_at")?,
+        })
+    }
+}
+
+impl<'r> FromRow<'r, PgRow> for TrackedAlert {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            source: row.try_get("source")?,
+            fingerprint: row.try_get("fingerprint")?,
+            incident_id: row.try_get("incident_id")?,
+            declared: row.try_get("declared")?,
+            firing: row.try_get("firing")?,
+            created_at: row.try_get("created_at")?,
+            updated_at: row.try_get("updated_at")?,
+        })
+    }
+}
+
+impl<'r> FromRow<'r, PgRow> for SlaBreach {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let severity_raw: String = row.try_get("severity")?;
+        let severity = Severity::from_db_str(&severity_raw)
+            .map_err(|e| decode_parse_error("severity", &severity_raw, e))?;
+        let metric_raw: String = row.try_get("metric")?;
+        let metric = metric_raw
+            .parse::<SlaMetric>()
+            .map_err(|e| decode_parse_error("metric", &metric_raw, e))?;
+
+        Ok(Self {
+            id: row.try_get("id")?,
+            incident_id: row.try_get("incident_id")?,
+            severity,
+            metric,
+            target_minutes: row.try_get("target_minutes")?,
+            deadline: row.try_get("deadline")?,
+            recorded_at: row.try_get("recorded_at")?,
+        })
+    }
+}
+
+impl<'r> FromRow<'r, PgRow> for FailedJob {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            id: row.try_get("id")?,
+            incident_id: row.try_get("incident_id")?,
+            job: row.try_get("job")?,
+            attempts: row.try_get("attempts")?,
+            last_error: row.try_get("last_error")?,
+            created_at: row.try_get("created_at")?,
+            last_attempted_at: row.try_get("last_attempted_at")?,
+            dead_lettered_at: row.try_get("dead_lettered_at")?,
+        })
+    }
+}
+
+impl<'r> FromRow<'r, PgRow> for PagingTest {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            id: row.try_get("id")?,
+            month: row.try_get("month")?,
+            started_at: row.try_get("started_at")?,
+            reported_at: row.try_get("reported_at")?,
+        })
+    }
+}
+
+impl<'r> FromRow<'r, PgRow> for PagingTestPage {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            test_id: row.try_get("test_id")?,
+            recipient_id: row.try_get("recipient_id")?,
+            source: row.try_get("source")?,
+            sent_at: row.try_get("sent_at")?,
+            delivery_error: row.try_get("delivery_error")?,
+            acknowledged_at: row.try_get("acknowledged_at")?,
+        })
+    }
+}
+
+impl<'r> FromRow<'r, PgRow> for MimPage {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            incident_id: row.try_get("incident_id")?,
+            user_id: row.try_get("user_id")?,
+            source: row.try_get("source")?,
+            paged_at: row.try_get("paged_at")?,
+            accepted_at: row.try_get("accepted_at")?,
+            accepted_as: row.try_get("accepted_as")?,
+        })
+    }
+}
+
+impl<'r> FromRow<'r, PgRow> for IncidentLink {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let kind_raw: String = row.try_get("kind")?;
+        let kind = LinkKind::from_db_str(&kind_raw)
+            .map_err(|e| decode_parse_error("kind", &kind_raw, e))?;
+
+        Ok(Self {
+            id: row.try_get("id")?,
+            incident_id: row.try_get("incident_id")?,
+            provider: row.try_get("provider")?,
+            kind,
+            repo: row.try_get("repo")?,
+            reference: row.try_get("reference")?,
+            url: row.try_get("url")?,
+            environment: row.try_get("environment")?,
+            linked_by: row.try_get("linked_by")?,
+            created_at: row.try_get("created_at")?,
+        })
+    }
+}
+
+impl<'r> FromRow<'r, PgRow> for Subscription {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            id: row.try_get("id")?,
+            user_id: row.try_get("user_id")?,
+            incident_id: row.try_get("incident_id")?,
+            service: row.try_get("service")?,
+            created_at: row.try_get("created_at")?,
+        })
+    }
+}
+
+impl<'r> FromRow<'r, PgRow> for StatusDraft {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let audience_raw: String = row.try_get("audience")?;
+        let audience = Audience::from_db_str(&audience_raw)
+            .map_err(|e| decode_parse_error("audience", &audience_raw, e))?;
+
+        Ok(Self {
+            id: row.try_get("id")?,
+            incident_id: row.try_get("incident_id")?,
+            drafted_by: row.try_get("drafted_by")?,
+            message: row.try_get("message")?,
+            audience,
+            created_at: row.try_get("created_at")?,
+            decided_by: row.try_get("decided_by")?,
+            decided_at: row.try_get("decided_at")?,
+            approved: row.try_get("approved")?,
+        })
+    }
+}
+
+impl<'r> FromRow<'r, PgRow> for Premortem {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            id: row.try_get("id")?,
+            service: row.try_get("service")?,
+            launch_name: row.try_get("launch_name")?,
+            window_start: row.try_get("window_start")?,
+            window_end: row.try_get("window_end")?,
+            created_by: row.try_get("created_by")?,
+            created_at: row.try_get("created_at")?,
+        })
+    }
+}
+
+impl<'r> FromRow<'r, PgRow> for PremortemRisk {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            id: row.try_get("id")?,
+            premortem_id: row.try_get("premortem_id")?,
+            failure_mode: row.try_get("failure_mode")?,
+            likelihood: row.try_get("likelihood")?,
+            mitigation: row.try_get("mitigation")?,
+            added_by: row.try_get("added_by")?,
+            created_at: row.try_get("created_at")?,
+        })
+    }
+}
+
+impl<'r> FromRow<'r, PgRow> for ChangeRecord {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            seq: row.try_get("seq")?,
+            incident_id: row.try_get("incident_id")?,
+            table_name: row.try_get("table_name")?,
+            operation: row.try_get("operation")?,
+            row_data: row.try_get("row_data")?,
+            changed_at: row.try_get("changed_at")?,
+        })
+    }
+}
+
+impl<'r> FromRow<'r, PgRow> for AuditEntry {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            id: row.try_get("id")?,
+            seq: row.try_get("seq")?,
+            incident_id: row.try_get("incident_id")?,
+            action: row.try_get("action")?,
+            actor_id: row.try_get("actor_id")?,
+            old_state: row.try_get("old_state")?,
+            new_state: row.try_get("new_state")?,
+            details: row.try_get("details")?,
+            timestamp: row.try_get("timestamp")?,
+            prev_hash: row.try_get("prev_hash")?,
+            row_hash: row.try_get("row_hash")?,
+        })
+    }
+}
+
+impl IncidentTemplate {
+    /// `{{name}}` placeholders in the title and description, which become
+    /// extra declare modal inputs.
+    pub fn placeholders(&self) -> Vec<String> {
+        crate::utils::placeholders::placeholders(&[
+            &self.title,
+            self.description.as_deref().unwrap_or(""),
+        ])
+    }
+}
+
 impl<'r> FromRow<'r, PgRow> for IncidentTemplate {
     fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
         let severity_raw: String = row.try_get("severity")?;
@@ -372,6 +1427,7 @@ impl<'r> FromRow<'r, PgRow> for IncidentTemplate {
             affected_service: row.try_get("affected_service")?,
             description: row.try_get("description")?,
             is_active: row.try_get("is_active")?,
+            source_incident_id: row.try_get("source_incident_id")?,
             created_at: row.try_get("created_at")?,
             updated_at: row.try_get("updated_at")?,
         })
@@ -400,10 +1456,24 @@ mod tests {
         assert!(!Declared.is_terminal());
     }
 
+    #[test]
+    fn test_webhook_event_names_round_trip() {
+        for event in WebhookEvent::ALL {
+            assert_eq!(event.as_db_str().parse::<WebhookEvent>().unwrap(), event);
+            assert_eq!(
+                serde_json::to_value(event).unwrap(),
+                serde_json::json!(event.as_db_str())
+            );
+        }
+        assert!("incident.deleted".parse::<WebhookEvent>().is_err());
+    }
+
     #[test]
     fn test_severity_display() {
         assert_eq!(Severity::P1.label(), "P1 (Critical)");
         assert_eq!(Severity::P1.emoji(), "🔴");
         assert_eq!(Severity::P3.emoji(), "🟢");
+        assert_eq!(IncidentStatus::Investigating.emoji(), "🔴");
+        assert_eq!(IncidentStatus::Resolved.emoji(), "🟢");
     }
 }
@@ -0,0 +1,105 @@
+use crate::db::models::{ActionItem, IncidentId};
+use crate::error::IncidentResult;
+use chrono::{DateTime, Utc};
+use sqlx_postgres::PgPool;
+
+/// Insert an action item with the incident's next sequence number.
+pub async fn create_action_item(
+    pool: &PgPool,
+    incident_id: IncidentId,
+    description: &str,
+    owner_id: &str,
+    created_by: &str,
+) -> IncidentResult<ActionItem> {
+    let item = sqlx::query_as::query_as::<_, ActionItem>(
+        r#"
+        INSERT INTO action_items (incident_id, number, description, owner_id, created_by)
+        SELECT $1, COALESCE(MAX(number), 0) + 1, $2, $3, $4
+        FROM action_items WHERE incident_id = $1
+        RETURNING *
+        "#,
+    )
+    .bind(incident_id)
+    .bind(description)
+    .bind(owner_id)
+    .bind(created_by)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(item)
+}
+
+pub async fn get_action_item(pool: &PgPool, id: uuid::Uuid) -> IncidentResult<Option<ActionItem>> {
+    let item = sqlx::query_as::query_as::<_, ActionItem>(
+        r#"
+        SELECT * FROM action_items
+        WHERE id = $1
+        "#,
+    )
+    .bind(id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(item)
+}
+
+pub async fn list_action_items(
+    pool: &PgPool,
+    incident_id: IncidentId,
+) -> IncidentResult<Vec<ActionItem>> {
+    let items = sqlx::query_as::query_as::<_, ActionItem>(
+        r#"
+        SELECT * FROM action_items
+        WHERE incident_id = $1
+        ORDER BY number ASC
+        "#,
+    )
+    .bind(incident_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(items)
+}
+
+/// Mark an open item done. Returns `None` if there is no such open item.
+pub async fn close_action_item(
+    pool: &PgPool,
+    incident_id: IncidentId,
+    number: i32,
+    closed_at: DateTime<Utc>,
+) -> IncidentResult<Option<ActionItem>> {
+    let item = sqlx::query_as::query_as::<_, ActionItem>(
+        r#"
+        UPDATE action_items
+        SET status = 'done', closed_at = $3
+        WHERE incident_id = $1 AND number = $2 AND status = 'open'
+        RETURNING *
+        "#,
+    )
+    .bind(incident_id)
+    .bind(number)
+    .bind(closed_at)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(item)
+}
+
+pub async fn set_jira_issue_key(
+    pool: &PgPool,
+    id: uuid::Uuid,
+    issue_key: &str,
+) -> IncidentResult<()> {
+    sqlx::query::query(
+        r#"
+        UPDATE action_items SET jira_issue_key = $2
+        WHERE id = $1
+        "#,
+    )
+    .bind(id)
+    .bind(issue_key)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,234 @@
+use crate::error::IncidentResult;
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx_postgres::PgPool;
+
+/// Raw incident counts for a set of services over `[start, end)`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ServiceStats {
+    /// Incidents declared in the period
+    pub declared: i64,
+    /// Incidents resolved in the period
+    pub resolved: i64,
+    /// Mean `duration_minutes` of the resolved incidents
+    pub avg_resolution_minutes: Option<f64>,
+    /// Resolved incidents that have had a postmortem generated
+    pub resolved_with_postmortem: i64,
+    /// Action items created in the period on these services' incidents
+    pub action_items: i64,
+    /// Of those, how many are done
+    pub action_items_closed: i64,
+}
+
+pub async fn service_stats(
+    pool: &PgPool,
+    services: &[String],
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> IncidentResult<ServiceStats> {
+    let (declared, resolved, avg_resolution_minutes, resolved_with_postmortem) =
+        sqlx::query_as::query_as::<_, (i64, i64, Option<f64>, i64)>(
+            r#"
+            SELECT
+                COUNT(*) FILTER (WHERE declared_at >= $2 AND declared_at < $3),
+                COUNT(*) FILTER (WHERE resolved_at >= $2 AND resolved_at < $3),
+                (AVG(duration_minutes) FILTER (WHERE resolved_at >= $2 AND resolved_at < $3))::FLOAT8,
+                COUNT(*) FILTER (
+                    WHERE resolved_at >= $2 AND resolved_at < $3
+                    AND EXISTS (
+                        SELECT 1 FROM audit_log a
+                        WHERE a.incident_id = i.id AND a.action = 'generate_postmortem'
+                    )
+                )
+            FROM incidents i
+            WHERE affected_service = ANY($1) AND NOT is_triage
+            "#,
+        )
+        .bind(services)
+        .bind(start)
+        .bind(end)
+        .fetch_one(pool)
+        .await?;
+
+    let (action_items, action_items_closed) = sqlx::query_as::query_as::<_, (i64, i64)>(
+        r#"
+            SELECT COUNT(*), COUNT(*) FILTER (WHERE a.status = 'done')
+            FROM action_items a
+            JOIN incidents i ON i.id = a.incident_id
+            WHERE i.affected_service = ANY($1)
+              AND a.created_at >= $2 AND a.created_at < $3
+            "#,
+    )
+    .bind(services)
+    .bind(start)
+    .bind(end)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(ServiceStats {
+        declared,
+        resolved,
+        avg_resolution_minutes,
+        resolved_with_postmortem,
+        action_items,
+        action_items_closed,
+    })
+}
+
+/// Record that `team`'s scorecard for the month starting `period_start` was
+/// sent. Returns `false` if it already had been.
+pub async fn claim_scorecard_run(
+    pool: &PgPool,
+    period_start: NaiveDate,
+    team: &str,
+) -> IncidentResult<bool> {
+    let claimed = sqlx::query_scalar::query_scalar::<_, String>(
+        r#"
+        INSERT INTO scorecard_runs (period_start, team)
+        VALUES ($1, $2)
+        ON CONFLICT (period_start, team) DO NOTHING
+        RETURNING team
+        "#,
+    )
+    .bind(period_start)
+    .bind(team)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(claimed.is_some())
+}
+
+/// Open incidents at `now` and at the same time on each of the previous
+/// `days - 1` days, oldest first.
+pub async fn open_incidents_by_day(
+    pool: &PgPool,
+    days: i32,
+    now: DateTime<Utc>,
+) -> IncidentResult<Vec<(DateTime<Utc>, i64)>> {
+    let rows = sqlx::query_as::query_as::<_, (DateTime<Utc>, i64)>(
+        r#"
+        SELECT s.at, COUNT(i.id)
+        FROM generate_series(
+            $2::timestamptz - make_interval(days => $1 - 1),
+            $2::timestamptz,
+            INTERVAL '1 day'
+        ) AS s(at)
+        LEFT JOIN incidents i
+            ON NOT i.is_triage
+           AND i.declared_at <= s.at
+           AND (COALESCE(i.resolved_at, i.canceled_at) IS NULL
+                OR COALESCE(i.resolved_at, i.canceled_at) > s.at)
+        GROUP BY s.at
+        ORDER BY s.at
+        "#,
+    )
+    .bind(days)
+    .bind(now)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows)
+}
+
+/// Start rendering the burndown for `rendered_on`. Returns `false` if that
+/// day's sparkline was already rendered (or is being rendered).
+pub async fn claim_burndown_render(pool: &PgPool, rendered_on: NaiveDate) -> IncidentResult<bool> {
+    let claimed = sqlx::query_scalar::query_scalar::<_, NaiveDate>(
+        r#"
+        INSERT INTO burndown_snapshots (rendered_on)
+        VALUES ($1)
+        ON CONFLICT (rendered_on) DO NOTHING
+        RETURNING rendered_on
+        "#,
+    )
+    .bind(rendered_on)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(claimed.is_some())
+}
+
+/// Give up a claimed render so the next pass retries it.
+pub async fn release_burndown_render(pool: &PgPool, rendered_on: NaiveDate) -> IncidentResult<()> {
+    sqlx::query::query(
+        r#"
+        DELETE FROM burndown_snapshots
+        WHERE rendered_on = $1 AND slack_file_id IS NULL
+        "#,
+    )
+    .bind(rendered_on)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn record_burndown_file(
+    pool: &PgPool,
+    rendered_on: NaiveDate,
+    slack_file_id: &str,
+) -> IncidentResult<()> {
+    sqlx::query::query(
+        r#"
+        UPDATE burndown_snapshots SET slack_file_id = $2
+        WHERE rendered_on = $1
+        "#,
+    )
+    .bind(rendered_on)
+    .bind(slack_file_id)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Slack file ID of the most recently uploaded burndown, if any.
+pub async fn latest_burndown_file(pool: &PgPool) -> IncidentResult<Option<String>> {
+    let file_id = sqlx::query_scalar::query_scalar::<_, String>(
+        r#"
+        SELECT slack_file_id FROM burndown_snapshots
+        WHERE slack_file_id IS NOT NULL
+        ORDER BY rendered_on DESC
+        LIMIT 1
+        "#,
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(file_id)
+}
+
+/// Record that the digest for the week starting `week_start` was posted.
+/// Returns `false` if it already had been.
+pub async fn claim_digest_run(pool: &PgPool, week_start: NaiveDate) -> IncidentResult<bool> {
+    let claimed = sqlx::query_scalar::query_scalar::<_, NaiveDate>(
+        r#"
+        INSERT INTO digest_runs (week_start)
+        VALUES ($1)
+        ON CONFLICT (week_start) DO NOTHING
+        RETURNING week_start
+        "#,
+    )
+    .bind(week_start)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(claimed.is_some())
+}
+
+/// Record that the weekly report for the week starting `week_start` was
+/// queued. Returns `false` if it already had been.
+pub async fn claim_weekly_report(pool: &PgPool, week_start: NaiveDate) -> IncidentResult<bool> {
+    let claimed = sqlx::query_scalar::query_scalar::<_, NaiveDate>(
+        r#"
+        INSERT INTO weekly_report_runs (week_start)
+        VALUES ($1)
+        ON CONFLICT (week_start) DO NOTHING
+        RETURNING week_start
+        "#,
+    )
+    .bind(week_start)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(claimed.is_some())
+}
@@ -0,0 +1,88 @@
+use crate::db::models::{Artifact, IncidentId};
+use crate::error::IncidentResult;
+use sqlx_postgres::PgPool;
+
+/// Index an artifact already written to the store. Re-recording a key
+/// replaces its metadata.
+pub async fn record_artifact(
+    pool: &PgPool,
+    incident_id: Option<IncidentId>,
+    kind: &str,
+    storage_key: &str,
+    content_type: &str,
+    size_bytes: i64,
+) -> IncidentResult<Artifact> {
+    let artifact = sqlx::query_as::query_as::<_, Artifact>(
+        r#"
+        INSERT INTO artifacts (incident_id, kind, storage_key, content_type, size_bytes)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (storage_key) DO UPDATE
+        SET content_type = EXCLUDED.content_type,
+            size_bytes = EXCLUDED.size_bytes,
+            created_at = NOW()
+        RETURNING *
+        "#,
+    )
+    .bind(incident_id)
+    .bind(kind)
+    .bind(storage_key)
+    .bind(content_type)
+    .bind(size_bytes)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(artifact)
+}
+
+pub async fn get_by_storage_key(
+    pool: &PgPool,
+    storage_key: &str,
+) -> IncidentResult<Option<Artifact>> {
+    let artifact = sqlx::query_as::query_as::<_, Artifact>(
+        r#"
+        SELECT * FROM artifacts
+        WHERE storage_key = $1
+        "#,
+    )
+    .bind(storage_key)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(artifact)
+}
+
+/// An incident's artifacts, newest first.
+pub async fn list_for_incident(
+    pool: &PgPool,
+    incident_id: IncidentId,
+) -> IncidentResult<Vec<Artifact>> {
+    let artifacts = sqlx::query_as::query_as::<_, Artifact>(
+        r#"
+        SELECT * FROM artifacts
+        WHERE incident_id = $1
+        ORDER BY created_at DESC
+        "#,
+    )
+    .bind(incident_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(artifacts)
+}
+
+/// The most recent artifact of `kind`, if any.
+pub async fn latest_of_kind(pool: &PgPool, kind: &str) -> IncidentResult<Option<Artifact>> {
+    let artifact = sqlx::query_as::query_as::<_, Artifact>(
+        r#"
+        SELECT * FROM artifacts
+        WHERE kind = $1
+        ORDER BY created_at DESC
+        LIMIT 1
+        "#,
+    )
+    .bind(kind)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(artifact)
+}
@@ -1,8 +1,16 @@
-use crate::db::models::IncidentId;
+use crate::db::models::{AuditEntry, IncidentId};
 use crate::error::IncidentResult;
+use crate::services::audit_chain;
+use chrono::{DateTime, SubsecRound, Utc};
 use serde_json::Value;
 use sqlx_postgres::PgPool;
+use uuid::Uuid;
 
+/// `pg_advisory_xact_lock` key serializing appends to the audit chain, so
+/// two writers can't both link onto the same previous row.
+const AUDIT_CHAIN_LOCK: i64 = 0x6175_6469_745f_6c67;
+
+/// Append a row to the audit log, chained onto the last one.
 pub async fn log_action(
     pool: &PgPool,
     incident_id: Option<IncidentId>,
@@ -12,20 +20,130 @@ pub async fn log_action(
     new_state: Option<Value>,
     details: Option<Value>,
 ) -> IncidentResult<()> {
+    let mut tx = pool.begin().await?;
+
+    sqlx::query::query("SELECT pg_advisory_xact_lock($1)")
+        .bind(AUDIT_CHAIN_LOCK)
+        .execute(&mut *tx)
+        .await?;
+    let last = sqlx::query_as::query_as::<_, (i64, Option<String>)>(
+        "SELECT seq, row_hash FROM audit_log ORDER BY seq DESC LIMIT 1",
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+    let (last_seq, prev_hash) = match last {
+        Some((seq, Some(hash))) => (seq, hash),
+        Some((seq, None)) => (seq, audit_chain::GENESIS_HASH.to_string()),
+        None => (0, audit_chain::GENESIS_HASH.to_string()),
+    };
+
+    let mut entry = AuditEntry {
+        id: Uuid::new_v4(),
+        seq: last_seq + 1,
+        incident_id,
+        action,
+        actor_id,
+        old_state,
+        new_state,
+        details,
+        // Postgres keeps microseconds; hash what will be read back
+        timestamp: Utc::now().trunc_subsecs(6),
+        row_hash: None,
+        prev_hash: None,
+    };
+    entry.row_hash = Some(audit_chain::entry_hash(
+        &prev_hash,
+        &audit_chain::hashed_fields(&entry),
+    ));
+    entry.prev_hash = Some(prev_hash);
+
     sqlx::query::query(
         r#"
-        INSERT INTO audit_log (incident_id, action, actor_id, old_state, new_state, details)
-        VALUES ($1, $2, $3, $4, $5, $6)
+        INSERT INTO audit_log
+            (id, seq, incident_id, action, actor_id, old_state, new_state, details,
+             timestamp, prev_hash, row_hash)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
         "#,
     )
-    .bind(incident_id)
-    .bind(action)
-    .bind(actor_id)
-    .bind(old_state)
-    .bind(new_state)
-    .bind(details)
-    .execute(pool)
+    .bind(entry.id)
+    .bind(entry.seq)
+    .bind(entry.incident_id)
+    .bind(&entry.action)
+    .bind(&entry.actor_id)
+    .bind(&entry.old_state)
+    .bind(&entry.new_state)
+    .bind(&entry.details)
+    .bind(entry.timestamp)
+    .bind(&entry.prev_hash)
+    .bind(&entry.row_hash)
+    .execute(&mut *tx)
     .await?;
 
+    tx.commit().await?;
     Ok(())
 }
+
+/// Rows after `after_seq` (all rows for 0), in chain order.
+pub async fn list_entries(pool: &PgPool, after_seq: i64) -> IncidentResult<Vec<AuditEntry>> {
+    let entries = sqlx::query_as::query_as::<_, AuditEntry>(
+        r#"
+        SELECT id, seq, incident_id, action, actor_id, old_state, new_state, details,
+               timestamp, prev_hash, row_hash
+        FROM audit_log
+        WHERE seq > $1
+        ORDER BY seq
+        "#,
+    )
+    .bind(after_seq)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(entries)
+}
+
+/// Filters for `search_entries`; `None` fields match everything.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AuditFilter {
+    pub incident_id: Option<IncidentId>,
+    pub actor_id: Option<String>,
+    pub action: Option<String>,
+    /// Inclusive start and exclusive end of the `timestamp` range
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+    /// Only rows before this `seq`, to page back through older rows
+    pub before_seq: Option<i64>,
+    pub limit: i64,
+}
+
+/// Rows matching `filter`, newest first.
+pub async fn search_entries(
+    pool: &PgPool,
+    filter: &AuditFilter,
+) -> IncidentResult<Vec<AuditEntry>> {
+    let entries = sqlx::query_as::query_as::<_, AuditEntry>(
+        r#"
+        SELECT id, seq, incident_id, action, actor_id, old_state, new_state, details,
+               timestamp, prev_hash, row_hash
+        FROM audit_log
+        WHERE ($1::UUID IS NULL OR incident_id = $1)
+          AND ($2::TEXT IS NULL OR actor_id = $2)
+          AND ($3::TEXT IS NULL OR action = $3)
+          AND ($4::TIMESTAMPTZ IS NULL OR timestamp >= $4)
+          AND ($5::TIMESTAMPTZ IS NULL OR timestamp < $5)
+          AND ($6::BIGINT IS NULL OR seq < $6)
+        ORDER BY seq DESC
+        LIMIT $7
+        "#,
+    )
+    .bind(filter.incident_id)
+    .bind(filter.actor_id.as_deref())
+    .bind(filter.action.as_deref())
+    .bind(filter.since)
+    .bind(filter.until)
+    .bind(filter.before_seq)
+    .bind(filter.limit)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(entries)
+}
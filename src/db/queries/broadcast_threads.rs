@@ -0,0 +1,65 @@
+use crate::db::models::IncidentId;
+use crate::error::IncidentResult;
+use sqlx_postgres::PgPool;
+
+/// `ts` of the incident's broadcast in `channel_id`, if it has one.
+pub async fn get_broadcast_thread(
+    pool: &PgPool,
+    incident_id: IncidentId,
+    channel_id: &str,
+) -> IncidentResult<Option<String>> {
+    let ts = sqlx::query_scalar::query_scalar::<_, String>(
+        r#"
+        SELECT message_ts FROM broadcast_threads
+        WHERE incident_id = $1 AND channel_id = $2
+        "#,
+    )
+    .bind(incident_id)
+    .bind(channel_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(ts)
+}
+
+/// `(channel_id, ts)` of every broadcast for the incident, oldest first.
+pub async fn list_broadcast_threads(
+    pool: &PgPool,
+    incident_id: IncidentId,
+) -> IncidentResult<Vec<(String, String)>> {
+    let threads = sqlx::query_as::query_as::<_, (String, String)>(
+        r#"
+        SELECT channel_id, message_ts FROM broadcast_threads
+        WHERE incident_id = $1
+        ORDER BY created_at, channel_id
+        "#,
+    )
+    .bind(incident_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(threads)
+}
+
+/// Remember the incident's broadcast in `channel_id`. The first one wins.
+pub async fn record_broadcast_thread(
+    pool: &PgPool,
+    incident_id: IncidentId,
+    channel_id: &str,
+    message_ts: &str,
+) -> IncidentResult<()> {
+    sqlx::query::query(
+        r#"
+        INSERT INTO broadcast_threads (incident_id, channel_id, message_ts)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (incident_id, channel_id) DO NOTHING
+        "#,
+    )
+    .bind(incident_id)
+    .bind(channel_id)
+    .bind(message_ts)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
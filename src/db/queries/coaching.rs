@@ -0,0 +1,168 @@
+use crate::error::IncidentResult;
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx_postgres::PgPool;
+
+/// Comms and follow-up figures for the incidents one person commanded.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CommanderStats {
+    pub commander_id: String,
+    /// Incidents declared in the period that they command
+    pub incidents: i64,
+    /// Mean minutes from declaration to the first status update
+    pub mean_first_update_minutes: Option<f64>,
+    /// Mean minutes between consecutive status updates
+    pub mean_update_interval_minutes: Option<f64>,
+    pub resolved: i64,
+    /// Resolved incidents that have had a postmortem generated
+    pub resolved_with_postmortem: i64,
+}
+
+/// Stats for every commander of incidents declared in `[start, end)`,
+/// ordered by commander.
+pub async fn commander_stats(
+    pool: &PgPool,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> IncidentResult<Vec<CommanderStats>> {
+    let rows = sqlx::query_as::query_as::<_, (String, i64, Option<f64>, Option<f64>, i64, i64)>(
+        r#"
+        WITH scoped AS (
+            SELECT
+                i.id,
+                i.commander_id,
+                i.declared_at,
+                i.resolved_at,
+                (
+                    SELECT MIN(t.timestamp) FROM incident_timeline t
+                    WHERE t.incident_id = i.id AND t.event_type = 'status_update'
+                ) AS first_update_at,
+                EXISTS (
+                    SELECT 1 FROM audit_log a
+                    WHERE a.incident_id = i.id AND a.action = 'generate_postmortem'
+                ) AS has_postmortem
+            FROM incidents i
+            WHERE i.declared_at >= $1 AND i.declared_at < $2 AND NOT i.is_triage
+        ),
+        intervals AS (
+            SELECT
+                s.commander_id,
+                EXTRACT(EPOCH FROM t.timestamp - LAG(t.timestamp) OVER (
+                    PARTITION BY t.incident_id ORDER BY t.timestamp
+                )) / 60 AS minutes
+            FROM incident_timeline t
+            JOIN scoped s ON s.id = t.incident_id
+            WHERE t.event_type = 'status_update'
+        )
+        SELECT
+            s.commander_id,
+            COUNT(*),
+            (AVG(EXTRACT(EPOCH FROM s.first_update_at - s.declared_at)) / 60)::FLOAT8,
+            (
+                SELECT AVG(v.minutes) FROM intervals v
+                WHERE v.commander_id = s.commander_id
+            )::FLOAT8,
+            COUNT(s.resolved_at),
+            COUNT(*) FILTER (WHERE s.resolved_at IS NOT NULL AND s.has_postmortem)
+        FROM scoped s
+        GROUP BY s.commander_id
+        ORDER BY s.commander_id
+        "#,
+    )
+    .bind(start)
+    .bind(end)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(
+            |(commander_id, incidents, first_update, interval, resolved, with_postmortem)| {
+                CommanderStats {
+                    commander_id,
+                    incidents,
+                    mean_first_update_minutes: first_update,
+                    mean_update_interval_minutes: interval,
+                    resolved,
+                    resolved_with_postmortem: with_postmortem,
+                }
+            },
+        )
+        .collect())
+}
+
+/// Opt `user_id` in to quarterly coaching reports. Returns `false` if they
+/// already were.
+pub async fn opt_in(pool: &PgPool, user_id: &str) -> IncidentResult<bool> {
+    let result = sqlx::query::query(
+        r#"
+        INSERT INTO coaching_opt_ins (user_id)
+        VALUES ($1)
+        ON CONFLICT (user_id) DO NOTHING
+        "#,
+    )
+    .bind(user_id)
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Returns `false` if `user_id` wasn't opted in.
+pub async fn opt_out(pool: &PgPool, user_id: &str) -> IncidentResult<bool> {
+    let result = sqlx::query::query("DELETE FROM coaching_opt_ins WHERE user_id = $1")
+        .bind(user_id)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn is_opted_in(pool: &PgPool, user_id: &str) -> IncidentResult<bool> {
+    let opted_in = sqlx::query_scalar::query_scalar::<_, bool>(
+        "SELECT EXISTS (SELECT 1 FROM coaching_opt_ins WHERE user_id = $1)",
+    )
+    .bind(user_id)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(opted_in)
+}
+
+/// Users who had opted in before `before`.
+pub async fn opted_in_before(pool: &PgPool, before: DateTime<Utc>) -> IncidentResult<Vec<String>> {
+    let users = sqlx::query_scalar::query_scalar::<_, String>(
+        r#"
+        SELECT user_id FROM coaching_opt_ins
+        WHERE opted_in_at < $1
+        ORDER BY user_id
+        "#,
+    )
+    .bind(before)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(users)
+}
+
+/// Record that `user_id`'s report for the quarter starting `period_start`
+/// was sent. Returns `false` if it already had been.
+pub async fn claim_coaching_report(
+    pool: &PgPool,
+    period_start: NaiveDate,
+    user_id: &str,
+) -> IncidentResult<bool> {
+    let claimed = sqlx::query_scalar::query_scalar::<_, String>(
+        r#"
+        INSERT INTO coaching_report_runs (period_start, user_id)
+        VALUES ($1, $2)
+        ON CONFLICT (period_start, user_id) DO NOTHING
+        RETURNING user_id
+        "#,
+    )
+    .bind(period_start)
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(claimed.is_some())
+}
@@ -0,0 +1,141 @@
+use crate::db::models::IncidentId;
+use crate::error::IncidentResult;
+use chrono::{DateTime, Utc};
+use sqlx_postgres::PgPool;
+
+/// Record that `commander_id` was active in `incident_id` at `now`.
+pub async fn mark_commander_seen(
+    pool: &PgPool,
+    incident_id: IncidentId,
+    commander_id: &str,
+    now: DateTime<Utc>,
+) -> IncidentResult<()> {
+    sqlx::query::query(
+        r#"
+        INSERT INTO commander_activity (incident_id, commander_id, seen_at)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (incident_id) DO UPDATE
+        SET commander_id = EXCLUDED.commander_id, seen_at = EXCLUDED.seen_at
+        "#,
+    )
+    .bind(incident_id)
+    .bind(commander_id)
+    .bind(now)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Record a message from `user_id` in `channel_id` if they command the open
+/// incident there. Returns whether it counted as commander activity.
+pub async fn mark_commander_seen_in_channel(
+    pool: &PgPool,
+    channel_id: &str,
+    user_id: &str,
+    now: DateTime<Utc>,
+) -> IncidentResult<bool> {
+    let result = sqlx::query::query(
+        r#"
+        INSERT INTO commander_activity (incident_id, commander_id, seen_at)
+        SELECT id, commander_id, $3
+        FROM incidents
+        WHERE slack_channel_id = $1 AND commander_id = $2 AND status NOT IN ('resolved', 'canceled')
+        ON CONFLICT (incident_id) DO UPDATE
+        SET commander_id = EXCLUDED.commander_id, seen_at = EXCLUDED.seen_at
+        "#,
+    )
+    .bind(channel_id)
+    .bind(user_id)
+    .bind(now)
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Open P1 incidents whose current commander has not been seen for
+/// `minutes` and has not been escalated yet, with when they were last seen.
+/// The commander counts as seen at declaration, on any timeline event they
+/// posted, and on channel messages or acknowledged nags.
+pub async fn absent_commanders(
+    pool: &PgPool,
+    minutes: i32,
+    now: DateTime<Utc>,
+) -> IncidentResult<Vec<(IncidentId, DateTime<Utc>)>> {
+    let rows = sqlx::query_as::query_as::<_, (IncidentId, DateTime<Utc>)>(
+        r#"
+        SELECT i.id, s.last_seen
+        FROM incidents i
+        CROSS JOIN LATERAL (
+            SELECT GREATEST(i.declared_at, MAX(t.timestamp), (
+                SELECT a.seen_at FROM commander_activity a
+                WHERE a.incident_id = i.id AND a.commander_id = i.commander_id
+            )) AS last_seen
+            FROM incident_timeline t
+            WHERE t.incident_id = i.id AND t.posted_by = i.commander_id
+        ) s
+        WHERE i.status NOT IN ('triaging', 'resolved', 'canceled')
+          AND i.severity = 'P1'
+          AND s.last_seen <= $2 - make_interval(mins => $1)
+          AND NOT EXISTS (
+              SELECT 1 FROM commander_escalations e
+              WHERE e.incident_id = i.id AND e.commander_id = i.commander_id
+          )
+        ORDER BY s.last_seen ASC
+        "#,
+    )
+    .bind(minutes)
+    .bind(now)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows)
+}
+
+/// Record an escalation away from `commander_id`. Returns `false` if that
+/// commander was already escalated for this incident.
+pub async fn claim_escalation(
+    pool: &PgPool,
+    incident_id: IncidentId,
+    commander_id: &str,
+    backup_ids: &[String],
+    now: DateTime<Utc>,
+) -> IncidentResult<bool> {
+    let claimed = sqlx::query_scalar::query_scalar::<_, IncidentId>(
+        r#"
+        INSERT INTO commander_escalations (incident_id, commander_id, backup_ids, escalated_at)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (incident_id, commander_id) DO NOTHING
+        RETURNING incident_id
+        "#,
+    )
+    .bind(incident_id)
+    .bind(commander_id)
+    .bind(backup_ids)
+    .bind(now)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(claimed.is_some())
+}
+
+/// Backups offered command when `commander_id` was escalated, if they were.
+pub async fn escalation_backups(
+    pool: &PgPool,
+    incident_id: IncidentId,
+    commander_id: &str,
+) -> IncidentResult<Option<Vec<String>>> {
+    let backups = sqlx::query_scalar::query_scalar::<_, Vec<String>>(
+        r#"
+        SELECT backup_ids FROM commander_escalations
+        WHERE incident_id = $1 AND commander_id = $2
+        "#,
+    )
+    .bind(incident_id)
+    .bind(commander_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(backups)
+}
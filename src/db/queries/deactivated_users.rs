@@ -0,0 +1,35 @@
+use crate::error::IncidentResult;
+use chrono::{DateTime, Utc};
+use sqlx_postgres::PgPool;
+
+/// Record that `user_id` was found deactivated. Returns `false` if they
+/// already were.
+pub async fn record_deactivated_user(
+    pool: &PgPool,
+    user_id: &str,
+    now: DateTime<Utc>,
+) -> IncidentResult<bool> {
+    let result = sqlx::query::query(
+        r#"
+        INSERT INTO deactivated_users (user_id, detected_at)
+        VALUES ($1, $2)
+        ON CONFLICT (user_id) DO NOTHING
+        "#,
+    )
+    .bind(user_id)
+    .bind(now)
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Forget users found active again, so a later deactivation is reported.
+pub async fn clear_deactivated_users(pool: &PgPool, user_ids: &[String]) -> IncidentResult<()> {
+    sqlx::query::query("DELETE FROM deactivated_users WHERE user_id = ANY($1)")
+        .bind(user_ids)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use crate::error::IncidentResult;
+use sqlx_postgres::PgPool;
+
+/// Services currently disabled, in name order.
+pub async fn list_disabled_services(pool: &PgPool) -> IncidentResult<Vec<String>> {
+    let services = sqlx::query_scalar::query_scalar::<_, String>(
+        "SELECT service FROM disabled_services ORDER BY service",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(services)
+}
+
+/// Disable `service`. Returns `false` if it already was.
+pub async fn disable_service(pool: &PgPool, service: &str, user_id: &str) -> IncidentResult<bool> {
+    let result = sqlx::query::query(
+        r#"
+        INSERT INTO disabled_services (service, disabled_by)
+        VALUES ($1, $2)
+        ON CONFLICT (service) DO NOTHING
+        "#,
+    )
+    .bind(service)
+    .bind(user_id)
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Enable `service` again. Returns `false` if it wasn't disabled.
+pub async fn enable_service(pool: &PgPool, service: &str) -> IncidentResult<bool> {
+    let result = sqlx::query::query("DELETE FROM disabled_services WHERE service = $1")
+        .bind(service)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
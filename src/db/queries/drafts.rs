@@ -0,0 +1,72 @@
+use crate::db::models::DeclareDraft;
+use crate::error::{IncidentError, IncidentResult};
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use sqlx_postgres::PgPool;
+
+/// Save `user_id`'s declare draft, replacing any earlier one, and drop
+/// drafts last touched before `expired_before`.
+pub async fn save_declare_draft(
+    pool: &PgPool,
+    user_id: &str,
+    draft: &DeclareDraft,
+    now: DateTime<Utc>,
+    expired_before: DateTime<Utc>,
+) -> IncidentResult<()> {
+    let draft =
+        serde_json::to_value(draft).map_err(|e| IncidentError::InternalError(e.to_string()))?;
+    sqlx::query::query(
+        r#"
+        INSERT INTO declare_drafts (user_id, draft, updated_at)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (user_id) DO UPDATE
+        SET draft = EXCLUDED.draft, updated_at = EXCLUDED.updated_at
+        "#,
+    )
+    .bind(user_id)
+    .bind(draft)
+    .bind(now)
+    .execute(pool)
+    .await?;
+
+    sqlx::query::query("DELETE FROM declare_drafts WHERE updated_at < $1")
+        .bind(expired_before)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+/// `user_id`'s draft and when it was saved, if saved at or after `since`.
+pub async fn get_declare_draft(
+    pool: &PgPool,
+    user_id: &str,
+    since: DateTime<Utc>,
+) -> IncidentResult<Option<(DeclareDraft, DateTime<Utc>)>> {
+    let row = sqlx::query_as::query_as::<_, (Value, DateTime<Utc>)>(
+        r#"
+        SELECT draft, updated_at FROM declare_drafts
+        WHERE user_id = $1 AND updated_at >= $2
+        "#,
+    )
+    .bind(user_id)
+    .bind(since)
+    .fetch_optional(pool)
+    .await?;
+
+    // A draft that no longer parses is as good as none
+    Ok(row.and_then(|(draft, saved_at)| {
+        serde_json::from_value(draft)
+            .ok()
+            .map(|draft| (draft, saved_at))
+    }))
+}
+
+pub async fn delete_declare_draft(pool: &PgPool, user_id: &str) -> IncidentResult<()> {
+    sqlx::query::query("DELETE FROM declare_drafts WHERE user_id = $1")
+        .bind(user_id)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use crate::config::ExecTier;
+use crate::db::models::IncidentId;
+use crate::error::IncidentResult;
+use chrono::{DateTime, Utc};
+use sqlx_postgres::PgPool;
+
+/// Record that `tier` of an incident's exec notification is going out.
+/// Returns false if it already went out.
+pub async fn claim_tier(
+    pool: &PgPool,
+    incident_id: IncidentId,
+    tier: i16,
+    recipients: &ExecTier,
+    now: DateTime<Utc>,
+) -> IncidentResult<bool> {
+    let claimed = sqlx::query_scalar::query_scalar::<_, IncidentId>(
+        r#"
+        INSERT INTO exec_tier_deliveries (incident_id, tier, users, user_groups, delivered_at)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (incident_id, tier) DO NOTHING
+        RETURNING incident_id
+        "#,
+    )
+    .bind(incident_id)
+    .bind(tier)
+    .bind(&recipients.users)
+    .bind(&recipients.user_groups)
+    .bind(now)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(claimed.is_some())
+}
+
+/// Everyone the incident's delivered tiers went to, each once.
+pub async fn delivered_recipients(
+    pool: &PgPool,
+    incident_id: IncidentId,
+) -> IncidentResult<ExecTier> {
+    let rows = sqlx::query_as::query_as::<_, (Vec<String>, Vec<String>)>(
+        "SELECT users, user_groups FROM exec_tier_deliveries WHERE incident_id = $1 ORDER BY tier",
+    )
+    .bind(incident_id)
+    .fetch_all(pool)
+    .await?;
+
+    let mut recipients = ExecTier::default();
+    for (users, user_groups) in rows {
+        for user_id in users {
+            if !recipients.users.contains(&user_id) {
+                recipients.users.push(user_id);
+            }
+        }
+        for group_id in user_groups {
+            if !recipients.user_groups.contains(&group_id) {
+                recipients.user_groups.push(group_id);
+            }
+        }
+    }
+    Ok(recipients)
+}
@@ -0,0 +1,187 @@
+use crate::db::models::{FailedJob, IncidentId};
+use crate::error::IncidentResult;
+use serde_json::Value;
+use sqlx_postgres::PgPool;
+use uuid::Uuid;
+
+/// Keep a job that couldn't run so a later pass can retry it.
+pub async fn record_failed_job(
+    pool: &PgPool,
+    incident_id: Option<IncidentId>,
+    job: &Value,
+    error: &str,
+) -> IncidentResult<()> {
+    sqlx::query::query(
+        r#"
+        INSERT INTO failed_jobs (incident_id, job, last_error)
+        VALUES ($1, $2, $3)
+        "#,
+    )
+    .bind(incident_id)
+    .bind(job)
+    .bind(error)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Dead-letter a job that failed for good, for an admin to retry or discard.
+pub async fn dead_letter_job(
+    pool: &PgPool,
+    incident_id: Option<IncidentId>,
+    job: &Value,
+    error: &str,
+) -> IncidentResult<()> {
+    sqlx::query::query(
+        r#"
+        INSERT INTO failed_jobs (incident_id, job, last_error, dead_lettered_at)
+        VALUES ($1, $2, $3, NOW())
+        "#,
+    )
+    .bind(incident_id)
+    .bind(job)
+    .bind(error)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Remove and return the oldest failed job. Rows another pass is taking are
+/// skipped, so each job is retried by one pass at a time.
+pub async fn take_oldest_failed_job(pool: &PgPool) -> IncidentResult<Option<FailedJob>> {
+    let job = sqlx::query_as::query_as::<_, FailedJob>(
+        r#"
+        DELETE FROM failed_jobs
+        WHERE id = (
+            SELECT id FROM failed_jobs
+            WHERE dead_lettered_at IS NULL
+            ORDER BY created_at ASC
+            LIMIT 1
+            FOR UPDATE SKIP LOCKED
+        )
+        RETURNING *
+        "#,
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(job)
+}
+
+/// Put back a job whose retry failed again, keeping its place in the queue.
+pub async fn restore_failed_job(
+    pool: &PgPool,
+    failed: &FailedJob,
+    error: &str,
+) -> IncidentResult<()> {
+    sqlx::query::query(
+        r#"
+        INSERT INTO failed_jobs (id, incident_id, job, attempts, last_error, created_at, last_attempted_at)
+        VALUES ($1, $2, $3, $4, $5, $6, NOW())
+        "#,
+    )
+    .bind(failed.id)
+    .bind(failed.incident_id)
+    .bind(&failed.job)
+    .bind(failed.attempts + 1)
+    .bind(error)
+    .bind(failed.created_at)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Dead-letter a deferred job whose retry failed for good.
+pub async fn dead_letter_failed_job(
+    pool: &PgPool,
+    failed: &FailedJob,
+    error: &str,
+) -> IncidentResult<()> {
+    sqlx::query::query(
+        r#"
+        INSERT INTO failed_jobs (id, incident_id, job, attempts, last_error, created_at, last_attempted_at, dead_lettered_at)
+        VALUES ($1, $2, $3, $4, $5, $6, NOW(), NOW())
+        "#,
+    )
+    .bind(failed.id)
+    .bind(failed.incident_id)
+    .bind(&failed.job)
+    .bind(failed.attempts + 1)
+    .bind(error)
+    .bind(failed.created_at)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Dead-lettered jobs, most recently given up on first.
+pub async fn list_dead_letters(pool: &PgPool, limit: i64) -> IncidentResult<Vec<FailedJob>> {
+    let jobs = sqlx::query_as::query_as::<_, FailedJob>(
+        r#"
+        SELECT * FROM failed_jobs
+        WHERE dead_lettered_at IS NOT NULL
+        ORDER BY dead_lettered_at DESC
+        LIMIT $1
+        "#,
+    )
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(jobs)
+}
+
+/// Dead-lettered jobs whose ID starts with `prefix`; at most two, enough to
+/// tell a unique match from an ambiguous one.
+pub async fn find_dead_letters_by_prefix(
+    pool: &PgPool,
+    prefix: &str,
+) -> IncidentResult<Vec<FailedJob>> {
+    let jobs = sqlx::query_as::query_as::<_, FailedJob>(
+        r#"
+        SELECT * FROM failed_jobs
+        WHERE dead_lettered_at IS NOT NULL AND starts_with(id::text, $1)
+        ORDER BY dead_lettered_at DESC
+        LIMIT 2
+        "#,
+    )
+    .bind(prefix.to_ascii_lowercase())
+    .fetch_all(pool)
+    .await?;
+
+    Ok(jobs)
+}
+
+pub async fn get_dead_letter(pool: &PgPool, id: Uuid) -> IncidentResult<Option<FailedJob>> {
+    let job = sqlx::query_as::query_as::<_, FailedJob>(
+        r#"
+        SELECT * FROM failed_jobs WHERE id = $1 AND dead_lettered_at IS NOT NULL
+        "#,
+    )
+    .bind(id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(job)
+}
+
+/// Remove a dead-lettered job being retried or discarded. Returns `None` if
+/// someone else already did.
+pub async fn take_dead_letter(pool: &PgPool, id: Uuid) -> IncidentResult<Option<FailedJob>> {
+    let job = sqlx::query_as::query_as::<_, FailedJob>(
+        r#"
+        DELETE FROM failed_jobs
+        WHERE id = $1 AND dead_lettered_at IS NOT NULL
+        RETURNING *
+        "#,
+    )
+    .bind(id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(job)
+}
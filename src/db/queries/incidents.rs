@@ -1,18 +1,89 @@
-use crate::db::models::{Incident, IncidentId, IncidentStatus, Severity, SlackChannelId};
+use crate::db::models::{
+    Incident, IncidentId, IncidentStatus, Severity, SeverityLevel, SlackChannelId,
+};
 use crate::error::IncidentResult;
+use chrono::{DateTime, NaiveDate, Utc};
 use sqlx_postgres::PgPool;
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+/// Optional filters for `list_incidents`; `None` fields match everything.
+#[derive(Debug, Clone, Default)]
+pub struct IncidentFilter {
+    pub status: Option<IncidentStatus>,
+    pub severity: Option<Severity>,
+    /// Only open (declared, unresolved) incidents when true.
+    pub open_only: bool,
+    pub limit: i64,
+}
+
+/// Criteria for `/incident search`; `None` fields match everything.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IncidentSearch {
+    /// Affected service, case-insensitive
+    pub service: Option<String>,
+    pub severity: Option<Severity>,
+    pub status: Option<IncidentStatus>,
+    /// Declared on or after this day (UTC)
+    pub declared_after: Option<NaiveDate>,
+    /// Case-insensitive substring of the title or any timeline message
+    pub text: Option<String>,
+    /// Set when the text is an incident number (`PLAT-12`), which then
+    /// matches besides the text
+    pub number: Option<(String, i32)>,
+}
 
 pub async fn create_incident(
     pool: &PgPool,
     title: String,
-    severity: Severity,
+    level: &SeverityLevel,
+    number_prefix: &str,
     affected_service: String,
     commander_id: String,
 ) -> IncidentResult<Incident> {
     let incident = sqlx::query_as::query_as::<_, Incident>(
         r#"
-        INSERT INTO incidents (title, severity, affected_service, commander_id, status, declared_at)
-        VALUES ($1, $2, $3, $4, 'declared', NOW())
+        INSERT INTO incidents (title, severity, severity_code, number_prefix, affected_service, commander_id, status, declared_at)
+        VALUES ($1, $2, $3, $4, $5, $6, 'declared', NOW())
+        RETURNING *
+        "#,
+    )
+    .bind(title)
+    .bind(level.tier.as_db_str())
+    .bind(&level.code)
+    .bind(number_prefix)
+    .bind(affected_service)
+    .bind(commander_id)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(incident)
+}
+
+/// Insert an incident that was run before the bot existed, already resolved.
+/// The channel is marked archived so the archive job leaves it alone.
+#[allow(clippy::too_many_arguments)]
+pub async fn insert_reconstructed_incident(
+    pool: &PgPool,
+    title: &str,
+    severity: Severity,
+    number_prefix: &str,
+    affected_service: &str,
+    commander_id: &str,
+    channel_id: &str,
+    declared_at: DateTime<Utc>,
+    resolved_at: DateTime<Utc>,
+) -> IncidentResult<Incident> {
+    let incident = sqlx::query_as::query_as::<_, Incident>(
+        r#"
+        INSERT INTO incidents (
+            title, severity, affected_service, commander_id, status, slack_channel_id,
+            declared_at, resolved_at, duration_minutes, channel_archived_at, number_prefix
+        )
+        VALUES (
+            $1, $2, $3, $4, 'resolved', $5,
+            $6, $7, ROUND(EXTRACT(EPOCH FROM ($7 - $6)) / 60), NOW(), $8
+        )
         RETURNING *
         "#,
     )
@@ -20,6 +91,10 @@ pub async fn create_incident(
     .bind(severity.as_db_str())
     .bind(affected_service)
     .bind(commander_id)
+    .bind(channel_id)
+    .bind(declared_at)
+    .bind(resolved_at)
+    .bind(number_prefix)
     .fetch_one(pool)
     .await?;
 
@@ -40,10 +115,30 @@ pub async fn get_incident_by_id(pool: &PgPool, id: IncidentId) -> IncidentResult
     Ok(incident)
 }
 
+/// `prefix` as stored (uppercase).
+pub async fn get_incident_by_number(
+    pool: &PgPool,
+    prefix: &str,
+    number: i32,
+) -> IncidentResult<Incident> {
+    let incident = sqlx::query_as::query_as::<_, Incident>(
+        r#"
+        SELECT * FROM incidents WHERE number_prefix = $1 AND number = $2
+        "#,
+    )
+    .bind(prefix)
+    .bind(number)
+    .fetch_optional(pool)
+    .await?
+    .ok_or(crate::error::IncidentError::NotFound)?;
+
+    Ok(incident)
+}
+
 pub async fn get_incident_by_channel(pool: &PgPool, channel_id: &str) -> IncidentResult<Incident> {
     let incident = sqlx::query_as::query_as::<_, Incident>(
         r#"
-        SELECT * FROM incidents WHERE slack_channel_id = $1 AND status != 'resolved'
+        SELECT * FROM incidents WHERE slack_channel_id = $1 AND status NOT IN ('resolved', 'canceled')
         "#,
     )
     .bind(channel_id)
@@ -74,6 +169,27 @@ pub async fn get_latest_incident_by_channel(
     Ok(incident)
 }
 
+/// The most recently declared unresolved incident for `service`, compared
+/// case-insensitively.
+pub async fn get_open_incident_for_service(
+    pool: &PgPool,
+    service: &str,
+) -> IncidentResult<Option<Incident>> {
+    let incident = sqlx::query_as::query_as::<_, Incident>(
+        r#"
+        SELECT * FROM incidents
+        WHERE LOWER(affected_service) = LOWER($1) AND status NOT IN ('triaging', 'resolved', 'canceled')
+        ORDER BY declared_at DESC
+        LIMIT 1
+        "#,
+    )
+    .bind(service)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(incident)
+}
+
 pub async fn update_channel_id(
     pool: &PgPool,
     incident_id: IncidentId,
@@ -93,6 +209,90 @@ pub async fn update_channel_id(
     Ok(())
 }
 
+/// Forget an open incident's channel after it was archived or deleted in
+/// Slack, along with the summary pinned there, so nothing posts to it again.
+/// Returns whether `channel_id` was still the incident's channel.
+pub async fn detach_channel(
+    pool: &PgPool,
+    incident_id: IncidentId,
+    channel_id: &str,
+) -> IncidentResult<bool> {
+    let result = sqlx::query::query(
+        r#"
+        UPDATE incidents
+        SET slack_channel_id = NULL, pinned_message_ts = NULL, updated_at = NOW()
+        WHERE id = $1 AND slack_channel_id = $2 AND status NOT IN ('resolved', 'canceled')
+        "#,
+    )
+    .bind(incident_id)
+    .bind(channel_id)
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() == 1)
+}
+
+/// Give a channel-less open incident a new channel. Returns false if it
+/// already has one (another click got there first) or was resolved.
+pub async fn attach_replacement_channel(
+    pool: &PgPool,
+    incident_id: IncidentId,
+    channel_id: &str,
+) -> IncidentResult<bool> {
+    let result = sqlx::query::query(
+        r#"
+        UPDATE incidents SET slack_channel_id = $2, updated_at = NOW()
+        WHERE id = $1 AND slack_channel_id IS NULL AND status NOT IN ('resolved', 'canceled')
+        "#,
+    )
+    .bind(incident_id)
+    .bind(channel_id)
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() == 1)
+}
+
+pub async fn update_pinned_message_ts(
+    pool: &PgPool,
+    incident_id: IncidentId,
+    ts: &str,
+) -> IncidentResult<()> {
+    sqlx::query::query(
+        r#"
+        UPDATE incidents SET pinned_message_ts = $1, updated_at = NOW()
+        WHERE id = $2
+        "#,
+    )
+    .bind(ts)
+    .bind(incident_id)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Store the incident's conference bridge. Returns false when it already has
+/// one, so a bridge is only ever announced once.
+pub async fn set_bridge_url(
+    pool: &PgPool,
+    incident_id: IncidentId,
+    bridge_url: &str,
+) -> IncidentResult<bool> {
+    let result = sqlx::query::query(
+        r#"
+        UPDATE incidents SET bridge_url = $2, updated_at = NOW()
+        WHERE id = $1 AND bridge_url IS NULL
+        "#,
+    )
+    .bind(incident_id)
+    .bind(bridge_url)
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() == 1)
+}
+
 pub async fn delete_incident(pool: &PgPool, incident_id: IncidentId) -> IncidentResult<()> {
     // Delete related records first (foreign key constraints)
     sqlx::query::query("DELETE FROM incident_notifications WHERE incident_id = $1")
@@ -105,10 +305,8 @@ pub async fn delete_incident(pool: &PgPool, incident_id: IncidentId) -> Incident
         .execute(pool)
         .await?;
 
-    sqlx::query::query("DELETE FROM audit_log WHERE incident_id = $1")
-        .bind(incident_id)
-        .execute(pool)
-        .await?;
+    // audit_log rows stay: they're hash-chained, and removing any breaks the
+    // chain for every row after it
 
     // Delete the incident itself
     sqlx::query::query("DELETE FROM incidents WHERE id = $1")
@@ -141,15 +339,35 @@ pub async fn update_status(
 pub async fn update_severity(
     pool: &PgPool,
     incident_id: IncidentId,
-    severity: Severity,
+    level: &SeverityLevel,
 ) -> IncidentResult<()> {
     sqlx::query::query(
         r#"
-        UPDATE incidents SET severity = $1, updated_at = NOW()
+        UPDATE incidents SET severity = $1, severity_code = $3, updated_at = NOW()
         WHERE id = $2
         "#,
     )
-    .bind(severity.as_db_str())
+    .bind(level.tier.as_db_str())
+    .bind(incident_id)
+    .bind(&level.code)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn update_commander(
+    pool: &PgPool,
+    incident_id: IncidentId,
+    commander_id: &str,
+) -> IncidentResult<()> {
+    sqlx::query::query(
+        r#"
+        UPDATE incidents SET commander_id = $1, updated_at = NOW()
+        WHERE id = $2
+        "#,
+    )
+    .bind(commander_id)
     .bind(incident_id)
     .execute(pool)
     .await?;
@@ -176,6 +394,282 @@ pub async fn resolve_incident(pool: &PgPool, incident_id: IncidentId) -> Inciden
     Ok(incident)
 }
 
+/// Move a resolved incident back to `investigating`, clearing its resolution.
+/// Returns `None` if the incident isn't resolved.
+pub async fn reopen_incident(
+    pool: &PgPool,
+    incident_id: IncidentId,
+) -> IncidentResult<Option<Incident>> {
+    let incident = sqlx::query_as::query_as::<_, Incident>(
+        r#"
+        UPDATE incidents
+        SET status = 'investigating',
+            resolved_at = NULL,
+            duration_minutes = NULL,
+            updated_at = NOW()
+        WHERE id = $1 AND status = 'resolved'
+        RETURNING *
+        "#,
+    )
+    .bind(incident_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(incident)
+}
+
+/// Close an open incident as a false alarm. Unlike a resolution it records no
+/// `resolved_at` or duration, which keeps it out of MTTR. Returns `None` if
+/// the incident was already resolved or canceled.
+pub async fn cancel_incident(
+    pool: &PgPool,
+    incident_id: IncidentId,
+    reason: &str,
+) -> IncidentResult<Option<Incident>> {
+    let incident = sqlx::query_as::query_as::<_, Incident>(
+        r#"
+        UPDATE incidents
+        SET status = 'canceled',
+            canceled_at = NOW(),
+            cancel_reason = $2,
+            updated_at = NOW()
+        WHERE id = $1 AND status NOT IN ('resolved', 'canceled')
+        RETURNING *
+        "#,
+    )
+    .bind(incident_id)
+    .bind(reason)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(incident)
+}
+
+/// Open a triage: a suspected issue with no channel, service or
+/// notifications yet, numbered under `incident_number::TRIAGE_PREFIX`.
+/// `declared_at` is provisional until `declare_triage`.
+pub async fn create_triage(
+    pool: &PgPool,
+    title: &str,
+    reporter_id: &str,
+) -> IncidentResult<Incident> {
+    let incident = sqlx::query_as::query_as::<_, Incident>(
+        r#"
+        INSERT INTO incidents (title, severity, affected_service, commander_id, status, declared_at, triaged_at, is_triage, number_prefix)
+        VALUES ($1, 'P4', '', $2, 'triaging', NOW(), NOW(), TRUE, $3)
+        RETURNING *
+        "#,
+    )
+    .bind(title)
+    .bind(reporter_id)
+    .bind(crate::utils::incident_number::TRIAGE_PREFIX)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(incident)
+}
+
+/// Turn a triage into a declared incident: it takes the declare modal's
+/// details, is renumbered under `number_prefix` and its clock restarts, so
+/// time spent triaging doesn't count towards MTTR. Returns `None` if it is no
+/// longer triaging (already declared or dismissed).
+#[allow(clippy::too_many_arguments)]
+pub async fn declare_triage(
+    pool: &PgPool,
+    incident_id: IncidentId,
+    title: &str,
+    level: &SeverityLevel,
+    affected_service: &str,
+    commander_id: &str,
+    channel_id: &str,
+    custom_fields: &BTreeMap<String, String>,
+    number_prefix: &str,
+    template_id: Option<Uuid>,
+) -> IncidentResult<Option<Incident>> {
+    let mut tx = pool.begin().await?;
+
+    // Lock the triage so a double submission can't take two numbers
+    let triaging = sqlx::query_scalar::query_scalar::<_, IncidentId>(
+        "SELECT id FROM incidents WHERE id = $1 AND status = 'triaging' FOR UPDATE",
+    )
+    .bind(incident_id)
+    .fetch_optional(&mut *tx)
+    .await?;
+    if triaging.is_none() {
+        return Ok(None);
+    }
+
+    let number = sqlx::query_scalar::query_scalar::<_, i32>(
+        r#"
+        INSERT INTO incident_number_sequences AS s (prefix, last_number)
+        VALUES ($1, 1)
+        ON CONFLICT (prefix) DO UPDATE SET last_number = s.last_number + 1
+        RETURNING last_number
+        "#,
+    )
+    .bind(number_prefix)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    let incident = sqlx::query_as::query_as::<_, Incident>(
+        r#"
+        UPDATE incidents
+        SET status = 'declared',
+            declared_at = NOW(),
+            is_triage = FALSE,
+            title = $2,
+            severity = $3,
+            severity_code = $4,
+            affected_service = $5,
+            commander_id = $6,
+            slack_channel_id = $7,
+            custom_fields = $8,
+            number_prefix = $9,
+            number = $10,
+            template_id = $11,
+            updated_at = NOW()
+        WHERE id = $1
+        RETURNING *
+        "#,
+    )
+    .bind(incident_id)
+    .bind(title)
+    .bind(level.tier.as_db_str())
+    .bind(&level.code)
+    .bind(affected_service)
+    .bind(commander_id)
+    .bind(channel_id)
+    .bind(serde_json::json!(custom_fields))
+    .bind(number_prefix)
+    .bind(number)
+    .bind(template_id)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(Some(incident))
+}
+
+/// Triages neither declared nor dismissed yet, oldest first.
+pub async fn list_open_triages(pool: &PgPool) -> IncidentResult<Vec<Incident>> {
+    let incidents = sqlx::query_as::query_as::<_, Incident>(
+        r#"
+        SELECT * FROM incidents
+        WHERE status = 'triaging'
+        ORDER BY triaged_at
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(incidents)
+}
+
+/// Resolved incidents whose channel is due for archiving: resolved at or
+/// before `resolved_before` and not archived yet. Canceled incidents are
+/// archived straight away; they're listed here only once that failed. Channels that existed
+/// before the incident (`/incident attach`) or that went on to host another
+/// incident are left alone.
+pub async fn channels_to_archive(
+    pool: &PgPool,
+    resolved_before: DateTime<Utc>,
+) -> IncidentResult<Vec<Incident>> {
+    let incidents = sqlx::query_as::query_as::<_, Incident>(
+        r#"
+        SELECT i.* FROM incidents i
+        WHERE i.status IN ('resolved', 'canceled')
+          AND i.slack_channel_id IS NOT NULL
+          AND i.channel_archived_at IS NULL
+          AND COALESCE(i.resolved_at, i.canceled_at) <= $1
+          AND NOT EXISTS (
+              SELECT 1 FROM audit_log a
+              WHERE a.incident_id = i.id
+                AND a.action = 'incident_declared'
+                AND (a.details->>'attached')::BOOLEAN
+          )
+          AND NOT EXISTS (
+              SELECT 1 FROM incidents later
+              WHERE later.slack_channel_id = i.slack_channel_id
+                AND later.id <> i.id
+                AND later.declared_at > i.declared_at
+          )
+        ORDER BY COALESCE(i.resolved_at, i.canceled_at) ASC
+        "#,
+    )
+    .bind(resolved_before)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(incidents)
+}
+
+/// Whether archiving may touch an incident's channel: the bot created it for
+/// this incident, and no later incident has moved in. The same rules as
+/// `channels_to_archive`, for a canceled incident archived straight away.
+pub async fn channel_archivable(pool: &PgPool, incident_id: IncidentId) -> IncidentResult<bool> {
+    let archivable = sqlx::query_scalar::query_scalar::<_, bool>(
+        r#"
+        SELECT NOT EXISTS (
+                   SELECT 1 FROM audit_log a
+                   WHERE a.incident_id = i.id
+                     AND a.action = 'incident_declared'
+                     AND (a.details->>'attached')::BOOLEAN
+               )
+           AND NOT EXISTS (
+                   SELECT 1 FROM incidents later
+                   WHERE later.slack_channel_id = i.slack_channel_id
+                     AND later.id <> i.id
+                     AND later.declared_at > i.declared_at
+               )
+        FROM incidents i
+        WHERE i.id = $1 AND i.slack_channel_id IS NOT NULL
+        "#,
+    )
+    .bind(incident_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(archivable.unwrap_or(false))
+}
+
+/// Claim an incident's channel for archiving. Returns `false` if it was
+/// already claimed or the incident was reopened meanwhile.
+pub async fn mark_channel_archived(
+    pool: &PgPool,
+    incident_id: IncidentId,
+    now: DateTime<Utc>,
+) -> IncidentResult<bool> {
+    let result = sqlx::query::query(
+        r#"
+        UPDATE incidents
+        SET channel_archived_at = $2
+        WHERE id = $1 AND status IN ('resolved', 'canceled') AND channel_archived_at IS NULL
+        "#,
+    )
+    .bind(incident_id)
+    .bind(now)
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() == 1)
+}
+
+/// Release a claim whose archive failed, so the next pass retries it.
+pub async fn clear_channel_archived(pool: &PgPool, incident_id: IncidentId) -> IncidentResult<()> {
+    sqlx::query::query(
+        r#"
+        UPDATE incidents
+        SET channel_archived_at = NULL
+        WHERE id = $1
+        "#,
+    )
+    .bind(incident_id)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
 pub async fn list_channels_by_prefix(pool: &PgPool, prefix: &str) -> IncidentResult<Vec<String>> {
     let channels = sqlx::query_scalar::query_scalar::<_, String>(
         r#"
@@ -189,3 +683,265 @@ pub async fn list_channels_by_prefix(pool: &PgPool, prefix: &str) -> IncidentRes
 
     Ok(channels)
 }
+
+pub async fn list_incidents(
+    pool: &PgPool,
+    filter: &IncidentFilter,
+) -> IncidentResult<Vec<Incident>> {
+    let incidents = sqlx::query_as::query_as::<_, Incident>(
+        r#"
+        SELECT * FROM incidents
+        WHERE ($1::text IS NULL OR status = $1)
+          AND ($2::text IS NULL OR severity = $2)
+          AND (NOT $3 OR status NOT IN ('triaging', 'resolved', 'canceled'))
+        ORDER BY declared_at DESC
+        LIMIT $4
+        "#,
+    )
+    .bind(filter.status.map(|s| s.as_db_str()))
+    .bind(filter.severity.map(|s| s.as_db_str()))
+    .bind(filter.open_only)
+    .bind(filter.limit)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(incidents)
+}
+
+/// Incidents declared in `[from, until)`, oldest first, quiet ones included.
+pub async fn list_declared_between(
+    pool: &PgPool,
+    from: DateTime<Utc>,
+    until: DateTime<Utc>,
+) -> IncidentResult<Vec<Incident>> {
+    let incidents = sqlx::query_as::query_as::<_, Incident>(
+        r#"
+        SELECT * FROM incidents
+        WHERE declared_at >= $1 AND declared_at < $2
+        ORDER BY declared_at, id
+        "#,
+    )
+    .bind(from)
+    .bind(until)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(incidents)
+}
+
+/// Incidents matching `search`, newest first, skipping `offset` rows.
+pub async fn search_incidents(
+    pool: &PgPool,
+    search: &IncidentSearch,
+    limit: i64,
+    offset: i64,
+) -> IncidentResult<Vec<Incident>> {
+    let pattern = search
+        .text
+        .as_deref()
+        .map(|text| format!("%{}%", escape_like(text)));
+
+    let incidents = sqlx::query_as::query_as::<_, Incident>(
+        r#"
+        SELECT * FROM incidents i
+        WHERE ($1::text IS NULL OR lower(i.affected_service) = lower($1))
+          AND ($2::text IS NULL OR i.severity = $2)
+          AND ($3::text IS NULL OR i.status = $3)
+          AND ($4::date IS NULL OR i.declared_at >= $4::timestamp AT TIME ZONE 'UTC')
+          AND ($5::text IS NULL
+               OR i.title ILIKE $5
+               OR EXISTS (
+                   SELECT 1 FROM incident_timeline t
+                   WHERE t.incident_id = i.id AND t.message ILIKE $5
+                     AND t.deleted_at IS NULL
+               )
+               OR (i.number_prefix = $8 AND i.number = $9))
+        ORDER BY i.declared_at DESC, i.id
+        LIMIT $6 OFFSET $7
+        "#,
+    )
+    .bind(search.service.as_deref())
+    .bind(search.severity.map(|s| s.as_db_str()))
+    .bind(search.status.map(|s| s.as_db_str()))
+    .bind(search.declared_after)
+    .bind(pattern)
+    .bind(limit)
+    .bind(offset)
+    .bind(search.number.as_ref().map(|(prefix, _)| prefix))
+    .bind(search.number.as_ref().map(|(_, number)| number))
+    .fetch_all(pool)
+    .await?;
+
+    Ok(incidents)
+}
+
+/// Escape `LIKE` wildcards so user text matches literally.
+fn escape_like(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+/// Unresolved incident counts per severity (severities with no open incidents are omitted).
+/// Triages don't count until they're declared, here or below.
+pub async fn count_open_by_severity(pool: &PgPool) -> IncidentResult<Vec<(Severity, i64)>> {
+    let rows = sqlx::query_as::query_as::<_, (String, i64)>(
+        r#"
+        SELECT severity, COUNT(*) FROM incidents
+        WHERE status NOT IN ('triaging', 'resolved', 'canceled')
+        GROUP BY severity
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|(severity, count)| {
+            Severity::from_db_str(&severity)
+                .ok()
+                .map(|severity| (severity, count))
+        })
+        .collect())
+}
+
+/// Like `count_open_by_severity`, leaving out quiet incidents, for counts
+/// shown in public channels.
+pub async fn count_open_announced_by_severity(
+    pool: &PgPool,
+) -> IncidentResult<Vec<(Severity, i64)>> {
+    let rows = sqlx::query_as::query_as::<_, (String, i64)>(
+        r#"
+        SELECT severity, COUNT(*) FROM incidents
+        WHERE status NOT IN ('triaging', 'resolved', 'canceled') AND NOT is_quiet
+        GROUP BY severity
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|(severity, count)| {
+            Severity::from_db_str(&severity)
+                .ok()
+                .map(|severity| (severity, count))
+        })
+        .collect())
+}
+
+/// Every unresolved incident, most severe first.
+pub async fn list_open_incidents(pool: &PgPool) -> IncidentResult<Vec<Incident>> {
+    let incidents = sqlx::query_as::query_as::<_, Incident>(
+        r#"
+        SELECT * FROM incidents
+        WHERE status NOT IN ('triaging', 'resolved', 'canceled')
+        ORDER BY severity, declared_at DESC
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(incidents)
+}
+
+/// Unresolved incidents shared with customers: those with a public status
+/// update, except quiet ones. Oldest first.
+pub async fn list_public_incidents(pool: &PgPool) -> IncidentResult<Vec<Incident>> {
+    let incidents = sqlx::query_as::query_as::<_, Incident>(
+        r#"
+        SELECT * FROM incidents i
+        WHERE status NOT IN ('triaging', 'resolved', 'canceled')
+          AND NOT is_quiet
+          AND EXISTS (
+              SELECT 1 FROM incident_timeline t
+              WHERE t.incident_id = i.id
+                AND t.audience = 'public'
+                AND t.deleted_at IS NULL
+          )
+        ORDER BY declared_at
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(incidents)
+}
+
+/// Unresolved incidents the user is involved in: as commander, as a role
+/// holder or workstream lead, or by having posted to the timeline.
+pub async fn list_open_for_user(pool: &PgPool, user_id: &str) -> IncidentResult<Vec<Incident>> {
+    let incidents = sqlx::query_as::query_as::<_, Incident>(
+        r#"
+        SELECT * FROM incidents i
+        WHERE i.status NOT IN ('triaging', 'resolved', 'canceled')
+          AND (
+            i.commander_id = $1
+            OR EXISTS (SELECT 1 FROM incident_roles r WHERE r.incident_id = i.id AND r.user_id = $1)
+            OR EXISTS (SELECT 1 FROM incident_workstreams w WHERE w.incident_id = i.id AND w.lead_id = $1)
+            OR EXISTS (SELECT 1 FROM incident_timeline t WHERE t.incident_id = i.id AND t.posted_by = $1)
+          )
+        ORDER BY i.severity, i.declared_at DESC
+        "#,
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(incidents)
+}
+
+/// Open incidents with no timeline event (and no stale reminder) for longer
+/// than their severity's threshold, with the time of their last timeline
+/// activity. `thresholds` maps severity (`"P1"`) to minutes; severities
+/// without an entry are never returned.
+pub async fn stale_incidents(
+    pool: &PgPool,
+    thresholds: &serde_json::Value,
+    now: DateTime<Utc>,
+) -> IncidentResult<Vec<(IncidentId, DateTime<Utc>)>> {
+    let rows = sqlx::query_as::query_as::<_, (IncidentId, DateTime<Utc>)>(
+        r#"
+        SELECT i.id, a.last_activity
+        FROM incidents i
+        CROSS JOIN LATERAL (
+            SELECT GREATEST(i.declared_at, MAX(t.timestamp)) AS last_activity
+            FROM incident_timeline t
+            WHERE t.incident_id = i.id
+        ) a
+        LEFT JOIN stale_reminders r ON r.incident_id = i.id
+        WHERE i.status NOT IN ('triaging', 'resolved', 'canceled')
+          AND ($1::jsonb ->> i.severity) IS NOT NULL
+          AND GREATEST(a.last_activity, r.reminded_at)
+              <= $2 - make_interval(mins => ($1::jsonb ->> i.severity)::int)
+        ORDER BY a.last_activity ASC
+        "#,
+    )
+    .bind(thresholds)
+    .bind(now)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows)
+}
+
+/// Record that the commander was nudged, restarting the stale clock.
+pub async fn mark_stale_reminded(
+    pool: &PgPool,
+    incident_id: IncidentId,
+    now: DateTime<Utc>,
+) -> IncidentResult<()> {
+    sqlx::query::query(
+        r#"
+        INSERT INTO stale_reminders (incident_id, reminded_at)
+        VALUES ($1, $2)
+        ON CONFLICT (incident_id) DO UPDATE SET reminded_at = EXCLUDED.reminded_at
+        "#,
+    )
+    .bind(incident_id)
+    .bind(now)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use crate::db::models::{IncidentId, IncidentLink, LinkKind};
+use crate::error::IncidentResult;
+use sqlx_postgres::PgPool;
+
+/// Link a PR, commit or deploy to an incident. Returns `None` if it was
+/// already linked (for deploys: that SHA to that environment).
+#[allow(clippy::too_many_arguments)]
+pub async fn add_link(
+    pool: &PgPool,
+    incident_id: IncidentId,
+    provider: &str,
+    kind: LinkKind,
+    repo: &str,
+    reference: &str,
+    url: &str,
+    environment: &str,
+    linked_by: &str,
+) -> IncidentResult<Option<IncidentLink>> {
+    let link = sqlx::query_as::query_as::<_, IncidentLink>(
+        r#"
+        INSERT INTO incident_links
+            (incident_id, provider, kind, repo, reference, url, environment, linked_by)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        ON CONFLICT (incident_id, kind, repo, reference, environment) DO NOTHING
+        RETURNING *
+        "#,
+    )
+    .bind(incident_id)
+    .bind(provider)
+    .bind(kind.as_db_str())
+    .bind(repo)
+    .bind(reference)
+    .bind(url)
+    .bind(environment)
+    .bind(linked_by)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(link)
+}
+
+pub async fn list_links(
+    pool: &PgPool,
+    incident_id: IncidentId,
+) -> IncidentResult<Vec<IncidentLink>> {
+    let links = sqlx::query_as::query_as::<_, IncidentLink>(
+        r#"
+        SELECT * FROM incident_links
+        WHERE incident_id = $1
+        ORDER BY created_at ASC
+        "#,
+    )
+    .bind(incident_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(links)
+}
@@ -0,0 +1,97 @@
+use crate::db::models::IncidentId;
+use crate::error::IncidentResult;
+use chrono::{DateTime, Utc};
+use sqlx_postgres::PgPool;
+
+/// A stretch of time someone held a role on an incident.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoleSpell {
+    pub incident_id: IncidentId,
+    pub user_id: String,
+    /// `commander` or a claimed role key (`comms_lead`, `scribe`, ...)
+    pub role: String,
+    pub started_at: DateTime<Utc>,
+    /// `None` while the incident is still open and the role still held
+    pub ended_at: Option<DateTime<Utc>>,
+}
+
+/// Role spells overlapping `[since, until)`, optionally for one user, ordered
+/// by user and start.
+///
+/// Commander spells come from the `incidents` rows in `incident_changes`: a
+/// spell starts whenever `commander_id` changes and ends at the next handoff
+/// or at resolution. Claimed roles are held from `claimed_at` to resolution.
+pub async fn role_spells(
+    pool: &PgPool,
+    user_id: Option<&str>,
+    since: DateTime<Utc>,
+    until: DateTime<Utc>,
+) -> IncidentResult<Vec<RoleSpell>> {
+    let rows = sqlx::query_as::query_as::<
+        _,
+        (
+            IncidentId,
+            String,
+            String,
+            DateTime<Utc>,
+            Option<DateTime<Utc>>,
+        ),
+    >(
+        r#"
+        WITH commander_changes AS (
+            SELECT
+                c.incident_id,
+                c.row_data->>'commander_id' AS user_id,
+                c.changed_at,
+                LAG(c.row_data->>'commander_id')
+                    OVER (PARTITION BY c.incident_id ORDER BY c.seq) AS previous_id
+            FROM incident_changes c
+            WHERE c.table_name = 'incidents' AND c.operation <> 'DELETE'
+        ),
+        handoffs AS (
+            SELECT
+                incident_id,
+                user_id,
+                changed_at AS started_at,
+                LEAD(changed_at) OVER (PARTITION BY incident_id ORDER BY changed_at) AS next_at
+            FROM commander_changes
+            WHERE previous_id IS DISTINCT FROM user_id
+        ),
+        spells AS (
+            SELECT h.incident_id, h.user_id, 'commander' AS role, h.started_at,
+                   LEAST(h.next_at, COALESCE(i.resolved_at, i.canceled_at)) AS ended_at
+            FROM handoffs h
+            JOIN incidents i ON i.id = h.incident_id AND NOT i.is_triage
+            UNION ALL
+            SELECT r.incident_id, r.user_id, r.role, r.claimed_at,
+                   COALESCE(i.resolved_at, i.canceled_at)
+            FROM incident_roles r
+            JOIN incidents i ON i.id = r.incident_id AND NOT i.is_triage
+        )
+        SELECT incident_id, user_id, role, started_at, ended_at
+        FROM spells
+        WHERE ($1::TEXT IS NULL OR user_id = $1)
+          AND started_at < $3
+          AND (ended_at IS NULL OR (ended_at > $2 AND ended_at > started_at))
+        ORDER BY user_id, started_at
+        "#,
+    )
+    .bind(user_id)
+    .bind(since)
+    .bind(until)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(
+            |(incident_id, user_id, role, started_at, ended_at)| RoleSpell {
+                incident_id,
+                user_id,
+                role,
+                started_at,
+                ended_at,
+            },
+        )
+        .collect())
+}
@@ -0,0 +1,234 @@
+use crate::db::models::Severity;
+use crate::error::{IncidentError, IncidentResult};
+use chrono::{DateTime, Utc};
+use sqlx_postgres::PgPool;
+
+/// What a `MetricsRow` is grouped by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricsGrouping {
+    /// Everything in the window; the key is `all`
+    Overall,
+    Severity,
+    Service,
+    /// Calendar month (UTC) of declaration; the key is `YYYY-MM`
+    Month,
+}
+
+/// Aggregates for one group of incidents declared in the window.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricsRow {
+    pub grouping: MetricsGrouping,
+    pub key: String,
+    pub declared: i64,
+    pub resolved: i64,
+    /// Mean `duration_minutes` of the resolved incidents (MTTR)
+    pub mean_minutes: Option<f64>,
+    pub median_minutes: Option<f64>,
+    /// Mean minutes from declaration to the first status update (MTTA)
+    pub mean_ack_minutes: Option<f64>,
+    /// Title and duration of the longest resolved incident. Quiet incidents
+    /// are left out so their titles never reach a report.
+    pub longest: Option<(String, i32)>,
+}
+
+/// Incidents declared since `since` (not triages never declared), limited
+/// to `services` when given: one overall row, then one row per severity,
+/// service and month, each ordered by key.
+pub async fn incident_metrics(
+    pool: &PgPool,
+    since: DateTime<Utc>,
+    services: Option<&[String]>,
+) -> IncidentResult<Vec<MetricsRow>> {
+    incident_metrics_between(pool, since, None, services).await
+}
+
+/// `incident_metrics` for incidents declared before `until` too, when given.
+pub async fn incident_metrics_between(
+    pool: &PgPool,
+    since: DateTime<Utc>,
+    until: Option<DateTime<Utc>>,
+    services: Option<&[String]>,
+) -> IncidentResult<Vec<MetricsRow>> {
+    let rows = sqlx::query_as::query_as::<
+        _,
+        (
+            String,
+            String,
+            i64,
+            i64,
+            Option<f64>,
+            Option<f64>,
+            Option<f64>,
+            Option<String>,
+            Option<i32>,
+        ),
+    >(
+        r#"
+        WITH scoped AS (
+            SELECT
+                i.*,
+                to_char(i.declared_at AT TIME ZONE 'UTC', 'YYYY-MM') AS month,
+                (
+                    SELECT MIN(t.timestamp) FROM incident_timeline t
+                    WHERE t.incident_id = i.id AND t.event_type = 'status_update'
+                ) AS acknowledged_at
+            FROM incidents i
+            WHERE i.declared_at >= $1
+              AND ($3::TIMESTAMPTZ IS NULL OR i.declared_at < $3)
+              AND NOT i.is_triage
+              AND ($2::TEXT[] IS NULL OR i.affected_service = ANY($2))
+        )
+        SELECT
+            CASE
+                WHEN GROUPING(severity) = 0 THEN 'severity'
+                WHEN GROUPING(affected_service) = 0 THEN 'service'
+                WHEN GROUPING(month) = 0 THEN 'month'
+                ELSE 'overall'
+            END AS grouping,
+            COALESCE(severity, affected_service, month, 'all') AS key,
+            COUNT(*),
+            COUNT(resolved_at),
+            AVG(duration_minutes)::FLOAT8,
+            (PERCENTILE_CONT(0.5) WITHIN GROUP (ORDER BY duration_minutes))::FLOAT8,
+            (AVG(EXTRACT(EPOCH FROM acknowledged_at - declared_at)) / 60)::FLOAT8,
+            (ARRAY_AGG(title ORDER BY duration_minutes DESC)
+                FILTER (WHERE duration_minutes IS NOT NULL AND NOT is_quiet))[1],
+            MAX(duration_minutes) FILTER (WHERE NOT is_quiet)
+        FROM scoped
+        GROUP BY GROUPING SETS ((), (severity), (affected_service), (month))
+        ORDER BY 1 DESC, 2
+        "#,
+    )
+    .bind(since)
+    .bind(services)
+    .bind(until)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(
+            |(grouping, key, declared, resolved, mean, median, ack, title, minutes)| MetricsRow {
+                grouping: match grouping.as_str() {
+                    "severity" => MetricsGrouping::Severity,
+                    "service" => MetricsGrouping::Service,
+                    "month" => MetricsGrouping::Month,
+                    _ => MetricsGrouping::Overall,
+                },
+                key,
+                declared,
+                resolved,
+                mean_minutes: mean,
+                median_minutes: median,
+                mean_ack_minutes: ack,
+                longest: title.zip(minutes),
+            },
+        )
+        .collect())
+}
+
+/// Severity and `duration_minutes` of every incident declared since `since`
+/// and resolved, limited to `services` when given.
+pub async fn resolved_durations(
+    pool: &PgPool,
+    since: DateTime<Utc>,
+    services: Option<&[String]>,
+) -> IncidentResult<Vec<(Severity, i32)>> {
+    let rows = sqlx::query_as::query_as::<_, (String, i32)>(
+        r#"
+        SELECT severity, duration_minutes FROM incidents
+        WHERE declared_at >= $1
+          AND duration_minutes IS NOT NULL
+          AND ($2::TEXT[] IS NULL OR affected_service = ANY($2))
+        ORDER BY severity, duration_minutes
+        "#,
+    )
+    .bind(since)
+    .bind(services)
+    .fetch_all(pool)
+    .await?;
+
+    rows.into_iter()
+        .map(|(severity, minutes)| {
+            let severity = severity
+                .parse::<Severity>()
+                .map_err(IncidentError::InternalError)?;
+            Ok((severity, minutes))
+        })
+        .collect()
+}
+
+/// Minutes incidents were open within `[since, until)`, summed over
+/// incidents (overlapping incidents both count). Triages and canceled
+/// incidents are left out.
+pub async fn downtime_minutes(
+    pool: &PgPool,
+    since: DateTime<Utc>,
+    until: DateTime<Utc>,
+) -> IncidentResult<i64> {
+    let minutes = sqlx::query_scalar::query_scalar::<_, i64>(
+        r#"
+        SELECT COALESCE(SUM(
+            EXTRACT(EPOCH FROM LEAST(COALESCE(resolved_at, $2), $2) - GREATEST(declared_at, $1)) / 60
+        ), 0)::BIGINT
+        FROM incidents
+        WHERE declared_at < $2
+          AND (resolved_at IS NULL OR resolved_at > $1)
+          AND NOT is_triage
+          AND status <> 'canceled'
+        "#,
+    )
+    .bind(since)
+    .bind(until)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(minutes)
+}
+
+/// Someone who worked the incidents declared in a report's window.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Contributor {
+    pub user_id: String,
+    pub incidents: i64,
+    pub timeline_events: i64,
+}
+
+/// The `limit` people with the most timeline events across incidents
+/// declared in `[since, until)`, then the most incidents. Integrations
+/// (non-user participants) and quiet incidents are left out.
+pub async fn top_contributors(
+    pool: &PgPool,
+    since: DateTime<Utc>,
+    until: DateTime<Utc>,
+    limit: i64,
+) -> IncidentResult<Vec<Contributor>> {
+    let rows = sqlx::query_as::query_as::<_, (String, i64, i64)>(
+        r#"
+        SELECT p.user_id, COUNT(*), SUM(p.timeline_events)::BIGINT
+        FROM incident_participants p
+        JOIN incidents i ON i.id = p.incident_id
+        WHERE i.declared_at >= $1 AND i.declared_at < $2
+          AND NOT i.is_triage
+          AND NOT i.is_quiet
+          AND p.user_id ~ '^[UW][A-Z0-9]+$'
+        GROUP BY p.user_id
+        ORDER BY 3 DESC, 2 DESC, 1
+        LIMIT $3
+        "#,
+    )
+    .bind(since)
+    .bind(until)
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(user_id, incidents, timeline_events)| Contributor {
+            user_id,
+            incidents,
+            timeline_events,
+        })
+        .collect())
+}
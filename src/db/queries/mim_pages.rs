@@ -0,0 +1,72 @@
+use crate::db::models::MimPage;
+use crate::error::IncidentResult;
+use chrono::{DateTime, Utc};
+use sqlx_postgres::PgPool;
+use uuid::Uuid;
+
+/// Record that `user_id` was paged for the incident. Returns `None` if a MIM
+/// was already paged for it (e.g. a P1 downgraded and escalated again).
+pub async fn claim_page(
+    pool: &PgPool,
+    incident_id: Uuid,
+    user_id: &str,
+    source: &str,
+    now: DateTime<Utc>,
+) -> IncidentResult<Option<MimPage>> {
+    let page = sqlx::query_as::query_as::<_, MimPage>(
+        r#"
+        INSERT INTO mim_pages (incident_id, user_id, source, paged_at)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (incident_id) DO NOTHING
+        RETURNING *
+        "#,
+    )
+    .bind(incident_id)
+    .bind(user_id)
+    .bind(source)
+    .bind(now)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(page)
+}
+
+pub async fn get_page(pool: &PgPool, incident_id: Uuid) -> IncidentResult<Option<MimPage>> {
+    let page = sqlx::query_as::query_as::<_, MimPage>(
+        r#"
+        SELECT * FROM mim_pages WHERE incident_id = $1
+        "#,
+    )
+    .bind(incident_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(page)
+}
+
+/// Record `user_id` accepting the page as `role`. Returns `None` if they
+/// weren't the one paged or it was already accepted.
+pub async fn accept_page(
+    pool: &PgPool,
+    incident_id: Uuid,
+    user_id: &str,
+    role: &str,
+    now: DateTime<Utc>,
+) -> IncidentResult<Option<MimPage>> {
+    let page = sqlx::query_as::query_as::<_, MimPage>(
+        r#"
+        UPDATE mim_pages
+        SET accepted_at = $4, accepted_as = $3
+        WHERE incident_id = $1 AND user_id = $2 AND accepted_at IS NULL
+        RETURNING *
+        "#,
+    )
+    .bind(incident_id)
+    .bind(user_id)
+    .bind(role)
+    .bind(now)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(page)
+}
@@ -1,6 +1,34 @@
+pub mod action_items;
+pub mod analytics;
+pub mod artifacts;
 pub mod audit;
+pub mod broadcast_threads;
+pub mod coaching;
+pub mod commanders;
+pub mod deactivated_users;
+pub mod disabled_services;
+pub mod drafts;
+pub mod exec_tiers;
+pub mod failed_jobs;
 pub mod incidents;
+pub mod links;
+pub mod load;
+pub mod metrics;
+pub mod mim_pages;
 pub mod notifications;
+pub mod paging_tests;
+pub mod participants;
+pub mod partner_mirror;
+pub mod postmortems;
+pub mod premortems;
+pub mod roles;
+pub mod sla;
+pub mod slack_events;
+pub mod status_drafts;
 pub mod statuspage;
+pub mod subscriptions;
 pub mod templates;
 pub mod timeline;
+pub mod tracked_alerts;
+pub mod webhooks;
+pub mod workstreams;
@@ -1,6 +1,9 @@
 use crate::db::models::{IncidentId, NotificationRecord, NotificationStatus, NotificationType};
 use crate::error::IncidentResult;
+use chrono::{DateTime, Utc};
+use serde_json::Value;
 use sqlx_postgres::PgPool;
+use uuid::Uuid;
 
 pub async fn log_notification(
     pool: &PgPool,
@@ -9,11 +12,12 @@ pub async fn log_notification(
     recipient: String,
     status: NotificationStatus,
     error_message: Option<String>,
+    blocks: Option<Value>,
 ) -> IncidentResult<NotificationRecord> {
     let record = sqlx::query_as::query_as::<_, NotificationRecord>(
         r#"
-        INSERT INTO incident_notifications (incident_id, notification_type, recipient, status, error_message)
-        VALUES ($1, $2, $3, $4, $5)
+        INSERT INTO incident_notifications (incident_id, notification_type, recipient, status, error_message, blocks)
+        VALUES ($1, $2, $3, $4, $5, $6)
         RETURNING *
         "#,
     )
@@ -22,8 +26,244 @@ pub async fn log_notification(
     .bind(recipient)
     .bind(status.as_db_str())
     .bind(error_message)
+    .bind(blocks)
     .fetch_one(pool)
     .await?;
 
     Ok(record)
 }
+
+/// A fan-out thread reply that wasn't sent, with whether it's broadcast.
+pub async fn log_thread_reply(
+    pool: &PgPool,
+    incident_id: IncidentId,
+    channel_id: &str,
+    status: NotificationStatus,
+    error_message: Option<String>,
+    broadcast: bool,
+    blocks: Value,
+) -> IncidentResult<NotificationRecord> {
+    let record = sqlx::query_as::query_as::<_, NotificationRecord>(
+        r#"
+        INSERT INTO incident_notifications (incident_id, notification_type, recipient, status, error_message, blocks, broadcast)
+        VALUES ($1, 'slack_channel', $2, $3, $4, $5, $6)
+        RETURNING *
+        "#,
+    )
+    .bind(incident_id)
+    .bind(channel_id)
+    .bind(status.as_db_str())
+    .bind(error_message)
+    .bind(blocks)
+    .bind(broadcast)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(record)
+}
+
+/// Every notification sent for an incident, oldest first.
+pub async fn list_for_incident(
+    pool: &PgPool,
+    incident_id: IncidentId,
+) -> IncidentResult<Vec<NotificationRecord>> {
+    let records = sqlx::query_as::query_as::<_, NotificationRecord>(
+        r#"
+        SELECT * FROM incident_notifications
+        WHERE incident_id = $1
+        ORDER BY sent_at ASC
+        "#,
+    )
+    .bind(incident_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(records)
+}
+
+/// Failed, throttled and deferred notifications since `since`, newest first.
+pub async fn list_unsent(
+    pool: &PgPool,
+    since: DateTime<Utc>,
+    limit: i64,
+) -> IncidentResult<Vec<NotificationRecord>> {
+    let records = sqlx::query_as::query_as::<_, NotificationRecord>(
+        r#"
+        SELECT * FROM incident_notifications
+        WHERE status IN ('failed', 'pending', 'throttled') AND sent_at >= $1
+        ORDER BY sent_at DESC
+        LIMIT $2
+        "#,
+    )
+    .bind(since)
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(records)
+}
+
+/// Unsent notifications whose ID starts with `prefix` (as shown by
+/// `/incident notifications`).
+pub async fn find_unsent_by_prefix(
+    pool: &PgPool,
+    prefix: &str,
+) -> IncidentResult<Vec<NotificationRecord>> {
+    let records = sqlx::query_as::query_as::<_, NotificationRecord>(
+        r#"
+        SELECT * FROM incident_notifications
+        WHERE status IN ('failed', 'pending', 'throttled') AND starts_with(id::text, $1)
+        ORDER BY sent_at DESC
+        LIMIT 2
+        "#,
+    )
+    .bind(prefix.to_ascii_lowercase())
+    .fetch_all(pool)
+    .await?;
+
+    Ok(records)
+}
+
+pub async fn get_notification(pool: &PgPool, id: Uuid) -> IncidentResult<NotificationRecord> {
+    let record = sqlx::query_as::query_as::<_, NotificationRecord>(
+        "SELECT * FROM incident_notifications WHERE id = $1",
+    )
+    .bind(id)
+    .fetch_optional(pool)
+    .await?
+    .ok_or(crate::error::IncidentError::NotFound)?;
+
+    Ok(record)
+}
+
+/// A retry went out. `None` if someone else already retried or skipped it.
+pub async fn mark_resent(
+    pool: &PgPool,
+    id: Uuid,
+    actor_id: &str,
+    now: DateTime<Utc>,
+) -> IncidentResult<Option<NotificationRecord>> {
+    let record = sqlx::query_as::query_as::<_, NotificationRecord>(
+        r#"
+        UPDATE incident_notifications
+        SET status = 'sent', sent_at = $3, attempts = attempts + 1,
+            error_message = NULL, resolved_by = $2
+        WHERE id = $1 AND status IN ('failed', 'pending', 'throttled')
+        RETURNING *
+        "#,
+    )
+    .bind(id)
+    .bind(actor_id)
+    .bind(now)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(record)
+}
+
+/// A retry failed too; the notification stays failed with the new error.
+pub async fn record_retry_failure(
+    pool: &PgPool,
+    id: Uuid,
+    error_message: &str,
+) -> IncidentResult<Option<NotificationRecord>> {
+    let record = sqlx::query_as::query_as::<_, NotificationRecord>(
+        r#"
+        UPDATE incident_notifications
+        SET status = 'failed', attempts = attempts + 1, error_message = $2
+        WHERE id = $1 AND status IN ('failed', 'pending', 'throttled')
+        RETURNING *
+        "#,
+    )
+    .bind(id)
+    .bind(error_message)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(record)
+}
+
+/// Give up on an unsent notification. `None` if it was already handled.
+pub async fn mark_skipped(
+    pool: &PgPool,
+    id: Uuid,
+    actor_id: &str,
+) -> IncidentResult<Option<NotificationRecord>> {
+    let record = sqlx::query_as::query_as::<_, NotificationRecord>(
+        r#"
+        UPDATE incident_notifications
+        SET status = 'skipped', resolved_by = $2
+        WHERE id = $1 AND status IN ('failed', 'pending', 'throttled')
+        RETURNING *
+        "#,
+    )
+    .bind(id)
+    .bind(actor_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(record)
+}
+
+/// Fan-out thread replies held back while Slack was rate limiting the bot,
+/// for one incident's thread in `channel_id`, oldest first.
+pub async fn list_deferred(
+    pool: &PgPool,
+    incident_id: IncidentId,
+    channel_id: &str,
+) -> IncidentResult<Vec<NotificationRecord>> {
+    let records = sqlx::query_as::query_as::<_, NotificationRecord>(
+        r#"
+        SELECT * FROM incident_notifications
+        WHERE incident_id = $1 AND recipient = $2
+          AND notification_type = 'slack_channel' AND status = 'pending'
+        ORDER BY sent_at ASC
+        "#,
+    )
+    .bind(incident_id)
+    .bind(channel_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(records)
+}
+
+/// `(incident_id, channel_id, thread_ts)` of every fan-out thread with
+/// deferred replies, longest waiting first.
+pub async fn list_deferred_threads(
+    pool: &PgPool,
+) -> IncidentResult<Vec<(IncidentId, String, String)>> {
+    let threads = sqlx::query_as::query_as::<_, (IncidentId, String, String)>(
+        r#"
+        SELECT n.incident_id, n.recipient, b.message_ts
+        FROM incident_notifications n
+        JOIN broadcast_threads b
+          ON b.incident_id = n.incident_id AND b.channel_id = n.recipient
+        WHERE n.notification_type = 'slack_channel' AND n.status = 'pending'
+        GROUP BY n.incident_id, n.recipient, b.message_ts
+        ORDER BY MIN(n.sent_at)
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(threads)
+}
+
+/// Deferred replies that went out batched into a later reply. Returns how
+/// many were still pending.
+pub async fn mark_batched(pool: &PgPool, ids: &[Uuid], now: DateTime<Utc>) -> IncidentResult<u64> {
+    let result = sqlx::query::query(
+        r#"
+        UPDATE incident_notifications
+        SET status = 'sent', sent_at = $2, attempts = attempts + 1
+        WHERE id = ANY($1) AND status = 'pending'
+        "#,
+    )
+    .bind(ids)
+    .bind(now)
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected())
+}
@@ -0,0 +1,122 @@
+use crate::db::models::{PagingTest, PagingTestPage};
+use crate::error::IncidentResult;
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx_postgres::PgPool;
+use uuid::Uuid;
+
+/// Claim the paging test for `month`. Returns `None` if it already ran.
+pub async fn claim_paging_test(
+    pool: &PgPool,
+    month: NaiveDate,
+    now: DateTime<Utc>,
+) -> IncidentResult<Option<PagingTest>> {
+    let test = sqlx::query_as::query_as::<_, PagingTest>(
+        r#"
+        INSERT INTO paging_tests (month, started_at)
+        VALUES ($1, $2)
+        ON CONFLICT (month) DO NOTHING
+        RETURNING *
+        "#,
+    )
+    .bind(month)
+    .bind(now)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(test)
+}
+
+pub async fn record_page(
+    pool: &PgPool,
+    test_id: Uuid,
+    recipient_id: &str,
+    source: &str,
+    sent_at: DateTime<Utc>,
+    delivery_error: Option<&str>,
+) -> IncidentResult<()> {
+    sqlx::query::query(
+        r#"
+        INSERT INTO paging_test_pages (test_id, recipient_id, source, sent_at, delivery_error)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (test_id, recipient_id) DO NOTHING
+        "#,
+    )
+    .bind(test_id)
+    .bind(recipient_id)
+    .bind(source)
+    .bind(sent_at)
+    .bind(delivery_error)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Record `user_id`'s acknowledgement. Returns `None` if they weren't paged
+/// by this test or already acknowledged it.
+pub async fn acknowledge_page(
+    pool: &PgPool,
+    test_id: Uuid,
+    user_id: &str,
+    now: DateTime<Utc>,
+) -> IncidentResult<Option<PagingTestPage>> {
+    let page = sqlx::query_as::query_as::<_, PagingTestPage>(
+        r#"
+        UPDATE paging_test_pages
+        SET acknowledged_at = $3
+        WHERE test_id = $1 AND recipient_id = $2
+          AND delivery_error IS NULL AND acknowledged_at IS NULL
+        RETURNING *
+        "#,
+    )
+    .bind(test_id)
+    .bind(user_id)
+    .bind(now)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(page)
+}
+
+/// Claim every unreported test started at or before `started_before` for
+/// reporting, oldest first.
+pub async fn claim_tests_to_report(
+    pool: &PgPool,
+    started_before: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> IncidentResult<Vec<PagingTest>> {
+    let tests = sqlx::query_as::query_as::<_, PagingTest>(
+        r#"
+        UPDATE paging_tests
+        SET reported_at = $2
+        WHERE reported_at IS NULL AND started_at <= $1
+        RETURNING *
+        "#,
+    )
+    .bind(started_before)
+    .bind(now)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(tests)
+}
+
+/// A test's pages: failed deliveries, then unacknowledged, then slowest
+/// acknowledgement first.
+pub async fn pages_for_test(pool: &PgPool, test_id: Uuid) -> IncidentResult<Vec<PagingTestPage>> {
+    let pages = sqlx::query_as::query_as::<_, PagingTestPage>(
+        r#"
+        SELECT * FROM paging_test_pages
+        WHERE test_id = $1
+        ORDER BY delivery_error IS NULL,
+                 acknowledged_at IS NOT NULL,
+                 acknowledged_at - sent_at DESC,
+                 recipient_id
+        "#,
+    )
+    .bind(test_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(pages)
+}
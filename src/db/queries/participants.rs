@@ -0,0 +1,94 @@
+use crate::db::models::{IncidentId, Participant};
+use crate::error::IncidentResult;
+use chrono::{DateTime, Utc};
+use sqlx_postgres::PgPool;
+
+/// Record `user_id` joining `channel_id` if it belongs to an open incident.
+/// Returns whether an incident matched.
+pub async fn record_join(
+    pool: &PgPool,
+    channel_id: &str,
+    user_id: &str,
+    now: DateTime<Utc>,
+) -> IncidentResult<bool> {
+    let result = sqlx::query::query(
+        r#"
+        INSERT INTO incident_participants (incident_id, user_id, joined_at, first_seen_at, last_seen_at)
+        SELECT id, $2, $3, $3, $3
+        FROM incidents
+        WHERE slack_channel_id = $1 AND status NOT IN ('resolved', 'canceled')
+        ON CONFLICT (incident_id, user_id) DO UPDATE
+        SET joined_at = COALESCE(incident_participants.joined_at, EXCLUDED.joined_at),
+            last_seen_at = GREATEST(incident_participants.last_seen_at, EXCLUDED.last_seen_at)
+        "#,
+    )
+    .bind(channel_id)
+    .bind(user_id)
+    .bind(now)
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Add `events` timeline events posted by `user_id` between `first` and `last`.
+pub async fn record_timeline_events(
+    pool: &PgPool,
+    incident_id: IncidentId,
+    user_id: &str,
+    events: i32,
+    first: DateTime<Utc>,
+    last: DateTime<Utc>,
+) -> IncidentResult<()> {
+    sqlx::query::query(
+        r#"
+        INSERT INTO incident_participants (incident_id, user_id, timeline_events, first_seen_at, last_seen_at)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (incident_id, user_id) DO UPDATE
+        SET timeline_events = incident_participants.timeline_events + EXCLUDED.timeline_events,
+            first_seen_at = LEAST(incident_participants.first_seen_at, EXCLUDED.first_seen_at),
+            last_seen_at = GREATEST(incident_participants.last_seen_at, EXCLUDED.last_seen_at)
+        "#,
+    )
+    .bind(incident_id)
+    .bind(user_id)
+    .bind(events)
+    .bind(first)
+    .bind(last)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn list_participants(
+    pool: &PgPool,
+    incident_id: IncidentId,
+) -> IncidentResult<Vec<Participant>> {
+    let participants = sqlx::query_as::query_as::<_, Participant>(
+        r#"
+        SELECT * FROM incident_participants
+        WHERE incident_id = $1
+        ORDER BY first_seen_at ASC, user_id ASC
+        "#,
+    )
+    .bind(incident_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(participants)
+}
+
+pub async fn count_participants(pool: &PgPool, incident_id: IncidentId) -> IncidentResult<i64> {
+    let count = sqlx::query_scalar::query_scalar::<_, i64>(
+        r#"
+        SELECT COUNT(*) FROM incident_participants
+        WHERE incident_id = $1
+        "#,
+    )
+    .bind(incident_id)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(count)
+}
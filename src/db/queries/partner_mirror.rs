@@ -0,0 +1,77 @@
+use crate::db::models::TimelineEvent;
+use crate::error::IncidentResult;
+use chrono::{DateTime, Utc};
+use sqlx_postgres::PgPool;
+use uuid::Uuid;
+
+/// Timeline events on `services`' incidents logged between `since` and
+/// `due_before` that haven't been mirrored yet, oldest first. Quiet
+/// incidents and notes never leave the workspace.
+pub async fn pending_partner_updates(
+    pool: &PgPool,
+    services: &[String],
+    since: DateTime<Utc>,
+    due_before: DateTime<Utc>,
+) -> IncidentResult<Vec<TimelineEvent>> {
+    let events = sqlx::query_as::query_as::<_, TimelineEvent>(
+        r#"
+        SELECT t.* FROM incident_timeline t
+        JOIN incidents i ON i.id = t.incident_id
+        WHERE i.affected_service = ANY($1)
+          AND NOT i.is_quiet
+          AND t.event_type IN ('declared', 'status_update', 'severity_change', 'resolved', 'reopened', 'canceled')
+          AND t.deleted_at IS NULL
+          AND t.timestamp > $2
+          AND t.timestamp <= $3
+          AND NOT EXISTS (
+              SELECT 1 FROM partner_mirror_posts p WHERE p.timeline_event_id = t.id
+          )
+        ORDER BY t.timestamp ASC
+        "#,
+    )
+    .bind(services)
+    .bind(since)
+    .bind(due_before)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(events)
+}
+
+/// Claim an event for mirroring. Returns false if another pass already did.
+pub async fn claim_partner_update(
+    pool: &PgPool,
+    timeline_event_id: Uuid,
+    channel_id: &str,
+    now: DateTime<Utc>,
+) -> IncidentResult<bool> {
+    let result = sqlx::query::query(
+        r#"
+        INSERT INTO partner_mirror_posts (timeline_event_id, channel_id, posted_at)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (timeline_event_id) DO NOTHING
+        "#,
+    )
+    .bind(timeline_event_id)
+    .bind(channel_id)
+    .bind(now)
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() == 1)
+}
+
+/// Release a claim whose post failed, so the next pass retries it.
+pub async fn release_partner_update(pool: &PgPool, timeline_event_id: Uuid) -> IncidentResult<()> {
+    sqlx::query::query(
+        r#"
+        DELETE FROM partner_mirror_posts
+        WHERE timeline_event_id = $1
+        "#,
+    )
+    .bind(timeline_event_id)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,182 @@
+use crate::db::models::{IncidentId, PendingPostmortem, Postmortem};
+use crate::error::{IncidentError, IncidentResult};
+use chrono::{DateTime, Utc};
+use sqlx_postgres::PgPool;
+
+pub async fn get_postmortem(
+    pool: &PgPool,
+    incident_id: IncidentId,
+) -> IncidentResult<Option<Postmortem>> {
+    let postmortem = sqlx::query_as::query_as::<_, Postmortem>(
+        r#"
+        SELECT * FROM postmortems
+        WHERE incident_id = $1
+        "#,
+    )
+    .bind(incident_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(postmortem)
+}
+
+/// Record a published page. If another publish won the race, its row is
+/// kept and returned.
+pub async fn record_postmortem(
+    pool: &PgPool,
+    incident_id: IncidentId,
+    confluence_page_id: &str,
+    url: &str,
+    published_by: &str,
+) -> IncidentResult<Postmortem> {
+    let inserted = sqlx::query_as::query_as::<_, Postmortem>(
+        r#"
+        INSERT INTO postmortems (incident_id, confluence_page_id, url, published_by)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (incident_id) DO NOTHING
+        RETURNING *
+        "#,
+    )
+    .bind(incident_id)
+    .bind(confluence_page_id)
+    .bind(url)
+    .bind(published_by)
+    .fetch_optional(pool)
+    .await?;
+
+    match inserted {
+        Some(postmortem) => Ok(postmortem),
+        None => get_postmortem(pool, incident_id)
+            .await?
+            .ok_or(IncidentError::NotFound),
+    }
+}
+
+/// Require a postmortem for `incident_id` by `due_at`. An existing
+/// requirement keeps its original due date. Returns whether one was added.
+pub async fn require_postmortem(
+    pool: &PgPool,
+    incident_id: IncidentId,
+    due_at: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> IncidentResult<bool> {
+    let result = sqlx::query::query(
+        r#"
+        INSERT INTO postmortem_requirements (incident_id, due_at, required_at)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (incident_id) DO NOTHING
+        "#,
+    )
+    .bind(incident_id)
+    .bind(due_at)
+    .bind(now)
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Drop an unpublished postmortem requirement (the incident was reopened);
+/// resolving again sets a fresh due date.
+pub async fn clear_postmortem_requirement(
+    pool: &PgPool,
+    incident_id: IncidentId,
+) -> IncidentResult<()> {
+    sqlx::query::query(
+        r#"
+        DELETE FROM postmortem_requirements r
+        WHERE r.incident_id = $1
+          AND NOT EXISTS (SELECT 1 FROM postmortems p WHERE p.incident_id = r.incident_id)
+        "#,
+    )
+    .bind(incident_id)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn postmortem_due_date(
+    pool: &PgPool,
+    incident_id: IncidentId,
+) -> IncidentResult<Option<DateTime<Utc>>> {
+    let due_at = sqlx::query_scalar::query_scalar::<_, DateTime<Utc>>(
+        r#"
+        SELECT due_at FROM postmortem_requirements
+        WHERE incident_id = $1
+        "#,
+    )
+    .bind(incident_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(due_at)
+}
+
+/// Required postmortems not yet published whose commander hasn't been
+/// reminded (or required) since `remind_before`, soonest due first.
+pub async fn postmortems_to_remind(
+    pool: &PgPool,
+    remind_before: DateTime<Utc>,
+) -> IncidentResult<Vec<PendingPostmortem>> {
+    let pending = sqlx::query_as::query_as::<_, PendingPostmortem>(
+        r#"
+        SELECT i.*, r.due_at
+        FROM postmortem_requirements r
+        JOIN incidents i ON i.id = r.incident_id
+        WHERE NOT EXISTS (SELECT 1 FROM postmortems p WHERE p.incident_id = r.incident_id)
+          AND COALESCE(r.reminded_at, r.required_at) <= $1
+        ORDER BY r.due_at ASC
+        "#,
+    )
+    .bind(remind_before)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(pending)
+}
+
+pub async fn mark_postmortem_reminded(
+    pool: &PgPool,
+    incident_id: IncidentId,
+    now: DateTime<Utc>,
+) -> IncidentResult<()> {
+    sqlx::query::query(
+        r#"
+        UPDATE postmortem_requirements
+        SET reminded_at = $2
+        WHERE incident_id = $1
+        "#,
+    )
+    .bind(incident_id)
+    .bind(now)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Required postmortems past their due date and still unpublished, most
+/// overdue first. Quiet incidents are left out since the list is posted
+/// to a shared channel.
+pub async fn overdue_postmortems(
+    pool: &PgPool,
+    now: DateTime<Utc>,
+) -> IncidentResult<Vec<PendingPostmortem>> {
+    let overdue = sqlx::query_as::query_as::<_, PendingPostmortem>(
+        r#"
+        SELECT i.*, r.due_at
+        FROM postmortem_requirements r
+        JOIN incidents i ON i.id = r.incident_id
+        WHERE NOT EXISTS (SELECT 1 FROM postmortems p WHERE p.incident_id = r.incident_id)
+          AND r.due_at < $1
+          AND NOT i.is_quiet
+        ORDER BY r.due_at ASC
+        "#,
+    )
+    .bind(now)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(overdue)
+}
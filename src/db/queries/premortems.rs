@@ -0,0 +1,151 @@
+use crate::db::models::{IncidentId, Premortem, PremortemRisk};
+use crate::error::IncidentResult;
+use chrono::{DateTime, Utc};
+use sqlx_postgres::PgPool;
+use uuid::Uuid;
+
+pub async fn create_premortem(
+    pool: &PgPool,
+    service: &str,
+    launch_name: &str,
+    window_start: DateTime<Utc>,
+    window_end: DateTime<Utc>,
+    created_by: &str,
+) -> IncidentResult<Premortem> {
+    let premortem = sqlx::query_as::query_as::<_, Premortem>(
+        r#"
+        INSERT INTO premortems (service, launch_name, window_start, window_end, created_by)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING *
+        "#,
+    )
+    .bind(service)
+    .bind(launch_name)
+    .bind(window_start)
+    .bind(window_end)
+    .bind(created_by)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(premortem)
+}
+
+pub async fn get_premortem(pool: &PgPool, id: Uuid) -> IncidentResult<Option<Premortem>> {
+    let premortem = sqlx::query_as::query_as::<_, Premortem>(
+        r#"
+        SELECT * FROM premortems WHERE id = $1
+        "#,
+    )
+    .bind(id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(premortem)
+}
+
+/// Pre-mortems whose launch window hasn't ended, soonest first.
+pub async fn list_upcoming(pool: &PgPool, now: DateTime<Utc>) -> IncidentResult<Vec<Premortem>> {
+    let premortems = sqlx::query_as::query_as::<_, Premortem>(
+        r#"
+        SELECT * FROM premortems
+        WHERE window_end > $1
+        ORDER BY window_start, created_at
+        "#,
+    )
+    .bind(now)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(premortems)
+}
+
+pub async fn add_risk(
+    pool: &PgPool,
+    premortem_id: Uuid,
+    failure_mode: &str,
+    likelihood: &str,
+    mitigation: Option<&str>,
+    added_by: &str,
+) -> IncidentResult<PremortemRisk> {
+    let risk = sqlx::query_as::query_as::<_, PremortemRisk>(
+        r#"
+        INSERT INTO premortem_risks (premortem_id, failure_mode, likelihood, mitigation, added_by)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING *
+        "#,
+    )
+    .bind(premortem_id)
+    .bind(failure_mode)
+    .bind(likelihood)
+    .bind(mitigation)
+    .bind(added_by)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(risk)
+}
+
+/// Risks in the order they were added.
+pub async fn list_risks(pool: &PgPool, premortem_id: Uuid) -> IncidentResult<Vec<PremortemRisk>> {
+    let risks = sqlx::query_as::query_as::<_, PremortemRisk>(
+        r#"
+        SELECT * FROM premortem_risks
+        WHERE premortem_id = $1
+        ORDER BY created_at, id
+        "#,
+    )
+    .bind(premortem_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(risks)
+}
+
+/// Link an incident declared at `declared_at` for `service` (ignoring case)
+/// to every pre-mortem whose launch window covers it. Returns the pre-mortems
+/// newly linked; already linked ones are skipped.
+pub async fn link_incident(
+    pool: &PgPool,
+    incident_id: IncidentId,
+    service: &str,
+    declared_at: DateTime<Utc>,
+) -> IncidentResult<Vec<Premortem>> {
+    let premortems = sqlx::query_as::query_as::<_, Premortem>(
+        r#"
+        WITH linked AS (
+            INSERT INTO premortem_incidents (premortem_id, incident_id)
+            SELECT id, $1 FROM premortems
+            WHERE LOWER(service) = LOWER($2)
+              AND window_start <= $3 AND window_end >= $3
+            ON CONFLICT DO NOTHING
+            RETURNING premortem_id
+        )
+        SELECT p.* FROM premortems p
+        JOIN linked l ON l.premortem_id = p.id
+        ORDER BY p.window_start, p.created_at
+        "#,
+    )
+    .bind(incident_id)
+    .bind(service)
+    .bind(declared_at)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(premortems)
+}
+
+/// IDs of the incidents linked to a pre-mortem, in the order they were linked.
+pub async fn linked_incidents(pool: &PgPool, premortem_id: Uuid) -> IncidentResult<Vec<Uuid>> {
+    let ids = sqlx::query_scalar::query_scalar::<_, Uuid>(
+        r#"
+        SELECT incident_id FROM premortem_incidents
+        WHERE premortem_id = $1
+        ORDER BY linked_at
+        "#,
+    )
+    .bind(premortem_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(ids)
+}
@@ -0,0 +1,46 @@
+use crate::db::models::{IncidentId, IncidentRole};
+use crate::error::IncidentResult;
+use sqlx_postgres::PgPool;
+
+/// Claim a role for an incident. Returns `None` if someone already holds it
+/// (first claim wins).
+pub async fn claim_role(
+    pool: &PgPool,
+    incident_id: IncidentId,
+    role: &str,
+    user_id: &str,
+) -> IncidentResult<Option<IncidentRole>> {
+    let claimed = sqlx::query_as::query_as::<_, IncidentRole>(
+        r#"
+        INSERT INTO incident_roles (incident_id, role, user_id)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (incident_id, role) DO NOTHING
+        RETURNING *
+        "#,
+    )
+    .bind(incident_id)
+    .bind(role)
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(claimed)
+}
+
+pub async fn list_roles(
+    pool: &PgPool,
+    incident_id: IncidentId,
+) -> IncidentResult<Vec<IncidentRole>> {
+    let roles = sqlx::query_as::query_as::<_, IncidentRole>(
+        r#"
+        SELECT * FROM incident_roles
+        WHERE incident_id = $1
+        ORDER BY claimed_at ASC
+        "#,
+    )
+    .bind(incident_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(roles)
+}
@@ -0,0 +1,72 @@
+use crate::db::models::{IncidentId, Severity, SlaBreach, SlaMetric};
+use crate::error::IncidentResult;
+use chrono::{DateTime, Utc};
+use sqlx_postgres::PgPool;
+
+/// Timestamps of the incident's first and latest status updates.
+pub async fn status_update_bounds(
+    pool: &PgPool,
+    incident_id: IncidentId,
+) -> IncidentResult<(Option<DateTime<Utc>>, Option<DateTime<Utc>>)> {
+    let bounds = sqlx::query_as::query_as::<_, (Option<DateTime<Utc>>, Option<DateTime<Utc>>)>(
+        r#"
+        SELECT MIN(timestamp), MAX(timestamp) FROM incident_timeline
+        WHERE incident_id = $1 AND event_type = 'status_update'
+        "#,
+    )
+    .bind(incident_id)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(bounds)
+}
+
+/// Record a missed deadline. Returns `false` if it was already recorded.
+pub async fn record_breach(
+    pool: &PgPool,
+    incident_id: IncidentId,
+    severity: Severity,
+    metric: SlaMetric,
+    target_minutes: i32,
+    deadline: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> IncidentResult<bool> {
+    let result = sqlx::query::query(
+        r#"
+        INSERT INTO sla_breaches (incident_id, severity, metric, target_minutes, deadline, recorded_at)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        ON CONFLICT (incident_id, metric, deadline) DO NOTHING
+        "#,
+    )
+    .bind(incident_id)
+    .bind(severity.as_db_str())
+    .bind(metric.as_db_str())
+    .bind(target_minutes)
+    .bind(deadline)
+    .bind(now)
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Breaches whose deadline fell in `[since, until)`, oldest first.
+pub async fn breaches_between(
+    pool: &PgPool,
+    since: DateTime<Utc>,
+    until: DateTime<Utc>,
+) -> IncidentResult<Vec<SlaBreach>> {
+    let breaches = sqlx::query_as::query_as::<_, SlaBreach>(
+        r#"
+        SELECT * FROM sla_breaches
+        WHERE deadline >= $1 AND deadline < $2
+        ORDER BY deadline
+        "#,
+    )
+    .bind(since)
+    .bind(until)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(breaches)
+}
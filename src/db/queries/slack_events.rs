@@ -0,0 +1,37 @@
+use crate::error::IncidentResult;
+use chrono::{DateTime, Utc};
+use sqlx_postgres::PgPool;
+
+/// Record an Events API delivery. Returns `false` if `event_id` was already
+/// processed, i.e. this delivery is a Slack retry.
+pub async fn claim_event(pool: &PgPool, event_id: &str, event_type: &str) -> IncidentResult<bool> {
+    let claimed = sqlx::query_scalar::query_scalar::<_, String>(
+        r#"
+        INSERT INTO processed_slack_events (event_id, event_type)
+        VALUES ($1, $2)
+        ON CONFLICT (event_id) DO NOTHING
+        RETURNING event_id
+        "#,
+    )
+    .bind(event_id)
+    .bind(event_type)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(claimed.is_some())
+}
+
+/// Forget events received before `cutoff`.
+pub async fn purge_events_before(pool: &PgPool, cutoff: DateTime<Utc>) -> IncidentResult<u64> {
+    let result = sqlx::query::query(
+        r#"
+        DELETE FROM processed_slack_events
+        WHERE received_at < $1
+        "#,
+    )
+    .bind(cutoff)
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected())
+}
@@ -0,0 +1,69 @@
+use crate::db::models::{Audience, IncidentId, StatusDraft};
+use crate::error::IncidentResult;
+use chrono::{DateTime, Utc};
+use sqlx_postgres::PgPool;
+use uuid::Uuid;
+
+pub async fn create_draft(
+    pool: &PgPool,
+    incident_id: IncidentId,
+    drafted_by: &str,
+    message: &str,
+    audience: Audience,
+) -> IncidentResult<StatusDraft> {
+    let draft = sqlx::query_as::query_as::<_, StatusDraft>(
+        r#"
+        INSERT INTO status_drafts (incident_id, drafted_by, message, audience)
+        VALUES ($1, $2, $3, $4)
+        RETURNING *
+        "#,
+    )
+    .bind(incident_id)
+    .bind(drafted_by)
+    .bind(message)
+    .bind(audience.as_db_str())
+    .fetch_one(pool)
+    .await?;
+
+    Ok(draft)
+}
+
+pub async fn get_draft(pool: &PgPool, id: Uuid) -> IncidentResult<Option<StatusDraft>> {
+    let draft = sqlx::query_as::query_as::<_, StatusDraft>(
+        r#"
+        SELECT * FROM status_drafts WHERE id = $1
+        "#,
+    )
+    .bind(id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(draft)
+}
+
+/// Record the commander's decision. Returns `None` if the draft was already
+/// approved or discarded, so a double-clicked button posts once.
+pub async fn decide_draft(
+    pool: &PgPool,
+    id: Uuid,
+    decided_by: &str,
+    approved: bool,
+    now: DateTime<Utc>,
+) -> IncidentResult<Option<StatusDraft>> {
+    let draft = sqlx::query_as::query_as::<_, StatusDraft>(
+        r#"
+        UPDATE status_drafts
+        SET decided_by = $2, approved = $3, decided_at = $4
+        WHERE id = $1 AND decided_at IS NULL
+        RETURNING *
+        "#,
+    )
+    .bind(id)
+    .bind(decided_by)
+    .bind(approved)
+    .bind(now)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(draft)
+}
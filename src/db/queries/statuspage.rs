@@ -1,3 +1,4 @@
+use crate::db::models::IncidentId;
 use crate::error::IncidentResult;
 use sqlx_postgres::PgPool;
 
@@ -14,3 +15,23 @@ pub async fn get_component_id(pool: &PgPool, service_name: &str) -> IncidentResu
 
     Ok(component_id)
 }
+
+pub async fn set_statuspage_incident_id(
+    pool: &PgPool,
+    incident_id: IncidentId,
+    statuspage_incident_id: &str,
+) -> IncidentResult<()> {
+    sqlx::query::query(
+        r#"
+        UPDATE incidents
+        SET statuspage_incident_id = $2, updated_at = NOW()
+        WHERE id = $1
+        "#,
+    )
+    .bind(incident_id)
+    .bind(statuspage_incident_id)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,117 @@
+use crate::db::models::{Incident, IncidentId, Subscription};
+use crate::error::IncidentResult;
+use sqlx_postgres::PgPool;
+
+/// Subscribe `user_id` to an incident. Returns false if already subscribed.
+pub async fn subscribe_to_incident(
+    pool: &PgPool,
+    user_id: &str,
+    incident_id: IncidentId,
+) -> IncidentResult<bool> {
+    let result = sqlx::query::query(
+        r#"
+        INSERT INTO subscriptions (user_id, incident_id)
+        VALUES ($1, $2)
+        ON CONFLICT (user_id, incident_id) WHERE incident_id IS NOT NULL DO NOTHING
+        "#,
+    )
+    .bind(user_id)
+    .bind(incident_id)
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Subscribe `user_id` to every incident on `service`. Returns false if
+/// already subscribed.
+pub async fn subscribe_to_service(
+    pool: &PgPool,
+    user_id: &str,
+    service: &str,
+) -> IncidentResult<bool> {
+    let result = sqlx::query::query(
+        r#"
+        INSERT INTO subscriptions (user_id, service)
+        VALUES ($1, $2)
+        ON CONFLICT (user_id, service) WHERE service IS NOT NULL DO NOTHING
+        "#,
+    )
+    .bind(user_id)
+    .bind(service)
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Returns false if `user_id` wasn't subscribed to the incident.
+pub async fn unsubscribe_from_incident(
+    pool: &PgPool,
+    user_id: &str,
+    incident_id: IncidentId,
+) -> IncidentResult<bool> {
+    let result =
+        sqlx::query::query("DELETE FROM subscriptions WHERE user_id = $1 AND incident_id = $2")
+            .bind(user_id)
+            .bind(incident_id)
+            .execute(pool)
+            .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Returns false if `user_id` wasn't subscribed to the service.
+pub async fn unsubscribe_from_service(
+    pool: &PgPool,
+    user_id: &str,
+    service: &str,
+) -> IncidentResult<bool> {
+    let result =
+        sqlx::query::query("DELETE FROM subscriptions WHERE user_id = $1 AND service = $2")
+            .bind(user_id)
+            .bind(service)
+            .execute(pool)
+            .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// A user's subscriptions: services first, then incidents, oldest first.
+pub async fn list_for_user(pool: &PgPool, user_id: &str) -> IncidentResult<Vec<Subscription>> {
+    let subscriptions = sqlx::query_as::query_as::<_, Subscription>(
+        r#"
+        SELECT * FROM subscriptions
+        WHERE user_id = $1
+        ORDER BY service IS NULL, created_at
+        "#,
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(subscriptions)
+}
+
+/// Who gets digests of `incident`: its subscribers, then its service's, one
+/// subscription per user (the incident's, if they have both). Service
+/// subscribers don't hear about quiet incidents.
+pub async fn subscribers_for(
+    pool: &PgPool,
+    incident: &Incident,
+) -> IncidentResult<Vec<Subscription>> {
+    let subscriptions = sqlx::query_as::query_as::<_, Subscription>(
+        r#"
+        SELECT DISTINCT ON (user_id) * FROM subscriptions
+        WHERE incident_id = $1 OR (service = $2 AND NOT $3)
+        ORDER BY user_id, incident_id IS NULL, created_at
+        "#,
+    )
+    .bind(incident.id)
+    .bind(&incident.affected_service)
+    .bind(incident.is_quiet)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(subscriptions)
+}
@@ -1,5 +1,5 @@
-use crate::db::models::IncidentTemplate;
-use crate::error::IncidentResult;
+use crate::db::models::{IncidentId, IncidentTemplate, Severity};
+use crate::error::{IncidentError, IncidentResult};
 use sqlx_postgres::PgPool;
 use uuid::Uuid;
 
@@ -50,3 +50,186 @@ pub async fn get_template_by_id(
 
     Ok(template)
 }
+
+/// Every template, disabled ones included, for the admin listing.
+pub async fn list_all_templates(pool: &PgPool) -> IncidentResult<Vec<IncidentTemplate>> {
+    let templates = sqlx::query_as::query_as::<_, IncidentTemplate>(
+        r#"
+        SELECT * FROM incident_templates
+        ORDER BY is_active DESC, name
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(templates)
+}
+
+/// Like `get_template_by_name`, but also finds disabled templates.
+pub async fn get_any_template_by_name(
+    pool: &PgPool,
+    name: &str,
+) -> IncidentResult<Option<IncidentTemplate>> {
+    let template = sqlx::query_as::query_as::<_, IncidentTemplate>(
+        r#"
+        SELECT * FROM incident_templates
+        WHERE name = $1
+        "#,
+    )
+    .bind(name)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(template)
+}
+
+/// Insert a template, or overwrite and re-enable a disabled one of the same
+/// name. Returns `None` if an active template already has the name.
+/// `source_incident_id` is the resolved incident it was created from, if any.
+pub async fn create_template(
+    pool: &PgPool,
+    name: &str,
+    title: &str,
+    severity: Severity,
+    affected_service: Option<&str>,
+    description: Option<&str>,
+    source_incident_id: Option<IncidentId>,
+) -> IncidentResult<Option<IncidentTemplate>> {
+    let template = sqlx::query_as::query_as::<_, IncidentTemplate>(
+        r#"
+        INSERT INTO incident_templates
+            (name, title, severity, affected_service, description, is_active, source_incident_id)
+        VALUES ($1, $2, $3, $4, $5, true, $6)
+        ON CONFLICT (name) DO UPDATE SET
+            title = EXCLUDED.title,
+            severity = EXCLUDED.severity,
+            affected_service = EXCLUDED.affected_service,
+            description = EXCLUDED.description,
+            is_active = true,
+            source_incident_id = EXCLUDED.source_incident_id,
+            updated_at = NOW()
+        WHERE NOT incident_templates.is_active
+        RETURNING *
+        "#,
+    )
+    .bind(name)
+    .bind(title)
+    .bind(severity.as_db_str())
+    .bind(affected_service)
+    .bind(description)
+    .bind(source_incident_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(template)
+}
+
+/// Overwrite a template's fields, leaving its name and enabled state alone.
+pub async fn update_template(
+    pool: &PgPool,
+    id: Uuid,
+    title: &str,
+    severity: Severity,
+    affected_service: Option<&str>,
+    description: Option<&str>,
+) -> IncidentResult<IncidentTemplate> {
+    let template = sqlx::query_as::query_as::<_, IncidentTemplate>(
+        r#"
+        UPDATE incident_templates
+        SET title = $2, severity = $3, affected_service = $4, description = $5,
+            updated_at = NOW()
+        WHERE id = $1
+        RETURNING *
+        "#,
+    )
+    .bind(id)
+    .bind(title)
+    .bind(severity.as_db_str())
+    .bind(affected_service)
+    .bind(description)
+    .fetch_optional(pool)
+    .await?
+    .ok_or(IncidentError::NotFound)?;
+
+    Ok(template)
+}
+
+/// Soft-disable: the template leaves the declare modal but is kept, so it
+/// can be edited or re-created later. Returns whether an active template
+/// was disabled.
+pub async fn disable_template(pool: &PgPool, name: &str) -> IncidentResult<bool> {
+    let result = sqlx::query::query(
+        r#"
+        UPDATE incident_templates SET is_active = false, updated_at = NOW()
+        WHERE name = $1 AND is_active
+        "#,
+    )
+    .bind(name)
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// How the incidents declared from one template (or from none) went.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TemplateUsage {
+    /// `None` for incidents declared without a template
+    pub template_id: Option<Uuid>,
+    pub incidents: i64,
+    pub resolved: i64,
+    /// Mean `duration_minutes` of the resolved incidents (MTTR)
+    pub mean_minutes: Option<f64>,
+}
+
+/// Declared incidents grouped by the template they were declared from, with
+/// one row for those declared without one, to compare MTTRs. Triages never
+/// declared are left out.
+pub async fn template_usage(pool: &PgPool) -> IncidentResult<Vec<TemplateUsage>> {
+    let rows = sqlx::query_as::query_as::<_, (Option<Uuid>, i64, i64, Option<f64>)>(
+        r#"
+        SELECT
+            template_id,
+            COUNT(*),
+            COUNT(*) FILTER (WHERE status = 'resolved'),
+            (AVG(duration_minutes) FILTER (WHERE status = 'resolved'))::FLOAT8
+        FROM incidents
+        WHERE NOT is_triage
+        GROUP BY template_id
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(
+            |(template_id, incidents, resolved, mean_minutes)| TemplateUsage {
+                template_id,
+                incidents,
+                resolved,
+                mean_minutes,
+            },
+        )
+        .collect())
+}
+
+/// Whether an active template was already created from `incident_id`.
+pub async fn has_template_from_incident(
+    pool: &PgPool,
+    incident_id: IncidentId,
+) -> IncidentResult<bool> {
+    let exists = sqlx::query_scalar::query_scalar::<_, bool>(
+        r#"
+        SELECT EXISTS (
+            SELECT 1 FROM incident_templates
+            WHERE source_incident_id = $1 AND is_active
+        )
+        "#,
+    )
+    .bind(incident_id)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(exists)
+}
@@ -1,6 +1,8 @@
-use crate::db::models::{IncidentId, TimelineEvent, TimelineEventType};
+use crate::db::models::{Audience, IncidentId, NewTimelineEvent, TimelineEvent, TimelineEventType};
 use crate::error::IncidentResult;
+use chrono::{DateTime, Utc};
 use sqlx_postgres::PgPool;
+use uuid::Uuid;
 
 pub async fn log_event(
     pool: &PgPool,
@@ -8,11 +10,12 @@ pub async fn log_event(
     event_type: TimelineEventType,
     message: String,
     posted_by: String,
+    audience: Audience,
 ) -> IncidentResult<TimelineEvent> {
     let event = sqlx::query_as::query_as::<_, TimelineEvent>(
         r#"
-        INSERT INTO incident_timeline (incident_id, event_type, message, posted_by)
-        VALUES ($1, $2, $3, $4)
+        INSERT INTO incident_timeline (incident_id, event_type, message, posted_by, audience)
+        VALUES ($1, $2, $3, $4, $5)
         RETURNING *
         "#,
     )
@@ -20,26 +23,211 @@ pub async fn log_event(
     .bind(event_type.as_db_str())
     .bind(message)
     .bind(posted_by)
+    .bind(audience.as_db_str())
     .fetch_one(pool)
     .await?;
 
     Ok(event)
 }
 
+/// Log a status update the commander posted from the scribe's draft.
+pub async fn log_drafted_status_update(
+    pool: &PgPool,
+    incident_id: IncidentId,
+    message: String,
+    posted_by: String,
+    drafted_by: String,
+    audience: Audience,
+) -> IncidentResult<TimelineEvent> {
+    let event = sqlx::query_as::query_as::<_, TimelineEvent>(
+        r#"
+        INSERT INTO incident_timeline
+            (incident_id, event_type, message, posted_by, audience, drafted_by)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING *
+        "#,
+    )
+    .bind(incident_id)
+    .bind(TimelineEventType::StatusUpdate.as_db_str())
+    .bind(message)
+    .bind(posted_by)
+    .bind(audience.as_db_str())
+    .bind(drafted_by)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(event)
+}
+
+/// Insert many events in a single round trip (one multi-row INSERT via UNNEST).
+///
+/// Events without a source timestamp are spaced 1µs apart from NOW() so that
+/// `get_timeline` (ordered by timestamp) keeps them in submission order.
+pub async fn log_events_batch(
+    pool: &PgPool,
+    incident_id: IncidentId,
+    events: &[NewTimelineEvent],
+) -> IncidentResult<Vec<TimelineEvent>> {
+    let event_types: Vec<&str> = events.iter().map(|e| e.event_type.as_db_str()).collect();
+    let messages: Vec<&str> = events.iter().map(|e| e.message.as_str()).collect();
+    let posted_by: Vec<&str> = events.iter().map(|e| e.posted_by.as_str()).collect();
+    let timestamps: Vec<Option<chrono::DateTime<chrono::Utc>>> =
+        events.iter().map(|e| e.timestamp).collect();
+
+    let inserted = sqlx::query_as::query_as::<_, TimelineEvent>(
+        r#"
+        INSERT INTO incident_timeline (incident_id, event_type, message, posted_by, timestamp)
+        SELECT $1, batch.event_type, batch.message, batch.posted_by, COALESCE(batch.ts, NOW() + (batch.ord - 1) * INTERVAL '1 microsecond')
+        FROM UNNEST($2::text[], $3::text[], $4::text[], $5::timestamptz[])
+            WITH ORDINALITY AS batch(event_type, message, posted_by, ts, ord)
+        ORDER BY batch.ord
+        RETURNING *
+        "#,
+    )
+    .bind(incident_id)
+    .bind(event_types)
+    .bind(messages)
+    .bind(posted_by)
+    .bind(timestamps)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(inserted)
+}
+
+/// Whether the incident already has an event by `posted_by` at exactly
+/// `timestamp`.
+pub async fn has_event_at(
+    pool: &PgPool,
+    incident_id: IncidentId,
+    posted_by: &str,
+    timestamp: chrono::DateTime<chrono::Utc>,
+) -> IncidentResult<bool> {
+    let exists = sqlx::query_scalar::query_scalar::<_, bool>(
+        r#"
+        SELECT EXISTS(
+            SELECT 1 FROM incident_timeline
+            WHERE incident_id = $1 AND posted_by = $2 AND timestamp = $3
+        )
+        "#,
+    )
+    .bind(incident_id)
+    .bind(posted_by)
+    .bind(timestamp)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(exists)
+}
+
 pub async fn get_timeline(
     pool: &PgPool,
     incident_id: IncidentId,
+) -> IncidentResult<Vec<TimelineEvent>> {
+    get_timeline_page(pool, incident_id, None, 0).await
+}
+
+/// Events of an incident oldest first, skipping the first `offset` and
+/// returning at most `limit` (every remaining event when `None`).
+pub async fn get_timeline_page(
+    pool: &PgPool,
+    incident_id: IncidentId,
+    limit: Option<i64>,
+    offset: i64,
 ) -> IncidentResult<Vec<TimelineEvent>> {
     let events = sqlx::query_as::query_as::<_, TimelineEvent>(
         r#"
         SELECT * FROM incident_timeline
         WHERE incident_id = $1
         ORDER BY timestamp ASC
+        LIMIT $2 OFFSET $3
+        "#,
+    )
+    .bind(incident_id)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(events)
+}
+
+/// The `limit` most recent status updates of an incident, oldest first.
+pub async fn latest_status_updates(
+    pool: &PgPool,
+    incident_id: IncidentId,
+    limit: i64,
+) -> IncidentResult<Vec<TimelineEvent>> {
+    let mut events = sqlx::query_as::query_as::<_, TimelineEvent>(
+        r#"
+        SELECT * FROM incident_timeline
+        WHERE incident_id = $1 AND event_type = 'status_update' AND deleted_at IS NULL
+        ORDER BY timestamp DESC
+        LIMIT $2
         "#,
     )
     .bind(incident_id)
+    .bind(limit)
     .fetch_all(pool)
     .await?;
+    events.reverse();
 
     Ok(events)
 }
+
+/// Edit a status update or note's text. Returns `None` if it was deleted.
+pub async fn update_event_message(
+    pool: &PgPool,
+    event_id: Uuid,
+    message: &str,
+    now: DateTime<Utc>,
+) -> IncidentResult<Option<TimelineEvent>> {
+    let event = sqlx::query_as::query_as::<_, TimelineEvent>(
+        r#"
+        UPDATE incident_timeline
+        SET message = $2, updated_at = $3
+        WHERE id = $1 AND deleted_at IS NULL
+        RETURNING *
+        "#,
+    )
+    .bind(event_id)
+    .bind(message)
+    .bind(now)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(event)
+}
+
+/// Soft-delete an event. Returns `None` if it was already deleted.
+pub async fn delete_event(
+    pool: &PgPool,
+    event_id: Uuid,
+    now: DateTime<Utc>,
+) -> IncidentResult<Option<TimelineEvent>> {
+    let event = sqlx::query_as::query_as::<_, TimelineEvent>(
+        r#"
+        UPDATE incident_timeline
+        SET deleted_at = $2
+        WHERE id = $1 AND deleted_at IS NULL
+        RETURNING *
+        "#,
+    )
+    .bind(event_id)
+    .bind(now)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(event)
+}
+
+pub async fn count_events(pool: &PgPool, incident_id: IncidentId) -> IncidentResult<i64> {
+    let count = sqlx::query_scalar::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM incident_timeline WHERE incident_id = $1",
+    )
+    .bind(incident_id)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(count)
+}
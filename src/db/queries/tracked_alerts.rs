@@ -0,0 +1,92 @@
+use crate::db::models::{IncidentId, TrackedAlert};
+use crate::error::IncidentResult;
+use sqlx_postgres::PgPool;
+
+pub async fn get_tracked_alert(
+    pool: &PgPool,
+    source: &str,
+    fingerprint: &str,
+) -> IncidentResult<Option<TrackedAlert>> {
+    let alert = sqlx::query_as::query_as::<_, TrackedAlert>(
+        "SELECT * FROM tracked_alerts WHERE source = $1 AND fingerprint = $2",
+    )
+    .bind(source)
+    .bind(fingerprint)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(alert)
+}
+
+/// Start tracking a firing alert against `incident_id`, replacing whatever
+/// incident the fingerprint belonged to before.
+pub async fn track_alert(
+    pool: &PgPool,
+    source: &str,
+    fingerprint: &str,
+    incident_id: IncidentId,
+    declared: bool,
+) -> IncidentResult<()> {
+    sqlx::query::query(
+        r#"
+        INSERT INTO tracked_alerts (source, fingerprint, incident_id, declared, firing)
+        VALUES ($1, $2, $3, $4, TRUE)
+        ON CONFLICT (source, fingerprint) DO UPDATE
+        SET incident_id = EXCLUDED.incident_id,
+            declared = EXCLUDED.declared,
+            firing = TRUE,
+            created_at = NOW(),
+            updated_at = NOW()
+        "#,
+    )
+    .bind(source)
+    .bind(fingerprint)
+    .bind(incident_id)
+    .bind(declared)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn set_alert_firing(
+    pool: &PgPool,
+    source: &str,
+    fingerprint: &str,
+    firing: bool,
+) -> IncidentResult<()> {
+    sqlx::query::query(
+        r#"
+        UPDATE tracked_alerts
+        SET firing = $3, updated_at = NOW()
+        WHERE source = $1 AND fingerprint = $2
+        "#,
+    )
+    .bind(source)
+    .bind(fingerprint)
+    .bind(firing)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Whether an alert declared `incident_id` and every alert on it has
+/// resolved.
+pub async fn all_alerts_resolved_for_declared_incident(
+    pool: &PgPool,
+    incident_id: IncidentId,
+) -> IncidentResult<bool> {
+    let resolved = sqlx::query_scalar::query_scalar::<_, bool>(
+        r#"
+        SELECT COALESCE(BOOL_OR(declared) AND NOT BOOL_OR(firing), FALSE)
+        FROM tracked_alerts
+        WHERE incident_id = $1
+        "#,
+    )
+    .bind(incident_id)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(resolved)
+}
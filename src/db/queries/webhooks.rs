@@ -0,0 +1,83 @@
+use crate::db::models::{Webhook, WebhookEvent};
+use crate::error::IncidentResult;
+use sqlx_postgres::PgPool;
+use uuid::Uuid;
+
+pub async fn create_webhook(
+    pool: &PgPool,
+    url: &str,
+    secret: &str,
+    events: &[WebhookEvent],
+) -> IncidentResult<Webhook> {
+    let events: Vec<&str> = events.iter().map(|e| e.as_db_str()).collect();
+    let webhook = sqlx::query_as::query_as::<_, Webhook>(
+        r#"
+        INSERT INTO webhooks (url, secret, events)
+        VALUES ($1, $2, $3)
+        RETURNING *
+        "#,
+    )
+    .bind(url)
+    .bind(secret)
+    .bind(events)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(webhook)
+}
+
+pub async fn get_webhook(pool: &PgPool, id: Uuid) -> IncidentResult<Option<Webhook>> {
+    let webhook = sqlx::query_as::query_as::<_, Webhook>(
+        r#"
+        SELECT * FROM webhooks
+        WHERE id = $1
+        "#,
+    )
+    .bind(id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(webhook)
+}
+
+pub async fn list_webhooks(pool: &PgPool) -> IncidentResult<Vec<Webhook>> {
+    let webhooks = sqlx::query_as::query_as::<_, Webhook>(
+        r#"
+        SELECT * FROM webhooks
+        ORDER BY created_at ASC
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(webhooks)
+}
+
+/// Active webhooks subscribed to `event`.
+pub async fn webhooks_for_event(
+    pool: &PgPool,
+    event: WebhookEvent,
+) -> IncidentResult<Vec<Webhook>> {
+    let webhooks = sqlx::query_as::query_as::<_, Webhook>(
+        r#"
+        SELECT * FROM webhooks
+        WHERE is_active AND (cardinality(events) = 0 OR $1 = ANY(events))
+        ORDER BY created_at ASC
+        "#,
+    )
+    .bind(event.as_db_str())
+    .fetch_all(pool)
+    .await?;
+
+    Ok(webhooks)
+}
+
+/// Returns whether a webhook was deleted.
+pub async fn delete_webhook(pool: &PgPool, id: Uuid) -> IncidentResult<bool> {
+    let result = sqlx::query::query("DELETE FROM webhooks WHERE id = $1")
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
@@ -0,0 +1,124 @@
+use crate::db::models::{IncidentId, Workstream};
+use crate::error::IncidentResult;
+use sqlx_postgres::PgPool;
+
+pub async fn create_workstream(
+    pool: &PgPool,
+    incident_id: IncidentId,
+    name: &str,
+    lead_id: &str,
+    created_by: &str,
+) -> IncidentResult<Workstream> {
+    let workstream = sqlx::query_as::query_as::<_, Workstream>(
+        r#"
+        INSERT INTO incident_workstreams (incident_id, name, lead_id, created_by)
+        VALUES ($1, $2, $3, $4)
+        RETURNING *
+        "#,
+    )
+    .bind(incident_id)
+    .bind(name)
+    .bind(lead_id)
+    .bind(created_by)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(workstream)
+}
+
+pub async fn get_workstream(
+    pool: &PgPool,
+    incident_id: IncidentId,
+    name: &str,
+) -> IncidentResult<Option<Workstream>> {
+    let workstream = sqlx::query_as::query_as::<_, Workstream>(
+        r#"
+        SELECT * FROM incident_workstreams
+        WHERE incident_id = $1 AND name = $2
+        "#,
+    )
+    .bind(incident_id)
+    .bind(name)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(workstream)
+}
+
+pub async fn list_workstreams(
+    pool: &PgPool,
+    incident_id: IncidentId,
+) -> IncidentResult<Vec<Workstream>> {
+    let workstreams = sqlx::query_as::query_as::<_, Workstream>(
+        r#"
+        SELECT * FROM incident_workstreams
+        WHERE incident_id = $1
+        ORDER BY created_at ASC
+        "#,
+    )
+    .bind(incident_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(workstreams)
+}
+
+pub async fn update_thread_ts(
+    pool: &PgPool,
+    workstream_id: uuid::Uuid,
+    thread_ts: &str,
+) -> IncidentResult<()> {
+    sqlx::query::query(
+        r#"
+        UPDATE incident_workstreams SET thread_ts = $1, updated_at = NOW()
+        WHERE id = $2
+        "#,
+    )
+    .bind(thread_ts)
+    .bind(workstream_id)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn record_update(
+    pool: &PgPool,
+    workstream_id: uuid::Uuid,
+    message: &str,
+) -> IncidentResult<Workstream> {
+    let workstream = sqlx::query_as::query_as::<_, Workstream>(
+        r#"
+        UPDATE incident_workstreams
+        SET last_update = $1, last_update_at = NOW(), updated_at = NOW()
+        WHERE id = $2
+        RETURNING *
+        "#,
+    )
+    .bind(message)
+    .bind(workstream_id)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(workstream)
+}
+
+pub async fn update_lead(
+    pool: &PgPool,
+    workstream_id: uuid::Uuid,
+    lead_id: &str,
+) -> IncidentResult<Workstream> {
+    let workstream = sqlx::query_as::query_as::<_, Workstream>(
+        r#"
+        UPDATE incident_workstreams SET lead_id = $1, updated_at = NOW()
+        WHERE id = $2
+        RETURNING *
+        "#,
+    )
+    .bind(lead_id)
+    .bind(workstream_id)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(workstream)
+}
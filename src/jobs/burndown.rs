@@ -0,0 +1,145 @@
+use crate::app_state::AppState;
+use crate::db::queries::{analytics, incidents, postmortems};
+use crate::error::IncidentResult;
+use crate::slack::blocks::{self, BURNDOWN_DAYS};
+use crate::utils::sparkline;
+use chrono::{DateTime, Datelike, Duration as ChronoDuration, NaiveDate, Utc};
+use std::time::Duration;
+use tracing::{error, info};
+
+/// How often to check whether today's sparkline or this week's digest is due.
+const CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Sparkline size in pixels; Slack scales image blocks to the column width.
+const SPARKLINE_WIDTH: u32 = 360;
+const SPARKLINE_HEIGHT: u32 = 60;
+
+/// Render the open-incidents burndown once a day for App Home, and post the
+/// weekly digest to `DIGEST_CHANNEL` once a week.
+pub async fn run(state: AppState) {
+    let mut interval = tokio::time::interval(CHECK_INTERVAL);
+    info!("Burndown renderer started");
+
+    loop {
+        interval.tick().await;
+        let now = Utc::now();
+        if let Err(e) = render_daily(&state, now).await {
+            error!("Burndown render failed: {}", e);
+        }
+        if let Err(e) = send_weekly_digest(&state, now).await {
+            error!("Weekly digest failed: {}", e);
+        }
+    }
+}
+
+/// Monday of the week containing `now`.
+pub fn week_start(now: DateTime<Utc>) -> NaiveDate {
+    let today = now.date_naive();
+    today - ChronoDuration::days(i64::from(today.weekday().num_days_from_monday()))
+}
+
+/// Render and upload today's sparkline unless it already exists. A failed
+/// upload is released so the next pass retries. Returns the new file ID.
+pub async fn render_daily(state: &AppState, now: DateTime<Utc>) -> IncidentResult<Option<String>> {
+    let today = now.date_naive();
+    if !analytics::claim_burndown_render(&state.pool, today).await? {
+        return Ok(None);
+    }
+
+    match upload_sparkline(state, now).await {
+        Ok(file_id) => {
+            analytics::record_burndown_file(&state.pool, today, &file_id).await?;
+            info!("Burndown sparkline for {} uploaded as {}", today, file_id);
+            Ok(Some(file_id))
+        }
+        Err(e) => {
+            analytics::release_burndown_render(&state.pool, today).await?;
+            Err(e)
+        }
+    }
+}
+
+async fn upload_sparkline(state: &AppState, now: DateTime<Utc>) -> IncidentResult<String> {
+    let counts: Vec<i64> = analytics::open_incidents_by_day(&state.pool, BURNDOWN_DAYS, now)
+        .await?
+        .into_iter()
+        .map(|(_, count)| count)
+        .collect();
+    let png = sparkline::render_png(&counts, SPARKLINE_WIDTH, SPARKLINE_HEIGHT);
+
+    state
+        .slack_client
+        .upload_file(
+            None,
+            &format!("burndown-{}.png", now.date_naive()),
+            &format!("Open incidents, last {} days", BURNDOWN_DAYS),
+            png,
+        )
+        .await
+}
+
+/// Post the digest for the current week to `DIGEST_CHANNEL` if it hasn't been
+/// posted yet. Returns whether a digest was posted.
+pub async fn send_weekly_digest(state: &AppState, now: DateTime<Utc>) -> IncidentResult<bool> {
+    let Some(channel_id) = state.config.digest_channel.as_deref() else {
+        return Ok(false);
+    };
+    let week_start = week_start(now);
+    if !analytics::claim_digest_run(&state.pool, week_start).await? {
+        return Ok(false);
+    }
+
+    let period_end = week_start
+        .and_hms_opt(0, 0, 0)
+        .expect("midnight always exists")
+        .and_utc();
+    let last_week = analytics::service_stats(
+        &state.pool,
+        &state.config.services,
+        period_end - ChronoDuration::days(7),
+        period_end,
+    )
+    .await?;
+    let open_now: i64 = incidents::count_open_by_severity(&state.pool)
+        .await?
+        .iter()
+        .map(|(_, count)| count)
+        .sum();
+    let overdue_postmortems = postmortems::overdue_postmortems(&state.pool, now).await?;
+    let burndown_file_id = analytics::latest_burndown_file(&state.pool).await?;
+
+    let mut digest = blocks::weekly_digest_blocks(
+        week_start,
+        &last_week,
+        open_now,
+        &overdue_postmortems,
+        burndown_file_id.as_deref(),
+    );
+    state
+        .context_banner
+        .insert(&state.pool, &mut digest, 1)
+        .await;
+    state.slack_client.post_message(channel_id, digest).await?;
+    info!("Weekly digest for week of {} posted", week_start);
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_week_start_is_monday() {
+        let sunday = Utc.with_ymd_and_hms(2026, 3, 8, 23, 0, 0).unwrap();
+        assert_eq!(
+            week_start(sunday),
+            NaiveDate::from_ymd_opt(2026, 3, 2).unwrap()
+        );
+        let monday = Utc.with_ymd_and_hms(2026, 3, 9, 0, 5, 0).unwrap();
+        assert_eq!(
+            week_start(monday),
+            NaiveDate::from_ymd_opt(2026, 3, 9).unwrap()
+        );
+    }
+}
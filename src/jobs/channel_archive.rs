@@ -0,0 +1,164 @@
+use crate::app_state::AppState;
+use crate::db::models::Incident;
+use crate::db::queries::{incidents, postmortems};
+use crate::error::{IncidentError, IncidentResult};
+use crate::services::artifact_store::ArtifactService;
+use crate::slack::blocks;
+use crate::slack::client::{is_retryable_error_code, HistoryRange};
+use chrono::{DateTime, Utc};
+use serde_json::json;
+use std::time::Duration;
+use tracing::{error, info};
+
+/// How often resolved incidents are checked for a channel due for archiving.
+const CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+pub const TRANSCRIPT_ARTIFACT_KIND: &str = "channel_transcript";
+
+/// Summarize and archive incident channels `channel_archive_after_days`
+/// after resolution.
+pub async fn run(state: AppState) {
+    if state.config.channel_archive_after_days == 0 {
+        info!("Incident channel archiving disabled");
+        return;
+    }
+    let mut interval = tokio::time::interval(CHECK_INTERVAL);
+    info!(
+        "Incident channel archiving started ({} days after resolution)",
+        state.config.channel_archive_after_days
+    );
+
+    loop {
+        interval.tick().await;
+        if let Err(e) = archive_once(&state, Utc::now()).await {
+            error!("Channel archive pass failed: {}", e);
+        }
+    }
+}
+
+/// One archive pass. Each channel is claimed on its incident row before the
+/// summary is posted, so a channel is summarized once; the claim is released
+/// if the archive fails transiently, for the next pass to retry. Returns the
+/// number of channels archived.
+pub async fn archive_once(state: &AppState, now: DateTime<Utc>) -> IncidentResult<usize> {
+    let days = state.config.channel_archive_after_days;
+    if days == 0 {
+        return Ok(0);
+    }
+    let due =
+        incidents::channels_to_archive(&state.pool, now - chrono::Duration::days(days as i64))
+            .await?;
+
+    let mut archived = 0;
+    for incident in due {
+        if archive_incident_channel(state, &incident, now).await? {
+            archived += 1;
+        }
+    }
+
+    Ok(archived)
+}
+
+/// Claim, summarize and archive one incident's channel: resolved ones from
+/// the archive pass, canceled ones straight away. Returns `false` if there
+/// was nothing to archive or the archive failed.
+pub(crate) async fn archive_incident_channel(
+    state: &AppState,
+    incident: &Incident,
+    now: DateTime<Utc>,
+) -> IncidentResult<bool> {
+    let Some(channel_id) = incident.slack_channel_id.as_deref() else {
+        return Ok(false);
+    };
+    if !incidents::mark_channel_archived(&state.pool, incident.id, now).await? {
+        return Ok(false);
+    }
+
+    if let Err(e) = save_transcript(state, incident, channel_id, now).await {
+        error!(
+            "Failed to save channel transcript for incident {}: {}",
+            incident.id, e
+        );
+    }
+
+    let postmortem = postmortems::get_postmortem(&state.pool, incident.id).await?;
+    if let Err(e) = state
+        .slack_client
+        .post_message(
+            channel_id,
+            blocks::channel_archive_summary_blocks(incident, postmortem.as_ref()),
+        )
+        .await
+    {
+        error!(
+            "Failed to post archive summary for incident {}: {}",
+            incident.id, e
+        );
+    }
+
+    match state.slack_client.archive_channel(channel_id).await {
+        Ok(()) => {}
+        Err(IncidentError::SlackAPIError {
+            ref slack_error_code,
+            ..
+        }) if slack_error_code == "already_archived" => {}
+        Err(e) => {
+            error!(
+                "Failed to archive channel {} for incident {}: {}",
+                channel_id, incident.id, e
+            );
+            // Slack's refusals (`not_in_channel`, `restricted_action`, ...)
+            // won't change by themselves; only retry transient failures
+            let transient = match &e {
+                IncidentError::SlackAPIError {
+                    slack_error_code, ..
+                } => is_retryable_error_code(slack_error_code),
+                _ => true,
+            };
+            if transient {
+                incidents::clear_channel_archived(&state.pool, incident.id).await?;
+            }
+            return Ok(false);
+        }
+    }
+
+    info!(
+        "Archived channel {} for incident {}",
+        channel_id, incident.id
+    );
+    Ok(true)
+}
+
+/// Keep the channel's history in the artifact store, since it becomes
+/// read-only and harder to find once archived.
+async fn save_transcript(
+    state: &AppState,
+    incident: &Incident,
+    channel_id: &str,
+    now: DateTime<Utc>,
+) -> IncidentResult<()> {
+    let messages = state
+        .slack_client
+        .fetch_channel_history(channel_id, &HistoryRange::default())
+        .await?;
+    let transcript = json!({
+        "incident_id": incident.id,
+        "channel_id": channel_id,
+        "exported_at": now,
+        "messages": messages,
+    });
+    let bytes = serde_json::to_vec(&transcript).map_err(|e| {
+        IncidentError::InternalError(format!("Failed to serialize transcript: {}", e))
+    })?;
+
+    ArtifactService::new(state.pool.clone(), state.artifact_store.clone())
+        .save(
+            Some(incident.id),
+            TRANSCRIPT_ARTIFACT_KIND,
+            &format!("incidents/{}/channel-transcript.json", incident.id),
+            "application/json",
+            bytes,
+        )
+        .await?;
+    Ok(())
+}
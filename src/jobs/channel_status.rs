@@ -0,0 +1,195 @@
+use crate::app_state::AppState;
+use crate::config::ChannelStatusIndicator;
+use crate::db::models::{Incident, IncidentId, IncidentStatus, Severity};
+use crate::db::queries::incidents as incident_queries;
+use crate::error::IncidentResult;
+use crate::jobs::Job;
+use crate::services::incident::IncidentService;
+use tracing::{debug, error, info};
+
+/// Slack's limits on channel topics and names.
+const MAX_TOPIC_CHARS: usize = 250;
+const MAX_NAME_CHARS: usize = 80;
+
+/// Prefix of channel names the bot generates; only those are renamed.
+const GENERATED_NAME_PREFIX: &str = "inc-";
+
+/// Enqueue a refresh of the incident channel's status marker and the index
+/// channel's open-incident count after a declaration or status change.
+pub fn enqueue(state: &AppState, incident: &Incident) {
+    if state.config.channel_status_indicator == ChannelStatusIndicator::Off
+        && state.config.incident_index_channel.is_none()
+    {
+        return;
+    }
+
+    let job = Job::SyncChannelStatus {
+        incident_id: incident.id,
+    };
+    if let Err(e) = state.job_sender.send(job) {
+        error!("Failed to enqueue channel status job: {}", e);
+    }
+}
+
+/// Mark the incident channel with the incident's current status and update
+/// the index channel topic. Reads the incident afresh, so jobs that run out
+/// of order still settle on the latest status.
+pub async fn execute(state: &AppState, incident_id: IncidentId) -> IncidentResult<()> {
+    let incident = IncidentService::new(state.pool.clone())
+        .get_by_id(incident_id)
+        .await?;
+
+    if let Some(channel_id) = &incident.slack_channel_id {
+        let result = match state.config.channel_status_indicator {
+            ChannelStatusIndicator::Off => Ok(()),
+            ChannelStatusIndicator::Topic => {
+                state
+                    .slack_client
+                    .set_channel_topic(channel_id, &status_topic(&incident))
+                    .await
+            }
+            ChannelStatusIndicator::Name => {
+                rename_channel(state, channel_id, incident.status).await
+            }
+        };
+        // The index topic is still worth updating
+        if let Err(e) = result {
+            error!(
+                "Failed to mark channel {} with incident status: {}",
+                channel_id, e
+            );
+        }
+    }
+
+    if let Some(index_channel) = &state.config.incident_index_channel {
+        let counts = incident_queries::count_open_announced_by_severity(&state.pool).await?;
+        state
+            .slack_client
+            .set_channel_topic(index_channel, &index_topic(&counts))
+            .await?;
+    }
+
+    info!("Channel status synced for incident {}", incident.id);
+    Ok(())
+}
+
+/// `🟠 Identified | P2: Checkout errors`
+pub fn status_topic(incident: &Incident) -> String {
+    let topic = format!(
+        "{} {} | {}: {}",
+        incident.status.emoji(),
+        incident.status.label(),
+        incident.severity_level().code,
+        incident.title
+    );
+    truncate_chars(&topic, MAX_TOPIC_CHARS)
+}
+
+/// `🔴 3 open incidents (P1: 1, P2: 2)`, or `🟢 No open incidents`.
+pub fn index_topic(counts: &[(Severity, i64)]) -> String {
+    let mut counts: Vec<(Severity, i64)> = counts.iter().copied().filter(|(_, n)| *n > 0).collect();
+    counts.sort_by_key(|(severity, _)| severity.as_db_str());
+    let total: i64 = counts.iter().map(|(_, n)| n).sum();
+    if total == 0 {
+        return "🟢 No open incidents".to_string();
+    }
+
+    let breakdown = counts
+        .iter()
+        .map(|(severity, n)| format!("{}: {}", severity.as_db_str(), n))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!(
+        "{} {} open incident{} ({})",
+        counts[0].0.emoji(),
+        total,
+        if total == 1 { "" } else { "s" },
+        breakdown
+    )
+}
+
+/// `inc-20241115-api` → `inc-20241115-api-identified`, replacing any earlier
+/// status suffix.
+pub fn status_channel_name(current: &str, status: IncidentStatus) -> String {
+    let base = [
+        IncidentStatus::Declared,
+        IncidentStatus::Investigating,
+        IncidentStatus::Identified,
+        IncidentStatus::Monitoring,
+        IncidentStatus::Resolved,
+        IncidentStatus::Canceled,
+    ]
+    .iter()
+    .find_map(|s| current.strip_suffix(&format!("-{}", s.as_db_str())))
+    .unwrap_or(current);
+
+    let suffix = format!("-{}", status.as_db_str());
+    let base: String = base.chars().take(MAX_NAME_CHARS - suffix.len()).collect();
+    format!("{}{}", base.trim_end_matches('-'), suffix)
+}
+
+async fn rename_channel(
+    state: &AppState,
+    channel_id: &str,
+    status: IncidentStatus,
+) -> IncidentResult<()> {
+    let channels = state.slack_client.list_conversations().await?;
+    let Some(channel) = channels.iter().find(|c| c.id == channel_id) else {
+        debug!("Channel {} not listed; leaving its name alone", channel_id);
+        return Ok(());
+    };
+    // Attached war rooms keep the name people gave them
+    if !channel.name.starts_with(GENERATED_NAME_PREFIX) {
+        return Ok(());
+    }
+
+    let name = status_channel_name(&channel.name, status);
+    if name != channel.name {
+        state.slack_client.rename_channel(channel_id, &name).await?;
+    }
+    Ok(())
+}
+
+fn truncate_chars(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        return text.to_string();
+    }
+    let mut truncated: String = text.chars().take(max - 1).collect();
+    truncated.push('…');
+    truncated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_index_topic_counts_open_incidents() {
+        assert_eq!(index_topic(&[]), "🟢 No open incidents");
+        assert_eq!(
+            index_topic(&[(Severity::P2, 1)]),
+            "🟡 1 open incident (P2: 1)"
+        );
+        assert_eq!(
+            index_topic(&[(Severity::P3, 2), (Severity::P1, 1)]),
+            "🔴 3 open incidents (P1: 1, P3: 2)"
+        );
+    }
+
+    #[test]
+    fn test_status_channel_name_replaces_previous_status() {
+        assert_eq!(
+            status_channel_name("inc-20241115-api", IncidentStatus::Investigating),
+            "inc-20241115-api-investigating"
+        );
+        assert_eq!(
+            status_channel_name("inc-20241115-api-investigating", IncidentStatus::Resolved),
+            "inc-20241115-api-resolved"
+        );
+
+        let long = format!("inc-20241115-{}", "a".repeat(68));
+        let renamed = status_channel_name(&long, IncidentStatus::Monitoring);
+        assert_eq!(renamed.len(), 80);
+        assert!(renamed.ends_with("a-monitoring"));
+    }
+}
@@ -0,0 +1,99 @@
+use crate::app_state::AppState;
+use crate::db::queries::coaching;
+use crate::error::IncidentResult;
+use crate::services::coaching::CoachingService;
+use crate::slack::blocks;
+use chrono::{DateTime, Datelike, Months, NaiveDate, NaiveTime, Utc};
+use std::time::Duration;
+use tracing::{error, info};
+
+/// How often to check whether last quarter's reports still need sending.
+const CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// DM each commander who opted in a coaching report for the previous
+/// quarter, shortly after the quarter rolls over.
+pub async fn run(state: AppState) {
+    let mut interval = tokio::time::interval(CHECK_INTERVAL);
+    info!("Quarterly coaching reports started");
+
+    loop {
+        interval.tick().await;
+        if let Err(e) = send_coaching_reports(&state, Utc::now()).await {
+            error!("Coaching report pass failed: {}", e);
+        }
+    }
+}
+
+/// The calendar quarter before the one containing `now`, as `[start, end)`.
+pub fn previous_quarter(now: DateTime<Utc>) -> (NaiveDate, NaiveDate) {
+    let today = now.date_naive();
+    let end = NaiveDate::from_ymd_opt(today.year(), today.month0() / 3 * 3 + 1, 1)
+        .expect("first day of a quarter always exists");
+    let start = end - Months::new(3);
+    (start, end)
+}
+
+/// Send last quarter's report to every commander who had opted in by the
+/// end of it and hasn't had one yet. Commanders with no incidents that
+/// quarter get nothing. Returns the number of reports sent.
+pub async fn send_coaching_reports(state: &AppState, now: DateTime<Utc>) -> IncidentResult<usize> {
+    let (period_start, period_end) = previous_quarter(now);
+    let coaching_service = CoachingService::new(state.pool.clone());
+    let opted_in =
+        coaching::opted_in_before(&state.pool, period_end.and_time(NaiveTime::MIN).and_utc())
+            .await?;
+
+    let mut sent = 0;
+    for user_id in &opted_in {
+        let Some(report) = coaching_service
+            .report(user_id, period_start, period_end)
+            .await?
+        else {
+            continue;
+        };
+        if !coaching::claim_coaching_report(&state.pool, period_start, user_id).await? {
+            continue;
+        }
+
+        if let Err(e) = state
+            .slack_client
+            .send_dm(user_id, blocks::coaching_report_blocks(&report))
+            .await
+        {
+            error!("Failed to DM coaching report to {}: {}", user_id, e);
+        }
+        sent += 1;
+    }
+
+    if sent > 0 {
+        info!(
+            "Sent {} coaching report(s) for {}",
+            sent,
+            blocks::quarter_label(period_start)
+        );
+    }
+    Ok(sent)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_previous_quarter_crosses_year_boundary() {
+        let now = Utc.with_ymd_and_hms(2026, 2, 14, 12, 0, 0).unwrap();
+        assert_eq!(
+            previous_quarter(now),
+            (
+                NaiveDate::from_ymd_opt(2025, 10, 1).unwrap(),
+                NaiveDate::from_ymd_opt(2026, 1, 1).unwrap()
+            )
+        );
+        let now = Utc.with_ymd_and_hms(2026, 9, 30, 23, 59, 0).unwrap();
+        assert_eq!(
+            previous_quarter(now).0,
+            NaiveDate::from_ymd_opt(2026, 4, 1).unwrap()
+        );
+    }
+}
@@ -0,0 +1,92 @@
+use crate::app_state::AppState;
+use crate::db::queries::commanders;
+use crate::error::IncidentResult;
+use crate::services::incident::IncidentService;
+use crate::slack::blocks;
+use chrono::{DateTime, Utc};
+use std::time::Duration;
+use tracing::{error, info};
+
+/// How often P1 commanders are checked against `COMMANDER_ABSENCE_MINUTES`.
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Periodically offer backup commanders command of P1 incidents whose
+/// commander has gone quiet.
+pub async fn run(state: AppState) {
+    if state.config.commander_absence_minutes == 0 {
+        info!("Commander absence escalation disabled");
+        return;
+    }
+    let mut interval = tokio::time::interval(CHECK_INTERVAL);
+    info!(
+        "Commander absence escalation started ({} min)",
+        state.config.commander_absence_minutes
+    );
+
+    loop {
+        interval.tick().await;
+        if let Err(e) = escalate_absent(&state, Utc::now()).await {
+            error!("Commander absence check failed: {}", e);
+        }
+    }
+}
+
+/// One escalation pass. Each absent commander is escalated at most once per
+/// incident; the escalation is recorded before anyone is notified. Returns
+/// the number of incidents escalated.
+pub async fn escalate_absent(state: &AppState, now: DateTime<Utc>) -> IncidentResult<usize> {
+    let minutes = state.config.commander_absence_minutes;
+    if minutes == 0 {
+        return Ok(0);
+    }
+
+    let absent = commanders::absent_commanders(&state.pool, minutes as i32, now).await?;
+    let incident_service = IncidentService::new(state.pool.clone());
+    let mut escalated = 0;
+    for (incident_id, last_seen_at) in absent {
+        let incident = incident_service.get_by_id(incident_id).await?;
+        let backups = state.config.backup_commanders_for(&incident);
+        if !commanders::claim_escalation(
+            &state.pool,
+            incident.id,
+            &incident.commander_id,
+            &backups,
+            now,
+        )
+        .await?
+        {
+            continue;
+        }
+        escalated += 1;
+
+        let idle_minutes = (now - last_seen_at).num_minutes();
+        let escalation = blocks::commander_absent_blocks(&incident, idle_minutes, &backups);
+        if let Some(channel_id) = &incident.slack_channel_id {
+            if let Err(e) = state
+                .slack_client
+                .post_message(channel_id, escalation.clone())
+                .await
+            {
+                error!(
+                    "Failed to post commander escalation for incident {}: {}",
+                    incident.id, e
+                );
+            }
+        }
+        for backup in &backups {
+            if let Err(e) = state.slack_client.send_dm(backup, escalation.clone()).await {
+                error!("Failed to DM backup commander {}: {}", backup, e);
+            }
+        }
+
+        info!(
+            "Commander {} absent from incident {} for {} min; escalated to {} backup(s)",
+            incident.commander_id,
+            incident.id,
+            idle_minutes,
+            backups.len()
+        );
+    }
+
+    Ok(escalated)
+}
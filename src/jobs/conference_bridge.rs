@@ -0,0 +1,77 @@
+use crate::adapters::conference::ConferenceClient;
+use crate::app_state::AppState;
+use crate::db::models::{Incident, IncidentId, Severity};
+use crate::db::queries::incidents as incident_queries;
+use crate::error::IncidentResult;
+use crate::jobs::Job;
+use crate::services::incident::IncidentService;
+use crate::slack::blocks;
+use tracing::{error, info};
+
+/// Enqueue a conference bridge for a P1/P2 declaration if conferencing is
+/// configured. Best-effort, like Statuspage sync.
+pub fn enqueue_for_incident(state: &AppState, incident: &Incident) {
+    if state.config.conference_credentials().is_none()
+        || !matches!(incident.severity, Severity::P1 | Severity::P2)
+    {
+        return;
+    }
+
+    let job = Job::CreateConferenceBridge {
+        incident_id: incident.id,
+    };
+    if let Err(e) = state.job_sender.send(job) {
+        error!("Failed to enqueue conference bridge job: {}", e);
+    }
+}
+
+/// Create the bridge, store its URL on the incident, and post and pin the
+/// join link in the incident channel.
+pub async fn execute(
+    conference_client: &ConferenceClient,
+    state: &AppState,
+    incident_id: IncidentId,
+) -> IncidentResult<()> {
+    let incident = IncidentService::new(state.pool.clone())
+        .get_by_id(incident_id)
+        .await?;
+    if incident.bridge_url.is_some() {
+        return Ok(());
+    }
+
+    // Meeting titles are visible outside Slack; keep quiet incidents vague
+    let topic = if incident.is_quiet {
+        "Incident bridge".to_string()
+    } else {
+        format!(
+            "{} incident: {}",
+            incident.severity_level().code,
+            incident.title
+        )
+    };
+    let bridge_url = conference_client.create_bridge(&topic).await?;
+    if !incident_queries::set_bridge_url(&state.pool, incident.id, &bridge_url).await? {
+        return Ok(());
+    }
+
+    if let Some(channel_id) = &incident.slack_channel_id {
+        match state
+            .slack_client
+            .post_message(
+                channel_id,
+                blocks::bridge_blocks(conference_client.provider().label(), &bridge_url),
+            )
+            .await
+        {
+            Ok(ts) => {
+                if let Err(e) = state.slack_client.pin_message(channel_id, &ts).await {
+                    error!("Failed to pin bridge link: {}", e);
+                }
+            }
+            Err(e) => error!("Failed to post bridge link: {}", e),
+        }
+    }
+
+    info!("Conference bridge created for incident {}", incident.id);
+    Ok(())
+}
@@ -0,0 +1,150 @@
+use crate::app_state::AppState;
+use crate::db::models::Incident;
+use crate::db::queries::{commanders, deactivated_users, incidents as incident_queries};
+use crate::error::IncidentResult;
+use crate::services::permissions::Permissions;
+use crate::slack::blocks;
+use chrono::{DateTime, Utc};
+use std::time::Duration;
+use tracing::{error, info, warn};
+
+/// Periodically look for deactivated Slack users the bot still depends on.
+pub async fn run(state: AppState) {
+    let hours = state.config.deactivated_user_check_hours;
+    if hours == 0 {
+        info!("Deactivated user check disabled");
+        return;
+    }
+    let mut interval = tokio::time::interval(Duration::from_secs(hours * 3600));
+    info!("Deactivated user check started (every {} h)", hours);
+
+    loop {
+        interval.tick().await;
+        if let Err(e) = check_deactivated_users(&state, Utc::now()).await {
+            error!("Deactivated user check failed: {}", e);
+        }
+    }
+}
+
+/// One pass. Every user named in the config or commanding an open incident
+/// is looked up; those newly found deactivated are reported to the admins
+/// once, and backups are offered command of their incidents. Returns the
+/// number of newly deactivated users.
+pub async fn check_deactivated_users(
+    state: &AppState,
+    now: DateTime<Utc>,
+) -> IncidentResult<usize> {
+    let mut references = state.config.user_references();
+    let open = incident_queries::list_open_incidents(&state.pool).await?;
+    for incident in &open {
+        references.entry(incident.commander_id.clone()).or_default();
+    }
+
+    let mut active = Vec::new();
+    let mut deactivated = Vec::new();
+    for (user_id, settings) in references {
+        match state.slack_client.user_info(&user_id).await {
+            Ok(user) if user.deleted => {
+                if deactivated_users::record_deactivated_user(&state.pool, &user_id, now).await? {
+                    deactivated.push((user_id, settings));
+                }
+            }
+            Ok(_) => active.push(user_id),
+            // Unknown either way; try again next pass
+            Err(e) => warn!("Failed to look up Slack user {}: {}", user_id, e),
+        }
+    }
+    deactivated_users::clear_deactivated_users(&state.pool, &active).await?;
+    if deactivated.is_empty() {
+        return Ok(0);
+    }
+
+    let orphaned: Vec<Incident> = open
+        .into_iter()
+        .filter(|incident| {
+            deactivated
+                .iter()
+                .any(|(user_id, _)| *user_id == incident.commander_id)
+        })
+        .collect();
+    for incident in &orphaned {
+        let backups: Vec<String> = state
+            .config
+            .backup_commanders_for(incident)
+            .into_iter()
+            .filter(|backup| !deactivated.iter().any(|(user_id, _)| user_id == backup))
+            .collect();
+        offer_command(state, incident, &backups, now).await?;
+    }
+
+    let admins = Permissions::from_state(state).admins().await;
+    if admins.is_empty() {
+        warn!(
+            "{} Slack user(s) deactivated but no ADMIN_USERS to tell",
+            deactivated.len()
+        );
+    }
+    let report = blocks::deactivated_users_blocks(&deactivated, &orphaned);
+    for admin in &admins {
+        if let Err(e) = state.slack_client.send_dm(admin, report.clone()).await {
+            error!(
+                "Failed to DM admin {} about deactivated users: {}",
+                admin, e
+            );
+        }
+    }
+
+    info!(
+        "Found {} deactivated Slack user(s) commanding {} open incident(s)",
+        deactivated.len(),
+        orphaned.len()
+    );
+    Ok(deactivated.len())
+}
+
+/// Offer `backups` command of an incident whose commander was deactivated,
+/// through the same "Take command" flow as an absence escalation.
+async fn offer_command(
+    state: &AppState,
+    incident: &Incident,
+    backups: &[String],
+    now: DateTime<Utc>,
+) -> IncidentResult<()> {
+    // A commander already escalated for absence keeps the backups offered
+    // then, since only they may take command
+    let backups = if commanders::claim_escalation(
+        &state.pool,
+        incident.id,
+        &incident.commander_id,
+        backups,
+        now,
+    )
+    .await?
+    {
+        backups.to_vec()
+    } else {
+        commanders::escalation_backups(&state.pool, incident.id, &incident.commander_id)
+            .await?
+            .unwrap_or_default()
+    };
+
+    let offer = blocks::commander_deactivated_blocks(incident, &backups);
+    if let Some(channel_id) = &incident.slack_channel_id {
+        if let Err(e) = state
+            .slack_client
+            .post_message(channel_id, offer.clone())
+            .await
+        {
+            error!(
+                "Failed to post deactivated commander notice for incident {}: {}",
+                incident.id, e
+            );
+        }
+    }
+    for backup in &backups {
+        if let Err(e) = state.slack_client.send_dm(backup, offer.clone()).await {
+            error!("Failed to DM backup commander {}: {}", backup, e);
+        }
+    }
+    Ok(())
+}
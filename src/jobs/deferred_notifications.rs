@@ -0,0 +1,28 @@
+use crate::app_state::AppState;
+use crate::services::notification::NotificationService;
+use std::time::Duration;
+use tracing::{error, info};
+
+/// How often thread replies deferred by Slack rate limiting are retried.
+const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Send fan-out thread replies held back while Slack was rate limiting the
+/// bot, once it stops, for threads no later notification has flushed.
+pub async fn run(state: AppState) {
+    let mut interval = tokio::time::interval(CHECK_INTERVAL);
+    info!("Deferred notification flushing started");
+
+    let notification_service = NotificationService::new(
+        state.pool.clone(),
+        state.slack_client.clone(),
+        state.config.clone(),
+    );
+    loop {
+        interval.tick().await;
+        match notification_service.flush_deferred().await {
+            Ok(0) => {}
+            Ok(flushed) => info!("Sent {} deferred notifications", flushed),
+            Err(e) => error!("Deferred notification flush failed: {}", e),
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use crate::app_state::AppState;
+use crate::db::queries::{exec_tiers, incidents as incident_queries};
+use crate::error::IncidentResult;
+use crate::services::notification::NotificationService;
+use crate::slack::blocks;
+use chrono::{DateTime, Utc};
+use std::time::Duration;
+use tracing::{error, info};
+
+/// How often open incidents are checked against `EXEC_TIER_DELAY_MINUTES`.
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Periodically DM the exec tiers of incidents that have stayed unresolved
+/// long enough.
+pub async fn run(state: AppState) {
+    let mut interval = tokio::time::interval(CHECK_INTERVAL);
+    info!("Exec notification tiers started");
+
+    loop {
+        interval.tick().await;
+        if let Err(e) = notify_due_tiers(&state, Utc::now()).await {
+            error!("Exec tier check failed: {}", e);
+        }
+    }
+}
+
+/// One pass over the open incidents. Each tier that is due goes out at most
+/// once per incident, and is recorded before anyone is DMed. Tier 1 normally
+/// goes out with the declaration; it is sent here only if that was missed,
+/// e.g. for an incident escalated into a severity with exec tiers. Returns
+/// the number of tiers delivered.
+pub async fn notify_due_tiers(state: &AppState, now: DateTime<Utc>) -> IncidentResult<usize> {
+    let notification_service = NotificationService::new(
+        state.pool.clone(),
+        state.slack_client.clone(),
+        state.config.clone(),
+    );
+    let mut delivered = 0;
+    for incident in incident_queries::list_open_incidents(&state.pool).await? {
+        // Quiet (security) incidents are never broadcast
+        if incident.is_quiet {
+            continue;
+        }
+        let open_minutes = (now - incident.declared_at).num_minutes();
+        let tiers = state.config.exec_tiers_for(incident.severity);
+        for (tier, after_minutes, recipients) in tiers.tiers() {
+            if open_minutes < after_minutes
+                || recipients.is_empty()
+                || !exec_tiers::claim_tier(&state.pool, incident.id, tier, recipients, now).await?
+            {
+                continue;
+            }
+            delivered += 1;
+
+            notification_service
+                .notify_exec_tier(
+                    &incident,
+                    recipients,
+                    blocks::exec_tier_blocks(&incident, tier, open_minutes),
+                )
+                .await?;
+            info!(
+                "Exec tier {} notified for incident {} ({} min unresolved)",
+                tier, incident.id, open_minutes
+            );
+        }
+    }
+
+    Ok(delivered)
+}
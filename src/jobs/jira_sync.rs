@@ -0,0 +1,84 @@
+use crate::adapters::jira::JiraClient;
+use crate::app_state::AppState;
+use crate::db::models::{ActionItem, Incident};
+use crate::db::queries::action_items as action_item_queries;
+use crate::error::{IncidentError, IncidentResult};
+use crate::jobs::Job;
+use crate::services::incident::IncidentService;
+use crate::slack::blocks;
+use crate::utils::time;
+use tracing::{error, info};
+
+/// Enqueue a Jira ticket for an action item if Jira is configured and the
+/// incident's service maps to a project. Best-effort, like Statuspage sync.
+pub fn enqueue_for_action_item(state: &AppState, incident: &Incident, item: &ActionItem) {
+    if state.config.jira_credentials().is_none() {
+        return;
+    }
+    let Some(project_key) = state.config.jira_project_for(&incident.affected_service) else {
+        return;
+    };
+
+    let job = Job::CreateJiraIssue {
+        action_item_id: item.id,
+        project_key: project_key.to_string(),
+    };
+    if let Err(e) = state.job_sender.send(job) {
+        error!("Failed to enqueue Jira issue job: {}", e);
+    }
+}
+
+/// Create the ticket, store its key on the action item, and announce it in
+/// the incident channel.
+pub async fn execute(
+    jira_client: &JiraClient,
+    state: &AppState,
+    action_item_id: uuid::Uuid,
+    project_key: String,
+) -> IncidentResult<()> {
+    let item = action_item_queries::get_action_item(&state.pool, action_item_id)
+        .await?
+        .ok_or(IncidentError::NotFound)?;
+    if item.jira_issue_key.is_some() {
+        return Ok(());
+    }
+    let incident = IncidentService::new(state.pool.clone())
+        .get_by_id(item.incident_id)
+        .await?;
+
+    let description = format!(
+        "Follow-up from {} incident \"{}\" ({}, declared {}). Owner in Slack: {}.",
+        incident.severity_level().label,
+        incident.title,
+        incident.affected_service,
+        time::date(&incident.declared_at),
+        item.owner_id
+    );
+    let issue_key = jira_client
+        .create_issue(&project_key, &item.description, &description)
+        .await?;
+    action_item_queries::set_jira_issue_key(&state.pool, item.id, &issue_key).await?;
+
+    if let Some(channel_id) = &incident.slack_channel_id {
+        if let Err(e) = state
+            .slack_client
+            .post_message(
+                channel_id,
+                blocks::jira_issue_created_blocks(
+                    &item,
+                    &issue_key,
+                    &jira_client.issue_url(&issue_key),
+                ),
+            )
+            .await
+        {
+            error!("Failed to announce Jira issue {}: {}", issue_key, e);
+        }
+    }
+
+    info!(
+        "Jira issue {} created for action item #{} of incident {}",
+        issue_key, item.number, incident.id
+    );
+    Ok(())
+}
@@ -1,7 +1,28 @@
+pub mod burndown;
+pub mod channel_archive;
+pub mod channel_status;
+pub mod coaching_reports;
+pub mod commander_escalation;
+pub mod conference_bridge;
+pub mod deactivated_users;
+pub mod deferred_notifications;
+pub mod exec_tiers;
+pub mod jira_sync;
+pub mod paging_test;
+pub mod partner_mirror;
+pub mod postmortem_reminder;
+pub mod resolution_snapshot;
+pub mod role_reminder;
+pub mod scorecards;
+pub mod sla_checks;
+pub mod stale_reminder;
+pub mod statuspage_retry;
 pub mod statuspage_sync;
+pub mod weekly_report;
 pub mod worker;
 
-use crate::db::models::{IncidentId, IncidentStatus, Severity};
+use crate::db::models::{IncidentId, IncidentStatus, Severity, WebhookEvent};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -12,4 +33,59 @@ pub enum Job {
         status: IncidentStatus,
         severity: Severity,
     },
+    StatuspageIncidentCreate {
+        incident_id: IncidentId,
+    },
+    StatuspageIncidentUpdate {
+        incident_id: IncidentId,
+        message: String,
+    },
+    StatuspageIncidentResolve {
+        incident_id: IncidentId,
+    },
+    StaleIncidentReminder {
+        incident_id: IncidentId,
+        last_activity_at: DateTime<Utc>,
+    },
+    CreateConferenceBridge {
+        incident_id: IncidentId,
+    },
+    CreateJiraIssue {
+        action_item_id: uuid::Uuid,
+        project_key: String,
+    },
+    DeliverWebhook {
+        webhook_id: uuid::Uuid,
+        event: WebhookEvent,
+        payload: serde_json::Value,
+    },
+    SyncChannelStatus {
+        incident_id: IncidentId,
+    },
+    SnapshotResolvedIncident {
+        incident_id: IncidentId,
+    },
+    /// The report on the week before `week_start` (a Monday)
+    WeeklyReport {
+        week_start: NaiveDate,
+    },
+}
+
+impl Job {
+    /// Incident the job is for, when it is tied to one.
+    pub fn incident_id(&self) -> Option<IncidentId> {
+        match self {
+            Job::StatuspageSync { incident_id, .. }
+            | Job::StatuspageIncidentCreate { incident_id }
+            | Job::StatuspageIncidentUpdate { incident_id, .. }
+            | Job::StatuspageIncidentResolve { incident_id }
+            | Job::StaleIncidentReminder { incident_id, .. }
+            | Job::CreateConferenceBridge { incident_id }
+            | Job::SyncChannelStatus { incident_id }
+            | Job::SnapshotResolvedIncident { incident_id } => Some(*incident_id),
+            Job::CreateJiraIssue { .. } | Job::DeliverWebhook { .. } | Job::WeeklyReport { .. } => {
+                None
+            }
+        }
+    }
 }
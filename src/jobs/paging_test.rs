@@ -0,0 +1,211 @@
+use crate::app_state::AppState;
+use crate::config::NotificationEvent;
+use crate::db::models::{PagingTest, Severity};
+use crate::db::queries::paging_tests;
+use crate::error::{IncidentError, IncidentResult};
+use crate::slack::blocks;
+use chrono::{DateTime, Datelike, Utc};
+use std::collections::HashSet;
+use std::time::Duration;
+use tracing::{error, info, warn};
+
+/// How often to check whether this month's test is due or a report is ready.
+const CHECK_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// Someone the test page goes to, and why they're on the chain.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChainMember {
+    pub recipient_id: String,
+    pub source: String,
+    /// Set when the recipient couldn't be resolved (user group lookup failed)
+    pub error: Option<String>,
+}
+
+/// Page the P1 escalation chain with a marked test once a month on
+/// `PAGING_TEST_DAY`, then report who acknowledged and how quickly.
+pub async fn run(state: AppState) {
+    if state.config.paging_test_day == 0 {
+        info!("Paging tests disabled");
+        return;
+    }
+    let mut interval = tokio::time::interval(CHECK_INTERVAL);
+    info!(
+        "Monthly paging test started (day {})",
+        state.config.paging_test_day
+    );
+
+    loop {
+        interval.tick().await;
+        let now = Utc::now();
+        if let Err(e) = start_due_test(&state, now).await {
+            error!("Paging test failed to start: {}", e);
+        }
+        if let Err(e) = report_finished_tests(&state, now).await {
+            error!("Paging test report failed: {}", e);
+        }
+    }
+}
+
+/// Everyone a P1 may DM (`EXEC_TIERS`/`P1_USERS` and `NOTIFICATION_RULES`,
+/// with user groups expanded), then `BACKUP_COMMANDERS`, each once.
+pub async fn escalation_chain(state: &AppState) -> Vec<ChainMember> {
+    let rule = state
+        .config
+        .notification_rule_for(Severity::P1, NotificationEvent::Declared);
+    let tiers = state.config.exec_tiers_for(Severity::P1);
+    let mut users: Vec<(&String, String)> = Vec::new();
+    let mut groups: Vec<&String> = Vec::new();
+    for (tier, _, recipients) in tiers.tiers() {
+        let source = format!("P1 exec tier {}", tier);
+        users.extend(recipients.users.iter().map(|u| (u, source.clone())));
+        groups.extend(&recipients.user_groups);
+    }
+    users.extend(rule.users.iter().map(|u| (u, "P1 DM".to_string())));
+    groups.extend(&rule.user_groups);
+
+    let mut chain: Vec<ChainMember> = users
+        .into_iter()
+        .map(|(user_id, source)| ChainMember {
+            recipient_id: user_id.clone(),
+            source,
+            error: None,
+        })
+        .collect();
+    for group_id in groups {
+        let source = format!("<!subteam^{}>", group_id);
+        match state.slack_client.usergroup_members(group_id).await {
+            Ok(members) => chain.extend(members.into_iter().map(|user_id| ChainMember {
+                recipient_id: user_id,
+                source: source.clone(),
+                error: None,
+            })),
+            Err(e) => chain.push(ChainMember {
+                recipient_id: group_id.clone(),
+                source,
+                error: Some(slack_error_code(&e)),
+            }),
+        }
+    }
+    chain.extend(
+        state
+            .config
+            .backup_commanders
+            .iter()
+            .map(|user_id| ChainMember {
+                recipient_id: user_id.clone(),
+                source: "backup commander".to_string(),
+                error: None,
+            }),
+    );
+
+    let mut seen = HashSet::new();
+    chain.retain(|member| seen.insert(member.recipient_id.clone()));
+    chain
+}
+
+/// Send this month's test page if `PAGING_TEST_DAY` has come and it hasn't
+/// been sent yet. Each page and its delivery error is recorded. Returns the
+/// test started, if any.
+pub async fn start_due_test(
+    state: &AppState,
+    now: DateTime<Utc>,
+) -> IncidentResult<Option<PagingTest>> {
+    let day = state.config.paging_test_day;
+    if day == 0 || now.day() < day {
+        return Ok(None);
+    }
+
+    let month = now.date_naive().with_day(1).expect("day 1 always exists");
+    let Some(test) = paging_tests::claim_paging_test(&state.pool, month, now).await? else {
+        return Ok(None);
+    };
+
+    // An empty chain is still reported: nobody would be paged for a P1
+    let chain = escalation_chain(state).await;
+    if chain.is_empty() {
+        warn!("Paging test found an empty P1 escalation chain");
+    }
+
+    let mut failed = 0;
+    for member in &chain {
+        let delivery_error = match &member.error {
+            Some(error) => Some(error.clone()),
+            None => state
+                .slack_client
+                .send_dm(
+                    &member.recipient_id,
+                    blocks::paging_test_page_blocks(&test, &member.source),
+                )
+                .await
+                .err()
+                .map(|e| slack_error_code(&e)),
+        };
+        if let Some(error) = &delivery_error {
+            warn!(
+                "Paging test page to {} failed: {}",
+                member.recipient_id, error
+            );
+            failed += 1;
+        }
+        paging_tests::record_page(
+            &state.pool,
+            test.id,
+            &member.recipient_id,
+            &member.source,
+            now,
+            delivery_error.as_deref(),
+        )
+        .await?;
+    }
+
+    info!(
+        "Paging test for {} sent to {} recipient(s), {} failed",
+        month.format("%B %Y"),
+        chain.len(),
+        failed
+    );
+    Ok(Some(test))
+}
+
+/// Report every test whose acknowledgement window has passed to
+/// `PAGING_TEST_CHANNEL`, or to `ADMIN_USERS` by DM. Returns the number of
+/// tests reported.
+pub async fn report_finished_tests(state: &AppState, now: DateTime<Utc>) -> IncidentResult<usize> {
+    let ack_minutes = state.config.paging_test_ack_minutes;
+    let tests = paging_tests::claim_tests_to_report(
+        &state.pool,
+        now - chrono::Duration::minutes(ack_minutes as i64),
+        now,
+    )
+    .await?;
+
+    for test in &tests {
+        let pages = paging_tests::pages_for_test(&state.pool, test.id).await?;
+        let report = blocks::paging_test_report_blocks(test, &pages, ack_minutes);
+        match state.config.paging_test_channel.as_deref() {
+            Some(channel_id) => {
+                if let Err(e) = state.slack_client.post_message(channel_id, report).await {
+                    error!("Failed to post paging test report: {}", e);
+                }
+            }
+            None => {
+                for admin in &state.config.admin_users {
+                    if let Err(e) = state.slack_client.send_dm(admin, report.clone()).await {
+                        error!("Failed to DM paging test report to {}: {}", admin, e);
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(tests.len())
+}
+
+fn slack_error_code(e: &IncidentError) -> String {
+    match e {
+        IncidentError::SlackAPIError {
+            slack_error_code, ..
+        } => slack_error_code.clone(),
+        e => e.to_string(),
+    }
+}
@@ -0,0 +1,94 @@
+use crate::app_state::AppState;
+use crate::db::models::{Incident, IncidentId};
+use crate::db::queries::{incidents, partner_mirror};
+use crate::error::IncidentResult;
+use crate::slack::blocks;
+use crate::utils::redact::redact;
+use chrono::{DateTime, Utc};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::time::Duration;
+use tracing::{error, info};
+
+/// How often timeline events are checked for a partner update that's due.
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Events older than this (past the delay) are never mirrored, so turning the
+/// mirror on doesn't replay old incidents into partner channels.
+const MAX_BACKLOG_HOURS: i64 = 24;
+
+/// Mirror incident updates to `PARTNER_CHANNELS`, redacted and
+/// `partner_mirror_delay_minutes` after they were logged.
+pub async fn run(state: AppState) {
+    if state.config.partner_channels.is_empty() {
+        info!("Partner update mirror disabled");
+        return;
+    }
+    let mut interval = tokio::time::interval(CHECK_INTERVAL);
+    info!(
+        "Partner update mirror started ({} min delay)",
+        state.config.partner_mirror_delay_minutes
+    );
+
+    loop {
+        interval.tick().await;
+        if let Err(e) = mirror_once(&state, Utc::now()).await {
+            error!("Partner mirror pass failed: {}", e);
+        }
+    }
+}
+
+/// One mirror pass. Each event is claimed before it's posted, so it's posted
+/// once; a failed post releases the claim for the next pass. Returns the
+/// number of updates posted.
+pub async fn mirror_once(state: &AppState, now: DateTime<Utc>) -> IncidentResult<usize> {
+    let config = &state.config;
+    if config.partner_channels.is_empty() {
+        return Ok(0);
+    }
+    let services: Vec<String> = config.partner_channels.keys().cloned().collect();
+    let due_before = now - chrono::Duration::minutes(config.partner_mirror_delay_minutes as i64);
+    let since = due_before - chrono::Duration::hours(MAX_BACKLOG_HOURS);
+    let events =
+        partner_mirror::pending_partner_updates(&state.pool, &services, since, due_before).await?;
+
+    let mut incidents_by_id: HashMap<IncidentId, Incident> = HashMap::new();
+    let mut posted = 0;
+    for event in events {
+        let incident = match incidents_by_id.entry(event.incident_id) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                entry.insert(incidents::get_incident_by_id(&state.pool, event.incident_id).await?)
+            }
+        };
+        let Some(channel_id) = config.partner_channel_for(&incident.affected_service) else {
+            continue;
+        };
+        if !partner_mirror::claim_partner_update(&state.pool, event.id, channel_id, now).await? {
+            continue;
+        }
+
+        let domains = &config.partner_redact_domains;
+        let update = blocks::partner_update_blocks(
+            incident,
+            &event,
+            &redact(&incident.title, domains),
+            &redact(&event.message, domains),
+            config.partner_mirror_delay_minutes,
+        );
+        if let Err(e) = state.slack_client.post_message(channel_id, update).await {
+            error!(
+                "Failed to mirror update {} of incident {} to {}: {}",
+                event.id, incident.id, channel_id, e
+            );
+            partner_mirror::release_partner_update(&state.pool, event.id).await?;
+            continue;
+        }
+        posted += 1;
+    }
+
+    if posted > 0 {
+        info!("Mirrored {} incident updates to partner channels", posted);
+    }
+    Ok(posted)
+}
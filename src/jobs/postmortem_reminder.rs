@@ -0,0 +1,56 @@
+use crate::app_state::AppState;
+use crate::db::queries::postmortems;
+use crate::error::IncidentResult;
+use crate::slack::blocks;
+use chrono::{DateTime, Utc};
+use std::time::Duration;
+use tracing::{error, info};
+
+/// How often required postmortems are checked for a due reminder.
+const CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// DM commanders every `postmortem_reminder_hours` until their incident's
+/// required postmortem is published.
+pub async fn run(state: AppState) {
+    let mut interval = tokio::time::interval(CHECK_INTERVAL);
+    info!(
+        "Postmortem reminder started (every {} h)",
+        state.config.postmortem_reminder_hours
+    );
+
+    loop {
+        interval.tick().await;
+        if let Err(e) = remind_once(&state, Utc::now()).await {
+            error!("Postmortem reminder pass failed: {}", e);
+        }
+    }
+}
+
+/// One reminder pass. A postmortem is first nagged a full interval after it
+/// was required, since resolving already posted the draft and due date.
+/// Returns the number of reminders sent.
+pub async fn remind_once(state: &AppState, now: DateTime<Utc>) -> IncidentResult<usize> {
+    let interval = chrono::Duration::hours(state.config.postmortem_reminder_hours as i64);
+    let pending = postmortems::postmortems_to_remind(&state.pool, now - interval).await?;
+
+    let mut reminded = 0;
+    for pending in pending {
+        postmortems::mark_postmortem_reminded(&state.pool, pending.incident.id, now).await?;
+        match state
+            .slack_client
+            .send_dm(
+                &pending.incident.commander_id,
+                blocks::postmortem_reminder_blocks(&pending, now),
+            )
+            .await
+        {
+            Ok(()) => reminded += 1,
+            Err(e) => error!(
+                "Failed to send postmortem reminder for incident {}: {}",
+                pending.incident.id, e
+            ),
+        }
+    }
+
+    Ok(reminded)
+}
@@ -0,0 +1,105 @@
+use crate::app_state::AppState;
+use crate::db::models::{Incident, IncidentId};
+use crate::db::queries::{artifacts, notifications, timeline};
+use crate::error::{IncidentError, IncidentResult};
+use crate::jobs::Job;
+use crate::services::artifact_store::ArtifactService;
+use crate::services::audit::AuditService;
+use crate::services::incident::IncidentService;
+use chrono::Utc;
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use tracing::{error, info};
+
+pub const SNAPSHOT_ARTIFACT_KIND: &str = "resolution_snapshot";
+
+/// Bumped when the snapshot layout changes, so old records stay readable.
+const SNAPSHOT_FORMAT_VERSION: u32 = 1;
+
+/// Enqueue a snapshot of a just-resolved incident.
+pub fn enqueue(state: &AppState, incident: &Incident) {
+    let job = Job::SnapshotResolvedIncident {
+        incident_id: incident.id,
+    };
+    if let Err(e) = state.job_sender.send(job) {
+        error!("Failed to enqueue resolution snapshot job: {}", e);
+    }
+}
+
+/// Write a JSON export of the incident, its full timeline (internal events
+/// included) and its notifications to the artifact store, keyed by the
+/// resolution time. Each resolution is recorded once and never overwritten,
+/// so the snapshot stays the record of what was known at resolution however
+/// the incident is edited, redacted or reopened later. Its SHA-256 goes in
+/// the audit log. Returns whether a snapshot was written.
+pub async fn execute(state: &AppState, incident_id: IncidentId) -> IncidentResult<bool> {
+    let incident = IncidentService::new(state.pool.clone())
+        .get_by_id(incident_id)
+        .await?;
+    // Reopened before the job ran; the next resolution takes its own snapshot
+    let Some(resolved_at) = incident
+        .resolved_at
+        .filter(|_| incident.status.is_terminal())
+    else {
+        info!(
+            "Incident {} is no longer resolved; skipping snapshot",
+            incident.id
+        );
+        return Ok(false);
+    };
+
+    let key = format!(
+        "incidents/{}/resolution-snapshot-{}.json",
+        incident.id,
+        resolved_at.format("%Y%m%dT%H%M%SZ")
+    );
+    if artifacts::get_by_storage_key(&state.pool, &key)
+        .await?
+        .is_some()
+    {
+        return Ok(false);
+    }
+
+    let snapshot = json!({
+        "format_version": SNAPSHOT_FORMAT_VERSION,
+        "snapshot_at": Utc::now(),
+        "incident": incident,
+        "timeline": timeline::get_timeline(&state.pool, incident.id).await?,
+        "notifications": notifications::list_for_incident(&state.pool, incident.id).await?,
+    });
+    let bytes = serde_json::to_vec_pretty(&snapshot).map_err(|e| {
+        IncidentError::InternalError(format!("Failed to serialize snapshot: {}", e))
+    })?;
+    let sha256 = hex::encode(Sha256::digest(&bytes));
+
+    let artifact = ArtifactService::new(state.pool.clone(), state.artifact_store.clone())
+        .save(
+            Some(incident.id),
+            SNAPSHOT_ARTIFACT_KIND,
+            &key,
+            "application/json",
+            bytes,
+        )
+        .await?;
+
+    AuditService::new(state.pool.clone())
+        .log_action(
+            Some(incident.id),
+            "resolution_snapshot_saved".to_string(),
+            "system".to_string(),
+            None,
+            None,
+            Some(json!({
+                "storage_key": artifact.storage_key,
+                "size_bytes": artifact.size_bytes,
+                "sha256": sha256,
+            })),
+        )
+        .await?;
+
+    info!(
+        "Saved resolution snapshot of incident {} ({})",
+        incident.id, key
+    );
+    Ok(true)
+}
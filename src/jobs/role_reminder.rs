@@ -0,0 +1,60 @@
+use crate::app_state::AppState;
+use crate::db::queries::incidents::IncidentFilter;
+use crate::error::IncidentResult;
+use crate::services::incident::IncidentService;
+use chrono::{DateTime, Utc};
+use std::time::Duration;
+use tracing::{error, info};
+
+/// Re-post the role claim prompt every `role_reminder_minutes` in open incidents
+/// whose mandatory roles are still unfilled.
+pub async fn run(state: AppState) {
+    let period = Duration::from_secs(state.config.role_reminder_minutes * 60);
+    let mut interval = tokio::time::interval(period);
+    // The first tick completes immediately; declare already posts the initial prompt
+    interval.tick().await;
+
+    info!(
+        "Role reminder started (every {} min)",
+        state.config.role_reminder_minutes
+    );
+
+    loop {
+        interval.tick().await;
+        if let Err(e) = remind_once(&state, Utc::now()).await {
+            error!("Role reminder pass failed: {}", e);
+        }
+    }
+}
+
+/// One reminder pass. Incidents younger than the reminder interval are skipped
+/// so a fresh declaration isn't nagged twice in quick succession. Returns the
+/// number of reminders posted.
+pub async fn remind_once(state: &AppState, now: DateTime<Utc>) -> IncidentResult<usize> {
+    let filter = IncidentFilter {
+        open_only: true,
+        limit: 200,
+        ..Default::default()
+    };
+    let incidents = IncidentService::new(state.pool.clone())
+        .list(&filter)
+        .await?;
+
+    let min_age = chrono::Duration::minutes(state.config.role_reminder_minutes as i64);
+    let mut reminded = 0;
+    for incident in incidents
+        .iter()
+        .filter(|incident| incident.declared_at + min_age <= now)
+    {
+        match crate::commands::roles::prompt_unfilled_roles(state, incident).await {
+            Ok(true) => reminded += 1,
+            Ok(false) => {}
+            Err(e) => error!(
+                "Failed to post role reminder for incident {}: {}",
+                incident.id, e
+            ),
+        }
+    }
+
+    Ok(reminded)
+}
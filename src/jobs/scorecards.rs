@@ -0,0 +1,100 @@
+use crate::app_state::AppState;
+use crate::db::queries::analytics;
+use crate::error::IncidentResult;
+use crate::services::analytics::AnalyticsService;
+use crate::slack::blocks;
+use chrono::{DateTime, Datelike, Months, NaiveDate, Utc};
+use std::time::Duration;
+use tracing::{error, info};
+
+/// How often to check whether last month's scorecards still need sending.
+const CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Send each configured team's scorecard for the previous month to its leads,
+/// shortly after the month rolls over.
+pub async fn run(state: AppState) {
+    if state.config.teams.is_empty() {
+        return;
+    }
+    let mut interval = tokio::time::interval(CHECK_INTERVAL);
+    info!("Monthly scorecards started");
+
+    loop {
+        interval.tick().await;
+        if let Err(e) = send_monthly_scorecards(&state, Utc::now()).await {
+            error!("Monthly scorecard pass failed: {}", e);
+        }
+    }
+}
+
+/// The calendar month before the one containing `now`, as `[start, end)`.
+pub fn previous_month(now: DateTime<Utc>) -> (NaiveDate, NaiveDate) {
+    let end = now.date_naive().with_day(1).expect("day 1 always exists");
+    let start = end - Months::new(1);
+    (start, end)
+}
+
+/// Send last month's scorecard for every team that hasn't had one yet.
+/// Teams without leads are skipped. Returns the number of teams sent.
+pub async fn send_monthly_scorecards(
+    state: &AppState,
+    now: DateTime<Utc>,
+) -> IncidentResult<usize> {
+    let (period_start, period_end) = previous_month(now);
+    let analytics_service = AnalyticsService::new(state.pool.clone());
+
+    let mut sent = 0;
+    for (team, config) in &state.config.teams {
+        if config.leads.is_empty() {
+            continue;
+        }
+        if !analytics::claim_scorecard_run(&state.pool, period_start, team).await? {
+            continue;
+        }
+
+        let scorecard = analytics_service
+            .scorecard(team, config, period_start, period_end)
+            .await?;
+        let scorecard_blocks = blocks::scorecard_blocks(&scorecard);
+        for lead in &config.leads {
+            if let Err(e) = state
+                .slack_client
+                .send_dm(lead, scorecard_blocks.clone())
+                .await
+            {
+                error!(
+                    "Failed to DM scorecard for team {} to {}: {}",
+                    team, lead, e
+                );
+            }
+        }
+        sent += 1;
+    }
+
+    if sent > 0 {
+        info!(
+            "Sent {} team scorecard(s) for {}",
+            sent,
+            period_start.format("%B %Y")
+        );
+    }
+    Ok(sent)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_previous_month_crosses_year_boundary() {
+        let now = Utc.with_ymd_and_hms(2026, 1, 1, 0, 30, 0).unwrap();
+        assert_eq!(
+            previous_month(now),
+            (
+                NaiveDate::from_ymd_opt(2025, 12, 1).unwrap(),
+                NaiveDate::from_ymd_opt(2026, 1, 1).unwrap()
+            )
+        );
+    }
+}
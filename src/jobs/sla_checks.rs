@@ -0,0 +1,175 @@
+use crate::app_state::AppState;
+use crate::config::SlaTarget;
+use crate::db::models::SlaMetric;
+use crate::db::queries::{incidents, sla};
+use crate::error::IncidentResult;
+use crate::slack::blocks;
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use std::time::Duration;
+use tracing::{error, info};
+
+/// How often open incidents are checked against their SLA targets.
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Periodically check open incidents against `SLA` and warn in the
+/// incident channel when one misses a target.
+pub async fn run(state: AppState) {
+    let mut interval = tokio::time::interval(CHECK_INTERVAL);
+    info!("SLA checks started");
+
+    loop {
+        interval.tick().await;
+        if let Err(e) = check_slas(&state, Utc::now()).await {
+            error!("SLA check failed: {}", e);
+        }
+    }
+}
+
+/// One pass. Each missed deadline is recorded once, and only newly recorded
+/// breaches are posted. Returns the number of new breaches.
+pub async fn check_slas(state: &AppState, now: DateTime<Utc>) -> IncidentResult<usize> {
+    if state.config.sla.is_empty() {
+        return Ok(0);
+    }
+
+    let mut recorded = 0;
+    for incident in incidents::list_open_incidents(&state.pool).await? {
+        let Some(target) = state.config.sla_for(incident.severity) else {
+            continue;
+        };
+        let (first_update, last_update) =
+            sla::status_update_bounds(&state.pool, incident.id).await?;
+
+        for (metric, target_minutes, deadline) in
+            due_breaches(incident.declared_at, target, first_update, last_update, now)
+        {
+            let is_new = sla::record_breach(
+                &state.pool,
+                incident.id,
+                incident.severity,
+                metric,
+                target_minutes as i32,
+                deadline,
+                now,
+            )
+            .await?;
+            if !is_new {
+                continue;
+            }
+            recorded += 1;
+            info!(
+                "Incident {} breached its {} SLA ({} min)",
+                incident.id,
+                metric.as_db_str(),
+                target_minutes
+            );
+
+            if let Some(channel_id) = &incident.slack_channel_id {
+                if let Err(e) = state
+                    .slack_client
+                    .post_message(
+                        channel_id,
+                        blocks::sla_breach_blocks(&incident, metric, target_minutes, deadline),
+                    )
+                    .await
+                {
+                    error!(
+                        "Failed to post SLA breach for incident {}: {}",
+                        incident.id, e
+                    );
+                }
+            }
+        }
+    }
+
+    Ok(recorded)
+}
+
+/// Targets an incident declared at `declared_at` has missed as of `now`, with their deadlines.
+///
+/// - ack: the first status update is due `ack_minutes` after declaration.
+/// - update: once acknowledged, the next update is due `update_minutes`
+///   after the latest one. Each missed gap has its own deadline.
+/// - resolve: resolution is due `resolve_minutes` after declaration.
+pub fn due_breaches(
+    declared_at: DateTime<Utc>,
+    target: SlaTarget,
+    first_update: Option<DateTime<Utc>>,
+    last_update: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+) -> Vec<(SlaMetric, i64, DateTime<Utc>)> {
+    let after = |start: DateTime<Utc>, minutes: u64| {
+        (
+            minutes as i64,
+            start + ChronoDuration::minutes(minutes as i64),
+        )
+    };
+    let mut due = Vec::new();
+
+    if let Some(minutes) = target.ack_minutes {
+        let (minutes, deadline) = after(declared_at, minutes);
+        let acked_in_time = first_update.is_some_and(|at| at <= deadline);
+        if !acked_in_time && now > deadline {
+            due.push((SlaMetric::Ack, minutes, deadline));
+        }
+    }
+    if let (Some(minutes), Some(last_update)) = (target.update_minutes, last_update) {
+        let (minutes, deadline) = after(last_update, minutes);
+        if now > deadline {
+            due.push((SlaMetric::Update, minutes, deadline));
+        }
+    }
+    if let Some(minutes) = target.resolve_minutes {
+        let (minutes, deadline) = after(declared_at, minutes);
+        if now > deadline {
+            due.push((SlaMetric::Resolve, minutes, deadline));
+        }
+    }
+
+    due
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_due_breaches() {
+        let declared = Utc.with_ymd_and_hms(2024, 11, 15, 10, 0, 0).unwrap();
+        let at = |minutes: i64| declared + ChronoDuration::minutes(minutes);
+        let target = SlaTarget {
+            ack_minutes: Some(5),
+            update_minutes: Some(30),
+            resolve_minutes: Some(240),
+        };
+
+        // Nothing due yet
+        assert!(due_breaches(declared, target, None, None, at(5)).is_empty());
+        // No update by the ack deadline
+        assert_eq!(
+            due_breaches(declared, target, None, None, at(6)),
+            vec![(SlaMetric::Ack, 5, at(5))]
+        );
+        // A late first update still counts as a missed ack
+        assert_eq!(
+            due_breaches(declared, target, Some(at(8)), Some(at(8)), at(9)),
+            vec![(SlaMetric::Ack, 5, at(5))]
+        );
+        // Update cadence runs from the latest update
+        assert!(due_breaches(declared, target, Some(at(2)), Some(at(20)), at(50)).is_empty());
+        assert_eq!(
+            due_breaches(declared, target, Some(at(2)), Some(at(20)), at(51)),
+            vec![(SlaMetric::Update, 30, at(50))]
+        );
+        // Unset targets aren't tracked
+        let resolve_only = SlaTarget {
+            resolve_minutes: Some(240),
+            ..Default::default()
+        };
+        assert_eq!(
+            due_breaches(declared, resolve_only, None, None, at(241)),
+            vec![(SlaMetric::Resolve, 240, at(240))]
+        );
+    }
+}
@@ -0,0 +1,105 @@
+use crate::app_state::AppState;
+use crate::db::models::{IncidentId, Severity};
+use crate::db::queries::incidents;
+use crate::error::IncidentResult;
+use crate::jobs::Job;
+use crate::services::incident::IncidentService;
+use crate::slack::blocks;
+use chrono::{DateTime, Utc};
+use serde_json::{Map, Value};
+use std::time::Duration;
+use tracing::{error, info};
+
+/// How often open incidents are checked against `STALE_INCIDENT_MINUTES`.
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Periodically enqueue `Job::StaleIncidentReminder` for open incidents with
+/// no timeline event within their severity's threshold.
+pub async fn run(state: AppState) {
+    let mut interval = tokio::time::interval(CHECK_INTERVAL);
+    info!("Stale incident reminder started");
+
+    loop {
+        interval.tick().await;
+        if let Err(e) = enqueue_stale(&state, Utc::now()).await {
+            error!("Stale incident check failed: {}", e);
+        }
+    }
+}
+
+/// One scheduler pass. Each stale incident is marked as reminded before its
+/// job is queued, so the clock restarts and it is nudged at most once per
+/// threshold. Returns the number of jobs queued.
+pub async fn enqueue_stale(state: &AppState, now: DateTime<Utc>) -> IncidentResult<usize> {
+    let thresholds: Map<String, Value> = [Severity::P1, Severity::P2, Severity::P3, Severity::P4]
+        .into_iter()
+        .filter_map(|severity| {
+            state
+                .config
+                .stale_threshold_for(severity)
+                .map(|minutes| (severity.as_db_str().to_string(), Value::from(minutes)))
+        })
+        .collect();
+    if thresholds.is_empty() {
+        return Ok(0);
+    }
+
+    let stale = incidents::stale_incidents(&state.pool, &Value::Object(thresholds), now).await?;
+    let mut queued = 0;
+    for (incident_id, last_activity_at) in stale {
+        incidents::mark_stale_reminded(&state.pool, incident_id, now).await?;
+        match state.job_sender.send(Job::StaleIncidentReminder {
+            incident_id,
+            last_activity_at,
+        }) {
+            Ok(()) => queued += 1,
+            Err(e) => error!(
+                "Failed to enqueue stale reminder for incident {}: {}",
+                incident_id, e
+            ),
+        }
+    }
+
+    Ok(queued)
+}
+
+/// DM the commander and post in the incident channel. Skips incidents
+/// resolved since the job was queued.
+pub async fn execute(
+    state: &AppState,
+    incident_id: IncidentId,
+    last_activity_at: DateTime<Utc>,
+) -> IncidentResult<()> {
+    let incident = IncidentService::new(state.pool.clone())
+        .get_by_id(incident_id)
+        .await?;
+    if incident.status.is_terminal() {
+        return Ok(());
+    }
+
+    let idle_minutes = (Utc::now() - last_activity_at).num_minutes();
+    let reminder = blocks::stale_incident_blocks(&incident, idle_minutes);
+
+    if let Some(channel_id) = &incident.slack_channel_id {
+        if let Err(e) = state
+            .slack_client
+            .post_message(channel_id, reminder.clone())
+            .await
+        {
+            error!(
+                "Failed to post stale reminder for incident {}: {}",
+                incident.id, e
+            );
+        }
+    }
+    state
+        .slack_client
+        .send_dm(&incident.commander_id, reminder)
+        .await?;
+
+    info!(
+        "Stale reminder sent for incident {} (idle {} min)",
+        incident.id, idle_minutes
+    );
+    Ok(())
+}
@@ -0,0 +1,107 @@
+use crate::adapters::statuspage::{self, StatuspageClient};
+use crate::app_state::AppState;
+use crate::db::queries::failed_jobs;
+use crate::db::queries::incidents as incident_queries;
+use crate::error::IncidentResult;
+use crate::jobs::statuspage_sync;
+use crate::jobs::Job;
+use std::time::Duration;
+use tracing::{error, info, warn};
+
+/// How often deferred Statuspage jobs are retried.
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Retry Statuspage jobs deferred while Statuspage was unavailable.
+pub async fn run(state: AppState, statuspage_client: Option<StatuspageClient>) {
+    let Some(statuspage_client) = statuspage_client else {
+        return;
+    };
+    let mut interval = tokio::time::interval(CHECK_INTERVAL);
+    info!("Deferred Statuspage job retries started");
+
+    loop {
+        interval.tick().await;
+        match retry_once(&state, &statuspage_client).await {
+            Ok(0) => {}
+            Ok(retried) => info!("Retried {} deferred Statuspage jobs", retried),
+            Err(e) => error!("Statuspage retry pass failed: {}", e),
+        }
+    }
+}
+
+/// One retry pass, oldest job first so updates reach Statuspage in the order
+/// they were made. Nothing is tried while the circuit breaker is open, and
+/// the pass stops at the first job that finds Statuspage still unavailable.
+/// Jobs Statuspage rejects are dead-lettered. Returns the number of jobs that
+/// ran.
+pub async fn retry_once(
+    state: &AppState,
+    statuspage_client: &StatuspageClient,
+) -> IncidentResult<usize> {
+    let mut retried = 0;
+    while !statuspage_client.is_circuit_open() {
+        let Some(failed) = failed_jobs::take_oldest_failed_job(&state.pool).await? else {
+            break;
+        };
+        let job = match serde_json::from_value::<Job>(failed.job.clone()) {
+            Ok(job) => job,
+            Err(e) => {
+                error!(
+                    "Dead-lettering unreadable deferred job {}: {}",
+                    failed.id, e
+                );
+                failed_jobs::dead_letter_failed_job(&state.pool, &failed, &e.to_string()).await?;
+                continue;
+            }
+        };
+
+        let result = match current(state, job).await {
+            Ok(job) => statuspage_sync::execute_job(statuspage_client, &state.pool, &job).await,
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(()) => retried += 1,
+            Err(e) if statuspage::is_unavailable(&e) => {
+                warn!(
+                    "Statuspage still unavailable (job {}, attempt {}): {}",
+                    failed.id,
+                    failed.attempts + 1,
+                    e
+                );
+                failed_jobs::restore_failed_job(&state.pool, &failed, &e.to_string()).await?;
+                break;
+            }
+            Err(e) => {
+                error!(
+                    "Dead-lettering deferred Statuspage job {} after {} attempts: {}",
+                    failed.id,
+                    failed.attempts + 1,
+                    e
+                );
+                failed_jobs::dead_letter_failed_job(&state.pool, &failed, &e.to_string()).await?;
+            }
+        }
+    }
+    Ok(retried)
+}
+
+/// Component syncs are replayed with the incident's current status and
+/// severity, so a late retry can't roll the component back.
+async fn current(state: &AppState, job: Job) -> IncidentResult<Job> {
+    match job {
+        Job::StatuspageSync {
+            incident_id,
+            component_id,
+            ..
+        } => {
+            let incident = incident_queries::get_incident_by_id(&state.pool, incident_id).await?;
+            Ok(Job::StatuspageSync {
+                incident_id,
+                component_id,
+                status: incident.status,
+                severity: incident.severity,
+            })
+        }
+        job => Ok(job),
+    }
+}
@@ -1,7 +1,198 @@
-use crate::adapters::statuspage::StatuspageClient;
-use crate::db::models::{IncidentId, IncidentStatus, Severity};
-use crate::error::IncidentResult;
-use tracing::{error, info};
+use crate::adapters::statuspage::{self, StatuspageClient};
+use crate::db::models::{Incident, IncidentId, IncidentStatus, Severity};
+use crate::db::queries::failed_jobs;
+use crate::db::queries::incidents as incident_queries;
+use crate::db::queries::statuspage as statuspage_queries;
+use crate::error::{IncidentError, IncidentResult};
+use crate::jobs::Job;
+use sqlx_postgres::PgPool;
+use tokio::sync::mpsc;
+use tracing::{error, info, warn};
+
+/// Enqueue a Statuspage sync if the incident's service has a component mapping.
+/// Quiet incidents are never synced.
+/// Best-effort: lookup and enqueue failures are logged, never returned.
+pub async fn enqueue_for_incident(
+    pool: &PgPool,
+    job_sender: &mpsc::UnboundedSender<Job>,
+    incident: &Incident,
+) {
+    if incident.is_quiet {
+        return;
+    }
+    if let Ok(Some(component_id)) =
+        crate::db::queries::statuspage::get_component_id(pool, &incident.affected_service).await
+    {
+        let job = Job::StatuspageSync {
+            incident_id: incident.id,
+            component_id,
+            status: incident.status,
+            severity: incident.severity,
+        };
+
+        if let Err(e) = job_sender.send(job) {
+            error!("Failed to enqueue Statuspage sync job: {}", e);
+        }
+    }
+}
+
+/// Enqueue opening a Statuspage incident for a newly declared incident whose
+/// service has a component mapping. Quiet incidents are never published.
+pub async fn enqueue_incident_create(
+    pool: &PgPool,
+    job_sender: &mpsc::UnboundedSender<Job>,
+    incident: &Incident,
+) {
+    if incident.is_quiet {
+        return;
+    }
+    if let Ok(Some(_)) =
+        statuspage_queries::get_component_id(pool, &incident.affected_service).await
+    {
+        let job = Job::StatuspageIncidentCreate {
+            incident_id: incident.id,
+        };
+
+        if let Err(e) = job_sender.send(job) {
+            error!("Failed to enqueue Statuspage incident create job: {}", e);
+        }
+    }
+}
+
+/// Enqueue a customer-facing update for the public status page. Internal
+/// updates never reach this path.
+pub fn enqueue_public_update(
+    job_sender: &mpsc::UnboundedSender<Job>,
+    incident: &Incident,
+    message: &str,
+) {
+    if incident.is_quiet {
+        return;
+    }
+    let job = Job::StatuspageIncidentUpdate {
+        incident_id: incident.id,
+        message: message.to_string(),
+    };
+
+    if let Err(e) = job_sender.send(job) {
+        error!("Failed to enqueue Statuspage public update job: {}", e);
+    }
+}
+
+/// Enqueue the templated public message for a status change, resolving the
+/// Statuspage incident on a terminal status. Incidents without an open
+/// Statuspage incident are skipped.
+pub fn enqueue_status_change(job_sender: &mpsc::UnboundedSender<Job>, incident: &Incident) {
+    if incident.is_quiet || incident.statuspage_incident_id.is_none() {
+        return;
+    }
+    let job = if incident.status.is_terminal() {
+        Job::StatuspageIncidentResolve {
+            incident_id: incident.id,
+        }
+    } else {
+        Job::StatuspageIncidentUpdate {
+            incident_id: incident.id,
+            message: public_message(
+                incident.severity,
+                incident.status,
+                &incident.affected_service,
+            ),
+        }
+    };
+
+    if let Err(e) = job_sender.send(job) {
+        error!("Failed to enqueue Statuspage status change job: {}", e);
+    }
+}
+
+/// Customer-facing wording for `status`, scaled to the severity's impact.
+pub fn public_message(severity: Severity, status: IncidentStatus, service: &str) -> String {
+    let issue = match severity {
+        Severity::P1 => "a major outage",
+        Severity::P2 => "a partial outage",
+        Severity::P3 | Severity::P4 => "degraded performance",
+    };
+    match status {
+        IncidentStatus::Triaging | IncidentStatus::Declared | IncidentStatus::Investigating => {
+            format!(
+                "We are investigating {} affecting {}. We will post updates as we learn more.",
+                issue, service
+            )
+        }
+        IncidentStatus::Identified => format!(
+            "We have identified the cause of {} affecting {} and are working on a fix.",
+            issue, service
+        ),
+        IncidentStatus::Monitoring => format!(
+            "A fix for {} affecting {} has been deployed and we are monitoring the results.",
+            issue, service
+        ),
+        IncidentStatus::Resolved => format!(
+            "This incident has been resolved and {} is operating normally.",
+            service
+        ),
+        IncidentStatus::Canceled => format!(
+            "This turned out to be a false alarm: {} was not affected.",
+            service
+        ),
+    }
+}
+
+/// Run a Statuspage job. If Statuspage is unavailable (after retries, or
+/// with the circuit breaker open) the job is stored in `failed_jobs` for
+/// `statuspage_retry` to run once it recovers; other failures are returned.
+pub async fn execute_or_defer(
+    statuspage_client: &StatuspageClient,
+    pool: &PgPool,
+    job: &Job,
+) -> IncidentResult<()> {
+    match execute_job(statuspage_client, pool, job).await {
+        Err(e) if statuspage::is_unavailable(&e) => {
+            warn!("Deferring Statuspage job until it recovers: {}", e);
+            let value = serde_json::to_value(job)
+                .map_err(|e| IncidentError::InternalError(e.to_string()))?;
+            failed_jobs::record_failed_job(pool, job.incident_id(), &value, &e.to_string()).await
+        }
+        result => result,
+    }
+}
+
+/// Run one of the Statuspage job variants; other jobs are ignored.
+pub async fn execute_job(
+    statuspage_client: &StatuspageClient,
+    pool: &PgPool,
+    job: &Job,
+) -> IncidentResult<()> {
+    match job {
+        Job::StatuspageSync {
+            incident_id,
+            component_id,
+            status,
+            severity,
+        } => {
+            execute(
+                statuspage_client,
+                *incident_id,
+                component_id.clone(),
+                *status,
+                *severity,
+            )
+            .await
+        }
+        Job::StatuspageIncidentCreate { incident_id } => {
+            execute_incident_create(statuspage_client, pool, *incident_id).await
+        }
+        Job::StatuspageIncidentUpdate {
+            incident_id,
+            message,
+        } => execute_incident_update(statuspage_client, pool, *incident_id, message.clone()).await,
+        Job::StatuspageIncidentResolve { incident_id } => {
+            execute_incident_resolve(statuspage_client, pool, *incident_id).await
+        }
+        _ => Ok(()),
+    }
+}
 
 pub async fn execute(
     statuspage_client: &StatuspageClient,
@@ -15,22 +206,135 @@ pub async fn execute(
         incident_id, component_id, status, severity
     );
 
-    match statuspage_client
+    statuspage_client
         .update_component_status(&component_id, status, severity)
-        .await
-    {
-        Ok(_) => {
-            info!("Successfully synced incident {} to Statuspage", incident_id);
-            Ok(())
-        }
-        Err(e) => {
-            error!(
-                "Failed to sync incident {} to Statuspage: {}",
-                incident_id, e
-            );
-            // Don't propagate error - log and continue
-            // Statuspage sync is best-effort
-            Ok(())
-        }
+        .await?;
+    info!("Successfully synced incident {} to Statuspage", incident_id);
+    Ok(())
+}
+
+/// Open the Statuspage incident with the templated message for the
+/// incident's severity. Skipped if one is already open, e.g. from a public
+/// update that ran first.
+pub async fn execute_incident_create(
+    statuspage_client: &StatuspageClient,
+    pool: &PgPool,
+    incident_id: IncidentId,
+) -> IncidentResult<()> {
+    let incident = incident_queries::get_incident_by_id(pool, incident_id).await?;
+    if incident.statuspage_incident_id.is_some() {
+        return Ok(());
+    }
+
+    let message = public_message(
+        incident.severity,
+        incident.status,
+        &incident.affected_service,
+    );
+    open_incident(statuspage_client, pool, &incident, &message).await
+}
+
+/// Post an update, opening the Statuspage incident on first use.
+pub async fn execute_incident_update(
+    statuspage_client: &StatuspageClient,
+    pool: &PgPool,
+    incident_id: IncidentId,
+    message: String,
+) -> IncidentResult<()> {
+    let incident = incident_queries::get_incident_by_id(pool, incident_id).await?;
+    let Some(statuspage_incident_id) = &incident.statuspage_incident_id else {
+        return open_incident(statuspage_client, pool, &incident, &message).await;
+    };
+
+    statuspage_client
+        .update_incident(statuspage_incident_id, incident.status, &message)
+        .await?;
+    info!(
+        "Published public update for incident {} to Statuspage incident {}",
+        incident_id, statuspage_incident_id
+    );
+    Ok(())
+}
+
+/// Resolve the Statuspage incident with the templated closing message (a
+/// false alarm note for canceled incidents).
+pub async fn execute_incident_resolve(
+    statuspage_client: &StatuspageClient,
+    pool: &PgPool,
+    incident_id: IncidentId,
+) -> IncidentResult<()> {
+    let incident = incident_queries::get_incident_by_id(pool, incident_id).await?;
+    let Some(statuspage_incident_id) = &incident.statuspage_incident_id else {
+        return Ok(());
+    };
+
+    let closing = match incident.status {
+        IncidentStatus::Canceled => IncidentStatus::Canceled,
+        _ => IncidentStatus::Resolved,
+    };
+    let message = public_message(incident.severity, closing, &incident.affected_service);
+    statuspage_client
+        .resolve_incident(statuspage_incident_id, &message)
+        .await?;
+    info!(
+        "Resolved Statuspage incident {} for incident {}",
+        statuspage_incident_id, incident_id
+    );
+    Ok(())
+}
+
+/// Create the Statuspage incident and remember its ID.
+async fn open_incident(
+    statuspage_client: &StatuspageClient,
+    pool: &PgPool,
+    incident: &Incident,
+    message: &str,
+) -> IncidentResult<()> {
+    let component_id =
+        match statuspage_queries::get_component_id(pool, &incident.affected_service).await {
+            Ok(component_id) => component_id,
+            Err(e) => {
+                error!("Failed to look up Statuspage component: {}", e);
+                None
+            }
+        };
+
+    let statuspage_incident_id = statuspage_client
+        .create_incident(
+            &incident.title,
+            incident.status,
+            incident.severity,
+            message,
+            component_id.as_deref(),
+        )
+        .await?;
+    statuspage_queries::set_statuspage_incident_id(pool, incident.id, &statuspage_incident_id)
+        .await?;
+    info!("Opened Statuspage incident for incident {}", incident.id);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_public_message_scales_with_severity() {
+        assert_eq!(
+            public_message(Severity::P1, IncidentStatus::Declared, "Checkout"),
+            "We are investigating a major outage affecting Checkout. We will post updates as we learn more."
+        );
+        assert!(
+            public_message(Severity::P3, IncidentStatus::Identified, "VPN")
+                .contains("degraded performance affecting VPN")
+        );
+        assert_eq!(
+            public_message(Severity::P2, IncidentStatus::Resolved, "VPN"),
+            "This incident has been resolved and VPN is operating normally."
+        );
+        assert_eq!(
+            public_message(Severity::P2, IncidentStatus::Canceled, "VPN"),
+            "This turned out to be a false alarm: VPN was not affected."
+        );
     }
 }
@@ -0,0 +1,69 @@
+use crate::app_state::AppState;
+use crate::db::queries::analytics;
+use crate::error::IncidentResult;
+use crate::jobs::burndown::week_start;
+use crate::jobs::Job;
+use crate::services::metrics::MetricsService;
+use crate::slack::blocks;
+use chrono::{DateTime, Datelike, NaiveDate, Utc, Weekday};
+use std::time::Duration;
+use tracing::{error, info};
+
+/// How often to check whether this week's report is due.
+const CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Queue `Job::WeeklyReport` once every Monday (UTC) while
+/// `WEEKLY_REPORT_CHANNEL` is set.
+pub async fn run(state: AppState) {
+    let mut interval = tokio::time::interval(CHECK_INTERVAL);
+    info!("Weekly report scheduler started");
+
+    loop {
+        interval.tick().await;
+        if let Err(e) = enqueue_due(&state, Utc::now()).await {
+            error!("Weekly report check failed: {}", e);
+        }
+    }
+}
+
+/// Queue this week's report if it's Monday and it hasn't been queued yet.
+/// A Monday the bot is down for is skipped rather than reported late.
+/// Returns whether a report was queued.
+pub async fn enqueue_due(state: &AppState, now: DateTime<Utc>) -> IncidentResult<bool> {
+    if state.config.weekly_report_channel.is_none() || now.weekday() != Weekday::Mon {
+        return Ok(false);
+    }
+    let week_start = week_start(now);
+    if !analytics::claim_weekly_report(&state.pool, week_start).await? {
+        return Ok(false);
+    }
+
+    match state.job_sender.send(Job::WeeklyReport { week_start }) {
+        Ok(()) => Ok(true),
+        Err(e) => {
+            error!(
+                "Failed to enqueue weekly report for week of {}: {}",
+                week_start, e
+            );
+            Ok(false)
+        }
+    }
+}
+
+/// Aggregate the week before `week_start` and post it to
+/// `WEEKLY_REPORT_CHANNEL`.
+pub async fn execute(state: &AppState, week_start: NaiveDate) -> IncidentResult<()> {
+    let Some(channel_id) = state.config.weekly_report_channel.as_deref() else {
+        return Ok(());
+    };
+
+    let report = MetricsService::new(state.pool.clone())
+        .weekly_report(week_start)
+        .await?;
+    state
+        .slack_client
+        .post_message(channel_id, blocks::weekly_report_blocks(&report))
+        .await?;
+    info!("Weekly report for week before {} posted", week_start);
+    Ok(())
+}
@@ -1,69 +1,250 @@
+use crate::adapters::conference::ConferenceClient;
+use crate::adapters::jira::JiraClient;
 use crate::adapters::statuspage::StatuspageClient;
+use crate::app_state::AppState;
 use crate::jobs::Job;
-use tokio::sync::mpsc;
-use tracing::{error, info};
+use crate::services::dead_letters;
+use crate::services::webhook::WebhookService;
+use crate::slack::client::RetryPolicy;
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::{self, JoinError, JoinSet};
+use tokio::time::Instant;
+use tracing::{error, info, warn};
+
+/// Error dead-lettered jobs interrupted by shutdown are stored with.
+pub const INTERRUPTED_BY_SHUTDOWN: &str =
+    "Interrupted by shutdown before it finished; it may have partly run";
 
 pub struct JobWorker {
     receiver: mpsc::UnboundedReceiver<Job>,
     statuspage_client: Option<StatuspageClient>,
+    jira_client: Option<JiraClient>,
+    conference_client: Option<ConferenceClient>,
+    state: AppState,
+    shutdown: Option<oneshot::Receiver<Instant>>,
 }
 
 impl JobWorker {
     pub fn new(
         receiver: mpsc::UnboundedReceiver<Job>,
         statuspage_client: Option<StatuspageClient>,
+        jira_client: Option<JiraClient>,
+        conference_client: Option<ConferenceClient>,
+        state: AppState,
     ) -> Self {
         Self {
             receiver,
             statuspage_client,
+            jira_client,
+            conference_client,
+            state,
+            shutdown: None,
         }
     }
 
+    /// Stop when `shutdown` sends a deadline: the queue is closed, queued
+    /// jobs are started, and running jobs get until the deadline to finish.
+    /// Any still running then are dead-lettered, to be retried with
+    /// `/incident jobs retry`.
+    pub fn with_shutdown(mut self, shutdown: oneshot::Receiver<Instant>) -> Self {
+        self.shutdown = Some(shutdown);
+        self
+    }
+
     pub async fn start(mut self) {
         info!("Job worker started");
 
-        while let Some(job) = self.receiver.recv().await {
-            // Spawn each job in a separate task to isolate panics and prevent worker death
-            let statuspage_client = self.statuspage_client.clone();
-            tokio::spawn(async move {
-                if let Err(e) = Self::process_job_static(statuspage_client, job).await {
-                    error!("Job processing error: {}", e);
+        let mut running = JoinSet::new();
+        let mut jobs = HashMap::new();
+        let shutdown = self.shutdown.take();
+        let shutdown = async move {
+            match shutdown {
+                Some(shutdown) => match shutdown.await {
+                    Ok(deadline) => deadline,
+                    Err(_) => std::future::pending().await,
+                },
+                None => std::future::pending().await,
+            }
+        };
+        tokio::pin!(shutdown);
+
+        let deadline = loop {
+            tokio::select! {
+                job = self.receiver.recv() => match job {
+                    Some(job) => self.spawn_job(&mut running, &mut jobs, job),
+                    None => break None,
+                },
+                Some(finished) = running.join_next_with_id() => {
+                    Self::forget_job(&mut jobs, finished);
                 }
-            });
+                deadline = &mut shutdown => break Some(deadline),
+            }
+        };
+
+        let Some(deadline) = deadline else {
+            // Every sender is gone; let running jobs finish on their own
+            running.detach_all();
+            info!("Job worker stopped");
+            return;
+        };
+
+        self.receiver.close();
+        let mut queued = 0;
+        while let Ok(job) = self.receiver.try_recv() {
+            self.spawn_job(&mut running, &mut jobs, job);
+            queued += 1;
+        }
+        info!(
+            "Job worker draining {} jobs ({} were queued)",
+            running.len(),
+            queued
+        );
+
+        let drained = tokio::time::timeout_at(deadline, async {
+            while let Some(finished) = running.join_next_with_id().await {
+                Self::forget_job(&mut jobs, finished);
+            }
+        })
+        .await;
+        if drained.is_err() {
+            running.abort_all();
+            warn!(
+                "{} jobs still running at the shutdown deadline; dead-lettering them",
+                jobs.len()
+            );
+            for job in jobs.into_values() {
+                dead_letters::record(&self.state.pool, &job, INTERRUPTED_BY_SHUTDOWN).await;
+            }
         }
 
         info!("Job worker stopped");
     }
 
+    fn spawn_job(&self, running: &mut JoinSet<()>, jobs: &mut HashMap<task::Id, Job>, job: Job) {
+        // Spawn each job in a separate task to isolate panics and prevent worker death
+        let statuspage_client = self.statuspage_client.clone();
+        let jira_client = self.jira_client.clone();
+        let conference_client = self.conference_client.clone();
+        let state = self.state.clone();
+        let task_job = job.clone();
+        let handle = running.spawn(async move {
+            if let Err(e) = Self::process_job_static(
+                statuspage_client,
+                jira_client,
+                conference_client,
+                state.clone(),
+                task_job.clone(),
+            )
+            .await
+            {
+                error!("Job processing error: {}", e);
+                dead_letters::record(&state.pool, &task_job, &e).await;
+            }
+        });
+        jobs.insert(handle.id(), job);
+    }
+
+    fn forget_job(jobs: &mut HashMap<task::Id, Job>, finished: Result<(task::Id, ()), JoinError>) {
+        let id = match finished {
+            Ok((id, ())) => id,
+            Err(e) => {
+                if e.is_panic() {
+                    error!("Job panicked: {}", e);
+                }
+                e.id()
+            }
+        };
+        jobs.remove(&id);
+    }
+
     async fn process_job_static(
         statuspage_client: Option<StatuspageClient>,
+        jira_client: Option<JiraClient>,
+        conference_client: Option<ConferenceClient>,
+        state: AppState,
         job: Job,
     ) -> Result<(), String> {
         match job {
-            Job::StatuspageSync {
+            Job::StatuspageSync { .. }
+            | Job::StatuspageIncidentCreate { .. }
+            | Job::StatuspageIncidentUpdate { .. }
+            | Job::StatuspageIncidentResolve { .. } => {
+                if let Some(client) = &statuspage_client {
+                    crate::jobs::statuspage_sync::execute_or_defer(client, &state.pool, &job)
+                        .await
+                        .map_err(|e| e.to_string())?;
+                } else {
+                    // No Statuspage client configured, skip
+                    info!("Statuspage not configured, skipping {:?}", job);
+                }
+            }
+            Job::StaleIncidentReminder {
                 incident_id,
-                component_id,
-                status,
-                severity,
+                last_activity_at,
             } => {
-                if let Some(client) = &statuspage_client {
-                    crate::jobs::statuspage_sync::execute(
-                        client,
-                        incident_id,
-                        component_id,
-                        status,
-                        severity,
-                    )
+                crate::jobs::stale_reminder::execute(&state, incident_id, last_activity_at)
                     .await
                     .map_err(|e| e.to_string())?;
+            }
+            Job::CreateConferenceBridge { incident_id } => {
+                if let Some(client) = &conference_client {
+                    crate::jobs::conference_bridge::execute(client, &state, incident_id)
+                        .await
+                        .map_err(|e| e.to_string())?;
                 } else {
-                    // No Statuspage client configured, skip
                     info!(
-                        "Statuspage not configured, skipping sync for incident {}",
+                        "Conferencing not configured, skipping bridge for incident {}",
                         incident_id
                     );
                 }
             }
+            Job::CreateJiraIssue {
+                action_item_id,
+                project_key,
+            } => {
+                if let Some(client) = &jira_client {
+                    crate::jobs::jira_sync::execute(client, &state, action_item_id, project_key)
+                        .await
+                        .map_err(|e| e.to_string())?;
+                } else {
+                    info!(
+                        "Jira not configured, skipping issue for action item {}",
+                        action_item_id
+                    );
+                }
+            }
+            Job::DeliverWebhook {
+                webhook_id,
+                event,
+                payload,
+            } => {
+                let retry_policy = RetryPolicy {
+                    max_retries: state.config.webhook_max_retries,
+                    base_delay: Duration::from_millis(state.config.webhook_retry_base_ms),
+                    ..RetryPolicy::default()
+                };
+                WebhookService::new(state.pool.clone())
+                    .deliver(webhook_id, event, &payload, retry_policy)
+                    .await
+                    .map_err(|e| e.to_string())?;
+            }
+            Job::SyncChannelStatus { incident_id } => {
+                crate::jobs::channel_status::execute(&state, incident_id)
+                    .await
+                    .map_err(|e| e.to_string())?;
+            }
+            Job::SnapshotResolvedIncident { incident_id } => {
+                crate::jobs::resolution_snapshot::execute(&state, incident_id)
+                    .await
+                    .map_err(|e| e.to_string())?;
+            }
+            Job::WeeklyReport { week_start } => {
+                crate::jobs::weekly_report::execute(&state, week_start)
+                    .await
+                    .map_err(|e| e.to_string())?;
+            }
         }
 
         Ok(())
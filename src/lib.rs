@@ -1,11 +1,14 @@
 pub mod adapters;
+pub mod api;
 pub mod app_state;
 pub mod commands;
 pub mod config;
 pub mod db;
 pub mod error;
 pub mod jobs;
+pub mod metrics;
 pub mod services;
+pub mod shutdown;
 pub mod slack;
 pub mod utils;
 
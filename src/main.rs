@@ -1,16 +1,51 @@
 use axum::routing::{get, post};
 use axum::Router;
+use incident_bot::adapters::conference::ConferenceClient;
+use incident_bot::adapters::jira::JiraClient;
 use incident_bot::adapters::statuspage::StatuspageClient;
+use incident_bot::config::SlackTransport;
 use incident_bot::jobs::worker::JobWorker;
+use incident_bot::slack::client::RetryPolicy;
 use incident_bot::{db, AppConfig, AppState};
 use std::net::SocketAddr;
-use tokio::sync::mpsc;
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot};
+use tokio::time::Instant;
 use tower_http::trace::TraceLayer;
-use tracing::info;
+use tracing::{error, info, warn};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
+    // `incident-bot manifest ...` prints the Slack app manifest and exits
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some("manifest") {
+        match incident_bot::slack::manifest::parse_options(&args[1..]) {
+            Ok(options) => print!("{}", incident_bot::slack::manifest::render(&options)),
+            Err(message) => {
+                eprintln!("{}", message);
+                std::process::exit(2);
+            }
+        }
+        return Ok(());
+    }
+    // `incident-bot verify-audit <file.csv>` checks an audit log export
+    if args.first().map(String::as_str) == Some("verify-audit") {
+        let Some(path) = args.get(1) else {
+            eprintln!("Usage: incident-bot verify-audit <audit-log.csv>");
+            std::process::exit(2);
+        };
+        let csv = std::fs::read_to_string(path)?;
+        match incident_bot::services::audit_chain::verify_csv(&csv) {
+            Ok(summary) => println!("{}", summary),
+            Err(message) => {
+                eprintln!("Audit log export FAILED verification: {}", message);
+                std::process::exit(1);
+            }
+        }
+        return Ok(());
+    }
+
     // Initialize tracing
     tracing_subscriber::registry()
         .with(
@@ -25,6 +60,8 @@ async fn main() -> anyhow::Result<()> {
     // Load configuration
     let config = AppConfig::from_env().expect("Failed to load configuration");
     config.validate().expect("Configuration validation failed");
+    incident_bot::utils::time::init(incident_bot::utils::time::TimeFormat::from_config(&config));
+    incident_bot::utils::severity::init(config.severities.clone());
 
     info!("Configuration loaded");
 
@@ -43,35 +80,171 @@ async fn main() -> anyhow::Result<()> {
         (&config.statuspage_api_key, &config.statuspage_page_id)
     {
         info!("Statuspage integration enabled");
-        Some(StatuspageClient::new(api_key.clone(), page_id.clone()))
+        let client = StatuspageClient::new(api_key.clone(), page_id.clone())
+            .with_request_timeout(Duration::from_secs(config.statuspage_timeout_secs))
+            .with_retry_policy(RetryPolicy {
+                max_retries: config.statuspage_max_retries,
+                ..RetryPolicy::default()
+            })
+            .with_circuit_breaker(
+                config.statuspage_circuit_breaker_threshold,
+                Duration::from_secs(config.statuspage_circuit_breaker_cooldown_secs),
+            );
+        Some(match config.statuspage_api_url.as_deref() {
+            Some(url) if !url.trim().is_empty() => client.with_base_url(url.to_string()),
+            _ => client,
+        })
     } else {
         info!("Statuspage integration disabled (no API key configured)");
         None
     };
 
+    // Create Jira client (if configured)
+    let jira_client = if let Some((base_url, email, api_token)) = config.jira_credentials() {
+        info!("Jira integration enabled");
+        Some(JiraClient::new(
+            base_url.to_string(),
+            email.to_string(),
+            api_token.to_string(),
+        ))
+    } else {
+        info!("Jira integration disabled (no credentials configured)");
+        None
+    };
+
+    // Create conferencing client (if configured)
+    let conference_client = if let Some((provider, client_id, client_secret, grant)) =
+        config.conference_credentials()
+    {
+        info!("{} bridges enabled", provider.label());
+        Some(ConferenceClient::new(
+            provider,
+            client_id.to_string(),
+            client_secret.to_string(),
+            grant.to_string(),
+        ))
+    } else {
+        info!("Conference bridges disabled (no provider configured)");
+        None
+    };
+
     // Create job queue
     let (job_sender, job_receiver) = mpsc::unbounded_channel();
 
-    // Start job worker
-    let worker = JobWorker::new(job_receiver, statuspage_client);
-    tokio::spawn(async move {
+    // Create app state
+    let state = AppState::new(pool.clone(), config.clone(), job_sender);
+
+    // Start job worker; on shutdown it's handed a deadline to drain by
+    let (worker_shutdown, worker_shutdown_receiver) = oneshot::channel();
+    let worker = JobWorker::new(
+        job_receiver,
+        statuspage_client.clone(),
+        jira_client,
+        conference_client,
+        state.clone(),
+    )
+    .with_shutdown(worker_shutdown_receiver);
+    let worker = tokio::spawn(async move {
         worker.start().await;
     });
 
-    // Create app state
-    let state = AppState::new(pool.clone(), config.clone(), job_sender);
+    // Nag incident channels until mandatory roles are claimed
+    tokio::spawn(incident_bot::jobs::role_reminder::run(state.clone()));
+
+    // Nudge commanders of incidents with no recent timeline activity
+    tokio::spawn(incident_bot::jobs::stale_reminder::run(state.clone()));
+
+    // Check open incidents against their SLA targets
+    tokio::spawn(incident_bot::jobs::sla_checks::run(state.clone()));
+
+    // Offer backups command of P1 incidents whose commander has gone quiet
+    tokio::spawn(incident_bot::jobs::commander_escalation::run(state.clone()));
+
+    // DM exec tiers 2 and 3 about incidents still unresolved after 30 minutes
+    // and 2 hours
+    tokio::spawn(incident_bot::jobs::exec_tiers::run(state.clone()));
+
+    // Tell admins about deactivated Slack users still named in config or
+    // commanding incidents
+    tokio::spawn(incident_bot::jobs::deactivated_users::run(state.clone()));
+
+    // Nag commanders until required postmortems are published
+    tokio::spawn(incident_bot::jobs::postmortem_reminder::run(state.clone()));
+
+    // Summarize and archive incident channels once they've been resolved a while
+    tokio::spawn(incident_bot::jobs::channel_archive::run(state.clone()));
+
+    // Render the open incidents sparkline daily and post the weekly digest
+    tokio::spawn(incident_bot::jobs::burndown::run(state.clone()));
+
+    // Post last week's incident report to the leadership channel on Mondays
+    tokio::spawn(incident_bot::jobs::weekly_report::run(state.clone()));
+
+    // DM team leads last month's incident scorecard
+    tokio::spawn(incident_bot::jobs::scorecards::run(state.clone()));
+
+    // DM opted-in commanders last quarter's coaching report
+    tokio::spawn(incident_bot::jobs::coaching_reports::run(state.clone()));
+
+    // Test page the P1 escalation chain monthly and report acknowledgements
+    tokio::spawn(incident_bot::jobs::paging_test::run(state.clone()));
+
+    // Mirror delayed, redacted incident updates to partner channels
+    tokio::spawn(incident_bot::jobs::partner_mirror::run(state.clone()));
+
+    // Send fan-out thread replies held back while Slack was rate limiting
+    tokio::spawn(incident_bot::jobs::deferred_notifications::run(
+        state.clone(),
+    ));
+
+    // Retry Statuspage syncs deferred while Statuspage was down
+    tokio::spawn(incident_bot::jobs::statuspage_retry::run(
+        state.clone(),
+        statuspage_client,
+    ));
 
     // Build router
-    let app = Router::new()
+    let mut app = Router::new()
         .route("/health", get(health_check))
+        .route("/metrics", get(incident_bot::metrics::handle_metrics))
         .route(
-            "/slack/commands",
-            post(incident_bot::slack::events::handle_slash_command),
-        )
-        .route(
-            "/slack/interactions",
-            post(incident_bot::slack::events::handle_interaction),
-        )
+            "/artifacts/{*key}",
+            get(incident_bot::api::artifacts::download_artifact),
+        );
+
+    // Slack reaches the bot either through Request URLs or a Socket Mode websocket
+    match config.slack_transport {
+        SlackTransport::Http => {
+            // Commands and interactions are rate limited per user; events
+            // aren't user-driven in the same way and Slack retries them
+            let user_routes = Router::new()
+                .route(
+                    incident_bot::slack::events::COMMANDS_PATH,
+                    post(incident_bot::slack::events::handle_slash_command),
+                )
+                .route(
+                    incident_bot::slack::events::INTERACTIONS_PATH,
+                    post(incident_bot::slack::events::handle_interaction),
+                )
+                .route_layer(axum::middleware::from_fn_with_state(
+                    state.clone(),
+                    incident_bot::slack::rate_limit::limit_slack_requests,
+                ));
+            app = app.merge(user_routes).route(
+                incident_bot::slack::events::EVENTS_PATH,
+                post(incident_bot::slack::events::handle_event),
+            );
+        }
+        SlackTransport::SocketMode => {
+            info!("Slack Socket Mode enabled; /slack/* routes are not served");
+            tokio::spawn(incident_bot::slack::socket_mode::run(state.clone()));
+        }
+    }
+
+    let in_flight = state.in_flight.clone();
+    let app = app
+        .nest("/api/v1", incident_bot::api::router(state.clone()))
+        .nest("/integrations", incident_bot::api::alerts::router())
         .with_state(state)
         .layer(TraceLayer::new_for_http());
 
@@ -80,7 +253,37 @@ async fn main() -> anyhow::Result<()> {
     info!("Listening on {}", addr);
 
     let listener = tokio::net::TcpListener::bind(addr).await?;
-    axum::serve(listener, app).await?;
+    let (shutdown_started, shutdown_started_receiver) = oneshot::channel();
+    axum::serve(listener, app)
+        .with_graceful_shutdown(async move {
+            incident_bot::shutdown::signal().await;
+            let _ = shutdown_started.send(Instant::now());
+        })
+        .await?;
+
+    // The server has stopped accepting and finished its requests. Commands
+    // and interactions acknowledged to Slack may still be running, and they
+    // and the requests may have queued jobs: give both until the deadline.
+    let deadline = shutdown_started_receiver
+        .await
+        .unwrap_or_else(|_| Instant::now())
+        + Duration::from_secs(config.shutdown_timeout_secs);
+    if tokio::time::timeout_at(deadline, in_flight.wait())
+        .await
+        .is_err()
+    {
+        warn!(
+            "{} Slack commands or interactions still running at the shutdown deadline",
+            in_flight.running()
+        );
+    }
+    let _ = worker_shutdown.send(deadline);
+    if let Err(e) = worker.await {
+        error!("Job worker failed during shutdown: {}", e);
+    }
+
+    pool.close().await;
+    info!("Shutdown complete");
 
     Ok(())
 }
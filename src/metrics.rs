@@ -0,0 +1,350 @@
+use crate::app_state::AppState;
+use crate::commands::SUBCOMMANDS;
+use crate::db::models::Severity;
+use axum::extract::State;
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use prometheus::{
+    Encoder, HistogramOpts, HistogramVec, IntCounterVec, IntGauge, IntGaugeVec, Opts, Registry,
+    TextEncoder,
+};
+use std::sync::OnceLock;
+use std::time::Duration;
+use tracing::error;
+
+/// Process-wide Prometheus collectors, scraped via `GET /metrics`.
+pub struct Metrics {
+    registry: Registry,
+    pub incidents_declared: IntCounterVec,
+    pub incidents_resolved: IntCounterVec,
+    pub incident_duration_minutes: HistogramVec,
+    pub incident_responders: HistogramVec,
+    pub open_incidents: IntGaugeVec,
+    pub notifications: IntCounterVec,
+    pub slack_commands: IntCounterVec,
+    pub slack_command_duration: HistogramVec,
+    pub slack_interactions: IntCounterVec,
+    pub slack_api_retries: IntCounterVec,
+    pub slack_event_retries: IntCounterVec,
+    pub slack_rate_limited: IntCounterVec,
+    pub slack_api_rate_limited: IntCounterVec,
+    pub slack_send_spacing: IntGauge,
+    pub webhook_deliveries: IntCounterVec,
+}
+
+impl Metrics {
+    fn new() -> Self {
+        let registry = Registry::new_custom(Some("incident_bot".to_string()), None)
+            .expect("valid metrics prefix");
+
+        let incidents_declared = IntCounterVec::new(
+            Opts::new("incidents_declared_total", "Incidents declared"),
+            &["severity"],
+        )
+        .expect("valid metric");
+        let incidents_resolved = IntCounterVec::new(
+            Opts::new("incidents_resolved_total", "Incidents resolved"),
+            &["severity"],
+        )
+        .expect("valid metric");
+        let incident_duration_minutes = HistogramVec::new(
+            HistogramOpts::new(
+                "incident_duration_minutes",
+                "Time from declaration to resolution",
+            )
+            .buckets(vec![5.0, 15.0, 30.0, 60.0, 120.0, 240.0, 480.0, 1440.0]),
+            &["severity"],
+        )
+        .expect("valid metric");
+        let incident_responders = HistogramVec::new(
+            HistogramOpts::new(
+                "incident_responders",
+                "People who joined the channel or posted to the timeline, observed at resolution",
+            )
+            .buckets(vec![1.0, 2.0, 3.0, 5.0, 8.0, 13.0, 21.0]),
+            &["severity"],
+        )
+        .expect("valid metric");
+        let open_incidents = IntGaugeVec::new(
+            Opts::new(
+                "open_incidents",
+                "Unresolved incidents, refreshed on scrape",
+            ),
+            &["severity"],
+        )
+        .expect("valid metric");
+        let notifications = IntCounterVec::new(
+            Opts::new("notifications_total", "Notification attempts by outcome"),
+            &["type", "status"],
+        )
+        .expect("valid metric");
+        let slack_commands = IntCounterVec::new(
+            Opts::new("slack_commands_total", "Slash commands processed"),
+            &["subcommand", "outcome"],
+        )
+        .expect("valid metric");
+        let slack_command_duration = HistogramVec::new(
+            HistogramOpts::new(
+                "slack_command_duration_seconds",
+                "Slash command processing time after ack",
+            ),
+            &["subcommand"],
+        )
+        .expect("valid metric");
+        let slack_interactions = IntCounterVec::new(
+            Opts::new("slack_interactions_total", "Interactive payloads processed"),
+            &["type", "outcome"],
+        )
+        .expect("valid metric");
+        let slack_api_retries = IntCounterVec::new(
+            Opts::new(
+                "slack_api_retries_total",
+                "Slack Web API calls retried after a transient failure",
+            ),
+            &["method"],
+        )
+        .expect("valid metric");
+
+        let slack_event_retries = IntCounterVec::new(
+            Opts::new(
+                "slack_event_retries_total",
+                "Slack deliveries (events, commands, interactions) skipped as retries of one already processed",
+            ),
+            &["event_type"],
+        )
+        .expect("valid metric");
+
+        let slack_rate_limited = IntCounterVec::new(
+            Opts::new(
+                "slack_rate_limited_total",
+                "Slash commands and interactions turned away by SLACK_RATE_LIMIT_PER_MINUTE",
+            ),
+            &["kind"],
+        )
+        .expect("valid metric");
+
+        let slack_api_rate_limited = IntCounterVec::new(
+            Opts::new(
+                "slack_api_rate_limited_total",
+                "Slack Web API calls answered with HTTP 429 / ratelimited",
+            ),
+            &["method"],
+        )
+        .expect("valid metric");
+        let slack_send_spacing = IntGauge::new(
+            "slack_send_spacing_ms",
+            "Current pause between fan-out notifications while Slack is rate limiting; 0 when not",
+        )
+        .expect("valid metric");
+
+        let webhook_deliveries = IntCounterVec::new(
+            Opts::new(
+                "webhook_deliveries_total",
+                "Outbound webhook deliveries by final outcome (after retries)",
+            ),
+            &["event", "outcome"],
+        )
+        .expect("valid metric");
+
+        for collector in [
+            Box::new(incidents_declared.clone()) as Box<dyn prometheus::core::Collector>,
+            Box::new(incidents_resolved.clone()),
+            Box::new(incident_duration_minutes.clone()),
+            Box::new(incident_responders.clone()),
+            Box::new(open_incidents.clone()),
+            Box::new(notifications.clone()),
+            Box::new(slack_commands.clone()),
+            Box::new(slack_command_duration.clone()),
+            Box::new(slack_interactions.clone()),
+            Box::new(slack_api_retries.clone()),
+            Box::new(slack_event_retries.clone()),
+            Box::new(slack_rate_limited.clone()),
+            Box::new(slack_api_rate_limited.clone()),
+            Box::new(slack_send_spacing.clone()),
+            Box::new(webhook_deliveries.clone()),
+        ] {
+            registry.register(collector).expect("unique metric name");
+        }
+
+        Self {
+            registry,
+            incidents_declared,
+            incidents_resolved,
+            incident_duration_minutes,
+            incident_responders,
+            open_incidents,
+            notifications,
+            slack_commands,
+            slack_command_duration,
+            slack_interactions,
+            slack_api_retries,
+            slack_event_retries,
+            slack_rate_limited,
+            slack_api_rate_limited,
+            slack_send_spacing,
+            webhook_deliveries,
+        }
+    }
+
+    pub fn record_declared(&self, severity: Severity) {
+        self.incidents_declared
+            .with_label_values(&[severity.as_db_str()])
+            .inc();
+    }
+
+    pub fn record_resolved(&self, severity: Severity, duration_minutes: Option<i32>) {
+        self.incidents_resolved
+            .with_label_values(&[severity.as_db_str()])
+            .inc();
+        if let Some(minutes) = duration_minutes {
+            self.incident_duration_minutes
+                .with_label_values(&[severity.as_db_str()])
+                .observe(f64::from(minutes));
+        }
+    }
+
+    pub fn record_responders(&self, severity: Severity, responders: i64) {
+        self.incident_responders
+            .with_label_values(&[severity.as_db_str()])
+            .observe(responders as f64);
+    }
+
+    pub fn record_notification(&self, notification_type: &str, status: &str) {
+        self.notifications
+            .with_label_values(&[notification_type, status])
+            .inc();
+    }
+
+    pub fn record_command(&self, text: &str, success: bool, elapsed: Duration) {
+        let subcommand = command_label(text);
+        self.slack_commands
+            .with_label_values(&[subcommand, outcome(success)])
+            .inc();
+        self.slack_command_duration
+            .with_label_values(&[subcommand])
+            .observe(elapsed.as_secs_f64());
+    }
+
+    pub fn record_interaction(&self, interaction_type: &str, success: bool) {
+        self.slack_interactions
+            .with_label_values(&[interaction_type, outcome(success)])
+            .inc();
+    }
+
+    pub fn record_slack_retry(&self, method: &str) {
+        self.slack_api_retries.with_label_values(&[method]).inc();
+    }
+
+    pub fn record_slack_event_retry(&self, event_type: &str) {
+        self.slack_event_retries
+            .with_label_values(&[event_type])
+            .inc();
+    }
+
+    pub fn record_slack_rate_limited(&self, kind: &str) {
+        self.slack_rate_limited.with_label_values(&[kind]).inc();
+    }
+
+    pub fn record_slack_api_rate_limited(&self, method: &str, spacing: Duration) {
+        self.slack_api_rate_limited
+            .with_label_values(&[method])
+            .inc();
+        self.set_slack_send_spacing(spacing);
+    }
+
+    pub fn set_slack_send_spacing(&self, spacing: Duration) {
+        self.slack_send_spacing.set(spacing.as_millis() as i64);
+    }
+
+    pub fn record_webhook_delivery(&self, event: &str, success: bool) {
+        self.webhook_deliveries
+            .with_label_values(&[event, outcome(success)])
+            .inc();
+    }
+
+    /// Render all collectors in the Prometheus text exposition format.
+    pub fn render(&self) -> String {
+        let mut buffer = Vec::new();
+        if let Err(e) = TextEncoder::new().encode(&self.registry.gather(), &mut buffer) {
+            error!("Failed to encode metrics: {}", e);
+        }
+        String::from_utf8(buffer).unwrap_or_default()
+    }
+}
+
+pub fn metrics() -> &'static Metrics {
+    static METRICS: OnceLock<Metrics> = OnceLock::new();
+    METRICS.get_or_init(Metrics::new)
+}
+
+/// `GET /metrics`
+pub async fn handle_metrics(State(state): State<AppState>) -> Response {
+    let metrics = metrics();
+
+    // Gauges derived from the database are refreshed per scrape so they stay
+    // correct across restarts and multiple replicas.
+    match crate::db::queries::incidents::count_open_by_severity(&state.pool).await {
+        Ok(counts) => {
+            metrics.open_incidents.reset();
+            for (severity, count) in counts {
+                metrics
+                    .open_incidents
+                    .with_label_values(&[severity.as_db_str()])
+                    .set(count);
+            }
+        }
+        Err(e) => error!("Failed to refresh open incident gauge: {}", e),
+    }
+
+    (
+        StatusCode::OK,
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        metrics.render(),
+    )
+        .into_response()
+}
+
+/// Known subcommands are labelled individually; anything else is counted as
+/// "unknown" to keep label cardinality bounded.
+fn command_label(text: &str) -> &'static str {
+    let subcommand = text.split_whitespace().next().unwrap_or("");
+    SUBCOMMANDS
+        .iter()
+        .find(|known| **known == subcommand)
+        .copied()
+        .unwrap_or("unknown")
+}
+
+fn outcome(success: bool) -> &'static str {
+    if success {
+        "ok"
+    } else {
+        "error"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command_label_bounds_cardinality() {
+        assert_eq!(command_label("status Rolling back"), "status");
+        assert_eq!(command_label("workstream create db"), "workstream");
+        assert_eq!(command_label("drop table incidents"), "unknown");
+        assert_eq!(command_label(""), "unknown");
+    }
+
+    #[test]
+    fn test_render_includes_recorded_series() {
+        let metrics = metrics();
+        metrics.record_declared(Severity::P1);
+        metrics.record_command("status ok", true, Duration::from_millis(20));
+
+        let output = metrics.render();
+        assert!(output.contains("incident_bot_incidents_declared_total{severity=\"P1\"}"));
+        assert!(output
+            .contains("incident_bot_slack_commands_total{outcome=\"ok\",subcommand=\"status\"}"));
+        assert!(output.contains("incident_bot_slack_command_duration_seconds_bucket"));
+    }
+}
@@ -0,0 +1,152 @@
+use crate::db::models::{ActionItem, Incident, TimelineEventType};
+use crate::db::queries::action_items as action_item_queries;
+use crate::error::{IncidentError, IncidentResult};
+use crate::services::audit::AuditService;
+use crate::services::timeline::TimelineService;
+use chrono::Utc;
+use serde_json::json;
+use sqlx_postgres::PgPool;
+use tracing::info;
+
+/// Jira caps summaries at 255 characters; keep a little headroom.
+pub const MAX_DESCRIPTION_LEN: usize = 200;
+
+pub struct ActionItemService {
+    pool: PgPool,
+    timeline_service: TimelineService,
+    audit_service: AuditService,
+}
+
+impl ActionItemService {
+    pub fn new(pool: PgPool) -> Self {
+        let timeline_service = TimelineService::new(pool.clone());
+        let audit_service = AuditService::new(pool.clone());
+        Self {
+            pool,
+            timeline_service,
+            audit_service,
+        }
+    }
+
+    /// Track a follow-up for `incident`. Allowed on resolved incidents too,
+    /// since most action items come out of the postmortem.
+    pub async fn create(
+        &self,
+        incident: &Incident,
+        description: &str,
+        owner_id: &str,
+        created_by: &str,
+    ) -> IncidentResult<ActionItem> {
+        let description = description.trim();
+        if description.is_empty() || description.chars().count() > MAX_DESCRIPTION_LEN {
+            return Err(IncidentError::ValidationError {
+                field: "description".to_string(),
+                reason: format!(
+                    "Action item description must be 1-{} characters",
+                    MAX_DESCRIPTION_LEN
+                ),
+            });
+        }
+
+        let item = action_item_queries::create_action_item(
+            &self.pool,
+            incident.id,
+            description,
+            owner_id,
+            created_by,
+        )
+        .await?;
+
+        self.timeline_service
+            .log_event(
+                incident.id,
+                TimelineEventType::Note,
+                format!(
+                    "Action item #{} added: {} (owner <@{}>)",
+                    item.number, item.description, item.owner_id
+                ),
+                created_by.to_string(),
+            )
+            .await?;
+
+        self.audit_service
+            .log_action(
+                Some(incident.id),
+                "create_action_item".to_string(),
+                created_by.to_string(),
+                None,
+                Some(json!({
+                    "number": item.number,
+                    "description": item.description,
+                    "owner_id": item.owner_id,
+                })),
+                None,
+            )
+            .await?;
+
+        info!(
+            "Action item #{} created for incident {}",
+            item.number, incident.id
+        );
+        Ok(item)
+    }
+
+    /// Mark item `number` done. Only its owner or the incident commander may
+    /// close it.
+    pub async fn close(
+        &self,
+        incident: &Incident,
+        number: i32,
+        closed_by: &str,
+    ) -> IncidentResult<ActionItem> {
+        let item = action_item_queries::list_action_items(&self.pool, incident.id)
+            .await?
+            .into_iter()
+            .find(|item| item.number == number)
+            .ok_or(IncidentError::NotFound)?;
+
+        if closed_by != item.owner_id && closed_by != incident.commander_id {
+            return Err(IncidentError::PermissionDenied {
+                user_id: closed_by.to_string(),
+                action: format!("close action item #{}", number),
+            });
+        }
+
+        let closed =
+            action_item_queries::close_action_item(&self.pool, incident.id, number, Utc::now())
+                .await?
+                .ok_or_else(|| IncidentError::ValidationError {
+                    field: "number".to_string(),
+                    reason: format!("Action item #{} is already done", number),
+                })?;
+
+        self.timeline_service
+            .log_event(
+                incident.id,
+                TimelineEventType::Note,
+                format!(
+                    "Action item #{} done: {}",
+                    closed.number, closed.description
+                ),
+                closed_by.to_string(),
+            )
+            .await?;
+
+        self.audit_service
+            .log_action(
+                Some(incident.id),
+                "close_action_item".to_string(),
+                closed_by.to_string(),
+                Some(json!({ "number": item.number, "status": item.status })),
+                Some(json!({ "number": closed.number, "status": closed.status })),
+                None,
+            )
+            .await?;
+
+        Ok(closed)
+    }
+
+    pub async fn list(&self, incident: &Incident) -> IncidentResult<Vec<ActionItem>> {
+        action_item_queries::list_action_items(&self.pool, incident.id).await
+    }
+}
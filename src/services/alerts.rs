@@ -0,0 +1,99 @@
+use crate::adapters::alert_sources::{Alert, AlertSource, AlertStatus};
+use crate::app_state::AppState;
+use crate::db::models::{IncidentId, NewTimelineEvent, TimelineEventType};
+use crate::db::queries::incidents as incident_queries;
+use crate::error::IncidentResult;
+use crate::services::timeline::{TimelineService, MAX_BATCH_SIZE};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use tracing::info;
+
+#[derive(Debug, Default, Serialize)]
+pub struct AlertReport {
+    pub received: usize,
+    /// Alerts added to an open incident's timeline
+    pub recorded: usize,
+    /// Alerts with no known enabled service or no open incident for it
+    pub unmatched: usize,
+}
+
+/// Record `alerts` on the timelines of the open incidents for their services.
+/// Alerts for services outside `SERVICES`, disabled ones, or with nothing
+/// open, are counted but otherwise ignored.
+pub async fn record_alerts(
+    state: &AppState,
+    source: &dyn AlertSource,
+    alerts: &[Alert],
+) -> IncidentResult<AlertReport> {
+    let mut report = AlertReport {
+        received: alerts.len(),
+        ..AlertReport::default()
+    };
+
+    let mut by_incident: BTreeMap<IncidentId, Vec<NewTimelineEvent>> = BTreeMap::new();
+    for alert in alerts {
+        let service = alert.service.as_deref().and_then(|service| {
+            state
+                .config
+                .services
+                .iter()
+                .find(|known| known.eq_ignore_ascii_case(service.trim()))
+        });
+        let incident = match service {
+            Some(service) if state.service_catalog.is_enabled(&state.pool, service).await => {
+                incident_queries::get_open_incident_for_service(&state.pool, service).await?
+            }
+            _ => None,
+        };
+        match incident {
+            Some(incident) => by_incident
+                .entry(incident.id)
+                .or_default()
+                .push(timeline_event(source, alert)),
+            None => report.unmatched += 1,
+        }
+    }
+
+    let timeline_service = TimelineService::new(state.pool.clone());
+    for (incident_id, events) in by_incident {
+        for chunk in events.chunks(MAX_BATCH_SIZE) {
+            let inserted = timeline_service
+                .log_events_batch(incident_id, chunk.to_vec())
+                .await?;
+            report.recorded += inserted.len();
+        }
+        info!(
+            "Recorded {} alert updates to incident {}",
+            source.label(),
+            incident_id
+        );
+    }
+
+    Ok(report)
+}
+
+pub(crate) fn timeline_event(source: &dyn AlertSource, alert: &Alert) -> NewTimelineEvent {
+    let message = match alert.status {
+        AlertStatus::Firing => {
+            let mut message = format!("🔔 {}: {} firing", source.label(), alert.title);
+            if let Some(description) = alert.description.as_deref().filter(|d| !d.is_empty()) {
+                message.push_str(&format!(" — {}", description));
+            }
+            if let Some(url) = &alert.url {
+                message.push_str(&format!(" ({})", url));
+            }
+            message
+        }
+        AlertStatus::Resolved => format!("✅ {}: {} resolved", source.label(), alert.title),
+    };
+    NewTimelineEvent {
+        event_type: TimelineEventType::Note,
+        message,
+        posted_by: source.name().to_string(),
+        // A resolution is recorded when it arrives, not at the alert's start
+        timestamp: match alert.status {
+            AlertStatus::Firing => alert.starts_at,
+            AlertStatus::Resolved => None,
+        },
+    }
+}
@@ -0,0 +1,68 @@
+use crate::config::{KpiTargets, TeamConfig};
+use crate::db::queries::analytics;
+use crate::error::IncidentResult;
+use chrono::{NaiveDate, NaiveTime};
+use sqlx_postgres::PgPool;
+
+/// A team's incident KPIs for one period, alongside its targets.
+#[derive(Debug, Clone)]
+pub struct Scorecard {
+    pub team: String,
+    /// First day of the period
+    pub period_start: NaiveDate,
+    /// First day after the period
+    pub period_end: NaiveDate,
+    pub declared: i64,
+    pub resolved: i64,
+    pub mttr_minutes: Option<f64>,
+    /// Share of resolved incidents with a generated postmortem (0.0-1.0)
+    pub postmortem_completion: Option<f64>,
+    /// Share of the period's action items that are done (0.0-1.0)
+    pub action_item_closure: Option<f64>,
+    pub targets: KpiTargets,
+}
+
+pub struct AnalyticsService {
+    pool: PgPool,
+}
+
+impl AnalyticsService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Compute `team`'s scorecard over `[period_start, period_end)` from the
+    /// incidents on the services it owns.
+    pub async fn scorecard(
+        &self,
+        team: &str,
+        config: &TeamConfig,
+        period_start: NaiveDate,
+        period_end: NaiveDate,
+    ) -> IncidentResult<Scorecard> {
+        let stats = analytics::service_stats(
+            &self.pool,
+            &config.services,
+            period_start.and_time(NaiveTime::MIN).and_utc(),
+            period_end.and_time(NaiveTime::MIN).and_utc(),
+        )
+        .await?;
+
+        let postmortem_completion = (stats.resolved > 0)
+            .then(|| stats.resolved_with_postmortem as f64 / stats.resolved as f64);
+        let action_item_closure = (stats.action_items > 0)
+            .then(|| stats.action_items_closed as f64 / stats.action_items as f64);
+
+        Ok(Scorecard {
+            team: team.to_string(),
+            period_start,
+            period_end,
+            declared: stats.declared,
+            resolved: stats.resolved,
+            mttr_minutes: stats.avg_resolution_minutes,
+            postmortem_completion,
+            action_item_closure,
+            targets: config.targets.clone(),
+        })
+    }
+}
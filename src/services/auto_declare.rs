@@ -0,0 +1,353 @@
+use crate::adapters::alert_sources::{Alert, AlertSource, AlertStatus};
+use crate::app_state::AppState;
+use crate::config::AppConfig;
+use crate::db::models::{Incident, IncidentStatus, Severity};
+use crate::db::queries::incidents as incident_queries;
+use crate::db::queries::tracked_alerts as alert_queries;
+use crate::error::IncidentResult;
+use crate::services::alerts::timeline_event;
+use crate::services::audit::AuditService;
+use crate::services::incident::IncidentService;
+use crate::services::timeline::TimelineService;
+use crate::slack::blocks;
+use crate::utils::channel;
+use chrono::Utc;
+use serde::Serialize;
+use serde_json::json;
+use tracing::{error, info, warn};
+
+#[derive(Debug, Default, Serialize)]
+pub struct AutoDeclareReport {
+    pub received: usize,
+    /// New alerts that declared an incident
+    pub declared: usize,
+    /// New alerts added to their service's open incident
+    pub attached: usize,
+    /// Known alerts firing again after they resolved
+    pub refired: usize,
+    /// Repeat firing notifications posted as news, for sources whose repeats
+    /// carry it
+    pub updated: usize,
+    /// Repeat notifications for alerts in the state already recorded
+    pub duplicates: usize,
+    pub resolved: usize,
+    /// Incidents resolved because every alert on them resolved
+    pub incidents_resolved: usize,
+    /// Alerts with no route or known enabled service, no commander to declare
+    /// with, or resolving an alert never seen firing
+    pub unmatched: usize,
+}
+
+/// Where an alert's incident goes, from the source's routes
+/// (`ALERTMANAGER_ROUTES`, `DATADOG_ROUTES`) or else the alert's service and
+/// severity.
+#[derive(Debug, PartialEq)]
+pub struct AlertRoute {
+    pub service: String,
+    /// Needed to declare; alerts without one can only join open incidents
+    pub severity: Option<Severity>,
+    /// The route's commander or the service's first owner
+    pub commander: Option<String>,
+}
+
+pub fn route_alert(config: &AppConfig, source: &str, alert: &Alert) -> Option<AlertRoute> {
+    let (service, severity, commander) = match config
+        .alert_routes_for(source)
+        .iter()
+        .find(|route| route.matches(&alert.labels))
+    {
+        Some(route) => (
+            route.service.clone(),
+            Some(route.severity),
+            route.commander.clone(),
+        ),
+        None => {
+            let service = alert.service.as_deref().and_then(|service| {
+                config
+                    .services
+                    .iter()
+                    .find(|known| known.eq_ignore_ascii_case(service.trim()))
+            })?;
+            (service.clone(), alert.severity, None)
+        }
+    };
+
+    let commander = commander.or_else(|| {
+        config
+            .service_owners
+            .get(&service)
+            .and_then(|owners| owners.first().cloned())
+    });
+    Some(AlertRoute {
+        service,
+        severity,
+        commander,
+    })
+}
+
+/// Declare, join or resolve incidents for `source`'s `alerts`. Alerts are
+/// deduplicated by fingerprint: a repeat notification for an alert already
+/// firing (or already resolved) is only counted, unless the source's repeats
+/// are updates, which are posted. An incident an alert declared is resolved
+/// once every alert on it has resolved; incidents declared by people are
+/// left for them to resolve.
+pub async fn process_alerts(
+    state: &AppState,
+    source: &dyn AlertSource,
+    alerts: &[Alert],
+) -> IncidentResult<AutoDeclareReport> {
+    let mut report = AutoDeclareReport {
+        received: alerts.len(),
+        ..AutoDeclareReport::default()
+    };
+
+    for alert in alerts {
+        if alert.fingerprint.is_empty() {
+            report.unmatched += 1;
+            continue;
+        }
+        match alert.status {
+            AlertStatus::Firing => record_firing(state, source, alert, &mut report).await?,
+            AlertStatus::Resolved => record_resolved(state, source, alert, &mut report).await?,
+        }
+    }
+
+    Ok(report)
+}
+
+async fn record_firing(
+    state: &AppState,
+    source: &dyn AlertSource,
+    alert: &Alert,
+    report: &mut AutoDeclareReport,
+) -> IncidentResult<()> {
+    let incident_service = IncidentService::new(state.pool.clone());
+
+    // Alerts of resolved incidents start over, like new ones
+    if let Some(tracked) =
+        alert_queries::get_tracked_alert(&state.pool, source.name(), &alert.fingerprint).await?
+    {
+        let incident = incident_service.get_by_id(tracked.incident_id).await?;
+        if !incident.status.is_terminal() {
+            if !tracked.firing {
+                alert_queries::set_alert_firing(
+                    &state.pool,
+                    source.name(),
+                    &alert.fingerprint,
+                    true,
+                )
+                .await?;
+                post_alert(state, source, &incident, alert, false).await?;
+                report.refired += 1;
+            } else if source.repeats_are_updates() {
+                post_alert(state, source, &incident, alert, true).await?;
+                report.updated += 1;
+            } else {
+                report.duplicates += 1;
+            }
+            return Ok(());
+        }
+    }
+
+    // New alerts for a disabled service go unmatched; ones already tracked
+    // still follow their incident above
+    let route = match route_alert(&state.config, source.name(), alert) {
+        Some(route)
+            if state
+                .service_catalog
+                .is_enabled(&state.pool, &route.service)
+                .await =>
+        {
+            route
+        }
+        _ => {
+            report.unmatched += 1;
+            return Ok(());
+        }
+    };
+
+    if let Some(incident) =
+        incident_queries::get_open_incident_for_service(&state.pool, &route.service).await?
+    {
+        alert_queries::track_alert(
+            &state.pool,
+            source.name(),
+            &alert.fingerprint,
+            incident.id,
+            false,
+        )
+        .await?;
+        post_alert(state, source, &incident, alert, false).await?;
+        report.attached += 1;
+        return Ok(());
+    }
+
+    let (Some(severity), Some(commander)) = (route.severity, route.commander.as_deref()) else {
+        warn!(
+            "Not declaring an incident for alert {}: no severity or commander for {}",
+            alert.fingerprint, route.service
+        );
+        report.unmatched += 1;
+        return Ok(());
+    };
+
+    let incident = declare(state, source, alert, &route.service, severity, commander).await?;
+    alert_queries::track_alert(
+        &state.pool,
+        source.name(),
+        &alert.fingerprint,
+        incident.id,
+        true,
+    )
+    .await?;
+    post_alert(state, source, &incident, alert, false).await?;
+    report.declared += 1;
+    Ok(())
+}
+
+async fn record_resolved(
+    state: &AppState,
+    source: &dyn AlertSource,
+    alert: &Alert,
+    report: &mut AutoDeclareReport,
+) -> IncidentResult<()> {
+    let Some(tracked) =
+        alert_queries::get_tracked_alert(&state.pool, source.name(), &alert.fingerprint).await?
+    else {
+        report.unmatched += 1;
+        return Ok(());
+    };
+    if !tracked.firing {
+        report.duplicates += 1;
+        return Ok(());
+    }
+
+    alert_queries::set_alert_firing(&state.pool, source.name(), &alert.fingerprint, false).await?;
+    report.resolved += 1;
+
+    let incident_service = IncidentService::new(state.pool.clone());
+    let incident = incident_service.get_by_id(tracked.incident_id).await?;
+    if incident.status.is_terminal() {
+        return Ok(());
+    }
+    post_alert(state, source, &incident, alert, false).await?;
+
+    if alert_queries::all_alerts_resolved_for_declared_incident(&state.pool, incident.id).await? {
+        let actor = source.name();
+        let resolved = incident_service
+            .transition_status(incident.id, IncidentStatus::Resolved, actor.to_string())
+            .await?;
+        crate::api::incidents::announce_status(state, &resolved, incident.status, actor).await;
+        info!(
+            "Incident {} auto-resolved: its alerts resolved",
+            incident.id
+        );
+        report.incidents_resolved += 1;
+    }
+    Ok(())
+}
+
+/// Declare an incident for `alert` in a new channel, announced like one
+/// declared from Slack.
+async fn declare(
+    state: &AppState,
+    source: &dyn AlertSource,
+    alert: &Alert,
+    service: &str,
+    severity: Severity,
+    commander: &str,
+) -> IncidentResult<Incident> {
+    let incident_service =
+        IncidentService::new(state.pool.clone()).with_config(state.config.clone());
+    let title: String = alert.title.chars().take(100).collect();
+    let incident = incident_service
+        .create_incident(title, severity, service.to_string(), commander.to_string())
+        .await?;
+
+    let date = Utc::now().date_naive();
+    let (channel_id, channel_name) = match channel::create_incident_channel(
+        state.slack_client.as_ref(),
+        service,
+        date,
+        incident.id,
+        false,
+    )
+    .await
+    {
+        Ok(channel) => channel,
+        Err(e) => {
+            // Compensation: an incident without a channel would go unnoticed
+            if let Err(delete_err) = incident_service.delete_incident(incident.id).await {
+                error!("Failed to delete incident during cleanup: {}", delete_err);
+            }
+            return Err(e);
+        }
+    };
+    incident_service
+        .update_channel_id(incident.id, channel_id.clone())
+        .await?;
+
+    AuditService::new(state.pool.clone())
+        .log_action(
+            Some(incident.id),
+            "incident_auto_declared".to_string(),
+            source.name().to_string(),
+            None,
+            None,
+            Some(json!({
+                "fingerprint": alert.fingerprint,
+                "labels": alert.labels,
+                "commander": commander,
+            })),
+        )
+        .await?;
+
+    let mut invitees = vec![commander.to_string()];
+    if let Some(owners) = state.config.service_owners.get(service) {
+        invitees.extend(owners.clone());
+    }
+    invitees.sort();
+    invitees.dedup();
+    if let Err(e) = state.slack_client.invite_users(&channel_id, invitees).await {
+        error!("Failed to invite users to channel: {}", e);
+    }
+
+    let incident = incident_service.get_by_id(incident.id).await?;
+    crate::commands::declare::announce_declared(state, &incident).await;
+
+    info!(
+        "Incident {} declared from {} alert {} in #{}",
+        incident.id,
+        source.label(),
+        alert.fingerprint,
+        channel_name
+    );
+    Ok(incident)
+}
+
+/// Post `alert` to the incident channel and note it on the timeline.
+async fn post_alert(
+    state: &AppState,
+    source: &dyn AlertSource,
+    incident: &Incident,
+    alert: &Alert,
+    update: bool,
+) -> IncidentResult<()> {
+    TimelineService::new(state.pool.clone())
+        .log_events_batch(incident.id, vec![timeline_event(source, alert)])
+        .await?;
+
+    if let Some(channel_id) = incident.slack_channel_id.as_deref() {
+        if let Err(e) = state
+            .slack_client
+            .post_message(
+                channel_id,
+                blocks::alert_blocks(source.label(), alert, update),
+            )
+            .await
+        {
+            error!("Failed to post alert to incident channel: {}", e);
+        }
+    }
+    Ok(())
+}
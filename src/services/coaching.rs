@@ -0,0 +1,111 @@
+use crate::db::queries::coaching::{self, CommanderStats};
+use crate::error::IncidentResult;
+use chrono::{NaiveDate, NaiveTime};
+use sqlx_postgres::PgPool;
+
+/// One commander's quarter, for the opt-in coaching DM. Alongside their own
+/// figures it carries the median across every commander that quarter, so
+/// the report reads as "here's where you stand" rather than a ranking.
+#[derive(Debug, Clone)]
+pub struct CoachingReport {
+    pub commander_id: String,
+    /// First day of the quarter
+    pub period_start: NaiveDate,
+    /// First day after the quarter
+    pub period_end: NaiveDate,
+    pub mine: CommanderStats,
+    pub median_first_update_minutes: Option<f64>,
+    pub median_update_interval_minutes: Option<f64>,
+    pub median_postmortem_completion: Option<f64>,
+}
+
+impl CoachingReport {
+    /// Share of their resolved incidents with a postmortem (0.0-1.0)
+    pub fn postmortem_completion(&self) -> Option<f64> {
+        postmortem_completion(&self.mine)
+    }
+}
+
+fn postmortem_completion(stats: &CommanderStats) -> Option<f64> {
+    (stats.resolved > 0).then(|| stats.resolved_with_postmortem as f64 / stats.resolved as f64)
+}
+
+fn median(mut values: Vec<f64>) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_by(f64::total_cmp);
+    let mid = values.len() / 2;
+    Some(if values.len().is_multiple_of(2) {
+        (values[mid - 1] + values[mid]) / 2.0
+    } else {
+        values[mid]
+    })
+}
+
+pub struct CoachingService {
+    pool: PgPool,
+}
+
+impl CoachingService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// `commander_id`'s report for incidents declared in
+    /// `[period_start, period_end)`, or `None` if they commanded none.
+    pub async fn report(
+        &self,
+        commander_id: &str,
+        period_start: NaiveDate,
+        period_end: NaiveDate,
+    ) -> IncidentResult<Option<CoachingReport>> {
+        let everyone = coaching::commander_stats(
+            &self.pool,
+            period_start.and_time(NaiveTime::MIN).and_utc(),
+            period_end.and_time(NaiveTime::MIN).and_utc(),
+        )
+        .await?;
+        let Some(mine) = everyone
+            .iter()
+            .find(|stats| stats.commander_id == commander_id)
+            .cloned()
+        else {
+            return Ok(None);
+        };
+
+        Ok(Some(CoachingReport {
+            commander_id: commander_id.to_string(),
+            period_start,
+            period_end,
+            mine,
+            median_first_update_minutes: median(
+                everyone
+                    .iter()
+                    .filter_map(|stats| stats.mean_first_update_minutes)
+                    .collect(),
+            ),
+            median_update_interval_minutes: median(
+                everyone
+                    .iter()
+                    .filter_map(|stats| stats.mean_update_interval_minutes)
+                    .collect(),
+            ),
+            median_postmortem_completion: median(
+                everyone.iter().filter_map(postmortem_completion).collect(),
+            ),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_median() {
+        assert_eq!(median(vec![]), None);
+        assert_eq!(median(vec![30.0, 10.0, 20.0]), Some(20.0));
+        assert_eq!(median(vec![40.0, 10.0, 20.0, 30.0]), Some(25.0));
+    }
+}
@@ -0,0 +1,119 @@
+use crate::db::models::Severity;
+use crate::db::queries::incidents;
+use crate::error::IncidentResult;
+use serde_json::{json, Value};
+use sqlx_postgres::PgPool;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+/// How long open-incident counts are reused. Banners go on App Home views
+/// and modals that open often, and a count a few seconds stale is fine.
+const CACHE_TTL: Duration = Duration::from_secs(30);
+
+/// Severities worth interrupting other messages for.
+const BANNER_SEVERITIES: [Severity; 2] = [Severity::P1, Severity::P2];
+
+type OpenCounts = Vec<(Severity, i64)>;
+
+/// The "⚠️ 2 open P1s right now" banner that digests, App Home, help output
+/// and the declare modal show while high-severity incidents are open. Quiet
+/// incidents are never counted, since banners reach people outside them.
+pub struct ContextBanner {
+    cached: Mutex<Option<(Instant, OpenCounts)>>,
+}
+
+impl Default for ContextBanner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ContextBanner {
+    pub fn new() -> Self {
+        Self {
+            cached: Mutex::new(None),
+        }
+    }
+
+    async fn open_counts(&self, pool: &PgPool) -> IncidentResult<OpenCounts> {
+        if let Some((fetched_at, counts)) = self.cached.lock().expect("banner cache").as_ref() {
+            if fetched_at.elapsed() < CACHE_TTL {
+                return Ok(counts.clone());
+            }
+        }
+        let counts = incidents::count_open_announced_by_severity(pool).await?;
+        *self.cached.lock().expect("banner cache") = Some((Instant::now(), counts.clone()));
+        Ok(counts)
+    }
+
+    /// The banner as a context block, or `None` when no high-severity
+    /// incident is open. Failures only cost the banner, never the message
+    /// it decorates.
+    pub async fn block(&self, pool: &PgPool) -> Option<Value> {
+        let counts = match self.open_counts(pool).await {
+            Ok(counts) => counts,
+            Err(e) => {
+                warn!("Failed to count open incidents for banner: {}", e);
+                return None;
+            }
+        };
+        let text = banner_text(&counts)?;
+        Some(json!({
+            "type": "context",
+            "elements": [{ "type": "mrkdwn", "text": text }]
+        }))
+    }
+
+    /// Insert the banner into `blocks` at `index` (clamped to the end), if
+    /// there is one.
+    pub async fn insert(&self, pool: &PgPool, blocks: &mut Vec<Value>, index: usize) {
+        if let Some(block) = self.block(pool).await {
+            blocks.insert(index.min(blocks.len()), block);
+        }
+    }
+}
+
+/// "⚠️ 2 open P1s and 1 open P2 right now", or `None` when there are none.
+pub fn banner_text(counts: &[(Severity, i64)]) -> Option<String> {
+    let parts: Vec<String> = BANNER_SEVERITIES
+        .iter()
+        .filter_map(|severity| {
+            let count = counts
+                .iter()
+                .find(|(s, _)| s == severity)
+                .map_or(0, |(_, count)| *count);
+            (count > 0).then(|| {
+                format!(
+                    "{} open {}{}",
+                    count,
+                    severity.as_db_str(),
+                    if count == 1 { "" } else { "s" }
+                )
+            })
+        })
+        .collect();
+    if parts.is_empty() {
+        return None;
+    }
+    Some(format!("⚠️ {} right now", parts.join(" and ")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_banner_text_counts_high_severities_only() {
+        assert_eq!(banner_text(&[]), None);
+        assert_eq!(banner_text(&[(Severity::P3, 4)]), None);
+        assert_eq!(
+            banner_text(&[(Severity::P1, 2)]).as_deref(),
+            Some("⚠️ 2 open P1s right now")
+        );
+        assert_eq!(
+            banner_text(&[(Severity::P4, 1), (Severity::P2, 1), (Severity::P1, 3)]).as_deref(),
+            Some("⚠️ 3 open P1s and 1 open P2 right now")
+        );
+    }
+}
@@ -0,0 +1,126 @@
+use crate::db::models::FailedJob;
+use crate::db::queries::failed_jobs;
+use crate::error::{IncidentError, IncidentResult};
+use crate::jobs::Job;
+use crate::services::audit::AuditService;
+use serde_json::json;
+use sqlx_postgres::PgPool;
+use tokio::sync::mpsc;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+/// Dead-letter a job that failed for good. Best-effort: a failure to store
+/// it is logged, like the job failure itself used to be.
+pub async fn record(pool: &PgPool, job: &Job, error: &str) {
+    let value = match serde_json::to_value(job) {
+        Ok(value) => value,
+        Err(e) => {
+            error!("Failed to serialize dead-lettered job {:?}: {}", job, e);
+            return;
+        }
+    };
+    match failed_jobs::dead_letter_job(pool, job.incident_id(), &value, error).await {
+        Ok(()) => warn!("Dead-lettered job {:?}: {}", job, error),
+        Err(e) => error!("Failed to dead-letter job {:?}: {}", job, e),
+    }
+}
+
+/// Retry or discard dead-lettered jobs.
+pub struct DeadLetterService {
+    pool: PgPool,
+    job_sender: mpsc::UnboundedSender<Job>,
+    audit_service: AuditService,
+}
+
+impl DeadLetterService {
+    pub fn new(pool: PgPool, job_sender: mpsc::UnboundedSender<Job>) -> Self {
+        let audit_service = AuditService::new(pool.clone());
+        Self {
+            pool,
+            job_sender,
+            audit_service,
+        }
+    }
+
+    /// Hand the job back to the worker. It runs in the background; if it
+    /// fails again it is dead-lettered again under a new ID.
+    pub async fn retry(&self, id: Uuid, actor_id: &str) -> IncidentResult<FailedJob> {
+        let dead = self.get(id).await?;
+        let job = serde_json::from_value::<Job>(dead.job.clone()).map_err(|_| {
+            IncidentError::ValidationError {
+                field: "job".to_string(),
+                reason: "This job can't be read by this version of the bot; discard it instead"
+                    .to_string(),
+            }
+        })?;
+        let dead = failed_jobs::take_dead_letter(&self.pool, id)
+            .await?
+            .ok_or_else(Self::already_handled)?;
+
+        if self.job_sender.send(job).is_err() {
+            // Keep it for another try once the worker is back
+            failed_jobs::dead_letter_failed_job(&self.pool, &dead, "job worker is not running")
+                .await?;
+            return Err(IncidentError::InternalError(
+                "Job worker is not running".to_string(),
+            ));
+        }
+
+        self.audit("job_retried", &dead, actor_id).await?;
+        info!(
+            "{} requeued dead-lettered {} job {}",
+            actor_id,
+            dead.kind(),
+            id
+        );
+        Ok(dead)
+    }
+
+    /// Drop the job for good.
+    pub async fn discard(&self, id: Uuid, actor_id: &str) -> IncidentResult<FailedJob> {
+        self.get(id).await?;
+        let dead = failed_jobs::take_dead_letter(&self.pool, id)
+            .await?
+            .ok_or_else(Self::already_handled)?;
+
+        self.audit("job_discarded", &dead, actor_id).await?;
+        info!(
+            "{} discarded dead-lettered {} job {}",
+            actor_id,
+            dead.kind(),
+            id
+        );
+        Ok(dead)
+    }
+
+    async fn get(&self, id: Uuid) -> IncidentResult<FailedJob> {
+        failed_jobs::get_dead_letter(&self.pool, id)
+            .await?
+            .ok_or(IncidentError::NotFound)
+    }
+
+    fn already_handled() -> IncidentError {
+        IncidentError::ValidationError {
+            field: "job".to_string(),
+            reason: "Job was retried or discarded by someone else".to_string(),
+        }
+    }
+
+    async fn audit(&self, action: &str, dead: &FailedJob, actor_id: &str) -> IncidentResult<()> {
+        self.audit_service
+            .log_action(
+                dead.incident_id,
+                action.to_string(),
+                actor_id.to_string(),
+                None,
+                None,
+                Some(json!({
+                    "job_id": dead.id,
+                    "kind": dead.kind(),
+                    "attempts": dead.attempts,
+                    "error": dead.last_error,
+                })),
+            )
+            .await
+    }
+}
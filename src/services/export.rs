@@ -0,0 +1,250 @@
+use crate::config::AppConfig;
+use crate::db::models::{Incident, TimelineEvent};
+use crate::db::queries::{incidents as incident_queries, timeline};
+use crate::error::{IncidentError, IncidentResult};
+use crate::services::permissions::FieldVisibility;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::Serialize;
+use sqlx_postgres::PgPool;
+
+/// Longest range one export may cover; a year is plenty for quarterly
+/// reviews and keeps the upload a reasonable size.
+pub const MAX_EXPORT_DAYS: i64 = 366;
+
+const CSV_HEADER: [&str; 18] = [
+    "incident_id",
+    "incident_number",
+    "title",
+    "severity",
+    "status",
+    "affected_service",
+    "commander_id",
+    "declared_at",
+    "resolved_at",
+    "duration_minutes",
+    "is_quiet",
+    "custom_fields",
+    "event_timestamp",
+    "event_type",
+    "audience",
+    "posted_by",
+    "message",
+    "event_deleted_at",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+        }
+    }
+}
+
+impl std::str::FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "csv" => Ok(ExportFormat::Csv),
+            "json" => Ok(ExportFormat::Json),
+            _ => Err(format!("Invalid export format '{}'. Use csv or json", s)),
+        }
+    }
+}
+
+/// An incident with its full timeline, internal events included.
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportedIncident {
+    #[serde(flatten)]
+    pub incident: Incident,
+    pub timeline: Vec<TimelineEvent>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct JsonExport<'a> {
+    exported_at: DateTime<Utc>,
+    from: NaiveDate,
+    to: NaiveDate,
+    incidents: &'a [ExportedIncident],
+}
+
+/// A rendered export, ready to upload.
+#[derive(Debug, Clone)]
+pub struct IncidentExport {
+    pub filename: String,
+    pub content: Vec<u8>,
+    pub incident_count: usize,
+}
+
+/// Every incident declared between `from` and `to` (inclusive, UTC days),
+/// quiet ones included, with their timelines. Exports are for admins, so
+/// custom fields restricted to the `admin` role are shown. Deleted timeline
+/// events keep their place with the text hidden, as everywhere else.
+pub async fn export_incidents(
+    pool: &PgPool,
+    config: &AppConfig,
+    from: NaiveDate,
+    to: NaiveDate,
+    format: ExportFormat,
+) -> IncidentResult<IncidentExport> {
+    if to < from || (to - from).num_days() >= MAX_EXPORT_DAYS {
+        return Err(IncidentError::ValidationError {
+            field: "range".to_string(),
+            reason: format!(
+                "The end date must be on or after the start date, at most {} days later",
+                MAX_EXPORT_DAYS
+            ),
+        });
+    }
+
+    let visibility = FieldVisibility::for_roles(config, &["admin"]);
+    let incidents = incident_queries::list_declared_between(
+        pool,
+        from.and_hms_opt(0, 0, 0).unwrap().and_utc(),
+        (to + Duration::days(1))
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+            .and_utc(),
+    )
+    .await?;
+
+    let mut exported = Vec::with_capacity(incidents.len());
+    for incident in incidents {
+        let events = timeline::get_timeline(pool, incident.id)
+            .await?
+            .into_iter()
+            .map(|mut event| {
+                if event.is_deleted() {
+                    event.message.clear();
+                }
+                event
+            })
+            .collect();
+        exported.push(ExportedIncident {
+            incident: visibility.redact_incident(incident),
+            timeline: events,
+        });
+    }
+
+    let content = match format {
+        ExportFormat::Csv => to_csv(&exported).into_bytes(),
+        ExportFormat::Json => serde_json::to_vec_pretty(&JsonExport {
+            exported_at: Utc::now(),
+            from,
+            to,
+            incidents: &exported,
+        })
+        .map_err(|e| IncidentError::InternalError(format!("Failed to serialize export: {}", e)))?,
+    };
+
+    Ok(IncidentExport {
+        filename: format!("incidents-{}-to-{}.{}", from, to, format.extension()),
+        content,
+        incident_count: exported.len(),
+    })
+}
+
+/// One row per timeline event, each carrying its incident's columns; an
+/// incident without events gets a single row with the event columns empty.
+fn to_csv(incidents: &[ExportedIncident]) -> String {
+    let mut csv = csv_row(CSV_HEADER.iter().map(|h| h.to_string()));
+    for exported in incidents {
+        let incident = &exported.incident;
+        let incident_columns = [
+            incident.id.to_string(),
+            incident.reference(),
+            incident.title.clone(),
+            incident.severity.as_db_str().to_string(),
+            incident.status.as_db_str().to_string(),
+            incident.affected_service.clone(),
+            incident.commander_id.clone(),
+            incident.declared_at.to_rfc3339(),
+            incident
+                .resolved_at
+                .map(|t| t.to_rfc3339())
+                .unwrap_or_default(),
+            incident
+                .duration_minutes
+                .map(|m| m.to_string())
+                .unwrap_or_default(),
+            incident.is_quiet.to_string(),
+            if incident.custom_fields.is_empty() {
+                String::new()
+            } else {
+                serde_json::to_string(&incident.custom_fields).unwrap_or_default()
+            },
+        ];
+
+        if exported.timeline.is_empty() {
+            let empty = std::iter::repeat_n(String::new(), 6);
+            csv.push_str(&csv_row(incident_columns.iter().cloned().chain(empty)));
+        }
+        for event in &exported.timeline {
+            let event_columns = [
+                event.timestamp.to_rfc3339(),
+                event.event_type.as_db_str().to_string(),
+                event.audience.as_db_str().to_string(),
+                event.posted_by.clone(),
+                event.message.clone(),
+                event.deleted_at.map(|t| t.to_rfc3339()).unwrap_or_default(),
+            ];
+            csv.push_str(&csv_row(
+                incident_columns.iter().cloned().chain(event_columns),
+            ));
+        }
+    }
+    csv
+}
+
+fn csv_row(fields: impl Iterator<Item = String>) -> String {
+    let mut row = fields
+        .map(|field| csv_field(&field))
+        .collect::<Vec<_>>()
+        .join(",");
+    row.push_str("\r\n");
+    row
+}
+
+/// RFC 4180 quoting. Text that a spreadsheet would read as a formula is
+/// prefixed with `'`, since titles and notes come from anyone in Slack.
+fn csv_field(value: &str) -> String {
+    let value = if value.starts_with(['=', '+', '-', '@']) {
+        format!("'{}", value)
+    } else {
+        value.to_string()
+    };
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv_field_quotes_and_defuses_formulas() {
+        assert_eq!(csv_field("VPN down"), "VPN down");
+        assert_eq!(csv_field("a, b"), "\"a, b\"");
+        assert_eq!(csv_field("said \"hi\""), "\"said \"\"hi\"\"\"");
+        assert_eq!(csv_field("line 1\nline 2"), "\"line 1\nline 2\"");
+        assert_eq!(csv_field("=HYPERLINK(\"x\")"), "\"'=HYPERLINK(\"\"x\"\")\"");
+        assert_eq!(csv_field("-5% errors"), "'-5% errors");
+    }
+
+    #[test]
+    fn test_export_format_parses() {
+        assert_eq!("CSV".parse::<ExportFormat>(), Ok(ExportFormat::Csv));
+        assert_eq!("json".parse::<ExportFormat>(), Ok(ExportFormat::Json));
+        assert!("xlsx".parse::<ExportFormat>().is_err());
+    }
+}
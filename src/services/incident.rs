@@ -1,16 +1,30 @@
-use crate::db::models::{Incident, IncidentId, IncidentStatus, Severity, TimelineEventType};
-use crate::db::queries::incidents as incident_queries;
+use crate::config::AppConfig;
+use crate::db::models::{
+    Audience, Incident, IncidentId, IncidentStatus, SeverityLevel, TimelineEventType,
+};
+use crate::db::queries::commanders as commander_queries;
+use crate::db::queries::incidents::{self as incident_queries, IncidentFilter};
+use crate::db::queries::participants as participant_queries;
+use crate::db::queries::postmortems as postmortem_queries;
 use crate::error::{IncidentError, IncidentResult};
+use crate::metrics::metrics;
 use crate::services::audit::AuditService;
+use crate::services::permissions::{Action, Permissions, Role};
 use crate::services::timeline::TimelineService;
+use crate::utils::time;
+use chrono::Utc;
 use serde_json::json;
 use sqlx_postgres::PgPool;
+use std::sync::Arc;
 use tracing::info;
+use uuid::Uuid;
 
 pub struct IncidentService {
     pool: PgPool,
     timeline_service: TimelineService,
     audit_service: AuditService,
+    permissions: Permissions,
+    config: Option<Arc<AppConfig>>,
 }
 
 impl IncidentService {
@@ -21,21 +35,45 @@ impl IncidentService {
             pool,
             timeline_service,
             audit_service,
+            permissions: Permissions::default(),
+            config: None,
         }
     }
 
+    /// Number new incidents with the configured prefixes (see
+    /// `AppConfig::incident_prefix_for`) rather than INC-N.
+    pub fn with_config(mut self, config: Arc<AppConfig>) -> Self {
+        self.config = Some(config);
+        self
+    }
+
+    /// Let bot administrators override commander-only actions.
+    pub fn with_permissions(mut self, permissions: Permissions) -> Self {
+        self.permissions = permissions;
+        self
+    }
+
+    /// `severity` is a `SeverityLevel`, or a bare tier for its stand-in level.
     pub async fn create_incident(
         &self,
         title: String,
-        severity: Severity,
+        severity: impl Into<SeverityLevel>,
         affected_service: String,
         commander_id: String,
     ) -> IncidentResult<Incident> {
+        let level = severity.into();
+        let number_prefix = self
+            .config
+            .as_ref()
+            .map_or(crate::utils::incident_number::DEFAULT_PREFIX, |config| {
+                config.incident_prefix_for(&affected_service, false)
+            });
         // Create incident in DB
         let incident = incident_queries::create_incident(
             &self.pool,
             title.clone(),
-            severity,
+            &level,
+            number_prefix,
             affected_service.clone(),
             commander_id.clone(),
         )
@@ -60,14 +98,58 @@ impl IncidentService {
                 None,
                 Some(json!({
                     "title": title,
-                    "severity": severity,
+                    "severity": level.code,
                     "service": affected_service,
                 })),
                 None,
             )
             .await?;
 
-        info!("Incident created: {} ({})", incident.id, title);
+        metrics().record_declared(level.tier);
+        info!(
+            "Incident created: {} {} ({})",
+            incident.reference(),
+            incident.id,
+            title
+        );
+        Ok(incident)
+    }
+
+    /// `/incident triage`: record a suspected issue without declaring it.
+    /// Triages stay out of metrics unless they're declared.
+    pub async fn start_triage(
+        &self,
+        title: String,
+        reporter_id: String,
+    ) -> IncidentResult<Incident> {
+        let incident = incident_queries::create_triage(&self.pool, &title, &reporter_id).await?;
+
+        self.timeline_service
+            .log_event(
+                incident.id,
+                TimelineEventType::Triaged,
+                format!("Triage started: {}", title),
+                reporter_id.clone(),
+            )
+            .await?;
+
+        self.audit_service
+            .log_action(
+                Some(incident.id),
+                "triage_started".to_string(),
+                reporter_id,
+                None,
+                Some(json!({ "title": title })),
+                None,
+            )
+            .await?;
+
+        info!(
+            "Triage started: {} {} ({})",
+            incident.reference(),
+            incident.id,
+            title
+        );
         Ok(incident)
     }
 
@@ -88,10 +170,25 @@ impl IncidentService {
         incident_id: IncidentId,
         message: String,
         posted_by: String,
+        audience: Audience,
+    ) -> IncidentResult<Incident> {
+        self.post_co_authored_update(incident_id, message, posted_by, None, audience)
+            .await
+    }
+
+    /// Post a status update, crediting the scribe whose draft it was if any.
+    pub async fn post_co_authored_update(
+        &self,
+        incident_id: IncidentId,
+        message: String,
+        posted_by: String,
+        drafted_by: Option<String>,
+        audience: Audience,
     ) -> IncidentResult<Incident> {
         // Get incident and validate commander
         let incident = self.get_by_id(incident_id).await?;
-        self.validate_commander(&incident, &posted_by).await?;
+        self.authorize(Action::PostStatusUpdate, &incident, &posted_by)
+            .await?;
 
         // Check if incident is resolved
         if incident.status.is_terminal() {
@@ -102,14 +199,26 @@ impl IncidentService {
         }
 
         // Log to timeline
-        self.timeline_service
-            .log_event(
-                incident_id,
-                TimelineEventType::StatusUpdate,
-                message.clone(),
-                posted_by.clone(),
-            )
-            .await?;
+        let details = match drafted_by {
+            Some(scribe) => {
+                self.timeline_service
+                    .log_drafted_status_update(
+                        incident_id,
+                        message.clone(),
+                        posted_by.clone(),
+                        scribe.clone(),
+                        audience,
+                    )
+                    .await?;
+                json!({ "message": message, "audience": audience, "drafted_by": scribe })
+            }
+            None => {
+                self.timeline_service
+                    .log_status_update(incident_id, message.clone(), posted_by.clone(), audience)
+                    .await?;
+                json!({ "message": message, "audience": audience })
+            }
+        };
 
         // Log to audit
         self.audit_service
@@ -119,7 +228,7 @@ impl IncidentService {
                 posted_by,
                 None,
                 None,
-                Some(json!({ "message": message })),
+                Some(details),
             )
             .await?;
 
@@ -127,35 +236,35 @@ impl IncidentService {
         self.get_by_id(incident_id).await
     }
 
+    /// Returns the updated incident and the level it had before.
     pub async fn change_severity(
         &self,
         incident_id: IncidentId,
-        new_severity: Severity,
+        new_severity: impl Into<SeverityLevel>,
         changed_by: String,
         reason: Option<String>,
-    ) -> IncidentResult<(Incident, Severity)> {
+    ) -> IncidentResult<(Incident, &'static SeverityLevel)> {
+        let new_level = new_severity.into();
         // Get incident and validate commander
         let incident = self.get_by_id(incident_id).await?;
-        self.validate_commander(&incident, &changed_by).await?;
+        self.authorize(Action::ChangeSeverity, &incident, &changed_by)
+            .await?;
 
-        let old_severity = incident.severity;
+        let old_level = incident.severity_level();
 
         // Update severity in DB
-        incident_queries::update_severity(&self.pool, incident_id, new_severity).await?;
+        incident_queries::update_severity(&self.pool, incident_id, &new_level).await?;
 
         // Log to timeline
         let message = if let Some(reason) = &reason {
             format!(
                 "Severity changed from {} to {} — {}",
-                old_severity.label(),
-                new_severity.label(),
-                reason
+                old_level.label, new_level.label, reason
             )
         } else {
             format!(
                 "Severity changed from {} to {}",
-                old_severity.label(),
-                new_severity.label()
+                old_level.label, new_level.label
             )
         };
 
@@ -174,15 +283,69 @@ impl IncidentService {
                 Some(incident_id),
                 "change_severity".to_string(),
                 changed_by,
-                Some(json!({ "severity": old_severity })),
-                Some(json!({ "severity": new_severity })),
+                Some(json!({ "severity": old_level.code })),
+                Some(json!({ "severity": new_level.code })),
                 reason.map(|r| json!({ "reason": r })),
             )
             .await?;
 
         // Get updated incident
         let updated_incident = self.get_by_id(incident_id).await?;
-        Ok((updated_incident, old_severity))
+        Ok((updated_incident, old_level))
+    }
+
+    /// Hand command to `new_commander`. Callers are responsible for
+    /// authorization; a backup may only take command after an escalation.
+    pub async fn reassign_commander(
+        &self,
+        incident_id: IncidentId,
+        new_commander: String,
+        changed_by: String,
+    ) -> IncidentResult<Incident> {
+        let incident = self.get_by_id(incident_id).await?;
+        if incident.status.is_terminal() {
+            return Err(IncidentError::ValidationError {
+                field: "status".to_string(),
+                reason: "Cannot reassign command of a resolved incident".to_string(),
+            });
+        }
+        if incident.commander_id == new_commander {
+            return Ok(incident); // Idempotent
+        }
+
+        incident_queries::update_commander(&self.pool, incident_id, &new_commander).await?;
+        // Restart the absence clock for the new commander
+        commander_queries::mark_commander_seen(&self.pool, incident_id, &new_commander, Utc::now())
+            .await?;
+
+        self.timeline_service
+            .log_event(
+                incident_id,
+                TimelineEventType::Note,
+                format!(
+                    "Command transferred from <@{}> to <@{}>",
+                    incident.commander_id, new_commander
+                ),
+                changed_by.clone(),
+            )
+            .await?;
+
+        self.audit_service
+            .log_action(
+                Some(incident_id),
+                "reassign_commander".to_string(),
+                changed_by,
+                Some(json!({ "commander_id": incident.commander_id })),
+                Some(json!({ "commander_id": new_commander })),
+                None,
+            )
+            .await?;
+
+        info!(
+            "Incident {} command transferred from {} to {}",
+            incident_id, incident.commander_id, new_commander
+        );
+        self.get_by_id(incident_id).await
     }
 
     pub async fn resolve_incident(
@@ -192,28 +355,179 @@ impl IncidentService {
     ) -> IncidentResult<Incident> {
         // Get incident and validate commander
         let incident = self.get_by_id(incident_id).await?;
-        self.validate_commander(&incident, &resolved_by).await?;
+        self.authorize(Action::Resolve, &incident, &resolved_by)
+            .await?;
 
         // Check if already resolved
         if incident.status.is_terminal() {
             return Ok(incident); // Idempotent
         }
 
+        self.complete_resolution(&incident, resolved_by).await
+    }
+
+    /// Reopen a resolved incident as `investigating`. Resolved is otherwise
+    /// terminal, so this bypasses the state machine; callers check the
+    /// commander. An unpublished required postmortem is dropped and will be
+    /// required again on the next resolution.
+    pub async fn reopen_incident(
+        &self,
+        incident_id: IncidentId,
+        reopened_by: String,
+        reason: Option<String>,
+    ) -> IncidentResult<Incident> {
+        let incident = self.get_by_id(incident_id).await?;
+        let reopened = incident_queries::reopen_incident(&self.pool, incident_id)
+            .await?
+            .ok_or_else(|| IncidentError::ValidationError {
+                field: "status".to_string(),
+                reason: "Only resolved incidents can be reopened".to_string(),
+            })?;
+        postmortem_queries::clear_postmortem_requirement(&self.pool, incident_id).await?;
+
+        let message = match &reason {
+            Some(reason) => format!("Incident reopened: {}", reason),
+            None => "Incident reopened".to_string(),
+        };
+        self.timeline_service
+            .log_event(
+                incident_id,
+                TimelineEventType::Reopened,
+                message,
+                reopened_by.clone(),
+            )
+            .await?;
+
+        self.audit_service
+            .log_action(
+                Some(incident_id),
+                "reopen_incident".to_string(),
+                reopened_by,
+                Some(json!({
+                    "status": incident.status,
+                    "resolved_at": incident.resolved_at,
+                    "duration_minutes": incident.duration_minutes,
+                })),
+                Some(json!({ "status": reopened.status })),
+                reason.map(|reason| json!({ "reason": reason })),
+            )
+            .await?;
+
+        info!("Incident {} reopened", incident_id);
+        Ok(reopened)
+    }
+
+    /// Close an open incident as a false alarm. Like reopening, this bypasses
+    /// the state machine; callers check the commander.
+    pub async fn cancel_incident(
+        &self,
+        incident_id: IncidentId,
+        canceled_by: String,
+        reason: String,
+    ) -> IncidentResult<Incident> {
+        let incident = self.get_by_id(incident_id).await?;
+        let canceled = incident_queries::cancel_incident(&self.pool, incident_id, &reason)
+            .await?
+            .ok_or_else(|| IncidentError::ValidationError {
+                field: "status".to_string(),
+                reason: "Only open incidents can be canceled".to_string(),
+            })?;
+
+        self.timeline_service
+            .log_event(
+                incident_id,
+                TimelineEventType::Canceled,
+                format!("Incident canceled as a false alarm: {}", reason),
+                canceled_by.clone(),
+            )
+            .await?;
+
+        self.audit_service
+            .log_action(
+                Some(incident_id),
+                "cancel_incident".to_string(),
+                canceled_by,
+                Some(json!({ "status": incident.status })),
+                Some(json!({ "status": canceled.status })),
+                Some(json!({ "reason": reason })),
+            )
+            .await?;
+
+        info!("Incident {} canceled", incident_id);
+        Ok(canceled)
+    }
+
+    /// Move an incident to `new_status`, enforcing the state machine.
+    ///
+    /// Callers are responsible for authorization; Slack commands check the
+    /// commander first, the REST API relies on its bearer token.
+    pub async fn transition_status(
+        &self,
+        incident_id: IncidentId,
+        new_status: IncidentStatus,
+        changed_by: String,
+    ) -> IncidentResult<Incident> {
+        let incident = self.get_by_id(incident_id).await?;
+
+        if !incident.status.can_transition_to(&new_status) {
+            return Err(IncidentError::InvalidStateTransition {
+                from: incident.status,
+                to: new_status,
+            });
+        }
+
+        if new_status == IncidentStatus::Resolved {
+            return self.complete_resolution(&incident, changed_by).await;
+        }
+
+        incident_queries::update_status(&self.pool, incident_id, new_status).await?;
+
+        self.timeline_service
+            .log_event(
+                incident_id,
+                TimelineEventType::StatusUpdate,
+                format!(
+                    "Status changed from {} to {}",
+                    incident.status.as_db_str(),
+                    new_status.as_db_str()
+                ),
+                changed_by.clone(),
+            )
+            .await?;
+
+        self.audit_service
+            .log_action(
+                Some(incident_id),
+                "change_status".to_string(),
+                changed_by,
+                Some(json!({ "status": incident.status })),
+                Some(json!({ "status": new_status })),
+                None,
+            )
+            .await?;
+
+        info!(
+            "Incident {} status changed from {:?} to {:?}",
+            incident_id, incident.status, new_status
+        );
+        self.get_by_id(incident_id).await
+    }
+
+    async fn complete_resolution(
+        &self,
+        incident: &Incident,
+        resolved_by: String,
+    ) -> IncidentResult<Incident> {
+        let incident_id = incident.id;
+
         // Update status in DB (sets resolved_at, duration_minutes)
         let resolved_incident = incident_queries::resolve_incident(&self.pool, incident_id).await?;
 
         // Log to timeline
-        let duration_text = if let Some(duration) = resolved_incident.duration_minutes {
-            let hours = duration / 60;
-            let mins = duration % 60;
-            if hours > 0 {
-                format!("{}h {}min", hours, mins)
-            } else {
-                format!("{}min", mins)
-            }
-        } else {
-            "unknown".to_string()
-        };
+        let duration_text = resolved_incident
+            .duration_minutes
+            .map(|duration| time::duration(duration.into()))
+            .unwrap_or_else(|| "unknown".to_string());
 
         self.timeline_service
             .log_event(
@@ -236,14 +550,46 @@ impl IncidentService {
             )
             .await?;
 
+        metrics().record_resolved(
+            resolved_incident.severity,
+            resolved_incident.duration_minutes,
+        );
+        metrics().record_responders(
+            resolved_incident.severity,
+            participant_queries::count_participants(&self.pool, incident_id).await?,
+        );
         info!("Incident resolved: {}", incident_id);
         Ok(resolved_incident)
     }
 
+    pub async fn list(&self, filter: &IncidentFilter) -> IncidentResult<Vec<Incident>> {
+        incident_queries::list_incidents(&self.pool, filter).await
+    }
+
     pub async fn get_by_id(&self, incident_id: IncidentId) -> IncidentResult<Incident> {
         incident_queries::get_incident_by_id(&self.pool, incident_id).await
     }
 
+    /// Look up an incident by ID, or by number in any prefix (`PLAT-12`,
+    /// `plat-12`, `#PLAT-12`).
+    pub async fn find(&self, reference: &str) -> IncidentResult<Incident> {
+        let reference = reference.trim();
+        if let Ok(incident_id) = Uuid::parse_str(reference) {
+            return self.get_by_id(incident_id).await;
+        }
+        let (prefix, number) =
+            crate::utils::incident_number::parse(reference).ok_or_else(|| {
+                IncidentError::ValidationError {
+                    field: "incident".to_string(),
+                    reason: format!(
+                        "'{}' isn't an incident ID or number such as INC-42",
+                        reference
+                    ),
+                }
+            })?;
+        incident_queries::get_incident_by_number(&self.pool, &prefix, number).await
+    }
+
     pub async fn get_by_channel(&self, channel_id: &str) -> IncidentResult<Incident> {
         incident_queries::get_incident_by_channel(&self.pool, channel_id).await
     }
@@ -252,24 +598,22 @@ impl IncidentService {
         incident_queries::get_latest_incident_by_channel(&self.pool, channel_id).await
     }
 
-    pub async fn validate_commander(
+    /// Check that `user_id` may take a commander-only `action`: they command
+    /// the incident, or are an administrator (see `with_permissions`).
+    pub async fn authorize(
         &self,
+        action: Action,
         incident: &Incident,
         user_id: &str,
-    ) -> IncidentResult<()> {
-        if incident.commander_id != user_id {
-            return Err(IncidentError::PermissionDenied {
-                user_id: user_id.to_string(),
-                action: "modify this incident".to_string(),
-            });
-        }
-        Ok(())
+    ) -> IncidentResult<Role> {
+        self.permissions.authorize(action, incident, user_id).await
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::db::models::Severity;
 
     #[test]
     fn test_state_machine_all_valid_transitions() {
@@ -299,6 +643,10 @@ mod tests {
         assert!(!Monitoring.can_transition_to(&Investigating));
         assert!(!Monitoring.can_transition_to(&Identified));
 
+        // Triages are declared or dismissed, never transitioned
+        assert!(!Triaging.can_transition_to(&Declared));
+        assert!(!Triaging.can_transition_to(&Resolved));
+
         // From Resolved (terminal)
         assert!(!Resolved.can_transition_to(&Declared));
         assert!(!Resolved.can_transition_to(&Investigating));
@@ -310,6 +658,7 @@ mod tests {
     fn test_terminal_states() {
         use IncidentStatus::*;
 
+        assert!(!Triaging.is_terminal());
         assert!(!Declared.is_terminal());
         assert!(!Investigating.is_terminal());
         assert!(!Identified.is_terminal());
@@ -0,0 +1,156 @@
+use crate::adapters::github::{self, Deployment, GithubRef};
+use crate::app_state::AppState;
+use crate::db::models::{Incident, IncidentId, IncidentLink, LinkKind, TimelineEventType};
+use crate::db::queries::{incidents as incident_queries, links as link_queries};
+use crate::error::IncidentResult;
+use crate::services::timeline::TimelineService;
+use crate::slack::blocks;
+use serde::Serialize;
+use tracing::{error, info};
+
+/// `posted_by` / `linked_by` for deploys reported by GitHub's webhook.
+const GITHUB_ACTOR: &str = "github";
+
+#[derive(Debug, Default, Serialize)]
+pub struct DeployReport {
+    /// Service the repository maps to in `GITHUB_REPOS`
+    pub service: Option<String>,
+    /// Open incident the deploy was noted on
+    pub incident_id: Option<IncidentId>,
+}
+
+/// Link a pull request or commit to an incident and note it on the
+/// timeline. Returns `None` if it was already linked.
+pub async fn link_github(
+    state: &AppState,
+    incident: &Incident,
+    github_ref: &GithubRef,
+    user_id: &str,
+) -> IncidentResult<Option<IncidentLink>> {
+    let Some(link) = link_queries::add_link(
+        &state.pool,
+        incident.id,
+        "github",
+        github_ref.kind,
+        &github_ref.repo,
+        &github_ref.reference,
+        &github_ref.url,
+        "",
+        user_id,
+    )
+    .await?
+    else {
+        return Ok(None);
+    };
+
+    let text = format!(
+        "🔗 Linked {} <{}|{}>",
+        kind_label(link.kind),
+        link.url,
+        github_ref.label()
+    );
+    TimelineService::new(state.pool.clone())
+        .log_event(
+            incident.id,
+            TimelineEventType::Note,
+            text.clone(),
+            user_id.to_string(),
+        )
+        .await?;
+    if let Some(channel_id) = &incident.slack_channel_id {
+        if let Err(e) = state
+            .slack_client
+            .post_message(channel_id, blocks::link_added_blocks(user_id, &link))
+            .await
+        {
+            error!("Failed to announce GitHub link: {}", e);
+        }
+    }
+    info!(
+        "{} linked {} to incident {}",
+        user_id,
+        github_ref.label(),
+        incident.id
+    );
+    Ok(Some(link))
+}
+
+/// Note a successful deploy on the open incident for the repository's
+/// service (per `GITHUB_REPOS`), so responders can correlate it with the
+/// impact. Redeliveries of the same deploy are noted once.
+pub async fn record_deploy(
+    state: &AppState,
+    deployment: &Deployment,
+) -> IncidentResult<DeployReport> {
+    let Some(service) = state.config.service_for_repo(&deployment.repo) else {
+        info!(
+            "Deploy of {} is not for a service in GITHUB_REPOS; ignored",
+            deployment.repo
+        );
+        return Ok(DeployReport::default());
+    };
+    let mut report = DeployReport {
+        service: Some(service.to_string()),
+        incident_id: None,
+    };
+    let Some(incident) =
+        incident_queries::get_open_incident_for_service(&state.pool, service).await?
+    else {
+        return Ok(report);
+    };
+
+    let Some(link) = link_queries::add_link(
+        &state.pool,
+        incident.id,
+        "github",
+        LinkKind::Deploy,
+        &deployment.repo,
+        &deployment.sha,
+        &deployment.url,
+        &deployment.environment,
+        GITHUB_ACTOR,
+    )
+    .await?
+    else {
+        return Ok(report);
+    };
+
+    let label = github::link_label(&deployment.repo, LinkKind::Deploy, &deployment.sha);
+    let by = deployment
+        .creator
+        .as_ref()
+        .map(|login| format!(" by {}", login))
+        .unwrap_or_default();
+    let text = format!(
+        "🚀 Deploy of <{}|{}> to *{}* landed{}",
+        link.url, label, deployment.environment, by
+    );
+    TimelineService::new(state.pool.clone())
+        .log_event(
+            incident.id,
+            TimelineEventType::Note,
+            text.clone(),
+            GITHUB_ACTOR.to_string(),
+        )
+        .await?;
+    if let Some(channel_id) = &incident.slack_channel_id {
+        if let Err(e) = state
+            .slack_client
+            .post_message(channel_id, blocks::deploy_landed_blocks(&text))
+            .await
+        {
+            error!("Failed to announce deploy: {}", e);
+        }
+    }
+    info!("Noted deploy of {} on incident {}", label, incident.id);
+    report.incident_id = Some(incident.id);
+    Ok(report)
+}
+
+pub fn kind_label(kind: LinkKind) -> &'static str {
+    match kind {
+        LinkKind::PullRequest => "pull request",
+        LinkKind::Commit => "commit",
+        LinkKind::Deploy => "deploy",
+    }
+}
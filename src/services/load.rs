@@ -0,0 +1,248 @@
+use crate::db::queries::load::{self as load_queries, RoleSpell};
+use crate::error::IncidentResult;
+use chrono::{DateTime, Datelike, Duration, DurationRound, FixedOffset, Timelike, Utc, Weekday};
+use serde::Serialize;
+use sqlx_postgres::PgPool;
+use std::collections::{BTreeMap, HashSet};
+
+/// Local hours counted as night on weekdays: 22:00 up to 08:00.
+const NIGHT_START_HOUR: u32 = 22;
+const NIGHT_END_HOUR: u32 = 8;
+
+/// Per-person incident load over a period, for on-call fairness reviews.
+#[derive(Debug, Clone, Serialize)]
+pub struct LoadReport {
+    pub since: DateTime<Utc>,
+    pub until: DateTime<Utc>,
+    /// Offset nights and weekends are judged in (`LOAD_REPORT_UTC_OFFSET_HOURS`)
+    pub utc_offset_hours: i32,
+    /// Most loaded first
+    pub people: Vec<PersonLoad>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PersonLoad {
+    pub user_id: String,
+    /// Incidents they commanded or held a role on during the period
+    pub incidents: usize,
+    pub command_hours: f64,
+    /// Hours holding claimed roles (comms lead, scribe, ...)
+    pub role_hours: f64,
+    /// Weeknight hours (22:00-08:00) on any incident role, overlaps counted once
+    pub night_hours: f64,
+    /// Saturday and Sunday hours on any incident role, overlaps counted once
+    pub weekend_hours: f64,
+}
+
+impl PersonLoad {
+    pub fn total_hours(&self) -> f64 {
+        self.command_hours + self.role_hours
+    }
+}
+
+pub struct LoadService {
+    pool: PgPool,
+}
+
+impl LoadService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Load over `[since, until)`, for one user or everyone who held an
+    /// incident role. Roles still held count up to `until`.
+    pub async fn report(
+        &self,
+        user_id: Option<&str>,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+        utc_offset_hours: i32,
+    ) -> IncidentResult<LoadReport> {
+        let spells = load_queries::role_spells(&self.pool, user_id, since, until).await?;
+        Ok(LoadReport {
+            since,
+            until,
+            utc_offset_hours,
+            people: summarize(&spells, since, until, utc_offset_hours),
+        })
+    }
+}
+
+/// Sum each person's spells clipped to `[since, until)`.
+pub fn summarize(
+    spells: &[RoleSpell],
+    since: DateTime<Utc>,
+    until: DateTime<Utc>,
+    utc_offset_hours: i32,
+) -> Vec<PersonLoad> {
+    let mut by_user: BTreeMap<&str, Vec<&RoleSpell>> = BTreeMap::new();
+    for spell in spells {
+        by_user.entry(&spell.user_id).or_default().push(spell);
+    }
+
+    let mut people: Vec<PersonLoad> = by_user
+        .into_iter()
+        .map(|(user_id, spells)| {
+            let mut load = PersonLoad {
+                user_id: user_id.to_string(),
+                incidents: 0,
+                command_hours: 0.0,
+                role_hours: 0.0,
+                night_hours: 0.0,
+                weekend_hours: 0.0,
+            };
+            let mut incidents = HashSet::new();
+            let mut intervals = Vec::new();
+            for spell in spells {
+                let start = spell.started_at.max(since);
+                let end = spell.ended_at.unwrap_or(until).min(until);
+                if end <= start {
+                    continue;
+                }
+                incidents.insert(spell.incident_id);
+                if spell.role == "commander" {
+                    load.command_hours += hours(end - start);
+                } else {
+                    load.role_hours += hours(end - start);
+                }
+                intervals.push((start, end));
+            }
+            load.incidents = incidents.len();
+            for (start, end) in merge(intervals) {
+                let (night, weekend) = off_hours(start, end, utc_offset_hours);
+                load.night_hours += night;
+                load.weekend_hours += weekend;
+            }
+            load
+        })
+        .filter(|load| load.incidents > 0)
+        .collect();
+
+    people.sort_by(|a, b| {
+        b.total_hours()
+            .total_cmp(&a.total_hours())
+            .then_with(|| a.user_id.cmp(&b.user_id))
+    });
+    people
+}
+
+fn hours(duration: Duration) -> f64 {
+    duration.num_seconds() as f64 / 3600.0
+}
+
+/// Collapse overlapping intervals so concurrent roles aren't double counted.
+fn merge(
+    mut intervals: Vec<(DateTime<Utc>, DateTime<Utc>)>,
+) -> Vec<(DateTime<Utc>, DateTime<Utc>)> {
+    intervals.sort();
+    let mut merged: Vec<(DateTime<Utc>, DateTime<Utc>)> = Vec::new();
+    for (start, end) in intervals {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+/// Weeknight and weekend hours within `[start, end)`, judged at
+/// `utc_offset_hours`. Offsets are whole hours, so local hour boundaries
+/// line up with UTC ones and each hour slice is classified once.
+fn off_hours(start: DateTime<Utc>, end: DateTime<Utc>, utc_offset_hours: i32) -> (f64, f64) {
+    let offset = FixedOffset::east_opt(utc_offset_hours * 3600)
+        .unwrap_or_else(|| FixedOffset::east_opt(0).unwrap());
+    let (mut night, mut weekend) = (0.0, 0.0);
+    let mut cursor = start;
+    while cursor < end {
+        let next = (cursor.duration_trunc(Duration::hours(1)).unwrap_or(cursor)
+            + Duration::hours(1))
+        .min(end);
+        let local = cursor.with_timezone(&offset);
+        let slice = hours(next - cursor);
+        if matches!(local.weekday(), Weekday::Sat | Weekday::Sun) {
+            weekend += slice;
+        } else if local.hour() >= NIGHT_START_HOUR || local.hour() < NIGHT_END_HOUR {
+            night += slice;
+        }
+        cursor = next;
+    }
+    (night, weekend)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use uuid::Uuid;
+
+    fn at(day: u32, hour: u32) -> DateTime<Utc> {
+        // November 2024: the 15th is a Friday
+        Utc.with_ymd_and_hms(2024, 11, day, hour, 0, 0).unwrap()
+    }
+
+    fn spell(
+        incident: u128,
+        user: &str,
+        role: &str,
+        start: DateTime<Utc>,
+        end: Option<DateTime<Utc>>,
+    ) -> RoleSpell {
+        RoleSpell {
+            incident_id: Uuid::from_u128(incident),
+            user_id: user.to_string(),
+            role: role.to_string(),
+            started_at: start,
+            ended_at: end,
+        }
+    }
+
+    #[test]
+    fn test_summarize_splits_command_nights_and_weekends() {
+        let spells = vec![
+            // Friday 20:00 to Saturday 02:00: 2 daytime, 2 night, 2 weekend hours
+            spell(1, "U_ALICE", "commander", at(15, 20), Some(at(16, 2))),
+            // Scribe on the same incident overlaps the command spell
+            spell(1, "U_ALICE", "scribe", at(15, 21), Some(at(15, 23))),
+            // Still open: counted up to `until`
+            spell(2, "U_BOB", "commander", at(18, 10), None),
+        ];
+
+        let people = summarize(&spells, at(1, 0), at(18, 12), 0);
+        assert_eq!(
+            people[0],
+            PersonLoad {
+                user_id: "U_ALICE".to_string(),
+                incidents: 1,
+                command_hours: 6.0,
+                role_hours: 2.0,
+                night_hours: 2.0,
+                weekend_hours: 2.0,
+            }
+        );
+        assert_eq!(people[1].user_id, "U_BOB");
+        assert_eq!(people[1].command_hours, 2.0);
+        assert_eq!(people[1].night_hours, 0.0);
+    }
+
+    #[test]
+    fn test_summarize_clips_to_period_and_applies_offset() {
+        let spells = vec![spell(
+            1,
+            "U_ALICE",
+            "commander",
+            at(14, 6),
+            Some(at(14, 12)),
+        )];
+
+        // Only 09:00-12:00 UTC falls in the period
+        let people = summarize(&spells, at(14, 9), at(30, 0), 0);
+        assert_eq!(people[0].command_hours, 3.0);
+        assert_eq!(people[0].night_hours, 0.0);
+
+        // At UTC-5, 09:00-12:00 UTC is 04:00-07:00 local: all night
+        let people = summarize(&spells, at(14, 9), at(30, 0), -5);
+        assert_eq!(people[0].night_hours, 3.0);
+
+        assert!(summarize(&spells, at(20, 0), at(30, 0), 0).is_empty());
+    }
+}
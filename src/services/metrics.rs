@@ -0,0 +1,209 @@
+use crate::db::models::Severity;
+use crate::db::queries::metrics::{
+    self as metrics_queries, Contributor, MetricsGrouping, MetricsRow,
+};
+use crate::error::IncidentResult;
+use crate::services::permissions::AnalyticsScope;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use sqlx_postgres::PgPool;
+
+/// Incident metrics over a trailing window, for `/incident metrics`.
+#[derive(Debug, Clone)]
+pub struct MetricsReport {
+    pub window_days: i64,
+    pub since: DateTime<Utc>,
+    /// Whose services the report covers
+    pub scope: AnalyticsScope,
+    pub overall: Option<MetricsRow>,
+    pub by_severity: Vec<MetricsRow>,
+    pub by_service: Vec<MetricsRow>,
+    pub by_month: Vec<MetricsRow>,
+}
+
+/// The weekly report for the leadership channel: the seven days before
+/// `week_start` (a Monday, UTC), across all services.
+#[derive(Debug, Clone)]
+pub struct WeeklyReport {
+    pub week_start: NaiveDate,
+    pub overall: Option<MetricsRow>,
+    pub by_severity: Vec<MetricsRow>,
+    pub by_service: Vec<MetricsRow>,
+    pub downtime_minutes: i64,
+    pub top_contributors: Vec<Contributor>,
+}
+
+/// People listed under top contributors in the weekly report.
+const TOP_CONTRIBUTORS: i64 = 5;
+
+/// Upper bounds (exclusive, in minutes) of the duration histogram buckets;
+/// a last bucket takes everything longer.
+pub const DURATION_BUCKETS: [i64; 6] = [15, 60, 4 * 60, 12 * 60, 24 * 60, 3 * 24 * 60];
+
+/// How long one severity's resolved incidents took, bucketed by
+/// `DURATION_BUCKETS`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DurationHistogram {
+    pub severity: Severity,
+    /// One count per bucket, shortest first
+    pub counts: Vec<i64>,
+}
+
+impl DurationHistogram {
+    pub fn total(&self) -> i64 {
+        self.counts.iter().sum()
+    }
+}
+
+/// Bucket `durations` (minutes) per severity, skipping severities without
+/// resolved incidents.
+pub fn duration_histograms(durations: &[(Severity, i32)]) -> Vec<DurationHistogram> {
+    let mut histograms: Vec<DurationHistogram> = Vec::new();
+    for &(severity, minutes) in durations {
+        let bucket = DURATION_BUCKETS
+            .iter()
+            .position(|&bound| i64::from(minutes) < bound)
+            .unwrap_or(DURATION_BUCKETS.len());
+        let index = match histograms.iter().position(|h| h.severity == severity) {
+            Some(index) => index,
+            None => {
+                histograms.push(DurationHistogram {
+                    severity,
+                    counts: vec![0; DURATION_BUCKETS.len() + 1],
+                });
+                histograms.len() - 1
+            }
+        };
+        histograms[index].counts[bucket] += 1;
+    }
+    histograms.sort_by_key(|h| h.severity.as_db_str());
+    histograms
+}
+
+/// Aggregate incident metrics (MTTR, MTTA, counts) for leadership reviews.
+/// Not to be confused with the Prometheus metrics in `crate::metrics`.
+pub struct MetricsService {
+    pool: PgPool,
+}
+
+impl MetricsService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Metrics for incidents declared in the `window_days` before `now`,
+    /// limited to the services `scope` allows.
+    pub async fn report(
+        &self,
+        window_days: i64,
+        now: DateTime<Utc>,
+        scope: AnalyticsScope,
+    ) -> IncidentResult<MetricsReport> {
+        let since = now - Duration::days(window_days);
+        let rows = metrics_queries::incident_metrics(&self.pool, since, scope.services()).await?;
+
+        let mut report = MetricsReport {
+            window_days,
+            since,
+            scope,
+            overall: None,
+            by_severity: Vec::new(),
+            by_service: Vec::new(),
+            by_month: Vec::new(),
+        };
+        for row in rows {
+            match row.grouping {
+                MetricsGrouping::Overall => report.overall = Some(row),
+                MetricsGrouping::Severity => report.by_severity.push(row),
+                MetricsGrouping::Service => report.by_service.push(row),
+                MetricsGrouping::Month => report.by_month.push(row),
+            }
+        }
+        // Busiest services first
+        report
+            .by_service
+            .sort_by(|a, b| b.declared.cmp(&a.declared).then_with(|| a.key.cmp(&b.key)));
+        Ok(report)
+    }
+
+    /// The report for the week before the one starting `week_start`.
+    pub async fn weekly_report(&self, week_start: NaiveDate) -> IncidentResult<WeeklyReport> {
+        let until = week_start
+            .and_hms_opt(0, 0, 0)
+            .expect("midnight always exists")
+            .and_utc();
+        let since = until - Duration::days(7);
+        let rows =
+            metrics_queries::incident_metrics_between(&self.pool, since, Some(until), None).await?;
+
+        let mut report = WeeklyReport {
+            week_start,
+            overall: None,
+            by_severity: Vec::new(),
+            by_service: Vec::new(),
+            downtime_minutes: metrics_queries::downtime_minutes(&self.pool, since, until).await?,
+            top_contributors: metrics_queries::top_contributors(
+                &self.pool,
+                since,
+                until,
+                TOP_CONTRIBUTORS,
+            )
+            .await?,
+        };
+        for row in rows {
+            match row.grouping {
+                MetricsGrouping::Overall => report.overall = Some(row),
+                MetricsGrouping::Severity => report.by_severity.push(row),
+                MetricsGrouping::Service => report.by_service.push(row),
+                MetricsGrouping::Month => {}
+            }
+        }
+        report
+            .by_service
+            .sort_by(|a, b| b.declared.cmp(&a.declared).then_with(|| a.key.cmp(&b.key)));
+        Ok(report)
+    }
+
+    /// Resolution time histograms per severity for the same incidents as
+    /// `report`.
+    pub async fn duration_histograms(
+        &self,
+        window_days: i64,
+        now: DateTime<Utc>,
+        scope: &AnalyticsScope,
+    ) -> IncidentResult<Vec<DurationHistogram>> {
+        let since = now - Duration::days(window_days);
+        let durations =
+            metrics_queries::resolved_durations(&self.pool, since, scope.services()).await?;
+        Ok(duration_histograms(&durations))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_duration_histograms_bucket_per_severity() {
+        let histograms = duration_histograms(&[
+            (Severity::P2, 10),
+            (Severity::P1, 14),
+            (Severity::P1, 15),
+            (Severity::P1, 90),
+            (Severity::P1, 5000),
+        ]);
+        assert_eq!(
+            histograms,
+            vec![
+                DurationHistogram {
+                    severity: Severity::P1,
+                    counts: vec![1, 1, 1, 0, 0, 0, 1],
+                },
+                DurationHistogram {
+                    severity: Severity::P2,
+                    counts: vec![1, 0, 0, 0, 0, 0, 0],
+                },
+            ]
+        );
+        assert_eq!(histograms[0].total(), 4);
+    }
+}
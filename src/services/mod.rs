@@ -1,5 +1,28 @@
+pub mod action_items;
+pub mod alerts;
+pub mod analytics;
+pub mod artifact_store;
 pub mod audit;
+pub mod audit_chain;
+pub mod auto_declare;
+pub mod coaching;
+pub mod context_banner;
+pub mod dead_letters;
+pub mod export;
 pub mod incident;
+pub mod links;
+pub mod load;
+pub mod metrics;
 pub mod notification;
+pub mod oncall;
+pub mod participants;
+pub mod permissions;
+pub mod pinned_summary;
 pub mod postmortem;
+pub mod reconstruction;
+pub mod residency;
+pub mod roles;
+pub mod service_catalog;
 pub mod timeline;
+pub mod webhook;
+pub mod workstream;
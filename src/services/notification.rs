@@ -1,28 +1,95 @@
-use crate::config::AppConfig;
-use crate::db::models::{Incident, IncidentId, NotificationStatus, NotificationType, Severity};
-use crate::db::queries::notifications;
-use crate::error::IncidentResult;
-use crate::slack::client::SlackClient;
-use serde_json::Value;
+use crate::config::{AppConfig, ExecTier, NotificationEvent};
+use crate::db::models::{
+    Incident, IncidentId, NotificationRecord, NotificationStatus, NotificationType, Severity,
+    SeverityLevel,
+};
+use crate::db::queries::{broadcast_threads, exec_tiers, notifications, subscriptions};
+use crate::error::{IncidentError, IncidentResult};
+use crate::metrics::metrics;
+use crate::services::audit::AuditService;
+use crate::slack::blocks::{
+    batched_update_messages, subscription_digest_blocks, user_group_mention_block,
+};
+use crate::slack::client::SlackApi;
+use crate::slack::throttle::is_rate_limited;
+use crate::utils::severity;
+use serde_json::{json, Value};
 use sqlx_postgres::PgPool;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use tracing::{error, info, warn};
+use uuid::Uuid;
 
 type NotificationThrottleKey = (String, IncidentId);
 type NotificationThrottleMap = HashMap<NotificationThrottleKey, chrono::DateTime<chrono::Utc>>;
 
+/// Where a severity-routed notification goes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NotificationTarget {
+    Channel(String),
+    Dm(String),
+    /// DM every member of a Slack user group
+    UserGroup(String),
+}
+
+/// Routing for `event` on an incident of `severity` in `service`, in send
+/// order: the incident channel, the rule's channels, the service's own
+/// channels (SERVICE_CHANNELS), then the rule's DMs and user groups (see
+/// `AppConfig::notification_rule_for`). A channel listed more than once is
+/// only posted to once.
+pub fn plan_notifications(
+    config: &AppConfig,
+    severity: Severity,
+    event: NotificationEvent,
+    service: &str,
+    incident_channel: Option<&str>,
+) -> Vec<NotificationTarget> {
+    let rule = config.notification_rule_for(severity, event);
+    let mut seen = HashSet::new();
+    incident_channel
+        .map(str::to_string)
+        .into_iter()
+        .chain(rule.channels)
+        .chain(config.service_channels_for(service).iter().cloned())
+        .filter(|channel| seen.insert(channel.clone()))
+        .map(NotificationTarget::Channel)
+        .chain(rule.users.into_iter().map(NotificationTarget::Dm))
+        .chain(
+            rule.user_groups
+                .into_iter()
+                .map(NotificationTarget::UserGroup),
+        )
+        .collect()
+}
+
+/// DM `execs` after the channels of `targets`, ahead of any other DMs.
+pub fn add_exec_targets(targets: &mut Vec<NotificationTarget>, execs: ExecTier) {
+    let first_dm = targets
+        .iter()
+        .position(|target| !matches!(target, NotificationTarget::Channel(_)))
+        .unwrap_or(targets.len());
+    targets.splice(
+        first_dm..first_dm,
+        execs.users.into_iter().map(NotificationTarget::Dm).chain(
+            execs
+                .user_groups
+                .into_iter()
+                .map(NotificationTarget::UserGroup),
+        ),
+    );
+}
+
 pub struct NotificationService {
     pool: PgPool,
-    slack_client: SlackClient,
+    slack_client: Arc<dyn SlackApi>,
     config: Arc<AppConfig>,
     // Throttle map: (recipient, incident_id) -> last notification timestamp
     throttle_map: Arc<Mutex<NotificationThrottleMap>>,
 }
 
 impl NotificationService {
-    pub fn new(pool: PgPool, slack_client: SlackClient, config: Arc<AppConfig>) -> Self {
+    pub fn new(pool: PgPool, slack_client: Arc<dyn SlackApi>, config: Arc<AppConfig>) -> Self {
         Self {
             pool,
             slack_client,
@@ -36,8 +103,16 @@ impl NotificationService {
         incident: &Incident,
         blocks: Vec<Value>,
     ) -> IncidentResult<()> {
-        self.route_by_severity(incident, blocks, "incident_declared")
-            .await
+        let execs = self.claim_first_exec_tier(incident).await?;
+        self.route_by_severity(
+            incident,
+            blocks.clone(),
+            NotificationEvent::Declared,
+            execs,
+            false,
+        )
+        .await?;
+        self.notify_subscribers(incident, &blocks).await
     }
 
     pub async fn notify_status_update(
@@ -45,35 +120,32 @@ impl NotificationService {
         incident: &Incident,
         blocks: Vec<Value>,
     ) -> IncidentResult<()> {
-        // Status updates only go to incident channel
-        if let Some(channel_id) = &incident.slack_channel_id {
-            self.send_to_channel(incident.id, channel_id, &blocks)
-                .await?;
-        }
-        Ok(())
+        self.post_to_channel_and_threads(incident, &blocks).await?;
+        self.notify_subscribers(incident, &blocks).await
     }
 
     pub async fn notify_severity_change(
         &self,
         incident: &Incident,
-        old_severity: Severity,
+        old_level: &SeverityLevel,
         blocks: Vec<Value>,
     ) -> IncidentResult<()> {
-        // If escalating TO P1 or P2, send broader notifications
-        let escalating_to_p1 = incident.severity == Severity::P1 && old_severity != Severity::P1;
-        let escalating_to_p2 = incident.severity == Severity::P2 && old_severity != Severity::P2;
-
-        if escalating_to_p1 || escalating_to_p2 {
-            self.route_by_severity(incident, blocks, "severity_escalation")
-                .await
+        // Escalations go out per the new tier's `escalated` rule
+        if severity::rank(incident.severity_level()) < severity::rank(old_level) {
+            let execs = self.claim_first_exec_tier(incident).await?;
+            self.route_by_severity(
+                incident,
+                blocks.clone(),
+                NotificationEvent::Escalated,
+                execs,
+                true,
+            )
+            .await?;
         } else {
-            // Downgrade or same severity: only incident channel
-            if let Some(channel_id) = &incident.slack_channel_id {
-                self.send_to_channel(incident.id, channel_id, &blocks)
-                    .await?;
-            }
-            Ok(())
+            // Downgrade or same severity: no new recipients
+            self.post_to_channel_and_threads(incident, &blocks).await?;
         }
+        self.notify_subscribers(incident, &blocks).await
     }
 
     pub async fn notify_resolution(
@@ -81,72 +153,362 @@ impl NotificationService {
         incident: &Incident,
         blocks: Vec<Value>,
     ) -> IncidentResult<()> {
-        // Resolution notifications go to same channels as initial declaration
-        self.route_by_severity(incident, blocks, "incident_resolved")
-            .await
+        // Resolution falls back to the declaration routing unless a
+        // `resolved` rule is configured
+        let execs = exec_tiers::delivered_recipients(&self.pool, incident.id).await?;
+        self.route_by_severity(
+            incident,
+            blocks.clone(),
+            NotificationEvent::Resolved,
+            execs,
+            true,
+        )
+        .await?;
+        self.notify_subscribers(incident, &blocks).await
+    }
+
+    /// Reopened incidents are announced wherever their declaration went.
+    pub async fn notify_reopened(
+        &self,
+        incident: &Incident,
+        blocks: Vec<Value>,
+    ) -> IncidentResult<()> {
+        let execs = exec_tiers::delivered_recipients(&self.pool, incident.id).await?;
+        self.route_by_severity(
+            incident,
+            blocks.clone(),
+            NotificationEvent::Declared,
+            execs,
+            true,
+        )
+        .await?;
+        self.notify_subscribers(incident, &blocks).await
+    }
+
+    /// False alarms are announced wherever their declaration went, so
+    /// everyone who heard about the incident hears it's off.
+    pub async fn notify_canceled(
+        &self,
+        incident: &Incident,
+        blocks: Vec<Value>,
+    ) -> IncidentResult<()> {
+        let execs = exec_tiers::delivered_recipients(&self.pool, incident.id).await?;
+        self.route_by_severity(
+            incident,
+            blocks.clone(),
+            NotificationEvent::Declared,
+            execs,
+            true,
+        )
+        .await?;
+        self.notify_subscribers(incident, &blocks).await
+    }
+
+    /// DM a digest of `blocks` to everyone subscribed to the incident or its
+    /// service (`/incident subscribe`). Not throttled: each digest is a
+    /// distinct change.
+    pub async fn notify_subscribers(
+        &self,
+        incident: &Incident,
+        blocks: &[Value],
+    ) -> IncidentResult<()> {
+        for subscription in subscriptions::subscribers_for(&self.pool, incident).await? {
+            let digest = subscription_digest_blocks(incident, &subscription, blocks);
+            self.send_dm(incident.id, &subscription.user_id, &digest)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// DM one exec tier of an unresolved incident, with user groups expanded.
+    /// The tier must already be claimed (`exec_tiers::claim_tier`).
+    pub async fn notify_exec_tier(
+        &self,
+        incident: &Incident,
+        recipients: &ExecTier,
+        blocks: Vec<Value>,
+    ) -> IncidentResult<()> {
+        let mut user_ids = recipients.users.clone();
+        for group_id in &recipients.user_groups {
+            match self.slack_client.usergroup_members(group_id).await {
+                Ok(members) => user_ids.extend(members),
+                Err(e) => warn!("Failed to expand user group {}: {}", group_id, e),
+            }
+        }
+        let mut seen = HashSet::new();
+        user_ids.retain(|user_id| seen.insert(user_id.clone()));
+        for user_id in user_ids {
+            self.send_dm(incident.id, &user_id, &blocks).await?;
+        }
+        Ok(())
+    }
+
+    /// Tier 1 of the incident's exec tiers, if it hasn't gone out yet. It is
+    /// claimed here and sent with the notification. Quiet incidents have no
+    /// exec notification.
+    async fn claim_first_exec_tier(&self, incident: &Incident) -> IncidentResult<ExecTier> {
+        let tier1 = self.config.exec_tiers_for(incident.severity).tier1;
+        if incident.is_quiet
+            || tier1.is_empty()
+            || !exec_tiers::claim_tier(&self.pool, incident.id, 1, &tier1, chrono::Utc::now())
+                .await?
+        {
+            return Ok(ExecTier::default());
+        }
+        Ok(tier1)
+    }
+
+    /// Updates that add no recipients: the incident channel, plus a quiet
+    /// reply in each fan-out channel's thread.
+    async fn post_to_channel_and_threads(
+        &self,
+        incident: &Incident,
+        blocks: &[Value],
+    ) -> IncidentResult<()> {
+        if let Some(channel_id) = &incident.slack_channel_id {
+            self.send_to_channel(incident.id, channel_id, None, blocks)
+                .await?;
+        }
+        for (channel_id, thread_ts) in
+            broadcast_threads::list_broadcast_threads(&self.pool, incident.id).await?
+        {
+            self.send_thread_reply(incident.id, &channel_id, &thread_ts, false, blocks)
+                .await?;
+        }
+        Ok(())
     }
 
+    /// Fan-out channels (everything but the incident's own channel) get one
+    /// top-level message per incident; later notifications reply in its
+    /// thread, shown in the channel too when `broadcast_replies` is set.
+    /// `execs` are DMed ahead of the rule's recipients.
     async fn route_by_severity(
         &self,
         incident: &Incident,
         blocks: Vec<Value>,
-        _event_type: &str,
+        event: NotificationEvent,
+        execs: ExecTier,
+        broadcast_replies: bool,
     ) -> IncidentResult<()> {
-        match incident.severity {
-            Severity::P1 => {
-                // P1: incident channel + #general + DM execs
-                if let Some(channel_id) = &incident.slack_channel_id {
-                    self.send_to_channel(incident.id, channel_id, &blocks)
-                        .await?;
+        // Quiet (security) incidents are never broadcast
+        let mut targets = if incident.is_quiet {
+            incident
+                .slack_channel_id
+                .iter()
+                .cloned()
+                .map(NotificationTarget::Channel)
+                .collect()
+        } else {
+            plan_notifications(
+                &self.config,
+                incident.severity,
+                event,
+                &incident.affected_service,
+                incident.slack_channel_id.as_deref(),
+            )
+        };
+
+        // P1 channel posts also mention the user groups, so the teams are
+        // paged where the incident is discussed and not only by DM
+        let groups: Vec<String> = targets
+            .iter()
+            .filter_map(|target| match target {
+                NotificationTarget::UserGroup(group_id) => Some(group_id.clone()),
+                _ => None,
+            })
+            .collect();
+        let channel_blocks = if incident.severity == Severity::P1 && !groups.is_empty() {
+            std::iter::once(user_group_mention_block(&groups))
+                .chain(blocks.iter().cloned())
+                .collect()
+        } else {
+            blocks.clone()
+        };
+
+        if !incident.is_quiet {
+            add_exec_targets(&mut targets, execs);
+        }
+
+        // Expand user groups into DMs, skipping users already DMed
+        let mut expanded = Vec::with_capacity(targets.len());
+        for target in targets {
+            match target {
+                NotificationTarget::UserGroup(group_id) => {
+                    match self.slack_client.usergroup_members(&group_id).await {
+                        Ok(members) => {
+                            expanded.extend(members.into_iter().map(NotificationTarget::Dm))
+                        }
+                        Err(e) => warn!("Failed to expand user group {}: {}", group_id, e),
+                    }
                 }
+                target => expanded.push(target),
+            }
+        }
+        let mut seen = HashSet::new();
+        expanded.retain(|t| !matches!(t, NotificationTarget::Dm(u) if !seen.insert(u.clone())));
 
-                // Post to all P1 channels
-                for channel_id in &self.config.p1_channels {
-                    self.send_to_channel(incident.id, channel_id, &blocks)
+        for target in expanded {
+            match target {
+                NotificationTarget::Channel(channel_id)
+                    if incident.slack_channel_id.as_ref() == Some(&channel_id) =>
+                {
+                    self.send_to_channel(incident.id, &channel_id, None, &channel_blocks)
                         .await?;
                 }
-
-                // DM all P1 recipients
-                for user_id in &self.config.p1_users {
-                    if self.should_send_dm(user_id, incident.id).await {
-                        self.send_dm(incident.id, user_id, &blocks).await?;
+                NotificationTarget::Channel(channel_id) => {
+                    self.send_to_fan_out_channel(
+                        incident.id,
+                        &channel_id,
+                        &channel_blocks,
+                        broadcast_replies,
+                    )
+                    .await?;
+                }
+                NotificationTarget::Dm(user_id) => {
+                    if self.should_send_dm(&user_id, incident.id).await {
+                        self.send_dm(incident.id, &user_id, &blocks).await?;
                     } else {
                         info!("Throttling DM to {} for incident {}", user_id, incident.id);
                         // Log throttled notification to database for audit trail
-                        notifications::log_notification(
-                            &self.pool,
+                        self.log_notification(
                             incident.id,
                             NotificationType::SlackDm,
-                            user_id.to_string(),
+                            user_id,
                             NotificationStatus::Throttled,
                             None,
+                            Some(&blocks),
                         )
                         .await?;
                     }
                 }
+                // Expanded into DMs above
+                NotificationTarget::UserGroup(_) => {}
             }
-            Severity::P2 => {
-                // P2: incident channel + #engineering
-                if let Some(channel_id) = &incident.slack_channel_id {
-                    self.send_to_channel(incident.id, channel_id, &blocks)
-                        .await?;
-                }
+        }
 
-                for channel_id in &self.config.p2_channels {
-                    self.send_to_channel(incident.id, channel_id, &blocks)
-                        .await?;
-                }
+        Ok(())
+    }
+
+    /// Resend a failed, throttled or deferred notification now, bypassing
+    /// the DM throttle. A resend that fails again leaves it failed with the
+    /// new error; either way the updated record is returned.
+    pub async fn retry(&self, id: Uuid, actor_id: &str) -> IncidentResult<NotificationRecord> {
+        let record = Self::unsent(notifications::get_notification(&self.pool, id).await?)?;
+        let blocks = match record.blocks.as_ref().and_then(Value::as_array) {
+            Some(blocks) => blocks.clone(),
+            None => {
+                return Err(IncidentError::ValidationError {
+                    field: "notification".to_string(),
+                    reason: "This notification was logged without its message and can't be resent; skip it instead".to_string(),
+                })
             }
-            Severity::P3 | Severity::P4 => {
-                // P3/P4: incident channel only
-                if let Some(channel_id) = &incident.slack_channel_id {
-                    self.send_to_channel(incident.id, channel_id, &blocks)
-                        .await?;
-                }
+        };
+
+        let sent = match record.notification_type {
+            // Fan-out channels get everything after the first broadcast in its thread
+            NotificationType::SlackChannel => match broadcast_threads::get_broadcast_thread(
+                &self.pool,
+                record.incident_id,
+                &record.recipient,
+            )
+            .await?
+            {
+                Some(thread_ts) => self
+                    .slack_client
+                    .post_thread_reply(&record.recipient, &thread_ts, blocks, record.broadcast)
+                    .await
+                    .map(|_| ()),
+                None => self
+                    .slack_client
+                    .post_message(&record.recipient, blocks)
+                    .await
+                    .map(|_| ()),
+            },
+            NotificationType::SlackDm => self.slack_client.send_dm(&record.recipient, blocks).await,
+        };
+        let updated = match sent {
+            Ok(()) => {
+                notifications::mark_resent(&self.pool, id, actor_id, chrono::Utc::now()).await?
+            }
+            Err(e) => {
+                warn!("Retry of notification {} failed: {}", id, e);
+                notifications::record_retry_failure(&self.pool, id, &e.to_string()).await?
             }
         }
+        .ok_or_else(Self::already_handled)?;
+        metrics().record_notification(
+            updated.notification_type.as_db_str(),
+            updated.status.as_db_str(),
+        );
 
-        Ok(())
+        self.audit("notification_retried", &record, &updated, actor_id)
+            .await?;
+        info!(
+            "{} retried notification {} to {}: {}",
+            actor_id,
+            id,
+            record.recipient,
+            updated.status.as_db_str()
+        );
+        Ok(updated)
+    }
+
+    /// Give up on a failed, throttled or deferred notification.
+    pub async fn skip(&self, id: Uuid, actor_id: &str) -> IncidentResult<NotificationRecord> {
+        let record = Self::unsent(notifications::get_notification(&self.pool, id).await?)?;
+        let updated = notifications::mark_skipped(&self.pool, id, actor_id)
+            .await?
+            .ok_or_else(Self::already_handled)?;
+
+        self.audit("notification_skipped", &record, &updated, actor_id)
+            .await?;
+        info!(
+            "{} skipped notification {} to {}",
+            actor_id, id, record.recipient
+        );
+        Ok(updated)
+    }
+
+    fn unsent(record: NotificationRecord) -> IncidentResult<NotificationRecord> {
+        if record.is_unsent() {
+            Ok(record)
+        } else {
+            Err(IncidentError::ValidationError {
+                field: "notification".to_string(),
+                reason: format!("Notification is already {}", record.status.as_db_str()),
+            })
+        }
+    }
+
+    fn already_handled() -> IncidentError {
+        IncidentError::ValidationError {
+            field: "notification".to_string(),
+            reason: "Notification was retried or skipped by someone else".to_string(),
+        }
+    }
+
+    async fn audit(
+        &self,
+        action: &str,
+        before: &NotificationRecord,
+        after: &NotificationRecord,
+        actor_id: &str,
+    ) -> IncidentResult<()> {
+        AuditService::new(self.pool.clone())
+            .log_action(
+                Some(before.incident_id),
+                action.to_string(),
+                actor_id.to_string(),
+                Some(json!({ "status": before.status.as_db_str() })),
+                Some(json!({ "status": after.status.as_db_str() })),
+                Some(json!({
+                    "notification_id": before.id,
+                    "type": before.notification_type.as_db_str(),
+                    "recipient": before.recipient,
+                    "error": after.error_message,
+                })),
+            )
+            .await
     }
 
     async fn should_send_dm(&self, user_id: &str, incident_id: IncidentId) -> bool {
@@ -174,39 +536,241 @@ impl NotificationService {
         true
     }
 
-    async fn send_to_channel(
+    async fn send_to_fan_out_channel(
         &self,
         incident_id: IncidentId,
         channel_id: &str,
         blocks: &[Value],
+        broadcast_replies: bool,
     ) -> IncidentResult<()> {
-        // Clone only when actually sending to reduce memory allocations
-        match self
-            .slack_client
-            .post_message(channel_id, blocks.to_vec())
-            .await
-        {
-            Ok(_) => {
-                notifications::log_notification(
+        match broadcast_threads::get_broadcast_thread(&self.pool, incident_id, channel_id).await? {
+            Some(thread_ts) => {
+                self.send_thread_reply(
+                    incident_id,
+                    channel_id,
+                    &thread_ts,
+                    broadcast_replies,
+                    blocks,
+                )
+                .await?;
+            }
+            None => {
+                let ts = self
+                    .send_to_channel(incident_id, channel_id, None, blocks)
+                    .await?;
+                broadcast_threads::record_broadcast_thread(
                     &self.pool,
+                    incident_id,
+                    channel_id,
+                    &ts,
+                )
+                .await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Reply in a fan-out channel's thread. While Slack is rate limiting the
+    /// bot the reply is held back, logged as pending, and goes out batched
+    /// with the next reply to the thread (or `flush_deferred`) once it stops.
+    async fn send_thread_reply(
+        &self,
+        incident_id: IncidentId,
+        channel_id: &str,
+        thread_ts: &str,
+        broadcast: bool,
+        blocks: &[Value],
+    ) -> IncidentResult<()> {
+        if self.slack_client.throttle().is_throttled() {
+            return self.defer(incident_id, channel_id, broadcast, blocks).await;
+        }
+        let deferred = notifications::list_deferred(&self.pool, incident_id, channel_id).await?;
+        let posted = if deferred.is_empty() {
+            self.slack_client
+                .post_thread_reply(channel_id, thread_ts, blocks.to_vec(), broadcast)
+                .await
+                .map(|_| ())
+        } else {
+            let mut updates = Self::deferred_updates(&deferred);
+            updates.push((broadcast, blocks.to_vec()));
+            self.post_batches(channel_id, thread_ts, &updates).await
+        };
+        match posted {
+            Ok(()) => {
+                self.mark_batched(&deferred).await?;
+                self.log_notification(
                     incident_id,
                     NotificationType::SlackChannel,
                     channel_id.to_string(),
                     NotificationStatus::Sent,
                     None,
+                    None,
+                )
+                .await
+            }
+            // Rate limited again: this one waits with the others
+            Err(e) if is_rate_limited(&e) => {
+                self.defer(incident_id, channel_id, broadcast, blocks).await
+            }
+            Err(e) => {
+                error!("Failed to post to channel {}: {}", channel_id, e);
+                self.log_thread_reply(
+                    incident_id,
+                    channel_id,
+                    NotificationStatus::Failed,
+                    Some(e.to_string()),
+                    broadcast,
+                    blocks,
                 )
                 .await?;
-                Ok(())
+                Err(e)
+            }
+        }
+    }
+
+    /// Send the thread replies deferred while Slack was rate limiting, one
+    /// batch per thread (split where replies differ in being broadcast). Does nothing while it still is; stops at the first
+    /// thread Slack rate limits again. Returns the number of replies sent.
+    pub async fn flush_deferred(&self) -> IncidentResult<usize> {
+        let mut flushed = 0;
+        for (incident_id, channel_id, thread_ts) in
+            notifications::list_deferred_threads(&self.pool).await?
+        {
+            if self.slack_client.throttle().is_throttled() {
+                break;
+            }
+            let deferred =
+                notifications::list_deferred(&self.pool, incident_id, &channel_id).await?;
+            let updates = Self::deferred_updates(&deferred);
+            if updates.is_empty() {
+                continue;
+            }
+            match self.post_batches(&channel_id, &thread_ts, &updates).await {
+                Ok(()) => flushed += self.mark_batched(&deferred).await?,
+                Err(e) if is_rate_limited(&e) => break,
+                // Left pending for `/incident notifications`
+                Err(e) => error!(
+                    "Failed to post deferred updates to {} for incident {}: {}",
+                    channel_id, incident_id, e
+                ),
+            }
+        }
+        Ok(flushed)
+    }
+
+    async fn defer(
+        &self,
+        incident_id: IncidentId,
+        channel_id: &str,
+        broadcast: bool,
+        blocks: &[Value],
+    ) -> IncidentResult<()> {
+        info!(
+            "Slack is rate limiting; deferring update to {} for incident {}",
+            channel_id, incident_id
+        );
+        self.log_thread_reply(
+            incident_id,
+            channel_id,
+            NotificationStatus::Pending,
+            None,
+            broadcast,
+            blocks,
+        )
+        .await
+    }
+
+    /// Post `(broadcast, blocks)` updates in order, batching consecutive ones
+    /// alike in being broadcast. A batch too long for one message goes out as
+    /// several; if a later one fails, retrying the batch repeats the earlier
+    /// ones, which is preferable to dropping updates.
+    async fn post_batches(
+        &self,
+        channel_id: &str,
+        thread_ts: &str,
+        updates: &[(bool, Vec<Value>)],
+    ) -> IncidentResult<()> {
+        for run in updates.chunk_by(|a, b| a.0 == b.0) {
+            let broadcast = run[0].0;
+            let batch: Vec<Vec<Value>> = run.iter().map(|(_, blocks)| blocks.clone()).collect();
+            for message in batched_update_messages(&batch) {
+                self.slack_client
+                    .post_thread_reply(channel_id, thread_ts, message, broadcast)
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    fn deferred_updates(deferred: &[NotificationRecord]) -> Vec<(bool, Vec<Value>)> {
+        deferred
+            .iter()
+            .filter_map(|record| {
+                let blocks = record.blocks.as_ref().and_then(Value::as_array)?;
+                Some((record.broadcast, blocks.clone()))
+            })
+            .collect()
+    }
+
+    async fn mark_batched(&self, deferred: &[NotificationRecord]) -> IncidentResult<usize> {
+        if deferred.is_empty() {
+            return Ok(0);
+        }
+        let ids: Vec<Uuid> = deferred.iter().map(|record| record.id).collect();
+        let sent = notifications::mark_batched(&self.pool, &ids, chrono::Utc::now()).await?;
+        for _ in 0..sent {
+            metrics().record_notification(
+                NotificationType::SlackChannel.as_db_str(),
+                NotificationStatus::Sent.as_db_str(),
+            );
+        }
+        Ok(sent as usize)
+    }
+
+    /// Post top-level, or as a reply to `(thread_ts, broadcast)`, returning
+    /// the message's `ts`.
+    async fn send_to_channel(
+        &self,
+        incident_id: IncidentId,
+        channel_id: &str,
+        thread: Option<(&str, bool)>,
+        blocks: &[Value],
+    ) -> IncidentResult<String> {
+        // Clone only when actually sending to reduce memory allocations
+        let posted = match thread {
+            Some((thread_ts, broadcast)) => {
+                self.slack_client
+                    .post_thread_reply(channel_id, thread_ts, blocks.to_vec(), broadcast)
+                    .await
+            }
+            None => {
+                self.slack_client
+                    .post_message(channel_id, blocks.to_vec())
+                    .await
+            }
+        };
+        match posted {
+            Ok(ts) => {
+                self.log_notification(
+                    incident_id,
+                    NotificationType::SlackChannel,
+                    channel_id.to_string(),
+                    NotificationStatus::Sent,
+                    None,
+                    None,
+                )
+                .await?;
+                Ok(ts)
             }
             Err(e) => {
                 error!("Failed to post to channel {}: {}", channel_id, e);
-                notifications::log_notification(
-                    &self.pool,
+                self.log_notification(
                     incident_id,
                     NotificationType::SlackChannel,
                     channel_id.to_string(),
                     NotificationStatus::Failed,
                     Some(e.to_string()),
+                    Some(blocks),
                 )
                 .await?;
                 Err(e)
@@ -220,29 +784,31 @@ impl NotificationService {
         user_id: &str,
         blocks: &[Value],
     ) -> IncidentResult<()> {
+        // Spaced out while Slack is rate limiting the bot
+        self.slack_client.throttle().pace().await;
         // Clone only when actually sending to reduce memory allocations
         match self.slack_client.send_dm(user_id, blocks.to_vec()).await {
             Ok(_) => {
-                notifications::log_notification(
-                    &self.pool,
+                self.log_notification(
                     incident_id,
                     NotificationType::SlackDm,
                     user_id.to_string(),
                     NotificationStatus::Sent,
                     None,
+                    None,
                 )
                 .await?;
                 Ok(())
             }
             Err(e) => {
                 warn!("Failed to send DM to {}: {}", user_id, e);
-                notifications::log_notification(
-                    &self.pool,
+                self.log_notification(
                     incident_id,
                     NotificationType::SlackDm,
                     user_id.to_string(),
                     NotificationStatus::Failed,
                     Some(e.to_string()),
+                    Some(blocks),
                 )
                 .await?;
                 // Don't fail the whole operation if one DM fails
@@ -250,4 +816,54 @@ impl NotificationService {
             }
         }
     }
+
+    async fn log_thread_reply(
+        &self,
+        incident_id: IncidentId,
+        channel_id: &str,
+        status: NotificationStatus,
+        error_message: Option<String>,
+        broadcast: bool,
+        blocks: &[Value],
+    ) -> IncidentResult<()> {
+        metrics().record_notification(
+            NotificationType::SlackChannel.as_db_str(),
+            status.as_db_str(),
+        );
+        notifications::log_thread_reply(
+            &self.pool,
+            incident_id,
+            channel_id,
+            status,
+            error_message,
+            broadcast,
+            Value::from(blocks.to_vec()),
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn log_notification(
+        &self,
+        incident_id: IncidentId,
+        notification_type: NotificationType,
+        recipient: String,
+        status: NotificationStatus,
+        error_message: Option<String>,
+        // Kept for notifications that weren't sent, so they can be retried
+        blocks: Option<&[Value]>,
+    ) -> IncidentResult<()> {
+        metrics().record_notification(notification_type.as_db_str(), status.as_db_str());
+        notifications::log_notification(
+            &self.pool,
+            incident_id,
+            notification_type,
+            recipient,
+            status,
+            error_message,
+            blocks.map(|blocks| Value::from(blocks.to_vec())),
+        )
+        .await?;
+        Ok(())
+    }
 }
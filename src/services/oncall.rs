@@ -0,0 +1,119 @@
+use crate::app_state::AppState;
+use crate::error::IncidentResult;
+use chrono::{DateTime, Utc};
+use tracing::warn;
+
+/// Who is on call for a service right now.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OnCall {
+    /// Matched to a Slack user by email
+    User(String),
+    /// On call in the schedule, but no Slack user has this email
+    Email(String),
+    Nobody,
+}
+
+/// Current on-call for `service`, or `None` when on-call lookups are off or
+/// the service has no schedule in ONCALL_SCHEDULES.
+pub async fn current_on_call(state: &AppState, service: &str) -> IncidentResult<Option<OnCall>> {
+    let (Some(client), Some(schedule_id)) = (
+        state.oncall_client.as_ref(),
+        state.config.oncall_schedule_for(service),
+    ) else {
+        return Ok(None);
+    };
+
+    let Some(email) = client.current_on_call(schedule_id).await? else {
+        return Ok(Some(OnCall::Nobody));
+    };
+    Ok(Some(
+        match state.slack_client.lookup_user_by_email(&email).await? {
+            Some(user_id) => OnCall::User(user_id),
+            None => OnCall::Email(email),
+        },
+    ))
+}
+
+/// Slack user to suggest as commander for `service`. Lookup failures are
+/// logged and yield no suggestion; they must never block a declaration.
+pub async fn suggested_commander(state: &AppState, service: &str) -> Option<String> {
+    match current_on_call(state, service).await {
+        Ok(Some(OnCall::User(user_id))) => Some(user_id),
+        Ok(_) => None,
+        Err(e) => {
+            warn!("On-call lookup for {} failed: {}", service, e);
+            None
+        }
+    }
+}
+
+/// Major incident manager on duty at `now` and where they came from
+/// (`MIM_USERS` or the schedule ID), or `None` when no rotation is set.
+pub async fn major_incident_manager(
+    state: &AppState,
+    now: DateTime<Utc>,
+) -> IncidentResult<Option<(OnCall, String)>> {
+    if let Some(user_id) = weekly_rotation(&state.config.mim_users, now) {
+        return Ok(Some((
+            OnCall::User(user_id.to_string()),
+            "MIM_USERS".to_string(),
+        )));
+    }
+    let (Some(client), Some(schedule_id)) = (
+        state.oncall_client.as_ref(),
+        state
+            .config
+            .mim_schedule
+            .as_deref()
+            .filter(|s| !s.trim().is_empty()),
+    ) else {
+        return Ok(None);
+    };
+
+    let on_call = match client.current_on_call(schedule_id).await? {
+        None => OnCall::Nobody,
+        Some(email) => match state.slack_client.lookup_user_by_email(&email).await? {
+            Some(user_id) => OnCall::User(user_id),
+            None => OnCall::Email(email),
+        },
+    };
+    Ok(Some((on_call, schedule_id.to_string())))
+}
+
+/// Member of `users` on duty in the week containing `now`; weeks start
+/// Monday 00:00 UTC and the rotation advances one member a week.
+fn weekly_rotation(users: &[String], now: DateTime<Utc>) -> Option<&str> {
+    if users.is_empty() {
+        return None;
+    }
+    // 1970-01-05 was the first Monday after the epoch
+    const FIRST_MONDAY_SECS: i64 = 4 * 86_400;
+    let week = (now.timestamp() - FIRST_MONDAY_SECS).div_euclid(7 * 86_400);
+    Some(users[week.rem_euclid(users.len() as i64) as usize].as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_weekly_rotation_hands_over_on_monday() {
+        let users = vec!["U_ANA".to_string(), "U_BEN".to_string()];
+        let sunday_night = Utc.with_ymd_and_hms(2026, 4, 5, 23, 59, 0).unwrap();
+        let monday = Utc.with_ymd_and_hms(2026, 4, 6, 0, 0, 0).unwrap();
+
+        let before = weekly_rotation(&users, sunday_night).unwrap();
+        let after = weekly_rotation(&users, monday).unwrap();
+        assert_ne!(before, after);
+        assert_eq!(
+            weekly_rotation(&users, monday + chrono::Duration::days(6)),
+            Some(after)
+        );
+        assert_eq!(
+            weekly_rotation(&users, monday + chrono::Duration::weeks(2)),
+            Some(after)
+        );
+        assert_eq!(weekly_rotation(&[], monday), None);
+    }
+}
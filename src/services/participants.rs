@@ -0,0 +1,91 @@
+use crate::db::models::{IncidentId, Participant, TimelineEvent};
+use crate::db::queries::participants as participant_queries;
+use crate::error::IncidentResult;
+use chrono::{DateTime, Utc};
+use sqlx_postgres::PgPool;
+use std::collections::BTreeMap;
+
+/// Tracks who responded to each incident: channel joins from
+/// `member_joined_channel` events and authors of timeline events.
+pub struct ParticipantService {
+    pool: PgPool,
+}
+
+impl ParticipantService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Record a join of `channel_id`; ignored unless it is an open
+    /// incident's channel.
+    pub async fn record_join(
+        &self,
+        channel_id: &str,
+        user_id: &str,
+        now: DateTime<Utc>,
+    ) -> IncidentResult<bool> {
+        participant_queries::record_join(&self.pool, channel_id, user_id, now).await
+    }
+
+    /// Credit the authors of freshly logged timeline events. Events posted
+    /// by integrations rather than Slack users are skipped.
+    pub async fn record_timeline_events(
+        &self,
+        incident_id: IncidentId,
+        events: &[TimelineEvent],
+    ) -> IncidentResult<()> {
+        // Per author: event count, first and last timestamp
+        let mut authors: BTreeMap<&str, (i32, DateTime<Utc>, DateTime<Utc>)> = BTreeMap::new();
+        for event in events.iter().filter(|e| is_slack_user_id(&e.posted_by)) {
+            let entry = authors.entry(event.posted_by.as_str()).or_insert((
+                0,
+                event.timestamp,
+                event.timestamp,
+            ));
+            entry.0 += 1;
+            entry.1 = entry.1.min(event.timestamp);
+            entry.2 = entry.2.max(event.timestamp);
+        }
+
+        for (user_id, (count, first, last)) in authors {
+            participant_queries::record_timeline_events(
+                &self.pool,
+                incident_id,
+                user_id,
+                count,
+                first,
+                last,
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Participants in the order they first showed up.
+    pub async fn list(&self, incident_id: IncidentId) -> IncidentResult<Vec<Participant>> {
+        participant_queries::list_participants(&self.pool, incident_id).await
+    }
+}
+
+/// Slack user IDs look like `U024BE7LH` (or `W…` on Enterprise Grid).
+pub(crate) fn is_slack_user_id(value: &str) -> bool {
+    let mut chars = value.chars();
+    matches!(chars.next(), Some('U' | 'W'))
+        && value.len() > 1
+        && chars.all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_slack_user_id() {
+        assert!(is_slack_user_id("U024BE7LH"));
+        assert!(is_slack_user_id("W012A3CDE"));
+        assert!(!is_slack_user_id("alertmanager"));
+        assert!(!is_slack_user_id("U"));
+        assert!(!is_slack_user_id("Uppercase"));
+        assert!(!is_slack_user_id(""));
+    }
+}
@@ -0,0 +1,294 @@
+use crate::app_state::AppState;
+use crate::config::{AppConfig, TeamConfig};
+use crate::db::models::{Incident, IncidentRole};
+use crate::error::{IncidentError, IncidentResult};
+use crate::slack::client::SlackApi;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
+use tracing::{info, warn};
+
+/// Incident actions reserved for the commander. Bot administrators may take
+/// any of them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    PostStatusUpdate,
+    ChangeStatus,
+    ChangeSeverity,
+    Resolve,
+    Reopen,
+    Cancel,
+    Reassign,
+    ManageWorkstreams,
+    /// Edit or delete someone else's status update or note (authors may
+    /// always change their own)
+    EditTimeline,
+}
+
+impl Action {
+    /// Completes "You don't have permission to ...".
+    pub fn description(self) -> &'static str {
+        match self {
+            Action::PostStatusUpdate => "post status updates",
+            Action::ChangeStatus => "change incident status",
+            Action::ChangeSeverity => "change incident severity",
+            Action::Resolve => "resolve the incident",
+            Action::Reopen => "reopen the incident",
+            Action::Cancel => "cancel the incident",
+            Action::Reassign => "reassign the incident commander",
+            Action::ManageWorkstreams => "manage workstreams",
+            Action::EditTimeline => "edit other people's timeline events",
+        }
+    }
+}
+
+/// Why an action was allowed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    Commander,
+    Admin,
+}
+
+/// Which incidents a user's analytics queries may cover.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AnalyticsScope {
+    /// Every service: admins, `REPORTING_USERS`, or no `TEAMS` configured
+    OrgWide,
+    /// Only the services of the user's teams (possibly none)
+    Services(Vec<String>),
+}
+
+impl AnalyticsScope {
+    pub fn allows(&self, service: &str) -> bool {
+        match self {
+            AnalyticsScope::OrgWide => true,
+            AnalyticsScope::Services(services) => services.iter().any(|s| s == service),
+        }
+    }
+
+    /// The services to filter queries to; `None` when unrestricted.
+    pub fn services(&self) -> Option<&[String]> {
+        match self {
+            AnalyticsScope::OrgWide => None,
+            AnalyticsScope::Services(services) => Some(services),
+        }
+    }
+}
+
+/// Shown instead of a restricted custom field's value.
+pub const REDACTED_FIELD: &str = "🔒 Restricted";
+
+/// Role API token holders (REST responses, outbound webhooks) hold in
+/// RESTRICTED_FIELDS.
+pub const API_FIELD_ROLE: &str = "api";
+
+/// Which custom fields a viewer may read: everything except the
+/// RESTRICTED_FIELDS entries whose roles they hold none of.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FieldVisibility {
+    hidden: HashSet<String>,
+}
+
+impl FieldVisibility {
+    pub fn for_roles(config: &AppConfig, roles: &[&str]) -> Self {
+        Self {
+            hidden: config
+                .restricted_fields
+                .iter()
+                .filter(|(_, allowed)| !allowed.iter().any(|r| roles.contains(&r.as_str())))
+                .map(|(field, _)| field.clone())
+                .collect(),
+        }
+    }
+
+    /// Messages everyone in a channel can read: only unrestricted fields.
+    pub fn shared(config: &AppConfig) -> Self {
+        Self::for_roles(config, &[])
+    }
+
+    /// REST API responses and webhook payloads.
+    pub fn api(config: &AppConfig) -> Self {
+        Self::for_roles(config, &[API_FIELD_ROLE])
+    }
+
+    /// `value`, or the placeholder when `field` is hidden.
+    pub fn value<'a>(&self, field: &str, value: &'a str) -> &'a str {
+        if self.hidden.contains(field) {
+            REDACTED_FIELD
+        } else {
+            value
+        }
+    }
+
+    pub fn redact(&self, fields: &BTreeMap<String, String>) -> BTreeMap<String, String> {
+        fields
+            .iter()
+            .map(|(field, value)| (field.clone(), self.value(field, value).to_string()))
+            .collect()
+    }
+
+    /// `incident` as this viewer may see it.
+    pub fn redact_incident(&self, mut incident: Incident) -> Incident {
+        incident.custom_fields = self.redact(&incident.custom_fields);
+        incident
+    }
+}
+
+/// Who may act on an incident: its commander, plus bot administrators
+/// (`ADMIN_USERS` and members of `ADMIN_USER_GROUPS`). The default has no
+/// administrators, leaving actions to the commander alone.
+#[derive(Clone, Default)]
+pub struct Permissions {
+    admin_users: Vec<String>,
+    admin_user_groups: Vec<String>,
+    reporting_users: Vec<String>,
+    reporting_user_groups: Vec<String>,
+    teams: HashMap<String, TeamConfig>,
+    slack_client: Option<Arc<dyn SlackApi>>,
+}
+
+impl Permissions {
+    pub fn from_state(state: &AppState) -> Self {
+        Self {
+            admin_users: state.config.admin_users.clone(),
+            admin_user_groups: state.config.admin_user_groups.clone(),
+            reporting_users: state.config.reporting_users.clone(),
+            reporting_user_groups: state.config.reporting_user_groups.clone(),
+            teams: state.config.teams.clone(),
+            slack_client: Some(state.slack_client.clone()),
+        }
+    }
+
+    /// Whether `user_id` is a bot administrator. User groups are looked up on
+    /// every check so membership changes apply immediately; a group that
+    /// can't be read is skipped.
+    pub async fn is_admin(&self, user_id: &str) -> bool {
+        self.is_member(user_id, &self.admin_users, &self.admin_user_groups)
+            .await
+    }
+
+    /// Every bot administrator: `ADMIN_USERS` and the members of
+    /// `ADMIN_USER_GROUPS`. Groups that can't be read are skipped.
+    pub async fn admins(&self) -> Vec<String> {
+        let mut admins = self.admin_users.clone();
+        if let Some(slack_client) = &self.slack_client {
+            for group in &self.admin_user_groups {
+                match slack_client.usergroup_members(group).await {
+                    Ok(members) => admins.extend(members),
+                    Err(e) => warn!("Failed to read user group {}: {}", group, e),
+                }
+            }
+        }
+        admins.sort();
+        admins.dedup();
+        admins
+    }
+
+    /// The incidents `user_id` may run analytics over. Service teams see
+    /// their own services; admins and holders of the org-wide reporting
+    /// scope see everything. Until `TEAMS` is configured there is no
+    /// ownership to scope by, so everyone is org-wide.
+    pub async fn analytics_scope(&self, user_id: &str) -> AnalyticsScope {
+        if self.teams.is_empty()
+            || self.is_admin(user_id).await
+            || self
+                .is_member(user_id, &self.reporting_users, &self.reporting_user_groups)
+                .await
+        {
+            return AnalyticsScope::OrgWide;
+        }
+
+        let mut services = Vec::new();
+        for team in self.teams.values() {
+            let mut users = team.leads.clone();
+            users.extend(team.members.iter().cloned());
+            if self.is_member(user_id, &users, &team.user_groups).await {
+                services.extend(team.services.iter().cloned());
+            }
+        }
+        services.sort();
+        services.dedup();
+        AnalyticsScope::Services(services)
+    }
+
+    /// The custom fields `user_id` may read on `incident`, given its claimed
+    /// `roles`. Admin and reporting membership is only looked up when a
+    /// restricted field is open to it.
+    pub async fn field_visibility(
+        &self,
+        config: &AppConfig,
+        incident: &Incident,
+        roles: &[IncidentRole],
+        user_id: &str,
+    ) -> FieldVisibility {
+        let wanted = |role: &str| {
+            config
+                .restricted_fields
+                .values()
+                .any(|allowed| allowed.iter().any(|r| r == role))
+        };
+        let mut held = Vec::new();
+        if incident.commander_id == user_id {
+            held.push("commander");
+        }
+        held.extend(
+            roles
+                .iter()
+                .filter(|r| r.user_id == user_id)
+                .map(|r| r.role.as_str()),
+        );
+        if wanted("admin") && self.is_admin(user_id).await {
+            held.push("admin");
+        }
+        if wanted("reporting")
+            && self
+                .is_member(user_id, &self.reporting_users, &self.reporting_user_groups)
+                .await
+        {
+            held.push("reporting");
+        }
+        FieldVisibility::for_roles(config, &held)
+    }
+
+    async fn is_member(&self, user_id: &str, users: &[String], groups: &[String]) -> bool {
+        if users.iter().any(|u| u == user_id) {
+            return true;
+        }
+        let Some(slack_client) = &self.slack_client else {
+            return false;
+        };
+        for group in groups {
+            match slack_client.usergroup_members(group).await {
+                Ok(members) if members.iter().any(|m| m == user_id) => return true,
+                Ok(_) => {}
+                Err(e) => warn!("Failed to read user group {}: {}", group, e),
+            }
+        }
+        false
+    }
+
+    /// Allow `action` for the incident's commander or an administrator.
+    pub async fn authorize(
+        &self,
+        action: Action,
+        incident: &Incident,
+        user_id: &str,
+    ) -> IncidentResult<Role> {
+        if incident.commander_id == user_id {
+            return Ok(Role::Commander);
+        }
+        if self.is_admin(user_id).await {
+            info!(
+                "Admin {} allowed to {} on incident {} (commander {})",
+                user_id,
+                action.description(),
+                incident.id,
+                incident.commander_id
+            );
+            return Ok(Role::Admin);
+        }
+        Err(IncidentError::PermissionDenied {
+            user_id: user_id.to_string(),
+            action: action.description().to_string(),
+        })
+    }
+}
@@ -0,0 +1,44 @@
+use crate::app_state::AppState;
+use crate::db::models::Incident;
+use crate::services::permissions::FieldVisibility;
+use crate::services::workstream::WorkstreamService;
+use crate::slack::blocks;
+use tracing::error;
+
+/// Rewrite the pinned incident summary with the incident's current status,
+/// severity, commander and each workstream's latest update, so people joining
+/// the channel see where things stand. Best-effort: failures are logged, and
+/// incidents declared before summaries were tracked have no pinned ts.
+pub async fn refresh(state: &AppState, incident: &Incident) {
+    let (Some(channel_id), Some(ts)) = (&incident.slack_channel_id, &incident.pinned_message_ts)
+    else {
+        return;
+    };
+
+    let workstreams = match WorkstreamService::new(state.pool.clone())
+        .list(incident)
+        .await
+    {
+        Ok(w) => w,
+        Err(e) => {
+            error!("Failed to load workstreams for summary: {}", e);
+            return;
+        }
+    };
+
+    if let Err(e) = state
+        .slack_client
+        .update_message(
+            channel_id,
+            ts,
+            blocks::incident_summary_blocks(
+                incident,
+                &workstreams,
+                &FieldVisibility::shared(&state.config),
+            ),
+        )
+        .await
+    {
+        error!("Failed to refresh pinned summary: {}", e);
+    }
+}
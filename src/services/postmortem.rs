@@ -1,34 +1,135 @@
-use crate::db::models::Incident;
-use crate::error::IncidentResult;
+use crate::adapters::confluence::ConfluenceClient;
+use crate::db::models::{
+    ActionItem, ActionItemStatus, Incident, IncidentId, Participant, Postmortem, TimelineEventType,
+};
+use crate::db::queries::action_items as action_item_queries;
+use crate::db::queries::postmortems as postmortem_queries;
+use crate::error::{IncidentError, IncidentResult};
+use crate::services::audit::AuditService;
+use crate::services::participants::ParticipantService;
 use crate::services::timeline::TimelineService;
+use crate::utils::time;
+use chrono::{DateTime, Utc};
+use serde_json::json;
 use sqlx_postgres::PgPool;
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tracing::info;
+
+/// How long `POSTMORTEM_PDF_COMMAND` may take before it is killed.
+const PDF_TIMEOUT: Duration = Duration::from_secs(30);
 
 pub struct PostmortemService {
+    pool: PgPool,
     timeline_service: TimelineService,
+    audit_service: AuditService,
+    participant_service: ParticipantService,
 }
 
 impl PostmortemService {
     pub fn new(pool: PgPool) -> Self {
         let timeline_service = TimelineService::new(pool.clone());
-        Self { timeline_service }
+        let audit_service = AuditService::new(pool.clone());
+        let participant_service = ParticipantService::new(pool.clone());
+        Self {
+            pool,
+            timeline_service,
+            audit_service,
+            participant_service,
+        }
+    }
+
+    /// Require a published postmortem by `due_at`. Returns `false` if one
+    /// was already required, e.g. for a reopened incident.
+    pub async fn require(
+        &self,
+        incident_id: IncidentId,
+        due_at: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> IncidentResult<bool> {
+        postmortem_queries::require_postmortem(&self.pool, incident_id, due_at, now).await
+    }
+
+    /// Due date of the incident's postmortem, if its severity required one.
+    pub async fn due_date(&self, incident_id: IncidentId) -> IncidentResult<Option<DateTime<Utc>>> {
+        postmortem_queries::postmortem_due_date(&self.pool, incident_id).await
+    }
+
+    pub async fn get_published(&self, incident: &Incident) -> IncidentResult<Option<Postmortem>> {
+        postmortem_queries::get_postmortem(&self.pool, incident.id).await
+    }
+
+    /// Publish the postmortem to Confluence. Each incident gets one page;
+    /// callers check [`Self::get_published`] first to avoid a duplicate.
+    pub async fn publish(
+        &self,
+        confluence: &ConfluenceClient,
+        space_key: &str,
+        parent_page_id: Option<&str>,
+        incident: &Incident,
+        published_by: String,
+    ) -> IncidentResult<Postmortem> {
+        let markdown = self.generate(incident).await?;
+        let title = format!(
+            "Postmortem: {} ({}, {})",
+            incident.title,
+            time::date(&incident.declared_at),
+            &incident.id.to_string()[..8]
+        );
+        let page = confluence
+            .create_page(space_key, parent_page_id, &title, &markdown)
+            .await?;
+
+        let postmortem = postmortem_queries::record_postmortem(
+            &self.pool,
+            incident.id,
+            &page.id,
+            &page.url,
+            &published_by,
+        )
+        .await?;
+
+        self.timeline_service
+            .log_event(
+                incident.id,
+                TimelineEventType::Note,
+                format!("Postmortem published to Confluence: {}", postmortem.url),
+                published_by.clone(),
+            )
+            .await?;
+
+        self.audit_service
+            .log_action(
+                Some(incident.id),
+                "publish_postmortem".to_string(),
+                published_by,
+                None,
+                None,
+                Some(json!({ "confluence_page_id": postmortem.confluence_page_id, "url": postmortem.url })),
+            )
+            .await?;
+
+        info!(
+            "Postmortem for incident {} published to {}",
+            incident.id, postmortem.url
+        );
+        Ok(postmortem)
     }
 
     pub async fn generate(&self, incident: &Incident) -> IncidentResult<String> {
         let events = self.timeline_service.get_timeline(incident.id).await?;
 
-        let duration_text = if let Some(duration) = incident.duration_minutes {
-            let hours = duration / 60;
-            let mins = duration % 60;
-            if hours > 0 {
-                format!("{}h {}min", hours, mins)
-            } else {
-                format!("{}min", mins)
-            }
-        } else {
-            "unknown".to_string()
-        };
+        let duration_text = incident
+            .duration_minutes
+            .map(|duration| time::duration(duration.into()))
+            .unwrap_or_else(|| "unknown".to_string());
 
         let timeline_md = self.timeline_service.format_as_markdown(&events);
+        let action_items = action_item_queries::list_action_items(&self.pool, incident.id).await?;
+        let action_items_md = format_open_action_items(&action_items);
+        let participants = self.participant_service.list(incident.id).await?;
+        let responders_md = format_responders(&participants);
 
         let template = format!(
             r#"# Postmortem: {} ({})
@@ -42,35 +143,139 @@ impl PostmortemService {
 - **Impact**: [TO BE FILLED BY TEAM]
 - **Root Cause**: [TO BE FILLED BY TEAM]
 
+## Responders
+{}
+
 ## Timeline
 
 {}
 
 ## Action Items
-- [ ] [TO BE ADDED BY TEAM]
+{}
 
 ## Lessons Learned
 - [TO BE FILLED BY TEAM]
 
 ---
 *Generated on {} by Incident Bot*
-*Edit this postmortem and use `/incident postmortem publish` to post to Confluence (Phase 2)*
+*Use `/incident postmortem publish` to post this draft to Confluence*
 "#,
             incident.title,
-            incident.declared_at.format("%Y-%m-%d"),
+            time::date(&incident.declared_at),
             duration_text,
-            incident.declared_at.format("%Y-%m-%d %H:%M %Z"),
-            incident
-                .resolved_at
-                .expect("Resolved incidents must have resolved_at timestamp")
-                .format("%Y-%m-%d %H:%M %Z"),
-            incident.severity.label(),
+            time::date_time(&incident.declared_at),
+            time::date_time(
+                &incident
+                    .resolved_at
+                    .expect("Resolved incidents must have resolved_at timestamp")
+            ),
+            incident.severity_level().label,
             incident.affected_service,
             incident.commander_id,
+            responders_md,
             timeline_md,
-            chrono::Utc::now().format("%Y-%m-%d %H:%M %Z"),
+            action_items_md,
+            time::date_time(&chrono::Utc::now()),
         );
 
         Ok(template)
     }
 }
+
+/// Render a postmortem's Markdown to PDF with `POSTMORTEM_PDF_COMMAND`,
+/// which reads Markdown on stdin and writes the PDF to stdout (for example
+/// `pandoc --from markdown --pdf-engine weasyprint --output -`). The command
+/// is split on whitespace and run without a shell.
+pub async fn render_pdf(command: &str, markdown: &str) -> IncidentResult<Vec<u8>> {
+    let mut args = command.split_whitespace();
+    let program = args
+        .next()
+        .ok_or_else(|| IncidentError::ConfigError("POSTMORTEM_PDF_COMMAND is empty".to_string()))?;
+    let failed = |message: String| IncidentError::ExternalAPIError {
+        service: "pdf".to_string(),
+        message,
+    };
+
+    let mut child = tokio::process::Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| failed(format!("Failed to run {}: {}", program, e)))?;
+
+    // Feed stdin while the output is read, so a large draft can't fill both
+    // pipes and deadlock. Dropping stdin closes it.
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    let input = markdown.as_bytes().to_vec();
+    let writer = tokio::spawn(async move { stdin.write_all(&input).await });
+
+    let output = tokio::time::timeout(PDF_TIMEOUT, child.wait_with_output())
+        .await
+        .map_err(|_| failed(format!("{} timed out", program)))?
+        .map_err(|e| failed(format!("{} failed: {}", program, e)))?;
+    // A command that exits without reading all of stdin fails on its status
+    let _ = writer.await;
+
+    if !output.status.success() {
+        return Err(failed(format!(
+            "{} exited with {}: {}",
+            program,
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    if output.stdout.is_empty() {
+        return Err(failed(format!("{} produced no output", program)));
+    }
+    Ok(output.stdout)
+}
+
+/// One line per participant, in the order they joined the response.
+fn format_responders(participants: &[Participant]) -> String {
+    if participants.is_empty() {
+        return "- [TO BE FILLED BY TEAM]".to_string();
+    }
+
+    participants
+        .iter()
+        .map(|p| {
+            let joined = p
+                .joined_at
+                .map(|at| format!("joined {}", time::clock(&at)))
+                .unwrap_or_else(|| "not in channel".to_string());
+            let events = match p.timeline_events {
+                1 => "1 timeline event".to_string(),
+                n => format!("{} timeline events", n),
+            };
+            format!("- <@{}> — {}, {}", p.user_id, joined, events)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Open action items as a markdown checklist; closed items are left out.
+fn format_open_action_items(items: &[ActionItem]) -> String {
+    let open: Vec<String> = items
+        .iter()
+        .filter(|item| item.status == ActionItemStatus::Open)
+        .map(|item| {
+            let jira = item
+                .jira_issue_key
+                .as_ref()
+                .map(|key| format!(" ({})", key))
+                .unwrap_or_default();
+            format!(
+                "- [ ] #{} {} — <@{}>{}",
+                item.number, item.description, item.owner_id, jira
+            )
+        })
+        .collect();
+
+    if open.is_empty() {
+        "- [ ] [TO BE ADDED BY TEAM]".to_string()
+    } else {
+        open.join("\n")
+    }
+}
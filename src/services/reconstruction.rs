@@ -0,0 +1,339 @@
+use crate::app_state::AppState;
+use crate::db::models::{Incident, NewTimelineEvent, Severity, SlackUserId, TimelineEventType};
+use crate::db::queries::incidents as incident_queries;
+use crate::error::{IncidentError, IncidentResult};
+use crate::services::audit::AuditService;
+use crate::services::timeline::{TimelineService, MAX_BATCH_SIZE};
+use crate::slack::client::{HistoryMessage, HistoryRange};
+use crate::utils::time;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+/// Most channel messages a single reconstruction will import.
+pub const MAX_MESSAGES: usize = 5000;
+
+/// Message subtypes that carry something a person said. Joins, topic changes,
+/// bot posts and the like are left out of reconstructed timelines.
+const CONVERSATION_SUBTYPES: &[&str] = &["thread_broadcast", "file_share", "me_message"];
+
+/// A past incident run by hand in `channel_id`, bounded by the Slack
+/// timestamps of the messages that declared and resolved it.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReconstructionRequest {
+    pub channel_id: String,
+    pub title: String,
+    pub severity: Severity,
+    pub affected_service: String,
+    pub commander_id: SlackUserId,
+    pub declared_ts: String,
+    pub resolved_ts: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Reconstruction {
+    pub dry_run: bool,
+    /// The created incident; `None` on a dry run
+    pub incident: Option<Incident>,
+    pub declared_at: DateTime<Utc>,
+    pub resolved_at: DateTime<Utc>,
+    pub duration_minutes: i64,
+    /// Channel messages left out (bots, joins, empty messages)
+    pub skipped_messages: usize,
+    pub events: Vec<NewTimelineEvent>,
+}
+
+/// A channel message with its Slack timestamp decoded, for choosing
+/// reconstruction boundaries.
+#[derive(Debug, Serialize)]
+pub struct ChannelMessage {
+    pub ts: String,
+    pub at: Option<DateTime<Utc>>,
+    pub user: Option<String>,
+    pub text: String,
+    pub subtype: Option<String>,
+}
+
+/// Decode a Slack message timestamp (`"1700000000.123456"`).
+pub fn parse_slack_ts(ts: &str) -> Option<DateTime<Utc>> {
+    let (seconds, micros) = ts.split_once('.').unwrap_or((ts, "0"));
+    if micros.is_empty() || micros.len() > 6 || !micros.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let seconds = seconds.parse::<i64>().ok()?;
+    let micros = format!("{:0<6}", micros).parse::<u32>().ok()?;
+    DateTime::from_timestamp(seconds, micros * 1000)
+}
+
+/// Top-level messages of `channel_id` between the optional bounds, oldest first.
+pub async fn channel_history(
+    state: &AppState,
+    channel_id: &str,
+    oldest: Option<String>,
+    latest: Option<String>,
+    limit: usize,
+) -> IncidentResult<Vec<ChannelMessage>> {
+    let range = HistoryRange {
+        oldest,
+        latest,
+        max_messages: Some(limit),
+    };
+    let messages = fetch_sorted(state, channel_id, &range).await?;
+    Ok(messages
+        .into_iter()
+        .map(|m| ChannelMessage {
+            at: parse_slack_ts(&m.ts),
+            ts: m.ts,
+            user: m.user,
+            text: m.text,
+            subtype: m.subtype,
+        })
+        .collect())
+}
+
+/// Rebuild a resolved incident and its timeline from the channel history
+/// between the declare and resolve messages. Unless `dry_run`, the incident
+/// is stored with its original times so it shows up in analytics and search.
+/// Nothing is announced: no notifications, webhooks or Statuspage updates.
+pub async fn reconstruct(
+    state: &AppState,
+    request: &ReconstructionRequest,
+    dry_run: bool,
+) -> IncidentResult<Reconstruction> {
+    let (declared_at, resolved_at) = validate_request(request, &state.config.services)?;
+
+    match incident_queries::get_latest_incident_by_channel(&state.pool, &request.channel_id).await {
+        Ok(existing) => {
+            return Err(IncidentError::ValidationError {
+                field: "channel_id".to_string(),
+                reason: format!("Channel already belongs to incident {}", existing.id),
+            })
+        }
+        Err(IncidentError::NotFound) => {}
+        Err(e) => return Err(e),
+    }
+
+    let range = HistoryRange {
+        oldest: Some(request.declared_ts.clone()),
+        latest: Some(request.resolved_ts.clone()),
+        max_messages: Some(MAX_MESSAGES + 1),
+    };
+    let messages = fetch_sorted(state, &request.channel_id, &range).await?;
+    if messages.len() > MAX_MESSAGES {
+        return Err(IncidentError::ValidationError {
+            field: "resolved_ts".to_string(),
+            reason: format!(
+                "More than {} messages between the boundaries; narrow the range",
+                MAX_MESSAGES
+            ),
+        });
+    }
+
+    let duration_minutes = (resolved_at - declared_at).num_minutes();
+    let (events, skipped_messages) = timeline_events(
+        request,
+        &messages,
+        declared_at,
+        resolved_at,
+        duration_minutes,
+    );
+
+    if dry_run {
+        return Ok(Reconstruction {
+            dry_run,
+            incident: None,
+            declared_at,
+            resolved_at,
+            duration_minutes,
+            skipped_messages,
+            events,
+        });
+    }
+
+    let incident = incident_queries::insert_reconstructed_incident(
+        &state.pool,
+        request.title.trim(),
+        request.severity,
+        state
+            .config
+            .incident_prefix_for(&request.affected_service, false),
+        &request.affected_service,
+        &request.commander_id,
+        &request.channel_id,
+        declared_at,
+        resolved_at,
+    )
+    .await?;
+
+    let timeline_service = TimelineService::new(state.pool.clone());
+    for chunk in events.chunks(MAX_BATCH_SIZE) {
+        timeline_service
+            .log_events_batch(incident.id, chunk.to_vec())
+            .await?;
+    }
+
+    AuditService::new(state.pool.clone())
+        .log_action(
+            Some(incident.id),
+            "incident_reconstructed".to_string(),
+            "api".to_string(),
+            None,
+            Some(json!({
+                "status": "resolved",
+                "declared_at": declared_at,
+                "resolved_at": resolved_at,
+            })),
+            Some(json!({
+                "channel_id": request.channel_id,
+                "declared_ts": request.declared_ts,
+                "resolved_ts": request.resolved_ts,
+                "events": events.len(),
+                "skipped_messages": skipped_messages,
+            })),
+        )
+        .await?;
+
+    Ok(Reconstruction {
+        dry_run,
+        incident: Some(incident),
+        declared_at,
+        resolved_at,
+        duration_minutes,
+        skipped_messages,
+        events,
+    })
+}
+
+async fn fetch_sorted(
+    state: &AppState,
+    channel_id: &str,
+    range: &HistoryRange,
+) -> IncidentResult<Vec<HistoryMessage>> {
+    let mut messages = state
+        .slack_client
+        .fetch_channel_history(channel_id, range)
+        .await?;
+    messages.sort_by_key(|m| parse_slack_ts(&m.ts));
+    Ok(messages)
+}
+
+fn validate_request(
+    request: &ReconstructionRequest,
+    services: &[String],
+) -> IncidentResult<(DateTime<Utc>, DateTime<Utc>)> {
+    let title = request.title.trim();
+    if title.is_empty() || title.chars().count() > 100 {
+        return Err(IncidentError::ValidationError {
+            field: "title".to_string(),
+            reason: "Must be 1-100 characters".to_string(),
+        });
+    }
+    if !services.contains(&request.affected_service) {
+        return Err(IncidentError::ValidationError {
+            field: "affected_service".to_string(),
+            reason: format!("Unknown service '{}'", request.affected_service),
+        });
+    }
+    for (field, value) in [
+        ("channel_id", &request.channel_id),
+        ("commander_id", &request.commander_id),
+    ] {
+        if value.trim().is_empty() {
+            return Err(IncidentError::ValidationError {
+                field: field.to_string(),
+                reason: "Required".to_string(),
+            });
+        }
+    }
+
+    let parse = |field: &str, ts: &str| {
+        parse_slack_ts(ts).ok_or_else(|| IncidentError::ValidationError {
+            field: field.to_string(),
+            reason: format!("'{}' is not a Slack message timestamp", ts),
+        })
+    };
+    let declared_at = parse("declared_ts", &request.declared_ts)?;
+    let resolved_at = parse("resolved_ts", &request.resolved_ts)?;
+    if resolved_at <= declared_at {
+        return Err(IncidentError::ValidationError {
+            field: "resolved_ts".to_string(),
+            reason: "Must be after declared_ts".to_string(),
+        });
+    }
+    Ok((declared_at, resolved_at))
+}
+
+/// Declared and Resolved bookends around one Note per conversation message.
+/// Returns the events and how many messages were skipped.
+fn timeline_events(
+    request: &ReconstructionRequest,
+    messages: &[HistoryMessage],
+    declared_at: DateTime<Utc>,
+    resolved_at: DateTime<Utc>,
+    duration_minutes: i64,
+) -> (Vec<NewTimelineEvent>, usize) {
+    let mut events = vec![NewTimelineEvent {
+        event_type: TimelineEventType::Declared,
+        message: format!(
+            "Incident declared: {} (reconstructed from channel history)",
+            request.title.trim()
+        ),
+        posted_by: request.commander_id.clone(),
+        timestamp: Some(declared_at),
+    }];
+
+    let mut skipped = 0;
+    for message in messages {
+        let conversational = message
+            .subtype
+            .as_deref()
+            .is_none_or(|s| CONVERSATION_SUBTYPES.contains(&s));
+        match (&message.user, parse_slack_ts(&message.ts)) {
+            (Some(user), Some(at)) if conversational && !message.text.trim().is_empty() => {
+                events.push(NewTimelineEvent {
+                    event_type: TimelineEventType::Note,
+                    message: message.text.clone(),
+                    posted_by: user.clone(),
+                    timestamp: Some(at),
+                });
+            }
+            _ => skipped += 1,
+        }
+    }
+
+    events.push(NewTimelineEvent {
+        event_type: TimelineEventType::Resolved,
+        message: format!(
+            "Incident resolved (duration: {})",
+            time::duration(duration_minutes)
+        ),
+        posted_by: request.commander_id.clone(),
+        timestamp: Some(resolved_at),
+    });
+
+    (events, skipped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_slack_ts() {
+        assert_eq!(
+            parse_slack_ts("1700000000.000200"),
+            DateTime::from_timestamp(1_700_000_000, 200_000)
+        );
+        assert_eq!(
+            parse_slack_ts("1700000000.5"),
+            DateTime::from_timestamp(1_700_000_000, 500_000_000)
+        );
+        assert_eq!(
+            parse_slack_ts("1700000000"),
+            DateTime::from_timestamp(1_700_000_000, 0)
+        );
+        assert_eq!(parse_slack_ts("1700000000."), None);
+        assert_eq!(parse_slack_ts("1700000000.1234567"), None);
+        assert_eq!(parse_slack_ts("yesterday"), None);
+    }
+}
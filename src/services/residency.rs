@@ -0,0 +1,237 @@
+use crate::adapters::{conference, statuspage};
+use crate::config::{AppConfig, ArtifactBackend, ConferenceProvider, DataResidency};
+use crate::services::artifact_store;
+use crate::slack::client::SLACK_API_BASE_URL;
+use serde::Serialize;
+
+/// External services the bot sends incident data to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExternalService {
+    Slack,
+    Statuspage,
+    Jira,
+    Confluence,
+    Conference,
+    Artifacts,
+    Webhooks,
+}
+
+impl ExternalService {
+    pub const ALL: [ExternalService; 7] = [
+        ExternalService::Slack,
+        ExternalService::Statuspage,
+        ExternalService::Jira,
+        ExternalService::Confluence,
+        ExternalService::Conference,
+        ExternalService::Artifacts,
+        ExternalService::Webhooks,
+    ];
+
+    /// Key in INTEGRATION_REGIONS.
+    pub fn key(&self) -> &'static str {
+        match self {
+            ExternalService::Slack => "slack",
+            ExternalService::Statuspage => "statuspage",
+            ExternalService::Jira => "jira",
+            ExternalService::Confluence => "confluence",
+            ExternalService::Conference => "conference",
+            ExternalService::Artifacts => "artifacts",
+            ExternalService::Webhooks => "webhooks",
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ExternalService::Slack => "Slack",
+            ExternalService::Statuspage => "Statuspage",
+            ExternalService::Jira => "Jira",
+            ExternalService::Confluence => "Confluence",
+            ExternalService::Conference => "Conference bridges",
+            ExternalService::Artifacts => "Artifact store",
+            ExternalService::Webhooks => "Outbound webhooks",
+        }
+    }
+
+    /// What the bot sends it.
+    pub fn data(&self) -> &'static str {
+        match self {
+            ExternalService::Slack => "Everything: incident channels, updates, DMs and reports",
+            ExternalService::Statuspage => "Public incident titles, statuses and updates",
+            ExternalService::Jira => "Action items with their incident's title and service",
+            ExternalService::Confluence => "Published postmortems",
+            ExternalService::Conference => "Incident titles as meeting topics",
+            ExternalService::Artifacts => "DR snapshots and channel transcripts",
+            ExternalService::Webhooks => "Incident lifecycle events with full incident details",
+        }
+    }
+}
+
+/// One row of the data residency audit.
+#[derive(Debug, Clone, Serialize)]
+pub struct ExternalServiceReport {
+    pub service: &'static str,
+    pub name: &'static str,
+    /// Whether incident data is sent to it with the current configuration
+    pub enabled: bool,
+    pub endpoints: Vec<String>,
+    pub region: Option<String>,
+    pub eu: bool,
+    /// The setting that declares `region`
+    pub region_setting: String,
+    pub data: &'static str,
+}
+
+/// Which external services receive incident data, and where they are hosted.
+#[derive(Debug, Clone, Serialize)]
+pub struct ResidencyReport {
+    pub data_residency: DataResidency,
+    /// True when every enabled service is EU-hosted
+    pub eu_only: bool,
+    pub services: Vec<ExternalServiceReport>,
+}
+
+impl ResidencyReport {
+    pub fn new(data_residency: DataResidency, services: Vec<ExternalServiceReport>) -> Self {
+        Self {
+            data_residency,
+            eu_only: services.iter().all(|s| !s.enabled || s.eu),
+            services,
+        }
+    }
+}
+
+/// AWS (`eu-west-1`), GCS (`europe-west1`, multi-region `eu`) and
+/// plain `eu` declarations.
+pub fn is_eu_region(region: &str) -> bool {
+    let region = region.trim().to_ascii_lowercase();
+    region == "eu"
+        || region == "europe"
+        || region.starts_with("eu-")
+        || region.starts_with("europe-")
+}
+
+/// Where `service` is hosted: ARTIFACT_REGION for object storage
+/// artifacts (local artifacts never leave the bot), INTEGRATION_REGIONS for
+/// everything else.
+fn region_of(config: &AppConfig, service: ExternalService) -> (Option<String>, String) {
+    if service == ExternalService::Artifacts && config.artifact_store != ArtifactBackend::Local {
+        let region = config
+            .artifact_region
+            .clone()
+            .filter(|r| !r.trim().is_empty());
+        return (region, "ARTIFACT_REGION".to_string());
+    }
+    (
+        config.integration_regions.get(service.key()).cloned(),
+        format!("INTEGRATION_REGIONS {}", service.key()),
+    )
+}
+
+/// Whether incident data may be sent to `service` under DATA_RESIDENCY.
+pub fn allows(config: &AppConfig, service: ExternalService) -> bool {
+    match config.data_residency {
+        DataResidency::Unrestricted => true,
+        DataResidency::Eu => region_of(config, service)
+            .0
+            .is_some_and(|r| is_eu_region(&r)),
+    }
+}
+
+/// The audit of every external service for this configuration. Webhook
+/// endpoints are registered at runtime, so their row lists none and counts
+/// as enabled; callers with a database fill it in.
+pub fn audit(config: &AppConfig) -> Vec<ExternalServiceReport> {
+    ExternalService::ALL
+        .iter()
+        .map(|&service| {
+            let (enabled, endpoints) = endpoints_of(config, service);
+            let (region, region_setting) = region_of(config, service);
+            ExternalServiceReport {
+                service: service.key(),
+                name: service.name(),
+                enabled,
+                endpoints,
+                eu: region.as_deref().is_some_and(is_eu_region),
+                region,
+                region_setting,
+                data: service.data(),
+            }
+        })
+        .collect()
+}
+
+/// The first enabled service that DATA_RESIDENCY=eu forbids, checked at
+/// startup. Webhooks are left to delivery time, where non-EU ones are
+/// dropped, since endpoints come and go at runtime.
+pub fn startup_violation(config: &AppConfig) -> Option<ExternalServiceReport> {
+    if config.data_residency != DataResidency::Eu {
+        return None;
+    }
+    audit(config)
+        .into_iter()
+        .find(|s| s.enabled && !s.eu && s.service != ExternalService::Webhooks.key())
+}
+
+fn endpoints_of(config: &AppConfig, service: ExternalService) -> (bool, Vec<String>) {
+    let one = |url: &str| vec![url.to_string()];
+    match service {
+        ExternalService::Slack => (true, one(SLACK_API_BASE_URL)),
+        ExternalService::Statuspage => (
+            config.statuspage_api_key.is_some() && config.statuspage_page_id.is_some(),
+            one(config
+                .statuspage_api_url
+                .as_deref()
+                .filter(|u| !u.trim().is_empty())
+                .unwrap_or(statuspage::STATUSPAGE_API_BASE_URL)),
+        ),
+        ExternalService::Jira => match config.jira_credentials() {
+            Some((base_url, _, _)) => (true, one(base_url)),
+            None => (false, Vec::new()),
+        },
+        ExternalService::Confluence => match config.confluence_credentials() {
+            Some((base_url, _, _, _)) => (true, one(base_url)),
+            None => (false, Vec::new()),
+        },
+        ExternalService::Conference => match config.conference_credentials() {
+            Some((ConferenceProvider::Zoom, ..)) => (true, one(conference::ZOOM_API_URL)),
+            Some((ConferenceProvider::GoogleMeet, ..)) => {
+                (true, one(conference::GOOGLE_MEET_API_URL))
+            }
+            None => (false, Vec::new()),
+        },
+        ExternalService::Artifacts => match config.artifact_store {
+            ArtifactBackend::Local => (false, Vec::new()),
+            backend => {
+                let endpoint = config
+                    .artifact_endpoint
+                    .clone()
+                    .filter(|e| !e.trim().is_empty())
+                    .unwrap_or_else(|| {
+                        let region = config
+                            .artifact_region
+                            .as_deref()
+                            .filter(|r| !r.trim().is_empty())
+                            .unwrap_or(artifact_store::default_region(backend));
+                        artifact_store::default_endpoint(backend, region)
+                    });
+                (true, vec![endpoint])
+            }
+        },
+        ExternalService::Webhooks => (true, Vec::new()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_eu_region() {
+        for region in ["eu", "EU", "eu-west-1", "eu-central-2", "europe-west1"] {
+            assert!(is_eu_region(region), "{}", region);
+        }
+        for region in ["us-east-1", "us", "europe1", "auto", "", "neu-1"] {
+            assert!(!is_eu_region(region), "{}", region);
+        }
+    }
+}
@@ -0,0 +1,182 @@
+use crate::db::models::{Incident, IncidentRole, TimelineEventType};
+use crate::db::queries::roles as role_queries;
+use crate::error::{IncidentError, IncidentResult};
+use crate::services::audit::AuditService;
+use crate::services::timeline::TimelineService;
+use serde::Serialize;
+use serde_json::json;
+use sqlx_postgres::PgPool;
+use tracing::info;
+
+/// Required roles for an incident split into who holds what and what is missing.
+#[derive(Debug, Clone, Serialize)]
+pub struct RoleStatus {
+    pub required: Vec<String>,
+    pub filled: Vec<IncidentRole>,
+    pub unfilled: Vec<String>,
+}
+
+impl RoleStatus {
+    pub fn is_complete(&self) -> bool {
+        self.unfilled.is_empty()
+    }
+}
+
+pub struct RoleService {
+    pool: PgPool,
+    timeline_service: TimelineService,
+    audit_service: AuditService,
+}
+
+impl RoleService {
+    pub fn new(pool: PgPool) -> Self {
+        let timeline_service = TimelineService::new(pool.clone());
+        let audit_service = AuditService::new(pool.clone());
+        Self {
+            pool,
+            timeline_service,
+            audit_service,
+        }
+    }
+
+    pub async fn status(
+        &self,
+        incident: &Incident,
+        required: &[String],
+    ) -> IncidentResult<RoleStatus> {
+        let filled = role_queries::list_roles(&self.pool, incident.id).await?;
+        Ok(role_status(required, filled))
+    }
+
+    /// Claim `role` for `user_id`. Only roles required for the incident can be
+    /// claimed and the first claim wins.
+    pub async fn claim(
+        &self,
+        incident: &Incident,
+        required: &[String],
+        role: &str,
+        user_id: &str,
+    ) -> IncidentResult<IncidentRole> {
+        if incident.status.is_terminal() {
+            return Err(IncidentError::ValidationError {
+                field: "incident".to_string(),
+                reason: "Incident is already resolved".to_string(),
+            });
+        }
+        if !required.iter().any(|r| r == role) {
+            return Err(IncidentError::ValidationError {
+                field: "role".to_string(),
+                reason: format!(
+                    "{} is not a required role for this incident",
+                    role_label(role)
+                ),
+            });
+        }
+
+        let claimed = match role_queries::claim_role(&self.pool, incident.id, role, user_id).await?
+        {
+            Some(claimed) => claimed,
+            None => {
+                let holder = role_queries::list_roles(&self.pool, incident.id)
+                    .await?
+                    .into_iter()
+                    .find(|r| r.role == role)
+                    .map(|r| r.user_id)
+                    .unwrap_or_default();
+                return Err(IncidentError::ValidationError {
+                    field: "role".to_string(),
+                    reason: format!("{} is already claimed by <@{}>", role_label(role), holder),
+                });
+            }
+        };
+
+        self.timeline_service
+            .log_event(
+                incident.id,
+                TimelineEventType::StatusUpdate,
+                format!("<@{}> is now {}", user_id, role_label(role)),
+                user_id.to_string(),
+            )
+            .await?;
+
+        self.audit_service
+            .log_action(
+                Some(incident.id),
+                "claim_role".to_string(),
+                user_id.to_string(),
+                None,
+                Some(json!({ "role": role })),
+                None,
+            )
+            .await?;
+
+        info!("{} claimed {} for incident {}", user_id, role, incident.id);
+        Ok(claimed)
+    }
+}
+
+fn role_status(required: &[String], filled: Vec<IncidentRole>) -> RoleStatus {
+    let unfilled = required
+        .iter()
+        .filter(|role| !filled.iter().any(|f| &f.role == *role))
+        .cloned()
+        .collect();
+    RoleStatus {
+        required: required.to_vec(),
+        filled,
+        unfilled,
+    }
+}
+
+/// Human-readable role name: `comms_lead` -> `Comms Lead`.
+pub fn role_label(role: &str) -> String {
+    role.split('_')
+        .filter(|w| !w.is_empty())
+        .map(|w| {
+            let mut chars = w.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    fn claimed(role: &str) -> IncidentRole {
+        IncidentRole {
+            incident_id: Uuid::new_v4(),
+            role: role.to_string(),
+            user_id: "U1".to_string(),
+            claimed_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_role_label() {
+        assert_eq!(role_label("comms_lead"), "Comms Lead");
+        assert_eq!(role_label("scribe"), "Scribe");
+        assert_eq!(role_label("__odd__key"), "Odd Key");
+    }
+
+    #[test]
+    fn test_role_status_lists_unfilled_in_config_order() {
+        let required = vec!["comms_lead".to_string(), "scribe".to_string()];
+
+        let status = role_status(&required, vec![claimed("scribe")]);
+        assert_eq!(status.unfilled, vec!["comms_lead"]);
+        assert!(!status.is_complete());
+
+        let status = role_status(&required, vec![claimed("scribe"), claimed("comms_lead")]);
+        assert!(status.is_complete());
+
+        // Nothing required means nothing to nag about
+        assert!(role_status(&[], vec![]).is_complete());
+    }
+}
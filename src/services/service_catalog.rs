@@ -0,0 +1,101 @@
+use crate::db::queries::disabled_services;
+use crate::error::IncidentResult;
+use sqlx_postgres::PgPool;
+use std::collections::HashSet;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+/// How long the disabled set is reused. This instance drops it as soon as a
+/// service is toggled; the TTL bounds how long other replicas lag behind.
+const CACHE_TTL: Duration = Duration::from_secs(30);
+
+/// Which of `SERVICES` are currently offered for new incidents. Admins hide
+/// a service (say, one being deprecated) with `/incident service disable`
+/// instead of a redeploy; the declare modals, incident API and alert mapping
+/// then leave it out. Incidents already open on it carry on.
+pub struct ServiceCatalog {
+    cached: Mutex<Option<(Instant, HashSet<String>)>>,
+}
+
+impl Default for ServiceCatalog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ServiceCatalog {
+    pub fn new() -> Self {
+        Self {
+            cached: Mutex::new(None),
+        }
+    }
+
+    /// Disabled services, from the cache while it's fresh.
+    pub async fn disabled(&self, pool: &PgPool) -> IncidentResult<HashSet<String>> {
+        if let Some((fetched_at, disabled)) = self.cached.lock().expect("service cache").as_ref() {
+            if fetched_at.elapsed() < CACHE_TTL {
+                return Ok(disabled.clone());
+            }
+        }
+        let disabled: HashSet<String> = disabled_services::list_disabled_services(pool)
+            .await?
+            .into_iter()
+            .collect();
+        *self.cached.lock().expect("service cache") = Some((Instant::now(), disabled.clone()));
+        Ok(disabled)
+    }
+
+    /// `services` without the disabled ones, in order. If the disabled set
+    /// can't be read every service is offered, so a database hiccup never
+    /// empties the declare modal.
+    pub async fn enabled(&self, pool: &PgPool, services: &[String]) -> Vec<String> {
+        match self.disabled(pool).await {
+            Ok(disabled) => services
+                .iter()
+                .filter(|service| !disabled.contains(*service))
+                .cloned()
+                .collect(),
+            Err(e) => {
+                warn!("Failed to load disabled services: {}", e);
+                services.to_vec()
+            }
+        }
+    }
+
+    /// Whether new incidents and alerts may use `service`; `true` when the
+    /// disabled set can't be read.
+    pub async fn is_enabled(&self, pool: &PgPool, service: &str) -> bool {
+        match self.disabled(pool).await {
+            Ok(disabled) => !disabled.contains(service),
+            Err(e) => {
+                warn!("Failed to load disabled services: {}", e);
+                true
+            }
+        }
+    }
+
+    /// Disable `service`, taking effect on this instance at once. Returns
+    /// `false` if it already was.
+    pub async fn disable(
+        &self,
+        pool: &PgPool,
+        service: &str,
+        user_id: &str,
+    ) -> IncidentResult<bool> {
+        let changed = disabled_services::disable_service(pool, service, user_id).await?;
+        self.invalidate();
+        Ok(changed)
+    }
+
+    /// Enable `service` again. Returns `false` if it wasn't disabled.
+    pub async fn enable(&self, pool: &PgPool, service: &str) -> IncidentResult<bool> {
+        let changed = disabled_services::enable_service(pool, service).await?;
+        self.invalidate();
+        Ok(changed)
+    }
+
+    fn invalidate(&self) {
+        *self.cached.lock().expect("service cache") = None;
+    }
+}
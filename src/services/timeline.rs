@@ -1,15 +1,29 @@
-use crate::db::models::{IncidentId, TimelineEvent, TimelineEventType};
+use crate::db::models::{
+    Audience, Incident, IncidentId, NewTimelineEvent, TimelineEvent, TimelineEventType,
+};
+use crate::db::queries::incidents as incident_queries;
 use crate::db::queries::timeline as timeline_queries;
-use crate::error::IncidentResult;
+use crate::error::{IncidentError, IncidentResult};
+use crate::services::participants::ParticipantService;
+use crate::utils::time;
+use chrono::{DateTime, Duration, Utc};
 use sqlx_postgres::PgPool;
 
+/// Upper bound on events accepted by a single `log_events_batch` call.
+pub const MAX_BATCH_SIZE: usize = 500;
+
 pub struct TimelineService {
     pool: PgPool,
+    participant_service: ParticipantService,
 }
 
 impl TimelineService {
     pub fn new(pool: PgPool) -> Self {
-        Self { pool }
+        let participant_service = ParticipantService::new(pool.clone());
+        Self {
+            pool,
+            participant_service,
+        }
     }
 
     pub async fn log_event(
@@ -19,7 +33,101 @@ impl TimelineService {
         message: String,
         posted_by: String,
     ) -> IncidentResult<TimelineEvent> {
-        timeline_queries::log_event(&self.pool, incident_id, event_type, message, posted_by).await
+        let event = timeline_queries::log_event(
+            &self.pool,
+            incident_id,
+            event_type,
+            message,
+            posted_by,
+            Audience::Internal,
+        )
+        .await?;
+        self.participant_service
+            .record_timeline_events(incident_id, std::slice::from_ref(&event))
+            .await?;
+        Ok(event)
+    }
+
+    /// Log a status update classified for `audience`.
+    pub async fn log_status_update(
+        &self,
+        incident_id: IncidentId,
+        message: String,
+        posted_by: String,
+        audience: Audience,
+    ) -> IncidentResult<TimelineEvent> {
+        let event = timeline_queries::log_event(
+            &self.pool,
+            incident_id,
+            TimelineEventType::StatusUpdate,
+            message,
+            posted_by,
+            audience,
+        )
+        .await?;
+        self.participant_service
+            .record_timeline_events(incident_id, std::slice::from_ref(&event))
+            .await?;
+        Ok(event)
+    }
+
+    /// Log a status update posted by the commander from the scribe's draft,
+    /// crediting both.
+    pub async fn log_drafted_status_update(
+        &self,
+        incident_id: IncidentId,
+        message: String,
+        posted_by: String,
+        drafted_by: String,
+        audience: Audience,
+    ) -> IncidentResult<TimelineEvent> {
+        let event = timeline_queries::log_drafted_status_update(
+            &self.pool,
+            incident_id,
+            message,
+            posted_by,
+            drafted_by,
+            audience,
+        )
+        .await?;
+        self.participant_service
+            .record_timeline_events(incident_id, std::slice::from_ref(&event))
+            .await?;
+        Ok(event)
+    }
+
+    /// Log many events for one incident with a single INSERT.
+    ///
+    /// Intended for integrations that stream events (alert updates, deploys) where
+    /// per-event round trips would pile up during an alert storm.
+    pub async fn log_events_batch(
+        &self,
+        incident_id: IncidentId,
+        events: Vec<NewTimelineEvent>,
+    ) -> IncidentResult<Vec<TimelineEvent>> {
+        validate_batch(&events)?;
+
+        // Surface a clean NotFound instead of a foreign-key violation
+        incident_queries::get_incident_by_id(&self.pool, incident_id).await?;
+
+        let inserted = timeline_queries::log_events_batch(&self.pool, incident_id, &events).await?;
+        self.participant_service
+            .record_timeline_events(incident_id, &inserted)
+            .await?;
+        Ok(inserted)
+    }
+
+    /// `log_events_batch` for events posted by external integrations through
+    /// the REST API: notes only, and only while the incident is open. Status
+    /// updates and lifecycle events drive MTTA and SLA checks, so they come
+    /// from the bot's own commands.
+    pub async fn log_integration_events(
+        &self,
+        incident: &Incident,
+        events: Vec<NewTimelineEvent>,
+    ) -> IncidentResult<Vec<TimelineEvent>> {
+        validate_integration_batch(incident, &events)?;
+        self.log_events_batch(incident.id, events).await
     }
 
     pub async fn get_timeline(
@@ -29,6 +137,74 @@ impl TimelineService {
         timeline_queries::get_timeline(&self.pool, incident_id).await
     }
 
+    /// A page of the timeline, oldest first: at most `limit` events after
+    /// skipping `offset`.
+    pub async fn get_timeline_page(
+        &self,
+        incident_id: IncidentId,
+        limit: Option<i64>,
+        offset: i64,
+    ) -> IncidentResult<Vec<TimelineEvent>> {
+        timeline_queries::get_timeline_page(&self.pool, incident_id, limit, offset).await
+    }
+
+    /// The newest `count` events, oldest first, and the total number of
+    /// events in the timeline.
+    pub async fn get_last_events(
+        &self,
+        incident_id: IncidentId,
+        count: i64,
+    ) -> IncidentResult<(Vec<TimelineEvent>, i64)> {
+        let total = timeline_queries::count_events(&self.pool, incident_id).await?;
+        let events = self
+            .get_timeline_page(incident_id, Some(count), (total - count).max(0))
+            .await?;
+        Ok((events, total))
+    }
+
+    /// Event `number` of the incident's timeline, counting from 1 in the
+    /// order `/incident timeline` shows them.
+    pub async fn get_event_by_number(
+        &self,
+        incident_id: IncidentId,
+        number: usize,
+    ) -> IncidentResult<TimelineEvent> {
+        if number == 0 {
+            return Err(IncidentError::NotFound);
+        }
+        self.get_timeline_page(incident_id, Some(1), number as i64 - 1)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or(IncidentError::NotFound)
+    }
+
+    /// Replace a status update or note's text.
+    pub async fn edit_event(
+        &self,
+        event: &TimelineEvent,
+        message: &str,
+    ) -> IncidentResult<TimelineEvent> {
+        check_editable(event)?;
+        if message.trim().is_empty() {
+            return Err(IncidentError::ValidationError {
+                field: "message".to_string(),
+                reason: "Message cannot be empty".to_string(),
+            });
+        }
+        timeline_queries::update_event_message(&self.pool, event.id, message.trim(), Utc::now())
+            .await?
+            .ok_or_else(already_deleted)
+    }
+
+    /// Hide a status update or note, keeping its place in the timeline.
+    pub async fn delete_event(&self, event: &TimelineEvent) -> IncidentResult<TimelineEvent> {
+        check_editable(event)?;
+        timeline_queries::delete_event(&self.pool, event.id, Utc::now())
+            .await?
+            .ok_or_else(already_deleted)
+    }
+
     pub fn format_as_markdown(&self, events: &[TimelineEvent]) -> String {
         if events.is_empty() {
             return "_No timeline events yet._".to_string();
@@ -37,21 +213,243 @@ impl TimelineService {
         events
             .iter()
             .map(|e| {
+                if e.is_deleted() {
+                    return format!("**{}** — 🗑️ _Deleted_\n", time::clock(&e.timestamp));
+                }
+                let edited = if e.updated_at.is_some() {
+                    " _(edited)_"
+                } else {
+                    ""
+                };
                 let event_icon = match e.event_type {
+                    TimelineEventType::Triaged => "🔍",
                     TimelineEventType::Declared => "🚨",
                     TimelineEventType::StatusUpdate => "📝",
                     TimelineEventType::SeverityChange => "⚠️",
                     TimelineEventType::Resolved => "✅",
+                    TimelineEventType::Note => {
+                        let quoted = e
+                            .message
+                            .lines()
+                            .map(|line| format!("> {}", line))
+                            .collect::<Vec<_>>()
+                            .join("\n");
+                        return format!(
+                            "**{}** — 🗒️ Note from {}{}\n{}\n",
+                            time::clock(&e.timestamp),
+                            e.posted_by,
+                            edited,
+                            quoted
+                        );
+                    }
+                    TimelineEventType::Reopened => "🔁",
+                    TimelineEventType::Canceled => "⚪",
                 };
                 format!(
-                    "**{}** — {} {}\n→ {}\n",
-                    e.timestamp.format("%H:%M"),
+                    "**{}** — {} {}\n→ {}{}\n",
+                    time::clock(&e.timestamp),
                     event_icon,
                     format!("{:?}", e.event_type).replace("_", " "),
-                    e.message
+                    e.message,
+                    edited
                 )
             })
             .collect::<Vec<_>>()
             .join("\n")
     }
 }
+
+/// Which events a posted timeline shows, driven by its filter buttons and
+/// time-window menu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TimelineFilter {
+    /// Only events of this type; `None` shows every type
+    pub event_type: Option<TimelineEventType>,
+    /// Only events from the last N hours; `None` shows the whole incident
+    pub window_hours: Option<i64>,
+}
+
+impl TimelineFilter {
+    pub fn is_unfiltered(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn apply(&self, events: Vec<TimelineEvent>, now: DateTime<Utc>) -> Vec<TimelineEvent> {
+        events
+            .into_iter()
+            .filter(|e| self.matches(e, now))
+            .collect()
+    }
+
+    pub fn matches(&self, event: &TimelineEvent, now: DateTime<Utc>) -> bool {
+        let since = self.window_hours.map(|hours| now - Duration::hours(hours));
+        (self.event_type.is_none() || self.event_type == Some(event.event_type))
+            && !matches!(since, Some(since) if event.timestamp < since)
+    }
+}
+
+/// Pair events with their numbers in the timeline, the first being `first`.
+pub fn number_events(events: Vec<TimelineEvent>, first: usize) -> Vec<(usize, TimelineEvent)> {
+    events
+        .into_iter()
+        .enumerate()
+        .map(|(i, event)| (first + i, event))
+        .collect()
+}
+
+fn check_editable(event: &TimelineEvent) -> IncidentResult<()> {
+    if !event.is_editable() {
+        return Err(IncidentError::ValidationError {
+            field: "event".to_string(),
+            reason: "Only status updates and notes can be edited or deleted".to_string(),
+        });
+    }
+    if event.is_deleted() {
+        return Err(already_deleted());
+    }
+    Ok(())
+}
+
+fn already_deleted() -> IncidentError {
+    IncidentError::ValidationError {
+        field: "event".to_string(),
+        reason: "That event has been deleted".to_string(),
+    }
+}
+
+fn validate_batch(events: &[NewTimelineEvent]) -> IncidentResult<()> {
+    if events.is_empty() {
+        return Err(IncidentError::ValidationError {
+            field: "events".to_string(),
+            reason: "Batch must contain at least one event".to_string(),
+        });
+    }
+    if events.len() > MAX_BATCH_SIZE {
+        return Err(IncidentError::ValidationError {
+            field: "events".to_string(),
+            reason: format!("Batch exceeds maximum of {} events", MAX_BATCH_SIZE),
+        });
+    }
+    if let Some(index) = events.iter().position(|e| e.message.trim().is_empty()) {
+        return Err(IncidentError::ValidationError {
+            field: format!("events[{}].message", index),
+            reason: "Message cannot be empty".to_string(),
+        });
+    }
+    if let Some(index) = events.iter().position(|e| e.posted_by.trim().is_empty()) {
+        return Err(IncidentError::ValidationError {
+            field: format!("events[{}].posted_by", index),
+            reason: "posted_by cannot be empty".to_string(),
+        });
+    }
+    Ok(())
+}
+
+fn validate_integration_batch(
+    incident: &Incident,
+    events: &[NewTimelineEvent],
+) -> IncidentResult<()> {
+    if incident.status.is_terminal() {
+        return Err(IncidentError::ValidationError {
+            field: "incident".to_string(),
+            reason: format!("Incident is already {}", incident.status.as_db_str()),
+        });
+    }
+    if let Some(index) = events
+        .iter()
+        .position(|e| e.event_type != TimelineEventType::Note)
+    {
+        return Err(IncidentError::ValidationError {
+            field: format!("events[{}].event_type", index),
+            reason: "Only Note events can be added through the API".to_string(),
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(message: &str) -> NewTimelineEvent {
+        NewTimelineEvent {
+            event_type: TimelineEventType::StatusUpdate,
+            message: message.to_string(),
+            posted_by: "alertmanager".to_string(),
+            timestamp: None,
+        }
+    }
+
+    #[test]
+    fn test_validate_batch_accepts_events_up_to_limit() {
+        let events = vec![event("firing"); MAX_BATCH_SIZE];
+        assert!(validate_batch(&events).is_ok());
+    }
+
+    #[test]
+    fn test_validate_batch_rejects_empty_and_oversized() {
+        assert!(matches!(
+            validate_batch(&[]),
+            Err(IncidentError::ValidationError { .. })
+        ));
+
+        let events = vec![event("firing"); MAX_BATCH_SIZE + 1];
+        assert!(matches!(
+            validate_batch(&events),
+            Err(IncidentError::ValidationError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_validate_batch_reports_index_of_blank_message() {
+        let events = vec![event("firing"), event("   ")];
+        match validate_batch(&events) {
+            Err(IncidentError::ValidationError { field, .. }) => {
+                assert_eq!(field, "events[1].message")
+            }
+            other => panic!("Expected validation error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_timeline_filter_by_type_and_window() {
+        let now = Utc::now();
+        let logged = |event_type, minutes_ago| TimelineEvent {
+            id: uuid::Uuid::new_v4(),
+            incident_id: uuid::Uuid::new_v4(),
+            event_type,
+            message: "event".to_string(),
+            posted_by: "U1".to_string(),
+            timestamp: now - Duration::minutes(minutes_ago),
+            audience: Audience::Internal,
+            updated_at: None,
+            deleted_at: None,
+            drafted_by: None,
+        };
+        let events = vec![
+            logged(TimelineEventType::Declared, 180),
+            logged(TimelineEventType::StatusUpdate, 120),
+            logged(TimelineEventType::Note, 90),
+            logged(TimelineEventType::StatusUpdate, 30),
+        ];
+
+        assert_eq!(
+            TimelineFilter::default().apply(events.clone(), now).len(),
+            4
+        );
+
+        let updates = TimelineFilter {
+            event_type: Some(TimelineEventType::StatusUpdate),
+            window_hours: None,
+        };
+        assert_eq!(updates.apply(events.clone(), now).len(), 2);
+
+        let recent_updates = TimelineFilter {
+            window_hours: Some(1),
+            ..updates
+        };
+        let filtered = recent_updates.apply(events, now);
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].timestamp, now - Duration::minutes(30));
+    }
+}
@@ -0,0 +1,286 @@
+use crate::app_state::AppState;
+use crate::db::models::{Incident, IncidentStatus, Webhook, WebhookEvent};
+use crate::db::queries::webhooks as webhook_queries;
+use crate::error::{IncidentError, IncidentResult};
+use crate::jobs::Job;
+use crate::metrics::metrics;
+use crate::services::permissions::FieldVisibility;
+use crate::services::residency::{self, ExternalService};
+use crate::slack::client::RetryPolicy;
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+use sha2::Sha256;
+use sqlx_postgres::PgPool;
+use std::time::Duration;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+type HmacSha256 = Hmac<Sha256>;
+
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Outbound webhooks: endpoint registration, and signed delivery of
+/// incident lifecycle events (run by the job worker).
+pub struct WebhookService {
+    pool: PgPool,
+    http_client: reqwest::Client,
+}
+
+impl WebhookService {
+    pub fn new(pool: PgPool) -> Self {
+        let http_client = reqwest::Client::builder()
+            .timeout(DELIVERY_TIMEOUT)
+            .build()
+            .expect("Failed to build HTTP client");
+        Self { pool, http_client }
+    }
+
+    /// Register an endpoint. Without a `secret` one is generated; either
+    /// way it is returned only here.
+    pub async fn register(
+        &self,
+        url: &str,
+        secret: Option<String>,
+        events: &[WebhookEvent],
+    ) -> IncidentResult<Webhook> {
+        let parsed = reqwest::Url::parse(url).map_err(|e| IncidentError::ValidationError {
+            field: "url".to_string(),
+            reason: format!("Invalid URL: {}", e),
+        })?;
+        if !matches!(parsed.scheme(), "http" | "https") {
+            return Err(IncidentError::ValidationError {
+                field: "url".to_string(),
+                reason: "URL must use http or https".to_string(),
+            });
+        }
+
+        let secret = match secret.map(|s| s.trim().to_string()) {
+            Some(secret) if secret.is_empty() => {
+                return Err(IncidentError::ValidationError {
+                    field: "secret".to_string(),
+                    reason: "secret cannot be empty".to_string(),
+                });
+            }
+            Some(secret) => secret,
+            None => format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple()),
+        };
+
+        let mut unique = Vec::new();
+        for event in events {
+            if !unique.contains(event) {
+                unique.push(*event);
+            }
+        }
+        let webhook = webhook_queries::create_webhook(&self.pool, url, &secret, &unique).await?;
+        info!("Webhook {} registered for {}", webhook.id, webhook.url);
+        Ok(webhook)
+    }
+
+    pub async fn list(&self) -> IncidentResult<Vec<Webhook>> {
+        webhook_queries::list_webhooks(&self.pool).await
+    }
+
+    pub async fn delete(&self, id: Uuid) -> IncidentResult<()> {
+        if !webhook_queries::delete_webhook(&self.pool, id).await? {
+            return Err(IncidentError::NotFound);
+        }
+        info!("Webhook {} deleted", id);
+        Ok(())
+    }
+
+    /// POST `payload` to the webhook, retrying network errors, 429 and 5xx
+    /// responses with exponential backoff. Other 4xx responses are final.
+    /// Webhooks deleted or disabled since the job was queued are skipped.
+    pub async fn deliver(
+        &self,
+        webhook_id: Uuid,
+        event: WebhookEvent,
+        payload: &Value,
+        retry_policy: RetryPolicy,
+    ) -> IncidentResult<()> {
+        let Some(webhook) = webhook_queries::get_webhook(&self.pool, webhook_id).await? else {
+            return Ok(());
+        };
+        if !webhook.is_active {
+            return Ok(());
+        }
+
+        let body = payload.to_string();
+        let mut attempt = 0;
+        let result = loop {
+            let timestamp = Utc::now().timestamp().to_string();
+            let response = self
+                .http_client
+                .post(&webhook.url)
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .header("X-Incident-Bot-Event", event.as_db_str())
+                .header(
+                    "X-Incident-Bot-Delivery",
+                    payload["id"].as_str().unwrap_or_default(),
+                )
+                .header("X-Incident-Bot-Timestamp", &timestamp)
+                .header(
+                    "X-Incident-Bot-Signature",
+                    sign(&webhook.secret, &timestamp, &body),
+                )
+                .body(body.clone())
+                .send()
+                .await;
+
+            let (retryable, message) = match response {
+                Ok(response) if response.status().is_success() => break Ok(()),
+                Ok(response) => {
+                    let status = response.status();
+                    (
+                        status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error(),
+                        format!("HTTP {}", status),
+                    )
+                }
+                Err(e) => (true, e.to_string()),
+            };
+            if !retryable || attempt >= retry_policy.max_retries {
+                break Err(message);
+            }
+
+            let delay = retry_policy.backoff(attempt);
+            warn!(
+                "Webhook {} delivery failed ({}); retry {} in {:?}",
+                webhook.id,
+                message,
+                attempt + 1,
+                delay
+            );
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        };
+
+        match result {
+            Ok(()) => {
+                metrics().record_webhook_delivery(event.as_db_str(), true);
+                info!("Delivered {} to webhook {}", event.as_db_str(), webhook.id);
+                Ok(())
+            }
+            Err(message) => {
+                metrics().record_webhook_delivery(event.as_db_str(), false);
+                Err(IncidentError::ExternalAPIError {
+                    service: "webhook".to_string(),
+                    message: format!(
+                        "{} delivery to {} failed: {}",
+                        event.as_db_str(),
+                        webhook.url,
+                        message
+                    ),
+                })
+            }
+        }
+    }
+}
+
+/// Queue `event` for every subscribed webhook. `previous` carries the
+/// values before the change, e.g. `{"severity": "P2"}`. Quiet incidents
+/// are not sent, nor is anything under DATA_RESIDENCY=eu unless webhooks
+/// are declared EU-hosted. Best-effort, like Statuspage sync: failures are
+/// logged, never returned.
+pub async fn enqueue(
+    state: &AppState,
+    event: WebhookEvent,
+    incident: &Incident,
+    previous: Option<Value>,
+) {
+    if incident.is_quiet {
+        return;
+    }
+    if !residency::allows(&state.config, ExternalService::Webhooks) {
+        warn!(
+            "Not sending {} webhooks for incident {}: DATA_RESIDENCY=eu and webhooks are not declared EU-hosted",
+            event.as_db_str(),
+            incident.id
+        );
+        return;
+    }
+    let webhooks = match webhook_queries::webhooks_for_event(&state.pool, event).await {
+        Ok(webhooks) => webhooks,
+        Err(e) => {
+            error!(
+                "Failed to look up webhooks for {}: {}",
+                event.as_db_str(),
+                e
+            );
+            return;
+        }
+    };
+    if webhooks.is_empty() {
+        return;
+    }
+
+    // Receivers are API consumers; restricted custom fields follow the API role
+    let incident = FieldVisibility::api(&state.config).redact_incident(incident.clone());
+    let payload = event_payload(event, &incident, previous);
+    for webhook in webhooks {
+        let job = Job::DeliverWebhook {
+            webhook_id: webhook.id,
+            event,
+            payload: payload.clone(),
+        };
+        if let Err(e) = state.job_sender.send(job) {
+            error!("Failed to enqueue webhook delivery: {}", e);
+        }
+    }
+}
+
+/// `resolved` for a resolution, otherwise `status.changed` (including a
+/// cancellation).
+pub fn status_event(status: IncidentStatus) -> WebhookEvent {
+    if status == IncidentStatus::Resolved {
+        WebhookEvent::Resolved
+    } else {
+        WebhookEvent::StatusChanged
+    }
+}
+
+/// JSON body shared by every endpoint receiving one event. `id` is unique
+/// per event, so receivers can drop duplicate deliveries.
+pub fn event_payload(event: WebhookEvent, incident: &Incident, previous: Option<Value>) -> Value {
+    json!({
+        "id": Uuid::new_v4(),
+        "event": event,
+        "occurred_at": Utc::now(),
+        "incident": incident,
+        "previous": previous,
+    })
+}
+
+/// `v1=` + hex HMAC-SHA256 of `v1:{timestamp}:{body}`, mirroring Slack's
+/// request signing so receivers can reuse the same verification code.
+pub fn sign(secret: &str, timestamp: &str, body: &str) -> String {
+    let mut mac =
+        HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC can take key of any size");
+    mac.update(format!("v1:{}:{}", timestamp, body).as_bytes());
+    format!("v1={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_is_stable_and_keyed() {
+        let signature = sign("secret", "1700000000", r#"{"event":"resolved"}"#);
+        assert!(signature.starts_with("v1="));
+        assert_eq!(signature.len(), 3 + 64);
+        assert_eq!(
+            signature,
+            sign("secret", "1700000000", r#"{"event":"resolved"}"#)
+        );
+        assert_ne!(
+            signature,
+            sign("other", "1700000000", r#"{"event":"resolved"}"#)
+        );
+        assert_ne!(
+            signature,
+            sign("secret", "1700000001", r#"{"event":"resolved"}"#)
+        );
+    }
+}
@@ -0,0 +1,220 @@
+use crate::db::models::{Incident, TimelineEventType, Workstream};
+use crate::db::queries::workstreams as workstream_queries;
+use crate::error::{IncidentError, IncidentResult};
+use crate::services::audit::AuditService;
+use crate::services::timeline::TimelineService;
+use serde_json::json;
+use sqlx_postgres::PgPool;
+use tracing::info;
+
+pub const MAX_WORKSTREAM_NAME_LEN: usize = 30;
+
+pub struct WorkstreamService {
+    pool: PgPool,
+    timeline_service: TimelineService,
+    audit_service: AuditService,
+}
+
+impl WorkstreamService {
+    pub fn new(pool: PgPool) -> Self {
+        let timeline_service = TimelineService::new(pool.clone());
+        let audit_service = AuditService::new(pool.clone());
+        Self {
+            pool,
+            timeline_service,
+            audit_service,
+        }
+    }
+
+    pub async fn create(
+        &self,
+        incident: &Incident,
+        name: &str,
+        lead_id: &str,
+        created_by: &str,
+    ) -> IncidentResult<Workstream> {
+        let name = normalize_name(name)?;
+        ensure_active(incident)?;
+
+        if workstream_queries::get_workstream(&self.pool, incident.id, &name)
+            .await?
+            .is_some()
+        {
+            return Err(IncidentError::ValidationError {
+                field: "name".to_string(),
+                reason: format!("Workstream '{}' already exists", name),
+            });
+        }
+
+        let workstream = workstream_queries::create_workstream(
+            &self.pool,
+            incident.id,
+            &name,
+            lead_id,
+            created_by,
+        )
+        .await?;
+
+        self.timeline_service
+            .log_event(
+                incident.id,
+                TimelineEventType::StatusUpdate,
+                format!("Workstream *{}* opened (lead <@{}>)", name, lead_id),
+                created_by.to_string(),
+            )
+            .await?;
+
+        self.audit_service
+            .log_action(
+                Some(incident.id),
+                "create_workstream".to_string(),
+                created_by.to_string(),
+                None,
+                Some(json!({
+                    "name": name,
+                    "lead_id": lead_id,
+                })),
+                None,
+            )
+            .await?;
+
+        info!("Workstream {} opened for incident {}", name, incident.id);
+        Ok(workstream)
+    }
+
+    pub async fn set_thread_ts(
+        &self,
+        workstream: &Workstream,
+        thread_ts: &str,
+    ) -> IncidentResult<()> {
+        workstream_queries::update_thread_ts(&self.pool, workstream.id, thread_ts).await
+    }
+
+    /// Record the workstream's latest update. Only its lead or the incident
+    /// commander may post.
+    pub async fn post_update(
+        &self,
+        incident: &Incident,
+        name: &str,
+        message: &str,
+        posted_by: &str,
+    ) -> IncidentResult<Workstream> {
+        ensure_active(incident)?;
+        let workstream = self.get(incident, name).await?;
+
+        if posted_by != workstream.lead_id && posted_by != incident.commander_id {
+            return Err(IncidentError::PermissionDenied {
+                user_id: posted_by.to_string(),
+                action: format!("post updates for workstream {}", workstream.name),
+            });
+        }
+
+        let updated = workstream_queries::record_update(&self.pool, workstream.id, message).await?;
+
+        self.timeline_service
+            .log_event(
+                incident.id,
+                TimelineEventType::StatusUpdate,
+                format!("[{}] {}", workstream.name, message),
+                posted_by.to_string(),
+            )
+            .await?;
+
+        Ok(updated)
+    }
+
+    pub async fn set_lead(
+        &self,
+        incident: &Incident,
+        name: &str,
+        lead_id: &str,
+        changed_by: &str,
+    ) -> IncidentResult<Workstream> {
+        ensure_active(incident)?;
+        let workstream = self.get(incident, name).await?;
+
+        let updated = workstream_queries::update_lead(&self.pool, workstream.id, lead_id).await?;
+
+        self.timeline_service
+            .log_event(
+                incident.id,
+                TimelineEventType::StatusUpdate,
+                format!(
+                    "Workstream *{}* lead changed to <@{}>",
+                    workstream.name, lead_id
+                ),
+                changed_by.to_string(),
+            )
+            .await?;
+
+        self.audit_service
+            .log_action(
+                Some(incident.id),
+                "change_workstream_lead".to_string(),
+                changed_by.to_string(),
+                Some(json!({ "name": workstream.name, "lead_id": workstream.lead_id })),
+                Some(json!({ "name": updated.name, "lead_id": updated.lead_id })),
+                None,
+            )
+            .await?;
+
+        Ok(updated)
+    }
+
+    pub async fn get(&self, incident: &Incident, name: &str) -> IncidentResult<Workstream> {
+        let name = normalize_name(name)?;
+        workstream_queries::get_workstream(&self.pool, incident.id, &name)
+            .await?
+            .ok_or(IncidentError::NotFound)
+    }
+
+    pub async fn list(&self, incident: &Incident) -> IncidentResult<Vec<Workstream>> {
+        workstream_queries::list_workstreams(&self.pool, incident.id).await
+    }
+}
+
+fn ensure_active(incident: &Incident) -> IncidentResult<()> {
+    if incident.status.is_terminal() {
+        return Err(IncidentError::ValidationError {
+            field: "incident".to_string(),
+            reason: "Incident is already resolved".to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Workstream names are short slugs (`database`, `customer-comms`) so they read
+/// well in thread headers and the pinned summary.
+pub fn normalize_name(name: &str) -> IncidentResult<String> {
+    let name = name.trim().to_lowercase();
+    let valid = !name.is_empty()
+        && name.len() <= MAX_WORKSTREAM_NAME_LEN
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+
+    if !valid {
+        return Err(IncidentError::ValidationError {
+            field: "name".to_string(),
+            reason: format!(
+                "Use 1-{} lowercase letters, digits, or hyphens",
+                MAX_WORKSTREAM_NAME_LEN
+            ),
+        });
+    }
+    Ok(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_name() {
+        assert_eq!(normalize_name(" Database ").unwrap(), "database");
+        assert_eq!(normalize_name("customer-comms").unwrap(), "customer-comms");
+        assert!(normalize_name("").is_err());
+        assert!(normalize_name("db failover").is_err());
+        assert!(normalize_name(&"a".repeat(MAX_WORKSTREAM_NAME_LEN + 1)).is_err());
+    }
+}
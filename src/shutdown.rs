@@ -0,0 +1,125 @@
+//! Graceful shutdown: wait for SIGTERM or Ctrl+C, and keep count of the
+//! Slack command and interaction tasks still running after their request
+//! was acknowledged, so a deploy doesn't cut off their Slack posts.
+
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio::sync::Notify;
+use tracing::info;
+
+/// Resolves on SIGTERM (what orchestrators send on deploy) or Ctrl+C.
+pub async fn signal() {
+    let ctrl_c = async {
+        if tokio::signal::ctrl_c().await.is_err() {
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                sigterm.recv().await;
+            }
+            Err(_) => std::future::pending::<()>().await,
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => info!("Received Ctrl+C, shutting down"),
+        _ = terminate => info!("Received SIGTERM, shutting down"),
+    }
+}
+
+/// Background tasks spawned on behalf of a request, which outlive it.
+#[derive(Clone, Default)]
+pub struct InFlight {
+    inner: Arc<Counter>,
+}
+
+#[derive(Default)]
+struct Counter {
+    running: AtomicUsize,
+    idle: Notify,
+}
+
+/// Decrements on drop, so a panicking task is still counted out.
+struct Guard(Arc<Counter>);
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        if self.0.running.fetch_sub(1, Ordering::AcqRel) == 1 {
+            self.0.idle.notify_waiters();
+        }
+    }
+}
+
+impl InFlight {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `tokio::spawn`, counted until the task finishes.
+    pub fn spawn<F>(&self, task: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        self.inner.running.fetch_add(1, Ordering::AcqRel);
+        let guard = Guard(self.inner.clone());
+        tokio::spawn(async move {
+            let _guard = guard;
+            task.await;
+        });
+    }
+
+    /// Tasks still running.
+    pub fn running(&self) -> usize {
+        self.inner.running.load(Ordering::Acquire)
+    }
+
+    /// Wait until no tasks are running.
+    pub async fn wait(&self) {
+        loop {
+            let idle = self.inner.idle.notified();
+            tokio::pin!(idle);
+            // Register before checking, so a task finishing in between still wakes us
+            idle.as_mut().enable();
+            if self.running() == 0 {
+                return;
+            }
+            idle.await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_wait_returns_once_tasks_finish() {
+        let in_flight = InFlight::new();
+        in_flight.wait().await;
+
+        let (release, released) = tokio::sync::oneshot::channel::<()>();
+        in_flight.spawn(async move {
+            let _ = released.await;
+        });
+        in_flight.spawn(async { panic!("task failed") });
+        assert!(
+            tokio::time::timeout(Duration::from_millis(50), in_flight.wait())
+                .await
+                .is_err()
+        );
+
+        release.send(()).unwrap();
+        tokio::time::timeout(Duration::from_secs(1), in_flight.wait())
+            .await
+            .unwrap();
+        assert_eq!(in_flight.running(), 0);
+    }
+}
@@ -1,38 +1,72 @@
-use crate::db::models::{Incident, Severity, TimelineEvent};
+use crate::adapters::alert_sources::{Alert, AlertStatus};
+use crate::adapters::github;
+use crate::config::MimRole;
+use crate::db::models::{
+    ActionItem, Audience, AuditEntry, DeclareDraft, Incident, IncidentId, IncidentLink,
+    IncidentRole, IncidentStatus, IncidentTemplate, LinkKind, PagingTest, PagingTestPage,
+    PendingPostmortem, Postmortem, Premortem, PremortemRisk, Severity, SeverityLevel, SlaMetric,
+    StatusDraft, Subscription, TimelineEvent, TimelineEventType, Workstream,
+};
+use crate::db::queries::analytics::ServiceStats;
+use crate::db::queries::metrics::MetricsRow;
+use crate::db::queries::templates::TemplateUsage;
+use crate::services::analytics::Scorecard;
+use crate::services::coaching::CoachingReport;
+use crate::services::load::LoadReport;
+use crate::services::metrics::{DurationHistogram, MetricsReport, WeeklyReport, DURATION_BUCKETS};
+use crate::services::participants::is_slack_user_id;
+use crate::services::permissions::FieldVisibility;
+use crate::services::roles::role_label;
+use crate::services::timeline::TimelineFilter;
+use crate::utils::{placeholders, severity, time};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
 use serde_json::{json, Value};
 
-pub fn incident_declared_blocks(incident: &Incident) -> Vec<Value> {
+/// Action IDs for the buttons on the declared-incident message; each value
+/// is the incident ID.
+pub const INCIDENT_ACK_ACTION: &str = "incident_acknowledge";
+pub const INCIDENT_UPDATE_STATUS_ACTION: &str = "incident_update_status";
+pub const INCIDENT_RESOLVE_ACTION: &str = "incident_resolve";
+
+/// Declaration details. Restricted custom fields `visibility` hides show a
+/// placeholder; messages posted to channels pass `FieldVisibility::shared`.
+pub fn incident_declared_blocks(incident: &Incident, visibility: &FieldVisibility) -> Vec<Value> {
+    let mut fields = vec![
+        json!({
+            "type": "mrkdwn",
+            "text": format!("*Title:*\n{}", incident.title)
+        }),
+        json!({
+            "type": "mrkdwn",
+            "text": format!("*Service:*\n{}", incident.affected_service)
+        }),
+        json!({
+            "type": "mrkdwn",
+            "text": format!("*Commander:*\n<@{}>", incident.commander_id)
+        }),
+        json!({
+            "type": "mrkdwn",
+            "text": format!("*Started:*\n<!date^{}^{{time}}|{}>",
+                incident.declared_at.timestamp(),
+                time::clock_utc(&incident.declared_at))
+        }),
+    ];
+    // Template placeholder values; a section holds at most 10 fields
+    fields.extend(custom_fields(incident, visibility, 6));
+
     vec![
         json!({
             "type": "header",
             "text": {
                 "type": "plain_text",
-                "text": format!("{} {} - Incident Declared", incident.severity.emoji(), incident.severity.label()),
+                "text": format!("{} {} · {} - Incident Declared", incident.severity_level().emoji, incident.reference(), incident.severity_level().label),
             }
         }),
         json!({
             "type": "section",
-            "fields": [
-                {
-                    "type": "mrkdwn",
-                    "text": format!("*Title:*\n{}", incident.title)
-                },
-                {
-                    "type": "mrkdwn",
-                    "text": format!("*Service:*\n{}", incident.affected_service)
-                },
-                {
-                    "type": "mrkdwn",
-                    "text": format!("*Commander:*\n<@{}>", incident.commander_id)
-                },
-                {
-                    "type": "mrkdwn",
-                    "text": format!("*Started:*\n<!date^{}^{{time}}|{}>",
-                        incident.declared_at.timestamp(),
-                        incident.declared_at.format("%H:%M %Z"))
-                },
-            ]
+            "fields": fields,
         }),
+        incident_actions(incident),
         json!({
             "type": "context",
             "elements": [
@@ -45,23 +79,421 @@ pub fn incident_declared_blocks(incident: &Incident) -> Vec<Value> {
     ]
 }
 
-pub fn status_update_blocks(severity: Severity, message: &str, posted_by: &str) -> Vec<Value> {
+/// Acknowledge / Update Status / Resolve buttons, so responders don't need
+/// the slash commands. Resolving asks for confirmation first.
+fn incident_actions(incident: &Incident) -> Value {
+    let incident_id = incident.id.to_string();
+    json!({
+        "type": "actions",
+        "block_id": "incident_actions",
+        "elements": [
+            {
+                "type": "button",
+                "text": { "type": "plain_text", "text": "Acknowledge" },
+                "style": "primary",
+                "action_id": INCIDENT_ACK_ACTION,
+                "value": incident_id
+            },
+            {
+                "type": "button",
+                "text": { "type": "plain_text", "text": "Update Status" },
+                "action_id": INCIDENT_UPDATE_STATUS_ACTION,
+                "value": incident_id
+            },
+            {
+                "type": "button",
+                "text": { "type": "plain_text", "text": "Resolve" },
+                "style": "danger",
+                "action_id": INCIDENT_RESOLVE_ACTION,
+                "value": incident_id,
+                "confirm": {
+                    "title": { "type": "plain_text", "text": "Resolve incident?" },
+                    "text": {
+                        "type": "mrkdwn",
+                        "text": format!("Mark *{}* as resolved and notify everyone it was announced to.", incident.title)
+                    },
+                    "confirm": { "type": "plain_text", "text": "Resolve" },
+                    "deny": { "type": "plain_text", "text": "Cancel" }
+                }
+            }
+        ]
+    })
+}
+
+/// Up to `max` custom field section fields, as `visibility` lets the viewer
+/// see them.
+fn custom_fields(incident: &Incident, visibility: &FieldVisibility, max: usize) -> Vec<Value> {
+    incident
+        .custom_fields
+        .iter()
+        .take(max)
+        .map(|(name, value)| {
+            json!({
+                "type": "mrkdwn",
+                "text": format!("*{}:*\n{}", placeholders::label(name), visibility.value(name, value))
+            })
+        })
+        .collect()
+}
+
+/// Pinned incident summary: declaration details plus each workstream's latest update.
+pub fn incident_summary_blocks(
+    incident: &Incident,
+    workstreams: &[Workstream],
+    visibility: &FieldVisibility,
+) -> Vec<Value> {
+    let mut blocks = incident_declared_blocks(incident, visibility);
+    // The pinned copy follows the incident past its declaration
+    if incident.status != IncidentStatus::Declared {
+        blocks[0]["text"]["text"] = json!(format!(
+            "{} {} · {} - {}",
+            incident.severity_level().emoji,
+            incident.reference(),
+            incident.severity_level().label,
+            incident.status.label()
+        ));
+    }
+
+    if workstreams.is_empty() {
+        return blocks;
+    }
+
+    let lines = workstreams
+        .iter()
+        .map(|w| {
+            let update = match (&w.last_update, w.last_update_at) {
+                (Some(message), Some(at)) => format!("_{}_ — {}", time::clock(&at), message),
+                _ => "_No updates yet_".to_string(),
+            };
+            format!("• *{}* (lead <@{}>): {}", w.name, w.lead_id, update)
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    // Keep the PII warning as the last block
+    let warning = blocks.pop();
+    blocks.push(json!({
+        "type": "section",
+        "text": {
+            "type": "mrkdwn",
+            "text": format!("*Workstreams*\n{}", lines)
+        }
+    }));
+    blocks.extend(warning);
+    blocks
+}
+
+/// Stakeholder-facing summary from `/incident summary`: where the incident
+/// stands and its latest status updates, without the channel's back-and-forth.
+/// Custom fields appear as `visibility` allows.
+pub fn executive_summary_blocks(
+    incident: &Incident,
+    updates: &[TimelineEvent],
+    now: DateTime<Utc>,
+    visibility: &FieldVisibility,
+) -> Vec<Value> {
+    let elapsed = match (incident.resolved_at, &incident.cancel_reason) {
+        (Some(_), _) => format!("*Resolved after:*\n{}", duration_text(incident)),
+        (None, Some(reason)) => format!("*Canceled as a false alarm:*\n{}", reason),
+        (None, None) => format!(
+            "*Ongoing for:*\n{}",
+            minutes_text((now - incident.declared_at).num_minutes().max(0))
+        ),
+    };
+    let mut fields = vec![
+        json!({
+            "type": "mrkdwn",
+            "text": format!("*Severity:*\n{} {}", incident.severity_level().emoji, incident.severity_level().label)
+        }),
+        json!({
+            "type": "mrkdwn",
+            "text": format!("*Status:*\n{} {}", incident.status.emoji(), incident.status.as_db_str())
+        }),
+        json!({
+            "type": "mrkdwn",
+            "text": format!("*Service:*\n{}", incident.affected_service)
+        }),
+        json!({ "type": "mrkdwn", "text": elapsed }),
+        json!({
+            "type": "mrkdwn",
+            "text": format!("*Commander:*\n<@{}>", incident.commander_id)
+        }),
+    ];
+    if let Some(channel_id) = &incident.slack_channel_id {
+        fields.push(json!({
+            "type": "mrkdwn",
+            "text": format!("*Channel:*\n<#{}>", channel_id)
+        }));
+    }
+    let room = 10 - fields.len();
+    fields.extend(custom_fields(incident, visibility, room));
+
+    let updates = if updates.is_empty() {
+        "_No status updates yet_".to_string()
+    } else {
+        updates
+            .iter()
+            .map(|u| format!("• _{}_ — {}", time::clock_utc(&u.timestamp), u.message))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    vec![
+        json!({
+            "type": "header",
+            "text": {
+                "type": "plain_text",
+                "text": format!("📋 {}", incident.title),
+            }
+        }),
+        json!({
+            "type": "section",
+            "fields": fields,
+        }),
+        json!({
+            "type": "section",
+            "text": {
+                "type": "mrkdwn",
+                "text": format!("*Latest updates*\n{}", updates)
+            }
+        }),
+    ]
+}
+
+pub fn workstream_started_blocks(workstream: &Workstream) -> Vec<Value> {
+    vec![json!({
+        "type": "section",
+        "text": {
+            "type": "mrkdwn",
+            "text": format!(
+                "🧵 *Workstream: {}*\nLead: <@{}>\n_Reply in this thread to coordinate. Use `/incident workstream update {} [message]` to update the pinned summary._",
+                workstream.name, workstream.lead_id, workstream.name
+            )
+        }
+    })]
+}
+
+/// Action ID prefix for "Claim <role>" buttons; the button value is `<incident_id>:<role>`.
+pub const CLAIM_ROLE_ACTION_PREFIX: &str = "claim_role_";
+
+/// Role roster with a claim button per unfilled required role.
+pub fn role_claim_blocks(
+    incident: &Incident,
+    filled: &[IncidentRole],
+    unfilled: &[String],
+) -> Vec<Value> {
+    let mut roster = vec![format!("• Commander: <@{}>", incident.commander_id)];
+    roster.extend(
+        filled
+            .iter()
+            .map(|r| format!("• {}: <@{}>", role_label(&r.role), r.user_id)),
+    );
+    roster.extend(
+        unfilled
+            .iter()
+            .map(|role| format!("• {}: _unfilled_", role_label(role))),
+    );
+
+    let header = if unfilled.is_empty() {
+        "✅ *All required roles are filled*".to_string()
+    } else {
+        format!(
+            "🙋 *{} incident needs: {}*\nClaim a role below so everyone knows who is covering what.",
+            incident.severity_level().code,
+            unfilled
+                .iter()
+                .map(|r| role_label(r))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    };
+
+    let mut blocks = vec![json!({
+        "type": "section",
+        "text": {
+            "type": "mrkdwn",
+            "text": format!("{}\n{}", header, roster.join("\n"))
+        }
+    })];
+
+    if !unfilled.is_empty() {
+        let buttons: Vec<Value> = unfilled
+            .iter()
+            .map(|role| {
+                json!({
+                    "type": "button",
+                    "text": {
+                        "type": "plain_text",
+                        "text": format!("Claim {}", role_label(role))
+                    },
+                    "action_id": format!("{}{}", CLAIM_ROLE_ACTION_PREFIX, role),
+                    "value": format!("{}:{}", incident.id, role)
+                })
+            })
+            .collect();
+        blocks.push(json!({
+            "type": "actions",
+            "elements": buttons
+        }));
+    }
+
+    blocks
+}
+
+pub fn status_update_blocks(level: &SeverityLevel, message: &str, posted_by: &str) -> Vec<Value> {
+    vec![json!({
+        "type": "section",
+        "text": {
+            "type": "mrkdwn",
+            "text": format!("{} *Status Update*\n{}\n_Posted by <@{}>_", level.emoji, message, posted_by)
+        }
+    })]
+}
+
+/// Context line marking a status update as shared outside Slack.
+pub fn public_update_context() -> Value {
+    json!({
+        "type": "context",
+        "elements": [{
+            "type": "mrkdwn",
+            "text": "🌐 Shared on the public status page"
+        }]
+    })
+}
+
+/// DM copy of an incident notification for a subscriber, saying why they got
+/// it and how to stop.
+pub fn subscription_digest_blocks(
+    incident: &Incident,
+    subscription: &Subscription,
+    notification: &[Value],
+) -> Vec<Value> {
+    let channel = incident
+        .slack_channel_id
+        .as_ref()
+        .map(|c| format!(" in <#{}>", c))
+        .unwrap_or_default();
+    let (reason, unsubscribe) = match &subscription.service {
+        Some(service) => (
+            format!("incidents on *{}*", service),
+            format!("`/incident unsubscribe service:{}`", service),
+        ),
+        None => (
+            "this incident".to_string(),
+            format!("`/incident unsubscribe`{}", channel),
+        ),
+    };
+
+    std::iter::once(json!({
+        "type": "section",
+        "text": {
+            "type": "mrkdwn",
+            "text": format!(
+                "📬 {} *{}*{} · {}",
+                incident.severity_level().emoji,
+                incident.title,
+                channel,
+                incident.status.as_db_str()
+            )
+        }
+    }))
+    .chain(notification.iter().cloned())
+    .chain(std::iter::once(json!({
+        "type": "context",
+        "elements": [{
+            "type": "mrkdwn",
+            "text": format!(
+                "You're subscribed to {}. Stop with {}.",
+                reason, unsubscribe
+            )
+        }]
+    })))
+    .collect()
+}
+
+/// Context line crediting the scribe who drafted a status update.
+pub fn drafted_by_context(drafted_by: &str) -> Value {
+    json!({
+        "type": "context",
+        "elements": [{
+            "type": "mrkdwn",
+            "text": format!("✍️ Drafted by <@{}>", drafted_by)
+        }]
+    })
+}
+
+/// Action IDs for the commander's buttons on a scribe's draft status update;
+/// the value is the draft ID.
+pub const STATUS_DRAFT_APPROVE_ACTION: &str = "status_draft_approve";
+pub const STATUS_DRAFT_DISCARD_ACTION: &str = "status_draft_discard";
+
+/// DM asking the commander to approve a status update the scribe drafted.
+pub fn status_draft_blocks(incident: &Incident, draft: &StatusDraft) -> Vec<Value> {
+    let channel = incident
+        .slack_channel_id
+        .as_ref()
+        .map(|c| format!(" in <#{}>", c))
+        .unwrap_or_default();
+    let audience = match draft.audience {
+        Audience::Public => " It will also be shared on the public status page.",
+        Audience::Internal => "",
+    };
+
+    vec![
+        mrkdwn_section(&format!(
+            "✍️ <@{}> drafted a status update for *{}*{}.{}\n{}",
+            draft.drafted_by,
+            incident.title,
+            channel,
+            audience,
+            quote(&draft.message)
+        )),
+        json!({
+            "type": "actions",
+            "elements": [
+                {
+                    "type": "button",
+                    "text": { "type": "plain_text", "text": "Approve & post" },
+                    "style": "primary",
+                    "action_id": STATUS_DRAFT_APPROVE_ACTION,
+                    "value": draft.id.to_string()
+                },
+                {
+                    "type": "button",
+                    "text": { "type": "plain_text", "text": "Discard" },
+                    "action_id": STATUS_DRAFT_DISCARD_ACTION,
+                    "value": draft.id.to_string()
+                }
+            ]
+        }),
+    ]
+}
+
+pub fn status_change_blocks(
+    old_status: IncidentStatus,
+    new_status: IncidentStatus,
+    changed_by: &str,
+) -> Vec<Value> {
     vec![json!({
         "type": "section",
         "text": {
             "type": "mrkdwn",
-            "text": format!("{} *Status Update*\n{}\n_Posted by <@{}>_", severity.emoji(), message, posted_by)
+            "text": format!("🔄 *Status changed from {} to {}*\n_Changed by <@{}>_",
+                old_status.as_db_str(),
+                new_status.as_db_str(),
+                changed_by)
         }
     })]
 }
 
 pub fn severity_change_blocks(
-    old_severity: Severity,
-    new_severity: Severity,
+    old_level: &SeverityLevel,
+    new_level: &SeverityLevel,
     changed_by: &str,
     reason: Option<&str>,
 ) -> Vec<Value> {
-    let direction = if new_severity as u8 > old_severity as u8 {
+    let downgraded = severity::rank(new_level) > severity::rank(old_level);
+    let direction = if downgraded {
         "⬇️ Downgraded"
     } else {
         "⬆️ Escalated"
@@ -73,9 +505,9 @@ pub fn severity_change_blocks(
             "type": "mrkdwn",
             "text": format!("{} *Severity {} from {} to {}*\n_Changed by <@{}>_",
                 direction,
-                if new_severity as u8 > old_severity as u8 { "downgraded" } else { "escalated" },
-                old_severity.label(),
-                new_severity.label(),
+                if downgraded { "downgraded" } else { "escalated" },
+                old_level.label,
+                new_level.label,
                 changed_by)
         }
     })];
@@ -93,18 +525,19 @@ pub fn severity_change_blocks(
     blocks
 }
 
+fn duration_text(incident: &Incident) -> String {
+    match incident.duration_minutes {
+        Some(duration) => minutes_text(duration.into()),
+        None => "unknown".to_string(),
+    }
+}
+
+fn minutes_text(minutes: i64) -> String {
+    time::duration(minutes)
+}
+
 pub fn resolution_blocks(incident: &Incident, resolved_by: &str) -> Vec<Value> {
-    let duration_text = if let Some(duration) = incident.duration_minutes {
-        let hours = duration / 60;
-        let mins = duration % 60;
-        if hours > 0 {
-            format!("{}h {}min", hours, mins)
-        } else {
-            format!("{}min", mins)
-        }
-    } else {
-        "unknown".to_string()
-    };
+    let duration_text = duration_text(incident);
 
     vec![
         json!({
@@ -130,57 +563,2468 @@ pub fn resolution_blocks(incident: &Incident, resolved_by: &str) -> Vec<Value> {
     ]
 }
 
-pub fn timeline_blocks(events: &[TimelineEvent]) -> Vec<Value> {
-    let mut blocks = vec![json!({
-        "type": "header",
-        "text": {
-            "type": "plain_text",
-            "text": "📋 Incident Timeline",
-        }
-    })];
+/// Announcement that an incident was a false alarm; goes wherever the
+/// declaration went.
+pub fn incident_canceled_blocks(
+    incident: &Incident,
+    canceled_by: &str,
+    reason: &str,
+) -> Vec<Value> {
+    vec![
+        json!({
+            "type": "header",
+            "text": {
+                "type": "plain_text",
+                "text": "⚪ CANCELED — FALSE ALARM",
+            }
+        }),
+        json!({
+            "type": "section",
+            "text": {
+                "type": "mrkdwn",
+                "text": format!(
+                    "{} *{}* ({}, {}) was a false alarm and has been canceled. No action is needed.",
+                    incident.severity_level().emoji,
+                    incident.title,
+                    incident.severity_level().code,
+                    incident.affected_service
+                )
+            }
+        }),
+        json!({
+            "type": "section",
+            "fields": [
+                {
+                    "type": "mrkdwn",
+                    "text": format!("*Canceled by:*\n<@{}>", canceled_by)
+                },
+                {
+                    "type": "mrkdwn",
+                    "text": format!("*Reason:*\n{}", reason)
+                },
+            ]
+        }),
+    ]
+}
 
-    if events.is_empty() {
-        blocks.push(json!({
+/// Announcement that a resolved incident was reopened; goes wherever the
+/// declaration went.
+pub fn incident_reopened_blocks(
+    incident: &Incident,
+    reopened_by: &str,
+    reason: Option<&str>,
+) -> Vec<Value> {
+    let channel = incident
+        .slack_channel_id
+        .as_ref()
+        .map(|c| format!(" in <#{}>", c))
+        .unwrap_or_default();
+
+    let mut blocks = vec![
+        json!({
+            "type": "header",
+            "text": {
+                "type": "plain_text",
+                "text": "🔁 REOPENED",
+            }
+        }),
+        json!({
             "type": "section",
             "text": {
                 "type": "mrkdwn",
-                "text": "_No timeline events yet._"
+                "text": format!(
+                    "{} *{}* ({}, {}) was reopened{} and is investigating again.",
+                    incident.severity_level().emoji,
+                    incident.title,
+                    incident.severity_level().code,
+                    incident.affected_service,
+                    channel
+                )
             }
+        }),
+        json!({
+            "type": "section",
+            "fields": [
+                {
+                    "type": "mrkdwn",
+                    "text": format!("*Reopened by:*\n<@{}>", reopened_by)
+                },
+                {
+                    "type": "mrkdwn",
+                    "text": format!("*Commander:*\n<@{}>", incident.commander_id)
+                },
+            ]
+        }),
+    ];
+    if let Some(reason) = reason {
+        blocks.push(json!({
+            "type": "context",
+            "elements": [{ "type": "mrkdwn", "text": format!("*Reason:* {}", reason) }]
         }));
-        return blocks;
     }
+    blocks
+}
 
-    let timeline_text = events
-        .iter()
-        .map(|e| {
-            let event_icon = match e.event_type {
-                crate::db::models::TimelineEventType::Declared => "🚨",
-                crate::db::models::TimelineEventType::StatusUpdate => "📝",
-                crate::db::models::TimelineEventType::SeverityChange => "⚠️",
-                crate::db::models::TimelineEventType::Resolved => "✅",
-            };
-            format!(
-                "{} *{}* — {}\n_by <@{}>_",
-                event_icon,
-                e.timestamp.format("%H:%M"),
-                e.message,
-                e.posted_by
-            )
-        })
-        .collect::<Vec<_>>()
-        .join("\n\n");
+/// Nudge for an incident with no timeline activity, posted in the incident
+/// channel and sent to the commander.
+pub fn stale_incident_blocks(incident: &Incident, idle_minutes: i64) -> Vec<Value> {
+    let channel = incident
+        .slack_channel_id
+        .as_ref()
+        .map(|c| format!(" in <#{}>", c))
+        .unwrap_or_default();
 
-    blocks.push(json!({
-        "type": "section",
-        "text": {
-            "type": "mrkdwn",
-            "text": timeline_text
+    vec![
+        json!({
+            "type": "section",
+            "text": {
+                "type": "mrkdwn",
+                "text": format!(
+                    "⏰ {} *{}*{} has had no updates for {} min. <@{}>, please post a status update with `/incident status`.",
+                    incident.severity_level().emoji,
+                    incident.title,
+                    channel,
+                    idle_minutes,
+                    incident.commander_id
+                )
+            }
+        }),
+        json!({
+            "type": "actions",
+            "elements": [{
+                "type": "button",
+                "text": { "type": "plain_text", "text": "I'm on it" },
+                "action_id": COMMANDER_ACK_ACTION,
+                "value": incident.id.to_string()
+            }]
+        }),
+    ]
+}
+
+/// Warning posted in the incident channel when it misses an SLA target.
+pub fn sla_breach_blocks(
+    incident: &Incident,
+    metric: SlaMetric,
+    target_minutes: i64,
+    deadline: DateTime<Utc>,
+) -> Vec<Value> {
+    let (missed, ask) = match metric {
+        SlaMetric::Ack => (
+            "first status update",
+            "please post a status update with `/incident status`",
+        ),
+        SlaMetric::Update => (
+            "status update cadence",
+            "please post a status update with `/incident status`",
+        ),
+        SlaMetric::Resolve => (
+            "time to resolve",
+            "please review the response and escalate if needed",
+        ),
+    };
+
+    vec![
+        json!({
+            "type": "section",
+            "text": {
+                "type": "mrkdwn",
+                "text": format!(
+                    "🚨 *SLA breached:* {} *{}* missed its {} target of {} for {}. <@{}>, {}.",
+                    incident.severity_level().emoji,
+                    incident.title,
+                    missed,
+                    minutes_text(target_minutes),
+                    incident.severity_level().code,
+                    incident.commander_id,
+                    ask
+                )
+            }
+        }),
+        json!({
+            "type": "context",
+            "elements": [{
+                "type": "mrkdwn",
+                "text": format!(
+                    "Deadline was <!date^{}^{{date_short_pretty}} at {{time}}|{}>",
+                    deadline.timestamp(),
+                    time::date_time(&deadline)
+                )
+            }]
+        }),
+    ]
+}
+
+/// Action ID for the commander's "I'm on it" button on nags; the value is the
+/// incident ID. Clicking it counts as commander activity.
+pub const COMMANDER_ACK_ACTION: &str = "commander_ack";
+
+/// Action ID for the backup's "Take command" button; the value is the incident ID.
+pub const TAKE_COMMAND_ACTION: &str = "take_command";
+
+/// Escalation when a commander has gone quiet, offering `backups` command.
+pub fn commander_absent_blocks(
+    incident: &Incident,
+    idle_minutes: i64,
+    backups: &[String],
+) -> Vec<Value> {
+    let channel = incident
+        .slack_channel_id
+        .as_ref()
+        .map(|c| format!(" in <#{}>", c))
+        .unwrap_or_default();
+    let ask = if backups.is_empty() {
+        format!(
+            "No backup commander is configured for *{}*.",
+            incident.affected_service
+        )
+    } else {
+        format!(
+            "Backup commanders: {}. Take command if <@{}> can't be reached.",
+            backups
+                .iter()
+                .map(|u| format!("<@{}>", u))
+                .collect::<Vec<_>>()
+                .join(", "),
+            incident.commander_id
+        )
+    };
+
+    let mut blocks = vec![json!({
+        "type": "section",
+        "text": {
+            "type": "mrkdwn",
+            "text": format!(
+                "🚨 {} *{}*{}: commander <@{}> hasn't been seen for {} min.\n{}",
+                incident.severity_level().emoji,
+                incident.title,
+                channel,
+                incident.commander_id,
+                idle_minutes,
+                ask
+            )
+        }
+    })];
+    if !backups.is_empty() {
+        blocks.push(json!({
+            "type": "actions",
+            "elements": [{
+                "type": "button",
+                "text": { "type": "plain_text", "text": "Take command" },
+                "style": "primary",
+                "action_id": TAKE_COMMAND_ACTION,
+                "value": incident.id.to_string()
+            }]
+        }));
+    }
+    blocks
+}
+
+/// DM to an exec tier for an incident still unresolved `open_minutes` after
+/// it was declared.
+pub fn exec_tier_blocks(incident: &Incident, tier: i16, open_minutes: i64) -> Vec<Value> {
+    let channel = incident
+        .slack_channel_id
+        .as_ref()
+        .map(|c| format!(" in <#{}>", c))
+        .unwrap_or_default();
+
+    vec![json!({
+        "type": "section",
+        "text": {
+            "type": "mrkdwn",
+            "text": format!(
+                "📟 *Exec notification (tier {})*: {} *{}*{} is still unresolved {} min after it was declared.\nService: {} · Status: {} · Commander: <@{}>",
+                tier,
+                incident.severity_level().emoji,
+                incident.title,
+                channel,
+                open_minutes,
+                incident.affected_service,
+                incident.status.as_db_str(),
+                incident.commander_id
+            )
+        }
+    })]
+}
+
+/// Channel post (and DM to backups) when an incident's commander has been
+/// deactivated in Slack. Backups and admins may take command.
+pub fn commander_deactivated_blocks(incident: &Incident, backups: &[String]) -> Vec<Value> {
+    let backups = if backups.is_empty() {
+        format!(
+            "No backup commander is configured for *{}*; an admin can take command.",
+            incident.affected_service
+        )
+    } else {
+        format!(
+            "Backup commanders: {}. Take command to keep the incident moving.",
+            backups
+                .iter()
+                .map(|u| format!("<@{}>", u))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    };
+
+    vec![
+        mrkdwn_section(&format!(
+            "🚫 {} *{}*: commander <@{}> has been deactivated in Slack.\n{}",
+            incident.severity_level().emoji,
+            incident.title,
+            incident.commander_id,
+            backups
+        )),
+        json!({
+            "type": "actions",
+            "elements": [{
+                "type": "button",
+                "text": { "type": "plain_text", "text": "Take command" },
+                "style": "primary",
+                "action_id": TAKE_COMMAND_ACTION,
+                "value": incident.id.to_string()
+            }]
+        }),
+    ]
+}
+
+/// Action IDs for the commander's choices when an open incident's channel was
+/// archived or deleted in Slack; the value is the incident ID.
+pub const REPLACE_CHANNEL_ACTION: &str = "replace_incident_channel";
+pub const CONTINUE_WITHOUT_CHANNEL_ACTION: &str = "continue_without_channel";
+
+/// DM to the commander when someone archived or deleted the channel of an
+/// open incident. `archived_by` is unknown for deletions.
+pub fn channel_lost_blocks(
+    incident: &Incident,
+    channel_id: &str,
+    archived_by: Option<&str>,
+) -> Vec<Value> {
+    let what = match archived_by {
+        Some(user) => format!("was archived by <@{}>", user),
+        None => "was deleted".to_string(),
+    };
+    vec![
+        mrkdwn_section(&format!(
+            "⚠️ The channel <#{}> for {} *{}* {}. Nothing more will be posted there.
+Create a new channel for the response, or continue without one: updates still reach the notification channels, and the incident can be managed from App Home and the REST API.",
+            channel_id,
+            incident.severity_level().emoji,
+            incident.title,
+            what
+        )),
+        json!({
+            "type": "actions",
+            "elements": [
+                {
+                    "type": "button",
+                    "text": { "type": "plain_text", "text": "Create new channel" },
+                    "style": "primary",
+                    "action_id": REPLACE_CHANNEL_ACTION,
+                    "value": incident.id.to_string()
+                },
+                {
+                    "type": "button",
+                    "text": { "type": "plain_text", "text": "Continue without a channel" },
+                    "action_id": CONTINUE_WITHOUT_CHANNEL_ACTION,
+                    "value": incident.id.to_string()
+                }
+            ]
+        }),
+    ]
+}
+
+/// Action ID for the major incident manager's "Accept" button on a P1 page;
+/// the value is the incident ID.
+pub const MIM_ACCEPT_ACTION: &str = "mim_accept";
+
+/// Page DMed to the major incident manager on duty when a P1 is declared.
+pub fn mim_page_blocks(incident: &Incident, source: &str, role: MimRole) -> Vec<Value> {
+    let channel = incident
+        .slack_channel_id
+        .as_ref()
+        .map(|c| format!(" in <#{}>", c))
+        .unwrap_or_default();
+    let accepting = match role {
+        MimRole::Commander => format!("take command from <@{}>", incident.commander_id),
+        MimRole::Advisor => format!(
+            "join as major incident manager alongside commander <@{}>",
+            incident.commander_id
+        ),
+    };
+
+    vec![
+        mrkdwn_section(&format!(
+            "📟 {} *{}*{} ({}).
+You're the major incident manager on duty via {}. Accept to {}.",
+            incident.severity_level().emoji,
+            incident.title,
+            channel,
+            incident.affected_service,
+            source,
+            accepting
+        )),
+        json!({
+            "type": "actions",
+            "elements": [{
+                "type": "button",
+                "text": { "type": "plain_text", "text": "Accept" },
+                "style": "primary",
+                "action_id": MIM_ACCEPT_ACTION,
+                "value": incident.id.to_string()
+            }]
+        }),
+    ]
+}
+
+/// DM to admins listing newly deactivated Slack users, the settings still
+/// naming them, and the open incidents they command.
+pub fn deactivated_users_blocks(
+    users: &[(String, Vec<String>)],
+    incidents: &[Incident],
+) -> Vec<Value> {
+    let mut lines = vec![format!(
+        "🚫 *{} Slack user{} deactivated* — notifications to them go nowhere.",
+        users.len(),
+        if users.len() == 1 { "" } else { "s" }
+    )];
+    for (user_id, settings) in users {
+        let mut line = format!("• <@{}> (`{}`)", user_id, user_id);
+        if !settings.is_empty() {
+            line.push_str(&format!(": remove from {}", settings.join(", ")));
+        }
+        let commanding: Vec<String> = incidents
+            .iter()
+            .filter(|i| &i.commander_id == user_id)
+            .map(|i| match &i.slack_channel_id {
+                Some(channel) => format!("<#{}>", channel),
+                None => format!("*{}*", i.title),
+            })
+            .collect();
+        if !commanding.is_empty() {
+            line.push_str(&format!(
+                "{}commands {}",
+                if settings.is_empty() { ": " } else { "; " },
+                commanding.join(", ")
+            ));
+        }
+        lines.push(line);
+    }
+
+    let mut blocks = vec![mrkdwn_section(&lines.join("\n"))];
+    if !incidents.is_empty() {
+        blocks.push(context_block(
+            "Backups were offered command in each incident channel; admins can take command there too.",
+        ));
+    }
+    blocks
+}
+
+/// Channel announcement after command changes hands.
+pub fn command_transferred_blocks(incident: &Incident, previous_commander: &str) -> Vec<Value> {
+    vec![json!({
+        "type": "section",
+        "text": {
+            "type": "mrkdwn",
+            "text": format!(
+                "🎖️ <@{}> has taken command of *{}* from <@{}>.",
+                incident.commander_id, incident.title, previous_commander
+            )
+        }
+    })]
+}
+
+/// Channel announcement for a new action item.
+pub fn action_item_added_blocks(item: &ActionItem, added_by: &str) -> Vec<Value> {
+    vec![json!({
+        "type": "section",
+        "text": {
+            "type": "mrkdwn",
+            "text": format!(
+                "📌 *Action item #{}:* {}\n_Owner <@{}> · added by <@{}>_",
+                item.number, item.description, item.owner_id, added_by
+            )
+        }
+    })]
+}
+
+pub fn jira_issue_created_blocks(
+    item: &ActionItem,
+    issue_key: &str,
+    issue_url: &str,
+) -> Vec<Value> {
+    vec![json!({
+        "type": "context",
+        "elements": [{
+            "type": "mrkdwn",
+            "text": format!(
+                "🎫 Created <{}|{}> for action item #{}",
+                issue_url, issue_key, item.number
+            )
+        }]
+    })]
+}
+
+/// Confirmation posted to the incident channel by `/incident note`.
+pub fn note_added_blocks(user_id: &str, note: &str) -> Vec<Value> {
+    vec![json!({
+        "type": "section",
+        "text": {
+            "type": "mrkdwn",
+            "text": format!("🗒️ <@{}> added a note to the timeline\n{}", user_id, quote(note))
+        }
+    })]
+}
+
+/// A PR or commit someone linked to the incident, posted to its channel.
+pub fn link_added_blocks(user_id: &str, link: &IncidentLink) -> Vec<Value> {
+    vec![mrkdwn_section(&format!(
+        "🔗 <@{}> linked {} <{}|{}>",
+        user_id,
+        crate::services::links::kind_label(link.kind),
+        link.url,
+        github::link_label(&link.repo, link.kind, &link.reference)
+    ))]
+}
+
+/// A deploy of the affected service that landed while the incident is open.
+pub fn deploy_landed_blocks(text: &str) -> Vec<Value> {
+    vec![
+        mrkdwn_section(text),
+        json!({
+            "type": "context",
+            "elements": [{
+                "type": "mrkdwn",
+                "text": "Reported by GitHub. Check whether the timing lines up with the impact."
+            }]
+        }),
+    ]
+}
+
+/// PRs, commits and deploys linked to an incident, oldest first.
+pub fn incident_links_blocks(links: &[IncidentLink]) -> Vec<Value> {
+    if links.is_empty() {
+        return vec![mrkdwn_section(
+            "No GitHub links yet. Add one with `/incident link github <url>`.",
+        )];
+    }
+    let lines: Vec<String> = links
+        .iter()
+        .map(|link| {
+            let environment = if link.environment.is_empty() {
+                String::new()
+            } else {
+                format!(" to {}", link.environment)
+            };
+            format!(
+                "• {} *{}* <{}|{}>{} — {}, {}",
+                match link.kind {
+                    LinkKind::PullRequest => "🔀",
+                    LinkKind::Commit => "📌",
+                    LinkKind::Deploy => "🚀",
+                },
+                crate::services::links::kind_label(link.kind),
+                link.url,
+                github::link_label(&link.repo, link.kind, &link.reference),
+                environment,
+                author(&link.linked_by),
+                time::clock(&link.created_at)
+            )
+        })
+        .collect();
+    vec![mrkdwn_section(&format!(
+        "*Linked to this incident*\n{}",
+        lines.join("\n")
+    ))]
+}
+
+/// `/incident subscribe list`: the caller's service and incident
+/// subscriptions.
+pub fn subscriptions_blocks(services: &[String], incidents: &[Incident]) -> Vec<Value> {
+    if services.is_empty() && incidents.is_empty() {
+        return vec![mrkdwn_section(
+            "No subscriptions. Run `/incident subscribe` in an incident channel, or `/incident subscribe service:<name>`.",
+        )];
+    }
+    let lines: Vec<String> = services
+        .iter()
+        .map(|service| format!("• Every incident on *{}*", service))
+        .chain(incidents.iter().map(|incident| {
+            let channel = incident
+                .slack_channel_id
+                .as_ref()
+                .map(|c| format!(" in <#{}>", c))
+                .unwrap_or_default();
+            format!(
+                "• {} *{}*{} ({})",
+                incident.severity_level().emoji,
+                incident.title,
+                channel,
+                incident.status.as_db_str()
+            )
+        }))
+        .collect();
+    vec![mrkdwn_section(&format!(
+        "*Your subscriptions*\n{}",
+        lines.join("\n")
+    ))]
+}
+
+/// Reply to `/incident subscribe` or `unsubscribe` for `what` ("this
+/// incident", "incidents on *VPN*"); `changed` is false if nothing changed.
+pub fn subscription_changed_blocks(what: &str, subscribed: bool, changed: bool) -> Vec<Value> {
+    let text = match (subscribed, changed) {
+        (true, true) => format!(
+            "📬 Subscribed to {}. You'll get a DM for each status change; `/incident unsubscribe` stops them.",
+            what
+        ),
+        (true, false) => format!("You're already subscribed to {}.", what),
+        (false, true) => format!("🔕 Unsubscribed from {}.", what),
+        (false, false) => format!("You weren't subscribed to {}.", what),
+    };
+    vec![mrkdwn_section(&text)]
+}
+
+/// A monitoring alert posted to the incident channel it declared or joined;
+/// `update` for news about an alert that was already firing.
+pub fn alert_blocks(source_label: &str, alert: &Alert, update: bool) -> Vec<Value> {
+    let mut text = match alert.status {
+        AlertStatus::Firing if update => {
+            format!("🔁 *{} alert update:* {}", source_label, alert.title)
+        }
+        AlertStatus::Firing => format!("🔔 *{} alert firing:* {}", source_label, alert.title),
+        AlertStatus::Resolved => format!("✅ *{} alert resolved:* {}", source_label, alert.title),
+    };
+    if let Some(description) = alert.description.as_deref().filter(|d| !d.is_empty()) {
+        text.push_str(&format!("\n{}", quote(description)));
+    }
+    let mut blocks = vec![mrkdwn_section(&text)];
+
+    let mut context: Vec<String> = alert
+        .labels
+        .iter()
+        .map(|(name, value)| format!("`{}={}`", name, value))
+        .collect();
+    if let Some(url) = &alert.url {
+        context.push(format!("<{}|Source>", url));
+    }
+    if let Some(snapshot_url) = &alert.snapshot_url {
+        context.push(format!("<{}|Snapshot>", snapshot_url));
+    }
+    if !context.is_empty() {
+        blocks.push(context_block(&context.join("  ")));
+    }
+    blocks
+}
+
+/// Mention Slack users; integrations (`alertmanager`, ...) are named as is.
+fn author(posted_by: &str) -> String {
+    if is_slack_user_id(posted_by) {
+        format!("<@{}>", posted_by)
+    } else {
+        posted_by.to_string()
+    }
+}
+
+/// Audit entry details are cut to this many characters in Slack.
+const MAX_AUDIT_DETAIL_CHARS: usize = 120;
+
+/// Audit log rows for `/incident audit`, newest first. `more` notes that
+/// older rows matched too.
+pub fn audit_entries_blocks(entries: &[AuditEntry], more: bool) -> Vec<Value> {
+    if entries.is_empty() {
+        return vec![json!({
+            "type": "section",
+            "text": { "type": "mrkdwn", "text": "No audit log entries match" }
+        })];
+    }
+
+    let lines: Vec<String> = entries
+        .iter()
+        .map(|entry| {
+            let incident = entry
+                .incident_id
+                .map(|id| format!(" · incident `{}`", &id.to_string()[..8]))
+                .unwrap_or_default();
+            let details = entry
+                .details
+                .as_ref()
+                .map(|details| {
+                    let text = details.to_string();
+                    let text = match text.char_indices().nth(MAX_AUDIT_DETAIL_CHARS) {
+                        Some((cut, _)) => format!("{}…", &text[..cut]),
+                        None => text,
+                    };
+                    format!("\n      `{}`", text)
+                })
+                .unwrap_or_default();
+            format!(
+                "`#{}` {} · *{}* by {}{}{}",
+                entry.seq,
+                time::date_time(&entry.timestamp),
+                entry.action,
+                author(&entry.actor_id),
+                incident,
+                details
+            )
+        })
+        .collect();
+
+    let mut blocks = vec![
+        json!({
+            "type": "header",
+            "text": { "type": "plain_text", "text": "🧾 Audit log" }
+        }),
+        json!({
+            "type": "section",
+            "text": { "type": "mrkdwn", "text": lines.join("\n") }
+        }),
+    ];
+    if more {
+        blocks.push(json!({
+            "type": "context",
+            "elements": [{
+                "type": "mrkdwn",
+                "text": "Older entries match too. Narrow the filters, or page through them with `GET /api/v1/audit`."
+            }]
+        }));
+    }
+    blocks
+}
+/// Slack block quote of every line of `text`.
+fn quote(text: &str) -> String {
+    text.lines()
+        .map(|line| format!(">{}", line))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Join link for the incident's conference bridge; pinned when first posted
+/// and re-surfaced by `/incident bridge`.
+pub fn bridge_blocks(provider: &str, bridge_url: &str) -> Vec<Value> {
+    vec![json!({
+        "type": "section",
+        "text": {
+            "type": "mrkdwn",
+            "text": format!("📞 *Incident bridge ({})*\n<{}|Join the call>", provider, bridge_url)
+        }
+    })]
+}
+
+/// One incident update mirrored to a partner channel. `title` and `message`
+/// are already redacted.
+pub fn partner_update_blocks(
+    incident: &Incident,
+    event: &TimelineEvent,
+    title: &str,
+    message: &str,
+    delay_minutes: u64,
+) -> Vec<Value> {
+    let label = match event.event_type {
+        TimelineEventType::Triaged => "Triage started",
+        TimelineEventType::Declared => "Declared",
+        TimelineEventType::StatusUpdate => "Update",
+        TimelineEventType::SeverityChange => "Severity changed",
+        TimelineEventType::Resolved => "Resolved",
+        TimelineEventType::Reopened => "Reopened",
+        TimelineEventType::Canceled => "Canceled",
+        TimelineEventType::Note => "Note",
+    };
+    vec![
+        json!({
+            "type": "section",
+            "text": {
+                "type": "mrkdwn",
+                "text": format!(
+                    "{} *{} incident on {}: {}*\n*{}:* {}",
+                    incident.severity_level().emoji,
+                    incident.severity_level().code,
+                    incident.affected_service,
+                    title,
+                    label,
+                    message
+                )
+            }
+        }),
+        json!({
+            "type": "context",
+            "elements": [{
+                "type": "mrkdwn",
+                "text": format!(
+                    "<!date^{}^{{date_short}} {{time}}|{}> · shared {} min after the fact",
+                    event.timestamp.timestamp(),
+                    time::date_time(&event.timestamp),
+                    delay_minutes
+                )
+            }]
+        }),
+    ]
+}
+
+/// Generated postmortem draft posted in the incident channel, above the
+/// uploaded draft file (`filename`). `due_at` is set when the incident's
+/// severity requires a published postmortem.
+pub fn postmortem_draft_blocks(filename: &str, due_at: Option<DateTime<Utc>>) -> Vec<Value> {
+    let mut blocks = vec![
+        json!({
+            "type": "header",
+            "text": {
+                "type": "plain_text",
+                "text": "📋 Incident Postmortem Draft",
+            }
+        }),
+        json!({
+            "type": "section",
+            "text": {
+                "type": "mrkdwn",
+                "text": format!("The full draft is attached below as `{}`.", filename)
+            }
+        }),
+        json!({
+            "type": "context",
+            "elements": [{
+                "type": "mrkdwn",
+                "text": "_Edit this template and add action items, root cause analysis, and lessons learned._"
+            }]
+        }),
+    ];
+    if let Some(due_at) = due_at {
+        blocks.push(json!({
+            "type": "section",
+            "text": {
+                "type": "mrkdwn",
+                "text": format!(
+                    "📝 A postmortem is required for this incident. Publish it with `/incident postmortem publish` by *{}*.",
+                    time::day(&due_at)
+                )
+            }
+        }));
+    }
+    blocks
+}
+
+/// DM nagging the commander about a required postmortem that hasn't been
+/// published yet.
+pub fn postmortem_reminder_blocks(pending: &PendingPostmortem, now: DateTime<Utc>) -> Vec<Value> {
+    let incident = &pending.incident;
+    let channel = incident
+        .slack_channel_id
+        .as_ref()
+        .map(|c| format!(" (<#{}>)", c))
+        .unwrap_or_default();
+    let due = if pending.is_overdue(now) {
+        format!(
+            "was due *{}* and is {} day(s) overdue",
+            time::day(&pending.due_at),
+            (now - pending.due_at).num_days().max(1)
+        )
+    } else {
+        format!("is due *{}*", time::day(&pending.due_at))
+    };
+
+    vec![json!({
+        "type": "section",
+        "text": {
+            "type": "mrkdwn",
+            "text": format!(
+                "📝 The postmortem for {} *{}*{} {}. Publish it from the incident channel with `/incident postmortem publish`.",
+                incident.severity_level().emoji,
+                incident.title,
+                channel,
+                due
+            )
+        }
+    })]
+}
+
+/// Final message posted in an incident channel right before it is archived.
+pub fn channel_archive_summary_blocks(
+    incident: &Incident,
+    postmortem: Option<&Postmortem>,
+) -> Vec<Value> {
+    let postmortem_text = match postmortem {
+        Some(postmortem) => format!("<{}|Open the postmortem>", postmortem.url),
+        None if incident.status == IncidentStatus::Canceled => {
+            "Not needed for a false alarm".to_string()
+        }
+        None => "No postmortem was published".to_string(),
+    };
+    let outcome = match &incident.cancel_reason {
+        Some(reason) if incident.status == IncidentStatus::Canceled => {
+            format!("*Canceled:*\n{}", reason)
+        }
+        _ => format!("*Duration:*\n{}", duration_text(incident)),
+    };
+
+    vec![
+        json!({
+            "type": "header",
+            "text": {
+                "type": "plain_text",
+                "text": "🗄️ Archiving incident channel",
+            }
+        }),
+        json!({
+            "type": "section",
+            "text": {
+                "type": "mrkdwn",
+                "text": format!("{} *{}*", incident.severity_level().emoji, incident.title)
+            },
+            "fields": [
+                {
+                    "type": "mrkdwn",
+                    "text": format!("*Service:*\n{}", incident.affected_service)
+                },
+                {
+                    "type": "mrkdwn",
+                    "text": format!("*Commander:*\n<@{}>", incident.commander_id)
+                },
+                {
+                    "type": "mrkdwn",
+                    "text": outcome
+                },
+                {
+                    "type": "mrkdwn",
+                    "text": format!("*Postmortem:*\n{}", postmortem_text)
+                },
+            ]
+        }),
+        json!({
+            "type": "context",
+            "elements": [{
+                "type": "mrkdwn",
+                "text": "🔒 This channel is now locked and read-only. Bot commands here are refused; if the problem comes back, declare a new incident with `/incident declare`."
+            }]
+        }),
+    ]
+}
+
+/// Reply to a command run in the channel of a resolved incident (see
+/// `commands::closed`): where to go instead, and the postmortem if there is one.
+pub fn closed_incident_blocks(
+    incident: &Incident,
+    postmortem: Option<&Postmortem>,
+    archived: bool,
+) -> Vec<Value> {
+    let canceled = incident.status == IncidentStatus::Canceled;
+    let next_step = match (archived, canceled) {
+        (true, true) => "🔒 This channel is locked: the incident was canceled as a false alarm and its channel archived. If there is a real problem, declare a new incident with `/incident declare`.",
+        (true, false) => "🔒 This channel is locked: the incident is resolved and its channel archived. If the problem is back, declare a new incident with `/incident declare`.",
+        (false, true) => "This incident was canceled as a false alarm. If there is a real problem, declare a new incident with `/incident declare`.",
+        (false, false) => "This incident is resolved. If the problem is back, the commander can reopen it with `/incident reopen [reason]`.",
+    };
+    let postmortem_text = match postmortem {
+        Some(postmortem) => format!("📚 <{}|Open the postmortem>", postmortem.url),
+        None if canceled => "No postmortem is needed for a false alarm".to_string(),
+        None if archived => "No postmortem was published".to_string(),
+        None => "No postmortem published yet; draft one with `/incident postmortem`".to_string(),
+    };
+
+    vec![json!({
+        "type": "section",
+        "text": {
+            "type": "mrkdwn",
+            "text": format!(
+                "{} *{}*\n{}\n{}",
+                incident.severity_level().emoji,
+                incident.title,
+                next_step,
+                postmortem_text
+            )
+        }
+    })]
+}
+
+pub fn postmortem_published_blocks(url: &str, published_by: &str) -> Vec<Value> {
+    vec![json!({
+        "type": "section",
+        "text": {
+            "type": "mrkdwn",
+            "text": format!(
+                "📚 *Postmortem published to Confluence*\n<{}|Open the postmortem>\n_Published by <@{}>_",
+                url, published_by
+            )
+        }
+    })]
+}
+
+/// Action ID for the "Acknowledge" button on a test page; the value is the
+/// paging test ID.
+pub const PAGING_TEST_ACK_ACTION: &str = "paging_test_ack";
+
+/// Monthly test page, DMed to everyone on the P1 escalation chain.
+pub fn paging_test_page_blocks(test: &PagingTest, source: &str) -> Vec<Value> {
+    vec![
+        json!({
+            "type": "header",
+            "text": {
+                "type": "plain_text",
+                "text": "🧪 PAGING TEST — not a real incident",
+            }
+        }),
+        json!({
+            "type": "section",
+            "text": {
+                "type": "mrkdwn",
+                "text": format!(
+                    "This is the monthly test of the incident paging path for {}. You're on the P1 escalation chain via {}. Please acknowledge so we know pages reach you; nothing else is needed.",
+                    test.month.format("%B %Y"),
+                    source
+                )
+            }
+        }),
+        json!({
+            "type": "actions",
+            "elements": [{
+                "type": "button",
+                "text": { "type": "plain_text", "text": "Acknowledge" },
+                "style": "primary",
+                "action_id": PAGING_TEST_ACK_ACTION,
+                "value": test.id.to_string()
+            }]
+        }),
+    ]
+}
+
+/// "45s", "3m 12s" or "2h 5m".
+pub fn latency_text(latency: Duration) -> String {
+    let seconds = latency.num_seconds().max(0);
+    if seconds < 60 {
+        format!("{}s", seconds)
+    } else if seconds < 3600 {
+        format!("{}m {}s", seconds / 60, seconds % 60)
+    } else {
+        format!("{}h {}m", seconds / 3600, seconds % 3600 / 60)
+    }
+}
+
+/// Results of a paging test once its acknowledgement window has passed.
+pub fn paging_test_report_blocks(
+    test: &PagingTest,
+    pages: &[PagingTestPage],
+    ack_minutes: u64,
+) -> Vec<Value> {
+    let mention = |recipient: &str| {
+        if recipient.starts_with('S') {
+            format!("<!subteam^{}>", recipient)
+        } else {
+            format!("<@{}>", recipient)
+        }
+    };
+    let acknowledged = pages.iter().filter(|p| p.acknowledged_at.is_some()).count();
+    let lines: Vec<String> = pages
+        .iter()
+        .map(|page| match (&page.delivery_error, page.ack_latency()) {
+            (Some(error), _) => format!(
+                "❌ {} ({}): page not delivered, `{}`",
+                mention(&page.recipient_id),
+                page.source,
+                error
+            ),
+            (None, Some(latency)) => format!(
+                "✅ {} ({}): acknowledged in {}",
+                mention(&page.recipient_id),
+                page.source,
+                latency_text(latency)
+            ),
+            (None, None) => format!(
+                "⏳ {} ({}): no acknowledgement within {} min",
+                mention(&page.recipient_id),
+                page.source,
+                ack_minutes
+            ),
+        })
+        .collect();
+
+    let mut blocks = vec![
+        json!({
+            "type": "header",
+            "text": {
+                "type": "plain_text",
+                "text": format!("🧪 Paging test — {}", test.month.format("%B %Y")),
+            }
+        }),
+        json!({
+            "type": "section",
+            "text": {
+                "type": "mrkdwn",
+                "text": format!(
+                    "*{} of {}* on the P1 escalation chain acknowledged the test page.",
+                    acknowledged,
+                    pages.len()
+                )
+            }
+        }),
+    ];
+    if pages.is_empty() {
+        blocks.push(json!({
+            "type": "section",
+            "text": {
+                "type": "mrkdwn",
+                "text": "⚠️ Nobody is on the chain: set `EXEC_TIERS`, `P1_USERS`, `NOTIFICATION_RULES` or `BACKUP_COMMANDERS`."
+            }
+        }));
+    }
+    // Section text is capped at 3000 characters
+    for chunk in lines.chunks(20) {
+        blocks.push(json!({
+            "type": "section",
+            "text": { "type": "mrkdwn", "text": chunk.join("\n") }
+        }));
+    }
+    blocks
+}
+
+/// Monthly KPI scorecard DMed to a team's leads. Each metric is marked ✅/❌
+/// when the team has a target for it.
+pub fn scorecard_blocks(scorecard: &Scorecard) -> Vec<Value> {
+    fn check(met: bool) -> &'static str {
+        if met {
+            "✅"
+        } else {
+            "❌"
+        }
+    }
+    let percent = |rate: f64| format!("{:.0}%", rate * 100.0);
+
+    let targets = &scorecard.targets;
+    let mttr = match (scorecard.mttr_minutes, targets.mttr_minutes) {
+        (None, _) => "n/a".to_string(),
+        (Some(actual), None) => format!("{:.0} min", actual),
+        (Some(actual), Some(target)) => format!(
+            "{:.0} min (target ≤ {:.0}) {}",
+            actual,
+            target,
+            check(actual <= target)
+        ),
+    };
+    let rate = |actual: Option<f64>, target: Option<f64>| match (actual, target) {
+        (None, _) => "n/a".to_string(),
+        (Some(actual), None) => percent(actual),
+        (Some(actual), Some(target)) => format!(
+            "{} (target ≥ {}) {}",
+            percent(actual),
+            percent(target),
+            check(actual >= target)
+        ),
+    };
+
+    vec![
+        json!({
+            "type": "header",
+            "text": {
+                "type": "plain_text",
+                "text": format!(
+                    "📊 {} incident scorecard — {}",
+                    scorecard.team,
+                    scorecard.period_start.format("%B %Y")
+                )
+            }
+        }),
+        json!({
+            "type": "section",
+            "fields": [
+                { "type": "mrkdwn", "text": format!("*Declared:*\n{}", scorecard.declared) },
+                { "type": "mrkdwn", "text": format!("*Resolved:*\n{}", scorecard.resolved) },
+                { "type": "mrkdwn", "text": format!("*MTTR:*\n{}", mttr) },
+                {
+                    "type": "mrkdwn",
+                    "text": format!(
+                        "*Postmortems completed:*\n{}",
+                        rate(scorecard.postmortem_completion, targets.postmortem_completion)
+                    )
+                },
+                {
+                    "type": "mrkdwn",
+                    "text": format!(
+                        "*Action items closed:*\n{}",
+                        rate(scorecard.action_item_closure, targets.action_item_closure)
+                    )
+                }
+            ]
+        }),
+    ]
+}
+
+/// "Q3 2026" for a quarter starting on `period_start`.
+pub fn quarter_label(period_start: NaiveDate) -> String {
+    format!(
+        "Q{} {}",
+        (period_start.month0() / 3) + 1,
+        period_start.year()
+    )
+}
+
+/// Quarterly coaching report DMed to a commander who opted in. Each figure
+/// sits next to the median across all commanders, with suggestions only
+/// where theirs trails it.
+pub fn coaching_report_blocks(report: &CoachingReport) -> Vec<Value> {
+    fn minutes(value: Option<f64>) -> String {
+        value.map_or("n/a".to_string(), |minutes| format!("{:.0} min", minutes))
+    }
+    let percent =
+        |rate: Option<f64>| rate.map_or("n/a".to_string(), |r| format!("{:.0}%", r * 100.0));
+    let mine = &report.mine;
+    let completion = report.postmortem_completion();
+    let trails = |actual: Option<f64>, median: Option<f64>| {
+        actual
+            .zip(median)
+            .is_some_and(|(actual, median)| actual > median)
+    };
+
+    let mut tips = Vec::new();
+    if trails(
+        mine.mean_first_update_minutes,
+        report.median_first_update_minutes,
+    ) {
+        tips.push("• A short first update, even just _investigating_, lets responders and stakeholders stop guessing. `/incident update-status` right after declaring is enough.");
+    }
+    if trails(
+        mine.mean_update_interval_minutes,
+        report.median_update_interval_minutes,
+    ) {
+        tips.push("• Regular updates keep questions out of the channel. Consider naming a comms lead with `/incident roles` on longer incidents.");
+    }
+    if trails(report.median_postmortem_completion, completion) {
+        tips.push("• Postmortems are where fixes come from. `/incident postmortem` drafts one from the timeline in a minute.");
+    }
+    let tips_text = if tips.is_empty() {
+        "🌟 You're at or ahead of the median on every measure. Thank you for keeping everyone informed.".to_string()
+    } else {
+        format!("*Ideas for next quarter*\n{}", tips.join("\n"))
+    };
+
+    vec![
+        json!({
+            "type": "header",
+            "text": {
+                "type": "plain_text",
+                "text": format!("🧭 Your incident command — {}", quarter_label(report.period_start))
+            }
+        }),
+        json!({
+            "type": "section",
+            "fields": [
+                { "type": "mrkdwn", "text": format!("*Incidents commanded:*\n{}", mine.incidents) },
+                {
+                    "type": "mrkdwn",
+                    "text": format!(
+                        "*Time to first update:*\n{} (median {})",
+                        minutes(mine.mean_first_update_minutes),
+                        minutes(report.median_first_update_minutes)
+                    )
+                },
+                {
+                    "type": "mrkdwn",
+                    "text": format!(
+                        "*Time between updates:*\n{} (median {})",
+                        minutes(mine.mean_update_interval_minutes),
+                        minutes(report.median_update_interval_minutes)
+                    )
+                },
+                {
+                    "type": "mrkdwn",
+                    "text": format!(
+                        "*Postmortems completed:*\n{} of {} resolved, {} (median {})",
+                        mine.resolved_with_postmortem,
+                        mine.resolved,
+                        percent(completion),
+                        percent(report.median_postmortem_completion)
+                    )
+                }
+            ]
+        }),
+        mrkdwn_section(&tips_text),
+        context_block("Only you receive this report. It compares you with the median commander, never with anyone by name. `/incident coaching off` to stop."),
+    ]
+}
+
+/// Names of the duration histogram buckets, e.g. `<15m`, `1h–4h`, `3d+`.
+pub fn duration_bucket_labels() -> Vec<String> {
+    fn short(minutes: i64) -> String {
+        if minutes % (24 * 60) == 0 {
+            format!("{}d", minutes / (24 * 60))
+        } else if minutes % 60 == 0 {
+            format!("{}h", minutes / 60)
+        } else {
+            format!("{}m", minutes)
+        }
+    }
+    let mut labels = vec![format!("<{}", short(DURATION_BUCKETS[0]))];
+    labels.extend(
+        DURATION_BUCKETS
+            .windows(2)
+            .map(|pair| format!("{}–{}", short(pair[0]), short(pair[1]))),
+    );
+    labels.push(format!(
+        "{}+",
+        short(DURATION_BUCKETS[DURATION_BUCKETS.len() - 1])
+    ));
+    labels
+}
+
+/// `/incident metrics --chart`: one resolution time histogram per severity,
+/// as an uploaded image when `file_id` is set, with the bucket counts
+/// underneath (the image has no axis labels).
+pub fn duration_histogram_blocks(charts: &[(DurationHistogram, Option<String>)]) -> Vec<Value> {
+    if charts.is_empty() {
+        return vec![json!({
+            "type": "context",
+            "elements": [{ "type": "mrkdwn", "text": "No resolved incidents to chart" }]
+        })];
+    }
+
+    let labels = duration_bucket_labels();
+    let mut blocks = Vec::new();
+    for (histogram, file_id) in charts {
+        let title = format!(
+            "{} {} time to resolve ({} resolved)",
+            histogram.severity.emoji(),
+            histogram.severity.label(),
+            histogram.total()
+        );
+        let counts = labels
+            .iter()
+            .zip(&histogram.counts)
+            .map(|(label, count)| format!("{} *{}*", label, count))
+            .collect::<Vec<_>>()
+            .join(" · ");
+        match file_id {
+            Some(file_id) => blocks.push(json!({
+                "type": "image",
+                "slack_file": { "id": file_id },
+                "title": { "type": "plain_text", "text": title },
+                "alt_text": format!("Histogram of {} resolution times: {}", histogram.severity.label(), counts.replace('*', ""))
+            })),
+            None => blocks.push(json!({
+                "type": "section",
+                "text": { "type": "mrkdwn", "text": format!("*{}*", title) }
+            })),
+        }
+        blocks.push(json!({
+            "type": "context",
+            "elements": [{ "type": "mrkdwn", "text": counts }]
+        }));
+    }
+    blocks
+}
+
+/// Services listed in the metrics report before the rest are summarized.
+const METRICS_MAX_SERVICES: usize = 10;
+
+/// `/incident metrics`: totals, then breakdowns by severity, service and month.
+pub fn metrics_report_blocks(report: &MetricsReport) -> Vec<Value> {
+    fn minutes(value: Option<f64>) -> String {
+        match value {
+            Some(minutes) if minutes >= 60.0 => time::duration(minutes.round() as i64),
+            Some(minutes) => format!("{:.0}min", minutes),
+            None => "n/a".to_string(),
+        }
+    }
+    fn longest(row: &MetricsRow) -> String {
+        match &row.longest {
+            Some((title, duration)) => {
+                format!("{} ({})", title, minutes(Some(f64::from(*duration))))
+            }
+            None => "n/a".to_string(),
+        }
+    }
+    fn line(label: &str, row: &MetricsRow) -> String {
+        format!(
+            "{} — {} declared · MTTR {} · median {} · MTTA {} · longest: {}",
+            label,
+            row.declared,
+            minutes(row.mean_minutes),
+            minutes(row.median_minutes),
+            minutes(row.mean_ack_minutes),
+            longest(row)
+        )
+    }
+    let section = |title: &str, lines: Vec<String>| {
+        json!({
+            "type": "section",
+            "text": {
+                "type": "mrkdwn",
+                "text": format!("*{}*\n{}", title, lines.join("\n"))
+            }
+        })
+    };
+
+    let mut blocks = vec![json!({
+        "type": "header",
+        "text": {
+            "type": "plain_text",
+            "text": format!("📈 Incident metrics — last {} days", report.window_days)
+        }
+    })];
+    if let Some(services) = report.scope.services() {
+        blocks.push(json!({
+            "type": "context",
+            "elements": [{
+                "type": "mrkdwn",
+                "text": format!("Limited to your teams' services: {}", services.join(", "))
+            }]
+        }));
+    }
+
+    let Some(overall) = report.overall.as_ref().filter(|row| row.declared > 0) else {
+        blocks.push(json!({
+            "type": "section",
+            "text": {
+                "type": "mrkdwn",
+                "text": format!("No incidents declared since {}", time::date(&report.since))
+            }
+        }));
+        return blocks;
+    };
+
+    blocks.push(json!({
+        "type": "section",
+        "fields": [
+            { "type": "mrkdwn", "text": format!("*Declared:*\n{}", overall.declared) },
+            { "type": "mrkdwn", "text": format!("*Resolved:*\n{}", overall.resolved) },
+            { "type": "mrkdwn", "text": format!("*MTTR:*\n{}", minutes(overall.mean_minutes)) },
+            { "type": "mrkdwn", "text": format!("*Median duration:*\n{}", minutes(overall.median_minutes)) },
+            { "type": "mrkdwn", "text": format!("*MTTA:*\n{}", minutes(overall.mean_ack_minutes)) },
+            { "type": "mrkdwn", "text": format!("*Longest:*\n{}", longest(overall)) }
+        ]
+    }));
+    blocks.push(json!({ "type": "divider" }));
+
+    let severity_lines = report
+        .by_severity
+        .iter()
+        .map(|row| {
+            let emoji = row
+                .key
+                .parse::<Severity>()
+                .map(|s| s.emoji())
+                .unwrap_or("⚪");
+            line(&format!("{} *{}*", emoji, row.key), row)
+        })
+        .collect();
+    blocks.push(section("By severity", severity_lines));
+
+    let mut service_lines: Vec<String> = report
+        .by_service
+        .iter()
+        .take(METRICS_MAX_SERVICES)
+        .map(|row| line(&format!("*{}*", row.key), row))
+        .collect();
+    if report.by_service.len() > METRICS_MAX_SERVICES {
+        service_lines.push(format!(
+            "_…and {} more_",
+            report.by_service.len() - METRICS_MAX_SERVICES
+        ));
+    }
+    blocks.push(section("By service", service_lines));
+
+    let month_lines = report
+        .by_month
+        .iter()
+        .map(|row| line(&format!("*{}*", row.key), row))
+        .collect();
+    blocks.push(section("By month", month_lines));
+
+    blocks.push(json!({
+        "type": "context",
+        "elements": [{
+            "type": "mrkdwn",
+            "text": "MTTR: mean time to resolve · MTTA: mean time from declaration to the first status update"
+        }]
+    }));
+
+    blocks
+}
+
+/// People listed in an all-hands `/incident load` report before truncating.
+const LOAD_MAX_PEOPLE: usize = 15;
+
+/// Incident load per person for `/incident load`.
+pub fn load_report_blocks(report: &LoadReport, window_days: i64) -> Vec<Value> {
+    let mut blocks = vec![json!({
+        "type": "header",
+        "text": {
+            "type": "plain_text",
+            "text": format!("🧯 Incident load — last {} days", window_days)
+        }
+    })];
+
+    if report.people.is_empty() {
+        blocks.push(json!({
+            "type": "section",
+            "text": {
+                "type": "mrkdwn",
+                "text": format!(
+                    "No incident command or roles held since {}",
+                    time::date(&report.since)
+                )
+            }
+        }));
+        return blocks;
+    }
+
+    let mut lines: Vec<String> = report
+        .people
+        .iter()
+        .take(LOAD_MAX_PEOPLE)
+        .map(|person| {
+            format!(
+                "<@{}> — {} incident{} · {:.1}h command · {:.1}h roles · {:.1}h nights · {:.1}h weekends",
+                person.user_id,
+                person.incidents,
+                if person.incidents == 1 { "" } else { "s" },
+                person.command_hours,
+                person.role_hours,
+                person.night_hours,
+                person.weekend_hours
+            )
+        })
+        .collect();
+    if report.people.len() > LOAD_MAX_PEOPLE {
+        lines.push(format!(
+            "_…and {} more_",
+            report.people.len() - LOAD_MAX_PEOPLE
+        ));
+    }
+    blocks.push(json!({
+        "type": "section",
+        "text": { "type": "mrkdwn", "text": lines.join("\n") }
+    }));
+    blocks.push(json!({
+        "type": "context",
+        "elements": [{
+            "type": "mrkdwn",
+            "text": format!(
+                "Nights are weekdays 22:00-08:00 and weekends all of Saturday and Sunday, at UTC{:+}. Overlapping roles count once toward nights and weekends.",
+                report.utc_offset_hours
+            )
+        }]
+    }));
+
+    blocks
+}
+
+/// Action ID for the "Next page" button on search results; the value is
+/// `<offset>:<query>` so the click re-runs the same search.
+pub const SEARCH_PAGE_ACTION: &str = "search_page";
+
+/// One page of `/incident search` results starting at `offset`.
+pub fn search_results_blocks(
+    query: &str,
+    incidents: &[Incident],
+    offset: i64,
+    has_more: bool,
+) -> Vec<Value> {
+    if incidents.is_empty() {
+        let text = if offset == 0 {
+            format!("🔎 No incidents match `{}`", query)
+        } else {
+            format!("🔎 No more incidents match `{}`", query)
+        };
+        return vec![json!({
+            "type": "section",
+            "text": { "type": "mrkdwn", "text": text }
+        })];
+    }
+
+    let mut blocks = vec![json!({
+        "type": "section",
+        "text": {
+            "type": "mrkdwn",
+            "text": format!(
+                "🔎 Results {}–{} for `{}`",
+                offset + 1,
+                offset + incidents.len() as i64,
+                query
+            )
+        }
+    })];
+    for incident in incidents {
+        let channel = incident
+            .slack_channel_id
+            .as_ref()
+            .map(|c| format!(" · <#{}>", c))
+            .unwrap_or_default();
+        blocks.push(json!({
+            "type": "section",
+            "text": {
+                "type": "mrkdwn",
+                "text": format!(
+                    "{} *{}* — {}\n{} · {} · {} · declared {}{}",
+                    incident.severity_level().emoji,
+                    incident.severity_level().code,
+                    incident.title,
+                    incident.reference(),
+                    incident.affected_service,
+                    incident.status.as_db_str(),
+                    time::date(&incident.declared_at),
+                    channel
+                )
+            }
+        }));
+    }
+    if has_more {
+        blocks.push(json!({
+            "type": "actions",
+            "elements": [{
+                "type": "button",
+                "text": { "type": "plain_text", "text": "Next page" },
+                "action_id": SEARCH_PAGE_ACTION,
+                "value": format!("{}:{}", offset + incidents.len() as i64, query)
+            }]
+        }));
+    }
+    blocks
+}
+
+/// Days covered by the open-incidents burndown sparkline.
+pub const BURNDOWN_DAYS: i32 = 30;
+
+/// Burndown sparkline uploaded by the burndown job, as an image block.
+pub fn burndown_image_block(file_id: &str) -> Value {
+    json!({
+        "type": "image",
+        "slack_file": { "id": file_id },
+        "title": {
+            "type": "plain_text",
+            "text": format!("Open incidents, last {} days", BURNDOWN_DAYS)
+        },
+        "alt_text": format!("Sparkline of open incidents over the last {} days", BURNDOWN_DAYS)
+    })
+}
+
+/// Weekly digest for the week starting `week_start`: last week's counts, what
+/// is open now, overdue required postmortems, and the burndown sparkline when
+/// one has been uploaded.
+pub fn weekly_digest_blocks(
+    week_start: NaiveDate,
+    last_week: &ServiceStats,
+    open_now: i64,
+    overdue_postmortems: &[PendingPostmortem],
+    burndown_file_id: Option<&str>,
+) -> Vec<Value> {
+    let mttr = last_week
+        .avg_resolution_minutes
+        .map(|minutes| format!("{:.0} min", minutes))
+        .unwrap_or_else(|| "n/a".to_string());
+
+    let mut blocks = vec![
+        json!({
+            "type": "header",
+            "text": {
+                "type": "plain_text",
+                "text": format!("🗓️ Weekly incident digest — week of {}", time::day(&week_start))
+            }
+        }),
+        json!({
+            "type": "section",
+            "fields": [
+                { "type": "mrkdwn", "text": format!("*Open now:*\n{}", open_now) },
+                { "type": "mrkdwn", "text": format!("*Declared last week:*\n{}", last_week.declared) },
+                { "type": "mrkdwn", "text": format!("*Resolved last week:*\n{}", last_week.resolved) },
+                { "type": "mrkdwn", "text": format!("*MTTR last week:*\n{}", mttr) }
+            ]
+        }),
+    ];
+    if !overdue_postmortems.is_empty() {
+        let lines: Vec<String> = overdue_postmortems
+            .iter()
+            .map(|pending| {
+                format!(
+                    "• {} *{}* — <@{}>, due {}",
+                    pending.incident.severity_level().emoji,
+                    pending.incident.title,
+                    pending.incident.commander_id,
+                    time::day(&pending.due_at)
+                )
+            })
+            .collect();
+        blocks.push(json!({
+            "type": "section",
+            "text": {
+                "type": "mrkdwn",
+                "text": format!(
+                    "*Overdue postmortems ({}):*\n{}",
+                    overdue_postmortems.len(),
+                    lines.join("\n")
+                )
+            }
+        }));
+    }
+    if let Some(file_id) = burndown_file_id {
+        blocks.push(burndown_image_block(file_id));
+    }
+    blocks
+}
+
+/// Services listed in the weekly report before truncating.
+const WEEKLY_REPORT_MAX_SERVICES: usize = 5;
+
+/// The weekly incident report for the leadership channel.
+pub fn weekly_report_blocks(report: &WeeklyReport) -> Vec<Value> {
+    let last_day = report.week_start - Duration::days(1);
+    let mut blocks = vec![
+        json!({
+            "type": "header",
+            "text": {
+                "type": "plain_text",
+                "text": "📊 Weekly incident report"
+            }
+        }),
+        context_block(&format!(
+            "{} – {} (UTC)",
+            time::day(&(report.week_start - Duration::days(7))),
+            time::day(&last_day)
+        )),
+    ];
+
+    let Some(overall) = report.overall.as_ref().filter(|row| row.declared > 0) else {
+        blocks.push(mrkdwn_section("🎉 No incidents were declared last week."));
+        return blocks;
+    };
+    let mttr = overall
+        .mean_minutes
+        .map(|minutes| time::duration(minutes.round() as i64))
+        .unwrap_or_else(|| "n/a".to_string());
+    blocks.push(json!({
+        "type": "section",
+        "fields": [
+            { "type": "mrkdwn", "text": format!("*Declared:*\n{}", overall.declared) },
+            { "type": "mrkdwn", "text": format!("*Resolved:*\n{}", overall.resolved) },
+            { "type": "mrkdwn", "text": format!("*Total downtime:*\n{}", time::duration(report.downtime_minutes)) },
+            { "type": "mrkdwn", "text": format!("*MTTR:*\n{}", mttr) }
+        ]
+    }));
+
+    let severities: Vec<String> = report
+        .by_severity
+        .iter()
+        .map(|row| {
+            let emoji = row
+                .key
+                .parse::<Severity>()
+                .map(|s| s.emoji())
+                .unwrap_or("⚪");
+            format!("{} {}: {}", emoji, row.key, row.declared)
+        })
+        .collect();
+    blocks.push(mrkdwn_section(&format!(
+        "*By severity*\n{}",
+        severities.join(" · ")
+    )));
+
+    let mut services: Vec<String> = report
+        .by_service
+        .iter()
+        .take(WEEKLY_REPORT_MAX_SERVICES)
+        .map(|row| format!("• *{}*: {}", row.key, row.declared))
+        .collect();
+    if report.by_service.len() > WEEKLY_REPORT_MAX_SERVICES {
+        services.push(format!(
+            "_…and {} more_",
+            report.by_service.len() - WEEKLY_REPORT_MAX_SERVICES
+        ));
+    }
+    blocks.push(mrkdwn_section(&format!(
+        "*By service*\n{}",
+        services.join("\n")
+    )));
+
+    if !report.top_contributors.is_empty() {
+        let contributors: Vec<String> = report
+            .top_contributors
+            .iter()
+            .map(|c| {
+                format!(
+                    "• <@{}> — {} incident{}, {} timeline update{}",
+                    c.user_id,
+                    c.incidents,
+                    if c.incidents == 1 { "" } else { "s" },
+                    c.timeline_events,
+                    if c.timeline_events == 1 { "" } else { "s" }
+                )
+            })
+            .collect();
+        blocks.push(mrkdwn_section(&format!(
+            "*Top contributors*\n{}",
+            contributors.join("\n")
+        )));
+    }
+    blocks.push(context_block(
+        "Downtime adds up the time each incident was open during the week, so overlapping incidents count twice",
+    ));
+    blocks
+}
+
+/// Action ID prefix for the filter controls on a posted timeline. Button and
+/// menu option values are `<incident_id>:<event_type|all>:<hours|all>`, so
+/// each control carries the filter it selects.
+pub const TIMELINE_FILTER_ACTION_PREFIX: &str = "timeline_filter_";
+
+/// Type filters offered as buttons: (event type, label).
+const TIMELINE_TYPE_FILTERS: &[(Option<TimelineEventType>, &str)] = &[
+    (None, "All"),
+    (Some(TimelineEventType::StatusUpdate), "Status updates"),
+    (Some(TimelineEventType::SeverityChange), "Severity changes"),
+    (Some(TimelineEventType::Note), "Notes"),
+];
+
+/// Time windows offered in the menu: (hours, label).
+const TIMELINE_WINDOWS: &[(Option<i64>, &str)] = &[
+    (None, "Whole incident"),
+    (Some(1), "Last hour"),
+    (Some(6), "Last 6 hours"),
+    (Some(24), "Last 24 hours"),
+];
+
+pub fn timeline_filter_value(incident_id: IncidentId, filter: &TimelineFilter) -> String {
+    format!(
+        "{}:{}:{}",
+        incident_id,
+        filter.event_type.map_or("all", |t| t.as_db_str()),
+        filter
+            .window_hours
+            .map_or_else(|| "all".to_string(), |h| h.to_string())
+    )
+}
+
+/// Slack rejects section text longer than this.
+const SECTION_MAX_CHARS: usize = 3000;
+
+/// Slack allows at most this many blocks in one message.
+const MESSAGE_MAX_BLOCKS: usize = 50;
+
+/// Timeline message with filter controls; `events` are already filtered,
+/// each with its number in the whole timeline (see
+/// `services::timeline::number_events`). Everything has to fit in one message (filter changes update it in
+/// place), so when the events don't fit, the newest are shown with a note
+/// on how many earlier events were left out.
+pub fn timeline_blocks(
+    incident_id: IncidentId,
+    events: &[(usize, TimelineEvent)],
+    filter: &TimelineFilter,
+) -> Vec<Value> {
+    let mut blocks = timeline_header(incident_id, filter);
+    if events.is_empty() {
+        blocks.push(timeline_empty_section(filter));
+        return blocks;
+    }
+
+    let mut chunks = timeline_chunks(events);
+    // Room for the header, the controls and the note
+    let room = MESSAGE_MAX_BLOCKS - blocks.len() - 1;
+    if chunks.len() > room {
+        let dropped = chunks.drain(..chunks.len() - room);
+        let omitted: usize = dropped.map(|(_, count)| count).sum();
+        blocks.push(context_block(&format!(
+            "{} earlier events not shown. Use `/incident timeline` to post the whole timeline.",
+            omitted
+        )));
+    }
+    blocks.extend(chunks.into_iter().map(|(text, _)| mrkdwn_section(&text)));
+    blocks
+}
+
+/// The `/incident timeline` post: as many messages as the events need, the
+/// first with the header and filter controls. `total` is the number of
+/// events in the timeline when `events` are only the newest of them.
+pub fn timeline_messages(
+    incident_id: IncidentId,
+    events: &[(usize, TimelineEvent)],
+    filter: &TimelineFilter,
+    total: usize,
+) -> Vec<Vec<Value>> {
+    let mut first = timeline_header(incident_id, filter);
+    if events.is_empty() {
+        first.push(timeline_empty_section(filter));
+        return vec![first];
+    }
+    if total > events.len() {
+        first.push(context_block(&format!(
+            "Showing the last {} of {} events",
+            events.len(),
+            total
+        )));
+    }
+
+    let mut messages = vec![first];
+    for (text, _) in timeline_chunks(events) {
+        if messages
+            .last()
+            .is_some_and(|m| m.len() >= MESSAGE_MAX_BLOCKS)
+        {
+            messages.push(Vec::new());
+        }
+        if let Some(message) = messages.last_mut() {
+            message.push(mrkdwn_section(&text));
+        }
+    }
+    messages
+}
+
+fn timeline_header(incident_id: IncidentId, filter: &TimelineFilter) -> Vec<Value> {
+    let mut blocks = vec![json!({
+        "type": "header",
+        "text": {
+            "type": "plain_text",
+            "text": "📋 Incident Timeline",
+        }
+    })];
+
+    let mut controls: Vec<Value> = TIMELINE_TYPE_FILTERS
+        .iter()
+        .map(|(event_type, label)| {
+            let target = TimelineFilter {
+                event_type: *event_type,
+                ..*filter
+            };
+            let mut button = json!({
+                "type": "button",
+                "text": { "type": "plain_text", "text": *label },
+                "action_id": format!(
+                    "{}type_{}",
+                    TIMELINE_FILTER_ACTION_PREFIX,
+                    event_type.map_or("all", |t| t.as_db_str())
+                ),
+                "value": timeline_filter_value(incident_id, &target)
+            });
+            if filter.event_type == *event_type {
+                button["style"] = json!("primary");
+            }
+            button
+        })
+        .collect();
+
+    let window_options: Vec<Value> = TIMELINE_WINDOWS
+        .iter()
+        .map(|(hours, label)| {
+            let target = TimelineFilter {
+                window_hours: *hours,
+                ..*filter
+            };
+            json!({
+                "text": { "type": "plain_text", "text": *label },
+                "value": timeline_filter_value(incident_id, &target)
+            })
+        })
+        .collect();
+    let selected = TIMELINE_WINDOWS
+        .iter()
+        .position(|(hours, _)| *hours == filter.window_hours)
+        .unwrap_or(0);
+    controls.push(json!({
+        "type": "static_select",
+        "action_id": format!("{}window", TIMELINE_FILTER_ACTION_PREFIX),
+        "placeholder": { "type": "plain_text", "text": "Time window" },
+        "initial_option": window_options[selected].clone(),
+        "options": window_options
+    }));
+    blocks.push(json!({ "type": "actions", "elements": controls }));
+    blocks
+}
+
+fn timeline_empty_section(filter: &TimelineFilter) -> Value {
+    let text = if filter.is_unfiltered() {
+        "_No timeline events yet._"
+    } else {
+        "_No timeline events match this filter._"
+    };
+    mrkdwn_section(text)
+}
+
+/// Event lines packed into section texts of at most `SECTION_MAX_CHARS`,
+/// each with the number of events it holds. An event too long for a
+/// section on its own is cut short.
+fn timeline_chunks(events: &[(usize, TimelineEvent)]) -> Vec<(String, usize)> {
+    let mut chunks: Vec<(String, usize)> = Vec::new();
+    for (number, event) in events {
+        let mut line = timeline_event_text(*number, event);
+        if line.chars().count() > SECTION_MAX_CHARS {
+            line = line.chars().take(SECTION_MAX_CHARS - 1).collect();
+            line.push('…');
+        }
+        match chunks.last_mut() {
+            Some((text, count))
+                if text.chars().count() + 2 + line.chars().count() <= SECTION_MAX_CHARS =>
+            {
+                text.push_str("\n\n");
+                text.push_str(&line);
+                *count += 1;
+            }
+            _ => chunks.push((line, 1)),
+        }
+    }
+    chunks
+}
+
+/// One timeline line, led by the number `/incident timeline edit` and
+/// `delete` refer to it by.
+fn timeline_event_text(number: usize, e: &TimelineEvent) -> String {
+    if e.is_deleted() {
+        return format!(
+            "`#{}` 🗑️ *{}* — _Deleted_",
+            number,
+            time::clock(&e.timestamp)
+        );
+    }
+    let edited = if e.updated_at.is_some() {
+        " (edited)"
+    } else {
+        ""
+    };
+    let event_icon = match e.event_type {
+        TimelineEventType::Triaged => "🔍",
+        TimelineEventType::Declared => "🚨",
+        TimelineEventType::StatusUpdate => "📝",
+        TimelineEventType::SeverityChange => "⚠️",
+        TimelineEventType::Resolved => "✅",
+        TimelineEventType::Note => {
+            // Notes are quoted so they stand apart from lifecycle events
+            return format!(
+                "`#{}` 🗒️ *{}* — _Note from {}{}_\n{}",
+                number,
+                time::clock(&e.timestamp),
+                author(&e.posted_by),
+                edited,
+                quote(&e.message)
+            );
+        }
+        TimelineEventType::Reopened => "🔁",
+        TimelineEventType::Canceled => "⚪",
+    };
+    let drafted = e
+        .drafted_by
+        .as_ref()
+        .map(|scribe| format!(", drafted by <@{}>", scribe))
+        .unwrap_or_default();
+    format!(
+        "`#{}` {} *{}* — {}\n_by <@{}>{}{}_",
+        number,
+        event_icon,
+        time::clock(&e.timestamp),
+        e.message,
+        e.posted_by,
+        drafted,
+        edited
+    )
+}
+
+fn mrkdwn_section(text: &str) -> Value {
+    json!({
+        "type": "section",
+        "text": {
+            "type": "mrkdwn",
+            "text": text
+        }
+    })
+}
+
+fn context_block(text: &str) -> Value {
+    json!({
+        "type": "context",
+        "elements": [{ "type": "mrkdwn", "text": text }]
+    })
+}
+
+pub fn simulation_trace_blocks(trace: &[String]) -> Vec<Value> {
+    vec![
+        json!({
+            "type": "section",
+            "text": {
+                "type": "mrkdwn",
+                "text": format!("🧪 *Declare simulation (dry run)*\n{}", trace.join("\n"))
+            }
+        }),
+        json!({
+            "type": "context",
+            "elements": [{
+                "type": "mrkdwn",
+                "text": "No incident, channel, invite, notification, or Statuspage update was created."
+            }]
+        }),
+    ]
+}
+
+/// Effective notification routing, one section per severity.
+pub fn notification_routing_blocks(sections: &[String]) -> Vec<Value> {
+    let mut blocks = vec![json!({
+        "type": "header",
+        "text": { "type": "plain_text", "text": "📣 Notification Routing" }
+    })];
+    blocks.extend(sections.iter().map(|section| {
+        json!({
+            "type": "section",
+            "text": { "type": "mrkdwn", "text": section }
+        })
+    }));
+    blocks.push(json!({
+        "type": "context",
+        "elements": [{
+            "type": "mrkdwn",
+            "text": "Quiet (security) incidents only ever post to their incident channel."
+        }]
+    }));
+    blocks
+}
+
+/// `/incident template list`: active templates first, then disabled ones.
+pub fn template_list_blocks(templates: &[IncidentTemplate], usage: &[TemplateUsage]) -> Vec<Value> {
+    let mut blocks = vec![json!({
+        "type": "header",
+        "text": { "type": "plain_text", "text": "📋 Incident Templates" }
+    })];
+    if templates.is_empty() {
+        blocks.push(json!({
+            "type": "section",
+            "text": { "type": "mrkdwn", "text": "_No templates yet._" }
+        }));
+    }
+    blocks.extend(templates.iter().map(|template| {
+        let mut text = format!(
+            "*{}* `{}` — {}",
+            template.title,
+            template.name,
+            template.severity.as_db_str()
+        );
+        if let Some(service) = &template.affected_service {
+            text.push_str(&format!(" · {}", service));
+        }
+        if !template.is_active {
+            text.push_str(" · _disabled_");
+        }
+        if let Some(description) = &template.description {
+            text.push_str(&format!("\n{}", description));
+        }
+        if let Some(usage) = usage.iter().find(|u| u.template_id == Some(template.id)) {
+            text.push_str(&format!("\n_{}_", template_usage_line(usage)));
+        }
+        json!({
+            "type": "section",
+            "text": { "type": "mrkdwn", "text": text }
+        })
+    }));
+    if let Some(untemplated) = usage.iter().find(|u| u.template_id.is_none()) {
+        blocks.push(context_block(&format!(
+            "Without a template: {}",
+            template_usage_line(untemplated)
+        )));
+    }
+    blocks.push(json!({
+        "type": "context",
+        "elements": [{
+            "type": "mrkdwn",
+            "text": "`/incident template create`, `edit <name>` or `disable <name>`. Creating a template with a disabled template's name re-enables it."
+        }]
+    }));
+    blocks
+}
+
+/// "3 incidents · MTTR 1h 5min", for comparing templates' MTTRs.
+fn template_usage_line(usage: &TemplateUsage) -> String {
+    let mut line = format!(
+        "{} incident{}",
+        usage.incidents,
+        if usage.incidents == 1 { "" } else { "s" }
+    );
+    if let Some(minutes) = usage.mean_minutes {
+        line.push_str(&format!(
+            " · MTTR {}",
+            time::duration(minutes.round() as i64)
+        ));
+    }
+    line
+}
+
+/// Action ID of the button under the prompt sent to a commander on
+/// resolution; the value is the incident ID. It opens the template modal
+/// prefilled from the incident.
+pub const TEMPLATE_FROM_INCIDENT_ACTION: &str = "template_from_incident";
+
+/// DMed to the commander of an incident resolved without a template.
+pub fn template_prompt_blocks(incident: &Incident) -> Vec<Value> {
+    vec![
+        mrkdwn_section(&format!(
+            "*{}* {} is resolved. Would a template have helped? Create one from this incident and the next one like it starts prefilled.",
+            incident.reference(),
+            incident.title
+        )),
+        json!({
+            "type": "actions",
+            "elements": [{
+                "type": "button",
+                "text": { "type": "plain_text", "text": "Create template" },
+                "action_id": TEMPLATE_FROM_INCIDENT_ACTION,
+                "value": incident.id.to_string()
+            }]
+        }),
+    ]
+}
+
+fn premortem_window(premortem: &Premortem) -> String {
+    format!(
+        "{} – {}",
+        time::date_time(&premortem.window_start),
+        time::date_time(&premortem.window_end)
+    )
+}
+
+fn premortem_risk_lines(risks: &[PremortemRisk]) -> String {
+    if risks.is_empty() {
+        return "_No failure modes recorded._".to_string();
+    }
+    risks
+        .iter()
+        .map(|risk| {
+            let icon = match risk.likelihood.as_str() {
+                "high" => "🔴",
+                "medium" => "🟡",
+                _ => "🟢",
+            };
+            let mut line = format!("{} *{}*: {}", icon, risk.likelihood, risk.failure_mode);
+            if let Some(mitigation) = &risk.mitigation {
+                line.push_str(&format!("\n      _Mitigation:_ {}", mitigation));
+            }
+            line
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// DM to the author once a pre-mortem's guided flow is finished.
+pub fn premortem_summary_blocks(premortem: &Premortem, risks: &[PremortemRisk]) -> Vec<Value> {
+    vec![
+        mrkdwn_section(&format!(
+            "🧭 Pre-mortem saved: *{}* ({})\nLaunch window: {}",
+            premortem.launch_name,
+            premortem.service,
+            premortem_window(premortem)
+        )),
+        mrkdwn_section(&premortem_risk_lines(risks)),
+        json!({
+            "type": "context",
+            "elements": [{
+                "type": "mrkdwn",
+                "text": format!(
+                    "Incidents declared for *{}* during the launch window will be linked to this pre-mortem.",
+                    premortem.service
+                )
+            }]
+        }),
+    ]
+}
+
+/// Posted in an incident channel when the incident was declared during the
+/// launch window of a pre-mortem for its service.
+pub fn premortem_linked_blocks(premortem: &Premortem, risks: &[PremortemRisk]) -> Vec<Value> {
+    vec![
+        mrkdwn_section(&format!(
+            "🧭 This incident was declared during the launch window of *{}* ({}). Its pre-mortem by <@{}> anticipated:",
+            premortem.launch_name,
+            premortem_window(premortem),
+            premortem.created_by
+        )),
+        mrkdwn_section(&premortem_risk_lines(risks)),
+    ]
+}
+
+/// `/incident premortem` without a service: pre-mortems whose launch window
+/// hasn't ended, with how many incidents are linked to each.
+pub fn premortem_list_blocks(premortems: &[(Premortem, usize)]) -> Vec<Value> {
+    let mut blocks = vec![json!({
+        "type": "header",
+        "text": { "type": "plain_text", "text": "🧭 Upcoming Launches" }
+    })];
+    if premortems.is_empty() {
+        blocks.push(mrkdwn_section(
+            "_No pre-mortems with an upcoming or active launch window._",
+        ));
+    }
+    blocks.extend(premortems.iter().map(|(premortem, linked)| {
+        let mut text = format!(
+            "*{}* · {} · {}",
+            premortem.launch_name,
+            premortem.service,
+            premortem_window(premortem)
+        );
+        if *linked > 0 {
+            text.push_str(&format!(
+                " · {} linked incident{}",
+                linked,
+                if *linked == 1 { "" } else { "s" }
+            ));
         }
+        mrkdwn_section(&text)
+    }));
+    blocks.push(json!({
+        "type": "context",
+        "elements": [{
+            "type": "mrkdwn",
+            "text": "Start one before a risky launch with `/incident premortem <service>`."
+        }]
     }));
+    blocks
+}
 
+/// Action ID for "Resume draft" on the declare draft offer.
+pub const DECLARE_RESUME_DRAFT_ACTION: &str = "declare_resume_draft";
+
+/// Action ID for "Start fresh" on the declare draft offer; discards the draft.
+pub const DECLARE_DISCARD_DRAFT_ACTION: &str = "declare_discard_draft";
+
+/// Offer to reopen the declare modal with an unsubmitted draft.
+pub fn declare_draft_blocks(draft: &DeclareDraft, age_minutes: i64) -> Vec<Value> {
+    let mut details: Vec<&str> = Vec::new();
+    if let Some(severity) = &draft.severity {
+        details.push(severity);
+    }
+    if let Some(service) = &draft.service {
+        details.push(service);
+    }
+    let details = if details.is_empty() {
+        String::new()
+    } else {
+        format!(" ({})", details.join(", "))
+    };
+
+    vec![
+        json!({
+            "type": "section",
+            "text": {
+                "type": "mrkdwn",
+                "text": format!(
+                    "📝 You have an unsubmitted incident from {} min ago: *{}*{}",
+                    age_minutes,
+                    draft.title.as_deref().unwrap_or("Untitled"),
+                    details
+                )
+            }
+        }),
+        json!({
+            "type": "actions",
+            "elements": [
+                {
+                    "type": "button",
+                    "text": { "type": "plain_text", "text": "Resume draft" },
+                    "style": "primary",
+                    "action_id": DECLARE_RESUME_DRAFT_ACTION
+                },
+                {
+                    "type": "button",
+                    "text": { "type": "plain_text", "text": "Start fresh" },
+                    "action_id": DECLARE_DISCARD_DRAFT_ACTION
+                }
+            ]
+        }),
+    ]
+}
+
+/// Action IDs for the buttons under a triage; the value is the incident ID.
+/// "Declare" opens the declare modal, "Dismiss" closes it as a false alarm.
+pub const TRIAGE_DECLARE_ACTION: &str = "triage_declare";
+pub const TRIAGE_DISMISS_ACTION: &str = "triage_dismiss";
+
+/// Posted where `/incident triage` was run: the suspected issue, and buttons
+/// to declare or dismiss it.
+pub fn triage_blocks(incident: &Incident) -> Vec<Value> {
+    vec![
+        mrkdwn_section(&format!(
+            "{} *{}* · Triage: *{}*\nStarted by <@{}> at {}. No channel or notifications until it is declared.",
+            IncidentStatus::Triaging.emoji(),
+            incident.reference(),
+            incident.title,
+            incident.commander_id,
+            time::clock_utc(&incident.declared_at)
+        )),
+        json!({
+            "type": "actions",
+            "elements": [
+                {
+                    "type": "button",
+                    "text": { "type": "plain_text", "text": "Declare incident" },
+                    "style": "primary",
+                    "action_id": TRIAGE_DECLARE_ACTION,
+                    "value": incident.id.to_string()
+                },
+                {
+                    "type": "button",
+                    "text": { "type": "plain_text", "text": "Dismiss" },
+                    "action_id": TRIAGE_DISMISS_ACTION,
+                    "value": incident.id.to_string(),
+                    "confirm": {
+                        "title": { "type": "plain_text", "text": "Dismiss triage?" },
+                        "text": { "type": "mrkdwn", "text": "It will be closed as not an incident." },
+                        "confirm": { "type": "plain_text", "text": "Dismiss" },
+                        "deny": { "type": "plain_text", "text": "Keep" }
+                    }
+                }
+            ]
+        }),
+    ]
+}
+
+/// `/incident triage list`: open triages, each with its buttons.
+pub fn triage_list_blocks(triages: &[Incident]) -> Vec<Value> {
+    if triages.is_empty() {
+        return vec![mrkdwn_section(
+            "No open triages. Start one with `/incident triage <title>`.",
+        )];
+    }
+    let mut blocks = vec![mrkdwn_section(&format!(
+        "*Open triages* ({})",
+        triages.len()
+    ))];
+    for triage in triages {
+        blocks.extend(triage_blocks(triage));
+    }
     blocks
 }
 
+/// Leading block of a P1 channel notification that mentions the user groups
+/// routed to it (`P1_USER_GROUPS` or a rule's `user_groups`).
+pub fn user_group_mention_block(group_ids: &[String]) -> Value {
+    let mentions = group_ids
+        .iter()
+        .map(|group_id| format!("<!subteam^{}>", group_id))
+        .collect::<Vec<_>>();
+    json!({
+        "type": "section",
+        "text": {
+            "type": "mrkdwn",
+            "text": format!("📣 {}", mentions.join(" "))
+        }
+    })
+}
+
+/// Thread replies held back while Slack was rate limiting the bot, sent
+/// together: a note, then each update in order between dividers, split
+/// over as many messages as the block limit needs.
+pub fn batched_update_messages(updates: &[Vec<Value>]) -> Vec<Vec<Value>> {
+    let mut messages = vec![vec![context_block(&format!(
+        "⏳ {} updates held back while Slack was rate limiting notifications",
+        updates.len()
+    ))]];
+    for update in updates {
+        let update: Vec<Value> = update
+            .iter()
+            .take(MESSAGE_MAX_BLOCKS - 1)
+            .cloned()
+            .collect();
+        if messages
+            .last()
+            .is_some_and(|m| m.len() + 1 + update.len() > MESSAGE_MAX_BLOCKS)
+        {
+            messages.push(Vec::new());
+        }
+        if let Some(message) = messages.last_mut() {
+            if !message.is_empty() {
+                message.push(json!({ "type": "divider" }));
+            }
+            message.extend(update);
+        }
+    }
+    messages
+}
+
 pub fn error_blocks(message: &str) -> Vec<Value> {
     vec![json!({
         "type": "section",
@@ -200,3 +3044,656 @@ pub fn permission_denied_blocks(action: &str) -> Vec<Value> {
         }
     })]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::models::IncidentStatus;
+    use crate::services::timeline::number_events;
+    use chrono::{TimeZone, Utc};
+    use uuid::Uuid;
+
+    fn incident() -> Incident {
+        let now = Utc.with_ymd_and_hms(2024, 11, 15, 10, 0, 0).unwrap();
+        Incident {
+            id: Uuid::new_v4(),
+            number_prefix: "INC".to_string(),
+            number: 7,
+            slack_channel_id: Some("C1".to_string()),
+            title: "VPN down".to_string(),
+            severity: Severity::P2,
+            severity_code: None,
+            status: IncidentStatus::Investigating,
+            affected_service: "vpn".to_string(),
+            commander_id: "U1".to_string(),
+            declared_at: now,
+            resolved_at: None,
+            duration_minutes: None,
+            pinned_message_ts: None,
+            is_quiet: false,
+            statuspage_incident_id: None,
+            canceled_at: None,
+            cancel_reason: None,
+            triaged_at: None,
+            is_triage: false,
+            channel_archived_at: None,
+            bridge_url: None,
+            custom_fields: Default::default(),
+            template_id: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn workstream(name: &str, last_update: Option<&str>) -> Workstream {
+        let now = Utc.with_ymd_and_hms(2024, 11, 15, 10, 32, 0).unwrap();
+        Workstream {
+            id: Uuid::new_v4(),
+            incident_id: Uuid::new_v4(),
+            name: name.to_string(),
+            lead_id: "U2".to_string(),
+            thread_ts: None,
+            last_update: last_update.map(ToString::to_string),
+            last_update_at: last_update.map(|_| now),
+            created_by: "U1".to_string(),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn test_role_claim_blocks_add_button_per_unfilled_role() {
+        let incident = incident();
+        let filled = vec![IncidentRole {
+            incident_id: incident.id,
+            role: "scribe".to_string(),
+            user_id: "U3".to_string(),
+            claimed_at: Utc::now(),
+        }];
+
+        let blocks = role_claim_blocks(&incident, &filled, &["comms_lead".to_string()]);
+        let text = blocks[0]["text"]["text"].as_str().unwrap();
+        assert!(text.contains("• Commander: <@U1>"));
+        assert!(text.contains("• Scribe: <@U3>"));
+        assert!(text.contains("• Comms Lead: _unfilled_"));
+
+        let buttons = blocks[1]["elements"].as_array().unwrap();
+        assert_eq!(buttons.len(), 1);
+        assert_eq!(buttons[0]["action_id"], "claim_role_comms_lead");
+        assert_eq!(buttons[0]["value"], format!("{}:comms_lead", incident.id));
+
+        // Fully staffed: roster only, no actions block
+        let complete = role_claim_blocks(&incident, &filled, &[]);
+        assert_eq!(complete.len(), 1);
+    }
+
+    #[test]
+    fn test_commander_absent_blocks_offer_take_command_to_backups() {
+        let incident = incident();
+        let blocks = commander_absent_blocks(&incident, 25, &["U2".to_string()]);
+        assert_eq!(blocks.len(), 2);
+        assert!(blocks[0]["text"]["text"]
+            .as_str()
+            .unwrap()
+            .contains("Backup commanders: <@U2>"));
+        assert_eq!(blocks[1]["elements"][0]["action_id"], TAKE_COMMAND_ACTION);
+        assert_eq!(blocks[1]["elements"][0]["value"], incident.id.to_string());
+
+        // Without backups there is nobody to hand the button to
+        let blocks = commander_absent_blocks(&incident, 25, &[]);
+        assert_eq!(blocks.len(), 1);
+        assert!(blocks[0]["text"]["text"]
+            .as_str()
+            .unwrap()
+            .contains("No backup commander is configured for *vpn*"));
+    }
+
+    #[test]
+    fn test_declare_draft_blocks_summarize_draft() {
+        let draft = DeclareDraft {
+            title: Some("Checkout errors".to_string()),
+            severity: Some("P1".to_string()),
+            service: Some("payments".to_string()),
+            ..Default::default()
+        };
+        let blocks = declare_draft_blocks(&draft, 7);
+        assert_eq!(
+            blocks[0]["text"]["text"],
+            "📝 You have an unsubmitted incident from 7 min ago: *Checkout errors* (P1, payments)"
+        );
+        assert_eq!(
+            blocks[1]["elements"][0]["action_id"],
+            DECLARE_RESUME_DRAFT_ACTION
+        );
+        assert_eq!(
+            blocks[1]["elements"][1]["action_id"],
+            DECLARE_DISCARD_DRAFT_ACTION
+        );
+
+        let blocks = declare_draft_blocks(&DeclareDraft::default(), 0);
+        assert!(blocks[0]["text"]["text"]
+            .as_str()
+            .unwrap()
+            .ends_with("*Untitled*"));
+    }
+
+    #[test]
+    fn test_weekly_digest_blocks_include_burndown_when_uploaded() {
+        let week_start = NaiveDate::from_ymd_opt(2024, 11, 18).unwrap();
+        let stats = ServiceStats {
+            declared: 4,
+            resolved: 3,
+            avg_resolution_minutes: Some(42.4),
+            ..Default::default()
+        };
+
+        let blocks = weekly_digest_blocks(week_start, &stats, 2, &[], Some("F123"));
+        assert_eq!(
+            blocks[0]["text"]["text"],
+            "🗓️ Weekly incident digest — week of Nov 18"
+        );
+        assert_eq!(blocks[1]["fields"][3]["text"], "*MTTR last week:*\n42 min");
+        assert_eq!(blocks[2]["slack_file"]["id"], "F123");
+
+        assert_eq!(
+            weekly_digest_blocks(week_start, &stats, 2, &[], None).len(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_weekly_digest_blocks_list_overdue_postmortems() {
+        let week_start = NaiveDate::from_ymd_opt(2024, 11, 18).unwrap();
+        let overdue = PendingPostmortem {
+            incident: incident(),
+            due_at: Utc.with_ymd_and_hms(2024, 11, 15, 12, 0, 0).unwrap(),
+        };
+
+        let blocks =
+            weekly_digest_blocks(week_start, &ServiceStats::default(), 0, &[overdue], None);
+        assert_eq!(blocks.len(), 3);
+        let text = blocks[2]["text"]["text"].as_str().unwrap();
+        assert!(text.starts_with("*Overdue postmortems (1):*"));
+        assert!(text.contains("due Nov 15"));
+    }
+
+    #[test]
+    fn test_weekly_report_blocks() {
+        use crate::db::queries::metrics::{Contributor, MetricsGrouping};
+
+        let row = |grouping, key: &str, declared| MetricsRow {
+            grouping,
+            key: key.to_string(),
+            declared,
+            resolved: declared,
+            mean_minutes: Some(95.0),
+            median_minutes: None,
+            mean_ack_minutes: None,
+            longest: None,
+        };
+        let mut report = WeeklyReport {
+            week_start: NaiveDate::from_ymd_opt(2024, 11, 18).unwrap(),
+            overall: Some(row(MetricsGrouping::Overall, "all", 8)),
+            by_severity: vec![
+                row(MetricsGrouping::Severity, "P1", 1),
+                row(MetricsGrouping::Severity, "P2", 7),
+            ],
+            by_service: (0..7)
+                .map(|i| row(MetricsGrouping::Service, &format!("svc-{}", i), 1))
+                .collect(),
+            downtime_minutes: 605,
+            top_contributors: vec![Contributor {
+                user_id: "U123".to_string(),
+                incidents: 1,
+                timeline_events: 12,
+            }],
+        };
+
+        let blocks = weekly_report_blocks(&report);
+        assert_eq!(blocks[1]["elements"][0]["text"], "Nov 11 – Nov 17 (UTC)");
+        assert_eq!(
+            blocks[2]["fields"][2]["text"],
+            "*Total downtime:*\n10h 5min"
+        );
+        assert_eq!(blocks[2]["fields"][3]["text"], "*MTTR:*\n1h 35min");
+        assert_eq!(
+            blocks[3]["text"]["text"],
+            "*By severity*\n🔴 P1: 1 · 🟡 P2: 7"
+        );
+        let services = blocks[4]["text"]["text"].as_str().unwrap();
+        assert!(services.contains("• *svc-4*: 1"));
+        assert!(services.ends_with("_…and 2 more_"));
+        assert_eq!(
+            blocks[5]["text"]["text"],
+            "*Top contributors*\n• <@U123> — 1 incident, 12 timeline updates"
+        );
+
+        report.overall = Some(row(MetricsGrouping::Overall, "all", 0));
+        let quiet_week = weekly_report_blocks(&report);
+        assert_eq!(quiet_week.len(), 3);
+        assert_eq!(
+            quiet_week[2]["text"]["text"],
+            "🎉 No incidents were declared last week."
+        );
+    }
+
+    #[test]
+    fn test_summary_without_workstreams_matches_declared_blocks() {
+        let mut incident = incident();
+        incident.status = IncidentStatus::Declared;
+        assert_eq!(
+            incident_summary_blocks(&incident, &[], &FieldVisibility::default()),
+            incident_declared_blocks(&incident, &FieldVisibility::default())
+        );
+    }
+
+    #[test]
+    fn test_summary_header_follows_status() {
+        let mut incident = incident();
+        let blocks = incident_summary_blocks(&incident, &[], &FieldVisibility::default());
+        assert_eq!(
+            blocks[0]["text"]["text"],
+            "🟡 INC-7 · P2 (High) - Investigating"
+        );
+
+        incident.status = IncidentStatus::Resolved;
+        let blocks = incident_summary_blocks(&incident, &[], &FieldVisibility::default());
+        assert_eq!(blocks[0]["text"]["text"], "🟡 INC-7 · P2 (High) - Resolved");
+    }
+
+    #[test]
+    fn test_declared_blocks_offer_incident_buttons() {
+        let incident = incident();
+        let blocks = incident_declared_blocks(&incident, &FieldVisibility::default());
+        let buttons = blocks[2]["elements"].as_array().unwrap();
+        let actions: Vec<&str> = buttons
+            .iter()
+            .map(|b| b["action_id"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            actions,
+            [
+                INCIDENT_ACK_ACTION,
+                INCIDENT_UPDATE_STATUS_ACTION,
+                INCIDENT_RESOLVE_ACTION
+            ]
+        );
+        assert!(buttons
+            .iter()
+            .all(|b| b["value"] == incident.id.to_string()));
+        // Resolving from a pinned message is easy to misclick
+        assert!(buttons[2]["confirm"].is_object());
+        assert_eq!(blocks.last().unwrap()["type"], "context");
+    }
+
+    #[test]
+    fn test_triage_blocks_offer_declare_and_dismiss() {
+        let mut incident = incident();
+        incident.number_prefix = "TRI".to_string();
+        incident.status = IncidentStatus::Triaging;
+        let blocks = triage_blocks(&incident);
+        let text = blocks[0]["text"]["text"].as_str().unwrap();
+        assert!(text.starts_with("🔍 *TRI-7* · Triage: *VPN down*"));
+        let buttons = blocks[1]["elements"].as_array().unwrap();
+        assert_eq!(buttons[0]["action_id"], TRIAGE_DECLARE_ACTION);
+        assert_eq!(buttons[1]["action_id"], TRIAGE_DISMISS_ACTION);
+        assert!(buttons
+            .iter()
+            .all(|b| b["value"] == incident.id.to_string()));
+        assert!(buttons[1]["confirm"].is_object());
+
+        assert_eq!(triage_list_blocks(&[]).len(), 1);
+        assert_eq!(triage_list_blocks(&[incident]).len(), 3);
+    }
+
+    #[test]
+    fn test_summary_lists_workstreams_before_pii_warning() {
+        let incident = incident();
+        let blocks = incident_summary_blocks(
+            &incident,
+            &[
+                workstream("database", Some("Failover complete")),
+                workstream("comms", None),
+            ],
+            &FieldVisibility::default(),
+        );
+
+        let section = blocks[blocks.len() - 2]["text"]["text"].as_str().unwrap();
+        assert!(section.contains("*database* (lead <@U2>): _10:32_ — Failover complete"));
+        assert!(section.contains("*comms* (lead <@U2>): _No updates yet_"));
+        assert_eq!(blocks.last().unwrap()["type"], "context");
+    }
+
+    #[test]
+    fn test_scorecard_blocks_mark_targets() {
+        let scorecard = Scorecard {
+            team: "network".to_string(),
+            period_start: chrono::NaiveDate::from_ymd_opt(2026, 2, 1).unwrap(),
+            period_end: chrono::NaiveDate::from_ymd_opt(2026, 3, 1).unwrap(),
+            declared: 4,
+            resolved: 3,
+            mttr_minutes: Some(75.0),
+            postmortem_completion: Some(2.0 / 3.0),
+            action_item_closure: None,
+            targets: crate::config::KpiTargets {
+                mttr_minutes: Some(60.0),
+                postmortem_completion: Some(0.5),
+                action_item_closure: Some(0.8),
+            },
+        };
+
+        let rendered = serde_json::to_string(&scorecard_blocks(&scorecard)).unwrap();
+        assert!(rendered.contains("network incident scorecard — February 2026"));
+        assert!(rendered.contains("75 min (target ≤ 60) ❌"));
+        assert!(rendered.contains("67% (target ≥ 50%) ✅"));
+        assert!(rendered.contains("*Action items closed:*\\nn/a"));
+    }
+
+    #[test]
+    fn test_coaching_report_blocks_suggest_only_where_trailing() {
+        let report = CoachingReport {
+            commander_id: "U024ALICE".to_string(),
+            period_start: NaiveDate::from_ymd_opt(2026, 7, 1).unwrap(),
+            period_end: NaiveDate::from_ymd_opt(2026, 10, 1).unwrap(),
+            mine: crate::db::queries::coaching::CommanderStats {
+                commander_id: "U024ALICE".to_string(),
+                incidents: 3,
+                mean_first_update_minutes: Some(25.0),
+                mean_update_interval_minutes: Some(20.0),
+                resolved: 2,
+                resolved_with_postmortem: 2,
+            },
+            median_first_update_minutes: Some(10.0),
+            median_update_interval_minutes: Some(30.0),
+            median_postmortem_completion: Some(0.5),
+        };
+
+        let rendered = serde_json::to_string(&coaching_report_blocks(&report)).unwrap();
+        assert!(rendered.contains("Your incident command — Q3 2026"));
+        assert!(rendered.contains("25 min (median 10 min)"));
+        assert!(rendered.contains("2 of 2 resolved, 100% (median 50%)"));
+        assert!(rendered.contains("A short first update"));
+        assert!(!rendered.contains("Regular updates"));
+        assert!(!rendered.contains("Postmortems are where"));
+    }
+
+    #[test]
+    fn test_metrics_report_blocks() {
+        use crate::db::queries::metrics::MetricsGrouping;
+        use crate::services::permissions::AnalyticsScope;
+
+        let row = |grouping, key: &str, declared| MetricsRow {
+            grouping,
+            key: key.to_string(),
+            declared,
+            resolved: declared,
+            mean_minutes: Some(95.0),
+            median_minutes: Some(40.4),
+            mean_ack_minutes: None,
+            longest: Some(("DNS outage".to_string(), 250)),
+        };
+        let report = MetricsReport {
+            window_days: 90,
+            since: Utc.with_ymd_and_hms(2024, 8, 1, 0, 0, 0).unwrap(),
+            scope: AnalyticsScope::OrgWide,
+            overall: Some(row(MetricsGrouping::Overall, "all", 14)),
+            by_severity: vec![row(MetricsGrouping::Severity, "P1", 2)],
+            by_service: (0..12)
+                .map(|i| row(MetricsGrouping::Service, &format!("svc-{:02}", i), 1))
+                .collect(),
+            by_month: vec![row(MetricsGrouping::Month, "2024-10", 14)],
+        };
+
+        let blocks = metrics_report_blocks(&report);
+        assert_eq!(
+            blocks[0]["text"]["text"],
+            "📈 Incident metrics — last 90 days"
+        );
+        let rendered = Value::Array(blocks).to_string();
+        assert!(rendered.contains("*MTTR:*\\n1h 35min"));
+        assert!(rendered.contains("*MTTA:*\\nn/a"));
+        assert!(rendered.contains(
+            "🔴 *P1* — 2 declared · MTTR 1h 35min · median 40min · MTTA n/a · longest: DNS outage (4h 10min)"
+        ));
+        assert!(rendered.contains("*svc-09*"));
+        assert!(!rendered.contains("*svc-10*"));
+        assert!(rendered.contains("_…and 2 more_"));
+        assert!(rendered.contains("*2024-10* — 14 declared"));
+
+        let empty = MetricsReport {
+            overall: Some(MetricsRow {
+                declared: 0,
+                resolved: 0,
+                longest: None,
+                ..row(MetricsGrouping::Overall, "all", 0)
+            }),
+            by_severity: vec![],
+            by_service: vec![],
+            by_month: vec![],
+            ..report
+        };
+        let blocks = metrics_report_blocks(&empty);
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(
+            blocks[1]["text"]["text"],
+            "No incidents declared since 2024-08-01"
+        );
+
+        let scoped = MetricsReport {
+            scope: AnalyticsScope::Services(vec!["VPN".to_string(), "Wi-Fi".to_string()]),
+            ..empty
+        };
+        let blocks = metrics_report_blocks(&scoped);
+        assert_eq!(
+            blocks[1]["elements"][0]["text"],
+            "Limited to your teams' services: VPN, Wi-Fi"
+        );
+    }
+
+    #[test]
+    fn test_timeline_blocks_quote_notes() {
+        let logged = |event_type, message: &str, posted_by: &str| TimelineEvent {
+            id: uuid::Uuid::new_v4(),
+            incident_id: uuid::Uuid::new_v4(),
+            event_type,
+            message: message.to_string(),
+            posted_by: posted_by.to_string(),
+            timestamp: DateTime::parse_from_rfc3339("2024-11-15T14:10:00Z")
+                .unwrap()
+                .with_timezone(&Utc),
+            audience: crate::db::models::Audience::Internal,
+            updated_at: None,
+            deleted_at: None,
+            drafted_by: None,
+        };
+        let mut edited = logged(TimelineEventType::StatusUpdate, "Fixed tpyo", "U024CMD");
+        edited.updated_at = Some(edited.timestamp);
+        let mut deleted = logged(TimelineEventType::Note, "Wrong channel", "U024BOB");
+        deleted.deleted_at = Some(deleted.timestamp);
+        let mut drafted = logged(TimelineEventType::StatusUpdate, "Fix deployed", "U024CMD");
+        drafted.drafted_by = Some("U024SCRIBE".to_string());
+        let events = vec![
+            logged(TimelineEventType::StatusUpdate, "Rolling back", "U024CMD"),
+            logged(
+                TimelineEventType::Note,
+                "Pool size is 1\nin 4.2.1",
+                "U024BOB",
+            ),
+            logged(TimelineEventType::Note, "🔔 Datadog: 5xx firing", "datadog"),
+            edited,
+            deleted,
+            drafted,
+        ];
+        let blocks = timeline_blocks(
+            uuid::Uuid::new_v4(),
+            &number_events(events, 1),
+            &TimelineFilter::default(),
+        );
+        let text = blocks.last().unwrap()["text"]["text"].as_str().unwrap();
+
+        assert!(text.contains("`#1` 📝 *14:10* — Rolling back\n_by <@U024CMD>_"));
+        assert!(
+            text.contains("`#2` 🗒️ *14:10* — _Note from <@U024BOB>_\n>Pool size is 1\n>in 4.2.1")
+        );
+        assert!(text.contains("_Note from datadog_\n>🔔 Datadog: 5xx firing"));
+        assert!(text.contains("`#4` 📝 *14:10* — Fixed tpyo\n_by <@U024CMD> (edited)_"));
+        assert!(text.contains("`#5` 🗑️ *14:10* — _Deleted_"));
+        assert!(!text.contains("Wrong channel"));
+        assert!(text
+            .contains("`#6` 📝 *14:10* — Fix deployed\n_by <@U024CMD>, drafted by <@U024SCRIBE>_"));
+    }
+
+    #[test]
+    fn test_long_timelines_are_split_across_sections_and_messages() {
+        let incident_id = uuid::Uuid::new_v4();
+        let events: Vec<(usize, TimelineEvent)> = (0..400)
+            .map(|i| TimelineEvent {
+                id: uuid::Uuid::new_v4(),
+                incident_id,
+                event_type: TimelineEventType::StatusUpdate,
+                message: format!(
+                    "Update {:03}: {}",
+                    i,
+                    "x".repeat(if i == 7 { 4000 } else { 400 })
+                ),
+                posted_by: "U024CMD".to_string(),
+                timestamp: Utc.with_ymd_and_hms(2024, 11, 15, 14, 10, 0).unwrap(),
+                audience: crate::db::models::Audience::Internal,
+                updated_at: None,
+                deleted_at: None,
+                drafted_by: None,
+            })
+            .enumerate()
+            .map(|(i, event)| (i + 1, event))
+            .collect();
+        let section_texts = |blocks: &[Value]| -> Vec<String> {
+            blocks
+                .iter()
+                .filter(|b| b["type"] == "section")
+                .map(|b| b["text"]["text"].as_str().unwrap().to_string())
+                .collect()
+        };
+
+        let messages = timeline_messages(incident_id, &events, &TimelineFilter::default(), 400);
+        assert!(messages.len() > 1);
+        assert!(messages.iter().all(|m| m.len() <= MESSAGE_MAX_BLOCKS));
+        assert_eq!(messages[0][0]["type"], "header");
+        let texts: Vec<String> = messages.iter().flat_map(|m| section_texts(m)).collect();
+        assert!(texts.iter().all(|t| t.chars().count() <= SECTION_MAX_CHARS));
+        // Every event is posted once, in order; the oversized one is cut short
+        let all = texts.join("\n\n");
+        assert_eq!(all.matches("📝").count(), 400);
+        assert!(all.find("Update 000").unwrap() < all.find("Update 399").unwrap());
+        assert!(all.contains("…"));
+
+        let shown = timeline_messages(incident_id, &events[390..], &TimelineFilter::default(), 400);
+        assert_eq!(shown.len(), 1);
+        assert_eq!(
+            shown[0][2]["elements"][0]["text"],
+            "Showing the last 10 of 400 events"
+        );
+
+        // Updated in place, so only the newest events that fit one message
+        let blocks = timeline_blocks(incident_id, &events, &TimelineFilter::default());
+        assert_eq!(blocks.len(), MESSAGE_MAX_BLOCKS);
+        let note = blocks[2]["elements"][0]["text"].as_str().unwrap();
+        assert!(note.ends_with(
+            "earlier events not shown. Use `/incident timeline` to post the whole timeline."
+        ));
+        let texts = section_texts(&blocks).join("\n\n");
+        assert!(texts.contains("Update 399"));
+        assert!(!texts.contains("Update 000"));
+    }
+
+    #[test]
+    fn test_latency_text() {
+        assert_eq!(latency_text(Duration::seconds(45)), "45s");
+        assert_eq!(latency_text(Duration::seconds(192)), "3m 12s");
+        assert_eq!(latency_text(Duration::minutes(125)), "2h 5m");
+        assert_eq!(latency_text(Duration::seconds(-5)), "0s");
+    }
+
+    #[test]
+    fn test_batched_update_messages_split_at_block_limit() {
+        let update = |n: usize| vec![mrkdwn_section(&format!("Update {}", n)); 3];
+        let updates: Vec<Vec<Value>> = (0..20).map(update).collect();
+
+        let messages = batched_update_messages(&updates);
+        assert_eq!(messages.len(), 2);
+        assert!(messages.iter().all(|m| m.len() <= MESSAGE_MAX_BLOCKS));
+        assert_eq!(
+            messages[0][0]["elements"][0]["text"],
+            "⏳ 20 updates held back while Slack was rate limiting notifications"
+        );
+        // Updates are kept whole and in order, with a divider between them
+        assert_eq!(messages[0][1]["type"], "divider");
+        assert_eq!(messages[1][0]["text"]["text"], "Update 12");
+        let sections = messages
+            .iter()
+            .flatten()
+            .filter(|b| b["type"] == "section")
+            .count();
+        assert_eq!(sections, 60);
+    }
+
+    #[test]
+    fn test_template_list_blocks_compare_mttr_with_untemplated() {
+        let now = Utc.with_ymd_and_hms(2024, 11, 15, 10, 0, 0).unwrap();
+        let template = |name: &str| IncidentTemplate {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            title: "VPN down".to_string(),
+            severity: Severity::P2,
+            affected_service: None,
+            description: None,
+            is_active: true,
+            source_incident_id: None,
+            created_at: now,
+            updated_at: now,
+        };
+        let (used, unused) = (template("vpn-down"), template("cdn-issues"));
+        let usage = vec![
+            TemplateUsage {
+                template_id: Some(used.id),
+                incidents: 3,
+                resolved: 2,
+                mean_minutes: Some(64.6),
+            },
+            TemplateUsage {
+                template_id: None,
+                incidents: 1,
+                resolved: 0,
+                mean_minutes: None,
+            },
+        ];
+
+        let blocks = template_list_blocks(&[used, unused], &usage);
+        assert!(blocks[1]["text"]["text"]
+            .as_str()
+            .unwrap()
+            .ends_with("\n_3 incidents · MTTR 1h 5min_"));
+        assert!(!blocks[2]["text"]["text"]
+            .as_str()
+            .unwrap()
+            .contains("incident"));
+        assert_eq!(
+            blocks[3]["elements"][0]["text"],
+            "Without a template: 1 incident"
+        );
+    }
+
+    #[test]
+    fn test_template_prompt_blocks() {
+        let incident = incident();
+        let blocks = template_prompt_blocks(&incident);
+        assert!(blocks[0]["text"]["text"]
+            .as_str()
+            .unwrap()
+            .contains("Would a template have helped? Create one from this incident"));
+        assert_eq!(
+            blocks[1]["elements"][0]["action_id"],
+            TEMPLATE_FROM_INCIDENT_ACTION
+        );
+        assert_eq!(blocks[1]["elements"][0]["value"], incident.id.to_string());
+    }
+}
@@ -1,14 +1,211 @@
 use crate::error::{IncidentError, IncidentResult};
+use crate::slack::throttle::{is_rate_limited, SlackThrottle};
+use async_trait::async_trait;
+use reqwest::header::{HeaderMap, RETRY_AFTER};
 use reqwest::Client;
-use serde::Deserialize;
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
+use std::sync::Arc;
 use std::time::Duration;
-use tracing::{debug, error};
+use tracing::{debug, error, warn};
+
+pub const SLACK_API_BASE_URL: &str = "https://slack.com/api";
+
+/// Slack error codes that indicate a transient condition worth retrying.
+/// Everything else (`channel_not_found`, `not_in_channel`, `invalid_auth`, ...)
+/// is fatal and returned immediately.
+const RETRYABLE_ERROR_CODES: &[&str] = &[
+    "ratelimited",
+    "rate_limited",
+    "internal_error",
+    "fatal_error",
+    "service_unavailable",
+    "request_timeout",
+];
+
+/// Methods that only accept form-encoded arguments, not a JSON body.
+const FORM_ENCODED_METHODS: &[&str] = &["files.getUploadURLExternal", "users.info"];
+
+/// `conversations.history` page size; Slack recommends no more than 200.
+const HISTORY_PAGE_SIZE: usize = 200;
+
+/// Pause between `conversations.history` pages. The method is Tier 3
+/// (~50 requests/minute), so long channels are paged just under that rate
+/// instead of tripping 429s.
+const HISTORY_PAGE_DELAY: Duration = Duration::from_millis(1200);
+
+/// Upper bound on a server-provided `Retry-After` so one response can't stall
+/// a notification fan-out indefinitely.
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+/// Slack Web API surface used by the bot.
+///
+/// Services and command handlers depend on this trait rather than the concrete
+/// HTTP client so they can be exercised against `MockSlackClient` in tests.
+#[async_trait]
+pub trait SlackApi: Send + Sync {
+    async fn create_conversation(&self, name: &str, is_private: bool) -> IncidentResult<String>;
+
+    async fn list_conversations(&self) -> IncidentResult<Vec<Channel>>;
+
+    /// User IDs in a user group (`usergroups.users.list`).
+    async fn usergroup_members(&self, usergroup_id: &str) -> IncidentResult<Vec<String>>;
+
+    /// Look up a user (`users.info`), including whether they were deactivated.
+    async fn user_info(&self, user_id: &str) -> IncidentResult<SlackUser>;
+
+    /// User ID for an email address (`users.lookupByEmail`), or `None` when
+    /// no one in the workspace has it.
+    async fn lookup_user_by_email(&self, email: &str) -> IncidentResult<Option<String>>;
+
+    async fn invite_users(&self, channel_id: &str, user_ids: Vec<String>) -> IncidentResult<()>;
+
+    async fn archive_channel(&self, channel_id: &str) -> IncidentResult<()>;
+
+    /// Replace the channel topic (`conversations.setTopic`, max 250 chars).
+    async fn set_channel_topic(&self, channel_id: &str, topic: &str) -> IncidentResult<()>;
+
+    /// Rename a channel the bot created (`conversations.rename`).
+    async fn rename_channel(&self, channel_id: &str, name: &str) -> IncidentResult<()>;
+
+    /// Join a public channel (`conversations.join`). Private channels
+    /// require the bot to be invited instead.
+    async fn join_conversation(&self, channel_id: &str) -> IncidentResult<()>;
+
+    /// Messages in `channel_id` within `range`, oldest first, following
+    /// cursors across pages. Thread replies are not included.
+    async fn fetch_channel_history(
+        &self,
+        channel_id: &str,
+        range: &HistoryRange,
+    ) -> IncidentResult<Vec<HistoryMessage>>;
+
+    async fn post_message(&self, channel_id: &str, blocks: Vec<Value>) -> IncidentResult<String>;
+
+    /// Reply in a thread; `broadcast` also shows the reply in the channel.
+    async fn post_thread_reply(
+        &self,
+        channel_id: &str,
+        thread_ts: &str,
+        blocks: Vec<Value>,
+        broadcast: bool,
+    ) -> IncidentResult<String>;
+
+    async fn update_message(
+        &self,
+        channel_id: &str,
+        timestamp: &str,
+        blocks: Vec<Value>,
+    ) -> IncidentResult<()>;
+
+    async fn pin_message(&self, channel_id: &str, timestamp: &str) -> IncidentResult<()>;
+
+    async fn send_dm(&self, user_id: &str, blocks: Vec<Value>) -> IncidentResult<()>;
+
+    async fn open_modal(&self, trigger_id: &str, view: Value) -> IncidentResult<()>;
+
+    /// Replace an open modal's contents (`views.update`).
+    async fn update_modal(&self, view_id: &str, view: Value) -> IncidentResult<()>;
+
+    async fn publish_view(&self, user_id: &str, view: Value) -> IncidentResult<()>;
+
+    /// Upload a file and return its ID. With a `channel_id` it's shared
+    /// there (e.g. a postmortem draft); without one it's only usable in image
+    /// blocks (`slack_file`).
+    async fn upload_file(
+        &self,
+        channel_id: Option<&str>,
+        filename: &str,
+        title: &str,
+        content: Vec<u8>,
+    ) -> IncidentResult<String>;
+
+    /// Upload a file and share it with `user_id` in a DM, e.g. an export.
+    async fn send_file_dm(
+        &self,
+        user_id: &str,
+        filename: &str,
+        title: &str,
+        content: Vec<u8>,
+    ) -> IncidentResult<()>;
+
+    async fn post_to_response_url(
+        &self,
+        response_url: &str,
+        blocks: Vec<Value>,
+    ) -> IncidentResult<()>;
+
+    /// Workspace-wide pacing, stretched whenever Slack answers with a 429.
+    fn throttle(&self) -> &SlackThrottle;
+}
+
+/// Retry behaviour for Slack Web API calls.
+///
+/// Rate-limited calls (HTTP 429) wait for the `Retry-After` header; other
+/// transient failures (5xx, timeouts, retryable Slack error codes) back off
+/// exponentially from `base_delay`, capped at `max_delay`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Delay before retry number `attempt` (0-based) when Slack gave no `Retry-After`.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt);
+        self.base_delay
+            .checked_mul(factor)
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay)
+    }
+
+    /// `backoff(attempt)` scaled to a random 50-100%, so clients that failed
+    /// together don't retry in lockstep.
+    pub fn jittered_backoff(&self, attempt: u32) -> Duration {
+        let fraction = (uuid::Uuid::new_v4().as_u128() % 1000) as u32;
+        let delay = self.backoff(attempt);
+        delay / 2 + delay / 2 * fraction / 1000
+    }
+}
 
 #[derive(Clone)]
 pub struct SlackClient {
     http_client: Client,
     bot_token: String,
+    base_url: String,
+    retry_policy: RetryPolicy,
+    history_page_delay: Duration,
+    throttle: Arc<SlackThrottle>,
+}
+
+/// A failed single attempt, with enough context to decide whether to retry.
+struct CallFailure {
+    error: IncidentError,
+    retryable: bool,
+    retry_after: Option<Duration>,
+}
+
+impl CallFailure {
+    fn fatal(error: IncidentError) -> Self {
+        Self {
+            error,
+            retryable: false,
+            retry_after: None,
+        }
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -19,12 +216,21 @@ struct SlackResponse<T> {
     data: Option<T>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct Channel {
     pub id: String,
     pub name: String,
 }
 
+/// A workspace member from `users.info`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct SlackUser {
+    pub id: String,
+    /// Set once the account is deactivated
+    #[serde(default)]
+    pub deleted: bool,
+}
+
 #[derive(Debug, Deserialize)]
 struct ChannelsListResponse {
     channels: Vec<Channel>,
@@ -36,6 +242,38 @@ struct ResponseMetadata {
     next_cursor: Option<String>,
 }
 
+/// A top-level channel message from `conversations.history`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryMessage {
+    pub ts: String,
+    /// Absent for bot and some system messages
+    pub user: Option<String>,
+    #[serde(default)]
+    pub text: String,
+    /// `channel_join`, `bot_message`, ... (absent for plain user messages)
+    pub subtype: Option<String>,
+    /// Set when the message starts a thread
+    pub thread_ts: Option<String>,
+}
+
+/// Bounds for `fetch_channel_history`. Timestamps are Slack `ts` strings
+/// and both ends are inclusive; `None` fields are unbounded.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HistoryRange {
+    pub oldest: Option<String>,
+    pub latest: Option<String>,
+    /// Stop after this many of the newest messages in the range
+    pub max_messages: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HistoryResponse {
+    messages: Vec<HistoryMessage>,
+    #[serde(default)]
+    has_more: bool,
+    response_metadata: Option<ResponseMetadata>,
+}
+
 impl SlackClient {
     pub fn new(bot_token: String) -> Self {
         // Set 30-second timeout to prevent hanging requests to Slack API
@@ -47,47 +285,213 @@ impl SlackClient {
         Self {
             http_client,
             bot_token,
+            base_url: SLACK_API_BASE_URL.to_string(),
+            retry_policy: RetryPolicy::default(),
+            history_page_delay: HISTORY_PAGE_DELAY,
+            throttle: Arc::new(SlackThrottle::default()),
         }
     }
 
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    pub fn with_history_page_delay(mut self, delay: Duration) -> Self {
+        self.history_page_delay = delay;
+        self
+    }
+
+    pub fn with_throttle(mut self, throttle: Arc<SlackThrottle>) -> Self {
+        self.throttle = throttle;
+        self
+    }
+
+    /// Call a Slack Web API method, retrying transient failures per `retry_policy`.
+    ///
+    /// Note: a timed-out `chat.postMessage` may have been delivered, so retries can
+    /// occasionally duplicate a message. For incident notifications a duplicate is
+    /// preferable to a silently dropped P1 alert.
     async fn call_api<T: for<'de> Deserialize<'de>>(
         &self,
         method: &str,
         payload: Value,
     ) -> IncidentResult<T> {
+        let mut attempt = 0;
+        loop {
+            let result = self.call_api_once(method, &payload).await;
+            if let Err(failure) = &result {
+                if is_rate_limited(&failure.error) {
+                    self.throttle
+                        .record_rate_limited(method, failure.retry_after);
+                }
+            }
+            match result {
+                Ok(data) => return Ok(data),
+                Err(failure) if failure.retryable && attempt < self.retry_policy.max_retries => {
+                    let delay = failure
+                        .retry_after
+                        .unwrap_or_else(|| self.retry_policy.backoff(attempt));
+                    warn!(
+                        "Slack API {} failed (attempt {}/{}), retrying in {:?}: {}",
+                        method,
+                        attempt + 1,
+                        self.retry_policy.max_retries + 1,
+                        delay,
+                        failure.error
+                    );
+                    crate::metrics::metrics().record_slack_retry(method);
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                Err(failure) => {
+                    error!("Slack API {} failed: {}", method, failure.error);
+                    return Err(failure.error);
+                }
+            }
+        }
+    }
+
+    async fn call_api_once<T: for<'de> Deserialize<'de>>(
+        &self,
+        method: &str,
+        payload: &Value,
+    ) -> Result<T, CallFailure> {
         debug!("Calling Slack API: {}", method);
 
-        let response = self
+        let request = self
             .http_client
-            .post(format!("https://slack.com/api/{}", method))
-            .header("Authorization", format!("Bearer {}", self.bot_token))
-            .header("Content-Type", "application/json; charset=utf-8")
-            .json(&payload)
-            .send()
-            .await?;
+            .post(format!("{}/{}", self.base_url, method))
+            .header("Authorization", format!("Bearer {}", self.bot_token));
+        let request = if FORM_ENCODED_METHODS.contains(&method) {
+            request.form(payload)
+        } else {
+            request
+                .header("Content-Type", "application/json; charset=utf-8")
+                .json(payload)
+        };
+        let response = request.send().await.map_err(|e| CallFailure {
+            retryable: e.is_timeout() || e.is_connect(),
+            retry_after: None,
+            error: e.into(),
+        })?;
 
-        let slack_response: SlackResponse<T> = response.json().await?;
+        let status = response.status();
+        if status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error() {
+            let slack_error_code = if status == StatusCode::TOO_MANY_REQUESTS {
+                "ratelimited".to_string()
+            } else {
+                status.as_u16().to_string()
+            };
+            return Err(CallFailure {
+                error: IncidentError::SlackAPIError {
+                    message: format!("API call failed: {} (HTTP {})", method, status.as_u16()),
+                    slack_error_code,
+                },
+                retryable: true,
+                retry_after: parse_retry_after(response.headers()),
+            });
+        }
+
+        let retry_after = parse_retry_after(response.headers());
+        let slack_response: SlackResponse<T> = response
+            .json()
+            .await
+            .map_err(|e| CallFailure::fatal(e.into()))?;
 
         if !slack_response.ok {
             let error_code = slack_response
                 .error
                 .unwrap_or_else(|| "unknown".to_string());
-            error!("Slack API error: {}", error_code);
-            return Err(IncidentError::SlackAPIError {
-                message: format!("API call failed: {}", method),
-                slack_error_code: error_code,
+            return Err(CallFailure {
+                retryable: is_retryable_error_code(&error_code),
+                retry_after,
+                error: IncidentError::SlackAPIError {
+                    message: format!("API call failed: {}", method),
+                    slack_error_code: error_code,
+                },
             });
         }
 
-        slack_response
-            .data
-            .ok_or_else(|| IncidentError::SlackAPIError {
+        slack_response.data.ok_or_else(|| {
+            CallFailure::fatal(IncidentError::SlackAPIError {
                 message: "No data in response".to_string(),
                 slack_error_code: "no_data".to_string(),
             })
+        })
     }
 
-    pub async fn create_conversation(&self, name: &str) -> IncidentResult<String> {
+    /// External upload (files.upload is retired): get an upload URL, POST the
+    /// bytes, then complete the upload, sharing it to `channel_id` if given.
+    async fn upload_external(
+        &self,
+        filename: &str,
+        title: &str,
+        content: Vec<u8>,
+        channel_id: Option<&str>,
+    ) -> IncidentResult<String> {
+        #[derive(Deserialize)]
+        struct UploadUrlResponse {
+            upload_url: String,
+            file_id: String,
+        }
+
+        let upload: UploadUrlResponse = self
+            .call_api(
+                "files.getUploadURLExternal",
+                json!({
+                    "filename": filename,
+                    "length": content.len(),
+                }),
+            )
+            .await?;
+
+        let response = self
+            .http_client
+            .post(&upload.upload_url)
+            .body(content)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(IncidentError::SlackAPIError {
+                message: format!("Failed to upload {}", filename),
+                slack_error_code: response.status().to_string(),
+            });
+        }
+
+        let mut complete = json!({
+            "files": [{ "id": upload.file_id, "title": title }],
+        });
+        if let Some(channel_id) = channel_id {
+            complete["channel_id"] = json!(channel_id);
+        }
+        let _: Value = self
+            .call_api("files.completeUploadExternal", complete)
+            .await?;
+
+        Ok(upload.file_id)
+    }
+}
+
+pub(crate) fn is_retryable_error_code(error_code: &str) -> bool {
+    RETRYABLE_ERROR_CODES.contains(&error_code)
+}
+
+/// Slack sends `Retry-After` as whole seconds.
+fn parse_retry_after(headers: &HeaderMap) -> Option<Duration> {
+    headers
+        .get(RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .map(|secs| Duration::from_secs(secs).min(MAX_RETRY_AFTER))
+}
+
+#[async_trait]
+impl SlackApi for SlackClient {
+    async fn create_conversation(&self, name: &str, is_private: bool) -> IncidentResult<String> {
         #[derive(Deserialize)]
         struct CreateResponse {
             channel: Channel,
@@ -98,7 +502,7 @@ impl SlackClient {
                 "conversations.create",
                 json!({
                     "name": name,
-                    "is_private": false,
+                    "is_private": is_private,
                 }),
             )
             .await?;
@@ -106,7 +510,7 @@ impl SlackClient {
         Ok(response.channel.id)
     }
 
-    pub async fn list_conversations(&self) -> IncidentResult<Vec<Channel>> {
+    async fn list_conversations(&self) -> IncidentResult<Vec<Channel>> {
         // Implement cursor-based pagination for workspaces with >1000 channels
         let mut all_channels = Vec::new();
         let mut cursor: Option<String> = None;
@@ -136,11 +540,111 @@ impl SlackClient {
         Ok(all_channels)
     }
 
-    pub async fn invite_users(
+    async fn usergroup_members(&self, usergroup_id: &str) -> IncidentResult<Vec<String>> {
+        #[derive(Deserialize)]
+        struct UsersResponse {
+            users: Vec<String>,
+        }
+
+        let response: UsersResponse = self
+            .call_api(
+                "usergroups.users.list",
+                json!({
+                    "usergroup": usergroup_id,
+                }),
+            )
+            .await?;
+
+        Ok(response.users)
+    }
+
+    async fn user_info(&self, user_id: &str) -> IncidentResult<SlackUser> {
+        #[derive(Deserialize)]
+        struct UserInfoResponse {
+            user: SlackUser,
+        }
+
+        let response: UserInfoResponse = self
+            .call_api("users.info", json!({ "user": user_id }))
+            .await?;
+
+        Ok(response.user)
+    }
+
+    async fn lookup_user_by_email(&self, email: &str) -> IncidentResult<Option<String>> {
+        #[derive(Deserialize)]
+        struct LookupUser {
+            id: String,
+        }
+        #[derive(Deserialize)]
+        struct LookupResponse {
+            user: LookupUser,
+        }
+
+        match self
+            .call_api::<LookupResponse>("users.lookupByEmail", json!({ "email": email }))
+            .await
+        {
+            Ok(response) => Ok(Some(response.user.id)),
+            Err(IncidentError::SlackAPIError {
+                slack_error_code, ..
+            }) if slack_error_code == "users_not_found" => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn fetch_channel_history(
         &self,
         channel_id: &str,
-        user_ids: Vec<String>,
-    ) -> IncidentResult<()> {
+        range: &HistoryRange,
+    ) -> IncidentResult<Vec<HistoryMessage>> {
+        let mut messages = Vec::new();
+        let mut cursor: Option<String> = None;
+
+        loop {
+            let remaining = range
+                .max_messages
+                .map(|max| max.saturating_sub(messages.len()))
+                .unwrap_or(HISTORY_PAGE_SIZE);
+            if remaining == 0 {
+                break;
+            }
+
+            let mut params = json!({
+                "channel": channel_id,
+                "limit": remaining.min(HISTORY_PAGE_SIZE),
+                "inclusive": true,
+            });
+            if let Some(oldest) = &range.oldest {
+                params["oldest"] = json!(oldest);
+            }
+            if let Some(latest) = &range.latest {
+                params["latest"] = json!(latest);
+            }
+            if let Some(ref c) = cursor {
+                params["cursor"] = json!(c);
+                // Pace follow-up pages; 429s are still retried by call_api
+                tokio::time::sleep(self.history_page_delay).await;
+            }
+
+            let response: HistoryResponse = self.call_api("conversations.history", params).await?;
+            messages.extend(response.messages);
+
+            match response.response_metadata.and_then(|m| m.next_cursor) {
+                Some(next) if response.has_more && !next.is_empty() => cursor = Some(next),
+                _ => break,
+            }
+        }
+
+        // Slack pages newest first
+        if let Some(max) = range.max_messages {
+            messages.truncate(max);
+        }
+        messages.reverse();
+        Ok(messages)
+    }
+
+    async fn invite_users(&self, channel_id: &str, user_ids: Vec<String>) -> IncidentResult<()> {
         if user_ids.is_empty() {
             return Ok(());
         }
@@ -158,7 +662,7 @@ impl SlackClient {
         Ok(())
     }
 
-    pub async fn archive_channel(&self, channel_id: &str) -> IncidentResult<()> {
+    async fn archive_channel(&self, channel_id: &str) -> IncidentResult<()> {
         let _: Value = self
             .call_api(
                 "conversations.archive",
@@ -171,10 +675,72 @@ impl SlackClient {
         Ok(())
     }
 
-    pub async fn post_message(
+    async fn set_channel_topic(&self, channel_id: &str, topic: &str) -> IncidentResult<()> {
+        let _: Value = self
+            .call_api(
+                "conversations.setTopic",
+                json!({
+                    "channel": channel_id,
+                    "topic": topic,
+                }),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn rename_channel(&self, channel_id: &str, name: &str) -> IncidentResult<()> {
+        let _: Value = self
+            .call_api(
+                "conversations.rename",
+                json!({
+                    "channel": channel_id,
+                    "name": name,
+                }),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn join_conversation(&self, channel_id: &str) -> IncidentResult<()> {
+        let _: Value = self
+            .call_api(
+                "conversations.join",
+                json!({
+                    "channel": channel_id,
+                }),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn post_message(&self, channel_id: &str, blocks: Vec<Value>) -> IncidentResult<String> {
+        #[derive(Deserialize)]
+        struct PostResponse {
+            ts: String,
+        }
+
+        let response: PostResponse = self
+            .call_api(
+                "chat.postMessage",
+                json!({
+                    "channel": channel_id,
+                    "blocks": blocks,
+                }),
+            )
+            .await?;
+
+        Ok(response.ts)
+    }
+
+    async fn post_thread_reply(
         &self,
         channel_id: &str,
+        thread_ts: &str,
         blocks: Vec<Value>,
+        broadcast: bool,
     ) -> IncidentResult<String> {
         #[derive(Deserialize)]
         struct PostResponse {
@@ -186,7 +752,9 @@ impl SlackClient {
                 "chat.postMessage",
                 json!({
                     "channel": channel_id,
+                    "thread_ts": thread_ts,
                     "blocks": blocks,
+                    "reply_broadcast": broadcast,
                 }),
             )
             .await?;
@@ -194,7 +762,27 @@ impl SlackClient {
         Ok(response.ts)
     }
 
-    pub async fn pin_message(&self, channel_id: &str, timestamp: &str) -> IncidentResult<()> {
+    async fn update_message(
+        &self,
+        channel_id: &str,
+        timestamp: &str,
+        blocks: Vec<Value>,
+    ) -> IncidentResult<()> {
+        let _: Value = self
+            .call_api(
+                "chat.update",
+                json!({
+                    "channel": channel_id,
+                    "ts": timestamp,
+                    "blocks": blocks,
+                }),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn pin_message(&self, channel_id: &str, timestamp: &str) -> IncidentResult<()> {
         let _: Value = self
             .call_api(
                 "pins.add",
@@ -208,7 +796,7 @@ impl SlackClient {
         Ok(())
     }
 
-    pub async fn send_dm(&self, user_id: &str, blocks: Vec<Value>) -> IncidentResult<()> {
+    async fn send_dm(&self, user_id: &str, blocks: Vec<Value>) -> IncidentResult<()> {
         // First open a DM conversation
         #[derive(Deserialize)]
         struct OpenResponse {
@@ -230,7 +818,7 @@ impl SlackClient {
         Ok(())
     }
 
-    pub async fn open_modal(&self, trigger_id: &str, view: Value) -> IncidentResult<()> {
+    async fn open_modal(&self, trigger_id: &str, view: Value) -> IncidentResult<()> {
         let _: Value = self
             .call_api(
                 "views.open",
@@ -244,7 +832,72 @@ impl SlackClient {
         Ok(())
     }
 
-    pub async fn post_to_response_url(
+    async fn update_modal(&self, view_id: &str, view: Value) -> IncidentResult<()> {
+        let _: Value = self
+            .call_api(
+                "views.update",
+                json!({
+                    "view_id": view_id,
+                    "view": view,
+                }),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn publish_view(&self, user_id: &str, view: Value) -> IncidentResult<()> {
+        let _: Value = self
+            .call_api(
+                "views.publish",
+                json!({
+                    "user_id": user_id,
+                    "view": view,
+                }),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn upload_file(
+        &self,
+        channel_id: Option<&str>,
+        filename: &str,
+        title: &str,
+        content: Vec<u8>,
+    ) -> IncidentResult<String> {
+        self.upload_external(filename, title, content, channel_id)
+            .await
+    }
+
+    async fn send_file_dm(
+        &self,
+        user_id: &str,
+        filename: &str,
+        title: &str,
+        content: Vec<u8>,
+    ) -> IncidentResult<()> {
+        #[derive(Deserialize)]
+        struct OpenResponse {
+            channel: Channel,
+        }
+
+        let open_response: OpenResponse = self
+            .call_api(
+                "conversations.open",
+                json!({
+                    "users": user_id,
+                }),
+            )
+            .await?;
+
+        self.upload_external(filename, title, content, Some(&open_response.channel.id))
+            .await?;
+        Ok(())
+    }
+
+    async fn post_to_response_url(
         &self,
         response_url: &str,
         blocks: Vec<Value>,
@@ -268,4 +921,276 @@ impl SlackClient {
 
         Ok(())
     }
+
+    fn throttle(&self) -> &SlackThrottle {
+        &self.throttle
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::StatusCode as AxumStatus;
+    use axum::response::IntoResponse;
+    use axum::routing::post;
+    use axum::{Json, Router};
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Arc;
+
+    fn fast_policy(max_retries: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_retries,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(5),
+        }
+    }
+
+    /// Serve a fake Slack API whose Nth response (0-based) is `responses(n)`.
+    async fn fake_slack<F>(responses: F) -> (SlackClient, Arc<AtomicU32>)
+    where
+        F: Fn(u32) -> axum::response::Response + Clone + Send + Sync + 'static,
+    {
+        let calls = Arc::new(AtomicU32::new(0));
+        let counter = calls.clone();
+        let app = Router::new().route(
+            "/{method}",
+            post(move || {
+                let responses = responses.clone();
+                let n = counter.fetch_add(1, Ordering::SeqCst);
+                async move { responses(n) }
+            }),
+        );
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        let mut client =
+            SlackClient::new("xoxb-test".to_string()).with_retry_policy(fast_policy(2));
+        client.base_url = format!("http://{}", addr);
+        (client, calls)
+    }
+
+    #[test]
+    fn test_backoff_doubles_and_caps() {
+        let policy = RetryPolicy {
+            max_retries: 5,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(3),
+        };
+        assert_eq!(policy.backoff(0), Duration::from_millis(500));
+        assert_eq!(policy.backoff(1), Duration::from_secs(1));
+        assert_eq!(policy.backoff(2), Duration::from_secs(2));
+        assert_eq!(policy.backoff(3), Duration::from_secs(3));
+        assert_eq!(policy.backoff(40), Duration::from_secs(3));
+        for attempt in [0, 1, 2, 40] {
+            let delay = policy.jittered_backoff(attempt);
+            assert!(delay >= policy.backoff(attempt) / 2 && delay <= policy.backoff(attempt));
+        }
+    }
+
+    #[test]
+    fn test_error_code_classification() {
+        assert!(is_retryable_error_code("ratelimited"));
+        assert!(is_retryable_error_code("internal_error"));
+        assert!(!is_retryable_error_code("channel_not_found"));
+        assert!(!is_retryable_error_code("invalid_auth"));
+    }
+
+    #[test]
+    fn test_parse_retry_after_caps_and_ignores_garbage() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(parse_retry_after(&headers), None);
+
+        headers.insert(RETRY_AFTER, "2".parse().unwrap());
+        assert_eq!(parse_retry_after(&headers), Some(Duration::from_secs(2)));
+
+        headers.insert(RETRY_AFTER, "3600".parse().unwrap());
+        assert_eq!(parse_retry_after(&headers), Some(MAX_RETRY_AFTER));
+
+        headers.insert(RETRY_AFTER, "soon".parse().unwrap());
+        assert_eq!(parse_retry_after(&headers), None);
+    }
+
+    #[tokio::test]
+    async fn test_retries_rate_limited_call_then_succeeds() {
+        let (client, calls) = fake_slack(|n| {
+            if n == 0 {
+                (AxumStatus::TOO_MANY_REQUESTS, [("Retry-After", "0")]).into_response()
+            } else {
+                Json(json!({ "ok": true, "ts": "1700000000.000100" })).into_response()
+            }
+        })
+        .await;
+
+        let ts = client.post_message("C1", vec![]).await.unwrap();
+        assert_eq!(ts, "1700000000.000100");
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_fatal_slack_error_is_not_retried() {
+        let (client, calls) = fake_slack(|_| {
+            Json(json!({ "ok": false, "error": "channel_not_found" })).into_response()
+        })
+        .await;
+
+        let err = client.post_message("C1", vec![]).await.unwrap_err();
+        assert!(matches!(
+            err,
+            IncidentError::SlackAPIError { ref slack_error_code, .. } if slack_error_code == "channel_not_found"
+        ));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_upload_file_uses_external_upload_flow() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let uploaded = Arc::new(AtomicU32::new(0));
+        let upload_counter = uploaded.clone();
+        let app = Router::new()
+            .route(
+                "/files.getUploadURLExternal",
+                post(move |body: String| async move {
+                    assert_eq!(body, "filename=burndown.png&length=3");
+                    Json(json!({
+                        "ok": true,
+                        "upload_url": format!("http://{}/upload", addr),
+                        "file_id": "F123"
+                    }))
+                }),
+            )
+            .route(
+                "/upload",
+                post(move |body: axum::body::Bytes| async move {
+                    upload_counter.store(body.len() as u32, Ordering::SeqCst);
+                    "OK - 3"
+                }),
+            )
+            .route(
+                "/files.completeUploadExternal",
+                post(|Json(body): Json<Value>| async move {
+                    assert_eq!(body["files"][0]["id"], "F123");
+                    Json(json!({ "ok": true, "files": [{ "id": "F123" }] }))
+                }),
+            );
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        let mut client = SlackClient::new("xoxb-test".to_string());
+        client.base_url = format!("http://{}", addr);
+        let file_id = client
+            .upload_file(None, "burndown.png", "Open incidents", vec![1, 2, 3])
+            .await
+            .unwrap();
+        assert_eq!(file_id, "F123");
+        assert_eq!(uploaded.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_send_file_dm_shares_upload_in_dm_channel() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let app = Router::new()
+            .route(
+                "/conversations.open",
+                post(|Json(body): Json<Value>| async move {
+                    assert_eq!(body["users"], "U123");
+                    Json(json!({ "ok": true, "channel": { "id": "D123", "name": "" } }))
+                }),
+            )
+            .route(
+                "/files.getUploadURLExternal",
+                post(move || async move {
+                    Json(json!({
+                        "ok": true,
+                        "upload_url": format!("http://{}/upload", addr),
+                        "file_id": "F456"
+                    }))
+                }),
+            )
+            .route("/upload", post(|| async { "OK" }))
+            .route(
+                "/files.completeUploadExternal",
+                post(|Json(body): Json<Value>| async move {
+                    assert_eq!(body["files"][0]["id"], "F456");
+                    assert_eq!(body["channel_id"], "D123");
+                    Json(json!({ "ok": true, "files": [{ "id": "F456" }] }))
+                }),
+            );
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        let mut client = SlackClient::new("xoxb-test".to_string());
+        client.base_url = format!("http://{}", addr);
+        client
+            .send_file_dm("U123", "export.csv", "Incident export", b"a,b".to_vec())
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_fetch_channel_history_follows_cursor_and_caps() {
+        // Even calls serve the newest page, odd calls the last one
+        let (client, calls) = fake_slack(|n| {
+            let (messages, cursor) = match n % 2 {
+                0 => (
+                    json!([
+                        { "ts": "3.0", "user": "U1", "text": "c" },
+                        { "ts": "2.0", "user": "U2", "text": "b" }
+                    ]),
+                    "page2",
+                ),
+                _ => (
+                    json!([{ "ts": "1.0", "subtype": "channel_join", "text": "a" }]),
+                    "",
+                ),
+            };
+            Json(json!({
+                "ok": true,
+                "messages": messages,
+                "has_more": !cursor.is_empty(),
+                "response_metadata": { "next_cursor": cursor }
+            }))
+            .into_response()
+        })
+        .await;
+        let client = client.with_history_page_delay(Duration::ZERO);
+
+        let messages = client
+            .fetch_channel_history("C1", &HistoryRange::default())
+            .await
+            .unwrap();
+        let ts: Vec<&str> = messages.iter().map(|m| m.ts.as_str()).collect();
+        assert_eq!(ts, vec!["1.0", "2.0", "3.0"]);
+        assert_eq!(messages[0].subtype.as_deref(), Some("channel_join"));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        // A cap met by the first page stops paging
+        let range = HistoryRange {
+            max_messages: Some(2),
+            ..Default::default()
+        };
+        let messages = client.fetch_channel_history("C1", &range).await.unwrap();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_retries() {
+        let (client, calls) = fake_slack(|_| AxumStatus::SERVICE_UNAVAILABLE.into_response()).await;
+
+        let err = client.post_message("C1", vec![]).await.unwrap_err();
+        assert!(matches!(
+            err,
+            IncidentError::SlackAPIError { ref slack_error_code, .. } if slack_error_code == "503"
+        ));
+        // Initial attempt + 2 retries
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
 }
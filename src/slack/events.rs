@@ -1,5 +1,6 @@
 use crate::app_state::AppState;
 use crate::error::IncidentResult;
+use crate::metrics::metrics;
 use crate::slack::blocks;
 use crate::slack::verification::verify_slack_signature;
 use axum::extract::State;
@@ -9,6 +10,24 @@ use serde::Deserialize;
 use serde_json::Value;
 use tracing::{debug, error, info};
 
+/// Request URL paths served in `SLACK_TRANSPORT=http` mode.
+pub const COMMANDS_PATH: &str = "/slack/commands";
+pub const INTERACTIONS_PATH: &str = "/slack/interactions";
+pub const EVENTS_PATH: &str = "/slack/events";
+
+/// Bot events handled by `dispatch_event`, as subscribed to in the Slack app
+/// (`message.channels` arrives as `message`).
+pub const BOT_EVENTS: &[&str] = &[
+    "app_home_opened",
+    "message.channels",
+    "member_joined_channel",
+    "reaction_added",
+    "channel_archive",
+    "channel_deleted",
+    "group_archive",
+    "group_deleted",
+];
+
 #[derive(Debug, Deserialize)]
 pub struct SlashCommandPayload {
     pub command: String,
@@ -20,21 +39,87 @@ pub struct SlashCommandPayload {
 }
 
 #[derive(Debug, Deserialize)]
-struct InteractionPayload {
+pub(crate) struct InteractionPayload {
     #[serde(rename = "type")]
     pub interaction_type: String,
     pub user: User,
     pub view: Option<ViewPayload>,
+    #[serde(default)]
+    pub actions: Vec<BlockAction>,
+    pub response_url: Option<String>,
+    /// Lets a button click open a modal
+    pub trigger_id: Option<String>,
+    /// Message the actions were clicked in (absent for modals and App Home)
+    pub container: Option<Container>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct Container {
+    pub channel_id: Option<String>,
+    pub message_ts: Option<String>,
+}
+
+/// Events API envelope (`url_verification` handshake or `event_callback`).
+#[derive(Debug, Deserialize)]
+pub(crate) struct EventEnvelope {
+    #[serde(rename = "type")]
+    pub envelope_type: String,
+    pub challenge: Option<String>,
+    /// Unique per event; Slack reuses it when retrying a delivery
+    pub event_id: Option<String>,
+    pub event: Option<SlackEvent>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct SlackEvent {
+    #[serde(rename = "type")]
+    pub event_type: String,
+    pub user: Option<String>,
+    pub tab: Option<String>,
+    /// Set for `message`, `member_joined_channel` and channel archive or
+    /// delete events
+    pub channel: Option<String>,
+    /// Set for edits, joins, bot posts and other non-plain messages
+    pub subtype: Option<String>,
+    /// Emoji name, for `reaction_added` events
+    pub reaction: Option<String>,
+    /// What was reacted to, for `reaction_added` events
+    pub item: Option<ReactionItem>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct ReactionItem {
+    #[serde(rename = "type")]
+    pub item_type: String,
+    pub channel: Option<String>,
+    pub ts: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct BlockAction {
+    pub action_id: String,
+    pub value: Option<String>,
+    /// Set for select menus instead of `value`
+    pub selected_option: Option<SelectedOption>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct SelectedOption {
+    pub value: String,
 }
 
 #[derive(Debug, Deserialize)]
-struct User {
+pub(crate) struct User {
     pub id: String,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct ViewPayload {
+    #[serde(default)]
+    pub id: String,
     pub callback_id: String,
+    #[serde(default)]
+    pub private_metadata: String,
     pub state: ViewState,
 }
 
@@ -82,14 +167,53 @@ pub async fn handle_slash_command(
         payload.command, payload.text
     );
 
-    // Spawn async task to process command
+    let retry_attempt = retry_header(&headers);
+    spawn_slash_command(state, payload, retry_attempt);
+
+    // Return 200 OK immediately (Slack's recommended ack-then-process pattern)
+    // Slack requires response within 3 seconds. Processing happens asynchronously.
+    // Errors are reported to user via response_url in spawned task.
+    StatusCode::OK.into_response()
+}
+
+/// Slack's retry counter (`X-Slack-Retry-Num`), if this delivery is a retry.
+fn retry_header(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get("X-Slack-Retry-Num")
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string)
+}
+
+/// Process a slash command in the background, reporting failures to the
+/// user via `response_url`. Shared by the HTTP and Socket Mode transports.
+/// A redelivery of a command already processed (same `trigger_id`) is
+/// dropped, so a retry can't declare a second incident. Counted in
+/// `state.in_flight`, so a graceful shutdown lets it finish.
+pub(crate) fn spawn_slash_command(
+    state: AppState,
+    payload: SlashCommandPayload,
+    retry_attempt: Option<String>,
+) {
     let state_clone = state.clone();
     let user_id = payload.user_id.clone();
     let command = payload.command.clone();
     let channel_id = payload.channel_id.clone();
     let response_url = payload.response_url.clone();
-    tokio::spawn(async move {
-        if let Err(e) = process_slash_command(state_clone.clone(), payload).await {
+    let text = payload.text.clone();
+    let in_flight = state.in_flight.clone();
+    in_flight.spawn(async move {
+        if !payload.trigger_id.is_empty() {
+            let key = format!("command:{}", payload.trigger_id);
+            if is_retry(&state, &key, "slash_command", retry_attempt.as_deref()).await {
+                return;
+            }
+        }
+
+        let started = std::time::Instant::now();
+        let result = process_slash_command(state_clone.clone(), payload).await;
+        metrics().record_command(&text, result.is_ok(), started.elapsed());
+
+        if let Err(e) = result {
             error!(
                 "Error processing command - user_id: {}, command: {}, channel_id: {}, error: {}",
                 user_id, command, channel_id, e
@@ -106,11 +230,6 @@ pub async fn handle_slash_command(
             }
         }
     });
-
-    // Return 200 OK immediately (Slack's recommended ack-then-process pattern)
-    // Slack requires response within 3 seconds. Processing happens asynchronously.
-    // Errors are reported to user via response_url in spawned task.
-    StatusCode::OK.into_response()
 }
 
 async fn process_slash_command(
@@ -120,6 +239,16 @@ async fn process_slash_command(
     let parts: Vec<&str> = payload.text.split_whitespace().collect();
     let subcommand = parts.first().copied().unwrap_or("");
 
+    if let Some(reply) =
+        crate::commands::closed::closed_incident_reply(&state, subcommand, &payload.channel_id)
+            .await?
+    {
+        return state
+            .slack_client
+            .post_to_response_url(&payload.response_url, reply)
+            .await;
+    }
+
     match subcommand {
         "declare" => {
             crate::commands::declare::handle_declare(state, payload).await?;
@@ -130,20 +259,110 @@ async fn process_slash_command(
         "severity" => {
             crate::commands::severity::handle_severity(state, payload).await?;
         }
+        "update-status" => {
+            crate::commands::update_status::handle_update_status(state, payload).await?;
+        }
         "resolved" => {
             crate::commands::resolved::handle_resolved(state, payload).await?;
         }
+        "reopen" => {
+            crate::commands::reopen::handle_reopen(state, payload).await?;
+        }
+        "cancel" => {
+            crate::commands::cancel::handle_cancel(state, payload).await?;
+        }
         "timeline" => {
             crate::commands::timeline::handle_timeline(state, payload).await?;
         }
+        "note" => {
+            crate::commands::note::handle_note(state, payload).await?;
+        }
+        "link" => {
+            crate::commands::link::handle_link(state, payload).await?;
+        }
         "postmortem" => {
             crate::commands::postmortem::handle_postmortem(state, payload).await?;
         }
+        "workstream" => {
+            crate::commands::workstream::handle_workstream(state, payload).await?;
+        }
+        "action" => {
+            crate::commands::action::handle_action(state, payload).await?;
+        }
+        "roles" => {
+            crate::commands::roles::handle_roles(state, payload).await?;
+        }
+        "simulate" => {
+            crate::commands::simulate::handle_simulate(state, payload).await?;
+        }
+        "search" => {
+            crate::commands::search::handle_search(state, payload).await?;
+        }
+        "metrics" => {
+            crate::commands::metrics::handle_metrics(state, payload).await?;
+        }
+        "attach" => {
+            crate::commands::attach::handle_attach(state, payload).await?;
+        }
+        "load" => {
+            crate::commands::load::handle_load(state, payload).await?;
+        }
+        "routing" => {
+            crate::commands::routing::handle_routing(state, payload).await?;
+        }
+        "bridge" => {
+            crate::commands::bridge::handle_bridge(state, payload).await?;
+        }
+        "template" => {
+            crate::commands::template::handle_template(state, payload).await?;
+        }
+        "coaching" => {
+            crate::commands::coaching::handle_coaching(state, payload).await?;
+        }
+        "summary" => {
+            crate::commands::summary::handle_summary(state, payload).await?;
+        }
+        "whoisoncall" => {
+            crate::commands::whoisoncall::handle_whoisoncall(state, payload).await?;
+        }
+        "export" => {
+            crate::commands::export::handle_export(state, payload).await?;
+        }
+        "notifications" => {
+            crate::commands::notifications::handle_notifications(state, payload).await?;
+        }
+        "jobs" => {
+            crate::commands::jobs::handle_jobs(state, payload).await?;
+        }
+        "audit" => {
+            crate::commands::audit::handle_audit(state, payload).await?;
+        }
+        "subscribe" => {
+            crate::commands::subscribe::handle_subscribe(state, payload).await?;
+        }
+        "unsubscribe" => {
+            crate::commands::subscribe::handle_unsubscribe(state, payload).await?;
+        }
+        "service" => {
+            crate::commands::service::handle_service(state, payload).await?;
+        }
+        "triage" => {
+            crate::commands::triage::handle_triage(state, payload).await?;
+        }
+        "premortem" => {
+            crate::commands::premortem::handle_premortem(state, payload).await?;
+        }
         _ => {
-            let blocks = blocks::error_blocks(&format!(
-                "Unknown subcommand: {}. Available: declare, status, severity, resolved, timeline, postmortem",
-                subcommand
+            let mut blocks = blocks::error_blocks(&format!(
+                "Unknown subcommand: {}. Available: {}",
+                subcommand,
+                crate::commands::SUBCOMMANDS.join(", ")
             ));
+            let end = blocks.len();
+            state
+                .context_banner
+                .insert(&state.pool, &mut blocks, end)
+                .await;
             state
                 .slack_client
                 .post_to_response_url(&payload.response_url, blocks)
@@ -206,30 +425,302 @@ pub async fn handle_interaction(
 
     debug!("Received interaction: {}", payload.interaction_type);
 
-    // Spawn async task to process interaction
+    let retry_attempt = retry_header(&headers);
+    spawn_interaction(state, payload, retry_attempt);
+
+    // Return 200 OK immediately
+    StatusCode::OK.into_response()
+}
+
+/// Process an interaction in the background. Shared by both transports.
+/// Redeliveries of an interaction already processed (same `trigger_id`) are
+/// dropped, like slash commands.
+pub(crate) fn spawn_interaction(
+    state: AppState,
+    payload: InteractionPayload,
+    retry_attempt: Option<String>,
+) {
     let state_clone = state.clone();
     let user_id = payload.user.id.clone();
     let interaction_type = payload.interaction_type.clone();
-    tokio::spawn(async move {
-        if let Err(e) = process_interaction(state_clone, payload).await {
+    let in_flight = state.in_flight.clone();
+    in_flight.spawn(async move {
+        if let Some(trigger_id) = payload.trigger_id.as_deref().filter(|t| !t.is_empty()) {
+            let key = format!("interaction:{}", trigger_id);
+            if is_retry(&state, &key, &interaction_type, retry_attempt.as_deref()).await {
+                return;
+            }
+        }
+
+        let result = process_interaction(state_clone, payload).await;
+        metrics().record_interaction(&interaction_type, result.is_ok());
+
+        if let Err(e) = result {
             error!(
                 "Error processing interaction - user_id: {}, type: {}, error: {}",
                 user_id, interaction_type, e
             );
         }
     });
-
-    // Return 200 OK immediately
-    StatusCode::OK.into_response()
 }
 
 async fn process_interaction(state: AppState, payload: InteractionPayload) -> IncidentResult<()> {
     match payload.interaction_type.as_str() {
         "view_submission" => {
             if let Some(view) = payload.view {
-                if view.callback_id == "declare_incident_modal" {
+                if view.callback_id == crate::slack::modals::DECLARE_MODAL_CALLBACK_ID {
                     crate::commands::declare::handle_modal_submission(state, view, payload.user.id)
                         .await?;
+                } else if view.callback_id == crate::slack::modals::UPDATE_STATUS_MODAL_CALLBACK_ID
+                {
+                    crate::commands::incident_actions::handle_update_status_submission(
+                        state,
+                        view,
+                        payload.user.id,
+                    )
+                    .await?;
+                } else if view.callback_id == crate::slack::modals::TEMPLATE_MODAL_CALLBACK_ID {
+                    crate::commands::template::handle_template_submission(
+                        state,
+                        view,
+                        payload.user.id,
+                    )
+                    .await?;
+                } else if view.callback_id == crate::slack::modals::PREMORTEM_LAUNCH_CALLBACK_ID {
+                    crate::commands::premortem::handle_launch_submission(
+                        state,
+                        view,
+                        payload.user.id,
+                        payload.trigger_id,
+                    )
+                    .await?;
+                } else if view.callback_id == crate::slack::modals::PREMORTEM_RISK_CALLBACK_ID {
+                    crate::commands::premortem::handle_risk_submission(
+                        state,
+                        view,
+                        payload.user.id,
+                        payload.trigger_id,
+                    )
+                    .await?;
+                }
+            }
+        }
+        "view_closed" => {
+            if let Some(view) = payload
+                .view
+                .filter(|v| v.callback_id == crate::slack::modals::PREMORTEM_RISK_CALLBACK_ID)
+            {
+                crate::commands::premortem::handle_risk_closed(state, view, payload.user.id)
+                    .await?;
+            }
+        }
+        "block_actions" => {
+            // Edits inside the declare modal: keep the user's draft
+            if let Some(view) = payload
+                .view
+                .as_ref()
+                .filter(|v| v.callback_id == crate::slack::modals::DECLARE_MODAL_CALLBACK_ID)
+            {
+                if payload
+                    .actions
+                    .iter()
+                    .any(|a| a.action_id == crate::slack::modals::TEMPLATE_SELECT_ACTION)
+                {
+                    crate::commands::declare::apply_template(&state, view).await?;
+                } else if payload
+                    .actions
+                    .iter()
+                    .any(|a| a.action_id == crate::slack::modals::SERVICE_SELECT_ACTION)
+                {
+                    crate::commands::declare::apply_service(&state, view).await?;
+                }
+                return crate::commands::declare::save_draft(&state, &payload.user.id, view).await;
+            }
+
+            for action in payload.actions {
+                if action.action_id == blocks::DECLARE_RESUME_DRAFT_ACTION {
+                    crate::commands::declare::handle_resume_draft(
+                        state.clone(),
+                        payload.user.id.clone(),
+                        payload.trigger_id.clone(),
+                    )
+                    .await?;
+                } else if action.action_id == blocks::DECLARE_DISCARD_DRAFT_ACTION {
+                    crate::commands::declare::handle_discard_draft(
+                        state.clone(),
+                        payload.user.id.clone(),
+                        payload.trigger_id.clone(),
+                    )
+                    .await?;
+                } else if action
+                    .action_id
+                    .starts_with(blocks::CLAIM_ROLE_ACTION_PREFIX)
+                {
+                    crate::commands::roles::handle_claim_role(
+                        state.clone(),
+                        payload.user.id.clone(),
+                        action.value.as_deref().unwrap_or(""),
+                        payload.response_url.clone(),
+                    )
+                    .await?;
+                } else if action.action_id == blocks::SEARCH_PAGE_ACTION {
+                    crate::commands::search::handle_search_page(
+                        state.clone(),
+                        action.value.as_deref().unwrap_or(""),
+                        payload.response_url.clone(),
+                    )
+                    .await?;
+                } else if action.action_id == blocks::TAKE_COMMAND_ACTION {
+                    crate::commands::commander::handle_take_command(
+                        state.clone(),
+                        payload.user.id.clone(),
+                        action.value.as_deref().unwrap_or(""),
+                        payload.response_url.clone(),
+                    )
+                    .await?;
+                } else if action.action_id == blocks::COMMANDER_ACK_ACTION {
+                    crate::commands::commander::handle_commander_ack(
+                        state.clone(),
+                        payload.user.id.clone(),
+                        action.value.as_deref().unwrap_or(""),
+                        payload.response_url.clone(),
+                    )
+                    .await?;
+                } else if action.action_id == blocks::INCIDENT_ACK_ACTION {
+                    crate::commands::incident_actions::handle_acknowledge(
+                        state.clone(),
+                        payload.user.id.clone(),
+                        action.value.as_deref().unwrap_or(""),
+                        payload.response_url.clone(),
+                    )
+                    .await?;
+                } else if action.action_id == blocks::INCIDENT_UPDATE_STATUS_ACTION {
+                    crate::commands::incident_actions::handle_update_status_button(
+                        state.clone(),
+                        payload.user.id.clone(),
+                        action.value.as_deref().unwrap_or(""),
+                        payload.trigger_id.clone(),
+                        payload.response_url.clone(),
+                    )
+                    .await?;
+                } else if action.action_id == blocks::INCIDENT_RESOLVE_ACTION {
+                    crate::commands::incident_actions::handle_resolve_button(
+                        state.clone(),
+                        payload.user.id.clone(),
+                        action.value.as_deref().unwrap_or(""),
+                        payload.response_url.clone(),
+                    )
+                    .await?;
+                } else if action.action_id == blocks::TRIAGE_DECLARE_ACTION {
+                    crate::commands::triage::handle_declare_button(
+                        state.clone(),
+                        payload.user.id.clone(),
+                        action.value.as_deref().unwrap_or(""),
+                        payload.trigger_id.clone(),
+                        payload.response_url.clone(),
+                    )
+                    .await?;
+                } else if action.action_id == blocks::TRIAGE_DISMISS_ACTION {
+                    crate::commands::triage::handle_dismiss_button(
+                        state.clone(),
+                        payload.user.id.clone(),
+                        action.value.as_deref().unwrap_or(""),
+                        payload.response_url.clone(),
+                    )
+                    .await?;
+                } else if action.action_id == blocks::PAGING_TEST_ACK_ACTION {
+                    crate::commands::paging_test::handle_paging_test_ack(
+                        state.clone(),
+                        payload.user.id.clone(),
+                        action.value.as_deref().unwrap_or(""),
+                        payload.response_url.clone(),
+                    )
+                    .await?;
+                } else if action.action_id == blocks::MIM_ACCEPT_ACTION {
+                    crate::commands::mim::handle_mim_accept(
+                        state.clone(),
+                        payload.user.id.clone(),
+                        action.value.as_deref().unwrap_or(""),
+                        payload.response_url.clone(),
+                    )
+                    .await?;
+                } else if action.action_id == blocks::STATUS_DRAFT_APPROVE_ACTION {
+                    crate::commands::status::handle_approve_draft(
+                        state.clone(),
+                        payload.user.id.clone(),
+                        action.value.as_deref().unwrap_or(""),
+                        payload.response_url.clone(),
+                    )
+                    .await?;
+                } else if action.action_id == blocks::STATUS_DRAFT_DISCARD_ACTION {
+                    crate::commands::status::handle_discard_draft(
+                        state.clone(),
+                        payload.user.id.clone(),
+                        action.value.as_deref().unwrap_or(""),
+                        payload.response_url.clone(),
+                    )
+                    .await?;
+                } else if action.action_id == blocks::REPLACE_CHANNEL_ACTION {
+                    crate::commands::channel_lost::handle_replace_channel(
+                        state.clone(),
+                        payload.user.id.clone(),
+                        action.value.as_deref().unwrap_or(""),
+                        payload.response_url.clone(),
+                    )
+                    .await?;
+                } else if action.action_id == blocks::CONTINUE_WITHOUT_CHANNEL_ACTION {
+                    crate::commands::channel_lost::handle_continue_without_channel(
+                        state.clone(),
+                        payload.user.id.clone(),
+                        action.value.as_deref().unwrap_or(""),
+                        payload.response_url.clone(),
+                    )
+                    .await?;
+                } else if action.action_id == blocks::TEMPLATE_FROM_INCIDENT_ACTION {
+                    crate::commands::template::handle_create_from_incident(
+                        state.clone(),
+                        payload.user.id.clone(),
+                        action.value.as_deref().unwrap_or(""),
+                        payload.trigger_id.clone(),
+                        payload.response_url.clone(),
+                    )
+                    .await?;
+                } else if action.action_id == crate::slack::home::HOME_RESOLVE_ACTION {
+                    crate::commands::resolved::handle_home_resolve(
+                        state.clone(),
+                        payload.user.id.clone(),
+                        action.value.as_deref().unwrap_or(""),
+                    )
+                    .await?;
+                } else if action
+                    .action_id
+                    .starts_with(blocks::TIMELINE_FILTER_ACTION_PREFIX)
+                {
+                    let container = payload.container.as_ref();
+                    let (Some(channel_id), Some(message_ts)) = (
+                        container.and_then(|c| c.channel_id.as_deref()),
+                        container.and_then(|c| c.message_ts.as_deref()),
+                    ) else {
+                        info!("Timeline filter action without a message container");
+                        continue;
+                    };
+                    let value = action
+                        .selected_option
+                        .as_ref()
+                        .map(|o| o.value.as_str())
+                        .or(action.value.as_deref())
+                        .unwrap_or("");
+                    crate::commands::timeline::handle_timeline_filter(
+                        state.clone(),
+                        channel_id,
+                        message_ts,
+                        value,
+                    )
+                    .await?;
+                } else if action.action_id == crate::slack::home::HOME_OPEN_CHANNEL_ACTION {
+                    // URL button; Slack opens the link client-side
+                } else {
+                    info!("Unhandled block action: {}", action.action_id);
                 }
             }
         }
@@ -240,3 +731,191 @@ async fn process_interaction(state: AppState, payload: InteractionPayload) -> In
 
     Ok(())
 }
+
+pub async fn handle_event(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: String,
+) -> Response {
+    // Verify Slack signature
+    let signature = headers
+        .get("X-Slack-Signature")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("");
+    let timestamp = headers
+        .get("X-Slack-Request-Timestamp")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("");
+
+    if let Err(e) = verify_slack_signature(
+        &state.config.slack_signing_secret,
+        timestamp,
+        &body,
+        signature,
+    ) {
+        error!("Signature verification failed: {}", e);
+        return (StatusCode::UNAUTHORIZED, "Invalid signature").into_response();
+    }
+
+    let envelope: EventEnvelope = match serde_json::from_str(&body) {
+        Ok(e) => e,
+        Err(e) => {
+            error!("Failed to parse event: {}", e);
+            return (StatusCode::BAD_REQUEST, "Invalid request").into_response();
+        }
+    };
+
+    // Slack verifies the Request URL by asking us to echo the challenge
+    if envelope.envelope_type == "url_verification" {
+        return envelope.challenge.unwrap_or_default().into_response();
+    }
+
+    let retry_attempt = retry_header(&headers);
+    dispatch_event(state, envelope, retry_attempt.as_deref()).await;
+
+    // Slack retries events not acked within 3 seconds
+    StatusCode::OK.into_response()
+}
+
+/// Handle an `event_callback` envelope, skipping Slack retries of events
+/// already processed. `retry_attempt` is Slack's retry counter, if sent.
+pub(crate) async fn dispatch_event(
+    state: AppState,
+    envelope: EventEnvelope,
+    retry_attempt: Option<&str>,
+) {
+    let Some(event) = envelope.event else {
+        return;
+    };
+    debug!("Received event: {}", event.event_type);
+
+    if let Some(event_id) = &envelope.event_id {
+        if is_retry(&state, event_id, &event.event_type, retry_attempt).await {
+            return;
+        }
+    }
+
+    match (event.event_type.as_str(), event.user) {
+        ("app_home_opened", Some(user_id)) if event.tab.as_deref() != Some("messages") => {
+            tokio::spawn(async move {
+                if let Err(e) = crate::slack::home::publish_home(&state, &user_id).await {
+                    error!("Failed to publish App Home for {}: {}", user_id, e);
+                }
+            });
+        }
+        ("message", Some(user_id)) if event.subtype.is_none() => {
+            let Some(channel_id) = event.channel else {
+                return;
+            };
+            tokio::spawn(async move {
+                if let Err(e) = crate::commands::commander::record_channel_message(
+                    &state,
+                    &channel_id,
+                    &user_id,
+                )
+                .await
+                {
+                    error!("Failed to record commander activity: {}", e);
+                }
+            });
+        }
+        ("member_joined_channel", Some(user_id)) => {
+            let Some(channel_id) = event.channel else {
+                return;
+            };
+            tokio::spawn(async move {
+                let participants =
+                    crate::services::participants::ParticipantService::new(state.pool.clone());
+                if let Err(e) = participants
+                    .record_join(&channel_id, &user_id, chrono::Utc::now())
+                    .await
+                {
+                    error!("Failed to record participant {}: {}", user_id, e);
+                }
+            });
+        }
+        ("reaction_added", Some(user_id)) => {
+            let (Some(reaction), Some(item)) = (event.reaction, event.item) else {
+                return;
+            };
+            let (Some(channel_id), Some(message_ts)) = (item.channel, item.ts) else {
+                return;
+            };
+            if item.item_type != "message" || reaction != state.config.timeline_reaction {
+                return;
+            }
+            tokio::spawn(async move {
+                if let Err(e) = crate::commands::timeline::record_reacted_message(
+                    &state,
+                    &channel_id,
+                    &message_ts,
+                    &user_id,
+                )
+                .await
+                {
+                    error!("Failed to add reacted message to timeline: {}", e);
+                }
+            });
+        }
+        ("channel_archive" | "group_archive" | "channel_deleted" | "group_deleted", user_id) => {
+            let Some(channel_id) = event.channel else {
+                return;
+            };
+            // Deletions carry no user
+            let archived_by = user_id.filter(|_| event.event_type.ends_with("_archive"));
+            tokio::spawn(async move {
+                if let Err(e) = crate::commands::channel_lost::handle_channel_gone(
+                    &state,
+                    &channel_id,
+                    archived_by.as_deref(),
+                )
+                .await
+                {
+                    error!("Failed to handle loss of channel {}: {}", channel_id, e);
+                }
+            });
+        }
+        (event_type, _) => info!("Unhandled event type: {}", event_type),
+    }
+}
+
+/// How long processed delivery IDs are remembered. Slack's three retries
+/// land within about five minutes, so an hour covers them with room to
+/// spare.
+const EVENT_REPLAY_WINDOW: chrono::Duration = chrono::Duration::hours(1);
+
+/// Record a delivery ID (an event ID, or a prefixed `trigger_id` for
+/// commands and interactions) and report whether it was already processed.
+/// Storage errors are logged and treated as first delivery: double-handling
+/// a delivery beats dropping it.
+async fn is_retry(
+    state: &AppState,
+    event_id: &str,
+    event_type: &str,
+    retry_attempt: Option<&str>,
+) -> bool {
+    use crate::db::queries::slack_events;
+
+    let cutoff = chrono::Utc::now() - EVENT_REPLAY_WINDOW;
+    if let Err(e) = slack_events::purge_events_before(&state.pool, cutoff).await {
+        error!("Failed to purge processed Slack events: {}", e);
+    }
+
+    match slack_events::claim_event(&state.pool, event_id, event_type).await {
+        Ok(true) => false,
+        Ok(false) => {
+            info!(
+                "Skipping Slack delivery {} ({}): already processed (retry {})",
+                event_id,
+                event_type,
+                retry_attempt.unwrap_or("?")
+            );
+            metrics().record_slack_event_retry(event_type);
+            true
+        }
+        Err(e) => {
+            error!("Failed to record Slack event {}: {}", event_id, e);
+            false
+        }
+    }
+}
@@ -0,0 +1,239 @@
+use crate::app_state::AppState;
+use crate::db::models::Incident;
+use crate::db::queries::{analytics, incidents, roles, workstreams};
+use crate::error::IncidentResult;
+use crate::services::roles::role_label;
+use crate::slack::blocks::burndown_image_block;
+use crate::utils::time;
+use serde_json::{json, Value};
+
+pub const HOME_OPEN_CHANNEL_ACTION: &str = "home_open_channel";
+pub const HOME_RESOLVE_ACTION: &str = "home_resolve_incident";
+
+/// An open incident as shown on a user's Home tab, with what they hold on it.
+#[derive(Debug)]
+pub struct HomeIncident {
+    pub incident: Incident,
+    pub assignments: Vec<String>,
+}
+
+/// Load the viewer's open incidents and publish their Home tab.
+pub async fn publish_home(state: &AppState, user_id: &str) -> IncidentResult<()> {
+    let mut entries = Vec::new();
+    for incident in incidents::list_open_for_user(&state.pool, user_id).await? {
+        let mut assignments = Vec::new();
+        if incident.commander_id == user_id {
+            assignments.push("Commander".to_string());
+        }
+        for role in roles::list_roles(&state.pool, incident.id).await? {
+            if role.user_id == user_id {
+                assignments.push(role_label(&role.role));
+            }
+        }
+        for workstream in workstreams::list_workstreams(&state.pool, incident.id).await? {
+            if workstream.lead_id == user_id {
+                assignments.push(format!("Lead of *{}*", workstream.name));
+            }
+        }
+        entries.push(HomeIncident {
+            incident,
+            assignments,
+        });
+    }
+
+    let burndown_file_id = analytics::latest_burndown_file(&state.pool).await?;
+
+    let mut view = home_view(user_id, &entries, burndown_file_id.as_deref());
+    if let Some(blocks) = view["blocks"].as_array_mut() {
+        // Under the header
+        state.context_banner.insert(&state.pool, blocks, 1).await;
+    }
+    state.slack_client.publish_view(user_id, view).await
+}
+
+/// Home tab listing the viewer's open incidents, followed by the org-wide
+/// burndown sparkline once one has been rendered. Only the commander gets a
+/// Resolve button, matching `/incident resolved`.
+pub fn home_view(user_id: &str, entries: &[HomeIncident], burndown_file_id: Option<&str>) -> Value {
+    let mut blocks = vec![
+        json!({
+            "type": "header",
+            "text": {
+                "type": "plain_text",
+                "text": "Your open incidents"
+            }
+        }),
+        json!({ "type": "divider" }),
+    ];
+
+    if entries.is_empty() {
+        blocks.push(json!({
+            "type": "section",
+            "text": {
+                "type": "mrkdwn",
+                "text": "🎉 You're not involved in any open incidents."
+            }
+        }));
+    }
+
+    for entry in entries {
+        let incident = &entry.incident;
+        let involvement = if entry.assignments.is_empty() {
+            "Participant".to_string()
+        } else {
+            entry.assignments.join(", ")
+        };
+
+        blocks.push(json!({
+            "type": "section",
+            "text": {
+                "type": "mrkdwn",
+                "text": format!(
+                    "{} *{}* — {}\n*{}* · *Service:* {} · *Status:* {} · *Declared:* <!date^{}^{{date_short_pretty}} {{time}}|{}>\n*You:* {}",
+                    incident.severity_level().emoji,
+                    incident.severity_level().label,
+                    incident.title,
+                    incident.reference(),
+                    incident.affected_service,
+                    incident.status.as_db_str(),
+                    incident.declared_at.timestamp(),
+                    time::date_time(&incident.declared_at),
+                    involvement
+                )
+            }
+        }));
+
+        let mut buttons = Vec::new();
+        if let Some(channel_id) = &incident.slack_channel_id {
+            buttons.push(json!({
+                "type": "button",
+                "action_id": HOME_OPEN_CHANNEL_ACTION,
+                "text": { "type": "plain_text", "text": "Open channel" },
+                "url": format!("https://slack.com/app_redirect?channel={}", channel_id)
+            }));
+        }
+        if incident.commander_id == user_id {
+            buttons.push(json!({
+                "type": "button",
+                "action_id": HOME_RESOLVE_ACTION,
+                "text": { "type": "plain_text", "text": "Resolve" },
+                "style": "danger",
+                "value": incident.id.to_string(),
+                "confirm": {
+                    "title": { "type": "plain_text", "text": "Resolve incident?" },
+                    "text": { "type": "plain_text", "text": incident.title.clone() },
+                    "confirm": { "type": "plain_text", "text": "Resolve" },
+                    "deny": { "type": "plain_text", "text": "Cancel" }
+                }
+            }));
+        }
+        if !buttons.is_empty() {
+            blocks.push(json!({ "type": "actions", "elements": buttons }));
+        }
+        blocks.push(json!({ "type": "divider" }));
+    }
+
+    if let Some(file_id) = burndown_file_id {
+        blocks.push(burndown_image_block(file_id));
+    }
+
+    json!({
+        "type": "home",
+        "blocks": blocks
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::models::{IncidentStatus, Severity};
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    fn incident(commander_id: &str, channel: Option<&str>) -> Incident {
+        let now = Utc::now();
+        Incident {
+            id: Uuid::new_v4(),
+            number_prefix: "INC".to_string(),
+            number: 7,
+            slack_channel_id: channel.map(ToString::to_string),
+            title: "Checkout errors".to_string(),
+            severity: Severity::P1,
+            severity_code: None,
+            status: IncidentStatus::Investigating,
+            affected_service: "API Gateway".to_string(),
+            commander_id: commander_id.to_string(),
+            declared_at: now,
+            resolved_at: None,
+            duration_minutes: None,
+            pinned_message_ts: None,
+            is_quiet: false,
+            statuspage_incident_id: None,
+            canceled_at: None,
+            cancel_reason: None,
+            triaged_at: None,
+            is_triage: false,
+            channel_archived_at: None,
+            bridge_url: None,
+            custom_fields: Default::default(),
+            template_id: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn actions(view: &Value) -> Vec<String> {
+        view["blocks"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|b| b["type"] == "actions")
+            .flat_map(|b| b["elements"].as_array().unwrap().clone())
+            .map(|e| e["action_id"].as_str().unwrap().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_home_view_offers_resolve_only_to_commander() {
+        let entries = vec![
+            HomeIncident {
+                incident: incident("U_ME", Some("C_ONE")),
+                assignments: vec!["Commander".to_string()],
+            },
+            HomeIncident {
+                incident: incident("U_OTHER", Some("C_TWO")),
+                assignments: vec!["Scribe".to_string()],
+            },
+        ];
+
+        let view = home_view("U_ME", &entries, None);
+        assert_eq!(view["type"], "home");
+        assert_eq!(
+            actions(&view),
+            vec![
+                HOME_OPEN_CHANNEL_ACTION,
+                HOME_RESOLVE_ACTION,
+                HOME_OPEN_CHANNEL_ACTION
+            ]
+        );
+        assert!(view.to_string().contains("*You:* Scribe"));
+    }
+
+    #[test]
+    fn test_home_view_empty_state() {
+        let view = home_view("U_ME", &[], None);
+        assert!(view
+            .to_string()
+            .contains("not involved in any open incidents"));
+        assert!(actions(&view).is_empty());
+    }
+
+    #[test]
+    fn test_home_view_ends_with_burndown_image() {
+        let view = home_view("U_ME", &[], Some("F_BURNDOWN"));
+        let blocks = view["blocks"].as_array().unwrap();
+        let last = blocks.last().unwrap();
+        assert_eq!(last["type"], "image");
+        assert_eq!(last["slack_file"]["id"], "F_BURNDOWN");
+    }
+}
@@ -0,0 +1,216 @@
+//! Slack app manifest for `incident-bot manifest`, built from the routes,
+//! events and subcommands the bot actually serves so the app's
+//! configuration can be pasted into api.slack.com instead of clicked together.
+//!
+//! The bot registers no global or message shortcuts, so the manifest has none.
+
+use crate::commands::SUBCOMMANDS;
+use crate::slack::events::{BOT_EVENTS, COMMANDS_PATH, EVENTS_PATH, INTERACTIONS_PATH};
+
+const USAGE: &str =
+    "Usage: incident-bot manifest (--url https://bot.example.com | --socket-mode) [--name NAME]";
+
+pub const SLASH_COMMAND: &str = "/incident";
+
+/// Bot token scopes; SLACK_SETUP.md explains what each is needed for.
+pub const BOT_SCOPES: &[&str] = &[
+    "commands",
+    "channels:manage",
+    "channels:read",
+    "channels:join",
+    "chat:write",
+    "pins:write",
+    "im:write",
+    "users:read",
+    "users:read.email",
+    "files:write",
+    "channels:history",
+    "groups:write",
+    "groups:read",
+    "reactions:read",
+    "usergroups:read",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ManifestOptions {
+    pub name: String,
+    /// Public base URL for Request URLs; `None` with Socket Mode
+    pub base_url: Option<String>,
+}
+
+pub fn parse_options(args: &[String]) -> Result<ManifestOptions, String> {
+    let mut name = "Incident Bot".to_string();
+    let mut base_url = None;
+    let mut socket_mode = false;
+    let mut args = args.iter();
+    while let Some(flag) = args.next() {
+        if flag == "--socket-mode" {
+            socket_mode = true;
+            continue;
+        }
+        let value = args
+            .next()
+            .filter(|v| !v.trim().is_empty())
+            .ok_or_else(|| format!("{} needs a value\n{}", flag, USAGE))?;
+        match flag.as_str() {
+            "--url" => {
+                let url = reqwest::Url::parse(value)
+                    .map_err(|_| format!("--url must be an absolute URL\n{}", USAGE))?;
+                if url.scheme() != "https" {
+                    return Err("--url must use https (Slack requires it)".to_string());
+                }
+                base_url = Some(value.trim_end_matches('/').to_string());
+            }
+            "--name" => name = value.clone(),
+            _ => return Err(USAGE.to_string()),
+        }
+    }
+
+    match (socket_mode, base_url) {
+        (true, Some(_)) => Err(format!(
+            "--url and --socket-mode are mutually exclusive\n{}",
+            USAGE
+        )),
+        (false, None) => Err(USAGE.to_string()),
+        (_, base_url) => Ok(ManifestOptions { name, base_url }),
+    }
+}
+
+/// The manifest as YAML. Request URLs are only set outside Socket Mode.
+pub fn render(options: &ManifestOptions) -> String {
+    let url = |path: &str| {
+        options
+            .base_url
+            .as_ref()
+            .map(|base| format!("{}{}", base, path))
+    };
+    let mut lines = vec![
+        "display_information:".to_string(),
+        format!("  name: {}", quote(&options.name)),
+        format!(
+            "  description: {}",
+            quote("Incident management orchestration")
+        ),
+        "features:".to_string(),
+        "  app_home:".to_string(),
+        "    home_tab_enabled: true".to_string(),
+        "    messages_tab_enabled: false".to_string(),
+        "  bot_user:".to_string(),
+        format!("    display_name: {}", quote(&options.name)),
+        "    always_online: true".to_string(),
+        "  slash_commands:".to_string(),
+        format!("    - command: {}", SLASH_COMMAND),
+    ];
+    if let Some(url) = url(COMMANDS_PATH) {
+        lines.push(format!("      url: {}", quote(&url)));
+    }
+    lines.extend([
+        format!("      description: {}", quote("Manage incidents")),
+        format!("      usage_hint: {}", quote(&SUBCOMMANDS.join(" | "))),
+        "      should_escape: true".to_string(),
+        "oauth_config:".to_string(),
+        "  scopes:".to_string(),
+        "    bot:".to_string(),
+    ]);
+    lines.extend(BOT_SCOPES.iter().map(|scope| format!("      - {}", scope)));
+    lines.extend([
+        "settings:".to_string(),
+        "  event_subscriptions:".to_string(),
+    ]);
+    if let Some(url) = url(EVENTS_PATH) {
+        lines.push(format!("    request_url: {}", quote(&url)));
+    }
+    lines.push("    bot_events:".to_string());
+    lines.extend(BOT_EVENTS.iter().map(|event| format!("      - {}", event)));
+    lines.extend([
+        "  interactivity:".to_string(),
+        "    is_enabled: true".to_string(),
+    ]);
+    if let Some(url) = url(INTERACTIONS_PATH) {
+        lines.push(format!("    request_url: {}", quote(&url)));
+    }
+    lines.extend([
+        "  org_deploy_enabled: false".to_string(),
+        format!("  socket_mode_enabled: {}", options.base_url.is_none()),
+        "  token_rotation_enabled: false".to_string(),
+    ]);
+
+    let mut yaml = lines.join("\n");
+    yaml.push('\n');
+    yaml
+}
+
+/// A double-quoted YAML scalar (JSON string syntax is valid YAML).
+fn quote(value: &str) -> String {
+    serde_json::Value::from(value).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn test_parse_options() {
+        assert_eq!(
+            parse_options(&args(&[
+                "--url",
+                "https://bot.example.com/",
+                "--name",
+                "Pager"
+            ])),
+            Ok(ManifestOptions {
+                name: "Pager".to_string(),
+                base_url: Some("https://bot.example.com".to_string()),
+            })
+        );
+        assert_eq!(
+            parse_options(&args(&["--socket-mode"])).unwrap().base_url,
+            None
+        );
+        assert_eq!(parse_options(&[]), Err(USAGE.to_string()));
+        assert!(parse_options(&args(&["--url", "http://bot.example.com"]))
+            .unwrap_err()
+            .contains("https"));
+        assert!(parse_options(&args(&[
+            "--socket-mode",
+            "--url",
+            "https://bot.example.com"
+        ]))
+        .unwrap_err()
+        .contains("mutually exclusive"));
+    }
+
+    #[test]
+    fn test_render_uses_registered_routes_events_and_subcommands() {
+        let yaml = render(&ManifestOptions {
+            name: "Incident Bot".to_string(),
+            base_url: Some("https://bot.example.com".to_string()),
+        });
+        assert!(yaml.contains("      url: \"https://bot.example.com/slack/commands\"\n"));
+        assert!(yaml.contains("    request_url: \"https://bot.example.com/slack/events\"\n"));
+        assert!(yaml.contains("    request_url: \"https://bot.example.com/slack/interactions\"\n"));
+        assert!(yaml.contains("usage_hint: \"declare | status | update-status"));
+        assert!(yaml.contains("      - message.channels\n"));
+        assert!(yaml.contains("      - usergroups:read\n"));
+        assert!(yaml.contains("  socket_mode_enabled: false\n"));
+
+        let socket = render(&ManifestOptions {
+            name: "Incident Bot".to_string(),
+            base_url: None,
+        });
+        assert!(!socket.contains("url:"));
+        assert!(socket.contains("  socket_mode_enabled: true\n"));
+    }
+
+    #[test]
+    fn test_setup_guide_lists_every_scope() {
+        let guide = include_str!("../../SLACK_SETUP.md");
+        for scope in BOT_SCOPES {
+            assert!(guide.contains(&format!("`{}`", scope)), "{}", scope);
+        }
+    }
+}
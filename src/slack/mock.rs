@@ -0,0 +1,670 @@
+use crate::error::{IncidentError, IncidentResult};
+use crate::slack::client::{Channel, HistoryMessage, HistoryRange, SlackApi, SlackUser};
+use crate::slack::throttle::{is_rate_limited, SlackThrottle};
+use async_trait::async_trait;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+
+/// A Slack API call captured by `MockSlackClient`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SlackCall {
+    CreateConversation {
+        name: String,
+        is_private: bool,
+    },
+    ListConversations,
+    UsergroupMembers {
+        usergroup_id: String,
+    },
+    UserInfo {
+        user_id: String,
+    },
+    LookupUserByEmail {
+        email: String,
+    },
+    InviteUsers {
+        channel_id: String,
+        user_ids: Vec<String>,
+    },
+    ArchiveChannel {
+        channel_id: String,
+    },
+    SetChannelTopic {
+        channel_id: String,
+        topic: String,
+    },
+    RenameChannel {
+        channel_id: String,
+        name: String,
+    },
+    JoinConversation {
+        channel_id: String,
+    },
+    FetchChannelHistory {
+        channel_id: String,
+        range: HistoryRange,
+    },
+    PostMessage {
+        channel_id: String,
+        blocks: Vec<Value>,
+    },
+    PostThreadReply {
+        channel_id: String,
+        thread_ts: String,
+        blocks: Vec<Value>,
+        broadcast: bool,
+    },
+    UpdateMessage {
+        channel_id: String,
+        timestamp: String,
+        blocks: Vec<Value>,
+    },
+    PinMessage {
+        channel_id: String,
+        timestamp: String,
+    },
+    SendDm {
+        user_id: String,
+        blocks: Vec<Value>,
+    },
+    OpenModal {
+        trigger_id: String,
+        view: Value,
+    },
+    UpdateModal {
+        view_id: String,
+        view: Value,
+    },
+    PublishView {
+        user_id: String,
+        view: Value,
+    },
+    UploadFile {
+        channel_id: Option<String>,
+        filename: String,
+        title: String,
+        content: Vec<u8>,
+    },
+    SendFileDm {
+        user_id: String,
+        filename: String,
+        title: String,
+        content: Vec<u8>,
+    },
+    PostToResponseUrl {
+        response_url: String,
+        blocks: Vec<Value>,
+    },
+}
+
+/// In-memory `SlackApi` implementation for tests.
+///
+/// Records every call, hands out deterministic channel IDs and message
+/// timestamps, and can be told to fail specific Slack methods.
+#[derive(Default)]
+pub struct MockSlackClient {
+    calls: Mutex<Vec<SlackCall>>,
+    channels: Mutex<Vec<Channel>>,
+    // Slack method name (e.g. "chat.postMessage") -> error code to return
+    failures: Mutex<HashMap<String, String>>,
+    message_counter: Mutex<u64>,
+    // Channel ID -> messages returned by `fetch_channel_history`, oldest first
+    history: Mutex<HashMap<String, Vec<HistoryMessage>>>,
+    // User group ID -> member user IDs
+    usergroups: Mutex<HashMap<String, Vec<String>>>,
+    // User ID -> error code `send_dm` fails with for that user only
+    dm_failures: Mutex<HashMap<String, String>>,
+    // Users `user_info` reports as deactivated
+    deactivated_users: Mutex<HashSet<String>>,
+    // Email -> user ID for `lookup_user_by_email`
+    user_emails: Mutex<HashMap<String, String>>,
+    // Fed by injected `ratelimited` failures, as the real client is by 429s
+    throttle: SlackThrottle,
+}
+
+impl MockSlackClient {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use `throttle` instead of the default one, e.g. with a short cooldown.
+    pub fn with_throttle(mut self, throttle: SlackThrottle) -> Self {
+        self.throttle = throttle;
+        self
+    }
+
+    /// Make every subsequent call to `method` fail with `error_code`.
+    pub fn fail_method(&self, method: &str, error_code: &str) {
+        self.failures
+            .lock()
+            .unwrap()
+            .insert(method.to_string(), error_code.to_string());
+    }
+
+    /// Make DMs to `user_id` fail with `error_code` (e.g. `user_not_found`
+    /// for someone who left the workspace).
+    pub fn fail_dm_to(&self, user_id: &str, error_code: &str) {
+        self.dm_failures
+            .lock()
+            .unwrap()
+            .insert(user_id.to_string(), error_code.to_string());
+    }
+
+    /// Make `user_info` report `user_id` as deactivated.
+    pub fn deactivate_user(&self, user_id: &str) {
+        self.deactivated_users
+            .lock()
+            .unwrap()
+            .insert(user_id.to_string());
+    }
+
+    /// Make `lookup_user_by_email` resolve `email` to `user_id`.
+    pub fn add_user_email(&self, email: &str, user_id: &str) {
+        self.user_emails
+            .lock()
+            .unwrap()
+            .insert(email.to_string(), user_id.to_string());
+    }
+
+    /// Register a pre-existing channel so `create_conversation` reports `name_taken`.
+    pub fn add_channel(&self, id: &str, name: &str) {
+        self.channels.lock().unwrap().push(Channel {
+            id: id.to_string(),
+            name: name.to_string(),
+        });
+    }
+
+    /// Seed the messages `fetch_channel_history` returns for `channel_id`.
+    pub fn add_history(&self, channel_id: &str, messages: Vec<HistoryMessage>) {
+        self.history
+            .lock()
+            .unwrap()
+            .entry(channel_id.to_string())
+            .or_default()
+            .extend(messages);
+    }
+
+    /// Seed the members `usergroup_members` returns for `usergroup_id`.
+    pub fn add_usergroup(&self, usergroup_id: &str, members: &[&str]) {
+        self.usergroups.lock().unwrap().insert(
+            usergroup_id.to_string(),
+            members.iter().map(ToString::to_string).collect(),
+        );
+    }
+
+    pub fn calls(&self) -> Vec<SlackCall> {
+        self.calls.lock().unwrap().clone()
+    }
+
+    /// Channel IDs that received `chat.postMessage`, in call order.
+    pub fn posted_channels(&self) -> Vec<String> {
+        self.calls()
+            .into_iter()
+            .filter_map(|call| match call {
+                SlackCall::PostMessage { channel_id, .. } => Some(channel_id),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// `(channel_id, thread_ts, broadcast)` of every thread reply, in call
+    /// order.
+    pub fn thread_replies(&self) -> Vec<(String, String, bool)> {
+        self.calls()
+            .into_iter()
+            .filter_map(|call| match call {
+                SlackCall::PostThreadReply {
+                    channel_id,
+                    thread_ts,
+                    broadcast,
+                    ..
+                } => Some((channel_id, thread_ts, broadcast)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// User IDs that received a DM, in call order.
+    pub fn dm_recipients(&self) -> Vec<String> {
+        self.calls()
+            .into_iter()
+            .filter_map(|call| match call {
+                SlackCall::SendDm { user_id, .. } => Some(user_id),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Section text of every ephemeral (`response_url`) reply, one per line.
+    pub fn ephemeral_text(&self) -> String {
+        self.calls()
+            .into_iter()
+            .filter_map(|call| match call {
+                SlackCall::PostToResponseUrl { blocks, .. } => Some(blocks),
+                _ => None,
+            })
+            .flatten()
+            .filter_map(|block| block["text"]["text"].as_str().map(ToString::to_string))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Blocks of the latest ephemeral reply as a JSON string, or empty if
+    /// none was sent.
+    pub fn last_response(&self) -> String {
+        self.calls()
+            .into_iter()
+            .rev()
+            .find_map(|call| match call {
+                SlackCall::PostToResponseUrl { blocks, .. } => Some(blocks),
+                _ => None,
+            })
+            .map(|blocks| serde_json::to_string(&blocks).unwrap())
+            .unwrap_or_default()
+    }
+
+    fn record(&self, method: &str, call: SlackCall) -> IncidentResult<()> {
+        self.calls.lock().unwrap().push(call);
+
+        if let Some(error_code) = self.failures.lock().unwrap().get(method) {
+            return Err(self.failure(method, error_code, format!("API call failed: {}", method)));
+        }
+
+        Ok(())
+    }
+
+    fn failure(&self, method: &str, error_code: &str, message: String) -> IncidentError {
+        let error = IncidentError::SlackAPIError {
+            message,
+            slack_error_code: error_code.to_string(),
+        };
+        if is_rate_limited(&error) {
+            self.throttle.record_rate_limited(method, None);
+        }
+        error
+    }
+
+    fn next_ts(&self) -> String {
+        let mut counter = self.message_counter.lock().unwrap();
+        *counter += 1;
+        format!("1700000000.{:06}", *counter)
+    }
+}
+
+#[async_trait]
+impl SlackApi for MockSlackClient {
+    async fn create_conversation(&self, name: &str, is_private: bool) -> IncidentResult<String> {
+        self.record(
+            "conversations.create",
+            SlackCall::CreateConversation {
+                name: name.to_string(),
+                is_private,
+            },
+        )?;
+
+        let mut channels = self.channels.lock().unwrap();
+        if channels.iter().any(|c| c.name == name) {
+            return Err(IncidentError::SlackAPIError {
+                message: "API call failed: conversations.create".to_string(),
+                slack_error_code: "name_taken".to_string(),
+            });
+        }
+
+        let id = format!("C_MOCK_{}", channels.len() + 1);
+        channels.push(Channel {
+            id: id.clone(),
+            name: name.to_string(),
+        });
+        Ok(id)
+    }
+
+    async fn list_conversations(&self) -> IncidentResult<Vec<Channel>> {
+        self.record("conversations.list", SlackCall::ListConversations)?;
+        Ok(self.channels.lock().unwrap().clone())
+    }
+
+    async fn usergroup_members(&self, usergroup_id: &str) -> IncidentResult<Vec<String>> {
+        self.record(
+            "usergroups.users.list",
+            SlackCall::UsergroupMembers {
+                usergroup_id: usergroup_id.to_string(),
+            },
+        )?;
+
+        self.usergroups
+            .lock()
+            .unwrap()
+            .get(usergroup_id)
+            .cloned()
+            .ok_or_else(|| IncidentError::SlackAPIError {
+                message: "API call failed: usergroups.users.list".to_string(),
+                slack_error_code: "no_such_subteam".to_string(),
+            })
+    }
+
+    async fn user_info(&self, user_id: &str) -> IncidentResult<SlackUser> {
+        self.record(
+            "users.info",
+            SlackCall::UserInfo {
+                user_id: user_id.to_string(),
+            },
+        )?;
+
+        Ok(SlackUser {
+            id: user_id.to_string(),
+            deleted: self.deactivated_users.lock().unwrap().contains(user_id),
+        })
+    }
+
+    async fn lookup_user_by_email(&self, email: &str) -> IncidentResult<Option<String>> {
+        self.record(
+            "users.lookupByEmail",
+            SlackCall::LookupUserByEmail {
+                email: email.to_string(),
+            },
+        )?;
+
+        Ok(self.user_emails.lock().unwrap().get(email).cloned())
+    }
+
+    async fn fetch_channel_history(
+        &self,
+        channel_id: &str,
+        range: &HistoryRange,
+    ) -> IncidentResult<Vec<HistoryMessage>> {
+        self.record(
+            "conversations.history",
+            SlackCall::FetchChannelHistory {
+                channel_id: channel_id.to_string(),
+                range: range.clone(),
+            },
+        )?;
+
+        // Slack compares ts values numerically
+        let ts = |value: &str| value.parse::<f64>().unwrap_or(0.0);
+        let mut messages: Vec<HistoryMessage> = self
+            .history
+            .lock()
+            .unwrap()
+            .get(channel_id)
+            .cloned()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|m| range.oldest.as_deref().is_none_or(|o| ts(&m.ts) >= ts(o)))
+            .filter(|m| range.latest.as_deref().is_none_or(|l| ts(&m.ts) <= ts(l)))
+            .collect();
+        if let Some(max) = range.max_messages {
+            let skip = messages.len().saturating_sub(max);
+            messages.drain(..skip);
+        }
+        Ok(messages)
+    }
+
+    async fn invite_users(&self, channel_id: &str, user_ids: Vec<String>) -> IncidentResult<()> {
+        self.record(
+            "conversations.invite",
+            SlackCall::InviteUsers {
+                channel_id: channel_id.to_string(),
+                user_ids,
+            },
+        )
+    }
+
+    async fn archive_channel(&self, channel_id: &str) -> IncidentResult<()> {
+        self.record(
+            "conversations.archive",
+            SlackCall::ArchiveChannel {
+                channel_id: channel_id.to_string(),
+            },
+        )
+    }
+
+    async fn set_channel_topic(&self, channel_id: &str, topic: &str) -> IncidentResult<()> {
+        self.record(
+            "conversations.setTopic",
+            SlackCall::SetChannelTopic {
+                channel_id: channel_id.to_string(),
+                topic: topic.to_string(),
+            },
+        )
+    }
+
+    async fn rename_channel(&self, channel_id: &str, name: &str) -> IncidentResult<()> {
+        self.record(
+            "conversations.rename",
+            SlackCall::RenameChannel {
+                channel_id: channel_id.to_string(),
+                name: name.to_string(),
+            },
+        )?;
+        if let Some(channel) = self
+            .channels
+            .lock()
+            .unwrap()
+            .iter_mut()
+            .find(|c| c.id == channel_id)
+        {
+            channel.name = name.to_string();
+        }
+        Ok(())
+    }
+
+    async fn join_conversation(&self, channel_id: &str) -> IncidentResult<()> {
+        self.record(
+            "conversations.join",
+            SlackCall::JoinConversation {
+                channel_id: channel_id.to_string(),
+            },
+        )
+    }
+
+    async fn post_message(&self, channel_id: &str, blocks: Vec<Value>) -> IncidentResult<String> {
+        self.record(
+            "chat.postMessage",
+            SlackCall::PostMessage {
+                channel_id: channel_id.to_string(),
+                blocks,
+            },
+        )?;
+        Ok(self.next_ts())
+    }
+
+    async fn post_thread_reply(
+        &self,
+        channel_id: &str,
+        thread_ts: &str,
+        blocks: Vec<Value>,
+        broadcast: bool,
+    ) -> IncidentResult<String> {
+        self.record(
+            "chat.postMessage",
+            SlackCall::PostThreadReply {
+                channel_id: channel_id.to_string(),
+                thread_ts: thread_ts.to_string(),
+                blocks,
+                broadcast,
+            },
+        )?;
+        Ok(self.next_ts())
+    }
+
+    async fn update_message(
+        &self,
+        channel_id: &str,
+        timestamp: &str,
+        blocks: Vec<Value>,
+    ) -> IncidentResult<()> {
+        self.record(
+            "chat.update",
+            SlackCall::UpdateMessage {
+                channel_id: channel_id.to_string(),
+                timestamp: timestamp.to_string(),
+                blocks,
+            },
+        )
+    }
+
+    async fn pin_message(&self, channel_id: &str, timestamp: &str) -> IncidentResult<()> {
+        self.record(
+            "pins.add",
+            SlackCall::PinMessage {
+                channel_id: channel_id.to_string(),
+                timestamp: timestamp.to_string(),
+            },
+        )
+    }
+
+    async fn send_dm(&self, user_id: &str, blocks: Vec<Value>) -> IncidentResult<()> {
+        self.record(
+            "conversations.open",
+            SlackCall::SendDm {
+                user_id: user_id.to_string(),
+                blocks,
+            },
+        )?;
+
+        match self.dm_failures.lock().unwrap().get(user_id) {
+            Some(error_code) => Err(self.failure(
+                "conversations.open",
+                error_code,
+                format!("API call failed: conversations.open for {}", user_id),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    async fn open_modal(&self, trigger_id: &str, view: Value) -> IncidentResult<()> {
+        self.record(
+            "views.open",
+            SlackCall::OpenModal {
+                trigger_id: trigger_id.to_string(),
+                view,
+            },
+        )
+    }
+
+    async fn update_modal(&self, view_id: &str, view: Value) -> IncidentResult<()> {
+        self.record(
+            "views.update",
+            SlackCall::UpdateModal {
+                view_id: view_id.to_string(),
+                view,
+            },
+        )
+    }
+
+    async fn publish_view(&self, user_id: &str, view: Value) -> IncidentResult<()> {
+        self.record(
+            "views.publish",
+            SlackCall::PublishView {
+                user_id: user_id.to_string(),
+                view,
+            },
+        )
+    }
+
+    async fn upload_file(
+        &self,
+        channel_id: Option<&str>,
+        filename: &str,
+        title: &str,
+        content: Vec<u8>,
+    ) -> IncidentResult<String> {
+        self.record(
+            "files.getUploadURLExternal",
+            SlackCall::UploadFile {
+                channel_id: channel_id.map(str::to_string),
+                filename: filename.to_string(),
+                title: title.to_string(),
+                content,
+            },
+        )?;
+        Ok(format!("F_MOCK_{}", self.next_ts().replace('.', "")))
+    }
+
+    async fn send_file_dm(
+        &self,
+        user_id: &str,
+        filename: &str,
+        title: &str,
+        content: Vec<u8>,
+    ) -> IncidentResult<()> {
+        self.record(
+            "files.getUploadURLExternal",
+            SlackCall::SendFileDm {
+                user_id: user_id.to_string(),
+                filename: filename.to_string(),
+                title: title.to_string(),
+                content,
+            },
+        )
+    }
+
+    async fn post_to_response_url(
+        &self,
+        response_url: &str,
+        blocks: Vec<Value>,
+    ) -> IncidentResult<()> {
+        self.record(
+            "response_url",
+            SlackCall::PostToResponseUrl {
+                response_url: response_url.to_string(),
+                blocks,
+            },
+        )
+    }
+
+    fn throttle(&self) -> &SlackThrottle {
+        &self.throttle
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_records_calls_and_returns_increasing_timestamps() {
+        let mock = MockSlackClient::new();
+
+        let ts1 = mock.post_message("C1", vec![json!({})]).await.unwrap();
+        let ts2 = mock.post_message("C2", vec![]).await.unwrap();
+
+        assert!(ts2 > ts1);
+        assert_eq!(mock.posted_channels(), vec!["C1", "C2"]);
+    }
+
+    #[tokio::test]
+    async fn test_create_conversation_reports_name_taken() {
+        let mock = MockSlackClient::new();
+        mock.add_channel("C_EXISTING", "inc-20240101-vpn");
+
+        let err = mock
+            .create_conversation("inc-20240101-vpn", false)
+            .await
+            .expect_err("Expected name_taken");
+        assert!(matches!(
+            err,
+            IncidentError::SlackAPIError { ref slack_error_code, .. } if slack_error_code == "name_taken"
+        ));
+
+        let id = mock
+            .create_conversation("inc-20240101-vpn-2", false)
+            .await
+            .unwrap();
+        assert_eq!(id, "C_MOCK_2");
+    }
+
+    #[tokio::test]
+    async fn test_fail_method_only_affects_that_method() {
+        let mock = MockSlackClient::new();
+        mock.fail_method("conversations.open", "user_not_found");
+
+        assert!(mock.send_dm("U1", vec![]).await.is_err());
+        assert!(mock.post_message("C1", vec![]).await.is_ok());
+        // Failed calls are still recorded
+        assert_eq!(mock.dm_recipients(), vec!["U1"]);
+    }
+}
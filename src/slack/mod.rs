@@ -1,5 +1,12 @@
 pub mod blocks;
 pub mod client;
 pub mod events;
+pub mod home;
+pub mod manifest;
+pub mod mock;
 pub mod modals;
+pub mod rate_limit;
+pub mod socket_mode;
+pub mod throttle;
 pub mod verification;
+pub mod websocket;
@@ -1,56 +1,173 @@
-use crate::db::models::IncidentTemplate;
+use crate::db::models::{
+    DeclareDraft, Incident, IncidentStatus, IncidentTemplate, Premortem, Severity,
+};
+use crate::utils::{placeholders, severity};
 use serde_json::{json, Value};
 
-pub fn declare_incident_modal(services: &[String], templates: &[IncidentTemplate]) -> Value {
-    let service_options: Vec<Value> = services
-        .iter()
-        .map(|s| {
-            json!({
-                "text": {
-                    "type": "plain_text",
-                    "text": s,
-                },
-                "value": s,
-            })
-        })
-        .collect();
+pub const DECLARE_MODAL_CALLBACK_ID: &str = "declare_incident_modal";
+/// `private_metadata` marking a quiet (security) declare modal.
+pub const QUIET_DECLARE_METADATA: &str = "quiet";
+/// `private_metadata` prefix for `/incident attach`, followed by the channel ID.
+pub const ATTACH_METADATA_PREFIX: &str = "attach:";
+/// `private_metadata` prefix for declaring a triage, followed by its ID.
+pub const TRIAGE_METADATA_PREFIX: &str = "triage:";
+/// Template picker in the declare modal; choosing one rebuilds the modal.
+pub const TEMPLATE_SELECT_ACTION: &str = "template_select";
+/// Service picker in the declare modal; choosing one suggests the on-call
+/// as commander.
+pub const SERVICE_SELECT_ACTION: &str = "service_select";
+/// Block ID prefix of a template placeholder input, followed by its name.
+pub const TEMPLATE_FIELD_BLOCK_PREFIX: &str = "template_field_";
+pub const TEMPLATE_FIELD_ACTION: &str = "template_field_input";
+/// Update Status modal; `private_metadata` is the incident ID.
+pub const UPDATE_STATUS_MODAL_CALLBACK_ID: &str = "update_status_modal";
+/// Template create/edit modal; `private_metadata` is the template ID when
+/// editing, empty when creating.
+pub const TEMPLATE_MODAL_CALLBACK_ID: &str = "template_modal";
+/// `private_metadata` prefix for a template created from a resolved
+/// incident, followed by the incident ID.
+pub const TEMPLATE_FROM_INCIDENT_METADATA_PREFIX: &str = "incident:";
+/// First step of `/incident premortem`: the launch and its window;
+/// `private_metadata` is the service.
+pub const PREMORTEM_LAUNCH_CALLBACK_ID: &str = "premortem_launch_modal";
+/// Each following step records one failure mode; `private_metadata` is the
+/// pre-mortem ID. Closing it ("Done") finishes the pre-mortem.
+pub const PREMORTEM_RISK_CALLBACK_ID: &str = "premortem_risk_modal";
+/// Pre-mortem failure mode likelihoods, as stored.
+pub const PREMORTEM_LIKELIHOODS: &[&str] = &["high", "medium", "low"];
+
+fn option(text: &str, value: &str) -> Value {
+    json!({
+        "text": {
+            "type": "plain_text",
+            "text": text,
+        },
+        "value": value,
+    })
+}
+
+/// The declare modal, prefilled from `draft` when resuming one. Inputs
+/// dispatch `block_actions` as they change so the draft can be saved. The
+/// selected template's `{{placeholders}}` get an input each, and inputs it
+/// has defaults for (title, severity, service) become optional.
+pub fn declare_incident_modal(
+    services: &[String],
+    templates: &[IncidentTemplate],
+    draft: Option<&DeclareDraft>,
+) -> Value {
+    let draft = draft.cloned().unwrap_or_default();
+
+    let service_options: Vec<Value> = services.iter().map(|s| option(s, s)).collect();
 
     let template_options: Vec<Value> = templates
         .iter()
-        .map(|t| {
-            json!({
-                "text": {
-                    "type": "plain_text",
-                    "text": &t.title,
-                },
-                "value": &t.name,
-            })
-        })
+        .map(|t| option(&t.title, &t.name))
         .collect();
 
+    let template = draft
+        .template
+        .as_ref()
+        .and_then(|name| templates.iter().find(|t| &t.name == name));
+    // Left empty, these fall back to the template on submission
+    let template_service = template
+        .and_then(|t| t.affected_service.as_ref())
+        .filter(|s| services.contains(s));
+
     // Build blocks array
     let mut blocks = Vec::new();
 
     // Add template selector if templates exist
     if !templates.is_empty() {
+        let mut element = json!({
+            "type": "static_select",
+            "action_id": TEMPLATE_SELECT_ACTION,
+            "placeholder": {
+                "type": "plain_text",
+                "text": "Select a template or fill manually",
+            },
+            "options": template_options,
+        });
+        if let Some(template) = template {
+            element["initial_option"] = option(&template.title, &template.name);
+        }
         blocks.push(json!({
             "type": "input",
             "block_id": "template_block",
+            "dispatch_action": true,
             "label": {
                 "type": "plain_text",
                 "text": "Use Template (Optional)",
             },
-            "element": {
-                "type": "static_select",
-                "action_id": "template_select",
-                "placeholder": {
-                    "type": "plain_text",
-                    "text": "Select a template or fill manually",
-                },
-                "options": template_options,
-            },
+            "element": element,
             "optional": true,
         }));
+
+        for name in template.map(|t| t.placeholders()).unwrap_or_default() {
+            let mut field_element = json!({
+                "type": "plain_text_input",
+                "action_id": TEMPLATE_FIELD_ACTION,
+                "max_length": 50,
+                "dispatch_action_config": {
+                    "trigger_actions_on": ["on_character_entered"],
+                },
+            });
+            if let Some(value) = draft.fields.get(&name) {
+                field_element["initial_value"] = json!(value);
+            }
+            blocks.push(json!({
+                "type": "input",
+                "block_id": format!("{}{}", TEMPLATE_FIELD_BLOCK_PREFIX, name),
+                "dispatch_action": true,
+                "label": {
+                    "type": "plain_text",
+                    "text": placeholders::label(&name),
+                },
+                "hint": {
+                    "type": "plain_text",
+                    "text": format!("Fills {{{{{}}}}} in the title", name),
+                },
+                "element": field_element,
+            }));
+        }
+    }
+
+    let mut title_element = json!({
+        "type": "plain_text_input",
+        "action_id": "title_input",
+        "placeholder": {
+            "type": "plain_text",
+            "text": "e.g., Okta SSO outage",
+        },
+        "max_length": 100,
+        "dispatch_action_config": {
+            "trigger_actions_on": ["on_character_entered"],
+        },
+    });
+    if let Some(title) = &draft.title {
+        title_element["initial_value"] = json!(title);
+    }
+
+    let severity = draft
+        .severity
+        .as_deref()
+        .and_then(severity::find)
+        .unwrap_or_else(|| severity::for_tier(template.map_or(Severity::P2, |t| t.severity)));
+
+    let mut service_element = json!({
+        "type": "static_select",
+        "action_id": SERVICE_SELECT_ACTION,
+        "options": service_options,
+    });
+    if let Some(service) = draft.service.as_ref().filter(|s| services.contains(s)) {
+        service_element["initial_option"] = option(service, service);
+    }
+
+    let mut commander_element = json!({
+        "type": "users_select",
+        "action_id": "commander_select",
+    });
+    if let Some(commander_id) = &draft.commander_id {
+        commander_element["initial_user"] = json!(commander_id);
     }
 
     // Add standard fields
@@ -58,23 +175,18 @@ pub fn declare_incident_modal(services: &[String], templates: &[IncidentTemplate
         json!({
             "type": "input",
             "block_id": "title_block",
+            "dispatch_action": true,
             "label": {
                 "type": "plain_text",
                 "text": "Incident Title",
             },
-            "element": {
-                "type": "plain_text_input",
-                "action_id": "title_input",
-                "placeholder": {
-                    "type": "plain_text",
-                    "text": "e.g., Okta SSO outage",
-                },
-                "max_length": 100,
-            },
+            "element": title_element,
+            "optional": template.is_some(),
         }),
         json!({
             "type": "input",
             "block_id": "severity_block",
+            "dispatch_action": true,
             "label": {
                 "type": "plain_text",
                 "text": "Severity",
@@ -82,76 +194,41 @@ pub fn declare_incident_modal(services: &[String], templates: &[IncidentTemplate
             "element": {
                 "type": "static_select",
                 "action_id": "severity_select",
-                "initial_option": {
-                    "text": {
-                        "type": "plain_text",
-                        "text": "P2 (High)",
-                    },
-                    "value": "P2",
-                },
-                "options": [
-                    {
-                        "text": {
-                            "type": "plain_text",
-                            "text": "P1 (Critical)",
-                        },
-                        "value": "P1",
-                    },
-                    {
-                        "text": {
-                            "type": "plain_text",
-                            "text": "P2 (High)",
-                        },
-                        "value": "P2",
-                    },
-                    {
-                        "text": {
-                            "type": "plain_text",
-                            "text": "P3 (Medium)",
-                        },
-                        "value": "P3",
-                    },
-                    {
-                        "text": {
-                            "type": "plain_text",
-                            "text": "P4 (Low)",
-                        },
-                        "value": "P4",
-                    },
-                ],
+                "initial_option": option(&severity.label, &severity.code),
+                "options": severity::levels()
+                    .iter()
+                    .map(|level| option(&level.label, &level.code))
+                    .collect::<Vec<_>>(),
             },
+            "optional": template.is_some(),
         }),
         json!({
             "type": "input",
             "block_id": "service_block",
+            "dispatch_action": true,
             "label": {
                 "type": "plain_text",
                 "text": "Affected Service",
             },
-            "element": {
-                "type": "static_select",
-                "action_id": "service_select",
-                "options": service_options,
-            },
+            "element": service_element,
+            "optional": template_service.is_some(),
         }),
         json!({
             "type": "input",
             "block_id": "commander_block",
+            "dispatch_action": true,
             "label": {
                 "type": "plain_text",
                 "text": "Incident Commander",
             },
-            "element": {
-                "type": "users_select",
-                "action_id": "commander_select",
-            },
+            "element": commander_element,
             "optional": true,
         }),
     ]);
 
     json!({
         "type": "modal",
-        "callback_id": "declare_incident_modal",
+        "callback_id": DECLARE_MODAL_CALLBACK_ID,
         "title": {
             "type": "plain_text",
             "text": "Declare Incident",
@@ -167,3 +244,518 @@ pub fn declare_incident_modal(services: &[String], templates: &[IncidentTemplate
         "blocks": blocks,
     })
 }
+
+/// The declare modal for `/incident declare quiet`: same inputs, flagged via
+/// `private_metadata` so the submission skips broadcasts.
+pub fn quiet_declare_modal(
+    services: &[String],
+    templates: &[IncidentTemplate],
+    draft: Option<&DeclareDraft>,
+) -> Value {
+    let mut modal = declare_incident_modal(services, templates, draft);
+    modal["title"]["text"] = json!("Quiet Declare");
+    modal["private_metadata"] = json!(QUIET_DECLARE_METADATA);
+    if let Some(blocks) = modal["blocks"].as_array_mut() {
+        blocks.insert(
+            0,
+            json!({
+                "type": "context",
+                "elements": [{
+                    "type": "mrkdwn",
+                    "text": "🔒 *Security incident.* The channel is private and only the commander and the security group are brought in. No broadcast notifications, Statuspage updates or webhooks.",
+                }],
+            }),
+        );
+    }
+    modal
+}
+
+/// The declare modal for `/incident attach`: same inputs, with the channel to
+/// bind carried in `private_metadata` so no new channel is created.
+pub fn attach_incident_modal(
+    services: &[String],
+    templates: &[IncidentTemplate],
+    channel_id: &str,
+    draft: Option<&DeclareDraft>,
+) -> Value {
+    let mut modal = declare_incident_modal(services, templates, draft);
+    modal["title"]["text"] = json!("Attach Incident");
+    modal["submit"]["text"] = json!("Attach");
+    modal["private_metadata"] = json!(format!("{}{}", ATTACH_METADATA_PREFIX, channel_id));
+    if let Some(blocks) = modal["blocks"].as_array_mut() {
+        blocks.insert(
+            0,
+            json!({
+                "type": "context",
+                "elements": [{
+                    "type": "mrkdwn",
+                    "text": format!("📎 The incident will run in <#{}> instead of a new channel.", channel_id),
+                }],
+            }),
+        );
+    }
+    modal
+}
+
+/// The declare modal for a triage's "Declare incident" button: same inputs,
+/// prefilled with the triage's title, with its ID carried in
+/// `private_metadata` so the submission upgrades it rather than inserting.
+pub fn triage_declare_modal(
+    services: &[String],
+    templates: &[IncidentTemplate],
+    incident: &Incident,
+    draft: Option<&DeclareDraft>,
+) -> Value {
+    let title_draft;
+    let draft = match draft {
+        Some(draft) => draft,
+        None => {
+            title_draft = DeclareDraft {
+                title: Some(incident.title.clone()),
+                ..DeclareDraft::default()
+            };
+            &title_draft
+        }
+    };
+    let mut modal = declare_incident_modal(services, templates, Some(draft));
+    modal["title"]["text"] = json!("Declare Triage");
+    modal["private_metadata"] = json!(format!("{}{}", TRIAGE_METADATA_PREFIX, incident.id));
+    if let Some(blocks) = modal["blocks"].as_array_mut() {
+        blocks.insert(
+            0,
+            json!({
+                "type": "context",
+                "elements": [{
+                    "type": "mrkdwn",
+                    "text": format!("🔍 Declaring triage *{}*. It gets a channel, notifications and a new number.", incident.reference()),
+                }],
+            }),
+        );
+    }
+    modal
+}
+
+/// Opened by the "Update Status" button: a new status and/or an internal
+/// status update, prefilled with the incident's current status. Only the
+/// current status and the states the state machine allows next are offered;
+/// resolving has its own button.
+pub fn update_status_modal(incident: &Incident) -> Value {
+    let statuses: Vec<IncidentStatus> = std::iter::once(incident.status)
+        .chain(incident.status.valid_transitions().iter().copied())
+        .filter(|s| !matches!(s, IncidentStatus::Declared | IncidentStatus::Resolved))
+        .collect();
+    let status_option = |status: &IncidentStatus| {
+        let label = status.as_db_str();
+        option(
+            &format!("{}{}", label[..1].to_uppercase(), &label[1..]),
+            label,
+        )
+    };
+
+    let mut status_element = json!({
+        "type": "static_select",
+        "action_id": "status_select",
+        "options": statuses.iter().map(status_option).collect::<Vec<_>>(),
+    });
+    if statuses.contains(&incident.status) {
+        status_element["initial_option"] = status_option(&incident.status);
+    }
+
+    json!({
+        "type": "modal",
+        "callback_id": UPDATE_STATUS_MODAL_CALLBACK_ID,
+        "private_metadata": incident.id.to_string(),
+        "title": {
+            "type": "plain_text",
+            "text": "Update Status",
+        },
+        "submit": {
+            "type": "plain_text",
+            "text": "Update",
+        },
+        "close": {
+            "type": "plain_text",
+            "text": "Cancel",
+        },
+        "blocks": [
+            {
+                "type": "input",
+                "block_id": "status_block",
+                "label": {
+                    "type": "plain_text",
+                    "text": "Status",
+                },
+                "element": status_element,
+            },
+            {
+                "type": "input",
+                "block_id": "message_block",
+                "label": {
+                    "type": "plain_text",
+                    "text": "Status Update",
+                },
+                "element": {
+                    "type": "plain_text_input",
+                    "action_id": "message_input",
+                    "multiline": true,
+                    "placeholder": {
+                        "type": "plain_text",
+                        "text": "e.g., Failover to the replica is complete",
+                    },
+                },
+                "optional": true,
+            },
+            {
+                "type": "context",
+                "elements": [{
+                    "type": "mrkdwn",
+                    "text": "Updates posted here are internal. Use `/incident status --public` to update the status page.",
+                }],
+            },
+        ],
+    })
+}
+
+/// Values a template modal opens with: a stored template's, to edit, or
+/// ones suggested from a resolved incident.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TemplateFields {
+    pub name: String,
+    pub title: String,
+    pub severity: Severity,
+    pub affected_service: Option<String>,
+    pub description: Option<String>,
+}
+
+impl From<&IncidentTemplate> for TemplateFields {
+    fn from(template: &IncidentTemplate) -> Self {
+        Self {
+            name: template.name.clone(),
+            title: template.title.clone(),
+            severity: template.severity,
+            affected_service: template.affected_service.clone(),
+            description: template.description.clone(),
+        }
+    }
+}
+
+/// `/incident template create|edit`: a blank form, or `template`'s fields to
+/// edit. Names are fixed once created, so editing shows the name as context.
+pub fn template_modal(services: &[String], template: Option<&IncidentTemplate>) -> Value {
+    let fields = template.map(TemplateFields::from);
+    let mut modal = template_form(services, fields.as_ref(), template.is_some());
+    modal["private_metadata"] = json!(template.map(|t| t.id.to_string()).unwrap_or_default());
+    modal
+}
+
+/// "Create template" under the prompt sent on resolution: a new template
+/// prefilled with `suggestion`, inferred from `incident`.
+pub fn template_from_incident_modal(
+    services: &[String],
+    incident: &Incident,
+    suggestion: &TemplateFields,
+) -> Value {
+    let mut modal = template_form(services, Some(suggestion), false);
+    modal["private_metadata"] = json!(format!(
+        "{}{}",
+        TEMPLATE_FROM_INCIDENT_METADATA_PREFIX, incident.id
+    ));
+    if let Some(blocks) = modal["blocks"].as_array_mut() {
+        blocks.insert(
+            0,
+            json!({
+                "type": "context",
+                "elements": [{
+                    "type": "mrkdwn",
+                    "text": format!(
+                        "Suggested from *{}*. Replace anything specific to it, like a region or host, with a `{{{{placeholder}}}}`.",
+                        incident.reference()
+                    ),
+                }],
+            }),
+        );
+    }
+    modal
+}
+
+fn template_form(services: &[String], fields: Option<&TemplateFields>, editing: bool) -> Value {
+    let input = |block_id: &str, label: &str, element: Value, optional: bool| {
+        json!({
+            "type": "input",
+            "block_id": block_id,
+            "label": {
+                "type": "plain_text",
+                "text": label,
+            },
+            "element": element,
+            "optional": optional,
+        })
+    };
+
+    let mut blocks = Vec::new();
+    match fields.filter(|_| editing) {
+        Some(fields) => blocks.push(json!({
+            "type": "context",
+            "elements": [{
+                "type": "mrkdwn",
+                "text": format!("Template `{}`", fields.name),
+            }],
+        })),
+        None => {
+            let mut name_element = json!({
+                "type": "plain_text_input",
+                "action_id": "name_input",
+                "max_length": 50,
+                "placeholder": {
+                    "type": "plain_text",
+                    "text": "e.g., database-outage",
+                },
+            });
+            if let Some(fields) = fields {
+                name_element["initial_value"] = json!(fields.name);
+            }
+            blocks.push(input("name_block", "Name", name_element, false));
+        }
+    }
+
+    let mut title_element = json!({
+        "type": "plain_text_input",
+        "action_id": "title_input",
+        "max_length": 100,
+        "placeholder": {
+            "type": "plain_text",
+            "text": "e.g., {{region}} database outage",
+        },
+    });
+    if let Some(fields) = fields {
+        title_element["initial_value"] = json!(fields.title);
+    }
+    blocks.push(input("title_block", "Incident Title", title_element, false));
+
+    let severities = [Severity::P1, Severity::P2, Severity::P3, Severity::P4];
+    let severity = fields.map(|f| f.severity).unwrap_or(Severity::P2);
+    blocks.push(input(
+        "severity_block",
+        "Severity",
+        json!({
+            "type": "static_select",
+            "action_id": "severity_select",
+            "initial_option": option(severity.label(), severity.as_db_str()),
+            "options": severities
+                .iter()
+                .map(|s| option(s.label(), s.as_db_str()))
+                .collect::<Vec<_>>(),
+        }),
+        false,
+    ));
+
+    // Keep a service no longer in SERVICES selectable so editing doesn't drop it
+    let current_service = fields.and_then(|f| f.affected_service.as_deref());
+    let mut service_names: Vec<&str> = services.iter().map(String::as_str).collect();
+    if let Some(service) = current_service.filter(|s| !service_names.contains(s)) {
+        service_names.push(service);
+    }
+    let mut service_element = json!({
+        "type": "static_select",
+        "action_id": "service_select",
+        "options": service_names
+            .iter()
+            .map(|s| option(s, s))
+            .collect::<Vec<_>>(),
+    });
+    if let Some(service) = current_service {
+        service_element["initial_option"] = option(service, service);
+    }
+    blocks.push(input(
+        "service_block",
+        "Affected Service",
+        service_element,
+        true,
+    ));
+
+    let mut description_element = json!({
+        "type": "plain_text_input",
+        "action_id": "description_input",
+        "multiline": true,
+        "max_length": 1000,
+    });
+    if let Some(description) = fields.and_then(|f| f.description.as_deref()) {
+        description_element["initial_value"] = json!(description);
+    }
+    blocks.push(input(
+        "description_block",
+        "Description",
+        description_element,
+        true,
+    ));
+    blocks.push(json!({
+        "type": "context",
+        "elements": [{
+            "type": "mrkdwn",
+            "text": "`{{placeholders}}` in the title or description become extra inputs in the declare modal.",
+        }],
+    }));
+
+    json!({
+        "type": "modal",
+        "callback_id": TEMPLATE_MODAL_CALLBACK_ID,
+        "private_metadata": "",
+        "title": {
+            "type": "plain_text",
+            "text": if editing { "Edit Template" } else { "New Template" },
+        },
+        "submit": {
+            "type": "plain_text",
+            "text": "Save",
+        },
+        "close": {
+            "type": "plain_text",
+            "text": "Cancel",
+        },
+        "blocks": blocks,
+    })
+}
+
+/// `/incident premortem <service>`, step one: name the launch and when it
+/// happens. Incidents declared for `service` in the window get linked.
+pub fn premortem_launch_modal(service: &str) -> Value {
+    json!({
+        "type": "modal",
+        "callback_id": PREMORTEM_LAUNCH_CALLBACK_ID,
+        "private_metadata": service,
+        "title": { "type": "plain_text", "text": "Pre-mortem" },
+        "submit": { "type": "plain_text", "text": "Next" },
+        "close": { "type": "plain_text", "text": "Cancel" },
+        "blocks": [
+            {
+                "type": "context",
+                "elements": [{
+                    "type": "mrkdwn",
+                    "text": format!(
+                        "Before a risky launch of *{}*, write down how it could go wrong. Incidents declared for the service during the launch window are linked back to this pre-mortem.",
+                        service
+                    ),
+                }],
+            },
+            {
+                "type": "input",
+                "block_id": "launch_block",
+                "label": { "type": "plain_text", "text": "Launch" },
+                "element": {
+                    "type": "plain_text_input",
+                    "action_id": "launch_input",
+                    "max_length": 100,
+                    "placeholder": {
+                        "type": "plain_text",
+                        "text": "e.g., Checkout v2 rollout",
+                    },
+                },
+            },
+            {
+                "type": "input",
+                "block_id": "start_block",
+                "label": { "type": "plain_text", "text": "Launch window starts" },
+                "element": {
+                    "type": "datetimepicker",
+                    "action_id": "start_input",
+                },
+            },
+            {
+                "type": "input",
+                "block_id": "hours_block",
+                "label": { "type": "plain_text", "text": "Window length (hours)" },
+                "element": {
+                    "type": "number_input",
+                    "action_id": "hours_input",
+                    "is_decimal_allowed": false,
+                    "min_value": "1",
+                    "max_value": "720",
+                    "initial_value": "24",
+                },
+            },
+        ],
+    })
+}
+
+/// A following step: one anticipated failure mode of `premortem`, the
+/// `number`th. Ticking "add another" opens the next step on submit.
+pub fn premortem_risk_modal(premortem: &Premortem, number: usize) -> Value {
+    let likelihoods: Vec<Value> = PREMORTEM_LIKELIHOODS
+        .iter()
+        .map(|l| option(&capitalize(l), l))
+        .collect();
+    json!({
+        "type": "modal",
+        "callback_id": PREMORTEM_RISK_CALLBACK_ID,
+        "private_metadata": premortem.id.to_string(),
+        "title": { "type": "plain_text", "text": format!("Failure mode {}", number) },
+        "submit": { "type": "plain_text", "text": "Save" },
+        "close": { "type": "plain_text", "text": "Done" },
+        "notify_on_close": true,
+        "blocks": [
+            {
+                "type": "context",
+                "elements": [{
+                    "type": "mrkdwn",
+                    "text": format!(
+                        "*{}* ({}). Imagine the launch has gone badly: what happened?",
+                        premortem.launch_name, premortem.service
+                    ),
+                }],
+            },
+            {
+                "type": "input",
+                "block_id": "failure_block",
+                "label": { "type": "plain_text", "text": "What could go wrong?" },
+                "element": {
+                    "type": "plain_text_input",
+                    "action_id": "failure_input",
+                    "multiline": true,
+                    "max_length": 500,
+                },
+            },
+            {
+                "type": "input",
+                "block_id": "likelihood_block",
+                "label": { "type": "plain_text", "text": "Likelihood" },
+                "element": {
+                    "type": "static_select",
+                    "action_id": "likelihood_select",
+                    "initial_option": likelihoods[1],
+                    "options": likelihoods,
+                },
+            },
+            {
+                "type": "input",
+                "block_id": "mitigation_block",
+                "label": { "type": "plain_text", "text": "Mitigation or early warning sign" },
+                "optional": true,
+                "element": {
+                    "type": "plain_text_input",
+                    "action_id": "mitigation_input",
+                    "multiline": true,
+                    "max_length": 1000,
+                },
+            },
+            {
+                "type": "input",
+                "block_id": "another_block",
+                "label": { "type": "plain_text", "text": "Next" },
+                "optional": true,
+                "element": {
+                    "type": "checkboxes",
+                    "action_id": "another_input",
+                    "options": [option("Add another failure mode after this one", "another")],
+                },
+            },
+        ],
+    })
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
@@ -0,0 +1,243 @@
+//! Per-user rate limit on the slash command and interaction Request URLs, so a
+//! command storm during a major outage can't exhaust the database pool.
+//! Applied as a route layer in `main.rs`; Socket Mode isn't limited.
+//! Modal submissions and the declare modal's draft capture (dispatched on
+//! every keystroke) aren't counted: Slack closes a modal whose submission
+//! gets an empty reply, which would drop the declaration.
+
+use crate::app_state::AppState;
+use crate::metrics::metrics;
+use crate::slack::blocks;
+use crate::slack::modals::DECLARE_MODAL_CALLBACK_ID;
+use crate::slack::verification::verify_slack_signature;
+use axum::body::Body;
+use axum::extract::{Request, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Instant;
+use tracing::{error, warn};
+
+/// axum's default body limit, which the handlers would apply anyway.
+const MAX_BODY_BYTES: usize = 2 * 1024 * 1024;
+
+/// Buckets kept before full ones are dropped.
+const MAX_TRACKED_SENDERS: usize = 10_000;
+
+/// Token bucket per sender holding up to a minute's allowance, refilled
+/// continuously, so short bursts pass while a steady flood is held to the
+/// per-minute rate.
+pub struct SlackRateLimiter {
+    per_minute: u32,
+    buckets: Mutex<HashMap<String, Bucket>>,
+}
+
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl SlackRateLimiter {
+    /// `per_minute` of 0 lets everything through.
+    pub fn new(per_minute: u32) -> Self {
+        Self {
+            per_minute,
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Spend one request of `key`'s allowance; `false` once it's used up.
+    pub fn allow(&self, key: &str) -> bool {
+        self.allow_at(key, Instant::now())
+    }
+
+    fn allow_at(&self, key: &str, now: Instant) -> bool {
+        if self.per_minute == 0 {
+            return true;
+        }
+        let capacity = f64::from(self.per_minute);
+        let refill_per_second = capacity / 60.0;
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+
+        if buckets.len() >= MAX_TRACKED_SENDERS {
+            buckets.retain(|_, bucket| {
+                bucket.tokens + now.duration_since(bucket.updated).as_secs_f64() * refill_per_second
+                    < capacity
+            });
+        }
+
+        let bucket = buckets.entry(key.to_string()).or_insert(Bucket {
+            tokens: capacity,
+            updated: now,
+        });
+        let elapsed = now.duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * refill_per_second).min(capacity);
+        bucket.updated = now;
+        if bucket.tokens < 1.0 {
+            return false;
+        }
+        bucket.tokens -= 1.0;
+        true
+    }
+}
+
+/// Who sent a verified command or interaction.
+struct Sender {
+    key: String,
+    user_id: String,
+    /// Interactions have no synchronous reply; they're told here instead
+    response_url: Option<String>,
+    interaction: bool,
+}
+
+/// Middleware for `/slack/commands` and `/slack/interactions`. Requests
+/// failing signature verification or parsing pass through untouched for the
+/// handler to reject, so forged requests can't spend a user's allowance.
+pub async fn limit_slack_requests(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if state.config.slack_rate_limit_per_minute == 0 {
+        return next.run(request).await;
+    }
+    let (parts, body) = request.into_parts();
+    let bytes = match axum::body::to_bytes(body, MAX_BODY_BYTES).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            error!("Failed to read Slack request body: {}", e);
+            return (StatusCode::BAD_REQUEST, "Invalid request").into_response();
+        }
+    };
+
+    if let Some(sender) = verified_sender(&state, &parts.headers, &bytes) {
+        if !state.slack_rate_limiter.allow(&sender.key) {
+            return reject(&state, sender);
+        }
+    }
+    next.run(Request::from_parts(parts, Body::from(bytes)))
+        .await
+}
+
+fn verified_sender(state: &AppState, headers: &HeaderMap, body: &[u8]) -> Option<Sender> {
+    let body = std::str::from_utf8(body).ok()?;
+    let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+    verify_slack_signature(
+        &state.config.slack_signing_secret,
+        header("X-Slack-Request-Timestamp")?,
+        body,
+        header("X-Slack-Signature")?,
+    )
+    .ok()?;
+
+    let form: HashMap<String, String> = serde_urlencoded::from_str(body).ok()?;
+    match form.get("payload") {
+        // Interaction: the fields are inside the JSON payload
+        Some(payload) => {
+            let payload: Value = serde_json::from_str(payload).ok()?;
+            if exempt(&payload) {
+                return None;
+            }
+            let user_id = payload["user"]["id"].as_str()?.to_string();
+            let team_id = payload["team"]["id"].as_str().unwrap_or_default();
+            Some(Sender {
+                key: format!("{}:{}", team_id, user_id),
+                user_id,
+                response_url: payload["response_url"].as_str().map(ToString::to_string),
+                interaction: true,
+            })
+        }
+        None => {
+            let user_id = form.get("user_id")?.clone();
+            let team_id = form.get("team_id").map(String::as_str).unwrap_or_default();
+            Some(Sender {
+                key: format!("{}:{}", team_id, user_id),
+                user_id,
+                response_url: None,
+                interaction: false,
+            })
+        }
+    }
+}
+
+/// Interactions let through regardless of the sender's allowance.
+fn exempt(payload: &Value) -> bool {
+    match payload["type"].as_str() {
+        Some("view_submission") => true,
+        Some("block_actions") => payload["view"]["callback_id"] == DECLARE_MODAL_CALLBACK_ID,
+        _ => false,
+    }
+}
+
+fn reject(state: &AppState, sender: Sender) -> Response {
+    let kind = if sender.interaction {
+        "interaction"
+    } else {
+        "command"
+    };
+    warn!("Rate limited Slack {} from {}", kind, sender.user_id);
+    metrics().record_slack_rate_limited(kind);
+    let message = format!(
+        "⏳ Easy there: that's more than {} commands and clicks in the last minute. \
+         Give it a few seconds and try again.",
+        state.config.slack_rate_limit_per_minute
+    );
+
+    if !sender.interaction {
+        // The response body is shown to the user as an ephemeral message
+        return Json(json!({ "response_type": "ephemeral", "text": message })).into_response();
+    }
+    if let Some(response_url) = sender.response_url {
+        let slack_client = state.slack_client.clone();
+        tokio::spawn(async move {
+            if let Err(e) = slack_client
+                .post_to_response_url(&response_url, blocks::error_blocks(&message))
+                .await
+            {
+                error!("Failed to tell a rate limited user: {}", e);
+            }
+        });
+    }
+    StatusCode::OK.into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_bucket_allows_a_minute_of_requests_then_refills() {
+        let limiter = SlackRateLimiter::new(3);
+        let start = Instant::now();
+        assert!((0..3).all(|_| limiter.allow_at("T1:U1", start)));
+        assert!(!limiter.allow_at("T1:U1", start));
+        // Others have their own allowance
+        assert!(limiter.allow_at("T1:U2", start));
+        // 3 per minute refills one every 20 seconds
+        assert!(!limiter.allow_at("T1:U1", start + Duration::from_secs(19)));
+        assert!(limiter.allow_at("T1:U1", start + Duration::from_secs(21)));
+        assert!(!limiter.allow_at("T1:U1", start + Duration::from_secs(21)));
+
+        let unlimited = SlackRateLimiter::new(0);
+        assert!((0..100).all(|_| unlimited.allow_at("T1:U1", start)));
+    }
+
+    #[test]
+    fn test_submissions_and_declare_drafts_are_exempt() {
+        assert!(exempt(&json!({ "type": "view_submission" })));
+        assert!(exempt(&json!({
+            "type": "block_actions",
+            "view": { "callback_id": DECLARE_MODAL_CALLBACK_ID }
+        })));
+        assert!(!exempt(&json!({ "type": "block_actions" })));
+        assert!(!exempt(&json!({
+            "type": "block_actions",
+            "view": { "callback_id": "update_status_modal" }
+        })));
+    }
+}
@@ -0,0 +1,170 @@
+use crate::app_state::AppState;
+use crate::error::{IncidentError, IncidentResult};
+use crate::slack::events::{
+    dispatch_event, spawn_interaction, spawn_slash_command, EventEnvelope, InteractionPayload,
+    SlashCommandPayload,
+};
+use crate::slack::websocket::{Message, WebSocket};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::time::Duration;
+use tracing::{debug, error, info, warn};
+
+const CONNECTIONS_OPEN_URL: &str = "https://slack.com/api/apps.connections.open";
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// Socket Mode frame: `hello`, `disconnect`, or an envelope wrapping the
+/// same payload Slack would POST to a Request URL.
+#[derive(Debug, Deserialize)]
+struct SocketEnvelope {
+    #[serde(rename = "type")]
+    envelope_type: String,
+    envelope_id: Option<String>,
+    payload: Option<Value>,
+    #[serde(default)]
+    retry_attempt: u32,
+    reason: Option<String>,
+}
+
+/// What the connection loop should do after a frame.
+#[derive(Debug, PartialEq)]
+pub enum SocketAction {
+    /// Send this acknowledgement back over the socket
+    Ack(String),
+    /// Slack is about to close this connection; open a new one
+    Reconnect,
+    Ignore,
+}
+
+/// Keep a Socket Mode connection open for the life of the process,
+/// reconnecting when Slack asks to or the socket drops.
+pub async fn run(state: AppState) {
+    let Some(app_token) = state.config.slack_app_token.clone() else {
+        error!("SLACK_APP_TOKEN is not set; Socket Mode disabled");
+        return;
+    };
+    let http_client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(30))
+        .build()
+        .expect("Failed to build HTTP client");
+
+    loop {
+        match connect_and_serve(&state, &http_client, &app_token).await {
+            Ok(()) => info!("Socket Mode connection closed; reconnecting"),
+            Err(e) => {
+                error!("Socket Mode connection failed: {}", e);
+                tokio::time::sleep(RECONNECT_DELAY).await;
+            }
+        }
+    }
+}
+
+async fn connect_and_serve(
+    state: &AppState,
+    http_client: &reqwest::Client,
+    app_token: &str,
+) -> IncidentResult<()> {
+    let url = open_connection(http_client, app_token).await?;
+    let mut socket = WebSocket::connect(&url).await?;
+    info!("Socket Mode connected");
+
+    loop {
+        let text = match socket.read_message().await? {
+            Message::Text(text) => text,
+            Message::Close => return Ok(()),
+        };
+        match handle_message(state, &text).await {
+            SocketAction::Ack(ack) => socket.send_text(&ack).await?,
+            SocketAction::Reconnect => return Ok(()),
+            SocketAction::Ignore => {}
+        }
+    }
+}
+
+/// `apps.connections.open`: exchange the app-level token for a one-off
+/// websocket URL.
+async fn open_connection(http_client: &reqwest::Client, app_token: &str) -> IncidentResult<String> {
+    let response: Value = http_client
+        .post(CONNECTIONS_OPEN_URL)
+        .bearer_auth(app_token)
+        .send()
+        .await?
+        .json()
+        .await?;
+
+    match response["url"].as_str() {
+        Some(url) if response["ok"].as_bool() == Some(true) => Ok(url.to_string()),
+        _ => Err(IncidentError::SlackAPIError {
+            message: "apps.connections.open failed".to_string(),
+            slack_error_code: response["error"]
+                .as_str()
+                .unwrap_or("unknown_error")
+                .to_string(),
+        }),
+    }
+}
+
+/// Dispatch one Socket Mode frame to the same handlers as the HTTP routes.
+/// Processing happens in the background, so the ack goes out well inside
+/// Slack's 3-second window.
+pub async fn handle_message(state: &AppState, text: &str) -> SocketAction {
+    let envelope: SocketEnvelope = match serde_json::from_str(text) {
+        Ok(envelope) => envelope,
+        Err(e) => {
+            error!("Failed to parse Socket Mode message: {}", e);
+            return SocketAction::Ignore;
+        }
+    };
+
+    match envelope.envelope_type.as_str() {
+        "hello" => {
+            debug!("Socket Mode hello");
+            return SocketAction::Ignore;
+        }
+        "disconnect" => {
+            info!(
+                "Socket Mode disconnect requested ({})",
+                envelope.reason.as_deref().unwrap_or("no reason")
+            );
+            return SocketAction::Reconnect;
+        }
+        _ => {}
+    }
+
+    let Some(envelope_id) = envelope.envelope_id else {
+        warn!(
+            "Socket Mode {} message without envelope_id",
+            envelope.envelope_type
+        );
+        return SocketAction::Ignore;
+    };
+    let payload = envelope.payload.unwrap_or_default();
+
+    let retry_attempt = (envelope.retry_attempt > 0).then(|| envelope.retry_attempt.to_string());
+    let result = match envelope.envelope_type.as_str() {
+        "slash_commands" => serde_json::from_value::<SlashCommandPayload>(payload)
+            .map(|payload| spawn_slash_command(state.clone(), payload, retry_attempt)),
+        "interactive" => serde_json::from_value::<InteractionPayload>(payload)
+            .map(|payload| spawn_interaction(state.clone(), payload, retry_attempt)),
+        "events_api" => match serde_json::from_value::<EventEnvelope>(payload) {
+            Ok(event) => {
+                dispatch_event(state.clone(), event, retry_attempt.as_deref()).await;
+                Ok(())
+            }
+            Err(e) => Err(e),
+        },
+        other => {
+            info!("Unhandled Socket Mode envelope type: {}", other);
+            Ok(())
+        }
+    };
+    if let Err(e) = result {
+        error!(
+            "Failed to parse Socket Mode {} payload: {}",
+            envelope.envelope_type, e
+        );
+    }
+
+    // Ack even unparseable payloads so Slack doesn't redeliver them
+    SocketAction::Ack(json!({ "envelope_id": envelope_id }).to_string())
+}
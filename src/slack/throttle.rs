@@ -0,0 +1,192 @@
+//! Adaptive pacing of outgoing Slack calls, shared by everything that posts
+//! with the bot token (one workspace). `SlackClient` reports each 429; while
+//! Slack keeps pushing back, `NotificationService` spaces out DM fan-out and
+//! holds fan-out thread replies back to send them batched, instead of
+//! hammering until deliveries fail.
+
+use crate::error::IncidentError;
+use crate::metrics::metrics;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+/// Spacing after the first 429; doubled on each further one.
+const MIN_SPACING: Duration = Duration::from_millis(250);
+
+/// Longest pause between two fan-out sends.
+const MAX_SPACING: Duration = Duration::from_secs(5);
+
+/// How long without a 429 before sends go out at full speed again.
+const COOLDOWN: Duration = Duration::from_secs(60);
+
+/// Whether Slack turned a call away for exceeding its rate limit.
+pub fn is_rate_limited(error: &IncidentError) -> bool {
+    matches!(
+        error,
+        IncidentError::SlackAPIError { slack_error_code, .. }
+            if slack_error_code == "ratelimited" || slack_error_code == "rate_limited"
+    )
+}
+
+pub struct SlackThrottle {
+    min_spacing: Duration,
+    max_spacing: Duration,
+    cooldown: Duration,
+    state: Mutex<ThrottleState>,
+}
+
+#[derive(Default)]
+struct ThrottleState {
+    /// Zero when Slack isn't rate limiting
+    spacing: Duration,
+    /// Until when sends are paced; `None` when not throttled
+    throttled_until: Option<Instant>,
+    /// Earliest time the next paced send may go out
+    next_slot: Option<Instant>,
+}
+
+impl Default for SlackThrottle {
+    fn default() -> Self {
+        Self::new(MIN_SPACING, MAX_SPACING, COOLDOWN)
+    }
+}
+
+impl SlackThrottle {
+    pub fn new(min_spacing: Duration, max_spacing: Duration, cooldown: Duration) -> Self {
+        Self {
+            min_spacing,
+            max_spacing,
+            cooldown,
+            state: Mutex::new(ThrottleState::default()),
+        }
+    }
+
+    /// Slack answered `method` with a 429 (or `ratelimited`): stretch the
+    /// spacing and stay throttled for the cooldown, or `retry_after` if longer.
+    pub fn record_rate_limited(&self, method: &str, retry_after: Option<Duration>) {
+        self.record_rate_limited_at(method, retry_after, Instant::now());
+    }
+
+    fn record_rate_limited_at(&self, method: &str, retry_after: Option<Duration>, now: Instant) {
+        let mut state = self.lock();
+        let was_throttled = Self::refresh(&mut state, now);
+        state.spacing = (state.spacing * 2).clamp(self.min_spacing, self.max_spacing);
+        state.throttled_until = Some(now + self.cooldown.max(retry_after.unwrap_or_default()));
+        metrics().record_slack_api_rate_limited(method, state.spacing);
+        if !was_throttled {
+            warn!(
+                "Slack is rate limiting the bot ({}); spacing notifications {:?} apart and batching thread replies until it stops",
+                method, state.spacing
+            );
+        }
+    }
+
+    /// Whether Slack has rate limited the bot within the cooldown.
+    pub fn is_throttled(&self) -> bool {
+        Self::refresh(&mut self.lock(), Instant::now())
+    }
+
+    /// Current spacing between paced sends; zero when not throttled.
+    pub fn spacing(&self) -> Duration {
+        let mut state = self.lock();
+        Self::refresh(&mut state, Instant::now());
+        state.spacing
+    }
+
+    /// Wait for this send's turn: returns at once unless throttled, otherwise
+    /// once `spacing` has passed since the previous paced send.
+    pub async fn pace(&self) {
+        if let Some(wait) = self.reserve_at(Instant::now()) {
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Take the next send slot, returning how long to wait for it.
+    fn reserve_at(&self, now: Instant) -> Option<Duration> {
+        let mut state = self.lock();
+        if !Self::refresh(&mut state, now) {
+            return None;
+        }
+        let slot = state.next_slot.map_or(now, |next| next.max(now));
+        state.next_slot = Some(slot + state.spacing);
+        Some(slot - now).filter(|wait| !wait.is_zero())
+    }
+
+    /// Clear the throttle once its cooldown has passed. Returns whether it
+    /// is still throttled.
+    fn refresh(state: &mut ThrottleState, now: Instant) -> bool {
+        match state.throttled_until {
+            Some(until) if now < until => true,
+            Some(_) => {
+                info!("Slack rate limiting eased; sending notifications at full speed");
+                *state = ThrottleState::default();
+                metrics().set_slack_send_spacing(Duration::ZERO);
+                false
+            }
+            None => false,
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, ThrottleState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn throttle() -> SlackThrottle {
+        SlackThrottle::new(
+            Duration::from_millis(250),
+            Duration::from_secs(1),
+            Duration::from_secs(60),
+        )
+    }
+
+    #[test]
+    fn test_rate_limits_stretch_spacing_up_to_max() {
+        let throttle = throttle();
+        let now = Instant::now();
+        assert_eq!(throttle.reserve_at(now), None);
+
+        throttle.record_rate_limited_at("chat.postMessage", None, now);
+        assert_eq!(throttle.lock().spacing, Duration::from_millis(250));
+        throttle.record_rate_limited_at("chat.postMessage", None, now);
+        assert_eq!(throttle.lock().spacing, Duration::from_millis(500));
+        for _ in 0..3 {
+            throttle.record_rate_limited_at("chat.postMessage", None, now);
+        }
+        assert_eq!(throttle.lock().spacing, Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_paced_sends_queue_behind_each_other() {
+        let throttle = throttle();
+        let now = Instant::now();
+        throttle.record_rate_limited_at("chat.postMessage", None, now);
+
+        assert_eq!(throttle.reserve_at(now), None);
+        assert_eq!(throttle.reserve_at(now), Some(Duration::from_millis(250)));
+        assert_eq!(throttle.reserve_at(now), Some(Duration::from_millis(500)));
+        // Time spent elsewhere counts towards the wait
+        assert_eq!(
+            throttle.reserve_at(now + Duration::from_millis(600)),
+            Some(Duration::from_millis(150))
+        );
+    }
+
+    #[test]
+    fn test_throttle_clears_after_cooldown_or_retry_after() {
+        let throttle = throttle();
+        let now = Instant::now();
+        throttle.record_rate_limited_at("chat.postMessage", Some(Duration::from_secs(90)), now);
+
+        throttle.reserve_at(now + Duration::from_secs(61));
+        assert!(throttle.lock().throttled_until.is_some());
+        let much_later = now + Duration::from_secs(91);
+        assert_eq!(throttle.reserve_at(much_later), None);
+        assert!(throttle.lock().throttled_until.is_none());
+        assert_eq!(throttle.lock().spacing, Duration::ZERO);
+    }
+}
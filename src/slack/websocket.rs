@@ -0,0 +1,324 @@
+use crate::error::{IncidentError, IncidentResult};
+use base64::Engine;
+use sha1::{Digest, Sha1};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+/// Minimal RFC 6455 client covering what Socket Mode needs: text frames,
+/// ping/pong and close. No extensions or compression.
+pub struct WebSocket {
+    stream: Box<dyn Stream>,
+}
+
+trait Stream: AsyncRead + AsyncWrite + Unpin + Send {}
+impl<T: AsyncRead + AsyncWrite + Unpin + Send> Stream for T {}
+
+#[derive(Debug, PartialEq)]
+pub enum Message {
+    Text(String),
+    Close,
+}
+
+const OPCODE_CONTINUATION: u8 = 0x0;
+const OPCODE_TEXT: u8 = 0x1;
+const OPCODE_BINARY: u8 = 0x2;
+const OPCODE_CLOSE: u8 = 0x8;
+const OPCODE_PING: u8 = 0x9;
+const OPCODE_PONG: u8 = 0xA;
+
+/// Socket Mode envelopes are small; anything bigger is a broken stream.
+const MAX_MESSAGE_BYTES: u64 = 16 * 1024 * 1024;
+
+/// Appended to the client key before hashing (RFC 6455 section 1.3).
+const ACCEPT_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+/// The `Sec-WebSocket-Accept` value a server must answer `key` with.
+fn accept_key(key: &str) -> String {
+    let digest = Sha1::digest(format!("{}{}", key, ACCEPT_GUID));
+    base64::engine::general_purpose::STANDARD.encode(digest)
+}
+
+fn socket_error(message: impl std::fmt::Display) -> IncidentError {
+    IncidentError::ExternalAPIError {
+        service: "Slack Socket Mode".to_string(),
+        message: message.to_string(),
+    }
+}
+
+impl WebSocket {
+    /// Connect to a `ws://` or `wss://` URL and complete the upgrade handshake.
+    pub async fn connect(url: &str) -> IncidentResult<Self> {
+        let url = reqwest::Url::parse(url).map_err(socket_error)?;
+        let host = url
+            .host_str()
+            .ok_or_else(|| socket_error("URL has no host"))?
+            .to_string();
+        let port = url
+            .port_or_known_default()
+            .ok_or_else(|| socket_error("URL has no port"))?;
+
+        let tcp = TcpStream::connect((host.as_str(), port))
+            .await
+            .map_err(socket_error)?;
+        let stream: Box<dyn Stream> = match url.scheme() {
+            "wss" => {
+                let connector = native_tls_connector()?;
+                Box::new(connector.connect(&host, tcp).await.map_err(socket_error)?)
+            }
+            "ws" => Box::new(tcp),
+            scheme => return Err(socket_error(format!("unsupported scheme {}", scheme))),
+        };
+
+        let mut socket = Self { stream };
+        let path = match url.query() {
+            Some(query) => format!("{}?{}", url.path(), query),
+            None => url.path().to_string(),
+        };
+        socket.handshake(&host, &path).await?;
+        Ok(socket)
+    }
+
+    /// Next text or close message. Pings are answered and pongs skipped.
+    pub async fn read_message(&mut self) -> IncidentResult<Message> {
+        let mut message = Vec::new();
+        loop {
+            let (fin, opcode, payload) = self.read_frame().await?;
+            match opcode {
+                OPCODE_PING => self.write_frame(OPCODE_PONG, &payload).await?,
+                OPCODE_PONG => {}
+                OPCODE_CLOSE => {
+                    // Echo the close; the peer closes the connection after
+                    let _ = self.write_frame(OPCODE_CLOSE, &payload).await;
+                    return Ok(Message::Close);
+                }
+                OPCODE_TEXT | OPCODE_BINARY | OPCODE_CONTINUATION => {
+                    message.extend_from_slice(&payload);
+                    if message.len() as u64 > MAX_MESSAGE_BYTES {
+                        return Err(socket_error("message too large"));
+                    }
+                    if fin {
+                        return String::from_utf8(message)
+                            .map(Message::Text)
+                            .map_err(socket_error);
+                    }
+                }
+                other => return Err(socket_error(format!("unknown opcode {:#x}", other))),
+            }
+        }
+    }
+
+    pub async fn send_text(&mut self, text: &str) -> IncidentResult<()> {
+        self.write_frame(OPCODE_TEXT, text.as_bytes()).await
+    }
+
+    async fn handshake(&mut self, host: &str, path: &str) -> IncidentResult<()> {
+        let key = base64::engine::general_purpose::STANDARD.encode(uuid::Uuid::new_v4().as_bytes());
+        let request = format!(
+            "GET {} HTTP/1.1\r\nHost: {}\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Key: {}\r\nSec-WebSocket-Version: 13\r\n\r\n",
+            path, host, key
+        );
+        self.stream
+            .write_all(request.as_bytes())
+            .await
+            .map_err(socket_error)?;
+
+        // Read the response head byte by byte so no frame data is consumed
+        let mut head = Vec::new();
+        while !head.ends_with(b"\r\n\r\n") {
+            if head.len() > 8192 {
+                return Err(socket_error("handshake response too large"));
+            }
+            head.push(self.stream.read_u8().await.map_err(socket_error)?);
+        }
+        let head = String::from_utf8_lossy(&head);
+        let status_line = head.lines().next().unwrap_or_default();
+        if status_line.split_whitespace().nth(1) != Some("101") {
+            return Err(socket_error(format!("handshake rejected: {}", status_line)));
+        }
+        // Proves the peer is a WebSocket server answering this handshake
+        // rather than a proxy or cache replaying some other 101
+        let accept = head.lines().skip(1).find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            name.trim()
+                .eq_ignore_ascii_case("sec-websocket-accept")
+                .then(|| value.trim())
+        });
+        if accept != Some(accept_key(&key).as_str()) {
+            return Err(socket_error(
+                "handshake rejected: Sec-WebSocket-Accept does not match the key",
+            ));
+        }
+        Ok(())
+    }
+
+    async fn read_frame(&mut self) -> IncidentResult<(bool, u8, Vec<u8>)> {
+        let mut header = [0u8; 2];
+        self.stream
+            .read_exact(&mut header)
+            .await
+            .map_err(socket_error)?;
+        let fin = header[0] & 0x80 != 0;
+        let opcode = header[0] & 0x0F;
+        let masked = header[1] & 0x80 != 0;
+
+        let len = match header[1] & 0x7F {
+            126 => u64::from(self.stream.read_u16().await.map_err(socket_error)?),
+            127 => self.stream.read_u64().await.map_err(socket_error)?,
+            len => u64::from(len),
+        };
+        if len > MAX_MESSAGE_BYTES {
+            return Err(socket_error("frame too large"));
+        }
+
+        let mut mask = [0u8; 4];
+        if masked {
+            self.stream
+                .read_exact(&mut mask)
+                .await
+                .map_err(socket_error)?;
+        }
+        let mut payload = vec![0u8; len as usize];
+        self.stream
+            .read_exact(&mut payload)
+            .await
+            .map_err(socket_error)?;
+        if masked {
+            apply_mask(&mut payload, mask);
+        }
+        Ok((fin, opcode, payload))
+    }
+
+    /// Client frames must be masked (RFC 6455 §5.3).
+    async fn write_frame(&mut self, opcode: u8, payload: &[u8]) -> IncidentResult<()> {
+        let mut frame = vec![0x80 | opcode];
+        match payload.len() {
+            len if len < 126 => frame.push(0x80 | len as u8),
+            len if len <= usize::from(u16::MAX) => {
+                frame.push(0x80 | 126);
+                frame.extend_from_slice(&(len as u16).to_be_bytes());
+            }
+            len => {
+                frame.push(0x80 | 127);
+                frame.extend_from_slice(&(len as u64).to_be_bytes());
+            }
+        }
+
+        let mut mask = [0u8; 4];
+        mask.copy_from_slice(&uuid::Uuid::new_v4().as_bytes()[..4]);
+        frame.extend_from_slice(&mask);
+        let mut masked = payload.to_vec();
+        apply_mask(&mut masked, mask);
+        frame.extend_from_slice(&masked);
+
+        self.stream.write_all(&frame).await.map_err(socket_error)?;
+        self.stream.flush().await.map_err(socket_error)
+    }
+}
+
+fn apply_mask(payload: &mut [u8], mask: [u8; 4]) {
+    for (i, byte) in payload.iter_mut().enumerate() {
+        *byte ^= mask[i % 4];
+    }
+}
+
+fn native_tls_connector() -> IncidentResult<tokio_native_tls::TlsConnector> {
+    tokio_native_tls::native_tls::TlsConnector::new()
+        .map(tokio_native_tls::TlsConnector::from)
+        .map_err(socket_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_reads_fragmented_text_and_answers_pings() {
+        let (client, mut server) = tokio::io::duplex(1024);
+        let mut socket = WebSocket {
+            stream: Box::new(client),
+        };
+
+        // Unmasked server frames: "hel" (no FIN), ping "p", "lo" (FIN)
+        server.write_all(&[0x01, 3]).await.unwrap();
+        server.write_all(b"hel").await.unwrap();
+        server.write_all(&[0x89, 1, b'p']).await.unwrap();
+        server.write_all(&[0x80, 2]).await.unwrap();
+        server.write_all(b"lo").await.unwrap();
+
+        assert_eq!(
+            socket.read_message().await.unwrap(),
+            Message::Text("hello".to_string())
+        );
+
+        // The pong is masked and echoes the ping payload
+        let mut pong = [0u8; 7];
+        server.read_exact(&mut pong).await.unwrap();
+        assert_eq!(pong[0], 0x80 | OPCODE_PONG);
+        assert_eq!(pong[1], 0x80 | 1);
+        assert_eq!(pong[6] ^ pong[2], b'p');
+
+        server.write_all(&[0x88, 0]).await.unwrap();
+        assert_eq!(socket.read_message().await.unwrap(), Message::Close);
+    }
+
+    #[test]
+    fn test_accept_key_matches_rfc_example() {
+        assert_eq!(
+            accept_key("dGhlIHNhbXBsZSBub25jZQ=="),
+            "s3pPLMBiTxaQ9kYGzzhZRbK+xOo="
+        );
+    }
+
+    /// Run the client handshake against a server that answers with
+    /// `respond(key)` as the response head.
+    async fn handshake_with(respond: fn(&str) -> String) -> IncidentResult<()> {
+        let (client, server) = tokio::io::duplex(1024);
+        let mut socket = WebSocket {
+            stream: Box::new(client),
+        };
+        let server = tokio::spawn(async move {
+            let mut server = server;
+            let mut request = Vec::new();
+            while !request.ends_with(b"\r\n\r\n") {
+                request.push(server.read_u8().await.unwrap());
+            }
+            let request = String::from_utf8(request).unwrap();
+            let key = request
+                .lines()
+                .find_map(|line| line.strip_prefix("Sec-WebSocket-Key: "))
+                .unwrap()
+                .to_string();
+            server.write_all(respond(&key).as_bytes()).await.unwrap();
+            server
+        });
+        let result = socket.handshake("slack.test", "/link").await;
+        server.await.unwrap();
+        result
+    }
+
+    #[tokio::test]
+    async fn test_handshake_verifies_accept_header() {
+        handshake_with(|key| {
+            format!(
+                "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nsec-websocket-accept: {}\r\n\r\n",
+                accept_key(key)
+            )
+        })
+        .await
+        .unwrap();
+
+        // A 101 answering some other key, or without the header, is not ours
+        let wrong = handshake_with(|_| {
+            format!(
+                "HTTP/1.1 101 Switching Protocols\r\nSec-WebSocket-Accept: {}\r\n\r\n",
+                accept_key("dGhlIHNhbXBsZSBub25jZQ==")
+            )
+        })
+        .await;
+        assert!(wrong.is_err());
+
+        let missing =
+            handshake_with(|_| "HTTP/1.1 101 Switching Protocols\r\n\r\n".to_string()).await;
+        assert!(missing.is_err());
+    }
+}
@@ -1,6 +1,6 @@
 use crate::db::models::IncidentId;
 use crate::error::{IncidentError, IncidentResult};
-use crate::slack::client::SlackClient;
+use crate::slack::client::SlackApi;
 use chrono::NaiveDate;
 use tracing::{debug, info};
 
@@ -28,18 +28,22 @@ pub fn generate_channel_name(service: &str, date: NaiveDate, incident_id: Incide
     }
 }
 
-/// Create incident channel with deduplication
+/// Create incident channel with deduplication (private for quiet incidents)
 /// Returns (channel_id, channel_name)
 pub async fn create_incident_channel(
-    slack_client: &SlackClient,
+    slack_client: &dyn SlackApi,
     service: &str,
     date: NaiveDate,
     incident_id: IncidentId,
+    is_private: bool,
 ) -> IncidentResult<(String, String)> {
     let base_name = generate_channel_name(service, date, incident_id);
 
     // Try to create channel
-    match slack_client.create_conversation(&base_name).await {
+    match slack_client
+        .create_conversation(&base_name, is_private)
+        .await
+    {
         Ok(channel_id) => {
             info!("Created channel #{} ({})", base_name, channel_id);
             Ok((channel_id, base_name))
@@ -53,7 +57,10 @@ pub async fn create_incident_channel(
 
             debug!("Channel #{} exists, trying #{}", base_name, unique_name);
 
-            match slack_client.create_conversation(&unique_name).await {
+            match slack_client
+                .create_conversation(&unique_name, is_private)
+                .await
+            {
                 Ok(channel_id) => {
                     info!("Created channel #{} ({})", unique_name, channel_id);
                     Ok((channel_id, unique_name))
@@ -0,0 +1,74 @@
+//! Bar chart PNGs for Slack image blocks, in the sparkline's palette.
+//!
+//! Bars carry no labels (there is no font to draw them with), so callers
+//! put bucket names and counts next to the image.
+
+use crate::utils::sparkline::{encode_png, BACKGROUND_INDEX, FILL_INDEX, LINE_INDEX};
+
+/// Blank columns between neighbouring bars.
+const BAR_GAP: usize = 4;
+
+/// Blank rows above the tallest bar.
+const TOP_PADDING: usize = 2;
+
+/// Render `counts` left to right as equal-width bars, scaled so the largest
+/// touches the top. Non-zero counts are at least two pixels tall so small
+/// buckets stay visible. Returns PNG bytes.
+pub fn render_png(counts: &[i64], width: u32, height: u32) -> Vec<u8> {
+    let pixels = rasterize(counts, width, height);
+    encode_png(&pixels, width, height)
+}
+
+/// One palette index per pixel, row-major.
+fn rasterize(counts: &[i64], width: u32, height: u32) -> Vec<u8> {
+    let (w, h) = (width as usize, height as usize);
+    let mut pixels = vec![BACKGROUND_INDEX; w * h];
+    if w == 0 || h == 0 || counts.is_empty() {
+        return pixels;
+    }
+
+    let max = counts.iter().copied().max().unwrap_or(0).max(1) as f64;
+    let usable = h.saturating_sub(TOP_PADDING) as f64;
+    let slot = w / counts.len();
+    for (i, &count) in counts.iter().enumerate() {
+        if count <= 0 {
+            continue;
+        }
+        let bar_height = ((count as f64 / max) * usable).round().max(2.0) as usize;
+        let top = h - bar_height.min(h);
+        let left = i * slot + BAR_GAP / 2;
+        let right = ((i + 1) * slot).saturating_sub(BAR_GAP / 2).max(left + 1);
+        for y in top..h {
+            for x in left..right.min(w) {
+                pixels[y * w + x] = if y == top { LINE_INDEX } else { FILL_INDEX };
+            }
+        }
+    }
+    pixels
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rasterize_scales_tallest_bar_to_top() {
+        let (w, h) = (40, 12);
+        let pixels = rasterize(&[0, 1, 4, 0], w, h);
+        let column = |x: usize| {
+            (0..h as usize)
+                .map(|y| pixels[y * w as usize + x])
+                .collect::<Vec<_>>()
+        };
+        // Empty bucket, and the gap between bars, stay blank
+        assert!(column(5).iter().all(|&p| p == BACKGROUND_INDEX));
+        assert!(column(20).iter().all(|&p| p == BACKGROUND_INDEX));
+        // Tallest bar reaches just under the padding
+        assert_eq!(column(25)[TOP_PADDING], LINE_INDEX);
+        assert_eq!(column(25)[11], FILL_INDEX);
+        // Smallest non-zero bar is still visible
+        assert_eq!(column(15)[11], FILL_INDEX);
+        assert_eq!(column(15)[TOP_PADDING], BACKGROUND_INDEX);
+        assert_eq!(&render_png(&[1], 8, 4)[..8], b"\x89PNG\r\n\x1a\n");
+    }
+}
@@ -0,0 +1,64 @@
+//! Incident numbers such as `INC-42` or `PLAT-12`: a prefix, and a number
+//! counted separately for each prefix.
+
+/// Prefix of incidents whose team sets none
+pub const DEFAULT_PREFIX: &str = "INC";
+
+/// Prefix of triages, renumbered under their real prefix once declared
+pub const TRIAGE_PREFIX: &str = "TRI";
+
+const MAX_PREFIX_LEN: usize = 10;
+
+/// 1-10 uppercase ASCII letters or digits, starting with a letter.
+pub fn is_valid_prefix(prefix: &str) -> bool {
+    prefix.len() <= MAX_PREFIX_LEN
+        && prefix.starts_with(|c: char| c.is_ascii_uppercase())
+        && prefix
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+}
+
+/// `PREFIX-N`, as shown in Slack.
+pub fn format(prefix: &str, number: i32) -> String {
+    format!("{}-{}", prefix, number)
+}
+
+/// Parse an incident number as typed: `PLAT-12`, `plat-12` or `#PLAT-12`.
+/// Returns the uppercased prefix and the number.
+pub fn parse(reference: &str) -> Option<(String, i32)> {
+    let reference = reference.trim().trim_start_matches('#');
+    let (prefix, number) = reference.rsplit_once('-')?;
+    let prefix = prefix.to_ascii_uppercase();
+    if !is_valid_prefix(&prefix) || !number.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let number = number.parse().ok().filter(|n| *n > 0)?;
+    Some((prefix, number))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse("PLAT-12"), Some(("PLAT".to_string(), 12)));
+        assert_eq!(parse(" #sec-7 "), Some(("SEC".to_string(), 7)));
+        assert_eq!(parse("P1OPS-3"), Some(("P1OPS".to_string(), 3)));
+        assert_eq!(parse("INC-0"), None);
+        assert_eq!(parse("INC-+4"), None);
+        assert_eq!(parse("INC42"), None);
+        assert_eq!(parse("9INC-4"), None);
+        assert_eq!(parse("6f1c2d3e-0000-4000-8000-000000000000"), None);
+    }
+
+    #[test]
+    fn test_is_valid_prefix() {
+        assert!(is_valid_prefix("INC"));
+        assert!(is_valid_prefix("P1OPS"));
+        assert!(!is_valid_prefix(""));
+        assert!(!is_valid_prefix("plat"));
+        assert!(!is_valid_prefix("SEC-OPS"));
+        assert!(!is_valid_prefix("ABCDEFGHIJK"));
+    }
+}
@@ -0,0 +1,94 @@
+/// Extract a Slack user ID from slash command text.
+///
+/// Accepts escaped mentions (`<@U024BE7LH>`, `<@U024BE7LH|alice>`) and raw IDs
+/// (`U024BE7LH`). Plain `@alice` cannot be resolved without a users.list lookup
+/// and is rejected.
+pub fn parse_user_mention(token: &str) -> Option<String> {
+    let token = token.trim();
+    let id = match token.strip_prefix("<@").and_then(|t| t.strip_suffix('>')) {
+        Some(inner) => inner.split('|').next().unwrap_or(""),
+        None => token,
+    };
+
+    let mut chars = id.chars();
+    let valid = matches!(chars.next(), Some('U') | Some('W'))
+        && id.len() >= 3
+        && chars.all(|c| c.is_ascii_uppercase() || c.is_ascii_digit());
+
+    if valid {
+        Some(id.to_string())
+    } else {
+        None
+    }
+}
+
+/// Extract a Slack channel ID from slash command text.
+///
+/// Accepts escaped channels (`<#C024BE91L>`, `<#C024BE91L|general>`) and raw
+/// IDs. Plain `#general` needs "Escape channels" on the slash command and is
+/// rejected.
+pub fn parse_channel_mention(token: &str) -> Option<String> {
+    let token = token.trim();
+    let id = match token.strip_prefix("<#").and_then(|t| t.strip_suffix('>')) {
+        Some(inner) => inner.split('|').next().unwrap_or(""),
+        None => token,
+    };
+
+    let mut chars = id.chars();
+    let valid = matches!(chars.next(), Some('C') | Some('G'))
+        && id.len() >= 3
+        && chars.all(|c| c.is_ascii_uppercase() || c.is_ascii_digit());
+
+    if valid {
+        Some(id.to_string())
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_escaped_and_raw_mentions() {
+        assert_eq!(
+            parse_user_mention("<@U024BE7LH>").as_deref(),
+            Some("U024BE7LH")
+        );
+        assert_eq!(
+            parse_user_mention("<@U024BE7LH|alice>").as_deref(),
+            Some("U024BE7LH")
+        );
+        assert_eq!(
+            parse_user_mention("W012ABCDE").as_deref(),
+            Some("W012ABCDE")
+        );
+    }
+
+    #[test]
+    fn test_rejects_unresolvable_or_malformed_mentions() {
+        assert_eq!(parse_user_mention("@alice"), None);
+        assert_eq!(parse_user_mention("<#C024BE91L|general>"), None);
+        assert_eq!(parse_user_mention("u024be7lh"), None);
+        assert_eq!(parse_user_mention(""), None);
+    }
+
+    #[test]
+    fn test_parses_escaped_and_raw_channels() {
+        assert_eq!(
+            parse_channel_mention("<#C024BE91L|exec-updates>").as_deref(),
+            Some("C024BE91L")
+        );
+        assert_eq!(
+            parse_channel_mention("<#G024BE91L>").as_deref(),
+            Some("G024BE91L")
+        );
+        assert_eq!(
+            parse_channel_mention("C024BE91L").as_deref(),
+            Some("C024BE91L")
+        );
+        assert_eq!(parse_channel_mention("#exec-updates"), None);
+        assert_eq!(parse_channel_mention("<@U024BE7LH>"), None);
+    }
+}
@@ -1 +1,9 @@
 pub mod channel;
+pub mod histogram;
+pub mod incident_number;
+pub mod mention;
+pub mod placeholders;
+pub mod redact;
+pub mod severity;
+pub mod sparkline;
+pub mod time;
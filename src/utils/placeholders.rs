@@ -0,0 +1,98 @@
+use std::collections::BTreeMap;
+
+/// Names of the `{{name}}` placeholders in `texts`, in order of first
+/// appearance. Names are letters, digits, `_` and `-`; anything else between
+/// braces is left alone as literal text.
+pub fn placeholders(texts: &[&str]) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for text in texts {
+        for (_, name) in scan(text) {
+            if !names.iter().any(|n| n == name) {
+                names.push(name.to_string());
+            }
+        }
+    }
+    names
+}
+
+/// Replace each `{{name}}` in `text` with its value. Placeholders without a
+/// value are kept as written.
+pub fn render(text: &str, values: &BTreeMap<String, String>) -> String {
+    let mut rendered = String::with_capacity(text.len());
+    let mut rest_start = 0;
+    for (range, name) in scan(text) {
+        if let Some(value) = values.get(name) {
+            rendered.push_str(&text[rest_start..range.start]);
+            rendered.push_str(value);
+            rest_start = range.end;
+        }
+    }
+    rendered.push_str(&text[rest_start..]);
+    rendered
+}
+
+/// Human label for a placeholder name: `customer_tier` -> "Customer tier".
+pub fn label(name: &str) -> String {
+    let spaced = name.replace(['_', '-'], " ");
+    let mut chars = spaced.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => spaced,
+    }
+}
+
+/// `(byte range, name)` of each well-formed placeholder, `{{ name }}` included.
+fn scan(text: &str) -> Vec<(std::ops::Range<usize>, &str)> {
+    let mut found = Vec::new();
+    let mut offset = 0;
+    while let Some(open) = text[offset..].find("{{") {
+        let start = offset + open;
+        let Some(close) = text[start + 2..].find("}}") else {
+            break;
+        };
+        let end = start + 2 + close + 2;
+        let name = text[start + 2..end - 2].trim();
+        if !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        {
+            found.push((start..end, name));
+            offset = end;
+        } else {
+            offset = start + 1;
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_placeholders_are_unique_and_ordered() {
+        assert_eq!(
+            placeholders(&[
+                "{{region}} outage for {{ customer }}",
+                "Customer {{customer}} in {{region}} via {{cdn-pop}}, not {{two words}} or {bad}"
+            ]),
+            vec!["region", "customer", "cdn-pop"]
+        );
+        assert!(placeholders(&["No variables", "{{", "{{}}"]).is_empty());
+    }
+
+    #[test]
+    fn test_render_fills_known_placeholders() {
+        let values = BTreeMap::from([
+            ("region".to_string(), "eu-west-1".to_string()),
+            ("customer".to_string(), "Acme".to_string()),
+        ]);
+        assert_eq!(
+            render("{{region}} outage for {{ customer }} ({{ticket}})", &values),
+            "eu-west-1 outage for Acme ({{ticket}})"
+        );
+        assert_eq!(render("{{{region}}}", &values), "{eu-west-1}");
+        assert_eq!(label("customer_tier"), "Customer tier");
+    }
+}
@@ -0,0 +1,137 @@
+const NAME: &str = "[name]";
+const EMAIL: &str = "[email]";
+const HOST: &str = "[internal host]";
+
+/// Sanitize `text` for readers outside the workspace: Slack mentions and
+/// `@handles` become `[name]`, email addresses `[email]`, and IP addresses and
+/// hostnames under `internal_domains` `[internal host]`. Links keep only their
+/// label (or are redacted like any other host).
+pub fn redact(text: &str, internal_domains: &[String]) -> String {
+    let text = redact_slack_markup(text);
+    let mut redacted = String::with_capacity(text.len());
+    let mut word = String::new();
+    for c in text.chars() {
+        if c.is_whitespace() {
+            redacted.push_str(&redact_word(&word, internal_domains));
+            redacted.push(c);
+            word.clear();
+        } else {
+            word.push(c);
+        }
+    }
+    redacted.push_str(&redact_word(&word, internal_domains));
+    redacted
+}
+
+/// Replace `<...>` markup: user mentions, channels, group mentions and links.
+fn redact_slack_markup(text: &str) -> String {
+    let mut redacted = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('<') {
+        let Some(len) = rest[start..].find('>') else {
+            break;
+        };
+        redacted.push_str(&rest[..start]);
+        let inner = &rest[start + 1..start + len];
+        match inner.chars().next() {
+            Some('@') => redacted.push_str(NAME),
+            Some('#') => redacted.push_str("[channel]"),
+            Some('!') => redacted.push_str("[team]"),
+            _ => match inner.split_once('|') {
+                Some((_, label)) => redacted.push_str(label),
+                None => redacted.push_str(inner),
+            },
+        }
+        rest = &rest[start + len + 1..];
+    }
+    redacted.push_str(rest);
+    redacted
+}
+
+fn redact_word(word: &str, internal_domains: &[String]) -> String {
+    let core = word.trim_start_matches(['(', '[', '"', '\'']);
+    let prefix = &word[..word.len() - core.len()];
+    let core = core.trim_end_matches(['.', ',', ';', ':', '!', '?', ')', ']', '"', '\'']);
+    let suffix = &word[prefix.len() + core.len()..];
+    if core.is_empty() {
+        return word.to_string();
+    }
+
+    let replacement = if core.len() > 1 && core.starts_with('@') {
+        NAME
+    } else if is_email(core) {
+        EMAIL
+    } else if is_internal_host(host_of(core), internal_domains) {
+        HOST
+    } else {
+        return word.to_string();
+    };
+    format!("{}{}{}", prefix, replacement, suffix)
+}
+
+fn is_email(word: &str) -> bool {
+    match word.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty() && domain.contains('.') && !domain.starts_with('.')
+        }
+        None => false,
+    }
+}
+
+/// The host part of a bare hostname or URL, without scheme, path or port.
+fn host_of(word: &str) -> &str {
+    let without_scheme = word.split_once("://").map_or(word, |(_, rest)| rest);
+    let host = without_scheme.split('/').next().unwrap_or("");
+    host.split(':').next().unwrap_or("")
+}
+
+fn is_internal_host(host: &str, internal_domains: &[String]) -> bool {
+    if host.parse::<std::net::Ipv4Addr>().is_ok() {
+        return true;
+    }
+    let host = host.to_ascii_lowercase();
+    internal_domains.iter().any(|domain| {
+        let domain = domain.trim_start_matches('.').to_ascii_lowercase();
+        !domain.is_empty() && (host == domain || host.ends_with(&format!(".{}", domain)))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redacts_names_and_internal_hosts() {
+        let domains = vec!["corp.example.com".to_string()];
+        assert_eq!(
+            redact(
+                "<@U024ABC> restarted db-1.corp.example.com (10.0.3.7) after a page from @oncall-bot; see <https://grafana.corp.example.com/d/x|the dashboard>",
+                &domains
+            ),
+            "[name] restarted [internal host] ([internal host]) after a page from [name]; see the dashboard"
+        );
+        assert_eq!(
+            redact(
+                "Ask ops@example.com or <#C024BE91L|inc-api>, <!subteam^S123|@sre>",
+                &domains
+            ),
+            "Ask [email] or [channel], [team]"
+        );
+        assert_eq!(
+            redact(
+                "Bare <https://API.CORP.EXAMPLE.COM:8443/health> link",
+                &domains
+            ),
+            "Bare [internal host] link"
+        );
+    }
+
+    #[test]
+    fn test_keeps_public_text() {
+        let domains = vec!["corp.example.com".to_string()];
+        let text = "Errors from https://status.example.com/ are down to 0.5% (v1.2.3).";
+        assert_eq!(redact(text, &domains), text);
+        assert_eq!(redact("", &domains), "");
+        assert_eq!(redact("a  b\n@", &[]), "a  b\n@");
+    }
+}
@@ -0,0 +1,105 @@
+//! The workspace's severities (SEVERITIES), most severe first.
+//!
+//! Each `SeverityLevel` maps onto one of the four routing tiers
+//! (`Severity::P1`..`P4`) that routing, SLAs and roles are configured by.
+//! Labels and emoji shown in Slack, the severity pickers and the codes
+//! accepted by commands come from here. Set once at startup like
+//! `utils::time`; until then (and in tests) there's one level per tier.
+
+use crate::db::models::{Severity, SeverityLevel};
+use std::sync::{LazyLock, OnceLock};
+
+static LEVELS: OnceLock<Vec<SeverityLevel>> = OnceLock::new();
+
+static BUILTIN: LazyLock<Vec<SeverityLevel>> = LazyLock::new(|| {
+    [Severity::P1, Severity::P2, Severity::P3, Severity::P4]
+        .into_iter()
+        .map(SeverityLevel::builtin)
+        .collect()
+});
+
+/// Set the configured levels once at startup. An empty list keeps the
+/// built-in P1-P4.
+pub fn init(levels: Vec<SeverityLevel>) {
+    if levels.is_empty() {
+        return;
+    }
+    if LEVELS.set(levels).is_err() {
+        tracing::warn!("Severities already initialised; keeping the first ones");
+    }
+}
+
+pub fn levels() -> &'static [SeverityLevel] {
+    LEVELS.get().unwrap_or(&BUILTIN)
+}
+
+/// The level with this code, ignoring case.
+pub fn find(code: &str) -> Option<&'static SeverityLevel> {
+    levels()
+        .iter()
+        .find(|level| level.code.eq_ignore_ascii_case(code.trim()))
+}
+
+/// A level code typed or picked by someone; a bare tier (`P2`) also works
+/// when no level uses it as a code.
+pub fn parse(input: &str) -> Result<&'static SeverityLevel, String> {
+    find(input)
+        .or_else(|| input.trim().parse::<Severity>().ok().map(for_tier))
+        .ok_or_else(|| format!("Invalid severity '{}'. Use {}", input.trim(), codes()))
+}
+
+/// The level standing in for a tier: the one whose code is the tier's name,
+/// else the tier's most severe level, else the built-in one.
+pub fn for_tier(tier: Severity) -> &'static SeverityLevel {
+    find(tier.as_db_str())
+        .filter(|level| level.tier == tier)
+        .or_else(|| levels().iter().find(|level| level.tier == tier))
+        .unwrap_or(&BUILTIN[tier as usize])
+}
+
+/// An incident's level: its stored code while still configured (and on the
+/// same tier), otherwise its tier's.
+pub fn resolve(code: Option<&str>, tier: Severity) -> &'static SeverityLevel {
+    code.and_then(find)
+        .filter(|level| level.tier == tier)
+        .unwrap_or_else(|| for_tier(tier))
+}
+
+/// Position in the list, 0 being most severe; a lower rank is an escalation.
+/// Unconfigured levels rank by tier after every configured one.
+pub fn rank(level: &SeverityLevel) -> usize {
+    levels()
+        .iter()
+        .position(|l| l.code == level.code)
+        .unwrap_or(levels().len() + level.tier as usize)
+}
+
+/// `P1, P2, P3 or P4`, for usage messages.
+pub fn codes() -> String {
+    let codes: Vec<&str> = levels().iter().map(|level| level.code.as_str()).collect();
+    match codes.split_last() {
+        Some((last, [])) => last.to_string(),
+        Some((last, rest)) => format!("{} or {}", rest.join(", "), last),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_levels() {
+        assert_eq!(codes(), "P1, P2, P3 or P4");
+        assert_eq!(parse("p2").unwrap().label, "P2 (High)");
+        assert_eq!(for_tier(Severity::P3).emoji, "🟢");
+        assert_eq!(resolve(None, Severity::P1).code, "P1");
+        // A code from a since-removed configuration falls back to the tier
+        assert_eq!(resolve(Some("SEV0"), Severity::P1).code, "P1");
+        assert!(rank(for_tier(Severity::P1)) < rank(for_tier(Severity::P4)));
+        assert_eq!(
+            parse("SEV9").unwrap_err(),
+            "Invalid severity 'SEV9'. Use P1, P2, P3 or P4"
+        );
+    }
+}
@@ -0,0 +1,172 @@
+//! Tiny PNG sparkline renderer for Slack image blocks.
+//!
+//! Sparklines are a few hundred pixels wide, so the PNG is written with a
+//! three-colour palette and uncompressed deflate blocks rather than pulling
+//! in an image crate.
+
+const BACKGROUND: [u8; 3] = [0xFF, 0xFF, 0xFF];
+const FILL: [u8; 3] = [0xD6, 0xE4, 0xF5];
+const LINE: [u8; 3] = [0x12, 0x64, 0xA3];
+
+pub(crate) const BACKGROUND_INDEX: u8 = 0;
+pub(crate) const FILL_INDEX: u8 = 1;
+pub(crate) const LINE_INDEX: u8 = 2;
+
+/// Blank rows above the highest point so the peak isn't clipped.
+const TOP_PADDING: u32 = 2;
+
+/// Render `values` left to right as a filled line chart, scaled so the
+/// largest value touches the top. Returns PNG bytes.
+pub fn render_png(values: &[i64], width: u32, height: u32) -> Vec<u8> {
+    let pixels = rasterize(values, width, height);
+    encode_png(&pixels, width, height)
+}
+
+/// One palette index per pixel, row-major.
+fn rasterize(values: &[i64], width: u32, height: u32) -> Vec<u8> {
+    let (w, h) = (width as usize, height as usize);
+    let mut pixels = vec![BACKGROUND_INDEX; w * h];
+    if w == 0 || h == 0 {
+        return pixels;
+    }
+
+    let max = values.iter().copied().max().unwrap_or(0).max(1) as f64;
+    let usable = height.saturating_sub(TOP_PADDING + 1) as f64;
+    let bottom = h - 1;
+    let row_for = |value: f64| bottom - ((value.max(0.0) / max) * usable).round() as usize;
+
+    let mut previous_row = None;
+    for x in 0..w {
+        let row = row_for(sample(values, x, w));
+        for y in row..h {
+            pixels[y * w + x] = FILL_INDEX;
+        }
+        // Join steep segments so the line stays continuous
+        let (top, low) = match previous_row {
+            Some(prev) => (row.min(prev), row.max(prev)),
+            None => (row, row),
+        };
+        for y in top..=low {
+            pixels[y * w + x] = LINE_INDEX;
+        }
+        previous_row = Some(row);
+    }
+    pixels
+}
+
+/// Value at column `x`, linearly interpolated between neighbouring points.
+fn sample(values: &[i64], x: usize, width: usize) -> f64 {
+    match values.len() {
+        0 => 0.0,
+        1 => values[0] as f64,
+        n => {
+            let position = x as f64 * (n - 1) as f64 / (width.max(2) - 1) as f64;
+            let left = position.floor() as usize;
+            let right = (left + 1).min(n - 1);
+            let t = position - left as f64;
+            values[left] as f64 * (1.0 - t) + values[right] as f64 * t
+        }
+    }
+}
+
+/// PNG of `pixels` (palette indices, row-major) in the sparkline colours.
+pub(crate) fn encode_png(pixels: &[u8], width: u32, height: u32) -> Vec<u8> {
+    let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+
+    let mut header = Vec::with_capacity(13);
+    header.extend_from_slice(&width.to_be_bytes());
+    header.extend_from_slice(&height.to_be_bytes());
+    // 8-bit palette, default compression/filter, no interlace
+    header.extend_from_slice(&[8, 3, 0, 0, 0]);
+    write_chunk(&mut png, b"IHDR", &header);
+    write_chunk(&mut png, b"PLTE", &[BACKGROUND, FILL, LINE].concat());
+
+    // Each scanline is prefixed with filter type 0 (none)
+    let mut raw = Vec::with_capacity(pixels.len() + height as usize);
+    for row in pixels.chunks(width.max(1) as usize) {
+        raw.push(0);
+        raw.extend_from_slice(row);
+    }
+    write_chunk(&mut png, b"IDAT", &zlib_stored(&raw));
+    write_chunk(&mut png, b"IEND", &[]);
+    png
+}
+
+fn write_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend_from_slice(kind);
+    png.extend_from_slice(data);
+    let crc = crc32(&png[start..]);
+    png.extend_from_slice(&crc.to_be_bytes());
+}
+
+/// zlib stream made of uncompressed deflate blocks (RFC 1950/1951).
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut out = vec![0x78, 0x01];
+    let mut blocks = data.chunks(u16::MAX as usize).peekable();
+    if blocks.peek().is_none() {
+        out.extend_from_slice(&[1, 0, 0, 0xFF, 0xFF]);
+    }
+    while let Some(block) = blocks.next() {
+        out.push(u8::from(blocks.peek().is_none()));
+        let len = block.len() as u16;
+        out.extend_from_slice(&len.to_le_bytes());
+        out.extend_from_slice(&(!len).to_le_bytes());
+        out.extend_from_slice(block);
+    }
+    out.extend_from_slice(&adler32(data).to_be_bytes());
+    out
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in data {
+        a = (a + u32::from(byte)) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for &byte in data {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_png_writes_valid_chunks() {
+        let png = render_png(&[0, 3, 1, 5], 40, 12);
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+        assert_eq!(&png[12..16], b"IHDR");
+        assert_eq!(u32::from_be_bytes(png[16..20].try_into().unwrap()), 40);
+        assert_eq!(u32::from_be_bytes(png[20..24].try_into().unwrap()), 12);
+        // IEND is empty, so its CRC is a well-known constant
+        assert_eq!(&png[png.len() - 8..], b"IEND\xAE\x42\x60\x82");
+    }
+
+    #[test]
+    fn test_rasterize_scales_peak_to_top() {
+        let pixels = rasterize(&[0, 4], 5, 10);
+        let column = |x: usize| (0..10).map(|y| pixels[y * 5 + x]).collect::<Vec<_>>();
+        // Zero sits on the bottom row, the peak just under the padding
+        assert_eq!(column(0)[9], LINE_INDEX);
+        assert_eq!(column(0)[8], BACKGROUND_INDEX);
+        assert_eq!(column(4)[TOP_PADDING as usize], LINE_INDEX);
+        assert_eq!(column(4)[9], FILL_INDEX);
+        assert_eq!(adler32(b"Wikipedia"), 0x11E6_0398);
+    }
+}
@@ -0,0 +1,148 @@
+//! Human-readable times, dates and durations in the workspace's
+//! `TIME_FORMAT` and `DATE_FORMAT`.
+//!
+//! Everything shown to people (Slack blocks, postmortems, the markdown
+//! timeline) goes through here; machine formats such as CSV/JSON exports,
+//! channel names and request signatures keep their fixed formats. Times are
+//! always UTC.
+
+use crate::config::{AppConfig, ClockFormat, DateFormat};
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use std::sync::OnceLock;
+
+static FORMAT: OnceLock<TimeFormat> = OnceLock::new();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TimeFormat {
+    pub clock: ClockFormat,
+    pub date: DateFormat,
+}
+
+impl TimeFormat {
+    pub fn from_config(config: &AppConfig) -> Self {
+        Self {
+            clock: config.time_format,
+            date: config.date_format,
+        }
+    }
+
+    /// `14:05` or `2:05 PM`
+    pub fn clock(&self, at: &DateTime<Utc>) -> String {
+        match self.clock {
+            ClockFormat::TwentyFourHour => at.format("%H:%M").to_string(),
+            ClockFormat::TwelveHour => at.format("%-I:%M %p").to_string(),
+        }
+    }
+
+    /// `2024-11-15`, `15/11/2024` or `11/15/2024`
+    pub fn date(&self, date: &impl Datelike) -> String {
+        let date = naive_date(date);
+        match self.date {
+            DateFormat::Ymd => date.format("%Y-%m-%d"),
+            DateFormat::Dmy => date.format("%d/%m/%Y"),
+            DateFormat::Mdy => date.format("%m/%d/%Y"),
+        }
+        .to_string()
+    }
+
+    /// Short day without the year: `Nov 15` or `15 Nov`
+    pub fn day(&self, date: &impl Datelike) -> String {
+        let date = naive_date(date);
+        match self.date {
+            DateFormat::Dmy => date.format("%-d %b"),
+            DateFormat::Ymd | DateFormat::Mdy => date.format("%b %-d"),
+        }
+        .to_string()
+    }
+
+    /// `2024-11-15 14:05 UTC`
+    pub fn date_time(&self, at: &DateTime<Utc>) -> String {
+        format!("{} {} UTC", self.date(at), self.clock(at))
+    }
+}
+
+/// Set the workspace format once at startup. Until then (and in tests) the
+/// defaults apply: 24-hour clock, ISO dates.
+pub fn init(format: TimeFormat) {
+    if FORMAT.set(format).is_err() {
+        tracing::warn!("Time format already initialised; keeping the first one");
+    }
+}
+
+pub fn current() -> TimeFormat {
+    FORMAT.get().copied().unwrap_or_default()
+}
+
+/// Time of day in the workspace clock, without a zone: `14:05`
+pub fn clock(at: &DateTime<Utc>) -> String {
+    current().clock(at)
+}
+
+/// Time of day with its zone: `14:05 UTC`
+pub fn clock_utc(at: &DateTime<Utc>) -> String {
+    format!("{} UTC", current().clock(at))
+}
+
+pub fn date(date: &impl Datelike) -> String {
+    current().date(date)
+}
+
+pub fn day(date: &impl Datelike) -> String {
+    current().day(date)
+}
+
+pub fn date_time(at: &DateTime<Utc>) -> String {
+    current().date_time(at)
+}
+
+/// Incident-style duration: `45min`, `2h 5min`
+pub fn duration(minutes: i64) -> String {
+    let (hours, mins) = (minutes / 60, minutes % 60);
+    if hours > 0 {
+        format!("{}h {}min", hours, mins)
+    } else {
+        format!("{}min", mins)
+    }
+}
+
+fn naive_date(date: &impl Datelike) -> NaiveDate {
+    NaiveDate::from_ymd_opt(date.year(), date.month(), date.day()).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_formats() {
+        let at = Utc.with_ymd_and_hms(2024, 11, 5, 14, 5, 0).unwrap();
+
+        let iso = TimeFormat::default();
+        assert_eq!(iso.clock(&at), "14:05");
+        assert_eq!(iso.date(&at), "2024-11-05");
+        assert_eq!(iso.day(&at), "Nov 5");
+        assert_eq!(iso.date_time(&at), "2024-11-05 14:05 UTC");
+
+        let uk = TimeFormat {
+            clock: ClockFormat::TwentyFourHour,
+            date: DateFormat::Dmy,
+        };
+        assert_eq!(uk.date(&at.date_naive()), "05/11/2024");
+        assert_eq!(uk.day(&at), "5 Nov");
+
+        let us = TimeFormat {
+            clock: ClockFormat::TwelveHour,
+            date: DateFormat::Mdy,
+        };
+        assert_eq!(us.clock(&at), "2:05 PM");
+        assert_eq!(us.date_time(&at), "11/05/2024 2:05 PM UTC");
+        assert_eq!(
+            us.clock(&Utc.with_ymd_and_hms(2024, 11, 5, 0, 30, 0).unwrap()),
+            "12:30 AM"
+        );
+
+        assert_eq!(duration(45), "45min");
+        assert_eq!(duration(125), "2h 5min");
+    }
+}
@@ -0,0 +1,160 @@
+use axum::routing::post;
+use axum::{Json, Router};
+use incident_bot::adapters::jira::JiraClient;
+use incident_bot::commands::action::handle_action;
+use incident_bot::db::models::{ActionItemStatus, Severity};
+use incident_bot::db::queries::action_items::list_action_items;
+use incident_bot::jobs::Job;
+use incident_bot::services::incident::IncidentService;
+use incident_bot::services::postmortem::PostmortemService;
+use incident_bot::slack::mock::{MockSlackClient, SlackCall};
+use incident_bot::AppState;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+mod common;
+
+fn jira_state(
+    ctx: &common::TestContext,
+    mock: Arc<MockSlackClient>,
+) -> (AppState, mpsc::UnboundedReceiver<Job>) {
+    let config = incident_bot::AppConfig {
+        jira_base_url: Some("https://example.atlassian.net".to_string()),
+        jira_email: Some("bot@example.com".to_string()),
+        jira_api_token: Some("jira-token".to_string()),
+        jira_projects: HashMap::from([("Test Service".to_string(), "OPS".to_string())]),
+        ..common::test_config()
+    };
+    let (job_sender, job_receiver) = mpsc::unbounded_channel();
+    (
+        AppState::with_slack_client(ctx.pool.clone(), config, job_sender, mock),
+        job_receiver,
+    )
+}
+
+#[tokio::test]
+async fn test_action_items_are_tracked_and_listed_in_postmortem() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let state = common::mock_state(&ctx.pool, mock.clone());
+    let incident =
+        common::incident_in_channel(&ctx, Severity::P2, "Test Service", "C_ACTION_ONE").await;
+
+    for text in [
+        "action \"fix failover\" <@U024OWNER|dana>",
+        "action \"add replica lag alarm\"",
+    ] {
+        handle_action(
+            state.clone(),
+            common::slash_command(text, "U024COMMANDER", "C_ACTION_ONE"),
+        )
+        .await
+        .expect("Action command failed");
+    }
+    assert_eq!(mock.posted_channels(), vec!["C_ACTION_ONE", "C_ACTION_ONE"]);
+
+    // Only the owner or commander can close an item
+    handle_action(
+        state.clone(),
+        common::slash_command("action done 1", "U024OTHER", "C_ACTION_ONE"),
+    )
+    .await
+    .unwrap();
+    assert!(mock
+        .ephemeral_text()
+        .contains("Only the action item owner or incident commander"));
+    handle_action(
+        state.clone(),
+        common::slash_command("action done 2", "U024COMMANDER", "C_ACTION_ONE"),
+    )
+    .await
+    .unwrap();
+
+    let items = list_action_items(&ctx.pool, incident.id).await.unwrap();
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[0].owner_id, "U024OWNER");
+    assert_eq!(items[0].status, ActionItemStatus::Open);
+    // Defaults to the caller when no owner is given
+    assert_eq!(items[1].owner_id, "U024COMMANDER");
+    assert_eq!(items[1].status, ActionItemStatus::Done);
+
+    let resolved = IncidentService::new(ctx.pool.clone())
+        .resolve_incident(incident.id, "U024COMMANDER".to_string())
+        .await
+        .unwrap();
+    let postmortem = PostmortemService::new(ctx.pool.clone())
+        .generate(&resolved)
+        .await
+        .unwrap();
+    assert!(postmortem.contains("- [ ] #1 fix failover — <@U024OWNER>"));
+    // Closed items are left off the checklist (they still appear in the timeline)
+    assert!(!postmortem.contains("- [ ] #2"));
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_mapped_service_gets_a_jira_ticket() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let (state, mut job_receiver) = jira_state(&ctx, mock.clone());
+    let incident =
+        common::incident_in_channel(&ctx, Severity::P2, "Test Service", "C_ACTION_JIRA").await;
+
+    handle_action(
+        state.clone(),
+        common::slash_command(
+            "action \"fix failover\" <@U024OWNER>",
+            "U024COMMANDER",
+            "C_ACTION_JIRA",
+        ),
+    )
+    .await
+    .expect("Action command failed");
+
+    let Ok(Job::CreateJiraIssue {
+        action_item_id,
+        project_key,
+    }) = job_receiver.try_recv()
+    else {
+        panic!("Expected a Jira issue job");
+    };
+    assert_eq!(project_key, "OPS");
+
+    // Fake Jira that echoes the project into the issue key
+    let app = Router::new().route(
+        "/rest/api/3/issue",
+        post(|Json(body): Json<Value>| async move {
+            let project = body["fields"]["project"]["key"]
+                .as_str()
+                .unwrap()
+                .to_string();
+            Json(json!({ "id": "10001", "key": format!("{}-42", project) }))
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+    let jira = JiraClient::new(
+        format!("http://{}", addr),
+        "bot@example.com".to_string(),
+        "jira-token".to_string(),
+    );
+
+    incident_bot::jobs::jira_sync::execute(&jira, &state, action_item_id, project_key)
+        .await
+        .expect("Jira sync failed");
+
+    let items = list_action_items(&ctx.pool, incident.id).await.unwrap();
+    assert_eq!(items[0].jira_issue_key.as_deref(), Some("OPS-42"));
+    let announced = mock.calls().into_iter().any(|call| {
+        matches!(call, SlackCall::PostMessage { blocks, .. } if blocks[0].to_string().contains("OPS-42"))
+    });
+    assert!(announced);
+
+    ctx.cleanup().await;
+}
@@ -0,0 +1,168 @@
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use axum::Router;
+use base64::Engine;
+use incident_bot::db::models::{Severity, TimelineEventType};
+use incident_bot::services::incident::IncidentService;
+use incident_bot::services::timeline::TimelineService;
+use incident_bot::slack::mock::MockSlackClient;
+use incident_bot::{AppConfig, AppState};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tower::ServiceExt;
+
+mod common;
+
+fn state(pool: &sqlx_postgres::PgPool) -> AppState {
+    let config = AppConfig {
+        alert_source_tokens: HashMap::from([
+            ("alertmanager".to_string(), "am-secret".to_string()),
+            ("cloudwatch".to_string(), "sns-secret".to_string()),
+        ]),
+        ..common::test_config()
+    };
+    let (job_sender, _job_receiver) = mpsc::unbounded_channel();
+    AppState::with_slack_client(
+        pool.clone(),
+        config,
+        job_sender,
+        Arc::new(MockSlackClient::new()),
+    )
+}
+
+async fn post(
+    state: &AppState,
+    source: &str,
+    authorization: &str,
+    body: Value,
+) -> (StatusCode, Value) {
+    let router = Router::new()
+        .nest("/integrations", incident_bot::api::alerts::router())
+        .with_state(state.clone());
+    let request = Request::builder()
+        .method("POST")
+        .uri(format!("/integrations/alerts/{}", source))
+        .header("Authorization", authorization)
+        .header("Content-Type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+    let response = router.oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (
+        status,
+        serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+    )
+}
+
+#[tokio::test]
+async fn test_alerts_are_recorded_on_the_open_incident_for_their_service() {
+    let ctx = common::TestContext::new().await;
+    let state = state(&ctx.pool);
+    let incident = IncidentService::new(ctx.pool.clone())
+        .create_incident(
+            "Checkout errors".to_string(),
+            Severity::P2,
+            "Test Service".to_string(),
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .unwrap();
+
+    let (status, report) = post(
+        &state,
+        "alertmanager",
+        "Bearer am-secret",
+        json!({
+            "version": "4",
+            "status": "firing",
+            "alerts": [
+                {
+                    "status": "firing",
+                    "labels": { "alertname": "HighErrorRate", "service": "test service", "severity": "critical" },
+                    "annotations": { "summary": "5xx above 5%", "description": "checkout-api is failing" },
+                    "startsAt": "2024-11-15T10:30:00Z",
+                    "generatorURL": "https://prometheus.example.com/graph",
+                    "fingerprint": "a1b2c3"
+                },
+                {
+                    "status": "firing",
+                    "labels": { "alertname": "DiskFull", "service": "billing" },
+                    "annotations": {},
+                    "fingerprint": "d4e5f6"
+                }
+            ]
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{}", report);
+    assert_eq!(
+        report,
+        json!({ "received": 2, "recorded": 1, "unmatched": 1 })
+    );
+
+    // CloudWatch alarms arrive through SNS with the secret as basic auth
+    let alarm = json!({
+        "AlarmName": "HighErrorRate",
+        "AlarmDescription": "Error rate on payments service:payments",
+        "NewStateValue": "OK",
+    });
+    let basic = base64::engine::general_purpose::STANDARD.encode("sns:sns-secret");
+    let (status, report) = post(
+        &state,
+        "cloudwatch",
+        &format!("Basic {}", basic),
+        json!({ "Type": "Notification", "Message": alarm.to_string() }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{}", report);
+    assert_eq!(report["recorded"], 0);
+    assert_eq!(report["unmatched"], 1);
+
+    let timeline = TimelineService::new(ctx.pool.clone())
+        .get_timeline(incident.id)
+        .await
+        .unwrap();
+    let alert = timeline
+        .iter()
+        .find(|e| e.posted_by == "alertmanager")
+        .unwrap();
+    assert_eq!(alert.event_type, TimelineEventType::Note);
+    assert_eq!(
+        alert.message,
+        "🔔 Alertmanager: 5xx above 5% firing — checkout-api is failing (https://prometheus.example.com/graph)"
+    );
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_alert_sources_require_their_own_token() {
+    let ctx = common::TestContext::new().await;
+    let state = state(&ctx.pool);
+    let body = json!({ "alerts": [] });
+
+    let (status, _) = post(&state, "alertmanager", "Bearer sns-secret", body.clone()).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    // Registered but not configured, and not registered at all
+    let (status, _) = post(&state, "datadog", "Bearer am-secret", body.clone()).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = post(&state, "pagerduty", "Bearer am-secret", body.clone()).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, _) = post(
+        &state,
+        "alertmanager",
+        "Bearer am-secret",
+        json!({ "alerts": "nope" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    ctx.cleanup().await;
+}
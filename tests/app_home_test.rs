@@ -0,0 +1,230 @@
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use axum::routing::post;
+use axum::Router;
+use hmac::{Hmac, Mac};
+use incident_bot::commands::resolved::handle_home_resolve;
+use incident_bot::db::models::{IncidentStatus, Severity};
+use incident_bot::services::incident::IncidentService;
+use incident_bot::services::roles::RoleService;
+use incident_bot::slack::home::{publish_home, HOME_RESOLVE_ACTION};
+use incident_bot::slack::mock::{MockSlackClient, SlackCall};
+use serde_json::Value;
+use sha2::Sha256;
+use std::sync::Arc;
+use tower::ServiceExt;
+
+mod common;
+
+fn published_views(mock: &MockSlackClient) -> Vec<(String, Value)> {
+    mock.calls()
+        .into_iter()
+        .filter_map(|call| match call {
+            SlackCall::PublishView { user_id, view } => Some((user_id, view)),
+            _ => None,
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_home_lists_incidents_where_viewer_is_involved() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let state = common::mock_state(&ctx.pool, mock.clone());
+    let incident =
+        common::incident_in_channel(&ctx, Severity::P1, "Test Service", "C_HOME_ONE").await;
+    RoleService::new(ctx.pool.clone())
+        .claim(
+            &incident,
+            &["scribe".to_string()],
+            "scribe",
+            "U_HOME_SCRIBE",
+        )
+        .await
+        .expect("Claim failed");
+
+    publish_home(&state, "U_HOME_SCRIBE").await.unwrap();
+    publish_home(&state, "U_HOME_BYSTANDER").await.unwrap();
+
+    let views = published_views(&mock);
+    let scribe_view = views[0].1.to_string();
+    assert_eq!(views[0].0, "U_HOME_SCRIBE");
+    assert!(scribe_view.contains("*You:* Scribe"));
+    assert!(scribe_view.contains("app_redirect?channel=C_HOME_ONE"));
+    // Only the commander can resolve from Home
+    assert!(!scribe_view.contains(HOME_RESOLVE_ACTION));
+    assert!(views[1].1.to_string().contains("not involved"));
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_home_shows_open_high_severity_banner() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let state = common::mock_state(&ctx.pool, mock.clone());
+
+    publish_home(&state, "U_HOME_BYSTANDER").await.unwrap();
+    let quiet =
+        common::incident_in_channel(&ctx, Severity::P1, "Test Service", "C_HOME_QUIET").await;
+    sqlx::query::query("UPDATE incidents SET is_quiet = TRUE WHERE id = $1")
+        .bind(quiet.id)
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+    common::incident_in_channel(&ctx, Severity::P1, "Test Service", "C_HOME_ONE").await;
+    common::incident_in_channel(&ctx, Severity::P1, "Test Service", "C_HOME_TWO").await;
+
+    // Counts are cached briefly, so the first view's "none open" sticks
+    publish_home(&state, "U_HOME_BYSTANDER").await.unwrap();
+    let fresh = common::mock_state(&ctx.pool, mock.clone());
+    publish_home(&fresh, "U_HOME_BYSTANDER").await.unwrap();
+
+    let views = published_views(&mock);
+    assert_eq!(views[0].1["blocks"][1]["type"], "divider");
+    assert_eq!(views[1].1["blocks"][1]["type"], "divider");
+    // Quiet incidents are never counted
+    assert_eq!(
+        views[2].1["blocks"][1]["elements"][0]["text"],
+        "⚠️ 2 open P1s right now"
+    );
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_home_resolve_button_requires_commander() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let state = common::mock_state(&ctx.pool, mock.clone());
+    let incident =
+        common::incident_in_channel(&ctx, Severity::P1, "Test Service", "C_HOME_TWO").await;
+    let incident_service = IncidentService::new(ctx.pool.clone());
+
+    handle_home_resolve(
+        state.clone(),
+        "U_HOME_OTHER".to_string(),
+        &incident.id.to_string(),
+    )
+    .await
+    .expect("Denial should be reported, not fail");
+    assert_eq!(mock.dm_recipients(), vec!["U_HOME_OTHER"]);
+    assert_eq!(
+        incident_service
+            .get_by_id(incident.id)
+            .await
+            .unwrap()
+            .status,
+        IncidentStatus::Declared
+    );
+
+    handle_home_resolve(state, "U024COMMANDER".to_string(), &incident.id.to_string())
+        .await
+        .expect("Resolve failed");
+    assert_eq!(
+        incident_service
+            .get_by_id(incident.id)
+            .await
+            .unwrap()
+            .status,
+        IncidentStatus::Resolved
+    );
+    // Home is republished for both clickers; the resolved incident drops off
+    let views = published_views(&mock);
+    assert_eq!(views.len(), 2);
+    assert!(views[1].1.to_string().contains("not involved"));
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_events_endpoint_answers_url_verification() {
+    let ctx = common::TestContext::new().await;
+    let state = common::mock_state(&ctx.pool, Arc::new(MockSlackClient::new()));
+    let app = Router::new()
+        .route(
+            "/slack/events",
+            post(incident_bot::slack::events::handle_event),
+        )
+        .with_state(state);
+
+    let body = r#"{"type":"url_verification","challenge":"3eZbrw1aBm2rZgRNFdxV"}"#;
+    let timestamp = chrono::Utc::now().timestamp().to_string();
+    let mut mac = Hmac::<Sha256>::new_from_slice(b"test-secret").unwrap();
+    mac.update(format!("v0:{}:{}", timestamp, body).as_bytes());
+    let signature = format!("v0={}", hex::encode(mac.finalize().into_bytes()));
+
+    let response = app
+        .oneshot(
+            Request::post("/slack/events")
+                .header("X-Slack-Request-Timestamp", timestamp)
+                .header("X-Slack-Signature", signature)
+                .body(Body::from(body))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert_eq!(&bytes[..], b"3eZbrw1aBm2rZgRNFdxV");
+}
+
+fn signed_event_request(body: &str, retry_num: Option<&str>) -> Request<Body> {
+    let timestamp = chrono::Utc::now().timestamp().to_string();
+    let mut mac = Hmac::<Sha256>::new_from_slice(b"test-secret").unwrap();
+    mac.update(format!("v0:{}:{}", timestamp, body).as_bytes());
+    let signature = format!("v0={}", hex::encode(mac.finalize().into_bytes()));
+
+    let mut request = Request::post("/slack/events")
+        .header("X-Slack-Request-Timestamp", timestamp)
+        .header("X-Slack-Signature", signature);
+    if let Some(retry_num) = retry_num {
+        request = request
+            .header("X-Slack-Retry-Num", retry_num)
+            .header("X-Slack-Retry-Reason", "http_timeout");
+    }
+    request.body(Body::from(body.to_string())).unwrap()
+}
+
+#[tokio::test]
+async fn test_events_endpoint_skips_retried_event_ids() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let state = common::mock_state(&ctx.pool, mock.clone());
+    let app = Router::new()
+        .route(
+            "/slack/events",
+            post(incident_bot::slack::events::handle_event),
+        )
+        .with_state(state);
+
+    let body = r#"{"type":"event_callback","event_id":"Ev_DEDUP_1","event":{"type":"app_home_opened","user":"U_HOME_RETRY","tab":"home"}}"#;
+    for retry_num in [None, Some("1"), Some("2")] {
+        let response = app
+            .clone()
+            .oneshot(signed_event_request(body, retry_num))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    // The first delivery publishes App Home in a spawned task
+    for _ in 0..50 {
+        if !published_views(&mock).is_empty() {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+    }
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    assert_eq!(published_views(&mock).len(), 1);
+
+    let output = incident_bot::metrics::metrics().render();
+    assert!(
+        output.contains("incident_bot_slack_event_retries_total{event_type=\"app_home_opened\"} 2")
+    );
+
+    ctx.cleanup().await;
+}
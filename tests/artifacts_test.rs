@@ -0,0 +1,161 @@
+use axum::http::StatusCode;
+use axum::routing::get;
+use axum::Router;
+use chrono::Duration;
+use incident_bot::commands::resolved::resolve_and_announce;
+use incident_bot::db::models::Severity;
+use incident_bot::jobs::channel_archive::archive_once;
+use incident_bot::services::incident::IncidentService;
+use incident_bot::slack::client::HistoryMessage;
+use incident_bot::slack::mock::MockSlackClient;
+use incident_bot::{AppConfig, AppState};
+use serde_json::Value;
+use std::sync::Arc;
+
+mod common;
+
+fn router(state: AppState) -> Router {
+    Router::new()
+        .route(
+            "/artifacts/{*key}",
+            get(incident_bot::api::artifacts::download_artifact),
+        )
+        .nest("/api/v1", incident_bot::api::router(state.clone()))
+        .with_state(state)
+}
+
+fn json(bytes: &[u8]) -> Value {
+    serde_json::from_slice(bytes).unwrap_or(Value::Null)
+}
+
+/// Path and query of a signed local link (`http://localhost:PORT/artifacts/...`).
+fn local_path(url: &str) -> String {
+    url.split_once("/artifacts/")
+        .map(|(_, rest)| format!("/artifacts/{}", rest))
+        .expect("local artifact link")
+}
+
+#[tokio::test]
+async fn test_snapshots_are_stored_and_served_through_signed_links() {
+    let ctx = common::TestContext::new().await;
+    let state = common::mock_state(&ctx.pool, Arc::new(MockSlackClient::new()));
+
+    let (status, _) = common::respond(
+        router(state.clone()),
+        common::api_request("GET", "/api/v1/replication/snapshots/latest", None),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, body) = common::respond(
+        router(state.clone()),
+        common::api_request("POST", "/api/v1/replication/snapshots", None),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let link = json(&body);
+    assert_eq!(link["kind"], "replication_snapshot");
+    assert_eq!(link["content_type"], "application/json");
+    assert!(link.get("storage_key").is_none());
+    let url = link["url"].as_str().unwrap();
+
+    let (status, body) = common::respond(
+        router(state.clone()),
+        common::api_request("GET", &local_path(url), None),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let snapshot = json(&body);
+    assert!(snapshot["exported_at"].is_string());
+    assert_eq!(link["size_bytes"].as_u64().unwrap(), body.len() as u64);
+
+    // Tampered links are refused
+    let tampered = local_path(url).replace("signature=", "signature=00");
+    let (status, _) = common::respond(
+        router(state.clone()),
+        common::api_request("GET", &tampered, None),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, body) = common::respond(
+        router(state.clone()),
+        common::api_request("GET", "/api/v1/replication/snapshots/latest", None),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json(&body)["id"], link["id"]);
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_channel_archive_keeps_transcript_artifact() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let config = AppConfig {
+        channel_archive_after_days: 7,
+        ..common::test_config()
+    };
+    let (job_sender, _job_receiver) = tokio::sync::mpsc::unbounded_channel();
+    let state = AppState::with_slack_client(ctx.pool.clone(), config, job_sender, mock.clone());
+
+    let incident_service = IncidentService::new(ctx.pool.clone());
+    let incident = incident_service
+        .create_incident(
+            "Transcript archive".to_string(),
+            Severity::P3,
+            "Test Service".to_string(),
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .unwrap();
+    incident_service
+        .update_channel_id(incident.id, "C_ARTIFACT_TRANSCRIPT".to_string())
+        .await
+        .unwrap();
+    mock.add_history(
+        "C_ARTIFACT_TRANSCRIPT",
+        vec![HistoryMessage {
+            ts: "1700000000.000100".to_string(),
+            user: Some("U024COMMANDER".to_string()),
+            text: "Rolled back the deploy".to_string(),
+            subtype: None,
+            thread_ts: None,
+        }],
+    );
+    let incident = incident_service.get_by_id(incident.id).await.unwrap();
+    let resolved = resolve_and_announce(&state, &incident, "U024COMMANDER")
+        .await
+        .unwrap();
+
+    archive_once(&state, resolved.resolved_at.unwrap() + Duration::days(8))
+        .await
+        .unwrap();
+
+    let (status, body) = common::respond(
+        router(state.clone()),
+        common::api_request(
+            "GET",
+            &format!("/api/v1/incidents/{}/artifacts", incident.id),
+            None,
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let links = json(&body);
+    assert_eq!(links.as_array().unwrap().len(), 1);
+    assert_eq!(links[0]["kind"], "channel_transcript");
+
+    let (status, body) = common::respond(
+        router(state.clone()),
+        common::api_request("GET", &local_path(links[0]["url"].as_str().unwrap()), None),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let transcript = json(&body);
+    assert_eq!(transcript["channel_id"], "C_ARTIFACT_TRANSCRIPT");
+    assert_eq!(transcript["messages"][0]["text"], "Rolled back the deploy");
+
+    ctx.cleanup().await;
+}
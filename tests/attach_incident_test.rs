@@ -0,0 +1,141 @@
+use incident_bot::commands::attach::handle_attach;
+use incident_bot::commands::declare::handle_modal_submission;
+use incident_bot::commands::resolved::resolve_and_announce;
+use incident_bot::db::queries::incidents;
+use incident_bot::slack::events::{SlashCommandPayload, ViewPayload};
+use incident_bot::slack::mock::{MockSlackClient, SlackCall};
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+mod common;
+
+const WAR_ROOM: &str = "C_WAR_ROOM";
+
+fn attach_command() -> SlashCommandPayload {
+    SlashCommandPayload {
+        command: "/incident".to_string(),
+        text: "attach".to_string(),
+        user_id: "U024COMMANDER".to_string(),
+        channel_id: WAR_ROOM.to_string(),
+        response_url: "https://hooks.slack.test/response".to_string(),
+        trigger_id: "trigger-attach".to_string(),
+    }
+}
+
+fn submitted(modal: &Value, title: &str) -> ViewPayload {
+    serde_json::from_value(json!({
+        "callback_id": modal["callback_id"],
+        "private_metadata": modal["private_metadata"],
+        "state": { "values": {
+            "title_block": { "title_input": { "value": title } },
+            "severity_block": { "severity_select": { "selected_option": { "value": "P2" } } },
+            "service_block": { "service_select": { "selected_option": { "value": "Test Service" } } },
+            "commander_block": { "commander_select": { "selected_user": null } }
+        } }
+    }))
+    .unwrap()
+}
+
+fn opened_modals(mock: &MockSlackClient) -> Vec<Value> {
+    mock.calls()
+        .into_iter()
+        .filter_map(|c| match c {
+            SlackCall::OpenModal { view, .. } => Some(view),
+            _ => None,
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_attach_binds_incident_to_existing_channel() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let mut config = common::test_config();
+    config
+        .service_owners
+        .insert("Test Service".to_string(), vec!["U024SVCOWNER".to_string()]);
+    let (job_sender, _job_receiver) = tokio::sync::mpsc::unbounded_channel();
+    let state = incident_bot::AppState::with_slack_client(
+        ctx.pool.clone(),
+        config,
+        job_sender,
+        mock.clone(),
+    );
+
+    handle_attach(state.clone(), attach_command())
+        .await
+        .unwrap();
+    let modal = opened_modals(&mock).remove(0);
+    assert_eq!(modal["private_metadata"], "attach:C_WAR_ROOM");
+    assert_eq!(modal["title"]["text"], "Attach Incident");
+
+    handle_modal_submission(
+        state.clone(),
+        submitted(&modal, "Checkout errors"),
+        "U024COMMANDER".to_string(),
+    )
+    .await
+    .unwrap();
+
+    let calls = mock.calls();
+    assert!(!calls
+        .iter()
+        .any(|c| matches!(c, SlackCall::CreateConversation { .. })));
+    assert!(calls.iter().any(
+        |c| matches!(c, SlackCall::JoinConversation { channel_id } if channel_id == WAR_ROOM)
+    ));
+    let invited = calls
+        .iter()
+        .find_map(|c| match c {
+            SlackCall::InviteUsers {
+                channel_id,
+                user_ids,
+            } if channel_id == WAR_ROOM => Some(user_ids.clone()),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(invited, vec!["U024COMMANDER", "U024SVCOWNER"]);
+    assert!(calls
+        .iter()
+        .any(|c| matches!(c, SlackCall::PinMessage { channel_id, .. } if channel_id == WAR_ROOM)));
+
+    let attached = incidents::get_incident_by_channel(&ctx.pool, WAR_ROOM)
+        .await
+        .unwrap();
+    assert_eq!(attached.title, "Checkout errors");
+    assert!(attached.pinned_message_ts.is_some());
+
+    // Only one open incident per channel
+    handle_attach(state.clone(), attach_command())
+        .await
+        .unwrap();
+    assert_eq!(opened_modals(&mock).len(), 1);
+    assert!(mock.calls().iter().any(|c| matches!(
+        c,
+        SlackCall::PostToResponseUrl { blocks, .. }
+            if blocks[0].to_string().contains("already has an open incident")
+    )));
+
+    // Once resolved, the war room can host the next incident
+    resolve_and_announce(&state, &attached, "U024COMMANDER")
+        .await
+        .unwrap();
+    handle_attach(state.clone(), attach_command())
+        .await
+        .unwrap();
+    let modal = opened_modals(&mock).pop().unwrap();
+    handle_modal_submission(
+        state,
+        submitted(&modal, "Login errors"),
+        "U024COMMANDER".to_string(),
+    )
+    .await
+    .unwrap();
+    let next = incidents::get_incident_by_channel(&ctx.pool, WAR_ROOM)
+        .await
+        .unwrap();
+    assert_eq!(next.title, "Login errors");
+    assert_ne!(next.id, attached.id);
+
+    ctx.cleanup().await;
+}
@@ -0,0 +1,103 @@
+use axum::body::Body;
+use axum::http::{header, Request, StatusCode};
+use axum::Router;
+use incident_bot::services::audit::AuditService;
+use incident_bot::services::audit_chain::{self, GENESIS_HASH};
+use incident_bot::slack::mock::MockSlackClient;
+use serde_json::json;
+use std::sync::Arc;
+use tower::ServiceExt;
+
+mod common;
+
+async fn export(ctx: &common::TestContext, uri: &str) -> String {
+    let state = common::mock_state(&ctx.pool, Arc::new(MockSlackClient::new()));
+    let router = Router::new()
+        .nest("/api/v1", incident_bot::api::router(state.clone()))
+        .with_state(state);
+
+    let request = Request::builder()
+        .uri(uri)
+        .header("Authorization", "Bearer test-api-token")
+        .body(Body::empty())
+        .unwrap();
+    let response = router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers()[header::CONTENT_TYPE]
+        .to_str()
+        .unwrap()
+        .starts_with("text/csv"));
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    String::from_utf8(bytes.to_vec()).unwrap()
+}
+
+#[tokio::test]
+async fn test_audit_export_verifies_and_detects_tampering() {
+    let ctx = common::TestContext::new().await;
+    let audit = AuditService::new(ctx.pool.clone());
+    for (action, details) in [
+        (
+            "change_status",
+            json!({ "note": "=HYPERLINK(\"x\")", "n": -1 }),
+        ),
+        ("change_severity", json!({ "from": "P2", "to": "P1" })),
+        ("resolve_incident", json!({ "note": "line one,\nline two" })),
+    ] {
+        audit
+            .log_action(
+                None,
+                action.to_string(),
+                "U024COMMANDER".to_string(),
+                None,
+                Some(json!({ "status": "mitigated" })),
+                Some(details),
+            )
+            .await
+            .unwrap();
+    }
+
+    let csv = export(&ctx, "/api/v1/admin/audit/export").await;
+    let summary = audit_chain::verify_csv(&csv).unwrap();
+    assert_eq!(summary.chained_rows, 3);
+    assert_eq!(summary.anchor.as_deref(), Some(GENESIS_HASH));
+
+    // The export itself was audited, and a follow-up export continues the chain
+    let (seq, hash): (i64, String) = sqlx::query_as::query_as(
+        "SELECT seq, row_hash FROM audit_log WHERE action = 'change_severity'",
+    )
+    .fetch_one(&ctx.pool)
+    .await
+    .unwrap();
+    let later = export(
+        &ctx,
+        &format!("/api/v1/admin/audit/export?after_seq={}", seq),
+    )
+    .await;
+    let summary = audit_chain::verify_csv(&later).unwrap();
+    assert_eq!(summary.chained_rows, 2);
+    assert_eq!(summary.anchor, Some(hash));
+    assert!(later.contains("audit_exported"));
+
+    // Editing the CSV breaks it
+    let edited = csv.replace("\"P1\"", "\"P3\"");
+    assert_ne!(edited, csv);
+    assert!(audit_chain::verify_csv(&edited)
+        .unwrap_err()
+        .contains("doesn't match its hash"));
+
+    // So does editing the database and exporting again
+    sqlx::query::query(
+        "UPDATE audit_log SET actor_id = 'U024SOMEONE' WHERE action = 'change_status'",
+    )
+    .execute(&ctx.pool)
+    .await
+    .unwrap();
+    let csv = export(&ctx, "/api/v1/admin/audit/export").await;
+    assert!(audit_chain::verify_csv(&csv)
+        .unwrap_err()
+        .contains("seq 1 doesn't match its hash"));
+
+    ctx.cleanup().await;
+}
@@ -0,0 +1,170 @@
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use axum::Router;
+use incident_bot::commands::audit::handle_audit;
+use incident_bot::db::models::Severity;
+use incident_bot::services::audit::AuditService;
+use incident_bot::services::incident::IncidentService;
+use incident_bot::slack::mock::MockSlackClient;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tower::ServiceExt;
+
+mod common;
+
+async fn get(router: Router, uri: &str) -> (StatusCode, Value) {
+    let request = Request::builder()
+        .uri(uri)
+        .header("Authorization", "Bearer test-api-token")
+        .body(Body::empty())
+        .unwrap();
+    let response = router.oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (
+        status,
+        serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+    )
+}
+
+#[tokio::test]
+async fn test_admin_lists_audit_entries_for_channel_incident() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let state = common::mock_state(&ctx.pool, mock.clone());
+
+    let incident_service = IncidentService::new(ctx.pool.clone());
+    let incident = incident_service
+        .create_incident(
+            "Audited".to_string(),
+            Severity::P2,
+            "Test Service".to_string(),
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .unwrap();
+    incident_service
+        .update_channel_id(incident.id, "C_AUDITED".to_string())
+        .await
+        .unwrap();
+    incident_service
+        .post_status_update(
+            incident.id,
+            "Rolling back".to_string(),
+            "U024COMMANDER".to_string(),
+            Default::default(),
+        )
+        .await
+        .unwrap();
+
+    handle_audit(
+        state.clone(),
+        common::slash_command("audit", "U_BYSTANDER", "C_AUDITED"),
+    )
+    .await
+    .unwrap();
+    assert!(mock
+        .last_response()
+        .contains("Only bot admins can view the audit log"));
+
+    handle_audit(
+        state.clone(),
+        common::slash_command("audit incident:here", "U_ADMIN", "C_AUDITED"),
+    )
+    .await
+    .unwrap();
+    let listed = mock.last_response();
+    assert!(listed.contains("*post_status_update* by <@U024COMMANDER>"));
+    assert!(listed.contains("Rolling back"));
+    assert!(listed.find("post_status_update") < listed.find("declare_incident"));
+
+    handle_audit(
+        state.clone(),
+        common::slash_command(
+            "audit incident:here action:declare_incident",
+            "U_ADMIN",
+            "C_AUDITED",
+        ),
+    )
+    .await
+    .unwrap();
+    assert!(!mock.last_response().contains("post_status_update"));
+
+    handle_audit(
+        state,
+        common::slash_command("audit since:tomorrow", "U_ADMIN", "C_AUDITED"),
+    )
+    .await
+    .unwrap();
+    assert!(mock.last_response().contains("Invalid date 'tomorrow'"));
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_audit_api_pages_through_filtered_entries() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let state = common::mock_state(&ctx.pool, mock);
+    let audit = AuditService::new(ctx.pool.clone());
+    for n in 0..3 {
+        audit
+            .log_action(
+                None,
+                "config_reviewed".to_string(),
+                "U_AUDIT_PAGER".to_string(),
+                None,
+                None,
+                Some(json!({ "n": n })),
+            )
+            .await
+            .unwrap();
+    }
+    audit
+        .log_action(
+            None,
+            "other_action".to_string(),
+            "U_AUDIT_PAGER".to_string(),
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+    let router = Router::new()
+        .nest("/api/v1", incident_bot::api::router(state.clone()))
+        .with_state(state);
+    let (status, first) = get(
+        router.clone(),
+        "/api/v1/audit?actor_id=U_AUDIT_PAGER&action=config_reviewed&limit=2",
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let entries = first["entries"].as_array().unwrap();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0]["details"]["n"], 2);
+    assert_eq!(entries[1]["details"]["n"], 1);
+    let next = first["next_before_seq"].as_i64().expect("another page");
+    assert_eq!(next, entries[1]["seq"].as_i64().unwrap());
+
+    let (_, second) = get(
+        router.clone(),
+        &format!(
+            "/api/v1/audit?actor_id=U_AUDIT_PAGER&action=config_reviewed&limit=2&before_seq={}",
+            next
+        ),
+    )
+    .await;
+    let entries = second["entries"].as_array().unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0]["details"]["n"], 0);
+    assert_eq!(second["next_before_seq"], Value::Null);
+
+    let (status, _) = get(router, "/api/v1/audit?limit=0").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    ctx.cleanup().await;
+}
@@ -0,0 +1,377 @@
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use axum::Router;
+use incident_bot::config::AlertRouteRule;
+use incident_bot::db::models::{IncidentStatus, Severity};
+use incident_bot::db::queries::incidents::get_open_incident_for_service;
+use incident_bot::services::incident::IncidentService;
+use incident_bot::slack::mock::{MockSlackClient, SlackCall};
+use incident_bot::{AppConfig, AppState};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+mod common;
+
+fn state(pool: &sqlx_postgres::PgPool, mock: Arc<MockSlackClient>) -> AppState {
+    let config = AppConfig {
+        alert_source_tokens: HashMap::from([
+            ("alertmanager".to_string(), "am-secret".to_string()),
+            ("datadog".to_string(), "dd-secret".to_string()),
+        ]),
+        alertmanager_routes: vec![AlertRouteRule {
+            matchers: HashMap::from([("team".to_string(), "checkout".to_string())]),
+            service: "Test Service".to_string(),
+            severity: Severity::P1,
+            commander: Some("U024COMMANDER".to_string()),
+        }],
+        datadog_routes: vec![AlertRouteRule {
+            matchers: HashMap::from([("team".to_string(), "payments".to_string())]),
+            service: "Test Service".to_string(),
+            severity: Severity::P2,
+            commander: Some("U024DDCOMMANDER".to_string()),
+        }],
+        ..common::test_config()
+    };
+    let (job_sender, _job_receiver) = mpsc::unbounded_channel();
+    AppState::with_slack_client(pool.clone(), config, job_sender, mock)
+}
+
+async fn post(state: &AppState, authorization: &str, alerts: Value) -> (StatusCode, Value) {
+    post_integration(
+        state,
+        "/integrations/alertmanager",
+        ("Authorization", authorization),
+        json!({ "version": "4", "alerts": alerts }),
+    )
+    .await
+}
+
+async fn post_datadog(state: &AppState, token: &str, payload: Value) -> (StatusCode, Value) {
+    post_integration(
+        state,
+        "/integrations/datadog",
+        ("X-Datadog-Webhook-Token", token),
+        payload,
+    )
+    .await
+}
+
+async fn post_integration(
+    state: &AppState,
+    uri: &str,
+    (header, value): (&str, &str),
+    body: Value,
+) -> (StatusCode, Value) {
+    let router = Router::new()
+        .nest("/integrations", incident_bot::api::alerts::router())
+        .with_state(state.clone());
+    let request = Request::builder()
+        .method("POST")
+        .uri(uri)
+        .header(header, value)
+        .header("Content-Type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+    let (status, bytes) = common::respond(router, request).await;
+    (
+        status,
+        serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+    )
+}
+
+fn alert(status: &str, fingerprint: &str, labels: Value) -> Value {
+    json!({
+        "status": status,
+        "labels": labels,
+        "annotations": { "summary": "Checkout 5xx above 5%", "description": "checkout-api is failing" },
+        "startsAt": "2024-11-15T10:30:00Z",
+        "fingerprint": fingerprint
+    })
+}
+
+fn report(pairs: &[(&str, usize)]) -> Value {
+    let mut report = json!({
+        "received": 0, "declared": 0, "attached": 0, "refired": 0, "updated": 0,
+        "duplicates": 0, "resolved": 0, "incidents_resolved": 0, "unmatched": 0
+    });
+    for (field, count) in pairs {
+        report[*field] = json!(count);
+    }
+    report
+}
+
+fn datadog_alert(transition: &str, scope: &str) -> Value {
+    json!({
+        "alert_id": "4242",
+        "scope": scope,
+        "title": format!("[{}] Checkout latency high", transition),
+        "transition": transition,
+        "tags": "env:prod,team:payments",
+        "body": "p99 above 2s",
+        "link": "https://app.datadoghq.com/monitors/4242",
+        "snapshot": "https://p.datadoghq.com/snapshot/view/4242.png"
+    })
+}
+
+fn alerts_posted(mock: &MockSlackClient, channel: &str) -> Vec<String> {
+    alert_messages(mock, channel)
+        .into_iter()
+        .map(|blocks| blocks[0]["text"]["text"].as_str().unwrap_or("").to_string())
+        .collect()
+}
+
+fn alert_messages(mock: &MockSlackClient, channel: &str) -> Vec<Vec<Value>> {
+    mock.calls()
+        .into_iter()
+        .filter_map(|call| match call {
+            SlackCall::PostMessage { channel_id, blocks } if channel_id == channel => Some(blocks),
+            _ => None,
+        })
+        .filter(|blocks| {
+            blocks[0]["text"]["text"]
+                .as_str()
+                .is_some_and(|text| text.contains(" alert "))
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_alerts_declare_and_resolve_incidents() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let state = state(&ctx.pool, mock.clone());
+    let checkout = json!({ "alertname": "HighErrorRate", "team": "checkout" });
+
+    // A routed alert declares an incident; one for an unknown service doesn't
+    let (status, body) = post(
+        &state,
+        "Bearer am-secret",
+        json!([
+            alert("firing", "fp-checkout", checkout.clone()),
+            alert(
+                "firing",
+                "fp-billing",
+                json!({ "service": "billing", "severity": "critical" })
+            ),
+        ]),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(
+        body,
+        report(&[("received", 2), ("declared", 1), ("unmatched", 1)])
+    );
+
+    let incident = get_open_incident_for_service(&ctx.pool, "Test Service")
+        .await
+        .unwrap()
+        .expect("incident declared");
+    assert_eq!(incident.title, "Checkout 5xx above 5%");
+    assert_eq!(incident.severity, Severity::P1);
+    assert_eq!(incident.commander_id, "U024COMMANDER");
+    let channel = incident.slack_channel_id.clone().expect("channel created");
+    assert_eq!(
+        alerts_posted(&mock, &channel),
+        vec!["🔔 *Alertmanager alert firing:* Checkout 5xx above 5%\n>checkout-api is failing"]
+    );
+
+    // Repeat notifications are deduplicated by fingerprint; a second alert
+    // for the service joins the open incident
+    let (_, body) = post(
+        &state,
+        "Bearer am-secret",
+        json!([
+            alert("firing", "fp-checkout", checkout.clone()),
+            alert(
+                "firing",
+                "fp-latency",
+                json!({ "service": "test service", "severity": "warning" })
+            ),
+        ]),
+    )
+    .await;
+    assert_eq!(
+        body,
+        report(&[("received", 2), ("duplicates", 1), ("attached", 1)])
+    );
+    assert_eq!(alerts_posted(&mock, &channel).len(), 2);
+
+    // The incident stays open until every alert on it resolves
+    let (_, body) = post(
+        &state,
+        "Bearer am-secret",
+        json!([alert("resolved", "fp-checkout", checkout.clone())]),
+    )
+    .await;
+    assert_eq!(body, report(&[("received", 1), ("resolved", 1)]));
+    let service = IncidentService::new(ctx.pool.clone());
+    assert!(!service
+        .get_by_id(incident.id)
+        .await
+        .unwrap()
+        .status
+        .is_terminal());
+
+    let (_, body) = post(
+        &state,
+        "Bearer am-secret",
+        json!([
+            alert(
+                "resolved",
+                "fp-latency",
+                json!({ "service": "test service" })
+            ),
+            alert(
+                "resolved",
+                "fp-latency",
+                json!({ "service": "test service" })
+            ),
+        ]),
+    )
+    .await;
+    assert_eq!(
+        body,
+        report(&[
+            ("received", 2),
+            ("resolved", 1),
+            ("incidents_resolved", 1),
+            ("duplicates", 1)
+        ])
+    );
+    let resolved = service.get_by_id(incident.id).await.unwrap();
+    assert_eq!(resolved.status, IncidentStatus::Resolved);
+
+    // Firing again after the incident resolved declares a new one
+    let (_, body) = post(
+        &state,
+        "Bearer am-secret",
+        json!([alert("firing", "fp-checkout", checkout)]),
+    )
+    .await;
+    assert_eq!(body, report(&[("received", 1), ("declared", 1)]));
+    let reopened = get_open_incident_for_service(&ctx.pool, "Test Service")
+        .await
+        .unwrap()
+        .expect("new incident declared");
+    assert_ne!(reopened.id, incident.id);
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_incidents_declared_by_people_are_not_auto_resolved() {
+    let ctx = common::TestContext::new().await;
+    let state = state(&ctx.pool, Arc::new(MockSlackClient::new()));
+    let incident = IncidentService::new(ctx.pool.clone())
+        .create_incident(
+            "Checkout errors".to_string(),
+            Severity::P2,
+            "Test Service".to_string(),
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .unwrap();
+
+    let labels = json!({ "team": "checkout" });
+    let (_, body) = post(
+        &state,
+        "Bearer am-secret",
+        json!([alert("firing", "fp-joined", labels.clone())]),
+    )
+    .await;
+    assert_eq!(body, report(&[("received", 1), ("attached", 1)]));
+
+    let (_, body) = post(
+        &state,
+        "Bearer am-secret",
+        json!([alert("resolved", "fp-joined", labels)]),
+    )
+    .await;
+    assert_eq!(body, report(&[("received", 1), ("resolved", 1)]));
+    let incident = IncidentService::new(ctx.pool.clone())
+        .get_by_id(incident.id)
+        .await
+        .unwrap();
+    assert_eq!(incident.status, IncidentStatus::Declared);
+
+    // Wrong tokens are rejected; without a token the route doesn't exist
+    let (status, _) = post(&state, "Bearer wrong", json!([])).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let unconfigured = common::mock_state(&ctx.pool, Arc::new(MockSlackClient::new()));
+    let (status, _) = post(&unconfigured, "Bearer am-secret", json!([])).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_datadog_monitors_declare_update_and_resolve_incidents() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let state = state(&ctx.pool, mock.clone());
+
+    // The team tag routes the monitor to a service
+    let (status, body) = post_datadog(
+        &state,
+        "dd-secret",
+        datadog_alert("Triggered", "host:web-1"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body, report(&[("received", 1), ("declared", 1)]));
+    let incident = get_open_incident_for_service(&ctx.pool, "Test Service")
+        .await
+        .unwrap()
+        .expect("incident declared");
+    assert_eq!(incident.title, "Checkout latency high");
+    assert_eq!(incident.severity, Severity::P2);
+    assert_eq!(incident.commander_id, "U024DDCOMMANDER");
+    let channel = incident.slack_channel_id.clone().expect("channel created");
+
+    let firing = alert_messages(&mock, &channel);
+    assert_eq!(firing.len(), 1);
+    let links = firing[0][1]["elements"][0]["text"].as_str().unwrap();
+    assert!(
+        links.contains("<https://p.datadoghq.com/snapshot/view/4242.png|Snapshot>"),
+        "{}",
+        links
+    );
+
+    // Renotifications are posted as updates; another group of the same
+    // multi-alert monitor joins the incident
+    let (_, body) = post_datadog(
+        &state,
+        "dd-secret",
+        datadog_alert("Re-Triggered", "host:web-1"),
+    )
+    .await;
+    assert_eq!(body, report(&[("received", 1), ("updated", 1)]));
+    let (_, body) = post_datadog(
+        &state,
+        "dd-secret",
+        datadog_alert("Triggered", "host:web-2"),
+    )
+    .await;
+    assert_eq!(body, report(&[("received", 1), ("attached", 1)]));
+    assert_eq!(
+        alerts_posted(&mock, &channel)[1],
+        "🔁 *Datadog alert update:* Checkout latency high\n>p99 above 2s"
+    );
+
+    // Recovery of every group resolves the incident
+    for scope in ["host:web-1", "host:web-2"] {
+        post_datadog(&state, "dd-secret", datadog_alert("Recovered", scope)).await;
+    }
+    let resolved = IncidentService::new(ctx.pool.clone())
+        .get_by_id(incident.id)
+        .await
+        .unwrap();
+    assert_eq!(resolved.status, IncidentStatus::Resolved);
+
+    let (status, _) = post_datadog(&state, "wrong", datadog_alert("Triggered", "")).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    ctx.cleanup().await;
+}
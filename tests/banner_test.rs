@@ -0,0 +1,140 @@
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use axum::Router;
+use incident_bot::config::AppConfig;
+use incident_bot::db::models::{Audience, Severity};
+use incident_bot::services::incident::IncidentService;
+use incident_bot::slack::mock::MockSlackClient;
+use incident_bot::AppState;
+use serde_json::Value;
+use std::sync::Arc;
+use tower::ServiceExt;
+
+mod common;
+
+fn banner_router(ctx: &common::TestContext, config: AppConfig) -> Router {
+    let (job_sender, _job_receiver) = tokio::sync::mpsc::unbounded_channel();
+    let state = AppState::with_slack_client(
+        ctx.pool.clone(),
+        config,
+        job_sender,
+        Arc::new(MockSlackClient::new()),
+    );
+    Router::new()
+        .nest("/api/v1", incident_bot::api::router(state.clone()))
+        .with_state(state)
+}
+
+async fn get(router: Router, uri: &str, key: Option<&str>) -> (StatusCode, Value) {
+    let mut request = Request::builder().uri(uri);
+    if let Some(key) = key {
+        request = request.header("X-Banner-Key", key);
+    }
+    let response = router
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    if status == StatusCode::OK {
+        assert_eq!(response.headers()["access-control-allow-origin"], "*");
+    }
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (
+        status,
+        serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+    )
+}
+
+/// The test incident's banner entry; other tests' incidents may be listed too.
+fn shown(banner: &Value) -> Option<&Value> {
+    banner["incidents"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|incident| incident["title"] == "Checkout errors from [internal host]")
+}
+
+#[tokio::test]
+async fn test_banner_shows_open_incidents_with_public_updates() {
+    let ctx = common::TestContext::new().await;
+    let router = banner_router(
+        &ctx,
+        AppConfig {
+            partner_redact_domains: vec!["corp.example.com".to_string()],
+            ..common::test_config()
+        },
+    );
+    let incident_service = IncidentService::new(ctx.pool.clone());
+    let incident = incident_service
+        .create_incident(
+            "Checkout errors from db1.corp.example.com".to_string(),
+            Severity::P1,
+            "Test Service".to_string(),
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .unwrap();
+    incident_service
+        .post_status_update(
+            incident.id,
+            "Failing over the primary".to_string(),
+            "U024COMMANDER".to_string(),
+            Audience::Internal,
+        )
+        .await
+        .unwrap();
+
+    // No API token needed; internal updates don't make it customer-facing
+    let (status, banner) = get(router.clone(), "/api/v1/banner", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(shown(&banner).is_none());
+
+    incident_service
+        .post_status_update(
+            incident.id,
+            "Some customers can't check out".to_string(),
+            "U024COMMANDER".to_string(),
+            Audience::Public,
+        )
+        .await
+        .unwrap();
+    let (_, banner) = get(router.clone(), "/api/v1/banner", None).await;
+    assert_eq!(banner["active"], true);
+    let entry = shown(&banner).expect("public incident should be on the banner");
+    assert_eq!(entry["status"], "Declared");
+    assert!(entry["started_at"].is_string());
+    assert!(entry.get("commander_id").is_none());
+
+    incident_service
+        .resolve_incident(incident.id, "U024COMMANDER".to_string())
+        .await
+        .unwrap();
+    let (_, banner) = get(router, "/api/v1/banner", None).await;
+    assert!(shown(&banner).is_none());
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_banner_requires_key_when_configured() {
+    let ctx = common::TestContext::new().await;
+    let router = banner_router(
+        &ctx,
+        AppConfig {
+            banner_api_key: Some("banner-key".to_string()),
+            ..common::test_config()
+        },
+    );
+
+    let (status, _) = get(router.clone(), "/api/v1/banner", None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, _) = get(router.clone(), "/api/v1/banner", Some("wrong")).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, _) = get(router.clone(), "/api/v1/banner", Some("banner-key")).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, banner) = get(router, "/api/v1/banner?key=banner-key", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(banner["active"].is_boolean());
+}
@@ -0,0 +1,135 @@
+use chrono::{Duration, Utc};
+use incident_bot::db::models::Severity;
+use incident_bot::jobs::burndown::{render_daily, send_weekly_digest};
+use incident_bot::services::incident::IncidentService;
+use incident_bot::slack::home::publish_home;
+use incident_bot::slack::mock::{MockSlackClient, SlackCall};
+use incident_bot::AppConfig;
+use incident_bot::AppState;
+use std::sync::Arc;
+
+mod common;
+
+fn state_with(
+    ctx: &common::TestContext,
+    config: AppConfig,
+    mock: Arc<MockSlackClient>,
+) -> AppState {
+    let (job_sender, _job_receiver) = tokio::sync::mpsc::unbounded_channel();
+    AppState::with_slack_client(ctx.pool.clone(), config, job_sender, mock)
+}
+
+async fn declare(ctx: &common::TestContext, title: &str) -> incident_bot::db::models::Incident {
+    IncidentService::new(ctx.pool.clone())
+        .create_incident(
+            title.to_string(),
+            Severity::P2,
+            "Test Service".to_string(),
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .expect("Failed to create incident")
+}
+
+#[tokio::test]
+async fn test_burndown_rendered_once_a_day_and_shown_in_app_home() {
+    let ctx = common::TestContext::new().await;
+    let now = Utc::now();
+
+    // A failed upload releases the day so the next pass retries
+    let failing = Arc::new(MockSlackClient::new());
+    failing.fail_method("files.getUploadURLExternal", "internal_error");
+    let state = state_with(&ctx, common::test_config(), failing);
+    assert!(render_daily(&state, now).await.is_err());
+
+    let mock = Arc::new(MockSlackClient::new());
+    let state = state_with(&ctx, common::test_config(), mock.clone());
+    declare(&ctx, "Still open").await;
+    let file_id = render_daily(&state, now)
+        .await
+        .unwrap()
+        .expect("Expected a new sparkline");
+    assert_eq!(
+        render_daily(&state, now + Duration::hours(1))
+            .await
+            .unwrap(),
+        None
+    );
+
+    let uploads: Vec<(String, Vec<u8>)> = mock
+        .calls()
+        .into_iter()
+        .filter_map(|call| match call {
+            SlackCall::UploadFile {
+                filename, content, ..
+            } => Some((filename, content)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(uploads.len(), 1);
+    assert_eq!(uploads[0].0, format!("burndown-{}.png", now.date_naive()));
+    assert_eq!(&uploads[0].1[..8], b"\x89PNG\r\n\x1a\n");
+
+    publish_home(&state, "U024COMMANDER").await.unwrap();
+    let view = mock
+        .calls()
+        .into_iter()
+        .find_map(|call| match call {
+            SlackCall::PublishView { view, .. } => Some(view),
+            _ => None,
+        })
+        .unwrap();
+    let last = view["blocks"].as_array().unwrap().last().unwrap().clone();
+    assert_eq!(last["type"], "image");
+    assert_eq!(last["slack_file"]["id"], file_id);
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_weekly_digest_posted_once_per_week() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let now = Utc::now();
+
+    // Disabled without a digest channel
+    let state = state_with(&ctx, common::test_config(), mock.clone());
+    assert!(!send_weekly_digest(&state, now).await.unwrap());
+
+    let config = AppConfig {
+        digest_channel: Some("C_DIGEST".to_string()),
+        ..common::test_config()
+    };
+    let state = state_with(&ctx, config, mock.clone());
+    declare(&ctx, "Open").await;
+    let resolved = declare(&ctx, "Resolved").await;
+    IncidentService::new(ctx.pool.clone())
+        .resolve_incident(resolved.id, "U024COMMANDER".to_string())
+        .await
+        .unwrap();
+    render_daily(&state, now).await.unwrap();
+
+    assert!(send_weekly_digest(&state, now).await.unwrap());
+    assert!(!send_weekly_digest(&state, now + Duration::hours(1))
+        .await
+        .unwrap());
+    assert!(send_weekly_digest(&state, now + Duration::days(7))
+        .await
+        .unwrap());
+
+    assert_eq!(mock.posted_channels(), vec!["C_DIGEST", "C_DIGEST"]);
+    let digest = mock
+        .calls()
+        .into_iter()
+        .find_map(|call| match call {
+            SlackCall::PostMessage { blocks, .. } => Some(blocks),
+            _ => None,
+        })
+        .unwrap();
+    // The open P2 puts the banner under the header
+    assert_eq!(digest[1]["elements"][0]["text"], "⚠️ 1 open P2 right now");
+    assert_eq!(digest[2]["fields"][0]["text"], "*Open now:*\n1");
+    assert_eq!(digest[3]["type"], "image");
+
+    ctx.cleanup().await;
+}
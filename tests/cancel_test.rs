@@ -0,0 +1,144 @@
+use chrono::{Duration, Utc};
+use incident_bot::commands::cancel::handle_cancel;
+use incident_bot::commands::closed::closed_incident_reply;
+use incident_bot::db::models::{IncidentStatus, Severity};
+use incident_bot::db::queries::{incidents, metrics};
+use incident_bot::services::incident::IncidentService;
+use incident_bot::slack::mock::{MockSlackClient, SlackCall};
+use std::sync::Arc;
+
+mod common;
+
+fn responses(mock: &MockSlackClient) -> String {
+    mock.calls()
+        .into_iter()
+        .filter_map(|call| match call {
+            SlackCall::PostToResponseUrl { blocks, .. } => Some(blocks),
+            _ => None,
+        })
+        .flatten()
+        .map(|block| block.to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn posted_to(mock: &MockSlackClient, channel: &str) -> String {
+    mock.calls()
+        .into_iter()
+        .filter_map(|call| match call {
+            SlackCall::PostMessage { channel_id, blocks } if channel_id == channel => Some(blocks),
+            _ => None,
+        })
+        .flatten()
+        .map(|block| block.to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[tokio::test]
+async fn test_commander_cancels_false_alarm() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let state = common::mock_state(&ctx.pool, mock.clone());
+    let incident =
+        common::incident_in_channel(&ctx, Severity::P1, "Test Service", "C_CANCEL").await;
+
+    handle_cancel(
+        state.clone(),
+        common::slash_command("cancel monitoring blip", "U_BYSTANDER", "C_CANCEL"),
+    )
+    .await
+    .unwrap();
+    handle_cancel(
+        state.clone(),
+        common::slash_command("cancel", "U024COMMANDER", "C_CANCEL"),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        incidents::get_incident_by_id(&ctx.pool, incident.id)
+            .await
+            .unwrap()
+            .status,
+        IncidentStatus::Declared
+    );
+    assert!(responses(&mock).contains("Usage: `/incident cancel <reason>`"));
+
+    handle_cancel(
+        state.clone(),
+        common::slash_command("cancel monitoring blip", "U024COMMANDER", "C_CANCEL"),
+    )
+    .await
+    .expect("Cancel failed");
+
+    let canceled = incidents::get_incident_by_id(&ctx.pool, incident.id)
+        .await
+        .unwrap();
+    assert_eq!(canceled.status, IncidentStatus::Canceled);
+    assert_eq!(canceled.cancel_reason.as_deref(), Some("monitoring blip"));
+    assert!(canceled.canceled_at.is_some());
+    assert_eq!(canceled.resolved_at, None);
+    assert_eq!(canceled.duration_minutes, None);
+    assert!(canceled.channel_archived_at.is_some());
+
+    // The P1 broadcast channel hears it was a false alarm
+    assert!(posted_to(&mock, "C_GENERAL").contains("was a false alarm"));
+    assert!(mock.calls().iter().any(|call| matches!(
+        call,
+        SlackCall::ArchiveChannel { channel_id } if channel_id == "C_CANCEL"
+    )));
+
+    // No postmortem is required
+    let required: i64 = sqlx::query_scalar::query_scalar(
+        "SELECT COUNT(*) FROM postmortem_requirements WHERE incident_id = $1",
+    )
+    .bind(incident.id)
+    .fetch_one(&ctx.pool)
+    .await
+    .unwrap();
+    assert_eq!(required, 0);
+
+    // Commands in the channel are refused from now on
+    let reply = closed_incident_reply(&state, "cancel", "C_CANCEL")
+        .await
+        .unwrap()
+        .expect("closed reply");
+    assert!(reply[0].to_string().contains("canceled as a false alarm"));
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_canceled_incidents_are_left_out_of_mttr() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let state = common::mock_state(&ctx.pool, mock);
+    let service = "Cancel Metrics";
+
+    let real = common::incident_in_channel(&ctx, Severity::P3, service, "C_CANCEL_REAL").await;
+    IncidentService::new(ctx.pool.clone())
+        .resolve_incident(real.id, "U024COMMANDER".to_string())
+        .await
+        .unwrap();
+    common::incident_in_channel(&ctx, Severity::P3, service, "C_CANCEL_FALSE").await;
+    handle_cancel(
+        state,
+        common::slash_command("cancel test alert", "U024COMMANDER", "C_CANCEL_FALSE"),
+    )
+    .await
+    .unwrap();
+
+    let rows = metrics::incident_metrics(
+        &ctx.pool,
+        Utc::now() - Duration::days(1),
+        Some(&[service.to_string()]),
+    )
+    .await
+    .unwrap();
+    let overall = rows.iter().find(|row| row.key == "all").unwrap();
+    assert_eq!(overall.declared, 2);
+    assert_eq!(overall.resolved, 1);
+    assert_eq!(overall.mean_minutes, Some(0.0));
+
+    ctx.cleanup().await;
+}
@@ -0,0 +1,147 @@
+use chrono::Duration;
+use incident_bot::commands::resolved::resolve_and_announce;
+use incident_bot::db::models::Severity;
+use incident_bot::db::queries::postmortems::record_postmortem;
+use incident_bot::jobs::channel_archive::archive_once;
+use incident_bot::services::incident::IncidentService;
+use incident_bot::slack::mock::{MockSlackClient, SlackCall};
+use incident_bot::{AppConfig, AppState};
+use std::sync::Arc;
+
+mod common;
+
+fn archive_state(ctx: &common::TestContext, mock: Arc<MockSlackClient>) -> AppState {
+    let config = AppConfig {
+        channel_archive_after_days: 7,
+        ..common::test_config()
+    };
+    let (job_sender, _job_receiver) = tokio::sync::mpsc::unbounded_channel();
+    AppState::with_slack_client(ctx.pool.clone(), config, job_sender, mock)
+}
+
+fn archived_channels(mock: &MockSlackClient) -> Vec<String> {
+    mock.calls()
+        .into_iter()
+        .filter_map(|call| match call {
+            SlackCall::ArchiveChannel { channel_id } => Some(channel_id),
+            _ => None,
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_resolved_channels_are_summarized_and_archived_once() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let state = archive_state(&ctx, mock.clone());
+
+    let incident =
+        common::incident_in_channel(&ctx, Severity::P3, "Test Service", "C_ARCHIVE_ONE").await;
+    let resolved = resolve_and_announce(&state, &incident, "U024COMMANDER")
+        .await
+        .unwrap();
+    let resolved_at = resolved.resolved_at.unwrap();
+    record_postmortem(
+        &ctx.pool,
+        incident.id,
+        "12345",
+        "https://example.atlassian.net/wiki/pages/12345",
+        "U024COMMANDER",
+    )
+    .await
+    .unwrap();
+
+    // A war room that went on to host another incident stays open
+    let earlier =
+        common::incident_in_channel(&ctx, Severity::P3, "Test Service", "C_ARCHIVE_REUSED").await;
+    resolve_and_announce(&state, &earlier, "U024COMMANDER")
+        .await
+        .unwrap();
+    common::incident_in_channel(&ctx, Severity::P3, "Test Service", "C_ARCHIVE_REUSED").await;
+
+    assert_eq!(
+        archive_once(&state, resolved_at + Duration::days(6))
+            .await
+            .unwrap(),
+        0
+    );
+    assert!(archived_channels(&mock).is_empty());
+
+    assert_eq!(
+        archive_once(&state, resolved_at + Duration::days(8))
+            .await
+            .unwrap(),
+        1
+    );
+    assert_eq!(archived_channels(&mock), vec!["C_ARCHIVE_ONE"]);
+    let summary = mock
+        .calls()
+        .into_iter()
+        .rev()
+        .find_map(|call| match call {
+            SlackCall::PostMessage { channel_id, blocks } if channel_id == "C_ARCHIVE_ONE" => {
+                Some(serde_json::to_string(&blocks).unwrap())
+            }
+            _ => None,
+        })
+        .unwrap();
+    assert!(summary.contains("Archiving incident channel"));
+    assert!(summary.contains("https://example.atlassian.net/wiki/pages/12345"));
+    assert!(summary.contains("This channel is now locked"));
+
+    let archived = IncidentService::new(ctx.pool.clone())
+        .get_by_id(incident.id)
+        .await
+        .unwrap();
+    assert!(archived.channel_archived_at.is_some());
+
+    // Idempotent: later passes leave the channel alone
+    assert_eq!(
+        archive_once(&state, resolved_at + Duration::days(9))
+            .await
+            .unwrap(),
+        0
+    );
+    assert_eq!(archived_channels(&mock).len(), 1);
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_transient_archive_failures_are_retried() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let state = archive_state(&ctx, mock.clone());
+
+    let transient =
+        common::incident_in_channel(&ctx, Severity::P3, "Test Service", "C_ARCHIVE_TRANSIENT")
+            .await;
+    let resolved = resolve_and_announce(&state, &transient, "U024COMMANDER")
+        .await
+        .unwrap();
+    let now = resolved.resolved_at.unwrap() + Duration::days(8);
+
+    mock.fail_method("conversations.archive", "internal_error");
+    assert_eq!(archive_once(&state, now).await.unwrap(), 0);
+    let incident_service = IncidentService::new(ctx.pool.clone());
+    assert!(incident_service
+        .get_by_id(transient.id)
+        .await
+        .unwrap()
+        .channel_archived_at
+        .is_none());
+
+    // Slack refusing outright isn't retried every pass
+    mock.fail_method("conversations.archive", "not_in_channel");
+    assert_eq!(archive_once(&state, now).await.unwrap(), 0);
+    assert!(incident_service
+        .get_by_id(transient.id)
+        .await
+        .unwrap()
+        .channel_archived_at
+        .is_some());
+    assert_eq!(archive_once(&state, now).await.unwrap(), 0);
+    assert_eq!(archived_channels(&mock).len(), 2);
+
+    ctx.cleanup().await;
+}
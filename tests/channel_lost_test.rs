@@ -0,0 +1,198 @@
+use incident_bot::commands::channel_lost::{
+    handle_channel_gone, handle_continue_without_channel, handle_replace_channel,
+};
+use incident_bot::db::models::{Incident, Severity};
+use incident_bot::db::queries::incidents;
+use incident_bot::services::incident::IncidentService;
+use incident_bot::slack::blocks::{CONTINUE_WITHOUT_CHANNEL_ACTION, REPLACE_CHANNEL_ACTION};
+use incident_bot::slack::mock::{MockSlackClient, SlackCall};
+use std::sync::Arc;
+
+mod common;
+
+const RESPONSE_URL: &str = "https://hooks.slack.test/response";
+
+async fn pinned_incident_in_channel(ctx: &common::TestContext, channel_id: &str) -> Incident {
+    let incident = common::incident_in_channel(ctx, Severity::P2, "Test Service", channel_id).await;
+    incidents::update_pinned_message_ts(&ctx.pool, incident.id, "1700000000.000001")
+        .await
+        .unwrap();
+    IncidentService::new(ctx.pool.clone())
+        .get_by_id(incident.id)
+        .await
+        .unwrap()
+}
+
+fn dms_to(mock: &MockSlackClient, user: &str) -> Vec<serde_json::Value> {
+    mock.calls()
+        .into_iter()
+        .filter_map(|call| match call {
+            SlackCall::SendDm { user_id, blocks } if user_id == user => Some(blocks),
+            _ => None,
+        })
+        .flatten()
+        .collect()
+}
+
+fn responses(mock: &MockSlackClient) -> String {
+    mock.calls()
+        .into_iter()
+        .filter_map(|call| match call {
+            SlackCall::PostToResponseUrl { blocks, .. } => Some(blocks),
+            _ => None,
+        })
+        .flatten()
+        .map(|block| block.to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[tokio::test]
+async fn test_archived_channel_is_detached_and_commander_asked_once() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let state = common::mock_state(&ctx.pool, mock.clone());
+    let incident = pinned_incident_in_channel(&ctx, "C_LOST_ARCHIVED").await;
+
+    handle_channel_gone(&state, "C_LOST_ARCHIVED", Some("U_TIDY"))
+        .await
+        .expect("Archive event failed");
+    // Slack follows up with a deletion; the commander shouldn't hear twice
+    handle_channel_gone(&state, "C_LOST_ARCHIVED", None)
+        .await
+        .expect("Delete event failed");
+
+    let updated = incidents::get_incident_by_id(&ctx.pool, incident.id)
+        .await
+        .unwrap();
+    assert_eq!(updated.slack_channel_id, None);
+    assert_eq!(updated.pinned_message_ts, None);
+
+    let dm = dms_to(&mock, "U024COMMANDER");
+    assert_eq!(dm.len(), 2, "one DM: text and buttons");
+    assert!(dm[0]["text"]["text"]
+        .as_str()
+        .unwrap()
+        .contains("was archived by <@U_TIDY>"));
+    assert_eq!(dm[1]["elements"][0]["action_id"], REPLACE_CHANNEL_ACTION);
+    assert_eq!(
+        dm[1]["elements"][1]["action_id"],
+        CONTINUE_WITHOUT_CHANNEL_ACTION
+    );
+
+    let timeline: Vec<String> = sqlx::query_scalar::query_scalar(
+        "SELECT message FROM incident_timeline WHERE incident_id = $1 ORDER BY timestamp",
+    )
+    .bind(incident.id)
+    .fetch_all(&ctx.pool)
+    .await
+    .unwrap();
+    assert_eq!(
+        timeline
+            .iter()
+            .filter(|m| m.contains("C_LOST_ARCHIVED"))
+            .count(),
+        1
+    );
+
+    // Later refreshes no longer touch the lost channel
+    incident_bot::services::pinned_summary::refresh(&state, &updated).await;
+    assert!(!mock.calls().iter().any(|call| matches!(
+        call,
+        SlackCall::UpdateMessage { channel_id, .. } | SlackCall::PostMessage { channel_id, .. }
+            if channel_id == "C_LOST_ARCHIVED"
+    )));
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_resolved_incident_channel_archive_is_ignored() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let state = common::mock_state(&ctx.pool, mock.clone());
+    let incident = pinned_incident_in_channel(&ctx, "C_LOST_RESOLVED").await;
+    IncidentService::new(ctx.pool.clone())
+        .resolve_incident(incident.id, "U024COMMANDER".to_string())
+        .await
+        .unwrap();
+
+    handle_channel_gone(&state, "C_LOST_RESOLVED", Some("U024COMMANDER"))
+        .await
+        .unwrap();
+
+    let updated = incidents::get_incident_by_id(&ctx.pool, incident.id)
+        .await
+        .unwrap();
+    assert_eq!(updated.slack_channel_id.as_deref(), Some("C_LOST_RESOLVED"));
+    assert!(dms_to(&mock, "U024COMMANDER").is_empty());
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_commander_replaces_lost_channel() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let state = common::mock_state(&ctx.pool, mock.clone());
+    let incident = pinned_incident_in_channel(&ctx, "C_LOST_REPLACED").await;
+    handle_channel_gone(&state, "C_LOST_REPLACED", None)
+        .await
+        .unwrap();
+
+    // Only the commander (or an admin) chooses
+    handle_replace_channel(
+        state.clone(),
+        "U_BYSTANDER".to_string(),
+        &incident.id.to_string(),
+        Some(RESPONSE_URL.to_string()),
+    )
+    .await
+    .unwrap();
+    assert!(!mock
+        .calls()
+        .iter()
+        .any(|call| matches!(call, SlackCall::CreateConversation { .. })));
+
+    handle_replace_channel(
+        state.clone(),
+        "U024COMMANDER".to_string(),
+        &incident.id.to_string(),
+        Some(RESPONSE_URL.to_string()),
+    )
+    .await
+    .expect("Replacing the channel failed");
+
+    let updated = incidents::get_incident_by_id(&ctx.pool, incident.id)
+        .await
+        .unwrap();
+    let channel_id = updated.slack_channel_id.clone().expect("new channel");
+    assert!(updated.pinned_message_ts.is_some());
+    assert!(mock.calls().iter().any(|call| matches!(
+        call,
+        SlackCall::InviteUsers { channel_id: c, user_ids } if *c == channel_id
+            && user_ids.contains(&"U024COMMANDER".to_string())
+    )));
+    assert!(mock.calls().iter().any(|call| matches!(
+        call,
+        SlackCall::PinMessage { channel_id: c, .. } if *c == channel_id
+    )));
+    assert!(responses(&mock).contains(&format!("continues in <#{}>", channel_id)));
+
+    // A second click (or "Continue without a channel") changes nothing
+    handle_continue_without_channel(
+        state,
+        "U024COMMANDER".to_string(),
+        &incident.id.to_string(),
+        Some(RESPONSE_URL.to_string()),
+    )
+    .await
+    .unwrap();
+    assert!(responses(&mock).contains("already continues in"));
+    let after = incidents::get_incident_by_id(&ctx.pool, incident.id)
+        .await
+        .unwrap();
+    assert_eq!(after.slack_channel_id, Some(channel_id));
+
+    ctx.cleanup().await;
+}
@@ -0,0 +1,197 @@
+use incident_bot::config::ChannelStatusIndicator;
+use incident_bot::db::models::{IncidentStatus, Severity};
+use incident_bot::jobs::channel_status::{enqueue, execute};
+use incident_bot::jobs::Job;
+use incident_bot::services::incident::IncidentService;
+use incident_bot::slack::mock::{MockSlackClient, SlackCall};
+use incident_bot::{AppConfig, AppState};
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+mod common;
+
+const INDEX_CHANNEL: &str = "C_INCIDENT_INDEX";
+
+fn state(
+    pool: &sqlx_postgres::PgPool,
+    mock: Arc<MockSlackClient>,
+    indicator: ChannelStatusIndicator,
+) -> (AppState, mpsc::UnboundedReceiver<Job>) {
+    let config = AppConfig {
+        channel_status_indicator: indicator,
+        incident_index_channel: Some(INDEX_CHANNEL.to_string()),
+        ..common::test_config()
+    };
+    let (job_sender, job_receiver) = mpsc::unbounded_channel();
+    (
+        AppState::with_slack_client(pool.clone(), config, job_sender, mock),
+        job_receiver,
+    )
+}
+
+fn topics(mock: &MockSlackClient, channel: &str) -> Vec<String> {
+    mock.calls()
+        .into_iter()
+        .filter_map(|call| match call {
+            SlackCall::SetChannelTopic { channel_id, topic } if channel_id == channel => {
+                Some(topic)
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn renames(mock: &MockSlackClient) -> Vec<String> {
+    mock.calls()
+        .into_iter()
+        .filter_map(|call| match call {
+            SlackCall::RenameChannel { name, .. } => Some(name),
+            _ => None,
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_topic_follows_status_and_index_counts_open_incidents() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let (state, mut jobs) = state(&ctx.pool, mock.clone(), ChannelStatusIndicator::Topic);
+
+    let incident_service = IncidentService::new(ctx.pool.clone());
+    let incident = incident_service
+        .create_incident(
+            "Checkout errors".to_string(),
+            Severity::P2,
+            "Test Service".to_string(),
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .unwrap();
+    incident_service
+        .update_channel_id(incident.id, "C_STATUS_TOPIC".to_string())
+        .await
+        .unwrap();
+    // Quiet incidents aren't counted in the public index
+    let quiet = incident_service
+        .create_incident(
+            "Credential leak".to_string(),
+            Severity::P1,
+            "Test Service".to_string(),
+            "U024SECURITY".to_string(),
+        )
+        .await
+        .unwrap();
+    sqlx::query::query("UPDATE incidents SET is_quiet = TRUE WHERE id = $1")
+        .bind(quiet.id)
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+
+    enqueue(&state, &incident);
+    assert!(matches!(
+        jobs.try_recv(),
+        Ok(Job::SyncChannelStatus { incident_id }) if incident_id == incident.id
+    ));
+
+    execute(&state, incident.id).await.unwrap();
+    let updated = incident_service
+        .transition_status(
+            incident.id,
+            IncidentStatus::Identified,
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .unwrap();
+    execute(&state, updated.id).await.unwrap();
+
+    assert_eq!(
+        topics(&mock, "C_STATUS_TOPIC"),
+        vec![
+            "🔴 Declared | P2: Checkout errors".to_string(),
+            "🟠 Identified | P2: Checkout errors".to_string()
+        ]
+    );
+    assert_eq!(
+        topics(&mock, INDEX_CHANNEL).last().unwrap(),
+        "🟡 1 open incident (P2: 1)"
+    );
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_name_mode_renames_only_generated_channels() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    mock.add_channel("C_STATUS_NAME", "inc-20241115-test-service");
+    mock.add_channel("C_WAR_ROOM", "payments-war-room");
+    let (state, _jobs) = state(&ctx.pool, mock.clone(), ChannelStatusIndicator::Name);
+
+    let incident_service = IncidentService::new(ctx.pool.clone());
+    let mut ids = vec![];
+    for (title, channel) in [("Generated", "C_STATUS_NAME"), ("Attached", "C_WAR_ROOM")] {
+        let incident = incident_service
+            .create_incident(
+                title.to_string(),
+                Severity::P3,
+                "Test Service".to_string(),
+                "U024COMMANDER".to_string(),
+            )
+            .await
+            .unwrap();
+        incident_service
+            .update_channel_id(incident.id, channel.to_string())
+            .await
+            .unwrap();
+        ids.push(incident.id);
+    }
+
+    execute(&state, ids[0]).await.unwrap();
+    execute(&state, ids[1]).await.unwrap();
+    incident_service
+        .transition_status(
+            ids[0],
+            IncidentStatus::Resolved,
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .unwrap();
+    execute(&state, ids[0]).await.unwrap();
+
+    assert_eq!(
+        renames(&mock),
+        vec![
+            "inc-20241115-test-service-declared".to_string(),
+            "inc-20241115-test-service-resolved".to_string()
+        ]
+    );
+    assert!(topics(&mock, "C_STATUS_NAME").is_empty());
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_nothing_is_enqueued_when_disabled() {
+    let ctx = common::TestContext::new().await;
+    let (job_sender, mut jobs) = mpsc::unbounded_channel();
+    let state = AppState::with_slack_client(
+        ctx.pool.clone(),
+        common::test_config(),
+        job_sender,
+        Arc::new(MockSlackClient::new()),
+    );
+    let incident = IncidentService::new(ctx.pool.clone())
+        .create_incident(
+            "Quiet Tuesday".to_string(),
+            Severity::P4,
+            "Test Service".to_string(),
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .unwrap();
+
+    enqueue(&state, &incident);
+    assert!(jobs.try_recv().is_err());
+
+    ctx.cleanup().await;
+}
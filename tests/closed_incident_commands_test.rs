@@ -0,0 +1,88 @@
+use chrono::Utc;
+use incident_bot::commands::closed::closed_incident_reply;
+use incident_bot::commands::resolved::resolve_and_announce;
+use incident_bot::db::models::Severity;
+use incident_bot::db::queries::incidents::mark_channel_archived;
+use incident_bot::db::queries::postmortems::record_postmortem;
+use incident_bot::services::incident::IncidentService;
+use incident_bot::slack::mock::MockSlackClient;
+use std::sync::Arc;
+
+mod common;
+
+const CHANNEL: &str = "C_CLOSED_CMDS";
+
+async fn reply_text(state: &incident_bot::AppState, subcommand: &str) -> Option<String> {
+    closed_incident_reply(state, subcommand, CHANNEL)
+        .await
+        .unwrap()
+        .map(|blocks| blocks[0]["text"]["text"].as_str().unwrap().to_string())
+}
+
+#[tokio::test]
+async fn test_commands_in_closed_incident_channels_point_elsewhere() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let state = common::mock_state(&ctx.pool, mock);
+    let incident_service = IncidentService::new(ctx.pool.clone());
+    let incident = incident_service
+        .create_incident(
+            "Closed channel".to_string(),
+            Severity::P3,
+            "Test Service".to_string(),
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .unwrap();
+    incident_service
+        .update_channel_id(incident.id, CHANNEL.to_string())
+        .await
+        .unwrap();
+    let incident = incident_service.get_by_id(incident.id).await.unwrap();
+
+    // Open incidents run every command
+    assert_eq!(reply_text(&state, "status").await, None);
+
+    resolve_and_announce(&state, &incident, "U024COMMANDER")
+        .await
+        .unwrap();
+
+    // Resolved: open-only commands point at reopen; follow-ups still run
+    let reply = reply_text(&state, "severity").await.unwrap();
+    assert!(reply.contains("`/incident reopen [reason]`"));
+    assert!(reply.contains("draft one with `/incident postmortem`"));
+    assert_eq!(reply_text(&state, "note").await, None);
+    assert_eq!(reply_text(&state, "reopen").await, None);
+    assert_eq!(reply_text(&state, "timeline").await, None);
+
+    // Archived: the channel is locked for everything but lookups
+    record_postmortem(
+        &ctx.pool,
+        incident.id,
+        "777",
+        "https://example.atlassian.net/wiki/pages/777",
+        "U024COMMANDER",
+    )
+    .await
+    .unwrap();
+    mark_channel_archived(&ctx.pool, incident.id, Utc::now())
+        .await
+        .unwrap();
+    for subcommand in ["note", "reopen", "status"] {
+        let reply = reply_text(&state, subcommand).await.unwrap();
+        assert!(reply.contains("This channel is locked"), "{}", subcommand);
+        assert!(reply.contains("`/incident declare`"));
+        assert!(reply.contains("https://example.atlassian.net/wiki/pages/777"));
+    }
+    assert_eq!(reply_text(&state, "summary").await, None);
+
+    // Channels without an incident are left to the command itself
+    assert_eq!(
+        closed_incident_reply(&state, "status", "C_CLOSED_NONE")
+            .await
+            .unwrap(),
+        None
+    );
+
+    ctx.cleanup().await;
+}
@@ -0,0 +1,182 @@
+use chrono::{DateTime, Duration, NaiveDate, TimeZone, Utc};
+use incident_bot::commands::coaching::handle_coaching;
+use incident_bot::db::models::Severity;
+use incident_bot::jobs::coaching_reports::send_coaching_reports;
+use incident_bot::services::audit::AuditService;
+use incident_bot::services::coaching::CoachingService;
+use incident_bot::services::incident::IncidentService;
+use incident_bot::slack::events::SlashCommandPayload;
+use incident_bot::slack::mock::{MockSlackClient, SlackCall};
+use std::sync::Arc;
+
+mod common;
+
+/// Create an incident commanded by `commander`, declared at `declared_at`
+/// with status updates `updates` minutes in and resolved, optionally with a
+/// postmortem.
+async fn commanded(
+    ctx: &common::TestContext,
+    commander: &str,
+    declared_at: DateTime<Utc>,
+    updates: &[i64],
+    postmortem: bool,
+) {
+    let incident_service = IncidentService::new(ctx.pool.clone());
+    let incident = incident_service
+        .create_incident(
+            "Coaching test".to_string(),
+            Severity::P2,
+            "Test Service".to_string(),
+            commander.to_string(),
+        )
+        .await
+        .expect("Failed to create incident");
+    incident_service
+        .resolve_incident(incident.id, commander.to_string())
+        .await
+        .expect("Failed to resolve incident");
+    sqlx::query::query("UPDATE incidents SET declared_at = $2, resolved_at = $2 + INTERVAL '2 hours' WHERE id = $1")
+        .bind(incident.id)
+        .bind(declared_at)
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+
+    for minutes in updates {
+        sqlx::query::query(
+            r#"
+            INSERT INTO incident_timeline (incident_id, event_type, message, posted_by, timestamp)
+            VALUES ($1, 'status_update', 'Investigating', $2, $3)
+            "#,
+        )
+        .bind(incident.id)
+        .bind(commander)
+        .bind(declared_at + Duration::minutes(*minutes))
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+    }
+    if postmortem {
+        AuditService::new(ctx.pool.clone())
+            .log_action(
+                Some(incident.id),
+                "generate_postmortem".to_string(),
+                commander.to_string(),
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+    }
+}
+
+/// Alice and Bob command incidents in Q1 2026; Alice also in Q2.
+async fn seed_first_quarter(ctx: &common::TestContext) {
+    let q1 = Utc.with_ymd_and_hms(2026, 2, 10, 9, 0, 0).unwrap();
+    commanded(ctx, "U_ALICE", q1, &[10, 40, 70], true).await;
+    commanded(ctx, "U_ALICE", q1 + Duration::days(20), &[30], false).await;
+    commanded(ctx, "U_BOB", q1, &[5, 15], true).await;
+    let q2 = Utc.with_ymd_and_hms(2026, 4, 2, 9, 0, 0).unwrap();
+    commanded(ctx, "U_ALICE", q2, &[600], false).await;
+}
+
+fn command(user_id: &str, text: &str) -> SlashCommandPayload {
+    SlashCommandPayload {
+        command: "/incident".to_string(),
+        text: text.to_string(),
+        user_id: user_id.to_string(),
+        channel_id: "C_ANYWHERE".to_string(),
+        response_url: "https://hooks.slack.test/response".to_string(),
+        trigger_id: "trigger-coaching".to_string(),
+    }
+}
+
+#[tokio::test]
+async fn test_coaching_report_compares_with_median_commander() {
+    let ctx = common::TestContext::new().await;
+    seed_first_quarter(&ctx).await;
+
+    let report = CoachingService::new(ctx.pool.clone())
+        .report(
+            "U_ALICE",
+            NaiveDate::from_ymd_opt(2026, 1, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2026, 4, 1).unwrap(),
+        )
+        .await
+        .unwrap()
+        .expect("Alice commanded incidents");
+
+    assert_eq!(report.mine.incidents, 2);
+    assert_eq!(report.mine.mean_first_update_minutes, Some(20.0));
+    assert_eq!(report.mine.mean_update_interval_minutes, Some(30.0));
+    assert_eq!(report.postmortem_completion(), Some(0.5));
+    assert_eq!(report.median_first_update_minutes, Some(12.5));
+    assert_eq!(report.median_update_interval_minutes, Some(20.0));
+    assert_eq!(report.median_postmortem_completion, Some(0.75));
+
+    let nobody = CoachingService::new(ctx.pool.clone())
+        .report(
+            "U_CAROL",
+            NaiveDate::from_ymd_opt(2026, 1, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2026, 4, 1).unwrap(),
+        )
+        .await
+        .unwrap();
+    assert!(nobody.is_none());
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_coaching_reports_go_only_to_commanders_who_opted_in() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let state = common::mock_state(&ctx.pool, mock.clone());
+    seed_first_quarter(&ctx).await;
+
+    // Carol opts in but commanded nothing; Bob opts in and back out
+    for (user_id, text) in [
+        ("U_ALICE", "coaching on"),
+        ("U_CAROL", "coaching on"),
+        ("U_BOB", "coaching on"),
+        ("U_BOB", "coaching off"),
+    ] {
+        handle_coaching(state.clone(), command(user_id, text))
+            .await
+            .unwrap();
+    }
+    sqlx::query::query("UPDATE coaching_opt_ins SET opted_in_at = '2026-03-01T00:00:00Z'")
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+
+    let now = Utc.with_ymd_and_hms(2026, 4, 1, 0, 30, 0).unwrap();
+    assert_eq!(send_coaching_reports(&state, now).await.unwrap(), 1);
+    assert_eq!(mock.dm_recipients(), vec!["U_ALICE"]);
+    let report = mock
+        .calls()
+        .into_iter()
+        .find_map(|call| match call {
+            SlackCall::SendDm { blocks, .. } => Some(serde_json::to_string(&blocks).unwrap()),
+            _ => None,
+        })
+        .unwrap();
+    assert!(
+        report.contains("Your incident command — Q1 2026"),
+        "{}",
+        report
+    );
+    assert!(report.contains("20 min (median 12 min)"), "{}", report);
+    assert!(
+        report.contains("1 of 2 resolved, 50% (median 75%)"),
+        "{}",
+        report
+    );
+
+    // A later pass in the same quarter (or another replica) sends nothing
+    assert_eq!(send_coaching_reports(&state, now).await.unwrap(), 0);
+    assert_eq!(mock.dm_recipients().len(), 1);
+
+    ctx.cleanup().await;
+}
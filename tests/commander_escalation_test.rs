@@ -0,0 +1,206 @@
+use chrono::{Duration, Utc};
+use incident_bot::commands::commander::{handle_commander_ack, handle_take_command};
+use incident_bot::config::AppConfig;
+use incident_bot::db::models::Severity;
+use incident_bot::db::queries::commanders;
+use incident_bot::jobs::commander_escalation::escalate_absent;
+use incident_bot::services::incident::IncidentService;
+use incident_bot::slack::mock::{MockSlackClient, SlackCall};
+use incident_bot::AppState;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+mod common;
+
+fn escalation_config() -> AppConfig {
+    AppConfig {
+        service_owners: HashMap::from([(
+            "Test Service".to_string(),
+            vec!["U024COMMANDER".to_string(), "U_OWNER".to_string()],
+        )]),
+        backup_commanders: vec!["U_ONCALL".to_string()],
+        ..common::test_config()
+    }
+}
+
+fn escalation_state(ctx: &common::TestContext, mock: Arc<MockSlackClient>) -> AppState {
+    let (job_sender, _job_receiver) = tokio::sync::mpsc::unbounded_channel();
+    AppState::with_slack_client(ctx.pool.clone(), escalation_config(), job_sender, mock)
+}
+
+#[tokio::test]
+async fn test_absent_p1_commander_is_escalated_once_to_backups() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let state = escalation_state(&ctx, mock.clone());
+    // Absence threshold is 20 min in test_config; only P1 escalates
+    let p1 = common::incident_in_channel(&ctx, Severity::P1, "Test Service", "C_ESC_P1").await;
+    common::incident_in_channel(&ctx, Severity::P2, "Test Service", "C_ESC_P2").await;
+    let start = p1.declared_at;
+
+    // Messages from others don't count; the commander's do
+    assert!(
+        !commanders::mark_commander_seen_in_channel(&ctx.pool, "C_ESC_P1", "U_OTHER", start)
+            .await
+            .unwrap()
+    );
+    assert!(commanders::mark_commander_seen_in_channel(
+        &ctx.pool,
+        "C_ESC_P1",
+        "U024COMMANDER",
+        start + Duration::minutes(15)
+    )
+    .await
+    .unwrap());
+
+    assert_eq!(
+        escalate_absent(&state, start + Duration::minutes(30))
+            .await
+            .unwrap(),
+        0
+    );
+    assert_eq!(
+        escalate_absent(&state, start + Duration::minutes(36))
+            .await
+            .unwrap(),
+        1
+    );
+    assert_eq!(
+        escalate_absent(&state, start + Duration::minutes(90))
+            .await
+            .unwrap(),
+        0
+    );
+
+    // Service owners first, then the global backups; never the commander
+    assert_eq!(mock.posted_channels(), vec!["C_ESC_P1"]);
+    assert_eq!(mock.dm_recipients(), vec!["U_OWNER", "U_ONCALL"]);
+    assert!(mock.calls().iter().any(|call| matches!(
+        call,
+        SlackCall::PostMessage { blocks, .. }
+            if blocks[1]["elements"][0]["action_id"] == "take_command"
+    )));
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_backup_takes_command_after_escalation() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let state = escalation_state(&ctx, mock.clone());
+    let incident =
+        common::incident_in_channel(&ctx, Severity::P1, "Test Service", "C_ESC_TAKE").await;
+    let value = incident.id.to_string();
+    let response_url = Some("https://hooks.slack.test/response".to_string());
+
+    // Nobody may take command before an escalation
+    handle_take_command(
+        state.clone(),
+        "U_OWNER".to_string(),
+        &value,
+        response_url.clone(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        escalate_absent(&state, incident.declared_at + Duration::minutes(25))
+            .await
+            .unwrap(),
+        1
+    );
+
+    // Only the commander can acknowledge a nag
+    handle_commander_ack(
+        state.clone(),
+        "U_OWNER".to_string(),
+        &value,
+        response_url.clone(),
+    )
+    .await
+    .unwrap();
+
+    // Not a backup
+    handle_take_command(
+        state.clone(),
+        "U_OTHER".to_string(),
+        &value,
+        response_url.clone(),
+    )
+    .await
+    .unwrap();
+    let incident_service = IncidentService::new(ctx.pool.clone());
+    assert_eq!(
+        incident_service
+            .get_by_id(incident.id)
+            .await
+            .unwrap()
+            .commander_id,
+        "U024COMMANDER"
+    );
+
+    handle_take_command(
+        state.clone(),
+        "U_ONCALL".to_string(),
+        &value,
+        response_url.clone(),
+    )
+    .await
+    .unwrap();
+    let updated = incident_service.get_by_id(incident.id).await.unwrap();
+    assert_eq!(updated.commander_id, "U_ONCALL");
+
+    // A second backup is told command already moved
+    handle_take_command(state.clone(), "U_OWNER".to_string(), &value, response_url)
+        .await
+        .unwrap();
+    assert_eq!(
+        incident_service
+            .get_by_id(incident.id)
+            .await
+            .unwrap()
+            .commander_id,
+        "U_ONCALL"
+    );
+
+    let replies: Vec<String> = mock
+        .calls()
+        .into_iter()
+        .filter_map(|call| match call {
+            SlackCall::PostToResponseUrl { blocks, .. } => {
+                Some(blocks[0]["text"]["text"].as_str().unwrap().to_string())
+            }
+            _ => None,
+        })
+        .collect();
+    assert_eq!(replies.len(), 5);
+    assert!(replies[0].contains("nothing to take over"));
+    assert!(replies[1].contains("Permission denied"));
+    assert!(replies[2].contains("Only the backup commanders"));
+    assert!(replies[3].contains("You are now the incident commander"));
+    assert!(replies[4].contains("<@U_ONCALL> is now commanding"));
+
+    assert!(mock.calls().iter().any(|call| matches!(
+        call,
+        SlackCall::InviteUsers { channel_id, user_ids }
+            if channel_id == "C_ESC_TAKE" && user_ids == &vec!["U_ONCALL".to_string()]
+    )));
+    assert!(mock.calls().iter().any(|call| matches!(
+        call,
+        SlackCall::PostMessage { blocks, .. }
+            if blocks[0]["text"]["text"]
+                .as_str()
+                .unwrap()
+                .contains("<@U_ONCALL> has taken command")
+    )));
+
+    // The new commander's absence clock starts now
+    assert_eq!(
+        escalate_absent(&state, Utc::now() + Duration::minutes(10))
+            .await
+            .unwrap(),
+        0
+    );
+
+    ctx.cleanup().await;
+}
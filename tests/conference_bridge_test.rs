@@ -0,0 +1,161 @@
+use axum::extract::Query;
+use axum::routing::post;
+use axum::{Json, Router};
+use incident_bot::adapters::conference::ConferenceClient;
+use incident_bot::commands::bridge::handle_bridge;
+use incident_bot::commands::declare::handle_modal_submission;
+use incident_bot::config::ConferenceProvider;
+use incident_bot::db::queries::incidents;
+use incident_bot::jobs::Job;
+use incident_bot::slack::events::{SlashCommandPayload, ViewPayload};
+use incident_bot::slack::mock::{MockSlackClient, SlackCall};
+use incident_bot::{AppConfig, AppState};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+mod common;
+
+fn declare_view(title: &str, severity: &str) -> ViewPayload {
+    serde_json::from_value(json!({
+        "callback_id": "declare_incident_modal",
+        "private_metadata": "",
+        "state": { "values": {
+            "title_block": { "title_input": { "value": title } },
+            "severity_block": { "severity_select": { "selected_option": { "value": severity } } },
+            "service_block": { "service_select": { "selected_option": { "value": "Test Service" } } },
+            "commander_block": { "commander_select": { "selected_user": null } }
+        } }
+    }))
+    .unwrap()
+}
+
+#[tokio::test]
+async fn test_p1_declaration_gets_a_pinned_bridge() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let config = AppConfig {
+        conference_provider: Some(ConferenceProvider::Zoom),
+        conference_client_id: Some("zoom-client".to_string()),
+        conference_client_secret: Some("zoom-secret".to_string()),
+        conference_account_id: Some("zoom-account".to_string()),
+        ..common::test_config()
+    };
+    let (job_sender, mut job_receiver) = mpsc::unbounded_channel();
+    let state = AppState::with_slack_client(ctx.pool.clone(), config, job_sender, mock.clone());
+
+    handle_modal_submission(
+        state.clone(),
+        declare_view("Typo on the careers page", "P3"),
+        "U024COMMANDER".to_string(),
+    )
+    .await
+    .unwrap();
+    assert!(!std::iter::from_fn(|| job_receiver.try_recv().ok())
+        .any(|job| matches!(job, Job::CreateConferenceBridge { .. })));
+
+    handle_modal_submission(
+        state.clone(),
+        declare_view("Checkout is down", "P1"),
+        "U024COMMANDER".to_string(),
+    )
+    .await
+    .unwrap();
+    let incident_id = std::iter::from_fn(|| job_receiver.try_recv().ok())
+        .find_map(|job| match job {
+            Job::CreateConferenceBridge { incident_id } => Some(incident_id),
+            _ => None,
+        })
+        .expect("Expected a conference bridge job");
+
+    // Fake Zoom: the token endpoint checks the account, the meeting echoes
+    // the topic into the join URL
+    let app = Router::new()
+        .route(
+            "/oauth/token",
+            post(|Query(query): Query<HashMap<String, String>>| async move {
+                assert_eq!(query["grant_type"], "account_credentials");
+                assert_eq!(query["account_id"], "zoom-account");
+                Json(json!({ "access_token": "zoom-token", "expires_in": 3600 }))
+            }),
+        )
+        .route(
+            "/v2/users/me/meetings",
+            post(|Json(body): Json<Value>| async move {
+                assert_eq!(body["topic"], "P1 incident: Checkout is down");
+                Json(json!({ "id": 8_512_345, "join_url": "https://zoom.us/j/8512345" }))
+            }),
+        );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+    let (provider, client_id, client_secret, grant) =
+        state.config.conference_credentials().unwrap();
+    let zoom = ConferenceClient::new(
+        provider,
+        client_id.to_string(),
+        client_secret.to_string(),
+        grant.to_string(),
+    )
+    .with_endpoints(
+        format!("http://{}/oauth/token", addr),
+        format!("http://{}/v2", addr),
+    );
+
+    incident_bot::jobs::conference_bridge::execute(&zoom, &state, incident_id)
+        .await
+        .expect("Bridge creation failed");
+    // A retried job doesn't create a second bridge
+    incident_bot::jobs::conference_bridge::execute(&zoom, &state, incident_id)
+        .await
+        .unwrap();
+
+    let incident = incidents::get_incident_by_id(&ctx.pool, incident_id)
+        .await
+        .unwrap();
+    assert_eq!(
+        incident.bridge_url.as_deref(),
+        Some("https://zoom.us/j/8512345")
+    );
+    let channel_id = incident.slack_channel_id.clone().unwrap();
+    let calls = mock.calls();
+    let bridge_posts = calls
+        .iter()
+        .filter(|call| {
+            matches!(call, SlackCall::PostMessage { channel_id: c, blocks }
+                if *c == channel_id && blocks[0].to_string().contains("zoom.us/j/8512345"))
+        })
+        .count();
+    assert_eq!(bridge_posts, 1);
+    let pins = calls
+        .iter()
+        .filter(
+            |call| matches!(call, SlackCall::PinMessage { channel_id: c, .. } if *c == channel_id),
+        )
+        .count();
+    assert_eq!(pins, 2, "incident details and bridge link are both pinned");
+
+    handle_bridge(
+        state,
+        SlashCommandPayload {
+            command: "/incident".to_string(),
+            text: "bridge".to_string(),
+            user_id: "U024RESPONDER".to_string(),
+            channel_id: channel_id.clone(),
+            response_url: "https://hooks.slack.test/response".to_string(),
+            trigger_id: "trigger-bridge".to_string(),
+        },
+    )
+    .await
+    .unwrap();
+    assert!(mock.calls().iter().any(|call| matches!(
+        call,
+        SlackCall::PostToResponseUrl { blocks, .. }
+            if blocks[0].to_string().contains("Incident bridge (Zoom)")
+    )));
+
+    ctx.cleanup().await;
+}
@@ -0,0 +1,134 @@
+use axum::http::StatusCode;
+use incident_bot::slack::mock::MockSlackClient;
+use serde_json::json;
+use std::sync::Arc;
+
+mod common;
+
+#[tokio::test]
+async fn test_config_bundle_round_trip_with_dry_run() {
+    let ctx = common::TestContext::new().await;
+    let state = common::mock_state(&ctx.pool, Arc::new(MockSlackClient::new()));
+
+    let (status, exported) = common::send(&state, "GET", "/api/v1/admin/config/export", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(exported["version"], 1);
+    assert_eq!(exported["environment"]["services"], json!(["Test Service"]));
+    assert_eq!(
+        exported["environment"]["severities"]["P1"],
+        json!({
+            "required_roles": ["comms_lead", "scribe"],
+            "stale_incident_minutes": 30,
+            "postmortem_due_days": 5
+        })
+    );
+
+    // Re-importing an unchanged export is a no-op
+    let (status, report) = common::send(
+        &state,
+        "POST",
+        "/api/v1/admin/config/import?dry_run=true",
+        Some(exported.clone()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(report["changes"], json!([]));
+    assert_eq!(report["environment_drift"], json!([]));
+
+    // Staging added a template, a Statuspage mapping and a service
+    let mut staging = exported.clone();
+    staging["templates"].as_array_mut().unwrap().push(json!({
+        "name": "bundle-test-queue",
+        "title": "Queue backlog",
+        "severity": "P3",
+        "affected_service": "Test Service",
+        "description": null
+    }));
+    staging["statuspage_components"]["Test Service"] = json!("cmp_bundle_test");
+    staging["environment"]["services"] = json!(["Test Service", "Queue"]);
+
+    let (status, report) = common::send(
+        &state,
+        "POST",
+        "/api/v1/admin/config/import?dry_run=true",
+        Some(staging.clone()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(report["dry_run"], true);
+    let changes: Vec<(&str, &str, &str)> = report["changes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|c| {
+            (
+                c["section"].as_str().unwrap(),
+                c["key"].as_str().unwrap(),
+                c["action"].as_str().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        changes,
+        vec![
+            ("templates", "bundle-test-queue", "added"),
+            ("statuspage_components", "Test Service", "added"),
+        ]
+    );
+    assert_eq!(report["environment_drift"], json!(["SERVICES"]));
+    // Nothing written yet
+    assert!(incident_bot::db::queries::templates::get_template_by_name(
+        &ctx.pool,
+        "bundle-test-queue"
+    )
+    .await
+    .unwrap()
+    .is_none());
+
+    let (status, report) = common::send(
+        &state,
+        "POST",
+        "/api/v1/admin/config/import",
+        Some(staging.clone()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(report["changes"].as_array().unwrap().len(), 2);
+
+    let (_, promoted) = common::send(&state, "GET", "/api/v1/admin/config/export", None).await;
+    let names: Vec<&str> = promoted["templates"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names.len(), staging["templates"].as_array().unwrap().len());
+    assert!(names.contains(&"bundle-test-queue"));
+    assert_eq!(
+        promoted["statuspage_components"],
+        json!({ "Test Service": "cmp_bundle_test" })
+    );
+
+    // Bundles from another version are rejected
+    let mut future = staging.clone();
+    future["version"] = json!(2);
+    let (status, _) =
+        common::send(&state, "POST", "/api/v1/admin/config/import", Some(future)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // Put the shared tables back for other suites
+    let (status, _) = common::send(
+        &state,
+        "POST",
+        "/api/v1/admin/config/import",
+        Some(exported),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    sqlx::query::query("DELETE FROM incident_templates WHERE name = 'bundle-test-queue'")
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+
+    ctx.cleanup().await;
+}
@@ -0,0 +1,102 @@
+use axum::http::StatusCode;
+use incident_bot::config::{AppConfig, DataResidency};
+use incident_bot::db::models::{Severity, WebhookEvent};
+use incident_bot::services::incident::IncidentService;
+use incident_bot::services::webhook::{self, WebhookService};
+use incident_bot::slack::mock::MockSlackClient;
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+mod common;
+
+fn eu_config(regions: &[(&str, &str)]) -> AppConfig {
+    let mut config = common::test_config();
+    config.data_residency = DataResidency::Eu;
+    config.integration_regions = regions
+        .iter()
+        .map(|(service, region)| (service.to_string(), region.to_string()))
+        .collect::<HashMap<_, _>>();
+    config
+}
+
+#[tokio::test]
+async fn test_eu_residency_blocks_webhooks_not_declared_eu_and_reports_them() {
+    let ctx = common::TestContext::new().await;
+
+    // Registered before residency was switched on
+    WebhookService::new(ctx.pool.clone())
+        .register(
+            "https://warehouse.example.com/incidents",
+            None,
+            &[WebhookEvent::IncidentDeclared],
+        )
+        .await
+        .unwrap();
+    let incident = IncidentService::new(ctx.pool.clone())
+        .create_incident(
+            "Residency".to_string(),
+            Severity::P3,
+            "Test Service".to_string(),
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .unwrap();
+
+    let (job_sender, mut job_receiver) = mpsc::unbounded_channel();
+    let state = incident_bot::AppState::with_slack_client(
+        ctx.pool.clone(),
+        eu_config(&[("slack", "eu")]),
+        job_sender,
+        Arc::new(MockSlackClient::new()),
+    );
+
+    webhook::enqueue(&state, WebhookEvent::IncidentDeclared, &incident, None).await;
+    assert!(job_receiver.try_recv().is_err());
+
+    let (status, _) = common::send(
+        &state,
+        "POST",
+        "/api/v1/webhooks",
+        Some(json!({ "url": "https://warehouse.example.com/other" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, report) = common::send(&state, "GET", "/api/v1/admin/data-residency", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(report["data_residency"], "eu");
+    assert_eq!(report["eu_only"], true);
+    let row = |service: &str| {
+        report["services"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|row| row["service"] == service)
+            .unwrap()
+            .clone()
+    };
+    assert_eq!(row("slack")["enabled"], true);
+    assert_eq!(row("slack")["eu"], true);
+    assert_eq!(row("jira")["enabled"], false);
+    let webhooks = row("webhooks");
+    assert_eq!(webhooks["enabled"], false);
+    assert_eq!(
+        webhooks["endpoints"],
+        json!(["https://warehouse.example.com/incidents"])
+    );
+
+    // Declared EU-hosted, webhooks are sent again
+    let (job_sender, mut job_receiver) = mpsc::unbounded_channel();
+    let state = incident_bot::AppState::with_slack_client(
+        ctx.pool.clone(),
+        eu_config(&[("slack", "eu"), ("webhooks", "eu-central-1")]),
+        job_sender,
+        Arc::new(MockSlackClient::new()),
+    );
+    webhook::enqueue(&state, WebhookEvent::IncidentDeclared, &incident, None).await;
+    assert!(job_receiver.try_recv().is_ok());
+
+    ctx.cleanup().await;
+}
@@ -0,0 +1,112 @@
+use chrono::Utc;
+use incident_bot::commands::commander::handle_take_command;
+use incident_bot::config::AppConfig;
+use incident_bot::jobs::deactivated_users::check_deactivated_users;
+use incident_bot::services::incident::IncidentService;
+use incident_bot::slack::mock::{MockSlackClient, SlackCall};
+use incident_bot::AppState;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+mod common;
+
+fn state(ctx: &common::TestContext, mock: Arc<MockSlackClient>) -> AppState {
+    let config = AppConfig {
+        p1_users: vec!["U_GONE_OWNER".to_string()],
+        service_owners: HashMap::from([(
+            "Test Service".to_string(),
+            vec!["U_GONE_OWNER".to_string(), "U_OWNER".to_string()],
+        )]),
+        backup_commanders: vec!["U_ONCALL".to_string()],
+        ..common::test_config()
+    };
+    let (job_sender, _job_receiver) = tokio::sync::mpsc::unbounded_channel();
+    AppState::with_slack_client(ctx.pool.clone(), config, job_sender, mock)
+}
+
+fn dm_text(mock: &MockSlackClient, recipient: &str) -> Vec<String> {
+    mock.calls()
+        .into_iter()
+        .filter_map(|call| match call {
+            SlackCall::SendDm { user_id, blocks } if user_id == recipient => {
+                blocks[0]["text"]["text"].as_str().map(str::to_string)
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_deactivated_users_are_reported_once_and_backups_offered_command() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let state = state(&ctx, mock.clone());
+    let incident_service = IncidentService::new(ctx.pool.clone());
+    let incident = incident_service
+        .create_incident(
+            "Checkout is down".to_string(),
+            incident_bot::db::models::Severity::P2,
+            "Test Service".to_string(),
+            "U_GONE_COMMANDER".to_string(),
+        )
+        .await
+        .unwrap();
+    incident_service
+        .update_channel_id(incident.id, "C_DEACTIVATED".to_string())
+        .await
+        .unwrap();
+
+    // Nothing to report while everyone is active
+    assert_eq!(
+        check_deactivated_users(&state, Utc::now()).await.unwrap(),
+        0
+    );
+    assert!(mock.dm_recipients().is_empty());
+
+    mock.deactivate_user("U_GONE_COMMANDER");
+    mock.deactivate_user("U_GONE_OWNER");
+    assert_eq!(
+        check_deactivated_users(&state, Utc::now()).await.unwrap(),
+        2
+    );
+
+    let report = dm_text(&mock, "U_ADMIN");
+    assert_eq!(report.len(), 1);
+    assert!(report[0].contains("*2 Slack users deactivated*"));
+    assert!(
+        report[0].contains("<@U_GONE_COMMANDER> (`U_GONE_COMMANDER`): commands <#C_DEACTIVATED>")
+    );
+    assert!(report[0].contains(
+        "<@U_GONE_OWNER> (`U_GONE_OWNER`): remove from P1_USERS, SERVICE_OWNERS (Test Service)"
+    ));
+
+    // The channel and the active backups are offered command
+    assert!(mock
+        .posted_channels()
+        .contains(&"C_DEACTIVATED".to_string()));
+    let mut recipients = mock.dm_recipients();
+    recipients.sort();
+    assert_eq!(recipients, vec!["U_ADMIN", "U_ONCALL", "U_OWNER"]);
+    assert!(dm_text(&mock, "U_ONCALL")[0]
+        .contains("commander <@U_GONE_COMMANDER> has been deactivated"));
+
+    // Known deactivations aren't reported again
+    assert_eq!(
+        check_deactivated_users(&state, Utc::now()).await.unwrap(),
+        0
+    );
+    assert_eq!(mock.dm_recipients().len(), 3);
+
+    handle_take_command(
+        state.clone(),
+        "U_ONCALL".to_string(),
+        &incident.id.to_string(),
+        None,
+    )
+    .await
+    .unwrap();
+    let incident = incident_service.get_by_id(incident.id).await.unwrap();
+    assert_eq!(incident.commander_id, "U_ONCALL");
+
+    ctx.cleanup().await;
+}
@@ -0,0 +1,212 @@
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use axum::Router;
+use incident_bot::commands::jobs::handle_jobs;
+use incident_bot::db::models::{Severity, WebhookEvent};
+use incident_bot::db::queries::{failed_jobs, webhooks};
+use incident_bot::jobs::worker::JobWorker;
+use incident_bot::jobs::Job;
+use incident_bot::services::incident::IncidentService;
+use incident_bot::slack::events::SlashCommandPayload;
+use incident_bot::slack::mock::{MockSlackClient, SlackCall};
+use incident_bot::AppState;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tower::ServiceExt;
+
+mod common;
+
+fn jobs(text: &str, user_id: &str) -> SlashCommandPayload {
+    SlashCommandPayload {
+        command: "/incident".to_string(),
+        text: text.to_string(),
+        user_id: user_id.to_string(),
+        channel_id: "C024OPS".to_string(),
+        response_url: "https://hooks.slack.test/response".to_string(),
+        trigger_id: "trigger-123".to_string(),
+    }
+}
+
+fn last_reply(mock: &MockSlackClient) -> String {
+    mock.calls()
+        .iter()
+        .rev()
+        .find_map(|call| match call {
+            SlackCall::PostToResponseUrl { blocks, .. } => {
+                blocks[0]["text"]["text"].as_str().map(str::to_string)
+            }
+            _ => None,
+        })
+        .expect("Expected a response_url reply")
+}
+
+async fn api(router: &Router, method: &str, uri: &str) -> (StatusCode, Value) {
+    let response = router
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method(method)
+                .uri(uri)
+                .header("Authorization", "Bearer test-api-token")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (
+        status,
+        serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+    )
+}
+
+#[tokio::test]
+async fn test_failed_jobs_are_dead_lettered_and_can_be_retried_or_discarded() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let (job_sender, job_receiver) = mpsc::unbounded_channel();
+    let state = AppState::with_slack_client(
+        ctx.pool.clone(),
+        common::test_config(),
+        job_sender.clone(),
+        mock.clone(),
+    );
+    tokio::spawn(JobWorker::new(job_receiver, None, None, None, state.clone()).start());
+
+    let incident = IncidentService::new(ctx.pool.clone())
+        .create_incident(
+            "Webhook receiver down".to_string(),
+            Severity::P2,
+            "Test Service".to_string(),
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .unwrap();
+    // A Statuspage job still waiting for Statuspage to recover is not dead
+    failed_jobs::record_failed_job(
+        &ctx.pool,
+        Some(incident.id),
+        &json!({ "StatuspageIncidentCreate": { "incident_id": incident.id } }),
+        "HTTP 503",
+    )
+    .await
+    .unwrap();
+
+    // Nothing listens on port 9: delivery fails after its retries
+    let webhook = webhooks::create_webhook(
+        &ctx.pool,
+        "http://127.0.0.1:9/hooks",
+        "secret",
+        &[WebhookEvent::IncidentDeclared],
+    )
+    .await
+    .unwrap();
+    let delivery = Job::DeliverWebhook {
+        webhook_id: webhook.id,
+        event: WebhookEvent::IncidentDeclared,
+        payload: json!({ "id": "delivery-1", "incident": { "id": incident.id } }),
+    };
+    job_sender.send(delivery).unwrap();
+
+    let mut dead = Vec::new();
+    for _ in 0..100 {
+        dead = failed_jobs::list_dead_letters(&ctx.pool, 10).await.unwrap();
+        if !dead.is_empty() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    assert_eq!(dead.len(), 1);
+    assert_eq!(dead[0].kind(), "DeliverWebhook");
+    assert!(dead[0]
+        .last_error
+        .contains("delivery to http://127.0.0.1:9/hooks failed"));
+    let first = dead[0].id.to_string();
+
+    let router = Router::new()
+        .nest("/api/v1", incident_bot::api::router(state.clone()))
+        .with_state(state.clone());
+    let (status, listed) = api(&router, "GET", "/api/v1/admin/jobs/dead-letters").await;
+    assert_eq!(status, StatusCode::OK);
+    let listed = listed.as_array().unwrap();
+    assert_eq!(listed.len(), 1);
+    assert_eq!(
+        listed[0]["job"]["DeliverWebhook"]["payload"]["id"],
+        "delivery-1"
+    );
+
+    // Only admins can use the command
+    handle_jobs(state.clone(), jobs("jobs", "U024BYSTANDER"))
+        .await
+        .unwrap();
+    assert!(last_reply(&mock).contains("Only bot admins"));
+
+    handle_jobs(state.clone(), jobs("jobs", "U_ADMIN"))
+        .await
+        .unwrap();
+    let reply = last_reply(&mock);
+    assert!(reply.contains(&first[..8]));
+    assert!(reply.contains("DeliverWebhook"));
+
+    // Retry hands it back to the worker; it fails again under a new ID
+    handle_jobs(
+        state.clone(),
+        jobs(&format!("jobs retry {}", &first[..8]), "U_ADMIN"),
+    )
+    .await
+    .unwrap();
+    assert!(last_reply(&mock).contains("Requeued"));
+    let mut dead = Vec::new();
+    for _ in 0..100 {
+        dead = failed_jobs::list_dead_letters(&ctx.pool, 10).await.unwrap();
+        if dead.first().is_some_and(|job| job.id.to_string() != first) {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    assert_eq!(dead.len(), 1);
+    let second = dead[0].id.to_string();
+    assert_ne!(second, first);
+
+    let (status, discarded) = api(
+        &router,
+        "POST",
+        &format!("/api/v1/admin/jobs/{}/discard", second),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(discarded["id"], second);
+    let (status, _) = api(
+        &router,
+        "POST",
+        &format!("/api/v1/admin/jobs/{}/discard", second),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    handle_jobs(state.clone(), jobs("jobs", "U_ADMIN"))
+        .await
+        .unwrap();
+    assert!(last_reply(&mock).contains("No dead-lettered jobs"));
+    // The deferred Statuspage job was left alone
+    let deferred = failed_jobs::take_oldest_failed_job(&ctx.pool)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(deferred.incident_id, Some(incident.id));
+
+    let audited: i64 = sqlx::query_scalar::query_scalar(
+        "SELECT COUNT(*) FROM audit_log WHERE action IN ('job_retried', 'job_discarded')",
+    )
+    .fetch_one(&ctx.pool)
+    .await
+    .unwrap();
+    assert_eq!(audited, 2);
+
+    ctx.cleanup().await;
+}
@@ -0,0 +1,155 @@
+use incident_bot::commands::declare::handle_declare;
+use incident_bot::slack::events::SlashCommandPayload;
+use incident_bot::slack::mock::{MockSlackClient, SlackCall};
+use incident_bot::slack::socket_mode::{handle_message, SocketAction};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use std::time::Duration;
+
+mod common;
+
+fn declare_command() -> SlashCommandPayload {
+    SlashCommandPayload {
+        command: "/incident".to_string(),
+        text: "declare".to_string(),
+        user_id: "U024DRAFTER".to_string(),
+        channel_id: "C_GENERAL".to_string(),
+        response_url: "https://hooks.slack.test/response".to_string(),
+        trigger_id: "trigger-declare".to_string(),
+    }
+}
+
+fn interactive(envelope_id: &str, payload: Value) -> String {
+    json!({
+        "type": "interactive",
+        "envelope_id": envelope_id,
+        "payload": payload
+    })
+    .to_string()
+}
+
+/// Wait for the spawned interaction handler to open a modal; returns the
+/// latest one opened with `trigger_id`.
+async fn wait_for_modal(mock: &MockSlackClient, trigger_id: &str) -> Value {
+    for _ in 0..50 {
+        let modal = mock.calls().into_iter().rev().find_map(|call| match call {
+            SlackCall::OpenModal {
+                trigger_id: t,
+                view,
+            } if t == trigger_id => Some(view),
+            _ => None,
+        });
+        if let Some(modal) = modal {
+            return modal;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    panic!("no modal opened for {}", trigger_id);
+}
+
+#[tokio::test]
+async fn test_declare_offers_to_resume_saved_draft() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let state = common::mock_state(&ctx.pool, mock.clone());
+
+    // No draft yet: the blank modal opens straight away
+    handle_declare(state.clone(), declare_command())
+        .await
+        .unwrap();
+    let modal = wait_for_modal(&mock, "trigger-declare").await;
+    assert!(!modal.to_string().contains("initial_value"));
+
+    // The user types a title and picks a severity, then closes the modal
+    let edit = interactive(
+        "env-draft-1",
+        json!({
+            "type": "block_actions",
+            "user": { "id": "U024DRAFTER" },
+            "actions": [{ "action_id": "severity_select", "selected_option": { "value": "P1" } }],
+            "view": {
+                "callback_id": "declare_incident_modal",
+                "state": { "values": {
+                    "title_block": { "title_input": { "value": "Checkout errors" } },
+                    "severity_block": { "severity_select": { "selected_option": { "value": "P1" } } }
+                } }
+            }
+        }),
+    );
+    assert!(matches!(
+        handle_message(&state, &edit).await,
+        SocketAction::Ack(_)
+    ));
+
+    // Saved by a spawned task; the next declare offers it back
+    let mut offer = None;
+    for _ in 0..50 {
+        handle_declare(state.clone(), declare_command())
+            .await
+            .unwrap();
+        offer = mock.calls().into_iter().find_map(|call| match call {
+            SlackCall::PostToResponseUrl { blocks, .. } => Some(blocks),
+            _ => None,
+        });
+        if offer.is_some() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    let offer = Value::Array(offer.expect("draft was not offered")).to_string();
+    assert!(offer.contains("*Checkout errors* (P1)"));
+    assert!(offer.contains("declare_resume_draft"));
+
+    let resume = interactive(
+        "env-draft-2",
+        json!({
+            "type": "block_actions",
+            "user": { "id": "U024DRAFTER" },
+            "trigger_id": "trigger-resume",
+            "response_url": "https://hooks.slack.test/response",
+            "actions": [{ "action_id": "declare_resume_draft" }]
+        }),
+    );
+    handle_message(&state, &resume).await;
+    let modal = wait_for_modal(&mock, "trigger-resume").await;
+    let blocks = modal["blocks"].as_array().unwrap();
+    let block = |id: &str| blocks.iter().find(|b| b["block_id"] == id).unwrap().clone();
+    assert_eq!(
+        block("title_block")["element"]["initial_value"],
+        "Checkout errors"
+    );
+    assert_eq!(
+        block("severity_block")["element"]["initial_option"]["value"],
+        "P1"
+    );
+
+    // "Start fresh" drops the draft
+    let discard = interactive(
+        "env-draft-3",
+        json!({
+            "type": "block_actions",
+            "user": { "id": "U024DRAFTER" },
+            "trigger_id": "trigger-fresh",
+            "actions": [{ "action_id": "declare_discard_draft" }]
+        }),
+    );
+    handle_message(&state, &discard).await;
+    let modal = wait_for_modal(&mock, "trigger-fresh").await;
+    assert!(!modal.to_string().contains("initial_value"));
+    let offers = mock
+        .calls()
+        .iter()
+        .filter(|call| matches!(call, SlackCall::PostToResponseUrl { .. }))
+        .count();
+    handle_declare(state.clone(), declare_command())
+        .await
+        .unwrap();
+    let offers_after = mock
+        .calls()
+        .iter()
+        .filter(|call| matches!(call, SlackCall::PostToResponseUrl { .. }))
+        .count();
+    assert_eq!(offers, offers_after);
+
+    ctx.cleanup().await;
+}
@@ -0,0 +1,139 @@
+use chrono::Duration;
+use incident_bot::config::{AppConfig, ExecTier, ExecTiers};
+use incident_bot::db::models::Severity;
+use incident_bot::jobs::exec_tiers::notify_due_tiers;
+use incident_bot::services::incident::IncidentService;
+use incident_bot::services::notification::NotificationService;
+use incident_bot::slack::mock::{MockSlackClient, SlackCall};
+use incident_bot::AppState;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+mod common;
+
+fn tier(users: &[&str], user_groups: &[&str]) -> ExecTier {
+    ExecTier {
+        users: users.iter().map(ToString::to_string).collect(),
+        user_groups: user_groups.iter().map(ToString::to_string).collect(),
+    }
+}
+
+fn tiered_config() -> AppConfig {
+    AppConfig {
+        exec_tiers: HashMap::from([(
+            "P1".to_string(),
+            ExecTiers {
+                tier1: tier(&["U_TIER_VP"], &[]),
+                tier2: tier(&["U_TIER_CTO"], &["S_TIER_DIRECTORS"]),
+                tier3: tier(&["U_TIER_CEO"], &[]),
+            },
+        )]),
+        ..common::test_config()
+    }
+}
+
+fn dms_since(mock: &MockSlackClient, start: usize) -> Vec<(String, String)> {
+    mock.calls()
+        .into_iter()
+        .skip(start)
+        .filter_map(|call| match call {
+            SlackCall::SendDm { user_id, blocks } => {
+                Some((user_id, serde_json::to_string(&blocks).unwrap()))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_exec_tiers_go_out_as_p1_stays_unresolved() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    mock.add_usergroup("S_TIER_DIRECTORS", &["U_TIER_DIR", "U_TIER_CTO"]);
+    let (job_sender, _job_receiver) = tokio::sync::mpsc::unbounded_channel();
+    let state =
+        AppState::with_slack_client(ctx.pool.clone(), tiered_config(), job_sender, mock.clone());
+    let notification_service =
+        NotificationService::new(ctx.pool.clone(), mock.clone(), Arc::new(tiered_config()));
+
+    let incident_service = IncidentService::new(ctx.pool.clone());
+    let incident = incident_service
+        .create_incident(
+            "Checkout down for everyone".to_string(),
+            Severity::P1,
+            "Test Service".to_string(),
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .unwrap();
+    incident_service
+        .update_channel_id(incident.id, "C_EXEC_TIERS".to_string())
+        .await
+        .unwrap();
+    let incident = incident_service.get_by_id(incident.id).await.unwrap();
+    let declared_at = incident.declared_at;
+
+    // Tier 1 replaces P1_USERS and goes out with the declaration
+    notification_service
+        .notify_incident_declared(&incident, vec![])
+        .await
+        .unwrap();
+    assert_eq!(mock.dm_recipients(), vec!["U_TIER_VP"]);
+
+    let mut seen = mock.calls().len();
+    notify_due_tiers(&state, declared_at + Duration::minutes(10))
+        .await
+        .unwrap();
+    assert!(dms_since(&mock, seen).is_empty());
+
+    notify_due_tiers(&state, declared_at + Duration::minutes(31))
+        .await
+        .unwrap();
+    let tier2 = dms_since(&mock, seen);
+    assert_eq!(
+        tier2.iter().map(|(u, _)| u.as_str()).collect::<Vec<_>>(),
+        vec!["U_TIER_CTO", "U_TIER_DIR"]
+    );
+    assert!(tier2[0]
+        .1
+        .contains("*Exec notification (tier 2)*: 🔴 *Checkout down for everyone* in <#C_EXEC_TIERS> is still unresolved 31 min after it was declared."));
+
+    // Each tier goes out once
+    seen = mock.calls().len();
+    notify_due_tiers(&state, declared_at + Duration::minutes(45))
+        .await
+        .unwrap();
+    assert!(dms_since(&mock, seen).is_empty());
+
+    notify_due_tiers(&state, declared_at + Duration::minutes(121))
+        .await
+        .unwrap();
+    let tier3 = dms_since(&mock, seen);
+    assert_eq!(tier3.len(), 1);
+    assert_eq!(tier3[0].0, "U_TIER_CEO");
+    assert!(tier3[0].1.contains("(tier 3)"));
+
+    let delivered = sqlx::query_scalar::query_scalar::<_, i16>(
+        "SELECT tier FROM exec_tier_deliveries WHERE incident_id = $1 ORDER BY tier",
+    )
+    .bind(incident.id)
+    .fetch_all(&ctx.pool)
+    .await
+    .unwrap();
+    assert_eq!(delivered, vec![1, 2, 3]);
+
+    // Everyone a tier reached hears about the resolution (from a fresh
+    // service, outside the declaration's DM throttle)
+    seen = mock.calls().len();
+    NotificationService::new(ctx.pool.clone(), mock.clone(), Arc::new(tiered_config()))
+        .notify_resolution(&incident, vec![])
+        .await
+        .unwrap();
+    let resolved_dms: Vec<String> = dms_since(&mock, seen).into_iter().map(|(u, _)| u).collect();
+    assert_eq!(
+        resolved_dms,
+        vec!["U_TIER_VP", "U_TIER_CTO", "U_TIER_CEO", "U_TIER_DIR"]
+    );
+
+    ctx.cleanup().await;
+}
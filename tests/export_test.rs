@@ -0,0 +1,193 @@
+use chrono::{Duration, Utc};
+use incident_bot::commands::export::handle_export;
+use incident_bot::db::models::{Severity, TimelineEventType};
+use incident_bot::services::incident::IncidentService;
+use incident_bot::services::timeline::TimelineService;
+use incident_bot::slack::events::SlashCommandPayload;
+use incident_bot::slack::mock::{MockSlackClient, SlackCall};
+use incident_bot::{AppConfig, AppState};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+mod common;
+
+fn export(text: &str, user_id: &str) -> SlashCommandPayload {
+    SlashCommandPayload {
+        command: "/incident".to_string(),
+        text: text.to_string(),
+        user_id: user_id.to_string(),
+        channel_id: "C024OPS".to_string(),
+        response_url: "https://hooks.slack.test/response".to_string(),
+        trigger_id: "trigger-123".to_string(),
+    }
+}
+
+fn last_reply(mock: &MockSlackClient) -> String {
+    mock.calls()
+        .iter()
+        .rev()
+        .find_map(|call| match call {
+            SlackCall::PostToResponseUrl { blocks, .. } => {
+                blocks[0]["text"]["text"].as_str().map(str::to_string)
+            }
+            _ => None,
+        })
+        .expect("Expected a response_url reply")
+}
+
+/// Filename and content of every file DMed to `user_id`.
+fn dmed_files(mock: &MockSlackClient, user: &str) -> Vec<(String, String)> {
+    mock.calls()
+        .into_iter()
+        .filter_map(|call| match call {
+            SlackCall::SendFileDm {
+                user_id,
+                filename,
+                content,
+                ..
+            } if user_id == user => Some((filename, String::from_utf8(content).unwrap())),
+            _ => None,
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_admin_exports_incidents_with_timelines() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let config = AppConfig {
+        restricted_fields: HashMap::from([(
+            "customer_name".to_string(),
+            vec!["admin".to_string()],
+        )]),
+        ..common::test_config()
+    };
+    let (job_sender, _job_receiver) = mpsc::unbounded_channel();
+    let state = AppState::with_slack_client(ctx.pool.clone(), config, job_sender, mock.clone());
+
+    let incident_service = IncidentService::new(ctx.pool.clone());
+    let timeline_service = TimelineService::new(ctx.pool.clone());
+    let incident = incident_service
+        .create_incident(
+            "Checkout errors, EU".to_string(),
+            Severity::P1,
+            "Test Service".to_string(),
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .unwrap();
+    sqlx::query::query("UPDATE incidents SET custom_fields = $2 WHERE id = $1")
+        .bind(incident.id)
+        .bind(json!({ "customer_name": "Acme Corp" }))
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+    timeline_service
+        .log_event(
+            incident.id,
+            TimelineEventType::Note,
+            "=cmd|' /C calc'!A0".to_string(),
+            "U024SCRIBE".to_string(),
+        )
+        .await
+        .unwrap();
+    let retracted = timeline_service
+        .log_event(
+            incident.id,
+            TimelineEventType::Note,
+            "Customer password in this note".to_string(),
+            "U024SCRIBE".to_string(),
+        )
+        .await
+        .unwrap();
+    sqlx::query::query("UPDATE incident_timeline SET deleted_at = NOW() WHERE id = $1")
+        .bind(retracted.id)
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+
+    // Declared long before the range
+    let old = incident_service
+        .create_incident(
+            "Old outage".to_string(),
+            Severity::P3,
+            "Test Service".to_string(),
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .unwrap();
+    sqlx::query::query("UPDATE incidents SET declared_at = '2020-01-01T00:00:00Z' WHERE id = $1")
+        .bind(old.id)
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+
+    let today = Utc::now().date_naive();
+    let range = format!("{} {}", today - Duration::days(1), today);
+
+    handle_export(
+        state.clone(),
+        export(&format!("export {}", range), "U024BYSTANDER"),
+    )
+    .await
+    .unwrap();
+    assert!(last_reply(&mock).contains("Only bot admins"));
+    assert!(dmed_files(&mock, "U024BYSTANDER").is_empty());
+
+    handle_export(
+        state.clone(),
+        export(&format!("export {}", range), "U_ADMIN"),
+    )
+    .await
+    .unwrap();
+    assert!(last_reply(&mock).contains("Exported 1 incident declared"));
+    let files = dmed_files(&mock, "U_ADMIN");
+    assert_eq!(files.len(), 1);
+    let (filename, csv) = &files[0];
+    assert_eq!(
+        filename,
+        &format!("incidents-{}-to-{}.csv", today - Duration::days(1), today)
+    );
+    let rows: Vec<&str> = csv.lines().collect();
+    assert!(rows[0].starts_with("incident_id,incident_number,title,severity,status"));
+    assert!(rows[1..]
+        .iter()
+        .all(|row| row.contains("\"Checkout errors, EU\"")));
+    assert!(csv.contains("Acme Corp"));
+    assert!(csv.contains("'=cmd|' /C calc'!A0"));
+    assert!(!csv.contains("Customer password"));
+    assert!(!csv.contains("Old outage"));
+
+    handle_export(
+        state.clone(),
+        export(&format!("export {} json", range), "U_ADMIN"),
+    )
+    .await
+    .unwrap();
+    let files = dmed_files(&mock, "U_ADMIN");
+    let body: Value = serde_json::from_str(&files[1].1).unwrap();
+    let incidents = body["incidents"].as_array().unwrap();
+    assert_eq!(incidents.len(), 1);
+    assert_eq!(incidents[0]["id"], incident.id.to_string());
+    assert_eq!(incidents[0]["custom_fields"]["customer_name"], "Acme Corp");
+    let timeline = incidents[0]["timeline"].as_array().unwrap();
+    assert_eq!(timeline.last().unwrap()["message"], "");
+    assert!(!timeline.last().unwrap()["deleted_at"].is_null());
+
+    let exports: i64 = sqlx::query_scalar::query_scalar(
+        "SELECT COUNT(*) FROM audit_log WHERE action = 'incidents_exported' AND actor_id = 'U_ADMIN'",
+    )
+    .fetch_one(&ctx.pool)
+    .await
+    .unwrap();
+    assert_eq!(exports, 2);
+
+    handle_export(state, export("export 2024-09-30 2024-07-01", "U_ADMIN"))
+        .await
+        .unwrap();
+    assert!(last_reply(&mock).contains("on or after the start date"));
+
+    ctx.cleanup().await;
+}
@@ -0,0 +1,231 @@
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use axum::Router;
+use hmac::{Hmac, Mac};
+use incident_bot::commands::link::handle_link;
+use incident_bot::db::models::{LinkKind, Severity, TimelineEventType};
+use incident_bot::db::queries::{links, timeline};
+use incident_bot::slack::mock::{MockSlackClient, SlackCall};
+use serde_json::{json, Value};
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tower::ServiceExt;
+
+mod common;
+
+const SECRET: &str = "github-test-secret";
+
+fn posted_to(mock: &MockSlackClient, channel: &str) -> Vec<String> {
+    mock.calls()
+        .into_iter()
+        .filter_map(|call| match call {
+            SlackCall::PostMessage { channel_id, blocks } if channel_id == channel => {
+                Some(serde_json::to_string(&blocks).unwrap())
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn signed_delivery(event: &str, body: &Value, secret: &str) -> Request<Body> {
+    let body = body.to_string();
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+    mac.update(body.as_bytes());
+    Request::builder()
+        .method("POST")
+        .uri("/integrations/github")
+        .header("X-GitHub-Event", event)
+        .header(
+            "X-Hub-Signature-256",
+            format!("sha256={}", hex::encode(mac.finalize().into_bytes())),
+        )
+        .header("Content-Type", "application/json")
+        .body(Body::from(body))
+        .unwrap()
+}
+
+fn deployment_status(repo: &str, sha: &str, environment: &str) -> Value {
+    json!({
+        "action": "created",
+        "deployment_status": {
+            "state": "success",
+            "target_url": format!("https://ci.acme.test/deploys/{}", sha)
+        },
+        "deployment": {
+            "sha": sha,
+            "environment": environment,
+            "creator": { "login": "octocat" }
+        },
+        "repository": { "full_name": repo }
+    })
+}
+
+#[tokio::test]
+async fn test_link_github_pull_requests_and_commits() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let state = common::mock_state(&ctx.pool, mock.clone());
+    let incident =
+        common::incident_in_channel(&ctx, Severity::P2, "Test Service", "C_GH_LINK").await;
+
+    handle_link(
+        state.clone(),
+        common::slash_command(
+            "link github <https://github.com/acme/checkout/pull/42/files>",
+            "U_RESPONDER",
+            "C_GH_LINK",
+        ),
+    )
+    .await
+    .unwrap();
+    handle_link(
+        state.clone(),
+        common::slash_command(
+            "link github https://github.com/acme/checkout/commit/9f8e7d6c5b4a",
+            "U_RESPONDER",
+            "C_GH_LINK",
+        ),
+    )
+    .await
+    .unwrap();
+
+    let linked = links::list_links(&ctx.pool, incident.id).await.unwrap();
+    assert_eq!(linked.len(), 2);
+    assert_eq!(linked[0].kind, LinkKind::PullRequest);
+    assert_eq!(linked[0].url, "https://github.com/acme/checkout/pull/42");
+    assert_eq!(linked[0].linked_by, "U_RESPONDER");
+    assert_eq!(linked[1].kind, LinkKind::Commit);
+    assert_eq!(linked[1].reference, "9f8e7d6c5b4a");
+
+    let posts = posted_to(&mock, "C_GH_LINK");
+    assert!(posts[0].contains(
+        "<@U_RESPONDER> linked pull request <https://github.com/acme/checkout/pull/42|acme/checkout#42>"
+    ));
+    let notes: Vec<_> = timeline::get_timeline(&ctx.pool, incident.id)
+        .await
+        .unwrap()
+        .into_iter()
+        .filter(|e| e.event_type == TimelineEventType::Note)
+        .collect();
+    assert_eq!(notes.len(), 2);
+    assert!(notes[1].message.contains("acme/checkout@9f8e7d6"));
+
+    handle_link(
+        state.clone(),
+        common::slash_command(
+            "link github https://github.com/acme/checkout/pull/42",
+            "U_RESPONDER",
+            "C_GH_LINK",
+        ),
+    )
+    .await
+    .unwrap();
+    assert!(mock
+        .last_response()
+        .contains("acme/checkout#42 is already linked"));
+
+    handle_link(
+        state.clone(),
+        common::slash_command(
+            "link github https://example.com/pr/1",
+            "U_RESPONDER",
+            "C_GH_LINK",
+        ),
+    )
+    .await
+    .unwrap();
+    assert!(mock
+        .last_response()
+        .contains("Not a GitHub pull request or commit URL"));
+
+    handle_link(
+        state,
+        common::slash_command("link", "U_RESPONDER", "C_GH_LINK"),
+    )
+    .await
+    .unwrap();
+    let listed = mock.last_response();
+    assert!(listed.contains("Linked to this incident"));
+    assert!(listed.find("acme/checkout#42") < listed.find("acme/checkout@9f8e7d6"));
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_deploy_webhook_annotates_open_incident_for_service() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let mut config = common::test_config();
+    config.services.push("GitHub Deploys".to_string());
+    config.github_webhook_secret = Some(SECRET.to_string());
+    config.github_repos =
+        HashMap::from([("acme/deploys".to_string(), "GitHub Deploys".to_string())]);
+    let (job_sender, _job_receiver) = tokio::sync::mpsc::unbounded_channel();
+    let state = incident_bot::AppState::with_slack_client(
+        ctx.pool.clone(),
+        config,
+        job_sender,
+        mock.clone(),
+    );
+    let incident =
+        common::incident_in_channel(&ctx, Severity::P2, "GitHub Deploys", "C_GH_DEPLOY").await;
+
+    let router = Router::new()
+        .nest("/integrations", incident_bot::api::alerts::router())
+        .with_state(state);
+    let deploy = deployment_status("Acme/Deploys", "0a1b2c3d4e5f", "production");
+
+    let response = router
+        .clone()
+        .oneshot(signed_delivery(
+            "deployment_status",
+            &deploy,
+            "wrong-secret",
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    for _ in 0..2 {
+        // The redelivery is noted once
+        let response = router
+            .clone()
+            .oneshot(signed_delivery("deployment_status", &deploy, SECRET))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+    let response = router
+        .clone()
+        .oneshot(signed_delivery(
+            "deployment_status",
+            &deployment_status("acme/unmapped", "0a1b2c3d4e5f", "production"),
+            SECRET,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let response = router
+        .oneshot(signed_delivery(
+            "ping",
+            &json!({ "zen": "Design for failure." }),
+            SECRET,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let linked = links::list_links(&ctx.pool, incident.id).await.unwrap();
+    assert_eq!(linked.len(), 1);
+    assert_eq!(linked[0].kind, LinkKind::Deploy);
+    assert_eq!(linked[0].environment, "production");
+    assert_eq!(linked[0].url, "https://ci.acme.test/deploys/0a1b2c3d4e5f");
+    assert_eq!(linked[0].linked_by, "github");
+
+    let posts = posted_to(&mock, "C_GH_DEPLOY");
+    assert_eq!(posts.len(), 1);
+    assert!(posts[0].contains("Deploy of <https://ci.acme.test/deploys/0a1b2c3d4e5f|Acme/Deploys@0a1b2c3> to *production* landed by octocat"));
+
+    ctx.cleanup().await;
+}
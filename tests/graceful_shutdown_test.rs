@@ -0,0 +1,77 @@
+use incident_bot::db::models::WebhookEvent;
+use incident_bot::db::queries::{failed_jobs, webhooks};
+use incident_bot::jobs::worker::{JobWorker, INTERRUPTED_BY_SHUTDOWN};
+use incident_bot::jobs::Job;
+use incident_bot::slack::mock::MockSlackClient;
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot};
+use tokio::time::Instant;
+
+mod common;
+
+#[tokio::test]
+async fn test_shutdown_drains_the_queue_and_dead_letters_unfinished_jobs() {
+    let ctx = common::TestContext::new().await;
+    let state = common::mock_state(&ctx.pool, Arc::new(MockSlackClient::new()));
+
+    // A receiver that accepts connections and never answers, so deliveries hang
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/hooks", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        let mut connections = Vec::new();
+        while let Ok((socket, _)) = listener.accept().await {
+            connections.push(socket);
+        }
+    });
+    let webhook =
+        webhooks::create_webhook(&ctx.pool, &url, "secret", &[WebhookEvent::IncidentDeclared])
+            .await
+            .unwrap();
+
+    let (job_sender, job_receiver) = mpsc::unbounded_channel();
+    let (shutdown, shutdown_receiver) = oneshot::channel();
+    let worker = tokio::spawn(
+        JobWorker::new(job_receiver, None, None, None, state.clone())
+            .with_shutdown(shutdown_receiver)
+            .start(),
+    );
+
+    for id in ["delivery-running", "delivery-queued"] {
+        job_sender
+            .send(Job::DeliverWebhook {
+                webhook_id: webhook.id,
+                event: WebhookEvent::IncidentDeclared,
+                payload: json!({ "id": id }),
+            })
+            .unwrap();
+    }
+    shutdown
+        .send(Instant::now() + Duration::from_millis(300))
+        .unwrap();
+    tokio::time::timeout(Duration::from_secs(5), worker)
+        .await
+        .expect("worker should stop by the deadline")
+        .unwrap();
+
+    // The queue is closed, and both jobs are kept for `/incident jobs retry`
+    assert!(job_sender
+        .send(Job::SyncChannelStatus {
+            incident_id: uuid::Uuid::new_v4(),
+        })
+        .is_err());
+    let dead = failed_jobs::list_dead_letters(&ctx.pool, 10).await.unwrap();
+    assert_eq!(dead.len(), 2);
+    assert!(dead
+        .iter()
+        .all(|job| job.kind() == "DeliverWebhook" && job.last_error == INTERRUPTED_BY_SHUTDOWN));
+    let mut ids: Vec<_> = dead
+        .iter()
+        .map(|job| job.job["DeliverWebhook"]["payload"]["id"].to_string())
+        .collect();
+    ids.sort();
+    assert_eq!(ids, vec!["\"delivery-queued\"", "\"delivery-running\""]);
+
+    ctx.cleanup().await;
+}
@@ -0,0 +1,178 @@
+use incident_bot::commands::incident_actions::{
+    handle_acknowledge, handle_resolve_button, handle_update_status_button,
+    handle_update_status_submission,
+};
+use incident_bot::db::models::{IncidentStatus, Severity, TimelineEventType};
+use incident_bot::db::queries::timeline::get_timeline;
+use incident_bot::services::incident::IncidentService;
+use incident_bot::slack::events::ViewPayload;
+use incident_bot::slack::mock::{MockSlackClient, SlackCall};
+use serde_json::json;
+use std::sync::Arc;
+
+mod common;
+
+const CHANNEL: &str = "C_BUTTONS";
+const RESPONSE_URL: &str = "https://hooks.slack.test/response";
+
+#[tokio::test]
+async fn test_incident_message_buttons() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let state = common::mock_state(&ctx.pool, mock.clone());
+    let incident_service = IncidentService::new(ctx.pool.clone());
+    let incident = incident_service
+        .create_incident(
+            "Login failures".to_string(),
+            Severity::P2,
+            "Test Service".to_string(),
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .unwrap();
+    incident_service
+        .update_channel_id(incident.id, CHANNEL.to_string())
+        .await
+        .unwrap();
+    let id = incident.id.to_string();
+    let response_url = || Some(RESPONSE_URL.to_string());
+
+    // Any responder can acknowledge; it starts the investigation once
+    handle_acknowledge(
+        state.clone(),
+        "U024RESPONDER".to_string(),
+        &id,
+        response_url(),
+    )
+    .await
+    .unwrap();
+    let incident = incident_service.get_by_id(incident.id).await.unwrap();
+    assert_eq!(incident.status, IncidentStatus::Investigating);
+    assert!(mock.last_response().contains("Acknowledged"));
+    let timeline = get_timeline(&ctx.pool, incident.id).await.unwrap();
+    assert!(
+        timeline
+            .iter()
+            .any(|e| e.event_type == TimelineEventType::StatusUpdate
+                && e.posted_by == "U024RESPONDER")
+    );
+
+    handle_acknowledge(state.clone(), "U024OTHER".to_string(), &id, response_url())
+        .await
+        .unwrap();
+    assert!(mock.last_response().contains("Already acknowledged"));
+
+    // Update Status opens the modal for the commander only
+    handle_update_status_button(
+        state.clone(),
+        "U024RESPONDER".to_string(),
+        &id,
+        Some("trigger-1".to_string()),
+        response_url(),
+    )
+    .await
+    .unwrap();
+    assert!(mock.last_response().contains("Permission denied"));
+    handle_update_status_button(
+        state.clone(),
+        "U024COMMANDER".to_string(),
+        &id,
+        Some("trigger-2".to_string()),
+        response_url(),
+    )
+    .await
+    .unwrap();
+    let modal = mock
+        .calls()
+        .into_iter()
+        .find_map(|call| match call {
+            SlackCall::OpenModal { trigger_id, view } if trigger_id == "trigger-2" => Some(view),
+            _ => None,
+        })
+        .expect("update status modal not opened");
+    assert_eq!(modal["private_metadata"], id);
+    assert_eq!(
+        modal["blocks"][0]["element"]["initial_option"]["value"],
+        "investigating"
+    );
+    // Only states reachable from investigating are offered; resolve has its own button
+    let offered: Vec<&str> = modal["blocks"][0]["element"]["options"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|o| o["value"].as_str().unwrap())
+        .collect();
+    assert_eq!(offered, vec!["investigating", "identified", "monitoring"]);
+
+    let view: ViewPayload = serde_json::from_value(json!({
+        "callback_id": modal["callback_id"],
+        "private_metadata": id,
+        "state": { "values": {
+            "status_block": { "status_select": { "selected_option": { "value": "identified" } } },
+            "message_block": { "message_input": { "value": "Bad deploy of the auth service" } }
+        } }
+    }))
+    .unwrap();
+    handle_update_status_submission(state.clone(), view, "U024COMMANDER".to_string())
+        .await
+        .unwrap();
+    let incident = incident_service.get_by_id(incident.id).await.unwrap();
+    assert_eq!(incident.status, IncidentStatus::Identified);
+    let posted = mock.calls().into_iter().any(|call| {
+        matches!(call, SlackCall::PostMessage { channel_id, blocks }
+            if channel_id == CHANNEL && blocks[0].to_string().contains("Bad deploy of the auth service"))
+    });
+    assert!(posted);
+
+    // A stale modal can't move the incident backwards; the DM lists what's allowed
+    let stale: ViewPayload = serde_json::from_value(json!({
+        "callback_id": modal["callback_id"],
+        "private_metadata": id,
+        "state": { "values": {
+            "status_block": { "status_select": { "selected_option": { "value": "investigating" } } },
+            "message_block": { "message_input": { "value": null } }
+        } }
+    }))
+    .unwrap();
+    handle_update_status_submission(state.clone(), stale, "U024COMMANDER".to_string())
+        .await
+        .unwrap();
+    assert!(mock.calls().into_iter().any(|call| matches!(
+        call,
+        SlackCall::SendDm { user_id, blocks } if user_id == "U024COMMANDER"
+            && blocks[0].to_string().contains(
+                "Cannot move incident from identified to investigating. Allowed: monitoring, resolved"
+            )
+    )));
+    let incident = incident_service.get_by_id(incident.id).await.unwrap();
+    assert_eq!(incident.status, IncidentStatus::Identified);
+
+    // Resolve is commander only, too
+    handle_resolve_button(
+        state.clone(),
+        "U024RESPONDER".to_string(),
+        &id,
+        response_url(),
+    )
+    .await
+    .unwrap();
+    assert!(mock.last_response().contains("Permission denied"));
+    handle_resolve_button(
+        state.clone(),
+        "U024COMMANDER".to_string(),
+        &id,
+        response_url(),
+    )
+    .await
+    .unwrap();
+    let incident = incident_service.get_by_id(incident.id).await.unwrap();
+    assert_eq!(incident.status, IncidentStatus::Resolved);
+    assert!(mock.last_response().contains("marked as resolved"));
+
+    handle_resolve_button(state, "U024COMMANDER".to_string(), &id, response_url())
+        .await
+        .unwrap();
+    assert!(mock.last_response().contains("already resolved"));
+
+    ctx.cleanup().await;
+}
@@ -1,4 +1,4 @@
-use incident_bot::db::models::{Severity, TimelineEventType};
+use incident_bot::db::models::{Audience, Severity, TimelineEventType};
 use incident_bot::services::incident::IncidentService;
 use incident_bot::services::timeline::TimelineService;
 
@@ -62,6 +62,7 @@ async fn test_post_status_update() {
             incident.id,
             "Investigating issue".to_string(),
             "U024COMMANDER".to_string(),
+            Audience::Internal,
         )
         .await;
 
@@ -104,6 +105,7 @@ async fn test_non_commander_cannot_update() {
             incident.id,
             "Unauthorized update".to_string(),
             "U024OTHER".to_string(),
+            Audience::Internal,
         )
         .await;
 
@@ -144,7 +146,7 @@ async fn test_change_severity() {
         .await
         .expect("Failed to change severity");
 
-    assert_eq!(old_severity, Severity::P2);
+    assert_eq!(old_severity.tier, Severity::P2);
     assert_eq!(updated.severity, Severity::P1);
 
     // Verify timeline
@@ -260,6 +262,7 @@ async fn test_full_incident_lifecycle() {
             incident.id,
             "Investigating".to_string(),
             "U024COMMANDER".to_string(),
+            Audience::Internal,
         )
         .await
         .expect("Failed to post status");
@@ -281,6 +284,7 @@ async fn test_full_incident_lifecycle() {
             incident.id,
             "Fix deployed".to_string(),
             "U024COMMANDER".to_string(),
+            Audience::Internal,
         )
         .await
         .expect("Failed to post status");
@@ -351,3 +355,68 @@ async fn test_get_latest_by_channel_includes_resolved_incidents() {
 
     ctx.cleanup().await;
 }
+
+#[tokio::test]
+async fn test_transition_status_enforces_state_machine() {
+    use incident_bot::db::models::IncidentStatus;
+
+    let ctx = common::TestContext::new().await;
+    let incident_service = IncidentService::new(ctx.pool.clone());
+
+    let incident = incident_service
+        .create_incident(
+            "Transition test".to_string(),
+            Severity::P2,
+            "Test Service".to_string(),
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .expect("Failed to create incident");
+
+    let identified = incident_service
+        .transition_status(
+            incident.id,
+            IncidentStatus::Identified,
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .expect("Declared -> Identified should be allowed");
+    assert_eq!(identified.status, IncidentStatus::Identified);
+
+    // Cannot move backwards
+    let backwards = incident_service
+        .transition_status(
+            incident.id,
+            IncidentStatus::Investigating,
+            "U024COMMANDER".to_string(),
+        )
+        .await;
+    assert!(matches!(
+        backwards,
+        Err(incident_bot::error::IncidentError::InvalidStateTransition { .. })
+    ));
+
+    // Resolving through the state machine records duration like resolve_incident
+    let resolved = incident_service
+        .transition_status(
+            incident.id,
+            IncidentStatus::Resolved,
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .expect("Identified -> Resolved should be allowed");
+    assert!(resolved.duration_minutes.is_some());
+
+    let timeline = TimelineService::new(ctx.pool.clone())
+        .get_timeline(incident.id)
+        .await
+        .expect("Failed to get timeline");
+    assert_eq!(timeline.len(), 3);
+    assert_eq!(
+        timeline[1].message,
+        "Status changed from declared to identified"
+    );
+    assert_eq!(timeline[2].event_type, TimelineEventType::Resolved);
+
+    ctx.cleanup().await;
+}
@@ -0,0 +1,350 @@
+use chrono::{Duration, Utc};
+use incident_bot::commands::metrics::handle_metrics;
+use incident_bot::config::TeamConfig;
+use incident_bot::db::models::Severity;
+use incident_bot::services::incident::IncidentService;
+use incident_bot::services::metrics::MetricsService;
+use incident_bot::services::permissions::{AnalyticsScope, Permissions};
+use incident_bot::slack::events::SlashCommandPayload;
+use incident_bot::slack::mock::{MockSlackClient, SlackCall};
+use incident_bot::{AppConfig, AppState};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+mod common;
+
+/// Create an incident declared `declared_hours_ago`, resolved after
+/// `duration_minutes` if given, and first updated `ack_minutes` in.
+async fn seed(
+    ctx: &common::TestContext,
+    title: &str,
+    severity: Severity,
+    service: &str,
+    declared_hours_ago: i64,
+    duration_minutes: Option<i64>,
+    ack_minutes: Option<i64>,
+) -> uuid::Uuid {
+    let incident = IncidentService::new(ctx.pool.clone())
+        .create_incident(
+            title.to_string(),
+            severity,
+            service.to_string(),
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .expect("Failed to create incident");
+
+    let declared_at = Utc::now() - Duration::hours(declared_hours_ago);
+    let resolved_at = duration_minutes.map(|m| declared_at + Duration::minutes(m));
+    sqlx::query::query(
+        r#"
+        UPDATE incidents
+        SET declared_at = $2, resolved_at = $3, duration_minutes = $4::INT,
+            status = CASE WHEN $3 IS NULL THEN status ELSE 'resolved' END
+        WHERE id = $1
+        "#,
+    )
+    .bind(incident.id)
+    .bind(declared_at)
+    .bind(resolved_at)
+    .bind(duration_minutes)
+    .execute(&ctx.pool)
+    .await
+    .unwrap();
+
+    if let Some(ack) = ack_minutes {
+        sqlx::query::query(
+            r#"
+            INSERT INTO incident_timeline (incident_id, event_type, message, posted_by, timestamp)
+            VALUES ($1, 'status_update', 'Investigating', 'U024COMMANDER', $2)
+            "#,
+        )
+        .bind(incident.id)
+        .bind(declared_at + Duration::minutes(ack))
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+    }
+    incident.id
+}
+
+#[tokio::test]
+async fn test_metrics_report_aggregates_window() {
+    let ctx = common::TestContext::new().await;
+
+    seed(
+        &ctx,
+        "Card declines",
+        Severity::P1,
+        "Payments",
+        5,
+        Some(180),
+        Some(10),
+    )
+    .await;
+    seed(
+        &ctx,
+        "Refund lag",
+        Severity::P1,
+        "Payments",
+        2,
+        Some(60),
+        Some(20),
+    )
+    .await;
+    seed(&ctx, "Slow queries", Severity::P3, "Search", 48, None, None).await;
+    let quiet = seed(
+        &ctx,
+        "Leaked admin key",
+        Severity::P2,
+        "Payments",
+        12,
+        Some(500),
+        None,
+    )
+    .await;
+    sqlx::query::query("UPDATE incidents SET is_quiet = TRUE WHERE id = $1")
+        .bind(quiet)
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+    // Outside the 30 day window
+    seed(
+        &ctx,
+        "Old outage",
+        Severity::P1,
+        "Search",
+        24 * 40,
+        Some(999),
+        None,
+    )
+    .await;
+
+    let report = MetricsService::new(ctx.pool.clone())
+        .report(30, Utc::now(), AnalyticsScope::OrgWide)
+        .await
+        .unwrap();
+
+    let overall = report.overall.expect("overall row");
+    assert_eq!((overall.declared, overall.resolved), (4, 3));
+    assert!((overall.mean_minutes.unwrap() - (180.0 + 60.0 + 500.0) / 3.0).abs() < 0.01);
+    assert_eq!(overall.median_minutes, Some(180.0));
+    assert!((overall.mean_ack_minutes.unwrap() - 15.0).abs() < 0.01);
+    // The quiet incident ran longest, but its title stays out of the report
+    assert_eq!(overall.longest, Some(("Card declines".to_string(), 180)));
+
+    let severities: Vec<(&str, i64)> = report
+        .by_severity
+        .iter()
+        .map(|r| (r.key.as_str(), r.declared))
+        .collect();
+    assert_eq!(severities, vec![("P1", 2), ("P2", 1), ("P3", 1)]);
+    assert_eq!(report.by_severity[1].longest, None);
+
+    let services: Vec<(&str, i64)> = report
+        .by_service
+        .iter()
+        .map(|r| (r.key.as_str(), r.declared))
+        .collect();
+    assert_eq!(services, vec![("Payments", 3), ("Search", 1)]);
+    assert_eq!(report.by_service[1].mean_minutes, None);
+
+    assert_eq!(report.by_month.iter().map(|r| r.declared).sum::<i64>(), 4);
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_team_members_only_see_their_services() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    mock.add_usergroup("S_PAYMENTS_ONCALL", &["U024ONCALL"]);
+    mock.add_usergroup("S_EXECS", &["U024VP"]);
+    let config = AppConfig {
+        teams: HashMap::from([
+            (
+                "payments".to_string(),
+                TeamConfig {
+                    leads: vec!["U024LEAD".to_string()],
+                    user_groups: vec!["S_PAYMENTS_ONCALL".to_string()],
+                    services: vec!["Payments".to_string()],
+                    ..Default::default()
+                },
+            ),
+            (
+                "search".to_string(),
+                TeamConfig {
+                    members: vec!["U024LEAD".to_string()],
+                    services: vec!["Search".to_string()],
+                    ..Default::default()
+                },
+            ),
+        ]),
+        reporting_user_groups: vec!["S_EXECS".to_string()],
+        ..common::test_config()
+    };
+    let (job_sender, _job_receiver) = mpsc::unbounded_channel();
+    let state = AppState::with_slack_client(ctx.pool.clone(), config, job_sender, mock.clone());
+
+    seed(
+        &ctx,
+        "Card declines",
+        Severity::P1,
+        "Payments",
+        5,
+        Some(60),
+        None,
+    )
+    .await;
+    seed(&ctx, "Slow queries", Severity::P3, "Search", 4, None, None).await;
+
+    let permissions = Permissions::from_state(&state);
+    let oncall = permissions.analytics_scope("U024ONCALL").await;
+    assert_eq!(
+        oncall,
+        AnalyticsScope::Services(vec!["Payments".to_string()])
+    );
+    assert!(oncall.allows("Payments") && !oncall.allows("Search"));
+    assert_eq!(
+        permissions.analytics_scope("U024LEAD").await,
+        AnalyticsScope::Services(vec!["Payments".to_string(), "Search".to_string()])
+    );
+    for user in ["U024VP", "U_ADMIN"] {
+        assert_eq!(
+            permissions.analytics_scope(user).await,
+            AnalyticsScope::OrgWide
+        );
+    }
+    assert_eq!(
+        permissions.analytics_scope("U024OUTSIDER").await,
+        AnalyticsScope::Services(vec![])
+    );
+
+    let report = MetricsService::new(ctx.pool.clone())
+        .report(30, Utc::now(), oncall)
+        .await
+        .unwrap();
+    assert_eq!(report.overall.unwrap().declared, 1);
+    assert_eq!(report.by_service.len(), 1);
+    assert_eq!(report.by_service[0].key, "Payments");
+
+    // Users outside every team are told why instead of getting an empty report
+    handle_metrics(
+        state.clone(),
+        SlashCommandPayload {
+            command: "/incident".to_string(),
+            text: "metrics".to_string(),
+            user_id: "U024OUTSIDER".to_string(),
+            channel_id: "C_ANYWHERE".to_string(),
+            response_url: "https://hooks.slack.test/response".to_string(),
+            trigger_id: "trigger-metrics".to_string(),
+        },
+    )
+    .await
+    .unwrap();
+    let replies: Vec<String> = mock
+        .calls()
+        .into_iter()
+        .filter_map(|call| match call {
+            SlackCall::PostToResponseUrl { blocks, .. } => {
+                Some(serde_json::to_string(&blocks).unwrap())
+            }
+            _ => None,
+        })
+        .collect();
+    assert_eq!(replies.len(), 1);
+    assert!(replies[0].contains("limited to your team's services"));
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_metrics_chart_uploads_a_duration_histogram_per_severity() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let state = common::mock_state(&ctx.pool, mock.clone());
+
+    seed(
+        &ctx,
+        "Card declines",
+        Severity::P1,
+        "Payments",
+        5,
+        Some(10),
+        None,
+    )
+    .await;
+    seed(
+        &ctx,
+        "Refund lag",
+        Severity::P1,
+        "Payments",
+        3,
+        Some(90),
+        None,
+    )
+    .await;
+    seed(
+        &ctx,
+        "Slow search",
+        Severity::P3,
+        "Search",
+        8,
+        Some(30),
+        None,
+    )
+    .await;
+    seed(&ctx, "Still open", Severity::P2, "Search", 1, None, None).await;
+
+    handle_metrics(
+        state,
+        SlashCommandPayload {
+            command: "/incident".to_string(),
+            text: "metrics --chart".to_string(),
+            user_id: "U024VP".to_string(),
+            channel_id: "C_ANYWHERE".to_string(),
+            response_url: "https://hooks.slack.test/response".to_string(),
+            trigger_id: "trigger-metrics-chart".to_string(),
+        },
+    )
+    .await
+    .unwrap();
+
+    let uploads: Vec<String> = mock
+        .calls()
+        .into_iter()
+        .filter_map(|call| match call {
+            SlackCall::UploadFile {
+                filename, content, ..
+            } => {
+                assert_eq!(&content[..8], b"\x89PNG\r\n\x1a\n");
+                Some(filename)
+            }
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        uploads,
+        vec!["time-to-resolve-P1-30d.png", "time-to-resolve-P3-30d.png"]
+    );
+
+    let reply = mock
+        .calls()
+        .into_iter()
+        .find_map(|call| match call {
+            SlackCall::PostToResponseUrl { blocks, .. } => Some(blocks),
+            _ => None,
+        })
+        .unwrap();
+    let images: Vec<&serde_json::Value> = reply.iter().filter(|b| b["type"] == "image").collect();
+    assert_eq!(images.len(), 2);
+    assert_eq!(
+        images[0]["title"]["text"],
+        "🔴 P1 (Critical) time to resolve (2 resolved)"
+    );
+    let counts = serde_json::to_string(&reply).unwrap();
+    assert!(counts.contains("<15m *1* · 15m–1h *0* · 1h–4h *1*"));
+
+    ctx.cleanup().await;
+}
@@ -0,0 +1,152 @@
+use axum::http::StatusCode;
+use incident_bot::commands::search::handle_search;
+use incident_bot::config::{AppConfig, TeamConfig};
+use incident_bot::db::models::Severity;
+use incident_bot::error::IncidentError;
+use incident_bot::services::incident::IncidentService;
+use incident_bot::slack::events::SlashCommandPayload;
+use incident_bot::slack::mock::{MockSlackClient, SlackCall};
+use incident_bot::AppState;
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+mod common;
+
+/// A prefix of its own, so other test binaries' incidents don't share its
+/// sequence.
+fn numbered_config() -> AppConfig {
+    AppConfig {
+        services: vec!["Test Service".to_string(), "Numbered Service".to_string()],
+        teams: HashMap::from([(
+            "numbering".to_string(),
+            TeamConfig {
+                services: vec!["Numbered Service".to_string()],
+                incident_prefix: Some("NUMT".to_string()),
+                ..TeamConfig::default()
+            },
+        )]),
+        ..common::test_config()
+    }
+}
+
+#[tokio::test]
+async fn test_incidents_are_numbered_per_prefix_and_found_by_number() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let (job_sender, _job_receiver) = tokio::sync::mpsc::unbounded_channel();
+    let state = AppState::with_slack_client(
+        ctx.pool.clone(),
+        numbered_config(),
+        job_sender,
+        mock.clone(),
+    );
+
+    // The owning team's prefix, counted on its own
+    let (status, first) = common::send(
+        &state,
+        "POST",
+        "/api/v1/incidents",
+        Some(json!({
+            "title": "Ledger replication lag",
+            "severity": "P3",
+            "affected_service": "Numbered Service",
+            "commander_id": "U024COMMANDER",
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(first["number_prefix"], "NUMT");
+    let number = first["number"].as_i64().unwrap() as i32;
+
+    let incident_service = IncidentService::new(ctx.pool.clone()).with_config(state.config.clone());
+    let second = incident_service
+        .create_incident(
+            "Ledger replication lag again".to_string(),
+            Severity::P3,
+            "Numbered Service".to_string(),
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(second.reference(), format!("NUMT-{}", number + 1));
+    let other = incident_service
+        .create_incident(
+            "VPN flapping".to_string(),
+            Severity::P3,
+            "Test Service".to_string(),
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(other.number_prefix, "INC");
+
+    // Any spelling of the number, or the ID
+    for reference in [
+        second.reference(),
+        format!("numt-{}", second.number),
+        format!("#NUMT-{}", second.number),
+        second.id.to_string(),
+    ] {
+        assert_eq!(
+            incident_service.find(&reference).await.unwrap().id,
+            second.id
+        );
+    }
+    assert!(matches!(
+        incident_service.find("NUMT-999999").await,
+        Err(IncidentError::NotFound)
+    ));
+    assert!(matches!(
+        incident_service.find("ledger").await,
+        Err(IncidentError::ValidationError { .. })
+    ));
+
+    let (status, fetched) = common::send(
+        &state,
+        "GET",
+        &format!("/api/v1/incidents/numt-{}", number),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(fetched["title"], "Ledger replication lag");
+    let (status, resolved) = common::send(
+        &state,
+        "POST",
+        &format!("/api/v1/incidents/NUMT-{}/resolve", number),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(resolved["status"], "Resolved");
+
+    handle_search(
+        state,
+        SlashCommandPayload {
+            command: "/incident".to_string(),
+            text: format!("search NUMT-{}", second.number),
+            user_id: "U024COMMANDER".to_string(),
+            channel_id: "C_ANYWHERE".to_string(),
+            response_url: "https://hooks.slack.test/response".to_string(),
+            trigger_id: "trigger".to_string(),
+        },
+    )
+    .await
+    .unwrap();
+    let results = mock
+        .calls()
+        .into_iter()
+        .find_map(|call| match call {
+            SlackCall::PostToResponseUrl { blocks, .. } => Some(blocks),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(results.len(), 2);
+    assert!(results[1]["text"]["text"]
+        .as_str()
+        .unwrap()
+        .contains("Ledger replication lag again\nNUMT-"));
+
+    ctx.cleanup().await;
+}
@@ -0,0 +1,202 @@
+use axum::http::StatusCode;
+use incident_bot::db::models::{IncidentStatus, TimelineEventType};
+use incident_bot::db::queries::incidents::get_latest_incident_by_channel;
+use incident_bot::services::incident::IncidentService;
+use incident_bot::services::timeline::TimelineService;
+use incident_bot::slack::client::HistoryMessage;
+use incident_bot::slack::mock::MockSlackClient;
+use serde_json::json;
+use std::sync::Arc;
+use uuid::Uuid;
+
+mod common;
+
+const CHANNEL: &str = "C_PRE_BOT_INCIDENT";
+
+fn message(ts: &str, user: Option<&str>, text: &str, subtype: Option<&str>) -> HistoryMessage {
+    HistoryMessage {
+        ts: ts.to_string(),
+        user: user.map(str::to_string),
+        text: text.to_string(),
+        subtype: subtype.map(str::to_string),
+        thread_ts: None,
+    }
+}
+
+#[tokio::test]
+async fn test_reconstructs_past_incident_from_channel_history() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    // Newest first, as Slack returns them
+    mock.add_history(
+        CHANNEL,
+        vec![
+            message(
+                "1700009000.000100",
+                Some("U024ALICE"),
+                "Writing the postmortem",
+                None,
+            ),
+            message(
+                "1700005400.000000",
+                Some("U024ALICE"),
+                "All clear, closing this out",
+                None,
+            ),
+            message(
+                "1700003000.000300",
+                None,
+                "Deploy bot: rollback finished",
+                Some("bot_message"),
+            ),
+            message(
+                "1700002000.000200",
+                Some("U024BOB"),
+                "Rolling back 4.2.1",
+                None,
+            ),
+            message(
+                "1700001000.000100",
+                Some("U024BOB"),
+                "<@U024BOB> has joined the channel",
+                Some("channel_join"),
+            ),
+            message(
+                "1700000000.000000",
+                Some("U024ALICE"),
+                "Checkout is failing for everyone",
+                None,
+            ),
+            message(
+                "1699990000.000000",
+                Some("U024ALICE"),
+                "Anyone else seeing slow builds?",
+                None,
+            ),
+        ],
+    );
+    let state = common::mock_state(&ctx.pool, mock);
+
+    // Pick boundaries from the history
+    let (status, history) = common::send(
+        &state,
+        "GET",
+        &format!(
+            "/api/v1/admin/channels/{}/history?latest=1700005400.000000",
+            CHANNEL
+        ),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(history.as_array().unwrap().len(), 6);
+    assert_eq!(history[0]["ts"], "1699990000.000000");
+    assert_eq!(history[1]["at"], "2023-11-14T22:13:20Z");
+
+    let request = json!({
+        "channel_id": CHANNEL,
+        "title": "Checkout outage",
+        "severity": "P1",
+        "affected_service": "Test Service",
+        "commander_id": "U024ALICE",
+        "declared_ts": "1700000000.000000",
+        "resolved_ts": "1700005400.000000"
+    });
+
+    // A dry run previews the timeline without writing anything
+    let (status, preview) = common::send(
+        &state,
+        "POST",
+        "/api/v1/admin/incidents/reconstruct?dry_run=true",
+        Some(request.clone()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(preview["incident"].is_null());
+    assert_eq!(preview["duration_minutes"], 90);
+    assert_eq!(preview["skipped_messages"], 2);
+    let events = preview["events"].as_array().unwrap();
+    assert_eq!(events.len(), 5);
+    assert_eq!(events[0]["event_type"], "Declared");
+    assert_eq!(events[2]["message"], "Rolling back 4.2.1");
+    assert_eq!(events[2]["posted_by"], "U024BOB");
+    assert_eq!(
+        events[4]["message"],
+        "Incident resolved (duration: 1h 30min)"
+    );
+    assert!(get_latest_incident_by_channel(&ctx.pool, CHANNEL)
+        .await
+        .is_err());
+
+    let (status, created) = common::send(
+        &state,
+        "POST",
+        "/api/v1/admin/incidents/reconstruct",
+        Some(request.clone()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let incident_id: Uuid = serde_json::from_value(created["incident"]["id"].clone()).unwrap();
+    let incident = IncidentService::new(ctx.pool.clone())
+        .get_by_id(incident_id)
+        .await
+        .unwrap();
+    assert_eq!(incident.status, IncidentStatus::Resolved);
+    assert_eq!(incident.declared_at.timestamp(), 1_700_000_000);
+    assert_eq!(incident.resolved_at.unwrap().timestamp(), 1_700_005_400);
+    assert_eq!(incident.duration_minutes, Some(90));
+    assert_eq!(incident.slack_channel_id.as_deref(), Some(CHANNEL));
+    assert!(incident.channel_archived_at.is_some());
+
+    let timeline = TimelineService::new(ctx.pool.clone())
+        .get_timeline(incident_id)
+        .await
+        .unwrap();
+    assert_eq!(timeline.len(), 5);
+    assert_eq!(timeline[0].event_type, TimelineEventType::Declared);
+    assert_eq!(timeline[1].message, "Checkout is failing for everyone");
+    assert_eq!(timeline[2].timestamp.timestamp(), 1_700_002_000);
+    assert_eq!(timeline[4].event_type, TimelineEventType::Resolved);
+
+    // A channel is only reconstructed once
+    let (status, _) = common::send(
+        &state,
+        "POST",
+        "/api/v1/admin/incidents/reconstruct",
+        Some(request),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_reconstruction_rejects_bad_boundaries() {
+    let ctx = common::TestContext::new().await;
+    let state = common::mock_state(&ctx.pool, Arc::new(MockSlackClient::new()));
+
+    for (declared_ts, resolved_ts) in [
+        ("1700005400.000000", "1700000000.000000"),
+        ("yesterday", "1700000000.000000"),
+    ] {
+        let (status, body) = common::send(
+            &state,
+            "POST",
+            "/api/v1/admin/incidents/reconstruct?dry_run=true",
+            Some(json!({
+                "channel_id": "C_BAD_BOUNDARIES",
+                "title": "Checkout outage",
+                "severity": "P2",
+                "affected_service": "Test Service",
+                "commander_id": "U024ALICE",
+                "declared_ts": declared_ts,
+                "resolved_ts": resolved_ts
+            })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{}", body);
+    }
+
+    ctx.cleanup().await;
+}
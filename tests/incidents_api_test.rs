@@ -0,0 +1,244 @@
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use incident_bot::slack::mock::MockSlackClient;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tower::ServiceExt;
+
+mod common;
+
+fn create_body(title: &str, severity: &str) -> Value {
+    json!({
+        "title": title,
+        "severity": severity,
+        "affected_service": "Test Service",
+        "commander_id": "U024COMMANDER",
+    })
+}
+
+#[tokio::test]
+async fn test_create_get_and_list_incidents() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let state = common::mock_state(&ctx.pool, mock.clone());
+
+    let (status, created) = common::send(
+        &state,
+        "POST",
+        "/api/v1/incidents",
+        Some(create_body("API declared P1", "P1")),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(created["status"], "Declared");
+    assert_eq!(created["severity"], "P1");
+    // No incident channel, but P1 still broadcasts and DMs
+    assert_eq!(mock.posted_channels(), vec!["C_GENERAL"]);
+    assert_eq!(mock.dm_recipients().len(), 2);
+
+    common::send(
+        &state,
+        "POST",
+        "/api/v1/incidents",
+        Some(create_body("API declared P3", "P3")),
+    )
+    .await;
+
+    let id = created["id"].as_str().unwrap();
+    let (status, fetched) =
+        common::send(&state, "GET", &format!("/api/v1/incidents/{}", id), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(fetched["title"], "API declared P1");
+
+    let (status, listed) = common::send(&state, "GET", "/api/v1/incidents?severity=p3", None).await;
+    assert_eq!(status, StatusCode::OK);
+    let listed = listed.as_array().unwrap();
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0]["title"], "API declared P3");
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_create_rejects_unknown_service_and_missing_token() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let state = common::mock_state(&ctx.pool, mock.clone());
+
+    let mut body = create_body("Bad service", "P2");
+    body["affected_service"] = json!("not-configured");
+    let (status, error) = common::send(&state, "POST", "/api/v1/incidents", Some(body)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(error["error"]
+        .as_str()
+        .unwrap()
+        .contains("affected_service"));
+
+    let response = common::api_router(&state)
+        .oneshot(
+            Request::get("/api/v1/incidents")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let (status, _) = common::send(
+        &state,
+        "GET",
+        &format!("/api/v1/incidents/{}", uuid::Uuid::new_v4()),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_update_status_and_resolve() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let state = common::mock_state(&ctx.pool, mock.clone());
+
+    let (_, created) = common::send(
+        &state,
+        "POST",
+        "/api/v1/incidents",
+        Some(create_body("Status flow", "P3")),
+    )
+    .await;
+    let id = created["id"].as_str().unwrap().to_string();
+
+    let (status, updated) = common::send(
+        &state,
+        "POST",
+        &format!("/api/v1/incidents/{}/status", id),
+        Some(json!({ "status": "Monitoring", "actor_id": "U024SRE" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(updated["status"], "Monitoring");
+
+    // Monitoring -> Investigating is not a valid transition
+    let (status, _) = common::send(
+        &state,
+        "POST",
+        &format!("/api/v1/incidents/{}/status", id),
+        Some(json!({ "status": "Investigating" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, resolved) = common::send(
+        &state,
+        "POST",
+        &format!("/api/v1/incidents/{}/resolve", id),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(resolved["status"], "Resolved");
+    assert!(resolved["duration_minutes"].is_number());
+
+    // Resolving again is idempotent
+    let (status, again) = common::send(
+        &state,
+        "POST",
+        &format!("/api/v1/incidents/{}/resolve", id),
+        Some(json!({ "actor_id": "U024SRE" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(again["resolved_at"], resolved["resolved_at"]);
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_roles_report_unfilled_required_roles() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let state = common::mock_state(&ctx.pool, mock.clone());
+
+    let (_, p1) = common::send(
+        &state,
+        "POST",
+        "/api/v1/incidents",
+        Some(create_body("Roles P1", "P1")),
+    )
+    .await;
+    let (status, roles) = common::send(
+        &state,
+        "GET",
+        &format!("/api/v1/incidents/{}/roles", p1["id"].as_str().unwrap()),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(roles["unfilled"], json!(["comms_lead", "scribe"]));
+    assert_eq!(roles["filled"], json!([]));
+
+    // No roles configured for P4
+    let (_, p4) = common::send(
+        &state,
+        "POST",
+        "/api/v1/incidents",
+        Some(create_body("Roles P4", "P4")),
+    )
+    .await;
+    let (_, roles) = common::send(
+        &state,
+        "GET",
+        &format!("/api/v1/incidents/{}/roles", p4["id"].as_str().unwrap()),
+        None,
+    )
+    .await;
+    assert_eq!(roles["required"], json!([]));
+
+    let (status, _) = common::send(
+        &state,
+        "GET",
+        &format!("/api/v1/incidents/{}/roles", uuid::Uuid::new_v4()),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_api_resolve_requires_postmortem_by_severity() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let state = common::mock_state(&ctx.pool, mock.clone());
+
+    let mut ids = Vec::new();
+    for severity in ["P2", "P4"] {
+        let (_, created) = common::send(
+            &state,
+            "POST",
+            "/api/v1/incidents",
+            Some(create_body("Postmortem policy", severity)),
+        )
+        .await;
+        let id = created["id"].as_str().unwrap().to_string();
+        let (status, _) = common::send(
+            &state,
+            "POST",
+            &format!("/api/v1/incidents/{}/resolve", id),
+            None,
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        ids.push(uuid::Uuid::parse_str(&id).unwrap());
+    }
+
+    let due = |id| incident_bot::db::queries::postmortems::postmortem_due_date(&ctx.pool, id);
+    assert!(due(ids[0]).await.unwrap().is_some());
+    assert!(due(ids[1]).await.unwrap().is_none());
+
+    ctx.cleanup().await;
+}
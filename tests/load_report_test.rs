@@ -0,0 +1,130 @@
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use axum::Router;
+use incident_bot::db::models::Severity;
+use incident_bot::db::queries::roles::claim_role;
+use incident_bot::services::incident::IncidentService;
+use incident_bot::slack::mock::MockSlackClient;
+use serde_json::Value;
+use std::sync::Arc;
+use tower::ServiceExt;
+
+mod common;
+
+async fn get(ctx: &common::TestContext, uri: &str) -> (StatusCode, Value) {
+    let state = common::mock_state(&ctx.pool, Arc::new(MockSlackClient::new()));
+    let router: Router = Router::new()
+        .nest("/api/v1", incident_bot::api::router(state.clone()))
+        .with_state(state);
+    let request = Request::builder()
+        .uri(uri)
+        .header("Authorization", "Bearer test-api-token")
+        .body(Body::empty())
+        .unwrap();
+    let response = router.oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (
+        status,
+        serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+    )
+}
+
+#[tokio::test]
+async fn test_load_report_counts_command_roles_nights_and_weekends() {
+    let ctx = common::TestContext::new().await;
+    let incident_service = IncidentService::new(ctx.pool.clone());
+    let incident = incident_service
+        .create_incident(
+            "Weekend database failover".to_string(),
+            Severity::P1,
+            "Test Service".to_string(),
+            "U_LOAD_ALICE".to_string(),
+        )
+        .await
+        .unwrap();
+    claim_role(&ctx.pool, incident.id, "scribe", "U_LOAD_BOB")
+        .await
+        .unwrap();
+    incident_service
+        .reassign_commander(
+            incident.id,
+            "U_LOAD_BOB".to_string(),
+            "U_LOAD_ALICE".to_string(),
+        )
+        .await
+        .unwrap();
+    incident_service
+        .resolve_incident(incident.id, "U_LOAD_BOB".to_string())
+        .await
+        .unwrap();
+
+    // Replay the history on a fixed timeline (2024-11-15 is a Friday):
+    // Alice commands 20:00-00:00, Bob is scribe from 21:00 and commands
+    // from midnight until resolution at 02:00 on Saturday.
+    sqlx::query::query(
+        r#"
+        UPDATE incident_changes
+        SET changed_at = CASE WHEN row_data->>'commander_id' = 'U_LOAD_ALICE'
+            THEN '2024-11-15T20:00:00Z'::timestamptz
+            ELSE '2024-11-16T00:00:00Z'::timestamptz END
+        WHERE incident_id = $1 AND table_name = 'incidents'
+        "#,
+    )
+    .bind(incident.id)
+    .execute(&ctx.pool)
+    .await
+    .unwrap();
+    sqlx::query::query(
+        "UPDATE incident_roles SET claimed_at = '2024-11-15T21:00:00Z' WHERE incident_id = $1",
+    )
+    .bind(incident.id)
+    .execute(&ctx.pool)
+    .await
+    .unwrap();
+    sqlx::query::query("UPDATE incidents SET resolved_at = '2024-11-16T02:00:00Z' WHERE id = $1")
+        .bind(incident.id)
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+
+    let (status, report) = get(
+        &ctx,
+        "/api/v1/reports/load?since=2024-11-01T00:00:00Z&until=2024-12-01T00:00:00Z",
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(report["utc_offset_hours"], 0);
+    let people = report["people"].as_array().unwrap();
+    assert_eq!(people.len(), 2);
+    // Bob: 2h command + 5h scribe; 21:00-02:00 once: 2h night, 2h weekend
+    assert_eq!(people[0]["user_id"], "U_LOAD_BOB");
+    assert_eq!(people[0]["incidents"], 1);
+    assert_eq!(people[0]["command_hours"], 2.0);
+    assert_eq!(people[0]["role_hours"], 5.0);
+    assert_eq!(people[0]["night_hours"], 2.0);
+    assert_eq!(people[0]["weekend_hours"], 2.0);
+    // Alice: 20:00-00:00 Friday, half of it after 22:00
+    assert_eq!(people[1]["user_id"], "U_LOAD_ALICE");
+    assert_eq!(people[1]["command_hours"], 4.0);
+    assert_eq!(people[1]["night_hours"], 2.0);
+    assert_eq!(people[1]["weekend_hours"], 0.0);
+
+    let (_, alice_only) = get(
+        &ctx,
+        "/api/v1/reports/load?user_id=U_LOAD_ALICE&since=2024-11-01T00:00:00Z&until=2024-12-01T00:00:00Z",
+    )
+    .await;
+    assert_eq!(alice_only["people"].as_array().unwrap().len(), 1);
+
+    let (status, _) = get(
+        &ctx,
+        "/api/v1/reports/load?since=2024-12-01T00:00:00Z&until=2024-11-01T00:00:00Z",
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    ctx.cleanup().await;
+}
@@ -0,0 +1,63 @@
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use axum::routing::get;
+use axum::Router;
+use incident_bot::db::models::Severity;
+use incident_bot::services::incident::IncidentService;
+use incident_bot::slack::mock::MockSlackClient;
+use std::sync::Arc;
+use tower::ServiceExt;
+
+mod common;
+
+#[tokio::test]
+async fn test_metrics_endpoint_reports_incident_counters_and_open_gauge() {
+    let ctx = common::TestContext::new().await;
+    let state = common::mock_state(&ctx.pool, Arc::new(MockSlackClient::new()));
+
+    let incident_service = IncidentService::new(ctx.pool.clone());
+    let resolved = incident_service
+        .create_incident(
+            "Metrics resolved".to_string(),
+            Severity::P2,
+            "Test Service".to_string(),
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .expect("Failed to create incident");
+    incident_service
+        .resolve_incident(resolved.id, "U024COMMANDER".to_string())
+        .await
+        .expect("Failed to resolve incident");
+    incident_service
+        .create_incident(
+            "Metrics open".to_string(),
+            Severity::P1,
+            "Test Service".to_string(),
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .expect("Failed to create incident");
+
+    let router = Router::new()
+        .route("/metrics", get(incident_bot::metrics::handle_metrics))
+        .with_state(state);
+    let response = router
+        .oneshot(Request::get("/metrics").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body = String::from_utf8(bytes.to_vec()).unwrap();
+
+    assert!(body.contains("incident_bot_open_incidents{severity=\"P1\"} 1"));
+    assert!(!body.contains("incident_bot_open_incidents{severity=\"P2\"}"));
+    assert!(body.contains("incident_bot_incidents_declared_total{severity=\"P1\"}"));
+    assert!(body.contains("incident_bot_incidents_resolved_total{severity=\"P2\"}"));
+    assert!(body.contains("incident_bot_incident_duration_minutes_bucket"));
+
+    ctx.cleanup().await;
+}
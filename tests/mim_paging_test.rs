@@ -0,0 +1,161 @@
+use incident_bot::commands::mim::{handle_mim_accept, page_major_incident_manager};
+use incident_bot::commands::severity::handle_severity;
+use incident_bot::config::MimRole;
+use incident_bot::db::models::{Incident, Severity};
+use incident_bot::services::incident::IncidentService;
+use incident_bot::slack::blocks::MIM_ACCEPT_ACTION;
+use incident_bot::slack::events::SlashCommandPayload;
+use incident_bot::slack::mock::{MockSlackClient, SlackCall};
+use incident_bot::{AppConfig, AppState};
+use serde_json::Value;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+mod common;
+
+fn mim_state(ctx: &common::TestContext, mock: Arc<MockSlackClient>, role: MimRole) -> AppState {
+    let config = AppConfig {
+        mim_users: vec!["U024MIM".to_string()],
+        mim_role: role,
+        ..common::test_config()
+    };
+    let (job_sender, _job_receiver) = mpsc::unbounded_channel();
+    AppState::with_slack_client(ctx.pool.clone(), config, job_sender, mock)
+}
+
+/// Blocks of every page DMed to `user`.
+fn pages_to(mock: &MockSlackClient, user: &str) -> Vec<Vec<Value>> {
+    mock.calls()
+        .into_iter()
+        .filter_map(|call| match call {
+            SlackCall::SendDm { user_id, blocks }
+                if user_id == user
+                    && blocks
+                        .iter()
+                        .any(|b| b["elements"][0]["action_id"] == MIM_ACCEPT_ACTION) =>
+            {
+                Some(blocks)
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn last_reply(mock: &MockSlackClient) -> String {
+    mock.calls()
+        .iter()
+        .rev()
+        .find_map(|call| match call {
+            SlackCall::PostToResponseUrl { blocks, .. } => {
+                blocks[0]["text"]["text"].as_str().map(str::to_string)
+            }
+            _ => None,
+        })
+        .expect("Expected a response_url reply")
+}
+
+async fn accept(state: &AppState, user_id: &str, incident: &Incident) {
+    handle_mim_accept(
+        state.clone(),
+        user_id.to_string(),
+        &incident.id.to_string(),
+        Some("https://hooks.slack.test/response".to_string()),
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn test_escalation_to_p1_pages_mim_who_accepts_as_advisor() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let state = mim_state(&ctx, mock.clone(), MimRole::Advisor);
+    let incident =
+        common::incident_in_channel(&ctx, Severity::P2, "Test Service", "C024MIMADV").await;
+
+    // A P2 pages nobody
+    page_major_incident_manager(&state, &incident).await;
+    assert!(pages_to(&mock, "U024MIM").is_empty());
+
+    handle_severity(
+        state.clone(),
+        SlashCommandPayload {
+            command: "/incident".to_string(),
+            text: "severity P1".to_string(),
+            user_id: "U024COMMANDER".to_string(),
+            channel_id: "C024MIMADV".to_string(),
+            response_url: "https://hooks.slack.test/response".to_string(),
+            trigger_id: "trigger-123".to_string(),
+        },
+    )
+    .await
+    .unwrap();
+    let pages = pages_to(&mock, "U024MIM");
+    assert_eq!(pages.len(), 1);
+    assert!(pages[0][0]["text"]["text"]
+        .as_str()
+        .unwrap()
+        .contains("via MIM_USERS"));
+
+    // Paging is once per incident
+    let incident = IncidentService::new(ctx.pool.clone())
+        .get_by_id(incident.id)
+        .await
+        .unwrap();
+    page_major_incident_manager(&state, &incident).await;
+    assert_eq!(pages_to(&mock, "U024MIM").len(), 1);
+
+    accept(&state, "U024BYSTANDER", &incident).await;
+    assert!(last_reply(&mock).contains("only they can accept it"));
+
+    accept(&state, "U024MIM", &incident).await;
+    assert!(last_reply(&mock).contains("You are the major incident manager"));
+    let holder: String = sqlx::query_scalar::query_scalar(
+        "SELECT user_id FROM incident_roles WHERE incident_id = $1 AND role = 'major_incident_manager'",
+    )
+    .bind(incident.id)
+    .fetch_one(&ctx.pool)
+    .await
+    .unwrap();
+    assert_eq!(holder, "U024MIM");
+    assert!(mock.calls().iter().any(|call| matches!(
+        call,
+        SlackCall::InviteUsers { channel_id, user_ids }
+            if channel_id == "C024MIMADV" && user_ids == &["U024MIM".to_string()]
+    )));
+
+    accept(&state, "U024MIM", &incident).await;
+    assert!(last_reply(&mock).contains("already accepted this page as advisor"));
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_mim_accepting_as_commander_takes_command() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let state = mim_state(&ctx, mock.clone(), MimRole::Commander);
+    let incident =
+        common::incident_in_channel(&ctx, Severity::P1, "Test Service", "C024MIMCMD").await;
+
+    page_major_incident_manager(&state, &incident).await;
+    assert_eq!(pages_to(&mock, "U024MIM").len(), 1);
+
+    accept(&state, "U024MIM", &incident).await;
+    assert!(last_reply(&mock).contains("You are now the incident commander"));
+    let incident = IncidentService::new(ctx.pool.clone())
+        .get_by_id(incident.id)
+        .await
+        .unwrap();
+    assert_eq!(incident.commander_id, "U024MIM");
+    let accepted_as: Option<String> = sqlx::query_scalar::query_scalar(
+        "SELECT accepted_as FROM mim_pages WHERE incident_id = $1",
+    )
+    .bind(incident.id)
+    .fetch_one(&ctx.pool)
+    .await
+    .unwrap();
+    assert_eq!(accepted_as.as_deref(), Some("commander"));
+
+    ctx.cleanup().await;
+}
@@ -0,0 +1,172 @@
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use axum::Router;
+use incident_bot::commands::notifications::handle_notifications;
+use incident_bot::db::models::Severity;
+use incident_bot::services::incident::IncidentService;
+use incident_bot::services::notification::NotificationService;
+use incident_bot::slack::events::SlashCommandPayload;
+use incident_bot::slack::mock::{MockSlackClient, SlackCall};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tower::ServiceExt;
+
+mod common;
+
+fn notifications(text: &str, user_id: &str) -> SlashCommandPayload {
+    SlashCommandPayload {
+        command: "/incident".to_string(),
+        text: text.to_string(),
+        user_id: user_id.to_string(),
+        channel_id: "C024OPS".to_string(),
+        response_url: "https://hooks.slack.test/response".to_string(),
+        trigger_id: "trigger-123".to_string(),
+    }
+}
+
+/// Text of the last response_url reply.
+fn last_reply(mock: &MockSlackClient) -> String {
+    mock.calls()
+        .iter()
+        .rev()
+        .find_map(|call| match call {
+            SlackCall::PostToResponseUrl { blocks, .. } => {
+                blocks[0]["text"]["text"].as_str().map(str::to_string)
+            }
+            _ => None,
+        })
+        .expect("Expected a response_url reply")
+}
+
+async fn api(router: &Router, method: &str, uri: &str) -> (StatusCode, Value) {
+    let response = router
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method(method)
+                .uri(uri)
+                .header("Authorization", "Bearer test-api-token")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (
+        status,
+        serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+    )
+}
+
+#[tokio::test]
+async fn test_failed_notifications_can_be_retried_or_skipped() {
+    let ctx = common::TestContext::new().await;
+    let incident_service = IncidentService::new(ctx.pool.clone());
+    let incident = incident_service
+        .create_incident(
+            "Slack outage fallout".to_string(),
+            Severity::P2,
+            "Test Service".to_string(),
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .unwrap();
+    incident_service
+        .update_channel_id(incident.id, "C024RETRY".to_string())
+        .await
+        .unwrap();
+    let incident = incident_service.get_by_id(incident.id).await.unwrap();
+
+    // Slack is down: both status updates fail and are kept with their message
+    let outage = Arc::new(MockSlackClient::new());
+    outage.fail_method("chat.postMessage", "service_unavailable");
+    let service =
+        NotificationService::new(ctx.pool.clone(), outage, Arc::new(common::test_config()));
+    for text in ["Rolling back", "Rollback complete"] {
+        let blocks = vec![json!({ "type": "section", "text": { "type": "mrkdwn", "text": text } })];
+        assert!(service
+            .notify_status_update(&incident, blocks)
+            .await
+            .is_err());
+    }
+
+    // Slack is back
+    let mock = Arc::new(MockSlackClient::new());
+    let state = common::mock_state(&ctx.pool, mock.clone());
+    let router = Router::new()
+        .nest("/api/v1", incident_bot::api::router(state.clone()))
+        .with_state(state.clone());
+
+    let (status, pending) = api(&router, "GET", "/api/v1/admin/notifications/pending-failed").await;
+    assert_eq!(status, StatusCode::OK);
+    let pending = pending.as_array().unwrap().clone();
+    assert_eq!(pending.len(), 2);
+    assert_eq!(pending[0]["status"], "Failed");
+    assert_eq!(pending[0]["recipient"], "C024RETRY");
+    assert!(pending[0].get("blocks").is_none());
+    let (newest, oldest) = (
+        pending[0]["id"].as_str().unwrap().to_string(),
+        pending[1]["id"].as_str().unwrap().to_string(),
+    );
+
+    // Retry resends the stored message
+    let (status, retried) = api(
+        &router,
+        "POST",
+        &format!("/api/v1/admin/notifications/{}/retry", oldest),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(retried["status"], "Sent");
+    assert_eq!(retried["attempts"], 2);
+    assert_eq!(retried["resolved_by"], "api");
+    assert!(mock.calls().iter().any(|call| matches!(
+        call,
+        SlackCall::PostMessage { channel_id, blocks }
+            if channel_id == "C024RETRY" && blocks[0]["text"]["text"] == "Rolling back"
+    )));
+    let (status, _) = api(
+        &router,
+        "POST",
+        &format!("/api/v1/admin/notifications/{}/retry", oldest),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // Only admins can use the command
+    handle_notifications(
+        state.clone(),
+        notifications("notifications", "U024BYSTANDER"),
+    )
+    .await
+    .unwrap();
+    assert!(last_reply(&mock).contains("Only bot admins"));
+
+    handle_notifications(state.clone(), notifications("notifications", "U_ADMIN"))
+        .await
+        .unwrap();
+    let listed = last_reply(&mock);
+    assert!(listed.contains(&newest[..8]));
+    assert!(listed.contains("<#C024RETRY> failed"));
+    assert!(!listed.contains(&oldest[..8]));
+
+    handle_notifications(
+        state.clone(),
+        notifications(&format!("notifications skip {}", &newest[..8]), "U_ADMIN"),
+    )
+    .await
+    .unwrap();
+    assert!(last_reply(&mock).contains("Skipped"));
+
+    handle_notifications(state.clone(), notifications("notifications", "U_ADMIN"))
+        .await
+        .unwrap();
+    assert!(last_reply(&mock).contains("No failed, throttled or deferred notifications"));
+    let (_, pending) = api(&router, "GET", "/api/v1/admin/notifications/pending-failed").await;
+    assert_eq!(pending, json!([]));
+
+    ctx.cleanup().await;
+}
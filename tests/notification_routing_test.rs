@@ -0,0 +1,439 @@
+use incident_bot::commands::routing::handle_routing;
+use incident_bot::config::NotificationRule;
+use incident_bot::db::models::{NotificationRecord, Severity, SeverityLevel};
+use incident_bot::services::incident::IncidentService;
+use incident_bot::services::notification::NotificationService;
+use incident_bot::slack::events::SlashCommandPayload;
+use incident_bot::slack::mock::{MockSlackClient, SlackCall};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+mod common;
+
+async fn create_incident_in_channel(
+    ctx: &common::TestContext,
+    severity: Severity,
+    channel_id: &str,
+) -> incident_bot::db::models::Incident {
+    let incident_service = IncidentService::new(ctx.pool.clone());
+    let incident = incident_service
+        .create_incident(
+            "Routing test".to_string(),
+            severity,
+            "Test Service".to_string(),
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .expect("Failed to create incident");
+    incident_service
+        .update_channel_id(incident.id, channel_id.to_string())
+        .await
+        .expect("Failed to set channel id");
+    incident_service
+        .get_by_id(incident.id)
+        .await
+        .expect("Failed to reload incident")
+}
+
+async fn notification_records(
+    ctx: &common::TestContext,
+    incident_id: uuid::Uuid,
+) -> Vec<NotificationRecord> {
+    sqlx::query_as::query_as::<_, NotificationRecord>(
+        "SELECT * FROM incident_notifications WHERE incident_id = $1 ORDER BY sent_at, created_at",
+    )
+    .bind(incident_id)
+    .fetch_all(&ctx.pool)
+    .await
+    .expect("Failed to load notifications")
+}
+
+#[tokio::test]
+async fn test_p1_declaration_routes_to_channels_and_execs() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let service = NotificationService::new(
+        ctx.pool.clone(),
+        mock.clone(),
+        Arc::new(common::test_config()),
+    );
+
+    let incident = create_incident_in_channel(&ctx, Severity::P1, "C_INC_P1").await;
+    service
+        .notify_incident_declared(&incident, vec![])
+        .await
+        .expect("Failed to notify");
+
+    assert_eq!(mock.posted_channels(), vec!["C_INC_P1", "C_GENERAL"]);
+    assert_eq!(mock.dm_recipients(), vec!["U_EXEC1", "U_EXEC2"]);
+    assert_eq!(notification_records(&ctx, incident.id).await.len(), 4);
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_p3_declaration_stays_in_incident_channel() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let service = NotificationService::new(
+        ctx.pool.clone(),
+        mock.clone(),
+        Arc::new(common::test_config()),
+    );
+
+    let incident = create_incident_in_channel(&ctx, Severity::P3, "C_INC_P3").await;
+    service
+        .notify_incident_declared(&incident, vec![])
+        .await
+        .expect("Failed to notify");
+
+    assert_eq!(mock.posted_channels(), vec!["C_INC_P3"]);
+    assert!(mock.dm_recipients().is_empty());
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_repeat_p1_dms_are_throttled() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let service = NotificationService::new(
+        ctx.pool.clone(),
+        mock.clone(),
+        Arc::new(common::test_config()),
+    );
+
+    let incident = create_incident_in_channel(&ctx, Severity::P1, "C_INC_THROTTLE").await;
+    service
+        .notify_incident_declared(&incident, vec![])
+        .await
+        .expect("Failed to notify");
+    service
+        .notify_resolution(&incident, vec![])
+        .await
+        .expect("Failed to notify");
+
+    // Second round of DMs falls inside the 5-minute throttle window
+    assert_eq!(mock.dm_recipients().len(), 2);
+    let throttled = notification_records(&ctx, incident.id)
+        .await
+        .into_iter()
+        .filter(|r| r.status == incident_bot::db::models::NotificationStatus::Throttled)
+        .count();
+    assert_eq!(throttled, 2);
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_failed_channel_post_is_logged_and_returned() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    mock.fail_method("chat.postMessage", "channel_not_found");
+    let service = NotificationService::new(
+        ctx.pool.clone(),
+        mock.clone(),
+        Arc::new(common::test_config()),
+    );
+
+    let incident = create_incident_in_channel(&ctx, Severity::P2, "C_INC_FAIL").await;
+    let result = service.notify_status_update(&incident, vec![]).await;
+
+    assert!(matches!(
+        result,
+        Err(incident_bot::error::IncidentError::SlackAPIError { .. })
+    ));
+    let records = notification_records(&ctx, incident.id).await;
+    assert_eq!(records.len(), 1);
+    assert_eq!(
+        records[0].status,
+        incident_bot::db::models::NotificationStatus::Failed
+    );
+    assert_eq!(
+        records[0].error_message.as_deref(),
+        Some("Slack API error: API call failed: chat.postMessage (code: channel_not_found)")
+    );
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_notification_rules_override_routing_per_event() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    mock.add_usergroup("S_ONCALL", &["U_ONCALL1", "U_EXEC1"]);
+    let mut config = common::test_config();
+    config.notification_rules = HashMap::from([(
+        "P2".to_string(),
+        HashMap::from([
+            (
+                "declared".to_string(),
+                NotificationRule {
+                    channels: vec!["C_SRE".to_string()],
+                    users: vec!["U_EXEC1".to_string()],
+                    user_groups: vec!["S_ONCALL".to_string()],
+                },
+            ),
+            ("resolved".to_string(), NotificationRule::default()),
+        ]),
+    )]);
+    let service = NotificationService::new(ctx.pool.clone(), mock.clone(), Arc::new(config));
+
+    let incident = create_incident_in_channel(&ctx, Severity::P2, "C_INC_RULES").await;
+    service
+        .notify_incident_declared(&incident, vec![])
+        .await
+        .expect("Failed to notify");
+
+    // P2_CHANNELS is replaced by the rule; group members already DMed are skipped
+    assert_eq!(mock.posted_channels(), vec!["C_INC_RULES", "C_SRE"]);
+    assert_eq!(mock.dm_recipients(), vec!["U_EXEC1", "U_ONCALL1"]);
+
+    service
+        .notify_resolution(&incident, vec![])
+        .await
+        .expect("Failed to notify");
+    assert_eq!(
+        mock.posted_channels(),
+        vec!["C_INC_RULES", "C_SRE", "C_INC_RULES"]
+    );
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_p1_user_groups_are_dmed_and_mentioned_in_channel_posts() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    mock.add_usergroup("S_SRE", &["U_SRE1", "U_EXEC1"]);
+    let mut config = common::test_config();
+    config.p1_user_groups = vec!["S_SRE".to_string()];
+    let service = NotificationService::new(ctx.pool.clone(), mock.clone(), Arc::new(config));
+
+    let incident = create_incident_in_channel(&ctx, Severity::P1, "C_INC_P1_GROUPS").await;
+    service
+        .notify_incident_declared(&incident, vec![])
+        .await
+        .expect("Failed to notify");
+
+    assert_eq!(mock.dm_recipients(), vec!["U_EXEC1", "U_EXEC2", "U_SRE1"]);
+    let channel_posts: Vec<(String, String)> = mock
+        .calls()
+        .into_iter()
+        .filter_map(|call| match call {
+            SlackCall::PostMessage { channel_id, blocks } => {
+                Some((channel_id, blocks[0]["text"]["text"].to_string()))
+            }
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        channel_posts,
+        vec![
+            (
+                "C_INC_P1_GROUPS".to_string(),
+                "\"📣 <!subteam^S_SRE>\"".to_string()
+            ),
+            (
+                "C_GENERAL".to_string(),
+                "\"📣 <!subteam^S_SRE>\"".to_string()
+            ),
+        ]
+    );
+    // DMs carry the notification alone
+    assert!(mock.calls().iter().all(|call| match call {
+        SlackCall::SendDm { blocks, .. } => blocks.is_empty(),
+        _ => true,
+    }));
+
+    ctx.cleanup().await;
+}
+
+fn routing_command(user_id: &str) -> SlashCommandPayload {
+    SlashCommandPayload {
+        command: "/incident".to_string(),
+        text: "routing".to_string(),
+        user_id: user_id.to_string(),
+        channel_id: "C_ADMIN".to_string(),
+        response_url: "https://hooks.slack.test/response".to_string(),
+        trigger_id: "trigger-routing".to_string(),
+    }
+}
+
+fn responses(mock: &MockSlackClient) -> Vec<String> {
+    mock.calls()
+        .into_iter()
+        .filter_map(|call| match call {
+            SlackCall::PostToResponseUrl { blocks, .. } => {
+                Some(serde_json::to_string(&blocks).unwrap())
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_service_channels_are_added_once_to_severity_routing() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let mut config = common::test_config();
+    config.service_channels = HashMap::from([(
+        "Test Service".to_string(),
+        vec![
+            "C_SERVICE_ALERTS".to_string(),
+            // Already routed: the P1 channel and the incident channel itself
+            "C_GENERAL".to_string(),
+            "C_INC_SVC1".to_string(),
+        ],
+    )]);
+    let service = NotificationService::new(ctx.pool.clone(), mock.clone(), Arc::new(config));
+
+    let incident = create_incident_in_channel(&ctx, Severity::P1, "C_INC_SVC1").await;
+    service
+        .notify_incident_declared(&incident, vec![])
+        .await
+        .expect("Failed to notify");
+    assert_eq!(
+        mock.posted_channels(),
+        vec!["C_INC_SVC1", "C_GENERAL", "C_SERVICE_ALERTS"]
+    );
+
+    // P3s don't go beyond the incident channel by severity, but do to the service's
+    let incident = create_incident_in_channel(&ctx, Severity::P3, "C_INC_SVC3").await;
+    service
+        .notify_resolution(&incident, vec![])
+        .await
+        .expect("Failed to notify");
+    assert_eq!(
+        mock.posted_channels()[3..],
+        ["C_INC_SVC3", "C_SERVICE_ALERTS", "C_GENERAL", "C_INC_SVC1"]
+    );
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_routing_command_shows_effective_rules_to_admins() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let mut config = common::test_config();
+    config.admin_users = vec!["U_ADMIN".to_string()];
+    config.notification_rules = HashMap::from([(
+        "P3".to_string(),
+        HashMap::from([(
+            "escalated".to_string(),
+            NotificationRule {
+                user_groups: vec!["S_ONCALL".to_string()],
+                ..NotificationRule::default()
+            },
+        )]),
+    )]);
+    config.service_channels = HashMap::from([(
+        "Test Service".to_string(),
+        vec!["C_SERVICE_ALERTS".to_string()],
+    )]);
+    let (job_sender, _job_receiver) = tokio::sync::mpsc::unbounded_channel();
+    let state = incident_bot::AppState::with_slack_client(
+        ctx.pool.clone(),
+        config,
+        job_sender,
+        mock.clone(),
+    );
+
+    handle_routing(state.clone(), routing_command("U_SOMEONE"))
+        .await
+        .unwrap();
+    handle_routing(state, routing_command("U_ADMIN"))
+        .await
+        .unwrap();
+
+    let responses = responses(&mock);
+    assert!(responses[0].contains("Only bot admins"));
+    let table = &responses[1];
+    assert!(table.contains("Notification Routing"));
+    assert!(table.contains(
+        "*P1 (Critical)* _(default: P1_CHANNELS, P1_USERS, P1_USER_GROUPS)_\\n• declared: incident channel, <#C_GENERAL>\\n"
+    ));
+    assert!(table.contains("• exec tier 1 (on declaration): <@U_EXEC1>, <@U_EXEC2>"));
+    assert!(table.contains("*P3 (Medium)* _(NOTIFICATION_RULES)_"));
+    assert!(table.contains(
+        "• declared: incident channel only\\n• escalated: incident channel, <!subteam^S_ONCALL>"
+    ));
+    assert!(table.contains("_(SERVICE_CHANNELS, added for every severity and event)_\\n• Test Service: <#C_SERVICE_ALERTS>"));
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_fan_out_channels_get_later_updates_in_the_broadcast_thread() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let service = NotificationService::new(
+        ctx.pool.clone(),
+        mock.clone(),
+        Arc::new(common::test_config()),
+    );
+
+    let incident = create_incident_in_channel(&ctx, Severity::P1, "C_INC_THREAD").await;
+    service
+        .notify_incident_declared(&incident, vec![])
+        .await
+        .expect("Failed to notify");
+    let thread_ts: String = sqlx::query_scalar::query_scalar(
+        "SELECT message_ts FROM broadcast_threads WHERE incident_id = $1 AND channel_id = 'C_GENERAL'",
+    )
+    .bind(incident.id)
+    .fetch_one(&ctx.pool)
+    .await
+    .expect("Broadcast thread not recorded");
+
+    // Status updates and downgrades stay in the thread; escalations and
+    // resolution are also shown in the channel
+    service
+        .notify_status_update(&incident, vec![])
+        .await
+        .expect("Failed to notify");
+    service
+        .notify_severity_change(&incident, &SeverityLevel::builtin(Severity::P1), vec![])
+        .await
+        .expect("Failed to notify");
+    service
+        .notify_severity_change(&incident, &SeverityLevel::builtin(Severity::P2), vec![])
+        .await
+        .expect("Failed to notify");
+    service
+        .notify_resolution(&incident, vec![])
+        .await
+        .expect("Failed to notify");
+
+    assert_eq!(
+        mock.posted_channels(),
+        vec![
+            "C_INC_THREAD",
+            "C_GENERAL",
+            "C_INC_THREAD",
+            "C_INC_THREAD",
+            "C_INC_THREAD",
+            "C_INC_THREAD"
+        ]
+    );
+    let replies: Vec<(String, bool)> = mock
+        .thread_replies()
+        .into_iter()
+        .map(|(channel_id, ts, broadcast)| {
+            assert_eq!(ts, thread_ts);
+            (channel_id, broadcast)
+        })
+        .collect();
+    assert_eq!(
+        replies,
+        vec![
+            ("C_GENERAL".to_string(), false),
+            ("C_GENERAL".to_string(), false),
+            ("C_GENERAL".to_string(), true),
+            ("C_GENERAL".to_string(), true),
+        ]
+    );
+
+    ctx.cleanup().await;
+}
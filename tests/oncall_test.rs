@@ -0,0 +1,205 @@
+use axum::extract::{Path, RawQuery};
+use axum::http::HeaderMap;
+use axum::routing::get;
+use axum::{Json, Router};
+use incident_bot::commands::declare::apply_service;
+use incident_bot::commands::whoisoncall::handle_whoisoncall;
+use incident_bot::config::OnCallProvider;
+use incident_bot::slack::events::{SlashCommandPayload, ViewPayload};
+use incident_bot::slack::mock::{MockSlackClient, SlackCall};
+use incident_bot::{AppConfig, AppState};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+mod common;
+
+fn whoisoncall(service: &str) -> SlashCommandPayload {
+    SlashCommandPayload {
+        command: "/incident".to_string(),
+        text: format!("whoisoncall {}", service),
+        user_id: "U024REPORTER".to_string(),
+        channel_id: "C024GENERAL".to_string(),
+        response_url: "https://hooks.slack.test/response".to_string(),
+        trigger_id: "trigger-123".to_string(),
+    }
+}
+
+fn declare_view(service: &str, commander: Option<&str>) -> ViewPayload {
+    serde_json::from_value(json!({
+        "id": "V024DECLARE",
+        "callback_id": "declare_incident_modal",
+        "private_metadata": "",
+        "state": { "values": {
+            "title_block": { "title_input": { "value": "Checkout is down" } },
+            "service_block": { "service_select": { "selected_option": { "value": service } } },
+            "commander_block": { "commander_select": { "selected_user": commander } }
+        } }
+    }))
+    .unwrap()
+}
+
+/// Serve `app` on a local port and return its base URL.
+async fn serve(app: Router) -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+    format!("http://{}", addr)
+}
+
+fn oncall_state(
+    ctx: &common::TestContext,
+    mock: Arc<MockSlackClient>,
+    provider: OnCallProvider,
+    api_url: String,
+) -> AppState {
+    let config = AppConfig {
+        services: vec!["Checkout".to_string(), "Search".to_string()],
+        oncall_provider: Some(provider),
+        oncall_api_token: Some("oncall-key".to_string()),
+        oncall_api_url: Some(api_url),
+        oncall_schedules: HashMap::from([("Checkout".to_string(), "PCHECKOUT".to_string())]),
+        ..common::test_config()
+    };
+    let (job_sender, _job_receiver) = mpsc::unbounded_channel();
+    AppState::with_slack_client(ctx.pool.clone(), config, job_sender, mock)
+}
+
+/// Concatenated text of every response_url reply.
+fn replies(mock: &MockSlackClient) -> String {
+    mock.calls()
+        .iter()
+        .filter_map(|call| match call {
+            SlackCall::PostToResponseUrl { blocks, .. } => Some(
+                blocks
+                    .iter()
+                    .map(|block| block["text"]["text"].as_str().unwrap_or_default())
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            ),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[tokio::test]
+async fn test_pagerduty_on_call_is_suggested_as_commander() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    mock.add_user_email("alice@example.com", "U024ALICE");
+
+    // Fake PagerDuty: the schedule has a secondary listed before its primary
+    let app = Router::new().route(
+        "/oncalls",
+        get(|headers: HeaderMap, RawQuery(query): RawQuery| async move {
+            assert_eq!(headers["authorization"], "Token token=oncall-key");
+            assert!(query.unwrap_or_default().contains("PCHECKOUT"));
+            Json(json!({ "oncalls": [
+                { "escalation_level": 2, "user": { "email": "bob@example.com" } },
+                { "escalation_level": 1, "user": { "email": "alice@example.com" } }
+            ] }))
+        }),
+    );
+    let state = oncall_state(
+        &ctx,
+        mock.clone(),
+        OnCallProvider::PagerDuty,
+        serve(app).await,
+    );
+
+    apply_service(&state, &declare_view("Checkout", None))
+        .await
+        .unwrap();
+    let updated: Vec<Value> = mock
+        .calls()
+        .into_iter()
+        .filter_map(|call| match call {
+            SlackCall::UpdateModal { view_id, view } if view_id == "V024DECLARE" => Some(view),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(updated.len(), 1);
+    let commander = updated[0]["blocks"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|block| block["block_id"] == "commander_block")
+        .unwrap();
+    assert_eq!(commander["element"]["initial_user"], "U024ALICE");
+
+    // A commander picked by hand, or a service without a schedule, is left alone
+    apply_service(&state, &declare_view("Checkout", Some("U024CAROL")))
+        .await
+        .unwrap();
+    apply_service(&state, &declare_view("Search", None))
+        .await
+        .unwrap();
+    let updates = mock
+        .calls()
+        .iter()
+        .filter(|call| matches!(call, SlackCall::UpdateModal { .. }))
+        .count();
+    assert_eq!(updates, 1);
+
+    handle_whoisoncall(state.clone(), whoisoncall("checkout"))
+        .await
+        .unwrap();
+    handle_whoisoncall(state.clone(), whoisoncall("Search"))
+        .await
+        .unwrap();
+    let text = replies(&mock);
+    assert!(text.contains("<@U024ALICE> is on call for *Checkout*"));
+    assert!(text.contains("Search has no on-call schedule"));
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_opsgenie_on_call_without_slack_account() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+
+    let app = Router::new().route(
+        "/v2/schedules/{schedule}/on-calls",
+        get(
+            |Path(schedule): Path<String>, headers: HeaderMap| async move {
+                assert_eq!(schedule, "PCHECKOUT");
+                assert_eq!(headers["authorization"], "GenieKey oncall-key");
+                Json(json!({ "data": { "onCallRecipients": ["dana@example.com"] } }))
+            },
+        ),
+    );
+    let state = oncall_state(
+        &ctx,
+        mock.clone(),
+        OnCallProvider::Opsgenie,
+        serve(app).await,
+    );
+
+    // Nobody to pre-select without a Slack match
+    apply_service(&state, &declare_view("Checkout", None))
+        .await
+        .unwrap();
+    assert!(!mock
+        .calls()
+        .iter()
+        .any(|call| matches!(call, SlackCall::UpdateModal { .. })));
+
+    handle_whoisoncall(state.clone(), whoisoncall("Checkout"))
+        .await
+        .unwrap();
+    assert!(replies(&mock).contains("dana@example.com is on call for *Checkout*"));
+
+    // Without a provider the command says so
+    let unconfigured = common::mock_state(&ctx.pool, mock.clone());
+    handle_whoisoncall(unconfigured, whoisoncall("Checkout"))
+        .await
+        .unwrap();
+    assert!(replies(&mock).contains("On-call schedules are not configured"));
+
+    ctx.cleanup().await;
+}
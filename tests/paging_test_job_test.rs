@@ -0,0 +1,114 @@
+use chrono::{Duration, Utc};
+use incident_bot::commands::paging_test::handle_paging_test_ack;
+use incident_bot::jobs::paging_test::{report_finished_tests, start_due_test};
+use incident_bot::slack::mock::{MockSlackClient, SlackCall};
+use incident_bot::{AppConfig, AppState};
+use std::sync::Arc;
+
+mod common;
+
+fn paging_state(ctx: &common::TestContext, mock: Arc<MockSlackClient>) -> AppState {
+    let config = AppConfig {
+        paging_test_day: 1,
+        p1_users: vec!["U_PAGE_ONCALL".to_string(), "U_PAGE_GONE".to_string()],
+        backup_commanders: vec!["U_PAGE_BACKUP".to_string(), "U_PAGE_ONCALL".to_string()],
+        paging_test_channel: Some("C_PAGING_REPORT".to_string()),
+        ..common::test_config()
+    };
+    let (job_sender, _job_receiver) = tokio::sync::mpsc::unbounded_channel();
+    AppState::with_slack_client(ctx.pool.clone(), config, job_sender, mock)
+}
+
+#[tokio::test]
+async fn test_monthly_paging_test_records_acks_and_failed_pages() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let state = paging_state(&ctx, mock.clone());
+    // Someone who has left the workspace
+    mock.fail_dm_to("U_PAGE_GONE", "user_not_found");
+
+    let now = Utc::now();
+    let test = start_due_test(&state, now)
+        .await
+        .unwrap()
+        .expect("test should start on or after PAGING_TEST_DAY");
+    assert!(start_due_test(&state, now + Duration::hours(1))
+        .await
+        .unwrap()
+        .is_none());
+    assert_eq!(
+        mock.dm_recipients(),
+        vec!["U_PAGE_ONCALL", "U_PAGE_GONE", "U_PAGE_BACKUP"]
+    );
+
+    handle_paging_test_ack(
+        state.clone(),
+        "U_PAGE_ONCALL".to_string(),
+        &test.id.to_string(),
+        Some("https://hooks.slack.com/actions/ack".to_string()),
+    )
+    .await
+    .unwrap();
+    // A second click is harmless
+    handle_paging_test_ack(
+        state.clone(),
+        "U_PAGE_ONCALL".to_string(),
+        &test.id.to_string(),
+        Some("https://hooks.slack.com/actions/ack".to_string()),
+    )
+    .await
+    .unwrap();
+    let replies: Vec<String> = mock
+        .calls()
+        .into_iter()
+        .filter_map(|call| match call {
+            SlackCall::PostToResponseUrl { blocks, .. } => {
+                Some(serde_json::to_string(&blocks).unwrap())
+            }
+            _ => None,
+        })
+        .collect();
+    assert!(replies[0].contains("Test page acknowledged after"));
+    assert!(replies[1].contains("already acknowledged"));
+
+    // Reported once the acknowledgement window (60 min) has passed
+    assert_eq!(
+        report_finished_tests(&state, now + Duration::minutes(30))
+            .await
+            .unwrap(),
+        0
+    );
+    assert_eq!(
+        report_finished_tests(&state, now + Duration::minutes(61))
+            .await
+            .unwrap(),
+        1
+    );
+    assert_eq!(
+        report_finished_tests(&state, now + Duration::minutes(120))
+            .await
+            .unwrap(),
+        0
+    );
+
+    let report = mock
+        .calls()
+        .into_iter()
+        .find_map(|call| match call {
+            SlackCall::PostMessage { channel_id, blocks } if channel_id == "C_PAGING_REPORT" => {
+                Some(serde_json::to_string(&blocks).unwrap())
+            }
+            _ => None,
+        })
+        .expect("report should be posted");
+    assert!(report.contains("*1 of 3*"));
+    assert!(
+        report.contains("<@U_PAGE_GONE> (P1 exec tier 1): page not delivered, `user_not_found`")
+    );
+    assert!(
+        report.contains("<@U_PAGE_BACKUP> (backup commander): no acknowledgement within 60 min")
+    );
+    assert!(report.contains("<@U_PAGE_ONCALL> (P1 exec tier 1): acknowledged in"));
+
+    ctx.cleanup().await;
+}
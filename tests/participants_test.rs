@@ -0,0 +1,143 @@
+use incident_bot::db::models::{NewTimelineEvent, Severity, TimelineEventType};
+use incident_bot::services::incident::IncidentService;
+use incident_bot::services::participants::ParticipantService;
+use incident_bot::services::postmortem::PostmortemService;
+use incident_bot::services::timeline::TimelineService;
+use incident_bot::slack::mock::MockSlackClient;
+use incident_bot::slack::socket_mode::{handle_message, SocketAction};
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+
+mod common;
+
+#[tokio::test]
+async fn test_participants_recorded_from_timeline_and_listed_in_postmortem() {
+    let ctx = common::TestContext::new().await;
+    let incident_service = IncidentService::new(ctx.pool.clone());
+    let incident = incident_service
+        .create_incident(
+            "Queue backlog".to_string(),
+            Severity::P2,
+            "Test Service".to_string(),
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .expect("Failed to create incident");
+    incident_service
+        .update_channel_id(incident.id, "C_PARTICIPANTS".to_string())
+        .await
+        .expect("Failed to set channel id");
+
+    let participants = ParticipantService::new(ctx.pool.clone());
+    assert!(participants
+        .record_join("C_PARTICIPANTS", "U024RESPONDER", chrono::Utc::now())
+        .await
+        .unwrap());
+    // Channels that aren't incident channels are ignored
+    assert!(!participants
+        .record_join("C_RANDOM", "U024RESPONDER", chrono::Utc::now())
+        .await
+        .unwrap());
+
+    let timeline = TimelineService::new(ctx.pool.clone());
+    timeline
+        .log_event(
+            incident.id,
+            TimelineEventType::Note,
+            "Draining the queue".to_string(),
+            "U024RESPONDER".to_string(),
+        )
+        .await
+        .unwrap();
+    // Integration authors are not people
+    timeline
+        .log_events_batch(
+            incident.id,
+            vec![NewTimelineEvent {
+                event_type: TimelineEventType::Note,
+                message: "Alert firing".to_string(),
+                posted_by: "alertmanager".to_string(),
+                timestamp: None,
+            }],
+        )
+        .await
+        .unwrap();
+
+    let listed = participants.list(incident.id).await.unwrap();
+    let users: Vec<&str> = listed.iter().map(|p| p.user_id.as_str()).collect();
+    assert_eq!(users, vec!["U024COMMANDER", "U024RESPONDER"]);
+    assert!(listed[0].joined_at.is_none());
+    assert!(listed[1].joined_at.is_some());
+    assert_eq!(listed[1].timeline_events, 1);
+
+    let resolved = incident_service
+        .resolve_incident(incident.id, "U024COMMANDER".to_string())
+        .await
+        .unwrap();
+    let postmortem = PostmortemService::new(ctx.pool.clone())
+        .generate(&resolved)
+        .await
+        .unwrap();
+    assert!(postmortem.contains("## Responders"));
+    assert!(postmortem.contains("- <@U024COMMANDER> — not in channel, 2 timeline events"));
+    assert!(postmortem.contains("- <@U024RESPONDER> — joined "));
+    assert!(!postmortem.contains("alertmanager>"));
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_member_joined_channel_event_records_participant() {
+    let ctx = common::TestContext::new().await;
+    let state = common::mock_state(&ctx.pool, Arc::new(MockSlackClient::new()));
+    let incident_service = IncidentService::new(ctx.pool.clone());
+    let incident = incident_service
+        .create_incident(
+            "Join tracking".to_string(),
+            Severity::P3,
+            "Test Service".to_string(),
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .expect("Failed to create incident");
+    incident_service
+        .update_channel_id(incident.id, "C_JOINED".to_string())
+        .await
+        .expect("Failed to set channel id");
+
+    let envelope = json!({
+        "type": "events_api",
+        "envelope_id": "env-join-1",
+        "payload": {
+            "type": "event_callback",
+            "event_id": "Ev_JOIN_1",
+            "event": { "type": "member_joined_channel", "user": "U024JOINER", "channel": "C_JOINED" }
+        }
+    });
+    assert!(matches!(
+        handle_message(&state, &envelope.to_string()).await,
+        SocketAction::Ack(_)
+    ));
+
+    // The join is recorded by a spawned task
+    let participants = ParticipantService::new(ctx.pool.clone());
+    let mut joined = None;
+    for _ in 0..50 {
+        joined = participants
+            .list(incident.id)
+            .await
+            .unwrap()
+            .into_iter()
+            .find(|p| p.user_id == "U024JOINER");
+        if joined.is_some() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    let joined = joined.expect("join was not recorded");
+    assert!(joined.joined_at.is_some());
+    assert_eq!(joined.timeline_events, 0);
+
+    ctx.cleanup().await;
+}
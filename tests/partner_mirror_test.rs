@@ -0,0 +1,96 @@
+use chrono::{Duration, Utc};
+use incident_bot::db::models::{Audience, Severity, TimelineEventType};
+use incident_bot::db::queries::timeline::log_event;
+use incident_bot::jobs::partner_mirror::mirror_once;
+use incident_bot::services::incident::IncidentService;
+use incident_bot::slack::mock::{MockSlackClient, SlackCall};
+use incident_bot::{AppConfig, AppState};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+mod common;
+
+const PARTNER_CHANNEL: &str = "C_PARTNER_MIRROR";
+
+fn partner_posts(mock: &MockSlackClient) -> Vec<String> {
+    mock.calls()
+        .into_iter()
+        .filter_map(|call| match call {
+            SlackCall::PostMessage { channel_id, blocks } if channel_id == PARTNER_CHANNEL => {
+                Some(blocks[0]["text"]["text"].as_str().unwrap().to_string())
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_partner_channel_gets_delayed_redacted_updates() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let config = AppConfig {
+        partner_channels: HashMap::from([(
+            "Test Service".to_string(),
+            PARTNER_CHANNEL.to_string(),
+        )]),
+        partner_mirror_delay_minutes: 15,
+        partner_redact_domains: vec!["corp.example.com".to_string()],
+        ..common::test_config()
+    };
+    let (job_sender, _job_receiver) = mpsc::unbounded_channel();
+    let state = AppState::with_slack_client(ctx.pool.clone(), config, job_sender, mock.clone());
+
+    let incident_service = IncidentService::new(ctx.pool.clone());
+    let incident = incident_service
+        .create_incident(
+            "Sync failures from sftp.corp.example.com".to_string(),
+            Severity::P2,
+            "Test Service".to_string(),
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .unwrap();
+    incident_service
+        .post_status_update(
+            incident.id,
+            "<@U024ALICE> restarted batch-7.corp.example.com (10.4.0.12); retries are draining"
+                .to_string(),
+            "U024COMMANDER".to_string(),
+            Audience::Internal,
+        )
+        .await
+        .unwrap();
+    log_event(
+        &ctx.pool,
+        incident.id,
+        TimelineEventType::Note,
+        "Customer ACME escalated via their TAM".to_string(),
+        "U024COMMANDER".to_string(),
+        Audience::Internal,
+    )
+    .await
+    .unwrap();
+
+    // Nothing is shared before the delay has passed
+    let now = Utc::now();
+    assert_eq!(mirror_once(&state, now).await.unwrap(), 0);
+    assert!(partner_posts(&mock).is_empty());
+
+    let later = now + Duration::minutes(16);
+    assert_eq!(mirror_once(&state, later).await.unwrap(), 2);
+    let posts = partner_posts(&mock);
+    assert_eq!(posts.len(), 2);
+    assert!(posts[0].contains("Sync failures from [internal host]"));
+    assert!(posts[0].contains("*Declared:*"));
+    assert!(posts[1].contains(
+        "*Update:* [name] restarted [internal host] ([internal host]); retries are draining"
+    ));
+    assert!(posts.iter().all(|p| !p.contains("ACME")));
+
+    // Each update is shared once
+    assert_eq!(mirror_once(&state, later).await.unwrap(), 0);
+    assert_eq!(partner_posts(&mock).len(), 2);
+
+    ctx.cleanup().await;
+}
@@ -0,0 +1,137 @@
+use incident_bot::commands::incident_actions::handle_resolve_button;
+use incident_bot::commands::severity::handle_severity;
+use incident_bot::db::models::{Audience, IncidentStatus, Severity};
+use incident_bot::error::IncidentError;
+use incident_bot::services::incident::IncidentService;
+use incident_bot::services::permissions::{Action, Permissions, Role};
+use incident_bot::slack::events::SlashCommandPayload;
+use incident_bot::slack::mock::{MockSlackClient, SlackCall};
+use incident_bot::{AppConfig, AppState};
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+mod common;
+
+const CHANNEL: &str = "C_PERMISSIONS";
+const RESPONSE_URL: &str = "https://hooks.slack.test/response";
+
+fn responses(mock: &MockSlackClient) -> Vec<String> {
+    mock.calls()
+        .into_iter()
+        .filter_map(|call| match call {
+            SlackCall::PostToResponseUrl { blocks, .. } => {
+                Some(serde_json::to_string(&blocks).unwrap())
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_admins_override_commander_only_actions() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    mock.add_usergroup("S_INCIDENT_ADMINS", &["U024GROUPADMIN"]);
+    let config = AppConfig {
+        admin_user_groups: vec!["S_INCIDENT_ADMINS".to_string()],
+        ..common::test_config()
+    };
+    let (job_sender, _job_receiver) = mpsc::unbounded_channel();
+    let state = AppState::with_slack_client(ctx.pool.clone(), config, job_sender, mock.clone());
+
+    let incident_service = IncidentService::new(ctx.pool.clone());
+    let incident = incident_service
+        .create_incident(
+            "Search results are stale".to_string(),
+            Severity::P3,
+            "Test Service".to_string(),
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .unwrap();
+    incident_service
+        .update_channel_id(incident.id, CHANNEL.to_string())
+        .await
+        .unwrap();
+
+    let permissions = Permissions::from_state(&state);
+    assert_eq!(
+        permissions
+            .authorize(Action::Resolve, &incident, "U024COMMANDER")
+            .await
+            .unwrap(),
+        Role::Commander
+    );
+    assert_eq!(
+        permissions
+            .authorize(Action::Resolve, &incident, "U_ADMIN")
+            .await
+            .unwrap(),
+        Role::Admin
+    );
+    assert!(matches!(
+        permissions
+            .authorize(Action::Resolve, &incident, "U024RESPONDER")
+            .await,
+        Err(IncidentError::PermissionDenied { .. })
+    ));
+
+    // Without permissions the service stays commander-only
+    let denied = incident_service
+        .post_status_update(
+            incident.id,
+            "Reindexing".to_string(),
+            "U_ADMIN".to_string(),
+            Audience::Internal,
+        )
+        .await;
+    assert!(matches!(
+        denied,
+        Err(IncidentError::PermissionDenied { .. })
+    ));
+
+    // ADMIN_USERS can change severity
+    handle_severity(
+        state.clone(),
+        SlashCommandPayload {
+            command: "/incident".to_string(),
+            text: "severity P2 Customers are noticing".to_string(),
+            user_id: "U_ADMIN".to_string(),
+            channel_id: CHANNEL.to_string(),
+            response_url: RESPONSE_URL.to_string(),
+            trigger_id: "trigger-severity".to_string(),
+        },
+    )
+    .await
+    .unwrap();
+    let incident = incident_service.get_by_id(incident.id).await.unwrap();
+    assert_eq!(incident.severity, Severity::P2);
+
+    // Responders still can't resolve; ADMIN_USER_GROUPS members can
+    let id = incident.id.to_string();
+    handle_resolve_button(
+        state.clone(),
+        "U024RESPONDER".to_string(),
+        &id,
+        Some(RESPONSE_URL.to_string()),
+    )
+    .await
+    .unwrap();
+    assert!(responses(&mock)
+        .last()
+        .unwrap()
+        .contains("Permission denied"));
+    handle_resolve_button(
+        state,
+        "U024GROUPADMIN".to_string(),
+        &id,
+        Some(RESPONSE_URL.to_string()),
+    )
+    .await
+    .unwrap();
+    let incident = incident_service.get_by_id(incident.id).await.unwrap();
+    assert_eq!(incident.status, IncidentStatus::Resolved);
+    assert_eq!(incident.commander_id, "U024COMMANDER");
+
+    ctx.cleanup().await;
+}
@@ -0,0 +1,143 @@
+use chrono::Duration;
+use incident_bot::commands::resolved::resolve_and_announce;
+use incident_bot::db::models::Severity;
+use incident_bot::db::queries::postmortems::{
+    overdue_postmortems, postmortem_due_date, record_postmortem,
+};
+use incident_bot::jobs::burndown::send_weekly_digest;
+use incident_bot::jobs::postmortem_reminder::remind_once;
+use incident_bot::slack::mock::{MockSlackClient, SlackCall};
+use incident_bot::AppConfig;
+use std::sync::Arc;
+
+mod common;
+
+fn posted_texts(mock: &MockSlackClient, channel: &str) -> Vec<String> {
+    mock.calls()
+        .into_iter()
+        .filter_map(|call| match call {
+            SlackCall::PostMessage { channel_id, blocks } if channel_id == channel => {
+                Some(serde_json::to_string(&blocks).unwrap())
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_required_postmortem_is_drafted_and_nagged_until_published() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let state = common::mock_state(&ctx.pool, mock.clone());
+
+    // test_config requires P1 and P2 postmortems (5 and 10 days); P3 is optional
+    let p2 = common::incident_in_channel(&ctx, Severity::P2, "Test Service", "C_PM_P2").await;
+    let p3 = common::incident_in_channel(&ctx, Severity::P3, "Test Service", "C_PM_P3").await;
+    let resolved = resolve_and_announce(&state, &p2, "U024COMMANDER")
+        .await
+        .unwrap();
+    resolve_and_announce(&state, &p3, "U024COMMANDER")
+        .await
+        .unwrap();
+
+    let due_at = postmortem_due_date(&ctx.pool, p2.id)
+        .await
+        .unwrap()
+        .expect("P2 postmortem should be required");
+    let resolved_at = resolved.resolved_at.unwrap();
+    assert_eq!(due_at, resolved_at + Duration::days(10));
+    assert!(postmortem_due_date(&ctx.pool, p3.id)
+        .await
+        .unwrap()
+        .is_none());
+
+    assert!(posted_texts(&mock, "C_PM_P2")
+        .iter()
+        .any(|text| text.contains("Incident Postmortem Draft")
+            && text.contains("A postmortem is required")));
+    assert!(!posted_texts(&mock, "C_PM_P3")
+        .iter()
+        .any(|text| text.contains("Incident Postmortem Draft")));
+
+    // First nag a full reminder interval (24h) after resolution, then daily.
+    // DMs before these are the template prompts sent on resolution
+    let prompts = mock.dm_recipients().len();
+    assert_eq!(
+        remind_once(&state, resolved_at + Duration::hours(1))
+            .await
+            .unwrap(),
+        0
+    );
+    assert_eq!(
+        remind_once(&state, resolved_at + Duration::hours(25))
+            .await
+            .unwrap(),
+        1
+    );
+    assert_eq!(
+        remind_once(&state, resolved_at + Duration::hours(26))
+            .await
+            .unwrap(),
+        0
+    );
+    assert_eq!(mock.dm_recipients()[prompts..], ["U024COMMANDER"]);
+
+    record_postmortem(
+        &ctx.pool,
+        p2.id,
+        "12345",
+        "https://example.atlassian.net/wiki/pages/12345",
+        "U024COMMANDER",
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        remind_once(&state, resolved_at + Duration::hours(72))
+            .await
+            .unwrap(),
+        0
+    );
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_weekly_digest_lists_overdue_postmortems() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let config = AppConfig {
+        digest_channel: Some("C_DIGEST".to_string()),
+        ..common::test_config()
+    };
+    let (job_sender, _job_receiver) = tokio::sync::mpsc::unbounded_channel();
+    let state = incident_bot::AppState::with_slack_client(
+        ctx.pool.clone(),
+        config,
+        job_sender,
+        mock.clone(),
+    );
+
+    let p1 = common::incident_in_channel(&ctx, Severity::P1, "Test Service", "C_PM_P1").await;
+    let resolved = resolve_and_announce(&state, &p1, "U024COMMANDER")
+        .await
+        .unwrap();
+    let overdue_at = resolved.resolved_at.unwrap() + Duration::days(6);
+
+    assert!(
+        overdue_postmortems(&ctx.pool, resolved.resolved_at.unwrap())
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    let overdue = overdue_postmortems(&ctx.pool, overdue_at).await.unwrap();
+    assert_eq!(overdue.len(), 1);
+    assert_eq!(overdue[0].incident.id, p1.id);
+
+    assert!(send_weekly_digest(&state, overdue_at).await.unwrap());
+    let digest = posted_texts(&mock, "C_DIGEST");
+    assert_eq!(digest.len(), 1);
+    assert!(digest[0].contains("Overdue postmortems (1)"));
+    assert!(digest[0].contains(&p1.title));
+
+    ctx.cleanup().await;
+}
@@ -0,0 +1,251 @@
+use axum::routing::post;
+use axum::{Json, Router};
+use incident_bot::commands::postmortem::handle_postmortem;
+use incident_bot::db::models::Severity;
+use incident_bot::db::queries::postmortems::get_postmortem;
+use incident_bot::services::incident::IncidentService;
+use incident_bot::slack::mock::{MockSlackClient, SlackCall};
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc;
+
+mod common;
+
+async fn resolved_incident_in_channel(
+    ctx: &common::TestContext,
+    channel_id: &str,
+) -> incident_bot::db::models::Incident {
+    let incident_service = IncidentService::new(ctx.pool.clone());
+    let incident = incident_service
+        .create_incident(
+            "Checkout latency".to_string(),
+            Severity::P2,
+            "Test Service".to_string(),
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .expect("Failed to create incident");
+    incident_service
+        .update_channel_id(incident.id, channel_id.to_string())
+        .await
+        .expect("Failed to set channel id");
+    incident_service
+        .resolve_incident(incident.id, "U024COMMANDER".to_string())
+        .await
+        .expect("Failed to resolve incident")
+}
+
+#[tokio::test]
+async fn test_postmortem_publish_creates_one_confluence_page() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let incident = resolved_incident_in_channel(&ctx, "C_PM_PUBLISH").await;
+
+    // Fake Confluence that records each page request
+    let requests = Arc::new(Mutex::new(Vec::<Value>::new()));
+    let pages = Arc::new(AtomicUsize::new(0));
+    let app = Router::new().route(
+        "/wiki/rest/api/content",
+        post({
+            let requests = requests.clone();
+            let pages = pages.clone();
+            move |Json(body): Json<Value>| async move {
+                requests.lock().unwrap().push(body);
+                let id = 1000 + pages.fetch_add(1, Ordering::SeqCst);
+                Json(json!({
+                    "id": id.to_string(),
+                    "_links": { "webui": format!("/spaces/OPS/pages/{}", id) }
+                }))
+            }
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+
+    let config = incident_bot::AppConfig {
+        confluence_base_url: Some(format!("http://{}/wiki", addr)),
+        confluence_email: Some("bot@example.com".to_string()),
+        confluence_api_token: Some("confluence-token".to_string()),
+        confluence_space_key: Some("OPS".to_string()),
+        confluence_parent_page_id: Some("42".to_string()),
+        ..common::test_config()
+    };
+    let (job_sender, _job_receiver) = mpsc::unbounded_channel();
+    let state = incident_bot::AppState::with_slack_client(
+        ctx.pool.clone(),
+        config,
+        job_sender,
+        mock.clone(),
+    );
+
+    for _ in 0..2 {
+        handle_postmortem(
+            state.clone(),
+            common::slash_command("postmortem publish", "U024RESPONDER", "C_PM_PUBLISH"),
+        )
+        .await
+        .expect("Postmortem publish failed");
+    }
+
+    // Republishing returns the stored page instead of creating another
+    let url = format!("http://{}/wiki/spaces/OPS/pages/1000", addr);
+    assert_eq!(pages.load(Ordering::SeqCst), 1);
+    let stored = get_postmortem(&ctx.pool, incident.id)
+        .await
+        .unwrap()
+        .expect("Postmortem not recorded");
+    assert_eq!(stored.url, url);
+    assert_eq!(stored.published_by, "U024RESPONDER");
+
+    let request = requests.lock().unwrap()[0].clone();
+    assert_eq!(request["space"]["key"], "OPS");
+    assert_eq!(request["ancestors"][0]["id"], "42");
+    assert!(request["title"]
+        .as_str()
+        .unwrap()
+        .starts_with("Postmortem: Checkout latency"));
+    assert!(request["body"]["storage"]["value"]
+        .as_str()
+        .unwrap()
+        .contains("<h2>Timeline</h2>"));
+
+    assert_eq!(mock.posted_channels(), vec!["C_PM_PUBLISH"]);
+    let replies = mock.ephemeral_text();
+    assert!(replies.contains("✅ Postmortem published"));
+    assert!(replies.contains("Postmortem already published"));
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_postmortem_publish_requires_confluence_config() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let incident = resolved_incident_in_channel(&ctx, "C_PM_NOCONFIG").await;
+
+    handle_postmortem(
+        common::mock_state(&ctx.pool, mock.clone()),
+        common::slash_command("postmortem publish", "U024COMMANDER", "C_PM_NOCONFIG"),
+    )
+    .await
+    .expect("Postmortem publish failed");
+
+    assert!(mock.posted_channels().is_empty());
+    assert!(mock
+        .ephemeral_text()
+        .contains("Confluence publishing is not configured"));
+    assert!(get_postmortem(&ctx.pool, incident.id)
+        .await
+        .unwrap()
+        .is_none());
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_postmortem_draft_is_uploaded_as_files() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let incident = resolved_incident_in_channel(&ctx, "C_PM_FILES").await;
+    // `cat` stands in for a PDF renderer: Markdown in, "PDF" out
+    let config = incident_bot::AppConfig {
+        postmortem_pdf_command: Some("cat".to_string()),
+        ..common::test_config()
+    };
+    let (job_sender, _job_receiver) = mpsc::unbounded_channel();
+    let state = incident_bot::AppState::with_slack_client(
+        ctx.pool.clone(),
+        config,
+        job_sender,
+        mock.clone(),
+    );
+
+    handle_postmortem(
+        state,
+        common::slash_command("postmortem", "U024COMMANDER", "C_PM_FILES"),
+    )
+    .await
+    .expect("Postmortem failed");
+
+    let files: Vec<(String, String, String)> = mock
+        .calls()
+        .into_iter()
+        .filter_map(|call| match call {
+            SlackCall::UploadFile {
+                channel_id: Some(channel_id),
+                filename,
+                content,
+                ..
+            } => Some((channel_id, filename, String::from_utf8(content).unwrap())),
+            _ => None,
+        })
+        .collect();
+    let name = format!("postmortem-{}", incident.reference().to_lowercase());
+    assert_eq!(files.len(), 2);
+    assert_eq!(files[0].0, "C_PM_FILES");
+    assert_eq!(files[0].1, format!("{}.md", name));
+    assert!(files[0].2.starts_with("# Postmortem: Checkout latency"));
+    assert!(files[0].2.contains("## Timeline"));
+    assert_eq!(files[1].1, format!("{}.pdf", name));
+    assert_eq!(files[1].2, files[0].2);
+
+    // The message points at the file rather than inlining the draft
+    let posted = mock
+        .calls()
+        .into_iter()
+        .find_map(|call| match call {
+            SlackCall::PostMessage { channel_id, blocks } if channel_id == "C_PM_FILES" => {
+                Some(serde_json::to_string(&blocks).unwrap())
+            }
+            _ => None,
+        })
+        .unwrap();
+    assert!(posted.contains("Incident Postmortem Draft"));
+    assert!(posted.contains(&format!("`{}.md`", name)));
+    assert!(!posted.contains("## Timeline"));
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_postmortem_pdf_failure_still_uploads_markdown() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    resolved_incident_in_channel(&ctx, "C_PM_NOPDF").await;
+    let config = incident_bot::AppConfig {
+        postmortem_pdf_command: Some("false".to_string()),
+        ..common::test_config()
+    };
+    let (job_sender, _job_receiver) = mpsc::unbounded_channel();
+    let state = incident_bot::AppState::with_slack_client(
+        ctx.pool.clone(),
+        config,
+        job_sender,
+        mock.clone(),
+    );
+
+    handle_postmortem(
+        state,
+        common::slash_command("postmortem", "U024COMMANDER", "C_PM_NOPDF"),
+    )
+    .await
+    .expect("Postmortem failed");
+
+    let filenames: Vec<String> = mock
+        .calls()
+        .into_iter()
+        .filter_map(|call| match call {
+            SlackCall::UploadFile { filename, .. } => Some(filename),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(filenames.len(), 1);
+    assert!(filenames[0].ends_with(".md"));
+    assert!(mock.ephemeral_text().contains("Postmortem draft posted"));
+
+    ctx.cleanup().await;
+}
@@ -0,0 +1,291 @@
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use axum::Router;
+use chrono::{Duration, Utc};
+use incident_bot::commands::premortem::{
+    handle_launch_submission, handle_premortem, handle_risk_closed, handle_risk_submission,
+    link_declared,
+};
+use incident_bot::db::models::Severity;
+use incident_bot::db::queries::premortems;
+use incident_bot::services::incident::IncidentService;
+use incident_bot::slack::events::{ViewPayload, ViewState};
+use incident_bot::slack::mock::{MockSlackClient, SlackCall};
+use incident_bot::slack::modals::{PREMORTEM_LAUNCH_CALLBACK_ID, PREMORTEM_RISK_CALLBACK_ID};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tower::ServiceExt;
+
+mod common;
+
+fn view(callback_id: &str, private_metadata: &str, values: Value) -> ViewPayload {
+    ViewPayload {
+        id: "V_PREMORTEM".to_string(),
+        callback_id: callback_id.to_string(),
+        private_metadata: private_metadata.to_string(),
+        state: ViewState {
+            values: values.as_object().unwrap().clone(),
+        },
+    }
+}
+
+fn risk_values(failure: &str, likelihood: &str, another: bool) -> Value {
+    let selected: Vec<Value> = if another {
+        vec![json!({ "value": "another" })]
+    } else {
+        Vec::new()
+    };
+    json!({
+        "failure_block": { "failure_input": { "value": failure } },
+        "likelihood_block": { "likelihood_select": { "selected_option": { "value": likelihood } } },
+        "mitigation_block": { "mitigation_input": { "value": "Feature flag to roll back" } },
+        "another_block": { "another_input": { "selected_options": selected } },
+    })
+}
+
+fn opened_modals(mock: &MockSlackClient) -> Vec<Value> {
+    mock.calls()
+        .into_iter()
+        .filter_map(|call| match call {
+            SlackCall::OpenModal { view, .. } => Some(view),
+            _ => None,
+        })
+        .collect()
+}
+
+fn dm_text(mock: &MockSlackClient, user: &str) -> String {
+    mock.calls()
+        .into_iter()
+        .filter_map(|call| match call {
+            SlackCall::SendDm { user_id, blocks } if user_id == user => Some(blocks),
+            _ => None,
+        })
+        .flatten()
+        .map(|block| block.to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[tokio::test]
+async fn test_guided_premortem_flow_records_launch_and_failure_modes() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let state = common::mock_state(&ctx.pool, mock.clone());
+
+    handle_premortem(
+        state.clone(),
+        common::slash_command("premortem test service", "U_LAUNCHER", "C_ANYWHERE"),
+    )
+    .await
+    .unwrap();
+    let launch_modal = opened_modals(&mock).pop().expect("launch modal");
+    assert_eq!(launch_modal["callback_id"], PREMORTEM_LAUNCH_CALLBACK_ID);
+    assert_eq!(launch_modal["private_metadata"], "Test Service");
+
+    let start = Utc::now() + Duration::days(2);
+    handle_launch_submission(
+        state.clone(),
+        view(
+            PREMORTEM_LAUNCH_CALLBACK_ID,
+            "Test Service",
+            json!({
+                "launch_block": { "launch_input": { "value": "Checkout v2" } },
+                "start_block": { "start_input": { "selected_date_time": start.timestamp() } },
+                "hours_block": { "hours_input": { "value": "12" } },
+            }),
+        ),
+        "U_LAUNCHER".to_string(),
+        Some("trigger-launch".to_string()),
+    )
+    .await
+    .unwrap();
+
+    let upcoming = premortems::list_upcoming(&ctx.pool, Utc::now())
+        .await
+        .unwrap();
+    let premortem = upcoming
+        .iter()
+        .find(|p| p.launch_name == "Checkout v2")
+        .expect("pre-mortem saved");
+    assert_eq!(premortem.service, "Test Service");
+    assert_eq!(premortem.created_by, "U_LAUNCHER");
+    assert_eq!(
+        premortem.window_end - premortem.window_start,
+        Duration::hours(12)
+    );
+
+    let step = opened_modals(&mock).pop().unwrap();
+    assert_eq!(step["callback_id"], PREMORTEM_RISK_CALLBACK_ID);
+    assert_eq!(step["private_metadata"], premortem.id.to_string());
+    assert_eq!(step["title"]["text"], "Failure mode 1");
+
+    let id = premortem.id.to_string();
+    handle_risk_submission(
+        state.clone(),
+        view(
+            PREMORTEM_RISK_CALLBACK_ID,
+            &id,
+            risk_values("Card payments double charge", "high", true),
+        ),
+        "U_LAUNCHER".to_string(),
+        Some("trigger-risk-1".to_string()),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        opened_modals(&mock).pop().unwrap()["title"]["text"],
+        "Failure mode 2"
+    );
+    assert!(dm_text(&mock, "U_LAUNCHER").is_empty());
+
+    handle_risk_submission(
+        state.clone(),
+        view(
+            PREMORTEM_RISK_CALLBACK_ID,
+            &id,
+            risk_values("Search index lags behind", "low", false),
+        ),
+        "U_LAUNCHER".to_string(),
+        Some("trigger-risk-2".to_string()),
+    )
+    .await
+    .unwrap();
+    assert_eq!(opened_modals(&mock).len(), 3, "no third step");
+
+    let summary = dm_text(&mock, "U_LAUNCHER");
+    assert!(summary.contains("Pre-mortem saved: *Checkout v2* (Test Service)"));
+    assert!(summary.contains("Card payments double charge"));
+    assert!(summary.contains("Search index lags behind"));
+
+    // Closing a step with "Done" also finishes the pre-mortem
+    handle_risk_closed(
+        state.clone(),
+        view(PREMORTEM_RISK_CALLBACK_ID, &id, json!({})),
+        "U_OTHER".to_string(),
+    )
+    .await
+    .unwrap();
+    assert!(dm_text(&mock, "U_OTHER").contains("Checkout v2"));
+
+    // Unknown services are refused before any modal opens
+    handle_premortem(
+        state,
+        common::slash_command("premortem billing", "U_LAUNCHER", "C_ANYWHERE"),
+    )
+    .await
+    .unwrap();
+    assert_eq!(opened_modals(&mock).len(), 3);
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_incidents_in_the_launch_window_are_linked() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let state = common::mock_state(&ctx.pool, mock.clone());
+
+    let now = Utc::now();
+    let premortem = premortems::create_premortem(
+        &ctx.pool,
+        "Test Service",
+        "Queue migration",
+        now - Duration::hours(1),
+        now + Duration::hours(5),
+        "U_LAUNCHER",
+    )
+    .await
+    .unwrap();
+    premortems::add_risk(
+        &ctx.pool,
+        premortem.id,
+        "Consumers fall behind",
+        "medium",
+        None,
+        "U_LAUNCHER",
+    )
+    .await
+    .unwrap();
+    // Already over: never linked
+    premortems::create_premortem(
+        &ctx.pool,
+        "Test Service",
+        "Last week's launch",
+        now - Duration::days(8),
+        now - Duration::days(7),
+        "U_LAUNCHER",
+    )
+    .await
+    .unwrap();
+
+    let incident_service = IncidentService::new(ctx.pool.clone());
+    let incident = incident_service
+        .create_incident(
+            "Queue backlog".to_string(),
+            Severity::P2,
+            "test service".to_string(),
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .unwrap();
+    incident_service
+        .update_channel_id(incident.id, "C_PREMORTEM_LINK".to_string())
+        .await
+        .unwrap();
+    let incident = incident_service.get_by_id(incident.id).await.unwrap();
+
+    link_declared(&state, &incident).await;
+    // Linking again (e.g. a retried hook) posts nothing new
+    link_declared(&state, &incident).await;
+
+    assert_eq!(
+        premortems::linked_incidents(&ctx.pool, premortem.id)
+            .await
+            .unwrap(),
+        vec![incident.id]
+    );
+    let posts: Vec<String> = mock
+        .calls()
+        .into_iter()
+        .filter_map(|call| match call {
+            SlackCall::PostMessage { channel_id, blocks } if channel_id == "C_PREMORTEM_LINK" => {
+                Some(serde_json::to_string(&blocks).unwrap())
+            }
+            _ => None,
+        })
+        .collect();
+    assert_eq!(posts.len(), 1);
+    assert!(posts[0].contains("launch window of *Queue migration*"));
+    assert!(posts[0].contains("Consumers fall behind"));
+
+    // Incidents created through the API are linked too
+    let router = Router::new()
+        .nest("/api/v1", incident_bot::api::router(state.clone()))
+        .with_state(state);
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/v1/incidents")
+        .header("Authorization", "Bearer test-api-token")
+        .header("Content-Type", "application/json")
+        .body(Body::from(
+            json!({
+                "title": "Queue backlog again",
+                "severity": "P3",
+                "affected_service": "Test Service",
+                "commander_id": "U024COMMANDER",
+            })
+            .to_string(),
+        ))
+        .unwrap();
+    let response = router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    assert_eq!(
+        premortems::linked_incidents(&ctx.pool, premortem.id)
+            .await
+            .unwrap()
+            .len(),
+        2
+    );
+
+    ctx.cleanup().await;
+}
@@ -0,0 +1,131 @@
+use incident_bot::commands::declare::{handle_declare, handle_modal_submission};
+use incident_bot::db::models::WebhookEvent;
+use incident_bot::db::queries::incidents;
+use incident_bot::services::webhook::WebhookService;
+use incident_bot::slack::events::{SlashCommandPayload, ViewPayload};
+use incident_bot::slack::mock::{MockSlackClient, SlackCall};
+use serde_json::json;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+mod common;
+
+fn quiet_command(user_id: &str) -> SlashCommandPayload {
+    SlashCommandPayload {
+        command: "/incident".to_string(),
+        text: "declare quiet".to_string(),
+        user_id: user_id.to_string(),
+        channel_id: "C_SECOPS".to_string(),
+        response_url: "https://hooks.slack.test/response".to_string(),
+        trigger_id: "trigger-quiet".to_string(),
+    }
+}
+
+#[tokio::test]
+async fn test_quiet_declare_requires_security_group() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    mock.add_usergroup("S_SECURITY", &["U024SECLEAD", "U024ANALYST"]);
+    let state = common::mock_state(&ctx.pool, mock.clone());
+
+    handle_declare(state.clone(), quiet_command("U024OUTSIDER"))
+        .await
+        .unwrap();
+    let calls = mock.calls();
+    assert!(!calls
+        .iter()
+        .any(|c| matches!(c, SlackCall::OpenModal { .. })));
+    assert!(calls.iter().any(|c| matches!(
+        c,
+        SlackCall::PostToResponseUrl { blocks, .. }
+            if blocks[0].to_string().contains("security user group")
+    )));
+
+    handle_declare(state, quiet_command("U024SECLEAD"))
+        .await
+        .unwrap();
+    let modal = mock
+        .calls()
+        .into_iter()
+        .find_map(|c| match c {
+            SlackCall::OpenModal { view, .. } => Some(view),
+            _ => None,
+        })
+        .expect("quiet modal not opened");
+    assert_eq!(modal["private_metadata"], "quiet");
+    assert_eq!(modal["callback_id"], "declare_incident_modal");
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_quiet_declare_skips_broadcasts() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    mock.add_usergroup("S_SECURITY", &["U024SECLEAD", "U024ANALYST"]);
+    let mut config = common::test_config();
+    config
+        .service_owners
+        .insert("Test Service".to_string(), vec!["U024SVCOWNER".to_string()]);
+    let (job_sender, mut job_receiver) = mpsc::unbounded_channel();
+    let state = incident_bot::AppState::with_slack_client(
+        ctx.pool.clone(),
+        config,
+        job_sender,
+        mock.clone(),
+    );
+    WebhookService::new(ctx.pool.clone())
+        .register(
+            "https://warehouse.example.com/incidents",
+            None,
+            &[WebhookEvent::IncidentDeclared],
+        )
+        .await
+        .unwrap();
+
+    let view: ViewPayload = serde_json::from_value(json!({
+        "callback_id": "declare_incident_modal",
+        "private_metadata": "quiet",
+        "state": { "values": {
+            "title_block": { "title_input": { "value": "Suspicious admin logins" } },
+            "severity_block": { "severity_select": { "selected_option": { "value": "P1" } } },
+            "service_block": { "service_select": { "selected_option": { "value": "Test Service" } } },
+            "commander_block": { "commander_select": { "selected_user": null } }
+        } }
+    }))
+    .unwrap();
+    handle_modal_submission(state, view, "U024SECLEAD".to_string())
+        .await
+        .unwrap();
+
+    let calls = mock.calls();
+    assert!(calls.iter().any(|c| matches!(
+        c,
+        SlackCall::CreateConversation {
+            is_private: true,
+            ..
+        }
+    )));
+    let channel_id = "C_MOCK_1";
+
+    let invited = calls
+        .iter()
+        .find_map(|c| match c {
+            SlackCall::InviteUsers { user_ids, .. } => Some(user_ids.clone()),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(invited, vec!["U024ANALYST", "U024SECLEAD"]);
+
+    // P1, yet nothing leaves the incident channel
+    assert!(mock.posted_channels().iter().all(|c| c == channel_id));
+    assert!(mock.dm_recipients().is_empty());
+    assert!(job_receiver.try_recv().is_err());
+
+    let incident = incidents::get_incident_by_channel(&ctx.pool, channel_id)
+        .await
+        .unwrap();
+    assert!(incident.is_quiet);
+
+    ctx.cleanup().await;
+}
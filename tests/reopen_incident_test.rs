@@ -0,0 +1,173 @@
+use incident_bot::commands::reopen::handle_reopen;
+use incident_bot::commands::resolved::resolve_and_announce;
+use incident_bot::db::models::{IncidentStatus, Severity, TimelineEventType};
+use incident_bot::db::queries::postmortems::postmortem_due_date;
+use incident_bot::db::queries::statuspage::set_statuspage_incident_id;
+use incident_bot::db::queries::timeline::get_timeline;
+use incident_bot::jobs::Job;
+use incident_bot::services::incident::IncidentService;
+use incident_bot::slack::events::SlashCommandPayload;
+use incident_bot::slack::mock::{MockSlackClient, SlackCall};
+use incident_bot::AppState;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+mod common;
+
+const REOPEN_SERVICE: &str = "Reopen Service";
+const CHANNEL: &str = "C_REOPEN";
+
+fn reopen_command(user_id: &str, text: &str) -> SlashCommandPayload {
+    SlashCommandPayload {
+        command: "/incident".to_string(),
+        text: text.to_string(),
+        user_id: user_id.to_string(),
+        channel_id: CHANNEL.to_string(),
+        response_url: "https://hooks.slack.test/response".to_string(),
+        trigger_id: "trigger-reopen".to_string(),
+    }
+}
+
+fn responses(mock: &MockSlackClient) -> Vec<String> {
+    mock.calls()
+        .into_iter()
+        .filter_map(|call| match call {
+            SlackCall::PostToResponseUrl { blocks, .. } => {
+                Some(serde_json::to_string(&blocks).unwrap())
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_reopen_restores_incident_notifies_and_degrades_statuspage() {
+    let ctx = common::TestContext::new().await;
+    sqlx::query::query(
+        "INSERT INTO statuspage_mappings (service_name, component_id) VALUES ($1, 'cmp_reopen')",
+    )
+    .bind(REOPEN_SERVICE)
+    .execute(&ctx.pool)
+    .await
+    .unwrap();
+
+    let mock = Arc::new(MockSlackClient::new());
+    let (job_sender, mut job_receiver) = mpsc::unbounded_channel();
+    let state = AppState::with_slack_client(
+        ctx.pool.clone(),
+        common::test_config(),
+        job_sender,
+        mock.clone(),
+    );
+    let incident_service = IncidentService::new(ctx.pool.clone());
+    let incident = incident_service
+        .create_incident(
+            "Checkout errors".to_string(),
+            Severity::P1,
+            REOPEN_SERVICE.to_string(),
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .unwrap();
+    incident_service
+        .update_channel_id(incident.id, CHANNEL.to_string())
+        .await
+        .unwrap();
+    set_statuspage_incident_id(&ctx.pool, incident.id, "sp_reopen")
+        .await
+        .unwrap();
+    let incident = incident_service.get_by_id(incident.id).await.unwrap();
+    resolve_and_announce(&state, &incident, "U024COMMANDER")
+        .await
+        .unwrap();
+    assert!(postmortem_due_date(&ctx.pool, incident.id)
+        .await
+        .unwrap()
+        .is_some());
+    while job_receiver.try_recv().is_ok() {}
+    let posts_before = mock.posted_channels().len();
+
+    // Only the commander may reopen
+    handle_reopen(state.clone(), reopen_command("U024SOMEONE", "reopen"))
+        .await
+        .unwrap();
+    assert_eq!(
+        incident_service
+            .get_by_id(incident.id)
+            .await
+            .unwrap()
+            .status,
+        IncidentStatus::Resolved
+    );
+
+    handle_reopen(
+        state.clone(),
+        reopen_command("U024COMMANDER", "reopen errors are back after the rollback"),
+    )
+    .await
+    .unwrap();
+
+    let reopened = incident_service.get_by_id(incident.id).await.unwrap();
+    assert_eq!(reopened.status, IncidentStatus::Investigating);
+    assert!(reopened.resolved_at.is_none());
+    assert!(reopened.duration_minutes.is_none());
+    // The unpublished postmortem will be required again on the next resolution
+    assert!(postmortem_due_date(&ctx.pool, incident.id)
+        .await
+        .unwrap()
+        .is_none());
+
+    let timeline = get_timeline(&ctx.pool, incident.id).await.unwrap();
+    let last = timeline.last().unwrap();
+    assert_eq!(last.event_type, TimelineEventType::Reopened);
+    assert_eq!(
+        last.message,
+        "Incident reopened: errors are back after the rollback"
+    );
+    let audited: i64 = sqlx::query_scalar::query_scalar(
+        "SELECT COUNT(*) FROM audit_log WHERE incident_id = $1 AND action = 'reopen_incident'",
+    )
+    .bind(incident.id)
+    .fetch_one(&ctx.pool)
+    .await
+    .unwrap();
+    assert_eq!(audited, 1);
+
+    // P1 routing: incident channel plus the P1 channels, where it's a reply
+    // to the resolution announcement that is also shown in the channel
+    assert_eq!(&mock.posted_channels()[posts_before..], &["C_REOPEN"]);
+    let replies = mock.thread_replies();
+    assert_eq!(replies.len(), 1);
+    assert_eq!(replies[0].0, "C_GENERAL");
+    assert!(replies[0].2);
+
+    let mut jobs = Vec::new();
+    while let Ok(job) = job_receiver.try_recv() {
+        jobs.push(job);
+    }
+    assert!(jobs.iter().any(|job| matches!(
+        job,
+        Job::StatuspageSync { status: IncidentStatus::Investigating, component_id, .. }
+            if component_id == "cmp_reopen"
+    )));
+    assert!(jobs.iter().any(|job| matches!(
+        job,
+        Job::StatuspageIncidentUpdate { incident_id, .. } if *incident_id == incident.id
+    )));
+
+    // Open incidents can't be reopened
+    handle_reopen(state, reopen_command("U024COMMANDER", "reopen"))
+        .await
+        .unwrap();
+    let responses = responses(&mock);
+    assert!(responses[0].contains("reopen the incident"));
+    assert!(responses[1].contains("Incident reopened"));
+    assert!(responses[2].contains("This incident is still open"));
+
+    sqlx::query::query("DELETE FROM statuspage_mappings WHERE service_name = $1")
+        .bind(REOPEN_SERVICE)
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+    ctx.cleanup().await;
+}
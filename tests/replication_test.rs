@@ -0,0 +1,295 @@
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use axum::Router;
+use incident_bot::db::models::{IncidentStatus, Severity};
+use incident_bot::db::queries::{broadcast_threads, mim_pages, templates};
+use incident_bot::db::replication::{changes_since, export_snapshot, import_snapshot};
+use incident_bot::services::incident::IncidentService;
+use incident_bot::services::timeline::TimelineService;
+use incident_bot::slack::mock::MockSlackClient;
+use serde_json::Value;
+use std::sync::Arc;
+use tower::ServiceExt;
+
+mod common;
+
+async fn create_incident(ctx: &common::TestContext) -> uuid::Uuid {
+    IncidentService::new(ctx.pool.clone())
+        .create_incident(
+            "Replication test".to_string(),
+            Severity::P2,
+            "Test Service".to_string(),
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .expect("Failed to create incident")
+        .id
+}
+
+#[tokio::test]
+async fn test_every_incident_mutation_emits_a_change_record() {
+    let ctx = common::TestContext::new().await;
+    let incident_service = IncidentService::new(ctx.pool.clone());
+    let start = changes_since(&ctx.pool, 0, 10_000)
+        .await
+        .unwrap()
+        .last()
+        .map_or(0, |c| c.seq);
+
+    let incident_id = create_incident(&ctx).await;
+    incident_service
+        .transition_status(
+            incident_id,
+            IncidentStatus::Investigating,
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .expect("Transition failed");
+    incident_service.delete_incident(incident_id).await.unwrap();
+
+    let changes: Vec<(String, String)> = changes_since(&ctx.pool, start, 100)
+        .await
+        .unwrap()
+        .into_iter()
+        .filter(|c| c.incident_id == incident_id)
+        .map(|c| (c.table_name, c.operation))
+        .collect();
+
+    let expect = |table: &str, op: &str| (table.to_string(), op.to_string());
+    assert!(changes.contains(&expect("incidents", "INSERT")));
+    assert!(changes.contains(&expect("incidents", "UPDATE")));
+    assert!(changes.contains(&expect("incident_timeline", "INSERT")));
+    // Deletes are still visible to tailers after the incident is gone
+    assert_eq!(changes.last(), Some(&expect("incidents", "DELETE")));
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_snapshot_round_trip_restores_deleted_incident() {
+    let ctx = common::TestContext::new().await;
+    let incident_service = IncidentService::new(ctx.pool.clone());
+    let incident_id = create_incident(&ctx).await;
+    let timeline_before = TimelineService::new(ctx.pool.clone())
+        .get_timeline(incident_id)
+        .await
+        .unwrap()
+        .len();
+    // The alert that declared it, so it still auto-resolves after a restore
+    sqlx::query::query(
+        "INSERT INTO tracked_alerts (source, fingerprint, incident_id, declared) VALUES ('alertmanager', 'replication-fp', $1, TRUE)",
+    )
+    .bind(incident_id)
+    .execute(&ctx.pool)
+    .await
+    .unwrap();
+    sqlx::query::query(
+        "INSERT INTO broadcast_threads (incident_id, channel_id, message_ts) VALUES ($1, 'C_GENERAL', '1700000000.000100')",
+    )
+    .bind(incident_id)
+    .execute(&ctx.pool)
+    .await
+    .unwrap();
+    mim_pages::claim_page(
+        &ctx.pool,
+        incident_id,
+        "U_MIM",
+        "MIM_USERS",
+        chrono::Utc::now(),
+    )
+    .await
+    .unwrap();
+    // A Statuspage sync waiting out the circuit breaker
+    sqlx::query::query(
+        "INSERT INTO failed_jobs (incident_id, job, last_error) VALUES ($1, '{}', 'circuit open')",
+    )
+    .bind(incident_id)
+    .execute(&ctx.pool)
+    .await
+    .unwrap();
+    sqlx::query::query("INSERT INTO coaching_opt_ins (user_id) VALUES ('U024COMMANDER')")
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+
+    let snapshot = export_snapshot(&ctx.pool).await.expect("Export failed");
+    incident_service.delete_incident(incident_id).await.unwrap();
+    sqlx::query::query("DELETE FROM coaching_opt_ins")
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+
+    let inserted = import_snapshot(&ctx.pool, &snapshot)
+        .await
+        .expect("Import failed");
+    assert_eq!(inserted["incidents"], 1);
+    assert_eq!(inserted["incident_timeline"], timeline_before as u64);
+    assert_eq!(inserted["tracked_alerts"], 1);
+    assert_eq!(inserted["coaching_opt_ins"], 1);
+    assert_eq!(inserted["failed_jobs"], 1);
+    // The MIM rotation isn't paged again for it
+    let page = mim_pages::get_page(&ctx.pool, incident_id).await.unwrap();
+    assert_eq!(page.map(|p| p.user_id).as_deref(), Some("U_MIM"));
+    // Later notifications keep threading under the first broadcast
+    assert_eq!(
+        broadcast_threads::get_broadcast_thread(&ctx.pool, incident_id, "C_GENERAL")
+            .await
+            .unwrap()
+            .as_deref(),
+        Some("1700000000.000100")
+    );
+
+    let restored = incident_service.get_by_id(incident_id).await.unwrap();
+    assert_eq!(restored.title, "Replication test");
+    let alert_incident = sqlx::query_scalar::query_scalar::<_, uuid::Uuid>(
+        "SELECT incident_id FROM tracked_alerts WHERE source = 'alertmanager' AND fingerprint = 'replication-fp'",
+    )
+    .fetch_one(&ctx.pool)
+    .await
+    .unwrap();
+    assert_eq!(alert_incident, incident_id);
+
+    // Re-importing is a no-op
+    let again = import_snapshot(&ctx.pool, &snapshot).await.unwrap();
+    assert!(again.values().all(|n| *n == 0));
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_snapshot_round_trip_keeps_incident_numbers() {
+    let ctx = common::TestContext::new().await;
+    let incident_service = IncidentService::new(ctx.pool.clone());
+    let incident_id = create_incident(&ctx).await;
+    let number = incident_service
+        .get_by_id(incident_id)
+        .await
+        .unwrap()
+        .number;
+
+    // Restore into a database that has handed out no numbers yet
+    let snapshot = export_snapshot(&ctx.pool).await.expect("Export failed");
+    incident_service.delete_incident(incident_id).await.unwrap();
+    sqlx::query::query("DELETE FROM incident_number_sequences")
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+    import_snapshot(&ctx.pool, &snapshot)
+        .await
+        .expect("Import failed");
+
+    let restored = incident_service.get_by_id(incident_id).await.unwrap();
+    assert_eq!(restored.number, number);
+    assert_eq!(
+        incident_service
+            .find(&restored.reference())
+            .await
+            .unwrap()
+            .id,
+        incident_id
+    );
+    // The next incident carries on after the restored ones
+    let next = create_incident(&ctx).await;
+    assert_eq!(
+        incident_service.get_by_id(next).await.unwrap().number,
+        number + 1
+    );
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_snapshot_round_trip_restores_template_links() {
+    let ctx = common::TestContext::new().await;
+    let incident_service = IncidentService::new(ctx.pool.clone());
+
+    // A template created from one incident, and another declared from it
+    let source_id = create_incident(&ctx).await;
+    let template = templates::create_template(
+        &ctx.pool,
+        "replication-test",
+        "Replication test",
+        Severity::P2,
+        Some("Test Service"),
+        None,
+        Some(source_id),
+    )
+    .await
+    .unwrap()
+    .expect("template created");
+    let declared_id = create_incident(&ctx).await;
+    sqlx::query::query("UPDATE incidents SET template_id = $1 WHERE id = $2")
+        .bind(template.id)
+        .bind(declared_id)
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+
+    let snapshot = export_snapshot(&ctx.pool).await.expect("Export failed");
+    for incident_id in [declared_id, source_id] {
+        incident_service.delete_incident(incident_id).await.unwrap();
+    }
+    sqlx::query::query("DELETE FROM incident_templates WHERE id = $1")
+        .bind(template.id)
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+
+    let inserted = import_snapshot(&ctx.pool, &snapshot)
+        .await
+        .expect("Import failed");
+    assert_eq!(inserted["incidents"], 2);
+    assert_eq!(inserted["incident_templates"], 1);
+    let declared = incident_service.get_by_id(declared_id).await.unwrap();
+    assert_eq!(declared.template_id, Some(template.id));
+    let restored = templates::get_template_by_id(&ctx.pool, template.id)
+        .await
+        .unwrap()
+        .expect("template restored");
+    assert_eq!(restored.source_incident_id, Some(source_id));
+
+    sqlx::query::query("DELETE FROM incident_templates WHERE id = $1")
+        .bind(template.id)
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_changes_endpoint_pages_by_seq() {
+    let ctx = common::TestContext::new().await;
+    let state = common::mock_state(&ctx.pool, Arc::new(MockSlackClient::new()));
+    let router = Router::new()
+        .nest("/api/v1", incident_bot::api::router(state.clone()))
+        .with_state(state);
+    create_incident(&ctx).await;
+
+    let get = |uri: &str| {
+        Request::get(uri)
+            .header("Authorization", "Bearer test-api-token")
+            .body(Body::empty())
+            .unwrap()
+    };
+
+    let response = router
+        .clone()
+        .oneshot(get("/api/v1/replication/changes?limit=1"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let page: Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(page["changes"].as_array().unwrap().len(), 1);
+    assert_eq!(page["next_after"], page["changes"][0]["seq"]);
+
+    let response = router
+        .oneshot(get("/api/v1/replication/changes?limit=0"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    ctx.cleanup().await;
+}
@@ -0,0 +1,107 @@
+use incident_bot::commands::resolved::resolve_and_announce;
+use incident_bot::db::models::{Audience, Severity, TimelineEventType};
+use incident_bot::db::queries::{artifacts, timeline};
+use incident_bot::jobs::resolution_snapshot::{execute, SNAPSHOT_ARTIFACT_KIND};
+use incident_bot::jobs::Job;
+use incident_bot::services::incident::IncidentService;
+use incident_bot::slack::mock::MockSlackClient;
+use incident_bot::AppState;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::path::Path;
+use std::sync::Arc;
+
+mod common;
+
+#[tokio::test]
+async fn test_resolution_snapshot_is_written_once_and_audited() {
+    let ctx = common::TestContext::new().await;
+    let config = common::test_config();
+    let artifact_dir = config.artifact_dir.clone();
+    let (job_sender, mut jobs) = tokio::sync::mpsc::unbounded_channel();
+    let state = AppState::with_slack_client(
+        ctx.pool.clone(),
+        config,
+        job_sender,
+        Arc::new(MockSlackClient::new()),
+    );
+
+    let incident_service = IncidentService::new(ctx.pool.clone());
+    let incident = incident_service
+        .create_incident(
+            "Snapshot at resolution".to_string(),
+            Severity::P2,
+            "Test Service".to_string(),
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .unwrap();
+    timeline::log_event(
+        &ctx.pool,
+        incident.id,
+        TimelineEventType::Note,
+        "Customer ACME escalated via their TAM".to_string(),
+        "U024COMMANDER".to_string(),
+        Audience::Internal,
+    )
+    .await
+    .unwrap();
+    let resolved = resolve_and_announce(&state, &incident, "U024COMMANDER")
+        .await
+        .unwrap();
+
+    let mut queued = false;
+    while let Ok(job) = jobs.try_recv() {
+        queued |= matches!(job, Job::SnapshotResolvedIncident { incident_id } if incident_id == incident.id);
+    }
+    assert!(queued);
+
+    assert!(execute(&state, incident.id).await.unwrap());
+    let stored = artifacts::list_for_incident(&ctx.pool, incident.id)
+        .await
+        .unwrap();
+    assert_eq!(stored.len(), 1);
+    assert_eq!(stored[0].kind, SNAPSHOT_ARTIFACT_KIND);
+
+    let bytes = std::fs::read(Path::new(&artifact_dir).join(&stored[0].storage_key)).unwrap();
+    let snapshot: Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(snapshot["format_version"], 1);
+    assert_eq!(snapshot["incident"]["status"], "Resolved");
+    assert_eq!(
+        snapshot["incident"]["resolved_at"],
+        serde_json::to_value(resolved.resolved_at).unwrap()
+    );
+    let events = snapshot["timeline"].as_array().unwrap();
+    assert!(events
+        .iter()
+        .any(|e| e["message"] == "Customer ACME escalated via their TAM"));
+    assert!(snapshot["notifications"].is_array());
+
+    let digest: String = sqlx::query_scalar::query_scalar(
+        "SELECT details->>'sha256' FROM audit_log WHERE incident_id = $1 AND action = 'resolution_snapshot_saved'",
+    )
+    .bind(incident.id)
+    .fetch_one(&ctx.pool)
+    .await
+    .unwrap();
+    assert_eq!(digest, hex::encode(Sha256::digest(&bytes)));
+
+    // Later edits don't touch the stored record
+    timeline::log_event(
+        &ctx.pool,
+        incident.id,
+        TimelineEventType::Note,
+        "Added after resolution".to_string(),
+        "U024COMMANDER".to_string(),
+        Audience::Internal,
+    )
+    .await
+    .unwrap();
+    assert!(!execute(&state, incident.id).await.unwrap());
+    assert_eq!(
+        std::fs::read(Path::new(&artifact_dir).join(&stored[0].storage_key)).unwrap(),
+        bytes
+    );
+
+    ctx.cleanup().await;
+}
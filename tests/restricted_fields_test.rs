@@ -0,0 +1,153 @@
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use axum::Router;
+use incident_bot::commands::summary::handle_summary;
+use incident_bot::db::models::Severity;
+use incident_bot::services::incident::IncidentService;
+use incident_bot::slack::events::SlashCommandPayload;
+use incident_bot::slack::mock::{MockSlackClient, SlackCall};
+use incident_bot::{AppConfig, AppState};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tower::ServiceExt;
+
+mod common;
+
+fn restricted_state(ctx: &common::TestContext, mock: Arc<MockSlackClient>) -> AppState {
+    let config = AppConfig {
+        restricted_fields: HashMap::from([
+            (
+                "customer_name".to_string(),
+                vec!["commander".to_string(), "admin".to_string()],
+            ),
+            ("revenue_impact".to_string(), vec!["api".to_string()]),
+        ]),
+        ..common::test_config()
+    };
+    let (job_sender, _job_receiver) = mpsc::unbounded_channel();
+    AppState::with_slack_client(ctx.pool.clone(), config, job_sender, mock)
+}
+
+fn summary(text: &str, user_id: &str) -> SlashCommandPayload {
+    SlashCommandPayload {
+        command: "/incident".to_string(),
+        text: text.to_string(),
+        user_id: user_id.to_string(),
+        channel_id: "C024RESTRICT".to_string(),
+        response_url: "https://hooks.slack.test/response".to_string(),
+        trigger_id: "trigger-123".to_string(),
+    }
+}
+
+/// Summary text from `user_id`'s private `/incident summary`.
+async fn private_summary(ctx: &common::TestContext, user_id: &str) -> String {
+    let mock = Arc::new(MockSlackClient::new());
+    handle_summary(
+        restricted_state(ctx, mock.clone()),
+        summary("summary", user_id),
+    )
+    .await
+    .unwrap();
+    mock.calls()
+        .iter()
+        .filter_map(|call| match call {
+            SlackCall::PostToResponseUrl { blocks, .. } => Some(Value::from(blocks.clone())),
+            _ => None,
+        })
+        .map(|blocks| blocks.to_string())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_restricted_fields_show_only_for_their_roles() {
+    let ctx = common::TestContext::new().await;
+    let incident_service = IncidentService::new(ctx.pool.clone());
+    let incident = incident_service
+        .create_incident(
+            "Checkout errors for one customer".to_string(),
+            Severity::P2,
+            "Test Service".to_string(),
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .unwrap();
+    incident_service
+        .update_channel_id(incident.id, "C024RESTRICT".to_string())
+        .await
+        .unwrap();
+    sqlx::query::query("UPDATE incidents SET custom_fields = $2 WHERE id = $1")
+        .bind(incident.id)
+        .bind(json!({
+            "customer_name": "Acme Corp",
+            "revenue_impact": "$40k/hour",
+            "region": "eu-west-1"
+        }))
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+
+    // The commander and admins see the customer; nobody sees revenue in Slack
+    for user_id in ["U024COMMANDER", "U_ADMIN"] {
+        let text = private_summary(&ctx, user_id).await;
+        assert!(text.contains("Acme Corp"), "{}", user_id);
+        assert!(!text.contains("$40k/hour"), "{}", user_id);
+        assert!(text.contains("eu-west-1"));
+    }
+    let text = private_summary(&ctx, "U024BYSTANDER").await;
+    assert!(!text.contains("Acme Corp"));
+    assert!(text.contains("🔒 Restricted"));
+    assert!(text.contains("eu-west-1"));
+
+    // Shared to a channel, even by the commander, restricted values are hidden
+    let mock = Arc::new(MockSlackClient::new());
+    handle_summary(
+        restricted_state(&ctx, mock.clone()),
+        summary("summary <#C024EXEC|exec>", "U024COMMANDER"),
+    )
+    .await
+    .unwrap();
+    let posted = mock
+        .calls()
+        .iter()
+        .find_map(|call| match call {
+            SlackCall::PostMessage { channel_id, blocks } if channel_id == "C024EXEC" => {
+                Some(Value::from(blocks.clone()).to_string())
+            }
+            _ => None,
+        })
+        .expect("Expected the summary in the exec channel");
+    assert!(!posted.contains("Acme Corp"));
+    assert!(posted.contains("🔒 Restricted"));
+
+    // API token holders have the `api` role
+    let state = restricted_state(&ctx, Arc::new(MockSlackClient::new()));
+    let router = Router::new()
+        .nest("/api/v1", incident_bot::api::router(state.clone()))
+        .with_state(state);
+    let response = router
+        .oneshot(
+            Request::builder()
+                .uri(format!("/api/v1/incidents/{}", incident.id))
+                .header("Authorization", "Bearer test-api-token")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body: Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(body["custom_fields"]["revenue_impact"], "$40k/hour");
+    assert_eq!(body["custom_fields"]["customer_name"], "🔒 Restricted");
+    assert_eq!(body["custom_fields"]["region"], "eu-west-1");
+
+    // The stored values are untouched
+    let stored = incident_service.get_by_id(incident.id).await.unwrap();
+    assert_eq!(stored.custom_fields["customer_name"], "Acme Corp");
+
+    ctx.cleanup().await;
+}
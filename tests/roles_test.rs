@@ -0,0 +1,101 @@
+use incident_bot::commands::roles::handle_claim_role;
+use incident_bot::db::models::Severity;
+use incident_bot::jobs::role_reminder::remind_once;
+use incident_bot::services::roles::RoleService;
+use incident_bot::slack::mock::MockSlackClient;
+use std::sync::Arc;
+
+mod common;
+
+const RESPONSE_URL: &str = "https://hooks.slack.test/response";
+
+#[tokio::test]
+async fn test_first_claim_wins_and_roster_is_reposted() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let state = common::mock_state(&ctx.pool, mock.clone());
+    let incident = common::incident_in_channel(&ctx, Severity::P1, "Test Service", "C_ROLES").await;
+    let value = format!("{}:scribe", incident.id);
+
+    handle_claim_role(
+        state.clone(),
+        "U024SCRIBE".to_string(),
+        &value,
+        Some(RESPONSE_URL.to_string()),
+    )
+    .await
+    .expect("Claim failed");
+    assert_eq!(mock.posted_channels(), vec!["C_ROLES"]);
+
+    // Second claimer is told who holds it; nothing is reposted
+    handle_claim_role(
+        state.clone(),
+        "U024OTHER".to_string(),
+        &value,
+        Some(RESPONSE_URL.to_string()),
+    )
+    .await
+    .expect("Duplicate claim should be reported, not fail");
+    assert!(mock
+        .ephemeral_text()
+        .contains("Scribe is already claimed by <@U024SCRIBE>"));
+    assert_eq!(mock.posted_channels().len(), 1);
+
+    // Roles outside the severity matrix can't be claimed
+    handle_claim_role(
+        state,
+        "U024OTHER".to_string(),
+        &format!("{}:deputy", incident.id),
+        Some(RESPONSE_URL.to_string()),
+    )
+    .await
+    .unwrap();
+    assert!(mock
+        .ephemeral_text()
+        .contains("Deputy is not a required role"));
+
+    let required = common::test_config().required_roles_for(Severity::P1);
+    let status = RoleService::new(ctx.pool.clone())
+        .status(&incident, &required)
+        .await
+        .unwrap();
+    assert_eq!(status.unfilled, vec!["comms_lead"]);
+    assert_eq!(status.filled[0].user_id, "U024SCRIBE");
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_reminder_nags_only_understaffed_incidents() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let state = common::mock_state(&ctx.pool, mock.clone());
+
+    let understaffed =
+        common::incident_in_channel(&ctx, Severity::P1, "Test Service", "C_P1_OPEN").await;
+    common::incident_in_channel(&ctx, Severity::P3, "Test Service", "C_P3").await;
+    let staffed =
+        common::incident_in_channel(&ctx, Severity::P1, "Test Service", "C_P1_STAFFED").await;
+    for role in ["comms_lead", "scribe"] {
+        handle_claim_role(
+            state.clone(),
+            "U024HELPER".to_string(),
+            &format!("{}:{}", staffed.id, role),
+            None,
+        )
+        .await
+        .unwrap();
+    }
+    let before = mock.posted_channels().len();
+
+    // Too soon after declaration: nothing posted
+    let reminded = remind_once(&state, chrono::Utc::now()).await.unwrap();
+    assert_eq!(reminded, 0);
+
+    let later = understaffed.declared_at + chrono::Duration::hours(1);
+    let reminded = remind_once(&state, later).await.unwrap();
+    assert_eq!(reminded, 1);
+    assert_eq!(mock.posted_channels()[before..], ["C_P1_OPEN"]);
+
+    ctx.cleanup().await;
+}
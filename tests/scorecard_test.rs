@@ -0,0 +1,117 @@
+use chrono::{NaiveDate, TimeZone, Utc};
+use incident_bot::config::{KpiTargets, TeamConfig};
+use incident_bot::db::models::Severity;
+use incident_bot::jobs::scorecards::send_monthly_scorecards;
+use incident_bot::services::analytics::AnalyticsService;
+use incident_bot::services::audit::AuditService;
+use incident_bot::services::incident::IncidentService;
+use incident_bot::slack::mock::MockSlackClient;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+mod common;
+
+/// Create and resolve an incident, then move it into February 2026 with the
+/// given duration.
+async fn resolved_incident(ctx: &common::TestContext, service: &str, minutes: i32) -> uuid::Uuid {
+    let incident_service = IncidentService::new(ctx.pool.clone());
+    let incident = incident_service
+        .create_incident(
+            "Scorecard test".to_string(),
+            Severity::P2,
+            service.to_string(),
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .expect("Failed to create incident");
+    incident_service
+        .resolve_incident(incident.id, "U024COMMANDER".to_string())
+        .await
+        .expect("Failed to resolve incident");
+
+    let declared_at = Utc.with_ymd_and_hms(2026, 2, 10, 9, 0, 0).unwrap();
+    sqlx::query::query(
+        "UPDATE incidents SET declared_at = $2, resolved_at = $2 + make_interval(mins => $3), duration_minutes = $3 WHERE id = $1",
+    )
+    .bind(incident.id)
+    .bind(declared_at)
+    .bind(minutes)
+    .execute(&ctx.pool)
+    .await
+    .unwrap();
+    incident.id
+}
+
+fn team(leads: &[&str]) -> TeamConfig {
+    TeamConfig {
+        leads: leads.iter().map(ToString::to_string).collect(),
+        services: vec!["Scorecard Service".to_string()],
+        targets: KpiTargets {
+            mttr_minutes: Some(60.0),
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_scorecard_computes_mttr_and_postmortem_completion() {
+    let ctx = common::TestContext::new().await;
+    let with_postmortem = resolved_incident(&ctx, "Scorecard Service", 30).await;
+    resolved_incident(&ctx, "Scorecard Service", 90).await;
+    // Other teams' services don't count
+    resolved_incident(&ctx, "Test Service", 600).await;
+    AuditService::new(ctx.pool.clone())
+        .log_action(
+            Some(with_postmortem),
+            "generate_postmortem".to_string(),
+            "U024COMMANDER".to_string(),
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+    let scorecard = AnalyticsService::new(ctx.pool.clone())
+        .scorecard(
+            "network",
+            &team(&["U_LEAD"]),
+            NaiveDate::from_ymd_opt(2026, 2, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2026, 3, 1).unwrap(),
+        )
+        .await
+        .expect("Scorecard failed");
+
+    assert_eq!(scorecard.declared, 2);
+    assert_eq!(scorecard.resolved, 2);
+    assert_eq!(scorecard.mttr_minutes, Some(60.0));
+    assert_eq!(scorecard.postmortem_completion, Some(0.5));
+    assert_eq!(scorecard.action_item_closure, None);
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_monthly_scorecards_are_sent_once_per_team() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let mut state = common::mock_state(&ctx.pool, mock.clone());
+    state.config = Arc::new(incident_bot::AppConfig {
+        teams: HashMap::from([
+            ("network".to_string(), team(&["U_LEAD1", "U_LEAD2"])),
+            ("leaderless".to_string(), team(&[])),
+        ]),
+        ..(*state.config).clone()
+    });
+
+    let now = Utc.with_ymd_and_hms(2026, 3, 1, 0, 30, 0).unwrap();
+    assert_eq!(send_monthly_scorecards(&state, now).await.unwrap(), 1);
+    assert_eq!(mock.dm_recipients(), vec!["U_LEAD1", "U_LEAD2"]);
+
+    // A later pass in the same month (or another replica) sends nothing
+    assert_eq!(send_monthly_scorecards(&state, now).await.unwrap(), 0);
+    assert_eq!(mock.dm_recipients().len(), 2);
+
+    ctx.cleanup().await;
+}
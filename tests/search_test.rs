@@ -0,0 +1,151 @@
+use incident_bot::commands::search::{handle_search, handle_search_page};
+use incident_bot::db::models::{Audience, Severity};
+use incident_bot::services::incident::IncidentService;
+use incident_bot::slack::mock::{MockSlackClient, SlackCall};
+use serde_json::Value;
+use std::sync::Arc;
+
+mod common;
+
+/// Blocks of the most recent response_url reply.
+fn last_reply(mock: &MockSlackClient) -> Vec<Value> {
+    mock.calls()
+        .into_iter()
+        .rev()
+        .find_map(|call| match call {
+            SlackCall::PostToResponseUrl { blocks, .. } => Some(blocks),
+            _ => None,
+        })
+        .expect("Expected a response_url reply")
+}
+
+async fn create(ctx: &common::TestContext, title: &str, severity: Severity) -> uuid::Uuid {
+    IncidentService::new(ctx.pool.clone())
+        .create_incident(
+            title.to_string(),
+            severity,
+            "Test Service".to_string(),
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .expect("Failed to create incident")
+        .id
+}
+
+#[tokio::test]
+async fn test_search_filters_and_paginates() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let state = common::mock_state(&ctx.pool, mock.clone());
+
+    for n in 0..12 {
+        create(&ctx, &format!("Checkout timeout {}", n), Severity::P2).await;
+    }
+    create(&ctx, "Checkout timeout on P3", Severity::P3).await;
+
+    handle_search(
+        state.clone(),
+        common::slash_command("search sev:P2 text:\"TIMEOUT\"", "U_SEARCHER", "C_ANYWHERE"),
+    )
+    .await
+    .unwrap();
+    let page = last_reply(&mock);
+    assert_eq!(
+        page[0]["text"]["text"],
+        "🔎 Results 1–10 for `sev:P2 text:\"TIMEOUT\"`"
+    );
+    // Header, ten results, next page button
+    assert_eq!(page.len(), 12);
+    let next = &page[11]["elements"][0];
+    assert_eq!(next["action_id"], "search_page");
+
+    handle_search_page(
+        state.clone(),
+        next["value"].as_str().unwrap(),
+        Some("https://hooks.slack.test/response".to_string()),
+    )
+    .await
+    .unwrap();
+    let page = last_reply(&mock);
+    assert_eq!(
+        page[0]["text"]["text"],
+        "🔎 Results 11–12 for `sev:P2 text:\"TIMEOUT\"`"
+    );
+    assert_eq!(page.len(), 3);
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_search_matches_timeline_and_status() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let state = common::mock_state(&ctx.pool, mock.clone());
+    let incident_service = IncidentService::new(ctx.pool.clone());
+
+    let db = create(&ctx, "Database down", Severity::P1).await;
+    incident_service
+        .post_status_update(
+            db,
+            "Replica hit 100% connection pool".to_string(),
+            "U024COMMANDER".to_string(),
+            Audience::Internal,
+        )
+        .await
+        .unwrap();
+    incident_service
+        .resolve_incident(db, "U024COMMANDER".to_string())
+        .await
+        .unwrap();
+    create(&ctx, "Pool party", Severity::P1).await;
+
+    // Matches a timeline message; `%` is literal, not a wildcard
+    handle_search(
+        state.clone(),
+        common::slash_command("search 100% connection", "U_SEARCHER", "C_ANYWHERE"),
+    )
+    .await
+    .unwrap();
+    let page = last_reply(&mock);
+    assert_eq!(page.len(), 2);
+    assert!(page[1]["text"]["text"]
+        .as_str()
+        .unwrap()
+        .contains("*P1* — Database down"));
+
+    handle_search(
+        state.clone(),
+        common::slash_command(
+            "search service:\"test service\" status:resolved",
+            "U_SEARCHER",
+            "C_ANYWHERE",
+        ),
+    )
+    .await
+    .unwrap();
+    assert_eq!(last_reply(&mock).len(), 2);
+
+    handle_search(
+        state.clone(),
+        common::slash_command("search after:2999-01-01", "U_SEARCHER", "C_ANYWHERE"),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        last_reply(&mock)[0]["text"]["text"],
+        "🔎 No incidents match `after:2999-01-01`"
+    );
+
+    handle_search(
+        state.clone(),
+        common::slash_command("search status:sleeping", "U_SEARCHER", "C_ANYWHERE"),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        last_reply(&mock)[0]["text"]["text"],
+        "❌ *Error:* Invalid incident status: sleeping"
+    );
+
+    ctx.cleanup().await;
+}
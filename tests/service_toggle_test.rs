@@ -0,0 +1,165 @@
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use axum::Router;
+use incident_bot::commands::declare::handle_declare;
+use incident_bot::commands::service::handle_service;
+use incident_bot::db::models::Severity;
+use incident_bot::services::incident::IncidentService;
+use incident_bot::slack::mock::{MockSlackClient, SlackCall};
+use incident_bot::{AppConfig, AppState};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tower::ServiceExt;
+
+mod common;
+
+const LEGACY: &str = "Legacy Service";
+
+/// Services offered by the most recently opened declare modal.
+async fn declare_modal_services(state: &AppState, mock: &MockSlackClient) -> Vec<String> {
+    handle_declare(
+        state.clone(),
+        common::slash_command("declare", "U_DECLARER", "C_GENERAL"),
+    )
+    .await
+    .unwrap();
+    let view = mock
+        .calls()
+        .into_iter()
+        .rev()
+        .find_map(|call| match call {
+            SlackCall::OpenModal { view, .. } => Some(view),
+            _ => None,
+        })
+        .unwrap();
+    let blocks = view["blocks"].as_array().unwrap();
+    let service_block = blocks
+        .iter()
+        .find(|b| b["block_id"] == "service_block")
+        .unwrap();
+    service_block["element"]["options"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|o| o["value"].as_str().unwrap().to_string())
+        .collect()
+}
+
+async fn post_alert(state: &AppState) -> Value {
+    let router = Router::new()
+        .nest("/integrations", incident_bot::api::alerts::router())
+        .with_state(state.clone());
+    let body = json!({
+        "version": "4",
+        "status": "firing",
+        "alerts": [{
+            "status": "firing",
+            "labels": { "alertname": "HighLatency", "service": "legacy service" },
+            "annotations": { "summary": "p99 above 2s" },
+            "fingerprint": "legacy-1"
+        }]
+    });
+    let request = Request::builder()
+        .method("POST")
+        .uri("/integrations/alerts/alertmanager")
+        .header("Authorization", "Bearer am-secret")
+        .header("Content-Type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+    let response = router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+#[tokio::test]
+async fn test_disabled_services_are_hidden_until_enabled_again() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let config = AppConfig {
+        services: vec!["Test Service".to_string(), LEGACY.to_string()],
+        alert_source_tokens: HashMap::from([("alertmanager".to_string(), "am-secret".to_string())]),
+        ..common::test_config()
+    };
+    let (job_sender, _job_receiver) = tokio::sync::mpsc::unbounded_channel();
+    let state = AppState::with_slack_client(ctx.pool.clone(), config, job_sender, mock.clone());
+    IncidentService::new(ctx.pool.clone())
+        .create_incident(
+            "Legacy latency".to_string(),
+            Severity::P3,
+            LEGACY.to_string(),
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .unwrap();
+    // Warm the cache, so the toggle below has to invalidate it
+    assert_eq!(
+        declare_modal_services(&state, &mock).await,
+        vec!["Test Service", LEGACY]
+    );
+
+    // Only admins may toggle services
+    handle_service(
+        state.clone(),
+        common::slash_command("service disable Legacy Service", "U_RESPONDER", "C_GENERAL"),
+    )
+    .await
+    .unwrap();
+    assert!(mock.last_response().contains("Only bot admins"));
+
+    handle_service(
+        state.clone(),
+        common::slash_command("service disable legacy service", "U_ADMIN", "C_GENERAL"),
+    )
+    .await
+    .unwrap();
+    assert!(mock.last_response().contains("no longer offered"));
+    assert_eq!(
+        declare_modal_services(&state, &mock).await,
+        vec!["Test Service"]
+    );
+    assert_eq!(post_alert(&state).await["unmatched"], 1);
+
+    handle_service(
+        state.clone(),
+        common::slash_command("service list", "U_ADMIN", "C_GENERAL"),
+    )
+    .await
+    .unwrap();
+    assert!(mock
+        .last_response()
+        .contains("~Legacy Service~ _(disabled)_"));
+
+    handle_service(
+        state.clone(),
+        common::slash_command("service enable Legacy Service", "U_ADMIN", "C_GENERAL"),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        declare_modal_services(&state, &mock).await,
+        vec!["Test Service", LEGACY]
+    );
+    assert_eq!(post_alert(&state).await["recorded"], 1);
+
+    // Toggles are audited; repeating one changes nothing
+    handle_service(
+        state.clone(),
+        common::slash_command("service enable Legacy Service", "U_ADMIN", "C_GENERAL"),
+    )
+    .await
+    .unwrap();
+    assert!(mock.last_response().contains("already enabled"));
+    let audited: i64 = sqlx::query_scalar::query_scalar(
+        "SELECT COUNT(*) FROM audit_log WHERE action IN ('service_disabled', 'service_enabled')",
+    )
+    .fetch_one(&ctx.pool)
+    .await
+    .unwrap();
+    assert_eq!(audited, 2);
+
+    ctx.cleanup().await;
+}
@@ -0,0 +1,153 @@
+use incident_bot::db::models::{Severity, SeverityLevel};
+use incident_bot::services::incident::IncidentService;
+use incident_bot::slack::mock::{MockSlackClient, SlackCall};
+use incident_bot::slack::modals;
+use incident_bot::utils::severity;
+use std::sync::Arc;
+
+mod common;
+
+// Every test in this binary shares the process-wide SEVERITIES.
+fn init_levels() {
+    let level = |code: &str, label: &str, emoji: &str, tier| SeverityLevel {
+        code: code.to_string(),
+        label: label.to_string(),
+        emoji: emoji.to_string(),
+        tier,
+    };
+    severity::init(vec![
+        level("SEV0", "SEV0 (Company-wide)", "🚨", Severity::P1),
+        level("SEV1", "SEV1 (Critical)", "🔴", Severity::P1),
+        level("SEV2", "SEV2 (High)", "🟠", Severity::P2),
+        level("SEV3", "SEV3 (Medium)", "🟡", Severity::P3),
+        level("SEV4", "SEV4 (Low)", "🟢", Severity::P4),
+    ]);
+}
+
+fn posted_text(mock: &MockSlackClient, channel: &str) -> String {
+    mock.calls()
+        .into_iter()
+        .filter_map(|call| match call {
+            SlackCall::PostMessage { channel_id, blocks } if channel_id == channel => Some(blocks),
+            SlackCall::PostToResponseUrl { blocks, .. } if channel == "response_url" => {
+                Some(blocks)
+            }
+            _ => None,
+        })
+        .flatten()
+        .map(|block| block.to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[tokio::test]
+async fn test_escalating_between_levels_on_one_tier() {
+    init_levels();
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let state = common::mock_state(&ctx.pool, mock.clone());
+
+    let incident_service = IncidentService::new(ctx.pool.clone());
+    let incident = incident_service
+        .create_incident(
+            "Checkout down in one region".to_string(),
+            severity::parse("sev1").unwrap().clone(),
+            "Test Service".to_string(),
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .expect("Failed to create incident");
+    incident_service
+        .update_channel_id(incident.id, "C_SEV_LEVELS".to_string())
+        .await
+        .unwrap();
+    assert_eq!(incident.severity, Severity::P1);
+    assert_eq!(incident.severity_code.as_deref(), Some("SEV1"));
+
+    incident_bot::commands::severity::handle_severity(
+        state,
+        common::slash_command(
+            "severity SEV0 Every region is down",
+            "U024COMMANDER",
+            "C_SEV_LEVELS",
+        ),
+    )
+    .await
+    .expect("Severity command failed");
+
+    let (tier, code): (String, Option<String>) =
+        sqlx::query_as::query_as("SELECT severity, severity_code FROM incidents WHERE id = $1")
+            .bind(incident.id)
+            .fetch_one(&ctx.pool)
+            .await
+            .unwrap();
+    assert_eq!(tier, "P1");
+    assert_eq!(code.as_deref(), Some("SEV0"));
+
+    let channel_text = posted_text(&mock, "C_SEV_LEVELS");
+    assert!(channel_text.contains("SEV1 (Critical)"));
+    assert!(channel_text.contains("SEV0 (Company-wide)"));
+    // SEV1 -> SEV0 is an escalation even though both are on the P1 tier
+    assert!(mock.posted_channels().contains(&"C_GENERAL".to_string()));
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_unknown_level_lists_configured_codes() {
+    init_levels();
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let state = common::mock_state(&ctx.pool, mock.clone());
+
+    let incident_service = IncidentService::new(ctx.pool.clone());
+    let incident = incident_service
+        .create_incident(
+            "Search is slow".to_string(),
+            Severity::P3,
+            "Test Service".to_string(),
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .unwrap();
+    incident_service
+        .update_channel_id(incident.id, "C_SEV_UNKNOWN".to_string())
+        .await
+        .unwrap();
+    // A bare tier stands in for the level named after it, or its first
+    assert_eq!(incident.severity_code.as_deref(), Some("SEV3"));
+
+    let _ = incident_bot::commands::severity::handle_severity(
+        state,
+        common::slash_command("severity SEV9 typo", "U024COMMANDER", "C_SEV_UNKNOWN"),
+    )
+    .await;
+
+    assert!(posted_text(&mock, "response_url")
+        .contains("Invalid severity 'SEV9'. Use SEV0, SEV1, SEV2, SEV3 or SEV4"));
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_declare_modal_offers_configured_levels() {
+    init_levels();
+    let modal = modals::declare_incident_modal(&["Okta".to_string()], &[], None);
+    let block = modal["blocks"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|block| block["block_id"] == "severity_block")
+        .expect("severity block");
+    let codes: Vec<&str> = block["element"]["options"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|option| option["value"].as_str().unwrap())
+        .collect();
+    assert_eq!(codes, vec!["SEV0", "SEV1", "SEV2", "SEV3", "SEV4"]);
+    assert_eq!(
+        block["element"]["options"][0]["text"]["text"],
+        "SEV0 (Company-wide)"
+    );
+}
@@ -0,0 +1,65 @@
+use incident_bot::commands::simulate::handle_simulate;
+use incident_bot::slack::mock::{MockSlackClient, SlackCall};
+use std::sync::Arc;
+
+mod common;
+
+async fn incident_count(ctx: &common::TestContext) -> i64 {
+    sqlx::query_scalar::query_scalar("SELECT COUNT(*) FROM incidents")
+        .fetch_one(&ctx.pool)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_simulate_declare_traces_without_side_effects() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let state = common::mock_state(&ctx.pool, mock.clone());
+    let before = incident_count(&ctx).await;
+
+    handle_simulate(
+        state,
+        common::slash_command("simulate declare P1 Test Service", "U_ADMIN", "C_ADMIN"),
+    )
+    .await
+    .expect("Simulation failed");
+
+    let trace = mock.ephemeral_text();
+    assert!(trace.contains("1. Create channel #inc-"));
+    assert!(trace.contains("4. Prompt for required roles: Comms Lead, Scribe"));
+    assert!(trace.contains("post to <#C_GENERAL>, DM <@U_EXEC1>, DM <@U_EXEC2>"));
+    assert!(trace.contains("Preview posted to sandbox <#C_SANDBOX>"));
+
+    // Only the sandbox preview is posted: no channel, invites, pins or DMs
+    assert_eq!(mock.posted_channels(), vec!["C_SANDBOX"]);
+    assert!(mock.dm_recipients().is_empty());
+    assert!(!mock.calls().iter().any(|call| matches!(
+        call,
+        SlackCall::CreateConversation { .. }
+            | SlackCall::InviteUsers { .. }
+            | SlackCall::PinMessage { .. }
+    )));
+    assert_eq!(incident_count(&ctx).await, before);
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_simulate_requires_admin() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let state = common::mock_state(&ctx.pool, mock.clone());
+
+    handle_simulate(
+        state,
+        common::slash_command("simulate declare P1 Test Service", "U_RANDOM", "C_ADMIN"),
+    )
+    .await
+    .expect("Denial should be reported, not fail");
+
+    assert!(mock.ephemeral_text().contains("Only bot admins"));
+    assert!(mock.posted_channels().is_empty());
+
+    ctx.cleanup().await;
+}
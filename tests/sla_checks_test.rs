@@ -0,0 +1,116 @@
+use chrono::{Duration, Utc};
+use incident_bot::config::{AppConfig, SlaTarget};
+use incident_bot::db::models::{Audience, Severity, TimelineEventType};
+use incident_bot::db::queries::{sla, timeline};
+use incident_bot::jobs::sla_checks::check_slas;
+use incident_bot::services::incident::IncidentService;
+use incident_bot::slack::mock::{MockSlackClient, SlackCall};
+use incident_bot::AppState;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+mod common;
+
+#[tokio::test]
+async fn test_sla_breaches_are_recorded_and_posted_once() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let config = AppConfig {
+        sla: HashMap::from([(
+            "P1".to_string(),
+            SlaTarget {
+                ack_minutes: Some(5),
+                update_minutes: Some(30),
+                resolve_minutes: Some(240),
+            },
+        )]),
+        ..common::test_config()
+    };
+    let (job_sender, _job_receiver) = tokio::sync::mpsc::unbounded_channel();
+    let state = AppState::with_slack_client(ctx.pool.clone(), config, job_sender, mock.clone());
+
+    let incident_service = IncidentService::new(ctx.pool.clone());
+    let incident = incident_service
+        .create_incident(
+            "Payments failing".to_string(),
+            Severity::P1,
+            "Test Service".to_string(),
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .unwrap();
+    incident_service
+        .update_channel_id(incident.id, "C024SLA".to_string())
+        .await
+        .unwrap();
+    // P3s have no targets
+    incident_service
+        .create_incident(
+            "Slow dashboard".to_string(),
+            Severity::P3,
+            "Test Service".to_string(),
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .unwrap();
+
+    // Within the ack target
+    let declared_at = incident.declared_at;
+    assert_eq!(
+        check_slas(&state, declared_at + Duration::minutes(4))
+            .await
+            .unwrap(),
+        0
+    );
+
+    // Ack target missed: recorded and posted once
+    let now = declared_at + Duration::minutes(10);
+    assert_eq!(check_slas(&state, now).await.unwrap(), 1);
+    assert_eq!(check_slas(&state, now).await.unwrap(), 0);
+    let posts: Vec<String> = mock
+        .calls()
+        .into_iter()
+        .filter_map(|call| match call {
+            SlackCall::PostMessage { channel_id, blocks } if channel_id == "C024SLA" => {
+                blocks[0]["text"]["text"].as_str().map(str::to_string)
+            }
+            _ => None,
+        })
+        .collect();
+    assert_eq!(posts.len(), 1);
+    assert!(posts[0].contains("first status update"));
+    assert!(posts[0].contains("<@U024COMMANDER>"));
+
+    // The update cadence runs from the latest status update
+    timeline::log_event(
+        &ctx.pool,
+        incident.id,
+        TimelineEventType::StatusUpdate,
+        "Rolling back".to_string(),
+        "U024COMMANDER".to_string(),
+        Audience::Internal,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        check_slas(&state, Utc::now() + Duration::minutes(31))
+            .await
+            .unwrap(),
+        1
+    );
+
+    let breaches = sla::breaches_between(
+        &ctx.pool,
+        declared_at - Duration::hours(1),
+        Utc::now() + Duration::hours(1),
+    )
+    .await
+    .unwrap();
+    let metrics: Vec<&str> = breaches.iter().map(|b| b.metric.as_db_str()).collect();
+    assert_eq!(metrics, vec!["ack", "update"]);
+    assert!(breaches.iter().all(|b| b.incident_id == incident.id));
+    assert_eq!(breaches[0].severity, Severity::P1);
+    assert_eq!(breaches[0].target_minutes, 5);
+
+    ctx.cleanup().await;
+}
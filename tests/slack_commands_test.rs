@@ -0,0 +1,671 @@
+use incident_bot::db::models::{Audience, IncidentStatus, Severity, TimelineEventType};
+use incident_bot::jobs::Job;
+use incident_bot::services::incident::IncidentService;
+use incident_bot::services::timeline::TimelineService;
+use incident_bot::slack::mock::{MockSlackClient, SlackCall};
+use incident_bot::AppState;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+mod common;
+
+fn mock_state(ctx: &common::TestContext, mock: Arc<MockSlackClient>) -> AppState {
+    common::mock_state(&ctx.pool, mock)
+}
+
+async fn create_incident_in_channel(ctx: &common::TestContext, channel_id: &str) -> uuid::Uuid {
+    let incident_service = IncidentService::new(ctx.pool.clone());
+    let incident = incident_service
+        .create_incident(
+            "Command test".to_string(),
+            Severity::P3,
+            "Test Service".to_string(),
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .expect("Failed to create incident");
+    incident_service
+        .update_channel_id(incident.id, channel_id.to_string())
+        .await
+        .expect("Failed to set channel id");
+    incident.id
+}
+
+#[tokio::test]
+async fn test_status_command_posts_update_to_incident_channel() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    create_incident_in_channel(&ctx, "C_CMD_STATUS").await;
+
+    incident_bot::commands::status::handle_status(
+        mock_state(&ctx, mock.clone()),
+        common::slash_command(
+            "status Rolling back deploy",
+            "U024COMMANDER",
+            "C_CMD_STATUS",
+        ),
+    )
+    .await
+    .expect("Status command failed");
+
+    assert_eq!(mock.posted_channels(), vec!["C_CMD_STATUS"]);
+    assert!(mock.ephemeral_text().contains("Status update posted"));
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_status_command_without_message_shows_usage() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+
+    incident_bot::commands::status::handle_status(
+        mock_state(&ctx, mock.clone()),
+        common::slash_command("status", "U024COMMANDER", "C_CMD_USAGE"),
+    )
+    .await
+    .expect("Status command failed");
+
+    assert!(mock.posted_channels().is_empty());
+    assert!(mock
+        .ephemeral_text()
+        .contains("Usage: /incident status [--public|--internal] [--draft] [message]"));
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_status_command_classifies_public_and_internal_updates() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let (job_sender, mut job_receiver) = mpsc::unbounded_channel();
+    let state = AppState::with_slack_client(
+        ctx.pool.clone(),
+        common::test_config(),
+        job_sender,
+        mock.clone(),
+    );
+    let incident_id = create_incident_in_channel(&ctx, "C_CMD_AUDIENCE").await;
+
+    for text in [
+        "status Rolling back build 42",
+        "status --public We have identified the cause and are deploying a fix",
+    ] {
+        incident_bot::commands::status::handle_status(
+            state.clone(),
+            common::slash_command(text, "U024COMMANDER", "C_CMD_AUDIENCE"),
+        )
+        .await
+        .expect("Status command failed");
+    }
+
+    let updates: Vec<_> = TimelineService::new(ctx.pool.clone())
+        .get_timeline(incident_id)
+        .await
+        .expect("Failed to load timeline")
+        .into_iter()
+        .filter(|e| e.event_type == TimelineEventType::StatusUpdate)
+        .map(|e| (e.message, e.audience))
+        .collect();
+    assert_eq!(
+        updates,
+        vec![
+            ("Rolling back build 42".to_string(), Audience::Internal),
+            (
+                "We have identified the cause and are deploying a fix".to_string(),
+                Audience::Public
+            ),
+        ]
+    );
+
+    // Only the public update is queued for the status page
+    let mut public_updates = Vec::new();
+    while let Ok(job) = job_receiver.try_recv() {
+        if let Job::StatuspageIncidentUpdate { message, .. } = job {
+            public_updates.push(message);
+        }
+    }
+    assert_eq!(
+        public_updates,
+        vec!["We have identified the cause and are deploying a fix"]
+    );
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_status_command_rejects_non_commander() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    create_incident_in_channel(&ctx, "C_CMD_DENIED").await;
+
+    incident_bot::commands::status::handle_status(
+        mock_state(&ctx, mock.clone()),
+        common::slash_command("status Trying to help", "U024OTHER", "C_CMD_DENIED"),
+    )
+    .await
+    .expect("Status command failed");
+
+    assert!(mock.posted_channels().is_empty());
+    assert!(mock.ephemeral_text().contains("Permission denied"));
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_update_status_command_walks_the_state_machine() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let state = mock_state(&ctx, mock.clone());
+    let incident_id = create_incident_in_channel(&ctx, "C_CMD_UPDATE").await;
+    let incident_service = IncidentService::new(ctx.pool.clone());
+
+    incident_bot::commands::update_status::handle_update_status(
+        state.clone(),
+        common::slash_command("update-status identified", "U024COMMANDER", "C_CMD_UPDATE"),
+    )
+    .await
+    .expect("Update status command failed");
+
+    let incident = incident_service.get_by_id(incident_id).await.unwrap();
+    assert_eq!(incident.status, IncidentStatus::Identified);
+    assert_eq!(mock.posted_channels(), vec!["C_CMD_UPDATE"]);
+    assert!(mock
+        .ephemeral_text()
+        .contains("Status changed to identified"));
+    let timeline = TimelineService::new(ctx.pool.clone())
+        .get_timeline(incident_id)
+        .await
+        .unwrap();
+    assert!(timeline
+        .iter()
+        .any(|e| e.message == "Status changed from declared to identified"));
+
+    // Going backwards is rejected by the state machine
+    incident_bot::commands::update_status::handle_update_status(
+        state,
+        common::slash_command(
+            "update-status investigating",
+            "U024COMMANDER",
+            "C_CMD_UPDATE",
+        ),
+    )
+    .await
+    .expect("Update status command failed");
+
+    assert!(mock.ephemeral_text().contains(
+        "Cannot move incident from identified to investigating. Allowed: monitoring, resolved"
+    ));
+    assert_eq!(
+        incident_service
+            .get_by_id(incident_id)
+            .await
+            .unwrap()
+            .status,
+        IncidentStatus::Identified
+    );
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_status_and_severity_changes_refresh_pinned_summary() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let state = mock_state(&ctx, mock.clone());
+    let incident_id = create_incident_in_channel(&ctx, "C_CMD_PINNED").await;
+    incident_bot::db::queries::incidents::update_pinned_message_ts(
+        &ctx.pool,
+        incident_id,
+        "1699999999.000002",
+    )
+    .await
+    .unwrap();
+
+    incident_bot::commands::update_status::handle_update_status(
+        state.clone(),
+        common::slash_command("update-status identified", "U024COMMANDER", "C_CMD_PINNED"),
+    )
+    .await
+    .expect("Update status command failed");
+    incident_bot::commands::severity::handle_severity(
+        state,
+        common::slash_command("severity P2", "U024COMMANDER", "C_CMD_PINNED"),
+    )
+    .await
+    .expect("Severity command failed");
+
+    let headers: Vec<String> = mock
+        .calls()
+        .into_iter()
+        .filter_map(|call| match call {
+            SlackCall::UpdateMessage {
+                channel_id,
+                timestamp,
+                blocks,
+            } if channel_id == "C_CMD_PINNED" && timestamp == "1699999999.000002" => {
+                blocks[0]["text"]["text"].as_str().map(ToString::to_string)
+            }
+            _ => None,
+        })
+        .collect();
+    let incident = IncidentService::new(ctx.pool.clone())
+        .get_by_id(incident_id)
+        .await
+        .unwrap();
+    assert_eq!(
+        headers,
+        vec![
+            format!("🟢 {} · P3 (Medium) - Identified", incident.reference()),
+            format!("🟡 {} · P2 (High) - Identified", incident.reference())
+        ]
+    );
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_update_status_command_rejects_resolved_and_non_commander() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let state = mock_state(&ctx, mock.clone());
+    create_incident_in_channel(&ctx, "C_CMD_UPDATE_DENY").await;
+
+    incident_bot::commands::update_status::handle_update_status(
+        state.clone(),
+        common::slash_command(
+            "update-status resolved",
+            "U024COMMANDER",
+            "C_CMD_UPDATE_DENY",
+        ),
+    )
+    .await
+    .expect("Update status command failed");
+    assert!(mock
+        .ephemeral_text()
+        .contains("Usage: /incident update-status"));
+
+    incident_bot::commands::update_status::handle_update_status(
+        state,
+        common::slash_command("update-status monitoring", "U024OTHER", "C_CMD_UPDATE_DENY"),
+    )
+    .await
+    .expect("Update status command failed");
+    assert!(mock.ephemeral_text().contains("Permission denied"));
+    assert!(mock.posted_channels().is_empty());
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_timeline_last_shows_only_the_newest_events() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let state = mock_state(&ctx, mock.clone());
+    let incident_id = create_incident_in_channel(&ctx, "C_CMD_TIMELINE_LAST").await;
+    let timeline_service = TimelineService::new(ctx.pool.clone());
+    for note in [
+        "Paged database on-call",
+        "Failover started",
+        "Failover done",
+    ] {
+        timeline_service
+            .log_event(
+                incident_id,
+                TimelineEventType::Note,
+                note.to_string(),
+                "U024COMMANDER".to_string(),
+            )
+            .await
+            .unwrap();
+    }
+    let total = timeline_service
+        .get_timeline(incident_id)
+        .await
+        .unwrap()
+        .len();
+
+    incident_bot::commands::timeline::handle_timeline(
+        state,
+        common::slash_command("timeline --last 2", "U024RESPONDER", "C_CMD_TIMELINE_LAST"),
+    )
+    .await
+    .expect("Timeline failed");
+
+    let posts: Vec<String> = mock
+        .calls()
+        .into_iter()
+        .filter_map(|call| match call {
+            SlackCall::PostMessage { channel_id, blocks }
+                if channel_id == "C_CMD_TIMELINE_LAST" =>
+            {
+                Some(serde_json::to_string(&blocks).unwrap())
+            }
+            _ => None,
+        })
+        .collect();
+    assert_eq!(posts.len(), 1);
+    assert!(posts[0].contains(&format!("Showing the last 2 of {} events", total)));
+    assert!(posts[0].contains("Failover started"));
+    assert!(posts[0].contains("Failover done"));
+    assert!(!posts[0].contains("Paged database on-call"));
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_timeline_filter_rerenders_message_in_place() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let state = mock_state(&ctx, mock.clone());
+    let incident_id = create_incident_in_channel(&ctx, "C_CMD_TIMELINE").await;
+    TimelineService::new(ctx.pool.clone())
+        .log_event(
+            incident_id,
+            TimelineEventType::Note,
+            "Customer support notified".to_string(),
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .unwrap();
+
+    incident_bot::commands::timeline::handle_timeline_filter(
+        state,
+        "C_CMD_TIMELINE",
+        "1700000000.000100",
+        &format!("{}:note:24", incident_id),
+    )
+    .await
+    .expect("Timeline filter failed");
+
+    let updates: Vec<_> = mock
+        .calls()
+        .into_iter()
+        .filter_map(|call| match call {
+            SlackCall::UpdateMessage {
+                channel_id,
+                timestamp,
+                blocks,
+            } => Some((
+                channel_id,
+                timestamp,
+                serde_json::to_string(&blocks).unwrap(),
+            )),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(updates.len(), 1);
+    let (channel_id, timestamp, rendered) = &updates[0];
+    assert_eq!(channel_id, "C_CMD_TIMELINE");
+    assert_eq!(timestamp, "1700000000.000100");
+    assert!(rendered.contains("Customer support notified"));
+    // The declaration event is filtered out
+    assert!(!rendered.contains("Incident declared"));
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_timeline_edit_and_delete_are_limited_to_author_or_commander() {
+    let ctx = common::TestContext::new().await;
+    let incident_id = create_incident_in_channel(&ctx, "C_CMD_TIMELINE_EDIT").await;
+    let timeline_service = TimelineService::new(ctx.pool.clone());
+    // #1 is the declaration
+    timeline_service
+        .log_event(
+            incident_id,
+            TimelineEventType::Note,
+            "Pool size is 1 in 4.2.1".to_string(),
+            "U024RESPONDER".to_string(),
+        )
+        .await
+        .unwrap();
+    timeline_service
+        .log_status_update(
+            incident_id,
+            "Rollback in progres".to_string(),
+            "U024COMMANDER".to_string(),
+            Audience::Internal,
+        )
+        .await
+        .unwrap();
+
+    let run = |text: &'static str, user_id: &'static str| {
+        let ctx_pool = ctx.pool.clone();
+        async move {
+            let mock = Arc::new(MockSlackClient::new());
+            incident_bot::commands::timeline::handle_timeline(
+                common::mock_state(&ctx_pool, mock.clone()),
+                common::slash_command(text, user_id, "C_CMD_TIMELINE_EDIT"),
+            )
+            .await
+            .expect("Timeline command failed");
+            mock.ephemeral_text()
+        }
+    };
+
+    // Someone else's note needs the commander
+    assert!(run("timeline edit 2 Pool size is 10", "U024OTHER")
+        .await
+        .contains("Permission denied"));
+    assert!(run("timeline delete 3", "U024RESPONDER")
+        .await
+        .contains("Permission denied"));
+    // Authors can fix their own
+    assert!(
+        run("timeline edit 2 Pool size is 1 in 4.2.0", "U024RESPONDER")
+            .await
+            .contains("Edited timeline event #2")
+    );
+    // The commander can change anyone's, but not lifecycle events
+    assert!(run("timeline edit 3 Rollback in progress", "U024COMMANDER")
+        .await
+        .contains("Edited timeline event #3"));
+    assert!(run("timeline delete 2", "U024COMMANDER")
+        .await
+        .contains("Deleted timeline event #2"));
+    assert!(run("timeline edit 1 Not declared", "U024COMMANDER")
+        .await
+        .contains("Only status updates and notes"));
+    assert!(run("timeline edit 2 Again", "U024RESPONDER")
+        .await
+        .contains("has been deleted"));
+    assert!(run("timeline delete 9", "U024COMMANDER")
+        .await
+        .contains("No timeline event #9"));
+
+    let events = timeline_service.get_timeline(incident_id).await.unwrap();
+    assert_eq!(events.len(), 3);
+    assert!(events[1].deleted_at.is_some());
+    assert_eq!(events[1].message, "Pool size is 1 in 4.2.0");
+    assert_eq!(events[2].message, "Rollback in progress");
+    assert!(events[2].updated_at.is_some());
+
+    let (old, new): (serde_json::Value, serde_json::Value) = sqlx::query_as::query_as(
+        "SELECT old_state, new_state FROM audit_log WHERE incident_id = $1 AND action = 'timeline_event_edited' AND actor_id = 'U024COMMANDER'",
+    )
+    .bind(incident_id)
+    .fetch_one(&ctx.pool)
+    .await
+    .unwrap();
+    assert_eq!(old["message"], "Rollback in progres");
+    assert_eq!(new["message"], "Rollback in progress");
+
+    // Postmortems keep the deleted event's place but not its text
+    let markdown = timeline_service.format_as_markdown(&events);
+    assert!(markdown.contains("🗑️ _Deleted_"));
+    assert!(!markdown.contains("Pool size"));
+    assert!(markdown.contains("Rollback in progress _(edited)_"));
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_note_command_lets_any_participant_add_notes() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let incident_id = create_incident_in_channel(&ctx, "C_CMD_NOTE").await;
+
+    incident_bot::commands::note::handle_note(
+        mock_state(&ctx, mock.clone()),
+        common::slash_command(
+            "note  Customer reports started at 14:02, before the deploy",
+            "U024RESPONDER",
+            "C_CMD_NOTE",
+        ),
+    )
+    .await
+    .expect("Note command failed");
+
+    let events = TimelineService::new(ctx.pool.clone())
+        .get_timeline(incident_id)
+        .await
+        .unwrap();
+    let note = events
+        .iter()
+        .find(|e| e.event_type == TimelineEventType::Note)
+        .expect("Note missing from timeline");
+    assert_eq!(
+        note.message,
+        "Customer reports started at 14:02, before the deploy"
+    );
+    assert_eq!(note.posted_by, "U024RESPONDER");
+    assert_eq!(mock.posted_channels(), vec!["C_CMD_NOTE"]);
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_note_command_rejects_empty_notes_and_other_channels() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let incident_id = create_incident_in_channel(&ctx, "C_CMD_NOTE_EMPTY").await;
+    let state = mock_state(&ctx, mock.clone());
+
+    incident_bot::commands::note::handle_note(
+        state.clone(),
+        common::slash_command("note   ", "U024RESPONDER", "C_CMD_NOTE_EMPTY"),
+    )
+    .await
+    .expect("Note command failed");
+    assert!(mock
+        .ephemeral_text()
+        .contains("Usage: /incident note <text>"));
+
+    incident_bot::commands::note::handle_note(
+        state.clone(),
+        common::slash_command(
+            &format!("note {}", "x".repeat(2001)),
+            "U024RESPONDER",
+            "C_CMD_NOTE_EMPTY",
+        ),
+    )
+    .await
+    .expect("Note command failed");
+    assert!(mock
+        .ephemeral_text()
+        .contains("Notes can be at most 2000 characters"));
+
+    incident_bot::commands::note::handle_note(
+        state,
+        common::slash_command("note Unrelated", "U024RESPONDER", "C_NOT_AN_INCIDENT"),
+    )
+    .await
+    .expect("Note command failed");
+    assert!(mock
+        .ephemeral_text()
+        .contains("No incident found in this channel"));
+
+    assert!(mock.posted_channels().is_empty());
+    let events = TimelineService::new(ctx.pool.clone())
+        .get_timeline(incident_id)
+        .await
+        .unwrap();
+    assert!(events
+        .iter()
+        .all(|e| e.event_type != TimelineEventType::Note));
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_summary_command_previews_privately_and_posts_for_the_commander() {
+    let ctx = common::TestContext::new().await;
+    let incident_id = create_incident_in_channel(&ctx, "C_CMD_SUMMARY").await;
+    let incident_service = IncidentService::new(ctx.pool.clone());
+    for update in [
+        "Paging DBA",
+        "Replica lagging",
+        "Failing over",
+        "Error rate falling",
+    ] {
+        incident_service
+            .post_status_update(
+                incident_id,
+                update.to_string(),
+                "U024COMMANDER".to_string(),
+                Audience::Internal,
+            )
+            .await
+            .unwrap();
+    }
+
+    // No channel: only the caller sees it, with the latest three updates
+    let mock = Arc::new(MockSlackClient::new());
+    incident_bot::commands::summary::handle_summary(
+        mock_state(&ctx, mock.clone()),
+        common::slash_command("summary", "U024BYSTANDER", "C_CMD_SUMMARY"),
+    )
+    .await
+    .unwrap();
+    assert!(mock.posted_channels().is_empty());
+    let preview = format!("{:?}", mock.calls());
+    assert!(preview.contains("📋 Command test"));
+    assert!(preview.contains("Error rate falling"));
+    assert!(!preview.contains("Paging DBA"));
+
+    let mock = Arc::new(MockSlackClient::new());
+    incident_bot::commands::summary::handle_summary(
+        mock_state(&ctx, mock.clone()),
+        common::slash_command(
+            "summary <#C024EXEC|exec-updates>",
+            "U024BYSTANDER",
+            "C_CMD_SUMMARY",
+        ),
+    )
+    .await
+    .unwrap();
+    assert!(mock.posted_channels().is_empty());
+    assert!(mock.ephemeral_text().contains("Permission denied"));
+
+    let mock = Arc::new(MockSlackClient::new());
+    incident_bot::commands::summary::handle_summary(
+        mock_state(&ctx, mock.clone()),
+        common::slash_command(
+            "summary <#C024EXEC|exec-updates>",
+            "U024COMMANDER",
+            "C_CMD_SUMMARY",
+        ),
+    )
+    .await
+    .unwrap();
+    assert_eq!(mock.posted_channels(), vec!["C024EXEC"]);
+    let posted = mock
+        .calls()
+        .into_iter()
+        .find_map(|call| match call {
+            SlackCall::PostMessage { blocks, .. } => Some(blocks),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(posted[1]["fields"][1]["text"], "*Status:*\n🔴 declared");
+    assert!(posted[2]["text"]["text"]
+        .as_str()
+        .unwrap()
+        .contains("Failing over"));
+    assert!(mock
+        .ephemeral_text()
+        .contains("Summary posted to <#C024EXEC>"));
+
+    ctx.cleanup().await;
+}
@@ -0,0 +1,291 @@
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use axum::routing::post;
+use axum::Router;
+use hmac::{Hmac, Mac};
+use incident_bot::config::AppConfig;
+use incident_bot::slack::events::{
+    handle_interaction, handle_slash_command, COMMANDS_PATH, INTERACTIONS_PATH,
+};
+use incident_bot::slack::mock::{MockSlackClient, SlackCall};
+use incident_bot::slack::rate_limit::limit_slack_requests;
+use incident_bot::AppState;
+use serde_json::Value;
+use sha2::Sha256;
+use std::sync::Arc;
+use tower::ServiceExt;
+
+mod common;
+
+fn app(state: AppState) -> Router {
+    Router::new()
+        .route(COMMANDS_PATH, post(handle_slash_command))
+        .route(INTERACTIONS_PATH, post(handle_interaction))
+        .route_layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            limit_slack_requests,
+        ))
+        .with_state(state)
+}
+
+fn signed_request(path: &str, body: &str, secret: &[u8]) -> Request<Body> {
+    let timestamp = chrono::Utc::now().timestamp().to_string();
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).unwrap();
+    mac.update(format!("v0:{}:{}", timestamp, body).as_bytes());
+    let signature = format!("v0={}", hex::encode(mac.finalize().into_bytes()));
+    Request::post(path)
+        .header("X-Slack-Request-Timestamp", timestamp)
+        .header("X-Slack-Signature", signature)
+        .header("Content-Type", "application/x-www-form-urlencoded")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+fn help_command(user_id: &str) -> String {
+    serde_urlencoded::to_string([
+        ("command", "/incident"),
+        ("text", "help"),
+        ("team_id", "T_LIMIT"),
+        ("user_id", user_id),
+        ("channel_id", "C_LIMIT"),
+        ("response_url", "https://hooks.slack.test/response"),
+        ("trigger_id", "trigger"),
+    ])
+    .unwrap()
+}
+
+async fn body_json(response: axum::response::Response) -> Option<Value> {
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    serde_json::from_slice(&bytes).ok()
+}
+
+fn is_rate_limited(body: &Option<Value>) -> bool {
+    body.as_ref()
+        .and_then(|b| b["text"].as_str())
+        .is_some_and(|text| text.contains("Easy there"))
+}
+
+#[tokio::test]
+async fn test_command_flood_gets_an_ephemeral_message_per_user() {
+    let ctx = common::TestContext::new().await;
+    let config = AppConfig {
+        slack_rate_limit_per_minute: 2,
+        ..common::test_config()
+    };
+    let (job_sender, _job_receiver) = tokio::sync::mpsc::unbounded_channel();
+    let state = AppState::with_slack_client(
+        ctx.pool.clone(),
+        config,
+        job_sender,
+        Arc::new(MockSlackClient::new()),
+    );
+    let app = app(state);
+
+    for _ in 0..2 {
+        let response = app
+            .clone()
+            .oneshot(signed_request(
+                COMMANDS_PATH,
+                &help_command("U_FLOOD"),
+                b"test-secret",
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(!is_rate_limited(&body_json(response).await));
+    }
+
+    let response = app
+        .clone()
+        .oneshot(signed_request(
+            COMMANDS_PATH,
+            &help_command("U_FLOOD"),
+            b"test-secret",
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = body_json(response).await;
+    assert!(is_rate_limited(&body));
+    assert_eq!(body.unwrap()["response_type"], "ephemeral");
+
+    // Forged requests are still rejected by the handler, not the limiter
+    let response = app
+        .clone()
+        .oneshot(signed_request(
+            COMMANDS_PATH,
+            &help_command("U_FLOOD"),
+            b"wrong-secret",
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    // Someone else in the workspace is unaffected
+    let response = app
+        .clone()
+        .oneshot(signed_request(
+            COMMANDS_PATH,
+            &help_command("U_CALM"),
+            b"test-secret",
+        ))
+        .await
+        .unwrap();
+    assert!(!is_rate_limited(&body_json(response).await));
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_interaction_flood_is_told_through_response_url() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let config = AppConfig {
+        slack_rate_limit_per_minute: 1,
+        ..common::test_config()
+    };
+    let (job_sender, _job_receiver) = tokio::sync::mpsc::unbounded_channel();
+    let state = AppState::with_slack_client(ctx.pool.clone(), config, job_sender, mock.clone());
+    let app = app(state);
+
+    // An unknown block action, so the first click does nothing of note
+    let payload = serde_json::json!({
+        "type": "block_actions",
+        "team": { "id": "T_LIMIT" },
+        "user": { "id": "U_CLICKER" },
+        "response_url": "https://hooks.slack.test/clicks",
+        "trigger_id": "trigger",
+        "actions": [{ "action_id": "rate_limit_test_noop", "value": "x" }]
+    });
+    let body = serde_urlencoded::to_string([("payload", payload.to_string())]).unwrap();
+
+    for _ in 0..2 {
+        let response = app
+            .clone()
+            .oneshot(signed_request(INTERACTIONS_PATH, &body, b"test-secret"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    // The notice is posted in a spawned task
+    let mut notices = Vec::new();
+    for _ in 0..50 {
+        notices = mock
+            .calls()
+            .into_iter()
+            .filter_map(|call| match call {
+                SlackCall::PostToResponseUrl {
+                    response_url,
+                    blocks,
+                } if response_url == "https://hooks.slack.test/clicks" => {
+                    Some(serde_json::to_string(&blocks).unwrap())
+                }
+                _ => None,
+            })
+            .filter(|blocks| blocks.contains("Easy there"))
+            .collect();
+        if !notices.is_empty() {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+    }
+    assert_eq!(notices.len(), 1);
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_declare_modal_is_not_rate_limited() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let config = AppConfig {
+        slack_rate_limit_per_minute: 1,
+        ..common::test_config()
+    };
+    let (job_sender, _job_receiver) = tokio::sync::mpsc::unbounded_channel();
+    let state = AppState::with_slack_client(ctx.pool.clone(), config, job_sender, mock.clone());
+    let app = app(state);
+
+    // Spend the allowance, then keep typing in the declare modal
+    let response = app
+        .clone()
+        .oneshot(signed_request(
+            COMMANDS_PATH,
+            &help_command("U_TYPIST"),
+            b"test-secret",
+        ))
+        .await
+        .unwrap();
+    assert!(!is_rate_limited(&body_json(response).await));
+    let values = serde_json::json!({
+        "title_block": { "title_input": { "value": "Checkout down" } },
+        "severity_block": { "severity_select": { "selected_option": { "value": "P2" } } },
+        "service_block": { "service_select": { "selected_option": { "value": "Test Service" } } },
+        "commander_block": { "commander_select": { "selected_user": null } }
+    });
+    for _ in 0..3 {
+        let payload = serde_json::json!({
+            "type": "block_actions",
+            "team": { "id": "T_LIMIT" },
+            "user": { "id": "U_TYPIST" },
+            "view": {
+                "id": "V_TYPIST",
+                "callback_id": "declare_incident_modal",
+                "state": { "values": values }
+            },
+            "actions": [{ "action_id": "title_input", "value": "Checkout down" }]
+        });
+        let body = serde_urlencoded::to_string([("payload", payload.to_string())]).unwrap();
+        let response = app
+            .clone()
+            .oneshot(signed_request(INTERACTIONS_PATH, &body, b"test-secret"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    // The submission still declares the incident rather than being dropped
+    let payload = serde_json::json!({
+        "type": "view_submission",
+        "team": { "id": "T_LIMIT" },
+        "user": { "id": "U_TYPIST" },
+        "trigger_id": "trigger-submit",
+        "view": {
+            "id": "V_TYPIST",
+            "callback_id": "declare_incident_modal",
+            "state": { "values": values }
+        }
+    });
+    let body = serde_urlencoded::to_string([("payload", payload.to_string())]).unwrap();
+    let response = app
+        .clone()
+        .oneshot(signed_request(INTERACTIONS_PATH, &body, b"test-secret"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let mut declared = 0;
+    for _ in 0..100 {
+        declared = sqlx::query_scalar::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM incidents WHERE commander_id = 'U_TYPIST'",
+        )
+        .fetch_one(&ctx.pool)
+        .await
+        .unwrap();
+        if declared > 0 {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+    }
+    assert_eq!(declared, 1);
+    assert!(!mock.calls().iter().any(|call| matches!(
+        call,
+        SlackCall::PostToResponseUrl { blocks, .. }
+            if serde_json::to_string(blocks).unwrap().contains("Easy there")
+    )));
+
+    ctx.cleanup().await;
+}
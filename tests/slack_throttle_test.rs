@@ -0,0 +1,222 @@
+use incident_bot::db::models::{
+    NotificationRecord, NotificationStatus, NotificationType, Severity,
+};
+use incident_bot::services::incident::IncidentService;
+use incident_bot::services::notification::NotificationService;
+use incident_bot::slack::client::SlackApi;
+use incident_bot::slack::mock::{MockSlackClient, SlackCall};
+use incident_bot::slack::throttle::SlackThrottle;
+use serde_json::Value;
+use std::sync::Arc;
+use std::time::Duration;
+
+mod common;
+
+/// Throttled for a moment after each 429, so the test can wait it out.
+const COOLDOWN: Duration = Duration::from_millis(300);
+
+async fn fan_out_replies(
+    ctx: &common::TestContext,
+    incident_id: uuid::Uuid,
+) -> Vec<NotificationRecord> {
+    sqlx::query_as::query_as::<_, NotificationRecord>(
+        "SELECT * FROM incident_notifications WHERE incident_id = $1 AND recipient = 'C_GENERAL' ORDER BY sent_at, created_at",
+    )
+    .bind(incident_id)
+    .fetch_all(&ctx.pool)
+    .await
+    .expect("Failed to load notifications")
+}
+
+/// Blocks of every thread reply, in call order.
+fn reply_blocks(mock: &MockSlackClient) -> Vec<Vec<Value>> {
+    mock.calls()
+        .into_iter()
+        .filter_map(|call| match call {
+            SlackCall::PostThreadReply { blocks, .. } => Some(blocks),
+            _ => None,
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_rate_limited_fan_out_replies_are_deferred_then_batched() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new().with_throttle(SlackThrottle::new(
+        Duration::from_millis(10),
+        Duration::from_millis(40),
+        COOLDOWN,
+    )));
+    let service = NotificationService::new(
+        ctx.pool.clone(),
+        mock.clone(),
+        Arc::new(common::test_config()),
+    );
+
+    let incident_service = IncidentService::new(ctx.pool.clone());
+    let incident = incident_service
+        .create_incident(
+            "Throttle test".to_string(),
+            Severity::P1,
+            "Test Service".to_string(),
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .expect("Failed to create incident");
+    incident_service
+        .update_channel_id(incident.id, "C_INC_THROTTLED".to_string())
+        .await
+        .expect("Failed to set channel id");
+    let incident = incident_service.get_by_id(incident.id).await.unwrap();
+
+    // A 429 on the first exec DM throttles the rest of the fan-out, which
+    // still goes out
+    mock.fail_dm_to("U_EXEC1", "ratelimited");
+    service
+        .notify_incident_declared(&incident, vec![])
+        .await
+        .expect("Failed to notify");
+    assert!(mock.throttle().is_throttled());
+    assert_eq!(mock.dm_recipients(), vec!["U_EXEC1", "U_EXEC2"]);
+
+    // Meanwhile the incident channel is posted to, the fan-out thread waits
+    service
+        .notify_status_update(&incident, vec![])
+        .await
+        .expect("Failed to notify");
+    service
+        .notify_status_update(&incident, vec![])
+        .await
+        .expect("Failed to notify");
+    assert!(mock.thread_replies().is_empty());
+    assert_eq!(
+        mock.posted_channels(),
+        vec![
+            "C_INC_THROTTLED",
+            "C_GENERAL",
+            "C_INC_THROTTLED",
+            "C_INC_THROTTLED"
+        ]
+    );
+    let deferred: Vec<NotificationStatus> = fan_out_replies(&ctx, incident.id)
+        .await
+        .into_iter()
+        .skip(1)
+        .map(|record| record.status)
+        .collect();
+    assert_eq!(
+        deferred,
+        vec![NotificationStatus::Pending, NotificationStatus::Pending]
+    );
+
+    // Once it eases, the next update carries the held-back ones in one reply
+    tokio::time::sleep(COOLDOWN).await;
+    assert!(!mock.throttle().is_throttled());
+    service
+        .notify_status_update(&incident, vec![])
+        .await
+        .expect("Failed to notify");
+    let replies = reply_blocks(&mock);
+    assert_eq!(replies.len(), 1);
+    assert_eq!(
+        replies[0][0]["elements"][0]["text"],
+        "⏳ 3 updates held back while Slack was rate limiting notifications"
+    );
+    let records = fan_out_replies(&ctx, incident.id).await;
+    assert_eq!(records.len(), 4);
+    assert!(records.iter().all(|r| r.status == NotificationStatus::Sent
+        && r.notification_type == NotificationType::SlackChannel));
+
+    // Updates deferred with nothing after them are flushed by the job
+    mock.throttle()
+        .record_rate_limited("chat.postMessage", None);
+    service
+        .notify_status_update(&incident, vec![])
+        .await
+        .expect("Failed to notify");
+    assert_eq!(service.flush_deferred().await.unwrap(), 0);
+    tokio::time::sleep(COOLDOWN).await;
+    assert_eq!(service.flush_deferred().await.unwrap(), 1);
+    assert_eq!(reply_blocks(&mock).len(), 2);
+    assert!(!mock.thread_replies()[1].2);
+    assert!(fan_out_replies(&ctx, incident.id)
+        .await
+        .iter()
+        .all(|r| r.status == NotificationStatus::Sent));
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_deferred_replies_keep_their_broadcast_flag() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new().with_throttle(SlackThrottle::new(
+        Duration::from_millis(10),
+        Duration::from_millis(40),
+        COOLDOWN,
+    )));
+    let service = NotificationService::new(
+        ctx.pool.clone(),
+        mock.clone(),
+        Arc::new(common::test_config()),
+    );
+    let incident =
+        common::incident_in_channel(&ctx, Severity::P1, "Test Service", "C_INC_FLAGS").await;
+    service
+        .notify_incident_declared(&incident, vec![])
+        .await
+        .expect("Failed to notify");
+
+    // A plain update and a resolution (broadcast to the channel) wait together
+    mock.throttle()
+        .record_rate_limited("chat.postMessage", None);
+    service
+        .notify_status_update(&incident, vec![])
+        .await
+        .expect("Failed to notify");
+    service
+        .notify_resolution(&incident, vec![])
+        .await
+        .expect("Failed to notify");
+    assert!(mock.thread_replies().is_empty());
+    let flags: Vec<bool> = fan_out_replies(&ctx, incident.id)
+        .await
+        .into_iter()
+        .skip(1)
+        .map(|record| record.broadcast)
+        .collect();
+    assert_eq!(flags, vec![false, true]);
+
+    // Flushed as separate replies, the resolution still broadcast
+    tokio::time::sleep(COOLDOWN).await;
+    assert_eq!(service.flush_deferred().await.unwrap(), 2);
+    let replies: Vec<bool> = mock
+        .thread_replies()
+        .into_iter()
+        .map(|(_, _, broadcast)| broadcast)
+        .collect();
+    assert_eq!(replies, vec![false, true]);
+
+    // Retrying a deferred reply posts it in the thread, not top-level
+    mock.throttle()
+        .record_rate_limited("chat.postMessage", None);
+    service
+        .notify_canceled(&incident, vec![])
+        .await
+        .expect("Failed to notify");
+    let pending = fan_out_replies(&ctx, incident.id)
+        .await
+        .into_iter()
+        .find(|record| record.status == NotificationStatus::Pending)
+        .expect("reply deferred");
+    let posted_before = mock.posted_channels().len();
+    service.retry(pending.id, "U_ADMIN").await.unwrap();
+    assert_eq!(mock.posted_channels().len(), posted_before);
+    let thread_ts = &mock.thread_replies()[0].1;
+    let (channel_id, retried_ts, broadcast) = mock.thread_replies().pop().unwrap();
+    assert_eq!(channel_id, "C_GENERAL");
+    assert_eq!(&retried_ts, thread_ts);
+    assert!(broadcast);
+
+    ctx.cleanup().await;
+}
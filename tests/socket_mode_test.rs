@@ -0,0 +1,178 @@
+use incident_bot::db::models::Severity;
+use incident_bot::services::incident::IncidentService;
+use incident_bot::slack::mock::{MockSlackClient, SlackCall};
+use incident_bot::slack::socket_mode::{handle_message, SocketAction};
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+
+mod common;
+
+/// Wait for spawned handlers to record at least `count` Slack calls.
+async fn wait_for_calls(mock: &MockSlackClient, count: usize) {
+    for _ in 0..50 {
+        if mock.calls().len() >= count {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+}
+
+#[tokio::test]
+async fn test_socket_mode_dispatches_slash_commands_and_acks() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let state = common::mock_state(&ctx.pool, mock.clone());
+    let incident_service = IncidentService::new(ctx.pool.clone());
+    let incident = incident_service
+        .create_incident(
+            "Socket Mode test".to_string(),
+            Severity::P3,
+            "Test Service".to_string(),
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .expect("Failed to create incident");
+    incident_service
+        .update_channel_id(incident.id, "C_SOCKET".to_string())
+        .await
+        .expect("Failed to set channel id");
+
+    assert_eq!(
+        handle_message(&state, r#"{"type":"hello","num_connections":1}"#).await,
+        SocketAction::Ignore
+    );
+
+    let envelope = json!({
+        "type": "slash_commands",
+        "envelope_id": "env-123",
+        "accepts_response_payload": true,
+        "payload": {
+            "command": "/incident",
+            "text": "status Failover complete",
+            "user_id": "U024COMMANDER",
+            "channel_id": "C_SOCKET",
+            "response_url": "https://hooks.slack.test/response",
+            "trigger_id": "trigger-123",
+            "team_id": "T024"
+        }
+    });
+    assert_eq!(
+        handle_message(&state, &envelope.to_string()).await,
+        SocketAction::Ack(r#"{"envelope_id":"env-123"}"#.to_string())
+    );
+
+    // Same handler as the HTTP route: channel post, then ephemeral ack
+    wait_for_calls(&mock, 2).await;
+    assert_eq!(mock.posted_channels(), vec!["C_SOCKET"]);
+    assert!(mock.calls().iter().any(|call| matches!(
+        call,
+        SlackCall::PostToResponseUrl { blocks, .. }
+            if blocks[0]["text"]["text"] == "✅ Status update posted"
+    )));
+
+    assert_eq!(
+        handle_message(
+            &state,
+            r#"{"type":"disconnect","reason":"refresh_requested"}"#
+        )
+        .await,
+        SocketAction::Reconnect
+    );
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_socket_mode_events_share_retry_dedup() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let state = common::mock_state(&ctx.pool, mock.clone());
+
+    for (envelope_id, retry_attempt) in [("env-1", 0), ("env-2", 1)] {
+        let envelope = json!({
+            "type": "events_api",
+            "envelope_id": envelope_id,
+            "retry_attempt": retry_attempt,
+            "payload": {
+                "type": "event_callback",
+                "event_id": "Ev_SOCKET_1",
+                "event": { "type": "app_home_opened", "user": "U_SOCKET", "tab": "home" }
+            }
+        });
+        assert_eq!(
+            handle_message(&state, &envelope.to_string()).await,
+            SocketAction::Ack(format!(r#"{{"envelope_id":"{}"}}"#, envelope_id))
+        );
+    }
+
+    wait_for_calls(&mock, 1).await;
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    let views = mock
+        .calls()
+        .into_iter()
+        .filter(|call| matches!(call, SlackCall::PublishView { .. }))
+        .count();
+    assert_eq!(views, 1);
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_socket_mode_redelivered_commands_and_submissions_run_once() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let state = common::mock_state(&ctx.pool, mock.clone());
+
+    let submission = json!({
+        "type": "view_submission",
+        "user": { "id": "U024COMMANDER" },
+        "trigger_id": "trigger-declare-1",
+        "view": {
+            "callback_id": "declare_incident_modal",
+            "private_metadata": "",
+            "state": { "values": {
+                "title_block": { "title_input": { "value": "Checkout is down" } },
+                "severity_block": { "severity_select": { "selected_option": { "value": "P3" } } },
+                "service_block": { "service_select": { "selected_option": { "value": "Test Service" } } },
+                "commander_block": { "commander_select": { "selected_user": null } }
+            } }
+        }
+    });
+    let command = json!({
+        "command": "/incident",
+        "text": "coaching on",
+        "user_id": "U024COMMANDER",
+        "channel_id": "C_ANYWHERE",
+        "response_url": "https://hooks.slack.test/response",
+        "trigger_id": "trigger-coaching-1"
+    });
+    for (envelope_id, retry_attempt) in [("env-1", 0), ("env-2", 1)] {
+        for (envelope_type, payload) in [("interactive", &submission), ("slash_commands", &command)]
+        {
+            let envelope = json!({
+                "type": envelope_type,
+                "envelope_id": format!("{}-{}", envelope_type, envelope_id),
+                "retry_attempt": retry_attempt,
+                "payload": payload
+            });
+            handle_message(&state, &envelope.to_string()).await;
+        }
+        // Let the first delivery claim its trigger before the retry arrives
+        tokio::time::sleep(Duration::from_millis(200)).await;
+    }
+
+    let declared: i64 = sqlx::query_scalar::query_scalar("SELECT COUNT(*) FROM incidents")
+        .fetch_one(&ctx.pool)
+        .await
+        .unwrap();
+    assert_eq!(declared, 1);
+    let replies = mock
+        .calls()
+        .into_iter()
+        .filter(|call| matches!(call, SlackCall::PostToResponseUrl { .. }))
+        .count();
+    assert_eq!(replies, 1);
+
+    ctx.cleanup().await;
+}
@@ -0,0 +1,127 @@
+use chrono::Duration;
+use incident_bot::db::models::{Audience, Severity};
+use incident_bot::jobs::stale_reminder::{enqueue_stale, execute};
+use incident_bot::jobs::Job;
+use incident_bot::services::incident::IncidentService;
+use incident_bot::slack::mock::MockSlackClient;
+use incident_bot::AppState;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+mod common;
+
+fn drain(receiver: &mut mpsc::UnboundedReceiver<Job>) -> Vec<Job> {
+    let mut jobs = Vec::new();
+    while let Ok(job) = receiver.try_recv() {
+        jobs.push(job);
+    }
+    jobs
+}
+
+#[tokio::test]
+async fn test_stale_incidents_are_nudged_once_per_threshold() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let (job_sender, mut job_receiver) = mpsc::unbounded_channel();
+    let state = AppState::with_slack_client(
+        ctx.pool.clone(),
+        common::test_config(),
+        job_sender,
+        mock.clone(),
+    );
+    // P1 threshold is 30 min in test_config; P4 has none
+    let p1 = common::incident_in_channel(&ctx, Severity::P1, "Test Service", "C_STALE_P1").await;
+    common::incident_in_channel(&ctx, Severity::P4, "Test Service", "C_STALE_P4").await;
+    let start = p1.declared_at;
+
+    assert_eq!(
+        enqueue_stale(&state, start + Duration::minutes(10))
+            .await
+            .unwrap(),
+        0
+    );
+    assert_eq!(
+        enqueue_stale(&state, start + Duration::minutes(31))
+            .await
+            .unwrap(),
+        1
+    );
+    // Reminding restarts the clock
+    assert_eq!(
+        enqueue_stale(&state, start + Duration::minutes(45))
+            .await
+            .unwrap(),
+        0
+    );
+    assert_eq!(
+        enqueue_stale(&state, start + Duration::minutes(62))
+            .await
+            .unwrap(),
+        1
+    );
+
+    let jobs = drain(&mut job_receiver);
+    assert_eq!(jobs.len(), 2);
+    let Job::StaleIncidentReminder {
+        incident_id,
+        last_activity_at,
+    } = jobs[0].clone()
+    else {
+        panic!("Unexpected job {:?}", jobs[0]);
+    };
+    assert_eq!(incident_id, p1.id);
+
+    execute(&state, incident_id, last_activity_at)
+        .await
+        .expect("Reminder failed");
+    assert_eq!(mock.posted_channels(), vec!["C_STALE_P1"]);
+    assert_eq!(mock.dm_recipients(), vec!["U024COMMANDER"]);
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_timeline_activity_and_resolution_prevent_reminders() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let (job_sender, _job_receiver) = mpsc::unbounded_channel();
+    let state = AppState::with_slack_client(
+        ctx.pool.clone(),
+        common::test_config(),
+        job_sender,
+        mock.clone(),
+    );
+    let incident_service = IncidentService::new(ctx.pool.clone());
+    let incident =
+        common::incident_in_channel(&ctx, Severity::P2, "Test Service", "C_STALE_P2").await;
+
+    // A status update resets the clock (P2 threshold is 60 min)
+    incident_service
+        .post_status_update(
+            incident.id,
+            "Still investigating".to_string(),
+            "U024COMMANDER".to_string(),
+            Audience::Internal,
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        enqueue_stale(&state, chrono::Utc::now() + Duration::minutes(59))
+            .await
+            .unwrap(),
+        0
+    );
+
+    incident_service
+        .resolve_incident(incident.id, "U024COMMANDER".to_string())
+        .await
+        .unwrap();
+    assert_eq!(
+        enqueue_stale(&state, chrono::Utc::now() + Duration::minutes(600))
+            .await
+            .unwrap(),
+        0
+    );
+
+    ctx.cleanup().await;
+}
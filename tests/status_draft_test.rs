@@ -0,0 +1,206 @@
+use incident_bot::commands::status::{handle_approve_draft, handle_discard_draft, handle_status};
+use incident_bot::db::models::{Audience, Incident, Severity, TimelineEventType};
+use incident_bot::db::queries::{roles, timeline};
+use incident_bot::services::incident::IncidentService;
+use incident_bot::slack::blocks::STATUS_DRAFT_APPROVE_ACTION;
+use incident_bot::slack::mock::{MockSlackClient, SlackCall};
+use serde_json::Value;
+use std::sync::Arc;
+
+mod common;
+
+async fn incident_with_scribe(ctx: &common::TestContext) -> Incident {
+    let incident_service = IncidentService::new(ctx.pool.clone());
+    let incident = incident_service
+        .create_incident(
+            "Checkout latency".to_string(),
+            Severity::P2,
+            "Test Service".to_string(),
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .unwrap();
+    incident_service
+        .update_channel_id(incident.id, "C_DRAFTS".to_string())
+        .await
+        .unwrap();
+    roles::claim_role(&ctx.pool, incident.id, "scribe", "U_SCRIBE")
+        .await
+        .unwrap()
+        .expect("scribe claimed");
+    incident_service.get_by_id(incident.id).await.unwrap()
+}
+
+fn dms_to(mock: &MockSlackClient, user: &str) -> Vec<Vec<Value>> {
+    mock.calls()
+        .into_iter()
+        .filter_map(|call| match call {
+            SlackCall::SendDm { user_id, blocks } if user_id == user => Some(blocks),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Draft ID on the newest approval prompt DMed to the commander.
+fn latest_draft_id(mock: &MockSlackClient) -> String {
+    dms_to(mock, "U024COMMANDER")
+        .into_iter()
+        .rev()
+        .flatten()
+        .find_map(|block| {
+            block["elements"].as_array().and_then(|elements| {
+                elements
+                    .iter()
+                    .find(|e| e["action_id"] == STATUS_DRAFT_APPROVE_ACTION)
+                    .map(|e| e["value"].as_str().unwrap().to_string())
+            })
+        })
+        .expect("approval prompt")
+}
+
+async fn status_updates(ctx: &common::TestContext, incident: &Incident) -> usize {
+    timeline::get_timeline(&ctx.pool, incident.id)
+        .await
+        .unwrap()
+        .iter()
+        .filter(|e| e.event_type == TimelineEventType::StatusUpdate && !e.message.contains("<@"))
+        .count()
+}
+
+#[tokio::test]
+async fn test_scribe_draft_is_posted_after_commander_approval() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let state = common::mock_state(&ctx.pool, mock.clone());
+    let incident = incident_with_scribe(&ctx).await;
+
+    handle_status(
+        state.clone(),
+        common::slash_command("status --draft Still digging", "U_BYSTANDER", "C_DRAFTS"),
+    )
+    .await
+    .unwrap();
+    assert!(mock
+        .last_response()
+        .contains("Only the incident's scribe can draft"));
+    assert!(dms_to(&mock, "U024COMMANDER").is_empty());
+
+    handle_status(
+        state.clone(),
+        common::slash_command(
+            "status --draft --public Fix deployed, monitoring",
+            "U_SCRIBE",
+            "C_DRAFTS",
+        ),
+    )
+    .await
+    .unwrap();
+    assert!(mock
+        .last_response()
+        .contains("Draft sent to <@U024COMMANDER> for approval"));
+    let prompt = serde_json::to_string(&dms_to(&mock, "U024COMMANDER")).unwrap();
+    assert!(prompt.contains("<@U_SCRIBE> drafted a status update for *Checkout latency*"));
+    assert!(prompt.contains(">Fix deployed, monitoring"));
+    assert!(prompt.contains("public status page"));
+    assert_eq!(
+        status_updates(&ctx, &incident).await,
+        0,
+        "held until approved"
+    );
+
+    let draft_id = latest_draft_id(&mock);
+    handle_approve_draft(state.clone(), "U_SCRIBE".to_string(), &draft_id, None)
+        .await
+        .unwrap();
+    assert!(serde_json::to_string(&dms_to(&mock, "U_SCRIBE"))
+        .unwrap()
+        .contains("Permission denied"));
+    assert_eq!(status_updates(&ctx, &incident).await, 0);
+
+    handle_approve_draft(
+        state.clone(),
+        "U024COMMANDER".to_string(),
+        &draft_id,
+        Some("https://hooks.slack.test/approve".to_string()),
+    )
+    .await
+    .unwrap();
+    assert!(mock
+        .last_response()
+        .contains("Posted <@U_SCRIBE>'s status update"));
+
+    let events = timeline::get_timeline(&ctx.pool, incident.id)
+        .await
+        .unwrap();
+    let posted = events
+        .iter()
+        .find(|e| e.message == "Fix deployed, monitoring")
+        .expect("draft posted");
+    assert_eq!(posted.posted_by, "U024COMMANDER");
+    assert_eq!(posted.drafted_by.as_deref(), Some("U_SCRIBE"));
+    assert_eq!(posted.audience, Audience::Public);
+    assert!(serde_json::to_string(&dms_to(&mock, "U_SCRIBE"))
+        .unwrap()
+        .contains("<@U024COMMANDER> approved and posted your status update"));
+
+    // A second click posts nothing more
+    handle_approve_draft(state, "U024COMMANDER".to_string(), &draft_id, None)
+        .await
+        .unwrap();
+    assert_eq!(status_updates(&ctx, &incident).await, 1);
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_commander_discards_draft_and_posts_directly_with_draft_flag() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let state = common::mock_state(&ctx.pool, mock.clone());
+    let incident = incident_with_scribe(&ctx).await;
+
+    handle_status(
+        state.clone(),
+        common::slash_command(
+            "status --draft Root cause is the cache",
+            "U_SCRIBE",
+            "C_DRAFTS",
+        ),
+    )
+    .await
+    .unwrap();
+    let draft_id = latest_draft_id(&mock);
+    handle_discard_draft(state.clone(), "U024COMMANDER".to_string(), &draft_id, None)
+        .await
+        .unwrap();
+    assert!(serde_json::to_string(&dms_to(&mock, "U024COMMANDER"))
+        .unwrap()
+        .contains("Draft discarded"));
+    assert!(serde_json::to_string(&dms_to(&mock, "U_SCRIBE"))
+        .unwrap()
+        .contains("discarded your draft status update"));
+
+    // Approving after a discard does not post it
+    handle_approve_draft(state.clone(), "U024COMMANDER".to_string(), &draft_id, None)
+        .await
+        .unwrap();
+    assert_eq!(status_updates(&ctx, &incident).await, 0);
+
+    // The commander needs no approval
+    handle_status(
+        state,
+        common::slash_command("status --draft Cache flushed", "U024COMMANDER", "C_DRAFTS"),
+    )
+    .await
+    .unwrap();
+    let events = timeline::get_timeline(&ctx.pool, incident.id)
+        .await
+        .unwrap();
+    let posted = events
+        .iter()
+        .find(|e| e.message == "Cache flushed")
+        .expect("posted directly");
+    assert_eq!(posted.drafted_by, None);
+
+    ctx.cleanup().await;
+}
@@ -0,0 +1,110 @@
+use incident_bot::commands::resolved::resolve_and_announce;
+use incident_bot::db::models::{IncidentStatus, Severity};
+use incident_bot::db::queries::statuspage::set_statuspage_incident_id;
+use incident_bot::jobs::statuspage_sync::{enqueue_incident_create, enqueue_status_change};
+use incident_bot::jobs::Job;
+use incident_bot::services::incident::IncidentService;
+use incident_bot::slack::mock::MockSlackClient;
+use incident_bot::AppState;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+mod common;
+
+const MAPPED_SERVICE: &str = "Statuspage Incident Service";
+
+fn drain(receiver: &mut mpsc::UnboundedReceiver<Job>) -> Vec<Job> {
+    let mut jobs = Vec::new();
+    while let Ok(job) = receiver.try_recv() {
+        jobs.push(job);
+    }
+    jobs
+}
+
+#[tokio::test]
+async fn test_statuspage_incident_opened_updated_and_resolved() {
+    let ctx = common::TestContext::new().await;
+    sqlx::query::query(
+        "INSERT INTO statuspage_mappings (service_name, component_id) VALUES ($1, 'cmp_sp_incident')",
+    )
+    .bind(MAPPED_SERVICE)
+    .execute(&ctx.pool)
+    .await
+    .unwrap();
+
+    let (job_sender, mut job_receiver) = mpsc::unbounded_channel();
+    let state = AppState::with_slack_client(
+        ctx.pool.clone(),
+        common::test_config(),
+        job_sender,
+        Arc::new(MockSlackClient::new()),
+    );
+    let incident_service = IncidentService::new(ctx.pool.clone());
+    let mapped = incident_service
+        .create_incident(
+            "Checkout errors".to_string(),
+            Severity::P3,
+            MAPPED_SERVICE.to_string(),
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .unwrap();
+    let unmapped = incident_service
+        .create_incident(
+            "Internal tooling slow".to_string(),
+            Severity::P3,
+            "Test Service".to_string(),
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .unwrap();
+
+    // Only services with a component mapping get a public incident
+    enqueue_incident_create(&ctx.pool, &state.job_sender, &mapped).await;
+    enqueue_incident_create(&ctx.pool, &state.job_sender, &unmapped).await;
+    let jobs = drain(&mut job_receiver);
+    assert_eq!(jobs.len(), 1);
+    assert!(
+        matches!(jobs[0], Job::StatuspageIncidentCreate { incident_id } if incident_id == mapped.id)
+    );
+
+    // Nothing to update until the Statuspage incident exists
+    let monitoring = incident_service
+        .transition_status(
+            mapped.id,
+            IncidentStatus::Monitoring,
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .unwrap();
+    enqueue_status_change(&state.job_sender, &monitoring);
+    assert!(drain(&mut job_receiver).is_empty());
+
+    set_statuspage_incident_id(&ctx.pool, mapped.id, "sp_123")
+        .await
+        .unwrap();
+    let monitoring = incident_service.get_by_id(mapped.id).await.unwrap();
+    assert_eq!(monitoring.statuspage_incident_id.as_deref(), Some("sp_123"));
+    enqueue_status_change(&state.job_sender, &monitoring);
+    match drain(&mut job_receiver).as_slice() {
+        [Job::StatuspageIncidentUpdate { message, .. }] => assert_eq!(
+            message,
+            "A fix for degraded performance affecting Statuspage Incident Service has been deployed and we are monitoring the results."
+        ),
+        other => panic!("Expected one update job, got {:?}", other),
+    }
+
+    resolve_and_announce(&state, &monitoring, "U024COMMANDER")
+        .await
+        .unwrap();
+    assert!(drain(&mut job_receiver).iter().any(
+        |job| matches!(job, Job::StatuspageIncidentResolve { incident_id } if *incident_id == mapped.id)
+    ));
+
+    sqlx::query::query("DELETE FROM statuspage_mappings WHERE service_name = $1")
+        .bind(MAPPED_SERVICE)
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+    ctx.cleanup().await;
+}
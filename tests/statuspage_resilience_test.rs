@@ -0,0 +1,207 @@
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::routing::{patch, post};
+use axum::{Json, Router};
+use incident_bot::adapters::statuspage::StatuspageClient;
+use incident_bot::db::models::{IncidentId, Severity};
+use incident_bot::jobs::statuspage_retry::retry_once;
+use incident_bot::jobs::statuspage_sync::execute_or_defer;
+use incident_bot::jobs::Job;
+use incident_bot::services::incident::IncidentService;
+use incident_bot::slack::client::RetryPolicy;
+use incident_bot::slack::mock::MockSlackClient;
+use serde_json::{json, Value};
+use sqlx_postgres::PgPool;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+mod common;
+
+/// Fake Statuspage that answers 503 to the first `outage_requests` requests.
+#[derive(Clone, Default)]
+struct FakeStatuspage {
+    outage_requests: Arc<AtomicUsize>,
+    requests: Arc<AtomicUsize>,
+    /// Bodies of successful incident updates, in arrival order
+    updates: Arc<Mutex<Vec<String>>>,
+}
+
+impl FakeStatuspage {
+    fn available(&self) -> bool {
+        self.requests.fetch_add(1, Ordering::SeqCst) >= self.outage_requests.load(Ordering::SeqCst)
+    }
+}
+
+async fn start(fake: FakeStatuspage) -> String {
+    let app = Router::new()
+        .route(
+            "/v1/pages/{page_id}/incidents",
+            post(|State(fake): State<FakeStatuspage>| async move {
+                if !fake.available() {
+                    return (StatusCode::SERVICE_UNAVAILABLE, Json(json!({})));
+                }
+                (StatusCode::CREATED, Json(json!({ "id": "sp_incident_1" })))
+            }),
+        )
+        .route(
+            "/v1/pages/{page_id}/incidents/{incident_id}",
+            patch(
+                |State(fake): State<FakeStatuspage>,
+                 Path((_, incident_id)): Path<(String, String)>,
+                 Json(body): Json<Value>| async move {
+                    if !fake.available() {
+                        return (StatusCode::SERVICE_UNAVAILABLE, Json(json!({})));
+                    }
+                    fake.updates
+                        .lock()
+                        .unwrap()
+                        .push(body["incident"]["body"].as_str().unwrap().to_string());
+                    (StatusCode::OK, Json(json!({ "id": incident_id })))
+                },
+            ),
+        )
+        .with_state(fake);
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+    format!("http://{}/v1", addr)
+}
+
+fn client(base_url: String, cooldown: Duration) -> StatuspageClient {
+    StatuspageClient::new("sp-key".to_string(), "page1".to_string())
+        .with_base_url(base_url)
+        .with_request_timeout(Duration::from_secs(2))
+        .with_retry_policy(RetryPolicy {
+            max_retries: 1,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(5),
+        })
+        .with_circuit_breaker(2, cooldown)
+}
+
+async fn deferred_jobs(pool: &PgPool, incident_id: IncidentId) -> i64 {
+    sqlx::query_scalar::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM failed_jobs WHERE incident_id = $1",
+    )
+    .bind(incident_id)
+    .fetch_one(pool)
+    .await
+    .unwrap()
+}
+
+async fn statuspage_incident_id(pool: &PgPool, incident_id: IncidentId) -> Option<String> {
+    IncidentService::new(pool.clone())
+        .get_by_id(incident_id)
+        .await
+        .unwrap()
+        .statuspage_incident_id
+}
+
+#[tokio::test]
+async fn test_transient_statuspage_errors_are_retried() {
+    let ctx = common::TestContext::new().await;
+    let fake = FakeStatuspage::default();
+    fake.outage_requests.store(1, Ordering::SeqCst);
+    let statuspage = client(start(fake.clone()).await, Duration::from_secs(60));
+
+    let incident = IncidentService::new(ctx.pool.clone())
+        .create_incident(
+            "Login errors".to_string(),
+            Severity::P2,
+            "Test Service".to_string(),
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .unwrap();
+
+    execute_or_defer(
+        &statuspage,
+        &ctx.pool,
+        &Job::StatuspageIncidentCreate {
+            incident_id: incident.id,
+        },
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(fake.requests.load(Ordering::SeqCst), 2);
+    assert_eq!(
+        statuspage_incident_id(&ctx.pool, incident.id)
+            .await
+            .as_deref(),
+        Some("sp_incident_1")
+    );
+    assert_eq!(deferred_jobs(&ctx.pool, incident.id).await, 0);
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_failed_syncs_wait_out_the_circuit_breaker_and_replay_in_order() {
+    let ctx = common::TestContext::new().await;
+    let state = common::mock_state(&ctx.pool, Arc::new(MockSlackClient::new()));
+    let fake = FakeStatuspage::default();
+    fake.outage_requests.store(usize::MAX, Ordering::SeqCst);
+    let statuspage = client(start(fake.clone()).await, Duration::from_millis(300));
+
+    let incident = IncidentService::new(ctx.pool.clone())
+        .create_incident(
+            "Checkout is down".to_string(),
+            Severity::P1,
+            "Test Service".to_string(),
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .unwrap();
+    let jobs = [
+        Job::StatuspageIncidentCreate {
+            incident_id: incident.id,
+        },
+        Job::StatuspageIncidentUpdate {
+            incident_id: incident.id,
+            message: "We have found the cause.".to_string(),
+        },
+        Job::StatuspageIncidentUpdate {
+            incident_id: incident.id,
+            message: "A fix is rolling out.".to_string(),
+        },
+    ];
+
+    // Two failed calls (each retried once) open the circuit; the third job
+    // is deferred without reaching Statuspage
+    for job in &jobs {
+        execute_or_defer(&statuspage, &ctx.pool, job).await.unwrap();
+    }
+    assert_eq!(fake.requests.load(Ordering::SeqCst), 4);
+    assert!(statuspage.is_circuit_open());
+    assert_eq!(deferred_jobs(&ctx.pool, incident.id).await, 3);
+
+    // Nothing is retried while the circuit is open
+    assert_eq!(retry_once(&state, &statuspage).await.unwrap(), 0);
+    assert_eq!(fake.requests.load(Ordering::SeqCst), 4);
+
+    // Statuspage recovers; after the cooldown the jobs run in order
+    fake.outage_requests.store(0, Ordering::SeqCst);
+    tokio::time::sleep(Duration::from_millis(350)).await;
+    assert!(!statuspage.is_circuit_open());
+    assert_eq!(retry_once(&state, &statuspage).await.unwrap(), 3);
+    assert_eq!(deferred_jobs(&ctx.pool, incident.id).await, 0);
+    assert_eq!(
+        statuspage_incident_id(&ctx.pool, incident.id)
+            .await
+            .as_deref(),
+        Some("sp_incident_1")
+    );
+    assert_eq!(
+        *fake.updates.lock().unwrap(),
+        vec![
+            "We have found the cause.".to_string(),
+            "A fix is rolling out.".to_string()
+        ]
+    );
+
+    ctx.cleanup().await;
+}
@@ -0,0 +1,155 @@
+use incident_bot::commands::subscribe::{handle_subscribe, handle_unsubscribe};
+use incident_bot::commands::update_status::change_status_and_announce;
+use incident_bot::config::AppConfig;
+use incident_bot::db::models::{IncidentStatus, Severity};
+use incident_bot::services::incident::IncidentService;
+use incident_bot::services::notification::NotificationService;
+use incident_bot::slack::mock::{MockSlackClient, SlackCall};
+use incident_bot::AppState;
+use std::sync::Arc;
+
+mod common;
+
+/// A service of its own, so no other test's incidents reach its subscribers.
+const SERVICE: &str = "Subscribed Service";
+
+fn digests(mock: &MockSlackClient) -> Vec<(String, String)> {
+    mock.calls()
+        .into_iter()
+        .filter_map(|call| match call {
+            SlackCall::SendDm { user_id, blocks } => {
+                Some((user_id, serde_json::to_string(&blocks).unwrap()))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_subscribers_get_digests_until_they_unsubscribe() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let config = AppConfig {
+        services: vec!["Test Service".to_string(), SERVICE.to_string()],
+        ..common::test_config()
+    };
+    let (job_sender, _job_receiver) = tokio::sync::mpsc::unbounded_channel();
+    let state = AppState::with_slack_client(ctx.pool.clone(), config, job_sender, mock.clone());
+
+    let incident_service = IncidentService::new(ctx.pool.clone());
+    let incident = incident_service
+        .create_incident(
+            "Payouts delayed".to_string(),
+            Severity::P3,
+            SERVICE.to_string(),
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .unwrap();
+    incident_service
+        .update_channel_id(incident.id, "C_SUBSCRIBED".to_string())
+        .await
+        .unwrap();
+
+    handle_subscribe(
+        state.clone(),
+        common::slash_command("subscribe", "U_SUB_PM", "C_SUBSCRIBED"),
+    )
+    .await
+    .unwrap();
+    assert!(mock.last_response().contains("Subscribed to this incident"));
+    handle_subscribe(
+        state.clone(),
+        common::slash_command("subscribe", "U_SUB_PM", "C_SUBSCRIBED"),
+    )
+    .await
+    .unwrap();
+    assert!(mock
+        .last_response()
+        .contains("already subscribed to this incident"));
+
+    handle_subscribe(
+        state.clone(),
+        common::slash_command(
+            "subscribe service:subscribed service",
+            "U_SUB_SUPPORT",
+            "D_SUPPORT",
+        ),
+    )
+    .await
+    .unwrap();
+    assert!(mock
+        .last_response()
+        .contains("Subscribed to incidents on *Subscribed Service*"));
+    handle_subscribe(
+        state.clone(),
+        common::slash_command("subscribe service:Billing", "U_SUB_SUPPORT", "D_SUPPORT"),
+    )
+    .await
+    .unwrap();
+    assert!(mock.last_response().contains("Unknown service 'Billing'"));
+
+    // Status updates and status changes reach both, each saying why
+    let incident = incident_service.get_by_id(incident.id).await.unwrap();
+    NotificationService::new(ctx.pool.clone(), mock.clone(), state.config.clone())
+        .notify_status_update(&incident, vec![])
+        .await
+        .unwrap();
+    change_status_and_announce(
+        &state,
+        &incident,
+        IncidentStatus::Investigating,
+        "U024COMMANDER",
+    )
+    .await
+    .unwrap();
+    let sent = digests(&mock);
+    assert_eq!(
+        sent.iter().map(|(u, _)| u.as_str()).collect::<Vec<_>>(),
+        vec!["U_SUB_PM", "U_SUB_SUPPORT", "U_SUB_PM", "U_SUB_SUPPORT"]
+    );
+    assert!(sent[0].1.contains("*Payouts delayed* in <#C_SUBSCRIBED>"));
+    assert!(sent[0].1.contains(
+        "You're subscribed to this incident. Stop with `/incident unsubscribe` in <#C_SUBSCRIBED>."
+    ));
+    assert!(sent[1].1.contains("incidents on *Subscribed Service*"));
+    assert!(sent[2].1.contains("investigating"));
+
+    handle_subscribe(
+        state.clone(),
+        common::slash_command("subscribe list", "U_SUB_SUPPORT", "D_SUPPORT"),
+    )
+    .await
+    .unwrap();
+    assert!(mock
+        .last_response()
+        .contains("Every incident on *Subscribed Service*"));
+
+    handle_unsubscribe(
+        state.clone(),
+        common::slash_command("unsubscribe", "U_SUB_PM", "C_SUBSCRIBED"),
+    )
+    .await
+    .unwrap();
+    assert!(mock
+        .last_response()
+        .contains("Unsubscribed from this incident"));
+    handle_unsubscribe(
+        state.clone(),
+        common::slash_command(
+            "unsubscribe service:Subscribed Service",
+            "U_SUB_SUPPORT",
+            "D_SUPPORT",
+        ),
+    )
+    .await
+    .unwrap();
+    let before = digests(&mock).len();
+    NotificationService::new(ctx.pool.clone(), mock.clone(), state.config.clone())
+        .notify_status_update(&incident, vec![])
+        .await
+        .unwrap();
+    assert_eq!(digests(&mock).len(), before);
+
+    ctx.cleanup().await;
+}
@@ -0,0 +1,208 @@
+use incident_bot::commands::template::{handle_template, handle_template_submission};
+use incident_bot::db::models::Severity;
+use incident_bot::db::queries::templates;
+use incident_bot::slack::events::{ViewPayload, ViewState};
+use incident_bot::slack::mock::{MockSlackClient, SlackCall};
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+mod common;
+
+const NAME: &str = "template-command-test";
+
+fn submission(private_metadata: String, name: &str, title: &str) -> ViewPayload {
+    let values = json!({
+        "name_block": { "name_input": { "value": name } },
+        "title_block": { "title_input": { "value": title } },
+        "severity_block": { "severity_select": { "selected_option": { "value": "P2" } } },
+        "service_block": { "service_select": { "selected_option": { "value": "Test Service" } } },
+        "description_block": { "description_input": { "value": "Queue depth over {{threshold}}" } },
+    });
+    ViewPayload {
+        id: "V_TEMPLATE".to_string(),
+        callback_id: incident_bot::slack::modals::TEMPLATE_MODAL_CALLBACK_ID.to_string(),
+        private_metadata,
+        state: ViewState {
+            values: values.as_object().unwrap().clone(),
+        },
+    }
+}
+
+/// mrkdwn text of every reply, by response URL or DM.
+fn replies(mock: &MockSlackClient) -> Vec<String> {
+    mock.calls()
+        .into_iter()
+        .filter_map(|call| match call {
+            SlackCall::PostToResponseUrl { blocks, .. } | SlackCall::SendDm { blocks, .. } => {
+                Some(blocks)
+            }
+            _ => None,
+        })
+        .flatten()
+        .filter_map(|block| block["text"]["text"].as_str().map(ToString::to_string))
+        .collect()
+}
+
+fn opened_modals(mock: &MockSlackClient) -> Vec<Value> {
+    mock.calls()
+        .into_iter()
+        .filter_map(|call| match call {
+            SlackCall::OpenModal { view, .. } => Some(view),
+            _ => None,
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_admin_creates_edits_and_disables_templates() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let state = common::mock_state(&ctx.pool, mock.clone());
+
+    handle_template(
+        state.clone(),
+        common::slash_command("template create", "U_ADMIN", "C_TEMPLATES"),
+    )
+    .await
+    .unwrap();
+    let modal = &opened_modals(&mock)[0];
+    assert_eq!(modal["private_metadata"], "");
+
+    handle_template_submission(
+        state.clone(),
+        submission(String::new(), NAME, "{{region}} queue backlog"),
+        "U_ADMIN".to_string(),
+    )
+    .await
+    .unwrap();
+    let created = templates::get_template_by_name(&ctx.pool, NAME)
+        .await
+        .unwrap()
+        .expect("template created");
+    assert_eq!(created.severity, Severity::P2);
+    assert_eq!(created.affected_service.as_deref(), Some("Test Service"));
+    assert_eq!(created.placeholders(), vec!["region", "threshold"]);
+
+    // A second create with the same name is refused
+    handle_template_submission(
+        state.clone(),
+        submission(String::new(), NAME, "Duplicate"),
+        "U_ADMIN".to_string(),
+    )
+    .await
+    .unwrap();
+    assert!(replies(&mock).last().unwrap().contains("already exists"));
+
+    // Edit opens the modal for the stored template and keeps its name
+    handle_template(
+        state.clone(),
+        common::slash_command(&format!("template edit {}", NAME), "U_ADMIN", "C_TEMPLATES"),
+    )
+    .await
+    .unwrap();
+    let modal = opened_modals(&mock).pop().unwrap();
+    assert_eq!(modal["private_metadata"], created.id.to_string());
+    handle_template_submission(
+        state.clone(),
+        submission(created.id.to_string(), "ignored-name", "Queue backlog"),
+        "U_ADMIN".to_string(),
+    )
+    .await
+    .unwrap();
+    let edited = templates::get_template_by_name(&ctx.pool, NAME)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(edited.title, "Queue backlog");
+
+    // Disabled templates leave the declare modal but stay listed
+    handle_template(
+        state.clone(),
+        common::slash_command(
+            &format!("template disable {}", NAME),
+            "U_ADMIN",
+            "C_TEMPLATES",
+        ),
+    )
+    .await
+    .unwrap();
+    assert!(templates::get_template_by_name(&ctx.pool, NAME)
+        .await
+        .unwrap()
+        .is_none());
+    handle_template(
+        state.clone(),
+        common::slash_command("template list", "U_ADMIN", "C_TEMPLATES"),
+    )
+    .await
+    .unwrap();
+    assert!(replies(&mock)
+        .iter()
+        .any(|r| r.contains(&format!("`{}`", NAME)) && r.contains("_disabled_")));
+
+    // Re-creating a disabled template enables it again
+    handle_template_submission(
+        state.clone(),
+        submission(String::new(), NAME, "Queue backlog again"),
+        "U_ADMIN".to_string(),
+    )
+    .await
+    .unwrap();
+    let recreated = templates::get_template_by_name(&ctx.pool, NAME)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(recreated.id, created.id);
+    assert_eq!(recreated.title, "Queue backlog again");
+
+    sqlx::query::query("DELETE FROM incident_templates WHERE name = $1")
+        .bind(NAME)
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_template_management_is_admin_only() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let state = common::mock_state(&ctx.pool, mock.clone());
+
+    handle_template(
+        state.clone(),
+        common::slash_command(
+            "template disable database-outage",
+            "U024RESPONDER",
+            "C_TEMPLATES",
+        ),
+    )
+    .await
+    .unwrap();
+    handle_template_submission(
+        state.clone(),
+        submission(String::new(), NAME, "Sneaky template"),
+        "U024RESPONDER".to_string(),
+    )
+    .await
+    .unwrap();
+
+    let replies = replies(&mock);
+    assert_eq!(replies.len(), 2);
+    assert!(replies
+        .iter()
+        .all(|r| r.contains("Only bot admins can manage templates")));
+    assert!(opened_modals(&mock).is_empty());
+    assert!(
+        templates::get_template_by_name(&ctx.pool, "database-outage")
+            .await
+            .unwrap()
+            .is_some()
+    );
+    assert!(templates::get_any_template_by_name(&ctx.pool, NAME)
+        .await
+        .unwrap()
+        .is_none());
+
+    ctx.cleanup().await;
+}
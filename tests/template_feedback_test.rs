@@ -0,0 +1,281 @@
+use incident_bot::commands::declare::handle_modal_submission;
+use incident_bot::commands::resolved::resolve_and_announce;
+use incident_bot::commands::template::{handle_create_from_incident, handle_template_submission};
+use incident_bot::db::models::{Audience, Incident, Severity};
+use incident_bot::db::queries::templates;
+use incident_bot::services::incident::IncidentService;
+use incident_bot::services::timeline::TimelineService;
+use incident_bot::slack::blocks::TEMPLATE_FROM_INCIDENT_ACTION;
+use incident_bot::slack::events::ViewPayload;
+use incident_bot::slack::mock::{MockSlackClient, SlackCall};
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+mod common;
+
+const NAME: &str = "checkout-502s";
+
+/// DMs sent to `user_id`, as blocks.
+fn dms_to(mock: &MockSlackClient, user_id: &str) -> Vec<Vec<Value>> {
+    mock.calls()
+        .into_iter()
+        .filter_map(|call| match call {
+            SlackCall::SendDm {
+                user_id: to,
+                blocks,
+            } if to == user_id => Some(blocks),
+            _ => None,
+        })
+        .collect()
+}
+
+fn is_template_prompt(blocks: &[Value]) -> bool {
+    blocks
+        .iter()
+        .any(|b| b["elements"][0]["action_id"] == TEMPLATE_FROM_INCIDENT_ACTION)
+}
+
+fn opened_modals(mock: &MockSlackClient) -> Vec<Value> {
+    mock.calls()
+        .into_iter()
+        .filter_map(|call| match call {
+            SlackCall::OpenModal { view, .. } => Some(view),
+            _ => None,
+        })
+        .collect()
+}
+
+fn modal_block<'a>(modal: &'a Value, block_id: &str) -> &'a Value {
+    modal["blocks"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|b| b["block_id"] == block_id)
+        .unwrap_or_else(|| panic!("missing block {}", block_id))
+}
+
+async fn resolved_incident(ctx: &common::TestContext, state: &incident_bot::AppState) -> Incident {
+    let incident_service = IncidentService::new(ctx.pool.clone());
+    let incident = incident_service
+        .create_incident(
+            "Checkout 502s".to_string(),
+            Severity::P2,
+            "Test Service".to_string(),
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .expect("Failed to create incident");
+    incident_service
+        .update_channel_id(incident.id, "C_INC_FEEDBACK".to_string())
+        .await
+        .expect("Failed to set channel id");
+    TimelineService::new(ctx.pool.clone())
+        .log_status_update(
+            incident.id,
+            "Card payments failing at checkout".to_string(),
+            "U024COMMANDER".to_string(),
+            Audience::Internal,
+        )
+        .await
+        .unwrap();
+    let incident = incident_service.get_by_id(incident.id).await.unwrap();
+    resolve_and_announce(state, &incident, "U024COMMANDER")
+        .await
+        .expect("Failed to resolve")
+}
+
+#[tokio::test]
+async fn test_commander_creates_template_from_resolved_incident() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let state = common::mock_state(&ctx.pool, mock.clone());
+
+    // Resolving an incident declared without a template prompts its commander
+    let incident = resolved_incident(&ctx, &state).await;
+    let prompts: Vec<_> = dms_to(&mock, "U024COMMANDER")
+        .into_iter()
+        .filter(|blocks| is_template_prompt(blocks))
+        .collect();
+    assert_eq!(prompts.len(), 1);
+    assert_eq!(
+        prompts[0][1]["elements"][0]["value"],
+        incident.id.to_string()
+    );
+
+    // Only the commander (or an admin) may take it up
+    handle_create_from_incident(
+        state.clone(),
+        "U024RESPONDER".to_string(),
+        &incident.id.to_string(),
+        Some("trigger-feedback".to_string()),
+        None,
+    )
+    .await
+    .unwrap();
+    assert!(opened_modals(&mock).is_empty());
+    assert!(dms_to(&mock, "U024RESPONDER")[0][0]["text"]["text"]
+        .as_str()
+        .unwrap()
+        .contains("Permission denied"));
+
+    // The modal opens with values inferred from the incident
+    handle_create_from_incident(
+        state.clone(),
+        "U024COMMANDER".to_string(),
+        &incident.id.to_string(),
+        Some("trigger-feedback".to_string()),
+        None,
+    )
+    .await
+    .unwrap();
+    let modal = opened_modals(&mock).pop().expect("template modal opened");
+    assert_eq!(
+        modal["private_metadata"],
+        format!("incident:{}", incident.id)
+    );
+    assert_eq!(
+        modal_block(&modal, "name_block")["element"]["initial_value"],
+        NAME
+    );
+    assert_eq!(
+        modal_block(&modal, "title_block")["element"]["initial_value"],
+        "Checkout 502s"
+    );
+    assert_eq!(
+        modal_block(&modal, "severity_block")["element"]["initial_option"]["value"],
+        "P2"
+    );
+    assert_eq!(
+        modal_block(&modal, "service_block")["element"]["initial_option"]["value"],
+        "Test Service"
+    );
+    assert_eq!(
+        modal_block(&modal, "description_block")["element"]["initial_value"],
+        "Card payments failing at checkout"
+    );
+
+    // Accepting creates the template, linked to the incident, without the
+    // commander being an admin
+    let view: ViewPayload = serde_json::from_value(json!({
+        "callback_id": "template_modal",
+        "private_metadata": modal["private_metadata"],
+        "state": { "values": {
+            "name_block": { "name_input": { "value": NAME } },
+            "title_block": { "title_input": { "value": "Checkout 502s" } },
+            "severity_block": { "severity_select": { "selected_option": { "value": "P2" } } },
+            "service_block": { "service_select": { "selected_option": { "value": "Test Service" } } },
+            "description_block": { "description_input": { "value": "Card payments failing at checkout" } }
+        } }
+    }))
+    .unwrap();
+    handle_template_submission(state.clone(), view, "U024COMMANDER".to_string())
+        .await
+        .unwrap();
+    let template = templates::get_template_by_name(&ctx.pool, NAME)
+        .await
+        .unwrap()
+        .expect("template created");
+    assert_eq!(template.source_incident_id, Some(incident.id));
+    assert_eq!(template.severity, Severity::P2);
+
+    // The prompt can't be taken up twice
+    handle_create_from_incident(
+        state.clone(),
+        "U024COMMANDER".to_string(),
+        &incident.id.to_string(),
+        Some("trigger-feedback".to_string()),
+        None,
+    )
+    .await
+    .unwrap();
+    assert_eq!(opened_modals(&mock).len(), 1);
+    assert!(
+        dms_to(&mock, "U024COMMANDER").last().unwrap()[0]["text"]["text"]
+            .as_str()
+            .unwrap()
+            .contains("already created")
+    );
+
+    // Incidents declared from the template are tagged with it, and aren't
+    // prompted for on resolution
+    let view: ViewPayload = serde_json::from_value(json!({
+        "callback_id": "declare_incident_modal",
+        "private_metadata": "",
+        "state": { "values": {
+            "template_block": { "template_select": { "selected_option": { "value": NAME } } },
+            "title_block": { "title_input": { "value": null } },
+            "severity_block": { "severity_select": { "selected_option": null } },
+            "service_block": { "service_select": { "selected_option": null } },
+            "commander_block": { "commander_select": { "selected_user": null } }
+        } }
+    }))
+    .unwrap();
+    handle_modal_submission(state.clone(), view, "U024TEMPLATER".to_string())
+        .await
+        .unwrap();
+    let declared = sqlx::query_as::query_as::<_, Incident>(
+        "SELECT * FROM incidents WHERE commander_id = 'U024TEMPLATER'",
+    )
+    .fetch_one(&ctx.pool)
+    .await
+    .unwrap();
+    assert_eq!(declared.template_id, Some(template.id));
+    assert_eq!(declared.title, "Checkout 502s");
+    resolve_and_announce(&state, &declared, "U024TEMPLATER")
+        .await
+        .unwrap();
+    assert!(!dms_to(&mock, "U024TEMPLATER")
+        .iter()
+        .any(|blocks| is_template_prompt(blocks)));
+
+    // Template-originated incidents are counted apart from the rest
+    let usage = templates::template_usage(&ctx.pool).await.unwrap();
+    let from_template = usage
+        .iter()
+        .find(|u| u.template_id == Some(template.id))
+        .expect("usage of the template");
+    assert_eq!((from_template.incidents, from_template.resolved), (1, 1));
+    assert!(from_template.mean_minutes.is_some());
+    let untemplated = usage.iter().find(|u| u.template_id.is_none()).unwrap();
+    assert_eq!((untemplated.incidents, untemplated.resolved), (1, 1));
+
+    sqlx::query::query("DELETE FROM incident_templates WHERE name = $1")
+        .bind(NAME)
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_quiet_incidents_are_not_prompted() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let state = common::mock_state(&ctx.pool, mock.clone());
+
+    let incident_service = IncidentService::new(ctx.pool.clone());
+    let incident = incident_service
+        .create_incident(
+            "Credential leak".to_string(),
+            Severity::P1,
+            "Test Service".to_string(),
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .expect("Failed to create incident");
+    sqlx::query::query("UPDATE incidents SET is_quiet = true WHERE id = $1")
+        .bind(incident.id)
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+    let incident = incident_service.get_by_id(incident.id).await.unwrap();
+    resolve_and_announce(&state, &incident, "U024COMMANDER")
+        .await
+        .unwrap();
+
+    assert!(!dms_to(&mock, "U024COMMANDER")
+        .iter()
+        .any(|blocks| is_template_prompt(blocks)));
+
+    ctx.cleanup().await;
+}
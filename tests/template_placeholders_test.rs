@@ -0,0 +1,284 @@
+use incident_bot::commands::declare::{apply_template, handle_modal_submission};
+use incident_bot::db::models::{Severity, TimelineEventType};
+use incident_bot::db::queries::timeline::get_timeline;
+use incident_bot::slack::events::ViewPayload;
+use incident_bot::slack::mock::{MockSlackClient, SlackCall};
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+mod common;
+
+const TEMPLATE: &str = "placeholder-test-region";
+
+#[tokio::test]
+async fn test_template_placeholders_become_modal_inputs() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let state = common::mock_state(&ctx.pool, mock.clone());
+    sqlx::query::query(
+        r#"
+        INSERT INTO incident_templates (name, title, severity, affected_service, description)
+        VALUES ($1, 'Elevated errors in {{region}}', 'P2', 'Test Service',
+                'Customers in {{region}} on {{customer_tier}} plans are affected')
+        ON CONFLICT (name) DO NOTHING
+        "#,
+    )
+    .bind(TEMPLATE)
+    .execute(&ctx.pool)
+    .await
+    .unwrap();
+
+    // Picking the template rebuilds the modal with one input per placeholder
+    let view: ViewPayload = serde_json::from_value(json!({
+        "id": "V_DECLARE",
+        "callback_id": "declare_incident_modal",
+        "private_metadata": "",
+        "state": { "values": {
+            "template_block": { "template_select": { "selected_option": { "value": TEMPLATE } } },
+            "title_block": { "title_input": { "value": null } }
+        } }
+    }))
+    .unwrap();
+    apply_template(&state, &view).await.unwrap();
+    let modal = mock
+        .calls()
+        .into_iter()
+        .find_map(|call| match call {
+            SlackCall::UpdateModal { view_id, view } if view_id == "V_DECLARE" => Some(view),
+            _ => None,
+        })
+        .expect("declare modal not updated");
+    let blocks = modal["blocks"].as_array().unwrap();
+    let block = |id: &str| -> &Value {
+        blocks
+            .iter()
+            .find(|b| b["block_id"] == id)
+            .unwrap_or_else(|| panic!("missing block {}", id))
+    };
+    assert_eq!(block("template_field_region")["label"]["text"], "Region");
+    assert_eq!(
+        block("template_field_customer_tier")["label"]["text"],
+        "Customer tier"
+    );
+    assert_eq!(
+        block("title_block")["element"]["initial_value"],
+        "Elevated errors in {{region}}"
+    );
+    assert_eq!(
+        block("severity_block")["element"]["initial_option"]["value"],
+        "P2"
+    );
+    // The template covers the title, severity and service if they're left empty
+    assert_eq!(block("title_block")["optional"], true);
+    assert_eq!(block("severity_block")["optional"], true);
+    assert_eq!(block("service_block")["optional"], true);
+
+    // Submitting renders the title and keeps the values on the incident
+    let view: ViewPayload = serde_json::from_value(json!({
+        "callback_id": "declare_incident_modal",
+        "private_metadata": "",
+        "state": { "values": {
+            "template_block": { "template_select": { "selected_option": { "value": TEMPLATE } } },
+            "template_field_region": { "template_field_input": { "value": " eu-west-1 " } },
+            "template_field_customer_tier": { "template_field_input": { "value": "enterprise" } },
+            "title_block": { "title_input": { "value": "Elevated errors in {{region}}" } },
+            "severity_block": { "severity_select": { "selected_option": { "value": "P2" } } },
+            "service_block": { "service_select": { "selected_option": { "value": "Test Service" } } },
+            "commander_block": { "commander_select": { "selected_user": null } }
+        } }
+    }))
+    .unwrap();
+    handle_modal_submission(state.clone(), view, "U024TEMPLATER".to_string())
+        .await
+        .unwrap();
+
+    let incident = sqlx::query_as::query_as::<_, incident_bot::db::models::Incident>(
+        "SELECT * FROM incidents WHERE commander_id = 'U024TEMPLATER'",
+    )
+    .fetch_one(&ctx.pool)
+    .await
+    .unwrap();
+    assert_eq!(incident.title, "Elevated errors in eu-west-1");
+    assert_eq!(incident.severity, Severity::P2);
+    assert_eq!(incident.custom_fields["region"], "eu-west-1");
+    assert_eq!(incident.custom_fields["customer_tier"], "enterprise");
+
+    let timeline = get_timeline(&ctx.pool, incident.id).await.unwrap();
+    assert!(timeline
+        .iter()
+        .any(|e| e.event_type == TimelineEventType::Declared
+            && e.message
+                .contains("Customers in eu-west-1 on enterprise plans are affected")));
+    let channel_id = incident.slack_channel_id.clone().unwrap();
+    assert!(mock.calls().iter().any(|call| matches!(
+        call,
+        SlackCall::PostMessage { channel_id: c, blocks }
+            if *c == channel_id && blocks[1].to_string().contains("*Customer tier:*\\nenterprise")
+    )));
+
+    sqlx::query::query("DELETE FROM incident_templates WHERE name = $1")
+        .bind(TEMPLATE)
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_submission_falls_back_to_template_defaults() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let state = common::mock_state(&ctx.pool, mock.clone());
+    let template = "fallback-test-queue";
+    sqlx::query::query(
+        r#"
+        INSERT INTO incident_templates (name, title, severity, affected_service, description)
+        VALUES ($1, 'Queue backlog', 'P1', 'Test Service', NULL)
+        ON CONFLICT (name) DO NOTHING
+        "#,
+    )
+    .bind(template)
+    .execute(&ctx.pool)
+    .await
+    .unwrap();
+
+    // Only the template was picked; the modal update never arrived
+    let view: ViewPayload = serde_json::from_value(json!({
+        "callback_id": "declare_incident_modal",
+        "private_metadata": "",
+        "state": { "values": {
+            "template_block": { "template_select": { "selected_option": { "value": template } } },
+            "title_block": { "title_input": { "value": null } },
+            "severity_block": { "severity_select": { "selected_option": null } },
+            "service_block": { "service_select": { "selected_option": null } },
+            "commander_block": { "commander_select": { "selected_user": null } }
+        } }
+    }))
+    .unwrap();
+    handle_modal_submission(state.clone(), view, "U024FALLBACK".to_string())
+        .await
+        .unwrap();
+
+    let incident = sqlx::query_as::query_as::<_, incident_bot::db::models::Incident>(
+        "SELECT * FROM incidents WHERE commander_id = 'U024FALLBACK'",
+    )
+    .fetch_one(&ctx.pool)
+    .await
+    .unwrap();
+    assert_eq!(incident.title, "Queue backlog");
+    assert_eq!(incident.severity, Severity::P1);
+    assert_eq!(incident.affected_service, "Test Service");
+
+    // Without a template the inputs are still required
+    let view: ViewPayload = serde_json::from_value(json!({
+        "callback_id": "declare_incident_modal",
+        "private_metadata": "",
+        "state": { "values": {
+            "title_block": { "title_input": { "value": "  " } },
+            "severity_block": { "severity_select": { "selected_option": { "value": "P3" } } },
+            "service_block": { "service_select": { "selected_option": { "value": "Test Service" } } }
+        } }
+    }))
+    .unwrap();
+    assert!(matches!(
+        handle_modal_submission(state.clone(), view, "U024FALLBACK".to_string()).await,
+        Err(incident_bot::error::IncidentError::ValidationError { .. })
+    ));
+
+    sqlx::query::query("DELETE FROM incident_templates WHERE name = $1")
+        .bind(template)
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_template_severity_is_optional_when_declaring() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let state = common::mock_state(&ctx.pool, mock.clone());
+    let template = "severity-test-search";
+    sqlx::query::query(
+        r#"
+        INSERT INTO incident_templates (name, title, severity, affected_service, description)
+        VALUES ($1, 'Search degraded', 'P1', 'Test Service', NULL)
+        ON CONFLICT (name) DO NOTHING
+        "#,
+    )
+    .bind(template)
+    .execute(&ctx.pool)
+    .await
+    .unwrap();
+
+    // Severity is required until a template is picked
+    let view: ViewPayload = serde_json::from_value(json!({
+        "id": "V_SEVERITY",
+        "callback_id": "declare_incident_modal",
+        "private_metadata": "",
+        "state": { "values": {
+            "template_block": { "template_select": { "selected_option": null } }
+        } }
+    }))
+    .unwrap();
+    apply_template(&state, &view).await.unwrap();
+    let view: ViewPayload = serde_json::from_value(json!({
+        "id": "V_SEVERITY",
+        "callback_id": "declare_incident_modal",
+        "private_metadata": "",
+        "state": { "values": {
+            "template_block": { "template_select": { "selected_option": { "value": template } } }
+        } }
+    }))
+    .unwrap();
+    apply_template(&state, &view).await.unwrap();
+    let severity_blocks: Vec<Value> = mock
+        .calls()
+        .into_iter()
+        .filter_map(|call| match call {
+            SlackCall::UpdateModal { view_id, view } if view_id == "V_SEVERITY" => view["blocks"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .find(|b| b["block_id"] == "severity_block")
+                .cloned(),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(severity_blocks.len(), 2);
+    assert_eq!(severity_blocks[0]["optional"], false);
+    assert_eq!(severity_blocks[1]["optional"], true);
+
+    // Clearing the severity declares at the template's
+    let view: ViewPayload = serde_json::from_value(json!({
+        "callback_id": "declare_incident_modal",
+        "private_metadata": "",
+        "state": { "values": {
+            "template_block": { "template_select": { "selected_option": { "value": template } } },
+            "title_block": { "title_input": { "value": "Search degraded in eu-west-1" } },
+            "severity_block": { "severity_select": { "selected_option": null } },
+            "service_block": { "service_select": { "selected_option": { "value": "Test Service" } } },
+            "commander_block": { "commander_select": { "selected_user": null } }
+        } }
+    }))
+    .unwrap();
+    handle_modal_submission(state.clone(), view, "U024SEVERITY".to_string())
+        .await
+        .unwrap();
+
+    let incident = sqlx::query_as::query_as::<_, incident_bot::db::models::Incident>(
+        "SELECT * FROM incidents WHERE commander_id = 'U024SEVERITY'",
+    )
+    .fetch_one(&ctx.pool)
+    .await
+    .unwrap();
+    assert_eq!(incident.title, "Search degraded in eu-west-1");
+    assert_eq!(incident.severity, Severity::P1);
+
+    sqlx::query::query("DELETE FROM incident_templates WHERE name = $1")
+        .bind(template)
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+    ctx.cleanup().await;
+}
@@ -0,0 +1,226 @@
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use incident_bot::db::models::{NewTimelineEvent, Severity, TimelineEventType};
+use incident_bot::error::IncidentError;
+use incident_bot::services::incident::IncidentService;
+use incident_bot::services::timeline::TimelineService;
+use incident_bot::slack::mock::MockSlackClient;
+use std::sync::Arc;
+
+mod common;
+
+async fn create_incident(ctx: &common::TestContext) -> uuid::Uuid {
+    IncidentService::new(ctx.pool.clone())
+        .create_incident(
+            "Batch timeline test".to_string(),
+            Severity::P2,
+            "Test Service".to_string(),
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .expect("Failed to create incident")
+        .id
+}
+
+fn alert_event(message: &str) -> NewTimelineEvent {
+    NewTimelineEvent {
+        event_type: TimelineEventType::StatusUpdate,
+        message: message.to_string(),
+        posted_by: "alertmanager".to_string(),
+        timestamp: None,
+    }
+}
+
+#[tokio::test]
+async fn test_log_events_batch_preserves_submission_order() {
+    let ctx = common::TestContext::new().await;
+    let incident_id = create_incident(&ctx).await;
+    let timeline_service = TimelineService::new(ctx.pool.clone());
+
+    let events = (1..=50)
+        .map(|i| alert_event(&format!("alert update {}", i)))
+        .collect();
+    let inserted = timeline_service
+        .log_events_batch(incident_id, events)
+        .await
+        .expect("Failed to insert batch");
+    assert_eq!(inserted.len(), 50);
+
+    let timeline = timeline_service
+        .get_timeline(incident_id)
+        .await
+        .expect("Failed to get timeline");
+    // Declared event + 50 batch events, in submission order
+    assert_eq!(timeline.len(), 51);
+    assert_eq!(timeline[1].message, "alert update 1");
+    assert_eq!(timeline[50].message, "alert update 50");
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_log_events_batch_keeps_source_timestamps() {
+    let ctx = common::TestContext::new().await;
+    let incident_id = create_incident(&ctx).await;
+    let timeline_service = TimelineService::new(ctx.pool.clone());
+
+    let source_time = chrono::DateTime::parse_from_rfc3339("2024-11-15T10:30:00Z")
+        .unwrap()
+        .with_timezone(&chrono::Utc);
+    let mut event = alert_event("deploy started");
+    event.timestamp = Some(source_time);
+
+    let inserted = timeline_service
+        .log_events_batch(incident_id, vec![event])
+        .await
+        .expect("Failed to insert batch");
+    assert_eq!(inserted[0].timestamp, source_time);
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_log_events_batch_unknown_incident_is_not_found() {
+    let ctx = common::TestContext::new().await;
+    let timeline_service = TimelineService::new(ctx.pool.clone());
+
+    let result = timeline_service
+        .log_events_batch(uuid::Uuid::new_v4(), vec![alert_event("orphan")])
+        .await;
+    assert!(matches!(result, Err(IncidentError::NotFound)));
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_timeline_endpoint_accepts_array() {
+    let ctx = common::TestContext::new().await;
+    let incident_id = create_incident(&ctx).await;
+
+    let state = common::mock_state(&ctx.pool, Arc::new(MockSlackClient::new()));
+    let (status, json) = common::send(
+        &state,
+        "POST",
+        &format!("/api/v1/incidents/{}/timeline", incident_id),
+        Some(serde_json::json!([
+            {"event_type": "Note", "message": "CPU alert firing", "posted_by": "alertmanager"},
+            {"event_type": "Note", "message": "CPU alert resolved", "posted_by": "alertmanager"}
+        ])),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(json["inserted"], 2);
+    assert_eq!(json["events"][1]["message"], "CPU alert resolved");
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_timeline_endpoint_rejects_bad_token_and_empty_batch() {
+    let ctx = common::TestContext::new().await;
+    let incident_id = create_incident(&ctx).await;
+    let uri = format!("/api/v1/incidents/{}/timeline", incident_id);
+
+    let state = common::mock_state(&ctx.pool, Arc::new(MockSlackClient::new()));
+
+    let (status, _) = common::respond(
+        common::api_router(&state),
+        Request::post(&uri)
+            .header("Authorization", "Bearer wrong-token")
+            .header("Content-Type", "application/json")
+            .body(Body::from("[]"))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let (status, _) = common::send(&state, "POST", &uri, Some(serde_json::json!([]))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    ctx.cleanup().await;
+}
+
+async fn post_timeline(
+    ctx: &common::TestContext,
+    incident_id: uuid::Uuid,
+    body: &str,
+) -> StatusCode {
+    let state = common::mock_state(&ctx.pool, Arc::new(MockSlackClient::new()));
+    let (status, _) = common::send(
+        &state,
+        "POST",
+        &format!("/api/v1/incidents/{}/timeline", incident_id),
+        Some(serde_json::from_str(body).unwrap()),
+    )
+    .await;
+    status
+}
+
+#[tokio::test]
+async fn test_timeline_endpoint_only_accepts_notes() {
+    let ctx = common::TestContext::new().await;
+    let incident_id = create_incident(&ctx).await;
+
+    // A status update would count as acknowledging the incident for MTTA
+    // and SLAs; one in the batch rejects all of it
+    for event_type in ["StatusUpdate", "Resolved", "Declared"] {
+        let body = format!(
+            r#"[
+                {{"event_type": "Note", "message": "CPU alert firing", "posted_by": "alertmanager"}},
+                {{"event_type": "{}", "message": "All good", "posted_by": "alertmanager"}}
+            ]"#,
+            event_type
+        );
+        assert_eq!(
+            post_timeline(&ctx, incident_id, &body).await,
+            StatusCode::BAD_REQUEST
+        );
+    }
+    let timeline = TimelineService::new(ctx.pool.clone())
+        .get_timeline(incident_id)
+        .await
+        .unwrap();
+    assert_eq!(timeline.len(), 1);
+    assert_eq!(timeline[0].event_type, TimelineEventType::Declared);
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_timeline_endpoint_rejects_closed_incidents() {
+    let ctx = common::TestContext::new().await;
+    let incident_service = IncidentService::new(ctx.pool.clone());
+    let resolved = create_incident(&ctx).await;
+    incident_service
+        .resolve_incident(resolved, "U024COMMANDER".to_string())
+        .await
+        .unwrap();
+    let canceled = create_incident(&ctx).await;
+    sqlx::query::query("UPDATE incidents SET status = 'canceled' WHERE id = $1")
+        .bind(canceled)
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+
+    let body = r#"[{"event_type": "Note", "message": "Late alert", "posted_by": "alertmanager"}]"#;
+    for incident_id in [resolved, canceled] {
+        let before = TimelineService::new(ctx.pool.clone())
+            .get_timeline(incident_id)
+            .await
+            .unwrap()
+            .len();
+        assert_eq!(
+            post_timeline(&ctx, incident_id, body).await,
+            StatusCode::BAD_REQUEST
+        );
+        let after = TimelineService::new(ctx.pool.clone())
+            .get_timeline(incident_id)
+            .await
+            .unwrap()
+            .len();
+        assert_eq!(after, before);
+    }
+
+    ctx.cleanup().await;
+}
@@ -0,0 +1,127 @@
+use incident_bot::commands::timeline::record_reacted_message;
+use incident_bot::db::models::{Severity, TimelineEventType};
+use incident_bot::services::incident::IncidentService;
+use incident_bot::services::timeline::TimelineService;
+use incident_bot::slack::client::HistoryMessage;
+use incident_bot::slack::mock::MockSlackClient;
+use std::sync::Arc;
+
+mod common;
+
+const CHANNEL: &str = "C_REACTION_PIN";
+
+fn message(ts: &str, user: Option<&str>, text: &str) -> HistoryMessage {
+    HistoryMessage {
+        ts: ts.to_string(),
+        user: user.map(str::to_string),
+        text: text.to_string(),
+        subtype: user.is_none().then(|| "bot_message".to_string()),
+        thread_ts: None,
+    }
+}
+
+#[tokio::test]
+async fn test_pinned_reaction_copies_message_to_timeline() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    mock.add_history(
+        CHANNEL,
+        vec![
+            message(
+                "1700000100.000200",
+                Some("U024BOB"),
+                "Found it: the 4.2.1 config drops the DB pool to 1",
+            ),
+            message("1700000050.000100", None, "Deploy bot: 4.2.1 rolled out"),
+        ],
+    );
+    let state = common::mock_state(&ctx.pool, mock);
+
+    let incident_service = IncidentService::new(ctx.pool.clone());
+    let incident = incident_service
+        .create_incident(
+            "Checkout errors".to_string(),
+            Severity::P2,
+            "Test Service".to_string(),
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .unwrap();
+    incident_service
+        .update_channel_id(incident.id, CHANNEL.to_string())
+        .await
+        .unwrap();
+
+    let added = record_reacted_message(&state, CHANNEL, "1700000100.000200", "U024ALICE")
+        .await
+        .unwrap();
+    assert!(added);
+
+    let timeline = TimelineService::new(ctx.pool.clone())
+        .get_timeline(incident.id)
+        .await
+        .unwrap();
+    let note = timeline
+        .iter()
+        .find(|e| e.event_type == TimelineEventType::Note)
+        .unwrap();
+    assert_eq!(
+        note.message,
+        "Found it: the 4.2.1 config drops the DB pool to 1"
+    );
+    assert_eq!(note.posted_by, "U024BOB");
+    assert_eq!(note.timestamp.timestamp_micros(), 1_700_000_100_000_200);
+
+    // A second reaction to the same message adds nothing
+    assert!(
+        !record_reacted_message(&state, CHANNEL, "1700000100.000200", "U024CAROL")
+            .await
+            .unwrap()
+    );
+    // Bot posts and messages outside the history (thread replies) are skipped
+    assert!(
+        !record_reacted_message(&state, CHANNEL, "1700000050.000100", "U024ALICE")
+            .await
+            .unwrap()
+    );
+    assert!(
+        !record_reacted_message(&state, CHANNEL, "1700000200.000300", "U024ALICE")
+            .await
+            .unwrap()
+    );
+    let timeline = TimelineService::new(ctx.pool.clone())
+        .get_timeline(incident.id)
+        .await
+        .unwrap();
+    assert_eq!(
+        timeline
+            .iter()
+            .filter(|e| e.event_type == TimelineEventType::Note)
+            .count(),
+        1
+    );
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_reactions_outside_incident_channels_are_ignored() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    mock.add_history(
+        "C_NOT_AN_INCIDENT",
+        vec![message("1700000100.000200", Some("U024BOB"), "Lunch?")],
+    );
+    let state = common::mock_state(&ctx.pool, mock);
+
+    assert!(!record_reacted_message(
+        &state,
+        "C_NOT_AN_INCIDENT",
+        "1700000100.000200",
+        "U024ALICE"
+    )
+    .await
+    .unwrap());
+
+    ctx.cleanup().await;
+}
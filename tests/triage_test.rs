@@ -0,0 +1,196 @@
+use chrono::{Duration, Utc};
+use incident_bot::commands::declare::handle_modal_submission;
+use incident_bot::commands::triage::{handle_declare_button, handle_dismiss_button, handle_triage};
+use incident_bot::db::models::{IncidentStatus, TimelineEventType};
+use incident_bot::db::queries::{incidents, metrics, timeline};
+use incident_bot::slack::blocks::{TRIAGE_DECLARE_ACTION, TRIAGE_DISMISS_ACTION};
+use incident_bot::slack::events::ViewPayload;
+use incident_bot::slack::mock::{MockSlackClient, SlackCall};
+use incident_bot::AppState;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+mod common;
+
+fn submitted(modal: &Value) -> ViewPayload {
+    serde_json::from_value(json!({
+        "callback_id": modal["callback_id"],
+        "private_metadata": modal["private_metadata"],
+        "state": { "values": {
+            "title_block": { "title_input": { "value": "Checkout errors confirmed" } },
+            "severity_block": { "severity_select": { "selected_option": { "value": "P2" } } },
+            "service_block": { "service_select": { "selected_option": { "value": "Test Service" } } },
+            "commander_block": { "commander_select": { "selected_user": null } }
+        } }
+    }))
+    .unwrap()
+}
+
+/// Value of the `action_id` button in the last triage message posted to
+/// the channel.
+fn button_value(mock: &MockSlackClient, action_id: &str) -> String {
+    mock.calls()
+        .into_iter()
+        .rev()
+        .find_map(|call| match call {
+            SlackCall::PostMessage { channel_id, blocks } if channel_id == "C_SUPPORT" => {
+                Some(blocks)
+            }
+            _ => None,
+        })
+        .unwrap()
+        .iter()
+        .flat_map(|b| b["elements"].as_array().cloned().unwrap_or_default())
+        .find(|e| e["action_id"] == action_id)
+        .unwrap()["value"]
+        .as_str()
+        .unwrap()
+        .to_string()
+}
+
+async fn event_types(ctx: &common::TestContext, incident_id: uuid::Uuid) -> Vec<TimelineEventType> {
+    timeline::get_timeline(&ctx.pool, incident_id)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|e| e.event_type)
+        .collect()
+}
+
+#[tokio::test]
+async fn test_triage_is_declared_or_dismissed_outside_metrics() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let (job_sender, _job_receiver) = tokio::sync::mpsc::unbounded_channel();
+    let state = AppState::with_slack_client(
+        ctx.pool.clone(),
+        common::test_config(),
+        job_sender,
+        mock.clone(),
+    );
+
+    // Starting a triage posts it with its buttons; no channel, no broadcast
+    handle_triage(
+        state.clone(),
+        common::slash_command("triage Checkout errors?", "U_REPORTER", "C_SUPPORT"),
+    )
+    .await
+    .unwrap();
+    let id: uuid::Uuid = button_value(&mock, TRIAGE_DECLARE_ACTION).parse().unwrap();
+    let triage = incidents::get_incident_by_id(&ctx.pool, id).await.unwrap();
+    assert_eq!(triage.status, IncidentStatus::Triaging);
+    assert_eq!(triage.number_prefix, "TRI");
+    assert!(triage.is_triage && triage.triaged_at.is_some());
+    assert!(!mock
+        .calls()
+        .iter()
+        .any(|c| matches!(c, SlackCall::CreateConversation { .. })));
+    assert!(incidents::list_open_incidents(&ctx.pool)
+        .await
+        .unwrap()
+        .iter()
+        .all(|i| i.id != id));
+
+    // Declaring it opens the declare modal prefilled with the title
+    handle_declare_button(
+        state.clone(),
+        "U_RESPONDER".to_string(),
+        &id.to_string(),
+        Some("trigger-declare".to_string()),
+        None,
+    )
+    .await
+    .unwrap();
+    let modal = mock
+        .calls()
+        .into_iter()
+        .find_map(|c| match c {
+            SlackCall::OpenModal { view, .. } => Some(view),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(modal["private_metadata"], format!("triage:{}", id));
+    assert!(modal.to_string().contains("Checkout errors?"));
+
+    handle_modal_submission(state.clone(), submitted(&modal), "U_RESPONDER".to_string())
+        .await
+        .unwrap();
+    let declared = incidents::get_incident_by_id(&ctx.pool, id).await.unwrap();
+    assert_eq!(declared.status, IncidentStatus::Declared);
+    assert_eq!(declared.number_prefix, "INC");
+    assert!(!declared.is_triage);
+    assert_eq!(declared.title, "Checkout errors confirmed");
+    assert_eq!(declared.affected_service, "Test Service");
+    assert!(declared.declared_at > triage.declared_at);
+    assert!(declared.slack_channel_id.is_some());
+    assert_eq!(
+        event_types(&ctx, id).await,
+        vec![TimelineEventType::Triaged, TimelineEventType::Declared]
+    );
+
+    // Its buttons no longer act on it
+    handle_dismiss_button(
+        state.clone(),
+        "U_REPORTER".to_string(),
+        &id.to_string(),
+        Some("https://hooks.slack.test/response".to_string()),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        incidents::get_incident_by_id(&ctx.pool, id)
+            .await
+            .unwrap()
+            .status,
+        IncidentStatus::Declared
+    );
+
+    // A false alarm is dismissed by whoever started it
+    handle_triage(
+        state.clone(),
+        common::slash_command("triage Slow search", "U_REPORTER", "C_SUPPORT"),
+    )
+    .await
+    .unwrap();
+    let false_alarm: uuid::Uuid = button_value(&mock, TRIAGE_DISMISS_ACTION).parse().unwrap();
+    handle_dismiss_button(
+        state.clone(),
+        "U_RESPONDER".to_string(),
+        &false_alarm.to_string(),
+        Some("https://hooks.slack.test/response".to_string()),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        incidents::get_incident_by_id(&ctx.pool, false_alarm)
+            .await
+            .unwrap()
+            .status,
+        IncidentStatus::Triaging
+    );
+    handle_dismiss_button(
+        state.clone(),
+        "U_REPORTER".to_string(),
+        &false_alarm.to_string(),
+        Some("https://hooks.slack.test/response".to_string()),
+    )
+    .await
+    .unwrap();
+    let dismissed = incidents::get_incident_by_id(&ctx.pool, false_alarm)
+        .await
+        .unwrap();
+    assert_eq!(dismissed.status, IncidentStatus::Canceled);
+    assert_eq!(
+        event_types(&ctx, false_alarm).await,
+        vec![TimelineEventType::Triaged, TimelineEventType::Canceled]
+    );
+
+    // Only the declared one counts
+    let rows = metrics::incident_metrics(&ctx.pool, Utc::now() - Duration::days(1), None)
+        .await
+        .unwrap();
+    let overall = rows.iter().find(|row| row.key == "all").unwrap();
+    assert_eq!(overall.declared, 1);
+
+    ctx.cleanup().await;
+}
@@ -0,0 +1,172 @@
+use axum::body::Bytes;
+use axum::http::{HeaderMap, StatusCode};
+use axum::routing::post;
+use axum::Router;
+use incident_bot::commands::resolved::resolve_and_announce;
+use incident_bot::db::models::{Severity, WebhookEvent};
+use incident_bot::jobs::Job;
+use incident_bot::services::incident::IncidentService;
+use incident_bot::services::webhook::{self, WebhookService};
+use incident_bot::slack::client::RetryPolicy;
+use incident_bot::slack::mock::MockSlackClient;
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+mod common;
+
+#[tokio::test]
+async fn test_resolution_delivers_signed_webhook_with_retry() {
+    let ctx = common::TestContext::new().await;
+
+    // Receiver that fails the first delivery, then accepts
+    let received = Arc::new(Mutex::new(Vec::<(HeaderMap, String)>::new()));
+    let app = Router::new().route(
+        "/hook",
+        post({
+            let received = received.clone();
+            move |headers: HeaderMap, body: Bytes| async move {
+                let mut received = received.lock().unwrap();
+                received.push((headers, String::from_utf8(body.to_vec()).unwrap()));
+                if received.len() == 1 {
+                    StatusCode::BAD_GATEWAY
+                } else {
+                    StatusCode::OK
+                }
+            }
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+
+    let webhooks = WebhookService::new(ctx.pool.clone());
+    webhooks
+        .register(
+            &format!("http://{}/hook", addr),
+            Some("s3cret".to_string()),
+            &[WebhookEvent::Resolved],
+        )
+        .await
+        .unwrap();
+
+    let (job_sender, mut job_receiver) = mpsc::unbounded_channel();
+    let state = incident_bot::AppState::with_slack_client(
+        ctx.pool.clone(),
+        common::test_config(),
+        job_sender,
+        Arc::new(MockSlackClient::new()),
+    );
+    let incident = IncidentService::new(ctx.pool.clone())
+        .create_incident(
+            "Webhook delivery".to_string(),
+            Severity::P3,
+            "Test Service".to_string(),
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .unwrap();
+
+    // Not subscribed to declarations
+    webhook::enqueue(&state, WebhookEvent::IncidentDeclared, &incident, None).await;
+    resolve_and_announce(&state, &incident, "U024COMMANDER")
+        .await
+        .unwrap();
+
+    let mut deliveries = Vec::new();
+    while let Ok(job) = job_receiver.try_recv() {
+        if let Job::DeliverWebhook {
+            webhook_id,
+            event,
+            payload,
+        } = job
+        {
+            deliveries.push((webhook_id, event, payload));
+        }
+    }
+    assert_eq!(deliveries.len(), 1);
+    let (webhook_id, event, payload) = deliveries.remove(0);
+    assert_eq!(event, WebhookEvent::Resolved);
+
+    let policy = RetryPolicy {
+        max_retries: 2,
+        base_delay: Duration::ZERO,
+        ..RetryPolicy::default()
+    };
+    webhooks
+        .deliver(webhook_id, event, &payload, policy)
+        .await
+        .expect("delivery should succeed on retry");
+
+    let (headers, body) = {
+        let received = received.lock().unwrap();
+        assert_eq!(received.len(), 2);
+        received[1].clone()
+    };
+    let header = |name: &str| headers[name].to_str().unwrap().to_string();
+    assert_eq!(header("x-incident-bot-event"), "resolved");
+    assert_eq!(
+        header("x-incident-bot-signature"),
+        webhook::sign("s3cret", &header("x-incident-bot-timestamp"), &body)
+    );
+
+    let body: Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(body["event"], "resolved");
+    assert_eq!(body["incident"]["id"], json!(incident.id));
+    assert_eq!(body["incident"]["status"], "Resolved");
+    assert_eq!(body["previous"]["status"], "Declared");
+    assert_eq!(
+        header("x-incident-bot-delivery"),
+        body["id"].as_str().unwrap()
+    );
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_webhook_api_hides_secret_after_creation() {
+    let ctx = common::TestContext::new().await;
+    let state = common::mock_state(&ctx.pool, Arc::new(MockSlackClient::new()));
+
+    let (status, created) = common::send(
+        &state,
+        "POST",
+        "/api/v1/webhooks",
+        Some(json!({
+            "url": "https://warehouse.example.com/incidents",
+            "events": ["incident.declared", "severity.changed"]
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(created["secret"].as_str().unwrap().len(), 64);
+    assert_eq!(
+        created["events"],
+        json!(["incident.declared", "severity.changed"])
+    );
+
+    let (status, listed) = common::send(&state, "GET", "/api/v1/webhooks", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(listed.as_array().unwrap().len(), 1);
+    assert!(listed[0].get("secret").is_none());
+
+    let (status, _) = common::send(
+        &state,
+        "POST",
+        "/api/v1/webhooks",
+        Some(json!({ "url": "ftp://warehouse.example.com" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let uri = format!("/api/v1/webhooks/{}", created["id"].as_str().unwrap());
+    let (status, _) = common::send(&state, "DELETE", &uri, None).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (status, _) = common::send(&state, "DELETE", &uri, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    ctx.cleanup().await;
+}
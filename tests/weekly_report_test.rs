@@ -0,0 +1,150 @@
+use chrono::{Duration, Utc};
+use incident_bot::db::models::Severity;
+use incident_bot::db::queries::participants;
+use incident_bot::jobs::burndown::week_start;
+use incident_bot::jobs::weekly_report::{enqueue_due, execute};
+use incident_bot::jobs::Job;
+use incident_bot::services::incident::IncidentService;
+use incident_bot::slack::mock::{MockSlackClient, SlackCall};
+use incident_bot::AppConfig;
+use incident_bot::AppState;
+use std::sync::Arc;
+
+mod common;
+
+/// Declare an incident, backdated to `days_ago` days before `week_start`
+/// and resolved after `minutes` when given.
+async fn declare_last_week(
+    ctx: &common::TestContext,
+    severity: Severity,
+    service: &str,
+    monday: chrono::DateTime<Utc>,
+    days_ago: i64,
+    minutes: Option<i64>,
+) -> uuid::Uuid {
+    let incident = IncidentService::new(ctx.pool.clone())
+        .create_incident(
+            "Weekly report test".to_string(),
+            severity,
+            service.to_string(),
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .expect("Failed to create incident");
+    let declared_at = monday - Duration::days(days_ago);
+    let resolved_at = minutes.map(|minutes| declared_at + Duration::minutes(minutes));
+    sqlx::query::query(
+        r#"
+        UPDATE incidents
+        SET declared_at = $2, resolved_at = $3, duration_minutes = $4,
+            status = CASE WHEN $3::TIMESTAMPTZ IS NULL THEN status ELSE 'resolved' END
+        WHERE id = $1
+        "#,
+    )
+    .bind(incident.id)
+    .bind(declared_at)
+    .bind(resolved_at)
+    .bind(minutes.map(|m| m as i32))
+    .execute(&ctx.pool)
+    .await
+    .expect("Failed to backdate incident");
+    incident.id
+}
+
+#[tokio::test]
+async fn test_weekly_report_queued_on_mondays_and_posted_to_channel() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let monday_start = week_start(Utc::now())
+        .and_hms_opt(0, 0, 0)
+        .unwrap()
+        .and_utc();
+    let monday = monday_start + Duration::hours(9);
+
+    // Disabled without a channel
+    let (job_sender, mut job_receiver) = tokio::sync::mpsc::unbounded_channel();
+    let state = AppState::with_slack_client(
+        ctx.pool.clone(),
+        common::test_config(),
+        job_sender.clone(),
+        mock.clone(),
+    );
+    assert!(!enqueue_due(&state, monday).await.unwrap());
+
+    let config = AppConfig {
+        weekly_report_channel: Some("C_LEADERSHIP".to_string()),
+        ..common::test_config()
+    };
+    let state = AppState::with_slack_client(ctx.pool.clone(), config, job_sender, mock.clone());
+
+    // Only on Mondays, once
+    assert!(!enqueue_due(&state, monday + Duration::days(1))
+        .await
+        .unwrap());
+    assert!(enqueue_due(&state, monday).await.unwrap());
+    assert!(!enqueue_due(&state, monday + Duration::hours(1))
+        .await
+        .unwrap());
+    let week = match job_receiver.try_recv() {
+        Ok(Job::WeeklyReport { week_start }) => week_start,
+        other => panic!("Expected a weekly report job, got {:?}", other),
+    };
+    assert_eq!(week, monday.date_naive());
+    assert!(job_receiver.try_recv().is_err());
+
+    // Last week: a P1 resolved in 2h, a P2 still open for its last day, and
+    // one this week that isn't counted. Integrations aren't contributors
+    let p1 = declare_last_week(
+        &ctx,
+        Severity::P1,
+        "Test Service",
+        monday_start,
+        3,
+        Some(120),
+    )
+    .await;
+    let p2 = declare_last_week(&ctx, Severity::P2, "Other Service", monday_start, 1, None).await;
+    let now = Utc::now();
+    participants::record_timeline_events(&ctx.pool, p1, "U0ALICE", 5, now, now)
+        .await
+        .unwrap();
+    participants::record_timeline_events(&ctx.pool, p1, "U0BOB", 1, now, now)
+        .await
+        .unwrap();
+    participants::record_timeline_events(&ctx.pool, p2, "U0BOB", 1, now, now)
+        .await
+        .unwrap();
+    participants::record_timeline_events(&ctx.pool, p2, "Datadog", 9, now, now)
+        .await
+        .unwrap();
+    declare_last_week(&ctx, Severity::P1, "Test Service", monday_start, -1, None).await;
+
+    execute(&state, week).await.unwrap();
+    assert_eq!(mock.posted_channels(), vec!["C_LEADERSHIP"]);
+    let report = mock
+        .calls()
+        .into_iter()
+        .find_map(|call| match call {
+            SlackCall::PostMessage { blocks, .. } => Some(blocks),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(report[0]["text"]["text"], "📊 Weekly incident report");
+    assert_eq!(report[2]["fields"][0]["text"], "*Declared:*\n2");
+    assert_eq!(report[2]["fields"][1]["text"], "*Resolved:*\n1");
+    // 2h for the P1 plus the P2's day open within the week
+    assert_eq!(
+        report[2]["fields"][2]["text"],
+        "*Total downtime:*\n26h 0min"
+    );
+    assert_eq!(
+        report[3]["text"]["text"],
+        "*By severity*\n🔴 P1: 1 · 🟡 P2: 1"
+    );
+    assert_eq!(
+        report[5]["text"]["text"],
+        "*Top contributors*\n• <@U0ALICE> — 1 incident, 5 timeline updates\n• <@U024COMMANDER> — 2 incidents, 2 timeline updates\n• <@U0BOB> — 2 incidents, 2 timeline updates"
+    );
+
+    ctx.cleanup().await;
+}
@@ -0,0 +1,182 @@
+use incident_bot::commands::workstream::handle_workstream;
+use incident_bot::db::models::Severity;
+use incident_bot::services::incident::IncidentService;
+use incident_bot::services::timeline::TimelineService;
+use incident_bot::services::workstream::WorkstreamService;
+use incident_bot::slack::mock::{MockSlackClient, SlackCall};
+use std::sync::Arc;
+
+mod common;
+
+const CHANNEL: &str = "C_WORKSTREAM";
+const PINNED_TS: &str = "1699999999.000001";
+
+async fn create_incident_with_pinned_summary(ctx: &common::TestContext) -> uuid::Uuid {
+    let incident_service = IncidentService::new(ctx.pool.clone());
+    let incident = incident_service
+        .create_incident(
+            "Workstream test".to_string(),
+            Severity::P1,
+            "Test Service".to_string(),
+            "U024COMMANDER".to_string(),
+        )
+        .await
+        .expect("Failed to create incident");
+    incident_service
+        .update_channel_id(incident.id, CHANNEL.to_string())
+        .await
+        .expect("Failed to set channel id");
+    incident_bot::db::queries::incidents::update_pinned_message_ts(
+        &ctx.pool,
+        incident.id,
+        PINNED_TS,
+    )
+    .await
+    .expect("Failed to set pinned ts");
+    incident.id
+}
+
+#[tokio::test]
+async fn test_create_opens_thread_and_refreshes_pinned_summary() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let incident_id = create_incident_with_pinned_summary(&ctx).await;
+
+    handle_workstream(
+        common::mock_state(&ctx.pool, mock.clone()),
+        common::slash_command(
+            "workstream create Database <@U024DBA|dana>",
+            "U024COMMANDER",
+            CHANNEL,
+        ),
+    )
+    .await
+    .expect("Workstream command failed");
+
+    assert_eq!(mock.posted_channels(), vec![CHANNEL]);
+    let summary = mock
+        .calls()
+        .into_iter()
+        .find_map(|call| match call {
+            SlackCall::UpdateMessage {
+                channel_id,
+                timestamp,
+                blocks,
+            } if channel_id == CHANNEL && timestamp == PINNED_TS => Some(blocks),
+            _ => None,
+        })
+        .expect("Pinned summary was not refreshed");
+    assert!(serde_json::to_string(&summary)
+        .unwrap()
+        .contains("*database* (lead <@U024DBA>)"));
+    assert!(mock
+        .ephemeral_text()
+        .contains("Workstream *database* created"));
+
+    let incident = IncidentService::new(ctx.pool.clone())
+        .get_by_id(incident_id)
+        .await
+        .unwrap();
+    let workstream = WorkstreamService::new(ctx.pool.clone())
+        .get(&incident, "database")
+        .await
+        .expect("Workstream not stored");
+    assert_eq!(workstream.lead_id, "U024DBA");
+    assert_eq!(workstream.thread_ts.as_deref(), Some("1700000000.000001"));
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_update_posts_to_thread_for_lead_only() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let incident_id = create_incident_with_pinned_summary(&ctx).await;
+    let state = common::mock_state(&ctx.pool, mock.clone());
+
+    handle_workstream(
+        state.clone(),
+        common::slash_command(
+            "workstream create comms <@U024COMMS>",
+            "U024COMMANDER",
+            CHANNEL,
+        ),
+    )
+    .await
+    .unwrap();
+
+    handle_workstream(
+        state.clone(),
+        common::slash_command(
+            "workstream update comms Customer email sent",
+            "U024OTHER",
+            CHANNEL,
+        ),
+    )
+    .await
+    .unwrap();
+    assert!(mock
+        .ephemeral_text()
+        .contains("Only the workstream lead or incident commander"));
+
+    handle_workstream(
+        state,
+        common::slash_command(
+            "workstream update comms Customer email sent",
+            "U024COMMS",
+            CHANNEL,
+        ),
+    )
+    .await
+    .unwrap();
+
+    let replies: Vec<String> = mock
+        .calls()
+        .into_iter()
+        .filter_map(|call| match call {
+            SlackCall::PostThreadReply { thread_ts, .. } => Some(thread_ts),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(replies, vec!["1700000000.000001"]);
+
+    let timeline = TimelineService::new(ctx.pool.clone())
+        .get_timeline(incident_id)
+        .await
+        .unwrap();
+    assert_eq!(
+        timeline.last().unwrap().message,
+        "[comms] Customer email sent"
+    );
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_only_commander_can_create_workstreams() {
+    let ctx = common::TestContext::new().await;
+    let mock = Arc::new(MockSlackClient::new());
+    let incident_id = create_incident_with_pinned_summary(&ctx).await;
+
+    handle_workstream(
+        common::mock_state(&ctx.pool, mock.clone()),
+        common::slash_command("workstream create database", "U024OTHER", CHANNEL),
+    )
+    .await
+    .unwrap();
+
+    assert!(mock.ephemeral_text().contains("Permission denied"));
+    assert!(mock.posted_channels().is_empty());
+
+    let incident = IncidentService::new(ctx.pool.clone())
+        .get_by_id(incident_id)
+        .await
+        .unwrap();
+    let workstreams = WorkstreamService::new(ctx.pool.clone())
+        .list(&incident)
+        .await
+        .unwrap();
+    assert!(workstreams.is_empty());
+
+    ctx.cleanup().await;
+}